@@ -62,6 +62,7 @@ DEFAULT_SYSTEM_PARAMETERS = {
     "disk_cluster_replicas_default": "true",
     "enable_alter_swap": "true",
     "enable_assert_not_null": "true",
+    "enable_builtin_expression_cache": "true",
     "enable_columnation_lgalloc": "true",
     "enable_comment": "true",
     "enable_compute_chunked_stack": "true",
@@ -70,9 +71,11 @@ DEFAULT_SYSTEM_PARAMETERS = {
     "enable_equivalence_propagation": "true",
     "enable_explain_broken": "true",
     "enable_expressions_in_limit_syntax": "true",
+    "enable_left_join_key_sharing": "true",
     "enable_logical_compaction_window": "true",
     "enable_multi_worker_storage_persist_sink": "true",
     "enable_mysql_source": "true",
+    "enable_plan_change_detection": "true",
     "enable_rbac_checks": "true",
     "enable_reduce_mfp_fusion": "true",
     "enable_refresh_every_mvs": "true",
@@ -10,20 +10,22 @@
 //! Coordinator bookkeeping for active compute sinks.
 
 use std::cmp::Ordering;
-use std::collections::BTreeSet;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet};
 use std::iter;
 
 use anyhow::anyhow;
+use differential_dataflow::consolidation::consolidate;
 use itertools::Itertools;
 use mz_adapter_types::connection::ConnectionId;
 use mz_compute_client::protocol::response::SubscribeBatch;
-use mz_controller_types::ClusterId;
+use mz_controller_types::{ClusterId, ReplicaId};
 use mz_expr::compare_columns;
 use mz_ore::cast::CastFrom;
 use mz_ore::now::EpochMillis;
 use mz_repr::adt::numeric;
-use mz_repr::{Datum, GlobalId, Row, Timestamp};
-use mz_sql::plan::SubscribeOutput;
+use mz_repr::{Datum, Diff, GlobalId, Row, Timestamp};
+use mz_sql::plan::{SubscribeFrom, SubscribeOutput};
 use mz_sql::session::metadata::SessionMetadata;
 use timely::progress::Antichain;
 use tokio::sync::mpsc;
@@ -88,6 +90,9 @@ pub enum ActiveComputeSinkRetireReason {
     /// The compute sink was forcibly terminated because an object it depended on
     /// was dropped.
     DependencyDropped(String),
+    /// The compute sink was canceled because it exceeded the session's
+    /// `statement_timeout`.
+    StatementTimeout,
 }
 
 /// A description of an active subscribe from coord's perspective
@@ -369,6 +374,7 @@ impl ActiveSubscribe {
         let message = match reason {
             ActiveComputeSinkRetireReason::Finished => return,
             ActiveComputeSinkRetireReason::Canceled => PeekResponseUnary::Canceled,
+            ActiveComputeSinkRetireReason::StatementTimeout => PeekResponseUnary::TimedOut,
             ActiveComputeSinkRetireReason::DependencyDropped(d) => PeekResponseUnary::Error(
                 format!("subscribe has been terminated because underlying {d} was dropped"),
             ),
@@ -385,6 +391,196 @@ impl ActiveSubscribe {
     }
 }
 
+/// A subscribe dataflow that serves all active subscribes of the same relation
+/// on the same cluster, if `enable_shared_subscribes` is set.
+///
+/// Subscribes attach to a running dataflow at a timestamp no earlier than the
+/// dataflow's upper, so that the dataflow never needs to produce updates from
+/// the past. If the dataflow emits a snapshot, the accumulated contents of the
+/// subscribed relation serve as the snapshot of attaching subscribes. Once the
+/// contents grow beyond `max_shared_subscribe_size`, they are discarded and
+/// later subscribes install their own dataflows instead.
+#[derive(Debug)]
+pub struct SharedSubscribe {
+    /// The ID of the cluster on which the dataflow is running.
+    pub cluster_id: ClusterId,
+    /// The replica targeted by the dataflow, if any.
+    pub replica_id: Option<ReplicaId>,
+    /// The relation to which the dataflow subscribes.
+    pub from: SubscribeFrom,
+    /// Whether the dataflow emits a snapshot.
+    pub with_snapshot: bool,
+    /// The IDs of the objects on which the dataflow depends.
+    pub depends_on: BTreeSet<GlobalId>,
+    /// The number of columns in the relation that was subscribed to.
+    pub arity: usize,
+    /// The logical timestamp at which the dataflow began execution.
+    pub as_of: Timestamp,
+    /// The upper of the updates the dataflow has emitted so far.
+    pub upper: Antichain<Timestamp>,
+    /// The accumulated contents of the relation, if the dataflow emits a
+    /// snapshot and other subscribes may still attach to it.
+    contents: BTreeMap<Row, Diff>,
+    /// The size in bytes of the rows in `contents`.
+    contents_size: usize,
+    /// The size in bytes beyond which `contents` are discarded.
+    max_contents_size: usize,
+    /// Whether other subscribes may attach to the dataflow.
+    shareable: bool,
+    /// The IDs of the subscribes served by the dataflow.
+    pub subscribers: BTreeSet<GlobalId>,
+    /// The IDs of the subscribes whose `as_of` the dataflow has not yet
+    /// passed.
+    pub pending: BTreeSet<GlobalId>,
+}
+
+impl SharedSubscribe {
+    /// Creates a shared subscribe for the dataflow of the identified
+    /// subscribe, which retains at most `max_contents_size` bytes of the
+    /// relation's contents.
+    pub fn new(
+        sink_id: GlobalId,
+        subscribe: &ActiveSubscribe,
+        replica_id: Option<ReplicaId>,
+        from: SubscribeFrom,
+        with_snapshot: bool,
+        max_contents_size: usize,
+    ) -> SharedSubscribe {
+        SharedSubscribe {
+            cluster_id: subscribe.cluster_id,
+            replica_id,
+            from,
+            with_snapshot,
+            depends_on: subscribe.depends_on.clone(),
+            arity: subscribe.arity,
+            as_of: subscribe.as_of,
+            upper: Antichain::from_elem(subscribe.as_of),
+            contents: BTreeMap::new(),
+            contents_size: 0,
+            max_contents_size,
+            shareable: true,
+            subscribers: BTreeSet::from([sink_id]),
+            pending: BTreeSet::new(),
+        }
+    }
+
+    /// Reports whether a subscribe with the specified parameters can attach
+    /// to the dataflow.
+    pub fn matches(
+        &self,
+        cluster_id: ClusterId,
+        replica_id: Option<ReplicaId>,
+        from: &SubscribeFrom,
+        with_snapshot: bool,
+    ) -> bool {
+        self.shareable
+            && !self.upper.is_empty()
+            && self.cluster_id == cluster_id
+            && self.replica_id == replica_id
+            && self.with_snapshot == with_snapshot
+            && &self.from == from
+    }
+
+    /// Returns the `as_of` of a subscribe attaching to the dataflow, which
+    /// must not be less than `min_as_of`.
+    ///
+    /// If the returned timestamp is less than `upper`, the subscribe can be
+    /// served its snapshot right away. Otherwise, the subscribe must wait for
+    /// the dataflow to pass its `as_of`.
+    pub fn attach_as_of(&self, min_as_of: Option<Timestamp>) -> Timestamp {
+        let upper = self
+            .upper
+            .as_option()
+            .and_then(|upper| upper.step_back())
+            .unwrap_or(self.as_of);
+        let as_of = std::cmp::max(self.as_of, upper);
+        min_as_of.map_or(as_of, |min_as_of| std::cmp::max(as_of, min_as_of))
+    }
+
+    /// Returns the snapshot of a subscribe attaching at `as_of`, which must be
+    /// the greatest timestamp less than `upper`.
+    pub fn snapshot(&self, as_of: Timestamp) -> SubscribeBatch {
+        SubscribeBatch {
+            lower: Antichain::from_elem(as_of),
+            upper: self.upper.clone(),
+            updates: Ok(self
+                .contents
+                .iter()
+                .map(|(row, diff)| (as_of, row.clone(), *diff))
+                .collect()),
+        }
+    }
+
+    /// Returns the batch of updates to send to a pending subscribe with the
+    /// specified `as_of`, or `None` if the subscribe must keep waiting.
+    ///
+    /// Once `batch` passes `as_of`, the subscribe receives the updates at
+    /// times not greater than `as_of` as its snapshot, followed by the
+    /// remaining updates of the batch.
+    pub fn catch_up(&self, as_of: Timestamp, batch: &SubscribeBatch) -> Option<SubscribeBatch> {
+        let updates = match &batch.updates {
+            Ok(updates) => updates,
+            Err(_) => return Some(batch.clone()),
+        };
+        if batch.upper.less_equal(&as_of) {
+            return None;
+        }
+
+        let mut snapshot = Vec::new();
+        let mut rows = Vec::new();
+        if self.with_snapshot {
+            snapshot.extend(self.contents.iter().map(|(row, diff)| (row.clone(), *diff)));
+        }
+        for (time, row, diff) in updates {
+            if *time > as_of {
+                rows.push((*time, row.clone(), *diff));
+            } else if self.with_snapshot {
+                snapshot.push((row.clone(), *diff));
+            }
+        }
+        consolidate(&mut snapshot);
+        rows.extend(snapshot.into_iter().map(|(row, diff)| (as_of, row, diff)));
+
+        Some(SubscribeBatch {
+            lower: Antichain::from_elem(as_of),
+            upper: batch.upper.clone(),
+            updates: Ok(rows),
+        })
+    }
+
+    /// Absorbs a batch of updates emitted by the dataflow.
+    ///
+    /// This method must be called after the batch has been sent to all
+    /// subscribes.
+    pub fn absorb(&mut self, batch: &SubscribeBatch) {
+        if let (true, true, Ok(updates)) = (self.with_snapshot, self.shareable, &batch.updates) {
+            for (_, row, diff) in updates {
+                match self.contents.entry(row.clone()) {
+                    Entry::Vacant(entry) => {
+                        self.contents_size += row.byte_len();
+                        entry.insert(*diff);
+                    }
+                    Entry::Occupied(mut entry) => {
+                        *entry.get_mut() += diff;
+                        if *entry.get() == 0 {
+                            self.contents_size -= row.byte_len();
+                            entry.remove();
+                        }
+                    }
+                }
+            }
+            // Subscribes that are waiting for the dataflow to catch up still
+            // need the contents for their snapshot.
+            if self.contents_size > self.max_contents_size && self.pending.is_empty() {
+                self.shareable = false;
+                self.contents = BTreeMap::new();
+                self.contents_size = 0;
+            }
+        }
+        self.upper.clone_from(&batch.upper);
+    }
+}
+
 /// A description of an active copy to sink from the coordinator's perspective.
 #[derive(Debug)]
 pub struct ActiveCopyTo {
@@ -422,6 +618,7 @@ impl ActiveCopyTo {
         let message = match reason {
             ActiveComputeSinkRetireReason::Finished => return,
             ActiveComputeSinkRetireReason::Canceled => Err(AdapterError::Canceled),
+            ActiveComputeSinkRetireReason::StatementTimeout => Err(AdapterError::StatementTimeout),
             ActiveComputeSinkRetireReason::DependencyDropped(d) => Err(AdapterError::Unstructured(
                 anyhow!("copy has been terminated because underlying {d} was dropped"),
             )),
@@ -429,3 +626,98 @@ impl ActiveCopyTo {
         let _ = self.ctx.retire(message);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mz_compute_client::protocol::response::SubscribeBatch;
+    use mz_controller_types::ClusterId;
+    use mz_repr::{Datum, Diff, GlobalId, Row, Timestamp};
+    use mz_sql::plan::SubscribeFrom;
+    use timely::progress::Antichain;
+
+    use super::SharedSubscribe;
+
+    fn shared_subscribe(as_of: u64, max_contents_size: usize) -> SharedSubscribe {
+        SharedSubscribe {
+            cluster_id: ClusterId::User(1),
+            replica_id: None,
+            from: SubscribeFrom::Id(GlobalId::User(1)),
+            with_snapshot: true,
+            depends_on: Default::default(),
+            arity: 1,
+            as_of: as_of.into(),
+            upper: Antichain::from_elem(as_of.into()),
+            contents: Default::default(),
+            contents_size: 0,
+            max_contents_size,
+            shareable: true,
+            subscribers: Default::default(),
+            pending: Default::default(),
+        }
+    }
+
+    fn updates(updates: &[(u64, i32, Diff)]) -> Vec<(Timestamp, Row, Diff)> {
+        updates
+            .iter()
+            .map(|(time, a, diff)| ((*time).into(), Row::pack([Datum::Int32(*a)]), *diff))
+            .collect()
+    }
+
+    fn batch(lower: u64, upper: u64, batch_updates: &[(u64, i32, Diff)]) -> SubscribeBatch {
+        SubscribeBatch {
+            lower: Antichain::from_elem(lower.into()),
+            upper: Antichain::from_elem(upper.into()),
+            updates: Ok(updates(batch_updates)),
+        }
+    }
+
+    #[mz_ore::test]
+    fn catch_up_pending_subscribe() {
+        let mut shared = shared_subscribe(10, usize::MAX);
+        shared.absorb(&batch(10, 11, &[(10, 1, 1)]));
+
+        // A subscribe attaching at 15 must wait until the dataflow passes 15.
+        let as_of = shared.attach_as_of(Some(15.into()));
+        assert_eq!(as_of, Timestamp::from(15));
+        assert!(shared.upper.less_equal(&as_of));
+        let early = batch(11, 14, &[(12, 2, 1)]);
+        assert_eq!(shared.catch_up(as_of, &early), None);
+        shared.absorb(&early);
+
+        // Once it does, the subscribe receives the accumulated contents and the
+        // updates up to its `as_of` as its snapshot, followed by the remaining
+        // updates of the batch.
+        let late = batch(14, 20, &[(14, 1, -1), (15, 3, 1), (17, 4, 1)]);
+        let caught_up = shared
+            .catch_up(as_of, &late)
+            .expect("dataflow passed as_of");
+        let mut actual = caught_up.updates.unwrap();
+        actual.sort();
+        assert_eq!(actual, updates(&[(15, 2, 1), (15, 3, 1), (17, 4, 1)]));
+        assert_eq!(caught_up.lower, Antichain::from_elem(as_of));
+        assert_eq!(caught_up.upper, late.upper);
+    }
+
+    #[mz_ore::test]
+    fn bounded_contents() {
+        let cluster_id = ClusterId::User(1);
+        let from = SubscribeFrom::Id(GlobalId::User(1));
+        let mut shared = shared_subscribe(10, Row::pack([Datum::Int32(1)]).byte_len());
+        shared.absorb(&batch(10, 11, &[(10, 1, 1)]));
+        assert!(shared.matches(cluster_id, None, &from, true));
+
+        // Contents are retained beyond the limit while a subscribe is pending.
+        shared.pending.insert(GlobalId::Transient(1));
+        shared.absorb(&batch(11, 12, &[(11, 2, 1)]));
+        assert!(shared.matches(cluster_id, None, &from, true));
+        assert_eq!(shared.contents.len(), 2);
+
+        // Otherwise, they are discarded and no more subscribes can attach.
+        shared.pending.clear();
+        shared.absorb(&batch(12, 13, &[(12, 3, 1)]));
+        assert!(!shared.matches(cluster_id, None, &from, true));
+        assert!(shared.contents.is_empty());
+        shared.absorb(&batch(13, 14, &[(13, 4, 1)]));
+        assert!(shared.contents.is_empty());
+    }
+}
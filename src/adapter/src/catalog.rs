@@ -64,12 +64,13 @@ use mz_repr::namespaces::MZ_TEMP_SCHEMA;
 use mz_repr::role_id::RoleId;
 use mz_repr::{Diff, GlobalId, ScalarType};
 use mz_secrets::InMemorySecretsController;
+use mz_sql::ast::transform::CreateSqlRewrite;
 use mz_sql::catalog::{
     CatalogCluster, CatalogClusterReplica, CatalogDatabase, CatalogError as SqlCatalogError,
     CatalogItem as SqlCatalogItem, CatalogItemType as SqlCatalogItemType, CatalogRecordField,
     CatalogRole, CatalogSchema, CatalogType, CatalogTypeDetails, DefaultPrivilegeAclItem,
-    DefaultPrivilegeObject, EnvironmentId, IdReference, NameReference, RoleAttributes,
-    RoleMembership, RoleVars, SessionCatalog, SystemObjectType,
+    DefaultPrivilegeObject, EnvironmentId, IdReference, NameReference, QuotaKind, QuotaSubject,
+    RoleAttributes, RoleMembership, RoleVars, SessionCatalog, SystemObjectType,
 };
 use mz_sql::names::{
     CommentObjectId, DatabaseId, FullItemName, FullSchemaName, ItemQualifiers, ObjectId,
@@ -90,7 +91,8 @@ use mz_transform::notice::OptimizerNotice;
 
 // DO NOT add any more imports from `crate` outside of `crate::catalog`.
 pub use crate::catalog::builtin_table_updates::BuiltinTableUpdate;
-pub use crate::catalog::open::BuiltinMigrationMetadata;
+pub use crate::catalog::open::{BuiltinMigrationMetadata, BuiltinMigrationReport, MigratedItem};
+use crate::catalog::plan_record::PlanRecord;
 pub use crate::catalog::state::CatalogState;
 use crate::command::CatalogDump;
 use crate::coord::{ConnMeta, TargetCluster};
@@ -100,7 +102,9 @@ use crate::{AdapterError, AdapterNotice, ExecuteResponse};
 
 mod builtin_table_updates;
 pub(crate) mod consistency;
+mod expr_cache;
 mod migrate;
+pub(crate) mod plan_record;
 
 mod inner;
 mod open;
@@ -653,6 +657,56 @@ impl Catalog {
             .err_into()
     }
 
+    /// Returns the plan records of indexes and materialized views from the durable catalog.
+    pub async fn get_plan_records(&self) -> Result<BTreeMap<GlobalId, PlanRecord>, Error> {
+        let mut storage = self.storage().await;
+        let txn = storage.transaction().await?;
+        Ok(plan_record::load(&txn))
+    }
+
+    /// Replaces the plan records in the durable catalog with `records`.
+    pub async fn set_plan_records(
+        &mut self,
+        records: &BTreeMap<GlobalId, PlanRecord>,
+    ) -> Result<(), Error> {
+        let mut storage = self.storage().await;
+        let mut txn = storage.transaction().await?;
+        let previous = txn.get_plan_records();
+        for id in previous.keys() {
+            if !records.contains_key(id) {
+                txn.set_plan_record(*id, None)?;
+            }
+        }
+        for (id, record) in records {
+            let value = plan_record::encode(record);
+            if previous.get(id) != Some(&value) {
+                txn.set_plan_record(*id, Some(value))?;
+            }
+        }
+        txn.commit().await?;
+        Ok(())
+    }
+
+    /// Returns the asynchronous SQL jobs of the HTTP API from the durable catalog, keyed by the
+    /// ID of the job.
+    pub async fn get_sql_jobs(&self) -> Result<BTreeMap<String, String>, Error> {
+        let mut storage = self.storage().await;
+        let txn = storage.transaction().await?;
+        Ok(txn.get_sql_jobs())
+    }
+
+    /// Updates the asynchronous SQL jobs in the durable catalog. Jobs mapped to `None` are
+    /// removed.
+    pub async fn set_sql_jobs(&self, jobs: BTreeMap<String, Option<String>>) -> Result<(), Error> {
+        let mut storage = self.storage().await;
+        let mut txn = storage.transaction().await?;
+        for (id, job) in jobs {
+            txn.set_sql_job(&id, job)?;
+        }
+        txn.commit().await?;
+        Ok(())
+    }
+
     /// Get the next system replica id without allocating it.
     pub async fn get_next_system_replica_id(&self) -> Result<u64, Error> {
         self.storage()
@@ -987,6 +1041,58 @@ impl Catalog {
         }
     }
 
+    /// Returns the IDs of the secrets that `item` references directly.
+    fn referenced_secrets(state: &CatalogState, item: &CatalogItem) -> BTreeSet<GlobalId> {
+        item.references()
+            .0
+            .iter()
+            .filter(|id| {
+                state.try_get_entry(id).map_or(false, |entry| {
+                    matches!(entry.item(), CatalogItem::Secret(_))
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Records in the audit log that the item `id`, named `name`, uses each secret in
+    /// `secret_ids`. The value of a secret is read into the configuration of every object that
+    /// references it, whenever the object connects to an external system.
+    fn audit_log_secret_usage(
+        state: &CatalogState,
+        oracle_write_ts: mz_repr::Timestamp,
+        session: Option<&ConnMeta>,
+        tx: &mut Transaction<'_>,
+        builtin_table_updates: &mut Vec<BuiltinTableUpdate>,
+        audit_events: &mut Vec<VersionedEvent>,
+        id: GlobalId,
+        name: &FullNameV1,
+        secret_ids: BTreeSet<GlobalId>,
+    ) -> Result<(), AdapterError> {
+        for secret_id in secret_ids {
+            let secret = state.get_entry(&secret_id);
+            let secret_name = Self::full_name_detail(
+                &state.resolve_full_name(secret.name(), session.map(|session| session.conn_id())),
+            );
+            state.add_to_audit_log(
+                oracle_write_ts,
+                session,
+                tx,
+                builtin_table_updates,
+                audit_events,
+                EventType::Use,
+                ObjectType::Secret,
+                EventDetails::UseSecretV1(mz_audit_log::UseSecretV1 {
+                    id: secret_id.to_string(),
+                    name: secret_name,
+                    used_by_id: id.to_string(),
+                    used_by_name: name.clone(),
+                }),
+            )?;
+        }
+        Ok(())
+    }
+
     pub fn find_available_cluster_name(&self, name: &str) -> String {
         let mut i = 0;
         let mut candidate = name.to_string();
@@ -1188,6 +1294,30 @@ impl Catalog {
 
                     info!("update role {name} ({id})");
                 }
+                Op::AlterQuota {
+                    subject,
+                    kind,
+                    limit,
+                } => {
+                    let quotas = state.quotas.entry(subject).or_default();
+                    let prev_limit = match limit {
+                        Some(limit) => quotas.insert(kind, limit),
+                        None => quotas.remove(&kind),
+                    };
+                    if quotas.is_empty() {
+                        state.quotas.remove(&subject);
+                    }
+                    tx.set_quota(subject, kind, limit)?;
+                    if let Some(prev_limit) = prev_limit {
+                        builtin_table_updates
+                            .push(state.pack_quota_update(subject, kind, prev_limit, -1));
+                    }
+                    if let Some(limit) = limit {
+                        builtin_table_updates
+                            .push(state.pack_quota_update(subject, kind, limit, 1));
+                    }
+                    info!("alter quota {kind} of {} {subject}", subject.subject_type());
+                }
                 Op::AlterSetCluster { id, cluster } => Self::transact_alter_set_cluster(
                     state,
                     tx,
@@ -1636,6 +1766,9 @@ impl Catalog {
                             )));
                         }
                         let schema_id = name.qualifiers.schema_spec.clone().into();
+                        tx.check_storage_quotas(schema_id, owner_id, |subject| {
+                            state.quota_storage_usage(subject)
+                        })?;
                         let serialized_item = item.to_serialized();
                         oid = tx.insert_user_item(
                             id,
@@ -1652,6 +1785,7 @@ impl Catalog {
                             &state
                                 .resolve_full_name(&name, session.map(|session| session.conn_id())),
                         );
+                        let used_by_name = name.clone();
                         let details = match &item {
                             CatalogItem::Source(s) => {
                                 EventDetails::CreateSourceSinkV3(mz_audit_log::CreateSourceSinkV3 {
@@ -1682,6 +1816,17 @@ impl Catalog {
                             catalog_type_to_audit_object_type(item.typ()),
                             details,
                         )?;
+                        Self::audit_log_secret_usage(
+                            state,
+                            oracle_write_ts,
+                            session,
+                            tx,
+                            builtin_table_updates,
+                            audit_events,
+                            id,
+                            &used_by_name,
+                            Self::referenced_secrets(state, &item),
+                        )?;
                     }
                     state.insert_item(
                         id,
@@ -1740,6 +1885,21 @@ impl Catalog {
                     });
                     builtin_table_updates.extend(updates);
 
+                    // Drop any associated quotas.
+                    let quota_subject = match &id {
+                        ObjectId::Database(id) => Some(QuotaSubject::Database(*id)),
+                        ObjectId::Role(id) => Some(QuotaSubject::Role(*id)),
+                        _ => None,
+                    };
+                    if let Some(subject) = quota_subject {
+                        tx.remove_quotas(subject);
+                        let quotas = state.quotas.remove(&subject).unwrap_or_default();
+                        let updates = quotas
+                            .into_iter()
+                            .map(|(kind, limit)| state.pack_quota_update(subject, kind, limit, -1));
+                        builtin_table_updates.extend(updates);
+                    }
+
                     // Drop the object.
                     match id {
                         ObjectId::Database(id) => {
@@ -2330,8 +2490,6 @@ impl Catalog {
                     to_name,
                     current_full_name,
                 } => {
-                    let mut updates = Vec::new();
-
                     let entry = state.get_entry(&id);
                     if let CatalogItem::Type(_) = entry.item() {
                         return Err(AdapterError::Catalog(Error::new(ErrorKind::TypeRename(
@@ -2375,65 +2533,23 @@ impl Catalog {
                         )?;
                     }
 
-                    // Rename item itself.
-                    let mut new_entry = entry.clone();
-                    new_entry.name.item = to_name.clone();
-                    new_entry.item = entry
-                        .item()
-                        .rename_item_refs(
-                            current_full_name.clone(),
-                            to_full_name.item.clone(),
-                            true,
-                        )
-                        .map_err(|e| {
-                            Error::new(ErrorKind::from(AmbiguousRename {
-                                depender: state
-                                    .resolve_full_name(entry.name(), entry.conn_id())
-                                    .to_string(),
-                                dependee: state
-                                    .resolve_full_name(entry.name(), entry.conn_id())
-                                    .to_string(),
-                                message: e,
-                            }))
-                        })?;
-
-                    for id in entry.referenced_by() {
-                        let dependent_item = state.get_entry(id);
-                        let mut to_entry = dependent_item.clone();
-                        to_entry.item = dependent_item
-                            .item()
-                            .rename_item_refs(
-                                current_full_name.clone(),
-                                to_full_name.item.clone(),
-                                false,
-                            )
-                            .map_err(|e| {
-                                Error::new(ErrorKind::from(AmbiguousRename {
-                                    depender: state
-                                        .resolve_full_name(
-                                            dependent_item.name(),
-                                            dependent_item.conn_id(),
-                                        )
-                                        .to_string(),
-                                    dependee: state
-                                        .resolve_full_name(entry.name(), entry.conn_id())
-                                        .to_string(),
-                                    message: e,
-                                }))
-                            })?;
-
-                        if !new_entry.item().is_temporary() {
-                            tx.update_item(*id, to_entry.clone().into())?;
-                        }
-                        builtin_table_updates.extend(state.pack_item_update(*id, -1));
-
-                        updates.push((id.clone(), dependent_item.name().clone(), to_entry.item));
-                    }
-                    if !new_entry.item().is_temporary() {
-                        tx.update_item(id, new_entry.clone().into())?;
-                    }
-                    builtin_table_updates.extend(state.pack_item_update(id, -1));
-                    updates.push((id, to_qualified_name, new_entry.item));
+                    // Rename the item itself and update the references of its dependents.
+                    let rename = |rename_self| CreateSqlRewrite::RenameItem {
+                        from: current_full_name.clone(),
+                        to_item: to_full_name.item.clone(),
+                        rename_self,
+                    };
+                    let mut rewrites: Vec<_> = entry
+                        .referenced_by()
+                        .iter()
+                        .map(|id| (*id, state.get_entry(id).name().clone(), rename(false)))
+                        .collect();
+                    rewrites.push((id, to_qualified_name, rename(true)));
+                    let dependee = state
+                        .resolve_full_name(entry.name(), entry.conn_id())
+                        .to_string();
+                    let updates =
+                        Self::rewrite_items(state, tx, builtin_table_updates, rewrites, &dependee)?;
                     for (id, to_name, to_item) in updates {
                         Self::update_item(state, builtin_table_updates, id, to_name, to_item)?;
                     }
@@ -2465,54 +2581,30 @@ impl Catalog {
                     let database = state.get_database(&database_id);
                     let database_name = &database.name;
 
-                    let mut updates = Vec::new();
+                    // Update all of the items in the schema, and everything that depends on
+                    // them.
+                    let mut rewrites = Vec::new();
                     let mut already_updated = HashSet::new();
-
-                    let mut update_item = |id| {
-                        if already_updated.contains(id) {
-                            return Ok(());
-                        }
-
-                        let entry = state.get_entry(id);
-
-                        // Update our item.
-                        let mut new_entry = entry.clone();
-                        new_entry.item = entry
-                            .item
-                            .rename_schema_refs(database_name, &cur_name, &new_name)
-                            .map_err(|(s, _i)| {
-                                Error::new(ErrorKind::from(AmbiguousRename {
-                                    depender: state
-                                        .resolve_full_name(entry.name(), entry.conn_id())
-                                        .to_string(),
-                                    dependee: format!("{database_name}.{cur_name}"),
-                                    message: format!("ambiguous reference to schema named {s}"),
-                                }))
-                            })?;
-
-                        // Update the catalog storage and Builtin Tables.
-                        if !new_entry.item().is_temporary() {
-                            tx.update_item(*id, new_entry.clone().into())?;
-                        }
-                        builtin_table_updates.extend(state.pack_item_update(*id, -1));
-                        updates.push((id.clone(), entry.name().clone(), new_entry.item));
-
-                        // Track which IDs we update.
-                        already_updated.insert(id);
-
-                        Ok::<_, AdapterError>(())
-                    };
-
-                    // Update all of the items in the schema.
                     for (_name, item_id) in &schema.items {
-                        // Update the item itself.
-                        update_item(item_id)?;
-
-                        // Update everything that depends on this item.
-                        for id in state.get_entry(item_id).referenced_by() {
-                            update_item(id)?;
+                        let dependents = state.get_entry(item_id).referenced_by();
+                        for id in std::iter::once(item_id).chain(dependents) {
+                            if already_updated.insert(*id) {
+                                let rewrite = CreateSqlRewrite::RenameSchema {
+                                    database: database_name.clone(),
+                                    from: cur_name.clone(),
+                                    to: new_name.clone(),
+                                };
+                                rewrites.push((*id, state.get_entry(id).name().clone(), rewrite));
+                            }
                         }
                     }
+                    let updates = Self::rewrite_items(
+                        state,
+                        tx,
+                        builtin_table_updates,
+                        rewrites,
+                        &format!("{database_name}.{cur_name}"),
+                    )?;
 
                     // Renaming temporary schemas is not supported.
                     let SchemaSpecifier::Id(schema_id) = *schema.id() else {
@@ -2608,6 +2700,9 @@ impl Catalog {
                                     ErrorKind::ReadOnlyCluster(cluster_name),
                                 )));
                             }
+                            if old_owner != new_owner {
+                                tx.check_cluster_owner_quotas(new_owner)?;
+                            }
                             builtin_table_updates
                                 .push(state.pack_cluster_update(&cluster_name, -1));
                             let cluster = state.get_cluster_mut(*id);
@@ -2707,6 +2802,12 @@ impl Catalog {
                                     ErrorKind::ReadOnlyItem(full_name.to_string()),
                                 )));
                             }
+                            if old_owner != new_owner && !state.get_entry(id).item().is_temporary()
+                            {
+                                tx.check_item_owner_quotas(new_owner, |subject| {
+                                    state.quota_storage_usage(subject)
+                                })?;
+                            }
                             builtin_table_updates.extend(state.pack_item_update(*id, -1));
                             let entry = state.get_entry_mut(id);
                             Self::update_privilege_owners(
@@ -2746,6 +2847,35 @@ impl Catalog {
                     builtin_table_updates.push(state.pack_cluster_update(&name, 1));
                     info!("update cluster {}", name);
                 }
+                Op::UpdateClusterReplicaConfig {
+                    cluster_id,
+                    replica_id,
+                    name,
+                    config,
+                } => {
+                    if cluster_id.is_system() {
+                        return Err(AdapterError::Catalog(Error::new(
+                            ErrorKind::ReadOnlyCluster(name.cluster.into_string()),
+                        )));
+                    }
+                    builtin_table_updates.extend(state.pack_cluster_replica_update(
+                        cluster_id,
+                        name.replica.as_str(),
+                        -1,
+                    ));
+                    let cluster = state.get_cluster_mut(cluster_id);
+                    let replica = cluster
+                        .replica_mut(replica_id)
+                        .expect("catalog out of sync");
+                    replica.config = config;
+                    tx.update_cluster_replica(replica_id, replica.clone().into())?;
+                    builtin_table_updates.extend(state.pack_cluster_replica_update(
+                        cluster_id,
+                        name.replica.as_str(),
+                        1,
+                    ));
+                    info!("update cluster replica {name}");
+                }
                 Op::UpdateClusterReplicaStatus { event } => {
                     builtin_table_updates.push(state.pack_cluster_replica_status_update(
                         event.cluster_id,
@@ -2770,6 +2900,8 @@ impl Catalog {
                     ));
                 }
                 Op::UpdateItem { id, name, to_item } => {
+                    let prev_secret_ids =
+                        Self::referenced_secrets(state, state.get_entry(&id).item());
                     builtin_table_updates.extend(state.pack_item_update(id, -1));
                     Self::update_item(
                         state,
@@ -2797,9 +2929,25 @@ impl Catalog {
                             catalog_type_to_audit_object_type(to_item.typ()),
                             EventDetails::UpdateItemV1(mz_audit_log::UpdateItemV1 {
                                 id: id.to_string(),
-                                name,
+                                name: name.clone(),
                             }),
                         )?;
+                        // Only secrets that the item did not use before.
+                        let secret_ids = Self::referenced_secrets(state, &to_item)
+                            .difference(&prev_secret_ids)
+                            .cloned()
+                            .collect();
+                        Self::audit_log_secret_usage(
+                            state,
+                            oracle_write_ts,
+                            session,
+                            tx,
+                            builtin_table_updates,
+                            audit_events,
+                            id,
+                            &name,
+                            secret_ids,
+                        )?;
                     }
                 }
                 Op::UpdateStorageUsage {
@@ -2815,6 +2963,45 @@ impl Catalog {
                         collection_timestamp,
                     )?;
                 }
+                Op::UpdateStorageIoUsage {
+                    object_id,
+                    bytes_read,
+                    bytes_written,
+                    collection_timestamp,
+                } => {
+                    state.add_to_storage_io_usage(
+                        tx,
+                        builtin_table_updates,
+                        object_id,
+                        bytes_read,
+                        bytes_written,
+                        collection_timestamp,
+                    )?;
+                }
+                Op::UpdateComputeUsage {
+                    cluster_id,
+                    role_id,
+                    cpu_nano_cores,
+                    memory_bytes,
+                    peek_count,
+                    peek_time_us,
+                    collection_timestamp,
+                } => {
+                    state.add_to_compute_usage(
+                        tx,
+                        builtin_table_updates,
+                        cluster_id,
+                        role_id,
+                        cpu_nano_cores,
+                        memory_bytes,
+                        peek_count,
+                        peek_time_us,
+                        collection_timestamp,
+                    )?;
+                }
+                Op::UpdatePlanRecord { id, record } => {
+                    tx.set_plan_record(id, Some(plan_record::encode(&record)))?;
+                }
                 Op::UpdateSystemConfiguration { name, value } => {
                     Self::update_system_configuration(state, tx, &name, value.borrow())?;
                 }
@@ -2884,6 +3071,49 @@ impl Catalog {
         }
     }
 
+    /// Applies the [`CreateSqlRewrite`] of each of `rewrites` to the `create_sql` of the
+    /// identified item, which is renamed to the accompanying name, and updates the durable items
+    /// in bulk.
+    ///
+    /// Returns the rewritten items, which the caller must install with [`Catalog::update_item`]
+    /// once it has updated the renamed objects in `state`. Errors if any rewrite is ambiguous,
+    /// reporting `dependee` as the renamed object.
+    fn rewrite_items(
+        state: &CatalogState,
+        tx: &mut Transaction<'_>,
+        builtin_table_updates: &mut Vec<BuiltinTableUpdate>,
+        rewrites: Vec<(GlobalId, QualifiedItemName, CreateSqlRewrite)>,
+        dependee: &str,
+    ) -> Result<Vec<(GlobalId, QualifiedItemName, CatalogItem)>, AdapterError> {
+        let mut updates = Vec::new();
+        let mut durable_updates = BTreeMap::new();
+        for (id, to_name, rewrite) in rewrites {
+            let entry = state.get_entry(&id);
+            let to_item = entry
+                .item()
+                .rewrite_create_sql(&rewrite)
+                .map_err(|message| {
+                    Error::new(ErrorKind::from(AmbiguousRename {
+                        depender: state
+                            .resolve_full_name(entry.name(), entry.conn_id())
+                            .to_string(),
+                        dependee: dependee.to_string(),
+                        message,
+                    }))
+                })?;
+            if !to_item.is_temporary() {
+                let mut to_entry = entry.clone();
+                to_entry.name = to_name.clone();
+                to_entry.item = to_item.clone();
+                durable_updates.insert(id, to_entry.into());
+            }
+            builtin_table_updates.extend(state.pack_item_update(id, -1));
+            updates.push((id, to_name, to_item));
+        }
+        tx.update_items(durable_updates)?;
+        Ok(updates)
+    }
+
     pub(crate) fn update_item(
         state: &mut CatalogState,
         builtin_table_updates: &mut Vec<BuiltinTableUpdate>,
@@ -3130,6 +3360,7 @@ impl Catalog {
             pcx,
             is_retained_metrics_object,
             custom_logical_compaction_window,
+            None,
         )
     }
 
@@ -3239,6 +3470,11 @@ impl Catalog {
         &self.state.system_privileges
     }
 
+    /// Sets the most recently collected storage usage of each collection, in bytes.
+    pub(crate) fn set_storage_usage(&mut self, storage_usage: BTreeMap<GlobalId, u64>) {
+        self.state.storage_usage = storage_usage;
+    }
+
     pub fn default_privileges(
         &self,
     ) -> impl Iterator<
@@ -3380,6 +3616,7 @@ pub(crate) fn system_object_type_to_audit_object_type(
             mz_sql::catalog::ObjectType::Database => ObjectType::Database,
             mz_sql::catalog::ObjectType::Schema => ObjectType::Schema,
             mz_sql::catalog::ObjectType::Func => ObjectType::Func,
+            mz_sql::catalog::ObjectType::ContinualTask => ObjectType::ContinualTask,
         },
         SystemObjectType::System => ObjectType::System,
     }
@@ -3421,6 +3658,12 @@ pub enum Op {
         attributes: RoleAttributes,
         vars: RoleVars,
     },
+    /// Sets the `kind` quota of `subject` to `limit`, or removes it if `limit` is `None`.
+    AlterQuota {
+        subject: QuotaSubject,
+        kind: QuotaKind,
+        limit: Option<u64>,
+    },
     CreateDatabase {
         name: String,
         owner_id: RoleId,
@@ -3512,6 +3755,12 @@ pub enum Op {
         name: String,
         config: ClusterConfig,
     },
+    UpdateClusterReplicaConfig {
+        cluster_id: ClusterId,
+        replica_id: ReplicaId,
+        name: QualifiedReplica,
+        config: ReplicaConfig,
+    },
     UpdateClusterReplicaStatus {
         event: ClusterEvent,
     },
@@ -3525,6 +3774,26 @@ pub enum Op {
         size_bytes: u64,
         collection_timestamp: EpochMillis,
     },
+    UpdateStorageIoUsage {
+        object_id: String,
+        bytes_read: u64,
+        bytes_written: u64,
+        collection_timestamp: EpochMillis,
+    },
+    UpdateComputeUsage {
+        cluster_id: String,
+        role_id: String,
+        cpu_nano_cores: u64,
+        memory_bytes: u64,
+        peek_count: u64,
+        peek_time_us: u64,
+        collection_timestamp: EpochMillis,
+    },
+    /// Records the plan of the index or materialized view `id` in the durable catalog.
+    UpdatePlanRecord {
+        id: GlobalId,
+        record: PlanRecord,
+    },
     UpdateSystemConfiguration {
         name: String,
         value: OwnedVarInput,
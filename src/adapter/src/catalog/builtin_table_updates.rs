@@ -18,19 +18,20 @@ use mz_catalog::builtin::{
     MZ_AGGREGATES, MZ_ARRAY_TYPES, MZ_AUDIT_EVENTS, MZ_AWS_CONNECTIONS,
     MZ_AWS_PRIVATELINK_CONNECTIONS, MZ_BASE_TYPES, MZ_CLUSTERS, MZ_CLUSTER_REPLICAS,
     MZ_CLUSTER_REPLICA_METRICS, MZ_CLUSTER_REPLICA_SIZES, MZ_CLUSTER_REPLICA_STATUSES, MZ_COLUMNS,
-    MZ_COMMENTS, MZ_CONNECTIONS, MZ_DATABASES, MZ_DEFAULT_PRIVILEGES, MZ_EGRESS_IPS, MZ_FUNCTIONS,
-    MZ_INDEXES, MZ_INDEX_COLUMNS, MZ_INTERNAL_CLUSTER_REPLICAS, MZ_KAFKA_CONNECTIONS,
-    MZ_KAFKA_SINKS, MZ_KAFKA_SOURCES, MZ_LIST_TYPES, MZ_MAP_TYPES, MZ_MATERIALIZED_VIEWS,
-    MZ_OBJECT_DEPENDENCIES, MZ_OPERATORS, MZ_POSTGRES_SOURCES, MZ_PSEUDO_TYPES, MZ_ROLES,
-    MZ_ROLE_MEMBERS, MZ_SCHEMAS, MZ_SECRETS, MZ_SESSIONS, MZ_SINKS, MZ_SOURCES,
-    MZ_SSH_TUNNEL_CONNECTIONS, MZ_STORAGE_USAGE_BY_SHARD, MZ_SUBSCRIPTIONS, MZ_SYSTEM_PRIVILEGES,
-    MZ_TABLES, MZ_TYPES, MZ_TYPE_PG_METADATA, MZ_VIEWS, MZ_WEBHOOKS_SOURCES,
+    MZ_COMMENTS, MZ_CONNECTIONS, MZ_CONTINUAL_TASKS, MZ_DATABASES, MZ_DEFAULT_PRIVILEGES,
+    MZ_EGRESS_IPS, MZ_FUNCTIONS, MZ_INDEXES, MZ_INDEX_COLUMNS, MZ_INTERNAL_CLUSTER_REPLICAS,
+    MZ_KAFKA_CONNECTIONS, MZ_KAFKA_SINKS, MZ_KAFKA_SOURCES, MZ_LIST_TYPES, MZ_MAP_TYPES,
+    MZ_MATERIALIZED_VIEWS, MZ_OBJECT_DEPENDENCIES, MZ_OPERATORS, MZ_POSTGRES_SOURCES,
+    MZ_PSEUDO_TYPES, MZ_QUOTAS, MZ_ROLES, MZ_ROLE_MEMBERS, MZ_SCHEMAS, MZ_SECRETS, MZ_SESSIONS,
+    MZ_SINKS, MZ_SOURCES, MZ_SOURCE_TIMESTAMP_POLICIES, MZ_SSH_TUNNEL_CONNECTIONS,
+    MZ_STORAGE_IO_USAGE, MZ_STORAGE_USAGE_BY_SHARD, MZ_SUBSCRIPTIONS, MZ_SYSTEM_PRIVILEGES,
+    MZ_TABLES, MZ_TYPES, MZ_TYPE_PG_METADATA, MZ_USAGE_BY_CLUSTER, MZ_VIEWS, MZ_WEBHOOKS_SOURCES,
 };
 use mz_catalog::config::AwsPrincipalContext;
 use mz_catalog::memory::error::{Error, ErrorKind};
 use mz_catalog::memory::objects::{
-    CatalogItem, ClusterVariant, Connection, DataSourceDesc, Database, Func, Index,
-    MaterializedView, Sink, Table, Type, View,
+    CatalogItem, ClusterVariant, Connection, ContinualTask, DataSourceDesc, Database, Func,
+    FuncDefinition, Index, MaterializedView, Sink, Table, Type, View,
 };
 use mz_catalog::SYSTEM_CONN_ID;
 use mz_controller::clusters::{
@@ -43,14 +44,15 @@ use mz_orchestrator::{CpuLimit, DiskLimit, MemoryLimit, NotReadyReason, ServiceP
 use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
 use mz_repr::adt::array::ArrayDimension;
+use mz_repr::adt::interval::Interval;
 use mz_repr::adt::jsonb::Jsonb;
 use mz_repr::adt::mz_acl_item::{AclMode, MzAclItem, PrivilegeMap};
 use mz_repr::role_id::RoleId;
 use mz_repr::{Datum, Diff, GlobalId, Row, RowPacker};
 use mz_sql::ast::{CreateIndexStatement, Statement};
 use mz_sql::catalog::{
-    CatalogCluster, CatalogDatabase, CatalogSchema, CatalogType, DefaultPrivilegeObject,
-    TypeCategory,
+    CatalogCluster, CatalogDatabase, CatalogSchema, CatalogType, DefaultPrivilegeObject, QuotaKind,
+    QuotaSubject, TypeCategory,
 };
 use mz_sql::func::FuncImplCatalogDetails;
 use mz_sql::names::{CommentObjectId, ResolvedDatabaseSpecifier, SchemaId, SchemaSpecifier};
@@ -60,7 +62,8 @@ use mz_storage_types::connections::inline::ReferencedConnection;
 use mz_storage_types::connections::{KafkaConnection, StringOrSecret};
 use mz_storage_types::sinks::{KafkaSinkConnection, StorageSinkConnection};
 use mz_storage_types::sources::{
-    GenericSourceConnection, KafkaSourceConnection, PostgresSourceConnection,
+    GenericSourceConnection, KafkaSourceConnection, PostgresSourceConnection, SourceDesc,
+    TimestampPolicy,
 };
 
 // DO NOT add any more imports from `crate` outside of `crate::catalog`.
@@ -373,15 +376,23 @@ impl CatalogState {
                     );
 
                     updates.extend(match &source.data_source {
-                        DataSourceDesc::Ingestion(ingestion) => match &ingestion.desc.connection {
-                            GenericSourceConnection::Postgres(postgres) => {
-                                self.pack_postgres_source_update(id, postgres, diff)
-                            }
-                            GenericSourceConnection::Kafka(kafka) => {
-                                self.pack_kafka_source_update(id, kafka, diff)
-                            }
-                            _ => vec![],
-                        },
+                        DataSourceDesc::Ingestion(ingestion) => {
+                            let mut updates = match &ingestion.desc.connection {
+                                GenericSourceConnection::Postgres(postgres) => {
+                                    self.pack_postgres_source_update(id, postgres, diff)
+                                }
+                                GenericSourceConnection::Kafka(kafka) => {
+                                    self.pack_kafka_source_update(id, kafka, diff)
+                                }
+                                _ => vec![],
+                            };
+                            updates.push(self.pack_source_timestamp_policy_update(
+                                id,
+                                &ingestion.desc,
+                                diff,
+                            ));
+                            updates
+                        }
                         DataSourceDesc::Webhook { .. } => {
                             vec![self.pack_webhook_source_update(id, diff)]
                         }
@@ -402,7 +413,7 @@ impl CatalogState {
                     self.pack_type_update(id, oid, schema_id, name, owner_id, privileges, ty, diff)
                 }
                 CatalogItem::Func(func) => {
-                    self.pack_func_update(id, schema_id, name, owner_id, func, diff)
+                    self.pack_func_update(id, oid, schema_id, name, owner_id, func, diff)
                 }
                 CatalogItem::Secret(_) => {
                     self.pack_secret_update(id, oid, schema_id, name, owner_id, privileges, diff)
@@ -410,6 +421,9 @@ impl CatalogState {
                 CatalogItem::Connection(connection) => self.pack_connection_update(
                     id, oid, schema_id, name, owner_id, privileges, connection, diff,
                 ),
+                CatalogItem::ContinualTask(ct) => self.pack_continual_task_update(
+                    id, oid, schema_id, name, owner_id, privileges, ct, diff,
+                ),
             };
 
         if !entry.item().is_temporary() {
@@ -586,6 +600,39 @@ impl CatalogState {
         }]
     }
 
+    fn pack_source_timestamp_policy_update(
+        &self,
+        id: GlobalId,
+        desc: &SourceDesc<ReferencedConnection>,
+        diff: Diff,
+    ) -> BuiltinTableUpdate {
+        let timestamp_interval = Interval::new(
+            0,
+            0,
+            i64::try_from(desc.timestamp_interval.as_micros())
+                .expect("timestamp interval must fit in an i64"),
+        );
+        // Reclocking always assigns non-decreasing timestamps to the records
+        // of a partition, but only sources that expose each record's
+        // partition and offset let consumers recover the exact order of
+        // records that share a timestamp.
+        let partition_order = match (&desc.connection, desc.timestamp_policy) {
+            (_, Some(TimestampPolicy::StrictPartitionOrder)) => Some("strict"),
+            (GenericSourceConnection::Kafka(_), _) => Some("timestamp"),
+            _ => None,
+        };
+        BuiltinTableUpdate {
+            id: self.resolve_builtin_table(&MZ_SOURCE_TIMESTAMP_POLICIES),
+            row: Row::pack_slice(&[
+                Datum::String(&id.to_string()),
+                Datum::from(desc.timestamp_policy.map(|p| p.as_str())),
+                Datum::Interval(timestamp_interval),
+                Datum::from(partition_order),
+            ]),
+            diff,
+        }
+    }
+
     fn pack_connection_update(
         &self,
         id: GlobalId,
@@ -620,6 +667,9 @@ impl CatalogState {
                     }
                     mz_storage_types::connections::Connection::Ssh { .. } => "ssh-tunnel",
                     mz_storage_types::connections::Connection::MySql { .. } => "mysql",
+                    mz_storage_types::connections::Connection::IcebergCatalog { .. } => {
+                        "iceberg-catalog"
+                    }
                 }),
                 Datum::String(&owner_id.to_string()),
                 privileges,
@@ -666,7 +716,8 @@ impl CatalogState {
             }
             mz_storage_types::connections::Connection::Csr(_)
             | mz_storage_types::connections::Connection::Postgres(_)
-            | mz_storage_types::connections::Connection::MySql(_) => (),
+            | mz_storage_types::connections::Connection::MySql(_)
+            | mz_storage_types::connections::Connection::IcebergCatalog(_) => (),
         };
         updates
     }
@@ -900,6 +951,53 @@ impl CatalogState {
         }]
     }
 
+    fn pack_continual_task_update(
+        &self,
+        id: GlobalId,
+        oid: u32,
+        schema_id: &SchemaSpecifier,
+        name: &str,
+        owner_id: &RoleId,
+        privileges: Datum,
+        ct: &ContinualTask,
+        diff: Diff,
+    ) -> Vec<BuiltinTableUpdate> {
+        let create_stmt = mz_sql::parse::parse(&ct.create_sql)
+            .unwrap_or_else(|e| {
+                panic!(
+                    "create_sql cannot be invalid: `{}` --- error: `{}`",
+                    ct.create_sql, e
+                )
+            })
+            .into_element()
+            .ast;
+        let query = match &create_stmt {
+            Statement::CreateContinualTask(stmt) => &stmt.query,
+            _ => unreachable!(),
+        };
+
+        let mut query_string = query.to_ast_string_stable();
+        query_string.push(';');
+
+        vec![BuiltinTableUpdate {
+            id: self.resolve_builtin_table(&MZ_CONTINUAL_TASKS),
+            row: Row::pack_slice(&[
+                Datum::String(&id.to_string()),
+                Datum::UInt32(oid),
+                Datum::String(&schema_id.to_string()),
+                Datum::String(name),
+                Datum::String(&ct.cluster_id.to_string()),
+                Datum::String(&ct.target_id.to_string()),
+                Datum::String(&query_string),
+                Datum::String(&owner_id.to_string()),
+                privileges,
+                Datum::String(&ct.create_sql),
+                Datum::String(&create_stmt.to_ast_string_redacted()),
+            ]),
+            diff,
+        }]
+    }
+
     fn pack_sink_update(
         &self,
         id: GlobalId,
@@ -924,6 +1022,7 @@ impl CatalogState {
                     diff,
                 });
             }
+            StorageSinkConnection::Iceberg(_) => {}
         };
 
         let create_stmt = mz_sql::parse::parse(&sink.create_sql)
@@ -1158,6 +1257,7 @@ impl CatalogState {
     fn pack_func_update(
         &self,
         id: GlobalId,
+        oid: u32,
         schema_id: &SchemaSpecifier,
         name: &str,
         owner_id: &RoleId,
@@ -1166,6 +1266,14 @@ impl CatalogState {
     ) -> Vec<BuiltinTableUpdate> {
         let mut updates = vec![];
         for func_impl_details in func.inner.func_impls() {
+            // Each implementation of a builtin function has its own OID, while
+            // user-defined functions have a single implementation that uses the
+            // OID of the function.
+            let oid = match func.inner {
+                FuncDefinition::Builtin(_) => func_impl_details.oid,
+                FuncDefinition::User(_) => oid,
+            };
+
             let arg_type_ids = func_impl_details
                 .arg_typs
                 .iter()
@@ -1190,7 +1298,7 @@ impl CatalogState {
                 id: self.resolve_builtin_table(&MZ_FUNCTIONS),
                 row: Row::pack_slice(&[
                     Datum::String(&id.to_string()),
-                    Datum::UInt32(func_impl_details.oid),
+                    Datum::UInt32(oid),
                     Datum::String(&schema_id.to_string()),
                     Datum::String(name),
                     arg_type_ids,
@@ -1212,11 +1320,11 @@ impl CatalogState {
                 diff,
             });
 
-            if let mz_sql::func::Func::Aggregate(_) = func.inner {
+            if let mz_sql::func::Func::Aggregate(_) = *func.inner {
                 updates.push(BuiltinTableUpdate {
                     id: self.resolve_builtin_table(&MZ_AGGREGATES),
                     row: Row::pack_slice(&[
-                        Datum::UInt32(func_impl_details.oid),
+                        Datum::UInt32(oid),
                         // TODO(materialize#3326): Support ordered-set aggregate functions.
                         Datum::String("n"),
                         Datum::Int16(0),
@@ -1311,6 +1419,13 @@ impl CatalogState {
                 &ev.user,
                 ev.occurred_at,
             ),
+            VersionedEvent::V2(ev) => (
+                &ev.event_type,
+                &ev.object_type,
+                &ev.details,
+                &ev.user,
+                ev.occurred_at,
+            ),
         };
         let details = Jsonb::from_serde_json(details.as_json())
             .map_err(|e| {
@@ -1324,6 +1439,20 @@ impl CatalogState {
             .iter()
             .next()
             .expect("details created above with a single jsonb column");
+        // Events before V2 don't record the context of the client.
+        let context = match event.context() {
+            Some(context) => Some(
+                Jsonb::from_serde_json(context.as_json())
+                    .map_err(|e| {
+                        Error::new(ErrorKind::Unstructured(format!(
+                            "could not pack audit log update: {}",
+                            e
+                        )))
+                    })?
+                    .into_row(),
+            ),
+            None => None,
+        };
         let dt = mz_ore::now::to_datetime(occurred_at).naive_utc();
         let id = event.sortable_id();
         Ok(BuiltinTableUpdate {
@@ -1338,6 +1467,9 @@ impl CatalogState {
                     None => Datum::Null,
                 },
                 Datum::TimestampTz(DateTime::from_utc(dt, Utc).try_into().expect("must fit")),
+                context
+                    .as_ref()
+                    .map_or(Datum::Null, |context| context.unpack_first()),
             ]),
             diff: 1,
         })
@@ -1345,19 +1477,55 @@ impl CatalogState {
 
     pub fn pack_storage_usage_update(
         &self,
-        VersionedStorageUsage::V1(event): &VersionedStorageUsage,
+        event: &VersionedStorageUsage,
     ) -> Result<BuiltinTableUpdate, Error> {
-        let id = self.resolve_builtin_table(&MZ_STORAGE_USAGE_BY_SHARD);
-        let row = Row::pack_slice(&[
-            Datum::UInt64(event.id),
-            Datum::from(event.shard_id.as_deref()),
-            Datum::UInt64(event.size_bytes),
-            Datum::TimestampTz(
-                mz_ore::now::to_datetime(event.collection_timestamp)
-                    .try_into()
-                    .expect("must fit"),
-            ),
-        ]);
+        let collection_timestamp = Datum::TimestampTz(
+            mz_ore::now::to_datetime(event.timestamp())
+                .try_into()
+                .expect("must fit"),
+        );
+        let (id, row) = match event {
+            VersionedStorageUsage::V1(event) => {
+                let id = self.resolve_builtin_table(&MZ_STORAGE_USAGE_BY_SHARD);
+                let row = Row::pack_slice(&[
+                    Datum::UInt64(event.id),
+                    Datum::from(event.shard_id.as_deref()),
+                    Datum::UInt64(event.size_bytes),
+                    collection_timestamp,
+                ]);
+                (id, row)
+            }
+            VersionedStorageUsage::IoV1(event) => {
+                let id = self.resolve_builtin_table(&MZ_STORAGE_IO_USAGE);
+                let row = Row::pack_slice(&[
+                    Datum::UInt64(event.id),
+                    Datum::String(&event.object_id),
+                    Datum::UInt64(event.bytes_read),
+                    Datum::UInt64(event.bytes_written),
+                    collection_timestamp,
+                ]);
+                (id, row)
+            }
+            VersionedStorageUsage::ComputeV1(event) => {
+                let id = self.resolve_builtin_table(&MZ_USAGE_BY_CLUSTER);
+                let peek_time = Interval::new(
+                    0,
+                    0,
+                    i64::try_from(event.peek_time_us).expect("peek time must fit in an i64"),
+                );
+                let row = Row::pack_slice(&[
+                    Datum::UInt64(event.id),
+                    Datum::String(&event.cluster_id),
+                    Datum::String(&event.role_id),
+                    Datum::UInt64(event.cpu_nano_cores),
+                    Datum::UInt64(event.memory_bytes),
+                    Datum::UInt64(event.peek_count),
+                    Datum::Interval(peek_time),
+                    collection_timestamp,
+                ]);
+                (id, row)
+            }
+        };
         Ok(BuiltinTableUpdate { id, row, diff: 1 })
     }
 
@@ -1481,6 +1649,7 @@ impl CatalogState {
                 Datum::UInt32(conn.conn_id().unhandled()),
                 Datum::String(&conn.authenticated_role_id().to_string()),
                 Datum::TimestampTz(connect_dt.try_into().expect("must fit")),
+                Datum::String(conn.authentication_method().as_str()),
             ]),
             diff,
         }
@@ -1532,6 +1701,25 @@ impl CatalogState {
         }
     }
 
+    pub fn pack_quota_update(
+        &self,
+        subject: QuotaSubject,
+        kind: QuotaKind,
+        max_value: u64,
+        diff: Diff,
+    ) -> BuiltinTableUpdate {
+        BuiltinTableUpdate {
+            id: self.resolve_builtin_table(&MZ_QUOTAS),
+            row: Row::pack_slice(&[
+                Datum::String(subject.subject_type()),
+                Datum::String(&subject.to_string()),
+                Datum::String(kind.as_str()),
+                Datum::UInt64(max_value),
+            ]),
+            diff,
+        }
+    }
+
     fn pack_privilege_array_row(&self, privileges: &PrivilegeMap) -> Row {
         let mut row = Row::default();
         let flat_privileges: Vec<_> = privileges.all_values_owned().collect();
@@ -1571,7 +1759,8 @@ impl CatalogState {
             | CommentObjectId::Func(global_id)
             | CommentObjectId::Connection(global_id)
             | CommentObjectId::Secret(global_id)
-            | CommentObjectId::Type(global_id) => global_id.to_string(),
+            | CommentObjectId::Type(global_id)
+            | CommentObjectId::ContinualTask(global_id) => global_id.to_string(),
             CommentObjectId::Role(role_id) => role_id.to_string(),
             CommentObjectId::Database(database_id) => database_id.to_string(),
             CommentObjectId::Schema((_, schema_id)) => schema_id.to_string(),
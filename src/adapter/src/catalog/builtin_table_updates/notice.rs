@@ -9,7 +9,7 @@
 
 use std::sync::Arc;
 
-use mz_catalog::builtin::notice::MZ_OPTIMIZER_NOTICES;
+use mz_catalog::builtin::notice::{MZ_NOTICE_TYPES, MZ_OPTIMIZER_NOTICES};
 use mz_repr::{Datum, Diff, GlobalId, Row};
 use mz_transform::dataflow::DataflowMetainfo;
 use mz_transform::notice::{
@@ -103,6 +103,23 @@ impl Catalog {
 }
 
 impl CatalogState {
+    /// Pack a [`BuiltinTableUpdate`] for each [`OptimizerNoticeKind`].
+    pub(crate) fn pack_notice_type_updates(&self) -> Vec<BuiltinTableUpdate> {
+        let id = self.resolve_builtin_table(&MZ_NOTICE_TYPES);
+        OptimizerNoticeKind::all()
+            .iter()
+            .map(|kind| BuiltinTableUpdate {
+                id,
+                row: Row::pack_slice(&[
+                    Datum::UInt64(kind.id()),
+                    Datum::String(kind.as_str()),
+                    Datum::String(kind.slug()),
+                ]),
+                diff: 1,
+            })
+            .collect()
+    }
+
     /// Pack a [`BuiltinTableUpdate`] with the given `diff` for each
     /// [`OptimizerNotice`] in `notices` into `updates`.
     pub(crate) fn pack_optimizer_notices<'a>(
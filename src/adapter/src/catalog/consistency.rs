@@ -215,7 +215,8 @@ impl CatalogState {
                 | CommentObjectId::Func(global_id)
                 | CommentObjectId::Connection(global_id)
                 | CommentObjectId::Type(global_id)
-                | CommentObjectId::Secret(global_id) => {
+                | CommentObjectId::Secret(global_id)
+                | CommentObjectId::ContinualTask(global_id) => {
                     let entry = self.entry_by_id.get(&global_id);
                     match entry {
                         None => comment_inconsistencies
@@ -480,6 +481,13 @@ impl CatalogState {
                         )
                         | Statement::CreateTable(ast::CreateTableStatement { name, .. })
                         | Statement::CreateType(ast::CreateTypeStatement { name, .. })
+                        | Statement::CreateFunction(ast::CreateFunctionStatement {
+                            name, ..
+                        })
+                        | Statement::CreateContinualTask(ast::CreateContinualTaskStatement {
+                            name,
+                            ..
+                        })
                         | Statement::CreateSecret(ast::CreateSecretStatement { name, .. }) => {
                             let [db_component, schema_component, item_component] = &name.0[..]
                             else {
@@ -0,0 +1,174 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A durable cache of the optimized expressions of builtin views.
+//!
+//! Optimizing the builtin views makes up a large part of the time it takes to
+//! open the catalog. The optimized expression of a view only depends on its
+//! HIR, the optimizer features, and the optimizer itself, so we persist it in
+//! the durable catalog under a digest of these inputs and reuse it on the next
+//! boot if the digest still matches.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use mz_build_info::BuildInfo;
+use mz_catalog::durable::{CatalogError, Transaction};
+use mz_expr::OptimizedMirRelationExpr;
+use mz_repr::GlobalId;
+use mz_sql::plan::HirRelationExpr;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+use crate::optimize::OptimizerConfig;
+
+/// A cached optimized expression, along with the digest of the inputs it was
+/// optimized from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CachedExpression {
+    key: String,
+    expr: OptimizedMirRelationExpr,
+}
+
+/// The optimized expressions of builtin views, keyed by the ID of the view.
+#[derive(Debug, Default)]
+pub(crate) struct ExpressionCache {
+    /// The entries read from the durable catalog.
+    durable: BTreeMap<GlobalId, CachedExpression>,
+    /// The entries that were missing or stale and must be written back.
+    updated: BTreeMap<GlobalId, CachedExpression>,
+    /// The IDs that were looked up in the cache.
+    used: BTreeSet<GlobalId>,
+    hits: usize,
+}
+
+impl ExpressionCache {
+    /// Loads the cache from the durable catalog. Entries that cannot be
+    /// deserialized, e.g. because the representation of expressions changed,
+    /// are treated as missing.
+    pub(crate) fn load(txn: &Transaction<'_>) -> Self {
+        let durable = txn
+            .get_cached_expressions()
+            .into_iter()
+            .filter_map(|(id, value)| match serde_json::from_str(&value) {
+                Ok(entry) => Some((id, entry)),
+                Err(e) => {
+                    warn!("ignoring invalid cached expression for {id}: {e}");
+                    None
+                }
+            })
+            .collect();
+        ExpressionCache {
+            durable,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the digest of the inputs that determine the optimized
+    /// expression of `raw_expr`.
+    pub(crate) fn key(
+        build_info: &BuildInfo,
+        config: &OptimizerConfig,
+        raw_expr: &HirRelationExpr,
+    ) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(build_info.version);
+        hasher.update(build_info.sha);
+        hasher.update(format!("{:?}", config.features));
+        hasher.update(format!("{raw_expr:?}"));
+        hex::encode(hasher.finalize())
+    }
+
+    /// Returns the cached expression of `id`, if it was optimized from inputs
+    /// with digest `key`.
+    pub(crate) fn get(&mut self, id: GlobalId, key: &str) -> Option<OptimizedMirRelationExpr> {
+        self.used.insert(id);
+        let entry = self.durable.get(&id).filter(|entry| entry.key == key)?;
+        self.hits += 1;
+        Some(entry.expr.clone())
+    }
+
+    /// Records `expr` as the optimized expression of `id`, which was optimized
+    /// from inputs with digest `key`.
+    pub(crate) fn insert(&mut self, id: GlobalId, key: String, expr: OptimizedMirRelationExpr) {
+        self.used.insert(id);
+        self.updated.insert(id, CachedExpression { key, expr });
+    }
+
+    /// Writes the new entries back to the durable catalog and removes the
+    /// entries of views that no longer exist.
+    pub(crate) fn persist(self, txn: &mut Transaction<'_>) -> Result<(), CatalogError> {
+        info!(
+            "builtin expression cache: {} hits, {} misses",
+            self.hits,
+            self.updated.len()
+        );
+        for (id, entry) in self.updated {
+            match serde_json::to_string(&entry) {
+                Ok(value) => txn.set_cached_expression(id, Some(value))?,
+                // The view will be optimized again on the next boot.
+                Err(e) => warn!("unable to cache optimized expression for {id}: {e}"),
+            }
+        }
+        for id in self.durable.keys() {
+            if !self.used.contains(id) {
+                txn.set_cached_expression(*id, None)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_build_info::DUMMY_BUILD_INFO;
+    use mz_expr::{MirRelationExpr, OptimizedMirRelationExpr};
+    use mz_repr::{GlobalId, RelationType};
+    use mz_sql::plan::HirRelationExpr;
+    use mz_sql::session::vars::SystemVars;
+
+    use super::{CachedExpression, ExpressionCache};
+    use crate::optimize::OptimizerConfig;
+
+    #[mz_ore::test]
+    fn test_expression_cache() {
+        let config = OptimizerConfig::from(&SystemVars::default());
+        let raw_expr = HirRelationExpr::constant(vec![], RelationType::empty());
+        let key = ExpressionCache::key(&DUMMY_BUILD_INFO, &config, &raw_expr);
+        let expr =
+            OptimizedMirRelationExpr(MirRelationExpr::constant(vec![], RelationType::empty()));
+
+        // Entries survive a serialization roundtrip.
+        let entry = CachedExpression {
+            key: key.clone(),
+            expr: expr.clone(),
+        };
+        let value = serde_json::to_string(&entry).unwrap();
+        assert_eq!(
+            serde_json::from_str::<CachedExpression>(&value).unwrap(),
+            entry
+        );
+
+        let mut cache = ExpressionCache::default();
+        cache.durable.insert(GlobalId::System(1), entry);
+        assert_eq!(cache.get(GlobalId::System(1), &key), Some(expr));
+        assert_eq!(cache.get(GlobalId::System(1), "stale"), None);
+        assert_eq!(cache.get(GlobalId::System(2), &key), None);
+        assert_eq!(cache.hits, 1);
+
+        // The key depends on the optimizer features.
+        let mut other_config = config.clone();
+        other_config.features.enable_eager_delta_joins =
+            !other_config.features.enable_eager_delta_joins;
+        assert_ne!(
+            ExpressionCache::key(&DUMMY_BUILD_INFO, &other_config, &raw_expr),
+            key
+        );
+    }
+}
@@ -174,8 +174,15 @@ fn _add_to_audit_log(
     occurred_at: EpochMillis,
 ) -> Result<(), anyhow::Error> {
     let id = tx.get_and_increment_id(mz_catalog::durable::AUDIT_LOG_ID_ALLOC_KEY.to_string())?;
-    let event =
-        mz_audit_log::VersionedEvent::new(id, event_type, object_type, details, None, occurred_at);
+    let event = mz_audit_log::VersionedEvent::new(
+        id,
+        event_type,
+        object_type,
+        details,
+        None,
+        mz_audit_log::EventContextV1::default(),
+        occurred_at,
+    );
     tx.insert_audit_log_event(event);
     Ok(())
 }
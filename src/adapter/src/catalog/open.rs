@@ -9,11 +9,13 @@
 
 //! Logic related to opening a [`Catalog`].
 use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use futures::future::{BoxFuture, FutureExt};
+use itertools::Itertools;
 use mz_adapter_types::compaction::CompactionWindow;
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -32,8 +34,8 @@ use mz_catalog::durable::objects::{
 use mz_catalog::durable::{Transaction, SYSTEM_CLUSTER_ID_ALLOC_KEY, SYSTEM_REPLICA_ID_ALLOC_KEY};
 use mz_catalog::memory::error::{Error, ErrorKind};
 use mz_catalog::memory::objects::{
-    CatalogEntry, CatalogItem, CommentsMap, DataSourceDesc, Database, DefaultPrivileges, Func, Log,
-    Role, Schema, Source, Table, Type,
+    CatalogEntry, CatalogItem, CommentsMap, DataSourceDesc, Database, DefaultPrivileges, Func,
+    FuncDefinition, Log, Role, Schema, Source, Table, Type,
 };
 use mz_catalog::SYSTEM_CONN_ID;
 use mz_cluster_client::ReplicaId;
@@ -55,8 +57,8 @@ use mz_sql::catalog::{
 };
 use mz_sql::func::OP_IMPLS;
 use mz_sql::names::{
-    ItemQualifiers, QualifiedItemName, QualifiedSchemaName, ResolvedDatabaseSpecifier, ResolvedIds,
-    SchemaId, SchemaSpecifier,
+    FullItemName, ItemQualifiers, QualifiedItemName, QualifiedSchemaName,
+    ResolvedDatabaseSpecifier, ResolvedIds, SchemaId, SchemaSpecifier,
 };
 use mz_sql::session::user::MZ_SYSTEM_ROLE_ID;
 use mz_sql::session::vars::{OwnedVarInput, SystemVars, VarError, VarInput};
@@ -67,6 +69,7 @@ use mz_ssh_util::keys::SshKeyPairSet;
 use mz_storage_types::sources::Timeline;
 
 // DO NOT add any more imports from `crate` outside of `crate::catalog`.
+use crate::catalog::expr_cache::ExpressionCache;
 use crate::catalog::{
     is_reserved_name, migrate, BuiltinTableUpdate, Catalog, CatalogPlans, CatalogState, Config,
 };
@@ -94,6 +97,8 @@ pub struct BuiltinMigrationMetadata {
     pub migrated_system_object_mappings: BTreeMap<GlobalId, SystemObjectMapping>,
     pub user_drop_ops: Vec<GlobalId>,
     pub user_create_ops: Vec<(GlobalId, SchemaId, u32, String)>,
+    // Used to describe the migration to operators
+    pub report: BuiltinMigrationReport,
 }
 
 impl BuiltinMigrationMetadata {
@@ -108,10 +113,84 @@ impl BuiltinMigrationMetadata {
             migrated_system_object_mappings: BTreeMap::new(),
             user_drop_ops: Vec::new(),
             user_create_ops: Vec::new(),
+            report: BuiltinMigrationReport::default(),
         }
     }
 }
 
+/// A description of the builtin schema migrations that opening a catalog performs.
+///
+/// Migrating a builtin item drops it along with everything that depends on it, and re-creates all
+/// of these items with new IDs. User items are re-created by planning their definitions again,
+/// which fails the upgrade if a definition no longer plans against the new builtin items.
+#[derive(Debug, Clone, Default)]
+pub struct BuiltinMigrationReport {
+    /// The builtin items that are replaced, in the order in which they are re-created.
+    pub replaced_builtins: Vec<MigratedItem>,
+    /// The user items that depend on replaced builtin items and are replanned, in the order in
+    /// which they are re-created.
+    pub replanned_user_items: Vec<MigratedItem>,
+}
+
+impl BuiltinMigrationReport {
+    /// Reports whether the migration replans any user items.
+    pub fn requires_replanning(&self) -> bool {
+        !self.replanned_user_items.is_empty()
+    }
+}
+
+impl fmt::Display for BuiltinMigrationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} builtin items replaced", self.replaced_builtins.len())?;
+        for item in &self.replaced_builtins {
+            writeln!(f, "  {item}")?;
+        }
+        writeln!(
+            f,
+            "{} user items replanned",
+            self.replanned_user_items.len()
+        )?;
+        for item in &self.replanned_user_items {
+            writeln!(f, "  {item}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An item that a builtin schema migration drops and re-creates.
+#[derive(Debug, Clone)]
+pub struct MigratedItem {
+    /// The name of the item.
+    pub name: FullItemName,
+    /// The type of the item.
+    pub item_type: CatalogItemType,
+    /// The ID of the item before the migration.
+    pub id: GlobalId,
+    /// The ID of the item after the migration.
+    pub new_id: GlobalId,
+    /// The migrated items that the item directly depends on, which is empty for builtin items
+    /// that are migrated because their own schema changed.
+    pub migrated_dependencies: Vec<FullItemName>,
+}
+
+impl fmt::Display for MigratedItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} ({} -> {})",
+            self.item_type, self.name, self.id, self.new_id
+        )?;
+        if !self.migrated_dependencies.is_empty() {
+            write!(
+                f,
+                ", depends on {}",
+                self.migrated_dependencies.iter().join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
 struct AllocatedBuiltinSystemIds<T> {
     all_builtins: Vec<(T, GlobalId)>,
     new_builtins: Vec<(T, GlobalId)>,
@@ -170,6 +249,7 @@ impl CatalogItemRebuilder {
                     None,
                     is_retained_metrics_object,
                     custom_logical_compaction_window,
+                    None,
                 )
                 .unwrap_or_else(|error| panic!("invalid persisted create sql ({error:?}): {sql}")),
         }
@@ -245,6 +325,8 @@ impl Catalog {
                 default_privileges: DefaultPrivileges::default(),
                 system_privileges: PrivilegeMap::default(),
                 comments: CommentsMap::default(),
+                quotas: BTreeMap::new(),
+                storage_usage: BTreeMap::new(),
             };
 
             let is_read_only = storage.is_read_only();
@@ -331,6 +413,15 @@ impl Catalog {
             let system_privileges = txn.get_system_privileges();
             state.system_privileges.grant_all(system_privileges);
 
+            for mz_catalog::durable::Quota {
+                subject,
+                kind,
+                limit,
+            } in txn.get_quotas()
+            {
+                state.quotas.entry(subject).or_default().insert(kind, limit);
+            }
+
             Catalog::load_system_configuration(
                 &mut state,
                 &mut txn,
@@ -418,6 +509,13 @@ impl Catalog {
                 .into_iter()
                 .partition(|(builtin, _)| matches!(builtin, Builtin::Index(_)));
 
+            // Optimizing the builtin views is expensive, so we reuse their optimized expressions
+            // from the previous boot if the inputs to the optimizer have not changed.
+            let mut expr_cache = state
+                .system_config()
+                .enable_builtin_expression_cache()
+                .then(|| ExpressionCache::load(&txn));
+
             {
                 let span = tracing::span!(tracing::Level::DEBUG, "builtin_non_indexes");
                 let _enter = span.enter();
@@ -466,6 +564,7 @@ impl Catalog {
                                     desc: table.desc.clone(),
                                     defaults: vec![Expr::null(); table.desc.arity()],
                                     conn_id: None,
+                                    delete_rows_on_commit: false,
                                     resolved_ids: ResolvedIds(BTreeSet::new()),
                                     custom_logical_compaction_window: table
                                         .is_retained_metrics_object
@@ -487,6 +586,7 @@ impl Catalog {
                                     None,
                                     false,
                                     None,
+                                    expr_cache.as_mut(),
                                 )
                                 .unwrap_or_else(|e| {
                                     panic!(
@@ -525,7 +625,11 @@ impl Catalog {
                                 id,
                                 oid,
                                 name.clone(),
-                                CatalogItem::Func(Func { inner: func.inner }),
+                                CatalogItem::Func(Func {
+                                    create_sql: None,
+                                    inner: FuncDefinition::Builtin(func.inner),
+                                    resolved_ids: ResolvedIds(BTreeSet::new()),
+                                }),
                                 MZ_SYSTEM_ROLE_ID,
                                 PrivilegeMap::default(),
                             );
@@ -561,6 +665,12 @@ impl Catalog {
                 }
             }
 
+            if let Some(expr_cache) = expr_cache {
+                if !is_read_only {
+                    expr_cache.persist(&mut txn)?;
+                }
+            }
+
             let clusters = txn.get_clusters();
             let mut cluster_azs = BTreeMap::new();
             for mz_catalog::durable::Cluster {
@@ -677,6 +787,7 @@ impl Catalog {
                                 None,
                                 index.is_retained_metrics_object,
                                 if index.is_retained_metrics_object { Some(state.system_config().metrics_retention().try_into().expect("invalid metrics retention")) } else { None },
+                                None,
                             )
                             .unwrap_or_else(|e| {
                                 panic!(
@@ -951,6 +1062,12 @@ impl Catalog {
                         .pack_system_privileges_update(system_privilege, 1),
                 );
             }
+            for (subject, quotas) in &catalog.state.quotas {
+                for (kind, limit) in quotas {
+                    builtin_table_updates
+                        .push(catalog.state.pack_quota_update(*subject, *kind, *limit, 1));
+                }
+            }
             for (id, cluster) in &catalog.state.clusters_by_id {
                 builtin_table_updates.push(catalog.state.pack_cluster_update(&cluster.name, 1));
                 for (replica_name, replica_id) in
@@ -1270,6 +1387,31 @@ impl Catalog {
                 );
             }
 
+            let migrated_dependencies = entry
+                .uses()
+                .into_iter()
+                .filter(|dep| ancestor_ids.contains_key(dep))
+                .map(|dep| state.resolve_full_name(state.get_entry(&dep).name(), None))
+                .collect();
+            let migrated_item = MigratedItem {
+                name,
+                item_type: entry.item_type(),
+                id,
+                new_id,
+                migrated_dependencies,
+            };
+            if id.is_user() {
+                migration_metadata
+                    .report
+                    .replanned_user_items
+                    .push(migrated_item);
+            } else {
+                migration_metadata
+                    .report
+                    .replaced_builtins
+                    .push(migrated_item);
+            }
+
             ancestor_ids.insert(id, new_id);
 
             // Push drop commands.
@@ -1309,7 +1451,8 @@ impl Catalog {
                 CatalogItem::Type(_)
                 | CatalogItem::Func(_)
                 | CatalogItem::Secret(_)
-                | CatalogItem::Connection(_) => unreachable!(
+                | CatalogItem::Connection(_)
+                | CatalogItem::ContinualTask(_) => unreachable!(
                     "impossible to migrate schema for builtin {}",
                     entry.item().typ()
                 ),
@@ -1807,6 +1950,7 @@ mod builtin_migration_tests {
     use mz_sql::session::user::MZ_SYSTEM_ROLE_ID;
     use mz_sql_parser::ast::Expr;
 
+    use crate::catalog::open::MigratedItem;
     use crate::catalog::{
         Catalog, CatalogItem, Index, MaterializedView, Op, OptimizedMirRelationExpr,
         DEFAULT_SCHEMA, SYSTEM_CONN_ID,
@@ -1845,6 +1989,7 @@ mod builtin_migration_tests {
                         .with_key(vec![0]),
                     defaults: vec![Expr::null(); 1],
                     conn_id: None,
+                    delete_rows_on_commit: false,
                     resolved_ids: ResolvedIds(BTreeSet::new()),
                     custom_logical_compaction_window: None,
                     is_retained_metrics_object: false,
@@ -1879,6 +2024,7 @@ mod builtin_migration_tests {
                         custom_logical_compaction_window: None,
                         refresh_schedule: None,
                         initial_as_of: None,
+                        memory_limit: None,
                     })
                 }
                 SimplifiedItem::Index { on } => {
@@ -2073,6 +2219,33 @@ mod builtin_migration_tests {
                 "{} test failed with wrong user create ops",
                 test_case.test_name
             );
+            assert_eq!(
+                migration_metadata
+                    .report
+                    .replanned_user_items
+                    .iter()
+                    .map(|item| item.name.item.clone())
+                    .collect::<Vec<_>>(),
+                test_case.expected_user_create_ops,
+                "{} test failed with wrong replanned user items",
+                test_case.test_name
+            );
+            assert_eq!(
+                migration_metadata
+                    .report
+                    .replaced_builtins
+                    .iter()
+                    .map(|item| item.name.item.clone())
+                    .collect::<Vec<_>>(),
+                test_case
+                    .expected_all_create_ops
+                    .iter()
+                    .filter(|name| !test_case.expected_user_create_ops.contains(name))
+                    .cloned()
+                    .collect::<Vec<_>>(),
+                "{} test failed with wrong replaced builtins",
+                test_case.test_name
+            );
             assert_eq!(
                 migration_metadata
                     .migrated_system_object_mappings
@@ -2091,6 +2264,85 @@ mod builtin_migration_tests {
         .await
     }
 
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] //  unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
+    async fn test_builtin_migration_report() {
+        Catalog::with_debug(NOW_ZERO.clone(), |mut catalog| async move {
+            let initial_state = vec![
+                SimplifiedCatalogEntry {
+                    name: "s1".to_string(),
+                    namespace: ItemNamespace::System,
+                    item: SimplifiedItem::Table,
+                },
+                SimplifiedCatalogEntry {
+                    name: "u1".to_string(),
+                    namespace: ItemNamespace::User,
+                    item: SimplifiedItem::MaterializedView {
+                        referenced_names: vec!["s1".to_string()],
+                    },
+                },
+                SimplifiedCatalogEntry {
+                    name: "u2".to_string(),
+                    namespace: ItemNamespace::User,
+                    item: SimplifiedItem::MaterializedView {
+                        referenced_names: vec!["u1".to_string()],
+                    },
+                },
+            ];
+            let mut id_mapping = BTreeMap::new();
+            for entry in initial_state {
+                let (name, namespace, item) = entry.to_catalog_item(&id_mapping);
+                let id = add_item(&mut catalog, name.clone(), item, namespace).await;
+                id_mapping.insert(name, id);
+            }
+
+            let report = {
+                let state = catalog.state.clone();
+                let mut storage = catalog.storage().await;
+                let mut txn = storage
+                    .transaction()
+                    .await
+                    .expect("failed to create transaction");
+                Catalog::generate_builtin_migration_metadata(
+                    &state,
+                    &mut txn,
+                    vec![id_mapping["s1"]],
+                    BTreeMap::from([(id_mapping["s1"], "".to_string())]),
+                )
+                .expect("failed to generate builtin migration metadata")
+                .report
+            };
+
+            let dependencies = |item: &MigratedItem| {
+                item.migrated_dependencies
+                    .iter()
+                    .map(|name| name.item.clone())
+                    .collect::<Vec<_>>()
+            };
+            assert!(report.requires_replanning());
+            assert_eq!(report.replaced_builtins.len(), 1);
+            assert_eq!(report.replaced_builtins[0].id, id_mapping["s1"]);
+            assert_ne!(report.replaced_builtins[0].new_id, id_mapping["s1"]);
+            assert_eq!(
+                dependencies(&report.replaced_builtins[0]),
+                Vec::<String>::new()
+            );
+            assert_eq!(
+                report
+                    .replanned_user_items
+                    .iter()
+                    .map(|item| (item.name.item.as_str(), dependencies(item)))
+                    .collect::<Vec<_>>(),
+                vec![
+                    ("u1", vec!["s1".to_string()]),
+                    ("u2", vec!["u1".to_string()])
+                ],
+            );
+            catalog.expire().await;
+        })
+        .await
+    }
+
     #[mz_ore::test(tokio::test)]
     #[cfg_attr(miri, ignore)] //  unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
     async fn test_builtin_migration_no_migrations() {
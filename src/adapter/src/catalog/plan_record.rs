@@ -0,0 +1,157 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Durable records of the plans of indexes and materialized views.
+//!
+//! For every index and materialized view we persist a fingerprint of its
+//! optimized plan in the durable catalog, along with the version of
+//! Materialize that produced it. When the coordinator re-optimizes these
+//! objects on boot, it compares the new fingerprint against the recorded one
+//! to detect plans that changed, usually because of an upgrade.
+
+use std::collections::BTreeMap;
+
+use mz_catalog::durable::Transaction;
+use mz_compute_types::dataflows::DataflowDescription;
+use mz_expr::OptimizedMirRelationExpr;
+use mz_ore::now::EpochMillis;
+use mz_repr::GlobalId;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+/// The recorded plan of an index or materialized view.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanRecord {
+    /// The version of Materialize that produced the plan.
+    pub version: String,
+    /// The fingerprint of the plan.
+    pub fingerprint: String,
+    /// The most recent change of the plan, if one was detected since the
+    /// last upgrade.
+    pub change: Option<PlanChange>,
+}
+
+/// A change of the plan of an index or materialized view.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanChange {
+    /// The version of Materialize that produced the previous plan.
+    pub previous_version: String,
+    /// The fingerprint of the previous plan.
+    pub previous_fingerprint: String,
+    /// The time at which the change was detected.
+    pub detected_at: EpochMillis,
+}
+
+impl PlanRecord {
+    /// Records the optimized `plan`, produced by Materialize `version`.
+    pub fn new(version: String, plan: &DataflowDescription<OptimizedMirRelationExpr>) -> Self {
+        PlanRecord {
+            version,
+            fingerprint: Self::fingerprint(plan),
+            change: None,
+        }
+    }
+
+    /// Returns the digest of the parts of `plan` that determine what the
+    /// dataflow computes and how.
+    ///
+    /// Identifiers that are allocated anew for every optimization, like the
+    /// transient ID of the view that backs a materialized view, are not part
+    /// of the fingerprint.
+    fn fingerprint(plan: &DataflowDescription<OptimizedMirRelationExpr>) -> String {
+        let mut hasher = Sha256::new();
+        for build in &plan.objects_to_build {
+            hasher.update(format!("{:?}", build.plan));
+        }
+        for (desc, _typ) in plan.index_exports.values() {
+            hasher.update(format!("{:?}", desc.keys));
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    /// Returns the record that replaces `self` once the object has been
+    /// optimized again into `next`.
+    ///
+    /// A different fingerprint is recorded as a change. A change is kept
+    /// across restarts until the next upgrade that leaves the plan intact.
+    pub fn advance(self, next: PlanRecord, now: EpochMillis) -> PlanRecord {
+        if self.fingerprint != next.fingerprint {
+            PlanRecord {
+                change: Some(PlanChange {
+                    previous_version: self.version,
+                    previous_fingerprint: self.fingerprint,
+                    detected_at: now,
+                }),
+                ..next
+            }
+        } else if self.version != next.version {
+            next
+        } else {
+            self
+        }
+    }
+}
+
+/// Loads the plan records from the durable catalog. Records that cannot be
+/// deserialized are treated as missing.
+pub(crate) fn load(txn: &Transaction<'_>) -> BTreeMap<GlobalId, PlanRecord> {
+    txn.get_plan_records()
+        .into_iter()
+        .filter_map(|(id, value)| match serde_json::from_str(&value) {
+            Ok(record) => Some((id, record)),
+            Err(e) => {
+                warn!("ignoring invalid plan record for {id}: {e}");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Returns the durable representation of `record`.
+pub(crate) fn encode(record: &PlanRecord) -> String {
+    serde_json::to_string(record).expect("serializing a plan record cannot fail")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PlanChange, PlanRecord};
+
+    fn record(version: &str, fingerprint: &str) -> PlanRecord {
+        PlanRecord {
+            version: version.into(),
+            fingerprint: fingerprint.into(),
+            change: None,
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_plan_record_advance() {
+        // An upgrade that changes the plan records the change.
+        let changed = record("v1", "a").advance(record("v2", "b"), 10);
+        assert_eq!(
+            changed,
+            PlanRecord {
+                version: "v2".into(),
+                fingerprint: "b".into(),
+                change: Some(PlanChange {
+                    previous_version: "v1".into(),
+                    previous_fingerprint: "a".into(),
+                    detected_at: 10,
+                }),
+            }
+        );
+
+        // A restart that does not change the plan keeps the change.
+        assert_eq!(changed.clone().advance(record("v2", "b"), 20), changed);
+
+        // An upgrade that does not change the plan forgets the change.
+        assert_eq!(changed.advance(record("v3", "b"), 30), record("v3", "b"));
+    }
+}
@@ -26,7 +26,9 @@ use timely::progress::Antichain;
 use tokio::sync::mpsc;
 use tracing::{info, warn};
 
-use mz_audit_log::{EventDetails, EventType, ObjectType, VersionedEvent, VersionedStorageUsage};
+use mz_audit_log::{
+    EventContextV1, EventDetails, EventType, ObjectType, VersionedEvent, VersionedStorageUsage,
+};
 use mz_build_info::DUMMY_BUILD_INFO;
 use mz_catalog::builtin::{
     Builtin, BuiltinCluster, BuiltinLog, BuiltinSource, BuiltinTable, BuiltinType, BUILTINS,
@@ -36,8 +38,8 @@ use mz_catalog::config::{AwsPrincipalContext, ClusterReplicaSizeMap};
 use mz_catalog::memory::error::{Error, ErrorKind};
 use mz_catalog::memory::objects::{
     CatalogEntry, CatalogItem, Cluster, ClusterConfig, ClusterReplica, ClusterReplicaProcessStatus,
-    CommentsMap, Connection, DataSourceDesc, Database, DefaultPrivileges, Index, MaterializedView,
-    Role, Schema, Secret, Sink, Source, Table, Type, View,
+    CommentsMap, Connection, ContinualTask, DataSourceDesc, Database, DefaultPrivileges, Func,
+    FuncDefinition, Index, MaterializedView, Role, Schema, Secret, Sink, Source, Table, Type, View,
 };
 use mz_catalog::SYSTEM_CONN_ID;
 use mz_controller::clusters::{
@@ -63,8 +65,8 @@ use mz_secrets::InMemorySecretsController;
 use mz_sql::catalog::{
     CatalogCluster, CatalogClusterReplica, CatalogConfig, CatalogDatabase,
     CatalogError as SqlCatalogError, CatalogItem as SqlCatalogItem, CatalogItemType, CatalogRole,
-    CatalogSchema, CatalogTypeDetails, EnvironmentId, IdReference, NameReference, SessionCatalog,
-    SystemObjectType, TypeReference,
+    CatalogSchema, CatalogTypeDetails, EnvironmentId, IdReference, NameReference, QuotaKind,
+    QuotaSubject, SessionCatalog, SystemObjectType, TypeReference,
 };
 use mz_sql::names::{
     CommentObjectId, DatabaseId, FullItemName, FullSchemaName, ItemQualifiers, ObjectId,
@@ -72,9 +74,9 @@ use mz_sql::names::{
     ResolvedDatabaseSpecifier, ResolvedIds, SchemaId, SchemaSpecifier, SystemObjectId,
 };
 use mz_sql::plan::{
-    CreateConnectionPlan, CreateIndexPlan, CreateMaterializedViewPlan, CreateSecretPlan,
-    CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan, CreateViewPlan, Params,
-    Plan, PlanContext,
+    CreateConnectionPlan, CreateContinualTaskPlan, CreateFunctionPlan, CreateIndexPlan,
+    CreateMaterializedViewPlan, CreateSecretPlan, CreateSinkPlan, CreateSourcePlan,
+    CreateTablePlan, CreateTypePlan, CreateViewPlan, Params, Plan, PlanContext,
 };
 use mz_sql::rbac;
 use mz_sql::session::user::MZ_SYSTEM_ROLE_ID;
@@ -86,6 +88,7 @@ use mz_storage_types::connections::inline::{
 use mz_storage_types::connections::ConnectionContext;
 
 // DO NOT add any more imports from `crate` outside of `crate::catalog`.
+use crate::catalog::expr_cache::ExpressionCache;
 use crate::catalog::{BuiltinTableUpdate, ConnCatalog};
 use crate::coord::ConnMeta;
 use crate::optimize::{self, Optimize};
@@ -131,6 +134,11 @@ pub struct CatalogState {
     pub(super) default_privileges: DefaultPrivileges,
     pub(super) system_privileges: PrivilegeMap,
     pub(super) comments: CommentsMap,
+    #[serde(serialize_with = "mz_ore::serde::map_key_to_string")]
+    pub(super) quotas: BTreeMap<QuotaSubject, BTreeMap<QuotaKind, u64>>,
+    /// The most recently collected storage usage of each collection, in bytes.
+    #[serde(skip)]
+    pub(super) storage_usage: BTreeMap<GlobalId, u64>,
 }
 
 fn skip_temp_items<S>(
@@ -182,6 +190,8 @@ impl CatalogState {
             default_privileges: Default::default(),
             system_privileges: Default::default(),
             comments: Default::default(),
+            quotas: Default::default(),
+            storage_usage: Default::default(),
         }
     }
 
@@ -301,7 +311,9 @@ impl CatalogState {
             CatalogItem::Log(_) => out.push(id),
             item @ (CatalogItem::View(_)
             | CatalogItem::MaterializedView(_)
-            | CatalogItem::Connection(_)) => {
+            | CatalogItem::Connection(_)
+            | CatalogItem::Func(_)
+            | CatalogItem::ContinualTask(_)) => {
                 // TODO(jkosh44) Unclear if this table wants to include all uses or only references.
                 for id in &item.references().0 {
                     self.introspection_dependencies_inner(*id, out);
@@ -312,7 +324,6 @@ impl CatalogState {
             CatalogItem::Table(_)
             | CatalogItem::Source(_)
             | CatalogItem::Type(_)
-            | CatalogItem::Func(_)
             | CatalogItem::Secret(_) => (),
         }
     }
@@ -705,6 +716,22 @@ impl CatalogState {
         self.roles_by_id.get_mut(id).expect("catalog out of sync")
     }
 
+    /// Returns the storage used by the objects of a quota subject, in bytes, as of the most
+    /// recent storage usage collection.
+    pub(super) fn quota_storage_usage(&self, subject: QuotaSubject) -> u64 {
+        self.storage_usage
+            .iter()
+            .filter_map(|(id, size_bytes)| Some((self.try_get_entry(id)?, size_bytes)))
+            .filter(|(entry, _)| match subject {
+                QuotaSubject::Role(id) => entry.owner_id() == &id,
+                QuotaSubject::Database(id) => {
+                    entry.name().qualifiers.database_spec == ResolvedDatabaseSpecifier::Id(id)
+                }
+            })
+            .map(|(_, size_bytes)| size_bytes)
+            .sum()
+    }
+
     pub(crate) fn collect_role_membership(&self, id: &RoleId) -> BTreeSet<RoleId> {
         let mut membership = BTreeSet::new();
         let mut queue = VecDeque::from(vec![id]);
@@ -783,13 +810,28 @@ impl CatalogState {
                 // Collect optimizer parameters.
                 let optimizer_config =
                     optimize::OptimizerConfig::from(session_catalog.system_vars());
+                let raw_expr = view.expr;
+                let expr_cache = expr_cache.map(|expr_cache| {
+                    let key =
+                        ExpressionCache::key(self.config.build_info, &optimizer_config, &raw_expr);
+                    (expr_cache, key)
+                });
 
                 // Build an optimizer for this VIEW.
                 let mut optimizer = optimize::view::Optimizer::new(optimizer_config);
 
                 // HIR ⇒ MIR lowering and MIR ⇒ MIR optimization (local)
-                let raw_expr = view.expr;
-                let optimized_expr = optimizer.optimize(raw_expr.clone())?;
+                let optimized_expr = match expr_cache {
+                    Some((expr_cache, key)) => match expr_cache.get(id, &key) {
+                        Some(optimized_expr) => optimized_expr,
+                        None => {
+                            let optimized_expr = optimizer.optimize(raw_expr.clone())?;
+                            expr_cache.insert(id, key, optimized_expr.clone());
+                            optimized_expr
+                        }
+                    },
+                    None => optimizer.optimize(raw_expr.clone())?,
+                };
 
                 CatalogItem::View(View {
                     create_sql: view.create_sql,
@@ -854,10 +896,13 @@ impl CatalogState {
         // TODO - The `None` needs to be changed if we ever allow custom
         // logical compaction windows in user-defined objects.
         let pcx = PlanContext::zero().with_planning_id(id);
-        self.parse_item(id, create_sql, Some(&pcx), false, None)
+        self.parse_item(id, create_sql, Some(&pcx), false, None, None)
     }
 
     /// Parses the given SQL string into a `CatalogItem`.
+    ///
+    /// If `expr_cache` is provided, the optimized expression of a view is
+    /// looked up in and recorded to it.
     #[mz_ore::instrument]
     pub(crate) fn parse_item(
         &self,
@@ -866,6 +911,7 @@ impl CatalogState {
         pcx: Option<&PlanContext>,
         is_retained_metrics_object: bool,
         custom_logical_compaction_window: Option<CompactionWindow>,
+        expr_cache: Option<&mut ExpressionCache>,
     ) -> Result<CatalogItem, AdapterError> {
         let mut session_catalog = self.for_system_session();
 
@@ -877,6 +923,7 @@ impl CatalogState {
                 desc: table.desc,
                 defaults: table.defaults,
                 conn_id: None,
+                delete_rows_on_commit: table.delete_rows_on_commit,
                 resolved_ids,
                 custom_logical_compaction_window: custom_logical_compaction_window
                     .or(table.compaction_window),
@@ -910,10 +957,12 @@ impl CatalogState {
                         validate_using,
                         body_format,
                         headers,
+                        deduplicate,
                     } => DataSourceDesc::Webhook {
                         validate_using,
                         body_format,
                         headers,
+                        deduplicate,
                         cluster_id: in_cluster
                             .expect("webhook sources must use an existing cluster"),
                     },
@@ -980,6 +1029,7 @@ impl CatalogState {
                     custom_logical_compaction_window: materialized_view.compaction_window,
                     refresh_schedule: materialized_view.refresh_schedule,
                     initial_as_of,
+                    memory_limit: materialized_view.memory_limit,
                 })
             }
             Plan::CreateIndex(CreateIndexPlan { index, .. }) => CatalogItem::Index(Index {
@@ -1017,6 +1067,11 @@ impl CatalogState {
                 },
                 resolved_ids,
             }),
+            Plan::CreateFunction(CreateFunctionPlan { function, .. }) => CatalogItem::Func(Func {
+                create_sql: Some(function.create_sql),
+                inner: FuncDefinition::User(Arc::new(function.inner)),
+                resolved_ids,
+            }),
             Plan::CreateSecret(CreateSecretPlan { secret, .. }) => CatalogItem::Secret(Secret {
                 create_sql: secret.create_sql,
             }),
@@ -1032,6 +1087,15 @@ impl CatalogState {
                 connection,
                 resolved_ids,
             }),
+            Plan::CreateContinualTask(CreateContinualTaskPlan { continual_task, .. }) => {
+                CatalogItem::ContinualTask(ContinualTask {
+                    create_sql: continual_task.create_sql,
+                    raw_expr: continual_task.expr,
+                    target_id: continual_task.target_id,
+                    resolved_ids,
+                    cluster_id: continual_task.cluster_id,
+                })
+            }
             _ => {
                 return Err(Error::new(ErrorKind::Corruption {
                     detail: "catalog entry generated inappropriate plan".to_string(),
@@ -1174,21 +1238,12 @@ impl CatalogState {
             &metadata.name().qualifiers.schema_spec,
             conn_id,
         );
-        if metadata.item_type() == CatalogItemType::Type {
-            schema
-                .types
-                .remove(&metadata.name().item)
-                .expect("catalog out of sync");
-        } else {
-            // Functions would need special handling, but we don't yet support
-            // dropping functions.
-            assert_ne!(metadata.item_type(), CatalogItemType::Func);
-
-            schema
-                .items
-                .remove(&metadata.name().item)
-                .expect("catalog out of sync");
-        };
+        match metadata.item_type() {
+            CatalogItemType::Type => schema.types.remove(&metadata.name().item),
+            CatalogItemType::Func => schema.functions.remove(&metadata.name().item),
+            _ => schema.items.remove(&metadata.name().item),
+        }
+        .expect("catalog out of sync");
 
         if !id.is_system() {
             if let Some(cluster_id) = metadata.item().cluster_id() {
@@ -1977,6 +2032,7 @@ impl CatalogState {
                     CatalogItemType::Connection => CommentObjectId::Connection(global_id),
                     CatalogItemType::Type => CommentObjectId::Type(global_id),
                     CatalogItemType::Secret => CommentObjectId::Secret(global_id),
+                    CatalogItemType::ContinualTask => CommentObjectId::ContinualTask(global_id),
                 }
             }
             ObjectId::Role(role_id) => CommentObjectId::Role(role_id),
@@ -2188,6 +2244,7 @@ impl CatalogState {
         details: EventDetails,
     ) -> Result<(), Error> {
         let user = session.map(|session| session.user().name.to_string());
+        let context = self.audit_event_context(session);
 
         // unsafe_mock_audit_event_timestamp can only be set to Some when running in unsafe mode.
 
@@ -2200,13 +2257,50 @@ impl CatalogState {
         };
         let id =
             tx.get_and_increment_id(mz_catalog::durable::AUDIT_LOG_ID_ALLOC_KEY.to_string())?;
-        let event = VersionedEvent::new(id, event_type, object_type, details, user, occurred_at);
+        let event = VersionedEvent::new(
+            id,
+            event_type,
+            object_type,
+            details,
+            user,
+            context,
+            occurred_at,
+        );
         builtin_table_updates.push(self.pack_audit_log_update(&event)?);
         audit_events.push(event.clone());
         tx.insert_audit_log_event(event);
         Ok(())
     }
 
+    /// Returns the context of the client of `session` to record in audit
+    /// events, restricted to the fields in `audit_event_context_fields`.
+    fn audit_event_context(&self, session: Option<&ConnMeta>) -> EventContextV1 {
+        let Some(session) = session else {
+            return EventContextV1::default();
+        };
+        let fields = self.system_configuration.audit_event_context_fields();
+        let enabled = |field: &str| fields.iter().any(|f| f == field);
+        EventContextV1 {
+            client_ip: session
+                .client_ip()
+                .filter(|_| enabled("client_ip"))
+                .map(|ip| ip.to_string()),
+            application_name: Some(session.application_name())
+                .filter(|name| enabled("application_name") && !name.is_empty())
+                .map(|name| name.to_string()),
+            http_request_id: session
+                .http_request_id()
+                .filter(|_| enabled("http_request_id"))
+                .map(|id| id.to_string()),
+            sso_subject: session
+                .user()
+                .external_metadata
+                .as_ref()
+                .filter(|_| enabled("sso_subject"))
+                .map(|metadata| metadata.user_id.to_string()),
+        }
+    }
+
     pub(super) fn add_to_storage_usage(
         &self,
         tx: &mut mz_catalog::durable::Transaction,
@@ -2224,6 +2318,61 @@ impl CatalogState {
         Ok(())
     }
 
+    pub(super) fn add_to_storage_io_usage(
+        &self,
+        tx: &mut mz_catalog::durable::Transaction,
+        builtin_table_updates: &mut Vec<BuiltinTableUpdate>,
+        object_id: String,
+        bytes_read: u64,
+        bytes_written: u64,
+        collection_timestamp: EpochMillis,
+    ) -> Result<(), Error> {
+        let id =
+            tx.get_and_increment_id(mz_catalog::durable::STORAGE_USAGE_ID_ALLOC_KEY.to_string())?;
+
+        let details = VersionedStorageUsage::new_io(
+            id,
+            object_id,
+            bytes_read,
+            bytes_written,
+            collection_timestamp,
+        );
+        builtin_table_updates.push(self.pack_storage_usage_update(&details)?);
+        tx.insert_storage_usage_event(details);
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn add_to_compute_usage(
+        &self,
+        tx: &mut mz_catalog::durable::Transaction,
+        builtin_table_updates: &mut Vec<BuiltinTableUpdate>,
+        cluster_id: String,
+        role_id: String,
+        cpu_nano_cores: u64,
+        memory_bytes: u64,
+        peek_count: u64,
+        peek_time_us: u64,
+        collection_timestamp: EpochMillis,
+    ) -> Result<(), Error> {
+        let id =
+            tx.get_and_increment_id(mz_catalog::durable::STORAGE_USAGE_ID_ALLOC_KEY.to_string())?;
+
+        let details = VersionedStorageUsage::new_compute(
+            id,
+            cluster_id,
+            role_id,
+            cpu_nano_cores,
+            memory_bytes,
+            peek_count,
+            peek_time_us,
+            collection_timestamp,
+        );
+        builtin_table_updates.push(self.pack_storage_usage_update(&details)?);
+        tx.insert_storage_usage_event(details);
+        Ok(())
+    }
+
     pub(super) fn get_owner_id(&self, id: &ObjectId, conn_id: &ConnectionId) -> Option<RoleId> {
         match id {
             ObjectId::Cluster(id) => Some(self.get_cluster(*id).owner_id()),
@@ -39,20 +39,23 @@ use mz_sql::session::vars::{OwnedVarInput, Var, CLUSTER};
 use mz_sql_parser::parser::{ParserStatementError, StatementParseResult};
 use prometheus::Histogram;
 use serde_json::json;
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{mpsc, oneshot, watch};
 use tracing::error;
 use uuid::Uuid;
 
 use crate::catalog::Catalog;
 use crate::command::{
-    CatalogDump, CatalogSnapshot, Command, ExecuteResponse, GetVariablesResponse, Response,
+    CatalogDump, CatalogSnapshot, Command, DrainStatus, ExecuteResponse, GetVariablesResponse,
+    Response,
 };
+use crate::coord::drain::SessionDrain;
 use crate::coord::{Coordinator, ExecuteContextExtra};
 use crate::error::AdapterError;
 use crate::metrics::Metrics;
 use crate::optimize::{self, Optimize};
 use crate::session::{
-    EndTransactionAction, PreparedStatement, Session, SessionConfig, TransactionId,
+    AuthenticationMethod, EndTransactionAction, PreparedStatement, Session, SessionConfig,
+    TransactionId,
 };
 use crate::statement_logging::StatementEndedExecutionReason;
 use crate::telemetry::{self, SegmentClientExt, StatementFailureType};
@@ -97,6 +100,7 @@ impl Handle {
 pub struct Client {
     build_info: &'static BuildInfo,
     inner_cmd_tx: mpsc::UnboundedSender<(OpenTelemetryContext, Command)>,
+    drain_rx: watch::Receiver<SessionDrain>,
     id_alloc: IdAllocator<IdAllocatorInnerBitSet>,
     now: NowFn,
     metrics: Metrics,
@@ -108,6 +112,7 @@ impl Client {
     pub(crate) fn new(
         build_info: &'static BuildInfo,
         cmd_tx: mpsc::UnboundedSender<(OpenTelemetryContext, Command)>,
+        drain_rx: watch::Receiver<SessionDrain>,
         metrics: Metrics,
         now: NowFn,
         environment_id: EnvironmentId,
@@ -124,6 +129,7 @@ impl Client {
         Client {
             build_info,
             inner_cmd_tx: cmd_tx,
+            drain_rx,
             id_alloc: IdAllocator::new(1, MAX_ORG_ID, env_lower),
             now,
             metrics,
@@ -162,6 +168,9 @@ impl Client {
         let secret_key = session.secret_key();
         let uuid = session.uuid();
         let application_name = session.application_name().into();
+        let authentication_method = session.authentication_method().clone();
+        let client_ip = session.client_ip();
+        let http_request_id = session.http_request_id().map(|id| id.to_string());
         let notice_tx = session.retain_notice_transmitter();
 
         let (tx, rx) = oneshot::channel();
@@ -172,6 +181,9 @@ impl Client {
             secret_key,
             uuid,
             application_name,
+            authentication_method,
+            client_ip,
+            http_request_id,
             notice_tx,
         });
 
@@ -334,6 +346,10 @@ Issue a SQL query to get started. Need help?
             conn_id,
             user: SUPPORT_USER.name.clone(),
             external_metadata_rx: None,
+            authentication_method: AuthenticationMethod::Trust,
+            trace_context: None,
+            client_ip: None,
+            http_request_id: None,
         });
         let mut session_client = self.startup(session).await?;
 
@@ -356,6 +372,8 @@ Issue a SQL query to get started. Need help?
             (ExecuteResponse::SendingRows { future }, _) => match future.await {
                 PeekResponseUnary::Rows(rows) => Ok(rows),
                 PeekResponseUnary::Canceled => bail!("query canceled"),
+                PeekResponseUnary::TimedOut => bail!(AdapterError::StatementTimeout),
+                PeekResponseUnary::ResultSizeExceeded(e) => bail!(AdapterError::ResultSize(e)),
                 PeekResponseUnary::Error(e) => bail!(e),
             },
             r => bail!("unsupported response type: {r:?}"),
@@ -397,6 +415,49 @@ Issue a SQL query to get started. Need help?
         response
     }
 
+    /// Starts draining the sessions of the coordinator ahead of a handoff to a standby process.
+    ///
+    /// New sessions of external users are rejected, and existing sessions close once they are
+    /// idle outside of a transaction. After `timeout`, the statements that are still running are
+    /// canceled. Returns the progress of the drain.
+    ///
+    /// No authorization is performed, so access to this function must be limited to internal
+    /// servers.
+    pub async fn drain(&self, timeout: Duration) -> DrainStatus {
+        let (tx, rx) = oneshot::channel();
+        self.send(Command::Drain { timeout, tx });
+        rx.await.expect("sender dropped")
+    }
+
+    /// Returns the progress of draining the sessions of the coordinator.
+    pub async fn drain_status(&self) -> DrainStatus {
+        let (tx, rx) = oneshot::channel();
+        self.send(Command::GetDrainStatus { tx });
+        rx.await.expect("sender dropped")
+    }
+
+    /// Returns the asynchronous SQL jobs of the HTTP API that are recorded in the durable
+    /// catalog, keyed by the ID of the job.
+    ///
+    /// The jobs are opaque to the coordinator. No authorization is performed, so callers must
+    /// only reveal jobs to the users that submitted them.
+    pub async fn get_sql_jobs(&self) -> Result<BTreeMap<String, String>, AdapterError> {
+        let (tx, rx) = oneshot::channel();
+        self.send(Command::GetSqlJobs { tx });
+        rx.await.expect("sender dropped")
+    }
+
+    /// Records the asynchronous SQL jobs `jobs` in the durable catalog, or removes the jobs that
+    /// are mapped to `None`.
+    pub async fn set_sql_jobs(
+        &self,
+        jobs: BTreeMap<String, Option<String>>,
+    ) -> Result<(), AdapterError> {
+        let (tx, rx) = oneshot::channel();
+        self.send(Command::SetSqlJobs { jobs, tx });
+        rx.await.expect("sender dropped")
+    }
+
     #[instrument(level = "debug")]
     fn send(&self, cmd: Command) {
         self.inner_cmd_tx
@@ -688,6 +749,21 @@ impl SessionClient {
         columns: Vec<usize>,
         rows: Vec<Row>,
         ctx_extra: ExecuteContextExtra,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let result = self.insert_rows_batch(id, columns, rows).await;
+        self.retire_execute(ctx_extra, (&result).into());
+        result
+    }
+
+    /// Inserts a batch of the rows of a `COPY ... FROM` statement into the
+    /// current transaction, without ending the execution of the statement.
+    ///
+    /// The rows of all batches are committed together with the transaction.
+    pub async fn insert_rows_batch(
+        &mut self,
+        id: GlobalId,
+        columns: Vec<usize>,
+        rows: Vec<Row>,
     ) -> Result<ExecuteResponse, AdapterError> {
         // TODO: Remove this clone once we always have the session. It's currently needed because
         // self.session returns a mut ref, so we can't call it twice.
@@ -701,16 +777,13 @@ impl SessionClient {
         // Build an optimizer for this VIEW.
         let mut optimizer = optimize::view::Optimizer::new(optimizer_config);
 
-        let result: Result<_, AdapterError> =
-            mz_sql::plan::plan_copy_from(&pcx, &conn_catalog, id, columns, rows)
-                .err_into()
-                .and_then(|values| optimizer.optimize(values).err_into())
-                .and_then(|values| {
-                    // Copied rows must always be constants.
-                    Coordinator::insert_constant(&catalog, self.session(), id, values.into_inner())
-                });
-        self.retire_execute(ctx_extra, (&result).into());
-        result
+        mz_sql::plan::plan_copy_from(&pcx, &conn_catalog, id, columns, rows)
+            .err_into()
+            .and_then(|values| optimizer.optimize(values).err_into())
+            .and_then(|values| {
+                // Copied rows must always be constants.
+                Coordinator::insert_constant(&catalog, self.session(), id, values.into_inner())
+            })
     }
 
     /// Gets the current value of all system variables.
@@ -806,7 +879,11 @@ impl SessionClient {
                 | Command::SetSystemVars { .. }
                 | Command::Terminate { .. }
                 | Command::RetireExecute { .. }
-                | Command::CheckConsistency { .. } => {}
+                | Command::CheckConsistency { .. }
+                | Command::Drain { .. }
+                | Command::GetDrainStatus { .. }
+                | Command::GetSqlJobs { .. }
+                | Command::SetSqlJobs { .. } => {}
             };
             cmd
         });
@@ -853,6 +930,14 @@ impl SessionClient {
                 self.timeouts.add_timeout(timeout, timeout_dur);
             }
         }
+        // An idle session must not keep other writes from committing for longer than its
+        // transaction may hold the write lock, regardless of the idle timeout.
+        let txn = self.session().transaction();
+        if let (Some(inner), Some(deadline)) = (txn.inner(), txn.write_lock_deadline()) {
+            let timeout = TimeoutType::WriteLock(inner.id);
+            let timeout_dur = deadline.saturating_duration_since(Instant::now());
+            self.timeouts.add_timeout(timeout, timeout_dur);
+        }
     }
 
     pub fn remove_idle_in_transaction_session_timeout(&mut self) {
@@ -861,6 +946,8 @@ impl SessionClient {
             let txn_id = txn.id.clone();
             self.timeouts
                 .remove_timeout(&TimeoutType::IdleInTransactionSession(txn_id));
+            self.timeouts
+                .remove_timeout(&TimeoutType::WriteLock(txn_id));
         }
     }
 
@@ -873,6 +960,38 @@ impl SessionClient {
     pub async fn recv_timeout(&mut self) -> Option<TimeoutType> {
         self.timeouts.recv().await
     }
+
+    /// Returns a future that completes once the session should close because the coordinator
+    /// is draining.
+    ///
+    /// A session outside of a transaction closes as soon as the drain starts, while a session
+    /// within a transaction closes once the deadline of the drain passed. Sessions of internal
+    /// users never close. The future does not borrow the client, so that it can be raced against
+    /// [`SessionClient::recv_timeout`].
+    ///
+    /// # Cancel safety
+    ///
+    /// The returned future is cancel safe.
+    pub fn drained(&mut self) -> impl Future<Output = ()> + Send + 'static {
+        let internal = self.session().user().is_internal();
+        let in_transaction = self.session().transaction().inner().is_some();
+        let mut drain_rx = self.inner().drain_rx.clone();
+        async move {
+            let closed = drain_rx
+                .wait_for(|drain| match drain {
+                    SessionDrain::None => false,
+                    SessionDrain::Idle => !internal && !in_transaction,
+                    SessionDrain::Immediate => !internal,
+                })
+                .await
+                .is_err();
+            if closed {
+                // The coordinator has shut down, which the session notices on its next
+                // command.
+                futures::future::pending::<()>().await;
+            }
+        }
+    }
 }
 
 impl Drop for SessionClient {
@@ -896,6 +1015,7 @@ impl Drop for SessionClient {
 #[derive(Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
 pub enum TimeoutType {
     IdleInTransactionSession(TransactionId),
+    WriteLock(TransactionId),
 }
 
 impl Display for TimeoutType {
@@ -904,6 +1024,9 @@ impl Display for TimeoutType {
             TimeoutType::IdleInTransactionSession(txn_id) => {
                 writeln!(f, "Idle in transaction session for transaction '{txn_id}'")
             }
+            TimeoutType::WriteLock(txn_id) => {
+                writeln!(f, "Write lock held too long by transaction '{txn_id}'")
+            }
         }
     }
 }
@@ -914,6 +1037,7 @@ impl From<TimeoutType> for AdapterError {
             TimeoutType::IdleInTransactionSession(_) => {
                 AdapterError::IdleInTransactionSessionTimeout
             }
+            TimeoutType::WriteLock(_) => AdapterError::WriteLockTimeout,
         }
     }
 }
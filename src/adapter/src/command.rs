@@ -9,8 +9,10 @@
 
 use std::collections::{BTreeMap, BTreeSet};
 use std::future::Future;
+use std::net::IpAddr;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 
 use derivative::Derivative;
 use enum_kinds::EnumKind;
@@ -28,6 +30,7 @@ use mz_sql::plan::{ExecuteTimeout, Plan, PlanKind};
 use mz_sql::session::user::User;
 use mz_sql::session::vars::{OwnedVarInput, Var};
 use mz_sql_parser::ast::{AlterObjectRenameStatement, AlterOwnerStatement, DropObjectsStatement};
+use serde::{Deserialize, Serialize};
 use tokio::sync::{mpsc, oneshot};
 use uuid::Uuid;
 
@@ -36,7 +39,7 @@ use crate::coord::consistency::CoordinatorInconsistencies;
 use crate::coord::peek::PeekResponseUnary;
 use crate::coord::ExecuteContextExtra;
 use crate::error::AdapterError;
-use crate::session::{EndTransactionAction, RowBatchStream, Session};
+use crate::session::{AuthenticationMethod, EndTransactionAction, RowBatchStream, Session};
 use crate::statement_logging::StatementEndedExecutionReason;
 use crate::util::Transmittable;
 use crate::webhook::AppendWebhookResponse;
@@ -60,6 +63,9 @@ pub enum Command {
         secret_key: u32,
         uuid: Uuid,
         application_name: String,
+        authentication_method: AuthenticationMethod,
+        client_ip: Option<IpAddr>,
+        http_request_id: Option<String>,
         notice_tx: mpsc::UnboundedSender<AdapterNotice>,
     },
 
@@ -122,6 +128,26 @@ pub enum Command {
     CheckConsistency {
         tx: oneshot::Sender<Result<(), CoordinatorInconsistencies>>,
     },
+
+    /// Starts draining the sessions of the coordinator, see
+    /// [`Client::drain`](crate::Client::drain).
+    Drain {
+        timeout: Duration,
+        tx: oneshot::Sender<DrainStatus>,
+    },
+
+    GetDrainStatus {
+        tx: oneshot::Sender<DrainStatus>,
+    },
+
+    GetSqlJobs {
+        tx: oneshot::Sender<Result<BTreeMap<String, String>, AdapterError>>,
+    },
+
+    SetSqlJobs {
+        jobs: BTreeMap<String, Option<String>>,
+        tx: oneshot::Sender<Result<(), AdapterError>>,
+    },
 }
 
 impl Command {
@@ -137,7 +163,11 @@ impl Command {
             | Command::GetSystemVars { .. }
             | Command::SetSystemVars { .. }
             | Command::RetireExecute { .. }
-            | Command::CheckConsistency { .. } => None,
+            | Command::CheckConsistency { .. }
+            | Command::Drain { .. }
+            | Command::GetDrainStatus { .. }
+            | Command::GetSqlJobs { .. }
+            | Command::SetSqlJobs { .. } => None,
         }
     }
 
@@ -153,7 +183,11 @@ impl Command {
             | Command::GetSystemVars { .. }
             | Command::SetSystemVars { .. }
             | Command::RetireExecute { .. }
-            | Command::CheckConsistency { .. } => None,
+            | Command::CheckConsistency { .. }
+            | Command::Drain { .. }
+            | Command::GetDrainStatus { .. }
+            | Command::GetSqlJobs { .. }
+            | Command::SetSqlJobs { .. } => None,
         }
     }
 }
@@ -190,6 +224,23 @@ impl Transmittable for StartupResponse {
     }
 }
 
+/// The progress of draining the sessions of the coordinator.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DrainStatus {
+    /// No drain was requested.
+    NotDraining,
+    /// New sessions are rejected, but sessions of external users remain.
+    Draining {
+        /// The number of remaining sessions.
+        remaining_sessions: usize,
+        /// Whether the statements of the remaining sessions were canceled because the deadline
+        /// of the drain passed.
+        deadline_passed: bool,
+    },
+    /// No session of an external user remains.
+    Drained,
+}
+
 /// The response to [`SessionClient::dump_catalog`](crate::SessionClient::dump_catalog).
 #[derive(Debug, Clone)]
 pub struct CatalogDump(String);
@@ -255,6 +306,8 @@ pub enum ExecuteResponse {
     AlteredObject(ObjectType),
     /// The index was altered.
     AlteredIndexLogicalCompaction,
+    /// The quota was altered.
+    AlteredQuota,
     /// The role was altered.
     AlteredRole,
     /// The system configuration was altered.
@@ -306,6 +359,10 @@ pub enum ExecuteResponse {
     CreatedMaterializedView,
     /// The requested type was created.
     CreatedType,
+    /// The requested function was created.
+    CreatedFunction,
+    /// The requested continual task was created.
+    CreatedContinualTask,
     /// The requested prepared statement was removed.
     Deallocate { all: bool },
     /// The requested cursor was declared.
@@ -314,6 +371,9 @@ pub enum ExecuteResponse {
     Deleted(usize),
     /// The temporary objects associated with the session have been discarded.
     DiscardedTemp,
+    /// The cached plans of the session's prepared statements have been
+    /// discarded.
+    DiscardedPlans,
     /// All state associated with the session has been discarded.
     DiscardedAll,
     /// The requested object was dropped.
@@ -408,16 +468,27 @@ impl TryFrom<&Statement<Raw>> for ExecuteResponse {
             }
         }
         let resp = match stmt {
-            Statement::DropObjects(DropObjectsStatement { object_type, .. }) => {
-                ExecuteResponse::DroppedObject((*object_type).into())
-            }
+            // Dry runs list the objects that would be dropped, which is not trivial.
+            Statement::DropObjects(DropObjectsStatement {
+                object_type,
+                dry_run: false,
+                ..
+            }) => ExecuteResponse::DroppedObject((*object_type).into()),
             Statement::AlterObjectRename(AlterObjectRenameStatement { object_type, .. })
             | Statement::AlterOwner(AlterOwnerStatement { object_type, .. }) => {
                 ExecuteResponse::AlteredObject((*object_type).into())
             }
             _ => return Err(()),
         };
-        // Ensure that if the planner ever adds possible plans we complain here.
+        // Ensure that if the planner ever adds possible plans we complain here. `DROP` statements
+        // are also planned as `SELECT`s, but only for dry runs, which are excluded above.
+        let resp_kinds = match stmt {
+            Statement::DropObjects(_) => resp_kinds
+                .into_iter()
+                .filter(|kind| !ExecuteResponse::generated_from(&PlanKind::Select).contains(kind))
+                .collect(),
+            _ => resp_kinds,
+        };
         soft_assert_no_log!(
             resp_kinds.len() == 1
                 && resp_kinds.first().expect("must exist") == &ExecuteResponseKind::from(&resp),
@@ -441,6 +512,7 @@ impl TryInto<ExecuteResponse> for ExecuteResponseKind {
             ExecuteResponseKind::AlteredIndexLogicalCompaction => {
                 Ok(ExecuteResponse::AlteredIndexLogicalCompaction)
             }
+            ExecuteResponseKind::AlteredQuota => Ok(ExecuteResponse::AlteredQuota),
             ExecuteResponseKind::AlteredRole => Ok(ExecuteResponse::AlteredRole),
             ExecuteResponseKind::AlteredSystemConfiguration => {
                 Ok(ExecuteResponse::AlteredSystemConfiguration)
@@ -469,10 +541,13 @@ impl TryInto<ExecuteResponse> for ExecuteResponseKind {
                 Ok(ExecuteResponse::CreatedMaterializedView)
             }
             ExecuteResponseKind::CreatedType => Ok(ExecuteResponse::CreatedType),
+            ExecuteResponseKind::CreatedFunction => Ok(ExecuteResponse::CreatedFunction),
+            ExecuteResponseKind::CreatedContinualTask => Ok(ExecuteResponse::CreatedContinualTask),
             ExecuteResponseKind::Deallocate => Err(()),
             ExecuteResponseKind::DeclaredCursor => Ok(ExecuteResponse::DeclaredCursor),
             ExecuteResponseKind::Deleted => Err(()),
             ExecuteResponseKind::DiscardedTemp => Ok(ExecuteResponse::DiscardedTemp),
+            ExecuteResponseKind::DiscardedPlans => Ok(ExecuteResponse::DiscardedPlans),
             ExecuteResponseKind::DiscardedAll => Ok(ExecuteResponse::DiscardedAll),
             ExecuteResponseKind::DroppedObject => Err(()),
             ExecuteResponseKind::DroppedOwned => Ok(ExecuteResponse::DroppedOwned),
@@ -506,6 +581,7 @@ impl ExecuteResponse {
             AlteredDefaultPrivileges => Some("ALTER DEFAULT PRIVILEGES".into()),
             AlteredObject(o) => Some(format!("ALTER {}", o)),
             AlteredIndexLogicalCompaction => Some("ALTER INDEX".into()),
+            AlteredQuota => Some("ALTER QUOTA".into()),
             AlteredRole => Some("ALTER ROLE".into()),
             AlteredSystemConfiguration => Some("ALTER SYSTEM".into()),
             ClosedCursor => Some("CLOSE CURSOR".into()),
@@ -528,10 +604,13 @@ impl ExecuteResponse {
             CreatedViews { .. } => Some("CREATE VIEWS".into()),
             CreatedMaterializedView { .. } => Some("CREATE MATERIALIZED VIEW".into()),
             CreatedType => Some("CREATE TYPE".into()),
+            CreatedFunction => Some("CREATE FUNCTION".into()),
+            CreatedContinualTask => Some("CREATE CONTINUAL TASK".into()),
             Deallocate { all } => Some(format!("DEALLOCATE{}", if *all { " ALL" } else { "" })),
             DeclaredCursor => Some("DECLARE CURSOR".into()),
             Deleted(n) => Some(format!("DELETE {}", n)),
             DiscardedTemp => Some("DISCARD TEMP".into()),
+            DiscardedPlans => Some("DISCARD PLANS".into()),
             DiscardedAll => Some("DISCARD ALL".into()),
             DroppedObject(o) => Some(format!("DROP {o}")),
             DroppedOwned => Some("DROP OWNED".into()),
@@ -579,6 +658,7 @@ impl ExecuteResponse {
             | AlterClusterSwap
             | AlterCluster
             | AlterClusterReplicaRename
+            | AlterClusterReplica
             | AlterOwner
             | AlterItemRename
             | AlterItemSwap
@@ -588,12 +668,14 @@ impl ExecuteResponse {
             | AlterSecret
             | AlterConnection
             | AlterSource
+            | AlterSinkResetOffset
             | PurifiedAlterSource => &[AlteredObject],
             AlterDefaultPrivileges => &[AlteredDefaultPrivileges],
             AlterSetCluster => &[AlteredObject],
             AlterIndexSetOptions | AlterIndexResetOptions => {
                 &[AlteredObject, AlteredIndexLogicalCompaction]
             }
+            AlterQuota => &[AlteredQuota],
             AlterRole => &[AlteredRole],
             AlterSystemSet | AlterSystemReset | AlterSystemResetAll => {
                 &[AlteredSystemConfiguration]
@@ -617,9 +699,12 @@ impl ExecuteResponse {
             CreateMaterializedView => &[CreatedMaterializedView],
             CreateIndex => &[CreatedIndex],
             CreateType => &[CreatedType],
+            CreateFunction => &[CreatedFunction],
+            CreateContinualTask => &[CreatedContinualTask],
             PlanKind::Deallocate => &[ExecuteResponseKind::Deallocate],
             Declare => &[DeclaredCursor],
             DiscardTemp => &[DiscardedTemp],
+            DiscardPlans => &[DiscardedPlans],
             DiscardAll => &[DiscardedAll],
             DropObjects => &[DroppedObject],
             DropOwned => &[DroppedOwned],
@@ -13,7 +13,7 @@ use mz_sql::session::user::SYSTEM_USER;
 use tracing::{error, info};
 
 use crate::config::SynchronizedParameters;
-use crate::session::SessionConfig;
+use crate::session::{AuthenticationMethod, SessionConfig};
 use crate::{AdapterError, Client, SessionClient};
 
 /// A backend client for pushing and pulling [SynchronizedParameters].
@@ -31,6 +31,10 @@ impl SystemParameterBackend {
             conn_id,
             user: SYSTEM_USER.name.clone(),
             external_metadata_rx: None,
+            authentication_method: AuthenticationMethod::Trust,
+            trace_context: None,
+            client_ip: None,
+            http_request_id: None,
         });
         let session_client = client.startup(session).await?;
         Ok(Self { session_client })
@@ -69,7 +69,7 @@
 use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::fmt;
-use std::net::Ipv4Addr;
+use std::net::{IpAddr, Ipv4Addr};
 use std::num::NonZeroI64;
 use std::ops::Neg;
 use std::str::FromStr;
@@ -102,15 +102,16 @@ use mz_orchestrator::ServiceProcessMetrics;
 use mz_ore::instrument;
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::{EpochMillis, NowFn};
-use mz_ore::task::{spawn, JoinHandle};
+use mz_ore::task::{spawn, AbortOnDropHandle, JoinHandle};
 use mz_ore::thread::JoinHandleExt;
 use mz_ore::tracing::{OpenTelemetryContext, TracingHandle};
 use mz_ore::{soft_assert_or_log, soft_panic_or_log, stack};
-use mz_persist_client::usage::{ShardsUsageReferenced, StorageUsageClient};
+use mz_persist_client::usage::{ShardHealth, ShardsUsageReferenced, StorageUsageClient};
+use mz_persist_client::ShardId;
 use mz_pgcopy::CopyFormatParams;
 use mz_repr::explain::{ExplainConfig, ExplainFormat};
 use mz_repr::role_id::RoleId;
-use mz_repr::{GlobalId, RelationDesc, Timestamp};
+use mz_repr::{GlobalId, RelationDesc, Row, Timestamp};
 use mz_secrets::cache::CachingSecretsReader;
 use mz_secrets::{SecretsController, SecretsReader};
 use mz_sql::ast::{CreateSubsourceStatement, Raw, Statement};
@@ -126,7 +127,7 @@ use mz_storage_client::controller::{CollectionDescription, DataSource, DataSourc
 use mz_storage_types::connections::inline::{IntoInlineConnection, ReferencedConnection};
 use mz_storage_types::connections::Connection as StorageConnection;
 use mz_storage_types::connections::ConnectionContext;
-use mz_storage_types::controller::PersistTxnTablesImpl;
+use mz_storage_types::controller::{PersistTxnTablesImpl, StorageError};
 use mz_storage_types::sources::Timeline;
 use mz_timestamp_oracle::WriteTimestamp;
 use mz_transform::dataflow::DataflowMetainfo;
@@ -135,19 +136,27 @@ use timely::progress::{Antichain, Timestamp as _};
 use timely::PartialOrder;
 use tokio::runtime::Handle as TokioHandle;
 use tokio::select;
-use tokio::sync::{mpsc, oneshot, OwnedMutexGuard};
+use tokio::sync::{mpsc, oneshot, watch, OwnedMutexGuard};
 use tracing::{debug, info, info_span, span, warn, Instrument, Level, Span};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 use uuid::Uuid;
 
-use crate::active_compute_sink::ActiveComputeSink;
+use crate::active_compute_sink::{ActiveComputeSink, SharedSubscribe};
 use crate::catalog::{BuiltinMigrationMetadata, BuiltinTableUpdate, Catalog};
 use crate::client::{Client, Handle};
 use crate::command::{Command, ExecuteResponse};
 use crate::config::{SynchronizedParameters, SystemParameterFrontend, SystemParameterSyncConfig};
 use crate::coord::appends::{Deferred, GroupCommitPermit, PendingWriteTxn};
+use crate::coord::cluster_moves::PendingClusterMove;
+use crate::coord::cluster_sizing::ClusterSizing;
+use crate::coord::drain::{Drain, SessionDrain};
 use crate::coord::id_bundle::CollectionIdBundle;
-use crate::coord::peek::PendingPeek;
+use crate::coord::index_usage::IndexUsage;
+use crate::coord::mixed_cluster_workload::MixedClusterWorkloadNotice;
+use crate::coord::peek::{PeekUsage, PendingPeek};
+use crate::coord::plan_changes::PlanChangeDetector;
+use crate::coord::query_queue::QueryQueue;
+use crate::coord::slow_messages::SlowMessage;
 use crate::coord::timeline::{TimelineContext, TimelineState};
 use crate::coord::timestamp_selection::{TimestampContext, TimestampDetermination};
 use crate::error::AdapterError;
@@ -157,10 +166,10 @@ use crate::optimize::dataflows::{
     dataflow_import_id_bundle, ComputeInstanceSnapshot, DataflowBuilder,
 };
 use crate::optimize::{self, Optimize, OptimizerConfig};
-use crate::session::{EndTransactionAction, Session};
+use crate::session::{AuthenticationMethod, EndTransactionAction, Session};
 use crate::statement_logging::StatementEndedExecutionReason;
 use crate::util::{ClientTransmitter, CompletedClientTransmitter, ResultExt};
-use crate::webhook::{WebhookAppenderInvalidator, WebhookConcurrencyLimiter};
+use crate::webhook::{WebhookAppenderInvalidator, WebhookConcurrencyLimiter, WebhookDeduplicator};
 use crate::{flags, AdapterNotice, TimestampProvider};
 use mz_catalog::builtin::BUILTINS;
 use mz_catalog::durable::OpenableDurableCatalogState;
@@ -171,6 +180,7 @@ use mz_timestamp_oracle::postgres_oracle::{
 
 use self::statement_logging::{StatementLogging, StatementLoggingId};
 
+pub(crate) mod drain;
 pub(crate) mod id_bundle;
 pub(crate) mod in_memory_oracle;
 pub(crate) mod peek;
@@ -179,15 +189,25 @@ pub(crate) mod timeline;
 pub(crate) mod timestamp_selection;
 
 mod appends;
+mod cluster_moves;
+mod cluster_sizing;
 mod command_handler;
 pub mod consistency;
 mod ddl;
+mod index_usage;
 mod indexes;
 mod introspection;
 mod message_handler;
+mod mixed_cluster_workload;
+mod object_status;
+mod plan_changes;
 mod privatelink_status;
+mod query_queue;
+mod read_hold_introspection;
 mod read_policy;
 mod sequencer;
+mod shard_health;
+mod slow_messages;
 mod sql;
 
 #[derive(Debug)]
@@ -197,6 +217,8 @@ pub enum Message<T = mz_repr::Timestamp> {
     PurifiedStatementReady(PurifiedStatementReady),
     CreateConnectionValidationReady(CreateConnectionValidationReady),
     AlterConnectionValidationReady(AlterConnectionValidationReady),
+    /// The progress of a sink was reset by `ALTER SINK ... RESET OFFSET`.
+    AlterSinkResetOffsetReady(AlterSinkResetOffsetReady),
     WriteLockGrant(tokio::sync::OwnedMutexGuard<()>),
     /// Initiates a group commit.
     GroupCommitInitiate(Span, Option<GroupCommitPermit>),
@@ -216,12 +238,23 @@ pub enum Message<T = mz_repr::Timestamp> {
     CancelPendingPeeks {
         conn_id: ConnectionId,
     },
+    /// The subscribe identified by `sink_id` exceeded its `statement_timeout`.
+    SubscribeStatementTimeout {
+        sink_id: GlobalId,
+    },
     LinearizeReads,
     StorageUsageFetch,
     StorageUsageUpdate(ShardsUsageReferenced),
+    ShardHealthFetch,
+    ShardHealthUpdate(BTreeMap<ShardId, ShardHealth>),
+    IndexUsageCollect,
+    MixedClusterWorkloadCheck,
+    ClusterSizingCheck,
+    ReadHoldsCollect,
+    ObjectStatusCheck,
     RealTimeRecencyTimestamp {
         conn_id: ConnectionId,
-        real_time_recency_ts: Timestamp,
+        real_time_recency_ts: Result<Timestamp, StorageError>,
         validity: PlanValidity,
     },
 
@@ -265,6 +298,30 @@ pub enum Message<T = mz_repr::Timestamp> {
     },
     DrainStatementLog,
     PrivateLinkVpcEndpointEvents(Vec<VpcEndpointEvent>),
+    /// Checks whether the graceful reconfiguration of a cluster can be completed.
+    ClusterReconfigurationCheck {
+        cluster_id: ClusterId,
+        /// The time after which the reconfiguration is completed even if the new replicas are
+        /// not yet hydrated.
+        deadline: Instant,
+    },
+    /// Turns the replicas of scheduled clusters on or off.
+    ClusterScheduleCheck,
+    /// Checks whether the move of the identified object to another cluster can be completed.
+    ClusterMoveCheck {
+        id: GlobalId,
+    },
+    /// Admits queued peeks to a cluster that may have capacity for them.
+    AdmitQueuedPeeks {
+        cluster_id: ClusterId,
+    },
+    /// Cancels a queued peek if it is still waiting for admission.
+    QueryQueueTimeout {
+        cluster_id: ClusterId,
+        id: Uuid,
+    },
+    /// Cancels the statements of the remaining sessions if the deadline of a drain passed.
+    DrainDeadline,
 }
 
 impl Message {
@@ -284,6 +341,10 @@ impl Message {
                 Command::Terminate { .. } => "command-terminate",
                 Command::RetireExecute { .. } => "command-retire_execute",
                 Command::CheckConsistency { .. } => "command-check_consistency",
+                Command::Drain { .. } => "command-drain",
+                Command::GetDrainStatus { .. } => "command-get_drain_status",
+                Command::GetSqlJobs { .. } => "command-get_sql_jobs",
+                Command::SetSqlJobs { .. } => "command-set_sql_jobs",
             },
             Message::ControllerReady => "controller_ready",
             Message::PurifiedStatementReady(_) => "purified_statement_ready",
@@ -294,9 +355,17 @@ impl Message {
             Message::AdvanceTimelines => "advance_timelines",
             Message::ClusterEvent(_) => "cluster_event",
             Message::CancelPendingPeeks { .. } => "cancel_pending_peeks",
+            Message::SubscribeStatementTimeout { .. } => "subscribe_statement_timeout",
             Message::LinearizeReads => "linearize_reads",
             Message::StorageUsageFetch => "storage_usage_fetch",
             Message::StorageUsageUpdate(_) => "storage_usage_update",
+            Message::ShardHealthFetch => "shard_health_fetch",
+            Message::ShardHealthUpdate(_) => "shard_health_update",
+            Message::IndexUsageCollect => "index_usage_collect",
+            Message::MixedClusterWorkloadCheck => "mixed_cluster_workload_check",
+            Message::ClusterSizingCheck => "cluster_sizing_check",
+            Message::ReadHoldsCollect => "read_holds_collect",
+            Message::ObjectStatusCheck => "object_status_check",
             Message::RealTimeRecencyTimestamp { .. } => "real_time_recency_timestamp",
             Message::RetireExecute { .. } => "retire_execute",
             Message::ExecuteSingleStatementTransaction { .. } => {
@@ -311,7 +380,14 @@ impl Message {
             Message::SubscribeStageReady { .. } => "subscribe_stage_ready",
             Message::DrainStatementLog => "drain_statement_log",
             Message::AlterConnectionValidationReady(..) => "alter_connection_validation_ready",
+            Message::AlterSinkResetOffsetReady(..) => "alter_sink_reset_offset_ready",
             Message::PrivateLinkVpcEndpointEvents(_) => "private_link_vpc_endpoint_events",
+            Message::ClusterReconfigurationCheck { .. } => "cluster_reconfiguration_check",
+            Message::ClusterScheduleCheck => "cluster_schedule_check",
+            Message::ClusterMoveCheck { .. } => "cluster_move_check",
+            Message::AdmitQueuedPeeks { .. } => "admit_queued_peeks",
+            Message::QueryQueueTimeout { .. } => "query_queue_timeout",
+            Message::DrainDeadline => "drain_deadline",
         }
     }
 }
@@ -347,6 +423,17 @@ pub struct ValidationReady<T> {
 pub type CreateConnectionValidationReady = ValidationReady<CreateConnectionPlan>;
 pub type AlterConnectionValidationReady = ValidationReady<Connection>;
 
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct AlterSinkResetOffsetReady {
+    #[derivative(Debug = "ignore")]
+    pub ctx: ExecuteContext,
+    pub result: Result<(), AdapterError>,
+    pub sink_id: GlobalId,
+    pub plan_validity: PlanValidity,
+    pub otel_ctx: OpenTelemetryContext,
+}
+
 #[derive(Debug)]
 pub enum RealTimeRecencyContext {
     ExplainTimestamp {
@@ -869,6 +956,46 @@ pub struct ReplicaMetadata {
     pub metrics: Option<Vec<ServiceProcessMetrics>>,
 }
 
+/// The CPU and memory that a compute replica used since the last usage collection.
+#[derive(Clone, Debug)]
+pub struct ReplicaUsage {
+    /// The cluster of the replica.
+    pub cluster_id: ClusterId,
+    /// The owner of the cluster when the replica last reported metrics.
+    pub owner_id: RoleId,
+    /// When the replica last reported metrics, and its CPU, in nano cores, and memory, in
+    /// bytes, at that time. `None` if the replica has not reported metrics yet or was dropped.
+    pub last_sample: Option<(Instant, u64, u64)>,
+    /// The CPU used since the last usage collection, in nano core milliseconds.
+    pub cpu_nano_core_ms: u128,
+    /// The memory used since the last usage collection, in byte milliseconds.
+    pub memory_byte_ms: u128,
+}
+
+impl ReplicaUsage {
+    fn new(cluster_id: ClusterId, owner_id: RoleId) -> ReplicaUsage {
+        ReplicaUsage {
+            cluster_id,
+            owner_id,
+            last_sample: None,
+            cpu_nano_core_ms: 0,
+            memory_byte_ms: 0,
+        }
+    }
+
+    /// Accumulates the CPU and memory of the last sample up to `now`, and then replaces the
+    /// last sample with `sample`.
+    fn advance(&mut self, now: Instant, sample: Option<(u64, u64)>) {
+        if let Some((at, cpu_nano_cores, memory_bytes)) = self.last_sample {
+            let ms = now.saturating_duration_since(at).as_millis();
+            self.cpu_nano_core_ms += u128::from(cpu_nano_cores) * ms;
+            self.memory_byte_ms += u128::from(memory_bytes) * ms;
+        }
+        self.last_sample =
+            sample.map(|(cpu_nano_cores, memory_bytes)| (now, cpu_nano_cores, memory_bytes));
+    }
+}
+
 /// Metadata about an active connection.
 #[derive(Debug)]
 pub struct ConnMeta {
@@ -895,6 +1022,15 @@ pub struct ConnMeta {
     /// WARNING: This role reference is not updated when the role is dropped.
     /// Consumers should not assume that this role exist.
     authenticated_role: RoleId,
+
+    /// How the session's user was authenticated.
+    authentication_method: AuthenticationMethod,
+
+    /// The IP address of the client, if known.
+    client_ip: Option<IpAddr>,
+
+    /// The ID of the HTTP request that started the session, if any.
+    http_request_id: Option<String>,
 }
 
 impl ConnMeta {
@@ -910,6 +1046,18 @@ impl ConnMeta {
         &self.application_name
     }
 
+    pub fn authentication_method(&self) -> &AuthenticationMethod {
+        &self.authentication_method
+    }
+
+    pub fn client_ip(&self) -> Option<IpAddr> {
+        self.client_ip
+    }
+
+    pub fn http_request_id(&self) -> Option<&str> {
+        self.http_request_id.as_deref()
+    }
+
     pub fn authenticated_role_id(&self) -> &RoleId {
         &self.authenticated_role
     }
@@ -999,6 +1147,21 @@ impl PendingReadTxn {
     }
 }
 
+/// The state of a running continual task.
+#[derive(Debug)]
+pub(crate) struct ActiveContinualTask {
+    /// The ID of the table to which the task appends its output.
+    target_id: GlobalId,
+    /// The frontier up to which the output of the task has been appended to
+    /// its target table. After a restart the task resumes from this frontier.
+    resume_frontier: Timestamp,
+    /// The error that stalled the task, if any.
+    error: Option<String>,
+    /// Read holds on the inputs of the task that allow it to resume from
+    /// `resume_frontier`.
+    read_holds: read_policy::ReadHolds<Timestamp>,
+}
+
 #[derive(Debug)]
 /// A pending read transaction waiting to be linearized.
 enum PendingRead {
@@ -1262,6 +1425,10 @@ pub struct Coordinator {
     /// A map from connection ID to metadata about that connection for all
     /// active connections.
     active_conns: BTreeMap<ConnectionId, ConnMeta>,
+    /// The state of draining the sessions, if a drain was requested.
+    drain: Option<Drain>,
+    /// Notifies sessions of a drain.
+    drain_tx: watch::Sender<SessionDrain>,
 
     /// For each identifier in STORAGE, its read policy and any read holds on time.
     ///
@@ -1297,6 +1464,18 @@ pub struct Coordinator {
     pending_peeks: BTreeMap<Uuid, PendingPeek>,
     /// A map from client connection ids to a set of all pending peeks for that client.
     client_pending_peeks: BTreeMap<ConnectionId, BTreeMap<Uuid, ClusterId>>,
+    /// The peeks that each role ran on each cluster since the last usage
+    /// collection.
+    peek_usage: BTreeMap<(ClusterId, RoleId), PeekUsage>,
+    /// The CPU and memory that each compute replica used since the last usage collection.
+    replica_usage: BTreeMap<ReplicaId, ReplicaUsage>,
+    /// When compute usage was last collected.
+    compute_usage_collected_at: Instant,
+    /// A map from clusters with a `MAX CONCURRENT QUERIES` limit to the peeks waiting for
+    /// admission to them.
+    query_queues: BTreeMap<ClusterId, QueryQueue>,
+    /// A map from objects being moved to another cluster to the state of their moves.
+    pending_cluster_moves: BTreeMap<GlobalId, PendingClusterMove>,
 
     /// A map from client connection ids to a pending real time recency timestamps.
     pending_real_time_recency_timestamp: BTreeMap<ConnectionId, RealTimeRecencyContext>,
@@ -1306,8 +1485,15 @@ pub struct Coordinator {
 
     /// A map from the compute sink ID to it's state description.
     active_compute_sinks: BTreeMap<GlobalId, ActiveComputeSink>,
+    /// A map from the sink ID of a shared subscribe dataflow to its state
+    /// description.
+    shared_subscribes: BTreeMap<GlobalId, SharedSubscribe>,
+    /// A map from the IDs of running continual tasks to their state.
+    continual_tasks: BTreeMap<GlobalId, ActiveContinualTask>,
     /// A map from active webhooks to their invalidation handle.
     active_webhooks: BTreeMap<GlobalId, WebhookAppenderInvalidator>,
+    /// A map from webhooks that deduplicate requests to the keys of their recent requests.
+    webhook_deduplicators: BTreeMap<GlobalId, WebhookDeduplicator>,
 
     /// Serializes accesses to write critical sections.
     write_lock: Arc<tokio::sync::Mutex<()>>,
@@ -1347,6 +1533,40 @@ pub struct Coordinator {
     storage_usage_client: StorageUsageClient,
     /// The interval at which to collect storage usage information.
     storage_usage_collection_interval: Duration,
+    /// The rows most recently written to `mz_persist_shard_health`.
+    shard_health_rows: Vec<Row>,
+    /// The pending timer for the next collection of shard health, if any. Replacing it cancels
+    /// the previously scheduled collection.
+    shard_health_timer: Option<AbortOnDropHandle<()>>,
+    /// How each index has been used since the coordinator started tracking it.
+    index_usage: BTreeMap<GlobalId, IndexUsage>,
+    /// The rows most recently written to `mz_index_usage`.
+    index_usage_rows: Vec<Row>,
+    /// The pending timer for the next collection of index usage, if any. Replacing it cancels
+    /// the previously scheduled collection.
+    index_usage_timer: Option<AbortOnDropHandle<()>>,
+    /// The `Mixed cluster workload` notices currently raised, by cluster.
+    mixed_cluster_workload_notices: BTreeMap<ClusterId, MixedClusterWorkloadNotice>,
+    /// The pending timer for the next check for mixed cluster workloads, if any. Replacing it
+    /// cancels the previously scheduled check.
+    mixed_cluster_workload_timer: Option<AbortOnDropHandle<()>>,
+    /// The utilization samples and `Cluster size recommendation` notices, by cluster.
+    cluster_sizing: BTreeMap<ClusterId, ClusterSizing>,
+    /// The pending timer for the next cluster sizing check, if any. Replacing it cancels the
+    /// previously scheduled check.
+    cluster_sizing_timer: Option<AbortOnDropHandle<()>>,
+    /// The rows written to `mz_plan_changes` for the objects whose plans changed on boot.
+    plan_change_rows: BTreeMap<GlobalId, Row>,
+    /// The rows most recently written to `mz_read_holds`.
+    read_holds_rows: Vec<Row>,
+    /// The pending timer for the next collection of read holds, if any. Replacing it cancels the
+    /// previously scheduled collection.
+    read_holds_timer: Option<AbortOnDropHandle<()>>,
+    /// The pending timer for the next check of the statuses of clusters and materialized views,
+    /// if any. Replacing it cancels the previously scheduled check.
+    object_status_timer: Option<AbortOnDropHandle<()>>,
+    /// The rows most recently written to `mz_coordinator_slow_messages`, oldest first.
+    slow_message_rows: VecDeque<Row>,
 
     /// Segment analytics client.
     #[derivative(Debug = "ignore")]
@@ -1559,7 +1779,13 @@ impl Coordinator {
         );
 
         debug!("coordinator init: optimizing dataflow plans");
-        self.bootstrap_dataflow_plans(&entries)?;
+        let mut plan_change_detector = self.plan_change_detector().await?;
+        self.bootstrap_dataflow_plans(&entries, plan_change_detector.as_mut())?;
+        if let Some(detector) = plan_change_detector {
+            debug!("coordinator init: recording plan changes");
+            self.finish_plan_change_detection(detector, &mut builtin_table_updates)
+                .await?;
+        }
 
         // Discover what indexes MVs depend on. Needed for as-of selection below.
         // This step relies on the dataflow plans created by `bootstrap_dataflow_plans`.
@@ -1571,6 +1797,7 @@ impl Coordinator {
 
         debug!("coordinator init: installing existing objects in catalog");
         let mut privatelink_connections = BTreeMap::new();
+        let mut continual_task_frontiers = self.bootstrap_continual_task_frontiers().await;
 
         for entry in &entries {
             debug!(
@@ -1718,6 +1945,38 @@ impl Coordinator {
 
                     self.ship_dataflow(df_desc, mview.cluster_id).await;
                 }
+                CatalogItem::ContinualTask(ct) => {
+                    // Continual tasks resume from the frontier up to which they appended their
+                    // output before the restart, so that the changes that happened while the
+                    // task was down are still inserted into the target table.
+                    let resume_frontier = continual_task_frontiers.remove(&entry.id());
+                    let debug_name = self
+                        .catalog()
+                        .resolve_full_name(entry.name(), None)
+                        .to_string();
+                    match self
+                        .optimize_continual_task(entry.id(), ct, debug_name, resume_frontier)
+                        .await
+                    {
+                        Ok(df_desc) => {
+                            let status_update = self
+                                .ship_continual_task(
+                                    entry.id(),
+                                    ct.target_id,
+                                    ct.cluster_id,
+                                    df_desc,
+                                )
+                                .await;
+                            builtin_table_updates.push(status_update);
+                        }
+                        Err(e) => {
+                            tracing::error!(
+                                id = %entry.id(),
+                                "unable to start continual task: {e}"
+                            );
+                        }
+                    }
+                }
                 CatalogItem::Sink(sink) => {
                     let id = entry.id();
                     self.create_storage_export(id, sink)
@@ -1780,6 +2039,8 @@ impl Coordinator {
 
         // Expose mapping from T-shirt sizes to actual sizes
         builtin_table_updates.extend(self.catalog().state().pack_all_replica_size_updates());
+        // Expose the kinds of notices
+        builtin_table_updates.extend(self.catalog().state().pack_notice_type_updates());
 
         // Advance all tables to the current timestamp
         debug!("coordinator init: advancing all tables to current timestamp");
@@ -2000,10 +2261,14 @@ impl Coordinator {
     ///
     /// This method does not perform timestamp selection for the dataflows, nor does it create them
     /// in the compute controller. Both of these steps happen later during bootstrapping.
+    ///
+    /// If `plan_change_detector` is given, the new plans are compared against the recorded ones,
+    /// and a notice is raised for every plan that changed.
     #[instrument]
     fn bootstrap_dataflow_plans(
         &mut self,
         ordered_catalog_entries: &[CatalogEntry],
+        mut plan_change_detector: Option<&mut PlanChangeDetector>,
     ) -> Result<(), AdapterError> {
         // The optimizer expects to be able to query its `ComputeInstanceSnapshot` for
         // collections the current dataflow can depend on. But since we don't yet install anything
@@ -2047,7 +2312,10 @@ impl Coordinator {
                     // MIR ⇒ LIR lowering and LIR ⇒ LIR optimization (global)
                     let global_lir_plan = optimizer.optimize(global_mir_plan)?;
 
-                    let (physical_plan, metainfo) = global_lir_plan.unapply();
+                    let (physical_plan, mut metainfo) = global_lir_plan.unapply();
+                    if let Some(detector) = plan_change_detector.as_deref_mut() {
+                        detector.observe(id, &optimized_plan, &mut metainfo);
+                    }
                     let metainfo = {
                         // Pre-allocate a vector of transient GlobalIds for each notice.
                         let notice_ids = std::iter::repeat_with(|| self.allocate_transient_id())
@@ -2087,6 +2355,7 @@ impl Coordinator {
                         mv.desc.iter_names().cloned().collect(),
                         mv.non_null_assertions.clone(),
                         mv.refresh_schedule.clone(),
+                        mv.memory_limit,
                         debug_name,
                         optimizer_config.clone(),
                     );
@@ -2098,7 +2367,10 @@ impl Coordinator {
                     // MIR ⇒ LIR lowering and LIR ⇒ LIR optimization (global)
                     let global_lir_plan = optimizer.optimize(global_mir_plan)?;
 
-                    let (physical_plan, metainfo) = global_lir_plan.unapply();
+                    let (physical_plan, mut metainfo) = global_lir_plan.unapply();
+                    if let Some(detector) = plan_change_detector.as_deref_mut() {
+                        detector.observe(id, &optimized_plan, &mut metainfo);
+                    }
                     let metainfo = {
                         // Pre-allocate a vector of transient GlobalIds for each notice.
                         let notice_ids = std::iter::repeat_with(|| self.allocate_transient_id())
@@ -2440,6 +2712,14 @@ impl Coordinator {
             });
 
             self.schedule_storage_usage_collection().await;
+            self.schedule_shard_health_collection();
+            self.schedule_index_usage_collection();
+            self.schedule_mixed_cluster_workload_check();
+            self.schedule_cluster_sizing_check();
+            self.schedule_read_holds_collection();
+            self.schedule_object_status_check();
+            self.resume_cluster_reconfigurations();
+            self.schedule_cluster_schedule_check();
             self.spawn_privatelink_vpc_endpoints_watch_task();
             self.spawn_statement_logging_task();
             flags::tracing_config(self.catalog.system_config()).apply(&self.tracing_handle);
@@ -2479,7 +2759,8 @@ impl Coordinator {
                         // writes.
                         let user_write_spans = self.pending_writes.iter().flat_map(|x| match x {
                             PendingWriteTxn::User{span, ..} => Some(span),
-                            PendingWriteTxn::System{..} => None,
+                            PendingWriteTxn::ContinualTask{..}
+                            | PendingWriteTxn::System{..} => None,
                         });
                         let span = match user_write_spans.exactly_one() {
                             Ok(span) => span.clone(),
@@ -2553,23 +2834,29 @@ impl Coordinator {
                 // Record the last kind of message in case we get stuck. For
                 // execute commands, we additionally stash the user's SQL,
                 // statement, so we can log it in case we get stuck.
+                let stmt = match &msg {
+                    Message::Command(
+                        _,
+                        Command::Execute {
+                            portal_name,
+                            session,
+                            ..
+                        },
+                    ) => session
+                        .get_portal_unverified(portal_name)
+                        .and_then(|p| p.stmt.as_ref().map(Arc::clone)),
+                    _ => None,
+                };
                 *last_message.lock().expect("poisoned") = LastMessage {
                     kind: msg_kind,
-                    stmt: match &msg {
-                        Message::Command(
-                            _,
-                            Command::Execute {
-                                portal_name,
-                                session,
-                                ..
-                            },
-                        ) => session
-                            .get_portal_unverified(portal_name)
-                            .and_then(|p| p.stmt.as_ref().map(Arc::clone)),
-                        _ => None,
-                    },
+                    stmt: stmt.clone(),
+                };
+                let session_id = match &msg {
+                    Message::Command(_, cmd) => cmd.session().map(|s| s.conn_id().unhandled()),
+                    _ => None,
                 };
 
+                let began_at = self.now();
                 let start = Instant::now();
                 self.handle_message(span, msg).await;
                 let duration = start.elapsed();
@@ -2580,8 +2867,8 @@ impl Coordinator {
                     .observe(duration.as_secs_f64());
 
                 // If something is _really_ slow, print a trace id for debugging, if OTEL is enabled.
+                let trace_id = otel_context.is_valid().then(|| otel_context.trace_id());
                 if duration > warn_threshold {
-                    let trace_id = otel_context.is_valid().then(|| otel_context.trace_id());
                     tracing::warn!(
                         ?msg_kind,
                         ?trace_id,
@@ -2589,6 +2876,23 @@ impl Coordinator {
                         "very slow coordinator message"
                     );
                 }
+
+                // Read the report threshold on every message, so that changes to it take effect
+                // immediately.
+                let report_threshold = self
+                    .catalog()
+                    .system_config()
+                    .coord_slow_message_report_threshold();
+                if duration > report_threshold {
+                    self.record_slow_message(SlowMessage {
+                        kind: msg_kind,
+                        began_at,
+                        duration,
+                        session_id,
+                        stmt,
+                        trace_id: trace_id.map(|id| id.to_string()),
+                    });
+                }
             }
             // Try and cleanup as a best effort. There may be some async tasks out there holding a
             // reference that prevents us from cleaning up.
@@ -2691,6 +2995,8 @@ impl Coordinator {
             dataflow.export_ids().collect()
         };
 
+        self.record_index_imports(dataflow.index_imports.keys().copied());
+
         self.controller
             .active_compute()
             .create_dataflow(instance, dataflow)
@@ -2802,6 +3108,7 @@ pub fn serve(
         info!("coordinator init: beginning");
 
         let (cmd_tx, cmd_rx) = mpsc::unbounded_channel();
+        let (drain_tx, drain_rx) = watch::channel(SessionDrain::None);
         let (internal_cmd_tx, internal_cmd_rx) = mpsc::unbounded_channel();
         let (group_commit_tx, group_commit_rx) = appends::notifier();
         let (strict_serializable_reads_tx, strict_serializable_reads_rx) =
@@ -2965,15 +3272,25 @@ pub fn serve(
                     global_timelines: timestamp_oracles,
                     transient_id_counter: 1,
                     active_conns: BTreeMap::new(),
+                    drain: None,
+                    drain_tx,
                     storage_read_capabilities: Default::default(),
                     compute_read_capabilities: Default::default(),
                     txn_read_holds: Default::default(),
                     pending_peeks: BTreeMap::new(),
                     client_pending_peeks: BTreeMap::new(),
+                    peek_usage: BTreeMap::new(),
+                    replica_usage: BTreeMap::new(),
+                    compute_usage_collected_at: Instant::now(),
+                    query_queues: BTreeMap::new(),
+                    pending_cluster_moves: BTreeMap::new(),
                     pending_real_time_recency_timestamp: BTreeMap::new(),
                     pending_linearize_read_txns: BTreeMap::new(),
                     active_compute_sinks: BTreeMap::new(),
+                    shared_subscribes: BTreeMap::new(),
+                    continual_tasks: BTreeMap::new(),
                     active_webhooks: BTreeMap::new(),
+                    webhook_deduplicators: BTreeMap::new(),
                     write_lock: Arc::new(tokio::sync::Mutex::new(())),
                     write_lock_wait_group: VecDeque::new(),
                     pending_writes: Vec::new(),
@@ -2984,6 +3301,20 @@ pub fn serve(
                     transient_replica_metadata: BTreeMap::new(),
                     storage_usage_client,
                     storage_usage_collection_interval,
+                    shard_health_rows: Vec::new(),
+                    shard_health_timer: None,
+                    index_usage: BTreeMap::new(),
+                    index_usage_rows: Vec::new(),
+                    index_usage_timer: None,
+                    mixed_cluster_workload_notices: BTreeMap::new(),
+                    mixed_cluster_workload_timer: None,
+                    cluster_sizing: BTreeMap::new(),
+                    cluster_sizing_timer: None,
+                    plan_change_rows: BTreeMap::new(),
+                    read_holds_rows: Vec::new(),
+                    read_holds_timer: None,
+                    object_status_timer: None,
+                    slow_message_rows: VecDeque::new(),
                     segment_client,
                     metrics,
                     tracing_handle,
@@ -3035,6 +3366,7 @@ pub fn serve(
                 let client = Client::new(
                     build_info,
                     cmd_tx.clone(),
+                    drain_rx,
                     metrics_clone,
                     now,
                     environment_id,
@@ -3186,3 +3518,35 @@ pub async fn load_remote_system_parameters(
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_replica_usage() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut usage = ReplicaUsage::new(ClusterId::User(1), RoleId::User(1));
+
+        // Nothing is accumulated before the first sample.
+        usage.advance(at(10), Some((2_000, 100)));
+        assert_eq!((usage.cpu_nano_core_ms, usage.memory_byte_ms), (0, 0));
+
+        // The last sample is assumed to hold until the next one.
+        usage.advance(at(20), Some((4_000, 300)));
+        assert_eq!(
+            (usage.cpu_nano_core_ms, usage.memory_byte_ms),
+            (20_000_000, 1_000_000)
+        );
+
+        // Nothing is accumulated after the replica is dropped.
+        usage.advance(at(25), None);
+        usage.advance(at(60), None);
+        assert_eq!(
+            (usage.cpu_nano_core_ms, usage.memory_byte_ms),
+            (40_000_000, 2_500_000)
+        );
+        assert_eq!(usage.last_sample, None);
+    }
+}
@@ -74,6 +74,18 @@ pub(crate) enum PendingWriteTxn {
         /// Inner transaction.
         pending_txn: PendingTxn,
     },
+    /// Write the changes produced by a continual task to its target table.
+    ContinualTask {
+        /// The ID of the task.
+        id: GlobalId,
+        /// The write operation of the task.
+        write: WriteOp,
+        /// The frontier up to which the task has produced its changes once
+        /// the write is committed.
+        resume_frontier: Timestamp,
+        /// The error that stalled the task, if any.
+        error: Option<String>,
+    },
     /// Write to a system table.
     System {
         updates: Vec<BuiltinTableUpdate>,
@@ -87,7 +99,19 @@ impl PendingWriteTxn {
             PendingWriteTxn::User {
                 write_lock_guard, ..
             } => std::mem::take(write_lock_guard),
-            PendingWriteTxn::System { .. } => None,
+            PendingWriteTxn::ContinualTask { .. } | PendingWriteTxn::System { .. } => None,
+        }
+    }
+
+    /// Reports whether the transaction may only be committed while holding the write lock.
+    ///
+    /// Continual tasks append to tables that users can also write to, so their writes must not be
+    /// committed while a user transaction, e.g. an `UPDATE`, holds the write lock between reading
+    /// and writing a table.
+    fn requires_write_lock(&self) -> bool {
+        match self {
+            PendingWriteTxn::User { .. } | PendingWriteTxn::ContinualTask { .. } => true,
+            PendingWriteTxn::System { .. } => false,
         }
     }
 
@@ -225,11 +249,11 @@ impl Coordinator {
         } else if self
             .pending_writes
             .iter()
-            .all(|write| matches!(write, PendingWriteTxn::System { .. }))
+            .all(|write| !write.requires_write_lock())
             || self.pending_writes.is_empty()
         {
-            // If none of the pending transactions are for user tables, then we don't need the
-            // write lock.
+            // If none of the pending transactions are for user tables or continual tasks, then we
+            // don't need the write lock.
             (None, self.pending_writes.drain(..).collect())
         } else if let Some(guard) = self
             .pending_writes
@@ -256,7 +280,7 @@ impl Coordinator {
             // Without the write lock we can only apply writes to system tables.
             let pending_writes = self
                 .pending_writes
-                .drain_filter_swapping(|w| matches!(w, PendingWriteTxn::System { .. }))
+                .drain_filter_swapping(|w| !w.requires_write_lock())
                 .collect();
             (None, pending_writes)
         };
@@ -320,6 +344,29 @@ impl Coordinator {
 
                     responses.push(CompletedClientTransmitter::new(ctx, response, action));
                 }
+                PendingWriteTxn::ContinualTask {
+                    id,
+                    write:
+                        WriteOp {
+                            id: target_id,
+                            rows,
+                        },
+                    resume_frontier,
+                    error,
+                } => {
+                    // The task may have been dropped while the write was waiting, in which case
+                    // we drop the write. Otherwise its status is committed along with its
+                    // output, so that after a restart the task resumes where the output ends.
+                    if let Some(updates) = self.advance_continual_task(id, resume_frontier, error) {
+                        appends.entry(target_id).or_default().extend(rows);
+                        for update in updates {
+                            appends
+                                .entry(update.id)
+                                .or_default()
+                                .push((update.row, update.diff));
+                        }
+                    }
+                }
                 PendingWriteTxn::System { updates, source } => {
                     for update in updates {
                         appends
@@ -495,8 +542,12 @@ impl Coordinator {
         &self,
         session: &mut Session,
     ) -> Result<(), tokio::sync::TryLockError> {
+        let timeout = self
+            .catalog()
+            .system_config()
+            .write_transaction_read_lock_timeout();
         Arc::clone(&self.write_lock).try_lock_owned().map(|p| {
-            session.grant_write_lock(p);
+            session.grant_write_lock(p, timeout);
         })
     }
 }
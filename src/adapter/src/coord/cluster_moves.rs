@@ -0,0 +1,325 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Moves of materialized views and indexes between clusters.
+//!
+//! `ALTER ... SET CLUSTER` installs the dataflow of the altered object on the target cluster,
+//! next to the dataflow on its current cluster, and waits for the new dataflow to hydrate. Only
+//! then is the catalog item moved to the target cluster and the dataflow on its previous cluster
+//! dropped, so the object stays readable throughout the move. Until the move completes, the new
+//! dataflow is invisible to the optimizer, which only considers the indexes that the catalog
+//! places on a cluster.
+//!
+//! Like graceful cluster reconfigurations, moves whose dataflows do not hydrate within
+//! `graceful_cluster_reconfiguration_timeout` are completed anyway.
+
+use std::collections::BTreeSet;
+use std::time::{Duration, Instant};
+
+use mz_adapter_types::connection::ConnectionId;
+use mz_compute_types::dataflows::DataflowDescription;
+use mz_compute_types::plan::Plan;
+use mz_controller_types::ClusterId;
+use mz_expr::OptimizedMirRelationExpr;
+use mz_ore::task;
+use mz_repr::GlobalId;
+use mz_sql::catalog::ObjectType;
+use mz_transform::dataflow::DataflowMetainfo;
+
+use crate::command::ExecuteResponse;
+use crate::coord::{Coordinator, Message};
+use crate::util::ResultExt;
+use crate::{catalog, AdapterError, ExecuteContext};
+
+/// How often to check whether the dataflows of pending moves are hydrated.
+const CLUSTER_MOVE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A move of a materialized view or index to another cluster that waits for the object's
+/// dataflow to hydrate on the target cluster.
+#[derive(Debug)]
+pub(crate) struct PendingClusterMove {
+    /// The context of the `ALTER ... SET CLUSTER` statement, retired once the move completes.
+    pub(super) ctx: ExecuteContext,
+    /// The type of the moved object.
+    pub(super) object_type: ObjectType,
+    /// The cluster that the object is moved from.
+    pub(super) from_cluster: ClusterId,
+    /// The cluster that the object is moved to.
+    pub(super) to_cluster: ClusterId,
+    /// The optimized plan of the dataflow on the target cluster.
+    pub(super) optimized_plan: DataflowDescription<OptimizedMirRelationExpr>,
+    /// The physical plan of the dataflow on the target cluster.
+    pub(super) physical_plan: DataflowDescription<Plan>,
+    /// The metainfo of the dataflow on the target cluster, with notices yet to be rendered.
+    pub(super) metainfo: DataflowMetainfo,
+    /// The time after which the move completes even if the dataflow is not yet hydrated.
+    pub(super) deadline: Instant,
+}
+
+impl Coordinator {
+    /// Installs `dataflow`, the dataflow of the object identified by `id`, on the target cluster
+    /// of `pending`, and schedules the completion of the move.
+    pub(crate) fn begin_cluster_move(
+        &mut self,
+        id: GlobalId,
+        dataflow: DataflowDescription<Plan>,
+        pending: PendingClusterMove,
+    ) {
+        let to_cluster = pending.to_cluster;
+        self.record_index_imports(dataflow.index_imports.keys().copied());
+        self.controller
+            .active_compute()
+            .create_dataflow(to_cluster, dataflow)
+            .unwrap_or_terminate("dataflow creation cannot fail");
+        // Both dataflows share the read capability of the object, which already accounts for
+        // the read holds on it.
+        self.set_moved_read_policy(id, to_cluster);
+
+        self.pending_cluster_moves.insert(id, pending);
+        self.schedule_cluster_move_check(id);
+    }
+
+    /// Schedules a [`Message::ClusterMoveCheck`] for the object identified by `id`.
+    fn schedule_cluster_move_check(&self, id: GlobalId) {
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "cluster_move_check", async move {
+            tokio::time::sleep(CLUSTER_MOVE_CHECK_INTERVAL).await;
+            if internal_cmd_tx
+                .send(Message::ClusterMoveCheck { id })
+                .is_err()
+            {
+                // If sending fails, the main thread has shutdown.
+            }
+        });
+    }
+
+    /// Completes the pending move of the object identified by `id`, if possible.
+    ///
+    /// A move is completed once the object's dataflow is hydrated on the target cluster, once
+    /// `deadline` has passed, or right away if the target cluster has no replicas that could
+    /// hydrate it. Otherwise, another check is scheduled.
+    pub(crate) async fn check_cluster_move(&mut self, id: GlobalId) {
+        // The move may have been aborted in the meantime.
+        let Some(pending) = self.pending_cluster_moves.get(&id) else {
+            return;
+        };
+        let to_cluster = pending.to_cluster;
+
+        let Some(cluster) = self.catalog().try_get_cluster(to_cluster) else {
+            self.abort_cluster_moves_to(to_cluster);
+            return;
+        };
+        let has_replicas = cluster.replicas().next().is_some();
+        let hydrated = self
+            .controller
+            .compute
+            .collection_hydrated(to_cluster, id)
+            .unwrap_or(false);
+        if !hydrated && has_replicas {
+            if Instant::now() < pending.deadline {
+                self.schedule_cluster_move_check(id);
+                return;
+            }
+            tracing::warn!(
+                %id,
+                %to_cluster,
+                "dataflow not hydrated before the reconfiguration timeout; completing move anyway"
+            );
+        }
+
+        let pending = self
+            .pending_cluster_moves
+            .remove(&id)
+            .expect("checked above");
+        self.complete_cluster_move(id, pending).await;
+    }
+
+    /// Moves the catalog item identified by `id` to the target cluster of `pending` and drops
+    /// its dataflow on the previous cluster.
+    async fn complete_cluster_move(&mut self, id: GlobalId, pending: PendingClusterMove) {
+        let PendingClusterMove {
+            ctx,
+            object_type,
+            from_cluster,
+            to_cluster,
+            optimized_plan,
+            physical_plan,
+            metainfo,
+            deadline: _,
+        } = pending;
+
+        // Dataflows that were created on the previous cluster while the move was pending may
+        // have imported the index.
+        if let Some(dependent_id) = self.index_dependent(id, from_cluster) {
+            self.drop_moved_dataflow(id, to_cluster);
+            let name = |id| {
+                let entry = self.catalog().get_entry(&id);
+                self.catalog()
+                    .resolve_full_name(entry.name(), Some(ctx.session().conn_id()))
+                    .to_string()
+            };
+            let err = AdapterError::IndexHasDependents {
+                index: name(id),
+                dependent: name(dependent_id),
+            };
+            ctx.retire(Err(err));
+            return;
+        }
+
+        // Pre-allocate a vector of transient GlobalIds for each notice.
+        let notice_ids = std::iter::repeat_with(|| self.allocate_transient_id())
+            .take(metainfo.optimizer_notices.len())
+            .collect::<Result<Vec<_>, _>>();
+        let notice_ids = match notice_ids {
+            Ok(notice_ids) => notice_ids,
+            Err(err) => {
+                self.drop_moved_dataflow(id, to_cluster);
+                ctx.retire(Err(err));
+                return;
+            }
+        };
+
+        let mut ops = vec![catalog::Op::AlterSetCluster {
+            id,
+            cluster: to_cluster,
+        }];
+        ops.extend(self.plan_record_op(id, &optimized_plan));
+        if let Err(err) = self.catalog_transact(Some(ctx.session()), ops).await {
+            self.drop_moved_dataflow(id, to_cluster);
+            ctx.retire(Err(err));
+            return;
+        }
+
+        // Replace the plan structures of the object.
+        let dropped_notices = self
+            .catalog_mut()
+            .drop_plans_and_metainfos(&BTreeSet::from([id]));
+        let metainfo = self
+            .catalog()
+            .render_notices(metainfo, notice_ids, Some(id));
+        let catalog = self.catalog_mut();
+        catalog.set_optimized_plan(id, optimized_plan);
+        catalog.set_physical_plan(id, physical_plan);
+        catalog.set_dataflow_metainfo(id, metainfo.clone());
+        if self.catalog().state().system_config().enable_mz_notices() {
+            let mut updates = Vec::new();
+            self.catalog()
+                .state()
+                .pack_optimizer_notices(&mut updates, dropped_notices.iter(), -1);
+            self.catalog().state().pack_optimizer_notices(
+                &mut updates,
+                metainfo.optimizer_notices.iter(),
+                1,
+            );
+            self.builtin_table_update().background(updates);
+        }
+
+        // From now on, reads are served by the dataflow on the target cluster.
+        self.move_compute_read_holds(id, from_cluster, to_cluster);
+        self.set_moved_read_policy(id, to_cluster);
+        let mut compute = self.controller.active_compute();
+        if compute.instance_exists(from_cluster) {
+            compute
+                .drop_collections(from_cluster, vec![id])
+                .unwrap_or_terminate("cannot fail to drop collections");
+        }
+
+        ctx.retire(Ok(ExecuteResponse::AlteredObject(object_type)));
+    }
+
+    /// Returns the ID of an index or materialized view on the cluster identified by
+    /// `cluster_id` whose dataflow imports the index identified by `id`, if any.
+    pub(crate) fn index_dependent(&self, id: GlobalId, cluster_id: ClusterId) -> Option<GlobalId> {
+        let cluster = self.catalog().try_get_cluster(cluster_id)?;
+        cluster
+            .bound_objects
+            .iter()
+            .find(|dependent_id| {
+                self.catalog()
+                    .try_get_physical_plan(dependent_id)
+                    .map_or(false, |plan| plan.index_imports.contains_key(&id))
+            })
+            .copied()
+    }
+
+    /// Aborts the pending moves of the dropped objects identified by `ids`.
+    pub(crate) fn abort_dropped_cluster_moves(&mut self, ids: &[GlobalId]) {
+        for id in ids {
+            if let Some(pending) = self.pending_cluster_moves.remove(id) {
+                self.drop_moved_dataflow(*id, pending.to_cluster);
+                pending
+                    .ctx
+                    .retire(Err(AdapterError::ChangedPlan(format!("{id} was removed"))));
+            }
+        }
+    }
+
+    /// Aborts the pending moves to the dropped cluster identified by `cluster_id`.
+    pub(crate) fn abort_cluster_moves_to(&mut self, cluster_id: ClusterId) {
+        let ids: Vec<_> = self
+            .pending_cluster_moves
+            .iter()
+            .filter(|(_, pending)| pending.to_cluster == cluster_id)
+            .map(|(id, _)| *id)
+            .collect();
+        for id in ids {
+            let pending = self
+                .pending_cluster_moves
+                .remove(&id)
+                .expect("known to exist");
+            self.drop_moved_dataflow(id, cluster_id);
+            pending.ctx.retire(Err(AdapterError::ChangedPlan(format!(
+                "cluster {} was removed",
+                cluster_id
+            ))));
+        }
+    }
+
+    /// Cancels the pending move issued by the identified connection, if any, and returns the
+    /// context of its statement.
+    pub(crate) fn cancel_cluster_move(&mut self, conn_id: &ConnectionId) -> Option<ExecuteContext> {
+        let id = self
+            .pending_cluster_moves
+            .iter()
+            .find(|(_, pending)| pending.ctx.session().conn_id() == conn_id)
+            .map(|(id, _)| *id)?;
+        let pending = self
+            .pending_cluster_moves
+            .remove(&id)
+            .expect("known to exist");
+        self.drop_moved_dataflow(id, pending.to_cluster);
+        Some(pending.ctx)
+    }
+
+    /// Drops the dataflow of the object identified by `id` from the target cluster of its move.
+    fn drop_moved_dataflow(&mut self, id: GlobalId, to_cluster: ClusterId) {
+        let mut compute = self.controller.active_compute();
+        // The cluster could have been dropped, so verify it exists.
+        if compute.instance_exists(to_cluster) {
+            compute
+                .drop_collections(to_cluster, vec![id])
+                .unwrap_or_terminate("cannot fail to drop collections");
+        }
+    }
+
+    /// Applies the read capability of the object identified by `id` to its dataflow on the
+    /// target cluster of its move.
+    ///
+    /// Materialized views only have a read capability if aggressive read hold downgrades are
+    /// disabled.
+    fn set_moved_read_policy(&mut self, id: GlobalId, to_cluster: ClusterId) {
+        if let Some(capability) = self.compute_read_capabilities.get(&id) {
+            let policy = capability.policy();
+            self.controller
+                .active_compute()
+                .set_read_policy(to_cluster, vec![(id, policy)])
+                .unwrap_or_terminate("cannot fail to set read policy");
+        }
+    }
+}
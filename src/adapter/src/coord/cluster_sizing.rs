@@ -0,0 +1,486 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Recommendations of cluster sizes.
+//!
+//! Every `cluster_sizing_check_interval`, the coordinator samples the CPU and memory usage of the
+//! replicas of every managed user cluster, as last reported by the orchestrator. The usage of a
+//! replica includes the arrangements of all of its dataflows. Once the samples cover
+//! `cluster_sizing_window`, a [`ClusterSizeRecommendation`] notice is raised for every cluster that
+//! has been consistently far over- or under-provisioned during that window, recommending the
+//! cheapest size that fits the peak usage of the cluster. Resizing a cluster discards its
+//! samples, and with them its notice.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
+use std::time::Instant;
+
+use dec::OrderedDecimal;
+use mz_catalog::memory::objects::{Cluster, ClusterVariant};
+use mz_controller::clusters::{ReplicaAllocation, ReplicaLocation};
+use mz_controller_types::ReplicaId;
+use mz_ore::cast::CastLossy;
+use mz_ore::task;
+use mz_sql::ast::Ident;
+use mz_sql_parser::ast::display::AstDisplay;
+use mz_transform::dataflow::DataflowMetainfo;
+use mz_transform::notice::{ClusterSizeRecommendation, OptimizerNotice};
+
+use crate::coord::{Coordinator, Message, ReplicaMetadata};
+use crate::util::ResultExt;
+
+/// The utilization below which a cluster is over-provisioned.
+const OVER_PROVISIONED_UTILIZATION: f64 = 0.2;
+/// The utilization above which a cluster is under-provisioned.
+const UNDER_PROVISIONED_UTILIZATION: f64 = 0.9;
+/// The utilization that the peak usage of a cluster should have on the recommended size.
+const TARGET_UTILIZATION: f64 = 0.6;
+
+/// The sizing state of a managed cluster.
+#[derive(Debug)]
+pub(crate) struct ClusterSizing {
+    /// The size of the cluster when sampling started.
+    size: String,
+    /// The time sampling started.
+    since: Instant,
+    /// The usage samples within the sizing window, oldest first.
+    samples: VecDeque<(Instant, Usage)>,
+    /// The raised notice, if any.
+    notice: Option<ClusterSizeRecommendationNotice>,
+}
+
+/// A raised [`ClusterSizeRecommendation`] notice.
+#[derive(Debug)]
+struct ClusterSizeRecommendationNotice {
+    /// The notice, as it was before rendering.
+    raw: ClusterSizeRecommendation,
+    /// The rendered notice.
+    rendered: Arc<OptimizerNotice>,
+}
+
+/// The CPU and memory usage of a replica, summed across its processes, or the capacity of a
+/// replica size.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Usage {
+    memory_bytes: Option<u64>,
+    cpu_nano_cores: Option<u64>,
+}
+
+impl Usage {
+    /// Returns the per-resource maximum of `self` and `other`.
+    fn max(self, other: Usage) -> Usage {
+        let max = |a: Option<u64>, b: Option<u64>| a.max(b);
+        Usage {
+            memory_bytes: max(self.memory_bytes, other.memory_bytes),
+            cpu_nano_cores: max(self.cpu_nano_cores, other.cpu_nano_cores),
+        }
+    }
+
+    /// Returns the capacity of a replica of the given allocation, or `None` for the resources
+    /// that are not limited.
+    fn capacity(allocation: &ReplicaAllocation) -> Usage {
+        let scale = u64::from(allocation.scale);
+        Usage {
+            memory_bytes: allocation
+                .memory_limit
+                .map(|limit| limit.0.as_u64().saturating_mul(scale)),
+            cpu_nano_cores: allocation
+                .cpu_limit
+                .map(|limit| limit.as_nanocpus().saturating_mul(scale)),
+        }
+    }
+
+    /// Returns the memory and CPU utilization of `capacity` by `self`, for the resources that
+    /// are both used and limited.
+    fn utilization(&self, capacity: &Usage) -> (Option<f64>, Option<f64>) {
+        let utilization = |usage: Option<u64>, capacity: Option<u64>| match (usage, capacity) {
+            (Some(usage), Some(capacity)) if capacity > 0 => {
+                Some(f64::cast_lossy(usage) / f64::cast_lossy(capacity))
+            }
+            _ => None,
+        };
+        (
+            utilization(self.memory_bytes, capacity.memory_bytes),
+            utilization(self.cpu_nano_cores, capacity.cpu_nano_cores),
+        )
+    }
+
+    /// Returns whether `capacity` fits `self` at the target utilization, considering only the
+    /// resources that `limited` limits.
+    fn fits(&self, capacity: &Usage, limited: &Usage) -> bool {
+        let fits = |usage: Option<u64>, capacity: Option<u64>, limited: Option<u64>| match (
+            usage, limited,
+        ) {
+            (Some(usage), Some(_)) => capacity.map_or(false, |capacity| {
+                f64::cast_lossy(usage) <= f64::cast_lossy(capacity) * TARGET_UTILIZATION
+            }),
+            _ => true,
+        };
+        fits(
+            self.memory_bytes,
+            capacity.memory_bytes,
+            limited.memory_bytes,
+        ) && fits(
+            self.cpu_nano_cores,
+            capacity.cpu_nano_cores,
+            limited.cpu_nano_cores,
+        )
+    }
+}
+
+/// A recommended size for a cluster.
+#[derive(Debug, PartialEq)]
+struct Recommendation<'a> {
+    size: &'a String,
+    over_provisioned: bool,
+    memory_utilization: Option<f64>,
+    cpu_utilization: Option<f64>,
+}
+
+impl Coordinator {
+    /// Samples the usage of clusters, raises or retracts [`ClusterSizeRecommendation`] notices,
+    /// and schedules the next check.
+    pub(crate) fn cluster_sizing_check(&mut self) {
+        let catalog = self.owned_catalog();
+        let system_config = catalog.system_config();
+        let notices_enabled = system_config.enable_mz_notices()
+            && system_config.enable_notices_for_cluster_size_recommendation();
+        let window = system_config.cluster_sizing_window();
+        let now = Instant::now();
+
+        let mut current = BTreeMap::new();
+        if notices_enabled {
+            for cluster in catalog.user_clusters() {
+                if let ClusterVariant::Managed(managed) = &cluster.config.variant {
+                    let usage = cluster_usage(cluster, &self.transient_replica_metadata);
+                    current.insert(cluster.id, (cluster, &managed.size, usage));
+                }
+            }
+        }
+
+        // Forget the clusters that have been dropped or resized since the last check.
+        let mut retracted = Vec::new();
+        self.cluster_sizing.retain(|cluster_id, sizing| {
+            let keep = current
+                .get(cluster_id)
+                .map_or(false, |(_, size, _)| *size == &sizing.size);
+            if !keep {
+                retracted.extend(sizing.notice.take().map(|notice| notice.rendered));
+            }
+            keep
+        });
+
+        let sizes = catalog.cluster_replica_sizes();
+        let allowed_sizes = system_config.allowed_cluster_replica_sizes();
+        let candidates: Vec<_> = sizes
+            .enabled_allocations()
+            .filter(|(name, _)| allowed_sizes.is_empty() || allowed_sizes.contains(name))
+            .collect();
+        let mut raised = Vec::new();
+        for (cluster_id, (cluster, size, usage)) in current {
+            let sizing = self
+                .cluster_sizing
+                .entry(cluster_id)
+                .or_insert_with(|| ClusterSizing {
+                    size: size.clone(),
+                    since: now,
+                    samples: VecDeque::new(),
+                    notice: None,
+                });
+            if let Some(usage) = usage {
+                sizing.samples.push_back((now, usage));
+            }
+            while let Some((sampled_at, _)) = sizing.samples.front() {
+                if now.duration_since(*sampled_at) <= window {
+                    break;
+                }
+                sizing.samples.pop_front();
+            }
+
+            let recommendation = match sizes.0.get(size) {
+                Some(allocation) if now.duration_since(sizing.since) >= window => recommend_size(
+                    allocation,
+                    sizing.samples.iter().map(|(_, usage)| *usage),
+                    candidates.iter().copied(),
+                ),
+                _ => None,
+            };
+            let raw = recommendation.map(|recommendation| {
+                let percent = |utilization: Option<f64>| {
+                    utilization.map(|utilization| u64::cast_lossy((utilization * 100.0).round()))
+                };
+                ClusterSizeRecommendation {
+                    cluster: Ident::new_unchecked(cluster.name.clone()).to_ast_string(),
+                    current_size: size.clone(),
+                    recommended_size: recommendation.size.clone(),
+                    over_provisioned: recommendation.over_provisioned,
+                    memory_utilization: percent(recommendation.memory_utilization),
+                    cpu_utilization: percent(recommendation.cpu_utilization),
+                }
+            });
+
+            // Keep the raised notice as long as the recommendation stays the same, even if the
+            // observed utilization changed.
+            let unchanged = match (&sizing.notice, &raw) {
+                (Some(notice), Some(raw)) => notice.raw.same_recommendation(raw),
+                (None, None) => true,
+                _ => false,
+            };
+            if unchanged {
+                continue;
+            }
+            if let Some(notice) = sizing.notice.take() {
+                retracted.push(notice.rendered);
+            }
+            if let Some(raw) = raw {
+                raised.push((cluster_id, raw));
+            }
+        }
+
+        let raised: Vec<_> = raised
+            .into_iter()
+            .map(|(cluster_id, raw)| {
+                let rendered = self.render_cluster_size_recommendation_notice(raw.clone());
+                let sizing = self
+                    .cluster_sizing
+                    .get_mut(&cluster_id)
+                    .expect("inserted above");
+                sizing.notice = Some(ClusterSizeRecommendationNotice {
+                    raw,
+                    rendered: Arc::clone(&rendered),
+                });
+                rendered
+            })
+            .collect();
+
+        if !retracted.is_empty() || !raised.is_empty() {
+            let mut updates = Vec::new();
+            self.catalog()
+                .state()
+                .pack_optimizer_notices(&mut updates, retracted.iter(), -1);
+            self.catalog()
+                .state()
+                .pack_optimizer_notices(&mut updates, raised.iter(), 1);
+            self.builtin_table_update().background(updates);
+        }
+
+        self.schedule_cluster_sizing_check();
+    }
+
+    /// Renders a [`ClusterSizeRecommendation`] notice.
+    fn render_cluster_size_recommendation_notice(
+        &mut self,
+        notice: ClusterSizeRecommendation,
+    ) -> Arc<OptimizerNotice> {
+        let notice_id = self
+            .allocate_transient_id()
+            .unwrap_or_terminate("cannot fail to allocate a notice id");
+        let mut df_meta = DataflowMetainfo::default();
+        df_meta.push_optimizer_notice_dedup(notice);
+        self.catalog()
+            .render_notices(df_meta, vec![notice_id], None)
+            .optimizer_notices
+            .pop()
+            .expect("rendered one notice")
+    }
+
+    /// Schedules the next cluster sizing check, replacing any previously scheduled check.
+    pub(crate) fn schedule_cluster_sizing_check(&mut self) {
+        let interval = self
+            .catalog()
+            .system_config()
+            .cluster_sizing_check_interval();
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let timer = task::spawn(|| "cluster_sizing_check", async move {
+            tokio::time::sleep(interval).await;
+            // If sending fails, the main thread has shutdown.
+            let _ = internal_cmd_tx.send(Message::ClusterSizingCheck);
+        });
+        self.cluster_sizing_timer = Some(timer.abort_on_drop());
+    }
+}
+
+/// Returns the usage of the busiest replica of `cluster`, or `None` if no replica of the cluster
+/// has reported its usage yet.
+fn cluster_usage(
+    cluster: &Cluster,
+    replica_metadata: &BTreeMap<ReplicaId, Option<ReplicaMetadata>>,
+) -> Option<Usage> {
+    cluster
+        .replicas()
+        .filter(|replica| matches!(replica.config.location, ReplicaLocation::Managed(_)))
+        .filter_map(|replica| {
+            let metrics = replica_metadata
+                .get(&replica.replica_id)?
+                .as_ref()?
+                .metrics
+                .as_ref()?;
+            let sum = |values: Vec<u64>| (!values.is_empty()).then(|| values.into_iter().sum());
+            Some(Usage {
+                memory_bytes: sum(metrics.iter().filter_map(|m| m.memory_bytes).collect()),
+                cpu_nano_cores: sum(metrics.iter().filter_map(|m| m.cpu_nano_cores).collect()),
+            })
+        })
+        .reduce(Usage::max)
+}
+
+/// Recommends a size for a cluster of the `current` allocation, given its usage `samples` over
+/// the sizing window, out of the `candidates` sizes.
+///
+/// Returns `None` if the cluster was not consistently over- or under-provisioned, or if no
+/// candidate size is better suited. Only the resources that the `current` allocation limits are
+/// considered.
+fn recommend_size<'a>(
+    current: &ReplicaAllocation,
+    samples: impl Iterator<Item = Usage>,
+    candidates: impl Iterator<Item = (&'a String, &'a ReplicaAllocation)> + Clone,
+) -> Option<Recommendation<'a>> {
+    let capacity = Usage::capacity(current);
+    let mut peak: Option<Usage> = None;
+    let mut peak_utilization = f64::MIN;
+    let mut min_utilization = f64::MAX;
+    for sample in samples {
+        peak = Some(peak.map_or(sample, |peak| peak.max(sample)));
+        let utilization = match sample.utilization(&capacity) {
+            (Some(memory), Some(cpu)) => memory.max(cpu),
+            (Some(utilization), None) | (None, Some(utilization)) => utilization,
+            (None, None) => return None,
+        };
+        peak_utilization = peak_utilization.max(utilization);
+        min_utilization = min_utilization.min(utilization);
+    }
+    let peak = peak?;
+
+    let over_provisioned = if peak_utilization < OVER_PROVISIONED_UTILIZATION {
+        true
+    } else if min_utilization > UNDER_PROVISIONED_UTILIZATION {
+        false
+    } else {
+        return None;
+    };
+
+    let credits = |allocation: &ReplicaAllocation| OrderedDecimal(allocation.credits_per_hour);
+    let size_key = |allocation: &ReplicaAllocation| {
+        let capacity = Usage::capacity(allocation);
+        (
+            credits(allocation),
+            capacity.memory_bytes,
+            capacity.cpu_nano_cores,
+        )
+    };
+    let larger = |allocation: &ReplicaAllocation| {
+        let other = Usage::capacity(allocation);
+        let larger = |current: Option<u64>, other: Option<u64>| match (current, other) {
+            (Some(current), Some(other)) => other > current,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        larger(capacity.memory_bytes, other.memory_bytes)
+            || larger(capacity.cpu_nano_cores, other.cpu_nano_cores)
+    };
+    let fitting = candidates
+        .clone()
+        .filter(|(_, allocation)| peak.fits(&Usage::capacity(allocation), &capacity));
+    let size = if over_provisioned {
+        fitting
+            .filter(|(_, allocation)| credits(allocation) < credits(current))
+            .min_by_key(|(_, allocation)| size_key(allocation))
+    } else {
+        // Prefer the cheapest larger size that fits the peak usage, or else the largest size.
+        let larger_sizes = candidates.filter(|(_, allocation)| larger(allocation));
+        fitting
+            .filter(|(_, allocation)| larger(allocation))
+            .min_by_key(|(_, allocation)| size_key(allocation))
+            .or_else(|| {
+                larger_sizes.max_by_key(|(_, allocation)| {
+                    let capacity = Usage::capacity(allocation);
+                    (capacity.memory_bytes, capacity.cpu_nano_cores)
+                })
+            })
+    };
+
+    let (size, _) = size?;
+    let (memory_utilization, cpu_utilization) = peak.utilization(&capacity);
+    Some(Recommendation {
+        size,
+        over_provisioned,
+        memory_utilization,
+        cpu_utilization,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use bytesize::ByteSize;
+    use mz_controller::clusters::ReplicaAllocation;
+    use mz_orchestrator::MemoryLimit;
+
+    use super::{recommend_size, Usage};
+
+    fn allocation(memory_gib: u64, credits: i32) -> ReplicaAllocation {
+        ReplicaAllocation {
+            memory_limit: Some(MemoryLimit(ByteSize::gib(memory_gib))),
+            cpu_limit: None,
+            disk_limit: None,
+            scale: 1,
+            workers: 1,
+            credits_per_hour: credits.into(),
+            cpu_exclusive: false,
+            disabled: false,
+            selectors: BTreeMap::new(),
+        }
+    }
+
+    fn memory(gib: u64) -> Usage {
+        Usage {
+            memory_bytes: Some(ByteSize::gib(gib).as_u64()),
+            cpu_nano_cores: None,
+        }
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `decContextDefault` on OS `linux`
+    fn test_recommend_size() {
+        let sizes: Vec<_> = [("small", 4, 1), ("medium", 16, 4), ("large", 64, 16)]
+            .into_iter()
+            .map(|(name, memory_gib, credits)| (name.to_string(), allocation(memory_gib, credits)))
+            .collect();
+        let candidates = || sizes.iter().map(|(name, allocation)| (name, allocation));
+        let recommend = |current: usize, samples: &[u64]| {
+            recommend_size(
+                &sizes[current].1,
+                samples.iter().map(|gib| memory(*gib)),
+                candidates(),
+            )
+            .map(|recommendation| {
+                (
+                    recommendation.size.as_str(),
+                    recommendation.over_provisioned,
+                )
+            })
+        };
+
+        // Over-provisioned clusters are moved to the cheapest size that fits their peak usage.
+        assert_eq!(recommend(2, &[1, 2, 1]), Some(("small", true)));
+        assert_eq!(recommend(2, &[2, 8, 4]), Some(("medium", true)));
+        // Clusters whose usage peaked in the middle are left alone.
+        assert_eq!(recommend(1, &[4, 8, 2]), None);
+        // Under-provisioned clusters are moved to a larger size.
+        assert_eq!(recommend(0, &[4, 4, 4]), Some(("medium", false)));
+        assert_eq!(recommend(1, &[15, 16, 15]), Some(("large", false)));
+        // A cluster is only under-provisioned if it consistently uses nearly all its resources.
+        assert_eq!(recommend(0, &[4, 1, 4]), None);
+        // Clusters of the largest size cannot grow.
+        assert_eq!(recommend(2, &[64, 64]), None);
+        // Clusters without samples are left alone.
+        assert_eq!(recommend(2, &[]), None);
+    }
+}
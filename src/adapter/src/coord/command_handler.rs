@@ -13,6 +13,7 @@
 use differential_dataflow::lattice::Lattice;
 use mz_sql::session::metadata::SessionMetadata;
 use std::collections::{BTreeMap, BTreeSet};
+use std::net::IpAddr;
 use std::sync::Arc;
 
 use futures::future::LocalBoxFuture;
@@ -26,7 +27,8 @@ use mz_ore::tracing::OpenTelemetryContext;
 use mz_repr::role_id::RoleId;
 use mz_repr::{ScalarType, Timestamp};
 use mz_sql::ast::{
-    ConstantVisitor, CopyRelation, CopyStatement, Raw, Statement, SubscribeStatement,
+    ConstantVisitor, CopyRelation, CopyStatement, DeclareStatement, Raw, Statement,
+    SubscribeStatement,
 };
 use mz_sql::catalog::RoleAttributes;
 use mz_sql::names::{Aug, PartialItemName, ResolvedIds};
@@ -44,7 +46,8 @@ use mz_sql::session::vars::{
 };
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::{
-    CreateMaterializedViewStatement, ExplainPlanStatement, Explainee, InsertStatement,
+    CreateMaterializedViewStatement, DropObjectsStatement, ExplainPlanStatement, Explainee,
+    InsertStatement,
 };
 use mz_storage_types::sources::Timeline;
 use opentelemetry::trace::TraceContextExt;
@@ -59,10 +62,11 @@ use crate::coord::appends::{Deferred, PendingWriteTxn};
 use crate::coord::{ConnMeta, Coordinator, Message, PendingTxn, PurifiedStatementReady};
 use crate::error::AdapterError;
 use crate::notice::AdapterNotice;
-use crate::session::{Session, TransactionOps, TransactionStatus};
+use crate::session::{AuthenticationMethod, Session, TransactionOps, TransactionStatus};
 use crate::util::{ClientTransmitter, ResultExt};
 use crate::webhook::{
     AppendWebhookResponse, AppendWebhookValidator, WebhookAppender, WebhookAppenderInvalidator,
+    WebhookDeduplicator,
 };
 use crate::{catalog, metrics, AppendWebhookError, ExecuteContext, TimestampProvider};
 
@@ -85,6 +89,9 @@ impl Coordinator {
                     secret_key,
                     uuid,
                     application_name,
+                    authentication_method,
+                    client_ip,
+                    http_request_id,
                     notice_tx,
                 } => {
                     // Note: We purposefully do not use a ClientTransmitter here because startup
@@ -96,6 +103,9 @@ impl Coordinator {
                         secret_key,
                         uuid,
                         application_name,
+                        authentication_method,
+                        client_ip,
+                        http_request_id,
                         notice_tx,
                     )
                     .await;
@@ -219,6 +229,24 @@ impl Coordinator {
                 Command::CheckConsistency { tx } => {
                     let _ = tx.send(self.check_consistency());
                 }
+
+                Command::Drain { timeout, tx } => {
+                    let _ = tx.send(self.start_drain(timeout));
+                }
+
+                Command::GetDrainStatus { tx } => {
+                    let _ = tx.send(self.drain_status());
+                }
+
+                Command::GetSqlJobs { tx } => {
+                    let jobs = self.catalog().get_sql_jobs().await;
+                    let _ = tx.send(jobs.map_err(AdapterError::from));
+                }
+
+                Command::SetSqlJobs { jobs, tx } => {
+                    let result = self.catalog().set_sql_jobs(jobs).await;
+                    let _ = tx.send(result.map_err(AdapterError::from));
+                }
             }
         }
         .instrument(debug_span!("handle_command"))
@@ -234,8 +262,18 @@ impl Coordinator {
         secret_key: u32,
         uuid: uuid::Uuid,
         application_name: String,
+        authentication_method: AuthenticationMethod,
+        client_ip: Option<IpAddr>,
+        http_request_id: Option<String>,
         notice_tx: mpsc::UnboundedSender<AdapterNotice>,
     ) {
+        // Sessions of internal users are still accepted, so that operators can connect to a
+        // draining coordinator.
+        if self.is_draining() && !user.is_internal() {
+            let _ = tx.send(Err(AdapterError::ServerDraining));
+            return;
+        }
+
         // Early return if successful, otherwise cleanup any possible state.
         match self.handle_startup_inner(&user, &conn_id).await {
             Ok(role_id) => {
@@ -281,6 +319,9 @@ impl Coordinator {
                     uuid,
                     conn_id: conn_id.clone(),
                     authenticated_role: role_id,
+                    authentication_method,
+                    client_ip,
+                    http_request_id,
                 };
                 let update = self.catalog().state().pack_session_update(&conn, 1);
                 self.begin_session_for_statement_logging(&conn);
@@ -469,11 +510,12 @@ impl Coordinator {
             // message. Postgres handles this by teaching Started to eagerly commit certain
             // statements that can't be run in a transaction block.
             TransactionStatus::Started(_) => {
-                if let Statement::Declare(_) = &*stmt {
+                if let Statement::Declare(DeclareStatement { hold: false, .. }) = &*stmt {
                     // Declare is an exception. Although it's not against any spec to execute
                     // it, it will always result in nothing happening, since all portals will be
                     // immediately closed. Users don't know this detail, so this error helps them
-                    // understand what's going wrong. Postgres does this too.
+                    // understand what's going wrong. Postgres does this too. Cursors declared
+                    // WITH HOLD outlive the transaction, and so are allowed.
                     return ctx.retire(Err(AdapterError::OperationRequiresTransaction(
                         "DECLARE CURSOR".into(),
                     )));
@@ -525,6 +567,10 @@ impl Coordinator {
                         // any cluster (no RETURNING) is always safe.
                     }
 
+                    Statement::DropObjects(DropObjectsStatement { dry_run: true, .. }) => {
+                        // Dry runs do not drop anything, so they are always safe.
+                    }
+
                     Statement::AlterObjectRename(_) | Statement::AlterObjectSwap(_) => {
                         let state = self.catalog().for_session(ctx.session()).state().clone();
                         let revision = self.catalog().transient_revision();
@@ -543,12 +589,14 @@ impl Coordinator {
 
                     // Statements below must by run singly (in Started).
                     Statement::AlterCluster(_)
+                    | Statement::AlterClusterReplica(_)
                     | Statement::AlterConnection(_)
                     | Statement::AlterDefaultPrivileges(_)
                     | Statement::AlterIndex(_)
                     | Statement::AlterSetCluster(_)
                     | Statement::AlterOwner(_)
                     | Statement::AlterRole(_)
+                    | Statement::AlterQuota(_)
                     | Statement::AlterSecret(_)
                     | Statement::AlterSink(_)
                     | Statement::AlterSource(_)
@@ -569,6 +617,8 @@ impl Coordinator {
                     | Statement::CreateSubsource(_)
                     | Statement::CreateTable(_)
                     | Statement::CreateType(_)
+                    | Statement::CreateFunction(_)
+                    | Statement::CreateContinualTask(_)
                     | Statement::CreateView(_)
                     | Statement::CreateWebhookSource(_)
                     | Statement::Delete(_)
@@ -926,6 +976,17 @@ impl Coordinator {
             maybe_ctx = Some(ctx);
         }
 
+        // Cancel peeks waiting for admission to a cluster. There is at most one queued peek per
+        // session.
+        if let Some(queued_peek) = self.remove_queued_peek(&conn_id) {
+            maybe_ctx = Some(queued_peek.ctx);
+        }
+
+        // Cancel moves to another cluster. There is at most one pending move per session.
+        if let Some(ctx) = self.cancel_cluster_move(&conn_id) {
+            maybe_ctx = Some(ctx);
+        }
+
         if let Some(ctx) = maybe_ctx {
             ctx.retire(Err(AdapterError::Canceled));
         }
@@ -962,6 +1023,12 @@ impl Coordinator {
             .with_label_values(&[session_type])
             .dec();
         self.cancel_pending_peeks(conn.conn_id());
+        if let Some(queued_peek) = self.remove_queued_peek(conn.conn_id()) {
+            queued_peek.ctx.retire(Err(AdapterError::Canceled));
+        }
+        if let Some(ctx) = self.cancel_cluster_move(conn.conn_id()) {
+            ctx.retire(Err(AdapterError::Canceled));
+        }
         self.end_session_for_statement_logging(conn.uuid());
 
         // Queue the builtin table update, but do not wait for it to complete. We explicitly do
@@ -1004,13 +1071,14 @@ impl Coordinator {
                 return Err(name);
             };
 
-            let (body_format, header_tys, validator) = match entry.item() {
+            let (body_format, header_tys, validator, deduplicate) = match entry.item() {
                 CatalogItem::Source(Source {
                     data_source:
                         DataSourceDesc::Webhook {
                             validate_using,
                             body_format,
                             headers,
+                            deduplicate,
                             ..
                         },
                     desc,
@@ -1043,7 +1111,12 @@ impl Coordinator {
                             coord.caching_secrets_reader.clone(),
                         )
                     });
-                    (*body_format, headers.clone(), validator)
+                    (
+                        *body_format,
+                        headers.clone(),
+                        validator,
+                        deduplicate.clone(),
+                    )
                 }
                 _ => return Err(name),
             };
@@ -1059,17 +1132,27 @@ impl Coordinator {
                 .storage
                 .webhook_statistics(entry.id())
                 .map_err(|_| name)?;
+            let id = entry.id();
             let invalidator = coord
                 .active_webhooks
-                .entry(entry.id())
+                .entry(id)
                 .or_insert_with(WebhookAppenderInvalidator::new);
             let tx = WebhookAppender::new(row_tx, invalidator.guard(), stats);
+            // Deduplication state outlives any single appender, e.g. across renames.
+            let deduplicator = deduplicate.map(|config| {
+                coord
+                    .webhook_deduplicators
+                    .entry(id)
+                    .or_insert_with(|| WebhookDeduplicator::new(config))
+                    .clone()
+            });
 
             Ok(AppendWebhookResponse {
                 tx,
                 body_format,
                 header_tys,
                 validator,
+                deduplicator,
             })
         }
 
@@ -12,7 +12,7 @@
 
 use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use fail::fail_point;
 use futures::Future;
@@ -21,7 +21,7 @@ use mz_adapter_types::compaction::SINCE_GRANULARITY;
 use mz_adapter_types::connection::ConnectionId;
 use mz_audit_log::VersionedEvent;
 use mz_catalog::memory::objects::{
-    CatalogItem, Connection, DataSourceDesc, Index, MaterializedView, Sink,
+    CatalogItem, Connection, ContinualTask, DataSourceDesc, Index, MaterializedView, Sink,
 };
 use mz_catalog::SYSTEM_CONN_ID;
 use mz_compute_client::protocol::response::PeekResponse;
@@ -191,6 +191,7 @@ impl Coordinator {
         let mut indexes_to_drop = vec![];
         let mut materialized_views_to_drop = vec![];
         let mut views_to_drop = vec![];
+        let mut continual_tasks_to_drop = vec![];
         let mut replication_slots_to_drop: Vec<(mz_postgres_util::Config, String)> = vec![];
         let mut secrets_to_drop = vec![];
         let mut vpc_endpoints_to_drop = vec![];
@@ -207,6 +208,12 @@ impl Coordinator {
         let mut update_cluster_scheduling_config = false;
         let mut update_default_arrangement_merge_options = false;
         let mut update_http_config = false;
+        let mut update_shard_health_collection = false;
+        let mut update_index_usage_collection = false;
+        let mut update_mixed_cluster_workload_check = false;
+        let mut update_cluster_sizing_check = false;
+        let mut update_read_holds_collection = false;
+        let mut update_object_status_check = false;
         let mut log_indexes_to_drop = Vec::new();
 
         for op in &ops {
@@ -258,6 +265,9 @@ impl Coordinator {
                             materialized_views_to_drop.push((*cluster_id, *id));
                         }
                         CatalogItem::View(_) => views_to_drop.push(*id),
+                        CatalogItem::ContinualTask(ContinualTask { cluster_id, .. }) => {
+                            continual_tasks_to_drop.push((*cluster_id, *id));
+                        }
                         CatalogItem::Secret(_) => {
                             secrets_to_drop.push(*id);
                         }
@@ -315,6 +325,17 @@ impl Coordinator {
                     update_default_arrangement_merge_options |=
                         name == vars::DEFAULT_ARRANGEMENT_EXERT_PROPORTIONALITY.name();
                     update_http_config |= vars::is_http_config_var(name);
+                    update_shard_health_collection |=
+                        name == vars::PERSIST_SHARD_HEALTH_COLLECTION_INTERVAL.name();
+                    update_index_usage_collection |=
+                        name == vars::INDEX_USAGE_COLLECTION_INTERVAL.name();
+                    update_mixed_cluster_workload_check |=
+                        name == vars::MIXED_CLUSTER_WORKLOAD_CHECK_INTERVAL.name();
+                    update_cluster_sizing_check |=
+                        name == vars::CLUSTER_SIZING_CHECK_INTERVAL.name();
+                    update_read_holds_collection |=
+                        name == vars::READ_HOLDS_COLLECTION_INTERVAL.name();
+                    update_object_status_check |= name == vars::OBJECT_STATUS_CHECK_INTERVAL.name();
                 }
                 catalog::Op::ResetAllSystemConfiguration => {
                     // Assume they all need to be updated.
@@ -329,6 +350,12 @@ impl Coordinator {
                     update_cluster_scheduling_config = true;
                     update_default_arrangement_merge_options = true;
                     update_http_config = true;
+                    update_shard_health_collection = true;
+                    update_index_usage_collection = true;
+                    update_mixed_cluster_workload_check = true;
+                    update_cluster_sizing_check = true;
+                    update_read_holds_collection = true;
+                    update_object_status_check = true;
                 }
                 catalog::Op::RenameItem { id, .. } => {
                     let item = self.catalog().get_entry(id);
@@ -542,6 +569,9 @@ impl Coordinator {
             if !materialized_views_to_drop.is_empty() {
                 self.drop_materialized_views(materialized_views_to_drop);
             }
+            if !continual_tasks_to_drop.is_empty() {
+                self.drop_continual_tasks(continual_tasks_to_drop);
+            }
             if !secrets_to_drop.is_empty() {
                 self.drop_secrets(secrets_to_drop).await;
             }
@@ -562,6 +592,8 @@ impl Coordinator {
             if !clusters_to_drop.is_empty() {
                 for cluster_id in clusters_to_drop {
                     self.controller.drop_cluster(cluster_id);
+                    self.drop_query_queue(cluster_id);
+                    self.abort_cluster_moves_to(cluster_id);
                 }
             }
 
@@ -618,6 +650,24 @@ impl Coordinator {
             if update_http_config {
                 self.update_http_config();
             }
+            if update_shard_health_collection {
+                self.schedule_shard_health_collection();
+            }
+            if update_index_usage_collection {
+                self.schedule_index_usage_collection();
+            }
+            if update_mixed_cluster_workload_check {
+                self.schedule_mixed_cluster_workload_check();
+            }
+            if update_cluster_sizing_check {
+                self.schedule_cluster_sizing_check();
+            }
+            if update_read_holds_collection {
+                self.schedule_read_holds_collection();
+            }
+            if update_object_status_check {
+                self.schedule_object_status_check();
+            }
         }
         .instrument(info_span!("coord::catalog_transact_with::finalize"))
         .await;
@@ -627,11 +677,11 @@ impl Coordinator {
             &self.segment_client,
             conn.and_then(|s| s.user().external_metadata.as_ref()),
         ) {
-            for VersionedEvent::V1(event) in audit_events {
+            for event in audit_events {
                 let event_type = format!(
                     "{} {}",
-                    event.object_type.as_title_case(),
-                    event.event_type.as_title_case()
+                    event.object_type().as_title_case(),
+                    event.event_type().as_title_case()
                 );
                 // Note: when there is no ConnMeta, that means something internal to
                 // environmentd initiated the transaction, hence the default name.
@@ -641,7 +691,7 @@ impl Coordinator {
                     application_name,
                     user_metadata.user_id,
                     event_type,
-                    json!({ "details": event.details.as_json() }),
+                    json!({ "details": event.details().as_json() }),
                 );
             }
         }
@@ -658,6 +708,11 @@ impl Coordinator {
     }
 
     async fn drop_replica(&mut self, cluster_id: ClusterId, replica_id: ReplicaId) {
+        // Stop accumulating usage for the replica, but keep what it used until the next usage
+        // collection.
+        if let Some(usage) = self.replica_usage.get_mut(&replica_id) {
+            usage.advance(Instant::now(), None);
+        }
         if let Some(Some(ReplicaMetadata { metrics })) =
             self.transient_replica_metadata.insert(replica_id, None)
         {
@@ -680,6 +735,7 @@ impl Coordinator {
     fn drop_sources(&mut self, sources: Vec<GlobalId>) {
         for id in &sources {
             self.active_webhooks.remove(id);
+            self.webhook_deduplicators.remove(id);
             self.drop_storage_read_policy(id);
         }
         self.controller
@@ -728,6 +784,13 @@ impl Coordinator {
                 Some(sink) => sink,
             };
 
+            // A subscribe that shares its dataflow with other subscribes leaves the dataflow
+            // running.
+            let Some(dataflow_id) = self.detach_shared_subscribe(sink_id) else {
+                by_id.insert(sink_id, sink);
+                continue;
+            };
+
             if !self
                 .controller
                 .compute
@@ -735,7 +798,7 @@ impl Coordinator {
             {
                 // If aggressive downgrades are disabled, compute sinks have read policies that we
                 // must drop.
-                if !self.drop_compute_read_policy(&sink_id) {
+                if !self.drop_compute_read_policy(&dataflow_id) {
                     tracing::error!("Instructed to drop a compute sink that isn't one");
                     continue;
                 }
@@ -744,7 +807,7 @@ impl Coordinator {
             by_cluster
                 .entry(sink.cluster_id())
                 .or_default()
-                .push(sink_id);
+                .push(dataflow_id);
             by_id.insert(sink_id, sink);
         }
         let mut compute = self.controller.active_compute();
@@ -821,6 +884,11 @@ impl Coordinator {
                 tracing::error!("Instructed to drop a non-index index");
             }
         }
+        for ids in by_cluster.values() {
+            self.drop_index_usage(ids);
+            self.drop_plan_changes(ids);
+            self.abort_dropped_cluster_moves(ids);
+        }
         let mut compute = self.controller.active_compute();
         for (cluster_id, ids) in by_cluster {
             // A cluster could have been dropped, so verify it exists.
@@ -852,6 +920,8 @@ impl Coordinator {
             by_cluster.entry(cluster_id).or_default().push(id);
             source_ids.push(id);
         }
+        self.drop_plan_changes(&source_ids);
+        self.abort_dropped_cluster_moves(&source_ids);
 
         // Drop compute sinks.
         let mut compute = self.controller.active_compute();
@@ -868,6 +938,37 @@ impl Coordinator {
         self.drop_sources(source_ids)
     }
 
+    fn drop_continual_tasks(&mut self, tasks: Vec<(ClusterId, GlobalId)>) {
+        let mut by_cluster: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for (cluster_id, id) in tasks {
+            self.retire_continual_task(id);
+            if !self
+                .controller
+                .compute
+                .enable_aggressive_readhold_downgrades()
+            {
+                // If aggressive downgrades are disabled, the dataflows of continual tasks have
+                // read policies that we must drop.
+                if !self.drop_compute_read_policy(&id) {
+                    tracing::error!("Instructed to drop a continual task that isn't one");
+                    continue;
+                }
+            }
+
+            by_cluster.entry(cluster_id).or_default().push(id);
+        }
+
+        let mut compute = self.controller.active_compute();
+        for (cluster_id, ids) in by_cluster {
+            // A cluster could have been dropped, so verify it exists.
+            if compute.instance_exists(cluster_id) {
+                compute
+                    .drop_collections(cluster_id, ids)
+                    .unwrap_or_terminate("cannot fail to drop collections");
+            }
+        }
+    }
+
     async fn drop_secrets(&mut self, secrets: Vec<GlobalId>) {
         fail_point!("drop_secrets");
         for secret in secrets {
@@ -1135,7 +1236,8 @@ impl Coordinator {
                                 Connection::Csr(_)
                                 | Connection::Ssh(_)
                                 | Connection::Aws(_)
-                                | Connection::MySql(_) => {}
+                                | Connection::MySql(_)
+                                | Connection::IcebergCatalog(_) => {}
                             }
                         }
                         CatalogItem::Table(_) => {
@@ -1155,7 +1257,8 @@ impl Coordinator {
                         | CatalogItem::View(_)
                         | CatalogItem::Index(_)
                         | CatalogItem::Type(_)
-                        | CatalogItem::Func(_) => {}
+                        | CatalogItem::Func(_)
+                        | CatalogItem::ContinualTask(_) => {}
                     }
                 }
                 Op::DropObject(id) => match id {
@@ -1220,7 +1323,8 @@ impl Coordinator {
                             | CatalogItem::View(_)
                             | CatalogItem::Index(_)
                             | CatalogItem::Type(_)
-                            | CatalogItem::Func(_) => {}
+                            | CatalogItem::Func(_)
+                            | CatalogItem::ContinualTask(_) => {}
                         }
                     }
                 },
@@ -1248,9 +1352,11 @@ impl Coordinator {
                     | CatalogItem::View(_)
                     | CatalogItem::Index(_)
                     | CatalogItem::Type(_)
-                    | CatalogItem::Func(_) => {}
+                    | CatalogItem::Func(_)
+                    | CatalogItem::ContinualTask(_) => {}
                 },
                 Op::AlterRole { .. }
+                | Op::AlterQuota { .. }
                 | Op::AlterSetCluster { .. }
                 | Op::UpdatePrivilege { .. }
                 | Op::UpdateDefaultPrivilege { .. }
@@ -1262,8 +1368,12 @@ impl Coordinator {
                 | Op::UpdateOwner { .. }
                 | Op::RevokeRole { .. }
                 | Op::UpdateClusterConfig { .. }
+                | Op::UpdateClusterReplicaConfig { .. }
                 | Op::UpdateClusterReplicaStatus { .. }
                 | Op::UpdateStorageUsage { .. }
+                | Op::UpdateStorageIoUsage { .. }
+                | Op::UpdateComputeUsage { .. }
+                | Op::UpdatePlanRecord { .. }
                 | Op::UpdateSystemConfiguration { .. }
                 | Op::ResetSystemConfiguration { .. }
                 | Op::ResetAllSystemConfiguration { .. }
@@ -1290,7 +1400,8 @@ impl Coordinator {
                 Connection::Csr(_)
                 | Connection::Ssh(_)
                 | Connection::Aws(_)
-                | Connection::MySql(_) => {}
+                | Connection::MySql(_)
+                | Connection::IcebergCatalog(_) => {}
             }
         }
         self.validate_resource_limit(
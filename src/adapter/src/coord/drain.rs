@@ -0,0 +1,133 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Draining of the sessions of the coordinator ahead of a handoff to a standby process.
+//!
+//! While draining, the coordinator rejects new sessions of external users, and sessions close
+//! once they are idle outside of a transaction. When the deadline of the drain passes, the
+//! statements that are still running are canceled, and the remaining sessions close as soon as
+//! they are idle, even within a transaction. Sessions of internal users are not drained, so that
+//! operators can still connect to a draining process.
+//!
+//! A drained process keeps running until a standby process is promoted, which fences it out of
+//! the catalog.
+//!
+//! Prepared statements and portals are not handed off. They are bound to the connection of the
+//! client, and nothing ties a new connection to the process that takes over to a drained one, so
+//! clients prepare their statements again after reconnecting.
+
+use std::time::{Duration, Instant};
+
+use mz_ore::task;
+
+use crate::command::DrainStatus;
+use crate::coord::{Coordinator, Message};
+
+/// How sessions react to a drain of the coordinator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SessionDrain {
+    /// No drain was requested.
+    None,
+    /// Sessions close once they are idle outside of a transaction.
+    Idle,
+    /// Sessions close once they are idle.
+    Immediate,
+}
+
+/// The state of a requested drain.
+#[derive(Debug)]
+pub(crate) struct Drain {
+    /// The time after which the statements of the remaining sessions are canceled.
+    deadline: Instant,
+    /// Whether the deadline passed.
+    deadline_passed: bool,
+}
+
+impl Coordinator {
+    /// Starts draining the sessions of the coordinator, with a deadline of `timeout` from now.
+    ///
+    /// Draining cannot be stopped. If a drain is already in progress, its deadline is moved to
+    /// the earlier of the two deadlines.
+    pub(crate) fn start_drain(&mut self, timeout: Duration) -> DrainStatus {
+        let deadline = Instant::now() + timeout;
+        let drain = self.drain.get_or_insert(Drain {
+            deadline,
+            deadline_passed: false,
+        });
+        drain.deadline = std::cmp::min(drain.deadline, deadline);
+        self.drain_tx.send_if_modified(|drain| {
+            let modified = *drain == SessionDrain::None;
+            if modified {
+                *drain = SessionDrain::Idle;
+            }
+            modified
+        });
+
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "drain_deadline", async move {
+            tokio::time::sleep(timeout).await;
+            if internal_cmd_tx.send(Message::DrainDeadline).is_err() {
+                // If sending fails, the main thread has shutdown.
+            }
+        });
+
+        self.drain_status()
+    }
+
+    /// Cancels the statements of the remaining sessions if the deadline of the drain passed.
+    pub(crate) async fn drain_deadline(&mut self) {
+        let Some(drain) = &mut self.drain else {
+            return;
+        };
+        if drain.deadline_passed || Instant::now() < drain.deadline {
+            return;
+        }
+        drain.deadline_passed = true;
+        self.drain_tx.send_replace(SessionDrain::Immediate);
+
+        let conn_ids: Vec<_> = self
+            .active_conns
+            .values()
+            .filter(|conn| !conn.user().is_internal())
+            .map(|conn| conn.conn_id().clone())
+            .collect();
+        tracing::info!(
+            "drain deadline passed, canceling the statements of {} sessions",
+            conn_ids.len()
+        );
+        for conn_id in conn_ids {
+            self.handle_privileged_cancel(conn_id).await;
+        }
+    }
+
+    /// Reports whether new sessions of external users are rejected.
+    pub(crate) fn is_draining(&self) -> bool {
+        self.drain.is_some()
+    }
+
+    /// Returns the progress of the drain.
+    pub(crate) fn drain_status(&self) -> DrainStatus {
+        let Some(drain) = &self.drain else {
+            return DrainStatus::NotDraining;
+        };
+        let remaining_sessions = self
+            .active_conns
+            .values()
+            .filter(|conn| !conn.user().is_internal())
+            .count();
+        if remaining_sessions == 0 {
+            DrainStatus::Drained
+        } else {
+            DrainStatus::Draining {
+                remaining_sessions,
+                deadline_passed: drain.deadline_passed,
+            }
+        }
+    }
+}
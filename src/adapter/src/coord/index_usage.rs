@@ -0,0 +1,223 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Tracking of how indexes are used.
+//!
+//! The coordinator counts, per index, the peeks that read from it and the dataflows that import
+//! it. Every `index_usage_collection_interval`, the counts replace the contents of
+//! `mz_internal.mz_index_usage`, and an [`UnusedIndex`] notice is raised for every user index
+//! that has not been used for longer than `unused_index_notice_threshold`.
+//!
+//! The counts are kept in memory only, so they restart from zero whenever the coordinator
+//! restarts. An index is never considered unused before it has been tracked for the full
+//! threshold.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use mz_catalog::builtin::MZ_INDEX_USAGE;
+use mz_ore::now::{to_datetime, EpochMillis};
+use mz_ore::task;
+use mz_repr::{Datum, GlobalId, Row};
+use mz_transform::dataflow::DataflowMetainfo;
+use mz_transform::notice::{OptimizerNotice, UnusedIndex};
+
+use crate::catalog::BuiltinTableUpdate;
+use crate::coord::{Coordinator, Message};
+use crate::util::ResultExt;
+
+/// How an index has been used since the coordinator started tracking it.
+#[derive(Debug)]
+pub(crate) struct IndexUsage {
+    /// The number of peeks that read from the index.
+    peeks: u64,
+    /// The number of dataflows that imported the index.
+    dataflow_imports: u64,
+    /// When the index was last used by a peek or a dataflow, if ever.
+    last_used_at: Option<EpochMillis>,
+    /// When the coordinator started tracking the index.
+    tracked_since: EpochMillis,
+    /// The notice raised because the index is unused, if any.
+    unused_notice: Option<Arc<OptimizerNotice>>,
+}
+
+impl IndexUsage {
+    fn new(now: EpochMillis) -> Self {
+        IndexUsage {
+            peeks: 0,
+            dataflow_imports: 0,
+            last_used_at: None,
+            tracked_since: now,
+            unused_notice: None,
+        }
+    }
+}
+
+impl Coordinator {
+    /// Records that a peek read from the indexes identified by `ids`.
+    pub(crate) fn record_index_peek(&mut self, ids: impl IntoIterator<Item = GlobalId>) {
+        let now = self.now();
+        for id in ids.into_iter().filter(|id| !id.is_transient()) {
+            let usage = self
+                .index_usage
+                .entry(id)
+                .or_insert_with(|| IndexUsage::new(now));
+            usage.peeks += 1;
+            usage.last_used_at = Some(now);
+        }
+    }
+
+    /// Records that a dataflow imported the indexes identified by `ids`.
+    pub(crate) fn record_index_imports(&mut self, ids: impl IntoIterator<Item = GlobalId>) {
+        let now = self.now();
+        for id in ids.into_iter().filter(|id| !id.is_transient()) {
+            let usage = self
+                .index_usage
+                .entry(id)
+                .or_insert_with(|| IndexUsage::new(now));
+            usage.dataflow_imports += 1;
+            usage.last_used_at = Some(now);
+        }
+    }
+
+    /// Stops tracking the usage of the dropped indexes identified by `ids`, and retracts their
+    /// notices.
+    pub(crate) fn drop_index_usage(&mut self, ids: &[GlobalId]) {
+        let notices: Vec<_> = ids
+            .iter()
+            .filter_map(|id| self.index_usage.remove(id))
+            .filter_map(|usage| usage.unused_notice)
+            .collect();
+        if !notices.is_empty() {
+            let mut updates = Vec::new();
+            self.catalog()
+                .state()
+                .pack_optimizer_notices(&mut updates, notices.iter(), -1);
+            self.builtin_table_update().background(updates);
+        }
+    }
+
+    /// Replaces the contents of `mz_index_usage`, raises or retracts [`UnusedIndex`] notices, and
+    /// schedules the next collection.
+    pub(crate) fn index_usage_update(&mut self) {
+        let now = self.now();
+        let system_config = self.catalog().system_config();
+        let threshold = u64::try_from(system_config.unused_index_notice_threshold().as_millis())
+            .unwrap_or(u64::MAX);
+        let notices_enabled =
+            system_config.enable_mz_notices() && system_config.enable_notices_for_unused_index();
+
+        // Indexes imported by a maintained dataflow are in use for as long as that dataflow
+        // exists, even if nothing has been recently installed that imports them.
+        let mut index_ids = Vec::new();
+        let mut imported = BTreeSet::new();
+        for entry in self.catalog().entries() {
+            if entry.is_index() {
+                index_ids.push(entry.id());
+            }
+            if let Some(plan) = self.catalog().try_get_physical_plan(&entry.id()) {
+                imported.extend(plan.index_imports.keys().copied());
+            }
+        }
+
+        let mut raised = Vec::new();
+        let mut retracted = Vec::new();
+        let mut rows = Vec::with_capacity(index_ids.len());
+        for id in index_ids {
+            let usage = self
+                .index_usage
+                .entry(id)
+                .or_insert_with(|| IndexUsage::new(now));
+            rows.push(pack_index_usage(id, usage));
+
+            let last_active = usage.last_used_at.unwrap_or(usage.tracked_since);
+            let unused = notices_enabled
+                && id.is_user()
+                && !imported.contains(&id)
+                && now.saturating_sub(last_active) >= threshold;
+            match (unused, usage.unused_notice.is_some()) {
+                (true, false) => raised.push(id),
+                (false, true) => retracted.extend(usage.unused_notice.take()),
+                _ => {}
+            }
+        }
+
+        let mut raised_notices = Vec::new();
+        for id in raised {
+            let notice = self.render_unused_index_notice(id);
+            raised_notices.push(Arc::clone(&notice));
+            self.index_usage
+                .get_mut(&id)
+                .expect("inserted above")
+                .unused_notice = Some(notice);
+        }
+        let mut updates = Vec::new();
+        self.catalog()
+            .state()
+            .pack_optimizer_notices(&mut updates, retracted.iter(), -1);
+        self.catalog()
+            .state()
+            .pack_optimizer_notices(&mut updates, raised_notices.iter(), 1);
+
+        let id = self.catalog().resolve_builtin_table(&MZ_INDEX_USAGE);
+        let retractions = std::mem::replace(&mut self.index_usage_rows, rows.clone())
+            .into_iter()
+            .map(|row| BuiltinTableUpdate { id, row, diff: -1 });
+        let insertions = rows
+            .into_iter()
+            .map(|row| BuiltinTableUpdate { id, row, diff: 1 });
+        updates.extend(retractions.chain(insertions));
+        self.builtin_table_update().background(updates);
+
+        self.schedule_index_usage_collection();
+    }
+
+    /// Renders the [`UnusedIndex`] notice for the index identified by `index_id`.
+    fn render_unused_index_notice(&mut self, index_id: GlobalId) -> Arc<OptimizerNotice> {
+        let notice_id = self
+            .allocate_transient_id()
+            .unwrap_or_terminate("cannot fail to allocate a notice id");
+        let mut df_meta = DataflowMetainfo::default();
+        df_meta.push_optimizer_notice_dedup(UnusedIndex { index_id });
+        self.catalog()
+            .render_notices(df_meta, vec![notice_id], Some(index_id))
+            .optimizer_notices
+            .pop()
+            .expect("rendered one notice")
+    }
+
+    /// Schedules the next collection of index usage, replacing any previously scheduled
+    /// collection.
+    pub(crate) fn schedule_index_usage_collection(&mut self) {
+        let interval = self
+            .catalog()
+            .system_config()
+            .index_usage_collection_interval();
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let timer = task::spawn(|| "index_usage_collection", async move {
+            tokio::time::sleep(interval).await;
+            // If sending fails, the main thread has shutdown.
+            let _ = internal_cmd_tx.send(Message::IndexUsageCollect);
+        });
+        self.index_usage_timer = Some(timer.abort_on_drop());
+    }
+}
+
+/// Packs the row of an index in `mz_index_usage`.
+fn pack_index_usage(id: GlobalId, usage: &IndexUsage) -> Row {
+    let timestamp =
+        |millis: EpochMillis| Datum::TimestampTz(to_datetime(millis).try_into().expect("must fit"));
+    Row::pack_slice(&[
+        Datum::String(&id.to_string()),
+        Datum::UInt64(usage.peeks),
+        Datum::UInt64(usage.dataflow_imports),
+        usage.last_used_at.map(timestamp).unwrap_or(Datum::Null),
+        timestamp(usage.tracked_since),
+    ])
+}
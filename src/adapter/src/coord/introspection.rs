@@ -82,8 +82,11 @@ pub fn auto_run_on_introspection<'a, 's, 'p>(
         | Plan::CreateMaterializedView(_)
         | Plan::CreateIndex(_)
         | Plan::CreateType(_)
+        | Plan::CreateFunction(_)
+        | Plan::CreateContinualTask(_)
         | Plan::Comment(_)
         | Plan::DiscardTemp
+        | Plan::DiscardPlans
         | Plan::DiscardAll
         | Plan::DropObjects(_)
         | Plan::DropOwned(_)
@@ -108,11 +111,13 @@ pub fn auto_run_on_introspection<'a, 's, 'p>(
         | Plan::AlterClusterRename(_)
         | Plan::AlterClusterSwap(_)
         | Plan::AlterClusterReplicaRename(_)
+        | Plan::AlterClusterReplica(_)
         | Plan::AlterCluster(_)
         | Plan::AlterIndexSetOptions(_)
         | Plan::AlterIndexResetOptions(_)
         | Plan::AlterConnection(_)
         | Plan::AlterSource(_)
+        | Plan::AlterSinkResetOffset(_)
         | Plan::PurifiedAlterSource { .. }
         | Plan::AlterSetCluster(_)
         | Plan::AlterItemRename(_)
@@ -124,6 +129,7 @@ pub fn auto_run_on_introspection<'a, 's, 'p>(
         | Plan::AlterSystemReset(_)
         | Plan::AlterSystemResetAll(_)
         | Plan::AlterRole(_)
+        | Plan::AlterQuota(_)
         | Plan::AlterOwner(_)
         | Plan::Declare(_)
         | Plan::Fetch(_)
@@ -19,13 +19,18 @@ use maplit::btreemap;
 use mz_adapter_types::connection::ConnectionId;
 use mz_controller::clusters::ClusterEvent;
 use mz_controller::ControllerResponse;
+use mz_controller_types::{ClusterId, ReplicaId};
+use mz_orchestrator::ServiceProcessMetrics;
 use mz_ore::now::EpochMillis;
 use mz_ore::task;
-use mz_persist_client::usage::ShardsUsageReferenced;
+use mz_persist_client::usage::{ShardIoUsage, ShardsUsageReferenced};
+use mz_persist_client::ShardId;
+use mz_repr::role_id::RoleId;
+use mz_repr::GlobalId;
 use mz_sql::ast::Statement;
 use mz_sql::names::ResolvedIds;
 use mz_sql::plan::{CreateSourcePlans, Plan};
-use mz_storage_types::controller::CollectionMetadata;
+use mz_storage_types::controller::{CollectionMetadata, StorageError};
 use opentelemetry::trace::TraceContextExt;
 use rand::{rngs, Rng, SeedableRng};
 use tracing::{event, warn, Instrument, Level};
@@ -34,17 +39,28 @@ use tracing_opentelemetry::OpenTelemetrySpanExt;
 use crate::active_compute_sink::{ActiveComputeSink, ActiveComputeSinkRetireReason};
 use crate::command::Command;
 use crate::coord::appends::Deferred;
+use crate::coord::peek::PeekUsage;
 use crate::coord::statement_logging::StatementLoggingId;
 use crate::coord::{
-    AlterConnectionValidationReady, Coordinator, CreateConnectionValidationReady, Message,
-    PeekStage, PeekStageTimestampReadHold, PlanValidity, PurifiedStatementReady,
-    RealTimeRecencyContext,
+    AlterConnectionValidationReady, AlterSinkResetOffsetReady, Coordinator,
+    CreateConnectionValidationReady, Message, PeekStage, PeekStageTimestampReadHold, PlanValidity,
+    PurifiedStatementReady, RealTimeRecencyContext, ReplicaUsage,
 };
+use crate::error::AdapterError;
 use crate::session::Session;
 use crate::statement_logging::StatementLifecycleEvent;
 use crate::util::ResultExt;
 use crate::{catalog, AdapterNotice, TimestampContext};
 
+/// The compute resources that a role used on a cluster over a usage
+/// collection interval.
+#[derive(Debug, Default)]
+struct ComputeUsage {
+    cpu_nano_cores: u64,
+    memory_bytes: u64,
+    peeks: PeekUsage,
+}
+
 impl Coordinator {
     /// BOXED FUTURE: As of Nov 2023 the returned Future from this function was 74KB. This would
     /// get stored on the stack which is bad for runtime performance, and blow up our stack usage.
@@ -89,6 +105,9 @@ impl Coordinator {
                 Message::AlterConnectionValidationReady(ready) => {
                     self.message_alter_connection_validation_ready(ready).await
                 }
+                Message::AlterSinkResetOffsetReady(ready) => {
+                    self.message_alter_sink_reset_offset_ready(ready).await
+                }
                 Message::WriteLockGrant(write_lock_guard) => {
                     self.message_write_lock_grant(write_lock_guard).await;
                 }
@@ -108,6 +127,15 @@ impl Coordinator {
                 Message::CancelPendingPeeks { conn_id } => {
                     self.cancel_pending_peeks(&conn_id);
                 }
+                Message::SubscribeStatementTimeout { sink_id } => {
+                    // The subscribe may have already finished or been canceled.
+                    if self.active_compute_sinks.contains_key(&sink_id) {
+                        self.retire_compute_sinks(btreemap! {
+                            sink_id => ActiveComputeSinkRetireReason::StatementTimeout,
+                        })
+                        .await;
+                    }
+                }
                 Message::LinearizeReads => {
                     self.message_linearize_reads().await;
                 }
@@ -117,6 +145,27 @@ impl Coordinator {
                 Message::StorageUsageUpdate(sizes) => {
                     self.storage_usage_update(sizes).await;
                 }
+                Message::ShardHealthFetch => {
+                    self.shard_health_fetch();
+                }
+                Message::ShardHealthUpdate(health) => {
+                    self.shard_health_update(health);
+                }
+                Message::IndexUsageCollect => {
+                    self.index_usage_update();
+                }
+                Message::MixedClusterWorkloadCheck => {
+                    self.mixed_cluster_workload_check();
+                }
+                Message::ClusterSizingCheck => {
+                    self.cluster_sizing_check();
+                }
+                Message::ReadHoldsCollect => {
+                    self.read_holds_update();
+                }
+                Message::ObjectStatusCheck => {
+                    self.object_status_check().await;
+                }
                 Message::RealTimeRecencyTimestamp {
                     conn_id,
                     real_time_recency_ts,
@@ -198,6 +247,28 @@ impl Coordinator {
                         )
                         .await;
                 }
+                Message::ClusterReconfigurationCheck {
+                    cluster_id,
+                    deadline,
+                } => {
+                    self.check_cluster_reconfiguration(cluster_id, deadline)
+                        .await;
+                }
+                Message::ClusterScheduleCheck => {
+                    self.check_cluster_schedules().await;
+                }
+                Message::ClusterMoveCheck { id } => {
+                    self.check_cluster_move(id).await;
+                }
+                Message::AdmitQueuedPeeks { cluster_id } => {
+                    self.admit_queued_peeks(cluster_id).await;
+                }
+                Message::QueryQueueTimeout { cluster_id, id } => {
+                    self.timeout_queued_peek(cluster_id, id);
+                }
+                Message::DrainDeadline => {
+                    self.drain_deadline().await;
+                }
             }
         }
         .instrument(span)
@@ -210,8 +281,31 @@ impl Coordinator {
         let client = self.storage_usage_client.clone();
 
         // Record the currently live shards.
-        let live_shards: BTreeSet<_> = self
-            .controller
+        let live_shards = self.live_shards();
+
+        let collection_metric = self
+            .metrics
+            .storage_usage_collection_time_seconds
+            .with_label_values(&[]);
+
+        // Spawn an asynchronous task to compute the storage usage, which
+        // requires a slow scan of the underlying storage engine.
+        task::spawn(|| "storage_usage_fetch", async move {
+            let collection_metric_timer = collection_metric.start_timer();
+            let shard_sizes = client.shards_usage_referenced(live_shards).await;
+            collection_metric_timer.observe_duration();
+
+            // It is not an error for shard sizes to become ready after
+            // `internal_cmd_rx` is dropped.
+            if let Err(e) = internal_cmd_tx.send(Message::StorageUsageUpdate(shard_sizes)) {
+                warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+            }
+        });
+    }
+
+    /// Returns the shards of all collections that have not been dropped.
+    pub(crate) fn live_shards(&self) -> BTreeSet<ShardId> {
+        self.controller
             .storage
             .collections()
             // A collection is dropped if its read capability has been advanced
@@ -236,26 +330,117 @@ impl Coordinator {
                 [*remap_shard, *status_shard, Some(*data_shard)].into_iter()
             })
             .filter_map(|shard| shard)
-            .collect();
+            .collect()
+    }
 
-        let collection_metric = self
-            .metrics
-            .storage_usage_collection_time_seconds
-            .with_label_values(&[]);
+    /// Returns the bytes read from and written to persist for each storage
+    /// collection since the previous call.
+    ///
+    /// IO is reported by the replicas of all clusters, as well as by this
+    /// process. IO on shards that don't belong to a collection, like the
+    /// catalog and txns shards, is not attributed.
+    /// Returns the storage collection that each shard belongs to.
+    fn shard_collections(&self) -> BTreeMap<ShardId, GlobalId> {
+        self.controller
+            .storage
+            .collections()
+            .flat_map(|(id, collection)| {
+                let CollectionMetadata {
+                    data_shard,
+                    remap_shard,
+                    status_shard,
+                    persist_location: _,
+                    relation_desc: _,
+                    txns_shard: _,
+                } = &collection.collection_metadata;
+                [Some(*data_shard), *remap_shard, *status_shard]
+                    .into_iter()
+                    .flatten()
+                    .map(|shard| (shard, *id))
+            })
+            .collect()
+    }
 
-        // Spawn an asynchronous task to compute the storage usage, which
-        // requires a slow scan of the underlying storage engine.
-        task::spawn(|| "storage_usage_fetch", async move {
-            let collection_metric_timer = collection_metric.start_timer();
-            let shard_sizes = client.shards_usage_referenced(live_shards).await;
-            collection_metric_timer.observe_duration();
+    fn take_storage_io_usage(&mut self) -> BTreeMap<GlobalId, ShardIoUsage> {
+        let shard_collections = self.shard_collections();
+        let replica_usage = self.controller.compute.take_persist_io_usage();
+        let local_usage = self.storage_usage_client.take_io_usage();
 
-            // It is not an error for shard sizes to become ready after
-            // `internal_cmd_rx` is dropped.
-            if let Err(e) = internal_cmd_tx.send(Message::StorageUsageUpdate(shard_sizes)) {
-                warn!("internal_cmd_rx dropped before we could send: {:?}", e);
-            }
-        });
+        let mut usage: BTreeMap<GlobalId, ShardIoUsage> = BTreeMap::new();
+        for (shard_id, shard_usage) in replica_usage.into_iter().chain(local_usage) {
+            let Some(id) = shard_collections.get(&shard_id) else {
+                continue;
+            };
+            let entry = usage.entry(*id).or_default();
+            entry.bytes_read += shard_usage.bytes_read;
+            entry.bytes_written += shard_usage.bytes_written;
+        }
+        usage
+    }
+
+    /// Accumulates the CPU and memory that a replica used up to its newly reported `metrics`.
+    fn record_replica_usage(&mut self, replica_id: ReplicaId, metrics: &[ServiceProcessMetrics]) {
+        let Some(cluster) = self
+            .catalog()
+            .clusters()
+            .find(|cluster| cluster.replica(replica_id).is_some())
+        else {
+            return;
+        };
+        let (cluster_id, owner_id) = (cluster.id, cluster.owner_id);
+        let usage = self
+            .replica_usage
+            .entry(replica_id)
+            .or_insert_with(|| ReplicaUsage::new(cluster_id, owner_id));
+        usage.owner_id = owner_id;
+        let cpu_nano_cores = metrics.iter().filter_map(|m| m.cpu_nano_cores).sum();
+        let memory_bytes = metrics.iter().filter_map(|m| m.memory_bytes).sum();
+        usage.advance(Instant::now(), Some((cpu_nano_cores, memory_bytes)));
+    }
+
+    /// Returns the compute resources that each role used on each cluster since the previous
+    /// call.
+    ///
+    /// The CPU and memory that the replicas of a cluster used are averaged over the time since
+    /// the previous call, and attributed to the owner of the cluster. Between two reports, a
+    /// replica is assumed to use the CPU and memory that it last reported. The peeks that
+    /// finished since the previous call are attributed to the role that ran them.
+    fn take_compute_usage(&mut self) -> BTreeMap<(ClusterId, RoleId), ComputeUsage> {
+        let now = Instant::now();
+        let interval_ms = now
+            .saturating_duration_since(std::mem::replace(&mut self.compute_usage_collected_at, now))
+            .as_millis()
+            .max(1);
+        let mut usage: BTreeMap<(ClusterId, RoleId), ComputeUsage> = BTreeMap::new();
+        for replica_usage in self.replica_usage.values_mut() {
+            let last_sample = replica_usage
+                .last_sample
+                .map(|(_, cpu_nano_cores, memory_bytes)| (cpu_nano_cores, memory_bytes));
+            replica_usage.advance(now, last_sample);
+            // Attribute the usage to the current owner of the cluster, unless it was dropped.
+            let owner_id = self
+                .catalog
+                .try_get_cluster(replica_usage.cluster_id)
+                .map_or(replica_usage.owner_id, |cluster| cluster.owner_id);
+            let entry = usage
+                .entry((replica_usage.cluster_id, owner_id))
+                .or_default();
+            let cpu_nano_core_ms = std::mem::take(&mut replica_usage.cpu_nano_core_ms);
+            let memory_byte_ms = std::mem::take(&mut replica_usage.memory_byte_ms);
+            entry.cpu_nano_cores = entry
+                .cpu_nano_cores
+                .saturating_add(u64::try_from(cpu_nano_core_ms / interval_ms).unwrap_or(u64::MAX));
+            entry.memory_bytes = entry
+                .memory_bytes
+                .saturating_add(u64::try_from(memory_byte_ms / interval_ms).unwrap_or(u64::MAX));
+        }
+        // Dropped replicas have no usage left to collect.
+        self.replica_usage
+            .retain(|_, replica_usage| replica_usage.last_sample.is_some());
+        for (key, peek_usage) in self.take_peek_usage() {
+            usage.entry(key).or_default().peeks = peek_usage;
+        }
+        usage
     }
 
     #[mz_ore::instrument(level = "debug")]
@@ -266,18 +451,45 @@ impl Coordinator {
         // timestamp in the past.
         let collection_timestamp: EpochMillis = self.get_local_write_ts().await.timestamp.into();
 
+        let shard_collections = self.shard_collections();
+        let mut storage_usage: BTreeMap<GlobalId, u64> = BTreeMap::new();
         let mut ops = vec![];
         for (shard_id, shard_usage) in shards_usage.by_shard {
+            if let Some(id) = shard_collections.get(&shard_id) {
+                *storage_usage.entry(*id).or_default() += shard_usage.size_bytes();
+            }
             ops.push(catalog::Op::UpdateStorageUsage {
                 shard_id: Some(shard_id.to_string()),
                 size_bytes: shard_usage.size_bytes(),
                 collection_timestamp,
             });
         }
+        for (id, io_usage) in self.take_storage_io_usage() {
+            ops.push(catalog::Op::UpdateStorageIoUsage {
+                object_id: id.to_string(),
+                bytes_read: io_usage.bytes_read,
+                bytes_written: io_usage.bytes_written,
+                collection_timestamp,
+            });
+        }
+        for ((cluster_id, role_id), usage) in self.take_compute_usage() {
+            ops.push(catalog::Op::UpdateComputeUsage {
+                cluster_id: cluster_id.to_string(),
+                role_id: role_id.to_string(),
+                cpu_nano_cores: usage.cpu_nano_cores,
+                memory_bytes: usage.memory_bytes,
+                peek_count: usage.peeks.count,
+                peek_time_us: u64::try_from(usage.peeks.time.as_micros())
+                    .expect("peek time must fit in a u64"),
+                collection_timestamp,
+            });
+        }
 
         if let Err(err) = self.catalog_transact(None::<&Session>, ops).await {
             tracing::warn!("Failed to update storage metrics: {:?}", err);
         }
+        // Remember the storage usage of each collection, to enforce storage quotas.
+        self.catalog_mut().set_storage_usage(storage_usage);
         self.schedule_storage_usage_collection().await;
     }
 
@@ -343,6 +555,17 @@ impl Coordinator {
             ControllerResponse::PeekResponse(uuid, response, otel_ctx) => {
                 self.send_peek_response(uuid, response, otel_ctx);
             }
+            ControllerResponse::SubscribeResponse(sink_id, response)
+                if self.shared_subscribes.contains_key(&sink_id) =>
+            {
+                self.process_shared_subscribe_response(sink_id, response)
+                    .await;
+            }
+            ControllerResponse::SubscribeResponse(sink_id, response)
+                if self.continual_tasks.contains_key(&sink_id) =>
+            {
+                self.process_continual_task_response(sink_id, response);
+            }
             ControllerResponse::SubscribeResponse(sink_id, response) => {
                 match self.active_compute_sinks.get_mut(&sink_id) {
                     Some(ActiveComputeSink::Subscribe(active_subscribe)) => {
@@ -400,6 +623,7 @@ impl Coordinator {
                     };
                     self.builtin_table_update().background(updates);
                 }
+                self.record_replica_usage(replica_id, &new);
             }
             ControllerResponse::WatchSetFinished(sets) => {
                 let now = self.now();
@@ -618,6 +842,32 @@ impl Coordinator {
         ctx.retire(result);
     }
 
+    #[mz_ore::instrument(level = "debug")]
+    async fn message_alter_sink_reset_offset_ready(
+        &mut self,
+        AlterSinkResetOffsetReady {
+            ctx,
+            result,
+            sink_id,
+            mut plan_validity,
+            otel_ctx,
+        }: AlterSinkResetOffsetReady,
+    ) {
+        otel_ctx.attach_as_parent();
+
+        // The sink may have been dropped while we were resetting its progress, in which case
+        // there is nothing to restart.
+        if let Err(e) = plan_validity.check(self.catalog()) {
+            return ctx.retire(Err(e));
+        }
+        if let Err(e) = result {
+            return ctx.retire(Err(e));
+        }
+
+        let result = self.sequence_alter_sink_reset_offset_finish(sink_id).await;
+        ctx.retire(result);
+    }
+
     #[mz_ore::instrument(level = "debug")]
     async fn message_write_lock_grant(
         &mut self,
@@ -628,7 +878,14 @@ impl Coordinator {
         if let Some(ready) = self.write_lock_wait_group.pop_front() {
             match ready {
                 Deferred::Plan(mut ready) => {
-                    ready.ctx.session_mut().grant_write_lock(write_lock_guard);
+                    let timeout = self
+                        .catalog()
+                        .system_config()
+                        .write_transaction_read_lock_timeout();
+                    ready
+                        .ctx
+                        .session_mut()
+                        .grant_write_lock(write_lock_guard, timeout);
                     if let Err(e) = ready.validity.check(self.catalog()) {
                         ready.ctx.retire(Err(e))
                     } else {
@@ -810,7 +1067,7 @@ impl Coordinator {
     async fn message_real_time_recency_timestamp(
         &mut self,
         conn_id: ConnectionId,
-        real_time_recency_ts: mz_repr::Timestamp,
+        real_time_recency_ts: Result<mz_repr::Timestamp, StorageError>,
         mut validity: PlanValidity,
     ) {
         let real_time_recency_context =
@@ -820,6 +1077,15 @@ impl Coordinator {
                 None => return,
             };
 
+        let real_time_recency_ts = match real_time_recency_ts {
+            Ok(ts) => ts,
+            Err(err) => {
+                let ctx = real_time_recency_context.take_context();
+                ctx.retire(Err(AdapterError::Storage(err)));
+                return;
+            }
+        };
+
         if let Err(err) = validity.check(self.catalog()) {
             let ctx = real_time_recency_context.take_context();
             ctx.retire(Err(err));
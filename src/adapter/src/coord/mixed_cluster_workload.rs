@@ -0,0 +1,169 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Detection of clusters with mixed workloads.
+//!
+//! Sources and sinks compete with the indexes installed on the same cluster for CPU and memory,
+//! which makes queries served from those indexes slow whenever ingestion is busy. Every
+//! `mixed_cluster_workload_check_interval`, the coordinator raises a [`MixedClusterWorkload`]
+//! notice for every user cluster that hosts both sources or sinks and user indexes, and retracts
+//! the notices of clusters whose workloads have since been split.
+
+use std::sync::Arc;
+
+use mz_catalog::memory::objects::{Cluster, ClusterVariant};
+use mz_controller::clusters::ReplicaLocation;
+use mz_ore::task;
+use mz_sql::ast::Ident;
+use mz_sql_parser::ast::display::AstDisplay;
+use mz_transform::dataflow::DataflowMetainfo;
+use mz_transform::notice::{MixedClusterWorkload, OptimizerNotice};
+
+use crate::catalog::Catalog;
+use crate::coord::{Coordinator, Message};
+use crate::util::ResultExt;
+
+/// A raised [`MixedClusterWorkload`] notice.
+#[derive(Debug)]
+pub(crate) struct MixedClusterWorkloadNotice {
+    /// The notice, as it was before rendering.
+    raw: MixedClusterWorkload,
+    /// The rendered notice.
+    rendered: Arc<OptimizerNotice>,
+}
+
+impl Coordinator {
+    /// Raises or retracts [`MixedClusterWorkload`] notices, and schedules the next check.
+    pub(crate) fn mixed_cluster_workload_check(&mut self) {
+        let system_config = self.catalog().system_config();
+        let notices_enabled = system_config.enable_mz_notices()
+            && system_config.enable_notices_for_mixed_cluster_workload();
+
+        let mut current = Vec::new();
+        if notices_enabled {
+            for cluster in self.catalog().user_clusters() {
+                if let Some(notice) = mixed_cluster_workload(self.catalog(), cluster) {
+                    current.push((cluster.id, notice));
+                }
+            }
+        }
+
+        // Retract the notices of clusters whose workloads changed, including the clusters that
+        // have been dropped, and raise the notices of clusters whose workloads are newly mixed.
+        let mut retracted = Vec::new();
+        self.mixed_cluster_workload_notices
+            .retain(|cluster_id, notice| {
+                let keep = current
+                    .iter()
+                    .any(|(id, raw)| id == cluster_id && raw == &notice.raw);
+                if !keep {
+                    retracted.push(Arc::clone(&notice.rendered));
+                }
+                keep
+            });
+        let mut raised = Vec::new();
+        for (cluster_id, raw) in current {
+            if self
+                .mixed_cluster_workload_notices
+                .contains_key(&cluster_id)
+            {
+                continue;
+            }
+            let rendered = self.render_mixed_cluster_workload_notice(raw.clone());
+            raised.push(Arc::clone(&rendered));
+            self.mixed_cluster_workload_notices
+                .insert(cluster_id, MixedClusterWorkloadNotice { raw, rendered });
+        }
+
+        if !retracted.is_empty() || !raised.is_empty() {
+            let mut updates = Vec::new();
+            self.catalog()
+                .state()
+                .pack_optimizer_notices(&mut updates, retracted.iter(), -1);
+            self.catalog()
+                .state()
+                .pack_optimizer_notices(&mut updates, raised.iter(), 1);
+            self.builtin_table_update().background(updates);
+        }
+
+        self.schedule_mixed_cluster_workload_check();
+    }
+
+    /// Renders a [`MixedClusterWorkload`] notice.
+    fn render_mixed_cluster_workload_notice(
+        &mut self,
+        notice: MixedClusterWorkload,
+    ) -> Arc<OptimizerNotice> {
+        let notice_id = self
+            .allocate_transient_id()
+            .unwrap_or_terminate("cannot fail to allocate a notice id");
+        let mut df_meta = DataflowMetainfo::default();
+        df_meta.push_optimizer_notice_dedup(notice);
+        self.catalog()
+            .render_notices(df_meta, vec![notice_id], None)
+            .optimizer_notices
+            .pop()
+            .expect("rendered one notice")
+    }
+
+    /// Schedules the next check for mixed cluster workloads, replacing any previously scheduled
+    /// check.
+    pub(crate) fn schedule_mixed_cluster_workload_check(&mut self) {
+        let interval = self
+            .catalog()
+            .system_config()
+            .mixed_cluster_workload_check_interval();
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let timer = task::spawn(|| "mixed_cluster_workload_check", async move {
+            tokio::time::sleep(interval).await;
+            // If sending fails, the main thread has shutdown.
+            let _ = internal_cmd_tx.send(Message::MixedClusterWorkloadCheck);
+        });
+        self.mixed_cluster_workload_timer = Some(timer.abort_on_drop());
+    }
+}
+
+/// Returns the [`MixedClusterWorkload`] notice for `cluster`, or `None` if the cluster does not
+/// host both sources or sinks and user indexes.
+///
+/// Clusters without replicas are ignored, as they do not run anything.
+fn mixed_cluster_workload(catalog: &Catalog, cluster: &Cluster) -> Option<MixedClusterWorkload> {
+    let mut ingestion_ids = Vec::new();
+    let mut index_ids = Vec::new();
+    for id in &cluster.bound_objects {
+        let entry = catalog.get_entry(id);
+        if entry.is_source() || entry.is_sink() {
+            ingestion_ids.push(*id);
+        } else if entry.is_index() && id.is_user() {
+            index_ids.push(*id);
+        }
+    }
+    if ingestion_ids.is_empty() || index_ids.is_empty() || cluster.replicas().next().is_none() {
+        return None;
+    }
+
+    let cluster_size = match &cluster.config.variant {
+        ClusterVariant::Managed(managed) => managed.size.clone(),
+        ClusterVariant::Unmanaged => {
+            cluster
+                .replicas()
+                .find_map(|replica| match &replica.config.location {
+                    ReplicaLocation::Managed(location) => Some(location.size.clone()),
+                    ReplicaLocation::Unmanaged(_) => None,
+                })?
+        }
+    };
+    Some(MixedClusterWorkload {
+        cluster: Ident::new_unchecked(cluster.name.clone()).to_ast_string(),
+        cluster_size,
+        serving_cluster: Ident::new_unchecked(format!("{}_serving", cluster.name)).to_ast_string(),
+        ingestion_ids,
+        index_ids,
+    })
+}
@@ -0,0 +1,126 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Periodic recording of the statuses of clusters and materialized views.
+//!
+//! Sources and sinks report their health to the storage controller, which records every change
+//! in `mz_internal.mz_object_status_history`. Clusters and materialized views have no such
+//! reports, so every `object_status_check_interval` the coordinator derives their status from
+//! the statuses of the cluster replicas and the hydration of the materialized views, and hands
+//! it to the storage controller, which records it if it changed.
+
+use mz_catalog::memory::objects::{CatalogItem, Cluster};
+use mz_controller::clusters::ClusterStatus;
+use mz_orchestrator::NotReadyReason;
+use mz_ore::task;
+use mz_storage_client::client::{ObjectStatus, ObjectStatusUpdate, StatusObjectType};
+
+use crate::coord::{Coordinator, Message};
+
+/// The reason of objects that are stalled because their cluster has no replicas.
+const NO_REPLICAS_REASON: &str = "cluster has no replicas";
+
+impl Coordinator {
+    /// Records the statuses of all clusters and materialized views and schedules the next check.
+    pub(crate) async fn object_status_check(&mut self) {
+        let timestamp = self.now_datetime();
+        let mut updates = Vec::new();
+
+        for cluster in self.catalog().clusters() {
+            let (status, reason) = cluster_status(cluster);
+            updates.push(ObjectStatusUpdate {
+                object_id: cluster.id.to_string(),
+                object_type: StatusObjectType::Cluster,
+                status,
+                reason,
+                timestamp,
+            });
+        }
+
+        for entry in self.catalog().entries() {
+            let CatalogItem::MaterializedView(mv) = entry.item() else {
+                continue;
+            };
+            let Some(cluster) = self.catalog().try_get_cluster(mv.cluster_id) else {
+                continue;
+            };
+            let (status, reason) = if cluster.replicas().next().is_none() {
+                (ObjectStatus::Stalled, Some(NO_REPLICAS_REASON.into()))
+            } else {
+                // The dataflow of a materialized view that was just created might not be known
+                // to the compute controller yet.
+                match self
+                    .controller
+                    .compute
+                    .collection_hydrated(mv.cluster_id, entry.id())
+                {
+                    Ok(true) => (ObjectStatus::Running, None),
+                    Ok(false) => (ObjectStatus::Starting, Some("hydrating".into())),
+                    Err(_) => continue,
+                }
+            };
+            updates.push(ObjectStatusUpdate {
+                object_id: entry.id().to_string(),
+                object_type: StatusObjectType::MaterializedView,
+                status,
+                reason,
+                timestamp,
+            });
+        }
+
+        self.controller
+            .storage
+            .record_object_statuses(updates)
+            .await;
+
+        self.schedule_object_status_check();
+    }
+
+    /// Schedules the next check of the statuses of clusters and materialized views, replacing
+    /// any previously scheduled check.
+    pub(crate) fn schedule_object_status_check(&mut self) {
+        let interval = self
+            .catalog()
+            .system_config()
+            .object_status_check_interval();
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let timer = task::spawn(|| "object_status_check", async move {
+            tokio::time::sleep(interval).await;
+            // If sending fails, the main thread has shutdown.
+            let _ = internal_cmd_tx.send(Message::ObjectStatusCheck);
+        });
+        self.object_status_timer = Some(timer.abort_on_drop());
+    }
+}
+
+/// Returns the status of `cluster` and the reason for it.
+///
+/// A cluster is running as soon as any of its replicas is ready, as it can then do its work.
+fn cluster_status(cluster: &Cluster) -> (ObjectStatus, Option<String>) {
+    let mut replicas = cluster.replicas().peekable();
+    if replicas.peek().is_none() {
+        return (ObjectStatus::Stalled, Some(NO_REPLICAS_REASON.into()));
+    }
+    let mut oom_killed = false;
+    for replica in replicas {
+        match replica.status() {
+            ClusterStatus::Ready => return (ObjectStatus::Running, None),
+            ClusterStatus::NotReady(Some(NotReadyReason::OomKilled)) => oom_killed = true,
+            ClusterStatus::NotReady(None) => {}
+        }
+    }
+    if oom_killed {
+        (
+            ObjectStatus::Errored,
+            Some("replicas ran out of memory".into()),
+        )
+    } else {
+        (ObjectStatus::Starting, Some("no replica is ready".into()))
+    }
+}
@@ -15,6 +15,7 @@
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::num::NonZeroUsize;
+use std::time::{Duration, Instant};
 
 use differential_dataflow::consolidation::consolidate;
 use futures::TryFutureExt;
@@ -38,13 +39,17 @@ use mz_repr::explain::text::DisplayText;
 use mz_repr::explain::{
     CompactScalars, IndexUsageType, Indices, PlanRenderingContext, UsedIndexes,
 };
+use mz_repr::role_id::RoleId;
 use mz_repr::{Diff, GlobalId, RelationType, Row};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use timely::progress::Timestamp;
 use tokio::sync::oneshot;
+use tracing::warn;
 use uuid::Uuid;
 
 use crate::coord::timestamp_selection::TimestampDetermination;
+use crate::coord::Message;
 use crate::optimize::OptimizerError;
 use crate::statement_logging::{StatementEndedExecutionReason, StatementExecutionStrategy};
 use crate::util::ResultExt;
@@ -61,6 +66,20 @@ pub(crate) struct PendingPeek {
     /// needed by the coordinator for retiring it.
     pub(crate) ctx_extra: ExecuteContextExtra,
     pub(crate) is_fast_path: bool,
+    /// The role of the session that issued the peek, to which its execution
+    /// time is attributed.
+    pub(crate) role_id: Option<RoleId>,
+    /// When the peek was issued.
+    pub(crate) started_at: Instant,
+}
+
+/// The peeks that a role ran on a cluster since the last usage collection.
+#[derive(Debug, Default)]
+pub(crate) struct PeekUsage {
+    /// The number of peeks that finished.
+    pub(crate) count: u64,
+    /// The total execution time of the peeks.
+    pub(crate) time: Duration,
 }
 
 /// The response from a `Peek`, with row multiplicities represented in unary.
@@ -72,6 +91,12 @@ pub enum PeekResponseUnary {
     Rows(Vec<Row>),
     Error(String),
     Canceled,
+    /// The peek or subscribe was canceled because it exceeded the session's
+    /// `statement_timeout`.
+    TimedOut,
+    /// The peek was aborted because its result exceeded the session's
+    /// `max_query_result_size` or `max_query_result_rows`.
+    ResultSizeExceeded(String),
 }
 
 #[derive(Clone, Debug)]
@@ -269,6 +294,51 @@ pub enum PeekPlan<T = mz_repr::Timestamp> {
     SlowPath(PeekDataflowPlan<T>),
 }
 
+impl<T: fmt::Debug> PeekPlan<T> {
+    /// Returns a fingerprint that identifies the plan across executions.
+    ///
+    /// Executions of the same query have the same fingerprint for as long as they are planned
+    /// the same way, which allows correlating statements with changes to their plans. The
+    /// identifiers of the transient objects built by a slow-path plan differ between executions,
+    /// so they are replaced by their position in the plan before fingerprinting.
+    pub fn fingerprint(&self) -> String {
+        let plan = match self {
+            PeekPlan::FastPath(plan) => format!("{plan:?}"),
+            PeekPlan::SlowPath(PeekDataflowPlan { desc, .. }) => {
+                let mut plan = format!(
+                    "{:?}{:?}{:?}",
+                    desc.source_imports, desc.index_imports, desc.objects_to_build
+                );
+                let transient_ids = desc
+                    .objects_to_build
+                    .iter()
+                    .map(|build| build.id)
+                    .chain(desc.index_exports.keys().copied())
+                    .filter(|id| id.is_transient());
+                for (position, id) in transient_ids.enumerate() {
+                    plan = plan.replace(&format!("{id:?}"), &format!("Transient(#{position})"));
+                }
+                plan
+            }
+        };
+        let digest = Sha256::digest(plan.as_bytes());
+        hex::encode(&digest[..8])
+    }
+}
+
+/// Returns an error if `rows` has more than `max_result_rows` rows.
+fn enforce_max_result_rows(
+    rows: Vec<Row>,
+    max_result_rows: Option<usize>,
+) -> Result<Vec<Row>, String> {
+    match max_result_rows {
+        Some(max_result_rows) if rows.len() > max_result_rows => {
+            Err(format!("result exceeds max row count of {max_result_rows}"))
+        }
+        _ => Ok(rows),
+    }
+}
+
 /// Convert `mfp` to an executable, non-temporal plan.
 /// It should be non-temporal, as OneShot preparation populates `mz_now`.
 fn mfp_to_safe_plan(
@@ -442,6 +512,7 @@ impl crate::coord::Coordinator {
         compute_instance: ComputeInstanceId,
         target_replica: Option<ReplicaId>,
         max_result_size: u64,
+        max_result_rows: Option<usize>,
     ) -> Result<crate::ExecuteResponse, AdapterError> {
         let PlannedPeek {
             plan: fast_path,
@@ -478,7 +549,9 @@ impl crate::coord::Coordinator {
                     ));
                 }
             }
-            let results = finishing.finish(results, max_result_size);
+            let results = finishing
+                .finish(results, max_result_size)
+                .and_then(|rows| enforce_max_result_rows(rows, max_result_rows));
             let (ret, reason) = match results {
                 Ok(rows) => {
                     let rows_returned = u64::cast_from(rows.len());
@@ -501,7 +574,8 @@ impl crate::coord::Coordinator {
 
         let timestamp = determination.timestamp_context.timestamp_or_default();
         if let Some(id) = ctx_extra.contents() {
-            self.set_statement_execution_timestamp(id, timestamp)
+            self.set_statement_execution_timestamp(id, timestamp);
+            self.set_statement_timestamp_determination(id, &determination);
         }
 
         // The remaining cases are a peek into a maintained arrangement, or building a dataflow.
@@ -509,6 +583,17 @@ impl crate::coord::Coordinator {
         // build a dataflow and drop it once the peek is issued. The peeks are also constructed
         // differently.
 
+        // Record the indexes that the peek reads from in `mz_index_usage`.
+        match &fast_path {
+            PeekPlan::FastPath(FastPathPlan::PeekExisting(_coll_id, idx_id, ..)) => {
+                self.record_index_peek([*idx_id]);
+            }
+            PeekPlan::SlowPath(PeekDataflowPlan { desc, .. }) => {
+                self.record_index_peek(desc.index_imports.keys().copied());
+            }
+            PeekPlan::FastPath(_) => {}
+        }
+
         // If we must build the view, ship the dataflow.
         let (peek_command, drop_dataflow, is_fast_path, peek_target) = match fast_path {
             PeekPlan::FastPath(FastPathPlan::PeekExisting(
@@ -600,6 +685,10 @@ impl crate::coord::Coordinator {
 
         // The peek is ready to go for both cases, fast and non-fast.
         // Stash the response mechanism, and broadcast dataflow construction.
+        let role_id = self
+            .active_conns
+            .get(&conn_id)
+            .map(|conn| *conn.authenticated_role_id());
         self.pending_peeks.insert(
             uuid,
             PendingPeek {
@@ -609,6 +698,8 @@ impl crate::coord::Coordinator {
                 depends_on: source_ids,
                 ctx_extra: std::mem::take(ctx_extra),
                 is_fast_path,
+                role_id,
+                started_at: Instant::now(),
             },
         );
         self.client_pending_peeks
@@ -636,9 +727,12 @@ impl crate::coord::Coordinator {
         let rows_rx = rows_rx.map_ok_or_else(
             |e| PeekResponseUnary::Error(e.to_string()),
             move |resp| match resp {
-                PeekResponse::Rows(rows) => match finishing.finish(rows, max_result_size) {
+                PeekResponse::Rows(rows) => match finishing
+                    .finish(rows, max_result_size)
+                    .and_then(|rows| enforce_max_result_rows(rows, max_result_rows))
+                {
                     Ok(rows) => PeekResponseUnary::Rows(rows),
-                    Err(e) => PeekResponseUnary::Error(e),
+                    Err(e) => PeekResponseUnary::ResultSizeExceeded(e),
                 },
                 PeekResponse::Canceled => PeekResponseUnary::Canceled,
                 PeekResponse::Error(e) => PeekResponseUnary::Error(e),
@@ -656,6 +750,45 @@ impl crate::coord::Coordinator {
         })
     }
 
+    /// Wraps the rows of a peek response so that the peek is canceled, and the
+    /// client receives [`PeekResponseUnary::TimedOut`], if the peek does not
+    /// complete within `timeout`. A timeout of zero disables this.
+    pub(crate) fn apply_statement_timeout(
+        &self,
+        conn_id: &ConnectionId,
+        timeout: Duration,
+        response: ExecuteResponse,
+    ) -> ExecuteResponse {
+        let ExecuteResponse::SendingRows { future } = response else {
+            return response;
+        };
+        if timeout == Duration::ZERO {
+            return ExecuteResponse::SendingRows { future };
+        }
+
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let conn_id = conn_id.clone();
+        // Start the timer now rather than when the client first polls the rows.
+        let deadline = tokio::time::sleep(timeout);
+        let future = async move {
+            tokio::select! {
+                response = future => response,
+                () = deadline => {
+                    // It is not an error for this timeout to occur after `internal_cmd_rx` has
+                    // been dropped.
+                    let result = internal_cmd_tx.send(Message::CancelPendingPeeks { conn_id });
+                    if let Err(e) = result {
+                        warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+                    }
+                    PeekResponseUnary::TimedOut
+                }
+            }
+        };
+        ExecuteResponse::SendingRows {
+            future: Box::pin(future),
+        }
+    }
+
     /// Cancel and remove all pending peeks that were initiated by the client with `conn_id`.
     #[mz_ore::instrument(level = "debug")]
     pub(crate) fn cancel_pending_peeks(&mut self, conn_id: &ConnectionId) {
@@ -685,6 +818,8 @@ impl crate::coord::Coordinator {
                 .filter_map(|(uuid, _)| self.pending_peeks.remove(uuid))
                 .collect::<Vec<_>>();
             for peek in peeks {
+                self.record_peek_usage(&peek);
+                self.trigger_query_queue_admission(peek.cluster_id);
                 self.retire_execution(StatementEndedExecutionReason::Canceled, peek.ctx_extra);
                 let _ = peek.sender.send(PeekResponse::Canceled);
             }
@@ -706,6 +841,8 @@ impl crate::coord::Coordinator {
             depends_on: _,
             ctx_extra,
             is_fast_path,
+            role_id: _,
+            started_at: _,
         }) = self.remove_pending_peek(&uuid)
         {
             let reason = match &response {
@@ -747,10 +884,32 @@ impl crate::coord::Coordinator {
             if uuids.is_empty() {
                 self.client_pending_peeks.remove(&pending_peek.conn_id);
             }
+            self.record_peek_usage(pending_peek);
+            self.trigger_query_queue_admission(pending_peek.cluster_id);
         }
         pending_peek
     }
 
+    /// Adds the execution time of a finished peek to the usage of its role on
+    /// its cluster.
+    fn record_peek_usage(&mut self, peek: &PendingPeek) {
+        let Some(role_id) = peek.role_id else {
+            return;
+        };
+        let usage = self
+            .peek_usage
+            .entry((peek.cluster_id, role_id))
+            .or_default();
+        usage.count += 1;
+        usage.time += peek.started_at.elapsed();
+    }
+
+    /// Returns the peeks that each role ran on each cluster since the last
+    /// call, and resets them.
+    pub(crate) fn take_peek_usage(&mut self) -> BTreeMap<(ClusterId, RoleId), PeekUsage> {
+        std::mem::take(&mut self.peek_usage)
+    }
+
     /// Constructs an [`ExecuteResponse`] that that will send some rows to the
     /// client immediately, as opposed to asking the dataflow layer to send along
     /// the rows after some computation.
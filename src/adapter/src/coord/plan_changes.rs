@@ -0,0 +1,191 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Detection of plan changes across restarts.
+//!
+//! The plan of every index and materialized view is recorded in the durable catalog when the
+//! object is created. During bootstrap, the coordinator optimizes these objects again and
+//! compares the new plans against the recorded ones. Objects whose plans changed, usually
+//! because of an upgrade, are reported in `mz_internal.mz_plan_changes` and get a `Plan
+//! changed` notice, so that operators can keep an eye on them before a regressed plan starts
+//! using more resources than expected.
+
+use std::collections::BTreeMap;
+
+use mz_catalog::builtin::MZ_PLAN_CHANGES;
+use mz_compute_types::dataflows::DataflowDescription;
+use mz_expr::OptimizedMirRelationExpr;
+use mz_ore::now::{to_datetime, EpochMillis};
+use mz_repr::{Datum, GlobalId, Row};
+use mz_transform::dataflow::DataflowMetainfo;
+use mz_transform::notice::PlanChanged;
+
+use crate::catalog::plan_record::PlanRecord;
+use crate::catalog::{self, BuiltinTableUpdate};
+use crate::coord::Coordinator;
+use crate::AdapterError;
+
+/// Compares the plans produced during bootstrap against the recorded ones.
+#[derive(Debug)]
+pub(crate) struct PlanChangeDetector {
+    /// The version of Materialize that produces the new plans.
+    version: String,
+    /// The time at which the new plans are produced.
+    now: EpochMillis,
+    /// Whether to raise `Plan changed` notices.
+    notices_enabled: bool,
+    /// The records read from the durable catalog.
+    previous: BTreeMap<GlobalId, PlanRecord>,
+    /// The records of the new plans.
+    records: BTreeMap<GlobalId, PlanRecord>,
+}
+
+impl PlanChangeDetector {
+    /// Records the freshly optimized `plan` of the index or materialized view `id`, and raises
+    /// a notice in `metainfo` if the plan differs from the recorded one.
+    ///
+    /// The plans of system objects are expected to change between versions and are ignored.
+    pub(crate) fn observe(
+        &mut self,
+        id: GlobalId,
+        plan: &DataflowDescription<OptimizedMirRelationExpr>,
+        metainfo: &mut DataflowMetainfo,
+    ) {
+        if !id.is_user() {
+            return;
+        }
+        let record = PlanRecord::new(self.version.clone(), plan);
+        let record = match self.previous.remove(&id) {
+            Some(previous) => previous.advance(record, self.now),
+            None => record,
+        };
+        if let (Some(change), true) = (&record.change, self.notices_enabled) {
+            metainfo.push_optimizer_notice_dedup(PlanChanged {
+                item_id: id,
+                previous_version: change.previous_version.clone(),
+                current_version: record.version.clone(),
+            });
+        }
+        self.records.insert(id, record);
+    }
+}
+
+impl Coordinator {
+    /// Returns a [`PlanChangeDetector`] seeded with the plan records from the durable catalog,
+    /// or `None` if plan change detection is disabled.
+    pub(crate) async fn plan_change_detector(
+        &self,
+    ) -> Result<Option<PlanChangeDetector>, AdapterError> {
+        let system_config = self.catalog().system_config();
+        if !system_config.enable_plan_change_detection() {
+            return Ok(None);
+        }
+        let notices_enabled = system_config.enable_notices_for_plan_changed();
+        let previous = self.catalog().get_plan_records().await?;
+        Ok(Some(PlanChangeDetector {
+            version: self.plan_record_version(),
+            now: self.now(),
+            notices_enabled,
+            previous,
+            records: BTreeMap::new(),
+        }))
+    }
+
+    /// Replaces the plan records in the durable catalog with the ones collected by `detector`,
+    /// and adds the changed plans to `mz_plan_changes`.
+    ///
+    /// Records of objects that the detector has not observed, e.g. because they were dropped,
+    /// are removed.
+    pub(crate) async fn finish_plan_change_detection(
+        &mut self,
+        detector: PlanChangeDetector,
+        builtin_table_updates: &mut Vec<BuiltinTableUpdate>,
+    ) -> Result<(), AdapterError> {
+        let table_id = self.catalog().resolve_builtin_table(&MZ_PLAN_CHANGES);
+        for (id, record) in &detector.records {
+            if let Some(row) = pack_plan_change(*id, record) {
+                builtin_table_updates.push(BuiltinTableUpdate {
+                    id: table_id,
+                    row: row.clone(),
+                    diff: 1,
+                });
+                self.plan_change_rows.insert(*id, row);
+            }
+        }
+        self.catalog_mut()
+            .set_plan_records(&detector.records)
+            .await?;
+        Ok(())
+    }
+
+    /// Returns the op that records the plan of the newly created index or materialized view
+    /// `id`, or `None` if plan change detection is disabled.
+    pub(crate) fn plan_record_op(
+        &self,
+        id: GlobalId,
+        plan: &DataflowDescription<OptimizedMirRelationExpr>,
+    ) -> Option<catalog::Op> {
+        if !self
+            .catalog()
+            .system_config()
+            .enable_plan_change_detection()
+        {
+            return None;
+        }
+        Some(catalog::Op::UpdatePlanRecord {
+            id,
+            record: PlanRecord::new(self.plan_record_version(), plan),
+        })
+    }
+
+    /// Retracts the rows of the dropped objects identified by `ids` from `mz_plan_changes`.
+    ///
+    /// Their durable records are removed on the next boot.
+    pub(crate) fn drop_plan_changes(&mut self, ids: &[GlobalId]) {
+        let table_id = self.catalog().resolve_builtin_table(&MZ_PLAN_CHANGES);
+        let updates: Vec<_> = ids
+            .iter()
+            .filter_map(|id| self.plan_change_rows.remove(id))
+            .map(|row| BuiltinTableUpdate {
+                id: table_id,
+                row,
+                diff: -1,
+            })
+            .collect();
+        if !updates.is_empty() {
+            self.builtin_table_update().background(updates);
+        }
+    }
+
+    /// Returns the version that plan records are attributed to.
+    fn plan_record_version(&self) -> String {
+        self.catalog()
+            .state()
+            .config()
+            .build_info
+            .version
+            .to_string()
+    }
+}
+
+/// Packs the row of `record` in `mz_plan_changes`, if the plan of `id` changed.
+fn pack_plan_change(id: GlobalId, record: &PlanRecord) -> Option<Row> {
+    let change = record.change.as_ref()?;
+    let detected_at = to_datetime(change.detected_at)
+        .try_into()
+        .expect("must fit");
+    Some(Row::pack_slice(&[
+        Datum::String(&id.to_string()),
+        Datum::String(&change.previous_version),
+        Datum::String(&record.version),
+        Datum::String(&change.previous_fingerprint),
+        Datum::String(&record.fingerprint),
+        Datum::TimestampTz(detected_at),
+    ]))
+}
@@ -0,0 +1,277 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Admission control for peeks on clusters with a `MAX CONCURRENT QUERIES` limit.
+//!
+//! Once a cluster runs its maximum number of concurrent peeks, new peeks wait in a per-cluster
+//! queue until a running peek completes, in the order in which they arrived. Peeks that wait
+//! longer than their session's `query_queue_timeout` are canceled. Each queue is reflected in
+//! `mz_internal.mz_cluster_query_queues`.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use mz_adapter_types::connection::ConnectionId;
+use mz_catalog::builtin::MZ_CLUSTER_QUERY_QUEUES;
+use mz_catalog::memory::objects::ClusterVariant;
+use mz_controller_types::ClusterId;
+use mz_ore::cast::CastFrom;
+use mz_ore::task;
+use mz_repr::adt::interval::Interval;
+use mz_repr::{Datum, Diff, Row};
+use uuid::Uuid;
+
+use crate::catalog::BuiltinTableUpdate;
+use crate::coord::{Coordinator, Message, PeekStageFinish};
+use crate::{AdapterError, ExecuteContext};
+
+/// The peeks waiting for admission to a cluster, along with statistics about the peeks that
+/// have passed through the queue.
+#[derive(Debug, Default)]
+pub(crate) struct QueryQueue {
+    /// The waiting peeks, in the order in which they arrived.
+    peeks: VecDeque<QueuedPeek>,
+    /// The number of queued peeks that were admitted.
+    admitted: u64,
+    /// The number of queued peeks that timed out.
+    timed_out: u64,
+    /// The total time admitted peeks spent waiting.
+    total_wait: Duration,
+    /// The longest time an admitted peek spent waiting.
+    max_wait: Duration,
+}
+
+impl QueryQueue {
+    /// Returns the number of waiting peeks.
+    pub(crate) fn len(&self) -> usize {
+        self.peeks.len()
+    }
+
+    /// Reports whether no peeks are waiting.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.peeks.is_empty()
+    }
+}
+
+/// A peek waiting for admission to a cluster.
+#[derive(Debug)]
+pub(crate) struct QueuedPeek {
+    id: Uuid,
+    enqueued_at: Instant,
+    pub(crate) ctx: ExecuteContext,
+    pub(crate) stage: PeekStageFinish,
+}
+
+impl Coordinator {
+    /// Admits a peek to its cluster, or queues it if the cluster is running its maximum number
+    /// of concurrent peeks. Returns the peek if it was admitted.
+    ///
+    /// Peeks are admitted in the order in which they arrived, so a peek is also queued if other
+    /// peeks are already waiting.
+    pub(crate) fn admit_peek(
+        &mut self,
+        ctx: ExecuteContext,
+        stage: PeekStageFinish,
+    ) -> Option<(ExecuteContext, PeekStageFinish)> {
+        let cluster_id = stage.optimizer.cluster_id();
+        let waiting = self
+            .query_queues
+            .get(&cluster_id)
+            .map_or(false, |queue| !queue.is_empty());
+        if !waiting && self.has_peek_capacity(cluster_id) {
+            return Some((ctx, stage));
+        }
+
+        let id = Uuid::new_v4();
+        let timeout = *ctx.session().vars().query_queue_timeout();
+        self.update_query_queue(cluster_id, |queue| {
+            queue.peeks.push_back(QueuedPeek {
+                id,
+                enqueued_at: Instant::now(),
+                ctx,
+                stage,
+            })
+        });
+
+        if timeout > Duration::ZERO {
+            let internal_cmd_tx = self.internal_cmd_tx.clone();
+            task::spawn(|| "query_queue_timeout", async move {
+                tokio::time::sleep(timeout).await;
+                let msg = Message::QueryQueueTimeout { cluster_id, id };
+                if internal_cmd_tx.send(msg).is_err() {
+                    // If sending fails, the main thread has shutdown.
+                }
+            });
+        }
+        None
+    }
+
+    /// Removes the next queued peek of the identified cluster, if the cluster can admit it.
+    pub(crate) fn pop_admissible_peek(&mut self, cluster_id: ClusterId) -> Option<QueuedPeek> {
+        let waiting = self
+            .query_queues
+            .get(&cluster_id)
+            .map_or(false, |queue| !queue.is_empty());
+        if !waiting || !self.has_peek_capacity(cluster_id) {
+            return None;
+        }
+
+        self.update_query_queue(cluster_id, |queue| {
+            let peek = queue.peeks.pop_front()?;
+            let wait = peek.enqueued_at.elapsed();
+            queue.admitted += 1;
+            queue.total_wait += wait;
+            queue.max_wait = std::cmp::max(queue.max_wait, wait);
+            Some(peek)
+        })
+    }
+
+    /// Asks the coordinator to admit the queued peeks of the identified cluster, e.g., because
+    /// one of its running peeks completed.
+    pub(crate) fn trigger_query_queue_admission(&self, cluster_id: ClusterId) {
+        let waiting = self
+            .query_queues
+            .get(&cluster_id)
+            .map_or(false, |queue| !queue.is_empty());
+        if waiting {
+            let msg = Message::AdmitQueuedPeeks { cluster_id };
+            if self.internal_cmd_tx.send(msg).is_err() {
+                // If sending fails, the main thread has shutdown.
+            }
+        }
+    }
+
+    /// Cancels the identified queued peek because it waited longer than its session's
+    /// `query_queue_timeout`.
+    pub(crate) fn timeout_queued_peek(&mut self, cluster_id: ClusterId, id: Uuid) {
+        let Some(queue) = self.query_queues.get(&cluster_id) else {
+            return;
+        };
+        // The peek may have been admitted or canceled in the meantime.
+        if !queue.peeks.iter().any(|peek| peek.id == id) {
+            return;
+        }
+
+        let peek = self.update_query_queue(cluster_id, |queue| {
+            queue.timed_out += 1;
+            let idx = queue.peeks.iter().position(|peek| peek.id == id)?;
+            queue.peeks.remove(idx)
+        });
+        if let Some(peek) = peek {
+            let cluster = self.catalog().get_cluster(cluster_id).name().to_string();
+            peek.ctx
+                .retire(Err(AdapterError::QueryQueueTimeout { cluster }));
+        }
+    }
+
+    /// Removes the queued peek of the identified connection, if any. There is at most one
+    /// queued peek per connection.
+    pub(crate) fn remove_queued_peek(&mut self, conn_id: &ConnectionId) -> Option<QueuedPeek> {
+        let cluster_id = self.query_queues.iter().find_map(|(cluster_id, queue)| {
+            queue
+                .peeks
+                .iter()
+                .any(|peek| peek.ctx.session().conn_id() == conn_id)
+                .then_some(*cluster_id)
+        })?;
+        self.update_query_queue(cluster_id, |queue| {
+            let idx = queue
+                .peeks
+                .iter()
+                .position(|peek| peek.ctx.session().conn_id() == conn_id)?;
+            queue.peeks.remove(idx)
+        })
+    }
+
+    /// Removes the query queue of a dropped cluster, failing its queued peeks.
+    pub(crate) fn drop_query_queue(&mut self, cluster_id: ClusterId) {
+        let Some(queue) = self.query_queues.remove(&cluster_id) else {
+            return;
+        };
+        let update = self.pack_query_queue_update(cluster_id, &queue, -1);
+        self.builtin_table_update().background(vec![update]);
+        for peek in queue.peeks {
+            peek.ctx.retire(Err(AdapterError::ChangedPlan(format!(
+                "cluster {} was removed",
+                cluster_id
+            ))));
+        }
+    }
+
+    /// Applies `f` to the query queue of the identified cluster, creating the queue if
+    /// necessary, and updates the queue's row in `mz_cluster_query_queues`.
+    fn update_query_queue<R>(
+        &mut self,
+        cluster_id: ClusterId,
+        f: impl FnOnce(&mut QueryQueue) -> R,
+    ) -> R {
+        let mut updates = Vec::new();
+        if let Some(queue) = self.query_queues.get(&cluster_id) {
+            updates.push(self.pack_query_queue_update(cluster_id, queue, -1));
+        }
+        let result = f(self.query_queues.entry(cluster_id).or_default());
+        let queue = &self.query_queues[&cluster_id];
+        updates.push(self.pack_query_queue_update(cluster_id, queue, 1));
+        self.builtin_table_update().background(updates);
+        result
+    }
+
+    /// Packs the row of a query queue in `mz_cluster_query_queues`.
+    fn pack_query_queue_update(
+        &self,
+        cluster_id: ClusterId,
+        queue: &QueryQueue,
+        diff: Diff,
+    ) -> BuiltinTableUpdate {
+        let interval = |duration: Duration| {
+            Interval::new(
+                0,
+                0,
+                i64::try_from(duration.as_micros()).expect("wait time must fit in an i64"),
+            )
+        };
+        BuiltinTableUpdate {
+            id: self
+                .catalog()
+                .resolve_builtin_table(&MZ_CLUSTER_QUERY_QUEUES),
+            row: Row::pack_slice(&[
+                Datum::String(&cluster_id.to_string()),
+                Datum::UInt64(u64::cast_from(queue.len())),
+                Datum::UInt64(queue.admitted),
+                Datum::UInt64(queue.timed_out),
+                Datum::Interval(interval(queue.total_wait)),
+                Datum::Interval(interval(queue.max_wait)),
+            ]),
+            diff,
+        }
+    }
+
+    /// Reports whether the identified cluster is running fewer than its maximum number of
+    /// concurrent peeks.
+    fn has_peek_capacity(&self, cluster_id: ClusterId) -> bool {
+        let Some(cluster) = self.catalog().try_get_cluster(cluster_id) else {
+            return true;
+        };
+        let max_concurrent_queries = match &cluster.config.variant {
+            ClusterVariant::Managed(managed) => managed.max_concurrent_queries,
+            ClusterVariant::Unmanaged => None,
+        };
+        match max_concurrent_queries {
+            Some(max) => {
+                let running = self
+                    .pending_peeks
+                    .values()
+                    .filter(|peek| peek.cluster_id == cluster_id)
+                    .count();
+                running < usize::cast_from(max)
+            }
+            None => true,
+        }
+    }
+}
@@ -0,0 +1,204 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Periodic reporting of the read holds that prevent collections from being compacted.
+//!
+//! Every `read_holds_collection_interval`, the coordinator replaces the contents of
+//! `mz_internal.mz_read_holds` with a row per read hold it knows about, together with the read
+//! and write frontiers of the held collection. Read holds are owned by a timeline, by the
+//! transaction of a session, or by a running `SUBSCRIBE`.
+
+use mz_adapter_types::connection::ConnectionIdType;
+use mz_catalog::builtin::MZ_READ_HOLDS;
+use mz_compute_types::ComputeInstanceId;
+use mz_ore::task;
+use mz_repr::{Datum, GlobalId, Row, Timestamp};
+use timely::progress::Antichain;
+
+use crate::active_compute_sink::ActiveComputeSink;
+use crate::catalog::BuiltinTableUpdate;
+use crate::coord::read_policy::ReadHolds;
+use crate::coord::{Coordinator, Message};
+
+/// The owner of a read hold.
+enum ReadHolder {
+    /// The read holds of a timeline, which keep its read timestamp valid.
+    Timeline(String),
+    /// The read holds of the transaction of a session.
+    Transaction(ConnectionIdType),
+    /// The holds of a running subscribe on its inputs.
+    Subscribe {
+        id: GlobalId,
+        session: ConnectionIdType,
+    },
+}
+
+impl ReadHolder {
+    fn holder_type(&self) -> &'static str {
+        match self {
+            ReadHolder::Timeline(_) => "timeline",
+            ReadHolder::Transaction(_) => "transaction",
+            ReadHolder::Subscribe { .. } => "subscribe",
+        }
+    }
+
+    fn holder_id(&self) -> String {
+        match self {
+            ReadHolder::Timeline(timeline) => timeline.clone(),
+            ReadHolder::Transaction(session) => session.to_string(),
+            ReadHolder::Subscribe { id, .. } => id.to_string(),
+        }
+    }
+
+    fn session(&self) -> Option<ConnectionIdType> {
+        match self {
+            ReadHolder::Timeline(_) => None,
+            ReadHolder::Transaction(session) | ReadHolder::Subscribe { session, .. } => {
+                Some(*session)
+            }
+        }
+    }
+}
+
+impl Coordinator {
+    /// Replaces the contents of `mz_read_holds` and schedules the next collection.
+    pub(crate) fn read_holds_update(&mut self) {
+        let mut rows = Vec::new();
+
+        for (timeline, state) in &self.global_timelines {
+            let holder = ReadHolder::Timeline(timeline.to_string());
+            self.pack_read_holds(&mut rows, &holder, &state.read_holds);
+        }
+
+        for (conn_id, holds) in &self.txn_read_holds {
+            let holder = ReadHolder::Transaction(conn_id.unhandled());
+            for holds in holds {
+                self.pack_read_holds(&mut rows, &holder, holds);
+            }
+        }
+
+        // A subscribe holds back its inputs at its write frontier, for as long as it runs.
+        for (id, sink) in &self.active_compute_sinks {
+            let ActiveComputeSink::Subscribe(subscribe) = sink else {
+                continue;
+            };
+            let Ok(collection) = self
+                .controller
+                .compute
+                .collection(subscribe.cluster_id, *id)
+            else {
+                continue;
+            };
+            let holder = ReadHolder::Subscribe {
+                id: *id,
+                session: subscribe.conn_id.unhandled(),
+            };
+            let time = collection.write_frontier().to_owned();
+            for dep_id in &subscribe.depends_on {
+                let cluster_id = self
+                    .controller
+                    .compute
+                    .collection(subscribe.cluster_id, *dep_id)
+                    .is_ok()
+                    .then_some(subscribe.cluster_id);
+                rows.push(self.pack_read_hold(*dep_id, cluster_id, &time, &holder));
+            }
+        }
+
+        let id = self.catalog().resolve_builtin_table(&MZ_READ_HOLDS);
+        let retractions = std::mem::replace(&mut self.read_holds_rows, rows.clone())
+            .into_iter()
+            .map(|row| BuiltinTableUpdate { id, row, diff: -1 });
+        let insertions = rows
+            .into_iter()
+            .map(|row| BuiltinTableUpdate { id, row, diff: 1 });
+        self.builtin_table_update()
+            .background(retractions.chain(insertions).collect());
+
+        self.schedule_read_holds_collection();
+    }
+
+    /// Schedules the next collection of read holds, replacing any previously scheduled
+    /// collection.
+    pub(crate) fn schedule_read_holds_collection(&mut self) {
+        let interval = self
+            .catalog()
+            .system_config()
+            .read_holds_collection_interval();
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let timer = task::spawn(|| "read_holds_collection", async move {
+            tokio::time::sleep(interval).await;
+            // If sending fails, the main thread has shutdown.
+            let _ = internal_cmd_tx.send(Message::ReadHoldsCollect);
+        });
+        self.read_holds_timer = Some(timer.abort_on_drop());
+    }
+
+    /// Packs a row of `mz_read_holds` for each collection held by `holds` into `rows`.
+    fn pack_read_holds(
+        &self,
+        rows: &mut Vec<Row>,
+        holder: &ReadHolder,
+        holds: &ReadHolds<Timestamp>,
+    ) {
+        for (time, id) in holds.storage_ids() {
+            rows.push(self.pack_read_hold(*id, None, time, holder));
+        }
+        for (cluster_id, ids) in holds.compute_ids() {
+            for (time, id) in ids {
+                rows.push(self.pack_read_hold(*id, Some(*cluster_id), time, holder));
+            }
+        }
+    }
+
+    /// Packs the row of a read hold on the collection identified by `id` in `mz_read_holds`.
+    ///
+    /// `cluster_id` identifies the cluster of compute collections, and is `None` for storage
+    /// collections.
+    fn pack_read_hold(
+        &self,
+        id: GlobalId,
+        cluster_id: Option<ComputeInstanceId>,
+        time: &Antichain<Timestamp>,
+        holder: &ReadHolder,
+    ) -> Row {
+        let (read_frontier, write_frontier) = match cluster_id {
+            Some(cluster_id) => match self.controller.compute.collection(cluster_id, id) {
+                Ok(collection) => (
+                    Some(collection.read_capability().clone()),
+                    Some(collection.write_frontier().to_owned()),
+                ),
+                Err(_) => (None, None),
+            },
+            None => match self.controller.storage.collection(id) {
+                Ok(collection) => (
+                    Some(collection.implied_capability.clone()),
+                    Some(collection.write_frontier.clone()),
+                ),
+                Err(_) => (None, None),
+            },
+        };
+        let frontier =
+            |frontier: Option<&Antichain<Timestamp>>| match frontier.and_then(|f| f.as_option()) {
+                Some(ts) => Datum::MzTimestamp(*ts),
+                None => Datum::Null,
+            };
+        let cluster_id = cluster_id.map(|id| id.to_string());
+        Row::pack_slice(&[
+            Datum::String(&id.to_string()),
+            cluster_id.as_deref().into(),
+            frontier(Some(time)),
+            Datum::String(holder.holder_type()),
+            Datum::String(&holder.holder_id()),
+            holder.session().map(Datum::UInt32).unwrap_or(Datum::Null),
+            frontier(read_frontier.as_ref()),
+            frontier(write_frontier.as_ref()),
+        ])
+    }
+}
@@ -145,6 +145,27 @@ impl<T: Eq + Hash + Ord> ReadHolds<T> {
         }
         self.holds.retain(|_, id_bundle| !id_bundle.is_empty());
     }
+
+    /// If the read hold contains a compute ID equal to `id` in compute instance `from`, moves it
+    /// to compute instance `to`.
+    pub fn move_compute_id(
+        &mut self,
+        id: &GlobalId,
+        from: ComputeInstanceId,
+        to: ComputeInstanceId,
+    ) {
+        for (_, id_bundle) in &mut self.holds {
+            let Some(compute_ids) = id_bundle.compute_ids.get_mut(&from) else {
+                continue;
+            };
+            if compute_ids.remove(id) {
+                if compute_ids.is_empty() {
+                    id_bundle.compute_ids.remove(&from);
+                }
+                id_bundle.compute_ids.entry(to).or_default().insert(*id);
+            }
+        }
+    }
 }
 
 impl crate::coord::Coordinator {
@@ -322,6 +343,27 @@ impl crate::coord::Coordinator {
         self.update_compute_base_read_policies(vec![(compute_instance, id, base_policy)])
     }
 
+    /// Moves the read holds on the compute collection `id` from compute instance `from` to
+    /// compute instance `to`, once the collection has moved between the instances.
+    ///
+    /// The read capability of the collection already accounts for the moved read holds, so it
+    /// is left untouched.
+    pub(crate) fn move_compute_read_holds(
+        &mut self,
+        id: GlobalId,
+        from: ComputeInstanceId,
+        to: ComputeInstanceId,
+    ) {
+        for TimelineState { read_holds, .. } in self.global_timelines.values_mut() {
+            read_holds.move_compute_id(&id, from, to);
+        }
+        for read_holdses in self.txn_read_holds.values_mut() {
+            for read_holds in read_holdses {
+                read_holds.move_compute_id(&id, from, to);
+            }
+        }
+    }
+
     /// Drop read policy in STORAGE for `id`.
     ///
     /// Returns true if `id` had a read policy and false otherwise.
@@ -31,6 +31,7 @@ use mz_sql::session::metadata::SessionMetadata;
 use mz_sql_parser::ast::{Raw, Statement};
 use mz_storage_types::connections::inline::IntoInlineConnection;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::oneshot;
 use tracing::{event, Instrument, Level, Span};
 
@@ -77,6 +78,14 @@ impl Coordinator {
             let responses = ExecuteResponse::generated_from(&PlanKind::from(&plan));
             ctx.tx_mut().set_allowed(responses);
 
+            // While a transaction holds the write lock, no other table writes can commit. Once it
+            // has held the lock for longer than allowed, it can only be rolled back.
+            if let Some(deadline) = ctx.session().transaction().write_lock_deadline() {
+                if Instant::now() > deadline && !matches!(plan, Plan::AbortTransaction(_)) {
+                    return ctx.retire(Err(AdapterError::WriteLockTimeout));
+                }
+            }
+
             // Scope the borrow of the Catalog because we need to mutate the Coordinator state below.
             let target_cluster = match ctx.session().transaction().cluster() {
                 // Use the current transaction's cluster.
@@ -212,6 +221,18 @@ impl Coordinator {
                         .await;
                     ctx.retire(result);
                 }
+                Plan::CreateFunction(plan) => {
+                    let result = self
+                        .sequence_create_function(ctx.session(), plan, resolved_ids)
+                        .await;
+                    ctx.retire(result);
+                }
+                Plan::CreateContinualTask(plan) => {
+                    let result = self
+                        .sequence_create_continual_task(ctx.session(), plan, resolved_ids)
+                        .await;
+                    ctx.retire(result);
+                }
                 Plan::Comment(plan) => {
                     let result = self.sequence_comment_on(ctx.session(), plan).await;
                     ctx.retire(result);
@@ -367,12 +388,20 @@ impl Coordinator {
                         .await;
                     ctx.retire(result);
                 }
+                Plan::AlterClusterReplica(plan) => {
+                    let result = self
+                        .sequence_alter_cluster_replica(ctx.session(), plan)
+                        .await;
+                    ctx.retire(result);
+                }
                 Plan::AlterConnection(plan) => {
                     self.sequence_alter_connection(ctx, plan).await;
                 }
                 Plan::AlterSetCluster(plan) => {
-                    let result = self.sequence_alter_set_cluster(ctx.session(), plan).await;
-                    ctx.retire(result);
+                    self.sequence_alter_set_cluster(ctx, plan).await;
+                }
+                Plan::AlterSinkResetOffset(plan) => {
+                    self.sequence_alter_sink_reset_offset(ctx, plan).await;
                 }
                 Plan::AlterItemRename(plan) => {
                     let result = self
@@ -409,6 +438,10 @@ impl Coordinator {
                     let result = self.sequence_alter_role(ctx.session_mut(), plan).await;
                     ctx.retire(result);
                 }
+                Plan::AlterQuota(plan) => {
+                    let result = self.sequence_alter_quota(ctx.session(), plan).await;
+                    ctx.retire(result);
+                }
                 Plan::AlterSecret(plan) => {
                     let result = self.sequence_alter_secret(ctx.session(), plan).await;
                     ctx.retire(result);
@@ -443,6 +476,10 @@ impl Coordinator {
                     self.drop_temp_items(ctx.session().conn_id()).await;
                     ctx.retire(Ok(ExecuteResponse::DiscardedTemp));
                 }
+                Plan::DiscardPlans => {
+                    ctx.session_mut().discard_plans();
+                    ctx.retire(Ok(ExecuteResponse::DiscardedPlans));
+                }
                 Plan::DiscardAll => {
                     let ret = if let TransactionStatus::Started(_) = ctx.session().transaction() {
                         self.clear_transaction(ctx.session_mut()).await;
@@ -457,7 +494,7 @@ impl Coordinator {
                     ctx.retire(ret);
                 }
                 Plan::Declare(plan) => {
-                    self.declare(ctx, plan.name, plan.stmt, plan.sql, plan.params);
+                    self.declare(ctx, plan);
                 }
                 Plan::Fetch(FetchPlan {
                     name,
@@ -7,29 +7,234 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::time::Instant;
+
+use differential_dataflow::lattice::Lattice;
+use mz_catalog::memory::error::{Error, ErrorKind};
+use mz_catalog::memory::objects::CatalogItem;
+use mz_compute_types::dataflows::DataflowDescription;
+use mz_compute_types::plan::Plan;
+use mz_controller_types::ClusterId;
+use mz_expr::OptimizedMirRelationExpr;
+use mz_repr::optimize::OverrideFrom;
+use mz_repr::GlobalId;
+use mz_sql::catalog::ObjectType;
 use mz_sql::plan::AlterSetClusterPlan;
+use mz_sql::session::metadata::SessionMetadata;
+use mz_transform::dataflow::DataflowMetainfo;
+use timely::progress::Antichain;
 
+use crate::coord::cluster_moves::PendingClusterMove;
+use crate::coord::sequencer::inner::return_if_err;
 use crate::coord::Coordinator;
+use crate::optimize::dataflows::dataflow_import_id_bundle;
+use crate::optimize::{self, Optimize, OptimizerConfig};
 use crate::session::Session;
-use crate::{AdapterError, ExecuteResponse};
+use crate::{AdapterError, ExecuteContext, TimestampProvider};
+
+/// The dataflow of an object that is moved to another cluster, optimized for that cluster.
+struct MovedDataflow {
+    optimized_plan: DataflowDescription<OptimizedMirRelationExpr>,
+    physical_plan: DataflowDescription<Plan>,
+    metainfo: DataflowMetainfo,
+}
 
 impl Coordinator {
-    /// Convert a [`AlterSetClusterPlan`] to a sequence of catalog operators and adjust state.
+    /// Moves the materialized view or index of an [`AlterSetClusterPlan`] to another cluster.
+    ///
+    /// The dataflow of the object is installed on the target cluster, next to the dataflow on its
+    /// current cluster, and the statement completes once the new dataflow is hydrated. See
+    /// [`Coordinator::check_cluster_move`].
     pub(super) async fn sequence_alter_set_cluster(
         &mut self,
-        _session: &Session,
-        AlterSetClusterPlan { id, set_cluster: _ }: AlterSetClusterPlan,
-    ) -> Result<ExecuteResponse, AdapterError> {
-        // TODO: This function needs to be implemented.
+        ctx: ExecuteContext,
+        AlterSetClusterPlan { id, set_cluster }: AlterSetClusterPlan,
+    ) {
+        return_if_err!(
+            self.validate_cluster_move(ctx.session(), id, set_cluster),
+            ctx
+        );
+        let from_cluster = self
+            .catalog()
+            .get_entry(&id)
+            .cluster_id()
+            .expect("planner only permits objects with a cluster");
+        let MovedDataflow {
+            optimized_plan,
+            physical_plan,
+            metainfo,
+        } = return_if_err!(self.optimize_moved_dataflow(id, set_cluster), ctx);
+
+        // Timestamp selection
+        let mut dataflow = physical_plan.clone();
+        let entry = self.catalog().get_entry(&id);
+        let object_type = ObjectType::from(entry.item().typ());
+        match entry.item() {
+            CatalogItem::MaterializedView(mv) => {
+                let as_of = self.bootstrap_materialized_view_as_of(&dataflow, set_cluster);
+                dataflow.set_as_of(as_of);
+                // If we have a refresh schedule that has a last refresh, then set the `until` to
+                // the last refresh.
+                let until = mv
+                    .refresh_schedule
+                    .as_ref()
+                    .and_then(|s| s.last_refresh())
+                    .and_then(|r| r.try_step_forward());
+                if let Some(until) = until {
+                    dataflow.until.meet_assign(&Antichain::from_elem(until));
+                }
+            }
+            _ => {
+                let id_bundle = dataflow_import_id_bundle(&dataflow, set_cluster);
+                let since = self.least_valid_read(&id_bundle);
+                dataflow.set_as_of(since);
+            }
+        }
+
+        // Emit notices.
+        self.emit_optimizer_notices(ctx.session(), &metainfo.optimizer_notices);
+
+        let timeout = self
+            .catalog()
+            .system_config()
+            .graceful_cluster_reconfiguration_timeout();
+        let pending = PendingClusterMove {
+            ctx,
+            object_type,
+            from_cluster,
+            to_cluster: set_cluster,
+            optimized_plan,
+            physical_plan,
+            metainfo,
+            deadline: Instant::now() + timeout,
+        };
+        self.begin_cluster_move(id, dataflow, pending);
+    }
 
-        // Satisfy Clippy that this is an async func.
-        async {}.await;
+    /// Checks that the object identified by `id` can be moved to the cluster identified by
+    /// `to_cluster`.
+    fn validate_cluster_move(
+        &self,
+        session: &Session,
+        id: GlobalId,
+        to_cluster: ClusterId,
+    ) -> Result<(), AdapterError> {
         let entry = self.catalog().get_entry(&id);
-        match entry.item().typ() {
+        match entry.item() {
+            CatalogItem::Index(_) | CatalogItem::MaterializedView(_) => {}
             _ => {
                 // Unexpected; planner permitted unsupported plan.
-                Err(AdapterError::Unsupported("ALTER SET CLUSTER"))
+                return Err(AdapterError::Unsupported("ALTER SET CLUSTER"));
             }
         }
+
+        // Only internal users can move objects to system clusters. The catalog checks this as
+        // well, but only once the object has been installed on the target cluster.
+        let cluster = self.catalog().get_cluster(to_cluster);
+        if cluster.id.is_system() && !session.user().is_internal() {
+            return Err(AdapterError::Catalog(Error::new(
+                ErrorKind::ReadOnlyCluster(cluster.name.clone()),
+            )));
+        }
+
+        if self.pending_cluster_moves.contains_key(&id) {
+            return Err(AdapterError::ChangedPlan(format!(
+                "{} is already being moved to another cluster",
+                self.catalog()
+                    .resolve_full_name(entry.name(), Some(session.conn_id()))
+            )));
+        }
+
+        // Dataflows cannot import indexes from other clusters, so indexes that other dataflows
+        // depend on must stay on their cluster.
+        let from_cluster = entry.cluster_id().expect("checked above");
+        if let Some(dependent_id) = self.index_dependent(id, from_cluster) {
+            let name = |id| {
+                let entry = self.catalog().get_entry(&id);
+                self.catalog()
+                    .resolve_full_name(entry.name(), Some(session.conn_id()))
+                    .to_string()
+            };
+            return Err(AdapterError::IndexHasDependents {
+                index: name(id),
+                dependent: name(dependent_id),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Optimizes the dataflow of the index or materialized view identified by `id` for the
+    /// cluster identified by `to_cluster`.
+    fn optimize_moved_dataflow(
+        &mut self,
+        id: GlobalId,
+        to_cluster: ClusterId,
+    ) -> Result<MovedDataflow, AdapterError> {
+        // Collect optimizer parameters.
+        let compute_instance = self
+            .instance_snapshot(to_cluster)
+            .expect("compute instance does not exist");
+        let optimizer_config = OptimizerConfig::from(self.catalog().system_config())
+            .override_from(&self.catalog().get_cluster(to_cluster).config.features());
+
+        let entry = self.catalog().get_entry(&id).clone();
+        let (optimized_plan, global_lir_plan) = match entry.item() {
+            CatalogItem::Index(idx) => {
+                // Build an optimizer for this INDEX.
+                let mut optimizer = optimize::index::Optimizer::new(
+                    self.owned_catalog(),
+                    compute_instance,
+                    id,
+                    optimizer_config,
+                );
+
+                // MIR ⇒ MIR optimization (global)
+                let index_plan = optimize::index::Index::new(entry.name(), &idx.on, &idx.keys);
+                let global_mir_plan = optimizer.optimize(index_plan)?;
+                let optimized_plan = global_mir_plan.df_desc().clone();
+
+                // MIR ⇒ LIR lowering and LIR ⇒ LIR optimization (global)
+                let global_lir_plan = optimizer.optimize(global_mir_plan)?;
+                (optimized_plan, global_lir_plan.unapply())
+            }
+            CatalogItem::MaterializedView(mv) => {
+                let internal_view_id = self.allocate_transient_id()?;
+                let debug_name = self
+                    .catalog()
+                    .resolve_full_name(entry.name(), None)
+                    .to_string();
+
+                // Build an optimizer for this MATERIALIZED VIEW.
+                let mut optimizer = optimize::materialized_view::Optimizer::new(
+                    self.owned_catalog(),
+                    compute_instance,
+                    id,
+                    internal_view_id,
+                    mv.desc.iter_names().cloned().collect(),
+                    mv.non_null_assertions.clone(),
+                    mv.refresh_schedule.clone(),
+                    mv.memory_limit,
+                    debug_name,
+                    optimizer_config,
+                );
+
+                // MIR ⇒ MIR optimization (global)
+                let global_mir_plan = optimizer.optimize(mv.optimized_expr.clone())?;
+                let optimized_plan = global_mir_plan.df_desc().clone();
+
+                // MIR ⇒ LIR lowering and LIR ⇒ LIR optimization (global)
+                let global_lir_plan = optimizer.optimize(global_mir_plan)?;
+                (optimized_plan, global_lir_plan.unapply())
+            }
+            _ => unreachable!("checked in `validate_cluster_move`"),
+        };
+        let (physical_plan, metainfo) = global_lir_plan;
+
+        Ok(MovedDataflow {
+            optimized_plan,
+            physical_plan,
+            metainfo,
+        })
     }
 }
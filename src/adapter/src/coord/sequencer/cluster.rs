@@ -10,7 +10,9 @@
 //! Coordinator functionality to sequence cluster-related plans
 
 use std::collections::BTreeSet;
+use std::time::{Duration, Instant};
 
+use chrono::{DateTime, Utc};
 use mz_adapter_types::compaction::CompactionWindow;
 use mz_catalog::memory::objects::{ClusterConfig, ClusterVariant, ClusterVariantManaged};
 use mz_compute_client::controller::ComputeReplicaConfig;
@@ -20,23 +22,33 @@ use mz_controller::clusters::{
 };
 use mz_controller_types::{ClusterId, ReplicaId, DEFAULT_REPLICA_LOGGING_INTERVAL};
 use mz_ore::cast::CastFrom;
+use mz_ore::task;
 use mz_repr::role_id::RoleId;
+use mz_sql::ast::{Ident, QualifiedReplica};
 use mz_sql::catalog::{CatalogCluster, ObjectType};
 use mz_sql::names::ObjectId;
 use mz_sql::plan::{
-    AlterClusterPlan, AlterClusterRenamePlan, AlterClusterReplicaRenamePlan, AlterClusterSwapPlan,
-    AlterOptionParameter, ComputeReplicaIntrospectionConfig, CreateClusterManagedPlan,
-    CreateClusterPlan, CreateClusterReplicaPlan, CreateClusterUnmanagedPlan, CreateClusterVariant,
-    PlanClusterOption,
+    AlterClusterPlan, AlterClusterRenamePlan, AlterClusterReplicaPlan,
+    AlterClusterReplicaRenamePlan, AlterClusterSwapPlan, AlterOptionParameter, ClusterSchedule,
+    ComputeReplicaIntrospectionConfig, CreateClusterManagedPlan, CreateClusterPlan,
+    CreateClusterReplicaPlan, CreateClusterUnmanagedPlan, CreateClusterVariant, PlanClusterOption,
 };
 use mz_sql::session::metadata::SessionMetadata;
 use mz_sql::session::vars::{SystemVars, Var, MAX_REPLICAS_PER_CLUSTER};
 
 use crate::catalog::Op;
-use crate::coord::Coordinator;
+use crate::coord::{Coordinator, Message};
 use crate::session::Session;
+use crate::util::ResultExt;
 use crate::{catalog, AdapterError, ExecuteResponse};
 
+/// The suffix of the names of replicas that were created by a graceful cluster reconfiguration
+/// and have not yet replaced the cluster's previous replicas.
+const PENDING_REPLICA_SUFFIX: &str = "-pending";
+
+/// The interval at which the hydration of pending replicas is checked.
+const CLUSTER_RECONFIGURATION_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
 impl Coordinator {
     #[mz_ore::instrument(level = "debug")]
     pub(super) async fn sequence_create_cluster(
@@ -70,6 +82,8 @@ impl Coordinator {
                     replication_factor: plan.replication_factor,
                     disk: plan.disk,
                     optimizer_feature_overrides: plan.optimizer_feature_overrides.clone(),
+                    schedule: plan.schedule.clone(),
+                    max_concurrent_queries: plan.max_concurrent_queries,
                 })
             }
             CreateClusterVariant::Unmanaged(_) => ClusterVariant::Unmanaged,
@@ -108,6 +122,8 @@ impl Coordinator {
             size,
             disk,
             optimizer_feature_overrides: _,
+            schedule,
+            max_concurrent_queries: _,
         }: CreateClusterManagedPlan,
         cluster_id: ClusterId,
         mut ops: Vec<catalog::Op>,
@@ -135,7 +151,10 @@ impl Coordinator {
             MAX_REPLICAS_PER_CLUSTER.name(),
         )?;
 
-        for replica_name in (0..replication_factor).map(managed_cluster_replica_name) {
+        // Scheduled clusters that are currently off are created without replicas.
+        let replica_count =
+            schedule.effective_replication_factor(replication_factor, self.now_datetime());
+        for replica_name in (0..replica_count).map(managed_cluster_replica_name) {
             let id = self.catalog_mut().allocate_user_replica_id().await?;
             self.create_managed_cluster_replica_op(
                 cluster_id,
@@ -579,6 +598,8 @@ impl Coordinator {
                     replication_factor: 1,
                     disk,
                     optimizer_feature_overrides: Default::default(),
+                    schedule: Default::default(),
+                    max_concurrent_queries: None,
                 });
             }
         }
@@ -592,6 +613,8 @@ impl Coordinator {
                 replication_factor,
                 disk,
                 optimizer_feature_overrides: _,
+                schedule,
+                max_concurrent_queries,
             }) => {
                 use AlterOptionParameter::*;
                 match &options.size {
@@ -629,6 +652,16 @@ impl Coordinator {
                     Reset => *replication_factor = 1,
                     Unchanged => {}
                 }
+                match &options.schedule {
+                    Set(new_schedule) => *schedule = new_schedule.clone(),
+                    Reset => *schedule = Default::default(),
+                    Unchanged => {}
+                }
+                match &options.max_concurrent_queries {
+                    Set(max) => *max_concurrent_queries = Some(*max),
+                    Reset => *max_concurrent_queries = None,
+                    Unchanged => {}
+                }
                 if !matches!(options.replicas, Unchanged) {
                     coord_bail!("Cannot change REPLICAS of managed clusters");
                 }
@@ -655,6 +688,12 @@ impl Coordinator {
                 if !matches!(options.replication_factor, Unchanged) {
                     coord_bail!("Cannot change REPLICATION FACTOR of unmanaged clusters");
                 }
+                if !matches!(options.schedule, Unchanged) {
+                    coord_bail!("Cannot change SCHEDULE of unmanaged clusters");
+                }
+                if !matches!(options.max_concurrent_queries, Unchanged) {
+                    coord_bail!("Cannot change MAX CONCURRENT QUERIES of unmanaged clusters");
+                }
             }
         }
 
@@ -662,6 +701,14 @@ impl Coordinator {
             return Ok(ExecuteResponse::AlteredObject(ObjectType::Cluster));
         }
 
+        let cluster = self.catalog.get_cluster(cluster_id);
+        if is_reconfiguring(cluster) {
+            coord_bail!(
+                "cluster {} is being reconfigured; try again once the reconfiguration completes",
+                cluster.name()
+            );
+        }
+
         match (&config.variant, new_config.variant) {
             (Managed(config), Managed(new_config)) => {
                 self.sequence_alter_cluster_managed_to_managed(
@@ -688,6 +735,9 @@ impl Coordinator {
             }
         }
 
+        // Changing `MAX CONCURRENT QUERIES` may allow queued peeks to be admitted.
+        self.trigger_query_queue_admission(cluster_id);
+
         Ok(ExecuteResponse::AlteredObject(ObjectType::Cluster))
     }
 
@@ -712,6 +762,8 @@ impl Coordinator {
                 idle_arrangement_merge_effort,
                 disk,
                 optimizer_feature_overrides: _,
+                schedule,
+                max_concurrent_queries: _,
             },
             ClusterVariantManaged {
                 size: new_size,
@@ -721,9 +773,24 @@ impl Coordinator {
                 idle_arrangement_merge_effort: new_idle_arrangement_merge_effort,
                 disk: new_disk,
                 optimizer_feature_overrides: _,
+                schedule: new_schedule,
+                max_concurrent_queries: _,
             },
         ) = (&config, &new_config);
 
+        // Scheduled clusters only have replicas while they are turned on. The current replica
+        // count is derived from the existing replicas, because the schedule may have turned the
+        // cluster on or off since it was last checked.
+        let replica_count = match schedule {
+            ClusterSchedule::Manual => *replication_factor,
+            ClusterSchedule::Cron(_) if cluster.user_replicas().next().is_some() => {
+                *replication_factor
+            }
+            ClusterSchedule::Cron(_) => 0,
+        };
+        let new_replica_count =
+            new_schedule.effective_replication_factor(*new_replication_factor, self.now_datetime());
+
         let allowed_replica_sizes = &self
             .catalog()
             .system_config()
@@ -732,6 +799,7 @@ impl Coordinator {
             .ensure_valid_replica_size(allowed_replica_sizes, new_size)?;
 
         let mut create_cluster_replicas = vec![];
+        let mut reconfiguring = false;
 
         let compute = mz_sql::plan::ComputeReplicaConfig {
             idle_arrangement_merge_effort: new_idle_arrangement_merge_effort.clone(),
@@ -765,17 +833,32 @@ impl Coordinator {
         {
             self.ensure_valid_azs(new_availability_zones.iter())?;
 
-            // tear down all replicas, create new ones
-            for name in (0..*replication_factor).map(managed_cluster_replica_name) {
-                let replica = cluster.replica_id(&name);
-                if let Some(replica) = replica {
-                    ops.push(catalog::Op::DropObject(ObjectId::ClusterReplica((
-                        cluster.id(),
-                        replica,
-                    ))))
+            // With graceful reconfiguration, the existing replicas keep serving until the new
+            // replicas are hydrated, at which point `check_cluster_reconfiguration` retires them.
+            // Otherwise, we tear down all replicas and create new ones.
+            reconfiguring = self
+                .catalog()
+                .system_config()
+                .enable_graceful_cluster_reconfiguration()
+                && cluster.replicas().next().is_some()
+                && new_replica_count > 0;
+            if !reconfiguring {
+                for name in (0..*replication_factor).map(managed_cluster_replica_name) {
+                    let replica = cluster.replica_id(&name);
+                    if let Some(replica) = replica {
+                        ops.push(catalog::Op::DropObject(ObjectId::ClusterReplica((
+                            cluster.id(),
+                            replica,
+                        ))))
+                    }
                 }
             }
-            for name in (0..*new_replication_factor).map(managed_cluster_replica_name) {
+            for name in (0..new_replica_count).map(managed_cluster_replica_name) {
+                let name = if reconfiguring {
+                    format!("{name}{PENDING_REPLICA_SUFFIX}")
+                } else {
+                    name
+                };
                 let id = self.catalog_mut().allocate_user_replica_id().await?;
                 self.create_managed_cluster_replica_op(
                     cluster_id,
@@ -790,11 +873,9 @@ impl Coordinator {
                 )?;
                 create_cluster_replicas.push((cluster_id, id))
             }
-        } else if new_replication_factor < replication_factor {
+        } else if new_replica_count < replica_count {
             // Adjust size down
-            for name in
-                (*new_replication_factor..*replication_factor).map(managed_cluster_replica_name)
-            {
+            for name in (new_replica_count..replica_count).map(managed_cluster_replica_name) {
                 let replica = cluster.replica_id(&name);
                 if let Some(replica) = replica {
                     ops.push(catalog::Op::DropObject(ObjectId::ClusterReplica((
@@ -803,11 +884,9 @@ impl Coordinator {
                     ))))
                 }
             }
-        } else if new_replication_factor > replication_factor {
+        } else if new_replica_count > replica_count {
             // Adjust size up
-            for name in
-                (*replication_factor..*new_replication_factor).map(managed_cluster_replica_name)
-            {
+            for name in (replica_count..new_replica_count).map(managed_cluster_replica_name) {
                 let id = self.catalog_mut().allocate_user_replica_id().await?;
                 self.create_managed_cluster_replica_op(
                     cluster_id,
@@ -835,6 +914,232 @@ impl Coordinator {
 
         self.catalog_transact(Some(session), ops).await?;
         self.create_cluster_replicas(&create_cluster_replicas).await;
+        if reconfiguring {
+            let timeout = self
+                .catalog()
+                .system_config()
+                .graceful_cluster_reconfiguration_timeout();
+            self.schedule_cluster_reconfiguration_check(cluster_id, Instant::now() + timeout);
+        }
+        Ok(())
+    }
+
+    /// Resumes the graceful reconfigurations of clusters that still have pending replicas, e.g.,
+    /// because `environmentd` restarted before they completed.
+    ///
+    /// The reconfiguration timeout restarts from the time of this call.
+    pub(crate) fn resume_cluster_reconfigurations(&self) {
+        let timeout = self
+            .catalog()
+            .system_config()
+            .graceful_cluster_reconfiguration_timeout();
+        let deadline = Instant::now() + timeout;
+        for cluster in self.catalog().user_clusters() {
+            if is_reconfiguring(cluster) {
+                self.schedule_cluster_reconfiguration_check(cluster.id(), deadline);
+            }
+        }
+    }
+
+    /// Schedules a [`Message::ClusterReconfigurationCheck`] for the identified cluster.
+    fn schedule_cluster_reconfiguration_check(&self, cluster_id: ClusterId, deadline: Instant) {
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "cluster_reconfiguration_check", async move {
+            tokio::time::sleep(CLUSTER_RECONFIGURATION_CHECK_INTERVAL).await;
+            let msg = Message::ClusterReconfigurationCheck {
+                cluster_id,
+                deadline,
+            };
+            if internal_cmd_tx.send(msg).is_err() {
+                // If sending fails, the main thread has shutdown.
+            }
+        });
+    }
+
+    /// Completes the graceful reconfiguration of the identified cluster, if possible.
+    ///
+    /// A reconfiguration is completed once all pending replicas are hydrated, or once `deadline`
+    /// has passed. Completing it drops the cluster's previous replicas and assigns their names to
+    /// the pending replicas. Otherwise, another check is scheduled.
+    pub(crate) async fn check_cluster_reconfiguration(
+        &mut self,
+        cluster_id: ClusterId,
+        deadline: Instant,
+    ) {
+        // The cluster may have been dropped in the meantime.
+        let Some(cluster) = self.catalog().try_get_cluster(cluster_id) else {
+            return;
+        };
+
+        let (pending, previous): (Vec<_>, Vec<_>) = cluster
+            .replicas()
+            .partition(|replica| replica.name.ends_with(PENDING_REPLICA_SUFFIX));
+        if pending.is_empty() {
+            return;
+        }
+
+        let hydrated = pending.iter().all(|replica| {
+            self.controller
+                .compute
+                .replica_hydrated(cluster_id, replica.replica_id)
+                .unwrap_or(false)
+        });
+        if !hydrated {
+            if Instant::now() < deadline {
+                self.schedule_cluster_reconfiguration_check(cluster_id, deadline);
+                return;
+            }
+            tracing::warn!(
+                %cluster_id,
+                "replicas not hydrated before the reconfiguration timeout; completing anyway"
+            );
+        }
+
+        let mut ops: Vec<_> = previous
+            .iter()
+            .map(|replica| {
+                catalog::Op::DropObject(ObjectId::ClusterReplica((cluster_id, replica.replica_id)))
+            })
+            .collect();
+        for replica in pending {
+            let to_name = replica
+                .name
+                .strip_suffix(PENDING_REPLICA_SUFFIX)
+                .expect("pending replica")
+                .to_string();
+            // Note: using unchecked here is okay because the values are coming from already
+            // existing names.
+            let name = QualifiedReplica {
+                cluster: Ident::new_unchecked(cluster.name()),
+                replica: Ident::new_unchecked(replica.name.clone()),
+            };
+            ops.push(catalog::Op::RenameClusterReplica {
+                cluster_id,
+                replica_id: replica.replica_id,
+                name,
+                to_name,
+            });
+        }
+
+        if let Err(err) = self.catalog_transact(None::<&Session>, ops).await {
+            tracing::warn!(%cluster_id, "failed to complete cluster reconfiguration: {err}");
+            self.schedule_cluster_reconfiguration_check(cluster_id, deadline);
+        }
+    }
+
+    /// Schedules the next [`Message::ClusterScheduleCheck`].
+    pub(crate) fn schedule_cluster_schedule_check(&self) {
+        let interval = self
+            .catalog()
+            .system_config()
+            .cluster_schedule_check_interval();
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "cluster_schedule_check", async move {
+            tokio::time::sleep(interval).await;
+            if internal_cmd_tx.send(Message::ClusterScheduleCheck).is_err() {
+                // If sending fails, the main thread has shutdown.
+            }
+        });
+    }
+
+    /// Turns scheduled clusters on or off, according to their schedules.
+    ///
+    /// While its schedule is on, a managed cluster with a cron schedule has one replica per its
+    /// replication factor. Otherwise, it has no replicas. Replicas are created and dropped like
+    /// any other replica of a managed cluster, so the changes show up in the audit log.
+    pub(crate) async fn check_cluster_schedules(&mut self) {
+        let scheduled: Vec<_> = self
+            .catalog()
+            .user_clusters()
+            .filter_map(|cluster| match &cluster.config.variant {
+                ClusterVariant::Managed(
+                    managed @ ClusterVariantManaged {
+                        schedule: ClusterSchedule::Cron(_),
+                        ..
+                    },
+                ) => Some((cluster.id(), managed.clone())),
+                _ => None,
+            })
+            .collect();
+
+        let now = self.now_datetime();
+        for (cluster_id, managed) in scheduled {
+            if let Err(err) = self.apply_cluster_schedule(cluster_id, &managed, now).await {
+                tracing::warn!(%cluster_id, "failed to apply cluster schedule: {err}");
+            }
+        }
+
+        self.schedule_cluster_schedule_check();
+    }
+
+    /// Creates or drops the replicas of the identified managed cluster, so that it has the
+    /// number of replicas its schedule prescribes at `now`.
+    async fn apply_cluster_schedule(
+        &mut self,
+        cluster_id: ClusterId,
+        managed: &ClusterVariantManaged,
+        now: DateTime<Utc>,
+    ) -> Result<(), AdapterError> {
+        let cluster = self.catalog().get_cluster(cluster_id);
+        // Graceful reconfigurations manage the cluster's replicas until they complete.
+        if is_reconfiguring(cluster) {
+            return Ok(());
+        }
+        let owner_id = cluster.owner_id();
+
+        let replica_count = managed
+            .schedule
+            .effective_replication_factor(managed.replication_factor, now);
+        let mut ops = vec![];
+        let mut missing = vec![];
+        for i in 0..managed.replication_factor {
+            let name = managed_cluster_replica_name(i);
+            match cluster.replica_id(&name) {
+                Some(replica_id) if i >= replica_count => {
+                    ops.push(catalog::Op::DropObject(ObjectId::ClusterReplica((
+                        cluster_id, replica_id,
+                    ))));
+                }
+                None if i < replica_count => missing.push(name),
+                _ => {}
+            }
+        }
+
+        let compute = mz_sql::plan::ComputeReplicaConfig {
+            idle_arrangement_merge_effort: managed.idle_arrangement_merge_effort,
+            introspection: managed.logging.interval.map(|interval| {
+                ComputeReplicaIntrospectionConfig {
+                    debugging: managed.logging.log_logging,
+                    interval,
+                }
+            }),
+        };
+        let mut create_cluster_replicas = vec![];
+        for name in missing {
+            let id = self.catalog_mut().allocate_user_replica_id().await?;
+            self.create_managed_cluster_replica_op(
+                cluster_id,
+                id,
+                name,
+                &compute,
+                &managed.size,
+                &mut ops,
+                if managed.availability_zones.is_empty() {
+                    None
+                } else {
+                    Some(managed.availability_zones.as_ref())
+                },
+                managed.disk,
+                owner_id,
+            )?;
+            create_cluster_replicas.push((cluster_id, id));
+        }
+
+        if ops.is_empty() {
+            return Ok(());
+        }
+        self.catalog_transact(None::<&Session>, ops).await?;
+        self.create_cluster_replicas(&create_cluster_replicas).await;
         Ok(())
     }
 
@@ -856,6 +1161,8 @@ impl Coordinator {
             idle_arrangement_merge_effort: _,
             disk: new_disk,
             optimizer_feature_overrides: _,
+            schedule: _,
+            max_concurrent_queries: _,
         } = &mut new_config;
 
         // Validate replication factor parameter
@@ -1090,8 +1397,64 @@ impl Coordinator {
             Err(err) => Err(err),
         }
     }
+
+    pub(super) async fn sequence_alter_cluster_replica(
+        &mut self,
+        session: &Session,
+        AlterClusterReplicaPlan {
+            cluster_id,
+            replica_id,
+            name,
+            introspection_debugging,
+            introspection_interval,
+        }: AlterClusterReplicaPlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        use AlterOptionParameter::*;
+
+        let replica = self.catalog().get_cluster_replica(cluster_id, replica_id);
+        let mut config = replica.config.clone();
+        let logging = &mut config.compute.logging;
+        match introspection_debugging {
+            Set(debugging) => logging.log_logging = debugging,
+            Reset => logging.log_logging = false,
+            Unchanged => {}
+        }
+        match introspection_interval {
+            Set(interval) => logging.interval = interval.0,
+            Reset => logging.interval = Some(DEFAULT_REPLICA_LOGGING_INTERVAL),
+            Unchanged => {}
+        }
+
+        let logging = config.compute.logging.clone();
+        if logging == replica.config.compute.logging {
+            return Ok(ExecuteResponse::AlteredObject(ObjectType::ClusterReplica));
+        }
+
+        let op = catalog::Op::UpdateClusterReplicaConfig {
+            cluster_id,
+            replica_id,
+            name,
+            config,
+        };
+        self.catalog_transact(Some(session), vec![op]).await?;
+
+        self.controller
+            .active_compute()
+            .update_replica_logging(cluster_id, replica_id, logging)
+            .unwrap_or_terminate("cannot fail to update replica logging");
+
+        Ok(ExecuteResponse::AlteredObject(ObjectType::ClusterReplica))
+    }
 }
 
 fn managed_cluster_replica_name(index: u32) -> String {
     format!("r{}", index + 1)
 }
+
+/// Returns whether the given cluster has pending replicas of an incomplete graceful
+/// reconfiguration.
+fn is_reconfiguring(cluster: &mz_catalog::memory::objects::Cluster) -> bool {
+    cluster
+        .replicas()
+        .any(|replica| replica.name.ends_with(PENDING_REPLICA_SUFFIX))
+}
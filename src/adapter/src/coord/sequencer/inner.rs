@@ -20,6 +20,7 @@ use itertools::Itertools;
 use maplit::{btreemap, btreeset};
 use mz_adapter_types::compaction::CompactionWindow;
 use mz_cloud_resources::VpcEndpointConfig;
+use mz_controller::clusters::{ClusterStatus, ManagedReplicaAvailabilityZones, ReplicaLocation};
 use mz_controller_types::{ClusterId, ReplicaId};
 use mz_expr::{CollectionPlan, MirScalarExpr, OptimizedMirRelationExpr, RowSetFinishing};
 
@@ -34,6 +35,7 @@ use mz_repr::explain::json::json_string;
 use mz_repr::explain::{ExplainFormat, ExprHumanizer};
 use mz_repr::role_id::RoleId;
 use mz_repr::{Datum, Diff, GlobalId, Row, RowArena, Timestamp};
+use mz_secrets::external::ExternalSecretReference;
 use mz_sql::ast::IndexOptionName;
 use mz_sql::catalog::{
     CatalogCluster, CatalogClusterReplica, CatalogDatabase, CatalogError,
@@ -47,7 +49,8 @@ use mz_sql::names::{
 // Import `plan` module, but only import select elements to avoid merge conflicts on use statements.
 use mz_adapter_types::connection::ConnectionId;
 use mz_catalog::memory::objects::{
-    CatalogItem, Cluster, Connection, DataSourceDesc, Secret, Sink, Source, Table, Type,
+    CatalogItem, Cluster, ClusterReplica, Connection, DataSourceDesc, Func, FuncDefinition, Secret,
+    Sink, Source, Table, Type,
 };
 use mz_ore::instrument;
 use mz_sql::plan::{
@@ -72,6 +75,8 @@ use mz_ssh_util::keys::SshKeyPairSet;
 use mz_storage_client::controller::{CollectionDescription, DataSource, DataSourceOther};
 use mz_storage_types::connections::inline::IntoInlineConnection;
 use mz_storage_types::controller::StorageError;
+use mz_storage_types::sinks::StorageSinkConnection;
+use mz_storage_types::sources::envelope::SourceEnvelope;
 use mz_transform::notice::{OptimizerNoticeApi, OptimizerNoticeKind, RawOptimizerNotice};
 use mz_transform::EmptyStatisticsOracle;
 use timely::progress::Antichain;
@@ -84,9 +89,10 @@ use crate::coord::appends::{Deferred, DeferredPlan, PendingWriteTxn};
 use crate::coord::id_bundle::CollectionIdBundle;
 use crate::coord::timestamp_selection::{TimestampDetermination, TimestampSource};
 use crate::coord::{
-    AlterConnectionValidationReady, Coordinator, CreateConnectionValidationReady, ExecuteContext,
-    ExplainContext, Message, PeekStage, PeekStageValidate, PendingRead, PendingReadTxn, PendingTxn,
-    PendingTxnResponse, PlanValidity, RealTimeRecencyContext, StageResult, Staged, TargetCluster,
+    AlterConnectionValidationReady, AlterSinkResetOffsetReady, Coordinator,
+    CreateConnectionValidationReady, ExecuteContext, ExplainContext, Message, PeekStage,
+    PeekStageValidate, PendingRead, PendingReadTxn, PendingTxn, PendingTxnResponse, PlanValidity,
+    RealTimeRecencyContext, StageResult, Staged, TargetCluster,
 };
 use crate::error::AdapterError;
 use crate::notice::{AdapterNotice, DroppedInUseIndex};
@@ -100,6 +106,7 @@ use crate::{
     guard_write_critical_section, PeekResponseUnary, TimelineContext, TimestampExplanation,
 };
 
+mod create_continual_task;
 mod create_index;
 mod create_materialized_view;
 mod create_view;
@@ -119,6 +126,12 @@ macro_rules! return_if_err {
 
 pub(super) use return_if_err;
 
+/// The maximum duration of read-then-write operations in sessions that do not
+/// set `statement_timeout`. These operations hold the write lock while they
+/// read, so they are bounded even when the session's statement timeout is
+/// disabled.
+const DEFAULT_READ_THEN_WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
 struct DropOps {
     ops: Vec<catalog::Op>,
     dropped_active_db: bool,
@@ -591,6 +604,7 @@ impl Coordinator {
             desc: table.desc,
             defaults: table.defaults,
             conn_id: conn_id.cloned(),
+            delete_rows_on_commit: table.delete_rows_on_commit,
             resolved_ids,
             custom_logical_compaction_window: table.compaction_window,
             is_retained_metrics_object: false,
@@ -662,7 +676,10 @@ impl Coordinator {
             if_not_exists,
         } = plan;
 
-        let payload = self.extract_secret(session, &mut secret.secret_as)?;
+        let mut payload = self.extract_secret(session, &mut secret.secret_as)?;
+        if secret.external {
+            payload = encode_external_secret(&payload)?;
+        }
 
         let id = self.catalog_mut().allocate_user_id().await?;
         let secret = Secret {
@@ -860,6 +877,29 @@ impl Coordinator {
         }
     }
 
+    #[instrument]
+    pub(super) async fn sequence_create_function(
+        &mut self,
+        session: &Session,
+        plan: plan::CreateFunctionPlan,
+        resolved_ids: ResolvedIds,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let func = Func {
+            create_sql: Some(plan.function.create_sql),
+            inner: FuncDefinition::User(Arc::new(plan.function.inner)),
+            resolved_ids,
+        };
+        let id = self.catalog_mut().allocate_user_id().await?;
+        let op = catalog::Op::CreateItem {
+            id,
+            name: plan.name,
+            item: CatalogItem::Func(func),
+            owner_id: *session.current_role_id(),
+        };
+        self.catalog_transact(Some(session), vec![op]).await?;
+        Ok(ExecuteResponse::CreatedFunction)
+    }
+
     #[instrument]
     pub(super) async fn sequence_comment_on(
         &mut self,
@@ -1733,6 +1773,9 @@ impl Coordinator {
         ),
         AdapterError,
     > {
+        if let EndTransactionAction::Rollback = action {
+            session.remove_uncommitted_held_cursors();
+        }
         let txn = self.clear_transaction(session).await;
 
         if let EndTransactionAction::Commit = action {
@@ -1752,6 +1795,19 @@ impl Coordinator {
 
                         // `rows` can be empty if, say, a DELETE's WHERE clause had 0 results.
                         writes.retain(|WriteOp { rows, .. }| !rows.is_empty());
+
+                        // The rows of `ON COMMIT DELETE ROWS` tables are deleted at commit.
+                        // Transactions cannot read their own writes, so discarding the writes
+                        // to these tables is indistinguishable from applying and deleting them.
+                        writes.retain(|WriteOp { id, .. }| {
+                            !matches!(
+                                self.catalog().get_entry(id).item(),
+                                CatalogItem::Table(Table {
+                                    delete_rows_on_commit: true,
+                                    ..
+                                })
+                            )
+                        });
                     }
                     TransactionOps::DDL {
                         ops,
@@ -1810,19 +1866,50 @@ impl Coordinator {
         &self,
         session: &Session,
         source_ids: impl Iterator<Item = GlobalId>,
-    ) -> Option<BoxFuture<'static, Timestamp>> {
+    ) -> Option<BoxFuture<'static, Result<Timestamp, StorageError>>> {
         // Ideally this logic belongs inside of
         // `mz-adapter::coord::timestamp_selection::determine_timestamp`. However, including the
         // logic in there would make it extremely difficult and inconvenient to pull the waiting off
         // of the main coord thread.
-        if session.vars().real_time_recency()
-            && session.vars().transaction_isolation() == &IsolationLevel::StrictSerializable
-            && !session.contains_read_timestamp()
+        let vars = session.vars();
+        if !vars.real_time_recency()
+            || vars.transaction_isolation() != &IsolationLevel::StrictSerializable
+            || session.contains_read_timestamp()
         {
-            Some(self.controller.recent_timestamp(source_ids))
-        } else {
-            None
+            return None;
         }
+
+        // The timestamp is determined by the sources that ingest data from external systems, which
+        // the query might only read through views or materialized views. Only user objects can
+        // depend on such sources.
+        let mut to_visit: VecDeque<_> = source_ids.filter(|id| id.is_user()).collect();
+        let mut ids = BTreeSet::new();
+        while let Some(id) = to_visit.pop_front() {
+            if !ids.insert(id) {
+                continue;
+            }
+            if let Some(entry) = self.catalog().try_get_entry(&id) {
+                to_visit.extend(entry.uses().into_iter().filter(|id| id.is_user()));
+            }
+        }
+
+        let fut = self
+            .controller
+            .recent_timestamp(ids, *vars.real_time_recency_timeout());
+        let wait_seconds = self.metrics.real_time_recency_wait_seconds.clone();
+        Some(Box::pin(async move {
+            let start = Instant::now();
+            let result = fut.await;
+            let outcome = match &result {
+                Ok(_) => "success",
+                Err(StorageError::RtrTimeout(_)) => "timeout",
+                Err(_) => "error",
+            };
+            wait_seconds
+                .with_label_values(&[outcome])
+                .observe(start.elapsed().as_secs_f64());
+            result
+        }))
     }
 
     #[instrument]
@@ -2066,11 +2153,14 @@ impl Coordinator {
             }
         }
         let respond_immediately = determination.respond_immediately();
+        let expected_wait = determination.expected_wait();
         TimestampExplanation {
             determination,
             sources,
             session_wall_time: session.pcx().wall_time,
             respond_immediately,
+            expected_wait,
+            isolation_level: session.vars().transaction_isolation().to_string(),
         }
     }
 
@@ -2376,10 +2466,8 @@ impl Coordinator {
             };
             let mut ctx = ExecuteContext::from_parts(tx, internal_cmd_tx.clone(), session, extra);
             let mut timeout_dur = *ctx.session().vars().statement_timeout();
-
-            // Timeout of 0 is equivalent to "off", meaning we will wait "forever."
             if timeout_dur == Duration::ZERO {
-                timeout_dur = Duration::MAX;
+                timeout_dur = DEFAULT_READ_THEN_WRITE_TIMEOUT;
             }
 
             let make_diffs = move |rows: Vec<Row>| -> Result<Vec<(Row, Diff)>, AdapterError> {
@@ -2435,6 +2523,10 @@ impl Coordinator {
                         Ok(res) => match res {
                             PeekResponseUnary::Rows(rows) => make_diffs(rows),
                             PeekResponseUnary::Canceled => Err(AdapterError::Canceled),
+                            PeekResponseUnary::TimedOut => Err(AdapterError::StatementTimeout),
+                            PeekResponseUnary::ResultSizeExceeded(e) => {
+                                Err(AdapterError::ResultSize(e))
+                            }
                             PeekResponseUnary::Error(e) => {
                                 Err(AdapterError::Unstructured(anyhow!(e)))
                             }
@@ -2726,6 +2818,9 @@ impl Coordinator {
                 if let Some(inherit) = attrs.inherit {
                     attributes.inherit = inherit;
                 }
+                if let Some(password) = attrs.password {
+                    attributes.password = password;
+                }
 
                 if let Some(notice) = self.should_emit_rbac_notice(session) {
                     notices.push(notice);
@@ -2786,18 +2881,59 @@ impl Coordinator {
         Ok(response)
     }
 
+    #[instrument]
+    pub(super) async fn sequence_alter_quota(
+        &mut self,
+        session: &Session,
+        plan::AlterQuotaPlan {
+            subject,
+            kind,
+            limit,
+        }: plan::AlterQuotaPlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let op = catalog::Op::AlterQuota {
+            subject,
+            kind,
+            limit,
+        };
+        self.catalog_transact(Some(session), vec![op]).await?;
+        Ok(ExecuteResponse::AlteredQuota)
+    }
+
     #[instrument]
     pub(super) async fn sequence_alter_secret(
         &mut self,
         session: &Session,
         plan: plan::AlterSecretPlan,
     ) -> Result<ExecuteResponse, AdapterError> {
-        let plan::AlterSecretPlan { id, mut secret_as } = plan;
+        let plan::AlterSecretPlan {
+            id,
+            mut secret_as,
+            external,
+        } = plan;
 
-        let payload = self.extract_secret(session, &mut secret_as)?;
+        let mut payload = self.extract_secret(session, &mut secret_as)?;
+        if external {
+            payload = encode_external_secret(&payload)?;
+        }
 
         self.secrets_controller.ensure(id, &payload).await?;
 
+        // Restart any running sources and sinks that use the secret so they
+        // observe its new value without needing to be recreated.
+        let skipped = self.restart_dependent_storage_objects(id).await;
+        if !skipped.is_empty() {
+            let sources = skipped
+                .iter()
+                .map(|id| {
+                    self.catalog()
+                        .resolve_full_name(self.catalog().get_entry(id).name(), None)
+                        .to_string()
+                })
+                .collect();
+            session.add_notice(AdapterNotice::UpsertSourcesNotRestarted { sources });
+        }
+
         Ok(ExecuteResponse::AlteredObject(ObjectType::Secret))
     }
 
@@ -3039,29 +3175,55 @@ impl Coordinator {
             _ => {}
         };
 
-        let entry = self.catalog().get_entry(&id);
+        // Planning rejects altering connections that UPSERT sources depend on,
+        // so no dependent source is skipped here.
+        let skipped = self.restart_dependent_storage_objects(id).await;
+        soft_assert_or_log!(skipped.is_empty(), "UPSERT sources depend on {id}");
+
+        Ok(ExecuteResponse::AlteredObject(ObjectType::Connection))
+    }
 
-        let mut connections = VecDeque::new();
-        connections.push_front(entry.id());
+    /// Restarts all sources and sinks that transitively depend on the
+    /// connection or secret identified by `id`, so that they pick up its
+    /// current definition.
+    ///
+    /// Returns the IDs of the `UPSERT` sources that were not restarted.
+    #[instrument]
+    async fn restart_dependent_storage_objects(&mut self, id: GlobalId) -> Vec<GlobalId> {
+        let mut dependents = VecDeque::new();
+        dependents.push_front(id);
 
         let mut sources = BTreeMap::new();
         let mut sinks = BTreeMap::new();
+        let mut skipped = Vec::new();
 
-        while let Some(id) = connections.pop_front() {
+        while let Some(id) = dependents.pop_front() {
             for id in self.catalog.get_entry(&id).used_by() {
                 let entry = self.catalog.get_entry(id);
                 match entry.item_type() {
-                    CatalogItemType::Connection => connections.push_back(*id),
+                    CatalogItemType::Connection => dependents.push_back(*id),
                     CatalogItemType::Source => {
-                        let ingestion =
-                            match &entry.source().expect("known to be source").data_source {
-                                DataSourceDesc::Ingestion(ingestion) => ingestion
-                                    .clone()
-                                    .into_inline_connection(self.catalog().state()),
-                                _ => unreachable!("only ingestions reference connections"),
-                            };
-
-                        sources.insert(*id, ingestion);
+                        match &entry.source().expect("known to be source").data_source {
+                            DataSourceDesc::Ingestion(ingestion) => {
+                                // Restarting UPSERT sources is not yet
+                                // supported (#25417); they keep running with
+                                // their current configuration.
+                                if matches!(ingestion.desc.envelope, SourceEnvelope::Upsert(_)) {
+                                    skipped.push(*id);
+                                    continue;
+                                }
+                                sources.insert(
+                                    *id,
+                                    ingestion
+                                        .clone()
+                                        .into_inline_connection(self.catalog().state()),
+                                );
+                            }
+                            // Webhook sources read secrets on each request
+                            // and have no running ingestion to restart.
+                            DataSourceDesc::Webhook { .. } => {}
+                            _ => unreachable!("only ingestions reference connections"),
+                        }
                     }
                     CatalogItemType::Sink => {
                         let export = entry.sink().expect("known to be sink");
@@ -3094,7 +3256,100 @@ impl Coordinator {
                 .expect("altering exports after txn must succeed")
         }
 
-        Ok(ExecuteResponse::AlteredObject(ObjectType::Connection))
+        skipped
+    }
+
+    #[instrument]
+    pub(super) async fn sequence_alter_sink_reset_offset(
+        &mut self,
+        ctx: ExecuteContext,
+        plan::AlterSinkResetOffsetPlan { id }: plan::AlterSinkResetOffsetPlan,
+    ) {
+        let sink = self
+            .catalog()
+            .get_entry(&id)
+            .sink()
+            .expect("known to be sink");
+        let connection = match sink
+            .connection
+            .clone()
+            .into_inline_connection(self.catalog().state())
+        {
+            StorageSinkConnection::Kafka(connection) => connection,
+            StorageSinkConnection::Iceberg(_) => {
+                return ctx.retire(Err(AdapterError::Unsupported(
+                    "ALTER SINK ... RESET OFFSET on non-Kafka sinks",
+                )));
+            }
+        };
+
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let transient_revision = self.catalog().transient_revision();
+        let conn_id = ctx.session().conn_id().clone();
+        let otel_ctx = OpenTelemetryContext::obtain();
+        let role_metadata = ctx.session().role_metadata().clone();
+        let storage_configuration = self.controller.storage.config().clone();
+
+        // Writing the reset record talks to the Kafka cluster, so do it off the coordinator's
+        // main loop, like connection validation.
+        task::spawn(
+            || format!("alter_sink_reset_offset:{conn_id}"),
+            async move {
+                let result = mz_storage_client::sink::reset_sink_progress(
+                    id,
+                    &connection,
+                    &storage_configuration,
+                )
+                .await
+                .map_err(|e| AdapterError::Unstructured(e.into()));
+
+                // It is not an error for the reset to complete after `internal_cmd_rx` is
+                // dropped.
+                let result = internal_cmd_tx.send(Message::AlterSinkResetOffsetReady(
+                    AlterSinkResetOffsetReady {
+                        ctx,
+                        result,
+                        sink_id: id,
+                        plan_validity: PlanValidity {
+                            transient_revision,
+                            dependency_ids: btreeset![id],
+                            cluster_id: None,
+                            replica_id: None,
+                            role_metadata,
+                        },
+                        otel_ctx,
+                    },
+                ));
+                if let Err(e) = result {
+                    tracing::warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+                }
+            },
+        );
+    }
+
+    /// Restarts the sink identified by `id` after its progress was reset, so that it picks up
+    /// the reset progress and starts over from its `as_of`.
+    #[instrument]
+    pub(crate) async fn sequence_alter_sink_reset_offset_finish(
+        &mut self,
+        id: GlobalId,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let sink = self
+            .catalog()
+            .get_entry(&id)
+            .sink()
+            .expect("known to be sink");
+        let connection = sink
+            .connection
+            .clone()
+            .into_inline_connection(self.catalog().state());
+        // Updating the connection of an export to the connection it already has restarts the
+        // sink.
+        self.controller
+            .storage
+            .update_export_connection(btreemap! { id => connection })
+            .await?;
+        Ok(ExecuteResponse::AlteredObject(ObjectType::Sink))
     }
 
     #[instrument]
@@ -4227,6 +4482,80 @@ impl Coordinator {
     }
 }
 
+/// Resolves the replica that should serve a peek or subscribe on `cluster`, according to the
+/// replica targeting and preference variables of the session.
+///
+/// Returns `None` if all replicas may serve the query. Otherwise, a replica that is ready is
+/// preferred over one that is not, and within those, a replica in the `preferred_availability_zone`
+/// is preferred over one that is not. Replicas in `excluded_cluster_replicas` are never selected.
+/// A replica selected by `cluster_replica` is always used, unless `cluster_replica_failover` is
+/// enabled and the replica is not ready.
+pub(super) fn resolve_target_replica(
+    cluster: &Cluster,
+    vars: &SessionVars,
+) -> Result<Option<ReplicaId>, AdapterError> {
+    let target_replica = vars
+        .cluster_replica()
+        .map(|name| {
+            cluster
+                .replica_id(name)
+                .ok_or(AdapterError::UnknownClusterReplica {
+                    cluster_name: cluster.name.clone(),
+                    replica_name: name.to_string(),
+                })
+        })
+        .transpose()?;
+
+    let is_ready = |replica: &ClusterReplica| matches!(replica.status(), ClusterStatus::Ready);
+    if let Some(replica_id) = target_replica {
+        let replica = cluster.replica(replica_id).expect("replica must exist");
+        if !vars.cluster_replica_failover() || is_ready(replica) {
+            return Ok(Some(replica_id));
+        }
+    }
+
+    let excluded = vars.excluded_cluster_replicas();
+    let zone = vars.preferred_availability_zone();
+    if target_replica.is_none() && excluded.is_empty() && zone.is_none() {
+        return Ok(None);
+    }
+
+    let in_zone = |replica: &ClusterReplica| {
+        let ReplicaLocation::Managed(location) = &replica.config.location else {
+            return false;
+        };
+        match (&location.availability_zones, zone) {
+            (ManagedReplicaAvailabilityZones::FromReplica(Some(az)), Some(zone)) => az == zone,
+            (ManagedReplicaAvailabilityZones::FromCluster(Some(azs)), Some(zone)) => {
+                !azs.is_empty() && azs.iter().all(|az| az == zone)
+            }
+            _ => false,
+        }
+    };
+    cluster
+        .replicas()
+        .filter(|replica| !excluded.iter().any(|name| name.as_str() == replica.name))
+        .max_by_key(|replica| {
+            (
+                is_ready(replica),
+                in_zone(replica),
+                Some(replica.replica_id) == target_replica,
+                std::cmp::Reverse(replica.replica_id),
+            )
+        })
+        .map(|replica| Some(replica.replica_id))
+        .ok_or_else(|| AdapterError::NoClusterReplicasAvailable(cluster.name.clone()))
+}
+
+/// Returns the contents to store in the secrets controller for the external secret referenced
+/// by `payload`, the evaluated value of `CREATE SECRET ... FROM EXTERNAL`.
+fn encode_external_secret(payload: &[u8]) -> Result<Vec<u8>, AdapterError> {
+    let reference = std::str::from_utf8(payload).expect("secrets are valid UTF-8");
+    let reference =
+        ExternalSecretReference::parse(reference).map_err(AdapterError::Unstructured)?;
+    Ok(reference.encode())
+}
+
 /// Checks whether we should emit diagnostic
 /// information associated with reading per-replica sources.
 ///
@@ -4300,6 +4629,17 @@ impl Coordinator {
                 OptimizerNoticeKind::IndexKeyEmpty => {
                     system_vars.enable_notices_for_index_empty_key()
                 }
+                OptimizerNoticeKind::UnusedIndex => system_vars.enable_notices_for_unused_index(),
+                OptimizerNoticeKind::OptimizationDidNotConverge => {
+                    system_vars.enable_notices_for_optimization_did_not_converge()
+                }
+                OptimizerNoticeKind::PlanChanged => system_vars.enable_notices_for_plan_changed(),
+                OptimizerNoticeKind::MixedClusterWorkload => {
+                    system_vars.enable_notices_for_mixed_cluster_workload()
+                }
+                OptimizerNoticeKind::ClusterSizeRecommendation => {
+                    system_vars.enable_notices_for_cluster_size_recommendation()
+                }
             };
             if notice_enabled {
                 // We don't need to redact the notice parts because
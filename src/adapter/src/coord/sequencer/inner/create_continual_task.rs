@@ -0,0 +1,392 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::BTreeMap;
+
+use differential_dataflow::lattice::Lattice;
+use mz_catalog::builtin::MZ_CONTINUAL_TASK_STATUSES;
+use mz_catalog::memory::objects::{CatalogItem, ContinualTask};
+use mz_compute_client::protocol::response::SubscribeBatch;
+use mz_compute_types::dataflows::DataflowDescription;
+use mz_compute_types::plan::Plan;
+use mz_controller_types::ClusterId;
+use mz_ore::instrument;
+use mz_repr::optimize::OverrideFrom;
+use mz_repr::{Datum, Diff, GlobalId, Row, Timestamp};
+use mz_sql::catalog::CatalogError;
+use mz_sql::names::ResolvedIds;
+use mz_sql::plan;
+use mz_sql::session::metadata::SessionMetadata;
+use timely::progress::Antichain;
+use timely::PartialOrder;
+use tracing::warn;
+
+use crate::catalog::BuiltinTableUpdate;
+use crate::command::ExecuteResponse;
+use crate::coord::appends::PendingWriteTxn;
+use crate::coord::read_policy::ReadHolds;
+use crate::coord::{ActiveContinualTask, Coordinator};
+use crate::error::AdapterError;
+use crate::optimize::dataflows::dataflow_import_id_bundle;
+use crate::optimize::{self, Optimize};
+use crate::session::{Session, WriteOp};
+use crate::util::ResultExt;
+use crate::{catalog, AdapterNotice, TimestampProvider};
+
+impl Coordinator {
+    #[instrument]
+    pub(crate) async fn sequence_create_continual_task(
+        &mut self,
+        session: &Session,
+        plan: plan::CreateContinualTaskPlan,
+        resolved_ids: ResolvedIds,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let plan::CreateContinualTaskPlan {
+            name,
+            continual_task:
+                plan::ContinualTask {
+                    create_sql,
+                    expr,
+                    target_id,
+                    cluster_id,
+                },
+            if_not_exists,
+        } = plan;
+
+        let id = self.catalog_mut().allocate_user_id().await?;
+        let continual_task = ContinualTask {
+            create_sql,
+            raw_expr: expr,
+            target_id,
+            resolved_ids,
+            cluster_id,
+        };
+
+        // Optimize the dataflow before updating the catalog, so that optimizer
+        // errors fail the statement.
+        let debug_name = self.catalog().resolve_full_name(&name, None).to_string();
+        let df_desc = self
+            .optimize_continual_task(id, &continual_task, debug_name, None)
+            .await?;
+
+        let ops = vec![catalog::Op::CreateItem {
+            id,
+            name: name.clone(),
+            item: CatalogItem::ContinualTask(continual_task),
+            owner_id: *session.current_role_id(),
+        }];
+        let transact_result = self
+            .catalog_transact_with_side_effects(Some(session), ops, |coord| async {
+                let status_update = coord
+                    .ship_continual_task(id, target_id, cluster_id, df_desc)
+                    .await;
+                coord.builtin_table_update().background(vec![status_update]);
+            })
+            .await;
+
+        match transact_result {
+            Ok(()) => Ok(ExecuteResponse::CreatedContinualTask),
+            Err(AdapterError::Catalog(mz_catalog::memory::error::Error {
+                kind:
+                    mz_catalog::memory::error::ErrorKind::Sql(CatalogError::ItemAlreadyExists(_, _)),
+            })) if if_not_exists => {
+                session.add_notice(AdapterNotice::ObjectAlreadyExists {
+                    name: name.item,
+                    ty: "continual task",
+                });
+                Ok(ExecuteResponse::CreatedContinualTask)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns the frontiers from which the continual tasks resume after a
+    /// restart, as recorded in `mz_continual_task_statuses` by the previous
+    /// process.
+    pub(crate) async fn bootstrap_continual_task_frontiers(
+        &mut self,
+    ) -> BTreeMap<GlobalId, Timestamp> {
+        let table_id = self
+            .catalog()
+            .resolve_builtin_table(&MZ_CONTINUAL_TASK_STATUSES);
+        let collection = self
+            .controller
+            .storage
+            .collection(table_id)
+            .expect("builtin tables exist");
+        // Builtin tables are reset only later during bootstrap, so the latest
+        // contents of the table are the ones written by the previous process.
+        let read_ts = collection
+            .write_frontier
+            .as_option()
+            .and_then(|upper| upper.step_back())
+            .filter(|ts| collection.read_capabilities.frontier().less_equal(ts));
+        let Some(read_ts) = read_ts else {
+            return BTreeMap::new();
+        };
+        let contents = self
+            .controller
+            .storage
+            .snapshot(table_id, read_ts)
+            .await
+            .unwrap_or_terminate("cannot fail to fetch snapshot");
+        contents
+            .into_iter()
+            .filter(|(_, diff)| *diff > 0)
+            .map(|(row, _)| {
+                let datums = row.unpack();
+                let id = datums[0]
+                    .unwrap_str()
+                    .parse()
+                    .expect("valid continual task ID");
+                (id, datums[1].unwrap_mz_timestamp())
+            })
+            .collect()
+    }
+
+    /// Optimizes the dataflow of the continual task `id`.
+    ///
+    /// A task that is resumed after a restart starts right before its
+    /// `resume_frontier`, so that all changes that the task did not append to
+    /// its target table before the restart are inserted. A new task starts at
+    /// the current read timestamp, and only the changes that happen after that
+    /// are inserted. In both cases the dataflow starts later if the inputs of
+    /// the task cannot be read at that time.
+    pub(crate) async fn optimize_continual_task(
+        &mut self,
+        id: GlobalId,
+        continual_task: &ContinualTask,
+        debug_name: String,
+        resume_frontier: Option<Timestamp>,
+    ) -> Result<DataflowDescription<Plan>, AdapterError> {
+        let ContinualTask {
+            raw_expr,
+            target_id,
+            cluster_id,
+            ..
+        } = continual_task;
+
+        // Collect optimizer parameters.
+        let compute_instance = self
+            .instance_snapshot(*cluster_id)
+            .expect("compute instance does not exist");
+        let view_id = self.allocate_transient_id()?;
+        let target_desc = self
+            .catalog()
+            .get_entry(target_id)
+            .desc_opt()
+            .expect("the target of a continual task is a table")
+            .into_owned();
+        let optimizer_config = optimize::OptimizerConfig::from(self.catalog().system_config())
+            .override_from(&self.catalog.get_cluster(*cluster_id).config.features());
+
+        // Build an optimizer for this CONTINUAL TASK.
+        let mut optimizer = optimize::continual_task::Optimizer::new(
+            self.owned_catalog(),
+            compute_instance,
+            view_id,
+            id,
+            target_desc,
+            debug_name,
+            optimizer_config,
+        );
+
+        // HIR ⇒ MIR lowering and MIR ⇒ MIR optimization (local and global)
+        let global_mir_plan = optimizer.catch_unwind_optimize(raw_expr.clone())?;
+
+        // Timestamp selection
+        let id_bundle = global_mir_plan.id_bundle(optimizer.cluster_id());
+        let mut as_of = self.least_valid_read(&id_bundle);
+        match resume_frontier {
+            Some(resume_frontier) => {
+                let resume_as_of =
+                    Antichain::from_elem(resume_frontier.step_back().unwrap_or(Timestamp::MIN));
+                if !PartialOrder::less_equal(&as_of, &resume_as_of) {
+                    warn!(
+                        %id,
+                        %resume_frontier,
+                        ?as_of,
+                        "inputs of continual task were compacted past its resume frontier, \
+                         changes before the as_of are not inserted"
+                    );
+                }
+                as_of.join_assign(&resume_as_of);
+            }
+            None => as_of.join_assign(&Antichain::from_elem(self.get_local_read_ts().await)),
+        }
+        let global_mir_plan = global_mir_plan.resolve(as_of);
+
+        // MIR ⇒ LIR lowering and LIR ⇒ LIR optimization (global)
+        let global_lir_plan = optimizer.catch_unwind_optimize(global_mir_plan)?;
+        let (df_desc, _df_meta) = global_lir_plan.unapply();
+
+        Ok(df_desc)
+    }
+
+    /// Installs the dataflow of the continual task `id` and starts appending
+    /// its output to the table `target_id`.
+    ///
+    /// Returns the update that inserts the status of the task into
+    /// `mz_continual_task_statuses`.
+    pub(crate) async fn ship_continual_task(
+        &mut self,
+        id: GlobalId,
+        target_id: GlobalId,
+        cluster_id: ClusterId,
+        df_desc: DataflowDescription<Plan>,
+    ) -> BuiltinTableUpdate {
+        let as_of = df_desc
+            .as_of
+            .as_ref()
+            .and_then(|as_of| as_of.as_option().copied())
+            .expect("continual task dataflows have an as_of");
+        // Hold back the inputs of the task, so that it can resume from the
+        // changes it has not yet appended after a restart.
+        let id_bundle = dataflow_import_id_bundle(&df_desc, cluster_id);
+        let read_holds = self
+            .acquire_read_holds(as_of, &id_bundle, false)
+            .expect("cannot fail to acquire read holds when imprecise");
+        let task = ActiveContinualTask {
+            target_id,
+            resume_frontier: as_of.step_forward(),
+            error: None,
+            read_holds,
+        };
+        let status_update = self.pack_continual_task_status_update(id, &task, 1);
+        self.continual_tasks.insert(id, task);
+        self.ship_dataflow(df_desc, cluster_id).await;
+        status_update
+    }
+
+    /// Appends a batch of updates from the dataflow of the continual task `id`
+    /// to the task's target table.
+    ///
+    /// Continual tasks are append-only, so only the insertions of the batch
+    /// are written. Retractions are ignored. If the batch contains an error,
+    /// the task stalls: it appends nothing anymore, and reports the error in
+    /// `mz_continual_task_statuses`.
+    pub(crate) fn process_continual_task_response(&mut self, id: GlobalId, batch: SubscribeBatch) {
+        let task = &self.continual_tasks[&id];
+        if task.error.is_some() {
+            return;
+        }
+        let target_id = task.target_id;
+        let (rows, frontier, error): (Vec<_>, _, _) = match batch.updates {
+            Ok(updates) => {
+                let rows = updates
+                    .into_iter()
+                    .filter(|(_, _, diff)| *diff > 0)
+                    .map(|(_, row, diff)| (row, diff))
+                    .collect();
+                (rows, batch.upper, None)
+            }
+            // Nothing at or beyond the lower of the failed batch was appended.
+            Err(error) => (Vec::new(), batch.lower, Some(error)),
+        };
+        let Some(resume_frontier) = frontier.as_option().copied() else {
+            return;
+        };
+        let has_updates = !rows.is_empty() || error.is_some();
+        let write = PendingWriteTxn::ContinualTask {
+            id,
+            write: WriteOp {
+                id: target_id,
+                rows,
+            },
+            resume_frontier,
+            error,
+        };
+        if has_updates {
+            self.submit_write(write);
+        } else {
+            // Progress is recorded by the next group commit, which happens at
+            // least every time the tables are advanced.
+            self.pending_writes.push(write);
+        }
+    }
+
+    /// Records that the continual task `id` has appended its output up to
+    /// `resume_frontier`, and that it stalled because of `error`, if any.
+    ///
+    /// Returns the updates of the task's status in `mz_continual_task_statuses`,
+    /// which must be committed along with the output of the task, or `None` if
+    /// the task has been dropped.
+    pub(crate) fn advance_continual_task(
+        &mut self,
+        id: GlobalId,
+        resume_frontier: Timestamp,
+        error: Option<String>,
+    ) -> Option<Vec<BuiltinTableUpdate>> {
+        let task = self.continual_tasks.get(&id)?;
+        // Writes of the same task may be committed out of order, so the
+        // frontier only ever advances.
+        let advanced = task.resume_frontier < resume_frontier;
+        if !advanced && error.is_none() {
+            return Some(Vec::new());
+        }
+        let retraction = self.pack_continual_task_status_update(id, task, -1);
+
+        let task = self.continual_tasks.get_mut(&id).expect("checked above");
+        if error.is_some() {
+            task.error = error;
+        }
+        if advanced {
+            task.resume_frontier = resume_frontier;
+            if let Some(read_ts) = resume_frontier.step_back() {
+                let read_holds = std::mem::replace(&mut task.read_holds, ReadHolds::new());
+                let read_holds = self.update_read_holds(read_holds, read_ts);
+                self.continual_tasks
+                    .get_mut(&id)
+                    .expect("checked above")
+                    .read_holds = read_holds;
+            }
+        }
+
+        let task = &self.continual_tasks[&id];
+        let insertion = self.pack_continual_task_status_update(id, task, 1);
+        Some(vec![retraction, insertion])
+    }
+
+    /// Forgets the dropped continual task `id`, releases its read holds, and
+    /// retracts its status from `mz_continual_task_statuses`.
+    pub(crate) fn retire_continual_task(&mut self, id: GlobalId) {
+        let Some(task) = self.continual_tasks.remove(&id) else {
+            return;
+        };
+        let retraction = self.pack_continual_task_status_update(id, &task, -1);
+        self.release_read_holds(vec![task.read_holds]);
+        self.builtin_table_update().background(vec![retraction]);
+    }
+
+    fn pack_continual_task_status_update(
+        &self,
+        id: GlobalId,
+        task: &ActiveContinualTask,
+        diff: Diff,
+    ) -> BuiltinTableUpdate {
+        let status = if task.error.is_some() {
+            "stalled"
+        } else {
+            "running"
+        };
+        let row = Row::pack_slice(&[
+            Datum::String(&id.to_string()),
+            Datum::MzTimestamp(task.resume_frontier),
+            Datum::String(status),
+            Datum::from(task.error.as_deref()),
+        ]);
+        BuiltinTableUpdate {
+            id: self
+                .catalog()
+                .resolve_builtin_table(&MZ_CONTINUAL_TASK_STATUSES),
+            row,
+            diff,
+        }
+    }
+}
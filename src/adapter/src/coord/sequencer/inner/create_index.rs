@@ -405,7 +405,7 @@ impl Coordinator {
             ..
         }: CreateIndexFinish,
     ) -> Result<StageResult<Box<CreateIndexStage>>, AdapterError> {
-        let ops = vec![catalog::Op::CreateItem {
+        let mut ops = vec![catalog::Op::CreateItem {
             id: exported_index_id,
             name: name.clone(),
             item: CatalogItem::Index(Index {
@@ -420,6 +420,7 @@ impl Coordinator {
             }),
             owner_id: *self.catalog().get_entry(&on).owner_id(),
         }];
+        ops.extend(self.plan_record_op(exported_index_id, global_mir_plan.df_desc()));
 
         // Pre-allocate a vector of transient GlobalIds for each notice.
         let notice_ids = std::iter::repeat_with(|| self.allocate_transient_id())
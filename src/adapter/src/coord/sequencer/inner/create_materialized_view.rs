@@ -383,6 +383,7 @@ impl Coordinator {
                     cluster_id,
                     non_null_assertions,
                     refresh_schedule,
+                    memory_limit,
                     ..
                 },
             ..
@@ -412,6 +413,7 @@ impl Coordinator {
             column_names.clone(),
             non_null_assertions.clone(),
             refresh_schedule.clone(),
+            *memory_limit,
             debug_name,
             optimizer_config,
         );
@@ -516,6 +518,7 @@ impl Coordinator {
                             non_null_assertions,
                             compaction_window,
                             refresh_schedule,
+                            memory_limit,
                             ..
                         },
                     drop_ids,
@@ -556,7 +559,7 @@ impl Coordinator {
             create_sql = stmt.to_ast_string_stable();
         }
 
-        let ops = itertools::chain(
+        let mut ops = itertools::chain(
             drop_ids
                 .into_iter()
                 .map(|id| catalog::Op::DropObject(ObjectId::Item(id))),
@@ -574,11 +577,13 @@ impl Coordinator {
                     custom_logical_compaction_window: compaction_window,
                     refresh_schedule,
                     initial_as_of: Some(storage_as_of.clone()),
+                    memory_limit,
                 }),
                 owner_id: *session.current_role_id(),
             }),
         )
         .collect::<Vec<_>>();
+        ops.extend(self.plan_record_op(sink_id, global_mir_plan.df_desc()));
 
         // Pre-allocate a vector of transient GlobalIds for each notice.
         let notice_ids = std::iter::repeat_with(|| self.allocate_transient_id())
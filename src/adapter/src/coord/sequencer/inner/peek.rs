@@ -26,19 +26,21 @@ use mz_sql::catalog::{CatalogCluster, SessionCatalog};
 // Import `plan` module, but only import select elements to avoid merge conflicts on use statements.
 use mz_catalog::memory::objects::CatalogItem;
 use mz_persist_client::stats::{SnapshotPartStats, SnapshotPartsStats};
-use mz_sql::plan::QueryWhen;
 use mz_sql::plan::{self, HirScalarExpr};
+use mz_sql::plan::{Plan, QueryWhen};
 use mz_sql::session::metadata::SessionMetadata;
 use mz_storage_types::stats::RelationPartStats;
 use mz_transform::EmptyStatisticsOracle;
 use tracing::Instrument;
-use tracing::{event, warn, Level};
+use tracing::{event, warn, Level, Span};
 
 use crate::active_compute_sink::{ActiveComputeSink, ActiveCopyTo};
 use crate::command::ExecuteResponse;
+use crate::coord::appends::{Deferred, DeferredPlan};
 use crate::coord::id_bundle::CollectionIdBundle;
 use crate::coord::peek::{self, PeekDataflowPlan, PeekPlan, PlannedPeek};
-use crate::coord::sequencer::inner::{check_log_reads, return_if_err};
+use crate::coord::query_queue::QueuedPeek;
+use crate::coord::sequencer::inner::{check_log_reads, resolve_target_replica, return_if_err};
 use crate::coord::timeline::TimelineContext;
 use crate::coord::timestamp_selection::{
     TimestampContext, TimestampDetermination, TimestampProvider,
@@ -52,6 +54,7 @@ use crate::coord::{
 };
 use crate::error::AdapterError;
 use crate::explain::optimizer_trace::OptimizerTrace;
+use crate::guard_write_critical_section;
 use crate::notice::AdapterNotice;
 use crate::optimize::dataflows::{prep_scalar_expr, EvalTime, ExprPrepStyle};
 use crate::optimize::{self, Optimize};
@@ -68,12 +71,43 @@ impl Coordinator {
     #[instrument]
     pub(crate) async fn sequence_peek(
         &mut self,
-        ctx: ExecuteContext,
-        plan: plan::SelectPlan,
+        mut ctx: ExecuteContext,
+        mut plan: plan::SelectPlan,
         target_cluster: TargetCluster,
     ) {
         event!(Level::TRACE, plan = format!("{:?}", plan));
 
+        // Reads in a transaction that has written to tables, including those of `UPDATE` and
+        // `DELETE`, hold the write lock until the transaction commits, so no other writes can
+        // commit between the read and the transaction's own writes, and they read the freshest
+        // table data. They cannot observe the transaction's own writes, so reads of the written
+        // tables are rejected.
+        let written_ids = ctx.session().transaction().written_ids();
+        if !written_ids.is_empty()
+            && matches!(
+                plan.when,
+                QueryWhen::Immediately | QueryWhen::FreshestTableWrite
+            )
+        {
+            let depends_on = plan.source.depends_on();
+            let written_dependency = depends_on
+                .iter()
+                .flat_map(|id| self.catalog().state().transitive_uses(*id))
+                .find(|id| written_ids.contains(id));
+            if let Some(id) = written_dependency {
+                let name = self.catalog().resolve_full_name(
+                    self.catalog().get_entry(&id).name(),
+                    Some(ctx.session().conn_id()),
+                );
+                ctx.retire(Err(AdapterError::ReadOfTableWrittenInTransaction(
+                    name.to_string(),
+                )));
+                return;
+            }
+            guard_write_critical_section!(self, ctx, Plan::Select(plan), depends_on);
+            plan.when = QueryWhen::FreshestTableWrite;
+        }
+
         self.execute_peek_stage(
             ctx,
             OpenTelemetryContext::obtain(),
@@ -252,6 +286,11 @@ impl Coordinator {
                     return;
                 }
                 Finish(stage) => {
+                    // Clusters with a `MAX CONCURRENT QUERIES` limit may queue the peek, in
+                    // which case it is finished once it is admitted.
+                    let Some((mut ctx, stage)) = self.admit_peek(ctx, stage) else {
+                        return;
+                    };
                     let res = self.peek_stage_finish(&mut ctx, stage).await;
                     ctx.retire(res);
                     return;
@@ -328,24 +367,14 @@ impl Coordinator {
             }
         };
 
-        let target_replica_name = session.vars().cluster_replica();
-        let mut target_replica = target_replica_name
-            .map(|name| {
-                cluster
-                    .replica_id(name)
-                    .ok_or(AdapterError::UnknownClusterReplica {
-                        cluster_name: cluster.name.clone(),
-                        replica_name: name.to_string(),
-                    })
-            })
-            .transpose()?;
-
         if cluster.replicas().next().is_none() {
             return Err(AdapterError::NoClusterReplicasAvailable(
                 cluster.name.clone(),
             ));
         }
 
+        let mut target_replica = resolve_target_replica(cluster, session.vars())?;
+
         let source_ids = plan.source.depends_on();
         let mut timeline_context = self.validate_timeline_context(source_ids.clone())?;
         if matches!(timeline_context, TimelineContext::TimestampIndependent)
@@ -539,132 +568,135 @@ impl Coordinator {
             .unwrap_or_else(|_| Box::new(EmptyStatisticsOracle));
         let session = ctx.session().meta();
 
+        let span = Span::current();
         mz_ore::task::spawn_blocking(
             || "optimize peek",
             move || {
-                let pipeline = || -> Result<Either<optimize::peek::GlobalLirPlan, optimize::copy_to::GlobalLirPlan>, AdapterError> {
-                    let _dispatch_guard = explain_ctx.dispatch_guard();
-
-                    let raw_expr = plan.source.clone();
-
-                    match optimizer.as_mut() {
-                        // Optimize SELECT statement.
-                        Either::Left(optimizer) => {
-                            // HIR ⇒ MIR lowering and MIR optimization (local)
-                            let local_mir_plan = optimizer.catch_unwind_optimize(raw_expr)?;
-                            // Attach resolved context required to continue the pipeline.
-                            let local_mir_plan = local_mir_plan.resolve(timestamp_context, &session, stats);
-                            // MIR optimization (global), MIR ⇒ LIR lowering, and LIR optimization (global)
-                            let global_lir_plan = optimizer.catch_unwind_optimize(local_mir_plan)?;
-
-                            Ok(Either::Left(global_lir_plan))
-                        }
-                        // Optimize COPY TO statement.
-                        Either::Right(optimizer) => {
-                            // HIR ⇒ MIR lowering and MIR optimization (local and global)
-                            let local_mir_plan = optimizer.catch_unwind_optimize(raw_expr)?;
-                            // Attach resolved context required to continue the pipeline.
-                            let local_mir_plan = local_mir_plan.resolve(timestamp_context, &session, stats);
-                            // MIR optimization (global), MIR ⇒ LIR lowering, and LIR optimization (global)
-                            let global_lir_plan = optimizer.catch_unwind_optimize(local_mir_plan)?;
-
-                            Ok(Either::Right(global_lir_plan))
+                span.in_scope(|| {
+                    let pipeline = || -> Result<Either<optimize::peek::GlobalLirPlan, optimize::copy_to::GlobalLirPlan>, AdapterError> {
+                        let _dispatch_guard = explain_ctx.dispatch_guard();
+
+                        let raw_expr = plan.source.clone();
+
+                        match optimizer.as_mut() {
+                            // Optimize SELECT statement.
+                            Either::Left(optimizer) => {
+                                // HIR ⇒ MIR lowering and MIR optimization (local)
+                                let local_mir_plan = optimizer.catch_unwind_optimize(raw_expr)?;
+                                // Attach resolved context required to continue the pipeline.
+                                let local_mir_plan = local_mir_plan.resolve(timestamp_context, &session, stats);
+                                // MIR optimization (global), MIR ⇒ LIR lowering, and LIR optimization (global)
+                                let global_lir_plan = optimizer.catch_unwind_optimize(local_mir_plan)?;
+
+                                Ok(Either::Left(global_lir_plan))
+                            }
+                            // Optimize COPY TO statement.
+                            Either::Right(optimizer) => {
+                                // HIR ⇒ MIR lowering and MIR optimization (local and global)
+                                let local_mir_plan = optimizer.catch_unwind_optimize(raw_expr)?;
+                                // Attach resolved context required to continue the pipeline.
+                                let local_mir_plan = local_mir_plan.resolve(timestamp_context, &session, stats);
+                                // MIR optimization (global), MIR ⇒ LIR lowering, and LIR optimization (global)
+                                let global_lir_plan = optimizer.catch_unwind_optimize(local_mir_plan)?;
+
+                                Ok(Either::Right(global_lir_plan))
+                            }
                         }
-                    }
-                };
-
-                let stage = match pipeline() {
-                    Ok(Either::Left(global_lir_plan)) => {
-                        let optimizer = optimizer.unwrap_left();
-                        match explain_ctx {
-                            ExplainContext::Plan(explain_ctx) => {
-                                let (_, df_meta, _) = global_lir_plan.unapply();
-                                PeekStage::ExplainPlan(PeekStageExplainPlan {
+                    };
+
+                    let stage = match pipeline() {
+                        Ok(Either::Left(global_lir_plan)) => {
+                            let optimizer = optimizer.unwrap_left();
+                            match explain_ctx {
+                                ExplainContext::Plan(explain_ctx) => {
+                                    let (_, df_meta, _) = global_lir_plan.unapply();
+                                    PeekStage::ExplainPlan(PeekStageExplainPlan {
+                                        validity,
+                                        select_id: optimizer.select_id(),
+                                        finishing: optimizer.finishing().clone(),
+                                        df_meta,
+                                        explain_ctx,
+                                    })
+                                }
+                                ExplainContext::None => PeekStage::Finish(PeekStageFinish {
                                     validity,
-                                    select_id: optimizer.select_id(),
-                                    finishing: optimizer.finishing().clone(),
-                                    df_meta,
-                                    explain_ctx,
-                                })
+                                    plan,
+                                    id_bundle,
+                                    target_replica,
+                                    source_ids,
+                                    determination,
+                                    optimizer,
+                                    global_lir_plan,
+                                }),
+                                ExplainContext::Pushdown => {
+                                    let (plan, _, _) = global_lir_plan.unapply();
+                                    let imports = match plan {
+                                        PeekPlan::SlowPath(plan) => plan
+                                            .desc
+                                            .source_imports
+                                            .into_iter()
+                                            .filter_map(|(id, (desc, _))| {
+                                                desc.arguments.operators.map(|mfp| (id, mfp))
+                                            })
+                                            .collect(),
+                                        PeekPlan::FastPath(_) => BTreeMap::default(),
+                                    };
+                                    PeekStage::ExplainPushdown(PeekStageExplainPushdown {
+                                        validity,
+                                        determination,
+                                        imports,
+                                    })
+                                }
                             }
-                            ExplainContext::None => PeekStage::Finish(PeekStageFinish {
+                        }
+                        Ok(Either::Right(global_lir_plan)) => {
+                            let optimizer = optimizer.unwrap_right();
+                            PeekStage::CopyTo(PeekStageCopyTo {
                                 validity,
-                                plan,
-                                id_bundle,
-                                target_replica,
-                                source_ids,
-                                determination,
                                 optimizer,
                                 global_lir_plan,
-                            }),
-                            ExplainContext::Pushdown => {
-                                let (plan, _, _) = global_lir_plan.unapply();
-                                let imports = match plan {
-                                    PeekPlan::SlowPath(plan) => plan
-                                        .desc
-                                        .source_imports
-                                        .into_iter()
-                                        .filter_map(|(id, (desc, _))| {
-                                            desc.arguments.operators.map(|mfp| (id, mfp))
-                                        })
-                                        .collect(),
-                                    PeekPlan::FastPath(_) => BTreeMap::default(),
-                                };
-                                PeekStage::ExplainPushdown(PeekStageExplainPushdown {
+                            })
+                        }
+                        // Internal optimizer errors are handled differently
+                        // depending on the caller.
+                        Err(err) => {
+                            let Some(optimizer) = optimizer.left() else {
+                                // In `COPY TO` contexts, immediately retire the
+                                // execution with the error.
+                                return ctx.retire(Err(err.into()));
+                            };
+                            let ExplainContext::Plan(explain_ctx) = explain_ctx else {
+                                // In `sequence_~` contexts, immediately retire the
+                                // execution with the error.
+                                return ctx.retire(Err(err.into()));
+                            };
+
+                            if explain_ctx.broken {
+                                // In `EXPLAIN BROKEN` contexts, just log the error
+                                // and move to the next stage with default
+                                // parameters.
+                                tracing::error!("error while handling EXPLAIN statement: {}", err);
+                                PeekStage::ExplainPlan(PeekStageExplainPlan {
                                     validity,
-                                    determination,
-                                    imports,
+                                    select_id: optimizer.select_id(),
+                                    finishing: optimizer.finishing().clone(),
+                                    df_meta: Default::default(),
+                                    explain_ctx,
                                 })
+                            } else {
+                                // In regular `EXPLAIN` contexts, immediately retire
+                                // the execution with the error.
+                                return ctx.retire(Err(err.into()));
                             }
                         }
-                    }
-                    Ok(Either::Right(global_lir_plan)) => {
-                        let optimizer = optimizer.unwrap_right();
-                        PeekStage::CopyTo(PeekStageCopyTo {
-                            validity,
-                            optimizer,
-                            global_lir_plan,
-                        })
-                    }
-                    // Internal optimizer errors are handled differently
-                    // depending on the caller.
-                    Err(err) => {
-                        let Some(optimizer) = optimizer.left() else {
-                            // In `COPY TO` contexts, immediately retire the
-                            // execution with the error.
-                            return ctx.retire(Err(err.into()));
-                        };
-                        let ExplainContext::Plan(explain_ctx) = explain_ctx else {
-                            // In `sequence_~` contexts, immediately retire the
-                            // execution with the error.
-                            return ctx.retire(Err(err.into()));
-                        };
-
-                        if explain_ctx.broken {
-                            // In `EXPLAIN BROKEN` contexts, just log the error
-                            // and move to the next stage with default
-                            // parameters.
-                            tracing::error!("error while handling EXPLAIN statement: {}", err);
-                            PeekStage::ExplainPlan(PeekStageExplainPlan {
-                                validity,
-                                select_id: optimizer.select_id(),
-                                finishing: optimizer.finishing().clone(),
-                                df_meta: Default::default(),
-                                explain_ctx,
-                            })
-                        } else {
-                            // In regular `EXPLAIN` contexts, immediately retire
-                            // the execution with the error.
-                            return ctx.retire(Err(err.into()));
-                        }
-                    }
-                };
+                    };
 
-                // Ignore errors if the coordinator has shut down.
-                let _ = internal_cmd_tx.send(Message::PeekStageReady {
-                    ctx,
-                    otel_ctx: root_otel_ctx,
-                    stage,
+                    // Ignore errors if the coordinator has shut down.
+                    let _ = internal_cmd_tx.send(Message::PeekStageReady {
+                        ctx,
+                        otel_ctx: root_otel_ctx,
+                        stage,
+                    });
                 });
             },
         );
@@ -735,6 +767,24 @@ impl Coordinator {
         }
     }
 
+    /// Finishes the queued peeks of the identified cluster while the cluster has capacity for
+    /// them.
+    #[instrument]
+    pub(crate) async fn admit_queued_peeks(&mut self, cluster_id: ClusterId) {
+        while let Some(QueuedPeek {
+            mut ctx, mut stage, ..
+        }) = self.pop_admissible_peek(cluster_id)
+        {
+            // The catalog may have changed while the peek was waiting.
+            if let Err(err) = stage.validity.check(self.catalog()) {
+                ctx.retire(Err(err));
+                continue;
+            }
+            let res = self.peek_stage_finish(&mut ctx, stage).await;
+            ctx.retire(res);
+        }
+    }
+
     #[instrument]
     async fn peek_stage_finish(
         &mut self,
@@ -766,6 +816,11 @@ impl Coordinator {
             source_ids,
         };
 
+        if let Some(statement_logging_id) = ctx.extra.contents() {
+            let fingerprint = planned_peek.plan.fingerprint();
+            self.set_statement_plan_fingerprint(statement_logging_id, fingerprint);
+        }
+
         if let Some(transient_index_id) = match &planned_peek.plan {
             peek::PeekPlan::FastPath(_) => None,
             peek::PeekPlan::SlowPath(PeekDataflowPlan { id, .. }) => Some(id),
@@ -818,8 +873,14 @@ impl Coordinator {
                 optimizer.cluster_id(),
                 target_replica,
                 max_query_result_size,
+                ctx.session().vars().max_query_result_rows(),
             )
             .await?;
+        let resp = self.apply_statement_timeout(
+            ctx.session().conn_id(),
+            *ctx.session().vars().statement_timeout(),
+            resp,
+        );
 
         if ctx.session().vars().emit_timestamp_notice() {
             let explanation = self.explain_timestamp(
@@ -1095,7 +1156,14 @@ impl Coordinator {
         // this that happen off thread, so no matter the kind of statement or transaction,
         // we must acquire read holds here so they are held until the off-thread work
         // returns to the coordinator.
-        if let Some(txn_reads) = self.txn_read_holds.get(session.conn_id()) {
+        // Reads in write transactions each determine their own timestamp, so they acquire their
+        // own read holds instead of being restricted to the time domain of the first read.
+        let in_write_txn = !session.transaction().written_ids().is_empty();
+        if let Some(txn_reads) = self
+            .txn_read_holds
+            .get(session.conn_id())
+            .filter(|_| !in_write_txn)
+        {
             // Transactions involving peeks will acquire read holds at most once.
             assert_eq!(txn_reads.len(), 1);
             let txn_reads = &txn_reads[0];
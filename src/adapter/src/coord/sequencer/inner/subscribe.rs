@@ -7,17 +7,27 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use mz_compute_client::protocol::response::SubscribeBatch;
+use mz_ore::cast::CastFrom;
 use mz_ore::instrument;
+use mz_ore::task;
 use mz_repr::optimize::OverrideFrom;
-use mz_sql::plan::{self, QueryWhen};
+use mz_repr::GlobalId;
+use mz_sql::plan::{self, CopyFormat, QueryWhen};
 use mz_sql::session::metadata::SessionMetadata;
 use timely::progress::Antichain;
 use tokio::sync::mpsc;
-use tracing::Span;
+use tracing::{warn, Span};
 
-use crate::active_compute_sink::{ActiveComputeSink, ActiveSubscribe};
+use crate::active_compute_sink::{
+    ActiveComputeSink, ActiveComputeSinkRetireReason, ActiveSubscribe, SharedSubscribe,
+};
 use crate::command::ExecuteResponse;
-use crate::coord::sequencer::inner::{check_log_reads, return_if_err};
+use crate::coord::peek::PeekResponseUnary;
+use crate::coord::sequencer::inner::{check_log_reads, resolve_target_replica, return_if_err};
 use crate::coord::{
     Coordinator, Message, PlanValidity, StageResult, Staged, SubscribeFinish, SubscribeOptimizeMir,
     SubscribeStage, SubscribeTimestampOptimizeLir, TargetCluster,
@@ -43,9 +53,7 @@ impl Staged for SubscribeStage {
         ctx: &mut ExecuteContext,
     ) -> Result<StageResult<Box<Self>>, AdapterError> {
         match self {
-            SubscribeStage::OptimizeMir(stage) => {
-                coord.subscribe_optimize_mir(ctx.session(), stage)
-            }
+            SubscribeStage::OptimizeMir(stage) => coord.subscribe_optimize_mir(ctx, stage).await,
             SubscribeStage::TimestampOptimizeLir(stage) => {
                 coord.subscribe_timestamp_optimize_lir(ctx, stage).await
             }
@@ -91,18 +99,7 @@ impl Coordinator {
             .resolve_target_cluster(target_cluster, session)?;
         let cluster_id = cluster.id;
 
-        let mut replica_id = session
-            .vars()
-            .cluster_replica()
-            .map(|name| {
-                cluster
-                    .replica_id(name)
-                    .ok_or(AdapterError::UnknownClusterReplica {
-                        cluster_name: cluster.name.clone(),
-                        replica_name: name.to_string(),
-                    })
-            })
-            .transpose()?;
+        let mut replica_id = resolve_target_replica(cluster, session.vars())?;
 
         // SUBSCRIBE AS OF, similar to peeks, doesn't need to worry about transaction
         // timestamp semantics.
@@ -148,15 +145,24 @@ impl Coordinator {
     }
 
     #[instrument]
-    fn subscribe_optimize_mir(
+    async fn subscribe_optimize_mir(
         &mut self,
-        session: &Session,
+        ctx: &mut ExecuteContext,
         SubscribeOptimizeMir {
             mut validity,
             plan,
             timeline,
         }: SubscribeOptimizeMir,
     ) -> Result<StageResult<Box<SubscribeStage>>, AdapterError> {
+        // Attach to a running dataflow instead of optimizing a new one, if
+        // possible.
+        if let Some(dataflow_id) = self.find_shared_subscribe(&validity, &plan) {
+            return self
+                .subscribe_attach(ctx, dataflow_id, plan, timeline)
+                .await;
+        }
+
+        let session = ctx.session();
         let plan::SubscribePlan {
             with_snapshot,
             up_to,
@@ -297,10 +303,13 @@ impl Coordinator {
             cluster_id,
             plan:
                 plan::SubscribePlan {
+                    from,
+                    with_snapshot,
+                    when,
+                    up_to,
                     copy_to,
                     emit_progress,
                     output,
-                    ..
                 },
             global_lir_plan,
         }: SubscribeFinish,
@@ -323,6 +332,23 @@ impl Coordinator {
         };
         active_subscribe.initialize();
 
+        // Let later identical subscribes attach to the new dataflow.
+        if self.catalog().system_config().enable_shared_subscribes()
+            && when == QueryWhen::Immediately
+            && up_to.is_none()
+        {
+            let max_contents_size = self.catalog().system_config().max_shared_subscribe_size();
+            let shared = SharedSubscribe::new(
+                sink_id,
+                &active_subscribe,
+                validity.replica_id,
+                from,
+                with_snapshot,
+                usize::cast_from(max_contents_size),
+            );
+            self.shared_subscribes.insert(sink_id, shared);
+        }
+
         let (df_desc, df_meta) = global_lir_plan.unapply();
         // Emit notices.
         self.emit_optimizer_notices(ctx.session(), &df_meta.optimizer_notices);
@@ -357,17 +383,207 @@ impl Coordinator {
                 .unwrap_or_terminate("cannot fail to set subscribe target replica");
         }
 
+        let resp = self.subscribe_response(ctx, sink_id, rx, copy_to);
+        Ok(StageResult::Response(resp))
+    }
+
+    /// Attaches a subscribe to the running dataflow of the identified shared
+    /// subscribe.
+    #[instrument]
+    async fn subscribe_attach(
+        &mut self,
+        ctx: &mut ExecuteContext,
+        dataflow_id: GlobalId,
+        plan: plan::SubscribePlan,
+        timeline: TimelineContext,
+    ) -> Result<StageResult<Box<SubscribeStage>>, AdapterError> {
+        let plan::SubscribePlan {
+            when,
+            copy_to,
+            emit_progress,
+            output,
+            ..
+        } = plan;
+
+        // The subscribe must not start before the timestamp it would have
+        // chosen for a dataflow of its own.
+        let oracle_read_ts = self.oracle_read_ts(ctx.session(), &timeline, &when).await;
+        let sink_id = self.allocate_transient_id()?;
+        let shared = &self.shared_subscribes[&dataflow_id];
+        let as_of = shared.attach_as_of(oracle_read_ts);
+        // If the dataflow has already passed the `as_of`, the subscribe
+        // receives its snapshot right away. Otherwise, it waits for the
+        // dataflow to catch up.
+        let snapshot = (!shared.upper.less_equal(&as_of)).then(|| shared.snapshot(as_of));
+        let cluster_id = shared.cluster_id;
+        let arity = shared.arity;
+        let depends_on = shared.depends_on.clone();
+
+        if let Some(id) = ctx.extra().contents() {
+            self.set_statement_execution_timestamp(id, as_of);
+        }
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut active_subscribe = ActiveSubscribe {
+            conn_id: ctx.session().conn_id().clone(),
+            channel: tx,
+            emit_progress,
+            as_of,
+            arity,
+            cluster_id,
+            depends_on,
+            start_time: self.now(),
+            output,
+        };
+        active_subscribe.initialize();
+
+        let shared = self
+            .shared_subscribes
+            .get_mut(&dataflow_id)
+            .expect("known to exist");
+        shared.subscribers.insert(sink_id);
+        match snapshot {
+            Some(snapshot) => {
+                active_subscribe.process_response(snapshot);
+            }
+            None => {
+                shared.pending.insert(sink_id);
+            }
+        }
+
+        self.add_active_compute_sink(sink_id, ActiveComputeSink::Subscribe(active_subscribe))
+            .await
+            .await;
+
+        let resp = self.subscribe_response(ctx, sink_id, rx, copy_to);
+        Ok(StageResult::Response(resp))
+    }
+
+    /// Returns the ID of a running dataflow to which the subscribe described by
+    /// `validity` and `plan` can attach, if any.
+    fn find_shared_subscribe(
+        &self,
+        validity: &PlanValidity,
+        plan: &plan::SubscribePlan,
+    ) -> Option<GlobalId> {
+        if !self.catalog().system_config().enable_shared_subscribes()
+            || plan.when != QueryWhen::Immediately
+            || plan.up_to.is_some()
+        {
+            return None;
+        }
+        let cluster_id = validity.cluster_id?;
+        self.shared_subscribes
+            .iter()
+            .find(|(_, shared)| {
+                shared.matches(
+                    cluster_id,
+                    validity.replica_id,
+                    &plan.from,
+                    plan.with_snapshot,
+                )
+            })
+            .map(|(dataflow_id, _)| *dataflow_id)
+    }
+
+    /// Sends a batch of updates from a shared subscribe dataflow to all the
+    /// subscribes it serves.
+    pub(crate) async fn process_shared_subscribe_response(
+        &mut self,
+        dataflow_id: GlobalId,
+        batch: SubscribeBatch,
+    ) {
+        let shared = self
+            .shared_subscribes
+            .get_mut(&dataflow_id)
+            .expect("known to exist");
+        let mut finished = BTreeMap::new();
+        for sink_id in shared.subscribers.clone() {
+            let Some(ActiveComputeSink::Subscribe(active_subscribe)) =
+                self.active_compute_sinks.get_mut(&sink_id)
+            else {
+                continue;
+            };
+            let batch = if shared.pending.contains(&sink_id) {
+                match shared.catch_up(active_subscribe.as_of, &batch) {
+                    Some(batch) => {
+                        shared.pending.remove(&sink_id);
+                        batch
+                    }
+                    None => continue,
+                }
+            } else {
+                batch.clone()
+            };
+            if active_subscribe.process_response(batch) {
+                finished.insert(sink_id, ActiveComputeSinkRetireReason::Finished);
+            }
+        }
+        shared.absorb(&batch);
+        self.retire_compute_sinks(finished).await;
+    }
+
+    /// Detaches the identified subscribe from its shared dataflow, if any.
+    ///
+    /// Returns the ID of the dataflow to drop along with the subscribe, which
+    /// is `None` if the dataflow still serves other subscribes.
+    pub(crate) fn detach_shared_subscribe(&mut self, sink_id: GlobalId) -> Option<GlobalId> {
+        let Some((dataflow_id, shared)) = self
+            .shared_subscribes
+            .iter_mut()
+            .find(|(_, shared)| shared.subscribers.contains(&sink_id))
+        else {
+            return Some(sink_id);
+        };
+        shared.subscribers.remove(&sink_id);
+        shared.pending.remove(&sink_id);
+        if !shared.subscribers.is_empty() {
+            return None;
+        }
+        let dataflow_id = *dataflow_id;
+        self.shared_subscribes.remove(&dataflow_id);
+        Some(dataflow_id)
+    }
+
+    /// Arranges for the identified subscribe to be canceled once it exceeds
+    /// the session's statement timeout and returns the response to the
+    /// client.
+    fn subscribe_response(
+        &mut self,
+        ctx: &mut ExecuteContext,
+        sink_id: GlobalId,
+        rx: mpsc::UnboundedReceiver<PeekResponseUnary>,
+        copy_to: Option<CopyFormat>,
+    ) -> ExecuteResponse {
+        // Cancel the subscribe once it exceeds the session's statement timeout.
+        let statement_timeout = *ctx.session().vars().statement_timeout();
+        if statement_timeout != Duration::ZERO {
+            let internal_cmd_tx = self.internal_cmd_tx.clone();
+            task::spawn(
+                || format!("subscribe_statement_timeout:{sink_id}"),
+                async move {
+                    tokio::time::sleep(statement_timeout).await;
+                    // It is not an error for this timeout to occur after `internal_cmd_rx` has been
+                    // dropped.
+                    let result =
+                        internal_cmd_tx.send(Message::SubscribeStatementTimeout { sink_id });
+                    if let Err(e) = result {
+                        warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+                    }
+                },
+            );
+        }
+
         let resp = ExecuteResponse::Subscribing {
             rx,
             ctx_extra: std::mem::take(ctx.extra_mut()),
         };
-        let resp = match copy_to {
+        match copy_to {
             None => resp,
             Some(format) => ExecuteResponse::CopyTo {
                 format,
                 resp: Box::new(resp),
             },
-        };
-        Ok(StageResult::Response(resp))
+        }
     }
 }
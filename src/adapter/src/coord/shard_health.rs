@@ -0,0 +1,102 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Periodic collection of the health of persist shards.
+//!
+//! Every `persist_shard_health_collection_interval`, the coordinator fetches the most recent
+//! state of every live shard and replaces the contents of `mz_internal.mz_persist_shard_health`
+//! with a summary of each shard's physical layout and compaction backlog.
+
+use std::collections::BTreeMap;
+
+use mz_catalog::builtin::MZ_PERSIST_SHARD_HEALTH;
+use mz_ore::cast::CastFrom;
+use mz_ore::task;
+use mz_persist_client::usage::ShardHealth;
+use mz_persist_client::ShardId;
+use mz_repr::{Datum, Row, Timestamp};
+use timely::progress::Antichain;
+use tracing::warn;
+
+use crate::catalog::BuiltinTableUpdate;
+use crate::coord::{Coordinator, Message};
+
+impl Coordinator {
+    /// Spawns a task to fetch the health of all live shards.
+    pub(crate) fn shard_health_fetch(&self) {
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let client = self.storage_usage_client.clone();
+        let live_shards = self.live_shards();
+
+        task::spawn(|| "shard_health_fetch", async move {
+            let health = client.shards_health(live_shards).await;
+            // It is not an error for shard health to become ready after `internal_cmd_rx` is
+            // dropped.
+            if let Err(e) = internal_cmd_tx.send(Message::ShardHealthUpdate(health)) {
+                warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+            }
+        });
+    }
+
+    /// Replaces the contents of `mz_persist_shard_health` and schedules the next collection.
+    pub(crate) fn shard_health_update(&mut self, health: BTreeMap<ShardId, ShardHealth>) {
+        let id = self
+            .catalog()
+            .resolve_builtin_table(&MZ_PERSIST_SHARD_HEALTH);
+        let rows: Vec<_> = health
+            .iter()
+            .map(|(shard_id, health)| pack_shard_health(shard_id, health))
+            .collect();
+        let retractions = std::mem::replace(&mut self.shard_health_rows, rows.clone())
+            .into_iter()
+            .map(|row| BuiltinTableUpdate { id, row, diff: -1 });
+        let insertions = rows
+            .into_iter()
+            .map(|row| BuiltinTableUpdate { id, row, diff: 1 });
+        self.builtin_table_update()
+            .background(retractions.chain(insertions).collect());
+
+        self.schedule_shard_health_collection();
+    }
+
+    /// Schedules the next collection of shard health, replacing any previously scheduled
+    /// collection.
+    pub(crate) fn schedule_shard_health_collection(&mut self) {
+        let interval = self
+            .catalog()
+            .system_config()
+            .persist_shard_health_collection_interval();
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let timer = task::spawn(|| "shard_health_collection", async move {
+            tokio::time::sleep(interval).await;
+            // If sending fails, the main thread has shutdown.
+            let _ = internal_cmd_tx.send(Message::ShardHealthFetch);
+        });
+        self.shard_health_timer = Some(timer.abort_on_drop());
+    }
+}
+
+/// Packs the row of a shard in `mz_persist_shard_health`.
+fn pack_shard_health(shard_id: &ShardId, health: &ShardHealth) -> Row {
+    let frontier = |frontier: &Antichain<u64>| match frontier.as_option() {
+        Some(ts) => Datum::MzTimestamp(Timestamp::from(*ts)),
+        None => Datum::Null,
+    };
+    Row::pack_slice(&[
+        Datum::String(&shard_id.to_string()),
+        frontier(&health.since),
+        frontier(&health.upper),
+        Datum::UInt64(u64::cast_from(health.batch_count)),
+        Datum::UInt64(u64::cast_from(health.spine_batch_count)),
+        Datum::UInt64(u64::cast_from(health.part_count)),
+        Datum::UInt64(health.size_bytes),
+        Datum::UInt64(health.uncompacted_bytes),
+        Datum::UInt64(u64::cast_from(health.compaction_backlog)),
+    ])
+}
@@ -0,0 +1,94 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Recording of coordinator messages that were slow to handle.
+//!
+//! Every message whose handling takes longer than `coord_slow_message_report_threshold` is
+//! recorded in `mz_internal.mz_coordinator_slow_messages`. Only the most recent
+//! [`MAX_SLOW_MESSAGES`] are retained, and the table is emptied whenever the coordinator restarts.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use mz_adapter_types::connection::ConnectionIdType;
+use mz_catalog::builtin::MZ_COORDINATOR_SLOW_MESSAGES;
+use mz_ore::now::{to_datetime, EpochMillis};
+use mz_repr::adt::interval::Interval;
+use mz_repr::{Datum, Row};
+use mz_sql::ast::{Raw, Statement};
+use mz_sql_parser::ast::display::AstDisplay;
+
+use crate::catalog::BuiltinTableUpdate;
+use crate::coord::Coordinator;
+
+/// The maximum number of slow messages retained in `mz_coordinator_slow_messages`.
+pub(crate) const MAX_SLOW_MESSAGES: usize = 1000;
+
+/// A coordinator message whose handling exceeded `coord_slow_message_report_threshold`.
+#[derive(Debug)]
+pub(crate) struct SlowMessage {
+    /// The kind of the message, as reported by `Message::kind`.
+    pub kind: &'static str,
+    /// When the coordinator began handling the message.
+    pub began_at: EpochMillis,
+    /// How long the coordinator took to handle the message.
+    pub duration: Duration,
+    /// The session that sent the message, if it is a command.
+    pub session_id: Option<ConnectionIdType>,
+    /// The statement the message executes, if any.
+    pub stmt: Option<Arc<Statement<Raw>>>,
+    /// The OpenTelemetry trace id of the handling of the message, if tracing is enabled.
+    pub trace_id: Option<String>,
+}
+
+impl Coordinator {
+    /// Records `message` in `mz_coordinator_slow_messages`, evicting the oldest recorded messages
+    /// beyond [`MAX_SLOW_MESSAGES`].
+    pub(crate) fn record_slow_message(&mut self, message: SlowMessage) {
+        let id = self
+            .catalog()
+            .resolve_builtin_table(&MZ_COORDINATOR_SLOW_MESSAGES);
+        let row = pack_slow_message(&message);
+        let mut updates = vec![BuiltinTableUpdate {
+            id,
+            row: row.clone(),
+            diff: 1,
+        }];
+        self.slow_message_rows.push_back(row);
+        while self.slow_message_rows.len() > MAX_SLOW_MESSAGES {
+            let row = self
+                .slow_message_rows
+                .pop_front()
+                .expect("length checked above");
+            updates.push(BuiltinTableUpdate { id, row, diff: -1 });
+        }
+        self.builtin_table_update().background(updates);
+    }
+}
+
+/// Packs the row of a slow message in `mz_coordinator_slow_messages`.
+fn pack_slow_message(message: &SlowMessage) -> Row {
+    let duration = Interval::new(
+        0,
+        0,
+        i64::try_from(message.duration.as_micros()).expect("duration must fit in an i64"),
+    );
+    let redacted_sql = message
+        .stmt
+        .as_ref()
+        .map(|stmt| stmt.to_ast_string_redacted());
+    Row::pack_slice(&[
+        Datum::TimestampTz(to_datetime(message.began_at).try_into().expect("must fit")),
+        Datum::String(message.kind),
+        Datum::Interval(duration),
+        message.session_id.map(Datum::UInt32).unwrap_or(Datum::Null),
+        redacted_sql.as_deref().into(),
+        message.trace_id.as_deref().into(),
+    ])
+}
@@ -14,7 +14,7 @@ use mz_adapter_types::connection::ConnectionId;
 use mz_ore::now::EpochMillis;
 use mz_repr::{GlobalId, ScalarType};
 use mz_sql::names::{Aug, ResolvedIds};
-use mz_sql::plan::{Params, StatementDesc};
+use mz_sql::plan::{DeclarePlan, StatementDesc};
 use mz_sql::session::metadata::SessionMetadata;
 use mz_sql_parser::ast::{Raw, Statement};
 
@@ -22,7 +22,7 @@ use crate::active_compute_sink::{ActiveComputeSink, ActiveComputeSinkRetireReaso
 use crate::catalog::Catalog;
 use crate::coord::appends::BuiltinTableAppendNotify;
 use crate::coord::Coordinator;
-use crate::session::{Session, TransactionStatus};
+use crate::session::{CursorHold, Session, TransactionStatus};
 use crate::util::describe;
 use crate::{metrics, AdapterError, ExecuteContext, ExecuteResponse};
 
@@ -40,20 +40,12 @@ impl Coordinator {
         Ok(plan)
     }
 
-    pub(crate) fn declare(
-        &self,
-        mut ctx: ExecuteContext,
-        name: String,
-        stmt: Statement<Raw>,
-        sql: String,
-        params: Params,
-    ) {
+    pub(crate) fn declare(&self, mut ctx: ExecuteContext, plan: DeclarePlan) {
         let catalog = self.owned_catalog();
         let now = self.now();
         mz_ore::task::spawn(|| "coord::declare", async move {
-            let result =
-                Self::declare_inner(ctx.session_mut(), &catalog, name, stmt, sql, params, now)
-                    .map(|()| ExecuteResponse::DeclaredCursor);
+            let result = Self::declare_inner(ctx.session_mut(), &catalog, plan, now)
+                .map(|()| ExecuteResponse::DeclaredCursor);
             ctx.retire(result);
         });
     }
@@ -61,10 +53,14 @@ impl Coordinator {
     fn declare_inner(
         session: &mut Session,
         catalog: &Catalog,
-        name: String,
-        stmt: Statement<Raw>,
-        sql: String,
-        params: Params,
+        DeclarePlan {
+            name,
+            scroll,
+            hold,
+            stmt,
+            sql,
+            params,
+        }: DeclarePlan,
         now: EpochMillis,
     ) -> Result<(), AdapterError> {
         let param_types = params
@@ -77,7 +73,7 @@ impl Coordinator {
         let result_formats = vec![mz_pgwire_common::Format::Text; desc.arity()];
         let logging = session.mint_logging(sql, Some(&stmt), now);
         session.set_portal(
-            name,
+            name.clone(),
             desc,
             Some(stmt),
             logging,
@@ -85,6 +81,13 @@ impl Coordinator {
             result_formats,
             catalog.transient_revision(),
         )?;
+        let portal = session
+            .get_portal_unverified_mut(&name)
+            .expect("portal was just set");
+        portal.scroll = scroll;
+        if hold {
+            portal.hold = CursorHold::Uncommitted;
+        }
         Ok(())
     }
 
@@ -120,11 +123,13 @@ impl Coordinator {
             ps.stmt(),
             ps.desc(),
             ps.catalog_revision,
+            ps.plans_discarded,
         )? {
             let ps = session
                 .get_prepared_statement_mut_unverified(name)
                 .expect("known to exist");
             ps.catalog_revision = revision;
+            ps.plans_discarded = false;
         }
 
         Ok(())
@@ -146,6 +151,7 @@ impl Coordinator {
             portal.stmt.as_deref(),
             &portal.desc,
             portal.catalog_revision,
+            false,
         )? {
             let portal = session
                 .get_portal_unverified_mut(name)
@@ -155,19 +161,21 @@ impl Coordinator {
         Ok(())
     }
 
-    /// If the catalog and portal revisions don't match, re-describe the statement
-    /// and ensure its result type has not changed. Return `Some(x)` with the new
-    /// (valid) revision if its plan has changed. Return `None` if the revisions
-    /// match. Return an error if the plan has changed.
+    /// If the catalog and portal revisions don't match, or `force` is set,
+    /// re-describe the statement and ensure its result type has not changed.
+    /// Return `Some(x)` with the new (valid) revision if its plan has changed.
+    /// Return `None` if the revisions match. Return an error if the plan has
+    /// changed.
     fn verify_statement_revision(
         catalog: &Catalog,
         session: &Session,
         stmt: Option<&Statement<Raw>>,
         desc: &StatementDesc,
         catalog_revision: u64,
+        force: bool,
     ) -> Result<Option<u64>, AdapterError> {
         let current_revision = catalog.transient_revision();
-        if catalog_revision != current_revision {
+        if force || catalog_revision != current_revision {
             let current_desc = Self::describe(
                 catalog,
                 session,
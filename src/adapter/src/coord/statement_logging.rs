@@ -9,6 +9,7 @@
 
 use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use bytes::BytesMut;
 use mz_controller_types::ClusterId;
@@ -16,6 +17,7 @@ use mz_ore::now::{to_datetime, NowFn};
 use mz_ore::task::spawn;
 use mz_ore::{cast::CastFrom, cast::CastInto, now::EpochMillis};
 use mz_repr::adt::array::ArrayDimension;
+use mz_repr::adt::interval::Interval;
 use mz_repr::adt::timestamp::TimestampLike;
 use mz_repr::{Datum, Diff, GlobalId, Row, RowPacker, Timestamp};
 use mz_sql::ast::display::AstDisplay;
@@ -32,8 +34,9 @@ use tokio::time::MissedTickBehavior;
 use tracing::debug;
 use uuid::Uuid;
 
+use crate::coord::timestamp_selection::TimestampDetermination;
 use crate::coord::{ConnMeta, Coordinator};
-use crate::session::Session;
+use crate::session::{AuthenticationMethod, Session};
 use crate::statement_logging::{
     SessionHistoryEvent, StatementBeganExecutionRecord, StatementEndedExecutionReason,
     StatementEndedExecutionRecord, StatementLifecycleEvent, StatementPreparedRecord,
@@ -86,10 +89,14 @@ impl PreparedStatementLoggingInfo {
     ) -> Self {
         let kind = stmt.map(StatementKind::from);
         let sql = match kind {
-            // We __always__ want to redact SQL statements that might contain secret values.
-            Some(StatementKind::CreateSecret | StatementKind::AlterSecret) => {
-                stmt.map(|s| s.to_ast_string_redacted()).unwrap_or_default()
-            }
+            // We __always__ want to redact SQL statements that might contain secret values or
+            // passwords.
+            Some(
+                StatementKind::CreateSecret
+                | StatementKind::AlterSecret
+                | StatementKind::CreateRole
+                | StatementKind::AlterRole,
+            ) => stmt.map(|s| s.to_ast_string_redacted()).unwrap_or_default(),
             _ => raw_sql,
         };
 
@@ -437,6 +444,9 @@ impl Coordinator {
             transaction_id,
             transient_index_id,
             mz_version,
+            plan_fingerprint: _,
+            timestamp_constraint: _,
+            expected_wait: _,
         } = record;
 
         let cluster = cluster_id.map(|id| id.to_string());
@@ -491,6 +501,12 @@ impl Coordinator {
             Datum::Null,
             // execution_status
             Datum::Null,
+            record.plan_fingerprint.as_deref().into(),
+            record.timestamp_constraint.map(|c| c.as_str()).into(),
+            record
+                .expected_wait
+                .map(Self::pack_expected_wait)
+                .unwrap_or(Datum::Null),
         ]);
         row
     }
@@ -520,6 +536,8 @@ impl Coordinator {
             connected_at,
             application_name,
             authenticated_user,
+            authentication_method,
+            client_cert_common_name,
         } = event;
         Row::pack_slice(&[
             Datum::Uuid(*id),
@@ -530,6 +548,8 @@ impl Coordinator {
             ),
             Datum::String(&*application_name),
             Datum::String(&*authenticated_user),
+            Datum::String(authentication_method),
+            client_cert_common_name.as_deref().into(),
         ])
     }
 
@@ -579,10 +599,24 @@ impl Coordinator {
             error_message.into(),
             rows_returned.into(),
             execution_strategy.into(),
+            began_record.plan_fingerprint.as_deref().into(),
+            began_record.timestamp_constraint.map(|c| c.as_str()).into(),
+            began_record
+                .expected_wait
+                .map(Self::pack_expected_wait)
+                .unwrap_or(Datum::Null),
         ]);
         row
     }
 
+    fn pack_expected_wait<'a>(expected_wait: Duration) -> Datum<'a> {
+        Datum::Interval(Interval::new(
+            0,
+            0,
+            i64::try_from(expected_wait.as_micros()).expect("expected wait must fit in an i64"),
+        ))
+    }
+
     pub fn pack_statement_ended_execution_updates(
         began_record: &StatementBeganExecutionRecord,
         ended_record: &StatementEndedExecutionRecord,
@@ -638,12 +672,34 @@ impl Coordinator {
         });
     }
 
+    /// Set the `timestamp_constraint` and `expected_wait` for a statement, once its timestamp
+    /// is determined.
+    pub fn set_statement_timestamp_determination(
+        &mut self,
+        id: StatementLoggingId,
+        determination: &TimestampDetermination<Timestamp>,
+    ) {
+        let timestamp_constraint = determination.constraint;
+        let expected_wait = determination.expected_wait();
+        self.mutate_record(id, |record| {
+            record.timestamp_constraint = Some(timestamp_constraint);
+            record.expected_wait = expected_wait;
+        });
+    }
+
     pub fn set_transient_index_id(&mut self, id: StatementLoggingId, transient_index_id: GlobalId) {
         self.mutate_record(id, |record| {
             record.transient_index_id = Some(transient_index_id)
         });
     }
 
+    /// Set the `plan_fingerprint` for a statement, once it's known.
+    pub fn set_statement_plan_fingerprint(&mut self, id: StatementLoggingId, fingerprint: String) {
+        self.mutate_record(id, |record| {
+            record.plan_fingerprint = Some(fingerprint);
+        });
+    }
+
     /// Possibly record the beginning of statement execution, depending on a randomly-chosen value.
     /// If the execution beginning was indeed logged, returns a `StatementLoggingId` that must be
     /// passed to `end_statement_execution` to record when it ends.
@@ -730,6 +786,9 @@ impl Coordinator {
             cluster_name: None,
             execution_timestamp: None,
             transient_index_id: None,
+            plan_fingerprint: None,
+            timestamp_constraint: None,
+            expected_wait: None,
         };
         let mseh_update = Self::pack_statement_began_execution_update(&record);
         self.statement_logging
@@ -757,16 +816,30 @@ impl Coordinator {
     }
 
     /// Record a new connection event
+    ///
+    /// Sessions whose user authenticated are recorded right away, so that the
+    /// session history serves as a record of every login. Other sessions are
+    /// only recorded once one of their statements is logged.
     pub fn begin_session_for_statement_logging(&mut self, session: &ConnMeta) {
         let id = session.uuid();
         let session_role = session.authenticated_role_id();
+        let authentication_method = session.authentication_method();
         let event = SessionHistoryEvent {
             id,
             connected_at: session.connected_at(),
             application_name: session.application_name().to_owned(),
             authenticated_user: self.catalog.get_role(session_role).name.clone(),
+            authentication_method: authentication_method.as_str(),
+            client_cert_common_name: authentication_method
+                .client_cert_common_name()
+                .map(|name| name.to_owned()),
         };
-        self.statement_logging.unlogged_sessions.insert(id, event);
+        if *authentication_method == AuthenticationMethod::Trust {
+            self.statement_logging.unlogged_sessions.insert(id, event);
+        } else {
+            let update = Self::pack_session_history_update(&event);
+            self.statement_logging.pending_session_events.push(update);
+        }
     }
 
     pub fn end_session_for_statement_logging(&mut self, uuid: Uuid) {
@@ -355,7 +355,8 @@ impl Coordinator {
                         | CatalogItem::Func(_)
                         | CatalogItem::Secret(_)
                         | CatalogItem::Connection(_)
-                        | CatalogItem::Log(_) => {}
+                        | CatalogItem::Log(_)
+                        | CatalogItem::ContinualTask(_) => {}
                     }
                 }
             }
@@ -478,7 +479,8 @@ impl Coordinator {
                     | CatalogItem::Type(_)
                     | CatalogItem::Func(_)
                     | CatalogItem::Secret(_)
-                    | CatalogItem::Connection(_) => {}
+                    | CatalogItem::Connection(_)
+                    | CatalogItem::ContinualTask(_) => {}
                 }
             }
         }
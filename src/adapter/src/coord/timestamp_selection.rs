@@ -10,6 +10,7 @@
 //! Logic for selecting timestamps for various operations on collections.
 
 use std::fmt;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use chrono::{DateTime, NaiveDateTime, Utc};
@@ -325,16 +326,27 @@ pub trait TimestampProvider {
             );
         }
 
-        // Initialize candidate to the minimum correct time.
+        // Initialize candidate to the minimum correct time. Each of the bounds below can advance
+        // the candidate, and we record the last bound that did as the constraint of the chosen
+        // timestamp.
         let mut candidate = Timestamp::minimum();
+        let mut constraint = TimestampConstraint::ReadFrontier;
 
         if let Some(timestamp) = when.advance_to_timestamp() {
             let ts = Coordinator::evaluate_when(catalog, timestamp, session)?;
             candidate.join_assign(&ts);
+            constraint = TimestampConstraint::AsOf;
         }
 
         if when.advance_to_since() {
-            candidate.advance_by(since.borrow());
+            if let Some(since) = since.as_option() {
+                advance_candidate(
+                    &mut candidate,
+                    &mut constraint,
+                    since,
+                    TimestampConstraint::ReadFrontier,
+                );
+            }
         }
 
         // If we've acquired a read timestamp from the timestamp oracle, use it
@@ -345,7 +357,12 @@ pub trait TimestampProvider {
             if isolation_level != &IsolationLevel::StrongSessionSerializable
                 || when.must_advance_to_timeline_ts()
             {
-                candidate.join_assign(timestamp);
+                advance_candidate(
+                    &mut candidate,
+                    &mut constraint,
+                    timestamp,
+                    TimestampConstraint::OracleReadTimestamp,
+                );
             }
         }
 
@@ -358,7 +375,12 @@ pub trait TimestampProvider {
         if when.can_advance_to_upper()
             && (isolation_level == &IsolationLevel::Serializable || timeline.is_none())
         {
-            candidate.join_assign(&largest_not_in_advance_of_upper);
+            advance_candidate(
+                &mut candidate,
+                &mut constraint,
+                &largest_not_in_advance_of_upper,
+                TimestampConstraint::WriteFrontier,
+            );
         }
 
         if let Some(real_time_recency_ts) = real_time_recency_ts {
@@ -368,7 +390,12 @@ pub trait TimestampProvider {
                 "real time recency timestamp should only be supplied when real time recency \
                             is enabled and the isolation level is strict serializable"
             );
-            candidate.join_assign(&real_time_recency_ts);
+            advance_candidate(
+                &mut candidate,
+                &mut constraint,
+                &real_time_recency_ts,
+                TimestampConstraint::RealTimeRecency,
+            );
         }
 
         let mut session_oracle_read_ts = None;
@@ -376,7 +403,12 @@ pub trait TimestampProvider {
             if let Some(timeline) = &timeline {
                 if let Some(oracle) = session.get_timestamp_oracle(timeline) {
                     let session_ts = oracle.read_ts();
-                    candidate.join_assign(&session_ts);
+                    advance_candidate(
+                        &mut candidate,
+                        &mut constraint,
+                        &session_ts,
+                        TimestampConstraint::SessionOracleReadTimestamp,
+                    );
                     session_oracle_read_ts = Some(session_ts);
                 }
             }
@@ -392,10 +424,14 @@ pub trait TimestampProvider {
             // query.
             if when.can_advance_to_upper() && when.can_advance_to_timeline_ts() {
                 let mut advance_to = largest_not_in_advance_of_upper;
+                let mut reason = TimestampConstraint::WriteFrontier;
                 if let Some(oracle_read_ts) = oracle_read_ts {
-                    advance_to = std::cmp::min(advance_to, oracle_read_ts);
+                    if oracle_read_ts < advance_to {
+                        advance_to = oracle_read_ts;
+                        reason = TimestampConstraint::OracleReadTimestamp;
+                    }
                 }
-                candidate.join_assign(&advance_to);
+                advance_candidate(&mut candidate, &mut constraint, &advance_to, reason);
             }
         }
 
@@ -416,10 +452,13 @@ pub trait TimestampProvider {
             );
             candidate
         } else {
-            coord_bail!(self.generate_timestamp_not_valid_error_msg(
+            return Err(self.generate_timestamp_not_valid_error(
+                catalog,
                 id_bundle,
                 compute_instance,
-                candidate
+                candidate,
+                &since,
+                &upper,
             ));
         };
 
@@ -437,6 +476,7 @@ pub trait TimestampProvider {
             largest_not_in_advance_of_upper,
             oracle_read_ts,
             session_oracle_read_ts,
+            constraint,
         })
     }
 
@@ -489,12 +529,21 @@ pub trait TimestampProvider {
         frontier
     }
 
-    fn generate_timestamp_not_valid_error_msg(
+    fn generate_timestamp_not_valid_error(
         &self,
+        catalog: &CatalogState,
         id_bundle: &CollectionIdBundle,
         compute_instance: ComputeInstanceId,
         candidate: mz_repr::Timestamp,
-    ) -> String {
+        since: &Antichain<mz_repr::Timestamp>,
+        upper: &Antichain<mz_repr::Timestamp>,
+    ) -> AdapterError {
+        let name = |id: GlobalId| match catalog.try_get_entry(&id) {
+            Some(entry) => catalog
+                .resolve_full_name(entry.name(), entry.conn_id())
+                .to_string(),
+            None => id.to_string(),
+        };
         let invalid_indexes =
             if let Some(compute_ids) = id_bundle.compute_ids.get(&compute_instance) {
                 compute_ids
@@ -504,7 +553,7 @@ pub trait TimestampProvider {
                         if since.less_equal(&candidate) {
                             None
                         } else {
-                            Some(since)
+                            Some((name(*id), since))
                         }
                     })
                     .collect()
@@ -516,17 +565,24 @@ pub trait TimestampProvider {
             if since.less_equal(&candidate) {
                 None
             } else {
-                Some(since)
+                Some((name(*id), since))
             }
         });
         let invalid = invalid_indexes
             .into_iter()
             .chain(invalid_sources)
             .collect::<Vec<_>>();
-        format!(
-            "Timestamp ({}) is not valid for all inputs: {:?}",
-            candidate, invalid,
-        )
+        // All inputs are readable from the largest of their read frontiers up to, but not
+        // including, the smallest of their write frontiers.
+        let readable = since
+            .as_option()
+            .filter(|lower| !upper.less_equal(lower))
+            .map(|lower| (*lower, Coordinator::largest_not_in_advance_of_upper(upper)));
+        AdapterError::TimestampNotValid {
+            timestamp: candidate,
+            invalid,
+            readable,
+        }
     }
 }
 
@@ -694,6 +750,8 @@ pub struct TimestampDetermination<T> {
     pub oracle_read_ts: Option<T>,
     /// The value of the session local timestamp's oracle timestamp, if used.
     pub session_oracle_read_ts: Option<T>,
+    /// The bound that determined the chosen timestamp.
+    pub constraint: TimestampConstraint,
 }
 
 impl<T: TimestampManipulation> TimestampDetermination<T> {
@@ -707,6 +765,83 @@ impl<T: TimestampManipulation> TimestampDetermination<T> {
     }
 }
 
+impl TimestampDetermination<mz_repr::Timestamp> {
+    /// Estimates how long a query waits before it can respond at the chosen timestamp: until
+    /// its inputs are readable at the timestamp, and, for linearized reads, until the timestamp
+    /// oracle catches up to it.
+    ///
+    /// The estimate is based on the frontiers at the time of the determination. Returns `None`
+    /// if the timestamps of the query's timeline are not wall-clock milliseconds.
+    pub fn expected_wait(&self) -> Option<Duration> {
+        match &self.timestamp_context {
+            TimestampContext::TimelineTimestamp {
+                timeline: Timeline::EpochMilliseconds,
+                chosen_ts,
+                oracle_ts,
+            } => {
+                let mut wait = chosen_ts.saturating_sub(self.largest_not_in_advance_of_upper);
+                if let Some(oracle_ts) = oracle_ts {
+                    wait = std::cmp::max(wait, chosen_ts.saturating_sub(*oracle_ts));
+                }
+                Some(Duration::from_millis(wait.into()))
+            }
+            TimestampContext::TimelineTimestamp { .. } => None,
+            TimestampContext::NoTimestamp => Some(Duration::ZERO),
+        }
+    }
+}
+
+/// The bound that determined the timestamp of a query.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampConstraint {
+    /// The timestamp requested with `AS OF`.
+    AsOf,
+    /// The read frontier of the inputs, below which they are compacted.
+    ReadFrontier,
+    /// The read timestamp of the timeline's timestamp oracle.
+    OracleReadTimestamp,
+    /// The write frontier of the inputs, which is the freshest timestamp at which they can be
+    /// read without waiting.
+    WriteFrontier,
+    /// The frontier of the upstream systems of the inputs, with real-time recency.
+    RealTimeRecency,
+    /// The read timestamp of the session's timestamp oracle.
+    SessionOracleReadTimestamp,
+}
+
+impl TimestampConstraint {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimestampConstraint::AsOf => "as of",
+            TimestampConstraint::ReadFrontier => "read frontier",
+            TimestampConstraint::OracleReadTimestamp => "oracle read timestamp",
+            TimestampConstraint::WriteFrontier => "write frontier",
+            TimestampConstraint::RealTimeRecency => "real-time recency",
+            TimestampConstraint::SessionOracleReadTimestamp => "session oracle read timestamp",
+        }
+    }
+}
+
+impl fmt::Display for TimestampConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Advances `candidate` to `ts`, and records `reason` as the `constraint` of the candidate if
+/// that moves it.
+fn advance_candidate(
+    candidate: &mut Timestamp,
+    constraint: &mut TimestampConstraint,
+    ts: &Timestamp,
+    reason: TimestampConstraint,
+) {
+    if *ts > *candidate {
+        *candidate = *ts;
+        *constraint = reason;
+    }
+}
+
 /// Information used when determining the timestamp for a query.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TimestampExplanation<T> {
@@ -718,6 +853,10 @@ pub struct TimestampExplanation<T> {
     pub session_wall_time: DateTime<Utc>,
     /// Cached value of determination.respond_immediately()
     pub respond_immediately: bool,
+    /// Cached value of determination.expected_wait()
+    pub expected_wait: Option<Duration>,
+    /// The isolation level the timestamp was determined with.
+    pub isolation_level: String,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -827,6 +966,23 @@ impl<T: fmt::Display + fmt::Debug + DisplayableInTimeline + TimestampManipulatio
             "        can respond immediately: {}",
             self.respond_immediately
         )?;
+        if let Some(expected_wait) = &self.expected_wait {
+            writeln!(
+                f,
+                "                  expected wait: {} ms",
+                expected_wait.as_millis()
+            )?;
+        }
+        writeln!(
+            f,
+            "           timestamp constraint: {}",
+            self.determination.constraint
+        )?;
+        writeln!(
+            f,
+            "                isolation level: {}",
+            self.isolation_level
+        )?;
         writeln!(f, "                       timeline: {:?}", &timeline)?;
         writeln!(
             f,
@@ -25,6 +25,7 @@ use mz_pgwire_common::{ErrorResponse, Severity};
 use mz_repr::adt::timestamp::TimestampError;
 use mz_repr::explain::ExplainError;
 use mz_repr::{NotNullViolation, Timestamp};
+use mz_sql::catalog::CatalogError as SqlCatalogError;
 use mz_sql::plan::PlanError;
 use mz_sql::rbac;
 use mz_sql::session::vars::VarError;
@@ -137,10 +138,19 @@ pub enum AdapterError {
     ///
     /// Note this differs slightly from PG's implementation/semantics.
     StatementTimeout,
+    /// Waiting for admission to a cluster that is running its maximum number of concurrent
+    /// queries timed out.
+    QueryQueueTimeout {
+        cluster: String,
+    },
     /// The user canceled the query
     Canceled,
     /// An idle session in a transaction has timed out.
     IdleInTransactionSessionTimeout,
+    /// The server is draining and does not accept new sessions.
+    ServerDraining,
+    /// The session was closed because the server is draining.
+    SessionDrained,
     /// The transaction is in single-subscribe mode.
     SubscribeOnlyTransaction,
     /// An error occurred in the the optimizer.
@@ -176,8 +186,11 @@ pub enum AdapterError {
     },
     /// The transaction is in write-only mode.
     WriteOnlyTransaction,
-    /// The transaction only supports single table writes
-    MultiTableWriteTransaction,
+    /// A read in a write transaction referenced a table that the transaction
+    /// has written to.
+    ReadOfTableWrittenInTransaction(String),
+    /// The transaction held the write lock for longer than allowed.
+    WriteLockTimeout,
     /// The transaction can only execute a single statement.
     SingleStatementTransaction,
     /// The transaction can only execute simple DDL.
@@ -202,6 +215,12 @@ pub enum AdapterError {
     ///
     /// The map keys are role names and values are detailed error messages.
     DependentObject(BTreeMap<String, Vec<String>>),
+    /// A statement tried to move an index to another cluster, but other dataflows on its
+    /// cluster import it.
+    IndexHasDependents {
+        index: String,
+        dependent: String,
+    },
     /// When performing an `ALTER` of some variety, re-planning the statement
     /// errored.
     InvalidAlter(&'static str, PlanError),
@@ -214,6 +233,16 @@ pub enum AdapterError {
     /// A CREATE MATERIALIZED VIEW statement tried to acquire a read hold at a REFRESH AT time,
     /// but was unable to get a precise read hold.
     InputNotReadableAtRefreshAtTime(Timestamp, Vec<(Antichain<Timestamp>, CollectionIdBundle)>),
+    /// A query requested a timestamp at which some of its inputs are not readable, e.g., because
+    /// of an `AS OF` that is further back than the inputs' retained history.
+    TimestampNotValid {
+        timestamp: Timestamp,
+        /// The names of the inputs that are not readable at `timestamp`, along with their read
+        /// frontiers.
+        invalid: Vec<(String, Antichain<Timestamp>)>,
+        /// The inclusive range of timestamps at which all inputs are currently readable, if any.
+        readable: Option<(Timestamp, Timestamp)>,
+    },
 }
 
 impl AdapterError {
@@ -330,6 +359,16 @@ impl AdapterError {
                     ).join("; "),
                 ))
             }
+            AdapterError::TimestampNotValid { invalid, .. } => Some(format!(
+                "The following inputs are not readable at the requested timestamp:\n{}",
+                invalid
+                    .iter()
+                    .map(|(name, since)| match since.as_option() {
+                        Some(since) => format!("    {}: readable from {}", name.quoted(), since),
+                        None => format!("    {}: not readable at any timestamp", name.quoted()),
+                    })
+                    .join("\n")
+            )),
             _ => None,
         }
     }
@@ -344,6 +383,25 @@ impl AdapterError {
             ),
             AdapterError::Catalog(c) => c.hint(),
             AdapterError::Eval(e) => e.hint(),
+            AdapterError::IndexHasDependents { .. } => Some(
+                "Move the objects that depend on the index to another cluster or drop them first."
+                    .into(),
+            ),
+            AdapterError::ReadOfTableWrittenInTransaction(_) => Some(
+                "Reads in a write transaction do not observe the transaction's own writes. \
+                Commit the transaction before reading from the table."
+                    .into(),
+            ),
+            AdapterError::WriteLockTimeout => Some(
+                "While a transaction that reads and writes tables is open, no other writes to \
+                tables can commit. Roll back the transaction and keep such transactions short."
+                    .into(),
+            ),
+            AdapterError::Storage(StorageError::RtrTimeout(_)) => Some(
+                "Increase real_time_recency_timeout, or disable real_time_recency to read the data \
+                the source has ingested so far."
+                    .into(),
+            ),
             AdapterError::InvalidClusterReplicaAz { expected, az: _ } => {
                 Some(if expected.is_empty() {
                     "No availability zones configured; do not specify AVAILABILITY ZONE".into()
@@ -371,12 +429,37 @@ impl AdapterError {
             AdapterError::ResourceExhaustion { resource_type, .. } => Some(format!(
                 "Drop an existing {resource_type} or contact support to request a limit increase."
             )),
+            AdapterError::TimestampNotValid { readable, .. } => {
+                let retain = "To query further back in time, increase the RETAIN HISTORY of the inputs.";
+                Some(match readable {
+                    Some((lower, upper)) => format!(
+                        "All inputs are currently readable at timestamps between {lower} and {upper}. {retain}"
+                    ),
+                    None => retain.into(),
+                })
+            }
+            AdapterError::ResultSize(_) => Some(
+                "Add a LIMIT clause or a more selective filter to the query, or create a \
+                 materialized view and read its changes incrementally with SUBSCRIBE. The limits \
+                 for this session are set by the max_query_result_size and \
+                 max_query_result_rows session variables."
+                    .into(),
+            ),
             AdapterError::StatementTimeout => Some(
                 "Consider increasing the maximum allowed statement duration for this session by \
                  setting the statement_timeout session variable. For example, `SET \
                  statement_timeout = '60s'`."
                     .into(),
             ),
+            AdapterError::QueryQueueTimeout { .. } => Some(
+                "Consider increasing the MAX CONCURRENT QUERIES of the cluster, or the maximum \
+                 allowed wait for admission to a cluster by setting the query_queue_timeout \
+                 session variable."
+                    .into(),
+            ),
+            AdapterError::ServerDraining | AdapterError::SessionDrained => {
+                Some("Reconnect to reach the server that takes over.".into())
+            }
             AdapterError::PlanError(e) => e.hint(),
             AdapterError::UnallowedOnCluster { cluster, .. } => {
                 (cluster != MZ_INTROSPECTION_CLUSTER.name).then(||
@@ -412,6 +495,9 @@ impl AdapterError {
                     VarError::RequiresUnsafeMode { .. } => SqlState::CANT_CHANGE_RUNTIME_PARAM,
                     VarError::RequiresFeatureFlag { .. } => SqlState::CANT_CHANGE_RUNTIME_PARAM,
                 },
+                mz_catalog::memory::error::ErrorKind::Sql(SqlCatalogError::QuotaExceeded {
+                    ..
+                }) => SqlState::INSUFFICIENT_RESOURCES,
                 _ => SqlState::INTERNAL_ERROR,
             },
             AdapterError::ChangedPlan(_) => SqlState::FEATURE_NOT_SUPPORTED,
@@ -451,10 +537,13 @@ impl AdapterError {
             AdapterError::ReadWriteUnavailable => SqlState::INVALID_TRANSACTION_STATE,
             AdapterError::SingleStatementTransaction => SqlState::INVALID_TRANSACTION_STATE,
             AdapterError::StatementTimeout => SqlState::QUERY_CANCELED,
+            AdapterError::QueryQueueTimeout { .. } => SqlState::QUERY_CANCELED,
             AdapterError::Canceled => SqlState::QUERY_CANCELED,
             AdapterError::IdleInTransactionSessionTimeout => {
                 SqlState::IDLE_IN_TRANSACTION_SESSION_TIMEOUT
             }
+            AdapterError::ServerDraining => SqlState::CANNOT_CONNECT_NOW,
+            AdapterError::SessionDrained => SqlState::ADMIN_SHUTDOWN,
             AdapterError::RecursionLimit(_) => SqlState::INTERNAL_ERROR,
             AdapterError::RelationOutsideTimeDomain { .. } => SqlState::INVALID_TRANSACTION_STATE,
             AdapterError::ResourceExhaustion { .. } => SqlState::INSUFFICIENT_RESOURCES,
@@ -497,17 +586,20 @@ impl AdapterError {
             // transaction" are not things in Postgres. This error code is the generic "bad txn
             // thing" code, so it's probably the best choice.
             AdapterError::WriteOnlyTransaction => SqlState::INVALID_TRANSACTION_STATE,
-            AdapterError::MultiTableWriteTransaction => SqlState::INVALID_TRANSACTION_STATE,
+            AdapterError::ReadOfTableWrittenInTransaction(_) => SqlState::FEATURE_NOT_SUPPORTED,
+            AdapterError::WriteLockTimeout => SqlState::LOCK_NOT_AVAILABLE,
             AdapterError::DDLOnlyTransaction => SqlState::INVALID_TRANSACTION_STATE,
             AdapterError::Storage(_) | AdapterError::Compute(_) | AdapterError::Orchestrator(_) => {
                 SqlState::INTERNAL_ERROR
             }
             AdapterError::DependentObject(_) => SqlState::DEPENDENT_OBJECTS_STILL_EXIST,
+            AdapterError::IndexHasDependents { .. } => SqlState::DEPENDENT_OBJECTS_STILL_EXIST,
             AdapterError::InvalidAlter(_, _) => SqlState::FEATURE_NOT_SUPPORTED,
             AdapterError::ConnectionValidation(_) => SqlState::SYSTEM_ERROR,
             // `DATA_EXCEPTION`, similarly to `AbsurdSubscribeBounds`.
             AdapterError::MaterializedViewWouldNeverRefresh(_, _) => SqlState::DATA_EXCEPTION,
             AdapterError::InputNotReadableAtRefreshAtTime(_, _) => SqlState::DATA_EXCEPTION,
+            AdapterError::TimestampNotValid { .. } => SqlState::DATA_EXCEPTION,
         }
     }
 
@@ -603,6 +695,13 @@ impl fmt::Display for AdapterError {
             AdapterError::StatementTimeout => {
                 write!(f, "canceling statement due to statement timeout")
             }
+            AdapterError::QueryQueueTimeout { cluster } => {
+                write!(
+                    f,
+                    "canceling statement due to query queue timeout on cluster {}",
+                    cluster.quoted()
+                )
+            }
             AdapterError::Canceled => {
                 write!(f, "canceling statement due to user request")
             }
@@ -612,6 +711,15 @@ impl fmt::Display for AdapterError {
                     "terminating connection due to idle-in-transaction timeout"
                 )
             }
+            AdapterError::ServerDraining => {
+                write!(
+                    f,
+                    "the server is draining and does not accept new connections"
+                )
+            }
+            AdapterError::SessionDrained => {
+                write!(f, "terminating connection because the server is draining")
+            }
             AdapterError::RecursionLimit(e) => e.fmt(f),
             AdapterError::RelationOutsideTimeDomain { .. } => {
                 write!(
@@ -663,6 +771,15 @@ impl fmt::Display for AdapterError {
             AdapterError::Unsupported(features) => write!(f, "{} are not supported", features),
             AdapterError::Unstructured(e) => write!(f, "{}", e.display_with_causes()),
             AdapterError::WriteOnlyTransaction => f.write_str("transaction in write-only mode"),
+            AdapterError::ReadOfTableWrittenInTransaction(name) => write!(
+                f,
+                "cannot read from {}, which was written to earlier in the transaction",
+                name.quoted()
+            ),
+            AdapterError::WriteLockTimeout => f.write_str(
+                "transaction held the write lock for longer than \
+                write_transaction_read_lock_timeout",
+            ),
             AdapterError::UnknownPreparedStatement(name) => {
                 write!(f, "prepared statement {} does not exist", name.quoted())
             }
@@ -681,9 +798,6 @@ impl fmt::Display for AdapterError {
             AdapterError::UntargetedLogRead { .. } => {
                 f.write_str("log source reads must target a replica")
             }
-            AdapterError::MultiTableWriteTransaction => {
-                f.write_str("write transactions only support writes to a single table")
-            }
             AdapterError::DDLOnlyTransaction => f.write_str(
                 "transactions which modify objects are restricted to just modifying objects",
             ),
@@ -706,6 +820,14 @@ impl fmt::Display for AdapterError {
                     dependent_objects.keys().join(", ")
                 )
             }
+            AdapterError::IndexHasDependents { index, dependent } => {
+                write!(
+                    f,
+                    "cannot move index {} to another cluster because {} depends on it",
+                    index.quoted(),
+                    dependent.quoted()
+                )
+            }
             AdapterError::InvalidAlter(t, e) => {
                 write!(f, "invalid ALTER {t}: {e}")
             }
@@ -723,6 +845,9 @@ impl fmt::Display for AdapterError {
                     "REFRESH AT requested for a time where not all the inputs are readable"
                 )
             }
+            AdapterError::TimestampNotValid { timestamp, .. } => {
+                write!(f, "Timestamp ({timestamp}) is not valid for all inputs")
+            }
         }
     }
 }
@@ -52,6 +52,8 @@ pub fn storage_config(config: &SystemVars) -> StorageParameters {
         keep_n_sink_status_history_entries: config.keep_n_sink_status_history_entries(),
         keep_n_privatelink_status_history_entries: config
             .keep_n_privatelink_status_history_entries(),
+        keep_n_object_status_history_entries: config.keep_n_object_status_history_entries(),
+        dataflow_statistics_history_retention: config.dataflow_statistics_history_retention(),
         upsert_rocksdb_tuning_config: {
             match mz_rocksdb_types::RocksDBTuningParameters::from_parameters(
                 config.upsert_rocksdb_compaction_style(),
@@ -58,12 +58,14 @@ pub mod telemetry;
 pub mod webhook;
 
 pub use crate::client::{Client, Handle, SessionClient};
-pub use crate::command::{ExecuteResponse, ExecuteResponseKind, RowsFuture, StartupResponse};
+pub use crate::command::{
+    DrainStatus, ExecuteResponse, ExecuteResponseKind, RowsFuture, StartupResponse,
+};
 pub use crate::coord::id_bundle::CollectionIdBundle;
 pub use crate::coord::peek::PeekResponseUnary;
 pub use crate::coord::timeline::TimelineContext;
 pub use crate::coord::timestamp_selection::{
-    TimestampContext, TimestampExplanation, TimestampProvider,
+    TimestampConstraint, TimestampContext, TimestampExplanation, TimestampProvider,
 };
 pub use crate::coord::ExecuteContext;
 pub use crate::coord::ExecuteContextExtra;
@@ -71,5 +73,6 @@ pub use crate::coord::{load_remote_system_parameters, serve, Config};
 pub use crate::error::AdapterError;
 pub use crate::notice::AdapterNotice;
 pub use crate::webhook::{
-    AppendWebhookError, AppendWebhookResponse, AppendWebhookValidator, WebhookAppenderCache,
+    AppendWebhookError, AppendWebhookResponse, AppendWebhookValidator, DeduplicationOutcome,
+    WebhookAppenderCache, WebhookDeduplicator,
 };
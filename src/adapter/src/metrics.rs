@@ -37,6 +37,7 @@ pub struct Metrics {
     pub append_table_duration_seconds: HistogramVec,
     pub webhook_validation_reduce_failures: IntCounterVec,
     pub webhook_get_appender: IntCounter,
+    pub real_time_recency_wait_seconds: HistogramVec,
 }
 
 impl Metrics {
@@ -142,6 +143,12 @@ impl Metrics {
                 name: "mz_webhook_get_appender_count",
                 help: "Count of getting a webhook appender from the Coordinator.",
             )),
+            real_time_recency_wait_seconds: registry.register(metric!(
+                name: "mz_real_time_recency_wait_seconds",
+                help: "How long queries with real-time recency waited for sources to ingest the data available upstream.",
+                var_labels: ["outcome"],
+                buckets: histogram_seconds_buckets(0.000_128, 32.0),
+            )),
         }
     }
 }
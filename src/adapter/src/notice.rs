@@ -132,6 +132,9 @@ pub enum AdapterNotice {
         role: Option<String>,
         var_name: Option<String>,
     },
+    UpsertSourcesNotRestarted {
+        sources: Vec<String>,
+    },
     Welcome(String),
 }
 
@@ -194,6 +197,7 @@ impl AdapterNotice {
             AdapterNotice::DroppedInUseIndex { .. } => Severity::Notice,
             AdapterNotice::PerReplicaLogRead { .. } => Severity::Notice,
             AdapterNotice::VarDefaultUpdated { .. } => Severity::Notice,
+            AdapterNotice::UpsertSourcesNotRestarted { .. } => Severity::Warning,
             AdapterNotice::Welcome(_) => Severity::Notice,
         }
     }
@@ -239,6 +243,7 @@ impl AdapterNotice {
             ),
             AdapterNotice::OptimizerNotice { notice: _, hint } => Some(hint.clone()),
             AdapterNotice::DroppedInUseIndex(..) => Some("To free up the resources used by the index, recreate all the above-mentioned objects.".into()),
+            AdapterNotice::UpsertSourcesNotRestarted { .. } => Some("To refresh the secret used by these sources, drop and recreate all replicas of the clusters hosting them.".into()),
             _ => None
         }
     }
@@ -286,6 +291,7 @@ impl AdapterNotice {
             AdapterNotice::WebhookSourceCreated { .. } => SqlState::WARNING,
             AdapterNotice::PerReplicaLogRead { .. } => SqlState::WARNING,
             AdapterNotice::VarDefaultUpdated { .. } => SqlState::SUCCESSFUL_COMPLETION,
+            AdapterNotice::UpsertSourcesNotRestarted { .. } => SqlState::WARNING,
             AdapterNotice::Welcome(_) => SqlState::SUCCESSFUL_COMPLETION,
         }
     }
@@ -455,6 +461,13 @@ impl fmt::Display for AdapterNotice {
                     "{vars} updated for {target}, this will have no effect on the current session"
                 )
             }
+            AdapterNotice::UpsertSourcesNotRestarted { sources } => {
+                write!(
+                    f,
+                    "UPSERT sources {} were not restarted and may keep using the old secret value",
+                    separated(", ", sources.iter().map(|s| s.quoted()))
+                )
+            }
             AdapterNotice::Welcome(message) => message.fmt(f),
         }
     }
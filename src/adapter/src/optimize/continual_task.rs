@@ -0,0 +1,108 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Optimizer implementation for `CREATE CONTINUAL TASK` statements.
+//!
+//! A continual task runs as a `SUBSCRIBE` dataflow over its query. The
+//! coordinator appends the updates that the dataflow emits to the target
+//! table of the task.
+
+use std::sync::Arc;
+
+use mz_catalog::SYSTEM_CONN_ID;
+use mz_compute_types::ComputeInstanceId;
+use mz_repr::{GlobalId, RelationDesc};
+use mz_sql::plan::{HirRelationExpr, SubscribeFrom};
+
+use crate::catalog::Catalog;
+use crate::optimize::dataflows::ComputeInstanceSnapshot;
+use crate::optimize::subscribe::{self, GlobalLirPlan, GlobalMirPlan, Resolved, Unresolved};
+use crate::optimize::{trace_plan, Optimize, OptimizerConfig, OptimizerError};
+
+pub struct Optimizer {
+    /// The optimizer for the `SUBSCRIBE` dataflow that runs the task.
+    subscribe: subscribe::Optimizer,
+    /// The description of the target table, which names the columns of the
+    /// task's query.
+    target_desc: RelationDesc,
+    // Optimizer config.
+    config: OptimizerConfig,
+}
+
+// A bogey `Debug` implementation that hides fields, see the one of
+// `subscribe::Optimizer`.
+impl std::fmt::Debug for Optimizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Optimizer")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Optimizer {
+    pub fn new(
+        catalog: Arc<Catalog>,
+        compute_instance: ComputeInstanceSnapshot,
+        view_id: GlobalId,
+        sink_id: GlobalId,
+        target_desc: RelationDesc,
+        debug_name: String,
+        config: OptimizerConfig,
+    ) -> Self {
+        // The task only inserts the changes that happen after it starts, so
+        // the dataflow never emits a snapshot and runs forever.
+        let subscribe = subscribe::Optimizer::new(
+            catalog,
+            compute_instance,
+            view_id,
+            sink_id,
+            SYSTEM_CONN_ID.clone(),
+            false,
+            None,
+            debug_name,
+            config.clone(),
+        );
+        Self {
+            subscribe,
+            target_desc,
+            config,
+        }
+    }
+
+    pub fn cluster_id(&self) -> ComputeInstanceId {
+        self.subscribe.cluster_id()
+    }
+}
+
+impl Optimize<HirRelationExpr> for Optimizer {
+    type To = GlobalMirPlan<Unresolved>;
+
+    fn optimize(&mut self, expr: HirRelationExpr) -> Result<Self::To, OptimizerError> {
+        // Trace the pipeline input under `optimize/raw`.
+        trace_plan!(at: "raw", &expr);
+
+        // HIR ⇒ MIR lowering and decorrelation
+        let expr = expr.lower(&self.config)?;
+
+        // MIR ⇒ MIR optimization (local and global)
+        self.subscribe.optimize(SubscribeFrom::Query {
+            expr,
+            desc: self.target_desc.clone(),
+        })
+    }
+}
+
+impl Optimize<GlobalMirPlan<Resolved>> for Optimizer {
+    type To = GlobalLirPlan;
+
+    fn optimize(&mut self, plan: GlobalMirPlan<Resolved>) -> Result<Self::To, OptimizerError> {
+        // MIR ⇒ LIR lowering and LIR ⇒ LIR optimization (global)
+        self.subscribe.optimize(plan)
+    }
+}
@@ -67,6 +67,8 @@ pub struct Optimizer {
     non_null_assertions: Vec<usize>,
     /// Refresh schedule, e.g., `REFRESH EVERY '1 day'`
     refresh_schedule: Option<RefreshSchedule>,
+    /// Memory limit of the dataflow, e.g., `MEMORY LIMIT '1GB'`
+    memory_limit: Option<usize>,
     /// A human-readable name exposed internally (useful for debugging).
     debug_name: String,
     // Optimizer config.
@@ -82,6 +84,7 @@ impl Optimizer {
         column_names: Vec<ColumnName>,
         non_null_assertions: Vec<usize>,
         refresh_schedule: Option<RefreshSchedule>,
+        memory_limit: Option<usize>,
         debug_name: String,
         config: OptimizerConfig,
     ) -> Self {
@@ -94,6 +97,7 @@ impl Optimizer {
             column_names,
             non_null_assertions,
             refresh_schedule,
+            memory_limit,
             debug_name,
             config,
         }
@@ -207,6 +211,7 @@ impl Optimize<LocalMirPlan> for Optimizer {
             DataflowBuilder::new(catalog, compute).with_config(&self.config)
         };
         let mut df_desc = MirDataflowDescription::new(self.debug_name.clone());
+        df_desc.memory_limit = self.memory_limit;
 
         df_builder.import_view_into_dataflow(&self.view_id, &expr, &mut df_desc)?;
         df_builder.maybe_reoptimize_imported_views(&mut df_desc, &self.config)?;
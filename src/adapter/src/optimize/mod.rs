@@ -52,6 +52,7 @@
 //! For details, see the `20230714_optimizer_interface.md` design doc in this
 //! repository.
 
+pub mod continual_task;
 pub mod copy_to;
 pub mod dataflows;
 pub mod index;
@@ -258,6 +259,7 @@ impl From<&OptimizerConfig> for mz_sql::plan::HirToMirConfig {
         Self {
             enable_new_outer_join_lowering: config.features.enable_new_outer_join_lowering,
             enable_variadic_left_join_lowering: config.features.enable_variadic_left_join_lowering,
+            enable_left_join_key_sharing: config.features.enable_left_join_key_sharing,
         }
     }
 }
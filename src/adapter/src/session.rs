@@ -15,7 +15,9 @@ use std::collections::btree_map::Entry;
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Debug;
 use std::mem;
+use std::net::IpAddr;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use chrono::{DateTime, Utc};
 use derivative::Derivative;
@@ -23,6 +25,7 @@ use mz_adapter_types::connection::ConnectionId;
 use mz_build_info::{BuildInfo, DUMMY_BUILD_INFO};
 use mz_controller_types::ClusterId;
 use mz_ore::now::{EpochMillis, NowFn};
+use mz_ore::tracing::OpenTelemetryContext;
 use mz_pgwire_common::Format;
 use mz_repr::role_id::RoleId;
 use mz_repr::user::ExternalUserMetadata;
@@ -93,6 +96,14 @@ where
     next_transaction_id: TransactionId,
     secret_key: u32,
     external_metadata_rx: Option<watch::Receiver<ExternalUserMetadata>>,
+    /// How the session's user was authenticated.
+    authentication_method: AuthenticationMethod,
+    /// The trace context supplied by the client, if any.
+    trace_context: Option<OpenTelemetryContext>,
+    /// The IP address of the client, if known.
+    client_ip: Option<IpAddr>,
+    /// The ID of the HTTP request that started the session, if any.
+    http_request_id: Option<String>,
     // Token allowing us to access `Arc<QCell<StatementLogging>>`
     // metadata. We want these to be reference-counted, because the same
     // statement might be referenced from multiple portals simultaneously.
@@ -175,6 +186,58 @@ pub struct SessionConfig {
     /// An optional receiver that the session will periodically check for
     /// updates to a user's external metadata.
     pub external_metadata_rx: Option<watch::Receiver<ExternalUserMetadata>>,
+    /// How the user was authenticated.
+    pub authentication_method: AuthenticationMethod,
+    /// An optional trace context supplied by the client, under which work
+    /// performed on behalf of the session is traced.
+    pub trace_context: Option<OpenTelemetryContext>,
+    /// The IP address of the client, if known.
+    pub client_ip: Option<IpAddr>,
+    /// The ID of the HTTP request that started the session, if the session
+    /// belongs to an HTTP request.
+    pub http_request_id: Option<String>,
+}
+
+/// How the user of a [`Session`] was authenticated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthenticationMethod {
+    /// The user was not authenticated, either because authentication is
+    /// disabled or because the session belongs to the system itself.
+    Trust,
+    /// The user presented a password.
+    Password,
+    /// The user presented an access token.
+    Token,
+    /// The user presented a TLS client certificate.
+    Certificate {
+        /// The common name of the certificate's subject, which the user was
+        /// mapped from.
+        common_name: String,
+    },
+}
+
+impl AuthenticationMethod {
+    /// Returns the name of the authentication method as reported in
+    /// `mz_sessions` and `mz_session_history`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthenticationMethod::Trust => "trust",
+            AuthenticationMethod::Password => "password",
+            AuthenticationMethod::Token => "token",
+            AuthenticationMethod::Certificate { .. } => "certificate",
+        }
+    }
+
+    /// Returns the common name of the client certificate that the user
+    /// authenticated with, if any.
+    pub fn client_cert_common_name(&self) -> Option<&str> {
+        match self {
+            AuthenticationMethod::Certificate { common_name } => Some(common_name),
+            AuthenticationMethod::Trust
+            | AuthenticationMethod::Password
+            | AuthenticationMethod::Token => None,
+        }
+    }
 }
 
 impl<T: TimestampManipulation> Session<T> {
@@ -246,6 +309,10 @@ impl<T: TimestampManipulation> Session<T> {
                 conn_id: DUMMY_CONNECTION_ID,
                 user: SYSTEM_USER.name.clone(),
                 external_metadata_rx: None,
+                authentication_method: AuthenticationMethod::Trust,
+                trace_context: None,
+                client_ip: None,
+                http_request_id: None,
             },
         );
         dummy.initialize_role_metadata(RoleId::User(0));
@@ -258,6 +325,10 @@ impl<T: TimestampManipulation> Session<T> {
             conn_id,
             user,
             mut external_metadata_rx,
+            authentication_method,
+            trace_context,
+            client_ip,
+            http_request_id,
         }: SessionConfig,
     ) -> Session<T> {
         let (notices_tx, notices_rx) = mpsc::unbounded_channel();
@@ -286,6 +357,10 @@ impl<T: TimestampManipulation> Session<T> {
             next_transaction_id: 0,
             secret_key: rand::thread_rng().gen(),
             external_metadata_rx,
+            authentication_method,
+            trace_context,
+            client_ip,
+            http_request_id,
             qcell_owner: QCellOwner::new(),
             session_oracles: BTreeMap::new(),
         }
@@ -296,6 +371,26 @@ impl<T: TimestampManipulation> Session<T> {
         self.secret_key
     }
 
+    /// Returns how the session's user was authenticated.
+    pub fn authentication_method(&self) -> &AuthenticationMethod {
+        &self.authentication_method
+    }
+
+    /// Returns the trace context supplied by the client, if any.
+    pub fn trace_context(&self) -> Option<&OpenTelemetryContext> {
+        self.trace_context.as_ref()
+    }
+
+    /// Returns the IP address of the client, if known.
+    pub fn client_ip(&self) -> Option<IpAddr> {
+        self.client_ip
+    }
+
+    /// Returns the ID of the HTTP request that started the session, if any.
+    pub fn http_request_id(&self) -> Option<&str> {
+        self.http_request_id.as_deref()
+    }
+
     fn new_pcx(&self, mut wall_time: DateTime<Utc>) -> PlanContext {
         if let Some(mock_time) = self.vars().unsafe_new_transaction_wall_time() {
             wall_time = *mock_time;
@@ -339,6 +434,7 @@ impl<T: TimestampManipulation> Session<T> {
                     pcx: self.new_pcx(wall_time),
                     ops: TransactionOps::None,
                     write_lock_guard: None,
+                    write_lock_deadline: None,
                     access,
                     id,
                 });
@@ -373,6 +469,7 @@ impl<T: TimestampManipulation> Session<T> {
                 pcx: self.new_pcx(wall_time),
                 ops: TransactionOps::None,
                 write_lock_guard: None,
+                write_lock_deadline: None,
                 access: None,
                 id,
             };
@@ -398,20 +495,41 @@ impl<T: TimestampManipulation> Session<T> {
     /// > a named portal object lasts till the end of the current transaction
     /// and
     /// > An unnamed portal is destroyed at the end of the transaction
+    ///
+    /// The exception are cursors declared `WITH HOLD`, whose rows have been
+    /// buffered and so can outlive the transaction. Callers that roll back the
+    /// transaction must first call [`Session::remove_uncommitted_held_cursors`].
     #[must_use]
     pub fn clear_transaction(&mut self) -> TransactionStatus<T> {
-        self.portals.clear();
+        self.portals.retain(|_, portal| {
+            let held = portal.hold != CursorHold::WithoutHold
+                && matches!(portal.state, PortalState::Spooled(_));
+            if held {
+                portal.hold = CursorHold::Committed;
+            }
+            held
+        });
         self.pcx = None;
         mem::take(&mut self.transaction)
     }
 
+    /// Removes the cursors declared `WITH HOLD` in the current transaction,
+    /// which must not outlive it when it is rolled back.
+    pub fn remove_uncommitted_held_cursors(&mut self) {
+        self.portals
+            .retain(|_, portal| portal.hold != CursorHold::Uncommitted);
+    }
+
     /// Marks the current transaction as failed.
     pub fn fail_transaction(mut self) -> Self {
         match self.transaction {
             TransactionStatus::Default => unreachable!(),
-            TransactionStatus::Started(txn)
-            | TransactionStatus::InTransactionImplicit(txn)
-            | TransactionStatus::InTransaction(txn) => {
+            TransactionStatus::Started(mut txn)
+            | TransactionStatus::InTransactionImplicit(mut txn)
+            | TransactionStatus::InTransaction(mut txn) => {
+                // A failed transaction never commits its writes, so it must not keep other
+                // writes from committing until it is rolled back.
+                txn.write_lock_guard = None;
                 self.transaction = TransactionStatus::Failed(txn);
             }
             TransactionStatus::Failed(_) => {}
@@ -543,6 +661,7 @@ impl<T: TimestampManipulation> Session<T> {
                 pcx: _,
                 ops: TransactionOps::Peeks { determination, .. },
                 write_lock_guard: _,
+                write_lock_deadline: _,
                 access: _,
                 id: _,
             }) => Some(determination.clone()),
@@ -564,6 +683,7 @@ impl<T: TimestampManipulation> Session<T> {
                     ..
                 },
                 write_lock_guard: _,
+                write_lock_deadline: _,
                 access: _,
                 id: _,
             })
@@ -592,6 +712,7 @@ impl<T: TimestampManipulation> Session<T> {
             stmt,
             desc,
             catalog_revision,
+            plans_discarded: false,
             logging: Arc::new(QCell::new(&self.qcell_owner, logging)),
         };
         self.prepared_statements.insert(name, statement);
@@ -668,6 +789,8 @@ impl<T: TimestampManipulation> Session<T> {
                 },
                 result_formats: result_formats.into_iter().map(Into::into).collect(),
                 state: PortalState::NotStarted,
+                scroll: false,
+                hold: CursorHold::WithoutHold,
                 logging,
             },
         );
@@ -719,6 +842,8 @@ impl<T: TimestampManipulation> Session<T> {
                         parameters,
                         result_formats,
                         state: PortalState::NotStarted,
+                        scroll: false,
+                        hold: CursorHold::WithoutHold,
                         logging,
                     });
                     return Ok(name);
@@ -729,10 +854,22 @@ impl<T: TimestampManipulation> Session<T> {
         coord_bail!("unable to create a new portal");
     }
 
+    /// Discards the cached plans of the session's prepared statements, as for
+    /// `DISCARD PLANS`.
+    ///
+    /// Statements are planned anew on every execution, so this only forces
+    /// prepared statements to be described again before their next use.
+    pub fn discard_plans(&mut self) {
+        for ps in self.prepared_statements.values_mut() {
+            ps.plans_discarded = true;
+        }
+    }
+
     /// Resets the session to its initial state. Returns sinks that need to be
     /// dropped.
     pub fn reset(&mut self) {
         let _ = self.clear_transaction();
+        self.portals.clear();
         self.prepared_statements.clear();
         self.vars.reset_all();
     }
@@ -755,13 +892,13 @@ impl<T: TimestampManipulation> Session<T> {
     }
 
     /// Grants the coordinator's write lock guard to this session's inner
-    /// transaction.
+    /// transaction, which may hold it for at most `timeout`.
     ///
     /// # Panics
     /// If the inner transaction is idle. See
     /// [`TransactionStatus::grant_write_lock`].
-    pub fn grant_write_lock(&mut self, guard: OwnedMutexGuard<()>) {
-        self.transaction.grant_write_lock(guard);
+    pub fn grant_write_lock(&mut self, guard: OwnedMutexGuard<()>, timeout: Duration) {
+        self.transaction.grant_write_lock(guard, timeout);
     }
 
     /// Returns whether or not this session currently holds the write lock.
@@ -841,6 +978,9 @@ pub struct PreparedStatement {
     desc: StatementDesc,
     /// The most recent catalog revision that has verified this statement.
     pub catalog_revision: u64,
+    /// Whether the statement must be verified again regardless of the catalog
+    /// revision, because its plans were discarded by `DISCARD PLANS`.
+    pub plans_discarded: bool,
     #[derivative(Debug = "ignore")]
     logging: Arc<QCell<PreparedStatementLoggingInfo>>,
 }
@@ -883,6 +1023,22 @@ pub struct Portal {
     /// The execution state of the portal.
     #[derivative(Debug = "ignore")]
     pub state: PortalState,
+    /// Whether the portal is a cursor declared `SCROLL`, which can be fetched
+    /// from in any direction.
+    pub scroll: bool,
+    /// Whether the portal is a cursor declared `WITH HOLD`.
+    pub hold: CursorHold,
+}
+
+/// Whether a cursor outlives the transaction that declared it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorHold {
+    /// The cursor is dropped at the end of its transaction.
+    WithoutHold,
+    /// The cursor was declared `WITH HOLD` in the current transaction.
+    Uncommitted,
+    /// The cursor was declared `WITH HOLD` in a transaction that committed.
+    Committed,
 }
 
 /// Execution states of a portal.
@@ -896,6 +1052,19 @@ pub enum PortalState {
     /// is present, it is returned as a CommandComplete tag, otherwise an error
     /// is sent.
     Completed(Option<String>),
+    /// Portal is a `SCROLL` or `WITH HOLD` cursor whose rows have all been
+    /// buffered.
+    Spooled(SpooledRows),
+}
+
+/// The buffered rows of a `SCROLL` or `WITH HOLD` cursor.
+#[derive(Debug)]
+pub struct SpooledRows {
+    /// All rows of the cursor.
+    pub rows: Vec<Row>,
+    /// The position of the cursor, where 0 is before the first row and
+    /// `rows.len() + 1` after the last row.
+    pub position: usize,
 }
 
 /// State of an in-progress, rows-returning portal.
@@ -1016,16 +1185,23 @@ impl<T: TimestampManipulation> TransactionStatus<T> {
     /// If `self` is `TransactionStatus::Default`, which indicates that the
     /// transaction is idle, which is not appropriate to assign the
     /// coordinator's write lock to.
-    pub fn grant_write_lock(&mut self, guard: OwnedMutexGuard<()>) {
+    pub fn grant_write_lock(&mut self, guard: OwnedMutexGuard<()>, timeout: Duration) {
         match self {
             TransactionStatus::Default => panic!("cannot grant write lock to txn not yet started"),
             TransactionStatus::Started(txn)
             | TransactionStatus::InTransaction(txn)
             | TransactionStatus::InTransactionImplicit(txn)
-            | TransactionStatus::Failed(txn) => txn.grant_write_lock(guard),
+            | TransactionStatus::Failed(txn) => txn.grant_write_lock(guard, timeout),
         }
     }
 
+    /// The instant until which the transaction may hold the write lock, if it holds it.
+    pub fn write_lock_deadline(&self) -> Option<Instant> {
+        self.inner()
+            .filter(|txn| txn.write_lock_guard.is_some())
+            .and_then(|txn| txn.write_lock_deadline)
+    }
+
     /// The timeline of the transaction, if one exists.
     pub fn timeline(&self) -> Option<Timeline> {
         match self {
@@ -1067,6 +1243,17 @@ impl<T: TimestampManipulation> TransactionStatus<T> {
         }
     }
 
+    /// The IDs of the tables this transaction has written to.
+    pub fn written_ids(&self) -> BTreeSet<GlobalId> {
+        match self.inner() {
+            Some(Transaction {
+                ops: TransactionOps::Writes(writes),
+                ..
+            }) => writes.iter().map(|write| write.id).collect(),
+            _ => BTreeSet::new(),
+        }
+    }
+
     /// Adds operations to the current transaction. An error is produced if
     /// they cannot be merged (i.e., a timestamp-dependent read cannot be
     /// merged to an insert).
@@ -1078,9 +1265,24 @@ impl<T: TimestampManipulation> TransactionStatus<T> {
     /// aligned.
     pub fn add_ops(&mut self, add_ops: TransactionOps<T>) -> Result<(), AdapterError> {
         match self {
-            TransactionStatus::Started(Transaction { ops, access, .. })
-            | TransactionStatus::InTransaction(Transaction { ops, access, .. })
-            | TransactionStatus::InTransactionImplicit(Transaction { ops, access, .. }) => {
+            TransactionStatus::Started(Transaction {
+                ops,
+                access,
+                write_lock_guard,
+                ..
+            })
+            | TransactionStatus::InTransaction(Transaction {
+                ops,
+                access,
+                write_lock_guard,
+                ..
+            })
+            | TransactionStatus::InTransactionImplicit(Transaction {
+                ops,
+                access,
+                write_lock_guard,
+                ..
+            }) => {
                 match ops {
                     TransactionOps::None => {
                         if matches!(access, Some(TransactionAccessMode::ReadOnly))
@@ -1152,22 +1354,19 @@ impl<T: TimestampManipulation> TransactionStatus<T> {
                             // We should have already checked the access above, but make sure we don't miss
                             // it anyway.
                             assert!(!matches!(access, Some(TransactionAccessMode::ReadOnly)));
+                            // Writes may target any number of tables, since they are all
+                            // committed atomically at the same timestamp by group commit.
                             txn_writes.append(&mut add_writes);
-
-                            if txn_writes
-                                .iter()
-                                .map(|op| op.id)
-                                .collect::<BTreeSet<_>>()
-                                .len()
-                                > 1
-                            {
-                                return Err(AdapterError::MultiTableWriteTransaction);
-                            }
                         }
                         // Iff peeks do not have a timestamp (i.e. they are
                         // constant), we can permit them.
                         TransactionOps::Peeks { determination, .. }
                             if !determination.timestamp_context.contains_timestamp() => {}
+                        // Peeks that depend on a timestamp are permitted once the
+                        // transaction holds the write lock, which keeps other writes
+                        // from committing between the peek and the transaction's own
+                        // writes.
+                        TransactionOps::Peeks { .. } if write_lock_guard.is_some() => {}
                         _ => {
                             return Err(AdapterError::WriteOnlyTransaction);
                         }
@@ -1228,14 +1427,20 @@ pub struct Transaction<T> {
     pub id: TransactionId,
     /// Holds the coordinator's write lock.
     write_lock_guard: Option<OwnedMutexGuard<()>>,
+    /// The instant until which the transaction may hold the write lock.
+    ///
+    /// The lock keeps all other table writes from committing, so a transaction
+    /// that holds it for longer can only be rolled back.
+    write_lock_deadline: Option<Instant>,
     /// Access mode (read only, read write).
     access: Option<TransactionAccessMode>,
 }
 
 impl<T> Transaction<T> {
     /// Grants the write lock to this transaction for the remainder of its lifetime.
-    fn grant_write_lock(&mut self, guard: OwnedMutexGuard<()>) {
+    fn grant_write_lock(&mut self, guard: OwnedMutexGuard<()>, timeout: Duration) {
         self.write_lock_guard = Some(guard);
+        self.write_lock_deadline = Some(Instant::now() + timeout);
     }
 
     /// The timeline of the transaction, if one exists.
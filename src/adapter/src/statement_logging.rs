@@ -7,6 +7,8 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::time::Duration;
+
 use mz_controller_types::ClusterId;
 use mz_ore::cast::CastFrom;
 use mz_ore::now::EpochMillis;
@@ -14,6 +16,7 @@ use mz_repr::GlobalId;
 use mz_sql_parser::ast::StatementKind;
 use uuid::Uuid;
 
+use crate::coord::timestamp_selection::TimestampConstraint;
 use crate::session::TransactionId;
 use crate::{AdapterError, ExecuteResponse};
 
@@ -54,6 +57,9 @@ pub struct StatementBeganExecutionRecord {
     pub transaction_id: TransactionId,
     pub transient_index_id: Option<GlobalId>,
     pub mz_version: String,
+    pub plan_fingerprint: Option<String>,
+    pub timestamp_constraint: Option<TimestampConstraint>,
+    pub expected_wait: Option<Duration>,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -128,6 +134,8 @@ pub struct SessionHistoryEvent {
     pub connected_at: EpochMillis,
     pub application_name: String,
     pub authenticated_user: String,
+    pub authentication_method: &'static str,
+    pub client_cert_common_name: Option<String>,
 }
 
 impl From<&Result<ExecuteResponse, AdapterError>> for StatementEndedExecutionReason {
@@ -184,6 +192,7 @@ impl From<&ExecuteResponse> for StatementEndedExecutionReason {
             ExecuteResponse::AlteredDefaultPrivileges
             | ExecuteResponse::AlteredObject(_)
             | ExecuteResponse::AlteredIndexLogicalCompaction
+            | ExecuteResponse::AlteredQuota
             | ExecuteResponse::AlteredRole
             | ExecuteResponse::AlteredSystemConfiguration
             | ExecuteResponse::ClosedCursor
@@ -204,10 +213,13 @@ impl From<&ExecuteResponse> for StatementEndedExecutionReason {
             | ExecuteResponse::CreatedViews
             | ExecuteResponse::CreatedMaterializedView
             | ExecuteResponse::CreatedType
+            | ExecuteResponse::CreatedFunction
+            | ExecuteResponse::CreatedContinualTask
             | ExecuteResponse::Deallocate { .. }
             | ExecuteResponse::DeclaredCursor
             | ExecuteResponse::Deleted(_)
             | ExecuteResponse::DiscardedTemp
+            | ExecuteResponse::DiscardedPlans
             | ExecuteResponse::DiscardedAll
             | ExecuteResponse::DroppedObject(_)
             | ExecuteResponse::DroppedOwned
@@ -134,6 +134,9 @@ pub fn analyze_audited_statement(
             Some((StatementAction::Create, ObjectType::ClusterReplica))
         }
         StatementKind::CreateConnection => Some((StatementAction::Create, ObjectType::Connection)),
+        StatementKind::CreateContinualTask => {
+            Some((StatementAction::Create, ObjectType::ContinualTask))
+        }
         StatementKind::CreateDatabase => Some((StatementAction::Create, ObjectType::Database)),
         StatementKind::CreateIndex => Some((StatementAction::Create, ObjectType::Index)),
         StatementKind::CreateMaterializedView => {
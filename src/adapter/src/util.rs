@@ -11,7 +11,7 @@ use std::fmt::Debug;
 
 use mz_compute_client::controller::error::{
     CollectionUpdateError, DataflowCreationError, InstanceMissing, PeekError, ReadPolicyError,
-    SubscribeTargetError,
+    ReplicaUpdateError, SubscribeTargetError,
 };
 use mz_controller_types::ClusterId;
 use mz_ore::tracing::OpenTelemetryContext;
@@ -340,7 +340,8 @@ impl ShouldHalt for StorageError {
             | StorageError::Generic(_)
             | StorageError::DataflowError(_)
             | StorageError::InvalidAlter { .. }
-            | StorageError::ShuttingDown(_) => false,
+            | StorageError::ShuttingDown(_)
+            | StorageError::RtrTimeout(_) => false,
             StorageError::IOError(e) => e.is_unrecoverable(),
         }
     }
@@ -400,6 +401,14 @@ impl ShouldHalt for SubscribeTargetError {
     }
 }
 
+impl ShouldHalt for ReplicaUpdateError {
+    fn should_halt(&self) -> bool {
+        match self {
+            ReplicaUpdateError::InstanceMissing(_) | ReplicaUpdateError::ReplicaMissing(_) => false,
+        }
+    }
+}
+
 impl ShouldHalt for TransformError {
     fn should_halt(&self) -> bool {
         match self {
@@ -7,9 +7,10 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, VecDeque};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use anyhow::Context;
 use chrono::{DateTime, Utc};
@@ -18,7 +19,10 @@ use mz_ore::cast::CastFrom;
 use mz_repr::{Datum, Diff, Row, RowArena};
 use mz_secrets::cache::CachingSecretsReader;
 use mz_secrets::SecretsReader;
-use mz_sql::plan::{WebhookBodyFormat, WebhookHeaders, WebhookValidation, WebhookValidationSecret};
+use mz_sql::plan::{
+    WebhookBodyFormat, WebhookDeduplication, WebhookHeaders, WebhookValidation,
+    WebhookValidationSecret,
+};
 use mz_storage_client::controller::MonotonicAppender;
 use mz_storage_client::statistics::WebhookStatistics;
 use mz_storage_types::controller::StorageError;
@@ -44,6 +48,8 @@ pub enum AppendWebhookError {
     },
     #[error("failed to validate the request")]
     ValidationFailed,
+    #[error("a request with the same idempotency key is already being processed")]
+    DuplicateInFlight,
     // Note: we should _NEVER_ add more detail to this error, including the actual error we got
     // when running validation. This is because the error messages might contain info about the
     // arguments provided to the validation expression, we could contains user SECRETs. So by
@@ -241,6 +247,137 @@ pub struct AppendWebhookResponse {
     /// Expression used to validate a webhook request.
     #[derivative(Debug = "ignore")]
     pub validator: Option<AppendWebhookValidator>,
+    /// Tracks the idempotency keys of recent requests, if the source deduplicates requests.
+    pub deduplicator: Option<WebhookDeduplicator>,
+}
+
+/// Tracks the idempotency keys of requests that were recently appended to a webhook source, so
+/// that retried deliveries of the same request are only appended once.
+///
+/// Keys are only retained in memory, so they are forgotten when `environmentd` restarts.
+#[derive(Clone, Debug)]
+pub struct WebhookDeduplicator {
+    config: WebhookDeduplication,
+    state: Arc<Mutex<DeduplicationState>>,
+}
+
+/// The maximum number of idempotency keys retained for a single webhook source.
+const MAX_DEDUPLICATION_KEYS: usize = 1_000_000;
+
+#[derive(Debug, Default)]
+struct DeduplicationState {
+    /// Keys of requests that are currently being appended, or were appended at the time noted.
+    keys: BTreeMap<String, Option<Instant>>,
+    /// Keys of appended requests, in the order they were appended.
+    expirations: VecDeque<(Instant, String)>,
+}
+
+/// The outcome of checking a webhook request against a [`WebhookDeduplicator`].
+#[derive(Debug)]
+pub enum DeduplicationOutcome {
+    /// The request does not contain an idempotency key, so it is not deduplicated.
+    Unkeyed,
+    /// The request should be appended, after which the reservation must be committed.
+    Reserved(WebhookDeduplicationReservation),
+    /// A request with the same idempotency key was already appended.
+    Duplicate,
+}
+
+impl WebhookDeduplicator {
+    pub(crate) fn new(config: WebhookDeduplication) -> Self {
+        WebhookDeduplicator {
+            config,
+            state: Arc::new(Mutex::new(DeduplicationState::default())),
+        }
+    }
+
+    /// Checks whether the request with the provided headers is a duplicate, reserving its
+    /// idempotency key if it is not.
+    pub fn reserve(
+        &self,
+        headers: &BTreeMap<String, String>,
+    ) -> Result<DeduplicationOutcome, AppendWebhookError> {
+        self.reserve_at(headers, Instant::now())
+    }
+
+    fn reserve_at(
+        &self,
+        headers: &BTreeMap<String, String>,
+        now: Instant,
+    ) -> Result<DeduplicationOutcome, AppendWebhookError> {
+        let Some(key) = headers.get(&self.config.header_name) else {
+            return Ok(DeduplicationOutcome::Unkeyed);
+        };
+
+        let mut state = self.state.lock().expect("lock poisoned");
+        state.expire(now, self.config.window);
+        match state.keys.get(key) {
+            Some(Some(_)) => Ok(DeduplicationOutcome::Duplicate),
+            Some(None) => Err(AppendWebhookError::DuplicateInFlight),
+            None => {
+                state.keys.insert(key.clone(), None);
+                Ok(DeduplicationOutcome::Reserved(
+                    WebhookDeduplicationReservation {
+                        key: Some(key.clone()),
+                        state: Arc::clone(&self.state),
+                    },
+                ))
+            }
+        }
+    }
+}
+
+impl DeduplicationState {
+    /// Forgets the keys of requests that were appended more than `window` ago.
+    fn expire(&mut self, now: Instant, window: Duration) {
+        while let Some((appended_at, _)) = self.expirations.front() {
+            if now.saturating_duration_since(*appended_at) < window {
+                break;
+            }
+            let (_, key) = self.expirations.pop_front().expect("known to exist");
+            self.keys.remove(&key);
+        }
+    }
+}
+
+/// A reserved idempotency key for a webhook request that is being appended.
+///
+/// Dropping the reservation without calling [`WebhookDeduplicationReservation::commit`] releases
+/// the key, so that the request can be retried.
+#[derive(Debug)]
+pub struct WebhookDeduplicationReservation {
+    key: Option<String>,
+    state: Arc<Mutex<DeduplicationState>>,
+}
+
+impl WebhookDeduplicationReservation {
+    /// Marks the request as appended, causing later requests with the same key to be treated as
+    /// duplicates.
+    pub fn commit(self) {
+        self.commit_at(Instant::now())
+    }
+
+    fn commit_at(mut self, now: Instant) {
+        let key = self.key.take().expect("only taken once");
+        let mut state = self.state.lock().expect("lock poisoned");
+        state.keys.insert(key.clone(), Some(now));
+        state.expirations.push_back((now, key));
+
+        // Bound the memory we use, even if that means forgetting keys early.
+        while state.expirations.len() > MAX_DEDUPLICATION_KEYS {
+            let (_, key) = state.expirations.pop_front().expect("known to exist");
+            state.keys.remove(&key);
+        }
+    }
+}
+
+impl Drop for WebhookDeduplicationReservation {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            let mut state = self.state.lock().expect("lock poisoned");
+            state.keys.remove(&key);
+        }
+    }
 }
 
 /// A wrapper around [`MonotonicAppender`] that can get closed by the `Coordinator` if the webhook
@@ -427,7 +564,64 @@ impl Default for WebhookConcurrencyLimiter {
 
 #[cfg(test)]
 mod test {
-    use super::WebhookConcurrencyLimiter;
+    use std::collections::BTreeMap;
+    use std::time::{Duration, Instant};
+
+    use mz_sql::plan::WebhookDeduplication;
+
+    use super::{
+        AppendWebhookError, DeduplicationOutcome, WebhookConcurrencyLimiter, WebhookDeduplicator,
+    };
+
+    #[mz_ore::test]
+    fn smoke_test_deduplicator() {
+        let deduplicator = WebhookDeduplicator::new(WebhookDeduplication {
+            header_name: "idempotency-key".to_string(),
+            window: Duration::from_secs(60),
+        });
+        let headers =
+            |key: &str| BTreeMap::from([("idempotency-key".to_string(), key.to_string())]);
+        let start = Instant::now();
+
+        // Requests without a key are never deduplicated.
+        let outcome = deduplicator.reserve_at(&BTreeMap::new(), start).unwrap();
+        assert!(matches!(outcome, DeduplicationOutcome::Unkeyed));
+
+        // A concurrent request with the same key is rejected.
+        let DeduplicationOutcome::Reserved(reservation) =
+            deduplicator.reserve_at(&headers("a"), start).unwrap()
+        else {
+            panic!("expected a reservation");
+        };
+        let outcome = deduplicator.reserve_at(&headers("a"), start);
+        assert!(matches!(
+            outcome,
+            Err(AppendWebhookError::DuplicateInFlight)
+        ));
+
+        // Failing to append releases the key.
+        drop(reservation);
+        let DeduplicationOutcome::Reserved(reservation) =
+            deduplicator.reserve_at(&headers("a"), start).unwrap()
+        else {
+            panic!("expected a reservation");
+        };
+
+        // Once appended, the key is a duplicate until the window passes.
+        reservation.commit_at(start);
+        let outcome = deduplicator
+            .reserve_at(&headers("a"), start + Duration::from_secs(59))
+            .unwrap();
+        assert!(matches!(outcome, DeduplicationOutcome::Duplicate));
+        let outcome = deduplicator
+            .reserve_at(&headers("b"), start + Duration::from_secs(59))
+            .unwrap();
+        assert!(matches!(outcome, DeduplicationOutcome::Reserved(_)));
+        let outcome = deduplicator
+            .reserve_at(&headers("a"), start + Duration::from_secs(60))
+            .unwrap();
+        assert!(matches!(outcome, DeduplicationOutcome::Reserved(_)));
+    }
 
     #[mz_ore::test(tokio::test)]
     #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
@@ -96,6 +96,7 @@ async fn datadriven() {
                                             desc: RelationDesc::empty(),
                                             defaults: vec![Expr::null(); 0],
                                             conn_id: None,
+                                            delete_rows_on_commit: false,
                                             resolved_ids: ResolvedIds(BTreeSet::new()),
                                             custom_logical_compaction_window: None,
                                             is_retained_metrics_object: false,
@@ -29,6 +29,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
 pub enum VersionedEvent {
     V1(EventV1),
+    V2(EventV2),
 }
 
 impl VersionedEvent {
@@ -43,14 +44,16 @@ impl VersionedEvent {
         object_type: ObjectType,
         details: EventDetails,
         user: Option<String>,
+        context: EventContextV1,
         occurred_at: EpochMillis,
     ) -> Self {
-        Self::V1(EventV1::new(
+        Self::V2(EventV2::new(
             id,
             event_type,
             object_type,
             details,
             user,
+            context,
             occurred_at,
         ))
     }
@@ -70,6 +73,51 @@ impl VersionedEvent {
     pub fn sortable_id(&self) -> u64 {
         match self {
             VersionedEvent::V1(ev) => ev.id,
+            VersionedEvent::V2(ev) => ev.id,
+        }
+    }
+
+    pub fn event_type(&self) -> &EventType {
+        match self {
+            VersionedEvent::V1(ev) => &ev.event_type,
+            VersionedEvent::V2(ev) => &ev.event_type,
+        }
+    }
+
+    pub fn object_type(&self) -> &ObjectType {
+        match self {
+            VersionedEvent::V1(ev) => &ev.object_type,
+            VersionedEvent::V2(ev) => &ev.object_type,
+        }
+    }
+
+    pub fn details(&self) -> &EventDetails {
+        match self {
+            VersionedEvent::V1(ev) => &ev.details,
+            VersionedEvent::V2(ev) => &ev.details,
+        }
+    }
+
+    pub fn user(&self) -> Option<&str> {
+        match self {
+            VersionedEvent::V1(ev) => ev.user.as_deref(),
+            VersionedEvent::V2(ev) => ev.user.as_deref(),
+        }
+    }
+
+    /// Returns the context of the client that caused the event, if the event
+    /// version records it.
+    pub fn context(&self) -> Option<&EventContextV1> {
+        match self {
+            VersionedEvent::V1(_) => None,
+            VersionedEvent::V2(ev) => Some(&ev.context),
+        }
+    }
+
+    pub fn occurred_at(&self) -> EpochMillis {
+        match self {
+            VersionedEvent::V1(ev) => ev.occurred_at,
+            VersionedEvent::V2(ev) => ev.occurred_at,
         }
     }
 }
@@ -82,6 +130,7 @@ pub enum EventType {
     Alter,
     Grant,
     Revoke,
+    Use,
 }
 
 impl EventType {
@@ -92,6 +141,7 @@ impl EventType {
             EventType::Alter => "Altered",
             EventType::Grant => "Granted",
             EventType::Revoke => "Revoked",
+            EventType::Use => "Used",
         }
     }
 }
@@ -104,6 +154,7 @@ pub enum ObjectType {
     Cluster,
     ClusterReplica,
     Connection,
+    ContinualTask,
     Database,
     Func,
     Index,
@@ -125,6 +176,7 @@ impl ObjectType {
             ObjectType::Cluster => "Cluster",
             ObjectType::ClusterReplica => "Cluster Replica",
             ObjectType::Connection => "Connection",
+            ObjectType::ContinualTask => "Continual Task",
             ObjectType::Database => "Database",
             ObjectType::Func => "Function",
             ObjectType::Index => "Index",
@@ -171,6 +223,7 @@ pub enum EventDetails {
     SchemaV2(SchemaV2),
     UpdateItemV1(UpdateItemV1),
     RenameSchemaV1(RenameSchemaV1),
+    UseSecretV1(UseSecretV1),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
@@ -366,6 +419,18 @@ pub struct UpdateItemV1 {
     pub name: FullNameV1,
 }
 
+/// Describes a secret that an object references, and whose value is therefore
+/// read into the configuration of the object when it connects to an external
+/// system.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
+pub struct UseSecretV1 {
+    pub id: String,
+    #[serde(flatten)]
+    pub name: FullNameV1,
+    pub used_by_id: String,
+    pub used_by_name: FullNameV1,
+}
+
 impl EventDetails {
     pub fn as_json(&self) -> serde_json::Value {
         match self {
@@ -400,6 +465,7 @@ impl EventDetails {
             }
             EventDetails::UpdateOwnerV1(v) => serde_json::to_value(v).expect("must serialize"),
             EventDetails::UpdateItemV1(v) => serde_json::to_value(v).expect("must serialize"),
+            EventDetails::UseSecretV1(v) => serde_json::to_value(v).expect("must serialize"),
         }
     }
 }
@@ -434,6 +500,67 @@ impl EventV1 {
     }
 }
 
+/// Describes the client that caused an event.
+///
+/// Which fields are recorded is controlled by the `audit_event_context_fields`
+/// system variable. Fields that are not recorded, or that are unknown for the
+/// client, are `None`.
+#[derive(
+    Clone, Debug, Default, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary,
+)]
+pub struct EventContextV1 {
+    /// The IP address of the client.
+    pub client_ip: Option<String>,
+    /// The value of the `application_name` session variable when the client
+    /// connected.
+    pub application_name: Option<String>,
+    /// The ID that the client supplied for the HTTP request that opened its
+    /// session.
+    pub http_request_id: Option<String>,
+    /// The ID of the user in the external identity provider that
+    /// authenticated the client.
+    pub sso_subject: Option<String>,
+}
+
+impl EventContextV1 {
+    pub fn as_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("must serialize")
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
+pub struct EventV2 {
+    pub id: u64,
+    pub event_type: EventType,
+    pub object_type: ObjectType,
+    pub details: EventDetails,
+    pub user: Option<String>,
+    pub context: EventContextV1,
+    pub occurred_at: EpochMillis,
+}
+
+impl EventV2 {
+    fn new(
+        id: u64,
+        event_type: EventType,
+        object_type: ObjectType,
+        details: EventDetails,
+        user: Option<String>,
+        context: EventContextV1,
+        occurred_at: EpochMillis,
+    ) -> EventV2 {
+        EventV2 {
+            id,
+            event_type,
+            object_type,
+            details,
+            user,
+            context,
+            occurred_at,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
 pub struct StorageUsageV1 {
     pub id: u64,
@@ -458,6 +585,78 @@ impl StorageUsageV1 {
     }
 }
 
+/// Describes the bytes read from and written to persist for a collection over a
+/// collection interval ending at `collection_timestamp`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
+pub struct StorageIoUsageV1 {
+    pub id: u64,
+    pub object_id: String,
+    pub bytes_read: u64,
+    pub bytes_written: u64,
+    pub collection_timestamp: EpochMillis,
+}
+
+impl StorageIoUsageV1 {
+    pub fn new(
+        id: u64,
+        object_id: String,
+        bytes_read: u64,
+        bytes_written: u64,
+        collection_timestamp: EpochMillis,
+    ) -> StorageIoUsageV1 {
+        StorageIoUsageV1 {
+            id,
+            object_id,
+            bytes_read,
+            bytes_written,
+            collection_timestamp,
+        }
+    }
+}
+
+/// Describes the compute resources that a role used on a cluster over a
+/// collection interval ending at `collection_timestamp`.
+///
+/// The CPU and memory of the cluster's replicas, averaged over the interval,
+/// are attributed to the role that owns the cluster. The peeks are
+/// attributed to the role that ran them.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
+pub struct ComputeUsageV1 {
+    pub id: u64,
+    pub cluster_id: String,
+    pub role_id: String,
+    pub cpu_nano_cores: u64,
+    pub memory_bytes: u64,
+    pub peek_count: u64,
+    pub peek_time_us: u64,
+    pub collection_timestamp: EpochMillis,
+}
+
+impl ComputeUsageV1 {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: u64,
+        cluster_id: String,
+        role_id: String,
+        cpu_nano_cores: u64,
+        memory_bytes: u64,
+        peek_count: u64,
+        peek_time_us: u64,
+        collection_timestamp: EpochMillis,
+    ) -> ComputeUsageV1 {
+        ComputeUsageV1 {
+            id,
+            cluster_id,
+            role_id,
+            cpu_nano_cores,
+            memory_bytes,
+            peek_count,
+            peek_time_us,
+            collection_timestamp,
+        }
+    }
+}
+
 /// Describes the environment's storage usage at a point in time.
 ///
 /// This type is persisted in the catalog across restarts, so any updates to the
@@ -465,6 +664,8 @@ impl StorageUsageV1 {
 #[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
 pub enum VersionedStorageUsage {
     V1(StorageUsageV1),
+    IoV1(StorageIoUsageV1),
+    ComputeV1(ComputeUsageV1),
 }
 
 impl VersionedStorageUsage {
@@ -485,6 +686,51 @@ impl VersionedStorageUsage {
         ))
     }
 
+    /// Create a new IO usage snapshot.
+    /// This function must always require and produce the most
+    /// recent IO variant of VersionedStorageMetrics.
+    pub fn new_io(
+        id: u64,
+        object_id: String,
+        bytes_read: u64,
+        bytes_written: u64,
+        collection_timestamp: EpochMillis,
+    ) -> Self {
+        Self::IoV1(StorageIoUsageV1::new(
+            id,
+            object_id,
+            bytes_read,
+            bytes_written,
+            collection_timestamp,
+        ))
+    }
+
+    /// Create a new compute usage snapshot.
+    /// This function must always require and produce the most
+    /// recent compute variant of VersionedStorageMetrics.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_compute(
+        id: u64,
+        cluster_id: String,
+        role_id: String,
+        cpu_nano_cores: u64,
+        memory_bytes: u64,
+        peek_count: u64,
+        peek_time_us: u64,
+        collection_timestamp: EpochMillis,
+    ) -> Self {
+        Self::ComputeV1(ComputeUsageV1::new(
+            id,
+            cluster_id,
+            role_id,
+            cpu_nano_cores,
+            memory_bytes,
+            peek_count,
+            peek_time_us,
+            collection_timestamp,
+        ))
+    }
+
     // Implement deserialize and serialize so writers and readers don't have to
     // coordinate about which Serializer to use.
     pub fn deserialize(data: &[u8]) -> Result<Self, anyhow::Error> {
@@ -500,6 +746,14 @@ impl VersionedStorageUsage {
             VersionedStorageUsage::V1(StorageUsageV1 {
                 collection_timestamp,
                 ..
+            })
+            | VersionedStorageUsage::IoV1(StorageIoUsageV1 {
+                collection_timestamp,
+                ..
+            })
+            | VersionedStorageUsage::ComputeV1(ComputeUsageV1 {
+                collection_timestamp,
+                ..
             }) => *collection_timestamp,
         }
     }
@@ -509,33 +763,60 @@ impl VersionedStorageUsage {
     pub fn sortable_id(&self) -> u64 {
         match self {
             VersionedStorageUsage::V1(usage) => usage.id,
+            VersionedStorageUsage::IoV1(usage) => usage.id,
+            VersionedStorageUsage::ComputeV1(usage) => usage.id,
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{EventDetails, EventType, EventV1, IdNameV1, ObjectType, VersionedEvent};
+    use crate::{
+        EventContextV1, EventDetails, EventType, EventV1, EventV2, IdNameV1, ObjectType,
+        VersionedEvent,
+    };
 
     // Test all versions of events. This test hard codes bytes so that
     // programmers are not able to change data structures here without this test
     // failing. Instead of changing data structures, add new variants.
     #[mz_ore::test]
     fn test_audit_log() -> Result<(), anyhow::Error> {
-        let cases: Vec<(VersionedEvent, &'static str)> = vec![(
-            VersionedEvent::V1(EventV1::new(
-                2,
-                EventType::Drop,
-                ObjectType::ClusterReplica,
-                EventDetails::IdNameV1(IdNameV1 {
-                    id: "u1".to_string(),
-                    name: "name".into(),
-                }),
-                None,
-                2,
-            )),
-            r#"{"V1":{"id":2,"event_type":"drop","object_type":"cluster-replica","details":{"IdNameV1":{"id":"u1","name":"name"}},"user":null,"occurred_at":2}}"#,
-        )];
+        let cases: Vec<(VersionedEvent, &'static str)> = vec![
+            (
+                VersionedEvent::V1(EventV1::new(
+                    2,
+                    EventType::Drop,
+                    ObjectType::ClusterReplica,
+                    EventDetails::IdNameV1(IdNameV1 {
+                        id: "u1".to_string(),
+                        name: "name".into(),
+                    }),
+                    None,
+                    2,
+                )),
+                r#"{"V1":{"id":2,"event_type":"drop","object_type":"cluster-replica","details":{"IdNameV1":{"id":"u1","name":"name"}},"user":null,"occurred_at":2}}"#,
+            ),
+            (
+                VersionedEvent::V2(EventV2::new(
+                    3,
+                    EventType::Drop,
+                    ObjectType::ClusterReplica,
+                    EventDetails::IdNameV1(IdNameV1 {
+                        id: "u1".to_string(),
+                        name: "name".into(),
+                    }),
+                    Some("user".into()),
+                    EventContextV1 {
+                        client_ip: Some("127.0.0.1".into()),
+                        application_name: Some("psql".into()),
+                        http_request_id: None,
+                        sso_subject: None,
+                    },
+                    3,
+                )),
+                r#"{"V2":{"id":3,"event_type":"drop","object_type":"cluster-replica","details":{"IdNameV1":{"id":"u1","name":"name"}},"user":"user","context":{"client_ip":"127.0.0.1","application_name":"psql","http_request_id":null,"sso_subject":null},"occurred_at":3}}"#,
+            ),
+        ];
 
         for (event, expected_bytes) in cases {
             let event_bytes = serde_json::to_vec(&event).unwrap();
@@ -10,7 +10,7 @@
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
-use anyhow::anyhow;
+use anyhow::{anyhow, bail};
 use async_trait::async_trait;
 use aws_config::SdkConfig;
 use aws_sdk_secretsmanager::config::Builder as SecretsManagerConfigBuilder;
@@ -18,9 +18,14 @@ use aws_sdk_secretsmanager::error::SdkError;
 use aws_sdk_secretsmanager::primitives::Blob;
 use aws_sdk_secretsmanager::types::{Filter, FilterNameStringType, Tag};
 use aws_sdk_secretsmanager::Client;
+use aws_types::region::Region;
 use mz_repr::GlobalId;
+use mz_secrets::external::{ExternalSecretReference, ExternalSecretsResolver};
 use mz_secrets::{SecretsController, SecretsReader};
 
+/// The prefix of the names of all secrets that secrets controllers store in AWS Secrets Manager.
+pub const SECRETS_CONTROLLER_NAMESPACE: &str = "/user-managed/";
+
 #[derive(Clone, Debug)]
 pub struct AwsSecretsController {
     pub client: AwsSecretsClient,
@@ -221,3 +226,49 @@ impl SecretsReader for AwsSecretsClient {
             .into_inner())
     }
 }
+
+/// Resolves external secrets stored in AWS Secrets Manager, with the credentials of the process.
+///
+/// Secrets in the [namespace](SECRETS_CONTROLLER_NAMESPACE) of secrets controllers are never
+/// resolved, as they hold the values of the secrets of Materialize itself.
+#[derive(Clone, Debug)]
+pub struct AwsExternalSecretsResolver {
+    sdk_config: SdkConfig,
+}
+
+impl AwsExternalSecretsResolver {
+    pub async fn new() -> Self {
+        AwsExternalSecretsResolver {
+            sdk_config: load_sdk_config().await,
+        }
+    }
+}
+
+#[async_trait]
+impl ExternalSecretsResolver for AwsExternalSecretsResolver {
+    async fn resolve(&self, reference: &ExternalSecretReference) -> Result<Vec<u8>, anyhow::Error> {
+        let ExternalSecretReference::AwsSecretsManager { arn } = reference;
+        if reference
+            .aws_secret_name()
+            .starts_with(SECRETS_CONTROLLER_NAMESPACE)
+        {
+            bail!("external secret {arn} is managed by Materialize");
+        }
+        // ARNs have the form `arn:aws:secretsmanager:<region>:<account>:secret:<name>`, and
+        // secrets can only be read from the region they are stored in.
+        let mut config = SecretsManagerConfigBuilder::from(&self.sdk_config);
+        if let Some(region) = arn.split(':').nth(3).filter(|region| !region.is_empty()) {
+            config = config.region(Region::new(region.to_string()));
+        }
+        let output = Client::from_conf(config.build())
+            .get_secret_value()
+            .secret_id(arn)
+            .send()
+            .await?;
+        match (output.secret_string(), output.secret_binary()) {
+            (Some(value), _) => Ok(value.as_bytes().to_vec()),
+            (None, Some(value)) => Ok(value.clone().into_inner()),
+            (None, None) => Err(anyhow!("secret {arn} has no value")),
+        }
+    }
+}
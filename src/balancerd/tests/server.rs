@@ -136,6 +136,7 @@ async fn test_balancer() {
     let cert_config = Some(TlsCertConfig {
         cert: server_cert.clone(),
         key: server_key.clone(),
+        client_auth: None,
     });
 
     let body = r#"{"query": "select 12234"}"#;
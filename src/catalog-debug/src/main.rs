@@ -27,9 +27,9 @@ use mz_catalog::durable::debug::{
     AuditLogCollection, ClusterCollection, ClusterIntrospectionSourceIndexCollection,
     ClusterReplicaCollection, Collection, CollectionTrace, CollectionType, CommentCollection,
     ConfigCollection, DatabaseCollection, DebugCatalogState, DefaultPrivilegeCollection,
-    IdAllocatorCollection, ItemCollection, RoleCollection, SchemaCollection, SettingCollection,
-    StorageUsageCollection, SystemConfigurationCollection, SystemItemMappingCollection,
-    SystemPrivilegeCollection, TimestampCollection, Trace,
+    IdAllocatorCollection, ItemCollection, QuotaCollection, RoleCollection, SchemaCollection,
+    SettingCollection, StorageUsageCollection, SystemConfigurationCollection,
+    SystemItemMappingCollection, SystemPrivilegeCollection, TimestampCollection, Trace,
 };
 use mz_catalog::durable::{
     persist_backed_catalog_state, BootstrapArgs, OpenableDurableCatalogState,
@@ -248,6 +248,7 @@ macro_rules! for_collection {
             CollectionType::DefaultPrivileges => $fn::<DefaultPrivilegeCollection>($($arg),*).await?,
             CollectionType::IdAlloc => $fn::<IdAllocatorCollection>($($arg),*).await?,
             CollectionType::Item => $fn::<ItemCollection>($($arg),*).await?,
+            CollectionType::Quota => $fn::<QuotaCollection>($($arg),*).await?,
             CollectionType::Role => $fn::<RoleCollection>($($arg),*).await?,
             CollectionType::Schema => $fn::<SchemaCollection>($($arg),*).await?,
             CollectionType::Setting => $fn::<SettingCollection>($($arg),*).await?,
@@ -374,6 +375,7 @@ async fn dump(
         default_privileges,
         id_allocator,
         items,
+        quotas,
         roles,
         schemas,
         settings,
@@ -396,6 +398,7 @@ async fn dump(
     dump_col(&mut data, default_privileges, &ignore, stats_only);
     dump_col(&mut data, id_allocator, &ignore, stats_only);
     dump_col(&mut data, items, &ignore, stats_only);
+    dump_col(&mut data, quotas, &ignore, stats_only);
     dump_col(&mut data, roles, &ignore, stats_only);
     dump_col(&mut data, schemas, &ignore, stats_only);
     dump_col(&mut data, settings, &ignore, stats_only);
@@ -448,7 +451,7 @@ async fn upgrade_check(
         .0
         .clone();
 
-    let (_catalog, _, last_catalog_version) = Catalog::initialize_state(
+    let (_catalog, builtin_migration_metadata, last_catalog_version) = Catalog::initialize_state(
         StateConfig {
             unsafe_mode: true,
             all_features: false,
@@ -482,6 +485,7 @@ async fn upgrade_check(
         dur.as_millis(),
     );
     println!("{msg}");
+    print!("{}", builtin_migration_metadata.report);
     Ok(())
 }
 
@@ -161,6 +161,7 @@ fn main() -> anyhow::Result<()> {
         .enum_attribute("ResolvedDatabaseSpecifier.value", ATTR)
         .enum_attribute("CommentKey.object", ATTR)
         .enum_attribute("CommentKey.sub_component", ATTR)
+        .enum_attribute("QuotaKey.subject", ATTR)
         .enum_attribute("ResolvedDatabaseSpecifier.spec", ATTR)
         .enum_attribute("SchemaSpecifier.spec", ATTR)
         .enum_attribute("RoleVars.Entry.val", ATTR)
@@ -171,6 +172,12 @@ fn main() -> anyhow::Result<()> {
         //
         // [^1]: https://serde.rs/enum-representations.html#internally-tagged
         .enum_attribute("StateUpdateKind.kind", "#[serde(tag = \"kind\")]")
+        // Roles without a password must serialize exactly like they did before the password was
+        // added, so that existing roles can be read and retracted without a migration.
+        .field_attribute(
+            "RoleAttributes.password",
+            "#[serde(skip_serializing_if = \"Option::is_none\")]",
+        )
         // We derive Arbitrary for all protobuf types for wire compatibility testing.
         .message_attribute(".", ARBITRARY_ATTR)
         .enum_attribute(".", ARBITRARY_ATTR)
@@ -47,9 +47,10 @@ use mz_sql::session::user::{
 };
 use mz_storage_client::controller::IntrospectionType;
 use mz_storage_client::healthcheck::{
-    MZ_AWS_PRIVATELINK_CONNECTION_STATUS_HISTORY_DESC, MZ_PREPARED_STATEMENT_HISTORY_DESC,
-    MZ_SESSION_HISTORY_DESC, MZ_SINK_STATUS_HISTORY_DESC, MZ_SOURCE_STATUS_HISTORY_DESC,
-    MZ_SQL_TEXT_DESC, MZ_STATEMENT_EXECUTION_HISTORY_DESC,
+    MZ_AWS_PRIVATELINK_CONNECTION_STATUS_HISTORY_DESC, MZ_DATAFLOW_STATISTICS_HISTORY_DESC,
+    MZ_OBJECT_STATUS_HISTORY_DESC, MZ_PREPARED_STATEMENT_HISTORY_DESC, MZ_SESSION_HISTORY_DESC,
+    MZ_SINK_STATUS_HISTORY_DESC, MZ_SOURCE_STATUS_HISTORY_DESC, MZ_SQL_TEXT_DESC,
+    MZ_STATEMENT_EXECUTION_HISTORY_DESC,
 };
 use mz_storage_client::statistics::{MZ_SINK_STATISTICS_RAW_DESC, MZ_SOURCE_STATISTICS_RAW_DESC};
 use once_cell::sync::Lazy;
@@ -1988,7 +1989,17 @@ pub static MZ_COMPUTE_HYDRATION_STATUSES: Lazy<BuiltinSource> = Lazy::new(|| Bui
     desc: RelationDesc::empty()
         .with_column("object_id", ScalarType::String.nullable(false))
         .with_column("replica_id", ScalarType::String.nullable(false))
-        .with_column("hydrated", ScalarType::Bool.nullable(false)),
+        .with_column("hydrated", ScalarType::Bool.nullable(false))
+        .with_column(
+            "hydration_started_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        )
+        .with_column(
+            "hydrated_at",
+            ScalarType::TimestampTz { precision: None }.nullable(true),
+        )
+        .with_column("records_processed", ScalarType::UInt64.nullable(true))
+        .with_column("bytes_processed", ScalarType::UInt64.nullable(true)),
     is_retained_metrics_object: false,
     access: vec![PUBLIC_SELECT],
 });
@@ -2008,6 +2019,56 @@ pub static MZ_COMPUTE_OPERATOR_HYDRATION_STATUSES_PER_WORKER: Lazy<BuiltinSource
         access: vec![PUBLIC_SELECT],
     });
 
+pub static MZ_DATAFLOW_STATISTICS_HISTORY: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_dataflow_statistics_history",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::SOURCE_MZ_DATAFLOW_STATISTICS_HISTORY_OID,
+    data_source: IntrospectionType::ComputeDataflowStatisticsHistory,
+    desc: MZ_DATAFLOW_STATISTICS_HISTORY_DESC.clone(),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_DATAFLOW_STATISTICS_RATES: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "mz_dataflow_statistics_rates",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::VIEW_MZ_DATAFLOW_STATISTICS_RATES_OID,
+    column_defs: None,
+    sql: "
+WITH samples AS (
+    SELECT
+        object_id,
+        replica_id,
+        sampled_at,
+        records_processed,
+        arrangement_size,
+        error_count,
+        lag(sampled_at) OVER (PARTITION BY object_id, replica_id ORDER BY sampled_at)
+            AS prev_sampled_at,
+        lag(records_processed) OVER (PARTITION BY object_id, replica_id ORDER BY sampled_at)
+            AS prev_records_processed,
+        lag(arrangement_size) OVER (PARTITION BY object_id, replica_id ORDER BY sampled_at)
+            AS prev_arrangement_size
+    FROM mz_internal.mz_dataflow_statistics_history
+)
+SELECT
+    object_id,
+    replica_id,
+    sampled_at,
+    -- The counter restarts from zero when the dataflow is reinstalled on the replica.
+    CASE WHEN records_processed >= prev_records_processed
+        THEN (records_processed::float8 - prev_records_processed::float8)
+            / EXTRACT(epoch FROM sampled_at - prev_sampled_at)::float8
+    END AS records_processed_per_second,
+    (arrangement_size::float8 - prev_arrangement_size::float8)
+        / EXTRACT(epoch FROM sampled_at - prev_sampled_at)::float8
+        AS arrangement_growth_per_second,
+    error_count
+FROM samples
+WHERE prev_sampled_at < sampled_at",
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_DATABASES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_databases",
     schema: MZ_CATALOG_SCHEMA,
@@ -2224,6 +2285,40 @@ pub static MZ_MATERIALIZED_VIEWS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable
     is_retained_metrics_object: false,
     access: vec![PUBLIC_SELECT],
 });
+pub static MZ_CONTINUAL_TASKS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_continual_tasks",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_CONTINUAL_TASKS_OID,
+    desc: RelationDesc::empty()
+        .with_column("id", ScalarType::String.nullable(false))
+        .with_column("oid", ScalarType::Oid.nullable(false))
+        .with_column("schema_id", ScalarType::String.nullable(false))
+        .with_column("name", ScalarType::String.nullable(false))
+        .with_column("cluster_id", ScalarType::String.nullable(false))
+        .with_column("target_id", ScalarType::String.nullable(false))
+        .with_column("definition", ScalarType::String.nullable(false))
+        .with_column("owner_id", ScalarType::String.nullable(false))
+        .with_column(
+            "privileges",
+            ScalarType::Array(Box::new(ScalarType::MzAclItem)).nullable(false),
+        )
+        .with_column("create_sql", ScalarType::String.nullable(false))
+        .with_column("redacted_create_sql", ScalarType::String.nullable(false)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+pub static MZ_CONTINUAL_TASK_STATUSES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_continual_task_statuses",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_CONTINUAL_TASK_STATUSES_OID,
+    desc: RelationDesc::empty()
+        .with_column("id", ScalarType::String.nullable(false))
+        .with_column("resume_frontier", ScalarType::MzTimestamp.nullable(false))
+        .with_column("status", ScalarType::String.nullable(false))
+        .with_column("error", ScalarType::String.nullable(true)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
 pub static MZ_TYPES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_types",
     schema: MZ_CATALOG_SCHEMA,
@@ -2538,7 +2633,9 @@ pub static MZ_AUDIT_EVENTS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
         .with_column(
             "occurred_at",
             ScalarType::TimestampTz { precision: None }.nullable(false),
-        ),
+        )
+        .with_column("context", ScalarType::Jsonb.nullable(true))
+        .with_key(vec![0]),
     is_retained_metrics_object: false,
     access: vec![PUBLIC_SELECT],
 });
@@ -2617,7 +2714,8 @@ pub static MZ_STATEMENT_EXECUTION_HISTORY_REDACTED: Lazy<BuiltinView> = Lazy::ne
 SELECT id, prepared_statement_id, sample_rate, cluster_id, application_name,
 cluster_name, transaction_isolation, execution_timestamp, transaction_id,
 transient_index_id, mz_version, began_at, finished_at, finished_status,
-error_message, rows_returned, execution_strategy
+error_message, rows_returned, execution_strategy, plan_fingerprint,
+timestamp_constraint, expected_wait
 FROM mz_internal.mz_statement_execution_history",
     access: vec![SUPPORT_SELECT, MONITOR_REDACTED_SELECT, MONITOR_SELECT],
 });
@@ -2651,6 +2749,28 @@ pub static MZ_SQL_TEXT_REDACTED: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     access: vec![MONITOR_SELECT, MONITOR_REDACTED_SELECT, SUPPORT_SELECT],
 });
 
+pub static MZ_SHOW_CREATE_REDACTED: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "mz_show_create_redacted",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::VIEW_MZ_SHOW_CREATE_REDACTED_OID,
+    column_defs: Some("id, schema_id, name, type, redacted_create_sql"),
+    sql: "
+SELECT o.id, o.schema_id, o.name, o.type, c.redacted_create_sql
+FROM mz_catalog.mz_objects o
+JOIN (
+    SELECT id, redacted_create_sql FROM mz_catalog.mz_tables
+    UNION ALL SELECT id, redacted_create_sql FROM mz_catalog.mz_sources
+    UNION ALL SELECT id, redacted_create_sql FROM mz_catalog.mz_sinks
+    UNION ALL SELECT id, redacted_create_sql FROM mz_catalog.mz_views
+    UNION ALL SELECT id, redacted_create_sql FROM mz_catalog.mz_materialized_views
+    UNION ALL SELECT id, redacted_create_sql FROM mz_catalog.mz_indexes
+    UNION ALL SELECT id, redacted_create_sql FROM mz_catalog.mz_connections
+    UNION ALL SELECT id, redacted_create_sql FROM mz_catalog.mz_types
+) c ON o.id = c.id
+WHERE c.redacted_create_sql IS NOT NULL",
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_RECENT_SQL_TEXT: Lazy<BuiltinView> = Lazy::new(|| {
     BuiltinView {
         name: "mz_recent_sql_text",
@@ -2705,7 +2825,8 @@ transaction_isolation, execution_timestamp, transient_index_id, params, mz_versi
 error_message, rows_returned, execution_strategy, transaction_id,
 mpsh.id AS prepared_statement_id, sql_hash, mpsh.name AS prepared_statement_name,
 session_id, prepared_at, statement_type, throttled_count,
-initial_application_name, authenticated_user
+initial_application_name, authenticated_user, plan_fingerprint, timestamp_constraint,
+expected_wait
 FROM mz_internal.mz_statement_execution_history mseh,
      mz_internal.mz_prepared_statement_history mpsh,
      mz_internal.mz_session_history msh
@@ -2752,6 +2873,48 @@ WHERE mralt.sql_hash = mrst.sql_hash",
     access: vec![MONITOR_SELECT, MONITOR_REDACTED_SELECT, SUPPORT_SELECT],
 });
 
+pub static MZ_STATEMENT_HISTORY: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "mz_statement_history",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::VIEW_MZ_STATEMENT_HISTORY_OID,
+    column_defs: None,
+    sql: "
+SELECT
+    malt.execution_id, malt.session_id, malt.authenticated_user, malt.application_name,
+    malt.cluster_id, malt.cluster_name, mst.sql, malt.statement_type, malt.began_at,
+    malt.finished_at, malt.finished_at - malt.began_at AS duration, malt.finished_status,
+    malt.error_message, malt.rows_returned, malt.execution_strategy, malt.plan_fingerprint,
+    malt.transaction_isolation, malt.execution_timestamp, malt.timestamp_constraint,
+    malt.expected_wait, malt.sample_rate
+FROM mz_internal.mz_activity_log_thinned malt,
+     (SELECT DISTINCT prepared_day, sql_hash, sql FROM mz_internal.mz_sql_text) mst
+WHERE malt.sql_hash = mst.sql_hash
+AND date_trunc('day', malt.prepared_at) = mst.prepared_day
+AND malt.finished_at + current_setting('statement_history_retention')::interval > now()",
+    access: vec![MONITOR_SELECT],
+});
+
+pub static MZ_STATEMENT_HISTORY_REDACTED: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "mz_statement_history_redacted",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::VIEW_MZ_STATEMENT_HISTORY_REDACTED_OID,
+    column_defs: None,
+    sql: "
+SELECT
+    malt.execution_id, malt.session_id, malt.authenticated_user, malt.application_name,
+    malt.cluster_id, malt.cluster_name, mst.redacted_sql, malt.statement_type, malt.began_at,
+    malt.finished_at, malt.finished_at - malt.began_at AS duration, malt.finished_status,
+    malt.error_message, malt.rows_returned, malt.execution_strategy, malt.plan_fingerprint,
+    malt.transaction_isolation, malt.execution_timestamp, malt.timestamp_constraint,
+    malt.expected_wait, malt.sample_rate
+FROM mz_internal.mz_activity_log_thinned malt,
+     (SELECT DISTINCT prepared_day, sql_hash, redacted_sql FROM mz_internal.mz_sql_text) mst
+WHERE malt.sql_hash = mst.sql_hash
+AND date_trunc('day', malt.prepared_at) = mst.prepared_day
+AND malt.finished_at + current_setting('statement_history_retention')::interval > now()",
+    access: vec![MONITOR_SELECT, MONITOR_REDACTED_SELECT, SUPPORT_SELECT],
+});
+
 pub static MZ_STATEMENT_LIFECYCLE_HISTORY: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
     name: "mz_statement_lifecycle_history",
     schema: MZ_INTERNAL_SCHEMA,
@@ -2847,6 +3010,27 @@ WHERE mz_sources.id NOT LIKE 's%';",
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_SOURCE_RETRIES: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "mz_source_retries",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::VIEW_MZ_SOURCE_RETRIES_OID,
+    column_defs: None,
+    sql: "
+SELECT
+    id,
+    CASE
+        WHEN details->'retry'->>'next_attempt_at' IS NULL THEN 'exhausted'
+        ELSE 'retrying'
+    END AS state,
+    (details->'retry'->>'attempt')::uint8 AS attempt,
+    (details->'retry'->>'max_attempts')::uint8 AS max_attempts,
+    (details->'retry'->>'next_attempt_at')::timestamptz AS next_attempt_at,
+    error
+FROM mz_internal.mz_source_statuses
+WHERE status = 'stalled' AND details->'retry' IS NOT NULL",
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_SINK_STATUS_HISTORY: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
     name: "mz_sink_status_history",
     schema: MZ_INTERNAL_SCHEMA,
@@ -2884,6 +3068,50 @@ WHERE
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_OBJECT_STATUS_HISTORY: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_object_status_history",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::SOURCE_MZ_OBJECT_STATUS_HISTORY_OID,
+    data_source: IntrospectionType::ObjectStatusHistory,
+    desc: MZ_OBJECT_STATUS_HISTORY_DESC.clone(),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_OBJECT_STATUSES: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "mz_object_statuses",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::VIEW_MZ_OBJECT_STATUSES_OID,
+    column_defs: None,
+    sql: "
+WITH latest_events AS (
+    SELECT DISTINCT ON (object_type, object_id)
+        occurred_at, object_id, object_type, status, reason
+    FROM mz_internal.mz_object_status_history
+    ORDER BY object_type, object_id, occurred_at DESC
+),
+objects AS (
+    SELECT id, name, 'source' AS type FROM mz_catalog.mz_sources
+    UNION ALL
+    SELECT id, name, 'sink' AS type FROM mz_catalog.mz_sinks
+    UNION ALL
+    SELECT id, name, 'cluster' AS type FROM mz_catalog.mz_clusters
+    UNION ALL
+    SELECT id, name, 'materialized-view' AS type FROM mz_catalog.mz_materialized_views
+)
+SELECT
+    objects.id,
+    objects.name,
+    objects.type,
+    latest_events.status,
+    latest_events.reason,
+    latest_events.occurred_at AS last_status_change_at
+FROM objects
+JOIN latest_events
+    ON objects.id = latest_events.object_id AND objects.type = latest_events.object_type",
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_STORAGE_USAGE_BY_SHARD: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_storage_usage_by_shard",
     schema: MZ_INTERNAL_SCHEMA,
@@ -2900,6 +3128,154 @@ pub static MZ_STORAGE_USAGE_BY_SHARD: Lazy<BuiltinTable> = Lazy::new(|| BuiltinT
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_STORAGE_IO_USAGE: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_storage_io_usage",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_STORAGE_IO_USAGE_OID,
+    desc: RelationDesc::empty()
+        .with_column("id", ScalarType::UInt64.nullable(false))
+        .with_column("object_id", ScalarType::String.nullable(false))
+        .with_column("bytes_read", ScalarType::UInt64.nullable(false))
+        .with_column("bytes_written", ScalarType::UInt64.nullable(false))
+        .with_column(
+            "collection_timestamp",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        ),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_USAGE_BY_CLUSTER: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_usage_by_cluster",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_USAGE_BY_CLUSTER_OID,
+    desc: RelationDesc::empty()
+        .with_column("id", ScalarType::UInt64.nullable(false))
+        .with_column("cluster_id", ScalarType::String.nullable(false))
+        .with_column("role_id", ScalarType::String.nullable(false))
+        .with_column("cpu_nano_cores", ScalarType::UInt64.nullable(false))
+        .with_column("memory_bytes", ScalarType::UInt64.nullable(false))
+        .with_column("peek_count", ScalarType::UInt64.nullable(false))
+        .with_column("peek_time", ScalarType::Interval.nullable(false))
+        .with_column(
+            "collection_timestamp",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        ),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_USAGE_BY_ROLE: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "mz_usage_by_role",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::VIEW_MZ_USAGE_BY_ROLE_OID,
+    column_defs: Some(
+        "role_id, cpu_nano_cores, memory_bytes, peek_count, peek_time, collection_timestamp",
+    ),
+    sql: "
+SELECT
+    role_id,
+    sum(cpu_nano_cores)::uint8,
+    sum(memory_bytes)::uint8,
+    sum(peek_count)::uint8,
+    sum(peek_time),
+    collection_timestamp
+FROM mz_internal.mz_usage_by_cluster
+GROUP BY role_id, collection_timestamp",
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_PERSIST_SHARD_HEALTH: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_persist_shard_health",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_PERSIST_SHARD_HEALTH_OID,
+    desc: RelationDesc::empty()
+        .with_column("shard_id", ScalarType::String.nullable(false))
+        .with_column("since", ScalarType::MzTimestamp.nullable(true))
+        .with_column("upper", ScalarType::MzTimestamp.nullable(true))
+        .with_column("batch_count", ScalarType::UInt64.nullable(false))
+        .with_column("spine_batch_count", ScalarType::UInt64.nullable(false))
+        .with_column("part_count", ScalarType::UInt64.nullable(false))
+        .with_column("size_bytes", ScalarType::UInt64.nullable(false))
+        .with_column("uncompacted_bytes", ScalarType::UInt64.nullable(false))
+        .with_column("compaction_backlog", ScalarType::UInt64.nullable(false)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_INDEX_USAGE: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_index_usage",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_INDEX_USAGE_OID,
+    desc: RelationDesc::empty()
+        .with_column("index_id", ScalarType::String.nullable(false))
+        .with_column("peek_count", ScalarType::UInt64.nullable(false))
+        .with_column("dataflow_import_count", ScalarType::UInt64.nullable(false))
+        .with_column(
+            "last_used_at",
+            ScalarType::TimestampTz { precision: None }.nullable(true),
+        )
+        .with_column(
+            "tracked_since",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        ),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_PLAN_CHANGES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_plan_changes",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_PLAN_CHANGES_OID,
+    desc: RelationDesc::empty()
+        .with_column("object_id", ScalarType::String.nullable(false))
+        .with_column("previous_version", ScalarType::String.nullable(false))
+        .with_column("current_version", ScalarType::String.nullable(false))
+        .with_column("previous_fingerprint", ScalarType::String.nullable(false))
+        .with_column("current_fingerprint", ScalarType::String.nullable(false))
+        .with_column(
+            "detected_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        ),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_READ_HOLDS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_read_holds",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_READ_HOLDS_OID,
+    desc: RelationDesc::empty()
+        .with_column("object_id", ScalarType::String.nullable(false))
+        .with_column("cluster_id", ScalarType::String.nullable(true))
+        .with_column("held_at", ScalarType::MzTimestamp.nullable(true))
+        .with_column("holder_type", ScalarType::String.nullable(false))
+        .with_column("holder_id", ScalarType::String.nullable(false))
+        .with_column("session_id", ScalarType::UInt32.nullable(true))
+        .with_column("read_frontier", ScalarType::MzTimestamp.nullable(true))
+        .with_column("write_frontier", ScalarType::MzTimestamp.nullable(true)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_COORDINATOR_SLOW_MESSAGES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_coordinator_slow_messages",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_COORDINATOR_SLOW_MESSAGES_OID,
+    desc: RelationDesc::empty()
+        .with_column(
+            "occurred_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        )
+        .with_column("message_kind", ScalarType::String.nullable(false))
+        .with_column("duration", ScalarType::Interval.nullable(false))
+        .with_column("session_id", ScalarType::UInt32.nullable(true))
+        .with_column("redacted_sql", ScalarType::String.nullable(true))
+        .with_column("trace_id", ScalarType::String.nullable(true)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_EGRESS_IPS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_egress_ips",
     schema: MZ_CATALOG_SCHEMA,
@@ -3027,6 +3403,21 @@ pub static MZ_SUBSCRIPTIONS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_CLUSTER_QUERY_QUEUES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_cluster_query_queues",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_CLUSTER_QUERY_QUEUES_OID,
+    desc: RelationDesc::empty()
+        .with_column("cluster_id", ScalarType::String.nullable(false))
+        .with_column("queued_queries", ScalarType::UInt64.nullable(false))
+        .with_column("admitted_queries", ScalarType::UInt64.nullable(false))
+        .with_column("timed_out_queries", ScalarType::UInt64.nullable(false))
+        .with_column("total_wait_time", ScalarType::Interval.nullable(false))
+        .with_column("max_wait_time", ScalarType::Interval.nullable(false)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_SESSIONS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_sessions",
     schema: MZ_INTERNAL_SCHEMA,
@@ -3037,7 +3428,8 @@ pub static MZ_SESSIONS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
         .with_column(
             "connected_at",
             ScalarType::TimestampTz { precision: None }.nullable(false),
-        ),
+        )
+        .with_column("authentication_method", ScalarType::String.nullable(false)),
     is_retained_metrics_object: false,
     access: vec![PUBLIC_SELECT],
 });
@@ -3091,6 +3483,19 @@ pub static MZ_WEBHOOKS_SOURCES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_SOURCE_TIMESTAMP_POLICIES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_source_timestamp_policies",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_SOURCE_TIMESTAMP_POLICIES_OID,
+    desc: RelationDesc::empty()
+        .with_column("id", ScalarType::String.nullable(false))
+        .with_column("policy", ScalarType::String.nullable(true))
+        .with_column("timestamp_interval", ScalarType::Interval.nullable(false))
+        .with_column("partition_order", ScalarType::String.nullable(true)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
 // These will be replaced with per-replica tables once source/sink multiplexing on
 // a single cluster is supported.
 pub static MZ_SOURCE_STATISTICS_RAW: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
@@ -3141,6 +3546,69 @@ GROUP BY object_id, collection_timestamp",
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_QUOTAS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_quotas",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_QUOTAS_OID,
+    desc: RelationDesc::empty()
+        .with_column("subject_type", ScalarType::String.nullable(false))
+        .with_column("subject_id", ScalarType::String.nullable(false))
+        .with_column("quota", ScalarType::String.nullable(false))
+        .with_column("max_value", ScalarType::UInt64.nullable(false)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_QUOTA_USAGE: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "mz_quota_usage",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::VIEW_MZ_QUOTA_USAGE_OID,
+    column_defs: Some("subject_type, subject_id, quota, usage, max_value"),
+    sql: "
+WITH
+    objects AS (
+        SELECT o.id, o.owner_id, s.database_id
+        FROM mz_catalog.mz_objects AS o
+        JOIN mz_catalog.mz_schemas AS s ON o.schema_id = s.id
+        WHERE o.id LIKE 'u%'
+    ),
+    storage AS (
+        SELECT object_id, size_bytes
+        FROM mz_catalog.mz_storage_usage
+        WHERE collection_timestamp =
+            (SELECT max(collection_timestamp) FROM mz_catalog.mz_storage_usage)
+    ),
+    usage AS (
+        SELECT 'role' AS subject_type, owner_id AS subject_id, 'max_objects' AS quota,
+            count(*)::uint8 AS usage
+        FROM objects
+        GROUP BY owner_id
+        UNION ALL
+        SELECT 'database', database_id, 'max_objects', count(*)::uint8
+        FROM objects
+        WHERE database_id IS NOT NULL
+        GROUP BY database_id
+        UNION ALL
+        SELECT 'role', owner_id, 'max_clusters', count(*)::uint8
+        FROM mz_catalog.mz_clusters
+        WHERE id LIKE 'u%'
+        GROUP BY owner_id
+        UNION ALL
+        SELECT 'role', owner_id, 'max_storage_bytes', sum(size_bytes)::uint8
+        FROM objects JOIN storage ON objects.id = storage.object_id
+        GROUP BY owner_id
+        UNION ALL
+        SELECT 'database', database_id, 'max_storage_bytes', sum(size_bytes)::uint8
+        FROM objects JOIN storage ON objects.id = storage.object_id
+        WHERE database_id IS NOT NULL
+        GROUP BY database_id
+    )
+SELECT q.subject_type, q.subject_id, q.quota, coalesce(u.usage, 0::uint8), q.max_value
+FROM mz_internal.mz_quotas AS q
+LEFT JOIN usage AS u USING (subject_type, subject_id, quota)",
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_RELATIONS: Lazy<BuiltinView> = Lazy::new(|| {
     BuiltinView {
         name: "mz_relations",
@@ -3200,7 +3668,9 @@ UNION ALL
 UNION ALL
     SELECT id, oid, schema_id, name, 'function', owner_id, NULL::mz_catalog.mz_aclitem[] FROM mz_catalog.mz_functions
 UNION ALL
-    SELECT id, oid, schema_id, name, 'secret', owner_id, privileges FROM mz_catalog.mz_secrets",
+    SELECT id, oid, schema_id, name, 'secret', owner_id, privileges FROM mz_catalog.mz_secrets
+UNION ALL
+    SELECT id, oid, schema_id, name, 'continual-task', owner_id, privileges FROM mz_internal.mz_continual_tasks",
         access: vec![PUBLIC_SELECT],
     }
 });
@@ -5604,7 +6074,8 @@ pub static MZ_SHOW_SOURCES: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     COALESCE(sources.size, clusters.size) AS size,
     clusters.name AS cluster,
     schema_id,
-    cluster_id
+    cluster_id,
+    sources.id
 FROM
     mz_catalog.mz_sources AS sources
         LEFT JOIN
@@ -5624,7 +6095,8 @@ pub static MZ_SHOW_SINKS: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
         COALESCE(sinks.size, clusters.size) AS size,
         clusters.name AS cluster,
         schema_id,
-        cluster_id
+        cluster_id,
+        sinks.id
     FROM
         mz_catalog.mz_sinks AS sinks
             JOIN
@@ -5638,7 +6110,7 @@ pub static MZ_SHOW_MATERIALIZED_VIEWS: Lazy<BuiltinView> = Lazy::new(|| BuiltinV
     schema: MZ_INTERNAL_SCHEMA,
     oid: oid::VIEW_MZ_SHOW_MATERIALIZED_VIEWS_OID,
     column_defs: None,
-    sql: "SELECT mviews.name, clusters.name AS cluster, schema_id, cluster_id
+    sql: "SELECT mviews.name, clusters.name AS cluster, schema_id, cluster_id, mviews.id
 FROM mz_materialized_views AS mviews
 JOIN mz_clusters AS clusters ON clusters.id = mviews.cluster_id",
     access: vec![PUBLIC_SELECT],
@@ -5690,7 +6162,10 @@ pub static MZ_SHOW_CLUSTER_REPLICAS: Lazy<BuiltinView> = Lazy::new(|| BuiltinVie
     mz_catalog.mz_clusters.name AS cluster,
     mz_catalog.mz_cluster_replicas.name AS replica,
     mz_catalog.mz_cluster_replicas.size AS size,
-    statuses.ready AS ready
+    statuses.ready AS ready,
+    utilization.cpu_percent AS cpu_percent,
+    utilization.memory_percent AS memory_percent,
+    sizes.credits_per_hour AS credits_per_hour
 FROM
     mz_catalog.mz_cluster_replicas
         JOIN mz_catalog.mz_clusters
@@ -5704,6 +6179,19 @@ FROM
                 GROUP BY replica_id
             ) AS statuses
             ON mz_catalog.mz_cluster_replicas.id = statuses.replica_id
+        -- A replica is only as healthy as its most utilized process.
+        LEFT JOIN
+            (
+                SELECT
+                    replica_id,
+                    max(cpu_percent) AS cpu_percent,
+                    max(memory_percent) AS memory_percent
+                FROM mz_internal.mz_cluster_replica_utilization
+                GROUP BY replica_id
+            ) AS utilization
+            ON mz_catalog.mz_cluster_replicas.id = utilization.replica_id
+        LEFT JOIN mz_internal.mz_cluster_replica_sizes AS sizes
+            ON mz_catalog.mz_cluster_replicas.size = sizes.size
 ORDER BY 1, 2"#,
     access: vec![PUBLIC_SELECT],
 });
@@ -6078,7 +6566,11 @@ indexes AS (
     SELECT
         i.id AS object_id,
         h.replica_id,
-        COALESCE(h.hydrated, false) AS hydrated
+        COALESCE(h.hydrated, false) AS hydrated,
+        h.hydration_started_at,
+        h.hydrated_at,
+        h.records_processed,
+        h.bytes_processed
     FROM mz_indexes i
     LEFT JOIN mz_internal.mz_compute_hydration_statuses h
         ON (h.object_id = i.id)
@@ -6087,7 +6579,11 @@ materialized_views AS (
     SELECT
         i.id AS object_id,
         h.replica_id,
-        COALESCE(h.hydrated, false) AS hydrated
+        COALESCE(h.hydrated, false) AS hydrated,
+        h.hydration_started_at,
+        h.hydrated_at,
+        h.records_processed,
+        h.bytes_processed
     FROM mz_materialized_views i
     LEFT JOIN mz_internal.mz_compute_hydration_statuses h
         ON (h.object_id = i.id)
@@ -6121,7 +6617,11 @@ sources AS (
     SELECT
         s.id AS object_id,
         r.id AS replica_id,
-        ss.rehydration_latency IS NOT NULL AS hydrated
+        ss.rehydration_latency IS NOT NULL AS hydrated,
+        NULL::timestamptz AS hydration_started_at,
+        NULL::timestamptz AS hydrated_at,
+        NULL::uint8 AS records_processed,
+        NULL::uint8 AS bytes_processed
     FROM sources_with_clusters s
     LEFT JOIN mz_internal.mz_source_statistics ss USING (id)
     JOIN mz_cluster_replicas r
@@ -6131,7 +6631,11 @@ sinks AS (
     SELECT
         s.id AS object_id,
         r.id AS replica_id,
-        ss.status = 'running' AS hydrated
+        ss.status = 'running' AS hydrated,
+        NULL::timestamptz AS hydration_started_at,
+        NULL::timestamptz AS hydrated_at,
+        NULL::uint8 AS records_processed,
+        NULL::uint8 AS bytes_processed
     FROM mz_sinks s
     LEFT JOIN mz_internal.mz_sink_statuses ss USING (id)
     JOIN mz_cluster_replicas r
@@ -6147,6 +6651,103 @@ SELECT * FROM sinks"#,
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_OBJECT_READINESS: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "mz_object_readiness",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::VIEW_MZ_OBJECT_READINESS_OID,
+    column_defs: None,
+    sql: r#"WITH
+objects AS (
+    SELECT id FROM mz_catalog.mz_indexes
+    UNION ALL
+    SELECT id FROM mz_catalog.mz_materialized_views
+    UNION ALL
+    SELECT id FROM mz_catalog.mz_sources WHERE type <> 'progress'
+    UNION ALL
+    SELECT id FROM mz_catalog.mz_sinks
+),
+-- An object is hydrated once any of the replicas of its cluster has hydrated
+-- it. Objects without a replica are reported without a `replica_id`, or not at
+-- all.
+hydration AS (
+    SELECT
+        object_id AS id,
+        bool_or(hydrated) AS hydrated,
+        bool_or(replica_id IS NOT NULL) AS scheduled
+    FROM mz_internal.mz_hydration_statuses
+    GROUP BY object_id
+),
+-- Webhook sources are not maintained by dataflows and are ready immediately.
+not_maintained AS (
+    SELECT id FROM mz_catalog.mz_sources WHERE type = 'webhook'
+),
+-- The hydration progress of compute objects is the fraction of hydrated
+-- operators on the most advanced replica.
+compute_progress AS (
+    SELECT id, MAX(progress) AS progress
+    FROM (
+        SELECT
+            object_id AS id,
+            SUM(CASE WHEN hydrated THEN 1 ELSE 0 END)::float8 / COUNT(*)::float8 AS progress
+        FROM mz_internal.mz_compute_operator_hydration_statuses
+        GROUP BY object_id, replica_id
+    )
+    GROUP BY id
+),
+-- The hydration progress of sources is the fraction of their snapshot that
+-- has been staged.
+source_progress AS (
+    SELECT
+        id,
+        CASE
+            WHEN snapshot_committed THEN 1.0
+            WHEN snapshot_records_known > 0
+            THEN LEAST(snapshot_records_staged::float8 / snapshot_records_known::float8, 1.0)
+        END AS progress
+    FROM mz_internal.mz_source_statistics
+),
+errors AS (
+    SELECT id, error
+    FROM mz_internal.mz_source_statuses
+    WHERE status IN ('stalled', 'failed')
+    UNION ALL
+    SELECT id, error
+    FROM mz_internal.mz_sink_statuses
+    WHERE status IN ('stalled', 'failed')
+),
+states AS (
+    SELECT
+        o.id AS object_id,
+        CASE
+            WHEN e.id IS NOT NULL THEN 'errored'
+            WHEN n.id IS NOT NULL OR COALESCE(h.hydrated, false) THEN 'ready'
+            WHEN COALESCE(h.scheduled, false) THEN 'hydrating'
+            ELSE 'created'
+        END AS state,
+        CASE
+            WHEN n.id IS NOT NULL OR COALESCE(h.hydrated, false) THEN 1.0
+            WHEN COALESCE(h.scheduled, false)
+            THEN COALESCE(cp.progress, sp.progress, 0.0)
+        END AS hydration_progress,
+        e.error
+    FROM objects o
+    LEFT JOIN hydration h ON h.id = o.id
+    LEFT JOIN not_maintained n ON n.id = o.id
+    LEFT JOIN compute_progress cp ON cp.id = o.id
+    LEFT JOIN source_progress sp ON sp.id = o.id
+    LEFT JOIN errors e ON e.id = o.id
+    WHERE o.id NOT LIKE 's%'
+)
+SELECT
+    object_id,
+    state,
+    hydration_progress,
+    state = 'ready' AS ready,
+    error
+FROM states"#,
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_MATERIALIZATION_LAG: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     name: "mz_materialization_lag",
     schema: MZ_INTERNAL_SCHEMA,
@@ -6504,7 +7105,19 @@ SELECT
     bool_and(snapshot_committed) as snapshot_committed,
     -- Gauges
     SUM(offset_known)::uint8 AS offset_known,
-    SUM(offset_committed)::uint8 AS offset_committed
+    SUM(offset_committed)::uint8 AS offset_committed,
+    SUM(messages_dead_lettered)::uint8 AS messages_dead_lettered,
+    MAX(last_received_at) AS last_received_at,
+    -- Derived statistics
+    greatest(SUM(offset_known) - SUM(offset_committed), 0)::uint8 AS offset_lag,
+    CASE
+        WHEN bool_and(snapshot_committed) THEN 100
+        WHEN SUM(snapshot_records_known) IS NULL OR SUM(snapshot_records_known) = 0 THEN NULL
+        ELSE least(
+            100,
+            round(100 * SUM(snapshot_records_staged) / SUM(snapshot_records_known), 2)
+        )
+    END::numeric AS snapshot_progress
 FROM mz_internal.mz_source_statistics_raw
 GROUP BY id",
     access: vec![PUBLIC_SELECT],
@@ -6519,6 +7132,69 @@ ON mz_internal.mz_source_statistics (id)",
     is_retained_metrics_object: true,
 };
 
+pub static MZ_SOURCE_SNAPSHOT_PROGRESS: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "mz_source_snapshot_progress",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::VIEW_MZ_SOURCE_SNAPSHOT_PROGRESS_OID,
+    column_defs: None,
+    sql: "
+WITH
+    -- The snapshot of a source starts over every time the source starts.
+    starts AS (
+        SELECT source_id, MAX(occurred_at) AS started_at
+        FROM mz_internal.mz_source_status_history
+        WHERE status = 'starting'
+        GROUP BY source_id
+    ),
+    -- Subsources are snapshotted by their parent source, so fall back to the
+    -- start of the parent if the subsource has not reported its own.
+    parents AS (
+        SELECT subsources.id AS self, sources.id AS parent
+        FROM
+            mz_sources AS subsources
+                JOIN
+                    mz_internal.mz_object_dependencies AS deps
+                    ON subsources.id = deps.referenced_object_id
+                JOIN mz_sources AS sources ON sources.id = deps.object_id
+    ),
+    progress AS (
+        SELECT
+            stats.id,
+            stats.snapshot_committed,
+            stats.snapshot_records_staged AS records_read,
+            stats.snapshot_records_known AS records_total,
+            stats.bytes_received AS bytes_read,
+            stats.snapshot_progress AS progress,
+            COALESCE(self_starts.started_at, parent_starts.started_at) AS started_at
+        FROM
+            mz_internal.mz_source_statistics AS stats
+                LEFT JOIN starts AS self_starts ON stats.id = self_starts.source_id
+                LEFT JOIN parents ON stats.id = parents.self
+                LEFT JOIN starts AS parent_starts ON parents.parent = parent_starts.source_id
+    )
+SELECT
+    id,
+    snapshot_committed,
+    records_read,
+    records_total,
+    bytes_read,
+    progress,
+    started_at,
+    -- Extrapolate the time spent so far at the rate at which records have
+    -- been read.
+    CASE
+        WHEN snapshot_committed THEN INTERVAL '0'
+        WHEN records_read IS NULL OR records_read = 0 THEN NULL
+        WHEN records_total IS NULL OR started_at IS NULL THEN NULL
+        ELSE (now() - started_at) * (
+            greatest(records_total::numeric - records_read::numeric, 0)
+                / records_read::numeric
+        )::float8
+    END AS estimated_time_remaining
+FROM progress",
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_SINK_STATISTICS: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     name: "mz_sink_statistics",
     schema: MZ_INTERNAL_SCHEMA,
@@ -6530,7 +7206,12 @@ SELECT
     SUM(messages_staged)::uint8 AS messages_staged,
     SUM(messages_committed)::uint8 AS messages_committed,
     SUM(bytes_staged)::uint8 AS bytes_staged,
-    SUM(bytes_committed)::uint8 AS bytes_committed
+    SUM(bytes_committed)::uint8 AS bytes_committed,
+    SUM(transactions_committed)::uint8 AS transactions_committed,
+    SUM(transactions_aborted)::uint8 AS transactions_aborted,
+    SUM(restarts)::uint8 AS restarts,
+    MAX(progress_frontier) AS progress_frontier,
+    MAX(last_committed_at) AS last_committed_at
 FROM mz_internal.mz_sink_statistics_raw
 GROUP BY id",
     access: vec![PUBLIC_SELECT],
@@ -6874,6 +7555,8 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Table(&MZ_SINKS),
         Builtin::Table(&MZ_VIEWS),
         Builtin::Table(&MZ_MATERIALIZED_VIEWS),
+        Builtin::Table(&MZ_CONTINUAL_TASKS),
+        Builtin::Table(&MZ_CONTINUAL_TASK_STATUSES),
         Builtin::Table(&MZ_TYPES),
         Builtin::Table(&MZ_TYPE_PG_METADATA),
         Builtin::Table(&MZ_ARRAY_TYPES),
@@ -6897,15 +7580,25 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Table(&MZ_INTERNAL_CLUSTER_REPLICAS),
         Builtin::Table(&MZ_AUDIT_EVENTS),
         Builtin::Table(&MZ_STORAGE_USAGE_BY_SHARD),
+        Builtin::Table(&MZ_STORAGE_IO_USAGE),
+        Builtin::Table(&MZ_USAGE_BY_CLUSTER),
+        Builtin::Table(&MZ_PERSIST_SHARD_HEALTH),
+        Builtin::Table(&MZ_INDEX_USAGE),
+        Builtin::Table(&MZ_PLAN_CHANGES),
+        Builtin::Table(&MZ_READ_HOLDS),
+        Builtin::Table(&MZ_COORDINATOR_SLOW_MESSAGES),
         Builtin::Table(&MZ_EGRESS_IPS),
         Builtin::Table(&MZ_AWS_PRIVATELINK_CONNECTIONS),
         Builtin::Table(&MZ_AWS_CONNECTIONS),
         Builtin::Table(&MZ_SUBSCRIPTIONS),
+        Builtin::Table(&MZ_CLUSTER_QUERY_QUEUES),
         Builtin::Table(&MZ_SESSIONS),
         Builtin::Table(&MZ_DEFAULT_PRIVILEGES),
         Builtin::Table(&MZ_SYSTEM_PRIVILEGES),
         Builtin::Table(&MZ_COMMENTS),
+        Builtin::Table(&MZ_QUOTAS),
         Builtin::Table(&MZ_WEBHOOKS_SOURCES),
+        Builtin::Table(&MZ_SOURCE_TIMESTAMP_POLICIES),
         Builtin::View(&MZ_RELATIONS),
         Builtin::View(&MZ_OBJECT_OID_ALIAS),
         Builtin::View(&MZ_OBJECTS),
@@ -7034,12 +7727,16 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Source(&MZ_SOURCE_STATUS_HISTORY),
         Builtin::Source(&MZ_AWS_PRIVATELINK_CONNECTION_STATUS_HISTORY),
         Builtin::View(&MZ_AWS_PRIVATELINK_CONNECTION_STATUSES),
+        Builtin::Source(&MZ_OBJECT_STATUS_HISTORY),
+        Builtin::View(&MZ_OBJECT_STATUSES),
+        Builtin::View(&MZ_USAGE_BY_ROLE),
         Builtin::Source(&MZ_STATEMENT_EXECUTION_HISTORY),
         Builtin::View(&MZ_STATEMENT_EXECUTION_HISTORY_REDACTED),
         Builtin::Source(&MZ_PREPARED_STATEMENT_HISTORY),
         Builtin::Source(&MZ_SESSION_HISTORY),
         Builtin::Source(&MZ_SQL_TEXT),
         Builtin::View(&MZ_SQL_TEXT_REDACTED),
+        Builtin::View(&MZ_SHOW_CREATE_REDACTED),
         Builtin::View(&MZ_RECENT_SQL_TEXT),
         Builtin::View(&MZ_RECENT_SQL_TEXT_REDACTED),
         Builtin::Index(&MZ_RECENT_SQL_TEXT_IND),
@@ -7047,23 +7744,31 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::View(&MZ_RECENT_ACTIVITY_LOG_THINNED),
         Builtin::View(&MZ_RECENT_ACTIVITY_LOG),
         Builtin::View(&MZ_RECENT_ACTIVITY_LOG_REDACTED),
+        Builtin::View(&MZ_STATEMENT_HISTORY),
+        Builtin::View(&MZ_STATEMENT_HISTORY_REDACTED),
         Builtin::Index(&MZ_RECENT_ACTIVITY_LOG_THINNED_IND),
         Builtin::View(&MZ_SOURCE_STATUSES),
+        Builtin::View(&MZ_SOURCE_RETRIES),
         Builtin::Source(&MZ_STATEMENT_LIFECYCLE_HISTORY),
         Builtin::Source(&MZ_STORAGE_SHARDS),
         Builtin::Source(&MZ_SOURCE_STATISTICS_RAW),
         Builtin::Source(&MZ_SINK_STATISTICS_RAW),
         Builtin::View(&MZ_SOURCE_STATISTICS),
         Builtin::Index(&MZ_SOURCE_STATISTICS_IND),
+        Builtin::View(&MZ_SOURCE_SNAPSHOT_PROGRESS),
         Builtin::View(&MZ_SINK_STATISTICS),
         Builtin::Index(&MZ_SINK_STATISTICS_IND),
         Builtin::View(&MZ_STORAGE_USAGE),
+        Builtin::View(&MZ_QUOTA_USAGE),
         Builtin::Source(&MZ_FRONTIERS),
         Builtin::View(&MZ_GLOBAL_FRONTIERS),
         Builtin::Source(&MZ_COMPUTE_DEPENDENCIES),
         Builtin::Source(&MZ_COMPUTE_HYDRATION_STATUSES),
         Builtin::Source(&MZ_COMPUTE_OPERATOR_HYDRATION_STATUSES_PER_WORKER),
+        Builtin::Source(&MZ_DATAFLOW_STATISTICS_HISTORY),
+        Builtin::View(&MZ_DATAFLOW_STATISTICS_RATES),
         Builtin::View(&MZ_HYDRATION_STATUSES),
+        Builtin::View(&MZ_OBJECT_READINESS),
         Builtin::View(&MZ_MATERIALIZATION_LAG),
         Builtin::View(&MZ_COMPUTE_ERROR_COUNTS_PER_WORKER),
         Builtin::View(&MZ_COMPUTE_ERROR_COUNTS),
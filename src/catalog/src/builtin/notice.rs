@@ -15,7 +15,7 @@ use once_cell::sync::Lazy;
 
 use crate::builtin::{Builtin, BuiltinIndex, BuiltinTable, BuiltinView, MONITOR_SELECT};
 
-use super::{MONITOR_REDACTED_SELECT, SUPPORT_SELECT};
+use super::{MONITOR_REDACTED_SELECT, PUBLIC_SELECT, SUPPORT_SELECT};
 
 pub static MZ_OPTIMIZER_NOTICES: Lazy<BuiltinTable> = Lazy::new(|| {
     use ScalarType::{List, String, TimestampTz};
@@ -53,6 +53,21 @@ pub static MZ_OPTIMIZER_NOTICES: Lazy<BuiltinTable> = Lazy::new(|| {
     }
 });
 
+/// The kinds of notices that the system can emit. The `name` of a kind is the
+/// `notice_type` of its notices in [`MZ_NOTICES`].
+pub static MZ_NOTICE_TYPES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_notice_types",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_NOTICE_TYPES_OID,
+    desc: RelationDesc::empty()
+        .with_column("id", ScalarType::UInt64.nullable(false))
+        .with_column("name", ScalarType::String.nullable(false))
+        .with_column("slug", ScalarType::String.nullable(false))
+        .with_key(vec![0]),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
 /// An [`MZ_NOTICES`] that is made safe to be viewed by Materialize staff
 /// because it binds the `redacted_~` from [`MZ_NOTICES`] as `~`.
 ///
@@ -121,6 +136,7 @@ pub const MZ_NOTICES_IND: BuiltinIndex = BuiltinIndex {
 pub(super) fn builtins() -> impl Iterator<Item = Builtin<NameReference>> {
     [
         Builtin::Table(&MZ_OPTIMIZER_NOTICES),
+        Builtin::Table(&MZ_NOTICE_TYPES),
         Builtin::View(&MZ_NOTICES),
         Builtin::View(&MZ_NOTICES_REDACTED),
         Builtin::Index(&MZ_NOTICES_IND),
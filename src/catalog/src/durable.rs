@@ -32,7 +32,7 @@ pub use crate::durable::metrics::Metrics;
 use crate::durable::objects::Snapshot;
 pub use crate::durable::objects::{
     Cluster, ClusterConfig, ClusterReplica, ClusterVariant, ClusterVariantManaged, Comment,
-    Database, DefaultPrivilege, Item, ReplicaConfig, ReplicaLocation, Role, Schema,
+    Database, DefaultPrivilege, Item, Quota, ReplicaConfig, ReplicaLocation, Role, Schema,
     SystemConfiguration, SystemObjectMapping, TimelineTimestamp,
 };
 use crate::durable::persist::UnopenedPersistCatalogState;
@@ -62,6 +62,9 @@ pub const AUDIT_LOG_ID_ALLOC_KEY: &str = "auditlog";
 pub const STORAGE_USAGE_ID_ALLOC_KEY: &str = "storage_usage";
 pub const OID_ALLOC_KEY: &str = "oid";
 pub(crate) const CATALOG_CONTENT_VERSION_KEY: &str = "catalog_content_version";
+pub(crate) const EXPRESSION_CACHE_KEY_PREFIX: &str = "expression_cache:";
+pub(crate) const PLAN_RECORD_KEY_PREFIX: &str = "plan_record:";
+pub(crate) const SQL_JOB_KEY_PREFIX: &str = "sql_job:";
 
 #[derive(Clone, Debug)]
 pub struct BootstrapArgs {
@@ -63,6 +63,7 @@ pub enum CollectionType {
     DefaultPrivileges,
     IdAlloc,
     Item,
+    Quota,
     Role,
     Schema,
     Setting,
@@ -196,6 +197,14 @@ collection_impl!({
     trace_field: items,
     update: StateUpdateKind::Item,
 });
+collection_impl!({
+    name: QuotaCollection,
+    key: proto::QuotaKey,
+    value: proto::QuotaValue,
+    collection_type: CollectionType::Quota,
+    trace_field: quotas,
+    update: StateUpdateKind::Quota,
+});
 collection_impl!({
     name: RoleCollection,
     key: proto::RoleKey,
@@ -289,6 +298,7 @@ pub struct Trace {
     pub default_privileges: CollectionTrace<DefaultPrivilegeCollection>,
     pub id_allocator: CollectionTrace<IdAllocatorCollection>,
     pub items: CollectionTrace<ItemCollection>,
+    pub quotas: CollectionTrace<QuotaCollection>,
     pub roles: CollectionTrace<RoleCollection>,
     pub schemas: CollectionTrace<SchemaCollection>,
     pub settings: CollectionTrace<SettingCollection>,
@@ -312,6 +322,7 @@ impl Trace {
             default_privileges: CollectionTrace::new(),
             id_allocator: CollectionTrace::new(),
             items: CollectionTrace::new(),
+            quotas: CollectionTrace::new(),
             roles: CollectionTrace::new(),
             schemas: CollectionTrace::new(),
             settings: CollectionTrace::new(),
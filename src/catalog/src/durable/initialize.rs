@@ -257,6 +257,7 @@ pub async fn initialize(
             ObjectType::Database => mz_audit_log::ObjectType::Database,
             ObjectType::Schema => mz_audit_log::ObjectType::Schema,
             ObjectType::Func => mz_audit_log::ObjectType::Func,
+            ObjectType::ContinualTask => mz_audit_log::ObjectType::ContinualTask,
         };
         audit_events.push((
             mz_audit_log::EventType::Grant,
@@ -637,6 +638,8 @@ fn default_cluster_config(args: &BootstrapArgs) -> ClusterConfig {
             idle_arrangement_merge_effort: None,
             disk: false,
             optimizer_feature_overrides: Default::default(),
+            schedule: Default::default(),
+            max_concurrent_queries: None,
         }),
     }
 }
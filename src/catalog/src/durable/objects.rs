@@ -19,10 +19,11 @@ use mz_repr::adt::mz_acl_item::{AclMode, MzAclItem};
 use mz_repr::role_id::RoleId;
 use mz_repr::GlobalId;
 use mz_sql::catalog::{
-    CatalogItemType, DefaultPrivilegeAclItem, DefaultPrivilegeObject, ObjectType, RoleAttributes,
-    RoleMembership, RoleVars,
+    CatalogItemType, DefaultPrivilegeAclItem, DefaultPrivilegeObject, ObjectType, QuotaKind,
+    QuotaSubject, RoleAttributes, RoleMembership, RoleVars,
 };
 use mz_sql::names::{CommentObjectId, DatabaseId, SchemaId};
+use mz_sql::plan::ClusterSchedule;
 use mz_storage_types::sources::Timeline;
 use proptest_derive::Arbitrary;
 
@@ -209,6 +210,8 @@ pub struct ClusterVariantManaged {
     pub replication_factor: u32,
     pub disk: bool,
     pub optimizer_feature_overrides: BTreeMap<String, String>,
+    pub schedule: ClusterSchedule,
+    pub max_concurrent_queries: Option<u32>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -688,6 +691,33 @@ impl DurableType<SystemPrivilegesKey, SystemPrivilegesValue> for MzAclItem {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Quota {
+    pub subject: QuotaSubject,
+    pub kind: QuotaKind,
+    pub limit: u64,
+}
+
+impl DurableType<QuotaKey, QuotaValue> for Quota {
+    fn into_key_value(self) -> (QuotaKey, QuotaValue) {
+        (
+            QuotaKey {
+                subject: self.subject,
+                kind: self.kind,
+            },
+            QuotaValue { limit: self.limit },
+        )
+    }
+
+    fn from_key_value(key: QuotaKey, value: QuotaValue) -> Self {
+        Self {
+            subject: key.subject,
+            kind: key.kind,
+            limit: value.limit,
+        }
+    }
+}
+
 // Structs used internally to represent on-disk state.
 
 /// A snapshot of the current on-disk state.
@@ -713,6 +743,7 @@ pub struct Snapshot {
         BTreeMap<proto::ServerConfigurationKey, proto::ServerConfigurationValue>,
     pub default_privileges: BTreeMap<proto::DefaultPrivilegesKey, proto::DefaultPrivilegesValue>,
     pub system_privileges: BTreeMap<proto::SystemPrivilegesKey, proto::SystemPrivilegesValue>,
+    pub quotas: BTreeMap<proto::QuotaKey, proto::QuotaValue>,
 }
 
 impl Snapshot {
@@ -734,6 +765,7 @@ impl Snapshot {
             system_configurations: BTreeMap::new(),
             default_privileges: BTreeMap::new(),
             system_privileges: BTreeMap::new(),
+            quotas: BTreeMap::new(),
         }
     }
 
@@ -755,6 +787,7 @@ impl Snapshot {
             system_configurations,
             default_privileges,
             system_privileges,
+            quotas,
         } = self;
         databases.is_empty()
             && schemas.is_empty()
@@ -772,6 +805,7 @@ impl Snapshot {
             && system_configurations.is_empty()
             && default_privileges.is_empty()
             && system_privileges.is_empty()
+            && quotas.is_empty()
     }
 }
 
@@ -909,6 +943,10 @@ impl ItemValue {
             Some("FUNCTION") => CatalogItemType::Func,
             Some("SECRET") => CatalogItemType::Secret,
             Some("CONNECTION") => CatalogItemType::Connection,
+            Some("CONTINUAL") => {
+                assert_eq!(tokens.next(), Some("TASK"));
+                CatalogItemType::ContinualTask
+            }
             _ => panic!("unexpected create sql: {}", self.create_sql),
         }
     }
@@ -1004,6 +1042,17 @@ pub struct SystemPrivilegesValue {
     pub(crate) acl_mode: AclMode,
 }
 
+#[derive(Debug, Clone, PartialOrd, PartialEq, Eq, Ord, Hash)]
+pub struct QuotaKey {
+    pub(crate) subject: QuotaSubject,
+    pub(crate) kind: QuotaKind,
+}
+
+#[derive(Debug, Clone, PartialOrd, PartialEq, Eq, Ord, Hash)]
+pub struct QuotaValue {
+    pub(crate) limit: u64,
+}
+
 #[cfg(test)]
 mod test {
     use mz_proto::{ProtoType, RustType};
@@ -10,12 +10,13 @@
 //! This module is responsible for serializing catalog objects into Protobuf.
 
 use mz_audit_log::{
-    AlterDefaultPrivilegeV1, AlterSetClusterV1, AlterSourceSinkV1, CreateClusterReplicaV1,
-    CreateSourceSinkV1, CreateSourceSinkV2, CreateSourceSinkV3, DropClusterReplicaV1, EventDetails,
-    EventType, EventV1, FullNameV1, GrantRoleV1, GrantRoleV2, IdFullNameV1, IdNameV1,
-    RenameClusterReplicaV1, RenameClusterV1, RenameItemV1, RenameSchemaV1, RevokeRoleV1,
-    RevokeRoleV2, SchemaV1, SchemaV2, StorageUsageV1, UpdateItemV1, UpdateOwnerV1,
-    UpdatePrivilegeV1, VersionedEvent, VersionedStorageUsage,
+    AlterDefaultPrivilegeV1, AlterSetClusterV1, AlterSourceSinkV1, ComputeUsageV1,
+    CreateClusterReplicaV1, CreateSourceSinkV1, CreateSourceSinkV2, CreateSourceSinkV3,
+    DropClusterReplicaV1, EventContextV1, EventDetails, EventType, EventV1, EventV2, FullNameV1,
+    GrantRoleV1, GrantRoleV2, IdFullNameV1, IdNameV1, RenameClusterReplicaV1, RenameClusterV1,
+    RenameItemV1, RenameSchemaV1, RevokeRoleV1, RevokeRoleV2, SchemaV1, SchemaV2, StorageIoUsageV1,
+    StorageUsageV1, UpdateItemV1, UpdateOwnerV1, UpdatePrivilegeV1, UseSecretV1, VersionedEvent,
+    VersionedStorageUsage,
 };
 use mz_compute_client::controller::ComputeReplicaLogging;
 use mz_controller_types::ReplicaId;
@@ -24,10 +25,13 @@ use mz_proto::{IntoRustIfSome, ProtoMapEntry, ProtoType, RustType, TryFromProtoE
 use mz_repr::adt::mz_acl_item::{AclMode, MzAclItem};
 use mz_repr::role_id::RoleId;
 use mz_repr::{GlobalId, Timestamp};
-use mz_sql::catalog::{CatalogItemType, ObjectType, RoleAttributes, RoleMembership, RoleVars};
+use mz_sql::catalog::{
+    CatalogItemType, ObjectType, QuotaKind, QuotaSubject, RoleAttributes, RoleMembership, RoleVars,
+};
 use mz_sql::names::{
     CommentObjectId, DatabaseId, ResolvedDatabaseSpecifier, SchemaId, SchemaSpecifier,
 };
+use mz_sql::plan::{ClusterSchedule, CronSchedule};
 use mz_sql::session::vars::OwnedVarInput;
 use mz_storage_types::instances::StorageInstanceId;
 use std::time::Duration;
@@ -38,9 +42,9 @@ use crate::durable::objects::{
     ClusterKey, ClusterReplicaKey, ClusterReplicaValue, ClusterValue, CommentKey, CommentValue,
     ConfigKey, ConfigValue, DatabaseKey, DatabaseValue, DefaultPrivilegesKey,
     DefaultPrivilegesValue, GidMappingKey, GidMappingValue, IdAllocKey, IdAllocValue, ItemKey,
-    ItemValue, RoleKey, RoleValue, SchemaKey, SchemaValue, ServerConfigurationKey,
-    ServerConfigurationValue, SettingKey, SettingValue, StorageUsageKey, SystemPrivilegesKey,
-    SystemPrivilegesValue, TimestampKey, TimestampValue,
+    ItemValue, QuotaKey, QuotaValue, RoleKey, RoleValue, SchemaKey, SchemaValue,
+    ServerConfigurationKey, ServerConfigurationValue, SettingKey, SettingValue, StorageUsageKey,
+    SystemPrivilegesKey, SystemPrivilegesValue, TimestampKey, TimestampValue,
 };
 use crate::durable::{
     ClusterConfig, ClusterVariant, ClusterVariantManaged, ReplicaConfig, ReplicaLocation,
@@ -102,6 +106,8 @@ impl RustType<proto::cluster_config::Variant> for ClusterVariant {
                 replication_factor,
                 disk,
                 optimizer_feature_overrides,
+                schedule,
+                max_concurrent_queries,
             }) => proto::cluster_config::Variant::Managed(proto::cluster_config::ManagedCluster {
                 size: size.to_string(),
                 availability_zones: availability_zones.clone(),
@@ -111,6 +117,8 @@ impl RustType<proto::cluster_config::Variant> for ClusterVariant {
                 replication_factor: *replication_factor,
                 disk: *disk,
                 optimizer_feature_overrides: optimizer_feature_overrides.into_proto(),
+                schedule: Some(schedule.into_proto()),
+                max_concurrent_queries: *max_concurrent_queries,
             }),
             ClusterVariant::Unmanaged => proto::cluster_config::Variant::Unmanaged(proto::Empty {}),
         }
@@ -132,12 +140,39 @@ impl RustType<proto::cluster_config::Variant> for ClusterVariant {
                     replication_factor: managed.replication_factor,
                     disk: managed.disk,
                     optimizer_feature_overrides: managed.optimizer_feature_overrides.into_rust()?,
+                    schedule: managed
+                        .schedule
+                        .into_rust_if_some("ManagedCluster::schedule")?,
+                    max_concurrent_queries: managed.max_concurrent_queries,
                 }))
             }
         }
     }
 }
 
+impl RustType<proto::ClusterSchedule> for ClusterSchedule {
+    fn into_proto(&self) -> proto::ClusterSchedule {
+        proto::ClusterSchedule {
+            value: Some(match self {
+                ClusterSchedule::Manual => proto::cluster_schedule::Value::Manual(proto::Empty {}),
+                ClusterSchedule::Cron(cron) => {
+                    proto::cluster_schedule::Value::Cron(cron.expr().to_string())
+                }
+            }),
+        }
+    }
+
+    fn from_proto(proto: proto::ClusterSchedule) -> Result<Self, TryFromProtoError> {
+        match proto.value {
+            Some(proto::cluster_schedule::Value::Manual(_)) => Ok(ClusterSchedule::Manual),
+            Some(proto::cluster_schedule::Value::Cron(expr)) => CronSchedule::parse(&expr)
+                .map(ClusterSchedule::Cron)
+                .map_err(TryFromProtoError::InvalidCronExpression),
+            None => Err(TryFromProtoError::missing_field("ClusterSchedule::value")),
+        }
+    }
+}
+
 impl RustType<proto::ReplicaConfig> for ReplicaConfig {
     fn into_proto(&self) -> proto::ReplicaConfig {
         proto::ReplicaConfig {
@@ -801,6 +836,77 @@ impl RustType<proto::SystemPrivilegesValue> for SystemPrivilegesValue {
     }
 }
 
+impl RustType<proto::QuotaKey> for QuotaKey {
+    fn into_proto(&self) -> proto::QuotaKey {
+        proto::QuotaKey {
+            subject: Some(self.subject.into_proto()),
+            kind: self.kind.into_proto().into(),
+        }
+    }
+
+    fn from_proto(proto: proto::QuotaKey) -> Result<Self, TryFromProtoError> {
+        Ok(QuotaKey {
+            subject: proto.subject.into_rust_if_some("QuotaKey::subject")?,
+            kind: proto::QuotaKind::from_i32(proto.kind)
+                .ok_or_else(|| TryFromProtoError::unknown_enum_variant("QuotaKind"))?
+                .into_rust()?,
+        })
+    }
+}
+
+impl RustType<proto::QuotaValue> for QuotaValue {
+    fn into_proto(&self) -> proto::QuotaValue {
+        proto::QuotaValue { limit: self.limit }
+    }
+
+    fn from_proto(proto: proto::QuotaValue) -> Result<Self, TryFromProtoError> {
+        Ok(QuotaValue { limit: proto.limit })
+    }
+}
+
+impl RustType<proto::quota_key::Subject> for QuotaSubject {
+    fn into_proto(&self) -> proto::quota_key::Subject {
+        match self {
+            QuotaSubject::Role(role_id) => proto::quota_key::Subject::Role(role_id.into_proto()),
+            QuotaSubject::Database(database_id) => {
+                proto::quota_key::Subject::Database(database_id.into_proto())
+            }
+        }
+    }
+
+    fn from_proto(proto: proto::quota_key::Subject) -> Result<Self, TryFromProtoError> {
+        match proto {
+            proto::quota_key::Subject::Role(role_id) => {
+                Ok(QuotaSubject::Role(role_id.into_rust()?))
+            }
+            proto::quota_key::Subject::Database(database_id) => {
+                Ok(QuotaSubject::Database(database_id.into_rust()?))
+            }
+        }
+    }
+}
+
+impl RustType<proto::QuotaKind> for QuotaKind {
+    fn into_proto(&self) -> proto::QuotaKind {
+        match self {
+            QuotaKind::MaxObjects => proto::QuotaKind::MaxObjects,
+            QuotaKind::MaxClusters => proto::QuotaKind::MaxClusters,
+            QuotaKind::MaxStorageBytes => proto::QuotaKind::MaxStorageBytes,
+        }
+    }
+
+    fn from_proto(proto: proto::QuotaKind) -> Result<Self, TryFromProtoError> {
+        match proto {
+            proto::QuotaKind::MaxObjects => Ok(QuotaKind::MaxObjects),
+            proto::QuotaKind::MaxClusters => Ok(QuotaKind::MaxClusters),
+            proto::QuotaKind::MaxStorageBytes => Ok(QuotaKind::MaxStorageBytes),
+            proto::QuotaKind::Unknown => Err(TryFromProtoError::unknown_enum_variant(
+                "QuotaKind::Unknown",
+            )),
+        }
+    }
+}
+
 impl RustType<proto::ClusterId> for StorageInstanceId {
     fn into_proto(&self) -> proto::ClusterId {
         let value = match self {
@@ -920,6 +1026,7 @@ impl RustType<proto::RoleAttributes> for RoleAttributes {
     fn into_proto(&self) -> proto::RoleAttributes {
         proto::RoleAttributes {
             inherit: self.inherit,
+            password: self.password.clone(),
         }
     }
 
@@ -927,6 +1034,7 @@ impl RustType<proto::RoleAttributes> for RoleAttributes {
         let mut attributes = RoleAttributes::new();
 
         attributes.inherit = proto.inherit;
+        attributes.password = proto.password;
 
         Ok(attributes)
     }
@@ -995,6 +1103,7 @@ impl RustType<proto::CatalogItemType> for CatalogItemType {
             CatalogItemType::Func => proto::CatalogItemType::Func,
             CatalogItemType::Secret => proto::CatalogItemType::Secret,
             CatalogItemType::Connection => proto::CatalogItemType::Connection,
+            CatalogItemType::ContinualTask => proto::CatalogItemType::ContinualTask,
         }
     }
 
@@ -1010,6 +1119,7 @@ impl RustType<proto::CatalogItemType> for CatalogItemType {
             proto::CatalogItemType::Func => CatalogItemType::Func,
             proto::CatalogItemType::Secret => CatalogItemType::Secret,
             proto::CatalogItemType::Connection => CatalogItemType::Connection,
+            proto::CatalogItemType::ContinualTask => CatalogItemType::ContinualTask,
             proto::CatalogItemType::Unknown => {
                 return Err(TryFromProtoError::unknown_enum_variant("CatalogItemType"));
             }
@@ -1036,6 +1146,7 @@ impl RustType<proto::ObjectType> for ObjectType {
             ObjectType::Database => proto::ObjectType::Database,
             ObjectType::Schema => proto::ObjectType::Schema,
             ObjectType::Func => proto::ObjectType::Func,
+            ObjectType::ContinualTask => proto::ObjectType::ContinualTask,
         }
     }
 
@@ -1056,6 +1167,7 @@ impl RustType<proto::ObjectType> for ObjectType {
             proto::ObjectType::Database => Ok(ObjectType::Database),
             proto::ObjectType::Schema => Ok(ObjectType::Schema),
             proto::ObjectType::Func => Ok(ObjectType::Func),
+            proto::ObjectType::ContinualTask => Ok(ObjectType::ContinualTask),
             proto::ObjectType::Unknown => Err(TryFromProtoError::unknown_enum_variant(
                 "ObjectType::Unknown",
             )),
@@ -1223,6 +1335,9 @@ impl RustType<proto::comment_key::Object> for CommentObjectId {
             CommentObjectId::Secret(global_id) => {
                 proto::comment_key::Object::Secret(global_id.into_proto())
             }
+            CommentObjectId::ContinualTask(global_id) => {
+                proto::comment_key::Object::ContinualTask(global_id.into_proto())
+            }
             CommentObjectId::Role(role_id) => {
                 proto::comment_key::Object::Role(role_id.into_proto())
             }
@@ -1280,6 +1395,9 @@ impl RustType<proto::comment_key::Object> for CommentObjectId {
             proto::comment_key::Object::Secret(global_id) => {
                 CommentObjectId::Secret(global_id.into_rust()?)
             }
+            proto::comment_key::Object::ContinualTask(global_id) => {
+                CommentObjectId::ContinualTask(global_id.into_rust()?)
+            }
             proto::comment_key::Object::Role(role_id) => {
                 CommentObjectId::Role(role_id.into_rust()?)
             }
@@ -1361,12 +1479,14 @@ impl RustType<proto::audit_log_key::Event> for VersionedEvent {
     fn into_proto(&self) -> proto::audit_log_key::Event {
         match self {
             VersionedEvent::V1(event) => proto::audit_log_key::Event::V1(event.into_proto()),
+            VersionedEvent::V2(event) => proto::audit_log_key::Event::V2(event.into_proto()),
         }
     }
 
     fn from_proto(proto: proto::audit_log_key::Event) -> Result<Self, TryFromProtoError> {
         match proto {
             proto::audit_log_key::Event::V1(event) => Ok(VersionedEvent::V1(event.into_rust()?)),
+            proto::audit_log_key::Event::V2(event) => Ok(VersionedEvent::V2(event.into_rust()?)),
         }
     }
 }
@@ -1379,6 +1499,7 @@ impl RustType<proto::audit_log_event_v1::EventType> for EventType {
             EventType::Alter => proto::audit_log_event_v1::EventType::Alter,
             EventType::Grant => proto::audit_log_event_v1::EventType::Grant,
             EventType::Revoke => proto::audit_log_event_v1::EventType::Revoke,
+            EventType::Use => proto::audit_log_event_v1::EventType::Use,
         }
     }
 
@@ -1389,6 +1510,7 @@ impl RustType<proto::audit_log_event_v1::EventType> for EventType {
             proto::audit_log_event_v1::EventType::Alter => Ok(EventType::Alter),
             proto::audit_log_event_v1::EventType::Grant => Ok(EventType::Grant),
             proto::audit_log_event_v1::EventType::Revoke => Ok(EventType::Revoke),
+            proto::audit_log_event_v1::EventType::Use => Ok(EventType::Use),
             proto::audit_log_event_v1::EventType::Unknown => Err(
                 TryFromProtoError::unknown_enum_variant("EventType::Unknown"),
             ),
@@ -1419,6 +1541,9 @@ impl RustType<proto::audit_log_event_v1::ObjectType> for mz_audit_log::ObjectTyp
             mz_audit_log::ObjectType::Connection => {
                 proto::audit_log_event_v1::ObjectType::Connection
             }
+            mz_audit_log::ObjectType::ContinualTask => {
+                proto::audit_log_event_v1::ObjectType::ContinualTask
+            }
             mz_audit_log::ObjectType::Database => proto::audit_log_event_v1::ObjectType::Database,
             mz_audit_log::ObjectType::Func => proto::audit_log_event_v1::ObjectType::Func,
             mz_audit_log::ObjectType::Index => proto::audit_log_event_v1::ObjectType::Index,
@@ -1446,6 +1571,9 @@ impl RustType<proto::audit_log_event_v1::ObjectType> for mz_audit_log::ObjectTyp
             proto::audit_log_event_v1::ObjectType::Connection => {
                 Ok(mz_audit_log::ObjectType::Connection)
             }
+            proto::audit_log_event_v1::ObjectType::ContinualTask => {
+                Ok(mz_audit_log::ObjectType::ContinualTask)
+            }
             proto::audit_log_event_v1::ObjectType::Database => {
                 Ok(mz_audit_log::ObjectType::Database)
             }
@@ -1988,6 +2116,30 @@ impl RustType<proto::audit_log_event_v1::UpdateItemV1> for UpdateItemV1 {
     }
 }
 
+impl RustType<proto::audit_log_event_v1::UseSecretV1> for UseSecretV1 {
+    fn into_proto(&self) -> proto::audit_log_event_v1::UseSecretV1 {
+        proto::audit_log_event_v1::UseSecretV1 {
+            id: self.id.to_string(),
+            name: Some(self.name.into_proto()),
+            used_by_id: self.used_by_id.to_string(),
+            used_by_name: Some(self.used_by_name.into_proto()),
+        }
+    }
+
+    fn from_proto(
+        proto: proto::audit_log_event_v1::UseSecretV1,
+    ) -> Result<Self, TryFromProtoError> {
+        Ok(UseSecretV1 {
+            id: proto.id,
+            name: proto.name.into_rust_if_some("UseSecretV1::name")?,
+            used_by_id: proto.used_by_id,
+            used_by_name: proto
+                .used_by_name
+                .into_rust_if_some("UseSecretV1::used_by_name")?,
+        })
+    }
+}
+
 impl RustType<proto::audit_log_event_v1::Details> for EventDetails {
     fn into_proto(&self) -> proto::audit_log_event_v1::Details {
         use proto::audit_log_event_v1::Details::*;
@@ -2024,6 +2176,7 @@ impl RustType<proto::audit_log_event_v1::Details> for EventDetails {
             EventDetails::SchemaV2(details) => SchemaV2(details.into_proto()),
             EventDetails::RenameSchemaV1(details) => RenameSchemaV1(details.into_proto()),
             EventDetails::UpdateItemV1(details) => UpdateItemV1(details.into_proto()),
+            EventDetails::UseSecretV1(details) => UseSecretV1(details.into_proto()),
         }
     }
 
@@ -2068,6 +2221,7 @@ impl RustType<proto::audit_log_event_v1::Details> for EventDetails {
             SchemaV2(details) => Ok(EventDetails::SchemaV2(details.into_rust()?)),
             RenameSchemaV1(details) => Ok(EventDetails::RenameSchemaV1(details.into_rust()?)),
             UpdateItemV1(details) => Ok(EventDetails::UpdateItemV1(details.into_rust()?)),
+            UseSecretV1(details) => Ok(EventDetails::UseSecretV1(details.into_rust()?)),
         }
     }
 }
@@ -2108,6 +2262,73 @@ impl RustType<proto::AuditLogEventV1> for EventV1 {
     }
 }
 
+impl RustType<proto::audit_log_event_v2::EventContextV1> for EventContextV1 {
+    fn into_proto(&self) -> proto::audit_log_event_v2::EventContextV1 {
+        proto::audit_log_event_v2::EventContextV1 {
+            client_ip: self.client_ip.clone(),
+            application_name: self.application_name.clone(),
+            http_request_id: self.http_request_id.clone(),
+            sso_subject: self.sso_subject.clone(),
+        }
+    }
+
+    fn from_proto(
+        proto: proto::audit_log_event_v2::EventContextV1,
+    ) -> Result<Self, TryFromProtoError> {
+        Ok(EventContextV1 {
+            client_ip: proto.client_ip,
+            application_name: proto.application_name,
+            http_request_id: proto.http_request_id,
+            sso_subject: proto.sso_subject,
+        })
+    }
+}
+
+impl RustType<proto::AuditLogEventV2> for EventV2 {
+    fn into_proto(&self) -> proto::AuditLogEventV2 {
+        // The fields that V2 events share with V1 events are encoded as a V1
+        // event.
+        let event = proto::AuditLogEventV1 {
+            id: self.id,
+            event_type: self.event_type.into_proto().into(),
+            object_type: self.object_type.into_proto().into(),
+            user: self.user.as_ref().map(|u| proto::StringWrapper {
+                inner: u.to_string(),
+            }),
+            occurred_at: Some(proto::EpochMillis {
+                millis: self.occurred_at,
+            }),
+            details: Some(self.details.into_proto()),
+        };
+        proto::AuditLogEventV2 {
+            event: Some(event),
+            context: Some(self.context.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: proto::AuditLogEventV2) -> Result<Self, TryFromProtoError> {
+        let EventV1 {
+            id,
+            event_type,
+            object_type,
+            details,
+            user,
+            occurred_at,
+        } = proto.event.into_rust_if_some("AuditLogEventV2::event")?;
+        Ok(EventV2 {
+            id,
+            event_type,
+            object_type,
+            details,
+            user,
+            context: proto
+                .context
+                .into_rust_if_some("AuditLogEventV2::context")?,
+            occurred_at,
+        })
+    }
+}
+
 impl RustType<proto::storage_usage_key::StorageUsageV1> for StorageUsageV1 {
     fn into_proto(&self) -> proto::storage_usage_key::StorageUsageV1 {
         proto::storage_usage_key::StorageUsageV1 {
@@ -2136,12 +2357,80 @@ impl RustType<proto::storage_usage_key::StorageUsageV1> for StorageUsageV1 {
     }
 }
 
+impl RustType<proto::storage_usage_key::StorageIoUsageV1> for StorageIoUsageV1 {
+    fn into_proto(&self) -> proto::storage_usage_key::StorageIoUsageV1 {
+        proto::storage_usage_key::StorageIoUsageV1 {
+            id: self.id,
+            object_id: self.object_id.clone(),
+            bytes_read: self.bytes_read,
+            bytes_written: self.bytes_written,
+            collection_timestamp: Some(proto::EpochMillis {
+                millis: self.collection_timestamp,
+            }),
+        }
+    }
+
+    fn from_proto(
+        proto: proto::storage_usage_key::StorageIoUsageV1,
+    ) -> Result<Self, TryFromProtoError> {
+        Ok(StorageIoUsageV1 {
+            id: proto.id,
+            object_id: proto.object_id,
+            bytes_read: proto.bytes_read,
+            bytes_written: proto.bytes_written,
+            collection_timestamp: proto
+                .collection_timestamp
+                .into_rust_if_some("StorageIoUsageV1::collection_timestamp")?,
+        })
+    }
+}
+
+impl RustType<proto::storage_usage_key::ComputeUsageV1> for ComputeUsageV1 {
+    fn into_proto(&self) -> proto::storage_usage_key::ComputeUsageV1 {
+        proto::storage_usage_key::ComputeUsageV1 {
+            id: self.id,
+            cluster_id: self.cluster_id.clone(),
+            role_id: self.role_id.clone(),
+            cpu_nano_cores: self.cpu_nano_cores,
+            memory_bytes: self.memory_bytes,
+            peek_count: self.peek_count,
+            peek_time_us: self.peek_time_us,
+            collection_timestamp: Some(proto::EpochMillis {
+                millis: self.collection_timestamp,
+            }),
+        }
+    }
+
+    fn from_proto(
+        proto: proto::storage_usage_key::ComputeUsageV1,
+    ) -> Result<Self, TryFromProtoError> {
+        Ok(ComputeUsageV1 {
+            id: proto.id,
+            cluster_id: proto.cluster_id,
+            role_id: proto.role_id,
+            cpu_nano_cores: proto.cpu_nano_cores,
+            memory_bytes: proto.memory_bytes,
+            peek_count: proto.peek_count,
+            peek_time_us: proto.peek_time_us,
+            collection_timestamp: proto
+                .collection_timestamp
+                .into_rust_if_some("ComputeUsageV1::collection_timestamp")?,
+        })
+    }
+}
+
 impl RustType<proto::storage_usage_key::Usage> for VersionedStorageUsage {
     fn into_proto(&self) -> proto::storage_usage_key::Usage {
         match self {
             VersionedStorageUsage::V1(usage) => {
                 proto::storage_usage_key::Usage::V1(usage.into_proto())
             }
+            VersionedStorageUsage::IoV1(usage) => {
+                proto::storage_usage_key::Usage::IoV1(usage.into_proto())
+            }
+            VersionedStorageUsage::ComputeV1(usage) => {
+                proto::storage_usage_key::Usage::ComputeV1(usage.into_proto())
+            }
         }
     }
 
@@ -2150,6 +2439,12 @@ impl RustType<proto::storage_usage_key::Usage> for VersionedStorageUsage {
             proto::storage_usage_key::Usage::V1(usage) => {
                 Ok(VersionedStorageUsage::V1(usage.into_rust()?))
             }
+            proto::storage_usage_key::Usage::IoV1(usage) => {
+                Ok(VersionedStorageUsage::IoV1(usage.into_rust()?))
+            }
+            proto::storage_usage_key::Usage::ComputeV1(usage) => {
+                Ok(VersionedStorageUsage::ComputeV1(usage.into_rust()?))
+            }
         }
     }
 }
@@ -91,6 +91,7 @@ impl StateUpdate {
             system_configurations,
             default_privileges,
             system_privileges,
+            quotas,
             audit_log_updates,
             storage_usage_updates,
         } = txn_batch;
@@ -120,6 +121,7 @@ impl StateUpdate {
         let default_privileges =
             from_batch(default_privileges, ts, StateUpdateKind::DefaultPrivilege);
         let system_privileges = from_batch(system_privileges, ts, StateUpdateKind::SystemPrivilege);
+        let quotas = from_batch(quotas, ts, StateUpdateKind::Quota);
         let audit_logs = from_batch(audit_log_updates, ts, StateUpdateKind::AuditLog);
         let storage_usage_updates =
             from_batch(storage_usage_updates, ts, StateUpdateKind::StorageUsage);
@@ -140,6 +142,7 @@ impl StateUpdate {
             .chain(system_configurations)
             .chain(default_privileges)
             .chain(system_privileges)
+            .chain(quotas)
             .chain(audit_logs)
             .chain(storage_usage_updates)
             .collect()
@@ -207,6 +210,7 @@ pub enum StateUpdateKind {
         proto::ClusterIntrospectionSourceIndexValue,
     ),
     Item(proto::ItemKey, proto::ItemValue),
+    Quota(proto::QuotaKey, proto::QuotaValue),
     Role(proto::RoleKey, proto::RoleValue),
     Schema(proto::SchemaKey, proto::SchemaValue),
     Setting(proto::SettingKey, proto::SettingValue),
@@ -236,6 +240,7 @@ impl StateUpdateKind {
                 Some(CollectionType::ComputeIntrospectionSourceIndex)
             }
             StateUpdateKind::Item(_, _) => Some(CollectionType::Item),
+            StateUpdateKind::Quota(_, _) => Some(CollectionType::Quota),
             StateUpdateKind::Role(_, _) => Some(CollectionType::Role),
             StateUpdateKind::Schema(_, _) => Some(CollectionType::Schema),
             StateUpdateKind::Setting(_, _) => Some(CollectionType::Setting),
@@ -322,6 +327,12 @@ impl RustType<proto::StateUpdateKind> for StateUpdateKind {
                         value: Some(value.clone()),
                     })
                 }
+                StateUpdateKind::Quota(key, value) => {
+                    proto::state_update_kind::Kind::Quota(proto::state_update_kind::Quota {
+                        key: Some(key.clone()),
+                        value: Some(value.clone()),
+                    })
+                }
                 StateUpdateKind::Role(key, value) => {
                     proto::state_update_kind::Kind::Role(proto::state_update_kind::Role {
                         key: Some(key.clone()),
@@ -504,6 +515,17 @@ impl RustType<proto::StateUpdateKind> for StateUpdateKind {
                         TryFromProtoError::missing_field("state_update_kind::Item::value")
                     })?,
                 ),
+                proto::state_update_kind::Kind::Quota(proto::state_update_kind::Quota {
+                    key,
+                    value,
+                }) => StateUpdateKind::Quota(
+                    key.ok_or_else(|| {
+                        TryFromProtoError::missing_field("state_update_kind::Quota::key")
+                    })?,
+                    value.ok_or_else(|| {
+                        TryFromProtoError::missing_field("state_update_kind::Quota::value")
+                    })?,
+                ),
                 proto::state_update_kind::Kind::Role(proto::state_update_kind::Role {
                     key,
                     value,
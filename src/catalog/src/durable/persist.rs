@@ -1097,6 +1097,9 @@ impl PersistCatalogState {
                     StateUpdateKind::Item(key, value) => {
                         apply(&mut snapshot.items, key, value, diff);
                     }
+                    StateUpdateKind::Quota(key, value) => {
+                        apply(&mut snapshot.quotas, key, value, diff);
+                    }
                     StateUpdateKind::Role(key, value) => {
                         apply(&mut snapshot.roles, key, value, diff);
                     }
@@ -1615,6 +1618,9 @@ impl Trace {
                 StateUpdateKind::Item(k, v) => {
                     trace.items.values.push(((k, v), ts.to_string(), diff))
                 }
+                StateUpdateKind::Quota(k, v) => {
+                    trace.quotas.values.push(((k, v), ts.to_string(), diff))
+                }
                 StateUpdateKind::Role(k, v) => {
                     trace.roles.values.push(((k, v), ts.to_string(), diff))
                 }
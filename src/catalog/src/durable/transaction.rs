@@ -18,6 +18,7 @@ use mz_audit_log::{VersionedEvent, VersionedStorageUsage};
 use mz_controller_types::{ClusterId, ReplicaId};
 use mz_ore::cast::{u64_to_usize, usize_to_u64};
 use mz_ore::collections::{CollectionExt, HashSet};
+use mz_ore::str::StrExt;
 use mz_ore::{soft_assert_no_log, soft_assert_or_log};
 use mz_pgrepr::oid::FIRST_USER_OID;
 use mz_proto::{RustType, TryFromProtoError};
@@ -25,8 +26,8 @@ use mz_repr::adt::mz_acl_item::{AclMode, MzAclItem};
 use mz_repr::role_id::RoleId;
 use mz_repr::{Diff, GlobalId};
 use mz_sql::catalog::{
-    CatalogError as SqlCatalogError, CatalogItemType, ObjectType, RoleAttributes, RoleMembership,
-    RoleVars,
+    CatalogError as SqlCatalogError, CatalogItemType, ObjectType, QuotaKind, QuotaSubject,
+    RoleAttributes, RoleMembership, RoleVars,
 };
 use mz_sql::names::{CommentObjectId, DatabaseId, SchemaId};
 use mz_sql::session::user::MZ_SYSTEM_ROLE_ID;
@@ -43,16 +44,16 @@ use crate::durable::objects::{
     ClusterReplicaValue, ClusterValue, CommentKey, CommentValue, Config, ConfigKey, ConfigValue,
     Database, DatabaseKey, DatabaseValue, DefaultPrivilegesKey, DefaultPrivilegesValue,
     DurableType, GidMappingKey, GidMappingValue, IdAllocKey, IdAllocValue,
-    IntrospectionSourceIndex, Item, ItemKey, ItemValue, ReplicaConfig, Role, RoleKey, RoleValue,
-    Schema, SchemaKey, SchemaValue, ServerConfigurationKey, ServerConfigurationValue, SettingKey,
-    SettingValue, StorageUsageKey, SystemObjectMapping, SystemPrivilegesKey, SystemPrivilegesValue,
-    TimestampKey, TimestampValue,
+    IntrospectionSourceIndex, Item, ItemKey, ItemValue, Quota, QuotaKey, QuotaValue, ReplicaConfig,
+    Role, RoleKey, RoleValue, Schema, SchemaKey, SchemaValue, ServerConfigurationKey,
+    ServerConfigurationValue, SettingKey, SettingValue, StorageUsageKey, SystemObjectMapping,
+    SystemPrivilegesKey, SystemPrivilegesValue, TimestampKey, TimestampValue,
 };
 use crate::durable::{
     CatalogError, Comment, DefaultPrivilege, DurableCatalogError, DurableCatalogState, Snapshot,
     SystemConfiguration, TimelineTimestamp, CATALOG_CONTENT_VERSION_KEY, DATABASE_ID_ALLOC_KEY,
-    OID_ALLOC_KEY, SCHEMA_ID_ALLOC_KEY, SYSTEM_ITEM_ALLOC_KEY, USER_ITEM_ALLOC_KEY,
-    USER_ROLE_ID_ALLOC_KEY,
+    EXPRESSION_CACHE_KEY_PREFIX, OID_ALLOC_KEY, PLAN_RECORD_KEY_PREFIX, SCHEMA_ID_ALLOC_KEY,
+    SQL_JOB_KEY_PREFIX, SYSTEM_ITEM_ALLOC_KEY, USER_ITEM_ALLOC_KEY, USER_ROLE_ID_ALLOC_KEY,
 };
 
 /// A [`Transaction`] batches multiple catalog operations together and commits them atomically.
@@ -79,6 +80,7 @@ pub struct Transaction<'a> {
     system_configurations: TableTransaction<ServerConfigurationKey, ServerConfigurationValue>,
     default_privileges: TableTransaction<DefaultPrivilegesKey, DefaultPrivilegesValue>,
     system_privileges: TableTransaction<SystemPrivilegesKey, SystemPrivilegesValue>,
+    quotas: TableTransaction<QuotaKey, QuotaValue>,
     // Don't make this a table transaction so that it's not read into the
     // in-memory cache.
     audit_log_updates: Vec<(proto::AuditLogKey, (), i64)>,
@@ -105,6 +107,7 @@ impl<'a> Transaction<'a> {
             system_configurations,
             default_privileges,
             system_privileges,
+            quotas,
         }: Snapshot,
     ) -> Result<Transaction, CatalogError> {
         Ok(Transaction {
@@ -138,6 +141,7 @@ impl<'a> Transaction<'a> {
             system_configurations: TableTransaction::new(system_configurations, |_a, _b| false)?,
             default_privileges: TableTransaction::new(default_privileges, |_a, _b| false)?,
             system_privileges: TableTransaction::new(system_privileges, |_a, _b| false)?,
+            quotas: TableTransaction::new(quotas, |_a, _b| false)?,
             audit_log_updates: Vec::new(),
             storage_usage_updates: Vec::new(),
         })
@@ -322,6 +326,7 @@ impl<'a> Transaction<'a> {
         privileges: Vec<MzAclItem>,
         config: ClusterConfig,
     ) -> Result<Vec<(&'static BuiltinLog, GlobalId, u32)>, CatalogError> {
+        self.check_cluster_quotas(owner_id, false)?;
         self.insert_cluster(
             cluster_id,
             cluster_name,
@@ -531,6 +536,7 @@ impl<'a> Transaction<'a> {
         owner_id: RoleId,
         privileges: Vec<MzAclItem>,
     ) -> Result<u32, CatalogError> {
+        self.check_object_quotas(schema_id, owner_id)?;
         let oid = self.allocate_oid()?;
         self.insert_item(
             id, oid, schema_id, item_name, create_sql, owner_id, privileges,
@@ -564,6 +570,162 @@ impl<'a> Transaction<'a> {
         }
     }
 
+    /// Returns an error if a new object owned by `owner_id` in the schema `schema_id` would
+    /// exceed the `max_objects` quota of the owner or of the schema's database.
+    fn check_object_quotas(
+        &self,
+        schema_id: SchemaId,
+        owner_id: RoleId,
+    ) -> Result<(), CatalogError> {
+        self.check_quota(
+            QuotaSubject::Role(owner_id),
+            QuotaKind::MaxObjects,
+            false,
+            || self.count_user_items(|v| v.owner_id == owner_id),
+        )?;
+        if let Some(database_id) = self.schema_database(schema_id) {
+            self.check_quota(
+                QuotaSubject::Database(database_id),
+                QuotaKind::MaxObjects,
+                false,
+                || {
+                    self.count_user_items(|v| {
+                        self.schema_database(v.schema_id) == Some(database_id)
+                    })
+                },
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Returns an error if the storage used by the owner `owner_id` or by the database of the
+    /// schema `schema_id` has reached its `max_storage_bytes` quota.
+    ///
+    /// The durable catalog does not know how much storage is used, so `usage` must return the
+    /// storage used by a role or database, in bytes.
+    pub fn check_storage_quotas(
+        &self,
+        schema_id: SchemaId,
+        owner_id: RoleId,
+        usage: impl Fn(QuotaSubject) -> u64,
+    ) -> Result<(), CatalogError> {
+        let subject = QuotaSubject::Role(owner_id);
+        self.check_quota(subject, QuotaKind::MaxStorageBytes, false, || {
+            usage(subject)
+        })?;
+        if let Some(database_id) = self.schema_database(schema_id) {
+            let subject = QuotaSubject::Database(database_id);
+            self.check_quota(subject, QuotaKind::MaxStorageBytes, false, || {
+                usage(subject)
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Returns an error if transferring the ownership of a user item to `new_owner_id` would
+    /// exceed the `max_objects` or `max_storage_bytes` quota of the new owner.
+    ///
+    /// The database of the item does not change, so its quotas are not checked. See
+    /// [`Transaction::check_storage_quotas`] for the meaning of `usage`.
+    pub fn check_item_owner_quotas(
+        &self,
+        new_owner_id: RoleId,
+        usage: impl Fn(QuotaSubject) -> u64,
+    ) -> Result<(), CatalogError> {
+        let subject = QuotaSubject::Role(new_owner_id);
+        self.check_quota(subject, QuotaKind::MaxObjects, true, || {
+            self.count_user_items(|v| v.owner_id == new_owner_id)
+        })?;
+        self.check_quota(subject, QuotaKind::MaxStorageBytes, true, || usage(subject))
+    }
+
+    /// Returns an error if transferring the ownership of a user cluster to `new_owner_id` would
+    /// exceed the `max_clusters` quota of the new owner.
+    pub fn check_cluster_owner_quotas(&self, new_owner_id: RoleId) -> Result<(), CatalogError> {
+        self.check_cluster_quotas(new_owner_id, true)
+    }
+
+    /// Returns an error if one more cluster owned by `owner_id` would exceed its
+    /// `max_clusters` quota.
+    fn check_cluster_quotas(
+        &self,
+        owner_id: RoleId,
+        ownership_transfer: bool,
+    ) -> Result<(), CatalogError> {
+        self.check_quota(
+            QuotaSubject::Role(owner_id),
+            QuotaKind::MaxClusters,
+            ownership_transfer,
+            || self.count_clusters(|k, v| k.id.is_user() && v.owner_id == owner_id),
+        )
+    }
+
+    /// Returns an error if the `current` usage has reached the `kind` quota of `subject`.
+    ///
+    /// `current` is only computed if `subject` has a quota of that kind.
+    fn check_quota(
+        &self,
+        subject: QuotaSubject,
+        kind: QuotaKind,
+        ownership_transfer: bool,
+        current: impl FnOnce() -> u64,
+    ) -> Result<(), CatalogError> {
+        let Some(QuotaValue { limit }) = self.quotas.get(&QuotaKey { subject, kind }) else {
+            return Ok(());
+        };
+        let current = current();
+        if current < *limit {
+            return Ok(());
+        }
+        let subject = match subject {
+            QuotaSubject::Role(id) => match self.roles.get(&RoleKey { id }) {
+                Some(role) => format!("role {}", role.name.quoted()),
+                None => format!("role {id}"),
+            },
+            QuotaSubject::Database(id) => match self.databases.get(&DatabaseKey { id }) {
+                Some(database) => format!("database {}", database.name.quoted()),
+                None => format!("database {id}"),
+            },
+        };
+        Err(SqlCatalogError::QuotaExceeded {
+            kind,
+            ownership_transfer,
+            subject,
+            limit: *limit,
+            current,
+        }
+        .into())
+    }
+
+    /// Returns the database of the schema `schema_id`, if any.
+    fn schema_database(&self, schema_id: SchemaId) -> Option<DatabaseId> {
+        self.schemas
+            .get(&SchemaKey { id: schema_id })
+            .and_then(|schema| schema.database_id)
+    }
+
+    /// Returns the number of user items that satisfy `f`.
+    fn count_user_items(&self, f: impl Fn(&ItemValue) -> bool) -> u64 {
+        let mut count = 0;
+        self.items.for_values(|k, v| {
+            if k.gid.is_user() && f(v) {
+                count += 1;
+            }
+        });
+        count
+    }
+
+    /// Returns the number of clusters that satisfy `f`.
+    fn count_clusters(&self, f: impl Fn(&ClusterKey, &ClusterValue) -> bool) -> u64 {
+        let mut count = 0;
+        self.clusters.for_values(|k, v| {
+            if f(k, v) {
+                count += 1;
+            }
+        });
+        count
+    }
+
     pub fn insert_timestamp(
         &mut self,
         timeline: Timeline,
@@ -1135,6 +1297,29 @@ impl<'a> Transaction<'a> {
         Ok(())
     }
 
+    /// Set persisted quota.
+    pub fn set_quota(
+        &mut self,
+        subject: QuotaSubject,
+        kind: QuotaKind,
+        limit: Option<u64>,
+    ) -> Result<(), CatalogError> {
+        self.quotas.set(
+            QuotaKey { subject, kind },
+            limit.map(|limit| QuotaValue { limit }),
+        )?;
+        Ok(())
+    }
+
+    /// Removes all persisted quotas of `subject`.
+    pub fn remove_quotas(&mut self, subject: QuotaSubject) -> Vec<Quota> {
+        self.quotas
+            .delete(|k, _v| k.subject == subject)
+            .into_iter()
+            .map(|(k, v)| DurableType::from_key_value(k, v))
+            .collect()
+    }
+
     /// Set persisted setting.
     pub(crate) fn set_setting(
         &mut self,
@@ -1152,6 +1337,30 @@ impl<'a> Transaction<'a> {
         self.set_setting(CATALOG_CONTENT_VERSION_KEY.to_string(), Some(version))
     }
 
+    /// Sets the persisted cached expression of the builtin item `id`, or removes it if `expr` is
+    /// `None`.
+    pub fn set_cached_expression(
+        &mut self,
+        id: GlobalId,
+        expr: Option<String>,
+    ) -> Result<(), CatalogError> {
+        self.set_setting(format!("{EXPRESSION_CACHE_KEY_PREFIX}{id}"), expr)
+    }
+
+    /// Sets the persisted plan record of the item `id`, or removes it if `record` is `None`.
+    pub fn set_plan_record(
+        &mut self,
+        id: GlobalId,
+        record: Option<String>,
+    ) -> Result<(), CatalogError> {
+        self.set_setting(format!("{PLAN_RECORD_KEY_PREFIX}{id}"), record)
+    }
+
+    /// Sets the persisted asynchronous SQL job `id`, or removes it if `job` is `None`.
+    pub fn set_sql_job(&mut self, id: &str, job: Option<String>) -> Result<(), CatalogError> {
+        self.set_setting(format!("{SQL_JOB_KEY_PREFIX}{id}"), job)
+    }
+
     /// Insert persisted introspection source index.
     pub fn insert_introspection_source_indexes(
         &mut self,
@@ -1377,6 +1586,14 @@ impl<'a> Transaction<'a> {
             .map(|(k, v)| DurableType::from_key_value(k, v))
     }
 
+    pub fn get_quotas(&self) -> impl Iterator<Item = Quota> {
+        self.quotas
+            .items()
+            .clone()
+            .into_iter()
+            .map(|(k, v)| DurableType::from_key_value(k, v))
+    }
+
     pub fn get_comments(&self) -> impl Iterator<Item = Comment> {
         self.comments
             .items()
@@ -1429,6 +1646,42 @@ impl<'a> Transaction<'a> {
             .map(|value| value.value.clone())
     }
 
+    /// Returns all persisted cached expressions of builtin items, keyed by the ID of the item.
+    pub fn get_cached_expressions(&self) -> BTreeMap<GlobalId, String> {
+        self.settings
+            .items()
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let id = key.name.strip_prefix(EXPRESSION_CACHE_KEY_PREFIX)?;
+                Some((id.parse().ok()?, value.value))
+            })
+            .collect()
+    }
+
+    /// Returns all persisted plan records, keyed by the ID of the item.
+    pub fn get_plan_records(&self) -> BTreeMap<GlobalId, String> {
+        self.settings
+            .items()
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let id = key.name.strip_prefix(PLAN_RECORD_KEY_PREFIX)?;
+                Some((id.parse().ok()?, value.value))
+            })
+            .collect()
+    }
+
+    /// Returns all persisted asynchronous SQL jobs, keyed by the ID of the job.
+    pub fn get_sql_jobs(&self) -> BTreeMap<String, String> {
+        self.settings
+            .items()
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let id = key.name.strip_prefix(SQL_JOB_KEY_PREFIX)?;
+                Some((id.to_string(), value.value))
+            })
+            .collect()
+    }
+
     // TODO(jkosh44) Can be removed after v0.92.X
     pub fn clean_up_stash_catalog(&mut self) -> Result<(), CatalogError> {
         self.configs.set(
@@ -1470,6 +1723,7 @@ impl<'a> Transaction<'a> {
             system_configurations: self.system_configurations.pending(),
             default_privileges: self.default_privileges.pending(),
             system_privileges: self.system_privileges.pending(),
+            quotas: self.quotas.pending(),
             audit_log_updates: self.audit_log_updates,
             storage_usage_updates: self.storage_usage_updates,
         };
@@ -1500,6 +1754,7 @@ impl<'a> Transaction<'a> {
             system_configurations,
             default_privileges,
             system_privileges,
+            quotas,
             audit_log_updates,
             storage_usage_updates,
         } = &mut txn_batch;
@@ -1521,6 +1776,7 @@ impl<'a> Transaction<'a> {
         differential_dataflow::consolidation::consolidate_updates(system_configurations);
         differential_dataflow::consolidation::consolidate_updates(default_privileges);
         differential_dataflow::consolidation::consolidate_updates(system_privileges);
+        differential_dataflow::consolidation::consolidate_updates(quotas);
         differential_dataflow::consolidation::consolidate_updates(audit_log_updates);
         differential_dataflow::consolidation::consolidate_updates(storage_usage_updates);
         durable_catalog.commit_transaction(txn_batch).await
@@ -1562,6 +1818,7 @@ pub struct TransactionBatch {
         proto::SystemPrivilegesValue,
         Diff,
     )>,
+    pub(crate) quotas: Vec<(proto::QuotaKey, proto::QuotaValue, Diff)>,
     pub(crate) audit_log_updates: Vec<(proto::AuditLogKey, (), Diff)>,
     pub(crate) storage_usage_updates: Vec<(proto::StorageUsageKey, (), Diff)>,
 }
@@ -1585,6 +1842,7 @@ impl TransactionBatch {
             system_configurations,
             default_privileges,
             system_privileges,
+            quotas,
             audit_log_updates,
             storage_usage_updates,
         } = self;
@@ -1605,6 +1863,7 @@ impl TransactionBatch {
             && system_configurations.is_empty()
             && default_privileges.is_empty()
             && system_privileges.is_empty()
+            && quotas.is_empty()
             && audit_log_updates.is_empty()
             && storage_usage_updates.is_empty()
     }
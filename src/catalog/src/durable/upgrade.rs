@@ -170,14 +170,14 @@ macro_rules! objects {
     }
 }
 
-objects!(v42, v43, v44, v45, v46, v47, v48);
+objects!(v42, v43, v44, v45, v46, v47, v48, v49, v50, v51, v52, v53, v54, v55, v56, v57);
 
 /// The current version of the `Catalog`.
 ///
 /// We will initialize new `Catalog`es with this version, and migrate existing `Catalog`es to this
 /// version. Whenever the `Catalog` changes, e.g. the protobufs we serialize in the `Catalog`
 /// change, we need to bump this version.
-pub const CATALOG_VERSION: u64 = 48;
+pub const CATALOG_VERSION: u64 = 57;
 
 /// The minimum `Catalog` version number that we support migrating from.
 ///
@@ -195,6 +195,15 @@ mod v44_to_v45;
 mod v45_to_v46;
 mod v46_to_v47;
 mod v47_to_v48;
+mod v48_to_v49;
+mod v49_to_v50;
+mod v50_to_v51;
+mod v51_to_v52;
+mod v52_to_v53;
+mod v53_to_v54;
+mod v54_to_v55;
+mod v55_to_v56;
+mod v56_to_v57;
 
 /// Describes a single action to take during a migration from `V1` to `V2`.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -294,6 +303,42 @@ pub(crate) async fn upgrade(
                 run_versioned_upgrade(unopened_catalog_state, mode, version, v47_to_v48::upgrade)
                     .await
             }
+            48 => {
+                run_versioned_upgrade(unopened_catalog_state, mode, version, v48_to_v49::upgrade)
+                    .await
+            }
+            49 => {
+                run_versioned_upgrade(unopened_catalog_state, mode, version, v49_to_v50::upgrade)
+                    .await
+            }
+            50 => {
+                run_versioned_upgrade(unopened_catalog_state, mode, version, v50_to_v51::upgrade)
+                    .await
+            }
+            51 => {
+                run_versioned_upgrade(unopened_catalog_state, mode, version, v51_to_v52::upgrade)
+                    .await
+            }
+            52 => {
+                run_versioned_upgrade(unopened_catalog_state, mode, version, v52_to_v53::upgrade)
+                    .await
+            }
+            53 => {
+                run_versioned_upgrade(unopened_catalog_state, mode, version, v53_to_v54::upgrade)
+                    .await
+            }
+            54 => {
+                run_versioned_upgrade(unopened_catalog_state, mode, version, v54_to_v55::upgrade)
+                    .await
+            }
+            55 => {
+                run_versioned_upgrade(unopened_catalog_state, mode, version, v55_to_v56::upgrade)
+                    .await
+            }
+            56 => {
+                run_versioned_upgrade(unopened_catalog_state, mode, version, v56_to_v57::upgrade)
+                    .await
+            }
 
             // Up-to-date, no migration needed!
             CATALOG_VERSION => Ok(CATALOG_VERSION),
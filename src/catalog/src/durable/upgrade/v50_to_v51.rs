@@ -0,0 +1,245 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use mz_stash::upgrade::WireCompatible;
+use mz_stash::wire_compatible;
+
+use crate::durable::upgrade::MigrationAction;
+use crate::durable::upgrade::{objects_v50 as v50, objects_v51 as v51};
+
+wire_compatible!(v50::ClusterKey with v51::ClusterKey);
+wire_compatible!(v50::MzAclItem with v51::MzAclItem);
+wire_compatible!(v50::RoleId with v51::RoleId);
+wire_compatible!(v50::ReplicaLogging with v51::ReplicaLogging);
+wire_compatible!(v50::ReplicaMergeEffort with v51::ReplicaMergeEffort);
+wire_compatible!(v50::OptimizerFeatureOverride with v51::OptimizerFeatureOverride);
+wire_compatible!(v50::ClusterSchedule with v51::ClusterSchedule);
+
+/// Introduce an unset `max_concurrent_queries` in `ManagedCluster`'s.
+pub fn upgrade(
+    snapshot: Vec<v50::StateUpdateKind>,
+) -> Vec<MigrationAction<v50::StateUpdateKind, v51::StateUpdateKind>> {
+    snapshot
+        .into_iter()
+        .filter_map(|update| {
+            let update = update.kind.as_ref().expect("missing field");
+            let v50::state_update_kind::Kind::Cluster(update) = update else {
+                return None;
+            };
+            if !update.is_managed() {
+                return None;
+            };
+
+            let old = v50::StateUpdateKind {
+                kind: Some(v50::state_update_kind::Kind::Cluster(
+                    v50::state_update_kind::Cluster {
+                        key: update.key.clone(),
+                        value: update.value.clone(),
+                    },
+                )),
+            };
+
+            let new = v51::StateUpdateKind {
+                kind: Some(v51::state_update_kind::Kind::Cluster(
+                    v51::state_update_kind::Cluster {
+                        key: update.key.as_ref().map(WireCompatible::convert),
+                        value: update.value.as_ref().map(|old_val| v51::ClusterValue {
+                            name: old_val.name.clone(),
+                            owner_id: old_val.owner_id.as_ref().map(WireCompatible::convert),
+                            privileges: old_val
+                                .privileges
+                                .iter()
+                                .map(WireCompatible::convert)
+                                .collect(),
+                            config: old_val.config.as_ref().map(|config| v51::ClusterConfig {
+                                variant: config.variant.as_ref().map(|variant| match variant {
+                                    v50::cluster_config::Variant::Unmanaged(_) => {
+                                        v51::cluster_config::Variant::Unmanaged(v51::Empty {})
+                                    }
+                                    v50::cluster_config::Variant::Managed(c) => {
+                                        v51::cluster_config::Variant::Managed(
+                                            v51::cluster_config::ManagedCluster {
+                                                size: c.size.clone(),
+                                                replication_factor: c.replication_factor,
+                                                availability_zones: c.availability_zones.clone(),
+                                                logging: c
+                                                    .logging
+                                                    .as_ref()
+                                                    .map(WireCompatible::convert),
+                                                idle_arrangement_merge_effort: c
+                                                    .idle_arrangement_merge_effort
+                                                    .as_ref()
+                                                    .map(WireCompatible::convert),
+                                                disk: c.disk,
+                                                optimizer_feature_overrides: c
+                                                    .optimizer_feature_overrides
+                                                    .iter()
+                                                    .map(WireCompatible::convert)
+                                                    .collect(),
+                                                schedule: c
+                                                    .schedule
+                                                    .as_ref()
+                                                    .map(WireCompatible::convert),
+                                                max_concurrent_queries: None,
+                                            },
+                                        )
+                                    }
+                                }),
+                            }),
+                        }),
+                    },
+                )),
+            };
+
+            Some(MigrationAction::Update(old, new))
+        })
+        .collect()
+}
+
+impl v50::state_update_kind::Cluster {
+    fn is_managed(&self) -> bool {
+        let Some(cluster) = self.value.as_ref() else {
+            return false;
+        };
+        let Some(config) = cluster.config.as_ref() else {
+            return false;
+        };
+        match config.variant.as_ref() {
+            Some(v50::cluster_config::Variant::Managed(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
+    async fn smoke_test_migration() {
+        let v50 = v50::StateUpdateKind {
+            kind: Some(v50::state_update_kind::Kind::Cluster(
+                v50::state_update_kind::Cluster {
+                    key: Some(v50::ClusterKey {
+                        id: Some(v50::ClusterId {
+                            value: Some(v50::cluster_id::Value::User(Default::default())),
+                        }),
+                    }),
+                    value: Some(v50::ClusterValue {
+                        name: Default::default(),
+                        owner_id: Some(v50::RoleId {
+                            value: Some(v50::role_id::Value::Public(Default::default())),
+                        }),
+                        privileges: vec![],
+                        config: Some(v50::ClusterConfig {
+                            variant: Some(v50::cluster_config::Variant::Managed(
+                                v50::cluster_config::ManagedCluster {
+                                    size: String::from("1cc"),
+                                    replication_factor: 2,
+                                    availability_zones: vec![
+                                        String::from("az1"),
+                                        String::from("az2"),
+                                    ],
+                                    logging: Some(v50::ReplicaLogging {
+                                        log_logging: true,
+                                        interval: Some(v50::Duration {
+                                            secs: 3600,
+                                            nanos: 747,
+                                        }),
+                                    }),
+                                    idle_arrangement_merge_effort: Some(v50::ReplicaMergeEffort {
+                                        effort: 42,
+                                    }),
+                                    disk: true,
+                                    optimizer_feature_overrides: vec![
+                                        v50::OptimizerFeatureOverride {
+                                            name: String::from("enable_eager_delta_joins"),
+                                            value: String::from("true"),
+                                        },
+                                    ],
+                                    schedule: Some(v50::ClusterSchedule {
+                                        value: Some(v50::cluster_schedule::Value::Cron(
+                                            String::from("* 8-17 * * 1-5"),
+                                        )),
+                                    }),
+                                },
+                            )),
+                        }),
+                    }),
+                },
+            )),
+        };
+
+        let v51 = v51::StateUpdateKind {
+            kind: Some(v51::state_update_kind::Kind::Cluster(
+                v51::state_update_kind::Cluster {
+                    key: Some(v51::ClusterKey {
+                        id: Some(v51::ClusterId {
+                            value: Some(v51::cluster_id::Value::User(Default::default())),
+                        }),
+                    }),
+                    value: Some(v51::ClusterValue {
+                        name: Default::default(),
+                        owner_id: Some(v51::RoleId {
+                            value: Some(v51::role_id::Value::Public(Default::default())),
+                        }),
+                        privileges: vec![],
+                        config: Some(v51::ClusterConfig {
+                            variant: Some(v51::cluster_config::Variant::Managed(
+                                v51::cluster_config::ManagedCluster {
+                                    size: String::from("1cc"),
+                                    replication_factor: 2,
+                                    availability_zones: vec![
+                                        String::from("az1"),
+                                        String::from("az2"),
+                                    ],
+                                    logging: Some(v51::ReplicaLogging {
+                                        log_logging: true,
+                                        interval: Some(v51::Duration {
+                                            secs: 3600,
+                                            nanos: 747,
+                                        }),
+                                    }),
+                                    idle_arrangement_merge_effort: Some(v51::ReplicaMergeEffort {
+                                        effort: 42,
+                                    }),
+                                    disk: true,
+                                    optimizer_feature_overrides: vec![
+                                        v51::OptimizerFeatureOverride {
+                                            name: String::from("enable_eager_delta_joins"),
+                                            value: String::from("true"),
+                                        },
+                                    ],
+                                    schedule: Some(v51::ClusterSchedule {
+                                        value: Some(v51::cluster_schedule::Value::Cron(
+                                            String::from("* 8-17 * * 1-5"),
+                                        )),
+                                    }),
+                                    max_concurrent_queries: None,
+                                },
+                            )),
+                        }),
+                    }),
+                },
+            )),
+        };
+
+        let actions = upgrade(vec![v50.clone()]);
+
+        match &actions[..] {
+            [MigrationAction::Update(old, new)] => {
+                assert_eq!(old, &v50);
+                assert_eq!(new, &v51);
+            }
+            o => panic!("expected single MigrationAction::Update, got {:?}", o),
+        }
+    }
+}
@@ -12,6 +12,7 @@
 use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet};
 use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
 use mz_adapter_types::compaction::CompactionWindow;
@@ -28,12 +29,11 @@ use mz_controller::clusters::{
 use mz_controller_types::{ClusterId, ReplicaId};
 use mz_expr::refresh_schedule::RefreshSchedule;
 use mz_expr::{CollectionPlan, MirScalarExpr, OptimizedMirRelationExpr};
-use mz_ore::collections::CollectionExt;
 use mz_repr::adt::mz_acl_item::{AclMode, PrivilegeMap};
 use mz_repr::optimize::OptimizerFeatureOverrides;
 use mz_repr::role_id::RoleId;
 use mz_repr::{GlobalId, RelationDesc};
-use mz_sql::ast::display::AstDisplay;
+use mz_sql::ast::transform::{rewrite_create_sql, CreateSqlRewrite};
 use mz_sql::ast::Expr;
 use mz_sql::catalog::{
     CatalogClusterReplica, CatalogError as SqlCatalogError, CatalogItem as SqlCatalogItem,
@@ -46,8 +46,8 @@ use mz_sql::names::{
     ResolvedDatabaseSpecifier, ResolvedIds, SchemaId, SchemaSpecifier,
 };
 use mz_sql::plan::{
-    CreateSourcePlan, HirRelationExpr, Ingestion as PlanIngestion, WebhookBodyFormat,
-    WebhookHeaders, WebhookValidation,
+    ClusterSchedule, CreateSourcePlan, HirRelationExpr, Ingestion as PlanIngestion,
+    WebhookBodyFormat, WebhookDeduplication, WebhookHeaders, WebhookValidation,
 };
 use mz_sql::rbac;
 use mz_sql::session::vars::OwnedVarInput;
@@ -340,6 +340,7 @@ pub enum CatalogItem {
     Func(Func),
     Secret(Secret),
     Connection(Connection),
+    ContinualTask(ContinualTask),
 }
 
 impl From<CatalogEntry> for durable::Item {
@@ -364,6 +365,10 @@ pub struct Table {
     pub defaults: Vec<Expr<Aug>>,
     #[serde(skip)]
     pub conn_id: Option<ConnectionId>,
+    /// Whether writes to the table are discarded when their transaction
+    /// commits, i.e. `ON COMMIT DELETE ROWS`. Only temporary tables can set
+    /// this.
+    pub delete_rows_on_commit: bool,
     pub resolved_ids: ResolvedIds,
     pub custom_logical_compaction_window: Option<CompactionWindow>,
     /// Whether the table's logical compaction window is controlled by
@@ -397,6 +402,8 @@ pub enum DataSourceDesc {
         body_format: WebhookBodyFormat,
         /// Describes whether or not to include headers and how to map them.
         headers: WebhookHeaders,
+        /// Optionally describes how to deduplicate requests.
+        deduplicate: Option<WebhookDeduplication>,
         /// The cluster which this source is associated with.
         cluster_id: ClusterId,
     },
@@ -493,10 +500,12 @@ impl Source {
                     validate_using,
                     body_format,
                     headers,
+                    deduplicate,
                 } => DataSourceDesc::Webhook {
                     validate_using,
                     body_format,
                     headers,
+                    deduplicate,
                     cluster_id: plan
                         .in_cluster
                         .expect("webhook sources must be given a cluster ID"),
@@ -659,10 +668,13 @@ impl Sink {
 
     /// Output format of the sink.
     pub fn format(&self) -> &str {
-        let StorageSinkConnection::Kafka(connection) = &self.connection;
-        match &connection.format {
-            KafkaSinkFormat::Avro { .. } => "avro",
-            KafkaSinkFormat::Json => "json",
+        match &self.connection {
+            StorageSinkConnection::Kafka(connection) => match &connection.format {
+                KafkaSinkFormat::Avro { .. } => "avro",
+                KafkaSinkFormat::Json => "json",
+                KafkaSinkFormat::Protobuf { .. } => "protobuf",
+            },
+            StorageSinkConnection::Iceberg(_) => "parquet",
         }
     }
 
@@ -695,6 +707,9 @@ pub struct MaterializedView {
     // The initial `as_of` of the storage collection associated with the materialized view.
     // (The dataflow's initial `as_of` can be different.)
     pub initial_as_of: Option<Antichain<mz_repr::Timestamp>>,
+    /// The maximum number of bytes the arrangements of the materialized view's dataflow may
+    /// occupy, if limited.
+    pub memory_limit: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -720,8 +735,43 @@ pub struct Type {
 
 #[derive(Debug, Clone, Serialize)]
 pub struct Func {
+    /// The `CREATE FUNCTION` statement of a user-defined function, or `None`
+    /// for builtin functions.
+    pub create_sql: Option<String>,
     #[serde(skip)]
-    pub inner: &'static mz_sql::func::Func,
+    pub inner: FuncDefinition,
+    pub resolved_ids: ResolvedIds,
+}
+
+/// The implementations of a [`Func`].
+#[derive(Debug, Clone)]
+pub enum FuncDefinition {
+    /// The implementations of a builtin function.
+    Builtin(&'static mz_sql::func::Func),
+    /// The implementations of a function created with `CREATE FUNCTION`.
+    User(Arc<mz_sql::func::Func>),
+}
+
+impl Deref for FuncDefinition {
+    type Target = mz_sql::func::Func;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            FuncDefinition::Builtin(func) => func,
+            FuncDefinition::User(func) => func,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContinualTask {
+    pub create_sql: String,
+    /// The query whose changes are inserted into `target_id`.
+    pub raw_expr: HirRelationExpr,
+    /// The table that the task inserts into.
+    pub target_id: GlobalId,
+    pub resolved_ids: ResolvedIds,
+    pub cluster_id: ClusterId,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -751,6 +801,7 @@ impl CatalogItem {
             CatalogItem::Func(_) => mz_sql::catalog::CatalogItemType::Func,
             CatalogItem::Secret(_) => mz_sql::catalog::CatalogItemType::Secret,
             CatalogItem::Connection(_) => mz_sql::catalog::CatalogItemType::Connection,
+            CatalogItem::ContinualTask(_) => mz_sql::catalog::CatalogItemType::ContinualTask,
         }
     }
 
@@ -773,16 +824,14 @@ impl CatalogItem {
             | CatalogItem::Index(_)
             | CatalogItem::Sink(_)
             | CatalogItem::Secret(_)
-            | CatalogItem::Connection(_) => None,
+            | CatalogItem::Connection(_)
+            | CatalogItem::ContinualTask(_) => None,
         }
     }
 
-    pub fn func(
-        &self,
-        entry: &CatalogEntry,
-    ) -> Result<&'static mz_sql::func::Func, SqlCatalogError> {
+    pub fn func(&self, entry: &CatalogEntry) -> Result<&mz_sql::func::Func, SqlCatalogError> {
         match &self {
-            CatalogItem::Func(func) => Ok(func.inner),
+            CatalogItem::Func(func) => Ok(&func.inner),
             _ => Err(SqlCatalogError::UnexpectedType {
                 name: entry.name().item.to_string(),
                 actual_type: entry.item_type(),
@@ -827,7 +876,7 @@ impl CatalogItem {
     pub fn references(&self) -> &ResolvedIds {
         static EMPTY: Lazy<ResolvedIds> = Lazy::new(|| ResolvedIds(BTreeSet::new()));
         match self {
-            CatalogItem::Func(_) => &*EMPTY,
+            CatalogItem::Func(func) => &func.resolved_ids,
             CatalogItem::Index(idx) => &idx.resolved_ids,
             CatalogItem::Sink(sink) => &sink.resolved_ids,
             CatalogItem::Source(source) => &source.resolved_ids,
@@ -838,6 +887,7 @@ impl CatalogItem {
             CatalogItem::MaterializedView(mview) => &mview.resolved_ids,
             CatalogItem::Secret(_) => &*EMPTY,
             CatalogItem::Connection(connection) => &connection.resolved_ids,
+            CatalogItem::ContinualTask(ct) => &ct.resolved_ids,
         }
     }
 
@@ -862,6 +912,7 @@ impl CatalogItem {
             CatalogItem::MaterializedView(mview) => uses.extend(mview.raw_expr.depends_on()),
             CatalogItem::Secret(_) => {}
             CatalogItem::Connection(_) => {}
+            CatalogItem::ContinualTask(ct) => uses.extend(ct.raw_expr.depends_on()),
         }
         uses
     }
@@ -880,7 +931,8 @@ impl CatalogItem {
             | CatalogItem::Secret(_)
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
-            | CatalogItem::Connection(_) => None,
+            | CatalogItem::Connection(_)
+            | CatalogItem::ContinualTask(_) => None,
         }
     }
 
@@ -889,28 +941,10 @@ impl CatalogItem {
         self.conn_id().is_some()
     }
 
-    pub fn rename_schema_refs(
-        &self,
-        database_name: &str,
-        cur_schema_name: &str,
-        new_schema_name: &str,
-    ) -> Result<CatalogItem, (String, String)> {
-        let do_rewrite = |create_sql: String| -> Result<String, (String, String)> {
-            let mut create_stmt = mz_sql::parse::parse(&create_sql)
-                .expect("invalid create sql persisted to catalog")
-                .into_element()
-                .ast;
-
-            // Rename all references to cur_schema_name.
-            mz_sql::ast::transform::create_stmt_rename_schema_refs(
-                &mut create_stmt,
-                database_name,
-                cur_schema_name,
-                new_schema_name,
-            )?;
-
-            Ok(create_stmt.to_ast_string_stable())
-        };
+    /// Returns a clone of `self` with `rewrite` applied to its `create_sql`, or errors if the
+    /// rewrite is ambiguous.
+    pub fn rewrite_create_sql(&self, rewrite: &CreateSqlRewrite) -> Result<CatalogItem, String> {
+        let do_rewrite = |create_sql: String| rewrite_create_sql(&create_sql, rewrite);
 
         match self {
             CatalogItem::Table(i) => {
@@ -959,76 +993,15 @@ impl CatalogItem {
                 i.create_sql = i.create_sql.map(do_rewrite).transpose()?;
                 Ok(CatalogItem::Type(i))
             }
-            CatalogItem::Func(i) => Ok(CatalogItem::Func(i.clone())),
-        }
-    }
-
-    /// Returns a clone of `self` with all instances of `from` renamed to `to`
-    /// (with the option of including the item's own name) or errors if request
-    /// is ambiguous.
-    pub fn rename_item_refs(
-        &self,
-        from: FullItemName,
-        to_item_name: String,
-        rename_self: bool,
-    ) -> Result<CatalogItem, String> {
-        let do_rewrite = |create_sql: String| -> Result<String, String> {
-            let mut create_stmt = mz_sql::parse::parse(&create_sql)
-                .expect("invalid create sql persisted to catalog")
-                .into_element()
-                .ast;
-            if rename_self {
-                mz_sql::ast::transform::create_stmt_rename(&mut create_stmt, to_item_name.clone());
-            }
-            // Determination of what constitutes an ambiguous request is done here.
-            mz_sql::ast::transform::create_stmt_rename_refs(&mut create_stmt, from, to_item_name)?;
-            Ok(create_stmt.to_ast_string_stable())
-        };
-
-        match self {
-            CatalogItem::Table(i) => {
-                let mut i = i.clone();
-                i.create_sql = i.create_sql.map(do_rewrite).transpose()?;
-                Ok(CatalogItem::Table(i))
-            }
-            CatalogItem::Log(i) => Ok(CatalogItem::Log(i.clone())),
-            CatalogItem::Source(i) => {
+            CatalogItem::Func(i) => {
                 let mut i = i.clone();
                 i.create_sql = i.create_sql.map(do_rewrite).transpose()?;
-                Ok(CatalogItem::Source(i))
-            }
-            CatalogItem::Sink(i) => {
-                let mut i = i.clone();
-                i.create_sql = do_rewrite(i.create_sql)?;
-                Ok(CatalogItem::Sink(i))
-            }
-            CatalogItem::View(i) => {
-                let mut i = i.clone();
-                i.create_sql = do_rewrite(i.create_sql)?;
-                Ok(CatalogItem::View(i))
-            }
-            CatalogItem::MaterializedView(i) => {
-                let mut i = i.clone();
-                i.create_sql = do_rewrite(i.create_sql)?;
-                Ok(CatalogItem::MaterializedView(i))
+                Ok(CatalogItem::Func(i))
             }
-            CatalogItem::Index(i) => {
+            CatalogItem::ContinualTask(i) => {
                 let mut i = i.clone();
                 i.create_sql = do_rewrite(i.create_sql)?;
-                Ok(CatalogItem::Index(i))
-            }
-            CatalogItem::Secret(i) => {
-                let mut i = i.clone();
-                i.create_sql = do_rewrite(i.create_sql)?;
-                Ok(CatalogItem::Secret(i))
-            }
-            CatalogItem::Func(_) | CatalogItem::Type(_) => {
-                unreachable!("{}s cannot be renamed", self.typ())
-            }
-            CatalogItem::Connection(i) => {
-                let mut i = i.clone();
-                i.create_sql = do_rewrite(i.create_sql)?;
-                Ok(CatalogItem::Connection(i))
+                Ok(CatalogItem::ContinualTask(i))
             }
         }
     }
@@ -1051,7 +1024,8 @@ impl CatalogItem {
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
-            | CatalogItem::Connection(_) => None,
+            | CatalogItem::Connection(_)
+            | CatalogItem::ContinualTask(_) => None,
         }
     }
 
@@ -1067,6 +1041,7 @@ impl CatalogItem {
                 | DataSourceDesc::Source => None,
             },
             CatalogItem::Sink(sink) => Some(sink.cluster_id),
+            CatalogItem::ContinualTask(ct) => Some(ct.cluster_id),
             CatalogItem::Table(_)
             | CatalogItem::Log(_)
             | CatalogItem::View(_)
@@ -1090,7 +1065,8 @@ impl CatalogItem {
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
-            | CatalogItem::Connection(_) => None,
+            | CatalogItem::Connection(_)
+            | CatalogItem::ContinualTask(_) => None,
         }
     }
 
@@ -1114,7 +1090,8 @@ impl CatalogItem {
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
-            | CatalogItem::Connection(_) => return None,
+            | CatalogItem::Connection(_)
+            | CatalogItem::ContinualTask(_) => return None,
         };
         Some(custom_logical_compaction_window.unwrap_or(CompactionWindow::Default))
     }
@@ -1134,7 +1111,8 @@ impl CatalogItem {
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
-            | CatalogItem::Connection(_) => false,
+            | CatalogItem::Connection(_)
+            | CatalogItem::ContinualTask(_) => false,
         }
     }
 
@@ -1168,7 +1146,12 @@ impl CatalogItem {
                 .clone(),
             CatalogItem::Secret(secret) => secret.create_sql.clone(),
             CatalogItem::Connection(connection) => connection.create_sql.clone(),
-            CatalogItem::Func(_) => unreachable!("cannot serialize functions yet"),
+            CatalogItem::ContinualTask(ct) => ct.create_sql.clone(),
+            CatalogItem::Func(func) => func
+                .create_sql
+                .as_ref()
+                .expect("builtin functions cannot be serialized")
+                .clone(),
         }
     }
 
@@ -1194,7 +1177,10 @@ impl CatalogItem {
             CatalogItem::Type(typ) => typ.create_sql.expect("builtin types cannot be serialized"),
             CatalogItem::Secret(secret) => secret.create_sql,
             CatalogItem::Connection(connection) => connection.create_sql,
-            CatalogItem::Func(_) => unreachable!("cannot serialize functions yet"),
+            CatalogItem::ContinualTask(ct) => ct.create_sql,
+            CatalogItem::Func(func) => func
+                .create_sql
+                .expect("builtin functions cannot be serialized"),
         }
     }
 }
@@ -1218,7 +1204,7 @@ impl CatalogEntry {
     }
 
     /// Returns the [`mz_sql::func::Func`] associated with this `CatalogEntry`.
-    pub fn func(&self) -> Result<&'static mz_sql::func::Func, SqlCatalogError> {
+    pub fn func(&self) -> Result<&mz_sql::func::Func, SqlCatalogError> {
         self.item.func(self)
     }
 
@@ -1337,7 +1323,8 @@ impl CatalogEntry {
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
-            | CatalogItem::Connection(_) => BTreeSet::new(),
+            | CatalogItem::Connection(_)
+            | CatalogItem::ContinualTask(_) => BTreeSet::new(),
         }
     }
 
@@ -1360,7 +1347,8 @@ impl CatalogEntry {
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
-            | CatalogItem::Connection(_) => None,
+            | CatalogItem::Connection(_)
+            | CatalogItem::ContinualTask(_) => None,
         }
     }
 
@@ -1766,6 +1754,8 @@ pub struct ClusterVariantManaged {
     pub replication_factor: u32,
     pub disk: bool,
     pub optimizer_feature_overrides: OptimizerFeatureOverrides,
+    pub schedule: ClusterSchedule,
+    pub max_concurrent_queries: Option<u32>,
 }
 
 impl From<ClusterVariantManaged> for durable::ClusterVariantManaged {
@@ -1778,6 +1768,8 @@ impl From<ClusterVariantManaged> for durable::ClusterVariantManaged {
             replication_factor: managed.replication_factor,
             disk: managed.disk,
             optimizer_feature_overrides: managed.optimizer_feature_overrides.into(),
+            schedule: managed.schedule,
+            max_concurrent_queries: managed.max_concurrent_queries,
         }
     }
 }
@@ -1792,6 +1784,8 @@ impl From<durable::ClusterVariantManaged> for ClusterVariantManaged {
             replication_factor: managed.replication_factor,
             disk: managed.disk,
             optimizer_feature_overrides: managed.optimizer_feature_overrides.into(),
+            schedule: managed.schedule,
+            max_concurrent_queries: managed.max_concurrent_queries,
         }
     }
 }
@@ -2001,7 +1995,7 @@ impl mz_sql::catalog::CatalogItem for CatalogEntry {
         self.desc(name)
     }
 
-    fn func(&self) -> Result<&'static mz_sql::func::Func, SqlCatalogError> {
+    fn func(&self) -> Result<&mz_sql::func::Func, SqlCatalogError> {
         self.func()
     }
 
@@ -2033,7 +2027,10 @@ impl mz_sql::catalog::CatalogItem for CatalogEntry {
             }
             CatalogItem::Secret(Secret { create_sql, .. }) => create_sql,
             CatalogItem::Connection(Connection { create_sql, .. }) => create_sql,
-            CatalogItem::Func(_) => "<builtin>",
+            CatalogItem::ContinualTask(ContinualTask { create_sql, .. }) => create_sql,
+            CatalogItem::Func(Func { create_sql, .. }) => {
+                create_sql.as_deref().unwrap_or("<builtin>")
+            }
             CatalogItem::Log(_) => "<builtin>",
         }
     }
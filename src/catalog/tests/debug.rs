@@ -55,6 +55,7 @@ impl Debug for HiddenUserVersionTrace<'_> {
             default_privileges,
             id_allocator,
             items,
+            quotas,
             roles,
             schemas,
             settings,
@@ -83,6 +84,7 @@ impl Debug for HiddenUserVersionTrace<'_> {
             .field("default_privileges", default_privileges)
             .field("id_allocator", id_allocator)
             .field("items", items)
+            .field("quotas", quotas)
             .field("roles", roles)
             .field("schemas", schemas)
             .field("settings", settings)
@@ -64,6 +64,7 @@ impl Debug for HiddenUserVersionSnapshot<'_> {
             system_configurations,
             default_privileges,
             system_privileges,
+            quotas,
         } = self.0;
         let mut configs: BTreeMap<proto::ConfigKey, proto::ConfigValue> = configs.clone();
         configs.remove(&Self::user_version_key());
@@ -84,6 +85,7 @@ impl Debug for HiddenUserVersionSnapshot<'_> {
             .field("system_configurations", system_configurations)
             .field("default_privileges", default_privileges)
             .field("system_privileges", system_privileges)
+            .field("quotas", quotas)
             .finish()
     }
 }
@@ -9,15 +9,14 @@
 
 use itertools::Itertools;
 use mz_audit_log::{
-    CreateClusterReplicaV1, EventDetails, EventType, EventV1, IdNameV1, StorageUsageV1,
-    VersionedEvent, VersionedStorageUsage,
+    ComputeUsageV1, CreateClusterReplicaV1, EventContextV1, EventDetails, EventType, EventV1,
+    EventV2, IdNameV1, StorageIoUsageV1, StorageUsageV1, VersionedEvent, VersionedStorageUsage,
 };
 use mz_catalog::durable::objects::{DurableType, IdAlloc};
 use mz_catalog::durable::{
     test_bootstrap_args, test_persist_backed_catalog_state, CatalogError, DurableCatalogError,
     Item, OpenableDurableCatalogState, USER_ITEM_ALLOC_KEY,
 };
-use mz_ore::collections::CollectionExt;
 use mz_ore::now::SYSTEM_TIME;
 use mz_persist_client::PersistClient;
 use mz_proto::RustType;
@@ -96,6 +95,40 @@ async fn test_get_and_prune_storage_usage(openable_state: impl OpenableDurableCa
         size_bytes: 42,
         collection_timestamp: 20,
     });
+    let old_io_event = VersionedStorageUsage::IoV1(StorageIoUsageV1 {
+        id: 2,
+        object_id: "u1".to_string(),
+        bytes_read: 7,
+        bytes_written: 42,
+        collection_timestamp: 10,
+    });
+    let recent_io_event = VersionedStorageUsage::IoV1(StorageIoUsageV1 {
+        id: 3,
+        object_id: "u1".to_string(),
+        bytes_read: 7,
+        bytes_written: 42,
+        collection_timestamp: 20,
+    });
+    let old_compute_event = VersionedStorageUsage::ComputeV1(ComputeUsageV1 {
+        id: 4,
+        cluster_id: "u1".to_string(),
+        role_id: "u1".to_string(),
+        cpu_nano_cores: 1_000_000,
+        memory_bytes: 42,
+        peek_count: 3,
+        peek_time_us: 7,
+        collection_timestamp: 10,
+    });
+    let recent_compute_event = VersionedStorageUsage::ComputeV1(ComputeUsageV1 {
+        id: 5,
+        cluster_id: "u1".to_string(),
+        role_id: "u1".to_string(),
+        cpu_nano_cores: 1_000_000,
+        memory_bytes: 42,
+        peek_count: 3,
+        peek_time_us: 7,
+        collection_timestamp: 20,
+    });
     let boot_ts = mz_repr::Timestamp::new(23);
 
     let mut state = Box::new(openable_state)
@@ -105,6 +138,10 @@ async fn test_get_and_prune_storage_usage(openable_state: impl OpenableDurableCa
     let mut txn = state.transaction().await.unwrap();
     txn.insert_storage_usage_event(old_event.clone());
     txn.insert_storage_usage_event(recent_event.clone());
+    txn.insert_storage_usage_event(old_io_event.clone());
+    txn.insert_storage_usage_event(recent_io_event.clone());
+    txn.insert_storage_usage_event(old_compute_event.clone());
+    txn.insert_storage_usage_event(recent_compute_event.clone());
     txn.commit().await.unwrap();
 
     // Test with no retention period.
@@ -112,17 +149,23 @@ async fn test_get_and_prune_storage_usage(openable_state: impl OpenableDurableCa
         .get_and_prune_storage_usage(None, boot_ts, false)
         .await
         .unwrap();
-    assert_eq!(events.len(), 2);
+    assert_eq!(events.len(), 6);
     assert!(events.contains(&old_event));
     assert!(events.contains(&recent_event));
+    assert!(events.contains(&old_io_event));
+    assert!(events.contains(&recent_io_event));
+    assert!(events.contains(&old_compute_event));
+    assert!(events.contains(&recent_compute_event));
 
     // Test with some retention period.
     let events = state
         .get_and_prune_storage_usage(Some(Duration::from_millis(10)), boot_ts, false)
         .await
         .unwrap();
-    assert_eq!(events.len(), 1);
-    assert_eq!(events.into_element(), recent_event);
+    assert_eq!(events.len(), 3);
+    assert!(events.contains(&recent_event));
+    assert!(events.contains(&recent_io_event));
+    assert!(events.contains(&recent_compute_event));
     Box::new(state).expire().await;
 }
 
@@ -204,6 +247,23 @@ async fn test_audit_logs(openable_state: impl OpenableDurableCatalogState) {
             user: Some("mike".to_string()),
             occurred_at: 200,
         }),
+        VersionedEvent::V2(EventV2 {
+            id: 300,
+            event_type: EventType::Drop,
+            object_type: mz_audit_log::ObjectType::Table,
+            details: EventDetails::IdNameV1(IdNameV1 {
+                id: "3".to_string(),
+                name: "t".to_string(),
+            }),
+            user: Some("ann".to_string()),
+            context: EventContextV1 {
+                client_ip: Some("10.0.0.1".to_string()),
+                application_name: Some("psql".to_string()),
+                http_request_id: None,
+                sso_subject: Some("3b3b7b62-4a1e-4a4e-9d8a-1f2e3d4c5b6a".to_string()),
+            },
+            occurred_at: 300,
+        }),
     ];
 
     let mut state = Box::new(openable_state)
@@ -179,6 +179,33 @@ impl Client {
         Ok(res.id)
     }
 
+    /// Gets the compatibility level that the schema registry enforces for the
+    /// specified subject.
+    ///
+    /// The compatibility level determines which schemas the schema registry
+    /// will accept as new versions of the subject. It is the subject's own
+    /// compatibility level, if it has one, and the global compatibility level
+    /// otherwise.
+    pub async fn get_subject_compatibility_level(
+        &self,
+        subject: &str,
+    ) -> Result<CompatibilityLevel, GetCompatibilityError> {
+        let req = self.make_request(Method::GET, &["config", subject]);
+        match send_request::<CompatibilityLevelResponse>(req).await {
+            Ok(res) => return Ok(res.compatibility_level),
+            // The subject does not exist or does not have a compatibility
+            // level of its own.
+            Err(UnhandledError::Api {
+                code: 40401 | 40408,
+                ..
+            }) => (),
+            Err(err) => return Err(err.into()),
+        }
+        let req = self.make_request(Method::GET, &["config"]);
+        let res: CompatibilityLevelResponse = send_request(req).await?;
+        Ok(res.compatibility_level)
+    }
+
     /// Lists the names of all subjects that the schema registry is aware of.
     pub async fn list_subjects(&self) -> Result<Vec<String>, ListError> {
         let req = self.make_request(Method::GET, &["subjects"]);
@@ -275,6 +302,43 @@ impl SchemaType {
     }
 }
 
+/// The compatibility level of a subject in a schema registry.
+///
+/// See the [schema registry documentation] for the meaning of each level.
+///
+/// [schema registry documentation]: https://docs.confluent.io/platform/current/schema-registry/fundamentals/schema-evolution.html#compatibility-types
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CompatibilityLevel {
+    Backward,
+    BackwardTransitive,
+    Forward,
+    ForwardTransitive,
+    Full,
+    FullTransitive,
+    None,
+}
+
+impl fmt::Display for CompatibilityLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            CompatibilityLevel::Backward => "BACKWARD",
+            CompatibilityLevel::BackwardTransitive => "BACKWARD_TRANSITIVE",
+            CompatibilityLevel::Forward => "FORWARD",
+            CompatibilityLevel::ForwardTransitive => "FORWARD_TRANSITIVE",
+            CompatibilityLevel::Full => "FULL",
+            CompatibilityLevel::FullTransitive => "FULL_TRANSITIVE",
+            CompatibilityLevel::None => "NONE",
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CompatibilityLevelResponse {
+    compatibility_level: CompatibilityLevel,
+}
+
 /// A schema stored by a schema registry.
 #[derive(Debug, Eq, PartialEq)]
 pub struct Schema {
@@ -503,6 +567,46 @@ impl fmt::Display for PublishError {
     }
 }
 
+/// Errors for compatibility level lookups.
+#[derive(Debug)]
+pub enum GetCompatibilityError {
+    /// The underlying HTTP transport failed.
+    Transport(reqwest::Error),
+    /// An internal server error occurred.
+    Server { code: i32, message: String },
+}
+
+impl From<UnhandledError> for GetCompatibilityError {
+    fn from(err: UnhandledError) -> GetCompatibilityError {
+        match err {
+            UnhandledError::Transport(err) => GetCompatibilityError::Transport(err),
+            UnhandledError::Api { code, message } => {
+                GetCompatibilityError::Server { code, message }
+            }
+        }
+    }
+}
+
+impl Error for GetCompatibilityError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GetCompatibilityError::Server { .. } => None,
+            GetCompatibilityError::Transport(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for GetCompatibilityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GetCompatibilityError::Transport(err) => write!(f, "transport: {}", err),
+            GetCompatibilityError::Server { code, message } => {
+                write!(f, "server error {}: {}", code, message)
+            }
+        }
+    }
+}
+
 /// Errors for list operations.
 #[derive(Debug)]
 pub enum ListError {
@@ -13,7 +13,8 @@ use hyper::server::conn::AddrIncoming;
 use hyper::{service, Body, Response, Server, StatusCode};
 use mz_ccsr::tls::Identity;
 use mz_ccsr::{
-    Client, DeleteError, GetByIdError, GetBySubjectError, PublishError, SchemaReference, SchemaType,
+    Client, CompatibilityLevel, DeleteError, GetByIdError, GetBySubjectError,
+    GetCompatibilityError, PublishError, SchemaReference, SchemaType,
 };
 use once_cell::sync::Lazy;
 
@@ -110,6 +111,24 @@ async fn test_client() -> Result<(), anyhow::Error> {
         .await?;
     assert_eq!(count_schemas(&client, "ccsr-test-").await?, 2);
 
+    {
+        // Subjects without a compatibility level of their own, including
+        // subjects that do not exist, report the global compatibility level,
+        // which defaults to BACKWARD.
+        let subject = "ccsr-test-compat-schema";
+        assert_eq!(
+            client.get_subject_compatibility_level(subject).await?,
+            CompatibilityLevel::Backward
+        );
+        client
+            .publish_schema(subject, schema_v1, SchemaType::Avro, &[])
+            .await?;
+        assert_eq!(
+            client.get_subject_compatibility_level(subject).await?,
+            CompatibilityLevel::Backward
+        );
+    }
+
     {
         let subject_with_slashes = "ccsr/test/schema";
         let schema_test_id = client
@@ -322,6 +341,14 @@ async fn test_server_errors() -> Result<(), anyhow::Error> {
         res => panic!("expected GetBySubjectError::Server, got {:?}", res),
     }
 
+    match client_graceful.get_subject_compatibility_level("foo").await {
+        Err(GetCompatibilityError::Server {
+            code: 50001,
+            ref message,
+        }) if message == "overloaded; try again later" => (),
+        res => panic!("expected GetCompatibilityError::Server, got {:?}", res),
+    }
+
     match client_graceful.delete_subject("foo").await {
         Err(DeleteError::Server {
             code: 50001,
@@ -44,6 +44,8 @@ use mz_compute_types::ComputeInstanceId;
 use mz_expr::RowSetFinishing;
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::tracing::OpenTelemetryContext;
+use mz_persist_client::usage::ShardIoUsage;
+use mz_persist_client::ShardId;
 use mz_repr::{Diff, GlobalId, Row, TimestampManipulation};
 use mz_storage_client::controller::{IntrospectionType, StorageController};
 use mz_storage_types::read_policy::ReadPolicy;
@@ -56,8 +58,8 @@ use uuid::Uuid;
 
 use crate::controller::error::{
     CollectionLookupError, CollectionMissing, CollectionUpdateError, DataflowCreationError,
-    InstanceExists, InstanceMissing, PeekError, ReadPolicyError, ReplicaCreationError,
-    ReplicaDropError, SubscribeTargetError,
+    HydrationCheckError, InstanceExists, InstanceMissing, PeekError, ReadPolicyError,
+    ReplicaCreationError, ReplicaDropError, ReplicaUpdateError, SubscribeTargetError,
 };
 use crate::controller::instance::{ActiveInstance, Instance};
 use crate::controller::replica::ReplicaConfig;
@@ -253,6 +255,22 @@ impl<T: Timestamp> ComputeController<T> {
             .ok_or(CollectionLookupError::CollectionMissing(collection_id))
     }
 
+    /// Returns the bytes replicas of all instances read from and wrote to persist, per shard,
+    /// since the previous call.
+    ///
+    /// Usage reported by replicas of instances that have since been dropped is not included.
+    pub fn take_persist_io_usage(&mut self) -> BTreeMap<ShardId, ShardIoUsage> {
+        let mut usage: BTreeMap<ShardId, ShardIoUsage> = BTreeMap::new();
+        for instance in self.instances.values_mut() {
+            for (shard_id, instance_usage) in instance.take_persist_io_usage() {
+                let entry = usage.entry(shard_id).or_default();
+                entry.bytes_read += instance_usage.bytes_read;
+                entry.bytes_written += instance_usage.bytes_written;
+            }
+        }
+        usage
+    }
+
     /// Acquire an [`ActiveComputeController`] by supplying a storage connection.
     pub fn activate<'a>(
         &'a mut self,
@@ -295,6 +313,29 @@ impl<T: Timestamp> ComputeController<T> {
         self.enable_aggressive_readhold_downgrades = value;
     }
 
+    /// Returns whether all collections installed on the identified replica are hydrated.
+    pub fn replica_hydrated(
+        &self,
+        instance_id: ComputeInstanceId,
+        replica_id: ReplicaId,
+    ) -> Result<bool, HydrationCheckError> {
+        let hydrated = self.instance(instance_id)?.replica_hydrated(replica_id)?;
+        Ok(hydrated)
+    }
+
+    /// Returns whether the identified collection is hydrated on any replica of the identified
+    /// instance.
+    pub fn collection_hydrated(
+        &self,
+        instance_id: ComputeInstanceId,
+        collection_id: GlobalId,
+    ) -> Result<bool, HydrationCheckError> {
+        let hydrated = self
+            .instance(instance_id)?
+            .collection_hydrated(collection_id)?;
+        Ok(hydrated)
+    }
+
     /// Returns the read and write frontiers for each collection.
     pub fn collection_frontiers(&self) -> BTreeMap<GlobalId, (Antichain<T>, Antichain<T>)> {
         let collections = self.instances.values().flat_map(|i| i.collections_iter());
@@ -532,6 +573,33 @@ where
         Ok(())
     }
 
+    /// Updates the logging configuration of a replica.
+    ///
+    /// The replica is reconnected with the new configuration, which makes it restart and
+    /// rehydrate its dataflows.
+    pub fn update_replica_logging(
+        &mut self,
+        instance_id: ComputeInstanceId,
+        replica_id: ReplicaId,
+        logging: ComputeReplicaLogging,
+    ) -> Result<(), ReplicaUpdateError> {
+        let (enable_logging, interval) = match logging.interval {
+            Some(interval) => (true, interval),
+            None => (false, Duration::from_secs(1)),
+        };
+
+        let logging = LoggingConfig {
+            interval,
+            enable_logging,
+            log_logging: logging.log_logging,
+            index_logs: Default::default(),
+        };
+
+        self.instance(instance_id)?
+            .update_replica_logging(replica_id, logging)?;
+        Ok(())
+    }
+
     /// Removes a replica from an instance, including its service in the orchestrator.
     pub fn drop_replica(
         &mut self,
@@ -116,6 +116,61 @@ impl From<instance::ReplicaMissing> for ReplicaDropError {
     }
 }
 
+/// Errors arising during replica configuration updates.
+#[derive(Error, Debug)]
+pub enum ReplicaUpdateError {
+    /// TODO(#25239): Add documentation.
+    #[error("instance does not exist: {0}")]
+    InstanceMissing(ComputeInstanceId),
+    /// TODO(#25239): Add documentation.
+    #[error("replica does not exist: {0}")]
+    ReplicaMissing(ReplicaId),
+}
+
+impl From<InstanceMissing> for ReplicaUpdateError {
+    fn from(error: InstanceMissing) -> Self {
+        Self::InstanceMissing(error.0)
+    }
+}
+
+impl From<instance::ReplicaMissing> for ReplicaUpdateError {
+    fn from(error: instance::ReplicaMissing) -> Self {
+        Self::ReplicaMissing(error.0)
+    }
+}
+
+/// Errors arising during replica hydration checks.
+#[derive(Error, Debug)]
+pub enum HydrationCheckError {
+    /// TODO(#25239): Add documentation.
+    #[error("instance does not exist: {0}")]
+    InstanceMissing(ComputeInstanceId),
+    /// TODO(#25239): Add documentation.
+    #[error("replica does not exist: {0}")]
+    ReplicaMissing(ReplicaId),
+    /// TODO(#25239): Add documentation.
+    #[error("collection does not exist: {0}")]
+    CollectionMissing(GlobalId),
+}
+
+impl From<InstanceMissing> for HydrationCheckError {
+    fn from(error: InstanceMissing) -> Self {
+        Self::InstanceMissing(error.0)
+    }
+}
+
+impl From<instance::ReplicaMissing> for HydrationCheckError {
+    fn from(error: instance::ReplicaMissing) -> Self {
+        Self::ReplicaMissing(error.0)
+    }
+}
+
+impl From<CollectionMissing> for HydrationCheckError {
+    fn from(error: CollectionMissing) -> Self {
+        Self::CollectionMissing(error.0)
+    }
+}
+
 /// Errors arising during dataflow creation.
 #[derive(Error, Debug)]
 pub enum DataflowCreationError {
@@ -28,6 +28,8 @@ use mz_compute_types::sources::SourceInstanceDesc;
 use mz_expr::RowSetFinishing;
 use mz_ore::cast::CastFrom;
 use mz_ore::tracing::OpenTelemetryContext;
+use mz_persist_client::usage::ShardIoUsage;
+use mz_persist_client::ShardId;
 use mz_repr::{Datum, Diff, GlobalId, Row, TimestampManipulation};
 use mz_storage_client::controller::{IntrospectionType, StorageController};
 use mz_storage_types::read_policy::ReadPolicy;
@@ -41,7 +43,7 @@ use crate::controller::replica::{ReplicaClient, ReplicaConfig};
 use crate::controller::{
     CollectionState, ComputeControllerResponse, IntrospectionUpdates, ReplicaId,
 };
-use crate::logging::LogVariant;
+use crate::logging::{LogVariant, LoggingConfig};
 use crate::metrics::{InstanceMetrics, ReplicaMetrics};
 use crate::metrics::{ReplicaCollectionMetrics, UIntGauge};
 use crate::protocol::command::{
@@ -49,8 +51,9 @@ use crate::protocol::command::{
 };
 use crate::protocol::history::ComputeCommandHistory;
 use crate::protocol::response::{
-    ComputeResponse, CopyToResponse, OperatorHydrationStatus, PeekResponse, StatusResponse,
-    SubscribeBatch, SubscribeResponse,
+    CollectionHydrationProgress, ComputeResponse, CopyToResponse, DataflowStatistics,
+    OperatorHydrationStatus, PeekResponse, PersistIoUsage, StatusResponse, SubscribeBatch,
+    SubscribeResponse,
 };
 use crate::service::{ComputeClient, ComputeGrpcClient};
 
@@ -189,6 +192,9 @@ pub(super) struct Instance<T> {
     /// This flag exists to derisk the rollout of the aggressive downgrading approach.
     /// TODO(teskje): Remove this after a couple weeks.
     enable_aggressive_readhold_downgrades: bool,
+    /// The bytes replicas read from and wrote to persist, per shard, since the last call to
+    /// [`Instance::take_persist_io_usage`].
+    persist_io_usage: BTreeMap<ShardId, ShardIoUsage>,
 }
 
 impl<T: Timestamp> Instance<T> {
@@ -353,6 +359,32 @@ impl<T: Timestamp> Instance<T> {
         self.replicas.keys().copied()
     }
 
+    /// Returns whether all collections installed on the identified replica are hydrated.
+    ///
+    /// A replica that has failed and awaits rehydration is never considered hydrated.
+    pub fn replica_hydrated(&self, id: ReplicaId) -> Result<bool, ReplicaMissing> {
+        let replica = self.replicas.get(&id).ok_or(ReplicaMissing(id))?;
+        let hydrated = !replica.failed && replica.collections.values().all(|c| c.hydrated());
+        Ok(hydrated)
+    }
+
+    /// Returns whether the identified collection is hydrated on any replica of this instance.
+    ///
+    /// Replicas that have failed and await rehydration are ignored.
+    pub fn collection_hydrated(&self, collection_id: GlobalId) -> Result<bool, CollectionMissing> {
+        if !self.collections.contains_key(&collection_id) {
+            return Err(CollectionMissing(collection_id));
+        }
+        let hydrated = self.replicas.values().any(|replica| {
+            !replica.failed
+                && replica
+                    .collections
+                    .get(&collection_id)
+                    .map_or(false, |c| c.hydrated())
+        });
+        Ok(hydrated)
+    }
+
     /// Return the IDs of pending peeks targeting the specified replica.
     fn peeks_targeting(
         &self,
@@ -459,6 +491,77 @@ impl<T: Timestamp> Instance<T> {
         }
     }
 
+    /// Returns the persist IO usage reported by replicas since the previous call.
+    pub fn take_persist_io_usage(&mut self) -> BTreeMap<ShardId, ShardIoUsage> {
+        std::mem::take(&mut self.persist_io_usage)
+    }
+
+    /// Accumulate persist IO usage reported by a replica.
+    fn update_persist_io_usage(&mut self, usage: PersistIoUsage) {
+        let entry = self.persist_io_usage.entry(usage.shard_id).or_default();
+        entry.bytes_read += usage.bytes_read;
+        entry.bytes_written += usage.bytes_written;
+    }
+
+    /// Update the tracked hydration progress for a collection according to a received status
+    /// update.
+    fn update_collection_hydration_progress(
+        &mut self,
+        replica_id: ReplicaId,
+        progress: CollectionHydrationProgress,
+    ) {
+        let Some(replica) = self.replicas.get_mut(&replica_id) else {
+            tracing::error!(
+                %replica_id, ?progress,
+                "status update for an unknown replica"
+            );
+            return;
+        };
+        let Some(collection) = replica.collections.get_mut(&progress.collection_id) else {
+            tracing::error!(
+                %replica_id, ?progress,
+                "status update for an unknown collection"
+            );
+            return;
+        };
+
+        collection
+            .hydration_state
+            .input_processed(progress.records, progress.bytes);
+    }
+
+    /// Update the tracked dataflow statistics for a collection according to a received status
+    /// update.
+    fn update_dataflow_statistics(
+        &mut self,
+        replica_id: ReplicaId,
+        statistics: DataflowStatistics,
+    ) {
+        let Some(replica) = self.replicas.get_mut(&replica_id) else {
+            tracing::error!(
+                %replica_id, ?statistics,
+                "status update for an unknown replica"
+            );
+            return;
+        };
+        let id = statistics.collection_id;
+        let Some(collection) = replica.collections.get_mut(&id) else {
+            tracing::error!(
+                %replica_id, ?statistics,
+                "status update for an unknown collection"
+            );
+            return;
+        };
+
+        let updates = collection.add_dataflow_statistics(id, replica_id, &statistics);
+        if !updates.is_empty() {
+            self.deliver_introspection_updates(
+                IntrospectionType::ComputeDataflowStatisticsHistory,
+                updates,
+            );
+        }
+    }
+
     /// Update the tracked hydration status for an operator according to a received status update.
     fn update_operator_hydration_status(
         &mut self,
@@ -572,6 +675,7 @@ where
             replica_epochs: Default::default(),
             metrics,
             enable_aggressive_readhold_downgrades,
+            persist_io_usage: Default::default(),
         };
 
         instance.send(ComputeCommand::CreateTimely {
@@ -859,6 +963,25 @@ where
         Ok(())
     }
 
+    /// Updates the logging configuration of the identified replica.
+    ///
+    /// The replica is rehydrated with the new configuration. Since the configuration differs from
+    /// the one the replica process was started with, the process restarts during reconciliation.
+    pub fn update_replica_logging(
+        &mut self,
+        id: ReplicaId,
+        logging: LoggingConfig,
+    ) -> Result<(), ReplicaMissing> {
+        let replica = self
+            .compute
+            .replicas
+            .get_mut(&id)
+            .ok_or(ReplicaMissing(id))?;
+        replica.config.logging = logging;
+        self.rehydrate_replica(id);
+        Ok(())
+    }
+
     /// Rehydrate the given instance replica.
     ///
     /// # Panics
@@ -888,6 +1011,7 @@ where
     }
 
     /// Create the described dataflows and initializes state for their output.
+    #[mz_ore::instrument(level = "debug")]
     pub fn create_dataflow(
         &mut self,
         dataflow: DataflowDescription<mz_compute_types::plan::Plan<T>, (), T>,
@@ -1083,6 +1207,7 @@ where
             as_of: dataflow.as_of.clone(),
             until: dataflow.until,
             debug_name: dataflow.debug_name,
+            memory_limit: dataflow.memory_limit,
         };
 
         if augmented_dataflow.is_transient() {
@@ -1797,6 +1922,13 @@ where
             StatusResponse::OperatorHydration(status) => self
                 .compute
                 .update_operator_hydration_status(replica_id, status),
+            StatusResponse::CollectionHydration(progress) => self
+                .compute
+                .update_collection_hydration_progress(replica_id, progress),
+            StatusResponse::PersistIoUsage(usage) => self.compute.update_persist_io_usage(usage),
+            StatusResponse::DataflowStatistics(statistics) => self
+                .compute
+                .update_dataflow_statistics(replica_id, statistics),
         }
     }
 }
@@ -1974,6 +2106,7 @@ impl<T: Debug> ReplicaState<T> {
             created_at: Instant::now(),
             as_of,
             hydration_state,
+            statistics: None,
         };
 
         // We need to consider the edge case where the as-of is the empty frontier. Such an as-of
@@ -2015,6 +2148,10 @@ struct ReplicaCollectionState<T> {
     as_of: Antichain<T>,
     /// Tracks hydration state for this collection.
     hydration_state: HydrationState,
+    /// The latest dataflow statistics sample for this collection.
+    ///
+    /// `None` if no worker has reported statistics yet.
+    statistics: Option<DataflowStatisticsSample>,
 }
 
 impl<T> ReplicaCollectionState<T> {
@@ -2032,6 +2169,79 @@ impl<T> ReplicaCollectionState<T> {
 
         self.hydration_state.collection_hydrated();
     }
+
+    /// Adds dataflow statistics reported by a replica worker and returns the resulting updates to
+    /// the dataflow statistics history.
+    ///
+    /// Reports for the current sample are summed up. A report for a later sample starts a new
+    /// sample, while late reports for earlier samples are ignored.
+    fn add_dataflow_statistics(
+        &mut self,
+        collection_id: GlobalId,
+        replica_id: ReplicaId,
+        statistics: &DataflowStatistics,
+    ) -> Vec<(Row, Diff)> {
+        let mut updates = Vec::new();
+        let sample = match &mut self.statistics {
+            Some(sample) if sample.sampled_at == statistics.sampled_at => {
+                updates.push((sample.to_row(collection_id, replica_id), -1));
+                sample.add(statistics);
+                sample
+            }
+            Some(sample) if sample.sampled_at > statistics.sampled_at => return updates,
+            sample => sample.insert(DataflowStatisticsSample::new(statistics)),
+        };
+        updates.push((sample.to_row(collection_id, replica_id), 1));
+        updates
+    }
+}
+
+/// A sample of the dataflow statistics of a collection, summed over the replica workers that have
+/// reported so far.
+///
+/// Samples are recorded in the dataflow statistics history and never retracted, except to be
+/// replaced by a sample that includes the reports of more workers.
+#[derive(Debug)]
+struct DataflowStatisticsSample {
+    /// The time at which the sample was taken, in milliseconds since the Unix epoch.
+    sampled_at: u64,
+    /// The number of records the dataflow read from its sources.
+    records_processed: u64,
+    /// The number of bytes the dataflow's arrangements occupy.
+    arrangement_size: u64,
+    /// The number of errors in the dataflow's exports.
+    error_count: i64,
+}
+
+impl DataflowStatisticsSample {
+    fn new(statistics: &DataflowStatistics) -> Self {
+        Self {
+            sampled_at: statistics.sampled_at,
+            records_processed: statistics.records_processed,
+            arrangement_size: statistics.arrangement_size,
+            error_count: statistics.error_count,
+        }
+    }
+
+    /// Add the statistics reported by another worker.
+    fn add(&mut self, statistics: &DataflowStatistics) {
+        self.records_processed += statistics.records_processed;
+        self.arrangement_size += statistics.arrangement_size;
+        self.error_count += statistics.error_count;
+    }
+
+    /// Return a `Row` reflecting this sample.
+    fn to_row(&self, collection_id: GlobalId, replica_id: ReplicaId) -> Row {
+        let sampled_at = mz_ore::now::to_datetime(self.sampled_at);
+        Row::pack_slice(&[
+            Datum::String(&collection_id.to_string()),
+            Datum::String(&replica_id.to_string()),
+            Datum::TimestampTz(sampled_at.try_into().expect("must fit")),
+            Datum::UInt64(self.records_processed),
+            Datum::UInt64(self.arrangement_size),
+            Datum::Int64(self.error_count),
+        ])
+    }
 }
 
 /// Maintains both global and operator-level hydration introspection for a given replica and
@@ -2044,6 +2254,15 @@ struct HydrationState {
     collection_id: GlobalId,
     /// Whether the collection is hydrated.
     hydrated: bool,
+    /// The wall-clock time at which the collection was installed on the replica.
+    started_at: DateTime<Utc>,
+    /// The wall-clock time at which the collection was observed to be hydrated.
+    hydrated_at: Option<DateTime<Utc>>,
+    /// The number of records and bytes the replica's workers read from the collection's inputs
+    /// during hydration, summed over the workers that have reported so far.
+    ///
+    /// `None` if no worker has reported yet.
+    input_processed: Option<(u64, u64)>,
     /// Operator-level hydration state.
     /// (lir_id, worker_id) -> hydrated
     operators: BTreeMap<(LirId, usize), bool>,
@@ -2062,6 +2281,9 @@ impl HydrationState {
             replica_id,
             collection_id,
             hydrated: false,
+            started_at: Utc::now(),
+            hydrated_at: None,
+            input_processed: None,
             operators: Default::default(),
             introspection_tx,
         };
@@ -2083,6 +2305,20 @@ impl HydrationState {
 
         let retraction = self.row_for_collection();
         self.hydrated = true;
+        self.hydrated_at = Some(Utc::now());
+        let insertion = self.row_for_collection();
+
+        self.send(
+            IntrospectionType::ComputeHydrationStatus,
+            vec![(retraction, -1), (insertion, 1)],
+        );
+    }
+
+    /// Add input processed by a replica worker during hydration.
+    fn input_processed(&mut self, records: u64, bytes: u64) {
+        let retraction = self.row_for_collection();
+        let (old_records, old_bytes) = self.input_processed.unwrap_or((0, 0));
+        self.input_processed = Some((old_records + records, old_bytes + bytes));
         let insertion = self.row_for_collection();
 
         self.send(
@@ -2111,10 +2347,20 @@ impl HydrationState {
 
     /// Return a `Row` reflecting the current collection hydration status.
     fn row_for_collection(&self) -> Row {
+        let timestamp = |t: DateTime<Utc>| Datum::TimestampTz(t.try_into().expect("must fit"));
+        let (records, bytes) = match self.input_processed {
+            Some((records, bytes)) => (Datum::UInt64(records), Datum::UInt64(bytes)),
+            None => (Datum::Null, Datum::Null),
+        };
+
         Row::pack_slice(&[
             Datum::String(&self.collection_id.to_string()),
             Datum::String(&self.replica_id.to_string()),
             Datum::from(self.hydrated),
+            timestamp(self.started_at),
+            self.hydrated_at.map_or(Datum::Null, timestamp),
+            records,
+            bytes,
         ])
     }
 
@@ -13,6 +13,7 @@ use std::num::NonZeroUsize;
 
 use mz_compute_types::plan::LirId;
 use mz_ore::tracing::OpenTelemetryContext;
+use mz_persist_client::ShardId;
 use mz_proto::{any_uuid, IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::{Diff, GlobalId, Row};
 use mz_storage_client::client::ProtoTrace;
@@ -555,6 +556,12 @@ impl Arbitrary for SubscribeBatch<mz_repr::Timestamp> {
 pub enum StatusResponse {
     /// Reports the hydration status of dataflow operators.
     OperatorHydration(OperatorHydrationStatus),
+    /// Reports the input processed by a worker while hydrating a collection.
+    CollectionHydration(CollectionHydrationProgress),
+    /// Reports the bytes a replica process read from and wrote to persist for a shard.
+    PersistIoUsage(PersistIoUsage),
+    /// Reports a sample of the maintenance statistics of a dataflow on a worker.
+    DataflowStatistics(DataflowStatistics),
 }
 
 impl RustType<ProtoStatusResponse> for StatusResponse {
@@ -563,6 +570,9 @@ impl RustType<ProtoStatusResponse> for StatusResponse {
 
         let kind = match self {
             Self::OperatorHydration(status) => Kind::OperatorHydration(status.into_proto()),
+            Self::CollectionHydration(progress) => Kind::CollectionHydration(progress.into_proto()),
+            Self::PersistIoUsage(usage) => Kind::PersistIoUsage(usage.into_proto()),
+            Self::DataflowStatistics(stats) => Kind::DataflowStatistics(stats.into_proto()),
         };
         ProtoStatusResponse { kind: Some(kind) }
     }
@@ -574,6 +584,13 @@ impl RustType<ProtoStatusResponse> for StatusResponse {
             Some(Kind::OperatorHydration(status)) => {
                 Ok(Self::OperatorHydration(status.into_rust()?))
             }
+            Some(Kind::CollectionHydration(progress)) => {
+                Ok(Self::CollectionHydration(progress.into_rust()?))
+            }
+            Some(Kind::PersistIoUsage(usage)) => Ok(Self::PersistIoUsage(usage.into_rust()?)),
+            Some(Kind::DataflowStatistics(stats)) => {
+                Ok(Self::DataflowStatistics(stats.into_rust()?))
+            }
             None => Err(TryFromProtoError::missing_field(
                 "ProtoStatusResponse::kind",
             )),
@@ -616,6 +633,121 @@ impl RustType<ProtoOperatorHydrationStatus> for OperatorHydrationStatus {
     }
 }
 
+/// The input a worker processed while hydrating a collection.
+///
+/// Workers send this update once, when they observe the collection to be hydrated.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct CollectionHydrationProgress {
+    /// The ID of the compute collection exported by the dataflow.
+    pub collection_id: GlobalId,
+    /// The ID of the worker that hydrated the collection.
+    pub worker_id: usize,
+    /// The number of records the worker read from the dataflow's sources during hydration.
+    pub records: u64,
+    /// The number of bytes the worker read from the dataflow's sources during hydration.
+    pub bytes: u64,
+}
+
+impl RustType<ProtoCollectionHydrationProgress> for CollectionHydrationProgress {
+    fn into_proto(&self) -> ProtoCollectionHydrationProgress {
+        ProtoCollectionHydrationProgress {
+            collection_id: Some(self.collection_id.into_proto()),
+            worker_id: self.worker_id.into_proto(),
+            records: self.records,
+            bytes: self.bytes,
+        }
+    }
+
+    fn from_proto(proto: ProtoCollectionHydrationProgress) -> Result<Self, TryFromProtoError> {
+        Ok(Self {
+            collection_id: proto
+                .collection_id
+                .into_rust_if_some("ProtoCollectionHydrationProgress::collection_id")?,
+            worker_id: proto.worker_id.into_rust()?,
+            records: proto.records,
+            bytes: proto.bytes,
+        })
+    }
+}
+
+/// The bytes a replica process read from and wrote to persist for a shard since its previous
+/// report.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct PersistIoUsage {
+    /// The ID of the shard.
+    pub shard_id: ShardId,
+    /// The number of bytes read from blob.
+    pub bytes_read: u64,
+    /// The number of bytes written to blob.
+    pub bytes_written: u64,
+}
+
+impl RustType<ProtoPersistIoUsage> for PersistIoUsage {
+    fn into_proto(&self) -> ProtoPersistIoUsage {
+        ProtoPersistIoUsage {
+            shard_id: self.shard_id.into_proto(),
+            bytes_read: self.bytes_read,
+            bytes_written: self.bytes_written,
+        }
+    }
+
+    fn from_proto(proto: ProtoPersistIoUsage) -> Result<Self, TryFromProtoError> {
+        Ok(Self {
+            shard_id: proto.shard_id.into_rust()?,
+            bytes_read: proto.bytes_read,
+            bytes_written: proto.bytes_written,
+        })
+    }
+}
+
+/// A sample of the maintenance statistics of the dataflow exporting a collection on a worker.
+///
+/// The counters are cumulative since the worker installed the dataflow, so consecutive samples
+/// can be used to compute rates.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct DataflowStatistics {
+    /// The ID of the compute collection exported by the dataflow.
+    pub collection_id: GlobalId,
+    /// The ID of the worker that took the sample.
+    pub worker_id: usize,
+    /// The time at which the sample was taken, in milliseconds since the Unix epoch.
+    ///
+    /// All workers of a replica take their samples at the same times.
+    pub sampled_at: u64,
+    /// The number of records the worker read from the dataflow's sources.
+    pub records_processed: u64,
+    /// The number of bytes the dataflow's arrangements occupy on the worker.
+    pub arrangement_size: u64,
+    /// The number of errors the dataflow's exports contain on the worker.
+    pub error_count: i64,
+}
+
+impl RustType<ProtoDataflowStatistics> for DataflowStatistics {
+    fn into_proto(&self) -> ProtoDataflowStatistics {
+        ProtoDataflowStatistics {
+            collection_id: Some(self.collection_id.into_proto()),
+            worker_id: self.worker_id.into_proto(),
+            sampled_at: self.sampled_at,
+            records_processed: self.records_processed,
+            arrangement_size: self.arrangement_size,
+            error_count: self.error_count,
+        }
+    }
+
+    fn from_proto(proto: ProtoDataflowStatistics) -> Result<Self, TryFromProtoError> {
+        Ok(Self {
+            collection_id: proto
+                .collection_id
+                .into_rust_if_some("ProtoDataflowStatistics::collection_id")?,
+            worker_id: proto.worker_id.into_rust()?,
+            sampled_at: proto.sampled_at,
+            records_processed: proto.records_processed,
+            arrangement_size: proto.arrangement_size,
+            error_count: proto.error_count,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use mz_proto::protobuf_roundtrip;
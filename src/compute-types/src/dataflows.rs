@@ -66,6 +66,13 @@ pub struct DataflowDescription<P, S: 'static = (), T = mz_repr::Timestamp> {
     pub until: Antichain<T>,
     /// Human readable name
     pub debug_name: String,
+    /// The maximum number of bytes the dataflow's arrangements may occupy across all workers of
+    /// a replica, if limited.
+    ///
+    /// Dataflows that exceed their memory limit on a replica are shed: they stop processing input
+    /// data and advancing their frontiers on that replica, instead of taking the whole replica
+    /// down.
+    pub memory_limit: Option<usize>,
 }
 
 impl<T> DataflowDescription<Plan<T>, (), mz_repr::Timestamp> {
@@ -133,6 +140,7 @@ impl<T> DataflowDescription<OptimizedMirRelationExpr, (), T> {
             as_of: Default::default(),
             until: Antichain::new(),
             debug_name: name,
+            memory_limit: None,
         }
     }
 
@@ -440,7 +448,8 @@ impl<P: PartialEq, S: PartialEq, T: timely::PartialOrder> DataflowDescription<P,
             && self.sink_exports == other.sink_exports
             && self.objects_to_build == other.objects_to_build
             && self.index_imports == other.index_imports
-            && self.source_imports == other.source_imports;
+            && self.source_imports == other.source_imports
+            && self.memory_limit == other.memory_limit;
         let partial = if let (Some(as_of), Some(other_as_of)) = (&self.as_of, &other.as_of) {
             timely::PartialOrder::less_equal(as_of, other_as_of)
         } else {
@@ -461,6 +470,7 @@ impl RustType<ProtoDataflowDescription> for DataflowDescription<FlatPlan, Collec
             as_of: self.as_of.into_proto(),
             until: Some(self.until.into_proto()),
             debug_name: self.debug_name.clone(),
+            memory_limit: self.memory_limit.into_proto(),
         }
     }
 
@@ -478,6 +488,7 @@ impl RustType<ProtoDataflowDescription> for DataflowDescription<FlatPlan, Collec
                 .transpose()?
                 .unwrap_or_else(Antichain::new),
             debug_name: proto.debug_name,
+            memory_limit: proto.memory_limit.into_rust()?,
         })
     }
 }
@@ -609,6 +620,7 @@ proptest::prop_compose! {
         as_of_some in any::<bool>(),
         as_of in proptest::collection::vec(any::<mz_repr::Timestamp>(), 1..5),
         debug_name in ".*",
+        memory_limit in any::<Option<usize>>(),
     ) -> DataflowDescription<FlatPlan, CollectionMetadata, mz_repr::Timestamp> {
         DataflowDescription {
             source_imports: BTreeMap::from_iter(source_imports.into_iter()),
@@ -625,6 +637,7 @@ proptest::prop_compose! {
             },
             until: Antichain::new(),
             debug_name,
+            memory_limit,
         }
     }
 }
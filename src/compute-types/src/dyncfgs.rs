@@ -106,6 +106,31 @@ pub const HYDRATION_CONCURRENCY: Config<usize> = Config::new(
     "Controls how many compute dataflows may hydrate concurrently.",
 );
 
+/// The default memory limit of materialized view dataflows that don't specify one.
+pub const DATAFLOW_MEMORY_LIMIT: Config<Option<usize>> = Config::new(
+    "compute_dataflow_memory_limit",
+    None,
+    "The maximum number of bytes the arrangements of a materialized view dataflow may occupy in \
+     a replica before the dataflow is shed, for materialized views without an explicit memory \
+     limit.",
+);
+
+/// The interval at which replicas report the bytes they read from and wrote to persist.
+pub const PERSIST_IO_USAGE_REPORT_INTERVAL: Config<Duration> = Config::new(
+    "compute_persist_io_usage_report_interval",
+    Duration::from_secs(60),
+    "The interval at which replicas report the bytes they read from and wrote to persist, per \
+     shard, to the controller.",
+);
+
+/// The interval at which replicas sample the maintenance statistics of their dataflows.
+pub const DATAFLOW_STATISTICS_INTERVAL: Config<Duration> = Config::new(
+    "compute_dataflow_statistics_interval",
+    Duration::from_secs(5 * 60),
+    "The interval at which replicas sample the maintenance statistics of their dataflows and \
+     report them to the controller.",
+);
+
 /// Adds the full set of all compute `Config`s.
 pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
     configs
@@ -121,4 +146,7 @@ pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
         .add(&LGALLOC_BACKGROUND_INTERVAL)
         .add(&LGALLOC_SLOW_CLEAR_BYTES)
         .add(&HYDRATION_CONCURRENCY)
+        .add(&DATAFLOW_MEMORY_LIMIT)
+        .add(&PERSIST_IO_USAGE_REPORT_INTERVAL)
+        .add(&DATAFLOW_STATISTICS_INTERVAL)
 }
@@ -115,6 +115,7 @@ impl Context {
             as_of: desc.as_of,
             until: desc.until,
             debug_name: desc.debug_name,
+            memory_limit: desc.memory_limit,
         })
     }
 
@@ -6,13 +6,13 @@
 //! Worker-local state for compute timely instances.
 
 use std::any::Any;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::num::NonZeroUsize;
 use std::ops::DerefMut;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 use std::sync::{mpsc, Arc};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use bytesize::ByteSize;
 use differential_dataflow::operators::arrange::TraceAgent;
@@ -24,8 +24,8 @@ use mz_compute_client::protocol::command::{
 };
 use mz_compute_client::protocol::history::ComputeCommandHistory;
 use mz_compute_client::protocol::response::{
-    ComputeResponse, CopyToResponse, OperatorHydrationStatus, PeekResponse, StatusResponse,
-    SubscribeResponse,
+    CollectionHydrationProgress, ComputeResponse, CopyToResponse, DataflowStatistics,
+    OperatorHydrationStatus, PeekResponse, PersistIoUsage, StatusResponse, SubscribeResponse,
 };
 use mz_compute_types::dataflows::DataflowDescription;
 use mz_compute_types::dyncfgs::HYDRATION_CONCURRENCY;
@@ -61,6 +61,7 @@ use crate::arrangement::manager::{SpecializedTraceHandle, TraceBundle, TraceMana
 use crate::logging;
 use crate::logging::compute::ComputeEvent;
 use crate::metrics::ComputeMetrics;
+use crate::render::memory_limit::{DataflowHeapSizes, HeapSizeHandle};
 use crate::render::{LinearJoinSpec, StartSignal};
 use crate::server::{ComputeInstanceContext, ResponseSender};
 
@@ -138,6 +139,12 @@ pub struct ComputeState {
     /// Each entry is a dataflow ID and a token that can be dropped to unsuspend the dataflow.
     /// Entries are `Option`s to enable efficient removal of dropped collections.
     hydration_queue: VecDeque<(usize, Box<dyn Any>)>,
+    /// Arrangement heap sizes of dataflows with a memory limit or maintenance statistics.
+    pub(crate) dataflow_heap_sizes: DataflowHeapSizes,
+    /// The time persist IO usage was last reported to the controller.
+    persist_io_usage_reported_at: Instant,
+    /// The time of the last dataflow statistics sample, in milliseconds since the Unix epoch.
+    dataflow_statistics_sampled_at: u64,
 }
 
 impl ComputeState {
@@ -173,6 +180,9 @@ impl ComputeState {
             hydration_tx,
             hydrating_collections: Default::default(),
             hydration_queue: Default::default(),
+            dataflow_heap_sizes: Default::default(),
+            persist_io_usage_reported_at: Instant::now(),
+            dataflow_statistics_sampled_at: 0,
         }
     }
 
@@ -197,6 +207,16 @@ impl ComputeState {
             .expect("collection must exist")
     }
 
+    /// Return a weak reference to the maintenance statistics of the identified collection, if it
+    /// has any.
+    pub(crate) fn maintenance_statistics(
+        &self,
+        id: GlobalId,
+    ) -> Option<Weak<MaintenanceStatistics>> {
+        let statistics = self.collections.get(&id)?.statistics.as_ref()?;
+        Some(Rc::downgrade(statistics))
+    }
+
     /// Apply the current `worker_config` to the compute state.
     fn apply_worker_config(&mut self) {
         use mz_compute_types::dyncfgs::*;
@@ -252,6 +272,31 @@ impl ComputeState {
         }
     }
 
+    /// Returns the memory limit of the given dataflow.
+    ///
+    /// Dataflows of materialized views that don't specify a memory limit fall back to the default
+    /// memory limit. Other dataflows, e.g. those of indexes, are only limited if they specify a
+    /// limit themselves.
+    pub fn dataflow_memory_limit<P, S>(
+        &self,
+        dataflow: &DataflowDescription<P, S>,
+    ) -> Option<usize> {
+        use mz_compute_types::dyncfgs::DATAFLOW_MEMORY_LIMIT;
+        use mz_compute_types::sinks::ComputeSinkConnection;
+
+        let is_materialized_view = dataflow.index_exports.is_empty()
+            && !dataflow.sink_exports.is_empty()
+            && dataflow
+                .sink_exports
+                .values()
+                .all(|sink| matches!(sink.connection, ComputeSinkConnection::Persist(_)));
+        dataflow.memory_limit.or_else(|| {
+            is_materialized_view
+                .then(|| DATAFLOW_MEMORY_LIMIT.get(&self.worker_config))
+                .flatten()
+        })
+    }
+
     /// Check for completed hydration of dataflows and begin hydrating new ones if capacity is
     /// available.
     pub fn process_sequential_hydration(&mut self) {
@@ -384,12 +429,24 @@ impl<'a, A: Allocate + 'static> ActiveComputeState<'a, A> {
             );
         };
 
+        // Track the input processed during hydration for non-transient dataflows. Transient
+        // dataflows are not reflected in hydration introspection.
+        let hydration_progress =
+            (!dataflow.is_transient()).then(|| Rc::new(HydrationProgress::default()));
+        // Likewise, only non-transient dataflows report maintenance statistics.
+        let statistics = (!dataflow.is_transient()).then(|| {
+            let heap_size = self.compute_state.dataflow_heap_sizes.track(dataflow_index);
+            Rc::new(MaintenanceStatistics::new(heap_size))
+        });
+
         // Initialize compute and logging state for each object.
         for object_id in dataflow.export_ids() {
             let mut collection = CollectionState::new();
 
             collection.as_of = as_of.clone();
             collection.dataflow_id = Some(dataflow_index);
+            collection.hydration_progress = hydration_progress.clone();
+            collection.statistics = statistics.clone();
             collection.reported_frontier = ReportedFrontier::NotReported {
                 lower: as_of.clone(),
             };
@@ -432,6 +489,8 @@ impl<'a, A: Allocate + 'static> ActiveComputeState<'a, A> {
             self.compute_state,
             dataflow,
             start_signal,
+            hydration_progress.as_ref().map(Rc::downgrade),
+            statistics.as_ref().map(Rc::downgrade),
         );
     }
 
@@ -550,7 +609,9 @@ impl<'a, A: Allocate + 'static> ActiveComputeState<'a, A> {
 
     /// Send progress information to the coordinator.
     pub fn report_compute_frontiers(&mut self) {
+        let worker_id = self.timely_worker.index();
         let mut new_uppers = Vec::new();
+        let mut hydration_progress = Vec::new();
 
         // Maintain a single allocation for `new_frontier` to avoid allocating on every iteration.
         let mut new_frontier = Antichain::new();
@@ -603,11 +664,29 @@ impl<'a, A: Allocate + 'static> ActiveComputeState<'a, A> {
 
             new_uppers.push((id, new_frontier.clone()));
             collection.reported_frontier = new_reported_frontier;
+
+            // Report the input processed during hydration once the collection has hydrated. The
+            // compute protocol forbids reporting `Status` about collections that have advanced to
+            // the empty frontier, so we don't report for those.
+            if collection.is_hydrated() && !new_frontier.is_empty() {
+                if let Some(progress) = collection.hydration_progress.take() {
+                    hydration_progress.push(CollectionHydrationProgress {
+                        collection_id: id,
+                        worker_id,
+                        records: progress.records.get(),
+                        bytes: progress.bytes.get(),
+                    });
+                }
+            }
         }
 
         for (id, upper) in new_uppers {
             self.send_compute_response(ComputeResponse::FrontierUpper { id, upper });
         }
+        for progress in hydration_progress {
+            let response = ComputeResponse::Status(StatusResponse::CollectionHydration(progress));
+            self.send_compute_response(response);
+        }
     }
 
     /// Report dropped collections to the controller.
@@ -653,6 +732,78 @@ impl<'a, A: Allocate + 'static> ActiveComputeState<'a, A> {
         }
     }
 
+    /// Report the bytes this process read from and wrote to persist since the previous report.
+    ///
+    /// Persist tracks IO usage per process rather than per worker, and the first worker to take
+    /// the accumulated usage reports it. The usage includes IO performed by the storage layer, as
+    /// the persist client cache is shared between compute and storage.
+    pub fn report_persist_io_usage(&mut self) {
+        use mz_compute_types::dyncfgs::PERSIST_IO_USAGE_REPORT_INTERVAL;
+
+        let interval = PERSIST_IO_USAGE_REPORT_INTERVAL.get(&self.compute_state.worker_config);
+        if self.compute_state.persist_io_usage_reported_at.elapsed() < interval {
+            return;
+        }
+        self.compute_state.persist_io_usage_reported_at = Instant::now();
+
+        let metrics = self.compute_state.persist_clients.metrics();
+        for (shard_id, usage) in metrics.shards.take_io_usage() {
+            let usage = PersistIoUsage {
+                shard_id,
+                bytes_read: usage.bytes_read,
+                bytes_written: usage.bytes_written,
+            };
+            let response = ComputeResponse::Status(StatusResponse::PersistIoUsage(usage));
+            self.send_compute_response(response);
+        }
+    }
+
+    /// Report a sample of the maintenance statistics of all non-transient dataflows.
+    ///
+    /// Samples are taken at multiples of the configured interval since the Unix epoch, so all
+    /// workers of a replica report their samples under the same times.
+    pub fn report_dataflow_statistics(&mut self) {
+        use mz_compute_types::dyncfgs::DATAFLOW_STATISTICS_INTERVAL;
+
+        let interval = DATAFLOW_STATISTICS_INTERVAL.get(&self.compute_state.worker_config);
+        let interval_ms = u64::try_from(interval.as_millis())
+            .unwrap_or(u64::MAX)
+            .max(1);
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |now| u64::try_from(now.as_millis()).unwrap_or(u64::MAX));
+        let sampled_at = now_ms - now_ms % interval_ms;
+        if sampled_at <= self.compute_state.dataflow_statistics_sampled_at {
+            return;
+        }
+        self.compute_state.dataflow_statistics_sampled_at = sampled_at;
+
+        let worker_id = self.timely_worker.index();
+        let mut samples = Vec::new();
+        for (&id, collection) in &self.compute_state.collections {
+            // The compute protocol forbids reporting `Status` about collections that have advanced
+            // to the empty frontier, so we don't report for those.
+            if collection.reported_frontier.is_empty() {
+                continue;
+            }
+            if let Some(statistics) = &collection.statistics {
+                samples.push(DataflowStatistics {
+                    collection_id: id,
+                    worker_id,
+                    sampled_at,
+                    records_processed: statistics.records_processed.get(),
+                    arrangement_size: u64::cast_from(statistics.heap_size.size()),
+                    error_count: statistics.error_count.get(),
+                });
+            }
+        }
+
+        for sample in samples {
+            let response = ComputeResponse::Status(StatusResponse::DataflowStatistics(sample));
+            self.send_compute_response(response);
+        }
+    }
+
     /// Either complete the peek (and send the response) or put it in the pending set.
     fn process_peek(&mut self, upper: &mut Antichain<Timestamp>, mut peek: PendingPeek) {
         let response = match &mut peek {
@@ -1389,6 +1540,14 @@ pub struct CollectionState {
     ///
     /// Only `Some` if the collection is a sink and *not* a subscribe.
     pub sink_write_frontier: Option<Rc<RefCell<Antichain<Timestamp>>>>,
+    /// The input processed by the collection's dataflow during hydration.
+    ///
+    /// Only `Some` if the collection is not transient and has not yet reported its hydration.
+    hydration_progress: Option<Rc<HydrationProgress>>,
+    /// The maintenance statistics of the collection's dataflow.
+    ///
+    /// Only `Some` if the collection is not transient.
+    statistics: Option<Rc<MaintenanceStatistics>>,
 }
 
 impl CollectionState {
@@ -1399,6 +1558,8 @@ impl CollectionState {
             reported_frontier: ReportedFrontier::new(),
             sink_token: None,
             sink_write_frontier: None,
+            hydration_progress: None,
+            statistics: None,
         }
     }
 
@@ -1414,6 +1575,66 @@ impl CollectionState {
     }
 }
 
+/// Counts the input a dataflow processed during hydration.
+///
+/// The rendered dataflow holds a weak reference to this counter, which stops the counting once
+/// all exports of the dataflow have reported their hydration.
+#[derive(Debug, Default)]
+pub struct HydrationProgress {
+    /// The number of records read from the dataflow's sources.
+    records: Cell<u64>,
+    /// The number of bytes read from the dataflow's sources.
+    bytes: Cell<u64>,
+}
+
+impl HydrationProgress {
+    /// Records the given source updates as processed.
+    pub fn record<'a>(&self, rows: impl IntoIterator<Item = &'a Row>) {
+        let (mut records, mut bytes) = (self.records.get(), self.bytes.get());
+        for row in rows {
+            records += 1;
+            bytes += u64::cast_from(row.data().len());
+        }
+        self.records.set(records);
+        self.bytes.set(bytes);
+    }
+}
+
+/// Counts the maintenance work a dataflow performs over its lifetime.
+///
+/// The rendered dataflow holds weak references to these statistics, which stops the counting once
+/// all exports of the dataflow have been dropped.
+#[derive(Debug)]
+pub struct MaintenanceStatistics {
+    /// The number of records read from the dataflow's sources.
+    records_processed: Cell<u64>,
+    /// The number of errors in the dataflow's exports.
+    error_count: Cell<i64>,
+    /// The heap size of the dataflow's arrangements.
+    pub(crate) heap_size: Rc<HeapSizeHandle>,
+}
+
+impl MaintenanceStatistics {
+    pub(crate) fn new(heap_size: HeapSizeHandle) -> Self {
+        Self {
+            records_processed: Default::default(),
+            error_count: Default::default(),
+            heap_size: Rc::new(heap_size),
+        }
+    }
+
+    /// Records the given number of source updates as processed.
+    pub fn record_processed(&self, records: usize) {
+        let records = self.records_processed.get() + u64::cast_from(records);
+        self.records_processed.set(records);
+    }
+
+    /// Records the given change in the number of errors in an export.
+    pub fn record_errors(&self, diff: Diff) {
+        self.error_count.set(self.error_count.get() + diff);
+    }
+}
+
 /// An event reporting the hydration status of an LIR node in a dataflow.
 pub struct HydrationEvent {
     /// The ID of the export this dataflow maintains.
@@ -21,6 +21,7 @@ use timely::dataflow::{Scope, ScopeParent};
 use timely::progress::Timestamp;
 
 use crate::logging::compute::ComputeEvent;
+use crate::render::memory_limit::{HeapSizeEvent, HEAP_SIZE_LOGGER};
 use crate::typedefs::{KeyAgent, KeyValAgent, RowAgent, RowRowAgent, RowValAgent};
 
 /// Extension trait to arrange data.
@@ -240,12 +241,14 @@ where
     L: FnMut(&Tr) -> (usize, usize, usize) + 'static,
 {
     let scope = arranged.stream.scope();
-    let Some(logger) = scope
+    let logger = scope
         .log_register()
-        .get::<ComputeEvent>("materialize/compute")
-    else {
+        .get::<ComputeEvent>("materialize/compute");
+    let heap_size_logger = scope.log_register().get::<HeapSizeEvent>(HEAP_SIZE_LOGGER);
+    if logger.is_none() && heap_size_logger.is_none() {
         return arranged;
-    };
+    }
+    let dataflow_index = scope.addr()[0];
     let operator = arranged.trace.operator().global_id;
     let trace = Rc::downgrade(&arranged.trace.trace_box_unstable());
 
@@ -256,7 +259,9 @@ where
         .unary(Pipeline, "ArrangementSize", |_cap, info| {
             let mut buffer = Default::default();
             let address = info.address;
-            logger.log(ComputeEvent::ArrangementHeapSizeOperator { operator, address });
+            if let Some(logger) = &logger {
+                logger.log(ComputeEvent::ArrangementHeapSizeOperator { operator, address });
+            }
             move |input, output| {
                 while let Some((time, data)) = input.next() {
                     data.swap(&mut buffer);
@@ -270,31 +275,45 @@ where
 
                 let size = size.try_into().expect("must fit");
                 if size != old_size {
-                    logger.log(ComputeEvent::ArrangementHeapSize {
-                        operator,
-                        delta_size: size - old_size,
-                    });
+                    let delta_size = size - old_size;
+                    if let Some(logger) = &logger {
+                        logger.log(ComputeEvent::ArrangementHeapSize {
+                            operator,
+                            delta_size,
+                        });
+                    }
+                    // Memory limits are enforced based on these events, so they must not linger
+                    // in the logger's buffer.
+                    if let Some(logger) = &heap_size_logger {
+                        logger.log(HeapSizeEvent {
+                            dataflow_index,
+                            delta_size,
+                        });
+                        logger.flush();
+                    }
                 }
 
-                let capacity = capacity.try_into().expect("must fit");
-                if capacity != old_capacity {
-                    logger.log(ComputeEvent::ArrangementHeapCapacity {
-                        operator,
-                        delta_capacity: capacity - old_capacity,
-                    });
-                }
+                if let Some(logger) = &logger {
+                    let capacity = capacity.try_into().expect("must fit");
+                    if capacity != old_capacity {
+                        logger.log(ComputeEvent::ArrangementHeapCapacity {
+                            operator,
+                            delta_capacity: capacity - old_capacity,
+                        });
+                    }
+                    old_capacity = capacity;
 
-                let allocations = allocations.try_into().expect("must fit");
-                if allocations != old_allocations {
-                    logger.log(ComputeEvent::ArrangementHeapAllocations {
-                        operator,
-                        delta_allocations: allocations - old_allocations,
-                    });
+                    let allocations = allocations.try_into().expect("must fit");
+                    if allocations != old_allocations {
+                        logger.log(ComputeEvent::ArrangementHeapAllocations {
+                            operator,
+                            delta_allocations: allocations - old_allocations,
+                        });
+                    }
+                    old_allocations = allocations;
                 }
 
                 old_size = size;
-                old_capacity = capacity;
-                old_allocations = allocations;
             }
         });
     Arranged {
@@ -1131,7 +1131,7 @@ where
 /// unique (key, value, time) tuples. We only use it on error streams, which are expected to
 /// contain only a small number of records, so this doesn't matter much. But avoid using it when
 /// batches might become large.
-fn sum_batch_diffs<B>(batch: &B) -> Diff
+pub(crate) fn sum_batch_diffs<B>(batch: &B) -> Diff
 where
     B: BatchReader<Diff = Diff>,
 {
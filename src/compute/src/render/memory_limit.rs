@@ -0,0 +1,187 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Enforcement of dataflow memory limits.
+//!
+//! Dataflows can be rendered with a memory limit, which bounds the heap size of their
+//! arrangements. Each worker tracks the heap size of the arrangements it maintains for such
+//! dataflows and compares it against its share of the limit. Once the share is exceeded, the
+//! dataflow is _shed_ on this replica: its persist source imports stop forwarding data and stop
+//! advancing their frontiers. The dataflow's outputs thus stall at the last time for which they
+//! are complete, and the other dataflows of the replica are unaffected.
+//!
+//! Shedding is a decision local to the replica. It never writes anything into the outputs of the
+//! dataflow, which are shared with other replicas, so replicas with more memory keep maintaining
+//! the outputs correctly. A shed dataflow remains stalled until it is recreated, e.g. when the
+//! replica restarts.
+//!
+//! Heap sizes are reported by the `ArrangementSize` operators, through a timely logger that is
+//! registered independently of the introspection logging, so limits are enforced even on replicas
+//! without introspection.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::{Rc, Weak};
+use std::time::{Duration, Instant};
+
+use mz_ore::collections::CollectionExt;
+use mz_repr::{Diff, Row, Timestamp};
+use timely::communication::Allocate;
+use timely::dataflow::channels::pact::Pipeline;
+use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
+use timely::dataflow::{Scope, Stream};
+use timely::logging::Logger;
+use timely::worker::Worker as TimelyWorker;
+use tracing::warn;
+
+/// The name of the logger receiving [`HeapSizeEvent`]s.
+pub(crate) const HEAP_SIZE_LOGGER: &str = "materialize/compute/heap_size";
+
+/// A change in the heap size of an arrangement.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct HeapSizeEvent {
+    /// The index of the dataflow maintaining the arrangement.
+    pub dataflow_index: usize,
+    /// The change in heap size, in bytes.
+    pub delta_size: isize,
+}
+
+/// The arrangement heap sizes of tracked dataflows, by dataflow index.
+///
+/// Dataflows are tracked if they have a memory limit or report maintenance statistics.
+///
+/// Only dataflows that are currently [tracked](DataflowHeapSizes::track) have an entry. Heap size
+/// changes of other dataflows are ignored.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct DataflowHeapSizes(Rc<RefCell<BTreeMap<usize, isize>>>);
+
+impl DataflowHeapSizes {
+    /// Registers the logger that applies [`HeapSizeEvent`]s to these heap sizes with `worker`.
+    pub fn register_logger<A: Allocate>(&self, worker: &mut TimelyWorker<A>) {
+        let sizes = Rc::clone(&self.0);
+        let logger = Logger::new(
+            Instant::now(),
+            Duration::default(),
+            worker.index(),
+            move |_time, data: &mut Vec<(Duration, usize, HeapSizeEvent)>| {
+                let mut sizes = sizes.borrow_mut();
+                for (_time, _worker, event) in data.drain(..) {
+                    if let Some(size) = sizes.get_mut(&event.dataflow_index) {
+                        *size += event.delta_size;
+                    }
+                }
+            },
+        );
+        worker
+            .log_register()
+            .insert_logger(HEAP_SIZE_LOGGER, logger);
+    }
+
+    /// Starts tracking the heap size of the given dataflow.
+    ///
+    /// Tracking stops when the returned handle is dropped.
+    pub fn track(&self, dataflow_index: usize) -> HeapSizeHandle {
+        self.0.borrow_mut().insert(dataflow_index, 0);
+        HeapSizeHandle {
+            sizes: Rc::clone(&self.0),
+            dataflow_index,
+        }
+    }
+}
+
+/// A handle to the heap size of a tracked dataflow.
+#[derive(Debug)]
+pub(crate) struct HeapSizeHandle {
+    sizes: Rc<RefCell<BTreeMap<usize, isize>>>,
+    dataflow_index: usize,
+}
+
+impl HeapSizeHandle {
+    /// Returns the current heap size of the dataflow's arrangements on this worker, in bytes.
+    pub fn size(&self) -> usize {
+        let size = self.sizes.borrow().get(&self.dataflow_index).copied();
+        size.unwrap_or(0).try_into().unwrap_or(0)
+    }
+}
+
+impl Drop for HeapSizeHandle {
+    fn drop(&mut self) {
+        self.sizes.borrow_mut().remove(&self.dataflow_index);
+    }
+}
+
+/// Sheds the updates of a source import once its dataflow exceeds its memory limit.
+///
+/// Updates pass through unchanged while the dataflow's heap size on this worker is within
+/// `worker_limit`. Once it is exceeded, all subsequent updates are dropped and the output frontier
+/// stops advancing, so that downstream operators never observe incomplete times. Shedding is
+/// permanent for the lifetime of the dataflow, which ends when the source's `token` is dropped.
+pub(crate) fn shed_on_memory_limit<G>(
+    stream: &Stream<G, (Row, Timestamp, Diff)>,
+    name: String,
+    heap_size: Rc<HeapSizeHandle>,
+    limit: usize,
+    worker_limit: usize,
+    token: Weak<dyn Any>,
+) -> Stream<G, (Row, Timestamp, Diff)>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let scope = stream.scope();
+    let worker_index = scope.index();
+    let mut builder = OperatorBuilder::new("ShedOnMemoryLimit".to_string(), scope);
+    let (mut output, output_stream) = builder.new_output();
+    let mut input = builder.new_input(stream, Pipeline);
+
+    builder.build(move |capabilities| {
+        // Holds back the output frontier to the frontier at which the dataflow was shed. Until
+        // then, it follows the input frontier.
+        let mut frontier_cap = Some(capabilities.into_element());
+        let mut shed = false;
+        let mut buffer = Vec::new();
+
+        move |frontiers| {
+            let mut output = output.activate();
+
+            if !shed && heap_size.size() > worker_limit {
+                shed = true;
+                let time = frontier_cap.as_ref().map(|cap| *cap.time());
+                warn!(
+                    "dataflow {name} exceeded its memory limit of {limit} bytes on worker \
+                     {worker_index}, stopping it at {time:?}"
+                );
+            }
+
+            input.for_each(|cap, data| {
+                if !shed {
+                    data.swap(&mut buffer);
+                    output.session(&cap).give_vec(&mut buffer);
+                }
+            });
+
+            if token.upgrade().is_none() {
+                // The dataflow is shutting down, so it no longer matters that its outputs are
+                // incomplete.
+                frontier_cap = None;
+            } else if !shed {
+                match frontiers[0].frontier().as_option() {
+                    Some(time) => {
+                        if let Some(cap) = frontier_cap.as_mut() {
+                            cap.downgrade(time);
+                        }
+                    }
+                    None => frontier_cap = None,
+                }
+            }
+        }
+    });
+
+    output_stream
+}
@@ -131,7 +131,7 @@ use timely::communication::Allocate;
 use timely::container::columnation::Columnation;
 use timely::dataflow::channels::pact::Pipeline;
 use timely::dataflow::operators::to_stream::ToStream;
-use timely::dataflow::operators::{BranchWhen, Operator};
+use timely::dataflow::operators::{BranchWhen, Inspect, Operator};
 use timely::dataflow::scopes::Child;
 use timely::dataflow::{Scope, Stream};
 use timely::order::Product;
@@ -141,10 +141,10 @@ use timely::worker::Worker as TimelyWorker;
 use timely::PartialOrder;
 
 use crate::arrangement::manager::TraceBundle;
-use crate::compute_state::ComputeState;
+use crate::compute_state::{ComputeState, HydrationProgress, MaintenanceStatistics};
 use crate::extensions::arrange::{ArrangementSize, KeyCollection, MzArrange};
 use crate::extensions::reduce::MzReduce;
-use crate::logging::compute::{LogDataflowErrors, LogImportFrontiers};
+use crate::logging::compute::{sum_batch_diffs, LogDataflowErrors, LogImportFrontiers};
 use crate::render::context::{
     ArrangementFlavor, Context, ShutdownToken, SpecializedArrangement, SpecializedArrangementImport,
 };
@@ -154,6 +154,7 @@ pub mod context;
 mod errors;
 mod flat_map;
 mod join;
+pub(crate) mod memory_limit;
 mod reduce;
 pub mod sinks;
 mod threshold;
@@ -167,11 +168,17 @@ pub use join::LinearJoinSpec;
 /// This method imports sources from provided assets, and then builds the remaining
 /// dataflow using "compute-local" assets like shared arrangements, and producing
 /// both arrangements and sinks.
+///
+/// If `hydration_progress` is provided, the updates read from source imports are counted in it
+/// for as long as it is alive. The same holds for `statistics`, which additionally counts the
+/// errors in the dataflow's exports.
 pub fn build_compute_dataflow<A: Allocate>(
     timely_worker: &mut TimelyWorker<A>,
     compute_state: &mut ComputeState,
     dataflow: DataflowDescription<FlatPlan, CollectionMetadata>,
     start_signal: StartSignal,
+    hydration_progress: Option<Weak<HydrationProgress>>,
+    statistics: Option<Weak<MaintenanceStatistics>>,
 ) {
     // Mutually recursive view definitions require special handling.
     let recursive = dataflow
@@ -193,6 +200,21 @@ pub fn build_compute_dataflow<A: Allocate>(
         .map(|(sink_id, sink)| (*sink_id, dataflow.depends_on(sink.from), sink.clone()))
         .collect::<Vec<_>>();
 
+    // Dataflows with a memory limit stop on this replica once their arrangements exceed it. Each
+    // worker enforces an equal share of the limit. Dataflows with maintenance statistics already
+    // track their heap size.
+    let memory_limit = compute_state.dataflow_memory_limit(&dataflow).map(|limit| {
+        let heap_size = match statistics.as_ref().and_then(Weak::upgrade) {
+            Some(statistics) => Rc::clone(&statistics.heap_size),
+            None => {
+                let dataflow_index = timely_worker.next_dataflow_index();
+                Rc::new(compute_state.dataflow_heap_sizes.track(dataflow_index))
+            }
+        };
+        let worker_limit = limit / timely_worker.peers();
+        (limit, worker_limit, heap_size)
+    });
+
     let worker_logging = timely_worker.log_register().get("timely");
 
     let name = format!("Dataflow: {}", &dataflow.debug_name);
@@ -250,6 +272,34 @@ pub fn build_compute_dataflow<A: Allocate>(
                         ok_stream = ok_stream.log_import_frontiers(logger, *source_id, export_ids);
                     }
 
+                    if let Some(progress) = hydration_progress.clone() {
+                        ok_stream = ok_stream.inspect_batch(move |_time, updates| {
+                            if let Some(progress) = progress.upgrade() {
+                                progress.record(updates.iter().map(|(row, _time, _diff)| row));
+                            }
+                        });
+                    }
+                    if let Some(statistics) = statistics.clone() {
+                        ok_stream = ok_stream.inspect_batch(move |_time, updates| {
+                            if let Some(statistics) = statistics.upgrade() {
+                                statistics.record_processed(updates.len());
+                            }
+                        });
+                    }
+
+                    let token: Rc<dyn Any> = Rc::new(token);
+
+                    if let Some((limit, worker_limit, heap_size)) = &memory_limit {
+                        ok_stream = memory_limit::shed_on_memory_limit(
+                            &ok_stream,
+                            dataflow.debug_name.clone(),
+                            Rc::clone(heap_size),
+                            *limit,
+                            *worker_limit,
+                            Rc::downgrade(&token),
+                        );
+                    }
+
                     let (oks, errs) = (
                         ok_stream.as_collection().leave_region().leave_region(),
                         err_stream.as_collection().leave_region().leave_region(),
@@ -258,7 +308,6 @@ pub fn build_compute_dataflow<A: Allocate>(
                     imported_sources.push((mz_expr::Id::Global(*source_id), (oks, errs)));
 
                     // Associate returned tokens with the source identifier.
-                    let token: Rc<dyn Any> = Rc::new(token);
                     tokens.insert(*source_id, token);
                 });
             }
@@ -498,6 +547,15 @@ where
                     errs.stream.log_dataflow_errors(logger, idx_id);
                 }
 
+                // Count the errors in the maintenance statistics of the dataflow.
+                if let Some(statistics) = compute_state.maintenance_statistics(idx_id) {
+                    errs.stream.inspect_batch(move |_time, batches| {
+                        if let Some(statistics) = statistics.upgrade() {
+                            statistics.record_errors(batches.iter().map(sum_batch_diffs).sum());
+                        }
+                    });
+                }
+
                 compute_state.traces.set(
                     idx_id,
                     TraceBundle::new(oks_trace, errs.trace).with_drop(needed_tokens),
@@ -569,6 +627,15 @@ where
                     errs.stream.log_dataflow_errors(logger, idx_id);
                 }
 
+                // Count the errors in the maintenance statistics of the dataflow.
+                if let Some(statistics) = compute_state.maintenance_statistics(idx_id) {
+                    errs.stream.inspect_batch(move |_time, batches| {
+                        if let Some(statistics) = statistics.upgrade() {
+                            statistics.record_errors(batches.iter().map(sum_batch_diffs).sum());
+                        }
+                    });
+                }
+
                 compute_state.traces.set(
                     idx_id,
                     TraceBundle::new(oks_trace, errs.trace).with_drop(needed_tokens),
@@ -23,6 +23,7 @@ use mz_repr::{Diff, GlobalId, Row};
 use mz_storage_types::controller::CollectionMetadata;
 use mz_storage_types::errors::DataflowError;
 use mz_timely_util::operator::CollectionExt;
+use timely::dataflow::operators::Inspect;
 use timely::dataflow::scopes::Child;
 use timely::dataflow::Scope;
 use timely::progress::Antichain;
@@ -87,6 +88,15 @@ where
             err_collection = err_collection.log_dataflow_errors(logger, sink_id);
         }
 
+        // Count the errors in the maintenance statistics of the dataflow.
+        if let Some(statistics) = compute_state.maintenance_statistics(sink_id) {
+            err_collection.inner.inspect_batch(move |_time, updates| {
+                if let Some(statistics) = statistics.upgrade() {
+                    statistics.record_errors(updates.iter().map(|(_err, _time, diff)| diff).sum());
+                }
+            });
+        }
+
         let mut ok_collection = ok_collection.leave();
         let mut err_collection = err_collection.leave();
 
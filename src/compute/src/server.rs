@@ -318,6 +318,7 @@ impl<'w, A: Allocate + 'static> Worker<'w, A> {
                         as_of: dataflow.as_of.clone(),
                         until: dataflow.until.clone(),
                         debug_name: dataflow.debug_name.clone(),
+                        memory_limit: dataflow.memory_limit,
                     })
                     .map(ComputeCommand::CreateDataflow)
                     .collect()
@@ -462,6 +463,8 @@ impl<'w, A: Allocate + 'static> Worker<'w, A> {
                 compute_state.report_compute_frontiers();
                 compute_state.report_dropped_collections();
                 compute_state.report_operator_hydration();
+                compute_state.report_persist_io_usage();
+                compute_state.report_dataflow_statistics();
             }
 
             // Handle any received commands.
@@ -499,13 +502,17 @@ impl<'w, A: Allocate + 'static> Worker<'w, A> {
     fn handle_command(&mut self, response_tx: &mut ResponseSender, cmd: ComputeCommand) {
         match &cmd {
             ComputeCommand::CreateInstance(_) => {
-                self.compute_state = Some(ComputeState::new(
+                let compute_state = ComputeState::new(
                     self.timely_worker.index(),
                     Arc::clone(&self.persist_clients),
                     self.metrics.clone(),
                     Arc::clone(&self.tracing_handle),
                     self.context.clone(),
-                ));
+                );
+                compute_state
+                    .dataflow_heap_sizes
+                    .register_logger(self.timely_worker);
+                self.compute_state = Some(compute_state);
             }
             _ => (),
         }
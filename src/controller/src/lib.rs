@@ -57,7 +57,7 @@ use mz_storage_client::client::{
 use mz_storage_client::controller::StorageController;
 use mz_storage_types::configuration::StorageConfiguration;
 use mz_storage_types::connections::ConnectionContext;
-use mz_storage_types::controller::PersistTxnTablesImpl;
+use mz_storage_types::controller::{PersistTxnTablesImpl, StorageError};
 use timely::order::TotalOrder;
 use timely::progress::{Antichain, Timestamp};
 use tokio::sync::mpsc::{self, UnboundedSender};
@@ -433,16 +433,16 @@ where
             .await;
     }
 
-    /// Produces a timestamp that reflects all data available in
-    /// `source_ids` at the time of the function call.
-    #[allow(unused)]
-    #[allow(clippy::unused_async)]
+    /// Produces a timestamp that reflects all data available in the upstream systems of the
+    /// sources among `ids` at the time the returned future is first polled.
+    ///
+    /// See [`StorageController::real_time_recent_timestamp`].
     pub fn recent_timestamp(
         &self,
-        source_ids: impl Iterator<Item = GlobalId>,
-    ) -> BoxFuture<'static, T> {
-        // Dummy implementation
-        Box::pin(async { T::minimum() })
+        ids: BTreeSet<GlobalId>,
+        timeout: Duration,
+    ) -> BoxFuture<'static, Result<T, StorageError>> {
+        self.storage.real_time_recent_timestamp(ids, timeout)
     }
 }
 
@@ -25,7 +25,7 @@ use clap::{ArgEnum, Parser};
 use fail::FailScenario;
 use http::header::HeaderValue;
 use itertools::Itertools;
-use mz_aws_secrets_controller::AwsSecretsController;
+use mz_aws_secrets_controller::{AwsSecretsController, SECRETS_CONTROLLER_NAMESPACE};
 use mz_build_info::BuildInfo;
 use mz_catalog::config::ClusterReplicaSizeMap;
 use mz_cloud_resources::{AwsExternalIdPrefix, CloudResourceController};
@@ -53,9 +53,11 @@ use mz_persist_client::rpc::{
 };
 use mz_persist_client::PersistLocation;
 use mz_secrets::SecretsController;
-use mz_server_core::TlsCliArgs;
+use mz_server_core::{TlsCliArgs, TlsClientAuthConfig};
 use mz_service::emit_boot_diagnostics;
-use mz_service::secrets::{SecretsControllerKind, SecretsReaderCliArgs};
+use mz_service::secrets::{
+    wrap_external_secrets_reader, SecretsControllerKind, SecretsReaderCliArgs,
+};
 use mz_sql::catalog::EnvironmentId;
 use mz_stash_types::metrics::Metrics as StashMetrics;
 use mz_storage_types::connections::ConnectionContext;
@@ -194,6 +196,35 @@ pub struct Args {
     cors_allowed_origin: Vec<HeaderValue>,
     #[clap(flatten)]
     tls: TlsCliArgs,
+    /// CA certificate file against which to verify TLS client certificates.
+    ///
+    /// If set, PostgreSQL clients connecting to the external SQL port may
+    /// authenticate by presenting a certificate signed by this CA whose
+    /// common name names the user they are connecting as, or is mapped to
+    /// that user by `--tls-client-cert-user-mapping`. When Frontegg
+    /// authentication is enabled, clients must still authenticate with
+    /// Frontegg.
+    #[clap(
+        long,
+        env = "TLS_CLIENT_CA",
+        requires = "tls-cert",
+        value_name = "PATH"
+    )]
+    tls_client_ca: Option<PathBuf>,
+    /// Whether to require PostgreSQL clients to authenticate with a TLS client
+    /// certificate.
+    #[clap(long, env = "TLS_REQUIRE_CLIENT_CERT", requires = "tls-client-ca")]
+    tls_require_client_cert: bool,
+    /// A list of COMMON_NAME=USER pairs that map the common names of TLS client
+    /// certificates to the users they authenticate as.
+    #[clap(
+        long,
+        env = "TLS_CLIENT_CERT_USER_MAPPING",
+        requires = "tls-client-ca",
+        multiple = true,
+        value_delimiter = ';'
+    )]
+    tls_client_cert_user_mapping: Vec<KeyValueArg<String, String>>,
     #[clap(flatten)]
     frontegg: FronteggCliArgs,
 
@@ -515,6 +546,19 @@ pub struct Args {
         required_if_eq("secrets-controller", "aws-secrets-manager")
     )]
     aws_secrets_controller_tags: Vec<KeyValueArg<String, String>>,
+    /// The ARN prefixes of the secrets in AWS Secrets Manager that external secrets may refer
+    /// to. External secrets are not supported if empty.
+    ///
+    /// External secrets are resolved with the credentials of environmentd and clusterd, so the
+    /// prefixes must only cover secrets that all users that can create secrets may read. Secrets
+    /// of the AWS secrets controller are never resolved.
+    #[clap(
+        long,
+        env = "EXTERNAL_SECRETS_AWS_ALLOWED_ARN_PREFIX",
+        multiple = true,
+        use_delimiter = true
+    )]
+    external_secrets_aws_allowed_arn_prefix: Vec<String>,
 
     /// The external host name to connect to the HTTP server of this instance.
     ///
@@ -528,6 +572,15 @@ pub struct Args {
 
     #[clap(long, env = "DEPLOY_GENERATION")]
     deploy_generation: Option<u64>,
+    /// Rehearse an upgrade of the catalog to this version instead of starting
+    /// the server.
+    ///
+    /// Runs the catalog migrations of this version against a savepoint of the
+    /// catalog without committing anything, prints which builtin items the
+    /// upgrade replaces and which user items it replans, and exits. Can be run
+    /// while an `environmentd` of the previous version serves the environment.
+    #[clap(long, env = "DRY_RUN_UPGRADE")]
+    dry_run_upgrade: bool,
 
     // === Tracing options. ===
     #[clap(flatten)]
@@ -543,7 +596,7 @@ enum OrchestratorKind {
 // TODO [Alex Hunt] move this to a shared function that can be imported by the
 // region-controller.
 fn aws_secrets_controller_prefix(env_id: &EnvironmentId) -> String {
-    format!("/user-managed/{}/", env_id)
+    format!("{SECRETS_CONTROLLER_NAMESPACE}{env_id}/")
 }
 fn aws_secrets_controller_key_alias(env_id: &EnvironmentId) -> String {
     // TODO [Alex Hunt] move this to a shared function that can be imported by the
@@ -617,7 +670,18 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
     let _failpoint_scenario = FailScenario::setup();
 
     // Configure connections.
-    let tls = args.tls.into_config()?;
+    let mut tls = args.tls.into_config()?;
+    if let (Some(tls), Some(ca)) = (&mut tls, args.tls_client_ca) {
+        tls.client_auth = Some(TlsClientAuthConfig {
+            ca,
+            required: args.tls_require_client_cert,
+            user_map: args
+                .tls_client_cert_user_mapping
+                .into_iter()
+                .map(|kv| (kv.key, kv.value))
+                .collect(),
+        });
+    }
     let frontegg = Authenticator::from_args(args.frontegg, &metrics_registry)?;
 
     // Configure CORS.
@@ -775,7 +839,10 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
         }
     };
     let cloud_resource_reader = cloud_resource_controller.as_ref().map(|c| c.reader());
-    let secrets_reader = secrets_controller.reader();
+    let secrets_reader = runtime.block_on(wrap_external_secrets_reader(
+        secrets_controller.reader(),
+        args.external_secrets_aws_allowed_arn_prefix.clone(),
+    ));
     let now = SYSTEM_TIME.clone();
 
     let mut persist_config = PersistConfig::new(
@@ -861,6 +928,8 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
             secrets_reader_local_file_dir: args.orchestrator_process_secrets_directory,
             secrets_reader_kubernetes_context: Some(args.orchestrator_kubernetes_context),
             secrets_reader_aws_prefix: Some(aws_secrets_controller_prefix(&args.environment_id)),
+            secrets_reader_external_aws_allowed_arn_prefix: args
+                .external_secrets_aws_allowed_arn_prefix,
         },
     };
 
@@ -872,6 +941,61 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
     emit_boot_diagnostics!(&BUILD_INFO);
     sys::adjust_rlimits();
 
+    let catalog_config = CatalogConfig {
+        persist_clients,
+        metrics: Arc::new(mz_catalog::durable::Metrics::new(&metrics_registry)),
+    };
+    let config = mz_environmentd::Config {
+        tls,
+        frontegg,
+        cors_allowed_origin,
+        catalog_config,
+        timestamp_oracle_url: args.timestamp_oracle_url,
+        controller,
+        secrets_controller,
+        cloud_resource_controller,
+        unsafe_mode: args.unsafe_mode,
+        all_features: args.all_features,
+        metrics_registry,
+        now,
+        environment_id: args.environment_id,
+        cluster_replica_sizes,
+        bootstrap_default_cluster_replica_size: args.bootstrap_default_cluster_replica_size,
+        bootstrap_builtin_cluster_replica_size: args.bootstrap_builtin_cluster_replica_size,
+        system_parameter_defaults: args
+            .system_parameter_default
+            .into_iter()
+            .map(|kv| (kv.key, kv.value))
+            .collect(),
+        availability_zones: args.availability_zone,
+        tracing_handle,
+        storage_usage_collection_interval: args.storage_usage_collection_interval_sec,
+        storage_usage_retention_period: args.storage_usage_retention_period,
+        segment_api_key: args.segment_api_key,
+        egress_ips: args.announce_egress_ip,
+        aws_account_id: args.aws_account_id,
+        aws_privatelink_availability_zones: args.aws_privatelink_availability_zones,
+        launchdarkly_sdk_key: args.launchdarkly_sdk_key,
+        launchdarkly_key_map: args
+            .launchdarkly_key_map
+            .into_iter()
+            .map(|kv| (kv.key, kv.value))
+            .collect(),
+        config_sync_timeout: args.config_sync_timeout,
+        config_sync_loop_interval: args.config_sync_loop_interval,
+        bootstrap_role: args.bootstrap_role,
+        deploy_generation: args.deploy_generation,
+        http_host_name: args.http_host_name,
+        internal_console_redirect_url: args.internal_console_redirect_url,
+        persist_txn_tables_cli: args.persist_txn_tables,
+    };
+
+    if args.dry_run_upgrade {
+        let report = runtime.block_on(mz_environmentd::dry_run_upgrade(config))?;
+        print!("{report}");
+        return Ok(());
+    }
+
     let server = runtime.block_on(async {
         let listeners = Listeners::bind(ListenersConfig {
             sql_listen_addr: args.sql_listen_addr,
@@ -882,56 +1006,7 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
             internal_http_listen_addr: args.internal_http_listen_addr,
         })
         .await?;
-        let catalog_config = CatalogConfig {
-            persist_clients,
-            metrics: Arc::new(mz_catalog::durable::Metrics::new(&metrics_registry)),
-        };
-        listeners
-            .serve(mz_environmentd::Config {
-                tls,
-                frontegg,
-                cors_allowed_origin,
-                catalog_config,
-                timestamp_oracle_url: args.timestamp_oracle_url,
-                controller,
-                secrets_controller,
-                cloud_resource_controller,
-                unsafe_mode: args.unsafe_mode,
-                all_features: args.all_features,
-                metrics_registry,
-                now,
-                environment_id: args.environment_id,
-                cluster_replica_sizes,
-                bootstrap_default_cluster_replica_size: args.bootstrap_default_cluster_replica_size,
-                bootstrap_builtin_cluster_replica_size: args.bootstrap_builtin_cluster_replica_size,
-                system_parameter_defaults: args
-                    .system_parameter_default
-                    .into_iter()
-                    .map(|kv| (kv.key, kv.value))
-                    .collect(),
-                availability_zones: args.availability_zone,
-                tracing_handle,
-                storage_usage_collection_interval: args.storage_usage_collection_interval_sec,
-                storage_usage_retention_period: args.storage_usage_retention_period,
-                segment_api_key: args.segment_api_key,
-                egress_ips: args.announce_egress_ip,
-                aws_account_id: args.aws_account_id,
-                aws_privatelink_availability_zones: args.aws_privatelink_availability_zones,
-                launchdarkly_sdk_key: args.launchdarkly_sdk_key,
-                launchdarkly_key_map: args
-                    .launchdarkly_key_map
-                    .into_iter()
-                    .map(|kv| (kv.key, kv.value))
-                    .collect(),
-                config_sync_timeout: args.config_sync_timeout,
-                config_sync_loop_interval: args.config_sync_loop_interval,
-                bootstrap_role: args.bootstrap_role,
-                deploy_generation: args.deploy_generation,
-                http_host_name: args.http_host_name,
-                internal_console_redirect_url: args.internal_console_redirect_url,
-                persist_txn_tables_cli: args.persist_txn_tables,
-            })
-            .await
+        listeners.serve(config).await
     })?;
 
     metrics.start_time_environmentd.set(
@@ -18,6 +18,7 @@
 
 use std::borrow::Cow;
 use std::collections::BTreeMap;
+use std::net::IpAddr;
 use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
@@ -33,15 +34,16 @@ use futures::future::{FutureExt, Shared, TryFutureExt};
 use headers::authorization::{Authorization, Basic, Bearer};
 use headers::{HeaderMapExt, HeaderName};
 use http::header::{AUTHORIZATION, CONTENT_TYPE};
-use http::{Method, Request, StatusCode};
+use http::{HeaderMap, Method, Request, StatusCode};
 use hyper_openssl::MaybeHttpsStream;
-use mz_adapter::session::{Session, SessionConfig};
+use mz_adapter::session::{AuthenticationMethod, Session, SessionConfig};
 use mz_adapter::{AdapterError, AdapterNotice, Client, SessionClient, WebhookAppenderCache};
 use mz_frontegg_auth::{Authenticator as FronteggAuthentication, Error as FronteggError};
 use mz_http_util::DynamicFilterTarget;
 use mz_ore::cast::u64_to_usize;
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::str::StrExt;
+use mz_ore::tracing::{OpenTelemetryContext, W3C_TRACEPARENT, W3C_TRACESTATE};
 use mz_repr::user::ExternalUserMetadata;
 use mz_server_core::{ConnectionHandler, Server};
 use mz_sql::session::metadata::SessionMetadata;
@@ -66,6 +68,8 @@ use crate::BUILD_INFO;
 
 mod catalog;
 mod console;
+mod diagnostics;
+mod drain;
 mod memory;
 mod metrics;
 mod probe;
@@ -73,8 +77,9 @@ mod root;
 mod sql;
 mod webhook;
 
+pub use drain::DrainResponse;
 pub use metrics::Metrics;
-pub use sql::{SqlResponse, WebSocketAuth, WebSocketResponse};
+pub use sql::{SqlJobs, SqlResponse, WebSocketAuth, WebSocketResponse};
 
 /// Maximum allowed size for a request.
 pub const MAX_REQUEST_SIZE: usize = u64_to_usize(2 * bytesize::MB);
@@ -89,6 +94,7 @@ pub struct HttpConfig {
     pub active_connection_count: Arc<Mutex<ConnectionCounter>>,
     pub concurrent_webhook_req: Arc<tokio::sync::Semaphore>,
     pub metrics: Metrics,
+    pub sql_jobs: SqlJobs,
 }
 
 #[derive(Debug, Clone)]
@@ -133,6 +139,7 @@ impl HttpServer {
             active_connection_count,
             concurrent_webhook_req,
             metrics,
+            sql_jobs,
         }: HttpConfig,
     ) -> HttpServer {
         let tls_mode = tls.as_ref().map(|tls| tls.mode).unwrap_or(TlsMode::Disable);
@@ -145,7 +152,11 @@ impl HttpServer {
         let adapter_client_rx = adapter_client_rx.shared();
         let webhook_cache = WebhookAppenderCache::new();
 
-        let base_router = base_router(BaseRouterConfig { profiling: false })
+        let config = BaseRouterConfig {
+            profiling: false,
+            sql_jobs,
+        };
+        let base_router = base_router(config)
             .layer(middleware::from_fn(move |req, next| {
                 let base_frontegg = Arc::clone(&base_frontegg);
                 async move { http_auth(req, next, tls_mode, base_frontegg.as_ref().as_ref()).await }
@@ -217,7 +228,8 @@ impl Server for HttpServer {
     fn handle_connection(&self, conn: TcpStream) -> ConnectionHandler {
         let router = self.router.clone();
         let tls_context = self.tls_context().cloned();
-        Box::pin(async {
+        let client_ip = conn.peer_addr().ok().map(|addr| ClientIp(addr.ip()));
+        Box::pin(async move {
             let (conn, conn_protocol) = match tls_context {
                 Some(tls_context) => {
                     let mut ssl_stream = SslStream::new(Ssl::new(&tls_context)?, conn)?;
@@ -229,7 +241,10 @@ impl Server for HttpServer {
                 }
                 _ => (MaybeHttpsStream::Http(conn), ConnProtocol::Http),
             };
-            let svc = router.layer(Extension(conn_protocol));
+            let mut svc = router.layer(Extension(conn_protocol));
+            if let Some(client_ip) = client_ip {
+                svc = svc.layer(Extension(client_ip));
+            }
             let http = hyper::server::conn::Http::new();
             http.serve_connection(conn, svc)
                 .with_upgrades()
@@ -246,6 +261,7 @@ pub struct InternalHttpConfig {
     pub promote_leader: oneshot::Sender<()>,
     pub ready_to_promote: oneshot::Receiver<()>,
     pub internal_console_redirect_url: Option<String>,
+    pub sql_jobs: SqlJobs,
 }
 
 pub struct InternalHttpServer {
@@ -380,6 +396,7 @@ impl InternalHttpServer {
             promote_leader,
             ready_to_promote,
             internal_console_redirect_url,
+            sql_jobs,
         }: InternalHttpConfig,
     ) -> InternalHttpServer {
         let metrics = Metrics::register_into(&metrics_registry, "mz_internal_http");
@@ -389,7 +406,11 @@ impl InternalHttpServer {
         ));
 
         let adapter_client_rx = adapter_client_rx.shared();
-        let router = base_router(BaseRouterConfig { profiling: true })
+        let config = BaseRouterConfig {
+            profiling: true,
+            sql_jobs,
+        };
+        let router = base_router(config)
             .route(
                 "/metrics",
                 routing::get(move || async move {
@@ -440,6 +461,15 @@ impl InternalHttpServer {
                 "/api/coordinator/check",
                 routing::get(catalog::handle_coordinator_check),
             )
+            .route(
+                "/api/diagnostics/bundle",
+                routing::get(diagnostics::handle_diagnostics_bundle),
+            )
+            .route("/api/drain", routing::post(drain::handle_drain))
+            .route(
+                "/api/drain/status",
+                routing::get(drain::handle_drain_status),
+            )
             .route(
                 "/internal-console",
                 routing::get(|| async { Redirect::temporary("/internal-console/") }),
@@ -504,6 +534,7 @@ async fn internal_http_auth<B>(mut req: Request<B>, next: Next<B>) -> impl IntoR
     req.extensions_mut().insert(AuthedUser {
         name: user_name,
         external_metadata_rx: None,
+        authentication_method: AuthenticationMethod::Trust,
     });
     Ok(next.run(req).await)
 }
@@ -534,10 +565,40 @@ enum ConnProtocol {
     Https,
 }
 
+/// The IP address of the client of an HTTP connection.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ClientIp(pub IpAddr);
+
+/// Metadata about the HTTP request that started a session.
+#[derive(Debug, Default)]
+pub(crate) struct RequestMetadata {
+    /// The trace context supplied by the client, if any.
+    pub trace_context: Option<OpenTelemetryContext>,
+    /// The IP address of the client, if known.
+    pub client_ip: Option<IpAddr>,
+    /// The ID of the request from the `x-request-id` header, if any.
+    pub request_id: Option<String>,
+}
+
+impl RequestMetadata {
+    pub(crate) fn new(headers: &HeaderMap, client_ip: Option<IpAddr>) -> RequestMetadata {
+        let request_id = headers
+            .get("x-request-id")
+            .and_then(|id| id.to_str().ok())
+            .map(|id| id.to_string());
+        RequestMetadata {
+            trace_context: trace_context_from_headers(headers),
+            client_ip,
+            request_id,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct AuthedUser {
     name: String,
     external_metadata_rx: Option<watch::Receiver<ExternalUserMetadata>>,
+    authentication_method: AuthenticationMethod,
 }
 
 pub struct AuthedClient {
@@ -552,6 +613,7 @@ impl AuthedClient {
         active_connection_count: SharedConnectionCounter,
         session_config: F,
         options: BTreeMap<String, String>,
+        request_metadata: RequestMetadata,
     ) -> Result<Self, AdapterError>
     where
         F: FnOnce(&mut Session),
@@ -561,6 +623,10 @@ impl AuthedClient {
             conn_id,
             user: user.name,
             external_metadata_rx: user.external_metadata_rx,
+            authentication_method: user.authentication_method,
+            trace_context: request_metadata.trace_context,
+            client_ip: request_metadata.client_ip,
+            http_request_id: request_metadata.request_id,
         });
         let drop_connection =
             DropConnection::new_connection(session.user(), active_connection_count)?;
@@ -618,6 +684,7 @@ where
             )
         })?;
         let active_connection_count = req.extensions.get::<SharedConnectionCounter>().unwrap();
+        let client_ip = req.extensions.get::<ClientIp>().map(|ClientIp(ip)| *ip);
 
         let options = if params.options.is_empty() {
             // It's possible 'options' simply wasn't provided, we don't want that to
@@ -646,6 +713,7 @@ where
                     .expect("known to exist")
             },
             options,
+            RequestMetadata::new(&req.headers, client_ip),
         )
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
@@ -739,6 +807,7 @@ async fn init_ws(
         active_connection_count,
     }: &WsState,
     existing_user: Option<AuthedUser>,
+    request_metadata: RequestMetadata,
     ws: &mut WebSocket,
 ) -> Result<AuthedClient, anyhow::Error> {
     // TODO: Add a timeout here to prevent resource leaks by clients that
@@ -817,12 +886,23 @@ async fn init_ws(
         Arc::clone(active_connection_count),
         |_session| (),
         options,
+        request_metadata,
     )
     .await?;
 
     Ok(client)
 }
 
+/// Extracts the W3C trace context supplied by the client in the `traceparent`
+/// and `tracestate` headers, if any.
+fn trace_context_from_headers(headers: &HeaderMap) -> Option<OpenTelemetryContext> {
+    let traceparent = headers.get(W3C_TRACEPARENT)?.to_str().ok()?;
+    let tracestate = headers
+        .get(W3C_TRACESTATE)
+        .and_then(|tracestate| tracestate.to_str().ok());
+    OpenTelemetryContext::from_w3c(traceparent, tracestate)
+}
+
 enum Credentials {
     User(String),
     DefaultUser,
@@ -844,11 +924,15 @@ async fn auth(
     // that is also present.
 
     // Then, handle Frontegg authentication if required.
-    let (name, external_metadata_rx) = match (frontegg, creds) {
+    let (name, external_metadata_rx, authentication_method) = match (frontegg, creds) {
         // If no Frontegg authentication, allow the default user.
-        (None, Credentials::DefaultUser) => (HTTP_DEFAULT_USER.name.to_string(), None),
+        (None, Credentials::DefaultUser) => (
+            HTTP_DEFAULT_USER.name.to_string(),
+            None,
+            AuthenticationMethod::Trust,
+        ),
         // If no Frontegg authentication, allow a protocol-specified user.
-        (None, Credentials::User(name)) => (name, None),
+        (None, Credentials::User(name)) => (name, None, AuthenticationMethod::Trust),
         // With frontegg disabled, specifying credentials is an error.
         (None, _) => return Err(AuthError::UnexpectedCredentials),
         // If we require Frontegg auth, fetch credentials from the HTTP auth
@@ -861,7 +945,7 @@ async fn auth(
                 let auth_session = frontegg.authenticate(&username, &password).await?;
                 let user = auth_session.user().into();
                 let external_metadata_rx = Some(auth_session.external_metadata_rx());
-                (user, external_metadata_rx)
+                (user, external_metadata_rx, AuthenticationMethod::Password)
             }
             Credentials::Token { token } => {
                 let claims = frontegg.validate_access_token(&token, None)?;
@@ -869,7 +953,11 @@ async fn auth(
                     user_id: claims.user_id,
                     admin: claims.is_admin,
                 });
-                (claims.email, Some(external_metadata_rx))
+                (
+                    claims.email,
+                    Some(external_metadata_rx),
+                    AuthenticationMethod::Token,
+                )
             }
             Credentials::DefaultUser | Credentials::User(_) => {
                 return Err(AuthError::MissingHttpAuthentication)
@@ -883,6 +971,7 @@ async fn auth(
     Ok(AuthedUser {
         name,
         external_metadata_rx,
+        authentication_method,
     })
 }
 
@@ -890,11 +979,18 @@ async fn auth(
 struct BaseRouterConfig {
     /// Whether to enable the profiling routes.
     profiling: bool,
+    /// The asynchronous SQL jobs, which are shared by all HTTP servers.
+    sql_jobs: SqlJobs,
 }
 
 /// Returns the router for routes that are shared between the internal and
 /// external HTTP servers.
-fn base_router(BaseRouterConfig { profiling }: BaseRouterConfig) -> Router {
+fn base_router(
+    BaseRouterConfig {
+        profiling,
+        sql_jobs,
+    }: BaseRouterConfig,
+) -> Router {
     // Adding a layer with in this function will only apply to the routes defined in this function.
     // https://docs.rs/axum/0.6.1/axum/routing/struct.Router.html#method.layer
     let mut router = Router::new()
@@ -903,6 +999,14 @@ fn base_router(BaseRouterConfig { profiling }: BaseRouterConfig) -> Router {
             routing::get(move || async move { root::handle_home(profiling).await }),
         )
         .route("/api/sql", routing::post(sql::handle_sql))
+        .route(
+            "/api/experimental/sql/jobs",
+            routing::post(sql::handle_sql_job_submit),
+        )
+        .route(
+            "/api/experimental/sql/jobs/:id",
+            routing::get(sql::handle_sql_job_status),
+        )
         .route("/memory", routing::get(memory::handle_memory))
         .route(
             "/hierarchical-memory",
@@ -914,7 +1018,7 @@ fn base_router(BaseRouterConfig { profiling }: BaseRouterConfig) -> Router {
         router = router.nest("/prof/", mz_prof_http::router(&BUILD_INFO));
     }
 
-    router
+    router.layer(Extension(sql_jobs))
 }
 
 /// Default layers that should be applied to all routes, and should get applied to both the
@@ -0,0 +1,232 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Diagnostic bundle HTTP endpoint.
+//!
+//! The bundle is a gzipped tarball that contains the results of a fixed set of
+//! introspection queries, one JSON file per query, plus a manifest. It is
+//! meant to be attached to support requests, so every query only reads
+//! redacted data: object definitions and statement text have their literals
+//! and secret references removed.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use axum::response::IntoResponse;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use http::{header, StatusCode};
+use mz_sql::session::metadata::SessionMetadata;
+use serde::Serialize;
+
+use crate::http::sql::execute_simple_query;
+use crate::http::AuthedClient;
+use crate::BUILD_INFO;
+
+/// The queries whose results make up a diagnostic bundle, keyed by the name
+/// of the file that holds their results.
+const SECTIONS: &[(&str, &str)] = &[
+    (
+        "catalog/objects",
+        "SELECT id, schema_id, name, type, redacted_create_sql
+        FROM mz_internal.mz_show_create_redacted
+        ORDER BY id",
+    ),
+    (
+        "catalog/names",
+        "SELECT id, name, object_type, schema_name, database_name
+        FROM mz_internal.mz_object_fully_qualified_names
+        ORDER BY id",
+    ),
+    (
+        "catalog/dependencies",
+        "SELECT object_id, referenced_object_id
+        FROM mz_internal.mz_object_dependencies
+        ORDER BY object_id, referenced_object_id",
+    ),
+    (
+        "clusters/clusters",
+        "SELECT id, name, managed, size, replication_factor
+        FROM mz_catalog.mz_clusters
+        ORDER BY id",
+    ),
+    (
+        "clusters/replicas",
+        "SELECT id, name, cluster_id, size, availability_zone
+        FROM mz_catalog.mz_cluster_replicas
+        ORDER BY id",
+    ),
+    (
+        "clusters/replica_statuses",
+        "SELECT * FROM mz_internal.mz_cluster_replica_statuses ORDER BY replica_id, process_id",
+    ),
+    (
+        "clusters/replica_utilization",
+        "SELECT * FROM mz_internal.mz_cluster_replica_utilization ORDER BY replica_id, process_id",
+    ),
+    (
+        "clusters/replica_sizes",
+        "SELECT * FROM mz_catalog.mz_cluster_replica_sizes ORDER BY size",
+    ),
+    (
+        "optimizer/notices",
+        "SELECT * FROM mz_internal.mz_notices_redacted ORDER BY id",
+    ),
+    (
+        "statements/recent_activity",
+        "SELECT * FROM mz_internal.mz_recent_activity_log_redacted
+        ORDER BY began_at DESC
+        LIMIT 1000",
+    ),
+    (
+        "statements/slow_coordinator_messages",
+        "SELECT message_kind, occurred_at, duration, session_id, redacted_sql
+        FROM mz_internal.mz_coordinator_slow_messages
+        ORDER BY occurred_at DESC",
+    ),
+    (
+        "frontiers/frontiers",
+        "SELECT * FROM mz_internal.mz_frontiers ORDER BY object_id",
+    ),
+    (
+        "frontiers/read_holds",
+        "SELECT * FROM mz_internal.mz_read_holds ORDER BY object_id, held_at",
+    ),
+    (
+        "frontiers/materialization_lag",
+        "SELECT * FROM mz_internal.mz_materialization_lag ORDER BY object_id",
+    ),
+    (
+        "status/hydration",
+        "SELECT * FROM mz_internal.mz_hydration_statuses ORDER BY object_id, replica_id",
+    ),
+    (
+        "status/sources",
+        "SELECT * FROM mz_internal.mz_source_statuses ORDER BY id",
+    ),
+    (
+        "status/sinks",
+        "SELECT * FROM mz_internal.mz_sink_statuses ORDER BY id",
+    ),
+];
+
+/// The contents of the `manifest.json` file of a diagnostic bundle.
+#[derive(Serialize)]
+struct Manifest<'a> {
+    version: String,
+    generated_at: u64,
+    user: String,
+    sections: Vec<&'a str>,
+}
+
+pub async fn handle_diagnostics_bundle(mut client: AuthedClient) -> impl IntoResponse {
+    let generated_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("after the epoch")
+        .as_secs();
+
+    // The introspection relations are indexed on the `mz_introspection`
+    // cluster, so querying them there is cheap and does not interfere with
+    // user workloads.
+    if let Err(e) = execute_simple_query(&mut client, "SET cluster = mz_introspection".into()).await
+    {
+        return Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()));
+    }
+
+    let mut files = Vec::with_capacity(SECTIONS.len() + 1);
+    for (name, query) in SECTIONS {
+        // A section that fails to generate, e.g. because a relation is not
+        // available in this version, records its error instead of failing the
+        // whole bundle.
+        let contents = match execute_simple_query(&mut client, query.to_string()).await {
+            Ok(results) => serde_json::to_vec_pretty(&results),
+            Err(e) => serde_json::to_vec_pretty(&serde_json::json!({ "error": e.to_string() })),
+        };
+        files.push((
+            format!("{name}.json"),
+            contents.expect("serializing JSON values cannot fail"),
+        ));
+    }
+    let manifest = Manifest {
+        version: BUILD_INFO.human_version(),
+        generated_at,
+        user: client.client.session().user().name.clone(),
+        sections: SECTIONS.iter().map(|(name, _)| *name).collect(),
+    };
+    files.push((
+        "manifest.json".into(),
+        serde_json::to_vec_pretty(&manifest).expect("serializing manifest cannot fail"),
+    ));
+
+    let bundle = build_tarball(&files, generated_at)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let filename = format!("mz-diagnostics-{generated_at}.tar.gz");
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/gzip".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        bundle,
+    ))
+}
+
+/// Returns the gzipped tarball with the given files, all of which have
+/// modification time `mtime`.
+fn build_tarball(files: &[(String, Vec<u8>)], mtime: u64) -> Result<Vec<u8>, std::io::Error> {
+    let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    for (path, contents) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(u64::try_from(contents.len()).expect("file size fits in a u64"));
+        header.set_mode(0o644);
+        header.set_mtime(mtime);
+        header.set_cksum();
+        builder.append_data(
+            &mut header,
+            format!("mz-diagnostics/{path}"),
+            contents.as_slice(),
+        )?;
+    }
+    builder.into_inner()?.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+
+    use super::build_tarball;
+
+    #[mz_ore::test]
+    fn test_build_tarball() {
+        let files = vec![
+            ("a.json".to_string(), b"[]".to_vec()),
+            ("b/c.json".to_string(), b"{}".to_vec()),
+        ];
+        let bundle = build_tarball(&files, 1).unwrap();
+        let mut archive = tar::Archive::new(GzDecoder::new(bundle.as_slice()));
+        let mut contents = vec![];
+        for entry in archive.entries().unwrap() {
+            let mut entry = entry.unwrap();
+            let path = entry.path().unwrap().display().to_string();
+            let mut data = String::new();
+            entry.read_to_string(&mut data).unwrap();
+            contents.push((path, data));
+        }
+        assert_eq!(
+            contents,
+            vec![
+                ("mz-diagnostics/a.json".to_string(), "[]".to_string()),
+                ("mz-diagnostics/b/c.json".to_string(), "{}".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apach
+
+//! Session drain HTTP endpoints.
+//!
+//! Draining prepares `environmentd` for a handoff to a standby process: new
+//! sessions of external users are rejected, and existing sessions close once
+//! their statements finish. Once the status reports that the process is
+//! drained, the standby can be promoted with `/api/leader/promote`, which
+//! fences this process out of the catalog.
+
+use std::time::Duration;
+
+use axum::extract::Query;
+use axum::response::IntoResponse;
+use axum::{Extension, Json};
+use futures::FutureExt;
+use http::StatusCode;
+use mz_adapter::DrainStatus;
+use serde::{Deserialize, Serialize};
+
+use crate::http::Delayed;
+
+/// The time to wait for running statements to finish if the request does not
+/// specify one.
+const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Query parameters for [`handle_drain`].
+#[derive(Deserialize)]
+pub struct DrainParams {
+    /// The number of seconds to wait for running statements to finish before
+    /// canceling them.
+    timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DrainResponse {
+    pub status: DrainStatus,
+}
+
+/// Starts draining the sessions of `environmentd`.
+///
+/// Draining cannot be stopped. Repeated requests report the progress of the
+/// drain, and can move its deadline earlier.
+pub async fn handle_drain(
+    Extension(client): Extension<Delayed<mz_adapter::Client>>,
+    Query(params): Query<DrainParams>,
+) -> impl IntoResponse {
+    let Some(Ok(client)) = client.now_or_never() else {
+        return Err((StatusCode::SERVICE_UNAVAILABLE, "not ready"));
+    };
+    let timeout = params
+        .timeout_secs
+        .map_or(DEFAULT_DRAIN_TIMEOUT, Duration::from_secs);
+    let status = client.drain(timeout).await;
+    Ok(Json(DrainResponse { status }))
+}
+
+/// Reports the progress of draining the sessions of `environmentd`.
+pub async fn handle_drain_status(
+    Extension(client): Extension<Delayed<mz_adapter::Client>>,
+) -> impl IntoResponse {
+    let Some(Ok(client)) = client.now_or_never() else {
+        return Err((StatusCode::SERVICE_UNAVAILABLE, "not ready"));
+    };
+    let status = client.drain_status().await;
+    Ok(Json(DrainResponse { status }))
+}
@@ -8,19 +8,19 @@
 // by the Apache License, Version 2.0.
 
 use std::borrow::Cow;
-use std::collections::BTreeMap;
-use std::sync::Arc;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use anyhow::anyhow;
 use async_trait::async_trait;
 use axum::extract::ws::{CloseFrame, Message, WebSocket};
-use axum::extract::{State, WebSocketUpgrade};
+use axum::extract::{Path, State, WebSocketUpgrade};
 use axum::response::IntoResponse;
 use axum::{Extension, Json};
 use futures::future::BoxFuture;
 use futures::Future;
-use http::StatusCode;
+use http::{HeaderMap, StatusCode};
 use itertools::izip;
 use mz_adapter::client::RecordFirstRowStream;
 use mz_adapter::session::{EndTransactionAction, TransactionStatus};
@@ -33,9 +33,12 @@ use mz_interchange::encode::TypedDatum;
 use mz_interchange::json::{JsonNumberPolicy, ToJson};
 use mz_ore::cast::CastFrom;
 use mz_ore::result::ResultExt;
-use mz_repr::{Datum, RelationDesc, RowArena};
+use mz_repr::{ColumnName, ColumnType, Datum, RelationDesc, RowArena, ScalarType, Timestamp};
 use mz_sql::ast::display::AstDisplay;
-use mz_sql::ast::{Raw, Statement, StatementKind};
+use mz_sql::ast::{
+    AsOf, Expr, Raw, Statement, StatementKind, SubscribeOption, SubscribeOptionName,
+    SubscribeOutput, SubscribeStatement, Value, WithOptionValue,
+};
 use mz_sql::parse::StatementParseResult;
 use mz_sql::plan::Plan;
 use mz_sql::session::metadata::SessionMetadata;
@@ -43,10 +46,13 @@ use serde::{Deserialize, Serialize};
 use tokio::{select, time};
 use tokio_postgres::error::SqlState;
 use tokio_stream::wrappers::UnboundedReceiverStream;
-use tracing::debug;
+use tracing::{debug, warn, Instrument, Span};
 use tungstenite::protocol::frame::coding::CloseCode;
+use uuid::Uuid;
 
-use crate::http::{init_ws, AuthedClient, AuthedUser, WsState, MAX_REQUEST_SIZE};
+use crate::http::{
+    init_ws, AuthedClient, AuthedUser, ClientIp, RequestMetadata, WsState, MAX_REQUEST_SIZE,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -62,6 +68,10 @@ pub enum Error {
     AbortedTransaction,
     #[error("unsupported via this API: {0}")]
     Unsupported(String),
+    #[error("invalid SUBSCRIBE resume token: {0}")]
+    InvalidResumeToken(String),
+    #[error("{0}")]
+    InvalidSubscribeOptions(String),
     #[error("{0}")]
     Unstructured(anyhow::Error),
 }
@@ -92,6 +102,9 @@ impl Error {
         match self {
             Error::Adapter(err) => err.code(),
             Error::AbortedTransaction => SqlState::IN_FAILED_SQL_TRANSACTION,
+            Error::InvalidResumeToken(_) | Error::InvalidSubscribeOptions(_) => {
+                SqlState::INVALID_PARAMETER_VALUE
+            }
             _ => SqlState::INTERNAL_ERROR,
         }
     }
@@ -106,12 +119,322 @@ pub async fn handle_sql(
     };
     // Don't need to worry about timeouts or resetting cancel here because there is always exactly 1
     // request.
-    match execute_request(&mut client, request, &mut res).await {
+    let span = request_span(&mut client);
+    match execute_request(&mut client, request, &mut res)
+        .instrument(span)
+        .await
+    {
         Ok(()) => Ok(Json(res)),
         Err(e) => Err((StatusCode::BAD_REQUEST, e.to_string())),
     }
 }
 
+/// The maximum number of jobs that can run at the same time. Further jobs are
+/// rejected until one of the running jobs finishes.
+const MAX_RUNNING_SQL_JOBS: usize = 16;
+
+/// The maximum number of finished jobs retained, after which the oldest
+/// finished jobs are forgotten.
+const MAX_FINISHED_SQL_JOBS: usize = 1000;
+
+/// The maximum length of the error message that is recorded in the catalog for
+/// a failed job.
+const MAX_RECORDED_SQL_JOB_ERROR_LEN: usize = 1024;
+
+/// The SQL requests that this process executes asynchronously.
+///
+/// Jobs and their results are tracked in memory, which is where polls are
+/// answered from. The catalog additionally records the status of each job,
+/// without its results, so that the outcome of a job survives a restart of the
+/// server. Jobs that the catalog records as running when the server starts were
+/// interrupted by a restart and are reported as failed.
+#[derive(Clone, Debug, Default)]
+pub struct SqlJobs {
+    inner: Arc<Mutex<SqlJobsInner>>,
+    /// Serializes the writes of job records to the catalog, so that the record
+    /// of a job cannot be written after it was removed.
+    write_lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+#[derive(Debug, Default)]
+struct SqlJobsInner {
+    jobs: BTreeMap<Uuid, SqlJob>,
+    /// The number of running jobs.
+    running: usize,
+    /// Finished jobs, oldest first.
+    finished: VecDeque<Uuid>,
+}
+
+#[derive(Debug)]
+struct SqlJob {
+    /// The user that submitted the job, who is the only one allowed to see it.
+    user: String,
+    /// The time at which the job finished, in milliseconds since the Unix epoch.
+    finished_at: Option<i64>,
+    status: SqlJobStatus,
+}
+
+/// The status of a SQL job, as reported to clients.
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum SqlJobStatus {
+    Running,
+    Succeeded {
+        /// The results of the job, which are not available for jobs that
+        /// finished before the server restarted.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        results: Option<Vec<SqlResult>>,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+/// A SQL job, as recorded in the catalog.
+#[derive(Debug, Serialize, Deserialize)]
+struct SqlJobRecord {
+    user: String,
+    finished_at: Option<i64>,
+    status: SqlJobRecordStatus,
+}
+
+/// The status of a SQL job, as recorded in the catalog. Results are not
+/// recorded and errors are truncated, to bound the size of the record.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum SqlJobRecordStatus {
+    Running,
+    Succeeded,
+    Failed { error: String },
+}
+
+impl SqlJob {
+    fn record(&self) -> String {
+        let status = match &self.status {
+            SqlJobStatus::Running => SqlJobRecordStatus::Running,
+            SqlJobStatus::Succeeded { .. } => SqlJobRecordStatus::Succeeded,
+            SqlJobStatus::Failed { error } => {
+                let mut error = error.clone();
+                if error.len() > MAX_RECORDED_SQL_JOB_ERROR_LEN {
+                    let mut len = MAX_RECORDED_SQL_JOB_ERROR_LEN;
+                    while !error.is_char_boundary(len) {
+                        len -= 1;
+                    }
+                    error.truncate(len);
+                }
+                SqlJobRecordStatus::Failed { error }
+            }
+        };
+        let record = SqlJobRecord {
+            user: self.user.clone(),
+            finished_at: self.finished_at,
+            status,
+        };
+        serde_json::to_string(&record).expect("serializing a SQL job cannot fail")
+    }
+}
+
+impl SqlJobs {
+    /// Loads the jobs that are recorded in the catalog.
+    ///
+    /// Must be called once when the server starts, before any job is
+    /// submitted. Jobs that are recorded as running were interrupted by the
+    /// restart and are recorded as failed.
+    pub async fn recover(&self, adapter_client: &mz_adapter::Client) -> Result<(), AdapterError> {
+        let _guard = self.write_lock.lock().await;
+        let now = adapter_client.now().timestamp_millis();
+        let mut updates = BTreeMap::new();
+        let mut recovered = Vec::new();
+        for (id, value) in adapter_client.get_sql_jobs().await? {
+            let (Ok(uuid), Ok(record)) = (
+                id.parse::<Uuid>(),
+                serde_json::from_str::<SqlJobRecord>(&value),
+            ) else {
+                updates.insert(id, None);
+                continue;
+            };
+            let mut job = SqlJob {
+                user: record.user,
+                finished_at: record.finished_at,
+                status: match record.status {
+                    SqlJobRecordStatus::Running => SqlJobStatus::Running,
+                    SqlJobRecordStatus::Succeeded => SqlJobStatus::Succeeded { results: None },
+                    SqlJobRecordStatus::Failed { error } => SqlJobStatus::Failed { error },
+                },
+            };
+            if matches!(job.status, SqlJobStatus::Running) {
+                job.status = SqlJobStatus::Failed {
+                    error: "job was interrupted by a restart of the server".into(),
+                };
+                job.finished_at = Some(now);
+                updates.insert(id, Some(job.record()));
+            }
+            recovered.push((job.finished_at.unwrap_or(now), uuid, job));
+        }
+        recovered.sort_by_key(|(finished_at, id, _)| (*finished_at, *id));
+
+        let mut inner = self.inner.lock().expect("lock poisoned");
+        for (_, id, job) in recovered {
+            inner.jobs.insert(id, job);
+            inner.finished.push_back(id);
+        }
+        for id in inner.evict() {
+            updates.insert(id.to_string(), None);
+        }
+        drop(inner);
+
+        if !updates.is_empty() {
+            adapter_client.set_sql_jobs(updates).await?;
+        }
+        Ok(())
+    }
+
+    /// Registers a new running job of `user`, or returns `None` if too many
+    /// jobs are running already.
+    fn start(&self, user: String) -> Option<Uuid> {
+        let mut inner = self.inner.lock().expect("lock poisoned");
+        if inner.running >= MAX_RUNNING_SQL_JOBS {
+            return None;
+        }
+        let id = Uuid::new_v4();
+        let job = SqlJob {
+            user,
+            finished_at: None,
+            status: SqlJobStatus::Running,
+        };
+        inner.jobs.insert(id, job);
+        inner.running += 1;
+        Some(id)
+    }
+
+    /// Records the job `id`, which was just started, in the catalog.
+    async fn record_start(
+        &self,
+        adapter_client: &mz_adapter::Client,
+        id: Uuid,
+    ) -> Result<(), AdapterError> {
+        let _guard = self.write_lock.lock().await;
+        let record = {
+            let inner = self.inner.lock().expect("lock poisoned");
+            inner.jobs[&id].record()
+        };
+        let updates = BTreeMap::from([(id.to_string(), Some(record))]);
+        let result = adapter_client.set_sql_jobs(updates).await;
+        if result.is_err() {
+            let mut inner = self.inner.lock().expect("lock poisoned");
+            inner.jobs.remove(&id);
+            inner.running -= 1;
+        }
+        result
+    }
+
+    /// Marks the job `id` as finished with `status`, and records its status in
+    /// the catalog.
+    ///
+    /// The oldest finished jobs beyond [`MAX_FINISHED_SQL_JOBS`] are forgotten.
+    async fn finish(
+        &self,
+        adapter_client: &mz_adapter::Client,
+        id: Uuid,
+        status: SqlJobStatus,
+    ) -> Result<(), AdapterError> {
+        let _guard = self.write_lock.lock().await;
+        let mut updates = BTreeMap::new();
+        {
+            let mut inner = self.inner.lock().expect("lock poisoned");
+            let job = inner.jobs.get_mut(&id).expect("running job must exist");
+            job.status = status;
+            job.finished_at = Some(adapter_client.now().timestamp_millis());
+            updates.insert(id.to_string(), Some(job.record()));
+            inner.running -= 1;
+            inner.finished.push_back(id);
+            for id in inner.evict() {
+                updates.insert(id.to_string(), None);
+            }
+        }
+        adapter_client.set_sql_jobs(updates).await
+    }
+}
+
+impl SqlJobsInner {
+    /// Forgets the oldest finished jobs beyond [`MAX_FINISHED_SQL_JOBS`], and
+    /// returns their IDs.
+    fn evict(&mut self) -> Vec<Uuid> {
+        let excess = self.finished.len().saturating_sub(MAX_FINISHED_SQL_JOBS);
+        let evicted: Vec<_> = self.finished.drain(..excess).collect();
+        for id in &evicted {
+            self.jobs.remove(id);
+        }
+        evicted
+    }
+}
+
+#[derive(Serialize)]
+struct SqlJobSubmitted {
+    job_id: Uuid,
+}
+
+/// Starts executing a SQL request in the background, e.g. DDL that takes a long
+/// time to complete, and returns the ID of the job, which can be used to poll
+/// for its status via [`handle_sql_job_status`].
+///
+/// At most [`MAX_RUNNING_SQL_JOBS`] jobs can run at the same time.
+pub async fn handle_sql_job_submit(
+    Extension(jobs): Extension<SqlJobs>,
+    Extension(user): Extension<AuthedUser>,
+    mut client: AuthedClient,
+    Json(request): Json<SqlRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let Some(job_id) = jobs.start(user.name) else {
+        return Err((
+            StatusCode::TOO_MANY_REQUESTS,
+            format!("too many running jobs; at most {MAX_RUNNING_SQL_JOBS} jobs can run at once"),
+        ));
+    };
+    let adapter_client = client.client.inner().clone();
+    if let Err(e) = jobs.record_start(&adapter_client, job_id).await {
+        return Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()));
+    }
+    mz_ore::task::spawn(|| format!("http_sql_job:{job_id}"), async move {
+        let mut res = SqlResponse {
+            results: Vec::new(),
+        };
+        let span = request_span(&mut client);
+        let status = match execute_request(&mut client, request, &mut res)
+            .instrument(span)
+            .await
+        {
+            Ok(()) => SqlJobStatus::Succeeded {
+                results: Some(res.results),
+            },
+            Err(e) => SqlJobStatus::Failed {
+                error: e.to_string(),
+            },
+        };
+        if let Err(e) = jobs.finish(&adapter_client, job_id, status).await {
+            warn!("failed to record the status of SQL job {job_id}: {e}");
+        }
+    });
+    Ok((StatusCode::ACCEPTED, Json(SqlJobSubmitted { job_id })))
+}
+
+/// Reports the status of a job submitted via [`handle_sql_job_submit`].
+pub async fn handle_sql_job_status(
+    Extension(jobs): Extension<SqlJobs>,
+    Extension(user): Extension<AuthedUser>,
+    Path(job_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let inner = jobs.inner.lock().expect("lock poisoned");
+    match inner.jobs.get(&job_id) {
+        // Don't reveal the existence of jobs submitted by other users.
+        Some(job) if job.user == user.name => match serde_json::to_value(&job.status) {
+            Ok(status) => Ok(Json(status)),
+            Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string())),
+        },
+        _ => Err((StatusCode::NOT_FOUND, format!("unknown job {job_id}"))),
+    }
+}
+
 #[derive(Serialize)]
 struct ErrorResponse {
     error: String,
@@ -120,12 +443,16 @@ struct ErrorResponse {
 pub async fn handle_sql_ws(
     State(state): State<WsState>,
     existing_user: Option<Extension<AuthedUser>>,
+    client_ip: Option<Extension<ClientIp>>,
+    headers: HeaderMap,
     ws: WebSocketUpgrade,
 ) -> impl IntoResponse {
     // An upstream middleware may have already provided the user for us
     let user = existing_user.and_then(|Extension(user)| Some(user));
+    let client_ip = client_ip.map(|Extension(ClientIp(ip))| ip);
+    let request_metadata = RequestMetadata::new(&headers, client_ip);
     ws.max_message_size(MAX_REQUEST_SIZE)
-        .on_upgrade(|ws| async move { run_ws(&state, user, ws).await })
+        .on_upgrade(|ws| async move { run_ws(&state, user, request_metadata, ws).await })
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
@@ -148,8 +475,13 @@ pub enum WebSocketAuth {
     },
 }
 
-async fn run_ws(state: &WsState, user: Option<AuthedUser>, mut ws: WebSocket) {
-    let mut client = match init_ws(state, user, &mut ws).await {
+async fn run_ws(
+    state: &WsState,
+    user: Option<AuthedUser>,
+    request_metadata: RequestMetadata,
+    mut ws: WebSocket,
+) {
+    let mut client = match init_ws(state, user, request_metadata, &mut ws).await {
         Ok(client) => client,
         Err(e) => {
             // We omit most detail from the error message we send to the client, to
@@ -202,6 +534,8 @@ async fn run_ws(state: &WsState, user: Option<AuthedUser>, mut ws: WebSocket) {
     }
 
     loop {
+        let drained = client.client.drained();
+
         // Handle timeouts first so we don't execute any statements when there's a pending timeout.
         let msg = select! {
             biased;
@@ -217,6 +551,15 @@ async fn run_ws(state: &WsState, user: Option<AuthedUser>, mut ws: WebSocket) {
                 let _ = send_ws_response(&mut ws, WebSocketResponse::Error(err.into())).await;
                 return;
             },
+            // `drained` is cancel-safe as per it's docs.
+            () = drained => {
+                client.client.terminate().await;
+                // As with timeouts, we only send the error in response to a request.
+                let _ = ws.recv().await;
+                let err = Error::from(AdapterError::SessionDrained);
+                let _ = send_ws_response(&mut ws, WebSocketResponse::Error(err.into())).await;
+                return;
+            },
             message = ws.recv() => message,
         };
 
@@ -230,7 +573,7 @@ async fn run_ws(state: &WsState, user: Option<AuthedUser>, mut ws: WebSocket) {
             }
         };
 
-        let req: Result<SqlRequest, Error> = match msg {
+        let req: Result<WebSocketRequest, Error> = match msg {
             Message::Text(data) => serde_json::from_str(&data).err_into(),
             Message::Binary(data) => serde_json::from_slice(&data).err_into(),
             // Handled automatically by the server.
@@ -283,12 +626,28 @@ async fn run_ws(state: &WsState, user: Option<AuthedUser>, mut ws: WebSocket) {
 }
 
 async fn run_ws_request(
-    req: Result<SqlRequest, Error>,
+    req: Result<WebSocketRequest, Error>,
     client: &mut AuthedClient,
     ws: &mut WebSocket,
 ) -> Result<(), Error> {
-    let req = req?;
-    execute_request(client, req, ws).await
+    let WebSocketRequest { request, subscribe } = req?;
+    let subscribe = match subscribe {
+        Some(options) if options.structured => Some(options),
+        Some(SubscribeOptions {
+            resume_token: Some(_),
+            ..
+        }) => {
+            return Err(Error::InvalidSubscribeOptions(
+                "resume_token requires structured SUBSCRIBE output".into(),
+            ))
+        }
+        _ => None,
+    };
+    let span = request_span(client);
+    let mut sender = WebSocketSender { ws, subscribe };
+    execute_request(client, request, &mut sender)
+        .instrument(span)
+        .await
 }
 
 /// Sends a single [`WebSocketResponse`] over the provided [`WebSocket`].
@@ -338,6 +697,34 @@ pub enum SqlRequest {
     },
 }
 
+/// A request to execute SQL over a WebSocket.
+///
+/// This is a [`SqlRequest`] plus options that only make sense for a streaming
+/// connection.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WebSocketRequest {
+    /// The SQL to execute.
+    #[serde(flatten)]
+    pub request: SqlRequest,
+    /// Options controlling how `SUBSCRIBE` results are delivered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subscribe: Option<SubscribeOptions>,
+}
+
+/// Options controlling how `SUBSCRIBE` results are delivered over a WebSocket.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct SubscribeOptions {
+    /// Whether to deliver results as `SubscribeSchema`, `SubscribeData`, and
+    /// `SubscribeProgress` messages instead of `Rows` and `Row` messages.
+    #[serde(default)]
+    pub structured: bool,
+    /// A token from a previously received `SubscribeProgress` message. If
+    /// present, the `SUBSCRIBE` skips its snapshot and emits only the updates
+    /// that the previous `SUBSCRIBE` had not yet delivered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resume_token: Option<String>,
+}
+
 /// An request to execute a SQL query using the extended protocol.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ExtendedRequest {
@@ -346,6 +733,11 @@ pub struct ExtendedRequest {
     /// Optional parameters for the query.
     #[serde(default)]
     params: Vec<Option<String>>,
+    /// Optional type OIDs for the parameters, in the same order as `params`.
+    /// Parameters whose type is not specified, or is specified as `0`, have
+    /// their type inferred from the query.
+    #[serde(default)]
+    types: Vec<u32>,
 }
 
 /// The response to a `SqlRequest`.
@@ -479,6 +871,9 @@ pub enum WebSocketResponse {
     Error(SqlError),
     ParameterStatus(ParameterStatus),
     BackendKeyData(BackendKeyData),
+    SubscribeSchema(Description),
+    SubscribeData(SubscribeData),
+    SubscribeProgress(SubscribeProgress),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -504,8 +899,13 @@ pub struct Description {
 
 impl From<&RelationDesc> for Description {
     fn from(desc: &RelationDesc) -> Self {
-        let columns = desc
-            .iter()
+        Description::from_columns(desc.iter())
+    }
+}
+
+impl Description {
+    fn from_columns<'a>(columns: impl Iterator<Item = (&'a ColumnName, &'a ColumnType)>) -> Self {
+        let columns = columns
             .map(|(name, typ)| {
                 let pg_type = mz_pgrepr::Type::from(&typ.scalar_type);
                 Column {
@@ -546,6 +946,29 @@ pub struct CommandStarting {
     is_streaming: bool,
 }
 
+/// A single update produced by a structured `SUBSCRIBE`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubscribeData {
+    /// The logical timestamp of the update.
+    pub timestamp: String,
+    /// The number of copies of `row` inserted (if positive) or deleted (if
+    /// negative).
+    pub diff: i64,
+    /// The updated row, whose columns are described by the `SubscribeSchema`
+    /// message.
+    pub row: Vec<serde_json::Value>,
+}
+
+/// Announces that a structured `SUBSCRIBE` has delivered all updates with
+/// timestamps less than `timestamp`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubscribeProgress {
+    pub timestamp: String,
+    /// An opaque token that can be passed back in the `resume_token` option to
+    /// resume the `SUBSCRIBE` from this point.
+    pub resume_token: String,
+}
+
 /// Trait describing how to transmit a response to a client. HTTP clients
 /// accumulate into a Vec and send all at once. WebSocket clients send each
 /// message as they occur.
@@ -570,6 +993,9 @@ trait ResultSender: Send {
     fn connection_error(&mut self) -> BoxFuture<Error>;
     /// Reports whether the client supports streaming SUBSCRIBE results.
     fn allow_subscribe(&self) -> bool;
+    /// Returns the options for delivering SUBSCRIBE results as structured
+    /// messages, if the client requested them.
+    fn subscribe_options(&self) -> Option<&SubscribeOptions>;
 
     async fn await_rows<F, R>(&mut self, f: F) -> Result<R, Error>
     where
@@ -632,10 +1058,21 @@ impl ResultSender for SqlResponse {
     fn allow_subscribe(&self) -> bool {
         false
     }
+
+    fn subscribe_options(&self) -> Option<&SubscribeOptions> {
+        None
+    }
+}
+
+/// Sends the results of a single [`WebSocketRequest`] over a [`WebSocket`].
+struct WebSocketSender<'a> {
+    ws: &'a mut WebSocket,
+    /// Present if SUBSCRIBE results should be delivered as structured messages.
+    subscribe: Option<SubscribeOptions>,
 }
 
 #[async_trait]
-impl ResultSender for WebSocket {
+impl ResultSender for WebSocketSender<'_> {
     // The first component of the return value is
     // Err if sending to the client
     // produced an error and the server should disconnect. It is Ok(Err) if the statement
@@ -662,7 +1099,7 @@ impl ResultSender for WebSocket {
             StatementResult::Subscribe { .. } => (true, true),
         };
         if let Err(e) = send(
-            self,
+            self.ws,
             WebSocketResponse::CommandStarting(CommandStarting {
                 has_rows,
                 is_streaming,
@@ -711,7 +1148,18 @@ impl ResultSender for WebSocket {
                 mut rx,
                 ctx_extra,
             } => {
-                if let Err(e) = send(self, WebSocketResponse::Rows(desc.into())).await {
+                // Structured output strips the `mz_timestamp`, `mz_progressed`,
+                // and `mz_diff` columns from the description; they are instead
+                // reported as fields of each `SubscribeData` message.
+                let structured = self.subscribe.is_some();
+                let schema = if structured {
+                    WebSocketResponse::SubscribeSchema(Description::from_columns(
+                        desc.iter().skip(STRUCTURED_SUBSCRIBE_METADATA_COLUMNS),
+                    ))
+                } else {
+                    WebSocketResponse::Rows(desc.into())
+                };
+                if let Err(e) = send(self.ws, schema).await {
                     // We consider the remote breaking the connection to be a cancellation,
                     // matching the behavior for pgwire
                     return (
@@ -722,6 +1170,7 @@ impl ResultSender for WebSocket {
 
                 let mut datum_vec = mz_repr::DatumVec::new();
                 let mut rows_returned = 0;
+                let mut seen_first_row = false;
                 loop {
                     let res = match self.await_rows(rx.recv()).await {
                         Ok(res) => res,
@@ -740,8 +1189,18 @@ impl ResultSender for WebSocket {
                             for row in rows {
                                 let datums = datum_vec.borrow_with(&row);
                                 let types = &desc.typ().column_types;
-                                if let Err(e) = send(
-                                    self,
+                                let msg = if structured {
+                                    // A progress message that precedes all data
+                                    // only announces the `AS OF` timestamp,
+                                    // before the snapshot has been delivered, so
+                                    // it is not a safe point to resume from.
+                                    let is_first_row = !seen_first_row;
+                                    seen_first_row = true;
+                                    if is_first_row && datums[1].unwrap_bool() {
+                                        continue;
+                                    }
+                                    structured_subscribe_message(&datums, types)
+                                } else {
                                     WebSocketResponse::Row(
                                         datums
                                             .iter()
@@ -751,10 +1210,9 @@ impl ResultSender for WebSocket {
                                                     .json(&JsonNumberPolicy::ConvertNumberToString)
                                             })
                                             .collect(),
-                                    ),
-                                )
-                                .await
-                                {
+                                    )
+                                };
+                                if let Err(e) = send(self.ws, msg).await {
                                     // We consider the remote breaking the connection to be a cancellation,
                                     // matching the behavior for pgwire
                                     return (
@@ -780,6 +1238,23 @@ impl ResultSender for WebSocket {
                                 Some((StatementEndedExecutionReason::Canceled, ctx_extra)),
                             )
                         }
+                        Some(PeekResponseUnary::TimedOut) => {
+                            let err = AdapterError::StatementTimeout;
+                            let error = err.to_string();
+                            break (
+                                true,
+                                vec![WebSocketResponse::Error(err.into())],
+                                Some((StatementEndedExecutionReason::Errored { error }, ctx_extra)),
+                            );
+                        }
+                        Some(PeekResponseUnary::ResultSizeExceeded(error)) => {
+                            let err = AdapterError::ResultSize(error.clone());
+                            break (
+                                true,
+                                vec![WebSocketResponse::Error(err.into())],
+                                Some((StatementEndedExecutionReason::Errored { error }, ctx_extra)),
+                            );
+                        }
                         None => {
                             break (
                                 false,
@@ -800,7 +1275,7 @@ impl ResultSender for WebSocket {
             }
         };
         for msg in msgs {
-            if let Err(e) = send(self, msg).await {
+            if let Err(e) = send(self.ws, msg).await {
                 return (
                     Err(e),
                     stmt_logging.map(|(_old_reason, ctx_extra)| {
@@ -820,7 +1295,7 @@ impl ResultSender for WebSocket {
             tick.tick().await;
             loop {
                 tick.tick().await;
-                if let Err(err) = self.send(Message::Ping(Vec::new())).await {
+                if let Err(err) = self.ws.send(Message::Ping(Vec::new())).await {
                     return err.into();
                 }
             }
@@ -830,6 +1305,89 @@ impl ResultSender for WebSocket {
     fn allow_subscribe(&self) -> bool {
         true
     }
+
+    fn subscribe_options(&self) -> Option<&SubscribeOptions> {
+        self.subscribe.as_ref()
+    }
+}
+
+/// The number of metadata columns (`mz_timestamp`, `mz_progressed`, and
+/// `mz_diff`) that precede the data columns in structured `SUBSCRIBE` output.
+const STRUCTURED_SUBSCRIBE_METADATA_COLUMNS: usize = 3;
+
+/// Converts a row of `SUBSCRIBE ... WITH (PROGRESS)` output into a
+/// `SubscribeData` or `SubscribeProgress` message.
+fn structured_subscribe_message(datums: &[Datum], types: &[ColumnType]) -> WebSocketResponse {
+    let timestamp = Timestamp::try_from(datums[0].unwrap_numeric().0)
+        .expect("mz_timestamp is a valid timestamp")
+        .to_string();
+    if datums[1].unwrap_bool() {
+        WebSocketResponse::SubscribeProgress(SubscribeProgress {
+            resume_token: timestamp.clone(),
+            timestamp,
+        })
+    } else {
+        let row = datums
+            .iter()
+            .zip(types)
+            .skip(STRUCTURED_SUBSCRIBE_METADATA_COLUMNS)
+            .map(|(d, typ)| TypedDatum::new(*d, typ).json(&JsonNumberPolicy::ConvertNumberToString))
+            .collect();
+        WebSocketResponse::SubscribeData(SubscribeData {
+            timestamp,
+            diff: datums[2].unwrap_int64(),
+            row,
+        })
+    }
+}
+
+/// Rewrites a `SUBSCRIBE` statement so that its output can be delivered as
+/// structured messages, resuming from `options.resume_token` if present.
+fn apply_subscribe_options(
+    stmt: &mut SubscribeStatement<Raw>,
+    options: &SubscribeOptions,
+) -> Result<(), Error> {
+    if !matches!(
+        stmt.output,
+        SubscribeOutput::Diffs | SubscribeOutput::WithinTimestampOrderBy { .. }
+    ) {
+        return Err(Error::InvalidSubscribeOptions(
+            "structured SUBSCRIBE output does not support ENVELOPE UPSERT or ENVELOPE DEBEZIUM"
+                .into(),
+        ));
+    }
+
+    fn set_option(stmt: &mut SubscribeStatement<Raw>, name: SubscribeOptionName, value: bool) {
+        stmt.options.retain(|o| o.name != name);
+        stmt.options.push(SubscribeOption {
+            name,
+            value: Some(WithOptionValue::Value(Value::Boolean(value))),
+        });
+    }
+
+    // Resume tokens are derived from progress messages.
+    set_option(stmt, SubscribeOptionName::Progress, true);
+
+    if let Some(token) = &options.resume_token {
+        if stmt.as_of.is_some() {
+            return Err(Error::InvalidSubscribeOptions(
+                "resume_token cannot be combined with AS OF".into(),
+            ));
+        }
+        // The token is the frontier of a previous `SUBSCRIBE`: every update at
+        // a time less than it has already been delivered. A `SUBSCRIBE` without
+        // a snapshot emits only updates at times strictly greater than its
+        // `AS OF`, so we start immediately before the frontier.
+        let as_of = token
+            .parse::<Timestamp>()
+            .ok()
+            .and_then(|frontier| frontier.step_back())
+            .ok_or_else(|| Error::InvalidResumeToken(token.clone()))?;
+        stmt.as_of = Some(AsOf::At(Expr::Value(Value::Number(as_of.to_string()))));
+        set_option(stmt, SubscribeOptionName::Snapshot, false);
+    }
+
+    Ok(())
 }
 
 async fn send_and_retire<S: ResultSender>(
@@ -848,10 +1406,15 @@ async fn send_and_retire<S: ResultSender>(
 async fn execute_stmt_group<S: ResultSender>(
     client: &mut SessionClient,
     sender: &mut S,
-    stmt_group: Vec<(Statement<Raw>, String, Vec<Option<String>>)>,
+    stmt_group: Vec<(
+        Statement<Raw>,
+        String,
+        Vec<Option<String>>,
+        Vec<Option<ScalarType>>,
+    )>,
 ) -> Result<Result<(), ()>, Error> {
     let num_stmts = stmt_group.len();
-    for (stmt, sql, params) in stmt_group {
+    for (stmt, sql, params, param_types) in stmt_group {
         assert!(num_stmts <= 1 || params.is_empty(),
             "statement groups contain more than 1 statement iff Simple request, which does not support parameters"
         );
@@ -870,7 +1433,7 @@ async fn execute_stmt_group<S: ResultSender>(
             let _ = send_and_retire(err.into(), client, sender).await?;
             return Ok(Err(()));
         }
-        let res = execute_stmt(client, sender, stmt, sql, params).await?;
+        let res = execute_stmt(client, sender, stmt, sql, params, param_types).await?;
         let is_err = send_and_retire(res, client, sender).await?;
 
         if is_err.is_err() {
@@ -899,6 +1462,34 @@ async fn execute_stmt_group<S: ResultSender>(
     Ok(Ok(()))
 }
 
+/// Returns the root span under which a [`SqlRequest`] is executed.
+///
+/// If the client supplied a trace context, the span is made a child of the
+/// client's span.
+fn request_span(client: &mut AuthedClient) -> Span {
+    let mut span = tracing::info_span!(parent: None, "execute_request");
+    span.follows_from(Span::current());
+    if let Some(trace_context) = client.client.session().trace_context() {
+        trace_context.attach_as_parent_to(&mut span);
+    }
+    span
+}
+
+/// Executes `query`, which may contain multiple statements, and returns the result of each
+/// statement.
+///
+/// Used by endpoints that run SQL on behalf of the user without exposing the SQL API.
+pub(crate) async fn execute_simple_query(
+    client: &mut AuthedClient,
+    query: String,
+) -> Result<Vec<SqlResult>, Error> {
+    let mut res = SqlResponse {
+        results: Vec::new(),
+    };
+    execute_request(client, SqlRequest::Simple { query }, &mut res).await?;
+    Ok(res.results)
+}
+
 /// Executes an entire [`SqlRequest`].
 ///
 /// See the user-facing documentation about the HTTP API for a description of
@@ -945,6 +1536,20 @@ async fn execute_request<S: ResultSender>(
         Ok(())
     }
 
+    // Mirrors the handling of parameter type OIDs in the pgwire Parse message.
+    fn resolve_param_types(oids: Vec<u32>) -> Result<Vec<Option<ScalarType>>, Error> {
+        oids.into_iter()
+            .map(|oid| match mz_pgrepr::Type::from_oid(oid) {
+                Ok(ty) => match ScalarType::try_from(&ty) {
+                    Ok(ty) => Ok(Some(ty)),
+                    Err(err) => Err(Error::Unstructured(anyhow!(err))),
+                },
+                Err(_) if oid == 0 => Ok(None),
+                Err(err) => Err(Error::Unstructured(anyhow!(err))),
+            })
+            .collect()
+    }
+
     fn parse<'a>(
         client: &SessionClient,
         query: &'a str,
@@ -955,6 +1560,24 @@ async fn execute_request<S: ResultSender>(
         result.map_err(|e| AdapterError::from(e).into())
     }
 
+    // Rewrites SUBSCRIBE statements if the client asked for structured
+    // output. The SQL text is regenerated so that it matches the statement
+    // that is actually executed.
+    fn rewrite_subscribe<S: ResultSender>(
+        sender: &S,
+        mut stmt: Statement<Raw>,
+        sql: &str,
+    ) -> Result<(Statement<Raw>, String), Error> {
+        match (&mut stmt, sender.subscribe_options()) {
+            (Statement::Subscribe(subscribe), Some(options)) => {
+                apply_subscribe_options(subscribe, options)?;
+                let sql = stmt.to_ast_string_stable();
+                Ok((stmt, sql))
+            }
+            _ => Ok((stmt, sql.to_string())),
+        }
+    }
+
     let mut stmt_groups = vec![];
 
     match request {
@@ -963,12 +1586,19 @@ async fn execute_request<S: ResultSender>(
             let mut stmt_group = Vec::with_capacity(stmts.len());
             for StatementParseResult { ast: stmt, sql } in stmts {
                 check_prohibited_stmts(sender, &stmt)?;
-                stmt_group.push((stmt, sql.to_string(), vec![]));
+                let (stmt, sql) = rewrite_subscribe(sender, stmt, sql)?;
+                stmt_group.push((stmt, sql, vec![], vec![]));
             }
             stmt_groups.push(stmt_group);
         }
         SqlRequest::Extended { queries } => {
-            for ExtendedRequest { query, params } in queries {
+            for ExtendedRequest {
+                query,
+                params,
+                types,
+            } in queries
+            {
+                let param_types = resolve_param_types(types)?;
                 let mut stmts = parse(client, &query)?;
                 if stmts.len() != 1 {
                     return Err(Error::Unstructured(anyhow!(
@@ -980,8 +1610,9 @@ async fn execute_request<S: ResultSender>(
 
                 let StatementParseResult { ast: stmt, sql } = stmts.pop().unwrap();
                 check_prohibited_stmts(sender, &stmt)?;
+                let (stmt, sql) = rewrite_subscribe(sender, stmt, sql)?;
 
-                stmt_groups.push(vec![(stmt, sql.to_string(), params)]);
+                stmt_groups.push(vec![(stmt, sql, params, param_types)]);
             }
         }
     }
@@ -1012,10 +1643,11 @@ async fn execute_stmt<S: ResultSender>(
     stmt: Statement<Raw>,
     sql: String,
     raw_params: Vec<Option<String>>,
+    param_types: Vec<Option<ScalarType>>,
 ) -> Result<StatementResult, Error> {
     const EMPTY_PORTAL: &str = "";
     if let Err(e) = client
-        .prepare(EMPTY_PORTAL.into(), Some(stmt.clone()), sql, vec![])
+        .prepare(EMPTY_PORTAL.into(), Some(stmt.clone()), sql, param_types)
         .await
     {
         return Ok(SqlResult::err(client, e).into());
@@ -1123,9 +1755,12 @@ async fn execute_stmt<S: ResultSender>(
         | ExecuteResponse::CreatedViews { .. }
         | ExecuteResponse::CreatedMaterializedView { .. }
         | ExecuteResponse::CreatedType
+        | ExecuteResponse::CreatedFunction
+        | ExecuteResponse::CreatedContinualTask
         | ExecuteResponse::Comment
         | ExecuteResponse::Deleted(_)
         | ExecuteResponse::DiscardedTemp
+        | ExecuteResponse::DiscardedPlans
         | ExecuteResponse::DiscardedAll
         | ExecuteResponse::DroppedObject(_)
         | ExecuteResponse::DroppedOwned
@@ -1143,6 +1778,7 @@ async fn execute_stmt<S: ResultSender>(
         | ExecuteResponse::Updated(_)
         | ExecuteResponse::AlteredObject(_)
         | ExecuteResponse::AlteredIndexLogicalCompaction
+        | ExecuteResponse::AlteredQuota
         | ExecuteResponse::AlteredRole
         | ExecuteResponse::AlteredSystemConfiguration
         | ExecuteResponse::Deallocate { .. }
@@ -1210,6 +1846,12 @@ async fn execute_stmt<S: ResultSender>(
                 PeekResponseUnary::Canceled => {
                     return Ok(SqlResult::err(client, AdapterError::Canceled).into());
                 }
+                PeekResponseUnary::TimedOut => {
+                    return Ok(SqlResult::err(client, AdapterError::StatementTimeout).into());
+                }
+                PeekResponseUnary::ResultSizeExceeded(e) => {
+                    return Ok(SqlResult::err(client, AdapterError::ResultSize(e)).into());
+                }
             };
             let mut sql_rows: Vec<Vec<serde_json::Value>> = vec![];
             let mut datum_vec = mz_repr::DatumVec::new();
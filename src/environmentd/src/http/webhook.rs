@@ -12,7 +12,9 @@
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
-use mz_adapter::{AppendWebhookError, AppendWebhookResponse, WebhookAppenderCache};
+use mz_adapter::{
+    AppendWebhookError, AppendWebhookResponse, DeduplicationOutcome, WebhookAppenderCache,
+};
 use mz_ore::cast::CastFrom;
 use mz_ore::retry::{Retry, RetryResult};
 use mz_ore::str::StrExt;
@@ -103,6 +105,7 @@ async fn append_webhook(
         body_format,
         header_tys,
         validator,
+        deduplicator,
     } = async {
         let mut guard = webhook_cache.entries.lock().await;
 
@@ -147,12 +150,27 @@ async fn append_webhook(
         }
     }
 
+    // If this source deduplicates requests, then only append the first delivery of a request.
+    // Validation happens first so that unauthenticated requests can't claim idempotency keys.
+    let reservation = match deduplicator.map(|d| d.reserve(headers)).transpose()? {
+        Some(DeduplicationOutcome::Duplicate) => {
+            tracing::debug!("skipping duplicate webhook request");
+            return Ok(());
+        }
+        Some(DeduplicationOutcome::Reserved(reservation)) => Some(reservation),
+        Some(DeduplicationOutcome::Unkeyed) | None => None,
+    };
+
     // Pack our body and headers into a Row.
     let rows = pack_rows(body, &body_format, headers, &header_tys)?;
 
     // Send the row to get appended.
     tx.append(rows).await?;
 
+    if let Some(reservation) = reservation {
+        reservation.commit();
+    }
+
     Ok(())
 }
 
@@ -322,6 +340,8 @@ pub enum WebhookError {
     InvalidBody { ty: ScalarType, msg: String },
     #[error("failed to validate the request")]
     ValidationFailed,
+    #[error("a request with the same idempotency key is already being processed")]
+    DuplicateInFlight,
     #[error("error occurred while running validation")]
     ValidationError,
     #[error("service unavailable")]
@@ -351,6 +371,7 @@ impl From<AppendWebhookError> for WebhookError {
                 name,
             } => WebhookError::NotFound(format!("'{database}.{schema}.{name}'")),
             AppendWebhookError::ValidationFailed => WebhookError::ValidationFailed,
+            AppendWebhookError::DuplicateInFlight => WebhookError::DuplicateInFlight,
             AppendWebhookError::ChannelClosed => {
                 WebhookError::Internal(anyhow::anyhow!("channel closed"))
             }
@@ -383,6 +404,9 @@ impl IntoResponse for WebhookError {
             e @ WebhookError::InvalidHeaders(_) => {
                 (StatusCode::UNAUTHORIZED, e.to_string()).into_response()
             }
+            e @ WebhookError::DuplicateInFlight => {
+                (StatusCode::CONFLICT, e.to_string()).into_response()
+            }
             e @ WebhookError::Unavailable => {
                 (StatusCode::SERVICE_UNAVAILABLE, e.to_string()).into_response()
             }
@@ -470,6 +494,10 @@ mod tests {
         ))
         .into_response();
         assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        // Concurrent deliveries of the same request should be retried by the client.
+        let resp = WebhookError::from(AppendWebhookError::DuplicateInFlight).into_response();
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
     }
 
     #[mz_ore::test]
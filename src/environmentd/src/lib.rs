@@ -23,11 +23,12 @@ use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use anyhow::{anyhow, bail, Context};
+use mz_adapter::catalog::{BuiltinMigrationReport, Catalog};
 use mz_adapter::config::{system_parameter_sync, SystemParameterSyncConfig};
 use mz_adapter::load_remote_system_parameters;
 use mz_adapter::webhook::WebhookConcurrencyLimiter;
 use mz_build_info::{build_info, BuildInfo};
-use mz_catalog::config::ClusterReplicaSizeMap;
+use mz_catalog::config::{AwsPrincipalContext, ClusterReplicaSizeMap, StateConfig};
 use mz_catalog::durable::{BootstrapArgs, CatalogError, OpenableDurableCatalogState};
 use mz_cloud_resources::CloudResourceController;
 use mz_controller::ControllerConfig;
@@ -47,9 +48,9 @@ use mz_storage_types::controller::PersistTxnTablesImpl;
 use tokio::sync::oneshot;
 use tokio::sync::oneshot::error::RecvError;
 use tower_http::cors::AllowOrigin;
-use tracing::{info, info_span, Instrument};
+use tracing::{info, info_span, warn, Instrument};
 
-use crate::http::{HttpConfig, HttpServer, InternalHttpConfig, InternalHttpServer};
+use crate::http::{HttpConfig, HttpServer, InternalHttpConfig, InternalHttpServer, SqlJobs};
 
 pub mod http;
 mod telemetry;
@@ -263,19 +264,33 @@ impl Listeners {
         } = self;
 
         // Validate TLS configuration, if present.
-        let (pgwire_tls, http_tls) = match &config.tls {
-            None => (None, None),
+        let (pgwire_tls, internal_pgwire_tls, http_tls) = match &config.tls {
+            None => (None, None, None),
             Some(tls_config) => {
                 let context = tls_config.load_context()?;
+                // Only the external SQL server supports client certificate
+                // authentication, so only it asks clients for certificates.
                 let pgwire_tls = mz_server_core::TlsConfig {
-                    context: context.clone(),
+                    context: tls_config.load_client_auth_context()?,
                     mode: mz_server_core::TlsMode::Require,
+                    client_auth: tls_config.client_auth.clone(),
+                };
+                // Allow, but do not require, TLS connections on the internal
+                // port. Some users of the internal SQL server do not support
+                // TLS, while others require it, so we allow both.
+                //
+                // TODO(benesch): migrate all internal applications to TLS and
+                // remove `TlsMode::Allow`.
+                let internal_pgwire_tls = mz_server_core::TlsConfig {
+                    context: context.clone(),
+                    mode: mz_server_core::TlsMode::Allow,
+                    client_auth: None,
                 };
                 let http_tls = http::TlsConfig {
                     context,
                     mode: http::TlsMode::Require,
                 };
-                (Some(pgwire_tls), Some(http_tls))
+                (Some(pgwire_tls), Some(internal_pgwire_tls), Some(http_tls))
             }
         };
 
@@ -291,6 +306,8 @@ impl Listeners {
         // endpoints require the adapter to be initialized; requests to those
         // endpoints block until the adapter client is installed.
         let (internal_http_adapter_client_tx, internal_http_adapter_client_rx) = oneshot::channel();
+        // Asynchronous SQL jobs can be submitted to and polled from any HTTP server.
+        let sql_jobs = SqlJobs::default();
         task::spawn(|| "internal_http_server", {
             let internal_http_server = InternalHttpServer::new(InternalHttpConfig {
                 metrics_registry: config.metrics_registry.clone(),
@@ -299,6 +316,7 @@ impl Listeners {
                 promote_leader: promote_leader_tx,
                 ready_to_promote: ready_to_promote_rx,
                 internal_console_redirect_url: config.internal_console_redirect_url,
+                sql_jobs: sql_jobs.clone(),
             });
             mz_server_core::serve(internal_http_conns, internal_http_server, None)
         });
@@ -321,19 +339,7 @@ impl Listeners {
             .await?,
         );
 
-        // Initialize the system parameter frontend if `launchdarkly_sdk_key` is set.
-        let system_parameter_sync_config = if let Some(ld_sdk_key) = config.launchdarkly_sdk_key {
-            Some(SystemParameterSyncConfig::new(
-                config.environment_id.clone(),
-                &BUILD_INFO,
-                &config.metrics_registry,
-                config.now.clone(),
-                ld_sdk_key,
-                config.launchdarkly_key_map,
-            ))
-        } else {
-            None
-        };
+        let system_parameter_sync_config = system_parameter_sync_config(&config);
         let remote_system_parameters = load_remote_system_parameters(
             &mut openable_adapter_storage,
             system_parameter_sync_config.clone(),
@@ -540,6 +546,12 @@ impl Listeners {
         .instrument(info_span!(parent: None, "adapter::serve"))
         .await?;
 
+        // Load the asynchronous SQL jobs that were recorded before the restart,
+        // before any HTTP server can accept jobs.
+        if let Err(e) = sql_jobs.recover(&adapter_client).await {
+            warn!("failed to recover asynchronous SQL jobs: {e}");
+        }
+
         // Install an adapter client in the internal HTTP server.
         internal_http_adapter_client_tx
             .send(adapter_client.clone())
@@ -550,7 +562,7 @@ impl Listeners {
         task::spawn(|| "sql_server", {
             let sql_server = mz_pgwire::Server::new(mz_pgwire::Config {
                 label: "external_pgwire",
-                tls: pgwire_tls.clone(),
+                tls: pgwire_tls,
                 adapter_client: adapter_client.clone(),
                 frontegg: config.frontegg.clone(),
                 metrics: metrics.clone(),
@@ -564,16 +576,7 @@ impl Listeners {
         task::spawn(|| "internal_sql_server", {
             let internal_sql_server = mz_pgwire::Server::new(mz_pgwire::Config {
                 label: "internal_pgwire",
-                tls: pgwire_tls.map(|mut pgwire_tls| {
-                    // Allow, but do not require, TLS connections on the internal
-                    // port. Some users of the internal SQL server do not support
-                    // TLS, while others require it, so we allow both.
-                    //
-                    // TODO(benesch): migrate all internal applications to TLS and
-                    // remove `TlsMode::Allow`.
-                    pgwire_tls.mode = mz_server_core::TlsMode::Allow;
-                    pgwire_tls
-                }),
+                tls: internal_pgwire_tls,
                 adapter_client: adapter_client.clone(),
                 frontegg: None,
                 metrics: metrics.clone(),
@@ -595,6 +598,7 @@ impl Listeners {
                 active_connection_count: Arc::clone(&active_connection_count),
                 concurrent_webhook_req: webhook_concurrency_limit.semaphore(),
                 metrics: http_metrics.clone(),
+                sql_jobs: sql_jobs.clone(),
             });
             mz_server_core::serve(http_conns, http_server, None)
         });
@@ -611,6 +615,7 @@ impl Listeners {
                 active_connection_count: Arc::clone(&active_connection_count),
                 concurrent_webhook_req: webhook_concurrency_limit.semaphore(),
                 metrics: http_metrics,
+                sql_jobs,
             });
             mz_server_core::serve(balancer_http_conns, balancer_http_server, None)
         });
@@ -680,6 +685,112 @@ impl Listeners {
     }
 }
 
+/// Rehearses an upgrade of the catalog to the version of this binary.
+///
+/// Runs the catalog migrations of this version against a savepoint of the catalog, so nothing is
+/// committed and an `environmentd` of the previous version can keep serving the environment.
+/// Returns a report of the builtin schema migrations that the upgrade performs, or the error that
+/// the upgrade would fail with.
+pub async fn dry_run_upgrade(config: Config) -> Result<BuiltinMigrationReport, anyhow::Error> {
+    let boot_ts = (config.now)();
+
+    let persist_client = config
+        .catalog_config
+        .persist_clients
+        .open(config.controller.persist_location.clone())
+        .await?;
+    let mut openable_adapter_storage: Box<dyn OpenableDurableCatalogState> = Box::new(
+        mz_catalog::durable::persist_backed_catalog_state(
+            persist_client,
+            config.environment_id.organization_id(),
+            BUILD_INFO.semver_version(),
+            Arc::clone(&config.catalog_config.metrics),
+        )
+        .await?,
+    );
+    if !openable_adapter_storage.is_initialized().await? {
+        bail!("the catalog is not initialized, so there is no upgrade to rehearse");
+    }
+
+    let system_parameter_sync_config = system_parameter_sync_config(&config);
+    let remote_system_parameters = load_remote_system_parameters(
+        &mut openable_adapter_storage,
+        system_parameter_sync_config,
+        config.config_sync_timeout,
+    )
+    .await?;
+
+    let mut storage = openable_adapter_storage
+        .open_savepoint(
+            boot_ts,
+            &BootstrapArgs {
+                default_cluster_replica_size: config.bootstrap_default_cluster_replica_size,
+                bootstrap_role: config.bootstrap_role,
+            },
+            None,
+            None,
+        )
+        .await?;
+
+    let connection_context = config.controller.connection_context;
+    let aws_principal_context = match (
+        config.aws_account_id,
+        connection_context.aws_external_id_prefix.clone(),
+    ) {
+        (Some(aws_account_id), Some(aws_external_id_prefix)) => Some(AwsPrincipalContext {
+            aws_account_id,
+            aws_external_id_prefix,
+        }),
+        _ => None,
+    };
+    let (_state, builtin_migration_metadata, last_catalog_version) = Catalog::initialize_state(
+        StateConfig {
+            unsafe_mode: config.unsafe_mode,
+            all_features: config.all_features,
+            build_info: &BUILD_INFO,
+            environment_id: config.environment_id,
+            now: config.now,
+            skip_migrations: false,
+            cluster_replica_sizes: config.cluster_replica_sizes,
+            builtin_cluster_replica_size: config.bootstrap_builtin_cluster_replica_size,
+            system_parameter_defaults: config.system_parameter_defaults,
+            remote_system_parameters,
+            availability_zones: config.availability_zones,
+            egress_ips: config.egress_ips,
+            aws_principal_context,
+            aws_privatelink_availability_zones: config
+                .aws_privatelink_availability_zones
+                .map(|azs| azs.into_iter().collect()),
+            http_host_name: config.http_host_name,
+            connection_context,
+            active_connection_count: Arc::new(Mutex::new(ConnectionCounter::new(0, 0))),
+        },
+        &mut storage,
+    )
+    .await?;
+    storage.expire().await;
+
+    info!(
+        "catalog upgrade from {} to {} would succeed",
+        last_catalog_version,
+        BUILD_INFO.human_version(),
+    );
+    Ok(builtin_migration_metadata.report)
+}
+
+/// Returns the configuration of the system parameter frontend, if `launchdarkly_sdk_key` is set.
+fn system_parameter_sync_config(config: &Config) -> Option<SystemParameterSyncConfig> {
+    let ld_sdk_key = config.launchdarkly_sdk_key.clone()?;
+    Some(SystemParameterSyncConfig::new(
+        config.environment_id.clone(),
+        &BUILD_INFO,
+        &config.metrics_registry,
+        config.now.clone(),
+        ld_sdk_key,
+        config.launchdarkly_key_map.clone(),
+    ))
+}
+
 fn get_ld_value<V>(
     name: &str,
     remote_system_parameters: &Option<BTreeMap<String, OwnedVarInput>>,
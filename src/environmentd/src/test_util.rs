@@ -40,7 +40,7 @@ use mz_persist_client::cfg::PersistConfig;
 use mz_persist_client::rpc::PersistGrpcPubSubServer;
 use mz_persist_client::PersistLocation;
 use mz_secrets::SecretsController;
-use mz_server_core::TlsCertConfig;
+use mz_server_core::{TlsCertConfig, TlsClientAuthConfig};
 use mz_sql::catalog::EnvironmentId;
 use mz_stash_types::metrics::Metrics as StashMetrics;
 use mz_storage_types::connections::ConnectionContext;
@@ -178,10 +178,38 @@ impl TestHarness {
         self.tls = Some(TlsCertConfig {
             cert: cert_path.into(),
             key: key_path.into(),
+            client_auth: None,
         });
         self
     }
 
+    /// Enables authentication via TLS client certificates signed by the CA at
+    /// `ca_path`. Must be called after [`Self::with_tls`].
+    pub fn with_tls_client_auth(mut self, ca_path: impl Into<PathBuf>, required: bool) -> Self {
+        let tls = self
+            .tls
+            .as_mut()
+            .expect("with_tls must be called before with_tls_client_auth");
+        tls.client_auth = Some(TlsClientAuthConfig {
+            ca: ca_path.into(),
+            required,
+            user_map: BTreeMap::new(),
+        });
+        self
+    }
+
+    /// Maps TLS client certificates with the common name `common_name` to
+    /// `user`. Must be called after [`Self::with_tls_client_auth`].
+    pub fn with_tls_client_cert_user_mapping(mut self, common_name: &str, user: &str) -> Self {
+        let client_auth = self
+            .tls
+            .as_mut()
+            .and_then(|tls| tls.client_auth.as_mut())
+            .expect("with_tls_client_auth must be called before with_tls_client_cert_user_mapping");
+        client_auth.user_map.insert(common_name.into(), user.into());
+        self
+    }
+
     pub fn unsafe_mode(mut self) -> Self {
         self.unsafe_mode = true;
         self
@@ -448,6 +476,7 @@ impl Listeners {
                         secrets_reader_local_file_dir: Some(data_directory.join("secrets")),
                         secrets_reader_kubernetes_context: None,
                         secrets_reader_aws_prefix: None,
+                        secrets_reader_external_aws_allowed_arn_prefix: Vec::new(),
                     },
                     connection_context,
                 },
@@ -40,8 +40,10 @@ use mz_ore::retry::Retry;
 use mz_sql::names::PUBLIC_ROLE_NAME;
 use mz_sql::session::user::{HTTP_DEFAULT_USER, SYSTEM_USER};
 use openssl::error::ErrorStack;
-use openssl::ssl::{SslConnector, SslConnectorBuilder, SslMethod, SslOptions, SslVerifyMode};
-use postgres::config::SslMode;
+use openssl::ssl::{
+    SslConnector, SslConnectorBuilder, SslFiletype, SslMethod, SslOptions, SslVerifyMode,
+};
+use postgres::config::{ChannelBinding, SslMode};
 use postgres::error::SqlState;
 use serde::Deserialize;
 use serde_json::json;
@@ -1335,6 +1337,307 @@ async fn test_auth_intermediate_ca() {
     .await;
 }
 
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `OPENSSL_init_ssl` on OS `linux`
+async fn test_auth_client_cert() {
+    let ca = Ca::new_root("test ca").unwrap();
+    let (server_cert, server_key) = ca
+        .request_cert("server", vec![IpAddr::V4(Ipv4Addr::LOCALHOST)])
+        .unwrap();
+    let (client_cert, client_key) = ca.request_client_cert("alice").unwrap();
+    let configure_client_cert = |b: &mut SslConnectorBuilder| {
+        b.set_verify(SslVerifyMode::NONE);
+        b.set_certificate_file(&client_cert, SslFiletype::PEM)?;
+        b.set_private_key_file(&client_key, SslFiletype::PEM)
+    };
+
+    // When client certificates are optional, a certificate that matches the
+    // user authenticates the connection, and connections without one fall
+    // back to the usual authentication flow.
+    let server = test_util::TestHarness::default()
+        .with_tls(&server_cert, &server_key)
+        .with_tls_client_auth(ca.ca_cert_path(), false)
+        .start()
+        .await;
+
+    run_tests(
+        "client certificates optional",
+        &server,
+        &[
+            TestCase::Pgwire {
+                user_to_auth_as: "alice",
+                user_reported_by_system: "alice",
+                password: None,
+                ssl_mode: SslMode::Require,
+                configure: Box::new(configure_client_cert),
+                assert: Assert::Success,
+            },
+            TestCase::Pgwire {
+                user_to_auth_as: "materialize",
+                user_reported_by_system: "materialize",
+                password: None,
+                ssl_mode: SslMode::Require,
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::Success,
+            },
+        ],
+    )
+    .await;
+
+    // The authentication method is reported in `mz_sessions`.
+    let client = server
+        .connect()
+        .ssl_mode(SslMode::Require)
+        .user("alice")
+        .with_tls(make_pg_tls(configure_client_cert))
+        .await
+        .unwrap();
+    let row = client
+        .query_one(
+            "SELECT authentication_method FROM mz_internal.mz_sessions
+             WHERE id::int4 = pg_backend_pid()",
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(row.get::<_, String>(0), "certificate");
+    let client = server
+        .connect()
+        .ssl_mode(SslMode::Require)
+        .with_tls(make_pg_tls(|b| Ok(b.set_verify(SslVerifyMode::NONE))))
+        .await
+        .unwrap();
+    let row = client
+        .query_one(
+            "SELECT authentication_method FROM mz_internal.mz_sessions
+             WHERE id::int4 = pg_backend_pid()",
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(row.get::<_, String>(0), "trust");
+
+    // When client certificates are required, connections must present a
+    // certificate whose identity matches the requested user.
+    let server = test_util::TestHarness::default()
+        .with_tls(&server_cert, &server_key)
+        .with_tls_client_auth(ca.ca_cert_path(), true)
+        .start()
+        .await;
+
+    run_tests(
+        "client certificates required",
+        &server,
+        &[
+            TestCase::Pgwire {
+                user_to_auth_as: "alice",
+                user_reported_by_system: "alice",
+                password: None,
+                ssl_mode: SslMode::Require,
+                configure: Box::new(configure_client_cert),
+                assert: Assert::Success,
+            },
+            TestCase::Pgwire {
+                user_to_auth_as: "bob",
+                user_reported_by_system: "bob",
+                password: None,
+                ssl_mode: SslMode::Require,
+                configure: Box::new(configure_client_cert),
+                assert: Assert::DbErr(Box::new(|err| {
+                    assert_eq!(*err.code(), SqlState::INVALID_AUTHORIZATION_SPECIFICATION);
+                    assert_eq!(
+                        err.message(),
+                        r#"certificate authentication failed for user "bob""#
+                    );
+                })),
+            },
+            TestCase::Pgwire {
+                user_to_auth_as: "alice",
+                user_reported_by_system: "alice",
+                password: None,
+                ssl_mode: SslMode::Require,
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::DbErr(Box::new(|err| {
+                    assert_eq!(*err.code(), SqlState::INVALID_AUTHORIZATION_SPECIFICATION);
+                    assert_eq!(
+                        err.message(),
+                        "connection requires a valid client certificate"
+                    );
+                })),
+            },
+        ],
+    )
+    .await;
+
+    // A mapping overrides the user that a certificate's common name
+    // authenticates as.
+    let server = test_util::TestHarness::default()
+        .with_tls(&server_cert, &server_key)
+        .with_tls_client_auth(ca.ca_cert_path(), true)
+        .with_tls_client_cert_user_mapping("alice", "carol")
+        .start()
+        .await;
+
+    run_tests(
+        "client certificate user mapping",
+        &server,
+        &[
+            TestCase::Pgwire {
+                user_to_auth_as: "carol",
+                user_reported_by_system: "carol",
+                password: None,
+                ssl_mode: SslMode::Require,
+                configure: Box::new(configure_client_cert),
+                assert: Assert::Success,
+            },
+            TestCase::Pgwire {
+                user_to_auth_as: "alice",
+                user_reported_by_system: "alice",
+                password: None,
+                ssl_mode: SslMode::Require,
+                configure: Box::new(configure_client_cert),
+                assert: Assert::DbErr(Box::new(|err| {
+                    assert_eq!(*err.code(), SqlState::INVALID_AUTHORIZATION_SPECIFICATION);
+                    assert_eq!(
+                        err.message(),
+                        r#"certificate authentication failed for user "alice""#
+                    );
+                })),
+            },
+        ],
+    )
+    .await;
+
+    // Certificate logins are recorded in the session history, along with the
+    // certificate's common name and the user that it was mapped to.
+    let client = server
+        .connect()
+        .ssl_mode(SslMode::Require)
+        .user("carol")
+        .with_tls(make_pg_tls(configure_client_cert))
+        .await
+        .unwrap();
+    let logins: Vec<(String, String)> = Retry::default()
+        .max_duration(Duration::from_secs(60))
+        .retry_async(|_| async {
+            let rows = client
+                .query(
+                    "SELECT authenticated_user, client_certificate_common_name
+                     FROM mz_internal.mz_session_history
+                     WHERE authentication_method = 'certificate'",
+                    &[],
+                )
+                .await
+                .unwrap();
+            if rows.len() < 2 {
+                return Err(());
+            }
+            Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+        })
+        .await
+        .unwrap();
+    for login in logins {
+        assert_eq!(login, ("carol".into(), "alice".into()));
+    }
+}
+
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `OPENSSL_init_ssl` on OS `linux`
+async fn test_auth_scram() {
+    let ca = Ca::new_root("test ca").unwrap();
+    let (server_cert, server_key) = ca
+        .request_cert("server", vec![IpAddr::V4(Ipv4Addr::LOCALHOST)])
+        .unwrap();
+
+    let server = test_util::TestHarness::default()
+        .with_tls(server_cert, server_key)
+        .start()
+        .await;
+    let mz_system = server.connect().internal().await.unwrap();
+    mz_system
+        .batch_execute("CREATE ROLE app WITH PASSWORD 'hunter2'; CREATE ROLE other")
+        .await
+        .unwrap();
+
+    run_tests(
+        "SCRAM-SHA-256",
+        &server,
+        &[
+            // Roles with a password must supply it.
+            TestCase::Pgwire {
+                user_to_auth_as: "app",
+                user_reported_by_system: "app",
+                password: Some("hunter2"),
+                ssl_mode: SslMode::Require,
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::Success,
+            },
+            TestCase::Pgwire {
+                user_to_auth_as: "app",
+                user_reported_by_system: "app",
+                password: Some("hunter3"),
+                ssl_mode: SslMode::Require,
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::DbErr(Box::new(|err| {
+                    assert_eq!(*err.code(), SqlState::INVALID_PASSWORD);
+                    assert_eq!(
+                        err.message(),
+                        r#"password authentication failed for user "app""#
+                    );
+                })),
+            },
+            // Roles without a password are unaffected.
+            TestCase::Pgwire {
+                user_to_auth_as: "other",
+                user_reported_by_system: "other",
+                password: None,
+                ssl_mode: SslMode::Require,
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::Success,
+            },
+        ],
+    )
+    .await;
+
+    // Clients that require channel binding can use SCRAM-SHA-256-PLUS.
+    let mut config = server
+        .connect()
+        .ssl_mode(SslMode::Require)
+        .user("app")
+        .password("hunter2")
+        .as_pg_config()
+        .clone();
+    config.channel_binding(ChannelBinding::Require);
+    let client = server
+        .connect()
+        .with_config(config)
+        .with_tls(make_pg_tls(|b| Ok(b.set_verify(SslVerifyMode::NONE))))
+        .await
+        .unwrap();
+    let row = client
+        .query_one(
+            "SELECT authentication_method FROM mz_internal.mz_sessions
+             WHERE id::int4 = pg_backend_pid()",
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(row.get::<_, String>(0), "password");
+
+    // Removing the password restores the previous behavior.
+    mz_system
+        .batch_execute("ALTER ROLE app PASSWORD NULL")
+        .await
+        .unwrap();
+    server
+        .connect()
+        .ssl_mode(SslMode::Require)
+        .user("app")
+        .with_tls(make_pg_tls(|b| Ok(b.set_verify(SslVerifyMode::NONE))))
+        .await
+        .unwrap();
+}
+
 #[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
 #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `OPENSSL_init_ssl` on OS `linux`
 async fn test_auth_admin_non_superuser() {
@@ -12,7 +12,7 @@
 use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::fmt::Write;
 use std::io::Write as _;
-use std::net::{IpAddr, Ipv4Addr};
+use std::net::{IpAddr, Ipv4Addr, TcpStream};
 use std::process::{Command, Stdio};
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
@@ -25,8 +25,9 @@ use futures::FutureExt;
 use http::{Request, StatusCode};
 use itertools::Itertools;
 use jsonwebtoken::{DecodingKey, EncodingKey};
+use mz_adapter::DrainStatus;
 use mz_environmentd::http::{
-    BecomeLeaderResponse, BecomeLeaderResult, LeaderStatus, LeaderStatusResponse,
+    BecomeLeaderResponse, BecomeLeaderResult, DrainResponse, LeaderStatus, LeaderStatusResponse,
 };
 use mz_environmentd::test_util::{self, make_pg_tls, Ca, PostgresErrorExt, KAFKA_ADDRS};
 use mz_environmentd::{WebSocketAuth, WebSocketResponse};
@@ -60,6 +61,7 @@ use tempfile::TempDir;
 use tokio_postgres::error::SqlState;
 use tracing::info;
 use tungstenite::error::ProtocolError;
+use tungstenite::stream::MaybeTlsStream;
 use tungstenite::{Error, Message};
 use uuid::Uuid;
 
@@ -1565,6 +1567,86 @@ fn test_max_request_size() {
     }
 }
 
+#[mz_ore::test]
+#[cfg_attr(miri, ignore)] // too slow
+fn test_http_sql_jobs() {
+    let data_dir = tempfile::tempdir().unwrap();
+    let harness = test_util::TestHarness::default().data_directory(data_dir.path());
+    let server = harness.clone().start_blocking();
+    let jobs_url = |server: &test_util::TestServerWithRuntime| {
+        format!(
+            "http://{}/api/experimental/sql/jobs",
+            server.inner().http_local_addr()
+        )
+    };
+    let base_url = jobs_url(&server);
+
+    let submit = |query: &str| -> String {
+        let res = Client::new()
+            .post(&base_url)
+            .json(&serde_json::json!({ "query": query }))
+            .send()
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::ACCEPTED);
+        let body: serde_json::Value = res.json().unwrap();
+        body["job_id"].as_str().unwrap().to_string()
+    };
+    let wait = |base_url: &str, job_id: &str| -> serde_json::Value {
+        Retry::default()
+            .max_duration(Duration::from_secs(30))
+            .retry(|_| {
+                let res = Client::new()
+                    .get(format!("{base_url}/{job_id}"))
+                    .send()
+                    .unwrap();
+                assert_eq!(res.status(), StatusCode::OK);
+                let body: serde_json::Value = res.json().unwrap();
+                if body["status"] == "running" {
+                    Err(body)
+                } else {
+                    Ok(body)
+                }
+            })
+            .unwrap()
+    };
+
+    let job_id = submit("CREATE TABLE t (a int); INSERT INTO t VALUES (1), (2)");
+    let status = wait(&base_url, &job_id);
+    assert_eq!(status["status"], "succeeded", "{status}");
+    assert_eq!(status["results"].as_array().unwrap().len(), 2);
+    let mut client = server.connect(postgres::NoTls).unwrap();
+    let count: i64 = client
+        .query_one("SELECT count(*) FROM t", &[])
+        .unwrap()
+        .get(0);
+    assert_eq!(count, 2);
+
+    // Errors are reported as part of the results, as for `/api/sql`.
+    let error_job_id = submit("SELECT * FROM nonexistent");
+    let status = wait(&base_url, &error_job_id);
+    assert_eq!(status["status"], "succeeded", "{status}");
+    assert_contains!(
+        status["results"][0]["error"]["message"].as_str().unwrap(),
+        "unknown catalog item 'nonexistent'"
+    );
+
+    let res = Client::new()
+        .get(format!("{base_url}/{}", Uuid::new_v4()))
+        .send()
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    // The status of jobs is recorded in the catalog, and so survives a
+    // restart, but their results are only kept in memory.
+    drop(client);
+    drop(server);
+    let server = harness.start_blocking();
+    let base_url = jobs_url(&server);
+    let status = wait(&base_url, &job_id);
+    assert_eq!(status["status"], "succeeded", "{status}");
+    assert!(status.get("results").is_none(), "{status}");
+}
+
 #[mz_ore::test]
 #[cfg_attr(miri, ignore)] // too slow
 fn test_max_statement_batch_size() {
@@ -1735,6 +1817,113 @@ fn test_ws_subscribe_no_crash() {
     std::thread::sleep(Duration::from_secs(1))
 }
 
+#[mz_ore::test]
+#[cfg_attr(miri, ignore)] // too slow
+fn test_ws_subscribe_structured() {
+    let server = test_util::TestHarness::default()
+        .with_system_parameter_default(
+            "enable_logical_compaction_window".to_string(),
+            "true".to_string(),
+        )
+        .start_blocking();
+    let mut client = server.connect(postgres::NoTls).unwrap();
+    client
+        .batch_execute(
+            "CREATE TABLE t (a INT);
+             INSERT INTO t VALUES (1), (2);
+             CREATE MATERIALIZED VIEW mv WITH (RETAIN HISTORY = FOR '1h') AS SELECT * FROM t;",
+        )
+        .unwrap();
+
+    fn read_msg(ws: &mut tungstenite::WebSocket<MaybeTlsStream<TcpStream>>) -> WebSocketResponse {
+        loop {
+            match ws.read().unwrap() {
+                Message::Text(msg) => return serde_json::from_str(&msg).unwrap(),
+                Message::Ping(_) => continue,
+                msg => panic!("unexpected message: {msg:?}"),
+            }
+        }
+    }
+
+    // Reads updates until the next progress message, returning the updates and
+    // the resume token.
+    fn read_until_progress(
+        ws: &mut tungstenite::WebSocket<MaybeTlsStream<TcpStream>>,
+    ) -> (Vec<(i64, Vec<serde_json::Value>)>, String) {
+        let mut updates = Vec::new();
+        loop {
+            match read_msg(ws) {
+                WebSocketResponse::SubscribeData(data) => updates.push((data.diff, data.row)),
+                WebSocketResponse::SubscribeProgress(progress) => {
+                    return (updates, progress.resume_token)
+                }
+                msg => panic!("unexpected message: {msg:?}"),
+            }
+        }
+    }
+
+    let subscribe = |resume_token: Option<&str>| {
+        let (mut ws, _resp) = tungstenite::connect(server.ws_addr()).unwrap();
+        test_util::auth_with_ws(&mut ws, Default::default()).unwrap();
+        let json = serde_json::json!({
+            "query": "SUBSCRIBE mv",
+            "subscribe": { "structured": true, "resume_token": resume_token },
+        });
+        ws.send(Message::Text(json.to_string())).unwrap();
+        assert!(matches!(
+            read_msg(&mut ws),
+            WebSocketResponse::CommandStarting(_)
+        ));
+        match read_msg(&mut ws) {
+            WebSocketResponse::SubscribeSchema(desc) => {
+                let names: Vec<_> = desc.columns.iter().map(|c| c.name.as_str()).collect();
+                assert_eq!(names, ["a"]);
+            }
+            msg => panic!("unexpected message: {msg:?}"),
+        }
+        ws
+    };
+
+    // The first progress message follows the snapshot.
+    let mut ws = subscribe(None);
+    let (mut updates, resume_token) = read_until_progress(&mut ws);
+    updates.sort();
+    assert_eq!(
+        updates,
+        [
+            (1, vec![serde_json::json!("1")]),
+            (1, vec![serde_json::json!("2")])
+        ]
+    );
+    drop(ws);
+
+    // Resuming skips the snapshot and only delivers new updates.
+    client.batch_execute("INSERT INTO t VALUES (3)").unwrap();
+    let mut ws = subscribe(Some(&resume_token));
+    let updates = loop {
+        let (updates, _) = read_until_progress(&mut ws);
+        if !updates.is_empty() {
+            break updates;
+        }
+    };
+    assert_eq!(updates, [(1, vec![serde_json::json!("3")])]);
+
+    // A malformed token is rejected.
+    let (mut ws, _resp) = tungstenite::connect(server.ws_addr()).unwrap();
+    test_util::auth_with_ws(&mut ws, Default::default()).unwrap();
+    let json = serde_json::json!({
+        "query": "SUBSCRIBE mv",
+        "subscribe": { "structured": true, "resume_token": "bogus" },
+    });
+    ws.send(Message::Text(json.to_string())).unwrap();
+    match read_msg(&mut ws) {
+        WebSocketResponse::Error(err) => {
+            assert_eq!(err.message, "invalid SUBSCRIBE resume token: bogus")
+        }
+        msg => panic!("unexpected message: {msg:?}"),
+    }
+}
+
 #[mz_ore::test]
 #[cfg_attr(miri, ignore)] // too slow
 fn test_ws_notifies_for_bad_options() {
@@ -2257,6 +2446,60 @@ fn test_internal_console_proxy() {
     );
 }
 
+#[mz_ore::test]
+#[cfg_attr(miri, ignore)] // too slow
+fn test_diagnostics_bundle() {
+    let server = test_util::TestHarness::default().start_blocking();
+    let mut client = server.connect(postgres::NoTls).unwrap();
+    client
+        .batch_execute("CREATE VIEW v AS SELECT 'hunter2' AS secret")
+        .unwrap();
+
+    let res = Client::new()
+        .get(
+            Url::parse(&format!(
+                "http://{}/api/diagnostics/bundle",
+                server.inner().internal_http_local_addr()
+            ))
+            .unwrap(),
+        )
+        .header("x-materialize-user", "mz_support")
+        .send()
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(
+        res.headers().get(CONTENT_TYPE).unwrap().to_str().unwrap(),
+        "application/gzip"
+    );
+
+    let bundle = res.bytes().unwrap();
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(&bundle[..]));
+    let mut files = BTreeMap::new();
+    for entry in archive.entries().unwrap() {
+        let mut entry = entry.unwrap();
+        let path = entry.path().unwrap().display().to_string();
+        let mut contents = String::new();
+        std::io::Read::read_to_string(&mut entry, &mut contents).unwrap();
+        files.insert(path, contents);
+    }
+
+    let manifest: serde_json::Value =
+        serde_json::from_str(&files["mz-diagnostics/manifest.json"]).unwrap();
+    assert_eq!(manifest["user"], "mz_support");
+    for section in manifest["sections"].as_array().unwrap() {
+        let path = format!("mz-diagnostics/{}.json", section.as_str().unwrap());
+        assert!(files.contains_key(&path), "missing {path}");
+    }
+
+    // Object definitions are redacted.
+    let objects = &files["mz-diagnostics/catalog/objects.json"];
+    assert_contains!(
+        objects,
+        "CREATE VIEW materialize.public.v AS SELECT '<REDACTED>'"
+    );
+    assert!(!objects.contains("hunter2"), "{objects}");
+}
+
 #[mz_ore::test]
 #[cfg_attr(miri, ignore)] // too slow
 fn test_internal_http_auth() {
@@ -2673,6 +2916,73 @@ async fn test_leader_promotion_mixed_code_version() {
     client_this.simple_query("SELECT 1").await.unwrap();
 }
 
+// Test that draining rejects new sessions and closes existing ones once they
+// are idle.
+#[mz_ore::test]
+#[cfg_attr(miri, ignore)] // too slow
+fn test_drain() {
+    let server = test_util::TestHarness::default().start_blocking();
+    let mut idle_client = server.connect(postgres::NoTls).unwrap();
+    let mut txn_client = server.connect(postgres::NoTls).unwrap();
+    let mut internal_client = server.connect_internal(postgres::NoTls).unwrap();
+    txn_client.batch_execute("BEGIN; SELECT 1").unwrap();
+
+    let drain_url = |path: &str| {
+        Url::parse(&format!(
+            "http://{}/api/drain{path}",
+            server.inner().internal_http_local_addr()
+        ))
+        .unwrap()
+    };
+    let drain_status = || {
+        let res = Client::new().get(drain_url("/status")).send().unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        res.json::<DrainResponse>().unwrap().status
+    };
+    assert_eq!(drain_status(), DrainStatus::NotDraining);
+
+    let res = Client::new()
+        .post(drain_url("?timeout_secs=1"))
+        .send()
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let status = res.json::<DrainResponse>().unwrap().status;
+    assert!(
+        matches!(
+            status,
+            DrainStatus::Draining {
+                deadline_passed: false,
+                ..
+            }
+        ),
+        "{status:?}"
+    );
+
+    // New sessions of external users are rejected.
+    let err = server.connect(postgres::NoTls).unwrap_db_error();
+    assert_eq!(err.code(), &SqlState::CANNOT_CONNECT_NOW);
+
+    // Idle sessions are closed.
+    let err = idle_client.query("SELECT 1", &[]).unwrap_db_error();
+    assert_eq!(err.code(), &SqlState::ADMIN_SHUTDOWN);
+
+    // Sessions of internal users are not affected.
+    internal_client.query("SELECT 1", &[]).unwrap();
+    let mut internal_client = server.connect_internal(postgres::NoTls).unwrap();
+    internal_client.query("SELECT 1", &[]).unwrap();
+
+    // Sessions within a transaction are closed once the deadline passed.
+    Retry::default()
+        .max_duration(Duration::from_secs(30))
+        .retry(|_| match drain_status() {
+            DrainStatus::Drained => Ok(()),
+            status => Err(format!("{status:?}")),
+        })
+        .unwrap();
+    let err = txn_client.query("SELECT 1", &[]).unwrap_db_error();
+    assert_eq!(err.code(), &SqlState::ADMIN_SHUTDOWN);
+}
+
 // Test that websockets observe cancellation.
 #[mz_ore::test]
 #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `epoll_wait` on OS `linux`
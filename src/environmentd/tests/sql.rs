@@ -1236,6 +1236,9 @@ largest not in advance of upper:<TIMESTAMP>
                           upper:[<TIMESTAMP>]
                           since:[<TIMESTAMP>]
         can respond immediately: true
+                  expected wait: 0 ms
+           timestamp constraint: oracle read timestamp
+                isolation level: strict serializable
                        timeline: Some(EpochMilliseconds)
               session wall time:<TIMESTAMP>
 
@@ -2286,6 +2289,80 @@ fn test_idle_in_transaction_session_timeout() {
     client.batch_execute("COMMIT").unwrap();
 }
 
+#[mz_ore::test]
+fn test_write_transaction_read_lock_timeout() {
+    let server = test_util::TestHarness::default()
+        .with_system_parameter_default(
+            "write_transaction_read_lock_timeout".to_string(),
+            "200ms".to_string(),
+        )
+        .start_blocking();
+    server.enable_feature_flags(&["enable_unsafe_functions"]);
+
+    let mut client = server.connect(postgres::NoTls).unwrap();
+    client
+        .batch_execute("CREATE TABLE t1 (a int); CREATE TABLE t2 (a int)")
+        .unwrap();
+    client
+        .batch_execute("SET idle_in_transaction_session_timeout TO 0")
+        .unwrap();
+
+    // A read in a write transaction acquires the write lock, which keeps other
+    // writes from committing. Once the transaction has held it for too long,
+    // its statements fail, and the failed transaction releases the lock.
+    let mut writer = server.connect(postgres::NoTls).unwrap();
+    client.batch_execute("BEGIN").unwrap();
+    client.batch_execute("INSERT INTO t1 VALUES (1)").unwrap();
+    client.query("SELECT * FROM t2", &[]).unwrap();
+    let error = client
+        .batch_execute("SELECT mz_unsafe.mz_sleep(0.5); INSERT INTO t1 VALUES (2)")
+        .unwrap_err();
+    assert!(
+        error
+            .as_db_error()
+            .unwrap()
+            .message()
+            .contains("write_transaction_read_lock_timeout"),
+        "{error:?}"
+    );
+    writer.batch_execute("INSERT INTO t2 VALUES (1)").unwrap();
+    client.batch_execute("ROLLBACK").unwrap();
+    let count: i64 = client
+        .query_one("SELECT count(*) FROM t1", &[])
+        .unwrap()
+        .get(0);
+    assert_eq!(count, 0);
+
+    // An idle session that holds the lock is terminated, even without an idle
+    // timeout, which lets other writes commit.
+    client.batch_execute("BEGIN").unwrap();
+    client.batch_execute("INSERT INTO t1 VALUES (1)").unwrap();
+    client.query("SELECT * FROM t2", &[]).unwrap();
+    writer.batch_execute("INSERT INTO t2 VALUES (2)").unwrap();
+    Retry::default()
+        .max_duration(Duration::from_secs(1))
+        .retry(|_| {
+            let res = client.query("SELECT 1", &[]);
+            if let Err(error) = res {
+                if error.is_closed() {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "error should indicates that the connection is closed: {error:?}"
+                    ))
+                }
+            } else {
+                Err(format!("query should return error: {res:?}"))
+            }
+        })
+        .unwrap();
+    let count: i64 = writer
+        .query_one("SELECT count(*) FROM t2", &[])
+        .unwrap()
+        .get(0);
+    assert_eq!(count, 2);
+}
+
 #[mz_ore::test]
 fn test_coord_startup_blocking() {
     let initial_time = 0;
@@ -2538,6 +2615,46 @@ fn test_emit_tracing_notice() {
     }
 }
 
+#[test] // allow(test-attribute)
+fn test_client_trace_context() {
+    let server = test_util::TestHarness::default()
+        .with_enable_tracing(true)
+        .with_system_parameter_default("opentelemetry_filter".to_string(), "info".to_string())
+        .with_system_parameter_default("log_filter".to_string(), "info".to_string())
+        .start_blocking();
+
+    let trace_id = "4bf92f3577b34da6a3ce929d0e0e4736";
+    let options = serde_json::to_string(&json!({ "emit_trace_id_notice": "true" })).unwrap();
+    let url = reqwest::Url::parse_with_params(
+        &format!("http://{}/api/sql", server.inner().http_local_addr()),
+        &[("options", options)],
+    )
+    .unwrap();
+    let request = |traceparent: &str| {
+        let res: serde_json::Value = reqwest::blocking::Client::new()
+            .post(url.clone())
+            .header("traceparent", traceparent)
+            .json(&json!({ "query": "SELECT 1" }))
+            .send()
+            .unwrap()
+            .json()
+            .unwrap();
+        res["results"][0]["notices"][0]["message"]
+            .as_str()
+            .expect("missing trace id notice")
+            .to_string()
+    };
+
+    // Statements run under the trace supplied by the client.
+    let notice = request(&format!("00-{trace_id}-00f067aa0ba902b7-01"));
+    assert_eq!(notice, format!("trace id: {trace_id}"));
+
+    // Invalid trace contexts are ignored.
+    let notice = request("garbage");
+    assert_contains!(notice, "trace id: ");
+    assert!(!notice.contains(trace_id));
+}
+
 #[mz_ore::test]
 fn test_subscribe_on_dropped_source() {
     fn test_subscribe_on_dropped_source_inner(
@@ -668,6 +668,7 @@ mod relation {
 /// Support for parsing [mz_expr::MirScalarExpr].
 mod scalar {
     use mz_expr::{BinaryFunc, ColumnOrder, MirScalarExpr};
+    use mz_repr::adt::collation::Collation;
     use mz_repr::{AsColumnType, Datum, Row, RowArena, ScalarType};
 
     use super::*;
@@ -939,10 +940,18 @@ mod scalar {
         let column = input.parse::<syn::LitInt>()?.base10_parse::<usize>()?;
         let desc = input.eat(kw::desc) || !input.eat(kw::asc);
         let nulls_last = input.eat(kw::nulls_last) || !input.eat(kw::nulls_first);
+        let collation = if input.eat(kw::collate) {
+            let name = input.parse::<syn::LitStr>()?;
+            let msg = format!("unsupported collation {}", name.value());
+            Collation::from_name(&name.value()).ok_or_else(|| Error::new(name.span(), msg))?
+        } else {
+            Collation::CodePoint
+        };
         Ok(ColumnOrder {
             column,
             desc,
             nulls_last,
+            collation,
         })
     }
 
@@ -1558,6 +1567,7 @@ mod kw {
     syn::custom_keyword!(ArrangeBy);
     syn::custom_keyword!(array);
     syn::custom_keyword!(asc);
+    syn::custom_keyword!(collate);
     syn::custom_keyword!(Constant);
     syn::custom_keyword!(CrossJoin);
     syn::custom_keyword!(cte);
@@ -1703,6 +1703,93 @@ mod tests {
         assert!(!range_out.may_contain(timestamp("2024-01-12T10:00:00")));
     }
 
+    #[mz_ore::test]
+    fn test_date_trunc() {
+        use chrono::NaiveDateTime;
+
+        let arena = RowArena::new();
+
+        let timestamp = |ts| {
+            Datum::Timestamp(
+                NaiveDateTime::parse_from_str(ts, "%Y-%m-%dT%H:%M:%S")
+                    .unwrap()
+                    .try_into()
+                    .unwrap(),
+            )
+        };
+
+        // date_trunc('day', #0) = '2024-01-11'
+        let expr = MirScalarExpr::CallBinary {
+            func: BinaryFunc::Eq,
+            expr1: Box::new(MirScalarExpr::CallUnary {
+                func: UnaryFunc::DateTruncTimestamp(DateTruncTimestamp(DateTimeUnits::Day)),
+                expr: Box::new(MirScalarExpr::Column(0)),
+            }),
+            expr2: Box::new(MirScalarExpr::literal_ok(
+                timestamp("2024-01-11T00:00:00"),
+                ScalarType::Timestamp { precision: None },
+            )),
+        };
+
+        let relation = RelationType::new(vec![
+            ScalarType::Timestamp { precision: None }.nullable(false)
+        ]);
+
+        // A part entirely before the day in question can be pruned...
+        let mut interpreter = ColumnSpecs::new(&relation, &arena);
+        interpreter.push_column(
+            0,
+            ResultSpec::value_between(
+                timestamp("2024-01-09T13:00:00"),
+                timestamp("2024-01-10T23:59:59"),
+            ),
+        );
+        let range_out = interpreter.expr(&expr).range;
+        assert!(!range_out.may_contain(Datum::True));
+        assert!(range_out.may_contain(Datum::False));
+
+        // ...but one that overlaps it cannot.
+        let mut interpreter = ColumnSpecs::new(&relation, &arena);
+        interpreter.push_column(
+            0,
+            ResultSpec::value_between(
+                timestamp("2024-01-10T13:00:00"),
+                timestamp("2024-01-11T01:00:00"),
+            ),
+        );
+        let range_out = interpreter.expr(&expr).range;
+        assert!(range_out.may_contain(Datum::True));
+        assert!(range_out.may_contain(Datum::False));
+
+        // The same holds when the units are not folded into the function.
+        let expr = MirScalarExpr::CallBinary {
+            func: BinaryFunc::Eq,
+            expr1: Box::new(MirScalarExpr::CallBinary {
+                func: BinaryFunc::DateTruncTimestamp,
+                expr1: Box::new(MirScalarExpr::literal_ok(
+                    Datum::String("day"),
+                    ScalarType::String,
+                )),
+                expr2: Box::new(MirScalarExpr::Column(0)),
+            }),
+            expr2: Box::new(MirScalarExpr::literal_ok(
+                timestamp("2024-01-11T00:00:00"),
+                ScalarType::Timestamp { precision: None },
+            )),
+        };
+        let mut interpreter = ColumnSpecs::new(&relation, &arena);
+        interpreter.push_column(
+            0,
+            ResultSpec::value_between(
+                timestamp("2024-01-12T00:00:00"),
+                timestamp("2024-01-13T00:00:00"),
+            ),
+        );
+        let range_out = interpreter.expr(&expr).range;
+        assert!(!range_out.may_contain(Datum::True));
+        assert!(range_out.may_contain(Datum::False));
+    }
+
     #[mz_ore::test]
     fn test_inequality() {
         let arena = RowArena::new();
@@ -25,6 +25,7 @@ use mz_ore::num::NonNeg;
 use mz_ore::stack::RecursionLimitError;
 use mz_ore::str::Indent;
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
+use mz_repr::adt::collation::Collation;
 use mz_repr::adt::numeric::NumericMaxScale;
 use mz_repr::explain::text::text_string_at;
 use mz_repr::explain::{
@@ -2289,6 +2290,9 @@ pub struct ColumnOrder {
     /// Whether to sort nulls last.
     #[serde(default)]
     pub nulls_last: bool,
+    /// The collation with which to order strings.
+    #[serde(default)]
+    pub collation: Collation,
 }
 
 impl Columnation for ColumnOrder {
@@ -2301,6 +2305,7 @@ impl RustType<ProtoColumnOrder> for ColumnOrder {
             column: self.column.into_proto(),
             desc: self.desc,
             nulls_last: self.nulls_last,
+            collation: self.collation.into_proto(),
         }
     }
 
@@ -2309,6 +2314,7 @@ impl RustType<ProtoColumnOrder> for ColumnOrder {
             column: proto.column.into_rust()?,
             desc: proto.desc,
             nulls_last: proto.nulls_last,
+            collation: proto.collation.into_rust()?,
         })
     }
 }
@@ -2329,7 +2335,11 @@ where
             } else {
                 "nulls_first"
             },
-        )
+        )?;
+        if self.expr.collation != Collation::CodePoint {
+            write!(f, " collate \"{}\"", self.expr.collation)?;
+        }
+        Ok(())
     }
 }
 
@@ -3194,6 +3204,13 @@ where
                     Ordering::Greater
                 }
             }
+            (Datum::String(lval), Datum::String(rval)) => {
+                if order.desc {
+                    order.collation.compare(rval, lval)
+                } else {
+                    order.collation.compare(lval, rval)
+                }
+            }
             (lval, rval) => {
                 if order.desc {
                     rval.cmp(lval)
@@ -3548,6 +3565,7 @@ mod tests {
                 column: 4,
                 desc: true,
                 nulls_last: true,
+                collation: Collation::CodePoint,
             }],
             limit: Some(NonNeg::try_from(7).unwrap()),
             offset: Default::default(),
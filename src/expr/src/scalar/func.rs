@@ -39,6 +39,7 @@ use mz_pgtz::timezone::{Timezone, TimezoneSpec};
 use mz_proto::chrono::any_naive_datetime;
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::adt::array::ArrayDimension;
+use mz_repr::adt::collation::Collation;
 use mz_repr::adt::date::Date;
 use mz_repr::adt::interval::{Interval, RoundBehavior};
 use mz_repr::adt::jsonb::JsonbRef;
@@ -1436,6 +1437,16 @@ fn range_difference<'a>(
     l.difference(&r)?.into_result(temp_storage)
 }
 
+fn range_merge<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let l = a.unwrap_range();
+    let r = b.unwrap_range();
+    l.merge(&r).into_result(temp_storage)
+}
+
 fn eq<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
     Datum::from(a == b)
 }
@@ -1460,6 +1471,12 @@ fn gte<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
     Datum::from(a >= b)
 }
 
+/// Compares two strings according to `collation`, returning -1, 0, or 1.
+fn compare_collated<'a>(a: Datum<'a>, b: Datum<'a>, collation: &Collation) -> Datum<'a> {
+    let ordering = collation.compare(a.unwrap_str(), b.unwrap_str());
+    Datum::Int32(ordering as i32)
+}
+
 fn to_char_timestamplike<'a, T>(ts: &T, format: &str, temp_storage: &'a RowArena) -> Datum<'a>
 where
     T: TimestampLike,
@@ -2337,10 +2354,12 @@ pub enum BinaryFunc {
     RangeUnion,
     RangeIntersection,
     RangeDifference,
+    RangeMerge,
     UuidGenerateV5,
     MzAclItemContainsPrivilege,
     ParseIdent,
     PrettySql,
+    CompareCollated(Collation),
 }
 
 impl BinaryFunc {
@@ -2602,10 +2621,12 @@ impl BinaryFunc {
             BinaryFunc::RangeUnion => range_union(a, b, temp_storage),
             BinaryFunc::RangeIntersection => range_intersection(a, b, temp_storage),
             BinaryFunc::RangeDifference => range_difference(a, b, temp_storage),
+            BinaryFunc::RangeMerge => range_merge(a, b, temp_storage),
             BinaryFunc::UuidGenerateV5 => Ok(uuid_generate_v5(a, b)),
             BinaryFunc::MzAclItemContainsPrivilege => mz_acl_item_contains_privilege(a, b),
             BinaryFunc::ParseIdent => parse_ident(a, b, temp_storage),
             BinaryFunc::PrettySql => pretty_sql(a, b, temp_storage),
+            BinaryFunc::CompareCollated(collation) => Ok(compare_collated(a, b, collation)),
         }
     }
 
@@ -2782,7 +2803,7 @@ impl BinaryFunc {
             | RangeOverright
             | RangeAdjacent => ScalarType::Bool.nullable(in_nullable),
 
-            RangeUnion | RangeIntersection | RangeDifference => {
+            RangeUnion | RangeIntersection | RangeDifference | RangeMerge => {
                 soft_assert_eq_or_log!(
                     input1_type.scalar_type.without_modifiers(),
                     input2_type.scalar_type.without_modifiers()
@@ -2794,6 +2815,7 @@ impl BinaryFunc {
 
             ParseIdent => ScalarType::Array(Box::new(ScalarType::String)).nullable(in_nullable),
             PrettySql => ScalarType::String.nullable(in_nullable),
+            CompareCollated(_) => ScalarType::Int32.nullable(in_nullable),
         }
     }
 
@@ -2991,10 +3013,12 @@ impl BinaryFunc {
             | RangeUnion
             | RangeIntersection
             | RangeDifference
+            | RangeMerge
             | UuidGenerateV5
             | MzAclItemContainsPrivilege
             | ParseIdent
-            | PrettySql => false,
+            | PrettySql
+            | CompareCollated(_) => false,
 
             JsonbGetInt64 { .. }
             | JsonbGetString { .. }
@@ -3198,7 +3222,9 @@ impl BinaryFunc {
             | ConstantTimeEqBytes
             | ConstantTimeEqString
             | ParseIdent
-            | PrettySql => false,
+            | RangeMerge
+            | PrettySql
+            | CompareCollated(_) => false,
         }
     }
 
@@ -3223,7 +3249,8 @@ impl BinaryFunc {
             | BinaryFunc::Lt
             | BinaryFunc::Gte
             | BinaryFunc::Gt
-            | BinaryFunc::Lte => false,
+            | BinaryFunc::Lte
+            | BinaryFunc::CompareCollated(_) => false,
             _ => true,
         }
     }
@@ -3355,9 +3382,10 @@ impl BinaryFunc {
             | BinaryFunc::DatePartTime
             | BinaryFunc::DatePartTimestamp
             | BinaryFunc::DatePartTimestampTz => (false, false),
-            BinaryFunc::DateTruncTimestamp
-            | BinaryFunc::DateTruncTimestampTz
-            | BinaryFunc::DateTruncInterval => (false, false),
+            // Truncating to any fixed unit preserves the order of timestamps, though the
+            // units themselves are not ordered.
+            BinaryFunc::DateTruncTimestamp | BinaryFunc::DateTruncTimestampTz => (false, true),
+            BinaryFunc::DateTruncInterval => (false, false),
             BinaryFunc::TimezoneTimestamp
             | BinaryFunc::TimezoneTimestampTz
             | BinaryFunc::TimezoneIntervalTimestamp
@@ -3416,12 +3444,14 @@ impl BinaryFunc {
             | BinaryFunc::RangeAdjacent
             | BinaryFunc::RangeUnion
             | BinaryFunc::RangeIntersection
-            | BinaryFunc::RangeDifference => (false, false),
+            | BinaryFunc::RangeDifference
+            | BinaryFunc::RangeMerge => (false, false),
             BinaryFunc::UuidGenerateV5 => (false, false),
             BinaryFunc::MzAclItemContainsPrivilege => (false, false),
             BinaryFunc::ParseIdent => (false, false),
             BinaryFunc::ConstantTimeEqBytes | BinaryFunc::ConstantTimeEqString => (false, false),
             BinaryFunc::PrettySql => (false, false),
+            BinaryFunc::CompareCollated(_) => (false, false),
         }
     }
 }
@@ -3625,10 +3655,14 @@ impl fmt::Display for BinaryFunc {
             BinaryFunc::RangeUnion => f.write_str("+"),
             BinaryFunc::RangeIntersection => f.write_str("*"),
             BinaryFunc::RangeDifference => f.write_str("-"),
+            BinaryFunc::RangeMerge => f.write_str("range_merge"),
             BinaryFunc::UuidGenerateV5 => f.write_str("uuid_generate_v5"),
             BinaryFunc::MzAclItemContainsPrivilege => f.write_str("mz_aclitem_contains_privilege"),
             BinaryFunc::ParseIdent => f.write_str("parse_ident"),
             BinaryFunc::PrettySql => f.write_str("pretty_sql"),
+            BinaryFunc::CompareCollated(collation) => {
+                write!(f, "compare_collated[\"{}\"]", collation)
+            }
         }
     }
 }
@@ -3841,7 +3875,11 @@ impl Arbitrary for BinaryFunc {
             Just(BinaryFunc::RangeUnion).boxed(),
             Just(BinaryFunc::RangeIntersection).boxed(),
             Just(BinaryFunc::RangeDifference).boxed(),
+            Just(BinaryFunc::RangeMerge).boxed(),
             Just(BinaryFunc::ParseIdent).boxed(),
+            Collation::arbitrary()
+                .prop_map(BinaryFunc::CompareCollated)
+                .boxed(),
         ])
     }
 }
@@ -4035,12 +4073,14 @@ impl RustType<ProtoBinaryFunc> for BinaryFunc {
             BinaryFunc::RangeUnion => RangeUnion(()),
             BinaryFunc::RangeIntersection => RangeIntersection(()),
             BinaryFunc::RangeDifference => RangeDifference(()),
+            BinaryFunc::RangeMerge => RangeMerge(()),
             BinaryFunc::UuidGenerateV5 => UuidGenerateV5(()),
             BinaryFunc::MzAclItemContainsPrivilege => MzAclItemContainsPrivilege(()),
             BinaryFunc::ParseIdent => ParseIdent(()),
             BinaryFunc::ConstantTimeEqBytes => ConstantTimeEqBytes(()),
             BinaryFunc::ConstantTimeEqString => ConstantTimeEqString(()),
             BinaryFunc::PrettySql => PrettySql(()),
+            BinaryFunc::CompareCollated(collation) => CompareCollated(collation.into_proto()),
         };
         ProtoBinaryFunc { kind: Some(kind) }
     }
@@ -4238,12 +4278,16 @@ impl RustType<ProtoBinaryFunc> for BinaryFunc {
                 RangeUnion(()) => Ok(BinaryFunc::RangeUnion),
                 RangeIntersection(()) => Ok(BinaryFunc::RangeIntersection),
                 RangeDifference(()) => Ok(BinaryFunc::RangeDifference),
+                RangeMerge(()) => Ok(BinaryFunc::RangeMerge),
                 UuidGenerateV5(()) => Ok(BinaryFunc::UuidGenerateV5),
                 MzAclItemContainsPrivilege(()) => Ok(BinaryFunc::MzAclItemContainsPrivilege),
                 ParseIdent(()) => Ok(BinaryFunc::ParseIdent),
                 ConstantTimeEqBytes(()) => Ok(BinaryFunc::ConstantTimeEqBytes),
                 ConstantTimeEqString(()) => Ok(BinaryFunc::ConstantTimeEqString),
                 PrettySql(()) => Ok(BinaryFunc::PrettySql),
+                CompareCollated(collation) => {
+                    Ok(BinaryFunc::CompareCollated(collation.into_rust()?))
+                }
             }
         } else {
             Err(TryFromProtoError::missing_field("ProtoBinaryFunc::kind"))
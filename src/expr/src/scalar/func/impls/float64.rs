@@ -454,6 +454,7 @@ sqlfunc!(
 
 sqlfunc!(
     #[sqlname = "tots"]
+    #[is_monotone = true]
     fn to_timestamp(f: f64) -> Result<CheckedTimestamp<DateTime<Utc>>, EvalError> {
         const NANO_SECONDS_PER_SECOND: i64 = 1_000_000_000;
         if f.is_nan() {
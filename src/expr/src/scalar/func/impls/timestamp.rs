@@ -486,6 +486,10 @@ impl<'a> EagerUnaryFunc<'a> for DatePartTimestamp {
     fn output_type(&self, input: ColumnType) -> ColumnType {
         ScalarType::Float64.nullable(input.nullable)
     }
+
+    fn is_monotone(&self) -> bool {
+        most_significant_unit(self.0)
+    }
 }
 
 impl fmt::Display for DatePartTimestamp {
@@ -510,6 +514,11 @@ impl<'a> EagerUnaryFunc<'a> for DatePartTimestampTz {
     fn output_type(&self, input: ColumnType) -> ColumnType {
         ScalarType::Float64.nullable(input.nullable)
     }
+
+    fn is_monotone(&self) -> bool {
+        // See the comment on `ExtractTimestampTz::is_monotone`.
+        self.0 == DateTimeUnits::Epoch
+    }
 }
 
 impl fmt::Display for DatePartTimestampTz {
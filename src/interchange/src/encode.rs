@@ -17,6 +17,18 @@ pub trait Encode {
     fn encode_key_unchecked(&self, row: Row) -> Vec<u8>;
 
     fn encode_value_unchecked(&self, row: Row) -> Vec<u8>;
+
+    /// Encodes `row` as a key, returning an error if it contains a value that
+    /// the format cannot represent.
+    fn encode_key(&self, row: Row) -> Result<Vec<u8>, anyhow::Error> {
+        Ok(self.encode_key_unchecked(row))
+    }
+
+    /// Encodes `row` as a value, returning an error if it contains a value
+    /// that the format cannot represent.
+    fn encode_value(&self, row: Row) -> Result<Vec<u8>, anyhow::Error> {
+        Ok(self.encode_value_unchecked(row))
+    }
 }
 
 /// Bundled information sufficient to encode Datums.
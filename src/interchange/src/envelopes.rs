@@ -140,7 +140,7 @@ pub static ENVELOPE_CUSTOM_NAMES: Lazy<BTreeMap<GlobalId, String>> = Lazy::new(|
     }
 });
 
-pub(crate) fn dbz_envelope(
+pub fn dbz_envelope(
     names_and_types: Vec<(ColumnName, ColumnType)>,
 ) -> Vec<(ColumnName, ColumnType)> {
     let row = ColumnType {
@@ -17,6 +17,10 @@ use prost_reflect::{
     ReflectMessage, Value,
 };
 
+mod encode;
+
+pub use crate::protobuf::encode::{derive_schema, MessageEncoder, ProtobufEncoder, SINK_PACKAGE};
+
 /// A decoded description of the schema of a Protobuf message.
 #[derive(Debug, PartialEq)]
 pub struct DecodedDescriptors {
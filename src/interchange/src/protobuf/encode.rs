@@ -0,0 +1,809 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Encoding of rows as Protobuf messages.
+//!
+//! Columns map to message fields of the same name. Records become nested
+//! messages and lists become repeated fields. Types without a Protobuf
+//! equivalent, like `numeric` or `timestamp`, are encoded as their text
+//! representation in `string` fields.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fmt::Write;
+
+use anyhow::{anyhow, bail, Context};
+use byteorder::{NetworkEndian, WriteBytesExt};
+use mz_ore::cast::CastFrom;
+use mz_ore::str::StrExt;
+use mz_repr::adt::char;
+use mz_repr::adt::jsonb::JsonbRef;
+use mz_repr::{strconv, ColumnName, ColumnType, Datum, Row, ScalarType};
+use prost::bytes::Bytes;
+use prost::encoding::encode_varint;
+use prost::Message;
+use prost_reflect::{DescriptorPool, DynamicMessage, Kind, MessageDescriptor, Value};
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::{
+    DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+    OneofDescriptorProto,
+};
+
+use crate::encode::Encode;
+
+/// The package of the messages in derived schemas.
+pub const SINK_PACKAGE: &str = "com.materialize.sink";
+
+/// The Protobuf representation of a column type.
+#[derive(Debug)]
+enum FieldType<'a> {
+    Bool,
+    Int32,
+    Int64,
+    UInt32,
+    UInt64,
+    Float,
+    Double,
+    Bytes,
+    /// A string, or the text representation of a type without a Protobuf
+    /// equivalent.
+    String,
+    Message(&'a [(ColumnName, ColumnType)]),
+    Repeated(Box<FieldType<'a>>),
+}
+
+impl<'a> FieldType<'a> {
+    fn from_scalar_type(
+        scalar_type: &'a ScalarType,
+        allow_repeated: bool,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(match scalar_type {
+            ScalarType::Bool => FieldType::Bool,
+            ScalarType::Int16 | ScalarType::Int32 => FieldType::Int32,
+            ScalarType::Int64 => FieldType::Int64,
+            ScalarType::PgLegacyChar
+            | ScalarType::UInt16
+            | ScalarType::UInt32
+            | ScalarType::Oid
+            | ScalarType::RegClass
+            | ScalarType::RegProc
+            | ScalarType::RegType => FieldType::UInt32,
+            ScalarType::UInt64 => FieldType::UInt64,
+            ScalarType::Float32 => FieldType::Float,
+            ScalarType::Float64 => FieldType::Double,
+            ScalarType::Bytes => FieldType::Bytes,
+            ScalarType::String
+            | ScalarType::VarChar { .. }
+            | ScalarType::Char { .. }
+            | ScalarType::PgLegacyName
+            | ScalarType::Numeric { .. }
+            | ScalarType::Date
+            | ScalarType::Time
+            | ScalarType::Timestamp { .. }
+            | ScalarType::TimestampTz { .. }
+            | ScalarType::Interval
+            | ScalarType::Uuid
+            | ScalarType::Jsonb
+            | ScalarType::MzTimestamp
+            | ScalarType::Range { .. }
+            | ScalarType::AclItem
+            | ScalarType::MzAclItem => FieldType::String,
+            ScalarType::Record { fields, .. } => FieldType::Message(fields),
+            ScalarType::List { element_type, .. } if allow_repeated => {
+                FieldType::Repeated(Box::new(Self::from_scalar_type(element_type, false)?))
+            }
+            ScalarType::List { .. }
+            | ScalarType::Array(_)
+            | ScalarType::Int2Vector
+            | ScalarType::Map { .. } => bail!("type has no Protobuf equivalent"),
+        })
+    }
+
+    /// Reports whether a field of kind `kind` can hold values of this type.
+    fn is_compatible(&self, kind: &Kind) -> bool {
+        match (self, kind) {
+            (FieldType::Bool, Kind::Bool)
+            | (FieldType::Int32, Kind::Int32 | Kind::Sint32 | Kind::Sfixed32)
+            | (FieldType::Int64, Kind::Int64 | Kind::Sint64 | Kind::Sfixed64)
+            | (FieldType::UInt32, Kind::Uint32 | Kind::Fixed32)
+            | (FieldType::UInt64, Kind::Uint64 | Kind::Fixed64)
+            | (FieldType::Float, Kind::Float)
+            | (FieldType::Double, Kind::Double)
+            | (FieldType::Bytes, Kind::Bytes)
+            | (FieldType::String, Kind::String) => true,
+            (FieldType::Message(_), Kind::Message(_)) => true,
+            _ => false,
+        }
+    }
+
+    fn proto_type(&self) -> Type {
+        match self {
+            FieldType::Bool => Type::Bool,
+            FieldType::Int32 => Type::Int32,
+            FieldType::Int64 => Type::Int64,
+            FieldType::UInt32 => Type::Uint32,
+            FieldType::UInt64 => Type::Uint64,
+            FieldType::Float => Type::Float,
+            FieldType::Double => Type::Double,
+            FieldType::Bytes => Type::Bytes,
+            FieldType::String => Type::String,
+            FieldType::Message(_) => Type::Message,
+            FieldType::Repeated(element) => element.proto_type(),
+        }
+    }
+
+    fn proto_type_name(&self) -> &'static str {
+        match self {
+            FieldType::Bool => "bool",
+            FieldType::Int32 => "int32",
+            FieldType::Int64 => "int64",
+            FieldType::UInt32 => "uint32",
+            FieldType::UInt64 => "uint64",
+            FieldType::Float => "float",
+            FieldType::Double => "double",
+            FieldType::Bytes => "bytes",
+            FieldType::String => "string",
+            FieldType::Message(_) | FieldType::Repeated(_) => {
+                unreachable!("message and repeated fields have no scalar type name")
+            }
+        }
+    }
+}
+
+/// Returns the name of the Protobuf field that holds the column named `name`.
+///
+/// Characters that are not valid in Protobuf identifiers are replaced with
+/// underscores, and names that do not start with a letter are prefixed with
+/// `f`.
+fn field_name(name: &ColumnName) -> String {
+    let mut field_name: String = name
+        .as_str()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !field_name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        field_name.insert(0, 'f');
+    }
+    field_name
+}
+
+/// Returns the name of the message nested in a message for its field named
+/// `field_name`.
+fn nested_message_name(field_name: &str) -> String {
+    let mut name = String::with_capacity(field_name.len());
+    let mut upper = true;
+    for c in field_name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            name.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            name.push(c);
+        }
+    }
+    name
+}
+
+/// Derives a Protobuf message named `message_name` that holds rows with the
+/// given columns.
+///
+/// Returns the text of the `.proto` file that declares the message, suitable
+/// for publishing to a schema registry, and the encoded `FileDescriptorSet`
+/// of that file. Nullable columns become fields with explicit presence, so
+/// that NULLs are distinguishable from default values. Repeated fields have
+/// no way to represent NULL elements, so lists that contain NULLs cannot be
+/// encoded.
+pub fn derive_schema(
+    message_name: &str,
+    columns: &[(ColumnName, ColumnType)],
+) -> Result<(String, Vec<u8>), anyhow::Error> {
+    let mut text = format!("syntax = \"proto3\";\n\npackage {SINK_PACKAGE};\n\n");
+    let message = derive_message(
+        message_name,
+        &format!(".{SINK_PACKAGE}.{message_name}"),
+        columns,
+        &mut text,
+        0,
+    )?;
+    let file = FileDescriptorProto {
+        name: Some(format!("{}.proto", message_name.to_lowercase())),
+        package: Some(SINK_PACKAGE.into()),
+        message_type: vec![message],
+        syntax: Some("proto3".into()),
+        ..Default::default()
+    };
+    let fds = FileDescriptorSet { file: vec![file] };
+    Ok((text, fds.encode_to_vec()))
+}
+
+fn derive_message(
+    name: &str,
+    full_name: &str,
+    columns: &[(ColumnName, ColumnType)],
+    text: &mut String,
+    depth: usize,
+) -> Result<DescriptorProto, anyhow::Error> {
+    let indent = "  ".repeat(depth);
+    writeln!(text, "{indent}message {name} {{").expect("writing to string cannot fail");
+
+    let mut message = DescriptorProto {
+        name: Some(name.into()),
+        ..Default::default()
+    };
+    let mut field_names = BTreeSet::new();
+    let mut nested_names = BTreeSet::new();
+    let mut nested_messages = vec![];
+    for (i, (column_name, column_type)) in columns.iter().enumerate() {
+        let field_name = field_name(column_name);
+        if !field_names.insert(field_name.clone()) {
+            bail!(
+                "column {} maps to duplicate Protobuf field {}",
+                column_name.as_str().quoted(),
+                field_name.quoted()
+            );
+        }
+        let number = i32::try_from(i + 1).context("too many columns")?;
+        let field_type =
+            FieldType::from_scalar_type(&column_type.scalar_type, true).with_context(|| {
+                format!(
+                    "column {} cannot be encoded as Protobuf",
+                    column_name.as_str().quoted()
+                )
+            })?;
+        let (repeated, element_type) = match &field_type {
+            FieldType::Repeated(element_type) => (true, &**element_type),
+            field_type => (false, field_type),
+        };
+
+        let mut field = FieldDescriptorProto {
+            name: Some(field_name.clone()),
+            number: Some(number),
+            label: Some(if repeated {
+                Label::Repeated as i32
+            } else {
+                Label::Optional as i32
+            }),
+            r#type: Some(field_type.proto_type() as i32),
+            ..Default::default()
+        };
+        let type_name = match element_type {
+            FieldType::Message(fields) => {
+                let mut nested_name = nested_message_name(&field_name);
+                let stem_len = nested_name.len();
+                let mut suffix = 1;
+                while !nested_names.insert(nested_name.clone()) {
+                    nested_name.truncate(stem_len);
+                    write!(nested_name, "{suffix}").expect("writing to string cannot fail");
+                    suffix += 1;
+                }
+                let nested_full_name = format!("{full_name}.{nested_name}");
+                field.type_name = Some(nested_full_name.clone());
+                nested_messages.push((nested_name.clone(), nested_full_name, *fields));
+                nested_name
+            }
+            element_type => element_type.proto_type_name().into(),
+        };
+
+        // Scalar fields in proto3 only track presence when declared
+        // `optional`, which is implemented as a synthetic oneof.
+        let optional = column_type.nullable && !repeated && field.type_name.is_none();
+        if optional {
+            let oneof_index = i32::try_from(message.oneof_decl.len()).expect("fits in i32");
+            message.oneof_decl.push(OneofDescriptorProto {
+                name: Some(format!("_{field_name}")),
+                ..Default::default()
+            });
+            field.oneof_index = Some(oneof_index);
+            field.proto3_optional = Some(true);
+        }
+        let label = match (repeated, optional) {
+            (true, _) => "repeated ",
+            (false, true) => "optional ",
+            (false, false) => "",
+        };
+        writeln!(
+            text,
+            "{indent}  {label}{type_name} {field_name} = {number};"
+        )
+        .expect("writing to string cannot fail");
+        message.field.push(field);
+    }
+
+    for (nested_name, nested_full_name, fields) in nested_messages {
+        writeln!(text).expect("writing to string cannot fail");
+        let nested = derive_message(&nested_name, &nested_full_name, fields, text, depth + 1)?;
+        message.nested_type.push(nested);
+    }
+    writeln!(text, "{indent}}}").expect("writing to string cannot fail");
+    Ok(message)
+}
+
+/// Checks that every column can be encoded into the field of `message` that
+/// holds it.
+fn validate_message(
+    message: &MessageDescriptor,
+    columns: &[(ColumnName, ColumnType)],
+) -> Result<(), anyhow::Error> {
+    for (column_name, column_type) in columns {
+        let field = message
+            .get_field_by_name(&field_name(column_name))
+            .ok_or_else(|| {
+                anyhow!(
+                    "protobuf message {} has no field for column {}",
+                    message.full_name().quoted(),
+                    column_name.as_str().quoted()
+                )
+            })?;
+        let field_type =
+            FieldType::from_scalar_type(&column_type.scalar_type, true).with_context(|| {
+                format!(
+                    "column {} cannot be encoded as Protobuf",
+                    column_name.as_str().quoted()
+                )
+            })?;
+        let kind = field.kind();
+        let compatible = match &field_type {
+            FieldType::Repeated(element_type) => {
+                field.is_list() && element_type.is_compatible(&kind)
+            }
+            field_type => !field.is_list() && !field.is_map() && field_type.is_compatible(&kind),
+        };
+        if !compatible {
+            bail!(
+                "protobuf field {} cannot hold column {} of type {:?}",
+                field.full_name().quoted(),
+                column_name.as_str().quoted(),
+                column_type.scalar_type
+            );
+        }
+        if column_type.nullable && !field.is_list() && !field.supports_presence() {
+            bail!(
+                "protobuf field {} must be declared optional to hold nullable column {}",
+                field.full_name().quoted(),
+                column_name.as_str().quoted()
+            );
+        }
+
+        let element_type = match field_type {
+            FieldType::Repeated(element_type) => *element_type,
+            field_type => field_type,
+        };
+        if let (FieldType::Message(fields), Kind::Message(nested)) = (element_type, &kind) {
+            validate_message(nested, fields)?;
+        }
+    }
+    Ok(())
+}
+
+/// Encodes rows as a particular Protobuf message.
+pub struct MessageEncoder {
+    descriptor: MessageDescriptor,
+    columns: Vec<(ColumnName, ColumnType)>,
+    /// The Confluent wire format header that prefixes every message, if the
+    /// message's schema is in a schema registry.
+    header: Vec<u8>,
+}
+
+impl fmt::Debug for MessageEncoder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MessageEncoder")
+            .field("message", &self.descriptor.full_name())
+            .field("header", &self.header)
+            .finish()
+    }
+}
+
+impl MessageEncoder {
+    /// Constructs an encoder of rows with the given columns into the message
+    /// named `message_name` in the encoded `FileDescriptorSet` `descriptors`.
+    ///
+    /// If `schema_id` is the ID of the message's schema in a schema registry,
+    /// messages are prefixed with the Confluent wire format header.
+    ///
+    /// Returns an error if any column cannot be encoded into the field of the
+    /// message with the same name.
+    pub fn new(
+        descriptors: &[u8],
+        message_name: &str,
+        columns: Vec<(ColumnName, ColumnType)>,
+        schema_id: Option<i32>,
+    ) -> Result<Self, anyhow::Error> {
+        let pool = DescriptorPool::decode(descriptors).context("decoding file descriptor set")?;
+        let descriptor = pool.get_message_by_name(message_name).ok_or_else(|| {
+            anyhow!(
+                "protobuf message {} not found in file descriptor set",
+                message_name.quoted(),
+            )
+        })?;
+        validate_message(&descriptor, &columns)?;
+        let header = match schema_id {
+            Some(schema_id) => confluent_header(schema_id, &descriptor),
+            None => vec![],
+        };
+        Ok(MessageEncoder {
+            descriptor,
+            columns,
+            header,
+        })
+    }
+
+    /// Encodes `row`, which must match the columns the encoder was
+    /// constructed with.
+    ///
+    /// Returns an error if `row` contains a list with a NULL element.
+    pub fn encode(&self, row: Row) -> Result<Vec<u8>, anyhow::Error> {
+        let message = encode_message(&self.descriptor, row.iter(), &self.columns)?;
+        let mut buf = self.header.clone();
+        message
+            .encode(&mut buf)
+            .expect("writing to vec cannot fail");
+        Ok(buf)
+    }
+}
+
+/// Returns the Confluent wire format header of messages of type `descriptor`,
+/// whose schema has the ID `schema_id`.
+///
+/// The header is the magic byte (0), the 32-bit schema ID, and the path of
+/// indexes that leads to the message through the message types declared by
+/// the schema and their nested message types. The path is encoded as its
+/// length followed by the indexes, all as zigzag varints, except that the
+/// path of the first message in the schema is encoded as a single 0.
+///
+/// <https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format>
+fn confluent_header(schema_id: i32, descriptor: &MessageDescriptor) -> Vec<u8> {
+    let mut indexes = vec![];
+    let mut message = descriptor.clone();
+    loop {
+        let (index, parent) = match message.parent_message() {
+            Some(parent) => (
+                parent.child_messages().position(|m| m == message),
+                Some(parent),
+            ),
+            None => (
+                message.parent_file().messages().position(|m| m == message),
+                None,
+            ),
+        };
+        indexes.push(index.expect("message is declared by its parent"));
+        match parent {
+            Some(parent) => message = parent,
+            None => break,
+        }
+    }
+    indexes.reverse();
+
+    let mut header = vec![];
+    header.write_u8(0).expect("writing to vec cannot fail");
+    header
+        .write_i32::<NetworkEndian>(schema_id)
+        .expect("writing to vec cannot fail");
+    if indexes == [0] {
+        header.write_u8(0).expect("writing to vec cannot fail");
+    } else {
+        // The zigzag encoding of a non-negative number is twice the number.
+        encode_varint(u64::cast_from(indexes.len()) << 1, &mut header);
+        for index in indexes {
+            encode_varint(u64::cast_from(index) << 1, &mut header);
+        }
+    }
+    header
+}
+
+fn encode_message<'a, I>(
+    descriptor: &MessageDescriptor,
+    datums: I,
+    columns: &[(ColumnName, ColumnType)],
+) -> Result<DynamicMessage, anyhow::Error>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut message = DynamicMessage::new(descriptor.clone());
+    for (datum, (column_name, column_type)) in datums.into_iter().zip(columns) {
+        if datum.is_null() {
+            continue;
+        }
+        let field = descriptor
+            .get_field_by_name(&field_name(column_name))
+            .expect("validated when constructing the encoder");
+        let kind = field.kind();
+        let value = match &column_type.scalar_type {
+            ScalarType::List { element_type, .. } => Value::List(
+                datum
+                    .unwrap_list()
+                    .iter()
+                    .map(|datum| match datum {
+                        Datum::Null => Err(anyhow!(
+                            "column {} contains a list with a NULL element, which cannot be \
+                             encoded as Protobuf",
+                            column_name.as_str().quoted()
+                        )),
+                        datum => encode_value(datum, element_type, &kind),
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            scalar_type => encode_value(datum, scalar_type, &kind)?,
+        };
+        message.set_field(&field, value);
+    }
+    Ok(message)
+}
+
+/// Encodes a non-NULL datum into a value of a field of kind `kind`.
+fn encode_value(
+    datum: Datum,
+    scalar_type: &ScalarType,
+    kind: &Kind,
+) -> Result<Value, anyhow::Error> {
+    Ok(match scalar_type {
+        ScalarType::Bool => Value::Bool(datum.unwrap_bool()),
+        ScalarType::Int16 => Value::I32(datum.unwrap_int16().into()),
+        ScalarType::Int32 => Value::I32(datum.unwrap_int32()),
+        ScalarType::Int64 => Value::I64(datum.unwrap_int64()),
+        ScalarType::PgLegacyChar => Value::U32(datum.unwrap_uint8().into()),
+        ScalarType::UInt16 => Value::U32(datum.unwrap_uint16().into()),
+        ScalarType::UInt32
+        | ScalarType::Oid
+        | ScalarType::RegClass
+        | ScalarType::RegProc
+        | ScalarType::RegType => Value::U32(datum.unwrap_uint32()),
+        ScalarType::UInt64 => Value::U64(datum.unwrap_uint64()),
+        ScalarType::Float32 => Value::F32(datum.unwrap_float32()),
+        ScalarType::Float64 => Value::F64(datum.unwrap_float64()),
+        ScalarType::Bytes => Value::Bytes(Bytes::copy_from_slice(datum.unwrap_bytes())),
+        ScalarType::String | ScalarType::VarChar { .. } | ScalarType::PgLegacyName => {
+            Value::String(datum.unwrap_str().into())
+        }
+        ScalarType::Char { length } => {
+            Value::String(char::format_str_pad(datum.unwrap_str(), *length))
+        }
+        ScalarType::Record { fields, .. } => {
+            let descriptor = kind.as_message().expect("validated as message field");
+            Value::Message(encode_message(descriptor, &datum.unwrap_list(), fields)?)
+        }
+        scalar_type => {
+            let mut buf = String::new();
+            match scalar_type {
+                ScalarType::Numeric { .. } => {
+                    strconv::format_numeric(&mut buf, &datum.unwrap_numeric());
+                }
+                ScalarType::Date => {
+                    strconv::format_date(&mut buf, datum.unwrap_date());
+                }
+                ScalarType::Time => {
+                    strconv::format_time(&mut buf, datum.unwrap_time());
+                }
+                ScalarType::Timestamp { .. } => {
+                    strconv::format_timestamp(&mut buf, &datum.unwrap_timestamp());
+                }
+                ScalarType::TimestampTz { .. } => {
+                    strconv::format_timestamptz(&mut buf, &datum.unwrap_timestamptz());
+                }
+                ScalarType::Interval => {
+                    strconv::format_interval(&mut buf, datum.unwrap_interval());
+                }
+                ScalarType::Uuid => {
+                    strconv::format_uuid(&mut buf, datum.unwrap_uuid());
+                }
+                ScalarType::Jsonb => {
+                    strconv::format_jsonb(&mut buf, JsonbRef::from_datum(datum));
+                }
+                ScalarType::MzTimestamp => {
+                    strconv::format_mz_timestamp(&mut buf, datum.unwrap_mz_timestamp());
+                }
+                ScalarType::Range { .. } => buf = datum.unwrap_range().to_string(),
+                ScalarType::AclItem => buf = datum.unwrap_acl_item().to_string(),
+                ScalarType::MzAclItem => buf = datum.unwrap_mz_acl_item().to_string(),
+                _ => unreachable!("type {scalar_type:?} validated as encodable"),
+            }
+            Value::String(buf)
+        }
+    })
+}
+
+/// Encodes the keys and values of a sink as Protobuf messages.
+#[derive(Debug)]
+pub struct ProtobufEncoder {
+    key: Option<MessageEncoder>,
+    value: MessageEncoder,
+}
+
+impl ProtobufEncoder {
+    pub fn new(key: Option<MessageEncoder>, value: MessageEncoder) -> Self {
+        ProtobufEncoder { key, value }
+    }
+}
+
+impl Encode for ProtobufEncoder {
+    fn get_format_name(&self) -> &str {
+        "protobuf"
+    }
+
+    fn encode_key_unchecked(&self, row: Row) -> Vec<u8> {
+        self.encode_key(row).expect("key has no NULL list elements")
+    }
+
+    fn encode_value_unchecked(&self, row: Row) -> Vec<u8> {
+        self.encode_value(row)
+            .expect("value has no NULL list elements")
+    }
+
+    fn encode_key(&self, row: Row) -> Result<Vec<u8>, anyhow::Error> {
+        self.key
+            .as_ref()
+            .expect("key message must exist")
+            .encode(row)
+    }
+
+    fn encode_value(&self, row: Row) -> Result<Vec<u8>, anyhow::Error> {
+        self.value.encode(row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::RelationDesc;
+
+    use super::*;
+    use crate::encode::column_names_and_types;
+    use crate::protobuf::{DecodedDescriptors, Decoder};
+
+    #[mz_ore::test]
+    fn derived_schema_roundtrip() {
+        let desc = RelationDesc::empty()
+            .with_column("id", ScalarType::Int64.nullable(false))
+            .with_column("name", ScalarType::String.nullable(true))
+            .with_column(
+                "point",
+                ScalarType::Record {
+                    fields: vec![
+                        ("x".into(), ScalarType::Int32.nullable(false)),
+                        ("y".into(), ScalarType::Int32.nullable(false)),
+                    ],
+                    custom_id: None,
+                }
+                .nullable(true),
+            )
+            .with_column(
+                "tags",
+                ScalarType::List {
+                    element_type: Box::new(ScalarType::String),
+                    custom_id: None,
+                }
+                .nullable(false),
+            );
+        let columns = column_names_and_types(desc);
+        let (text, descriptors) = derive_schema("Row", &columns).unwrap();
+        assert_eq!(
+            text,
+            "syntax = \"proto3\";
+
+package com.materialize.sink;
+
+message Row {
+  int64 id = 1;
+  optional string name = 2;
+  Point point = 3;
+  repeated string tags = 4;
+
+  message Point {
+    int32 x = 1;
+    int32 y = 2;
+  }
+}
+"
+        );
+
+        let encoder = MessageEncoder::new(
+            &descriptors,
+            "com.materialize.sink.Row",
+            columns.clone(),
+            None,
+        )
+        .unwrap();
+        let mut row = Row::default();
+        let mut packer = row.packer();
+        packer.push(Datum::Int64(1));
+        packer.push(Datum::Null);
+        packer.push_list([Datum::Int32(2), Datum::Int32(3)]);
+        packer.push_list([Datum::String("a"), Datum::String("b")]);
+        let bytes = encoder.encode(row.clone()).unwrap();
+
+        let decoded =
+            DecodedDescriptors::from_bytes(&descriptors, "com.materialize.sink.Row".into())
+                .unwrap();
+        let mut decoder = Decoder::new(decoded, false).unwrap();
+        // The decoder presents absent scalar fields as their default value.
+        let mut expected = Row::default();
+        let mut packer = expected.packer();
+        packer.push(Datum::Int64(1));
+        packer.push(Datum::String(""));
+        packer.push_list([Datum::Int32(2), Datum::Int32(3)]);
+        packer.push_list([Datum::String("a"), Datum::String("b")]);
+        assert_eq!(decoder.decode(&bytes).unwrap(), Some(expected));
+    }
+
+    #[mz_ore::test]
+    fn incompatible_message() {
+        let (_text, descriptors) =
+            derive_schema("Row", &[("a".into(), ScalarType::String.nullable(false))]).unwrap();
+        let columns = vec![("a".into(), ScalarType::Int32.nullable(false))];
+        let err = MessageEncoder::new(&descriptors, "com.materialize.sink.Row", columns, None)
+            .unwrap_err();
+        assert!(err.to_string().contains("cannot hold column"), "{err}");
+
+        let columns = vec![("b".into(), ScalarType::String.nullable(false))];
+        let err = MessageEncoder::new(&descriptors, "com.materialize.sink.Row", columns, None)
+            .unwrap_err();
+        assert!(err.to_string().contains("has no field for column"), "{err}");
+
+        let columns = vec![("a".into(), ScalarType::String.nullable(true))];
+        let err = MessageEncoder::new(&descriptors, "com.materialize.sink.Row", columns, None)
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("must be declared optional"),
+            "{err}"
+        );
+    }
+
+    #[mz_ore::test]
+    fn null_list_element() {
+        let columns = vec![(
+            "tags".into(),
+            ScalarType::List {
+                element_type: Box::new(ScalarType::String),
+                custom_id: None,
+            }
+            .nullable(false),
+        )];
+        let (_text, descriptors) = derive_schema("Row", &columns).unwrap();
+        let encoder =
+            MessageEncoder::new(&descriptors, "com.materialize.sink.Row", columns, None).unwrap();
+        let mut row = Row::default();
+        row.packer().push_list([Datum::String("a"), Datum::Null]);
+        let err = encoder.encode(row).unwrap_err();
+        assert!(err.to_string().contains("NULL element"), "{err}");
+    }
+
+    #[mz_ore::test]
+    fn confluent_header() {
+        let point = vec![
+            ("x".into(), ScalarType::Int32.nullable(false)),
+            ("y".into(), ScalarType::Int32.nullable(false)),
+        ];
+        let columns = vec![(
+            "point".into(),
+            ScalarType::Record {
+                fields: point.clone(),
+                custom_id: None,
+            }
+            .nullable(false),
+        )];
+        let (_text, descriptors) = derive_schema("Row", &columns).unwrap();
+
+        // The first message in the schema has the path [0], which is encoded
+        // as a single 0.
+        let encoder =
+            MessageEncoder::new(&descriptors, "com.materialize.sink.Row", columns, Some(7))
+                .unwrap();
+        assert_eq!(encoder.header, [0, 0, 0, 0, 7, 0]);
+
+        // Nested messages are encoded as the zigzag length of their path
+        // followed by the zigzag indexes.
+        let encoder = MessageEncoder::new(
+            &descriptors,
+            "com.materialize.sink.Row.Point",
+            point,
+            Some(7),
+        )
+        .unwrap();
+        assert_eq!(encoder.header, [0, 0, 0, 0, 7, 4, 0, 0]);
+    }
+}
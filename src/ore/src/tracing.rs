@@ -40,7 +40,8 @@ use hyper::client::HttpConnector;
 use hyper_tls::HttpsConnector;
 use once_cell::sync::Lazy;
 use opentelemetry::global::Error;
-use opentelemetry::propagation::{Extractor, Injector};
+use opentelemetry::propagation::{Extractor, Injector, TextMapPropagator};
+use opentelemetry::trace::TraceContextExt;
 use opentelemetry::{global, KeyValue};
 use opentelemetry_sdk::propagation::TraceContextPropagator;
 use opentelemetry_sdk::{trace, Resource};
@@ -710,8 +711,33 @@ impl OpenTelemetryContext {
             inner: BTreeMap::new(),
         }
     }
+
+    /// Constructs a `Context` from the W3C Trace Context `traceparent` and
+    /// `tracestate` values supplied by a client.
+    ///
+    /// Returns `None` if `traceparent` does not describe a valid remote span.
+    pub fn from_w3c(traceparent: &str, tracestate: Option<&str>) -> Option<Self> {
+        let mut context = Self::empty();
+        context.set(W3C_TRACEPARENT, traceparent.into());
+        if let Some(tracestate) = tracestate {
+            context.set(W3C_TRACESTATE, tracestate.into());
+        }
+        let parent_cx = TraceContextPropagator::new().extract(&context);
+        if parent_cx.span().span_context().is_valid() {
+            Some(context)
+        } else {
+            None
+        }
+    }
 }
 
+/// The name of the W3C Trace Context header that identifies the parent span.
+pub const W3C_TRACEPARENT: &str = "traceparent";
+
+/// The name of the W3C Trace Context header that carries vendor-specific
+/// trace state.
+pub const W3C_TRACESTATE: &str = "tracestate";
+
 impl Extractor for OpenTelemetryContext {
     fn get(&self, key: &str) -> Option<&str> {
         self.inner.get(&key.to_lowercase()).map(|v| v.as_str())
@@ -767,6 +793,28 @@ mod tests {
     use tracing::Level;
     use tracing_subscriber::filter::{EnvFilter, LevelFilter, Targets};
 
+    #[crate::test]
+    fn w3c_context() {
+        let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let context = super::OpenTelemetryContext::from_w3c(traceparent, Some("mz=1"))
+            .expect("valid traceparent");
+        let map: std::collections::BTreeMap<_, _> = context.into();
+        assert_eq!(
+            map.get("traceparent").map(|s| s.as_str()),
+            Some(traceparent)
+        );
+        assert_eq!(map.get("tracestate").map(|s| s.as_str()), Some("mz=1"));
+
+        for invalid in [
+            "",
+            "garbage",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+        ] {
+            assert!(super::OpenTelemetryContext::from_w3c(invalid, None).is_none());
+        }
+    }
+
     #[crate::test]
     fn overriding_targets() {
         let user_defined = Targets::new().with_target("my_crate", Level::INFO);
@@ -383,6 +383,7 @@ impl Service for TransactorService {
                     blob_uri,
                     Box::new(config.clone()),
                     metrics.s3_blob.clone(),
+                    metrics.azure_blob.clone(),
                     config.configs.clone(),
                 )
                 .await
@@ -885,6 +885,7 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
                 .await;
                 batch_metrics.seconds.inc_by(start.elapsed().as_secs_f64());
                 batch_metrics.bytes.inc_by(u64::cast_from(payload_len));
+                shard_metrics.inc_blob_set_bytes(u64::cast_from(payload_len));
                 batch_metrics.goodbytes.inc_by(u64::cast_from(goodbytes));
                 let stats = stats.map(|(stats, stats_step_timing, trimmed_bytes)| {
                     batch_metrics
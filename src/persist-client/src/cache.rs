@@ -208,6 +208,7 @@ impl PersistClientCache {
                     x.key(),
                     Box::new(self.cfg.clone()),
                     self.metrics.s3_blob.clone(),
+                    self.metrics.azure_blob.clone(),
                     self.cfg.configs.clone(),
                 )
                 .await?;
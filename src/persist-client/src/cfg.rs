@@ -173,9 +173,6 @@ impl PersistConfig {
             configs,
             dynamic: Arc::new(DynamicConfig {
                 batch_builder_max_outstanding_parts: AtomicUsize::new(2),
-                compaction_heuristic_min_inputs: AtomicUsize::new(8),
-                compaction_heuristic_min_parts: AtomicUsize::new(8),
-                compaction_heuristic_min_updates: AtomicUsize::new(1024),
                 compaction_memory_bound_bytes: AtomicUsize::new(1024 * MiB),
                 gc_blob_delete_concurrency_limit: AtomicUsize::new(32),
                 state_versions_recent_live_diffs_limit: AtomicUsize::new(
@@ -281,6 +278,10 @@ pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
         .add(&crate::cfg::CRDB_CONNECT_TIMEOUT)
         .add(&crate::cfg::CRDB_TCP_USER_TIMEOUT)
         .add(&crate::internal::cache::BLOB_CACHE_MEM_LIMIT_BYTES)
+        .add(&crate::internal::compact::COMPACTION_EAGER_SHARDS)
+        .add(&crate::internal::compact::COMPACTION_HEURISTIC_MIN_INPUTS)
+        .add(&crate::internal::compact::COMPACTION_HEURISTIC_MIN_PARTS)
+        .add(&crate::internal::compact::COMPACTION_HEURISTIC_MIN_UPDATES)
         .add(&crate::internal::compact::COMPACTION_MINIMUM_TIMEOUT)
         .add(&crate::internal::machine::NEXT_LISTEN_BATCH_RETRYER_CLAMP)
         .add(&crate::internal::machine::NEXT_LISTEN_BATCH_RETRYER_FIXED_SLEEP)
@@ -406,9 +407,6 @@ impl PostgresClientKnobs for PersistConfig {
 #[derive(Debug)]
 pub struct DynamicConfig {
     batch_builder_max_outstanding_parts: AtomicUsize,
-    compaction_heuristic_min_inputs: AtomicUsize,
-    compaction_heuristic_min_parts: AtomicUsize,
-    compaction_heuristic_min_updates: AtomicUsize,
     compaction_memory_bound_bytes: AtomicUsize,
     gc_blob_delete_concurrency_limit: AtomicUsize,
     state_versions_recent_live_diffs_limit: AtomicUsize,
@@ -451,30 +449,6 @@ impl DynamicConfig {
             .load(Self::LOAD_ORDERING)
     }
 
-    /// In Compactor::compact_and_apply, we do the compaction (don't skip it)
-    /// if the number of inputs is at least this many. Compaction is performed
-    /// if any of the heuristic criteria are met (they are OR'd).
-    pub fn compaction_heuristic_min_inputs(&self) -> usize {
-        self.compaction_heuristic_min_inputs
-            .load(Self::LOAD_ORDERING)
-    }
-
-    /// In Compactor::compact_and_apply, we do the compaction (don't skip it)
-    /// if the number of batch parts is at least this many. Compaction is performed
-    /// if any of the heuristic criteria are met (they are OR'd).
-    pub fn compaction_heuristic_min_parts(&self) -> usize {
-        self.compaction_heuristic_min_parts
-            .load(Self::LOAD_ORDERING)
-    }
-
-    /// In Compactor::compact_and_apply, we do the compaction (don't skip it)
-    /// if the number of updates is at least this many. Compaction is performed
-    /// if any of the heuristic criteria are met (they are OR'd).
-    pub fn compaction_heuristic_min_updates(&self) -> usize {
-        self.compaction_heuristic_min_updates
-            .load(Self::LOAD_ORDERING)
-    }
-
     /// The upper bound on compaction's memory consumption. The value must be at
     /// least 4*`blob_target_size`. Increasing this value beyond the minimum allows
     /// compaction to merge together more runs at once, providing greater
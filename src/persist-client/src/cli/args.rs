@@ -151,6 +151,7 @@ pub(super) async fn make_blob(
         blob_uri,
         Box::new(cfg.clone()),
         metrics.s3_blob.clone(),
+        metrics.azure_blob.clone(),
         cfg.configs.clone(),
     )
     .await?;
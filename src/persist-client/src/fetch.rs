@@ -263,6 +263,7 @@ pub(crate) async fn fetch_batch_part_blob(
 
     read_metrics.part_count.inc();
     read_metrics.part_bytes.inc_by(u64::cast_from(value.len()));
+    shard_metrics.inc_blob_get_bytes(u64::cast_from(value.len()));
     read_metrics.seconds.inc_by(now.elapsed().as_secs_f64());
 
     Ok(value)
@@ -128,6 +128,56 @@ pub(crate) const COMPACTION_MINIMUM_TIMEOUT: Config<Duration> = Config::new(
     before timing it out (Materialize).",
 );
 
+/// In Compactor::compact_and_apply_background, we do the compaction (don't
+/// skip it) if the number of inputs is at least this many. Compaction is
+/// performed if any of the heuristic criteria are met (they are OR'd).
+pub(crate) const COMPACTION_HEURISTIC_MIN_INPUTS: Config<usize> = Config::new(
+    "persist_compaction_heuristic_min_inputs",
+    8,
+    "Don't skip compaction if we have more inputs than this many (Materialize).",
+);
+
+/// In Compactor::compact_and_apply_background, we do the compaction (don't
+/// skip it) if the number of batch parts is at least this many. Compaction is
+/// performed if any of the heuristic criteria are met (they are OR'd).
+pub(crate) const COMPACTION_HEURISTIC_MIN_PARTS: Config<usize> = Config::new(
+    "persist_compaction_heuristic_min_parts",
+    8,
+    "Don't skip compaction if we have more parts than this many (Materialize).",
+);
+
+/// In Compactor::compact_and_apply_background, we do the compaction (don't
+/// skip it) if the number of updates is at least this many. Compaction is
+/// performed if any of the heuristic criteria are met (they are OR'd).
+pub(crate) const COMPACTION_HEURISTIC_MIN_UPDATES: Config<usize> = Config::new(
+    "persist_compaction_heuristic_min_updates",
+    1024,
+    "Don't skip compaction if we have more updates than this many (Materialize).",
+);
+
+/// Shards for which every compaction request is performed, bypassing the
+/// heuristics above.
+///
+/// This is an escape hatch for hot shards whose reads have slowed down because
+/// compaction fell behind: it trades additional blob traffic for fewer, larger
+/// batches.
+pub(crate) const COMPACTION_EAGER_SHARDS: Config<String> = Config::new(
+    "persist_compaction_eager_shards",
+    "",
+    "\
+    Comma separated list of shard ids for which persist compaction skips its \
+    heuristics and compacts every request (Materialize).",
+);
+
+/// Returns whether compaction heuristics should be bypassed for `shard_id`.
+pub(crate) fn is_eager_compaction_shard(cfg: &PersistConfig, shard_id: &ShardId) -> bool {
+    let shard_id = shard_id.to_string();
+    COMPACTION_EAGER_SHARDS
+        .get(cfg)
+        .split(',')
+        .any(|x| x.trim() == shard_id)
+}
+
 impl<K, V, T, D> Compactor<K, V, T, D>
 where
     K: Debug + Codec,
@@ -245,11 +295,12 @@ where
         // were just written, but it does result in non-trivial blob traffic
         // (especially in aggregate). This heuristic is something we'll need to
         // tune over time.
-        let should_compact = req.inputs.len() >= self.cfg.dynamic.compaction_heuristic_min_inputs()
+        let should_compact = req.inputs.len() >= COMPACTION_HEURISTIC_MIN_INPUTS.get(&self.cfg)
             || req.inputs.iter().map(|x| x.parts.len()).sum::<usize>()
-                >= self.cfg.dynamic.compaction_heuristic_min_parts()
+                >= COMPACTION_HEURISTIC_MIN_PARTS.get(&self.cfg)
             || req.inputs.iter().map(|x| x.len).sum::<usize>()
-                >= self.cfg.dynamic.compaction_heuristic_min_updates();
+                >= COMPACTION_HEURISTIC_MIN_UPDATES.get(&self.cfg)
+            || is_eager_compaction_shard(&self.cfg, &machine.shard_id());
         if !should_compact {
             self.metrics.compaction.skipped.inc();
             return None;
@@ -814,6 +865,18 @@ mod tests {
 
     use super::*;
 
+    #[mz_ore::test]
+    fn eager_compaction_shards() {
+        let cfg = PersistConfig::new_for_tests();
+        let (a, b) = (ShardId::new(), ShardId::new());
+        assert!(!is_eager_compaction_shard(&cfg, &a));
+
+        cfg.set_config(&COMPACTION_EAGER_SHARDS, format!("{}, {}", a, b));
+        assert!(is_eager_compaction_shard(&cfg, &a));
+        assert!(is_eager_compaction_shard(&cfg, &b));
+        assert!(!is_eager_compaction_shard(&cfg, &ShardId::new()));
+    }
+
     // A regression test for a bug caught during development of #13160 (never
     // made it to main) where batches written by compaction would always have a
     // since of the minimum timestamp.
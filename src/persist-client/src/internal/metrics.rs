@@ -31,7 +31,7 @@ use mz_persist::location::{
     Atomicity, Blob, BlobMetadata, CaSResult, Consensus, ExternalError, ResultStream, SeqNo,
     VersionedData,
 };
-use mz_persist::metrics::{ColumnarMetrics, S3BlobMetrics};
+use mz_persist::metrics::{AzureBlobMetrics, ColumnarMetrics, S3BlobMetrics};
 use mz_persist::retry::RetryStream;
 use mz_persist_types::Codec64;
 use mz_postgres_client::metrics::PostgresClientMetrics;
@@ -43,6 +43,7 @@ use tokio_metrics::TaskMonitor;
 use tracing::error;
 
 use crate::internal::paths::BlobKey;
+use crate::usage::ShardIoUsage;
 use crate::{PersistConfig, ShardId};
 
 /// Prometheus monitoring metrics.
@@ -102,6 +103,8 @@ pub struct Metrics {
 
     /// Metrics for S3-backed blob implementation
     pub s3_blob: S3BlobMetrics,
+    /// Metrics for Azure-backed blob implementation
+    pub azure_blob: AzureBlobMetrics,
     /// Metrics for Postgres-backed consensus implementation
     pub postgres_consensus: PostgresClientMetrics,
 }
@@ -155,6 +158,7 @@ impl Metrics {
             columnar,
             sink: SinkMetrics::new(registry),
             s3_blob,
+            azure_blob: AzureBlobMetrics::new(registry),
             postgres_consensus: PostgresClientMetrics::new(registry, "mz_persist"),
             _vecs: vecs,
             _uptime: uptime,
@@ -1191,6 +1195,8 @@ pub struct ShardsMetrics {
     pubsub_push_diff_not_applied_out_of_order: mz_ore::metrics::IntCounterVec,
     blob_gets: mz_ore::metrics::IntCounterVec,
     blob_sets: mz_ore::metrics::IntCounterVec,
+    blob_get_bytes: mz_ore::metrics::IntCounterVec,
+    blob_set_bytes: mz_ore::metrics::IntCounterVec,
     live_writers: mz_ore::metrics::UIntGaugeVec,
     unconsolidated_snapshot: mz_ore::metrics::IntCounterVec,
     backpressure_emitted_bytes: IntCounterVec,
@@ -1200,6 +1206,10 @@ pub struct ShardsMetrics {
     // here as `Weak`. This allows us to discover if it's no longer in use and
     // so we can remove it from the map.
     shards: Arc<Mutex<BTreeMap<ShardId, Weak<ShardMetrics>>>>,
+    // Bytes read from and written to blob per shard since the last call to
+    // `take_io_usage`. Unlike `shards`, entries outlive the handles that
+    // recorded them, so that usage is not lost when a shard is dropped.
+    io_usage: Arc<Mutex<BTreeMap<ShardId, ShardIoUsage>>>,
 }
 
 impl ShardsMetrics {
@@ -1353,6 +1363,16 @@ impl ShardsMetrics {
                 help: "number of Blob::set calls for this shard",
                 var_labels: ["shard", "name"],
             )),
+            blob_get_bytes: registry.register(metric!(
+                name: "mz_persist_shard_blob_get_bytes",
+                help: "number of bytes returned by Blob::get calls for this shard",
+                var_labels: ["shard", "name"],
+            )),
+            blob_set_bytes: registry.register(metric!(
+                name: "mz_persist_shard_blob_set_bytes",
+                help: "number of bytes written by Blob::set calls for this shard",
+                var_labels: ["shard", "name"],
+            )),
             live_writers: registry.register(metric!(
                 name: "mz_persist_shard_live_writers",
                 help: "number of writers that have recently appended updates to this shard",
@@ -1381,6 +1401,7 @@ impl ShardsMetrics {
                 var_labels: ["shard", "name"],
             )),
             shards,
+            io_usage: Arc::new(Mutex::new(BTreeMap::new())),
         }
     }
 
@@ -1400,6 +1421,12 @@ impl ShardsMetrics {
         shard
     }
 
+    /// Returns the bytes read from and written to blob for each shard since the
+    /// previous call, resetting the accumulated usage.
+    pub fn take_io_usage(&self) -> BTreeMap<ShardId, ShardIoUsage> {
+        std::mem::take(&mut *self.io_usage.lock().expect("mutex poisoned"))
+    }
+
     fn compute<F: FnMut(&ShardMetrics)>(
         shards: &Arc<Mutex<BTreeMap<ShardId, Weak<ShardMetrics>>>>,
         mut f: F,
@@ -1451,6 +1478,9 @@ pub struct ShardMetrics {
         DeleteOnDropCounter<'static, AtomicU64, Vec<String>>,
     pub blob_gets: DeleteOnDropCounter<'static, AtomicU64, Vec<String>>,
     pub blob_sets: DeleteOnDropCounter<'static, AtomicU64, Vec<String>>,
+    blob_get_bytes: DeleteOnDropCounter<'static, AtomicU64, Vec<String>>,
+    blob_set_bytes: DeleteOnDropCounter<'static, AtomicU64, Vec<String>>,
+    io_usage: Arc<Mutex<BTreeMap<ShardId, ShardIoUsage>>>,
     pub live_writers: DeleteOnDropGauge<'static, AtomicU64, Vec<String>>,
     pub unconsolidated_snapshot: DeleteOnDropCounter<'static, AtomicU64, Vec<String>>,
     pub backpressure_emitted_bytes: Arc<DeleteOnDropCounter<'static, AtomicU64, Vec<String>>>,
@@ -1545,6 +1575,13 @@ impl ShardMetrics {
             blob_sets: shards_metrics
                 .blob_sets
                 .get_delete_on_drop_counter(vec![shard.clone(), name.to_string()]),
+            blob_get_bytes: shards_metrics
+                .blob_get_bytes
+                .get_delete_on_drop_counter(vec![shard.clone(), name.to_string()]),
+            blob_set_bytes: shards_metrics
+                .blob_set_bytes
+                .get_delete_on_drop_counter(vec![shard.clone(), name.to_string()]),
+            io_usage: Arc::clone(&shards_metrics.io_usage),
             live_writers: shards_metrics
                 .live_writers
                 .get_delete_on_drop_gauge(vec![shard.clone(), name.to_string()]),
@@ -1569,6 +1606,20 @@ impl ShardMetrics {
         }
     }
 
+    /// Records that `bytes` were read from blob for this shard.
+    pub fn inc_blob_get_bytes(&self, bytes: u64) {
+        self.blob_get_bytes.inc_by(bytes);
+        let mut io_usage = self.io_usage.lock().expect("mutex poisoned");
+        io_usage.entry(self.shard_id).or_default().bytes_read += bytes;
+    }
+
+    /// Records that `bytes` were written to blob for this shard.
+    pub fn inc_blob_set_bytes(&self, bytes: u64) {
+        self.blob_set_bytes.inc_by(bytes);
+        let mut io_usage = self.io_usage.lock().expect("mutex poisoned");
+        io_usage.entry(self.shard_id).or_default().bytes_written += bytes;
+    }
+
     pub fn set_since<T: Codec64>(&self, since: &Antichain<T>) {
         self.since.set(encode_ts_metric(since))
     }
@@ -141,6 +141,35 @@ impl<T> Trace<T> {
         ret
     }
 
+    /// The number of spine batches whose parts are still awaiting a merge.
+    ///
+    /// Each of these corresponds to an outstanding compaction request, so this
+    /// is a measure of how far behind compaction is.
+    pub fn num_fueled_merges(&self) -> usize {
+        let mut ret = 0;
+        self.spine.map_batches(|b| {
+            if let SpineBatch::Fueled { .. } = b {
+                ret += 1;
+            }
+        });
+        ret
+    }
+
+    /// The encoded size of all parts in spine batches still awaiting a merge.
+    pub fn fueled_merge_bytes(&self) -> usize {
+        let mut ret = 0;
+        self.spine.map_batches(|b| {
+            if let SpineBatch::Fueled { parts, .. } = b {
+                for b in parts.iter() {
+                    for part in b.batch.parts.iter() {
+                        ret += part.encoded_size_bytes;
+                    }
+                }
+            }
+        });
+        ret
+    }
+
     #[cfg(test)]
     pub fn num_hollow_batches(&self) -> usize {
         let mut ret = 0;
@@ -16,6 +16,7 @@ use std::time::Instant;
 use futures::stream::{FuturesUnordered, StreamExt};
 use mz_ore::cast::CastFrom;
 use mz_persist::location::Blob;
+use timely::progress::Antichain;
 use tokio::sync::Semaphore;
 use tracing::{error, info};
 
@@ -151,6 +152,41 @@ impl ShardBlobUsage {
     }
 }
 
+/// The number of bytes a process read from and wrote to blob for a shard.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ShardIoUsage {
+    /// Bytes of batch parts fetched from blob.
+    pub bytes_read: u64,
+    /// Bytes of batch parts written to blob, including those written by
+    /// compaction.
+    pub bytes_written: u64,
+}
+
+/// A summary of the physical layout of a shard as of its most recent state.
+///
+/// Useful for diagnosing shards whose reads have slowed down because
+/// compaction has fallen behind.
+#[derive(Clone, Debug)]
+pub struct ShardHealth {
+    /// The since frontier of the shard.
+    pub since: Antichain<u64>,
+    /// The upper frontier of the shard.
+    pub upper: Antichain<u64>,
+    /// The number of batches referenced by state.
+    pub batch_count: usize,
+    /// The number of batches in the shard's spine. A spine batch is made up of
+    /// one or more batches that are (or are waiting to be) merged together.
+    pub spine_batch_count: usize,
+    /// The number of blob parts referenced by state.
+    pub part_count: usize,
+    /// Byte size of all batch parts referenced by state.
+    pub size_bytes: u64,
+    /// Byte size of the batch parts that are waiting to be compacted.
+    pub uncompacted_bytes: u64,
+    /// The number of outstanding compaction requests.
+    pub compaction_backlog: usize,
+}
+
 /// Provides access to storage usage metrics for a specific Blob
 #[derive(Clone, Debug)]
 pub struct StorageUsageClient {
@@ -277,6 +313,75 @@ impl StorageUsageClient {
         ShardsUsageReferenced { by_shard }
     }
 
+    /// Returns the bytes this process read from and wrote to blob for each shard since the
+    /// previous call, resetting the accumulated usage.
+    ///
+    /// Usage is accumulated for all clients that share this client's metrics, which is
+    /// typically every client opened from the same [crate::cache::PersistClientCache].
+    pub fn take_io_usage(&self) -> BTreeMap<ShardId, ShardIoUsage> {
+        self.metrics.shards.take_io_usage()
+    }
+
+    /// Computes [ShardHealth] for a single shard from its most recent state.
+    ///
+    /// Returns `None` if the shard has not been initialized.
+    pub async fn shard_health(&self, shard_id: ShardId) -> Option<ShardHealth> {
+        let diffs = self
+            .state_versions
+            .fetch_recent_live_diffs::<u64>(&shard_id)
+            .await;
+        if diffs.0.is_empty() {
+            return None;
+        }
+        let state = self
+            .state_versions
+            .fetch_current_state::<u64>(&shard_id, diffs.0)
+            .await
+            .check_ts_codec(&shard_id)
+            .expect("ts should be a u64 in all prod shards");
+
+        let sizes = state.size_metrics();
+        let trace = &state.collections.trace;
+        Some(ShardHealth {
+            since: trace.since().clone(),
+            upper: trace.upper().clone(),
+            batch_count: sizes.hollow_batch_count,
+            spine_batch_count: trace.num_spine_batches(),
+            part_count: sizes.batch_part_count,
+            size_bytes: u64::cast_from(sizes.state_batches_bytes),
+            uncompacted_bytes: u64::cast_from(trace.fueled_merge_bytes()),
+            compaction_backlog: trace.num_fueled_merges(),
+        })
+    }
+
+    /// Computes [ShardHealth] for a given set of shards, skipping any that have
+    /// not been initialized.
+    pub async fn shards_health<I>(&self, shard_ids: I) -> BTreeMap<ShardId, ShardHealth>
+    where
+        I: IntoIterator<Item = ShardId>,
+    {
+        let semaphore = Arc::new(Semaphore::new(
+            self.cfg.dynamic.usage_state_fetch_concurrency_limit(),
+        ));
+        let by_shard_futures = FuturesUnordered::new();
+        for shard_id in shard_ids {
+            let semaphore = Arc::clone(&semaphore);
+            let shard_health_fut = async move {
+                let _permit = semaphore
+                    .acquire()
+                    .await
+                    .expect("acquiring permit from open semaphore");
+                let shard_health = self.shard_health(shard_id).await;
+                shard_health.map(|x| (shard_id, x))
+            };
+            by_shard_futures.push(shard_health_fut);
+        }
+        by_shard_futures
+            .filter_map(|x| async move { x })
+            .collect()
+            .await
+    }
+
     /// Computes [ShardUsageAudit] for a single shard.
     ///
     /// Performs a full scan of [Blob] and [mz_persist::location::Consensus] to compute a full audit
@@ -845,6 +950,79 @@ mod tests {
         );
     }
 
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    async fn shard_health() {
+        let data = vec![
+            (("1".to_owned(), "one".to_owned()), 1, 1),
+            (("2".to_owned(), "two".to_owned()), 2, 1),
+            (("3".to_owned(), "three".to_owned()), 3, 1),
+        ];
+
+        let client = new_test_client().await;
+        let shard_id = ShardId::new();
+        let (mut write, _) = client
+            .expect_open::<String, String, u64, i64>(shard_id)
+            .await;
+        write.expect_append(&data[..1], vec![0], vec![2]).await;
+        write.expect_append(&data[1..], vec![2], vec![4]).await;
+
+        let usage = StorageUsageClient::open(client);
+        assert!(usage.shard_health(ShardId::new()).await.is_none());
+
+        let health = usage
+            .shard_health(shard_id)
+            .await
+            .expect("shard is initialized");
+        assert_eq!(health.since, Antichain::from_elem(0));
+        assert_eq!(health.upper, Antichain::from_elem(4));
+        assert_eq!(health.batch_count, 2);
+        assert_eq!(health.part_count, 2);
+        assert!(health.size_bytes > 0);
+        assert!(health.uncompacted_bytes <= health.size_bytes);
+
+        let shards_health = usage.shards_health([shard_id, ShardId::new()]).await;
+        assert_eq!(shards_health.len(), 1);
+        assert_eq!(
+            shards_health.get(&shard_id).map(|x| x.size_bytes),
+            Some(health.size_bytes)
+        );
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    async fn io_usage() {
+        let data = vec![
+            (("1".to_owned(), "one".to_owned()), 1, 1),
+            (("2".to_owned(), "two".to_owned()), 2, 1),
+            (("3".to_owned(), "three".to_owned()), 3, 1),
+        ];
+
+        let client = new_test_client().await;
+        let shard_id = ShardId::new();
+        let (mut write, mut read) = client
+            .expect_open::<String, String, u64, i64>(shard_id)
+            .await;
+        let usage = StorageUsageClient::open(client);
+
+        write.expect_append(&data[..], vec![0], vec![4]).await;
+        let io_usage = usage.take_io_usage();
+        let written = io_usage
+            .get(&shard_id)
+            .expect("shard was written")
+            .bytes_written;
+        assert!(written > 0);
+
+        // Usage is reset once taken.
+        assert_eq!(usage.take_io_usage().get(&shard_id), None);
+
+        assert_eq!(read.expect_snapshot_and_fetch(3).await.len(), 3);
+        let io_usage = usage.take_io_usage();
+        let shard_usage = io_usage.get(&shard_id).expect("shard was read");
+        assert!(shard_usage.bytes_read > 0);
+        assert_eq!(shard_usage.bytes_written, 0);
+    }
+
     /// This is just a sanity check for the overall flow of computing ShardUsage.
     /// The edge cases are exercised in separate tests.
     #[mz_ore::test(tokio::test)]
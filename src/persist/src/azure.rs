@@ -0,0 +1,478 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! An Azure Blob Storage implementation of [Blob] storage.
+
+use std::fmt::Debug;
+use std::future::IntoFuture;
+use std::time::{Duration, Instant};
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use azure_core::StatusCode;
+use azure_storage::{CloudLocation, StorageCredentials, EMULATOR_ACCOUNT};
+use azure_storage_blobs::prelude::{ClientBuilder, ContainerClient};
+use bytes::Bytes;
+use futures_util::{FutureExt, StreamExt};
+use mz_ore::bytes::{MaybeLgBytes, SegmentedBytes};
+use mz_ore::cast::CastFrom;
+use mz_ore::metrics::MetricsRegistry;
+use tracing::{debug, info};
+use url::Url;
+use uuid::Uuid;
+
+use crate::cfg::BlobKnobs;
+use crate::error::Error;
+use crate::location::{Atomicity, Blob, BlobMetadata, Determinate, ExternalError};
+use crate::metrics::AzureBlobMetrics;
+
+/// The suffix of the host of Azure Blob Storage accounts.
+const BLOB_HOST_SUFFIX: &str = ".blob.core.windows.net";
+
+/// The URL scheme that explicitly selects Azure Blob Storage, e.g. for the
+/// Azurite emulator, whose host can't be told apart from any other.
+const AZURE_SCHEME: &str = "azure";
+
+/// Reports whether `url` is the location of an Azure Blob Storage container:
+/// either an `https` URL of an Azure storage account or a URL with the
+/// explicit `azure` scheme.
+pub(crate) fn is_azure_location(url: &Url) -> bool {
+    match url.scheme() {
+        AZURE_SCHEME => true,
+        "https" => url
+            .host_str()
+            .map_or(false, |host| host.ends_with(BLOB_HOST_SUFFIX)),
+        _ => false,
+    }
+}
+
+/// Configuration for opening an [AzureBlob].
+#[derive(Clone, Debug)]
+pub struct AzureBlobConfig {
+    metrics: AzureBlobMetrics,
+    client: ContainerClient,
+    prefix: String,
+    operation_timeout: Duration,
+}
+
+impl AzureBlobConfig {
+    const EXTERNAL_TESTS_AZURE_CONTAINER: &'static str =
+        "MZ_PERSIST_EXTERNAL_STORAGE_TEST_AZURE_CONTAINER";
+
+    /// Returns a new [AzureBlobConfig] for use in production.
+    ///
+    /// Stores objects in the container identified by `url`, prepended with the
+    /// (possibly empty) prefix. `url` is either of the form
+    /// `https://<account>.blob.core.windows.net/<container>/<prefix>` or, for
+    /// the Azurite emulator, `azure://<host>:<port>/devstoreaccount1/<container>/<prefix>`.
+    ///
+    /// If the query of `url` contains a SAS token, it is used to authenticate.
+    /// Otherwise, credentials are discovered from the environment, e.g., from a
+    /// managed identity or the `AZURE_*` environment variables.
+    pub fn new(
+        url: &Url,
+        knobs: Box<dyn BlobKnobs>,
+        metrics: AzureBlobMetrics,
+    ) -> Result<Self, Error> {
+        if !is_azure_location(url) {
+            return Err(Error::from(format!(
+                "azure blob location must be in {} or use the {} scheme: {}",
+                BLOB_HOST_SUFFIX, AZURE_SCHEME, url
+            )));
+        }
+        let host = url
+            .host_str()
+            .ok_or_else(|| Error::from(format!("missing azure account: {}", url)))?;
+        let mut segments = url
+            .path_segments()
+            .ok_or_else(|| Error::from(format!("missing azure container: {}", url)))?;
+
+        let (location, credentials) = match host.strip_suffix(BLOB_HOST_SUFFIX) {
+            Some(account) => {
+                let credentials = match url.query() {
+                    Some(sas_token) => StorageCredentials::sas_token(sas_token)
+                        .map_err(|err| Error::from(format!("invalid azure sas token: {}", err)))?,
+                    None => StorageCredentials::token_credential(
+                        azure_identity::create_default_credential().map_err(|err| {
+                            Error::from(format!("failed to discover azure credentials: {}", err))
+                        })?,
+                    ),
+                };
+                let location = CloudLocation::Public {
+                    account: account.to_string(),
+                };
+                (location, credentials)
+            }
+            None => {
+                if segments.next() != Some(EMULATOR_ACCOUNT) {
+                    return Err(Error::from(format!(
+                        "azure blob location must be in {} or the emulator account {}: {}",
+                        BLOB_HOST_SUFFIX, EMULATOR_ACCOUNT, url
+                    )));
+                }
+                info!("persist using the azure blob storage emulator at {}", host);
+                let location = CloudLocation::Emulator {
+                    address: host.to_string(),
+                    port: url.port().unwrap_or(10000),
+                };
+                (location, StorageCredentials::emulator())
+            }
+        };
+
+        let container = segments
+            .next()
+            .filter(|container| !container.is_empty())
+            .ok_or_else(|| Error::from(format!("missing azure container: {}", url)))?;
+        let prefix = segments.collect::<Vec<_>>().join("/");
+        let client =
+            ClientBuilder::with_location(location, credentials).container_client(container);
+
+        Ok(AzureBlobConfig {
+            metrics,
+            client,
+            prefix,
+            operation_timeout: knobs.operation_timeout(),
+        })
+    }
+
+    /// Returns a new [AzureBlobConfig] for use in unit tests.
+    ///
+    /// Like the S3 tests, persist tests that use Azure Blob Storage are no-ops
+    /// unless the `MZ_PERSIST_EXTERNAL_STORAGE_TEST_AZURE_CONTAINER`
+    /// environment variable is set to the URL of a container, e.g. one served
+    /// by a local Azurite emulator.
+    pub fn new_for_test() -> Result<Option<Self>, Error> {
+        let container = match std::env::var(Self::EXTERNAL_TESTS_AZURE_CONTAINER) {
+            Ok(container) => container,
+            Err(_) => return Ok(None),
+        };
+        let url = Url::parse(&container)
+            .map_err(|err| Error::from(format!("invalid azure container url: {}", err)))?;
+
+        #[derive(Debug)]
+        struct TestBlobKnobs;
+        impl BlobKnobs for TestBlobKnobs {
+            fn operation_timeout(&self) -> Duration {
+                Duration::from_secs(60)
+            }
+
+            fn operation_attempt_timeout(&self) -> Duration {
+                Duration::from_secs(30)
+            }
+
+            fn connect_timeout(&self) -> Duration {
+                Duration::from_secs(10)
+            }
+
+            fn read_timeout(&self) -> Duration {
+                Duration::from_secs(10)
+            }
+
+            fn is_cc_active(&self) -> bool {
+                false
+            }
+        }
+
+        let metrics = AzureBlobMetrics::new(&MetricsRegistry::new());
+        let mut config = AzureBlobConfig::new(&url, Box::new(TestBlobKnobs), metrics)?;
+        // Give each test a unique prefix so they don't conflict.
+        config.prefix = Uuid::new_v4().to_string();
+        Ok(Some(config))
+    }
+}
+
+/// Implementation of [Blob] backed by Azure Blob Storage.
+#[derive(Debug)]
+pub struct AzureBlob {
+    metrics: AzureBlobMetrics,
+    client: ContainerClient,
+    prefix: String,
+    operation_timeout: Duration,
+}
+
+impl AzureBlob {
+    /// Opens the given location for non-exclusive read-write access.
+    pub async fn open(config: AzureBlobConfig) -> Result<Self, ExternalError> {
+        let ret = AzureBlob {
+            metrics: config.metrics,
+            client: config.client,
+            prefix: config.prefix,
+            operation_timeout: config.operation_timeout,
+        };
+        // Connect before returning success. We don't particularly care about
+        // what's stored in this blob (nothing writes to it, so presumably it's
+        // empty) just that we were able and allowed to fetch it.
+        let _ = ret.get("HEALTH_CHECK").await?;
+        Ok(ret)
+    }
+
+    fn get_path(&self, key: &str) -> String {
+        format!("{}/{}", self.prefix, key)
+    }
+
+    /// Runs a request against Azure, failing it once it exceeds the operation
+    /// timeout.
+    async fn request<T>(
+        &self,
+        name: &str,
+        request: impl IntoFuture<Output = azure_core::Result<T>>,
+    ) -> Result<Option<T>, ExternalError> {
+        match tokio::time::timeout(self.operation_timeout, request).await {
+            Ok(Ok(response)) => Ok(Some(response)),
+            Ok(Err(err)) if is_not_found(&err) => Ok(None),
+            Ok(Err(err)) => Err(ExternalError::from(anyhow!("azure {} err: {}", name, err))),
+            Err(_) => {
+                self.metrics.operation_timeouts.inc();
+                Err(ExternalError::from(anyhow!(
+                    "azure {} timed out after {:?}",
+                    name,
+                    self.operation_timeout
+                )))
+            }
+        }
+    }
+}
+
+/// Reports whether an Azure request failed because the blob does not exist.
+fn is_not_found(err: &azure_core::Error) -> bool {
+    err.as_http_error()
+        .map_or(false, |err| err.status() == StatusCode::NotFound)
+}
+
+#[async_trait]
+impl Blob for AzureBlob {
+    async fn get(&self, key: &str) -> Result<Option<SegmentedBytes>, ExternalError> {
+        let start = Instant::now();
+        let blob = self.client.blob_client(self.get_path(key));
+
+        // Large blobs are returned in several chunks, each of which becomes a
+        // segment of the result.
+        let mut segments = Vec::new();
+        let mut chunks = blob.get().into_stream();
+        loop {
+            self.metrics.get_part.inc();
+            let chunk = chunks.next().map(Option::transpose);
+            let Some(chunk) = self.request("get", chunk).await? else {
+                return Ok(None);
+            };
+            let Some(chunk) = chunk else {
+                break;
+            };
+            let data = self
+                .request("get body", chunk.data.collect())
+                .await?
+                .ok_or_else(|| ExternalError::from(anyhow!("azure get body err: not found")))?;
+            segments.push(MaybeLgBytes::Bytes(data));
+        }
+
+        debug!(
+            "azure get took {:?} ({} chunks)",
+            start.elapsed(),
+            segments.len()
+        );
+        Ok(Some(SegmentedBytes::from(segments)))
+    }
+
+    async fn list_keys_and_metadata(
+        &self,
+        key_prefix: &str,
+        f: &mut (dyn FnMut(BlobMetadata) + Send + Sync),
+    ) -> Result<(), ExternalError> {
+        // we only want to return keys that match the specified blob key prefix
+        let blob_key_prefix = self.get_path(key_prefix);
+        // but we want to exclude the shared root prefix from our returned keys,
+        // so only the blob key itself is passed in to `f`
+        let strippable_root_prefix = format!("{}/", self.prefix);
+
+        let mut pages = self
+            .client
+            .list_blobs()
+            .prefix(blob_key_prefix)
+            .into_stream();
+        loop {
+            self.metrics.list_objects.inc();
+            let page = match self
+                .request("list", pages.next().map(Option::transpose))
+                .await?
+            {
+                Some(Some(page)) => page,
+                Some(None) => break,
+                None => return Err(ExternalError::from(anyhow!("azure list err: not found"))),
+            };
+            for blob in page.blobs.blobs() {
+                let Some(key) = blob.name.strip_prefix(&strippable_root_prefix) else {
+                    return Err(ExternalError::from(anyhow!(
+                        "found key with invalid prefix: {}",
+                        blob.name
+                    )));
+                };
+                f(BlobMetadata {
+                    key,
+                    size_in_bytes: blob.properties.content_length,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn set(&self, key: &str, value: Bytes, _atomic: Atomicity) -> Result<(), ExternalError> {
+        // NB: Block blobs that are uploaded with a single request are always
+        // atomic, so we're free to ignore the atomic param.
+        let blob = self.client.blob_client(self.get_path(key));
+        self.metrics.set_single.inc();
+        self.request("put", blob.put_block_blob(value))
+            .await?
+            .ok_or_else(|| ExternalError::from(anyhow!("azure put err: not found")))?;
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<Option<usize>, ExternalError> {
+        // There is a race condition here where, if two delete calls for the
+        // same key occur simultaneously, both might think they did the actual
+        // deletion. This return value is only used for metrics, so it's
+        // unfortunate, but fine.
+        let blob = self.client.blob_client(self.get_path(key));
+        self.metrics.delete_head.inc();
+        let Some(properties) = self
+            .request("delete properties", blob.get_properties())
+            .await?
+        else {
+            return Ok(None);
+        };
+        self.metrics.delete_object.inc();
+        let _ = self.request("delete", blob.delete()).await?;
+        Ok(Some(usize::cast_from(
+            properties.blob.properties.content_length,
+        )))
+    }
+
+    async fn restore(&self, key: &str) -> Result<(), ExternalError> {
+        let blob = self.client.blob_client(self.get_path(key));
+        self.metrics.restore_head.inc();
+        if self
+            .request("restore properties", blob.get_properties())
+            .await?
+            .is_some()
+        {
+            return Ok(());
+        }
+        // Deleted blobs can only be restored if soft delete is enabled for the
+        // storage account. Like any other request, a transient failure is
+        // returned as is, so that the caller retries it.
+        self.metrics.undelete.inc();
+        match self.request("undelete", blob.undelete()).await? {
+            Some(_) => Ok(()),
+            None => Err(Determinate::new(anyhow!(
+                "unable to restore {key} in azure blob storage: no valid version exists"
+            ))
+            .into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing::info;
+
+    use crate::location::tests::blob_impl_test;
+
+    use super::*;
+
+    #[mz_ore::test(tokio::test(flavor = "multi_thread"))]
+    #[cfg_attr(coverage, ignore)] // https://github.com/MaterializeInc/materialize/issues/18898
+    #[cfg_attr(miri, ignore)] // error: unsupported operation: can't call foreign function `TLS_method` on OS `linux`
+    async fn azure_blob() -> Result<(), ExternalError> {
+        let config = match AzureBlobConfig::new_for_test()? {
+            Some(config) => config,
+            None => {
+                info!(
+                    "{} env not set: skipping test that uses external service",
+                    AzureBlobConfig::EXTERNAL_TESTS_AZURE_CONTAINER
+                );
+                return Ok(());
+            }
+        };
+
+        blob_impl_test(move |path| {
+            let path = path.to_owned();
+            let config = config.clone();
+            async move {
+                let config = AzureBlobConfig {
+                    prefix: format!("{}/azure_blob_impl_test/{}", config.prefix, path),
+                    ..config
+                };
+                AzureBlob::open(config).await
+            }
+        })
+        .await
+    }
+
+    #[mz_ore::test]
+    fn azure_blob_config() {
+        #[derive(Debug)]
+        struct TestBlobKnobs;
+        impl BlobKnobs for TestBlobKnobs {
+            fn operation_timeout(&self) -> Duration {
+                Duration::from_secs(1)
+            }
+
+            fn operation_attempt_timeout(&self) -> Duration {
+                Duration::from_secs(1)
+            }
+
+            fn connect_timeout(&self) -> Duration {
+                Duration::from_secs(1)
+            }
+
+            fn read_timeout(&self) -> Duration {
+                Duration::from_secs(1)
+            }
+
+            fn is_cc_active(&self) -> bool {
+                false
+            }
+        }
+
+        let config = |url: &str| {
+            let url = Url::parse(url).unwrap();
+            let metrics = AzureBlobMetrics::new(&MetricsRegistry::new());
+            AzureBlobConfig::new(&url, Box::new(TestBlobKnobs), metrics)
+        };
+
+        let emulator = config("azure://localhost:10000/devstoreaccount1/container/a/b").unwrap();
+        assert_eq!(emulator.prefix, "a/b");
+        assert_eq!(emulator.client.container_name(), "container");
+
+        let sas =
+            config("https://account.blob.core.windows.net/container?sv=2022-11-02&sig=x").unwrap();
+        assert_eq!(sas.prefix, "");
+
+        let explicit = config("azure://account.blob.core.windows.net/container/a").unwrap();
+        assert_eq!(explicit.prefix, "a");
+
+        assert!(is_azure_location(
+            &Url::parse("https://account.blob.core.windows.net/container").unwrap()
+        ));
+        assert!(!is_azure_location(
+            &Url::parse("https://account.example.com/container").unwrap()
+        ));
+
+        for invalid in [
+            "https://account.example.com/container",
+            "http://localhost:10000/devstoreaccount1/container",
+            "http://account.blob.core.windows.net/container",
+            "azure://localhost:10000/account/container",
+            "azure://localhost:10000/devstoreaccount1",
+            "https://account.blob.core.windows.net/",
+        ] {
+            assert!(config(invalid).is_err(), "{invalid}");
+        }
+    }
+}
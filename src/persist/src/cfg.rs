@@ -21,10 +21,11 @@ use url::Url;
 use mz_postgres_client::metrics::PostgresClientMetrics;
 use mz_postgres_client::PostgresClientKnobs;
 
+use crate::azure::{is_azure_location, AzureBlob, AzureBlobConfig};
 use crate::file::{FileBlob, FileBlobConfig};
 use crate::location::{Blob, Consensus, Determinate, ExternalError};
 use crate::mem::{MemBlob, MemBlobConfig, MemConsensus};
-use crate::metrics::S3BlobMetrics;
+use crate::metrics::{AzureBlobMetrics, S3BlobMetrics};
 use crate::postgres::{PostgresConsensus, PostgresConsensusConfig};
 use crate::s3::{S3Blob, S3BlobConfig};
 
@@ -44,6 +45,8 @@ pub enum BlobConfig {
     File(FileBlobConfig),
     /// Config for [S3Blob].
     S3(S3BlobConfig),
+    /// Config for [AzureBlob].
+    Azure(AzureBlobConfig),
     /// Config for [MemBlob], only available in testing to prevent
     /// footguns.
     Mem(bool),
@@ -69,6 +72,7 @@ impl BlobConfig {
         match self {
             BlobConfig::File(config) => Ok(Arc::new(FileBlob::open(config).await?)),
             BlobConfig::S3(config) => Ok(Arc::new(S3Blob::open(config).await?)),
+            BlobConfig::Azure(config) => Ok(Arc::new(AzureBlob::open(config).await?)),
             BlobConfig::Mem(tombstone) => {
                 Ok(Arc::new(MemBlob::open(MemBlobConfig::new(tombstone))))
             }
@@ -80,6 +84,7 @@ impl BlobConfig {
         value: &str,
         knobs: Box<dyn BlobKnobs>,
         metrics: S3BlobMetrics,
+        azure_metrics: AzureBlobMetrics,
         cfg: ConfigSet,
     ) -> Result<Self, ExternalError> {
         let url = Url::parse(value)
@@ -128,6 +133,13 @@ impl BlobConfig {
 
                 Ok(BlobConfig::S3(config))
             }
+            "azure" | "https" if is_azure_location(&url) => {
+                // The query of an Azure blob location is a SAS token, not a
+                // list of params.
+                query_params.clear();
+                let config = AzureBlobConfig::new(&url, knobs, azure_metrics)?;
+                Ok(BlobConfig::Azure(config))
+            }
             "mem" => {
                 if !cfg!(debug_assertions) {
                     warn!("persist unexpectedly using in-mem blob in a release binary");
@@ -75,6 +75,43 @@ impl S3BlobMetrics {
     }
 }
 
+/// Metrics specific to AzureBlob's internal workings.
+#[derive(Debug, Clone)]
+pub struct AzureBlobMetrics {
+    pub(crate) operation_timeouts: IntCounter,
+    pub(crate) get_part: IntCounter,
+    pub(crate) set_single: IntCounter,
+    pub(crate) delete_head: IntCounter,
+    pub(crate) delete_object: IntCounter,
+    pub(crate) list_objects: IntCounter,
+    pub(crate) restore_head: IntCounter,
+    pub(crate) undelete: IntCounter,
+}
+
+impl AzureBlobMetrics {
+    /// Returns a new [AzureBlobMetrics] instance connected to the given registry.
+    pub fn new(registry: &MetricsRegistry) -> Self {
+        let operations: IntCounterVec = registry.register(metric!(
+            name: "mz_persist_azure_operations",
+            help: "number of raw azure blob storage calls on behalf of Blob interface methods",
+            var_labels: ["op"],
+        ));
+        Self {
+            operation_timeouts: registry.register(metric!(
+                name: "mz_persist_azure_operation_timeouts",
+                help: "number of operation timeouts",
+            )),
+            get_part: operations.with_label_values(&["get_part"]),
+            set_single: operations.with_label_values(&["set_single"]),
+            delete_head: operations.with_label_values(&["delete_head"]),
+            delete_object: operations.with_label_values(&["delete_object"]),
+            list_objects: operations.with_label_values(&["list_objects"]),
+            restore_head: operations.with_label_values(&["restore_head"]),
+            undelete: operations.with_label_values(&["undelete"]),
+        }
+    }
+}
+
 /// Metrics for `ColumnarRecords`.
 #[derive(Debug)]
 pub struct ColumnarMetrics {
@@ -14,8 +14,8 @@ use bytes::BytesMut;
 use csv::{ByteRecord, ReaderBuilder};
 use mz_proto::{ProtoType, RustType, TryFromProtoError};
 use mz_repr::{Datum, RelationType, Row, RowArena};
-use proptest::prelude::any;
 use proptest::prelude::Arbitrary;
+use proptest::prelude::{any, Just};
 use proptest::strategy::{BoxedStrategy, Strategy, Union};
 use serde::Deserialize;
 use serde::Serialize;
@@ -436,6 +436,9 @@ impl<'a> RawIterator<'a> {
 pub enum CopyFormatParams<'a> {
     Text(CopyTextFormatParams<'a>),
     Csv(CopyCsvFormatParams<'a>),
+    /// Parquet is a columnar file format, so it can only be produced by
+    /// writers that see whole batches of rows, like `COPY ... TO <expr>`.
+    Parquet,
 }
 
 impl RustType<ProtoCopyFormatParams> for CopyFormatParams<'static> {
@@ -445,6 +448,7 @@ impl RustType<ProtoCopyFormatParams> for CopyFormatParams<'static> {
             kind: Some(match self {
                 Self::Text(f) => Kind::Text(f.into_proto()),
                 Self::Csv(f) => Kind::Csv(f.into_proto()),
+                Self::Parquet => Kind::Parquet(()),
             }),
         }
     }
@@ -454,6 +458,7 @@ impl RustType<ProtoCopyFormatParams> for CopyFormatParams<'static> {
         match proto.kind {
             Some(Kind::Text(f)) => Ok(Self::Text(f.into_rust()?)),
             Some(Kind::Csv(f)) => Ok(Self::Csv(f.into_rust()?)),
+            Some(Kind::Parquet(())) => Ok(Self::Parquet),
             None => Err(TryFromProtoError::missing_field(
                 "ProtoCopyFormatParams::kind",
             )),
@@ -469,6 +474,7 @@ impl Arbitrary for CopyFormatParams<'static> {
         Union::new(vec![
             any::<CopyTextFormatParams>().prop_map(Self::Text).boxed(),
             any::<CopyCsvFormatParams>().prop_map(Self::Csv).boxed(),
+            Just(Self::Parquet).boxed(),
         ])
     }
 }
@@ -482,6 +488,7 @@ pub fn decode_copy_format<'a>(
     match params {
         CopyFormatParams::Text(params) => decode_copy_format_text(data, column_types, params),
         CopyFormatParams::Csv(params) => decode_copy_format_csv(data, column_types, params),
+        CopyFormatParams::Parquet => Err(parquet_unsupported()),
     }
 }
 
@@ -495,10 +502,19 @@ pub fn encode_copy_format<'a>(
     match params {
         CopyFormatParams::Text(params) => encode_copy_row_text(params, row, typ, out),
         CopyFormatParams::Csv(params) => encode_copy_row_csv(params, row, typ, out),
+        CopyFormatParams::Parquet => Err(parquet_unsupported()),
         // TODO (mouli): Handle Binary format here as well?
     }
 }
 
+/// Parquet files cannot be encoded or decoded one row at a time.
+fn parquet_unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "PARQUET format cannot be encoded or decoded row by row",
+    )
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
 pub struct CopyTextFormatParams<'a> {
     pub null: Cow<'a, str>,
@@ -547,10 +563,22 @@ impl Arbitrary for CopyTextFormatParams<'static> {
 pub fn decode_copy_format_text(
     data: &[u8],
     column_types: &[mz_pgrepr::Type],
-    CopyTextFormatParams { null, delimiter }: CopyTextFormatParams,
+    params: CopyTextFormatParams,
 ) -> Result<Vec<Row>, io::Error> {
     let mut rows = Vec::new();
+    decode_copy_format_text_into(data, column_types, params, &mut rows)?;
+    Ok(rows)
+}
 
+/// Decodes `data` as text-formatted COPY data, appending the rows to `rows`.
+///
+/// Returns whether decoding stopped at an end of copy marker.
+fn decode_copy_format_text_into(
+    data: &[u8],
+    column_types: &[mz_pgrepr::Type],
+    CopyTextFormatParams { null, delimiter }: CopyTextFormatParams,
+    rows: &mut Vec<Row>,
+) -> Result<bool, io::Error> {
     // TODO: pass the `CopyTextFormatParams` to the `new` method
     let mut parser = CopyTextFormatParser::new(data, delimiter, &null);
     while !parser.is_eof() && !parser.is_end_of_copy_marker() {
@@ -578,7 +606,7 @@ pub fn decode_copy_format_text(
     }
     // Note that if there is any junk data after the end of copy marker, we drop
     // it on the floor as PG does.
-    Ok(rows)
+    Ok(parser.is_end_of_copy_marker())
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
@@ -683,6 +711,19 @@ impl<'a> CopyCsvFormatParams<'a> {
 }
 
 pub fn decode_copy_format_csv(
+    data: &[u8],
+    column_types: &[mz_pgrepr::Type],
+    params: CopyCsvFormatParams,
+) -> Result<Vec<Row>, io::Error> {
+    let mut rows = Vec::new();
+    decode_copy_format_csv_into(data, column_types, params, &mut rows)?;
+    Ok(rows)
+}
+
+/// Decodes `data` as CSV-formatted COPY data, appending the rows to `rows`.
+///
+/// Returns whether decoding stopped at an end of copy marker.
+fn decode_copy_format_csv_into(
     data: &[u8],
     column_types: &[mz_pgrepr::Type],
     CopyCsvFormatParams {
@@ -692,9 +733,8 @@ pub fn decode_copy_format_csv(
         null,
         header,
     }: CopyCsvFormatParams,
-) -> Result<Vec<Row>, io::Error> {
-    let mut rows = Vec::new();
-
+    rows: &mut Vec<Row>,
+) -> Result<bool, io::Error> {
     let (double_quote, escape) = if quote == escape {
         (true, None)
     } else {
@@ -718,7 +758,7 @@ pub fn decode_copy_format_csv(
 
     while rdr.read_byte_record(&mut record)? {
         if record.len() == 1 && record.iter().next() == Some(END_OF_COPY_MARKER) {
-            break;
+            return Ok(true);
         }
 
         match record.len().cmp(&column_types.len()) {
@@ -752,7 +792,163 @@ pub fn decode_copy_format_csv(
         rows.push(Row::pack(row));
     }
 
-    Ok(rows)
+    Ok(false)
+}
+
+/// Incrementally decodes the data sent by a client during `COPY ... FROM STDIN`.
+///
+/// Data may be pushed in arbitrarily sized chunks as it arrives. Complete rows
+/// are decoded eagerly, so only the trailing partial row needs to be buffered
+/// as raw bytes, and malformed input is reported without waiting for the
+/// client to finish sending.
+#[derive(Debug)]
+pub struct CopyFromDecoder<'a> {
+    column_types: Vec<mz_pgrepr::Type>,
+    params: CopyFormatParams<'a>,
+    /// Received bytes that do not yet form a complete row.
+    pending: Vec<u8>,
+    /// The offset in `pending` up to which it has been scanned for the end of
+    /// a row, so that each byte is only scanned once.
+    scanned: usize,
+    /// Whether the scan of `pending` ended within a quoted CSV field.
+    in_quotes: bool,
+    rows: Vec<Row>,
+    /// The sum of the `byte_len` of `rows`.
+    rows_size: usize,
+    /// Whether the end of copy marker has been decoded. Any subsequent data is
+    /// dropped on the floor, as PG does.
+    done: bool,
+}
+
+impl<'a> CopyFromDecoder<'a> {
+    pub fn new(column_types: Vec<mz_pgrepr::Type>, params: CopyFormatParams<'a>) -> Self {
+        CopyFromDecoder {
+            column_types,
+            params,
+            pending: Vec::new(),
+            scanned: 0,
+            in_quotes: false,
+            rows: Vec::new(),
+            rows_size: 0,
+            done: false,
+        }
+    }
+
+    /// Appends `data` to the input, decoding any rows it completes.
+    pub fn push(&mut self, data: &[u8]) -> Result<(), io::Error> {
+        if self.done {
+            return Ok(());
+        }
+        self.pending.extend_from_slice(data);
+        if let Some(end) = self.complete_rows_end() {
+            let chunk: Vec<u8> = self.pending.drain(..end).collect();
+            self.scanned -= end;
+            self.decode(&chunk)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the number of bytes held by the decoder, counting both decoded
+    /// rows and not yet decoded input.
+    pub fn buffered_bytes(&self) -> usize {
+        self.rows_size + self.pending.len()
+    }
+
+    /// Returns the rows decoded so far, which are no longer held by the
+    /// decoder.
+    pub fn take_rows(&mut self) -> Vec<Row> {
+        self.rows_size = 0;
+        std::mem::take(&mut self.rows)
+    }
+
+    /// Decodes any remaining input and returns all decoded rows.
+    pub fn finish(mut self) -> Result<Vec<Row>, io::Error> {
+        if !self.done && !self.pending.is_empty() {
+            let chunk = std::mem::take(&mut self.pending);
+            self.decode(&chunk)?;
+        }
+        Ok(self.rows)
+    }
+
+    fn decode(&mut self, data: &[u8]) -> Result<(), io::Error> {
+        let start = self.rows.len();
+        let done = match &mut self.params {
+            CopyFormatParams::Text(params) => decode_copy_format_text_into(
+                data,
+                &self.column_types,
+                params.clone(),
+                &mut self.rows,
+            )?,
+            CopyFormatParams::Csv(params) => {
+                let done = decode_copy_format_csv_into(
+                    data,
+                    &self.column_types,
+                    params.clone(),
+                    &mut self.rows,
+                )?;
+                // Only the first chunk can contain the header.
+                params.header = false;
+                done
+            }
+            CopyFormatParams::Parquet => return Err(parquet_unsupported()),
+        };
+        self.rows_size += self.rows[start..]
+            .iter()
+            .map(|row| row.byte_len())
+            .sum::<usize>();
+        if done {
+            self.done = true;
+            self.pending = Vec::new();
+            self.scanned = 0;
+        }
+        Ok(())
+    }
+
+    /// Returns the offset just past the last complete row in the pending
+    /// input, if there is one.
+    ///
+    /// A row is complete once we see its terminating newline, unless that
+    /// newline is escaped or, for CSV, within a quoted field. The scan resumes
+    /// where the previous one ended.
+    fn complete_rows_end(&mut self) -> Option<usize> {
+        let (quote, escape) = match &self.params {
+            CopyFormatParams::Text(_) => (None, b'\\'),
+            CopyFormatParams::Csv(params) => (Some(params.quote), params.escape),
+            // Everything is decoded at once, and rejected, in `decode`.
+            CopyFormatParams::Parquet => {
+                self.scanned = self.pending.len();
+                return Some(self.pending.len());
+            }
+        };
+        let mut end = None;
+        let mut in_quotes = self.in_quotes;
+        let mut i = self.scanned;
+        while i < self.pending.len() {
+            let b = self.pending[i];
+            // In CSV, the escape character is only meaningful within quotes,
+            // and when it is the quote character it just toggles the quoting
+            // state twice.
+            let is_escape = match quote {
+                None => b == escape,
+                Some(quote) => in_quotes && b == escape && escape != quote,
+            };
+            if is_escape {
+                // Skip the escaped byte. If it has not arrived yet, neither
+                // has the end of the row.
+                i += 2;
+                continue;
+            }
+            if Some(b) == quote {
+                in_quotes = !in_quotes;
+            } else if b == b'\n' && !in_quotes {
+                end = Some(i + 1);
+            }
+            i += 1;
+        }
+        self.scanned = i;
+        self.in_quotes = in_quotes;
+        end
+    }
 }
 
 #[cfg(test)]
@@ -965,6 +1161,45 @@ mod tests {
         );
     }
 
+    #[mz_ore::test]
+    fn test_copy_from_decoder() {
+        let column_types = vec![mz_pgrepr::Type::Int8, mz_pgrepr::Type::Text];
+        let tests: [(CopyFormatParams, &[u8]); 3] = [
+            (
+                CopyFormatParams::Text(CopyTextFormatParams::default()),
+                b"1\tone\n2\ttw\\no\n3\t\\N\n\\.\n4\tjunk\n",
+            ),
+            (
+                CopyFormatParams::Csv(CopyCsvFormatParams {
+                    header: true,
+                    ..Default::default()
+                }),
+                b"i,t\n1,one\n2,\"tw\no\"\n3,\"\"\"three\"\"\"\n4,",
+            ),
+            (
+                CopyFormatParams::Csv(CopyCsvFormatParams {
+                    escape: b'\\',
+                    ..Default::default()
+                }),
+                b"1,\"o\\\"\nne\"\n2,two\n\\.\n3,junk\n",
+            ),
+        ];
+        for (params, data) in tests {
+            let expected = decode_copy_format(data, &column_types, params.clone()).unwrap();
+            // The decoded rows must not depend on how the input is chunked.
+            for chunk_size in 1..=data.len() {
+                let mut decoder = CopyFromDecoder::new(column_types.clone(), params.clone());
+                let mut rows = Vec::new();
+                for chunk in data.chunks(chunk_size) {
+                    decoder.push(chunk).unwrap();
+                    rows.extend(decoder.take_rows());
+                }
+                rows.extend(decoder.finish().unwrap());
+                assert_eq!(rows, expected, "chunk size {chunk_size}");
+            }
+        }
+    }
+
     #[mz_ore::test]
     fn test_copy_csv_row() -> Result<(), io::Error> {
         let mut row = Row::default();
@@ -17,6 +17,7 @@ mod copy;
 
 pub use copy::{
     decode_copy_format, encode_copy_format, encode_copy_row_binary, encode_copy_row_text,
-    CopyCsvFormatParams, CopyFormatParams, CopyTextFormatParams, CopyTextFormatParser,
-    ProtoCopyCsvFormatParams, ProtoCopyFormatParams, ProtoCopyTextFormatParams,
+    CopyCsvFormatParams, CopyFormatParams, CopyFromDecoder, CopyTextFormatParams,
+    CopyTextFormatParser, ProtoCopyCsvFormatParams, ProtoCopyFormatParams,
+    ProtoCopyTextFormatParams,
 };
@@ -668,3 +668,28 @@ pub const VIEW_MZ_NOTICES_OID: u32 = 16941;
 pub const VIEW_MZ_NOTICES_REDACTED_OID: u32 = 16942;
 pub const INDEX_MZ_NOTICES_IND_OID: u32 = 16943;
 pub const ROLE_PUBLIC_OID: u32 = 16944;
+pub const TABLE_MZ_SOURCE_TIMESTAMP_POLICIES_OID: u32 = 16945;
+pub const VIEW_MZ_OBJECT_READINESS_OID: u32 = 16946;
+pub const VIEW_MZ_SOURCE_RETRIES_OID: u32 = 16947;
+pub const TABLE_MZ_CLUSTER_QUERY_QUEUES_OID: u32 = 16948;
+pub const TABLE_MZ_PERSIST_SHARD_HEALTH_OID: u32 = 16949;
+pub const TABLE_MZ_STORAGE_IO_USAGE_OID: u32 = 16950;
+pub const VIEW_MZ_STATEMENT_HISTORY_OID: u32 = 16951;
+pub const VIEW_MZ_STATEMENT_HISTORY_REDACTED_OID: u32 = 16952;
+pub const TABLE_MZ_INDEX_USAGE_OID: u32 = 16953;
+pub const TABLE_MZ_READ_HOLDS_OID: u32 = 16954;
+pub const TABLE_MZ_COORDINATOR_SLOW_MESSAGES_OID: u32 = 16955;
+pub const VIEW_MZ_SHOW_CREATE_REDACTED_OID: u32 = 16956;
+pub const VIEW_MZ_SOURCE_SNAPSHOT_PROGRESS_OID: u32 = 16957;
+pub const TABLE_MZ_PLAN_CHANGES_OID: u32 = 16958;
+pub const TABLE_MZ_NOTICE_TYPES_OID: u32 = 16959;
+pub const SOURCE_MZ_OBJECT_STATUS_HISTORY_OID: u32 = 16960;
+pub const VIEW_MZ_OBJECT_STATUSES_OID: u32 = 16961;
+pub const TABLE_MZ_CONTINUAL_TASKS_OID: u32 = 16962;
+pub const TABLE_MZ_USAGE_BY_CLUSTER_OID: u32 = 16963;
+pub const VIEW_MZ_USAGE_BY_ROLE_OID: u32 = 16964;
+pub const SOURCE_MZ_DATAFLOW_STATISTICS_HISTORY_OID: u32 = 16965;
+pub const VIEW_MZ_DATAFLOW_STATISTICS_RATES_OID: u32 = 16966;
+pub const TABLE_MZ_QUOTAS_OID: u32 = 16967;
+pub const VIEW_MZ_QUOTA_USAGE_OID: u32 = 16968;
+pub const TABLE_MZ_CONTINUAL_TASK_STATUSES_OID: u32 = 16969;
@@ -428,9 +428,18 @@ impl Value {
                 }
                 Ok(postgres_types::IsNull::No)
             }
-            // TODO: what is the binary format of vector types?
-            Value::Int2Vector { .. } => {
-                Err("binary encoding of int2vector is not implemented".into())
+            // PostgreSQL sends vector types as one-dimensional arrays whose
+            // lower bound is zero.
+            Value::Int2Vector { elements } => {
+                buf.put_i32(1);
+                buf.put_i32(0);
+                buf.put_u32(Type::Int2.oid());
+                buf.put_i32(pg_len("int2vector length", elements.len())?);
+                buf.put_i32(0);
+                for elem in elements {
+                    encode_element(buf, elem.as_ref(), &Type::Int2)?;
+                }
+                Ok(postgres_types::IsNull::No)
             }
             Value::Bool(b) => b.to_sql(&PgType::BOOL, buf),
             Value::Bytea(b) => b.to_sql(&PgType::BYTEA, buf),
@@ -691,7 +700,23 @@ impl Value {
                 let t: mz_repr::Timestamp = s.parse()?;
                 Ok(Value::MzTimestamp(t))
             }
-            Type::Range { .. } => Err("binary decoding of range types is not implemented".into()),
+            Type::Range { element_type } => {
+                let (flags, mut raw) = raw.split_first().ok_or("missing range flags")?;
+                let range = Range::from_pg_flag_bits(
+                    *flags,
+                    || -> Result<_, Box<dyn Error + Sync + Send>> {
+                        let len = raw.get(..4).ok_or("missing range bound length")?;
+                        let len = usize::try_from(i32::from_be_bytes(len.try_into()?))?;
+                        let bound = raw.get(4..4 + len).ok_or("missing range bound")?;
+                        raw = &raw[4 + len..];
+                        Value::decode_binary(element_type, bound).map(Box::new)
+                    },
+                )?;
+                if !raw.is_empty() {
+                    return Err("junk after range bounds".into());
+                }
+                Ok(Value::Range(range))
+            }
             Type::MzAclItem => {
                 let mz_acl_item = MzAclItem::decode_binary(raw)?;
                 Ok(Value::MzAclItem(mz_acl_item))
@@ -739,6 +764,32 @@ pub fn values_from_row(row: &Row, typ: &RelationType) -> Vec<Option<Value>> {
 mod tests {
     use super::*;
 
+    #[mz_ore::test]
+    fn range_binary_roundtrip() {
+        let ty = Type::Range {
+            element_type: Box::new(Type::Int4),
+        };
+        for text in ["empty", "[1,5)", "(,5)", "[1,)", "(,)"] {
+            let value = Value::decode_text(&ty, text.as_bytes()).unwrap();
+            let mut buf = BytesMut::new();
+            value.encode_binary(&ty, &mut buf).unwrap();
+            let decoded = Value::decode_binary(&ty, &buf).unwrap();
+            let mut decoded_text = BytesMut::new();
+            decoded.encode_text(&mut decoded_text);
+            assert_eq!(&decoded_text[..], text.as_bytes());
+        }
+
+        // A bound that is missing or followed by junk is an error.
+        let mut buf = BytesMut::new();
+        Value::decode_text(&ty, b"[1,5)")
+            .unwrap()
+            .encode_binary(&ty, &mut buf)
+            .unwrap();
+        assert!(Value::decode_binary(&ty, &buf[..buf.len() - 1]).is_err());
+        buf.extend_from_slice(&[0]);
+        assert!(Value::decode_binary(&ty, &buf).is_err());
+    }
+
     /// Verifies that we correctly print the chain of parsing errors, all the way through the stack.
     #[mz_ore::test]
     fn decode_text_error_smoke_test() {
@@ -16,6 +16,7 @@ mod codec;
 mod conn;
 mod format;
 mod message;
+mod scram;
 mod severity;
 
 pub use codec::{
@@ -28,4 +29,8 @@ pub use message::{
     ErrorResponse, FrontendMessage, FrontendStartupMessage, VERSIONS, VERSION_3, VERSION_CANCEL,
     VERSION_GSSENC, VERSION_SSL,
 };
+pub use scram::{
+    tls_server_end_point, ScramError, ScramExchange, ScramVerifier, SCRAM_SHA_256,
+    SCRAM_SHA_256_PLUS,
+};
 pub use severity::Severity;
@@ -182,6 +182,11 @@ pub enum FrontendMessage {
     Password {
         password: String,
     },
+
+    /// The body of an authentication message whose interpretation depends on
+    /// the authentication exchange in progress. It may be a password, or a
+    /// SASL initial or continuation response.
+    RawAuthentication(Vec<u8>),
 }
 
 impl FrontendMessage {
@@ -202,6 +207,7 @@ impl FrontendMessage {
             FrontendMessage::CopyDone => "copy_done",
             FrontendMessage::CopyFail(_) => "copy_fail",
             FrontendMessage::Password { .. } => "password",
+            FrontendMessage::RawAuthentication(_) => "raw_authentication",
         }
     }
 }
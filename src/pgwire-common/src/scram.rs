@@ -0,0 +1,551 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! SCRAM-SHA-256 password verifiers and the server side of the SCRAM
+//! authentication exchange.
+//!
+//! See [RFC 5802] and [RFC 7677] for the protocol, and the [PostgreSQL
+//! documentation] for how it is embedded in the PostgreSQL wire protocol.
+//!
+//! Passwords are used as-is, without the SASLprep normalization described in
+//! RFC 5802. PostgreSQL also falls back to the unnormalized password whenever
+//! normalization fails, and the two agree for passwords consisting only of
+//! printable ASCII characters.
+//!
+//! [RFC 5802]: https://datatracker.ietf.org/doc/html/rfc5802
+//! [RFC 7677]: https://datatracker.ietf.org/doc/html/rfc7677
+//! [PostgreSQL documentation]: https://www.postgresql.org/docs/current/sasl-authentication.html
+
+use std::fmt;
+
+use mz_ore::cast::CastFrom;
+use openssl::error::ErrorStack;
+use openssl::hash::MessageDigest;
+use openssl::memcmp;
+use openssl::nid::Nid;
+use openssl::pkcs5;
+use openssl::pkey::PKey;
+use openssl::rand;
+use openssl::sha;
+use openssl::sign::Signer;
+use openssl::x509::X509Ref;
+
+/// The name of the SCRAM-SHA-256 SASL mechanism.
+pub const SCRAM_SHA_256: &str = "SCRAM-SHA-256";
+
+/// The name of the SCRAM-SHA-256 SASL mechanism with channel binding.
+pub const SCRAM_SHA_256_PLUS: &str = "SCRAM-SHA-256-PLUS";
+
+/// The only channel binding type supported by PostgreSQL clients.
+const TLS_SERVER_END_POINT: &str = "tls-server-end-point";
+
+/// The number of PBKDF2 iterations used when hashing new passwords. Matches
+/// PostgreSQL's default for `scram_iterations`.
+const DEFAULT_ITERATIONS: u32 = 4096;
+
+/// The length of the random salt used when hashing new passwords, in bytes.
+const SALT_LEN: usize = 16;
+
+/// The length of the random portion of the server nonce, in bytes.
+const NONCE_LEN: usize = 18;
+
+const KEY_LEN: usize = 32;
+
+/// A SCRAM-SHA-256 password verifier.
+///
+/// A verifier allows checking a client's proof that it knows a password
+/// without storing the password itself. Verifiers are formatted identically to
+/// the verifiers PostgreSQL stores in `pg_authid.rolpassword`:
+///
+/// ```text
+/// SCRAM-SHA-256$<iterations>:<salt>$<stored key>:<server key>
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScramVerifier {
+    iterations: u32,
+    salt: Vec<u8>,
+    stored_key: [u8; KEY_LEN],
+    server_key: [u8; KEY_LEN],
+}
+
+impl ScramVerifier {
+    /// Computes a verifier for `password` using a freshly generated salt.
+    pub fn new(password: &str) -> Result<ScramVerifier, ErrorStack> {
+        let mut salt = vec![0; SALT_LEN];
+        rand::rand_bytes(&mut salt)?;
+        ScramVerifier::with_salt(password, salt, DEFAULT_ITERATIONS)
+    }
+
+    fn with_salt(
+        password: &str,
+        salt: Vec<u8>,
+        iterations: u32,
+    ) -> Result<ScramVerifier, ErrorStack> {
+        let mut salted_password = [0; KEY_LEN];
+        pkcs5::pbkdf2_hmac(
+            password.as_bytes(),
+            &salt,
+            usize::cast_from(iterations),
+            MessageDigest::sha256(),
+            &mut salted_password,
+        )?;
+        let client_key = hmac(&salted_password, b"Client Key")?;
+        let stored_key = sha::sha256(&client_key);
+        let server_key = hmac(&salted_password, b"Server Key")?;
+        Ok(ScramVerifier {
+            iterations,
+            salt,
+            stored_key,
+            server_key,
+        })
+    }
+
+    /// Parses a verifier in the format produced by this type's `Display`
+    /// implementation, returning `None` if `s` is not a valid verifier.
+    pub fn parse(s: &str) -> Option<ScramVerifier> {
+        let rest = s.strip_prefix(SCRAM_SHA_256)?.strip_prefix('$')?;
+        let (params, keys) = rest.split_once('$')?;
+        let (iterations, salt) = params.split_once(':')?;
+        let (stored_key, server_key) = keys.split_once(':')?;
+        let iterations = iterations.parse().ok().filter(|i| *i > 0)?;
+        let salt = base64::decode(salt).ok()?;
+        let stored_key = base64::decode(stored_key).ok()?.try_into().ok()?;
+        let server_key = base64::decode(server_key).ok()?.try_into().ok()?;
+        Some(ScramVerifier {
+            iterations,
+            salt,
+            stored_key,
+            server_key,
+        })
+    }
+}
+
+impl fmt::Display for ScramVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}${}:{}${}:{}",
+            SCRAM_SHA_256,
+            self.iterations,
+            base64::encode(&self.salt),
+            base64::encode(self.stored_key),
+            base64::encode(self.server_key),
+        )
+    }
+}
+
+/// Computes the `tls-server-end-point` channel binding data for a server
+/// certificate, as described in [RFC 5929].
+///
+/// [RFC 5929]: https://datatracker.ietf.org/doc/html/rfc5929#section-4.1
+pub fn tls_server_end_point(cert: &X509Ref) -> Result<Vec<u8>, ErrorStack> {
+    // The certificate is hashed with the hash function used in its signature,
+    // except that MD5 and SHA-1 are upgraded to SHA-256.
+    let digest = cert
+        .signature_algorithm()
+        .object()
+        .nid()
+        .signature_algorithms()
+        .and_then(|algs| match algs.digest {
+            Nid::MD5 | Nid::SHA1 => None,
+            nid => MessageDigest::from_nid(nid),
+        })
+        .unwrap_or_else(MessageDigest::sha256);
+    Ok(cert.digest(digest)?.to_vec())
+}
+
+/// An error in a SCRAM authentication exchange.
+#[derive(Debug, Clone)]
+pub enum ScramError {
+    /// The client sent a message that does not conform to the protocol.
+    Malformed(&'static str),
+    /// The client's channel binding settings do not match the server's.
+    ChannelBinding(&'static str),
+    /// The client's proof does not match the verifier.
+    InvalidProof,
+    /// An internal cryptographic operation failed.
+    Crypto(ErrorStack),
+}
+
+impl fmt::Display for ScramError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScramError::Malformed(detail) => write!(f, "malformed SCRAM message: {detail}"),
+            ScramError::ChannelBinding(detail) => {
+                write!(f, "SCRAM channel binding negotiation error: {detail}")
+            }
+            ScramError::InvalidProof => f.write_str("invalid SCRAM proof"),
+            ScramError::Crypto(e) => write!(f, "SCRAM cryptographic operation failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ScramError {}
+
+impl From<ErrorStack> for ScramError {
+    fn from(e: ErrorStack) -> ScramError {
+        ScramError::Crypto(e)
+    }
+}
+
+/// The server side of an in-progress SCRAM-SHA-256 exchange.
+///
+/// An exchange is started with the client's first message, which is carried in
+/// the `SASLInitialResponse` message, and yields the server's first message.
+/// It is completed with the client's final message, which is carried in a
+/// `SASLResponse` message, and yields the server's final message.
+#[derive(Debug)]
+pub struct ScramExchange {
+    verifier: ScramVerifier,
+    /// The expected value of the channel binding attribute in the client's
+    /// final message.
+    cbind_input: Vec<u8>,
+    nonce: String,
+    client_first_bare: String,
+    server_first: String,
+}
+
+impl ScramExchange {
+    /// Starts an exchange in which the client authenticates against
+    /// `verifier`.
+    ///
+    /// `mechanism` is the mechanism selected by the client, and
+    /// `client_first` the client's first message. `tls_server_end_point` is
+    /// the channel binding data for the connection, if the connection is
+    /// encrypted and the server offered [`SCRAM_SHA_256_PLUS`].
+    pub fn start(
+        verifier: ScramVerifier,
+        mechanism: &str,
+        client_first: &[u8],
+        tls_server_end_point: Option<&[u8]>,
+    ) -> Result<ScramExchange, ScramError> {
+        let mut server_nonce = [0; NONCE_LEN];
+        rand::rand_bytes(&mut server_nonce)?;
+        ScramExchange::start_with_server_nonce(
+            verifier,
+            mechanism,
+            client_first,
+            tls_server_end_point,
+            &base64::encode(server_nonce),
+        )
+    }
+
+    fn start_with_server_nonce(
+        verifier: ScramVerifier,
+        mechanism: &str,
+        client_first: &[u8],
+        tls_server_end_point: Option<&[u8]>,
+        server_nonce: &str,
+    ) -> Result<ScramExchange, ScramError> {
+        let client_first = std::str::from_utf8(client_first)
+            .map_err(|_| ScramError::Malformed("message is not valid UTF-8"))?;
+
+        // client-first-message = gs2-header client-first-message-bare
+        // gs2-header = gs2-cbind-flag "," [ authzid ] ","
+        let (cbind_flag, rest) = client_first
+            .split_once(',')
+            .ok_or(ScramError::Malformed("missing GS2 header"))?;
+        let (authzid, client_first_bare) = rest
+            .split_once(',')
+            .ok_or(ScramError::Malformed("missing GS2 header"))?;
+        let gs2_header = &client_first[..cbind_flag.len() + authzid.len() + 2];
+
+        let cbind_data = match (mechanism, cbind_flag) {
+            (SCRAM_SHA_256_PLUS, flag) => {
+                let Some(cbind_type) = flag.strip_prefix("p=") else {
+                    return Err(ScramError::ChannelBinding(
+                        "client selected SCRAM-SHA-256-PLUS without channel binding",
+                    ));
+                };
+                if cbind_type != TLS_SERVER_END_POINT {
+                    return Err(ScramError::ChannelBinding(
+                        "unsupported channel binding type",
+                    ));
+                }
+                let Some(data) = tls_server_end_point else {
+                    return Err(ScramError::ChannelBinding(
+                        "channel binding is only supported over TLS",
+                    ));
+                };
+                data
+            }
+            (SCRAM_SHA_256, "n") => &[][..],
+            (SCRAM_SHA_256, "y") => {
+                // The client supports channel binding but believes the
+                // server does not. If the server did offer channel binding,
+                // an attacker must have stripped it from the offer.
+                if tls_server_end_point.is_some() {
+                    return Err(ScramError::ChannelBinding(
+                        "client supports channel binding, but the server offered it",
+                    ));
+                }
+                &[][..]
+            }
+            (SCRAM_SHA_256, flag) if flag.starts_with("p=") => {
+                return Err(ScramError::ChannelBinding(
+                    "client requested channel binding without selecting SCRAM-SHA-256-PLUS",
+                ));
+            }
+            (SCRAM_SHA_256, _) => {
+                return Err(ScramError::Malformed("invalid channel binding flag"));
+            }
+            _ => return Err(ScramError::Malformed("unsupported SASL mechanism")),
+        };
+        if !authzid.is_empty() {
+            return Err(ScramError::Malformed(
+                "authorization identities are not supported",
+            ));
+        }
+
+        // client-first-message-bare = [reserved-mext ","] username "," nonce ["," extensions]
+        //
+        // The username is ignored in favor of the user specified in the
+        // startup message, as in PostgreSQL.
+        let mut attrs = client_first_bare.split(',');
+        match attrs.next() {
+            Some(attr) if attr.starts_with("n=") => (),
+            Some(attr) if attr.starts_with("m=") => {
+                return Err(ScramError::Malformed(
+                    "mandatory extensions are not supported",
+                ))
+            }
+            _ => return Err(ScramError::Malformed("missing username attribute")),
+        }
+        let client_nonce = attrs
+            .next()
+            .and_then(|attr| attr.strip_prefix("r="))
+            .filter(|nonce| !nonce.is_empty() && nonce.bytes().all(is_printable))
+            .ok_or(ScramError::Malformed("missing or invalid nonce attribute"))?;
+
+        let nonce = format!("{client_nonce}{server_nonce}");
+        let server_first = format!(
+            "r={nonce},s={},i={}",
+            base64::encode(&verifier.salt),
+            verifier.iterations
+        );
+
+        let mut cbind_input = gs2_header.as_bytes().to_vec();
+        cbind_input.extend(cbind_data);
+
+        Ok(ScramExchange {
+            verifier,
+            cbind_input,
+            nonce,
+            client_first_bare: client_first_bare.into(),
+            server_first,
+        })
+    }
+
+    /// Returns the server's first message, to be sent to the client in an
+    /// `AuthenticationSASLContinue` message.
+    pub fn server_first_message(&self) -> &str {
+        &self.server_first
+    }
+
+    /// Completes the exchange by verifying the client's final message.
+    ///
+    /// If the client proved that it knows the password, returns the server's
+    /// final message, to be sent to the client in an `AuthenticationSASLFinal`
+    /// message.
+    pub fn finish(self, client_final: &[u8]) -> Result<String, ScramError> {
+        let client_final = std::str::from_utf8(client_final)
+            .map_err(|_| ScramError::Malformed("message is not valid UTF-8"))?;
+
+        // client-final-message = client-final-message-without-proof "," proof
+        // client-final-message-without-proof = channel-binding "," nonce ["," extensions]
+        let (without_proof, proof) = client_final
+            .rsplit_once(',')
+            .ok_or(ScramError::Malformed("missing proof attribute"))?;
+        let proof = proof
+            .strip_prefix("p=")
+            .and_then(|proof| base64::decode(proof).ok())
+            .filter(|proof| proof.len() == KEY_LEN)
+            .ok_or(ScramError::Malformed("missing or invalid proof attribute"))?;
+        let mut attrs = without_proof.split(',');
+        let cbind_input = attrs
+            .next()
+            .and_then(|attr| attr.strip_prefix("c="))
+            .and_then(|cbind| base64::decode(cbind).ok())
+            .ok_or(ScramError::Malformed(
+                "missing or invalid channel binding attribute",
+            ))?;
+        if cbind_input != self.cbind_input {
+            return Err(ScramError::ChannelBinding(
+                "channel binding data does not match",
+            ));
+        }
+        let nonce = attrs
+            .next()
+            .and_then(|attr| attr.strip_prefix("r="))
+            .ok_or(ScramError::Malformed("missing nonce attribute"))?;
+        if nonce != self.nonce {
+            return Err(ScramError::Malformed("nonce does not match"));
+        }
+
+        let auth_message = format!(
+            "{},{},{}",
+            self.client_first_bare, self.server_first, without_proof
+        );
+        let client_signature = hmac(&self.verifier.stored_key, auth_message.as_bytes())?;
+        let mut client_key = [0; KEY_LEN];
+        for (key, (proof, signature)) in client_key
+            .iter_mut()
+            .zip(proof.iter().zip(client_signature.iter()))
+        {
+            *key = proof ^ signature;
+        }
+        if !memcmp::eq(&sha::sha256(&client_key), &self.verifier.stored_key) {
+            return Err(ScramError::InvalidProof);
+        }
+
+        let server_signature = hmac(&self.verifier.server_key, auth_message.as_bytes())?;
+        Ok(format!("v={}", base64::encode(server_signature)))
+    }
+}
+
+/// Reports whether `b` is a printable character other than a comma, per the
+/// definition of the `printable` production in RFC 5802.
+fn is_printable(b: u8) -> bool {
+    (0x21..=0x7e).contains(&b) && b != b','
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Result<[u8; KEY_LEN], ErrorStack> {
+    let key = PKey::hmac(key)?;
+    let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+    signer.update(data)?;
+    let mut out = [0; KEY_LEN];
+    signer.sign(&mut out)?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The example exchange from RFC 7677, section 3, for the password
+    /// "pencil".
+    const RFC_SALT: &str = "W22ZaJ0SNY7soEsUEjb6gQ==";
+    const RFC_CLIENT_FIRST: &str = "n,,n=user,r=rOprNGfwEbeRWgbNEkqO";
+    const RFC_SERVER_NONCE: &str = "%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0";
+    const RFC_SERVER_FIRST: &str =
+        "r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096";
+    const RFC_CLIENT_FINAL: &str = "c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,\
+         p=dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ=";
+    const RFC_SERVER_FINAL: &str = "v=6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=";
+
+    fn rfc_exchange(password: &str) -> ScramExchange {
+        let verifier =
+            ScramVerifier::with_salt(password, base64::decode(RFC_SALT).unwrap(), 4096).unwrap();
+        ScramExchange::start_with_server_nonce(
+            verifier,
+            SCRAM_SHA_256,
+            RFC_CLIENT_FIRST.as_bytes(),
+            None,
+            RFC_SERVER_NONCE,
+        )
+        .unwrap()
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `OPENSSL_init_ssl` on OS `linux`
+    fn test_verifier_roundtrip() {
+        let verifier = ScramVerifier::new("hunter2").unwrap();
+        let s = verifier.to_string();
+        assert!(s.starts_with("SCRAM-SHA-256$4096:"));
+        assert_eq!(ScramVerifier::parse(&s), Some(verifier));
+
+        for invalid in [
+            "",
+            "hunter2",
+            "SCRAM-SHA-256$",
+            "SCRAM-SHA-256$0:c2FsdA==$AAAA:AAAA",
+            "SCRAM-SHA-256$4096:c2FsdA==$AAAA:AAAA",
+            "md5a3556571e93b0d20722ba62be61e8c2d",
+        ] {
+            assert_eq!(ScramVerifier::parse(invalid), None, "{invalid}");
+        }
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `OPENSSL_init_ssl` on OS `linux`
+    fn test_exchange() {
+        let exchange = rfc_exchange("pencil");
+        assert_eq!(exchange.server_first_message(), RFC_SERVER_FIRST);
+        assert_eq!(
+            exchange.finish(RFC_CLIENT_FINAL.as_bytes()).unwrap(),
+            RFC_SERVER_FINAL
+        );
+
+        let exchange = rfc_exchange("pen");
+        assert!(matches!(
+            exchange.finish(RFC_CLIENT_FINAL.as_bytes()),
+            Err(ScramError::InvalidProof)
+        ));
+
+        let exchange = rfc_exchange("pencil");
+        let tampered = RFC_CLIENT_FINAL.replace("c=biws", "c=eSws");
+        assert!(matches!(
+            exchange.finish(tampered.as_bytes()),
+            Err(ScramError::ChannelBinding(_))
+        ));
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `OPENSSL_init_ssl` on OS `linux`
+    fn test_channel_binding_negotiation() {
+        let verifier = ScramVerifier::new("hunter2").unwrap();
+        let cbind = [1, 2, 3];
+        let start = |mechanism, client_first: &str, cbind: Option<&[u8]>| {
+            ScramExchange::start(verifier.clone(), mechanism, client_first.as_bytes(), cbind)
+                .map(|_| ())
+        };
+
+        assert!(start(SCRAM_SHA_256, "n,,n=,r=abc", None).is_ok());
+        assert!(start(SCRAM_SHA_256, "n,,n=,r=abc", Some(&cbind)).is_ok());
+        assert!(start(SCRAM_SHA_256, "y,,n=,r=abc", None).is_ok());
+        assert!(matches!(
+            start(SCRAM_SHA_256, "y,,n=,r=abc", Some(&cbind)),
+            Err(ScramError::ChannelBinding(_))
+        ));
+        assert!(start(
+            SCRAM_SHA_256_PLUS,
+            "p=tls-server-end-point,,n=,r=abc",
+            Some(&cbind)
+        )
+        .is_ok());
+        assert!(matches!(
+            start(SCRAM_SHA_256_PLUS, "p=tls-server-end-point,,n=,r=abc", None),
+            Err(ScramError::ChannelBinding(_))
+        ));
+        assert!(matches!(
+            start(SCRAM_SHA_256_PLUS, "p=tls-unique,,n=,r=abc", Some(&cbind)),
+            Err(ScramError::ChannelBinding(_))
+        ));
+        assert!(matches!(
+            start(
+                SCRAM_SHA_256,
+                "p=tls-server-end-point,,n=,r=abc",
+                Some(&cbind)
+            ),
+            Err(ScramError::ChannelBinding(_))
+        ));
+        assert!(matches!(
+            start(SCRAM_SHA_256, "n,a=alice,n=,r=abc", None),
+            Err(ScramError::Malformed(_))
+        ));
+        assert!(matches!(
+            start(SCRAM_SHA_256, "n,,n=", None),
+            Err(ScramError::Malformed(_))
+        ));
+        assert!(matches!(
+            start("SCRAM-SHA-1", "n,,n=,r=abc", None),
+            Err(ScramError::Malformed(_))
+        ));
+    }
+}
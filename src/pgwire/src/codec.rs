@@ -225,6 +225,9 @@ impl Encoder<BackendMessage> for Codec {
         let byte = match &msg {
             BackendMessage::AuthenticationOk => b'R',
             BackendMessage::AuthenticationCleartextPassword => b'R',
+            BackendMessage::AuthenticationSASL { .. } => b'R',
+            BackendMessage::AuthenticationSASLContinue(_) => b'R',
+            BackendMessage::AuthenticationSASLFinal(_) => b'R',
             BackendMessage::RowDescription(_) => b'T',
             BackendMessage::DataRow(_) => b'D',
             BackendMessage::CommandComplete { .. } => b'C',
@@ -282,6 +285,21 @@ impl Encoder<BackendMessage> for Codec {
             BackendMessage::AuthenticationCleartextPassword => {
                 dst.put_u32(3);
             }
+            BackendMessage::AuthenticationSASL { mechanisms } => {
+                dst.put_u32(10);
+                for mechanism in mechanisms {
+                    dst.put_string(mechanism);
+                }
+                dst.put_u8(b'\0');
+            }
+            BackendMessage::AuthenticationSASLContinue(data) => {
+                dst.put_u32(11);
+                dst.put_slice(&data);
+            }
+            BackendMessage::AuthenticationSASLFinal(data) => {
+                dst.put_u32(12);
+                dst.put_slice(&data);
+            }
             BackendMessage::RowDescription(fields) => {
                 dst.put_length_i16(fields.len())?;
                 for f in &fields {
@@ -472,7 +490,7 @@ impl Decoder for Codec {
                         b'X' => decode_terminate(buf)?,
 
                         // Authentication.
-                        b'p' => decode_password(buf)?,
+                        b'p' => decode_raw_authentication(buf, frame_len)?,
 
                         // Copy from flow.
                         b'f' => decode_copy_fail(buf)?,
@@ -501,10 +519,37 @@ fn decode_terminate(mut _buf: Cursor) -> Result<FrontendMessage, io::Error> {
     Ok(FrontendMessage::Terminate)
 }
 
-fn decode_password(mut buf: Cursor) -> Result<FrontendMessage, io::Error> {
-    Ok(FrontendMessage::Password {
-        password: buf.read_cstr()?.to_owned(),
-    })
+fn decode_raw_authentication(
+    mut buf: Cursor,
+    frame_len: usize,
+) -> Result<FrontendMessage, io::Error> {
+    let mut data = Vec::with_capacity(frame_len);
+    for _ in 0..frame_len {
+        data.push(buf.read_byte()?);
+    }
+    Ok(FrontendMessage::RawAuthentication(data))
+}
+
+/// Decodes the body of a `PasswordMessage`.
+pub fn decode_password(data: &[u8]) -> Result<String, io::Error> {
+    Ok(Cursor::new(data).read_cstr()?.to_owned())
+}
+
+/// Decodes the body of a `SASLInitialResponse` message into the name of the
+/// SASL mechanism selected by the client and the client's initial response.
+pub fn decode_sasl_initial_response(data: &[u8]) -> Result<(String, Vec<u8>), io::Error> {
+    let mut buf = Cursor::new(data);
+    let mechanism = buf.read_cstr()?.to_owned();
+    let len = buf.read_i32()?;
+    let mut response = vec![];
+    // A length of -1 indicates that there is no initial response.
+    if len != -1 {
+        let len = usize::try_from(len).map_err(|_| input_err("invalid response length"))?;
+        for _ in 0..len {
+            response.push(buf.read_byte()?);
+        }
+    }
+    Ok((mechanism, response))
 }
 
 fn decode_query(mut buf: Cursor) -> Result<FrontendMessage, io::Error> {
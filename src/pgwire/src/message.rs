@@ -21,6 +21,11 @@ use mz_repr::{ColumnName, RelationDesc};
 pub enum BackendMessage {
     AuthenticationOk,
     AuthenticationCleartextPassword,
+    AuthenticationSASL {
+        mechanisms: Vec<&'static str>,
+    },
+    AuthenticationSASLContinue(Vec<u8>),
+    AuthenticationSASLFinal(Vec<u8>),
     CommandComplete {
         tag: String,
     },
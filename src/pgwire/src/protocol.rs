@@ -10,16 +10,19 @@
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::future::Future;
+use std::net::IpAddr;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use std::{cmp, iter, mem};
 
 use byteorder::{ByteOrder, NetworkEndian};
+use bytesize::ByteSize;
 use futures::future::{pending, BoxFuture, FutureExt};
 use itertools::izip;
 use mz_adapter::client::RecordFirstRowStream;
 use mz_adapter::session::{
-    EndTransactionAction, InProgressRows, Portal, PortalState, SessionConfig, TransactionStatus,
+    AuthenticationMethod, CursorHold, EndTransactionAction, InProgressRows, Portal, PortalState,
+    SessionConfig, SpooledRows, TransactionStatus,
 };
 use mz_adapter::statement_logging::StatementEndedExecutionReason;
 use mz_adapter::{
@@ -31,17 +34,23 @@ use mz_ore::cast::CastFrom;
 use mz_ore::instrument;
 use mz_ore::netio::AsyncReady;
 use mz_ore::str::StrExt;
-use mz_pgcopy::{CopyFormatParams, CopyTextFormatParams};
-use mz_pgwire_common::{ErrorResponse, Format, FrontendMessage, Severity, VERSIONS, VERSION_3};
+use mz_ore::tracing::{OpenTelemetryContext, W3C_TRACEPARENT, W3C_TRACESTATE};
+use mz_pgcopy::{CopyFormatParams, CopyFromDecoder, CopyTextFormatParams};
+use mz_pgwire_common::{
+    tls_server_end_point, Conn, ErrorResponse, Format, FrontendMessage, ScramError, ScramExchange,
+    ScramVerifier, Severity, SCRAM_SHA_256, SCRAM_SHA_256_PLUS, VERSIONS, VERSION_3,
+};
 use mz_repr::{Datum, GlobalId, RelationDesc, RelationType, Row, RowArena, ScalarType};
-use mz_server_core::TlsMode;
+use mz_server_core::{TlsClientAuthConfig, TlsMode};
 use mz_sql::ast::display::AstDisplay;
-use mz_sql::ast::{FetchDirection, Ident, Raw, Statement};
+use mz_sql::ast::{DeclareStatement, FetchDirection, Ident, Raw, Statement};
 use mz_sql::parse::StatementParseResult;
 use mz_sql::plan::{CopyFormat, ExecuteTimeout, StatementDesc};
 use mz_sql::session::metadata::SessionMetadata;
 use mz_sql::session::user::INTERNAL_USER_NAMES;
 use mz_sql::session::vars::{ConnectionCounter, DropConnection, Var, VarInput, MAX_COPY_FROM_SIZE};
+use openssl::nid::Nid;
+use openssl::x509::X509VerifyResult;
 use postgres::error::SqlState;
 use tokio::io::{self, AsyncRead, AsyncWrite};
 use tokio::select;
@@ -50,7 +59,7 @@ use tokio::time::{self};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::{debug, debug_span, warn, Instrument};
 
-use crate::codec::FramedConn;
+use crate::codec::{self, FramedConn};
 use crate::message::{self, BackendMessage};
 
 /// Reports whether the given stream begins with a pgwire handshake.
@@ -73,6 +82,141 @@ pub fn match_handshake(buf: &[u8]) -> bool {
     VERSIONS.contains(&version)
 }
 
+/// Returns the common name of the client's TLS certificate and the user that
+/// the certificate authenticates as, if the connection is encrypted and the
+/// client supplied a certificate that verified against the configured
+/// certificate authority and that has a common name.
+///
+/// A certificate authenticates as the user that `client_auth` maps its
+/// subject's common name to or, if the common name is not mapped, as the user
+/// named by the common name. Subject alternative names are not considered.
+fn client_cert_user<A>(
+    conn: &Conn<A>,
+    client_auth: &TlsClientAuthConfig,
+) -> Option<(String, String)> {
+    let Conn::Ssl(stream) = conn else {
+        return None;
+    };
+    let ssl = stream.ssl();
+    let cert = ssl.peer_certificate()?;
+    if ssl.verify_result() != X509VerifyResult::OK {
+        return None;
+    }
+    let common_name = cert
+        .subject_name()
+        .entries_by_nid(Nid::COMMONNAME)
+        .next()?
+        .data()
+        .as_utf8()
+        .ok()?
+        .to_string();
+    let user = match client_auth.user_map.get(&common_name) {
+        Some(user) => user.clone(),
+        None => common_name.clone(),
+    };
+    Some((common_name, user))
+}
+
+/// Returns the SCRAM-SHA-256 verifier for the password of the role named
+/// `user`, if the role exists and has a password.
+async fn password_verifier(
+    adapter_client: &mz_adapter::Client,
+    user: &str,
+) -> Option<ScramVerifier> {
+    let catalog = adapter_client.catalog_snapshot().await;
+    let role = catalog.try_get_role_by_name(user)?;
+    let verifier = role.attributes.password.as_ref()?;
+    let verifier = ScramVerifier::parse(verifier);
+    if verifier.is_none() {
+        warn!(user, "role has an invalid password verifier");
+    }
+    verifier
+}
+
+/// Runs a SCRAM-SHA-256 exchange in which the client proves that it knows the
+/// password described by `verifier`.
+///
+/// Channel binding is offered to clients whose connections are encrypted.
+/// Returns the error to deliver to the client if authentication fails.
+async fn authenticate_scram<A>(
+    conn: &mut FramedConn<A>,
+    user: &str,
+    verifier: ScramVerifier,
+) -> Result<Result<(), ErrorResponse>, io::Error>
+where
+    A: AsyncRead + AsyncWrite + AsyncReady + Send + Sync + Unpin,
+{
+    let scram_error = |err: ScramError| match err {
+        ScramError::InvalidProof => {
+            warn!(user, "pgwire connection failed password authentication");
+            ErrorResponse::fatal(
+                SqlState::INVALID_PASSWORD,
+                format!("password authentication failed for user {}", user.quoted()),
+            )
+        }
+        ScramError::Crypto(_) => ErrorResponse::fatal(SqlState::INTERNAL_ERROR, err.to_string()),
+        ScramError::Malformed(_) | ScramError::ChannelBinding(_) => {
+            ErrorResponse::fatal(SqlState::PROTOCOL_VIOLATION, err.to_string())
+        }
+    };
+    let expected_sasl_response =
+        || ErrorResponse::fatal(SqlState::PROTOCOL_VIOLATION, "expected SASL response");
+
+    let cbind = match conn.inner() {
+        Conn::Ssl(stream) => match stream.ssl().certificate().map(tls_server_end_point) {
+            Some(Ok(cbind)) => Some(cbind),
+            Some(Err(err)) => {
+                warn!(?err, "unable to compute channel binding data");
+                None
+            }
+            None => None,
+        },
+        Conn::Unencrypted(_) => None,
+    };
+    let mechanisms = match cbind {
+        Some(_) => vec![SCRAM_SHA_256_PLUS, SCRAM_SHA_256],
+        None => vec![SCRAM_SHA_256],
+    };
+    conn.send(BackendMessage::AuthenticationSASL { mechanisms })
+        .await?;
+    conn.flush().await?;
+
+    let (mechanism, client_first) = match conn.recv().await? {
+        Some(FrontendMessage::RawAuthentication(data)) => {
+            match codec::decode_sasl_initial_response(&data) {
+                Ok(response) => response,
+                Err(_) => return Ok(Err(expected_sasl_response())),
+            }
+        }
+        _ => return Ok(Err(expected_sasl_response())),
+    };
+    let exchange = match ScramExchange::start(verifier, &mechanism, &client_first, cbind.as_deref())
+    {
+        Ok(exchange) => exchange,
+        Err(err) => return Ok(Err(scram_error(err))),
+    };
+    conn.send(BackendMessage::AuthenticationSASLContinue(
+        exchange.server_first_message().as_bytes().to_vec(),
+    ))
+    .await?;
+    conn.flush().await?;
+
+    let client_final = match conn.recv().await? {
+        Some(FrontendMessage::RawAuthentication(data)) => data,
+        _ => return Ok(Err(expected_sasl_response())),
+    };
+    match exchange.finish(&client_final) {
+        Ok(server_final) => {
+            conn.send(BackendMessage::AuthenticationSASLFinal(
+                server_final.into_bytes(),
+            ))
+            .await?;
+            Ok(Ok(()))
+        }
+        Err(err) => Ok(Err(scram_error(err))),
+    }
+}
+
 /// Parameters for the [`run`] function.
 pub struct RunParams<'a, A> {
     /// The TLS mode of the pgwire server.
@@ -87,11 +231,16 @@ pub struct RunParams<'a, A> {
     pub params: BTreeMap<String, String>,
     /// Frontegg authentication.
     pub frontegg: Option<&'a FronteggAuthentication>,
+    /// How clients may authenticate with TLS client certificates, if the
+    /// server verifies them.
+    pub client_cert_auth: Option<&'a TlsClientAuthConfig>,
     /// Whether this is an internal server that permits access to restricted
     /// system resources.
     pub internal: bool,
     /// Global connection limit and count
     pub active_connection_count: Arc<Mutex<ConnectionCounter>>,
+    /// The IP address of the client, if known.
+    pub client_ip: Option<IpAddr>,
 }
 
 /// Runs a pgwire connection to completion.
@@ -112,8 +261,10 @@ pub async fn run<'a, A>(
         version,
         mut params,
         frontegg,
+        client_cert_auth,
         internal,
         active_connection_count,
+        client_ip,
     }: RunParams<'a, A>,
 ) -> Result<(), io::Error>
 where
@@ -130,6 +281,15 @@ where
 
     let user = params.remove("user").unwrap_or_else(String::new);
 
+    // A W3C trace context supplied by the client causes the work performed on
+    // behalf of the session to be reported as part of the client's trace.
+    let traceparent = params.remove(W3C_TRACEPARENT);
+    let tracestate = params.remove(W3C_TRACESTATE);
+    let trace_context = traceparent
+        .as_deref()
+        .and_then(|tp| OpenTelemetryContext::from_w3c(tp, tracestate.as_deref()));
+    let invalid_traceparent = traceparent.is_some() && trace_context.is_none();
+
     if internal {
         // The internal server can only be used to connect to the internal users.
         if !INTERNAL_USER_NAMES.contains(&user) {
@@ -152,12 +312,49 @@ where
         return conn.send(err).await;
     }
 
+    // A verified client certificate that maps to the requested user
+    // authenticates the connection. With Frontegg, the client must still
+    // authenticate with Frontegg, which resolves the user's metadata;
+    // otherwise, the certificate is sufficient on its own.
+    let cert_user =
+        client_cert_auth.and_then(|client_auth| client_cert_user(conn.inner(), client_auth));
+    let cert_common_name = match &cert_user {
+        Some((common_name, cert_user)) if *cert_user == user => Some(common_name.clone()),
+        _ => None,
+    };
+    let require_client_cert = client_cert_auth.map_or(false, |client_auth| client_auth.required);
+    if require_client_cert && cert_common_name.is_none() {
+        let msg = match cert_user {
+            None => "connection requires a valid client certificate".into(),
+            Some(_) => format!(
+                "certificate authentication failed for user {}",
+                user.quoted()
+            ),
+        };
+        return conn
+            .send(ErrorResponse::fatal(
+                SqlState::INVALID_AUTHORIZATION_SPECIFICATION,
+                msg,
+            ))
+            .await;
+    }
+
     let (mut session, expired) = if let Some(frontegg) = frontegg {
         conn.send(BackendMessage::AuthenticationCleartextPassword)
             .await?;
         conn.flush().await?;
         let password = match conn.recv().await? {
-            Some(FrontendMessage::Password { password }) => password,
+            Some(FrontendMessage::RawAuthentication(data)) => match codec::decode_password(&data) {
+                Ok(password) => password,
+                Err(err) => {
+                    return conn
+                        .send(ErrorResponse::fatal(
+                            SqlState::PROTOCOL_VIOLATION,
+                            err.to_string(),
+                        ))
+                        .await
+                }
+            },
             _ => {
                 return conn
                     .send(ErrorResponse::fatal(
@@ -182,6 +379,10 @@ where
                     conn_id: conn.conn_id().clone(),
                     user: auth_session.user().into(),
                     external_metadata_rx: Some(auth_session.external_metadata_rx()),
+                    authentication_method: AuthenticationMethod::Password,
+                    trace_context,
+                    client_ip,
+                    http_request_id: None,
                 });
                 let expired = async move { auth_session.expired().await };
                 (session, expired.left_future())
@@ -196,17 +397,59 @@ where
                     .await;
             }
         }
+    } else if let Some(common_name) = cert_common_name {
+        let session = adapter_client.new_session(SessionConfig {
+            conn_id: conn.conn_id().clone(),
+            user,
+            external_metadata_rx: None,
+            authentication_method: AuthenticationMethod::Certificate { common_name },
+            trace_context,
+            client_ip,
+            http_request_id: None,
+        });
+        // Certificates are validated once, at connection time.
+        let auth_session = pending().right_future();
+        (session, auth_session)
+    } else if let Some(verifier) = password_verifier(&adapter_client, &user).await {
+        // The role has a password, so the client must prove that it knows
+        // the password before it is permitted to connect.
+        if let Err(err) = authenticate_scram(conn, &user, verifier).await? {
+            return conn.send(err).await;
+        }
+        let session = adapter_client.new_session(SessionConfig {
+            conn_id: conn.conn_id().clone(),
+            user,
+            external_metadata_rx: None,
+            authentication_method: AuthenticationMethod::Password,
+            trace_context,
+            client_ip,
+            http_request_id: None,
+        });
+        // Passwords are checked once, at connection time.
+        let auth_session = pending().right_future();
+        (session, auth_session)
     } else {
         let session = adapter_client.new_session(SessionConfig {
             conn_id: conn.conn_id().clone(),
             user,
             external_metadata_rx: None,
+            authentication_method: AuthenticationMethod::Trust,
+            trace_context,
+            client_ip,
+            http_request_id: None,
         });
         // No frontegg check, so auth session lasts indefinitely.
         let auth_session = pending().right_future();
         (session, auth_session)
     };
 
+    if invalid_traceparent {
+        session.add_notice(AdapterNotice::BadStartupSetting {
+            name: W3C_TRACEPARENT.into(),
+            reason: "invalid trace context".into(),
+        });
+    }
+
     for (name, value) in params {
         let settings = match name.as_str() {
             "options" => match parse_options(&value) {
@@ -412,6 +655,10 @@ enum SendRowsEndedReason {
 const ABORTED_TXN_MSG: &str =
     "current transaction is aborted, commands ignored until end of transaction block";
 
+/// The number of bytes that `COPY ... FROM STDIN` buffers before it writes the
+/// decoded rows to the transaction.
+const COPY_FROM_BATCH_SIZE: usize = 8 << 20;
+
 impl<'a, A> StateMachine<'a, A>
 where
     A: AsyncRead + AsyncWrite + AsyncReady + Send + Sync + Unpin + 'a,
@@ -437,8 +684,24 @@ where
         }
     }
 
+    /// Returns a new root span for processing the frontend message named
+    /// `message_name`.
+    ///
+    /// If the client supplied a trace context at startup, the span is made a
+    /// child of the client's span.
+    fn root_span(&mut self, message_name: &str) -> tracing::Span {
+        let mut span = tracing::info_span!(parent: None, "advance_ready", otel.name = message_name);
+        span.follows_from(tracing::Span::current());
+        if let Some(trace_context) = self.adapter_client.session().trace_context() {
+            trace_context.attach_as_parent_to(&mut span);
+        }
+        span
+    }
+
     #[instrument(level = "debug")]
     async fn advance_ready(&mut self) -> Result<State, io::Error> {
+        let drained = self.adapter_client.drained();
+
         // Handle timeouts first so we don't execute any statements when there's a pending timeout.
         let message = select! {
             biased;
@@ -462,6 +725,21 @@ where
                 let _ = self.conn.recv().await?;
                 return error_state;
             },
+            // `drained` is cancel-safe as per it's docs.
+            () = drained => {
+                let conn_id = self.adapter_client.session().conn_id();
+                tracing::info!("server is draining, closing session, conn_id {}", conn_id);
+
+                let err = AdapterError::SessionDrained;
+                let error_response = err.into_response(Severity::Fatal);
+                let error_state = self.error(error_response).await;
+                self.adapter_client.terminate().await;
+
+                // As with timeouts, we can only send the error response in response to a client
+                // message.
+                let _ = self.conn.recv().await?;
+                return error_state;
+            },
             // `recv()` is cancel-safe as per it's docs.
             message = self.conn.recv() => message?,
         };
@@ -475,9 +753,7 @@ where
 
         let next_state = match message {
             Some(FrontendMessage::Query { sql }) => {
-                let query_root_span =
-                    tracing::info_span!(parent: None, "advance_ready", otel.name = message_name);
-                query_root_span.follows_from(tracing::Span::current());
+                let query_root_span = self.root_span(message_name);
                 self.query(sql).instrument(query_root_span).await?
             }
             Some(FrontendMessage::Parse {
@@ -509,9 +785,7 @@ where
                     Ok(0) | Err(_) => ExecuteCount::All, // If `max_rows < 0`, no limit.
                     Ok(n) => ExecuteCount::Count(n),
                 };
-                let execute_root_span =
-                    tracing::info_span!(parent: None, "advance_ready", otel.name = message_name);
-                execute_root_span.follows_from(tracing::Span::current());
+                let execute_root_span = self.root_span(message_name);
                 let state = self
                     .execute(
                         portal_name,
@@ -552,10 +826,14 @@ where
             Some(FrontendMessage::Sync) => self.sync().await?,
             Some(FrontendMessage::Terminate) => State::Done,
 
+            // A client may have sent more copy messages than we consumed before a COPY FROM
+            // STDIN failed. As in PostgreSQL, these are dropped rather than treated as an error,
+            // since entering the drain state would swallow any subsequent simple queries.
             Some(FrontendMessage::CopyData(_))
             | Some(FrontendMessage::CopyDone)
-            | Some(FrontendMessage::CopyFail(_))
-            | Some(FrontendMessage::Password { .. }) => State::Drain,
+            | Some(FrontendMessage::CopyFail(_)) => State::Ready,
+            Some(FrontendMessage::Password { .. })
+            | Some(FrontendMessage::RawAuthentication(_)) => State::Drain,
             None => State::Done,
         };
 
@@ -570,7 +848,8 @@ where
         }
         match message {
             Some(FrontendMessage::Sync) => self.sync().await,
-            None => Ok(State::Done),
+            // The client may give up on a pipeline that failed without waiting for the `Sync`.
+            Some(FrontendMessage::Terminate) | None => Ok(State::Done),
             _ => Ok(State::Drain),
         }
     }
@@ -901,32 +1180,12 @@ where
 
         if let Some(desc) = stmt.desc().relation_desc.clone() {
             for (format, ty) in result_formats.iter().zip(desc.iter_types()) {
-                match (format, &ty.scalar_type) {
-                    (Format::Binary, mz_repr::ScalarType::List { .. }) => {
-                        return self
-                            .error(ErrorResponse::error(
-                                SqlState::PROTOCOL_VIOLATION,
-                                "binary encoding of list types is not implemented",
-                            ))
-                            .await;
-                    }
-                    (Format::Binary, mz_repr::ScalarType::Map { .. }) => {
-                        return self
-                            .error(ErrorResponse::error(
-                                SqlState::PROTOCOL_VIOLATION,
-                                "binary encoding of map types is not implemented",
-                            ))
-                            .await;
-                    }
-                    (Format::Binary, mz_repr::ScalarType::AclItem) => {
+                if let Format::Binary = format {
+                    if let Some(msg) = unsupported_binary_encoding(&ty.scalar_type) {
                         return self
-                            .error(ErrorResponse::error(
-                                SqlState::PROTOCOL_VIOLATION,
-                                "binary encoding of aclitem types does not exist",
-                            ))
+                            .error(ErrorResponse::error(SqlState::PROTOCOL_VIOLATION, msg))
                             .await;
                     }
-                    _ => (),
                 }
             }
         }
@@ -1103,7 +1362,8 @@ where
                     self.send(BackendMessage::CommandComplete { tag }).await?;
                     Ok(State::Ready)
                 }
-                PortalState::Completed(None) => {
+                // Spooled cursors can only be read with FETCH.
+                PortalState::Completed(None) | PortalState::Spooled(_) => {
                     let error = format!(
                         "portal {} cannot be run",
                         Ident::new_unchecked(portal_name).to_ast_string_stable()
@@ -1216,6 +1476,30 @@ where
         // instead of All.
         let count = count.unwrap_or(FetchDirection::ForwardCount(1));
 
+        // SCROLL and WITH HOLD cursors are served from their buffered rows.
+        // Other cursors can only move forward, as their rows are streamed.
+        let (scroll, hold) = match self.adapter_client.session().get_portal_unverified(&name) {
+            Some(portal) => (portal.scroll, portal.hold),
+            None => (false, CursorHold::WithoutHold),
+        };
+        if scroll || hold != CursorHold::WithoutHold {
+            return self
+                .fetch_spooled(name, count, max_rows, fetch_portal_name, ctx_extra)
+                .await;
+        }
+        if !count.is_forward() {
+            let msg = "cursor can only scan forward";
+            self.adapter_client.retire_execute(
+                ctx_extra,
+                StatementEndedExecutionReason::Errored {
+                    error: msg.to_string(),
+                },
+            );
+            let mut err = ErrorResponse::error(SqlState::OBJECT_NOT_IN_PREREQUISITE_STATE, msg);
+            err.hint = Some("Declare it with SCROLL option to enable backward scan.".into());
+            return self.error(err).await;
+        }
+
         // Figure out how many rows we should send back by looking at the various
         // combinations of the execute and fetch.
         //
@@ -1261,6 +1545,7 @@ where
             (ExecuteCount::All, FetchDirection::ForwardCount(count)) => {
                 ExecuteCount::Count(usize::cast_from(count))
             }
+            (_, direction) => unreachable!("{direction} is not forward"),
         };
         let cursor_name = name.to_string();
         self.execute(
@@ -1274,6 +1559,228 @@ where
         .await
     }
 
+    /// Serves a FETCH from a `SCROLL` or `WITH HOLD` cursor, first buffering all
+    /// of the cursor's rows if that has not happened yet.
+    async fn fetch_spooled(
+        &mut self,
+        name: String,
+        direction: FetchDirection,
+        max_rows: ExecuteCount,
+        fetch_portal_name: Option<String>,
+        ctx_extra: ExecuteContextExtra,
+    ) -> Result<State, io::Error> {
+        if let Some(err) = self.spool_cursor(&name).await? {
+            self.adapter_client.retire_execute(
+                ctx_extra,
+                StatementEndedExecutionReason::Errored {
+                    error: err.message.clone(),
+                },
+            );
+            return self.error(err).await;
+        }
+
+        let portal = self
+            .adapter_client
+            .session()
+            .get_portal_unverified_mut(&name)
+            .expect("cursor was just spooled");
+        let row_desc = portal
+            .desc
+            .relation_desc
+            .clone()
+            .expect("spooled cursors return rows");
+        let PortalState::Spooled(spooled) = &mut portal.state else {
+            unreachable!("cursor was just spooled");
+        };
+        let (indexes, position) = scroll_cursor(spooled.position, spooled.rows.len(), direction);
+        if let ExecuteCount::Count(max_rows) = max_rows {
+            if max_rows < indexes.len() {
+                let msg = "Execute with max_rows < a FETCH's count is not supported";
+                self.adapter_client.retire_execute(
+                    ctx_extra,
+                    StatementEndedExecutionReason::Errored {
+                        error: msg.to_string(),
+                    },
+                );
+                return self
+                    .error(ErrorResponse::error(SqlState::FEATURE_NOT_SUPPORTED, msg))
+                    .await;
+            }
+        }
+        spooled.position = position;
+        let messages: Vec<_> = indexes
+            .iter()
+            .map(|i| {
+                BackendMessage::DataRow(mz_pgrepr::values_from_row(
+                    &spooled.rows[*i],
+                    row_desc.typ(),
+                ))
+            })
+            .collect();
+
+        // Use the result formats of the portal that is executing the FETCH.
+        let result_formats = fetch_portal_name
+            .as_deref()
+            .and_then(|name| self.adapter_client.session().get_portal_unverified(name))
+            .map(|portal| portal.result_formats.clone())
+            .unwrap_or_else(|| vec![Format::Text; row_desc.arity()]);
+        self.conn.set_encode_state(
+            row_desc
+                .typ()
+                .column_types
+                .iter()
+                .map(|ty| mz_pgrepr::Type::from(&ty.scalar_type))
+                .zip(result_formats)
+                .collect(),
+        );
+        let count = messages.len();
+        self.send_all(messages).await?;
+
+        let fetch_portal = fetch_portal_name.map(|name| {
+            self.adapter_client
+                .session()
+                .get_portal_unverified_mut(&name)
+                .expect("valid fetch portal")
+        });
+        let response_message = fetch_message(max_rows, count, fetch_portal);
+        self.send(response_message).await?;
+        self.adapter_client.retire_execute(
+            ctx_extra,
+            StatementEndedExecutionReason::Success {
+                rows_returned: Some(u64::cast_from(count)),
+                execution_strategy: None,
+            },
+        );
+        Ok(State::Ready)
+    }
+
+    /// Buffers all remaining rows of the cursor `name` in its portal, so that
+    /// they can be fetched in any direction and outlive the transaction that
+    /// declared the cursor. Does nothing if the rows are already buffered.
+    ///
+    /// Returns an error response if the rows could not be buffered.
+    async fn spool_cursor(&mut self, name: &str) -> Result<Option<ErrorResponse>, io::Error> {
+        let max_size = self.adapter_client.session().vars().max_cursor_spool_size();
+        let portal = self
+            .adapter_client
+            .session()
+            .get_portal_unverified_mut(name)
+            .expect("cursor should exist");
+        // Until the rows are buffered, the portal cannot be run.
+        let state = mem::replace(&mut portal.state, PortalState::Completed(None));
+        let (mut rows, stream) = match state {
+            PortalState::Spooled(spooled) => {
+                portal.state = PortalState::Spooled(spooled);
+                return Ok(None);
+            }
+            PortalState::InProgress(rows) => {
+                let rows = rows.expect("InProgress rows must be populated");
+                (rows.current.unwrap_or_default(), Some(rows.remaining))
+            }
+            PortalState::Completed(_) => {
+                let msg = format!(
+                    "portal {} cannot be run",
+                    Ident::new_unchecked(name).to_ast_string_stable()
+                );
+                return Ok(Some(ErrorResponse::error(
+                    SqlState::OBJECT_NOT_IN_PREREQUISITE_STATE,
+                    msg,
+                )));
+            }
+            PortalState::NotStarted => {
+                self.ensure_transaction(1).await?;
+                let response = self
+                    .adapter_client
+                    .execute(name.to_string(), self.conn.wait_closed(), None)
+                    .await;
+                self.send_pending_notices().await?;
+                match response {
+                    Ok((ExecuteResponse::SendingRows { future }, execute_started)) => {
+                        let span = tracing::debug_span!("spooling_rows");
+                        let rows = self.row_future_to_stream(&span, future).await?;
+                        let stream = RecordFirstRowStream::new(
+                            Box::new(UnboundedReceiverStream::new(rows)),
+                            execute_started,
+                            &self.adapter_client,
+                        );
+                        (Vec::new(), Some(stream))
+                    }
+                    Ok((ExecuteResponse::SendingRowsImmediate { rows }, _)) => (rows, None),
+                    Ok(_) => {
+                        return Ok(Some(ErrorResponse::error(
+                            SqlState::FEATURE_NOT_SUPPORTED,
+                            "SCROLL and WITH HOLD cursors must return rows",
+                        )))
+                    }
+                    Err(e) => return Ok(Some(e.into_response(Severity::Error))),
+                }
+            }
+        };
+
+        let exceeded = || {
+            ErrorResponse::error(
+                SqlState::PROGRAM_LIMIT_EXCEEDED,
+                format!(
+                    "cursor {} exceeds max_cursor_spool_size of {}",
+                    name.quoted(),
+                    ByteSize::b(max_size)
+                ),
+            )
+        };
+        let mut size: u64 = rows.iter().map(|row| u64::cast_from(row.byte_len())).sum();
+        if size > max_size {
+            return Ok(Some(exceeded()));
+        }
+        if let Some(mut stream) = stream {
+            loop {
+                let batch = tokio::select! {
+                    err = self.conn.wait_closed() => return Err(err),
+                    batch = stream.recv() => batch,
+                };
+                match batch {
+                    None => break,
+                    Some(PeekResponseUnary::Rows(batch)) => {
+                        size += batch
+                            .iter()
+                            .map(|row| u64::cast_from(row.byte_len()))
+                            .sum::<u64>();
+                        if size > max_size {
+                            return Ok(Some(exceeded()));
+                        }
+                        rows.extend(batch);
+                    }
+                    Some(PeekResponseUnary::Error(text)) => {
+                        return Ok(Some(ErrorResponse::error(SqlState::INTERNAL_ERROR, text)));
+                    }
+                    Some(PeekResponseUnary::Canceled) => {
+                        return Ok(Some(ErrorResponse::error(
+                            SqlState::QUERY_CANCELED,
+                            "canceling statement due to user request",
+                        )));
+                    }
+                    Some(PeekResponseUnary::TimedOut) => {
+                        return Ok(Some(
+                            AdapterError::StatementTimeout.into_response(Severity::Error),
+                        ));
+                    }
+                    Some(PeekResponseUnary::ResultSizeExceeded(e)) => {
+                        return Ok(Some(
+                            AdapterError::ResultSize(e).into_response(Severity::Error),
+                        ));
+                    }
+                }
+            }
+        }
+
+        let portal = self
+            .adapter_client
+            .session()
+            .get_portal_unverified_mut(name)
+            .expect("cursor should exist");
+        portal.state = PortalState::Spooled(SpooledRows { rows, position: 0 });
+        Ok(None)
+    }
+
     async fn flush(&mut self) -> Result<State, io::Error> {
         self.conn.flush().await?;
         Ok(State::Ready)
@@ -1386,7 +1893,25 @@ where
                 command_complete!()
             }
             ExecuteResponse::DeclaredCursor => {
+                // The rows of a WITH HOLD cursor must be computed while the
+                // declaring transaction is still open.
+                let held_cursor = match self
+                    .adapter_client
+                    .session()
+                    .get_portal_unverified(&portal_name)
+                    .and_then(|portal| portal.stmt.as_deref())
+                {
+                    Some(Statement::Declare(DeclareStatement {
+                        name, hold: true, ..
+                    })) => Some(name.to_string()),
+                    _ => None,
+                };
                 self.complete_portal(&portal_name);
+                if let Some(name) = held_cursor {
+                    if let Some(err) = self.spool_cursor(&name).await? {
+                        return self.error(err).await;
+                    }
+                }
                 command_complete!()
             }
             ExecuteResponse::EmptyQuery => {
@@ -1663,6 +2188,7 @@ where
             ExecuteResponse::AlteredDefaultPrivileges
             | ExecuteResponse::AlteredIndexLogicalCompaction
             | ExecuteResponse::AlteredObject(..)
+            | ExecuteResponse::AlteredQuota
             | ExecuteResponse::AlteredRole
             | ExecuteResponse::AlteredSystemConfiguration
             | ExecuteResponse::CreatedCluster { .. }
@@ -1678,12 +2204,15 @@ where
             | ExecuteResponse::CreatedSource { .. }
             | ExecuteResponse::CreatedTable { .. }
             | ExecuteResponse::CreatedType
+            | ExecuteResponse::CreatedFunction
+            | ExecuteResponse::CreatedContinualTask
             | ExecuteResponse::CreatedView { .. }
             | ExecuteResponse::CreatedViews { .. }
             | ExecuteResponse::Comment
             | ExecuteResponse::Deallocate { .. }
             | ExecuteResponse::Deleted(..)
             | ExecuteResponse::DiscardedAll
+            | ExecuteResponse::DiscardedPlans
             | ExecuteResponse::DiscardedTemp
             | ExecuteResponse::DroppedObject(_)
             | ExecuteResponse::DroppedOwned
@@ -1782,6 +2311,8 @@ where
                         Some(PeekResponseUnary::Rows(rows)) => FetchResult::Rows(Some(rows)),
                         Some(PeekResponseUnary::Error(err)) => FetchResult::Error(err),
                         Some(PeekResponseUnary::Canceled) => FetchResult::Canceled,
+                        Some(PeekResponseUnary::TimedOut) => FetchResult::TimedOut,
+                        Some(PeekResponseUnary::ResultSizeExceeded(e)) => FetchResult::ResultSizeExceeded(e),
                     },
                 }
             };
@@ -1870,6 +2401,21 @@ where
                         .await
                         .map(|state| (state, SendRowsEndedReason::Canceled));
                 }
+                FetchResult::TimedOut => {
+                    let err = AdapterError::StatementTimeout;
+                    let error = err.to_string();
+                    return self
+                        .error(err.into_response(Severity::Error))
+                        .await
+                        .map(|state| (state, SendRowsEndedReason::Errored { error }));
+                }
+                FetchResult::ResultSizeExceeded(error) => {
+                    let err = AdapterError::ResultSize(error.clone());
+                    return self
+                        .error(err.into_response(Severity::Error))
+                        .await
+                        .map(|state| (state, SendRowsEndedReason::Errored { error }));
+                }
             }
         }
 
@@ -1932,6 +2478,21 @@ where
         };
 
         let typ = row_desc.typ();
+        if let Format::Binary = encode_format {
+            if let Some(msg) = typ
+                .column_types
+                .iter()
+                .find_map(|ty| unsupported_binary_encoding(&ty.scalar_type))
+            {
+                return self
+                    .error(ErrorResponse::error(SqlState::PROTOCOL_VIOLATION, msg))
+                    .await
+                    .map(|state| {
+                        let error = msg.to_string();
+                        (state, SendRowsEndedReason::Errored { error })
+                    });
+            }
+        }
         let column_formats = iter::repeat(encode_format)
             .take(typ.column_types.len())
             .collect();
@@ -1975,6 +2536,17 @@ where
                             ))
                             .await.map(|state| (state, SendRowsEndedReason::Canceled));
                     }
+                    Some(PeekResponseUnary::TimedOut) => {
+                        let err = AdapterError::StatementTimeout;
+                        let error = err.to_string();
+                        return self.error(err.into_response(Severity::Error))
+                            .await.map(|state| (state, SendRowsEndedReason::Errored { error }));
+                    }
+                    Some(PeekResponseUnary::ResultSizeExceeded(error)) => {
+                        let err = AdapterError::ResultSize(error.clone());
+                        return self.error(err.into_response(Severity::Error))
+                            .await.map(|state| (state, SendRowsEndedReason::Errored { error }));
+                    }
                     Some(PeekResponseUnary::Rows(rows)) => {
                         count += rows.len();
                         for row in rows {
@@ -2076,21 +2648,63 @@ where
             .unwrap_or(usize::MAX);
         tracing::debug!("COPY FROM max buffer size: {max_size} bytes");
 
-        let mut data = Vec::new();
+        let column_types = typ
+            .column_types
+            .iter()
+            .map(|x| &x.scalar_type)
+            .map(mz_pgrepr::Type::from)
+            .collect::<Vec<mz_pgrepr::Type>>();
+
+        // Decode rows as the data arrives, rather than buffering the raw input
+        // until `CopyDone`, so that malformed input fails fast and we only hold
+        // on to one copy of the data. Decoded rows are written to the
+        // transaction in batches.
+        let mut decoder = CopyFromDecoder::new(column_types, params);
+        let mut count = 0;
+        let mut written_bytes = 0;
         loop {
             let message = self.conn.recv().await?;
             match message {
                 Some(FrontendMessage::CopyData(buf)) => {
                     // Bail before we OOM.
-                    if (data.len() + buf.len()) > max_size {
+                    if (written_bytes + decoder.buffered_bytes() + buf.len()) > max_size {
+                        let msg = "COPY FROM STDIN too large";
+                        self.adapter_client.retire_execute(
+                            std::mem::take(ctx_extra),
+                            StatementEndedExecutionReason::Errored {
+                                error: msg.to_string(),
+                            },
+                        );
                         return self
-                            .error(ErrorResponse::error(
-                                SqlState::INSUFFICIENT_RESOURCES,
-                                "COPY FROM STDIN too large",
-                            ))
+                            .error(ErrorResponse::error(SqlState::INSUFFICIENT_RESOURCES, msg))
                             .await;
                     }
-                    data.extend(buf)
+                    if let Err(e) = decoder.push(&buf) {
+                        return self.copy_from_decode_error(e, ctx_extra).await;
+                    }
+                    if decoder.buffered_bytes() < COPY_FROM_BATCH_SIZE {
+                        continue;
+                    }
+                    let rows = decoder.take_rows();
+                    // The buffered bytes may all belong to a row that is not complete yet.
+                    if rows.is_empty() {
+                        continue;
+                    }
+                    written_bytes += rows.iter().map(|row| row.byte_len()).sum::<usize>();
+                    count += rows.len();
+                    if let Err(e) = self
+                        .adapter_client
+                        .insert_rows_batch(id, columns.clone(), rows)
+                        .await
+                    {
+                        self.adapter_client.retire_execute(
+                            std::mem::take(ctx_extra),
+                            StatementEndedExecutionReason::Errored {
+                                error: e.to_string(),
+                            },
+                        );
+                        return self.error(e.into_response(Severity::Error)).await;
+                    }
                 }
                 Some(FrontendMessage::CopyDone) => break,
                 Some(FrontendMessage::CopyFail(err)) => {
@@ -2124,32 +2738,12 @@ where
             }
         }
 
-        let column_types = typ
-            .column_types
-            .iter()
-            .map(|x| &x.scalar_type)
-            .map(mz_pgrepr::Type::from)
-            .collect::<Vec<mz_pgrepr::Type>>();
-
-        let rows = match mz_pgcopy::decode_copy_format(&data, &column_types, params) {
+        let rows = match decoder.finish() {
             Ok(rows) => rows,
-            Err(e) => {
-                self.adapter_client.retire_execute(
-                    std::mem::take(ctx_extra),
-                    StatementEndedExecutionReason::Errored {
-                        error: e.to_string(),
-                    },
-                );
-                return self
-                    .error(ErrorResponse::error(
-                        SqlState::BAD_COPY_FILE_FORMAT,
-                        format!("{}", e),
-                    ))
-                    .await;
-            }
+            Err(e) => return self.copy_from_decode_error(e, ctx_extra).await,
         };
 
-        let count = rows.len();
+        count += rows.len();
 
         if let Err(e) = self
             .adapter_client
@@ -2171,6 +2765,24 @@ where
         Ok(State::Ready)
     }
 
+    async fn copy_from_decode_error(
+        &mut self,
+        e: io::Error,
+        ctx_extra: &mut ExecuteContextExtra,
+    ) -> Result<State, io::Error> {
+        self.adapter_client.retire_execute(
+            std::mem::take(ctx_extra),
+            StatementEndedExecutionReason::Errored {
+                error: e.to_string(),
+            },
+        );
+        self.error(ErrorResponse::error(
+            SqlState::BAD_COPY_FILE_FORMAT,
+            format!("{}", e),
+        ))
+        .await
+    }
+
     #[instrument(level = "debug")]
     async fn send_pending_notices(&mut self) -> Result<(), io::Error> {
         let notices = self
@@ -2236,6 +2848,27 @@ where
     }
 }
 
+/// Returns an error message if values of type `ty` cannot be sent in the binary
+/// format, either because the type has no binary encoding or because one of its
+/// component types does not.
+///
+/// Callers report the error with `SqlState::PROTOCOL_VIOLATION`, whether the
+/// binary format was requested by a Bind message or by `COPY TO`.
+fn unsupported_binary_encoding(ty: &ScalarType) -> Option<&'static str> {
+    match ty {
+        ScalarType::List { .. } => Some("binary encoding of list types is not implemented"),
+        ScalarType::Map { .. } => Some("binary encoding of map types is not implemented"),
+        ScalarType::AclItem => Some("binary encoding of aclitem types does not exist"),
+        ScalarType::Array(element_type) | ScalarType::Range { element_type } => {
+            unsupported_binary_encoding(element_type)
+        }
+        ScalarType::Record { fields, .. } => fields
+            .iter()
+            .find_map(|(_, ty)| unsupported_binary_encoding(&ty.scalar_type)),
+        _ => None,
+    }
+}
+
 fn pad_formats(formats: Vec<Format>, n: usize) -> Result<Vec<Format>, String> {
     match (formats.len(), n) {
         (0, e) => Ok(vec![Format::Text; e]),
@@ -2286,6 +2919,60 @@ fn portal_exec_message(
     }
 }
 
+/// Computes the rows that a FETCH in `direction` returns from a cursor with
+/// `len` buffered rows at `position`, following PostgreSQL's semantics.
+///
+/// Positions are 1-based, with 0 before the first row and `len + 1` after the
+/// last row. Returns the 0-based indexes of the rows, in the order in which
+/// they are returned, and the new position of the cursor.
+fn scroll_cursor(position: usize, len: usize, direction: FetchDirection) -> (Vec<usize>, usize) {
+    // Moves to the absolute row `target`, returning it if it exists.
+    let seek = |target: i128| -> (Vec<usize>, usize) {
+        if target < 1 {
+            (vec![], 0)
+        } else if target > i128::cast_from(len) {
+            (vec![], len + 1)
+        } else {
+            let target = usize::try_from(target).expect("known to be in range");
+            (vec![target - 1], target)
+        }
+    };
+    let forward = |count: usize| -> (Vec<usize>, usize) {
+        let end = position.saturating_add(count);
+        let indexes = (position..cmp::min(end, len)).collect();
+        (indexes, if end > len { len + 1 } else { end })
+    };
+    let backward = |count: usize| -> (Vec<usize>, usize) {
+        let start = cmp::min(position, len + 1);
+        let indexes = (position.saturating_sub(count).max(1)..start)
+            .rev()
+            .map(|row| row - 1)
+            .collect();
+        (indexes, start.saturating_sub(count))
+    };
+    match direction {
+        // As in PostgreSQL, fetching zero rows re-fetches the current row.
+        FetchDirection::ForwardCount(0) | FetchDirection::BackwardCount(0) => {
+            if position >= 1 && position <= len {
+                (vec![position - 1], position)
+            } else {
+                (vec![], position)
+            }
+        }
+        FetchDirection::ForwardCount(count) => forward(usize::cast_from(count)),
+        FetchDirection::ForwardAll => forward(usize::MAX),
+        FetchDirection::BackwardCount(count) => backward(usize::cast_from(count)),
+        FetchDirection::BackwardAll => backward(usize::MAX),
+        FetchDirection::Absolute(0) => (vec![], 0),
+        FetchDirection::Absolute(row) if row > 0 => seek(i128::from(row)),
+        FetchDirection::Absolute(row) => seek(i128::cast_from(len) + 1 + i128::from(row)),
+        FetchDirection::Relative(0) => {
+            scroll_cursor(position, len, FetchDirection::ForwardCount(0))
+        }
+        FetchDirection::Relative(offset) => seek(i128::cast_from(position) + i128::from(offset)),
+    }
+}
+
 // A GetResponse used by send_rows during FETCH queries.
 fn fetch_message(
     _max_rows: ExecuteCount,
@@ -2318,6 +3005,8 @@ fn is_txn_exit_stmt(stmt: Option<&Statement<Raw>>) -> bool {
 enum FetchResult {
     Rows(Option<Vec<Row>>),
     Canceled,
+    TimedOut,
+    ResultSizeExceeded(String),
     Error(String),
     Notice(AdapterNotice),
 }
@@ -73,6 +73,8 @@ pub enum TryFromProtoError {
     InvalidBitFlags(String),
     /// Failed to deserialize a LIKE/ILIKE pattern.
     LikePatternDeserializationError(String),
+    /// Failed to parse a serialized cron expression.
+    InvalidCronExpression(String),
 }
 
 impl TryFromProtoError {
@@ -164,6 +166,7 @@ impl std::fmt::Display for TryFromProtoError {
                 "Protobuf deserialization failed for a LIKE/ILIKE pattern: `{}`",
                 inner_error
             ),
+            InvalidCronExpression(error) => error.fmt(f),
         }
     }
 }
@@ -198,6 +201,7 @@ impl std::error::Error for TryFromProtoError {
             InvalidUrl(error) => Some(error),
             InvalidBitFlags(_) => None,
             LikePatternDeserializationError(_) => None,
+            InvalidCronExpression(_) => None,
         }
     }
 }
@@ -0,0 +1,247 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Text collations.
+//!
+//! Strings are stored and, by default, ordered by their code points. A
+//! collation changes how strings are compared and ordered, but never how they
+//! are stored. Besides the code point collations that PostgreSQL also offers
+//! (`C`, `POSIX`, and `ucs_basic`), we support the ICU collations of a set of
+//! common locales, which are named like the ICU collations that PostgreSQL
+//! creates, e.g. `de-x-icu`.
+
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use icu_collator::{Collator, CollatorOptions};
+use icu_locid::Locale;
+use mz_lowertest::MzReflect;
+use mz_proto::{RustType, TryFromProtoError};
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+/// A collation, which determines how strings are compared and ordered.
+#[derive(
+    Arbitrary,
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize,
+    MzReflect,
+)]
+pub enum Collation {
+    /// Orders strings by their code points, which is how strings are ordered
+    /// when no collation is specified.
+    #[default]
+    CodePoint,
+    /// Orders strings with the Unicode Collation Algorithm, as tailored by ICU
+    /// for a locale.
+    Icu(IcuLocale),
+}
+
+/// The names of the collations that order strings by their code points.
+const CODE_POINT_COLLATIONS: &[&str] = &["default", "C", "POSIX", "ucs_basic"];
+
+/// The suffix of the names of ICU collations.
+const ICU_SUFFIX: &str = "-x-icu";
+
+impl Collation {
+    /// Returns the collation with the given name, if it is supported.
+    ///
+    /// As in PostgreSQL, collation names are case sensitive.
+    pub fn from_name(name: &str) -> Option<Collation> {
+        if CODE_POINT_COLLATIONS.contains(&name) {
+            return Some(Collation::CodePoint);
+        }
+        let tag = name.strip_suffix(ICU_SUFFIX)?;
+        IcuLocale::from_tag(tag).map(Collation::Icu)
+    }
+
+    /// Compares `a` and `b` according to this collation.
+    ///
+    /// Strings that an ICU collation considers equal, e.g. because they only
+    /// differ in characters that the collation ignores, are ordered by their
+    /// code points. As in PostgreSQL, collations are thus deterministic: only
+    /// identical strings compare as equal.
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        match self {
+            Collation::CodePoint => a.cmp(b),
+            Collation::Icu(locale) => locale.compare(a, b).then_with(|| a.cmp(b)),
+        }
+    }
+}
+
+impl fmt::Display for Collation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Collation::CodePoint => f.write_str("C"),
+            Collation::Icu(locale) => write!(f, "{}{ICU_SUFFIX}", locale.tag()),
+        }
+    }
+}
+
+impl RustType<String> for Collation {
+    fn into_proto(&self) -> String {
+        self.to_string()
+    }
+
+    fn from_proto(proto: String) -> Result<Self, TryFromProtoError> {
+        if proto.is_empty() {
+            return Ok(Collation::CodePoint);
+        }
+        Collation::from_name(&proto)
+            .ok_or_else(|| TryFromProtoError::unknown_enum_variant(format!("Collation::{proto}")))
+    }
+}
+
+/// The locales whose ICU collations we support.
+#[derive(
+    Arbitrary,
+    Debug,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize,
+    MzReflect,
+)]
+pub enum IcuLocale {
+    /// The root locale, which applies the Unicode Collation Algorithm without
+    /// any tailoring.
+    Root,
+    Danish,
+    Dutch,
+    English,
+    EnglishGreatBritain,
+    EnglishUnitedStates,
+    Finnish,
+    French,
+    German,
+    Italian,
+    Japanese,
+    Korean,
+    NorwegianBokmal,
+    Polish,
+    Portuguese,
+    Russian,
+    Spanish,
+    Swedish,
+    Turkish,
+    Chinese,
+}
+
+/// The BCP 47 language tag of each supported locale.
+const ICU_LOCALES: &[(IcuLocale, &str)] = &[
+    (IcuLocale::Root, "und"),
+    (IcuLocale::Danish, "da"),
+    (IcuLocale::Dutch, "nl"),
+    (IcuLocale::English, "en"),
+    (IcuLocale::EnglishGreatBritain, "en-GB"),
+    (IcuLocale::EnglishUnitedStates, "en-US"),
+    (IcuLocale::Finnish, "fi"),
+    (IcuLocale::French, "fr"),
+    (IcuLocale::German, "de"),
+    (IcuLocale::Italian, "it"),
+    (IcuLocale::Japanese, "ja"),
+    (IcuLocale::Korean, "ko"),
+    (IcuLocale::NorwegianBokmal, "nb"),
+    (IcuLocale::Polish, "pl"),
+    (IcuLocale::Portuguese, "pt"),
+    (IcuLocale::Russian, "ru"),
+    (IcuLocale::Spanish, "es"),
+    (IcuLocale::Swedish, "sv"),
+    (IcuLocale::Turkish, "tr"),
+    (IcuLocale::Chinese, "zh"),
+];
+
+thread_local! {
+    /// The collators of the locales that were used by this thread. Creating a
+    /// collator loads its data, so collators are reused across comparisons.
+    static ICU_COLLATORS: RefCell<BTreeMap<IcuLocale, Collator>> = RefCell::new(BTreeMap::new());
+}
+
+impl IcuLocale {
+    /// Returns the locale with the BCP 47 language tag `tag`, if it is supported.
+    fn from_tag(tag: &str) -> Option<IcuLocale> {
+        ICU_LOCALES
+            .iter()
+            .find(|(_, t)| *t == tag)
+            .map(|(locale, _)| *locale)
+    }
+
+    /// Returns the BCP 47 language tag of the locale.
+    pub fn tag(&self) -> &'static str {
+        ICU_LOCALES
+            .iter()
+            .find(|(locale, _)| locale == self)
+            .map(|(_, tag)| *tag)
+            .expect("all locales have a tag")
+    }
+
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        ICU_COLLATORS.with(|collators| {
+            let mut collators = collators.borrow_mut();
+            let collator = collators.entry(*self).or_insert_with(|| {
+                let locale: Locale = self.tag().parse().expect("valid language tag");
+                Collator::try_new(&(&locale).into(), CollatorOptions::new())
+                    .expect("collation data is compiled in for all locales")
+            });
+            collator.compare(a, b)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::{Collation, IcuLocale};
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // too slow
+    fn test_icu_collations() {
+        let german = Collation::from_name("de-x-icu").unwrap();
+        assert_eq!(german, Collation::Icu(IcuLocale::German));
+        assert_eq!(german.to_string(), "de-x-icu");
+
+        // Code points order all uppercase letters before all lowercase letters,
+        // and accented letters after all ASCII letters.
+        let code_point = Collation::from_name("C").unwrap();
+        assert_eq!(code_point.compare("B", "a"), Ordering::Less);
+        assert_eq!(code_point.compare("é", "f"), Ordering::Greater);
+        assert_eq!(german.compare("B", "a"), Ordering::Greater);
+        assert_eq!(german.compare("é", "f"), Ordering::Less);
+        assert_eq!(german.compare("a", "A"), Ordering::Less);
+
+        // Swedish orders "ä" after "z", unlike German.
+        let swedish = Collation::from_name("sv-x-icu").unwrap();
+        assert_eq!(german.compare("ä", "z"), Ordering::Less);
+        assert_eq!(swedish.compare("ä", "z"), Ordering::Greater);
+
+        // Collations are deterministic.
+        assert_eq!(german.compare("a", "a"), Ordering::Equal);
+        assert_ne!(german.compare("a\u{0}", "a"), Ordering::Equal);
+
+        assert_eq!(Collation::from_name("de-X-ICU"), None);
+        assert_eq!(Collation::from_name("xx-x-icu"), None);
+    }
+}
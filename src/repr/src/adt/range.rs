@@ -247,6 +247,40 @@ impl<D> Range<D> {
         flags.bits()
     }
 
+    /// Constructs a range from flag bits appropriate to PG-compatible
+    /// encodings, calling `bound` to produce the value of each finite bound,
+    /// lower bound first.
+    pub fn from_pg_flag_bits<E, F>(flags: u8, mut bound: F) -> Result<Range<D>, E>
+    where
+        F: FnMut() -> Result<D, E>,
+        E: From<&'static str>,
+    {
+        let flags = PgFlags::from_bits(flags).ok_or("invalid range flags")?;
+        if flags.contains(PgFlags::EMPTY) {
+            return Ok(Range { inner: None });
+        }
+        let lower = match flags.contains(PgFlags::LB_INFINITE) {
+            true => None,
+            false => Some(bound()?),
+        };
+        let upper = match flags.contains(PgFlags::UB_INFINITE) {
+            true => None,
+            false => Some(bound()?),
+        };
+        Ok(Range {
+            inner: Some(RangeInner {
+                lower: RangeBound {
+                    inclusive: flags.contains(PgFlags::LB_INCLUSIVE),
+                    bound: lower,
+                },
+                upper: RangeBound {
+                    inclusive: flags.contains(PgFlags::UB_INCLUSIVE),
+                    bound: upper,
+                },
+            }),
+        })
+    }
+
     /// Converts `self` from having bounds of type `D` to type `O`, converting
     /// the current bounds using `conv`.
     pub fn into_bounds<F, O>(self, conv: F) -> Range<O>
@@ -391,6 +425,24 @@ where
         })
     }
 
+    /// Returns the smallest range that contains both `self` and `other`.
+    ///
+    /// Unlike [`Range::union`], the ranges need not overlap or be adjacent.
+    pub fn merge(&self, other: &Range<B>) -> Range<B> {
+        let (s, o) = match (self.inner, other.inner) {
+            (None, None) => return Range { inner: None },
+            (inner @ Some(_), None) | (None, inner @ Some(_)) => return Range { inner },
+            (Some(s), Some(o)) => (s, o),
+        };
+
+        let lower = std::cmp::min(s.lower, o.lower);
+        let upper = std::cmp::max(s.upper, o.upper);
+
+        Range {
+            inner: Some(RangeInner { lower, upper }),
+        }
+    }
+
     pub fn intersection(&self, other: &Range<B>) -> Range<B> {
         // Handle self or other being empty
         let (s, o) = match (self.inner, other.inner) {
@@ -105,6 +105,9 @@ optimizer_feature_flags!({
     enable_reduce_mfp_fusion: bool,
     // Enable joint HIR ⇒ MIR lowering of stacks of left joins.
     enable_variadic_left_join_lowering: bool,
+    // Share key arrangements across fanned-out left joins in the joint HIR ⇒ MIR
+    // lowering of stacks of left joins.
+    enable_left_join_key_sharing: bool,
     // An exclusive upper bound on the number of results we may return from a
     // Persist fast-path peek. Required by the `create_fast_path_plan` call in
     // `peek::Optimizer`.
@@ -0,0 +1,287 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Secrets whose values live outside of Materialize.
+//!
+//! An external secret is stored by the [`SecretsController`](crate::SecretsController) as a
+//! reference to a secret in an external secrets manager rather than as the value itself.
+//! [`ExternalSecretsReader`] wraps the reader of the controller and resolves references whenever
+//! a secret is read, i.e. whenever a connection is established. The value therefore never touches
+//! the catalog or the secrets controller, and rotating it in the external secrets manager does not
+//! require any DDL.
+//!
+//! References are resolved with the credentials of the process, which can usually read many more
+//! secrets than the users that create external secrets should have access to. Only references
+//! that are allowed by an [`ExternalSecretsAllowlist`], configured by the operator, are therefore
+//! resolved.
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context};
+use async_trait::async_trait;
+use mz_repr::GlobalId;
+
+use crate::SecretsReader;
+
+/// The prefix of the contents of a secret that refers to an external secret.
+///
+/// The values of regular secrets are valid UTF-8, and `0xFF` never appears in UTF-8, so the value
+/// of a regular secret can never be mistaken for a reference.
+const EXTERNAL_SECRET_PREFIX: &[u8] = b"\xffexternal:";
+
+/// The ARN prefix of secrets stored in AWS Secrets Manager.
+const AWS_SECRETS_MANAGER_ARN_PREFIX: &str = "arn:aws:secretsmanager:";
+
+/// A reference to a secret in an external secrets manager.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExternalSecretReference {
+    /// A secret in AWS Secrets Manager, identified by its ARN.
+    AwsSecretsManager { arn: String },
+}
+
+impl ExternalSecretReference {
+    /// Parses a user-provided reference to an external secret.
+    ///
+    /// ARNs of AWS Secrets Manager secrets have the form
+    /// `arn:aws:secretsmanager:<region>:<account>:secret:<name>`.
+    pub fn parse(reference: &str) -> Result<Self, anyhow::Error> {
+        let parts: Vec<_> = reference.splitn(7, ':').collect();
+        match parts[..] {
+            [_, _, _, _, _, "secret", name]
+                if reference.starts_with(AWS_SECRETS_MANAGER_ARN_PREFIX) && !name.is_empty() =>
+            {
+                Ok(ExternalSecretReference::AwsSecretsManager {
+                    arn: reference.to_string(),
+                })
+            }
+            _ => bail!(
+                "invalid external secret reference {reference:?}: \
+                expected the ARN of an AWS Secrets Manager secret"
+            ),
+        }
+    }
+
+    /// Returns the name of the referenced secret in AWS Secrets Manager.
+    pub fn aws_secret_name(&self) -> &str {
+        let ExternalSecretReference::AwsSecretsManager { arn } = self;
+        arn.splitn(7, ':').nth(6).expect("validated when parsed")
+    }
+
+    /// Returns the contents to store in the secrets controller for this reference.
+    pub fn encode(&self) -> Vec<u8> {
+        let ExternalSecretReference::AwsSecretsManager { arn } = self;
+        let mut contents = EXTERNAL_SECRET_PREFIX.to_vec();
+        contents.extend_from_slice(arn.as_bytes());
+        contents
+    }
+
+    /// Decodes the `contents` of a secret, returning `None` if they are the value of a regular
+    /// secret.
+    pub fn decode(contents: &[u8]) -> Result<Option<Self>, anyhow::Error> {
+        let Some(reference) = contents.strip_prefix(EXTERNAL_SECRET_PREFIX) else {
+            return Ok(None);
+        };
+        let reference = std::str::from_utf8(reference).context("decoding external secret")?;
+        Self::parse(reference).map(Some)
+    }
+}
+
+/// The external secrets that may be resolved.
+#[derive(Debug, Clone, Default)]
+pub struct ExternalSecretsAllowlist {
+    /// The ARN prefixes of the AWS Secrets Manager secrets that may be resolved.
+    aws_arn_prefixes: Vec<String>,
+}
+
+impl ExternalSecretsAllowlist {
+    pub fn new(aws_arn_prefixes: Vec<String>) -> Self {
+        ExternalSecretsAllowlist { aws_arn_prefixes }
+    }
+
+    /// Reports whether no external secrets may be resolved.
+    pub fn is_empty(&self) -> bool {
+        self.aws_arn_prefixes.is_empty()
+    }
+
+    /// Returns an error if `reference` may not be resolved.
+    pub fn check(&self, reference: &ExternalSecretReference) -> Result<(), anyhow::Error> {
+        let ExternalSecretReference::AwsSecretsManager { arn } = reference;
+        if !self
+            .aws_arn_prefixes
+            .iter()
+            .any(|prefix| arn.starts_with(prefix))
+        {
+            bail!("external secret {arn} does not match any of the allowed ARN prefixes");
+        }
+        Ok(())
+    }
+}
+
+/// Fetches the values of external secrets.
+#[async_trait]
+pub trait ExternalSecretsResolver: Debug + Send + Sync {
+    /// Returns the current value of the referenced secret.
+    async fn resolve(&self, reference: &ExternalSecretReference) -> Result<Vec<u8>, anyhow::Error>;
+}
+
+/// A [`SecretsReader`] that resolves the references of external secrets read from another reader.
+#[derive(Debug)]
+pub struct ExternalSecretsReader {
+    inner: Arc<dyn SecretsReader>,
+    /// The resolver of external secrets, or `None` if external secrets are not supported.
+    resolver: Option<Arc<dyn ExternalSecretsResolver>>,
+    /// The external secrets that may be resolved.
+    allowlist: ExternalSecretsAllowlist,
+}
+
+impl ExternalSecretsReader {
+    pub fn new(
+        inner: Arc<dyn SecretsReader>,
+        resolver: Option<Arc<dyn ExternalSecretsResolver>>,
+        allowlist: ExternalSecretsAllowlist,
+    ) -> Self {
+        ExternalSecretsReader {
+            inner,
+            resolver,
+            allowlist,
+        }
+    }
+}
+
+#[async_trait]
+impl SecretsReader for ExternalSecretsReader {
+    async fn read(&self, id: GlobalId) -> Result<Vec<u8>, anyhow::Error> {
+        let contents = self.inner.read(id).await?;
+        let Some(reference) = ExternalSecretReference::decode(&contents)? else {
+            return Ok(contents);
+        };
+        let resolver = self.resolver.as_ref().ok_or_else(|| {
+            anyhow!(
+                "secret {id} refers to an external secret, \
+                but no external secrets backend is configured"
+            )
+        })?;
+        self.allowlist
+            .check(&reference)
+            .with_context(|| format!("resolving external secret {id}"))?;
+        resolver
+            .resolve(&reference)
+            .await
+            .with_context(|| format!("resolving external secret {id}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use mz_repr::GlobalId;
+
+    use super::{
+        ExternalSecretReference, ExternalSecretsAllowlist, ExternalSecretsReader,
+        ExternalSecretsResolver,
+    };
+    use crate::{InMemorySecretsController, SecretsController, SecretsReader};
+
+    const ARN: &str = "arn:aws:secretsmanager:us-east-1:000000000000:secret:pg-password";
+
+    #[derive(Debug)]
+    struct TestResolver;
+
+    #[async_trait]
+    impl ExternalSecretsResolver for TestResolver {
+        async fn resolve(
+            &self,
+            reference: &ExternalSecretReference,
+        ) -> Result<Vec<u8>, anyhow::Error> {
+            let ExternalSecretReference::AwsSecretsManager { arn } = reference;
+            Ok(format!("value of {arn}").into_bytes())
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_external_secret_reference() {
+        let reference = ExternalSecretReference::parse(ARN).unwrap();
+        assert_eq!(
+            ExternalSecretReference::decode(&reference.encode()).unwrap(),
+            Some(reference)
+        );
+
+        // Regular values are never references.
+        assert_eq!(
+            ExternalSecretReference::decode(ARN.as_bytes()).unwrap(),
+            None
+        );
+        assert_eq!(ExternalSecretReference::decode(b"").unwrap(), None);
+
+        assert_eq!(reference.aws_secret_name(), "pg-password");
+
+        assert!(ExternalSecretReference::parse("vault:secret/pg").is_err());
+        assert!(ExternalSecretReference::parse("arn:aws:secretsmanager:").is_err());
+        assert!(ExternalSecretReference::parse(
+            "arn:aws:secretsmanager:us-east-1:000000000000:secret:"
+        )
+        .is_err());
+        assert!(ExternalSecretReference::parse(
+            "arn:aws:secretsmanager:us-east-1:000000000000:other:pg-password"
+        )
+        .is_err());
+    }
+
+    #[mz_ore::test(tokio::test)]
+    async fn test_external_secrets_reader() {
+        let controller = InMemorySecretsController::new();
+        let reference = ExternalSecretReference::parse(ARN).unwrap();
+        controller
+            .ensure(GlobalId::User(1), b"regular")
+            .await
+            .unwrap();
+        controller
+            .ensure(GlobalId::User(2), &reference.encode())
+            .await
+            .unwrap();
+
+        let allowlist = ExternalSecretsAllowlist::new(vec![
+            "arn:aws:secretsmanager:us-east-1:000000000000:secret:pg-".into(),
+        ]);
+        let reader = ExternalSecretsReader::new(
+            controller.reader(),
+            Some(Arc::new(TestResolver)),
+            allowlist,
+        );
+        assert_eq!(reader.read(GlobalId::User(1)).await.unwrap(), b"regular");
+        assert_eq!(
+            reader.read_string(GlobalId::User(2)).await.unwrap(),
+            format!("value of {ARN}")
+        );
+
+        // Secrets that are not allowed are not resolved.
+        let allowlist = ExternalSecretsAllowlist::new(vec![
+            "arn:aws:secretsmanager:us-east-1:000000000000:secret:kafka-".into(),
+        ]);
+        let reader = ExternalSecretsReader::new(
+            controller.reader(),
+            Some(Arc::new(TestResolver)),
+            allowlist,
+        );
+        assert_eq!(reader.read(GlobalId::User(1)).await.unwrap(), b"regular");
+        assert!(reader.read(GlobalId::User(2)).await.is_err());
+
+        // Without a resolver, only regular secrets can be read.
+        let reader = ExternalSecretsReader::new(
+            controller.reader(),
+            None,
+            ExternalSecretsAllowlist::default(),
+        );
+        assert_eq!(reader.read(GlobalId::User(1)).await.unwrap(), b"regular");
+        assert!(reader.read(GlobalId::User(2)).await.is_err());
+    }
+}
@@ -19,6 +19,7 @@ use async_trait::async_trait;
 use mz_repr::GlobalId;
 
 pub mod cache;
+pub mod external;
 
 /// Securely manages user secrets.
 #[async_trait]
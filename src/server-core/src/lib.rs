@@ -9,6 +9,7 @@
 
 //! Methods common to servers listening for TCP connections.
 
+use std::collections::BTreeMap;
 use std::future::Future;
 use std::io;
 use std::net::SocketAddr;
@@ -21,7 +22,10 @@ use anyhow::bail;
 use futures::stream::{BoxStream, Stream, StreamExt};
 use mz_ore::error::ErrorExt;
 use mz_ore::task::JoinSetExt;
-use openssl::ssl::{SslAcceptor, SslContext, SslFiletype, SslMethod};
+use openssl::ssl::{
+    SslAcceptor, SslAcceptorBuilder, SslContext, SslFiletype, SslMethod, SslVerifyMode,
+};
+use openssl::x509::X509Name;
 use socket2::{SockRef, TcpKeepalive};
 use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::oneshot;
@@ -196,6 +200,11 @@ pub struct TlsConfig {
     pub context: SslContext,
     /// The TLS mode.
     pub mode: TlsMode,
+    /// How clients may authenticate with TLS client certificates.
+    ///
+    /// Must only be set if `context` verifies client certificates. See
+    /// [`TlsCertConfig::load_client_auth_context`].
+    pub client_auth: Option<TlsClientAuthConfig>,
 }
 
 /// Specifies how strictly to enforce TLS encryption.
@@ -214,11 +223,52 @@ pub struct TlsCertConfig {
     pub cert: PathBuf,
     /// The path to the TLS key.
     pub key: PathBuf,
+    /// Configuration for authenticating clients via TLS client certificates,
+    /// if enabled.
+    pub client_auth: Option<TlsClientAuthConfig>,
+}
+
+/// Configures authentication of clients via TLS client certificates.
+#[derive(Debug, Clone)]
+pub struct TlsClientAuthConfig {
+    /// The path to the CA certificate that client certificates must be signed
+    /// by.
+    pub ca: PathBuf,
+    /// Whether clients must authenticate with a certificate, rather than via
+    /// any of the server's other authentication methods.
+    pub required: bool,
+    /// Maps the common names of client certificates to the users that they
+    /// authenticate as. Certificates whose common name is not mapped
+    /// authenticate as the user named by their common name.
+    pub user_map: BTreeMap<String, String>,
 }
 
 impl TlsCertConfig {
     /// Returns the SSL context to use in TlsConfigs.
     pub fn load_context(&self) -> Result<SslContext, anyhow::Error> {
+        Ok(self.acceptor_builder()?.build().into_context())
+    }
+
+    /// Like [Self::load_context], but if client certificate authentication is
+    /// configured, additionally requests a certificate from clients and
+    /// verifies it against the configured CA.
+    ///
+    /// Clients that do not present a certificate are still permitted to
+    /// complete the handshake; it is up to the server to decide whether they
+    /// may authenticate some other way.
+    pub fn load_client_auth_context(&self) -> Result<SslContext, anyhow::Error> {
+        let mut builder = self.acceptor_builder()?;
+        if let Some(client_auth) = &self.client_auth {
+            builder.set_ca_file(&client_auth.ca)?;
+            builder.set_client_ca_list(X509Name::load_client_ca_file(&client_auth.ca)?);
+            builder.set_verify(SslVerifyMode::PEER);
+            // Required by OpenSSL to resume sessions when verifying peers.
+            builder.set_session_id_context(b"materialize")?;
+        }
+        Ok(builder.build().into_context())
+    }
+
+    fn acceptor_builder(&self) -> Result<SslAcceptorBuilder, anyhow::Error> {
         // Mozilla publishes three presets: old, intermediate, and modern. They
         // recommend the intermediate preset for general purpose servers, which
         // is what we use, as it is compatible with nearly every client released
@@ -228,7 +278,7 @@ impl TlsCertConfig {
         let mut builder = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls())?;
         builder.set_certificate_chain_file(&self.cert)?;
         builder.set_private_key_file(&self.key, SslFiletype::PEM)?;
-        Ok(builder.build().into_context())
+        Ok(builder)
     }
 
     /// Like [Self::load_context] but attempts to reload the files each time `ticker` yields an item.
@@ -343,7 +393,11 @@ impl TlsCliArgs {
         } else {
             let cert = self.tls_cert.unwrap();
             let key = self.tls_key.unwrap();
-            Ok(Some(TlsCertConfig { cert, key }))
+            Ok(Some(TlsCertConfig {
+                cert,
+                key,
+                client_auth: None,
+            }))
         }
     }
 }
@@ -11,9 +11,12 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use clap::ArgEnum;
-use mz_aws_secrets_controller::AwsSecretsClient;
+use mz_aws_secrets_controller::{AwsExternalSecretsResolver, AwsSecretsClient};
 use mz_orchestrator_kubernetes::secrets::KubernetesSecretsReader;
 use mz_orchestrator_process::secrets::ProcessSecretsReader;
+use mz_secrets::external::{
+    ExternalSecretsAllowlist, ExternalSecretsReader, ExternalSecretsResolver,
+};
 use mz_secrets::SecretsReader;
 
 #[derive(clap::Parser, Clone, Debug)]
@@ -44,6 +47,15 @@ pub struct SecretsReaderCliArgs {
         env = "SECRETS_READER_AWS_PREFIX"
     )]
     pub secrets_reader_aws_prefix: Option<String>,
+    /// The ARN prefixes of the external secrets in AWS Secrets Manager that may be resolved.
+    /// External secrets are not supported if empty.
+    #[structopt(
+        long,
+        env = "SECRETS_READER_EXTERNAL_AWS_ALLOWED_ARN_PREFIX",
+        multiple = true,
+        use_delimiter = true
+    )]
+    pub secrets_reader_external_aws_allowed_arn_prefix: Vec<String>,
 }
 
 #[derive(ArgEnum, Debug, Clone, Copy)]
@@ -56,6 +68,12 @@ pub enum SecretsControllerKind {
 impl SecretsReaderCliArgs {
     /// Loads the secrets reader specified by the command-line arguments.
     pub async fn load(self) -> Result<Arc<dyn SecretsReader>, anyhow::Error> {
+        let allowed_arn_prefixes = self.secrets_reader_external_aws_allowed_arn_prefix.clone();
+        let reader = self.load_inner().await?;
+        Ok(wrap_external_secrets_reader(reader, allowed_arn_prefixes).await)
+    }
+
+    async fn load_inner(self) -> Result<Arc<dyn SecretsReader>, anyhow::Error> {
         match self.secrets_reader {
             SecretsControllerKind::LocalFile => {
                 let dir = self.secrets_reader_local_file_dir.expect("clap enforced");
@@ -78,6 +96,16 @@ impl SecretsReaderCliArgs {
     ///
     /// Expects the correct arguments to be filled in, based on the `clap` requirements.
     pub fn to_flags(&self) -> Vec<String> {
+        let mut flags = self.reader_flags();
+        for prefix in &self.secrets_reader_external_aws_allowed_arn_prefix {
+            flags.push(format!(
+                "--secrets-reader-external-aws-allowed-arn-prefix={prefix}"
+            ));
+        }
+        flags
+    }
+
+    fn reader_flags(&self) -> Vec<String> {
         match self.secrets_reader {
             SecretsControllerKind::LocalFile => {
                 vec![
@@ -116,3 +144,19 @@ impl SecretsReaderCliArgs {
         }
     }
 }
+
+/// Wraps `reader` in a reader that resolves external secrets in AWS Secrets Manager whose ARNs
+/// start with one of `aws_allowed_arn_prefixes`. Without allowed prefixes, reading an external
+/// secret returns an error.
+pub async fn wrap_external_secrets_reader(
+    reader: Arc<dyn SecretsReader>,
+    aws_allowed_arn_prefixes: Vec<String>,
+) -> Arc<dyn SecretsReader> {
+    let allowlist = ExternalSecretsAllowlist::new(aws_allowed_arn_prefixes);
+    let resolver: Option<Arc<dyn ExternalSecretsResolver>> = if allowlist.is_empty() {
+        None
+    } else {
+        Some(Arc::new(AwsExternalSecretsResolver::new().await))
+    };
+    Arc::new(ExternalSecretsReader::new(reader, resolver, allowlist))
+}
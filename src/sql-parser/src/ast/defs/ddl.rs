@@ -33,6 +33,8 @@ pub enum MaterializedViewOptionName {
     RetainHistory,
     /// The `REFRESH [=] ...` option.
     Refresh,
+    /// The `MEMORY LIMIT [=] <size>` option.
+    MemoryLimit,
 }
 
 impl AstDisplay for MaterializedViewOptionName {
@@ -41,6 +43,7 @@ impl AstDisplay for MaterializedViewOptionName {
             MaterializedViewOptionName::AssertNotNull => f.write_str("ASSERT NOT NULL"),
             MaterializedViewOptionName::RetainHistory => f.write_str("RETAIN HISTORY"),
             MaterializedViewOptionName::Refresh => f.write_str("REFRESH"),
+            MaterializedViewOptionName::MemoryLimit => f.write_str("MEMORY LIMIT"),
         }
     }
 }
@@ -176,6 +179,7 @@ pub enum CsrConfigOptionName<T: AstInfo> {
     AvroValueFullname,
     NullDefaults,
     AvroDocOn(AvroDocOn<T>),
+    ValueSchemaEvolution,
 }
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct AvroDocOn<T: AstInfo> {
@@ -223,6 +227,7 @@ impl<T: AstInfo> AstDisplay for CsrConfigOptionName<T> {
             CsrConfigOptionName::AvroValueFullname => f.write_str("AVRO VALUE FULLNAME"),
             CsrConfigOptionName::NullDefaults => f.write_str("NULL DEFAULTS"),
             CsrConfigOptionName::AvroDocOn(doc_on) => f.write_node(doc_on),
+            CsrConfigOptionName::ValueSchemaEvolution => f.write_str("VALUE SCHEMA EVOLUTION"),
         }
     }
 }
@@ -407,7 +412,7 @@ pub enum Format<T: AstInfo> {
     Protobuf(ProtobufSchema<T>),
     Regex(String),
     Csv {
-        columns: CsvColumns,
+        columns: CsvColumns<T>,
         delimiter: char,
     },
     Json {
@@ -417,31 +422,52 @@ pub enum Format<T: AstInfo> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum CsvColumns {
+pub enum CsvColumns<T: AstInfo> {
     /// `WITH count COLUMNS`
     Count(u64),
-    /// `WITH HEADER (ident, ...)?`: `names` is empty if there are no names specified
-    Header { names: Vec<Ident> },
+    /// `WITH HEADER (ident [data_type], ...)?`: `columns` is empty if there are no names
+    /// specified
+    Header { columns: Vec<CsvHeaderColumn<T>> },
 }
 
-impl AstDisplay for CsvColumns {
+impl<T: AstInfo> AstDisplay for CsvColumns<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
             CsvColumns::Count(n) => {
                 f.write_str(n);
                 f.write_str(" COLUMNS")
             }
-            CsvColumns::Header { names } => {
+            CsvColumns::Header { columns } => {
                 f.write_str("HEADER");
-                if !names.is_empty() {
+                if !columns.is_empty() {
                     f.write_str(" (");
-                    f.write_node(&display::comma_separated(names));
+                    f.write_node(&display::comma_separated(columns));
                     f.write_str(")");
                 }
             }
         }
     }
 }
+impl_display_t!(CsvColumns);
+
+/// A column of a `FORMAT CSV WITH HEADER` column list, with an optional type
+/// that its text values are cast to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CsvHeaderColumn<T: AstInfo> {
+    pub name: Ident,
+    pub data_type: Option<T::DataType>,
+}
+
+impl<T: AstInfo> AstDisplay for CsvHeaderColumn<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(data_type) = &self.data_type {
+            f.write_str(" ");
+            f.write_node(data_type);
+        }
+    }
+}
+impl_display_t!(CsvHeaderColumn);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum SourceIncludeMetadata {
@@ -656,6 +682,7 @@ pub enum ConnectionOptionName {
     AssumeRoleArn,
     AssumeRoleSessionName,
     AvailabilityZones,
+    AwsConnection,
     AwsPrivatelink,
     Broker,
     Brokers,
@@ -678,8 +705,10 @@ pub enum ConnectionOptionName {
     SslKey,
     SslMode,
     SessionToken,
+    Token,
     Url,
     User,
+    Warehouse,
 }
 
 impl AstDisplay for ConnectionOptionName {
@@ -687,6 +716,7 @@ impl AstDisplay for ConnectionOptionName {
         f.write_str(match self {
             ConnectionOptionName::AccessKeyId => "ACCESS KEY ID",
             ConnectionOptionName::AvailabilityZones => "AVAILABILITY ZONES",
+            ConnectionOptionName::AwsConnection => "AWS CONNECTION",
             ConnectionOptionName::AwsPrivatelink => "AWS PRIVATELINK",
             ConnectionOptionName::Broker => "BROKER",
             ConnectionOptionName::Brokers => "BROKERS",
@@ -711,8 +741,10 @@ impl AstDisplay for ConnectionOptionName {
             ConnectionOptionName::SslKey => "SSL KEY",
             ConnectionOptionName::SslMode => "SSL MODE",
             ConnectionOptionName::SessionToken => "SESSION TOKEN",
+            ConnectionOptionName::Token => "TOKEN",
             ConnectionOptionName::Url => "URL",
             ConnectionOptionName::User => "USER",
+            ConnectionOptionName::Warehouse => "WAREHOUSE",
         })
     }
 }
@@ -745,6 +777,7 @@ pub enum CreateConnectionType {
     Postgres,
     Ssh,
     MySql,
+    IcebergCatalog,
 }
 
 impl AstDisplay for CreateConnectionType {
@@ -771,6 +804,9 @@ impl AstDisplay for CreateConnectionType {
             Self::MySql => {
                 f.write_str("MYSQL");
             }
+            Self::IcebergCatalog => {
+                f.write_str("ICEBERG CATALOG");
+            }
         }
     }
 }
@@ -854,6 +890,9 @@ pub enum KafkaSinkConfigOptionName {
     CompressionType,
     ProgressGroupIdPrefix,
     Topic,
+    TopicConfig,
+    TopicPartitionCount,
+    TopicReplicationFactor,
     TransactionalIdPrefix,
     LegacyIds,
 }
@@ -864,6 +903,9 @@ impl AstDisplay for KafkaSinkConfigOptionName {
             KafkaSinkConfigOptionName::CompressionType => "COMPRESSION TYPE",
             KafkaSinkConfigOptionName::ProgressGroupIdPrefix => "PROGRESS GROUP ID PREFIX",
             KafkaSinkConfigOptionName::Topic => "TOPIC",
+            KafkaSinkConfigOptionName::TopicConfig => "TOPIC CONFIG",
+            KafkaSinkConfigOptionName::TopicPartitionCount => "TOPIC PARTITION COUNT",
+            KafkaSinkConfigOptionName::TopicReplicationFactor => "TOPIC REPLICATION FACTOR",
             KafkaSinkConfigOptionName::TransactionalIdPrefix => "TRANSACTIONAL ID PREFIX",
             KafkaSinkConfigOptionName::LegacyIds => "LEGACY IDS",
         })
@@ -888,6 +930,41 @@ impl<T: AstInfo> AstDisplay for KafkaSinkConfigOption<T> {
 }
 impl_display_t!(KafkaSinkConfigOption);
 
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IcebergSinkConfigOptionName {
+    CommitInterval,
+    Namespace,
+    Table,
+}
+
+impl AstDisplay for IcebergSinkConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            IcebergSinkConfigOptionName::CommitInterval => "COMMIT INTERVAL",
+            IcebergSinkConfigOptionName::Namespace => "NAMESPACE",
+            IcebergSinkConfigOptionName::Table => "TABLE",
+        })
+    }
+}
+impl_display!(IcebergSinkConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IcebergSinkConfigOption<T: AstInfo> {
+    pub name: IcebergSinkConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for IcebergSinkConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(IcebergSinkConfigOption);
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum PgConfigOptionName {
     /// Hex encoded string of binary serialization of
@@ -1105,6 +1182,12 @@ pub enum CreateSinkConnection<T: AstInfo> {
         connection: T::ItemName,
         options: Vec<KafkaSinkConfigOption<T>>,
         key: Option<KafkaSinkKey>,
+        headers: Option<Ident>,
+    },
+    Iceberg {
+        connection: T::ItemName,
+        options: Vec<IcebergSinkConfigOption<T>>,
+        key: Option<KafkaSinkKey>,
     },
 }
 
@@ -1115,6 +1198,7 @@ impl<T: AstInfo> AstDisplay for CreateSinkConnection<T> {
                 connection,
                 options,
                 key,
+                headers,
             } => {
                 f.write_str("KAFKA CONNECTION ");
                 f.write_node(connection);
@@ -1126,6 +1210,26 @@ impl<T: AstInfo> AstDisplay for CreateSinkConnection<T> {
                 if let Some(key) = key.as_ref() {
                     f.write_node(key);
                 }
+                if let Some(headers) = headers {
+                    f.write_str(" HEADERS FROM ");
+                    f.write_node(headers);
+                }
+            }
+            CreateSinkConnection::Iceberg {
+                connection,
+                options,
+                key,
+            } => {
+                f.write_str("ICEBERG CATALOG CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+                if let Some(key) = key.as_ref() {
+                    f.write_node(key);
+                }
             }
         }
     }
@@ -1250,18 +1354,34 @@ impl_display!(KeyConstraint);
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum CreateSourceOptionName {
+    Errors,
     IgnoreKeys,
+    RetryBackoff,
+    RetryMaxAttempts,
+    RetryMaxBackoff,
+    StartTimestamp,
     Timeline,
     TimestampInterval,
+    TimestampPolicy,
+    UpsertCompactionStyle,
+    UpsertMemoryLimit,
     RetainHistory,
 }
 
 impl AstDisplay for CreateSourceOptionName {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str(match self {
+            CreateSourceOptionName::Errors => "ERRORS",
             CreateSourceOptionName::IgnoreKeys => "IGNORE KEYS",
+            CreateSourceOptionName::RetryBackoff => "RETRY BACKOFF",
+            CreateSourceOptionName::RetryMaxAttempts => "RETRY MAX ATTEMPTS",
+            CreateSourceOptionName::RetryMaxBackoff => "RETRY MAX BACKOFF",
+            CreateSourceOptionName::StartTimestamp => "START TIMESTAMP",
             CreateSourceOptionName::Timeline => "TIMELINE",
             CreateSourceOptionName::TimestampInterval => "TIMESTAMP INTERVAL",
+            CreateSourceOptionName::TimestampPolicy => "TIMESTAMP POLICY",
+            CreateSourceOptionName::UpsertCompactionStyle => "UPSERT COMPACTION STYLE",
+            CreateSourceOptionName::UpsertMemoryLimit => "UPSERT MEMORY LIMIT",
             CreateSourceOptionName::RetainHistory => "RETAIN HISTORY",
         })
     }
@@ -18,6 +18,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::fmt;
 
 use enum_kinds::EnumKind;
@@ -53,14 +54,17 @@ pub enum Statement<T: AstInfo> {
     CreateSink(CreateSinkStatement<T>),
     CreateView(CreateViewStatement<T>),
     CreateMaterializedView(CreateMaterializedViewStatement<T>),
+    CreateContinualTask(CreateContinualTaskStatement<T>),
     CreateTable(CreateTableStatement<T>),
     CreateIndex(CreateIndexStatement<T>),
     CreateType(CreateTypeStatement<T>),
+    CreateFunction(CreateFunctionStatement<T>),
     CreateRole(CreateRoleStatement),
     CreateCluster(CreateClusterStatement<T>),
     CreateClusterReplica(CreateClusterReplicaStatement<T>),
     CreateSecret(CreateSecretStatement<T>),
     AlterCluster(AlterClusterStatement<T>),
+    AlterClusterReplica(AlterClusterReplicaStatement<T>),
     AlterOwner(AlterOwnerStatement<T>),
     AlterObjectRename(AlterObjectRenameStatement),
     AlterObjectSwap(AlterObjectSwapStatement),
@@ -74,6 +78,7 @@ pub enum Statement<T: AstInfo> {
     AlterSystemResetAll(AlterSystemResetAllStatement),
     AlterConnection(AlterConnectionStatement<T>),
     AlterRole(AlterRoleStatement<T>),
+    AlterQuota(AlterQuotaStatement<T>),
     Discard(DiscardStatement),
     DropObjects(DropObjectsStatement),
     DropOwned(DropOwnedStatement<T>),
@@ -123,14 +128,17 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::CreateSink(stmt) => f.write_node(stmt),
             Statement::CreateView(stmt) => f.write_node(stmt),
             Statement::CreateMaterializedView(stmt) => f.write_node(stmt),
+            Statement::CreateContinualTask(stmt) => f.write_node(stmt),
             Statement::CreateTable(stmt) => f.write_node(stmt),
             Statement::CreateIndex(stmt) => f.write_node(stmt),
             Statement::CreateRole(stmt) => f.write_node(stmt),
             Statement::CreateSecret(stmt) => f.write_node(stmt),
             Statement::CreateType(stmt) => f.write_node(stmt),
+            Statement::CreateFunction(stmt) => f.write_node(stmt),
             Statement::CreateCluster(stmt) => f.write_node(stmt),
             Statement::CreateClusterReplica(stmt) => f.write_node(stmt),
             Statement::AlterCluster(stmt) => f.write_node(stmt),
+            Statement::AlterClusterReplica(stmt) => f.write_node(stmt),
             Statement::AlterOwner(stmt) => f.write_node(stmt),
             Statement::AlterObjectRename(stmt) => f.write_node(stmt),
             Statement::AlterObjectSwap(stmt) => f.write_node(stmt),
@@ -144,6 +152,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::AlterSystemResetAll(stmt) => f.write_node(stmt),
             Statement::AlterConnection(stmt) => f.write_node(stmt),
             Statement::AlterRole(stmt) => f.write_node(stmt),
+            Statement::AlterQuota(stmt) => f.write_node(stmt),
             Statement::Discard(stmt) => f.write_node(stmt),
             Statement::DropObjects(stmt) => f.write_node(stmt),
             Statement::DropOwned(stmt) => f.write_node(stmt),
@@ -196,18 +205,22 @@ pub fn statement_kind_label_value(kind: StatementKind) -> &'static str {
         StatementKind::CreateSink => "create_sink",
         StatementKind::CreateView => "create_view",
         StatementKind::CreateMaterializedView => "create_materialized_view",
+        StatementKind::CreateContinualTask => "create_continual_task",
         StatementKind::CreateTable => "create_table",
         StatementKind::CreateIndex => "create_index",
         StatementKind::CreateType => "create_type",
+        StatementKind::CreateFunction => "create_function",
         StatementKind::CreateRole => "create_role",
         StatementKind::CreateCluster => "create_cluster",
         StatementKind::CreateClusterReplica => "create_cluster_replica",
         StatementKind::CreateSecret => "create_secret",
         StatementKind::AlterCluster => "alter_cluster",
+        StatementKind::AlterClusterReplica => "alter_cluster_replica",
         StatementKind::AlterObjectRename => "alter_object_rename",
         StatementKind::AlterObjectSwap => "alter_object_swap",
         StatementKind::AlterIndex => "alter_index",
         StatementKind::AlterRole => "alter_role",
+        StatementKind::AlterQuota => "alter_quota",
         StatementKind::AlterSecret => "alter_secret",
         StatementKind::AlterSetCluster => "alter_set_cluster",
         StatementKind::AlterSink => "alter_sink",
@@ -706,6 +719,7 @@ pub struct CreateWebhookSourceStatement<T: AstInfo> {
     pub body_format: Format<T>,
     pub include_headers: CreateWebhookSourceIncludeHeaders,
     pub validate_using: Option<CreateWebhookSourceCheck<T>>,
+    pub deduplicate: Option<CreateWebhookSourceDeduplicate>,
     pub in_cluster: Option<T::ClusterName>,
 }
 
@@ -733,6 +747,11 @@ impl<T: AstInfo> AstDisplay for CreateWebhookSourceStatement<T> {
             f.write_str(" ");
             f.write_node(validate);
         }
+
+        if let Some(deduplicate) = &self.deduplicate {
+            f.write_str(" ");
+            f.write_node(deduplicate);
+        }
     }
 }
 
@@ -761,6 +780,29 @@ impl<T: AstInfo> AstDisplay for CreateWebhookSourceCheck<T> {
 
 impl_display_t!(CreateWebhookSourceCheck);
 
+/// `DEDUPLICATE ON HEADER <name> [WITHIN <window>]`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CreateWebhookSourceDeduplicate {
+    /// Name of the header that contains the idempotency key of a request.
+    pub header_name: String,
+    /// How long an idempotency key is remembered for, as an interval.
+    pub window: Option<String>,
+}
+
+impl AstDisplay for CreateWebhookSourceDeduplicate {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("DEDUPLICATE ON HEADER ");
+        f.write_node(&display::escaped_string_literal(&self.header_name));
+
+        if let Some(window) = &self.window {
+            f.write_str(" WITHIN ");
+            f.write_node(&display::escaped_string_literal(window));
+        }
+    }
+}
+
+impl_display!(CreateWebhookSourceDeduplicate);
+
 /// `CHECK ( WITH ( ... ) )`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CreateWebhookSourceCheckOptions<T: AstInfo> {
@@ -1137,6 +1179,7 @@ impl_display_t!(CreateSubsourceStatement);
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum CreateSinkOptionName {
     Snapshot,
+    PartitionBy,
 }
 
 impl AstDisplay for CreateSinkOptionName {
@@ -1145,6 +1188,9 @@ impl AstDisplay for CreateSinkOptionName {
             CreateSinkOptionName::Snapshot => {
                 f.write_str("SNAPSHOT");
             }
+            CreateSinkOptionName::PartitionBy => {
+                f.write_str("PARTITION BY");
+            }
         }
     }
 }
@@ -1325,6 +1371,45 @@ impl<T: AstInfo> AstDisplay for CreateMaterializedViewStatement<T> {
 }
 impl_display_t!(CreateMaterializedViewStatement);
 
+/// `CREATE CONTINUAL TASK`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CreateContinualTaskStatement<T: AstInfo> {
+    pub if_not_exists: bool,
+    pub name: UnresolvedItemName,
+    pub in_cluster: Option<T::ClusterName>,
+    /// The table that the changes of `query` are inserted into.
+    pub target: T::ItemName,
+    /// The columns of `target` that the output of `query` maps to.
+    pub columns: Vec<Ident>,
+    pub query: Query<T>,
+}
+
+impl<T: AstInfo> AstDisplay for CreateContinualTaskStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("CREATE CONTINUAL TASK ");
+        if self.if_not_exists {
+            f.write_str("IF NOT EXISTS ");
+        }
+        f.write_node(&self.name);
+
+        if let Some(cluster) = &self.in_cluster {
+            f.write_str(" IN CLUSTER ");
+            f.write_node(cluster);
+        }
+
+        f.write_str(" AS INSERT INTO ");
+        f.write_node(&self.target);
+        if !self.columns.is_empty() {
+            f.write_str(" (");
+            f.write_node(&display::comma_separated(&self.columns));
+            f.write_str(")");
+        }
+        f.write_str(" ");
+        f.write_node(&self.query);
+    }
+}
+impl_display_t!(CreateContinualTaskStatement);
+
 /// `ALTER SET CLUSTER`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AlterSetClusterStatement<T: AstInfo> {
@@ -1362,6 +1447,8 @@ pub struct CreateTableStatement<T: AstInfo> {
     pub constraints: Vec<TableConstraint<T>>,
     pub if_not_exists: bool,
     pub temporary: bool,
+    /// What happens to a temporary table at the end of each transaction.
+    pub on_commit: Option<OnCommitAction>,
     pub with_options: Vec<TableOption<T>>,
 }
 
@@ -1373,6 +1460,7 @@ impl<T: AstInfo> AstDisplay for CreateTableStatement<T> {
             constraints,
             if_not_exists,
             temporary,
+            on_commit,
             with_options,
         } = self;
         f.write_str("CREATE ");
@@ -1396,10 +1484,36 @@ impl<T: AstInfo> AstDisplay for CreateTableStatement<T> {
             f.write_node(&display::comma_separated(&self.with_options));
             f.write_str(")");
         }
+        if let Some(on_commit) = on_commit {
+            f.write_str(" ON COMMIT ");
+            f.write_node(on_commit);
+        }
     }
 }
 impl_display_t!(CreateTableStatement);
 
+/// The `ON COMMIT` action of a temporary table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OnCommitAction {
+    /// `PRESERVE ROWS`: keep the rows of the table.
+    PreserveRows,
+    /// `DELETE ROWS`: delete all rows of the table.
+    DeleteRows,
+    /// `DROP`: drop the table.
+    Drop,
+}
+
+impl AstDisplay for OnCommitAction {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            OnCommitAction::PreserveRows => f.write_str("PRESERVE ROWS"),
+            OnCommitAction::DeleteRows => f.write_str("DELETE ROWS"),
+            OnCommitAction::Drop => f.write_str("DROP"),
+        }
+    }
+}
+impl_display!(OnCommitAction);
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum TableOptionName {
     // The `RETAIN HISTORY` option
@@ -1544,6 +1658,8 @@ pub enum RoleAttribute {
     Inherit,
     /// The `NOINHERIT` option.
     NoInherit,
+    /// The `PASSWORD` option. `None` represents `PASSWORD NULL`.
+    Password(Option<String>),
     // The following are not supported, but included to give helpful error messages.
     Login,
     NoLogin,
@@ -1572,6 +1688,17 @@ impl AstDisplay for RoleAttribute {
             RoleAttribute::NoCreateDB => f.write_str("NOCREATEDB"),
             RoleAttribute::CreateRole => f.write_str("CREATEROLE"),
             RoleAttribute::NoCreateRole => f.write_str("NOCREATEROLE"),
+            RoleAttribute::Password(None) => f.write_str("PASSWORD NULL"),
+            RoleAttribute::Password(Some(password)) => {
+                f.write_str("PASSWORD ");
+                if f.redacted() {
+                    f.write_str("'<REDACTED>'");
+                } else {
+                    f.write_str("'");
+                    f.write_node(&display::escape_single_quote_string(password));
+                    f.write_str("'");
+                }
+            }
         }
     }
 }
@@ -1610,6 +1737,9 @@ pub struct CreateSecretStatement<T: AstInfo> {
     pub name: UnresolvedItemName,
     pub if_not_exists: bool,
     pub value: Expr<T>,
+    /// Whether `value` is a reference to a secret in an external secrets manager, i.e. whether
+    /// the statement is `CREATE SECRET ... FROM EXTERNAL`.
+    pub external: bool,
 }
 
 impl<T: AstInfo> AstDisplay for CreateSecretStatement<T> {
@@ -1619,7 +1749,11 @@ impl<T: AstInfo> AstDisplay for CreateSecretStatement<T> {
             f.write_str("IF NOT EXISTS ");
         }
         f.write_node(&self.name);
-        f.write_str(" AS ");
+        if self.external {
+            f.write_str(" FROM EXTERNAL ");
+        } else {
+            f.write_str(" AS ");
+        }
 
         if f.redacted() {
             f.write_str("'<REDACTED>'");
@@ -1673,6 +1807,85 @@ impl<T: AstInfo> AstDisplay for CreateTypeStatement<T> {
 }
 impl_display_t!(CreateTypeStatement);
 
+/// `CREATE FUNCTION ..`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CreateFunctionStatement<T: AstInfo> {
+    /// Name of the created function.
+    pub name: UnresolvedItemName,
+    /// The parameters of the function.
+    pub params: Vec<FunctionParameter<T>>,
+    /// The columns of the table that the function returns.
+    pub returns: Vec<FunctionColumnDef<T>>,
+    /// The query that computes the rows that the function returns.
+    pub body: Query<T>,
+}
+
+impl<T: AstInfo> AstDisplay for CreateFunctionStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("CREATE FUNCTION ");
+        f.write_node(&self.name);
+        f.write_str("(");
+        f.write_node(&display::comma_separated(&self.params));
+        f.write_str(") RETURNS TABLE (");
+        f.write_node(&display::comma_separated(&self.returns));
+        f.write_str(") AS ");
+
+        let body = if f.stable() {
+            self.body.to_ast_string_stable()
+        } else if f.redacted() {
+            self.body.to_ast_string_redacted()
+        } else {
+            self.body.to_ast_string()
+        };
+        // Dollar-quote the body with a tag that does not occur in it, taking
+        // care of bodies that end in a `$`.
+        let mut tag = String::new();
+        let mut i = 0;
+        while format!("{body}$").contains(&format!("${tag}$")) {
+            i += 1;
+            tag = format!("body{i}");
+        }
+        f.write_str(format!("${tag}${body}${tag}$"));
+        f.write_str(" LANGUAGE SQL");
+    }
+}
+impl_display_t!(CreateFunctionStatement);
+
+/// A parameter of a function created with `CREATE FUNCTION`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FunctionParameter<T: AstInfo> {
+    /// The name of the parameter, if it has one.
+    pub name: Option<Ident>,
+    pub data_type: T::DataType,
+}
+
+impl<T: AstInfo> AstDisplay for FunctionParameter<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        if let Some(name) = &self.name {
+            f.write_node(name);
+            f.write_str(" ");
+        }
+        f.write_node(&self.data_type);
+    }
+}
+impl_display_t!(FunctionParameter);
+
+/// A column of the table that a function created with `CREATE FUNCTION` returns.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FunctionColumnDef<T: AstInfo> {
+    pub name: Ident,
+    pub data_type: T::DataType,
+}
+
+impl<T: AstInfo> AstDisplay for FunctionColumnDef<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        f.write_str(" ");
+        f.write_node(&self.data_type);
+    }
+}
+impl_display_t!(FunctionColumnDef);
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ClusterOptionName {
     /// The `AVAILABILITY ZONES [[=] '[' <values> ']' ]` option.
@@ -1687,10 +1900,14 @@ pub enum ClusterOptionName {
     IdleArrangementMergeEffort,
     /// The `MANAGED` option.
     Managed,
+    /// The `MAX CONCURRENT QUERIES` option.
+    MaxConcurrentQueries,
     /// The `REPLICAS` option.
     Replicas,
     /// The `REPLICATION FACTOR` option.
     ReplicationFactor,
+    /// The `SCHEDULE [[=] MANUAL | ON (CRON '<expr>')]` option.
+    Schedule,
     /// The `SIZE` option.
     Size,
 }
@@ -1706,8 +1923,10 @@ impl AstDisplay for ClusterOptionName {
             ClusterOptionName::IntrospectionDebugging => f.write_str("INTROSPECTION DEBUGGING"),
             ClusterOptionName::IntrospectionInterval => f.write_str("INTROSPECTION INTERVAL"),
             ClusterOptionName::Managed => f.write_str("MANAGED"),
+            ClusterOptionName::MaxConcurrentQueries => f.write_str("MAX CONCURRENT QUERIES"),
             ClusterOptionName::Replicas => f.write_str("REPLICAS"),
             ClusterOptionName::ReplicationFactor => f.write_str("REPLICATION FACTOR"),
+            ClusterOptionName::Schedule => f.write_str("SCHEDULE"),
             ClusterOptionName::Size => f.write_str("SIZE"),
         }
     }
@@ -1847,6 +2066,47 @@ impl<T: AstInfo> AstDisplay for AlterClusterStatement<T> {
 }
 impl_display_t!(AlterClusterStatement);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AlterClusterReplicaAction<T: AstInfo> {
+    SetOptions(Vec<ReplicaOption<T>>),
+    ResetOptions(Vec<ReplicaOptionName>),
+}
+
+/// `ALTER CLUSTER REPLICA .. SET ...`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterClusterReplicaStatement<T: AstInfo> {
+    /// The `IF EXISTS` option.
+    pub if_exists: bool,
+    /// Name of the altered replica.
+    pub name: QualifiedReplica,
+    /// The action.
+    pub action: AlterClusterReplicaAction<T>,
+}
+
+impl<T: AstInfo> AstDisplay for AlterClusterReplicaStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ALTER CLUSTER REPLICA ");
+        if self.if_exists {
+            f.write_str("IF EXISTS ");
+        }
+        f.write_node(&self.name);
+        f.write_str(" ");
+        match &self.action {
+            AlterClusterReplicaAction::SetOptions(options) => {
+                f.write_str("SET (");
+                f.write_node(&display::comma_separated(options));
+                f.write_str(")");
+            }
+            AlterClusterReplicaAction::ResetOptions(options) => {
+                f.write_str("RESET (");
+                f.write_node(&display::comma_separated(options));
+                f.write_str(")");
+            }
+        }
+    }
+}
+impl_display_t!(AlterClusterReplicaStatement);
+
 /// `CREATE CLUSTER REPLICA ..`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CreateClusterReplicaStatement<T: AstInfo> {
@@ -2137,6 +2397,8 @@ impl_display_t!(AlterIndexStatement);
 pub enum AlterSinkAction<T: AstInfo> {
     SetOptions(Vec<CreateSinkOption<T>>),
     ResetOptions(Vec<CreateSinkOptionName>),
+    /// Discards the progress that the sink has recorded, so that it starts over.
+    ResetOffset,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -2166,6 +2428,7 @@ impl<T: AstInfo> AstDisplay for AlterSinkStatement<T> {
                 f.write_node(&display::comma_separated(options));
                 f.write_str(")");
             }
+            AlterSinkAction::ResetOffset => f.write_str("RESET OFFSET"),
         }
     }
 }
@@ -2283,12 +2546,14 @@ impl<T: AstInfo> AstDisplay for AlterSourceStatement<T> {
 
 impl_display_t!(AlterSourceStatement);
 
-/// `ALTER SECRET ... AS`
+/// `ALTER SECRET ... { AS | FROM EXTERNAL }`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AlterSecretStatement<T: AstInfo> {
     pub name: UnresolvedItemName,
     pub if_exists: bool,
     pub value: Expr<T>,
+    /// Whether `value` is a reference to a secret in an external secrets manager.
+    pub external: bool,
 }
 
 impl<T: AstInfo> AstDisplay for AlterSecretStatement<T> {
@@ -2298,7 +2563,11 @@ impl<T: AstInfo> AstDisplay for AlterSecretStatement<T> {
             f.write_str("IF EXISTS ");
         }
         f.write_node(&self.name);
-        f.write_str(" AS ");
+        if self.external {
+            f.write_str(" FROM EXTERNAL ");
+        } else {
+            f.write_str(" AS ");
+        }
 
         if f.redacted() {
             f.write_str("'<REDACTED>'");
@@ -2415,6 +2684,60 @@ impl<T: AstInfo> AstDisplay for AlterRoleStatement<T> {
 }
 impl_display_t!(AlterRoleStatement);
 
+/// `ALTER QUOTA FOR { ROLE | DATABASE } ... { SET ... = ... | RESET ... }`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterQuotaStatement<T: AstInfo> {
+    /// The role or database whose quota is altered.
+    pub subject: QuotaSubjectSpecification<T>,
+    /// The name of the quota.
+    pub quota: Ident,
+    /// The new limit of the quota, or `None` to remove the quota.
+    pub limit: Option<u64>,
+}
+
+impl<T: AstInfo> AstDisplay for AlterQuotaStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ALTER QUOTA FOR ");
+        f.write_node(&self.subject);
+        match &self.limit {
+            Some(limit) => {
+                f.write_str(" SET ");
+                f.write_node(&self.quota);
+                f.write_str(" = ");
+                f.write_str(limit);
+            }
+            None => {
+                f.write_str(" RESET ");
+                f.write_node(&self.quota);
+            }
+        }
+    }
+}
+impl_display_t!(AlterQuotaStatement);
+
+/// The role or database of an `ALTER QUOTA` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum QuotaSubjectSpecification<T: AstInfo> {
+    Role(T::RoleName),
+    Database(T::DatabaseName),
+}
+
+impl<T: AstInfo> AstDisplay for QuotaSubjectSpecification<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            QuotaSubjectSpecification::Role(name) => {
+                f.write_str("ROLE ");
+                f.write_node(name);
+            }
+            QuotaSubjectSpecification::Database(name) => {
+                f.write_str("DATABASE ");
+                f.write_node(name);
+            }
+        }
+    }
+}
+impl_display_t!(QuotaSubjectSpecification);
+
 /// `ALTER ROLE ... [ WITH | SET ] ...`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AlterRoleOption {
@@ -2487,6 +2810,9 @@ pub struct DropObjectsStatement {
     /// Whether `CASCADE` was specified. This will be `false` when
     /// `RESTRICT` was specified.
     pub cascade: bool,
+    /// Whether `DRY RUN` was specified, in which case the objects that would
+    /// be dropped are listed instead of dropped.
+    pub dry_run: bool,
 }
 
 impl AstDisplay for DropObjectsStatement {
@@ -2503,6 +2829,9 @@ impl AstDisplay for DropObjectsStatement {
         } else if !self.cascade && self.object_type == ObjectType::Database {
             f.write_str(" RESTRICT");
         }
+        if self.dry_run {
+            f.write_str(" DRY RUN");
+        }
     }
 }
 impl_display!(DropObjectsStatement);
@@ -2657,6 +2986,10 @@ pub enum ShowObjectType<T: AstInfo> {
     RoleMembership {
         role: Option<T::RoleName>,
     },
+    Dependent {
+        of_object: T::ItemName,
+        cascade: bool,
+    },
 }
 /// `SHOW <object>S`
 ///
@@ -2671,6 +3004,8 @@ pub struct ShowObjectsStatement<T: AstInfo> {
     pub object_type: ShowObjectType<T>,
     pub from: Option<T::SchemaName>,
     pub filter: Option<ShowStatementFilter<T>>,
+    /// Whether to include the status of the objects, for `SHOW EXTENDED`.
+    pub extended: bool,
 }
 
 impl<T: AstInfo> AstDisplay for ShowObjectsStatement<T> {
@@ -2678,6 +3013,10 @@ impl<T: AstInfo> AstDisplay for ShowObjectsStatement<T> {
         f.write_str("SHOW");
         f.write_str(" ");
 
+        if self.extended {
+            f.write_str("EXTENDED ");
+        }
+
         f.write_str(match &self.object_type {
             ShowObjectType::Table => "TABLES",
             ShowObjectType::View => "VIEWS",
@@ -2698,8 +3037,17 @@ impl<T: AstInfo> AstDisplay for ShowObjectsStatement<T> {
             ShowObjectType::Privileges { .. } => "PRIVILEGES",
             ShowObjectType::DefaultPrivileges { .. } => "DEFAULT PRIVILEGES",
             ShowObjectType::RoleMembership { .. } => "ROLE MEMBERSHIP",
+            ShowObjectType::Dependent { .. } => "DEPENDENTS",
         });
 
+        if let ShowObjectType::Dependent { of_object, cascade } = &self.object_type {
+            f.write_str(" OF ");
+            f.write_node(of_object);
+            if *cascade {
+                f.write_str(" CASCADE");
+            }
+        }
+
         if let ShowObjectType::Index { on_object, .. } = &self.object_type {
             if let Some(on_object) = on_object {
                 f.write_str(" ON ");
@@ -2802,85 +3150,115 @@ impl<T: AstInfo> AstDisplay for ShowColumnsStatement<T> {
 }
 impl_display_t!(ShowColumnsStatement);
 
-/// `SHOW CREATE VIEW <view>`
+/// `SHOW [REDACTED] CREATE VIEW <view>`
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ShowCreateViewStatement<T: AstInfo> {
     pub view_name: T::ItemName,
+    pub redacted: bool,
 }
 
 impl<T: AstInfo> AstDisplay for ShowCreateViewStatement<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
-        f.write_str("SHOW CREATE VIEW ");
+        f.write_str("SHOW ");
+        if self.redacted {
+            f.write_str("REDACTED ");
+        }
+        f.write_str("CREATE VIEW ");
         f.write_node(&self.view_name);
     }
 }
 impl_display_t!(ShowCreateViewStatement);
 
-/// `SHOW CREATE MATERIALIZED VIEW <name>`
+/// `SHOW [REDACTED] CREATE MATERIALIZED VIEW <name>`
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ShowCreateMaterializedViewStatement<T: AstInfo> {
     pub materialized_view_name: T::ItemName,
+    pub redacted: bool,
 }
 
 impl<T: AstInfo> AstDisplay for ShowCreateMaterializedViewStatement<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
-        f.write_str("SHOW CREATE MATERIALIZED VIEW ");
+        f.write_str("SHOW ");
+        if self.redacted {
+            f.write_str("REDACTED ");
+        }
+        f.write_str("CREATE MATERIALIZED VIEW ");
         f.write_node(&self.materialized_view_name);
     }
 }
 impl_display_t!(ShowCreateMaterializedViewStatement);
 
-/// `SHOW CREATE SOURCE <source>`
+/// `SHOW [REDACTED] CREATE SOURCE <source>`
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ShowCreateSourceStatement<T: AstInfo> {
     pub source_name: T::ItemName,
+    pub redacted: bool,
 }
 
 impl<T: AstInfo> AstDisplay for ShowCreateSourceStatement<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
-        f.write_str("SHOW CREATE SOURCE ");
+        f.write_str("SHOW ");
+        if self.redacted {
+            f.write_str("REDACTED ");
+        }
+        f.write_str("CREATE SOURCE ");
         f.write_node(&self.source_name);
     }
 }
 impl_display_t!(ShowCreateSourceStatement);
 
-/// `SHOW CREATE TABLE <table>`
+/// `SHOW [REDACTED] CREATE TABLE <table>`
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ShowCreateTableStatement<T: AstInfo> {
     pub table_name: T::ItemName,
+    pub redacted: bool,
 }
 
 impl<T: AstInfo> AstDisplay for ShowCreateTableStatement<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
-        f.write_str("SHOW CREATE TABLE ");
+        f.write_str("SHOW ");
+        if self.redacted {
+            f.write_str("REDACTED ");
+        }
+        f.write_str("CREATE TABLE ");
         f.write_node(&self.table_name);
     }
 }
 impl_display_t!(ShowCreateTableStatement);
 
-/// `SHOW CREATE SINK <sink>`
+/// `SHOW [REDACTED] CREATE SINK <sink>`
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ShowCreateSinkStatement<T: AstInfo> {
     pub sink_name: T::ItemName,
+    pub redacted: bool,
 }
 
 impl<T: AstInfo> AstDisplay for ShowCreateSinkStatement<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
-        f.write_str("SHOW CREATE SINK ");
+        f.write_str("SHOW ");
+        if self.redacted {
+            f.write_str("REDACTED ");
+        }
+        f.write_str("CREATE SINK ");
         f.write_node(&self.sink_name);
     }
 }
 impl_display_t!(ShowCreateSinkStatement);
 
-/// `SHOW CREATE INDEX <index>`
+/// `SHOW [REDACTED] CREATE INDEX <index>`
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ShowCreateIndexStatement<T: AstInfo> {
     pub index_name: T::ItemName,
+    pub redacted: bool,
 }
 
 impl<T: AstInfo> AstDisplay for ShowCreateIndexStatement<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
-        f.write_str("SHOW CREATE INDEX ");
+        f.write_str("SHOW ");
+        if self.redacted {
+            f.write_str("REDACTED ");
+        }
+        f.write_str("CREATE INDEX ");
         f.write_node(&self.index_name);
     }
 }
@@ -2889,11 +3267,16 @@ impl_display_t!(ShowCreateIndexStatement);
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ShowCreateConnectionStatement<T: AstInfo> {
     pub connection_name: T::ItemName,
+    pub redacted: bool,
 }
 
 impl<T: AstInfo> AstDisplay for ShowCreateConnectionStatement<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
-        f.write_str("SHOW CREATE CONNECTION ");
+        f.write_str("SHOW ");
+        if self.redacted {
+            f.write_str("REDACTED ");
+        }
+        f.write_str("CREATE CONNECTION ");
         f.write_node(&self.connection_name);
     }
 }
@@ -3107,6 +3490,7 @@ pub enum ExplainPlanOptionName {
     EnableEagerDeltaJoins,
     EnableEquivalencePropagation,
     EnableVariadicLeftJoinLowering,
+    EnableLeftJoinKeySharing,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -3212,6 +3596,7 @@ pub enum ObjectType {
     Schema,
     Func,
     Subsource,
+    ContinualTask,
 }
 
 impl ObjectType {
@@ -3227,7 +3612,8 @@ impl ObjectType {
             | ObjectType::Secret
             | ObjectType::Connection
             | ObjectType::Func
-            | ObjectType::Subsource => true,
+            | ObjectType::Subsource
+            | ObjectType::ContinualTask => true,
             ObjectType::Database
             | ObjectType::Schema
             | ObjectType::Cluster
@@ -3256,6 +3642,7 @@ impl AstDisplay for ObjectType {
             ObjectType::Schema => "SCHEMA",
             ObjectType::Func => "FUNCTION",
             ObjectType::Subsource => "SUBSOURCE",
+            ObjectType::ContinualTask => "CONTINUAL TASK",
         })
     }
 }
@@ -3310,12 +3697,15 @@ pub enum WithOptionValue<T: AstInfo> {
     Item(T::ItemName),
     UnresolvedItemName(UnresolvedItemName),
     Sequence(Vec<WithOptionValue<T>>),
+    Map(BTreeMap<String, WithOptionValue<T>>),
     // Special cases.
     ClusterReplicas(Vec<ReplicaDefinition<T>>),
+    ClusterSchedule(ClusterScheduleOptionValue),
     ConnectionKafkaBroker(KafkaBroker<T>),
     ConnectionAwsPrivatelink(ConnectionDefaultAwsPrivatelink<T>),
     RetainHistoryFor(Value),
     Refresh(RefreshOptionValue<T>),
+    Expr(Expr<T>),
 }
 
 impl<T: AstInfo> AstDisplay for WithOptionValue<T> {
@@ -3326,15 +3716,18 @@ impl<T: AstInfo> AstDisplay for WithOptionValue<T> {
             match self {
                 WithOptionValue::Value(_)
                 | WithOptionValue::Sequence(_)
+                | WithOptionValue::Map(_)
                 | WithOptionValue::RetainHistoryFor(_)
-                | WithOptionValue::Refresh(_) => {
+                | WithOptionValue::Refresh(_)
+                | WithOptionValue::Expr(_) => {
                     // These are redact-aware.
                 }
                 WithOptionValue::DataType(_)
                 | WithOptionValue::Item(_)
                 | WithOptionValue::UnresolvedItemName(_)
                 | WithOptionValue::ConnectionAwsPrivatelink(_)
-                | WithOptionValue::ClusterReplicas(_) => {
+                | WithOptionValue::ClusterReplicas(_)
+                | WithOptionValue::ClusterSchedule(_) => {
 
                     // These do not need redaction.
                 }
@@ -3352,6 +3745,19 @@ impl<T: AstInfo> AstDisplay for WithOptionValue<T> {
                 f.write_node(&display::comma_separated(values));
                 f.write_str(")");
             }
+            WithOptionValue::Map(values) => {
+                f.write_str("MAP[");
+                let len = values.len();
+                for (i, (key, value)) in values.iter().enumerate() {
+                    f.write_node(&display::escaped_string_literal(key));
+                    f.write_str(" => ");
+                    f.write_node(value);
+                    if i + 1 < len {
+                        f.write_str(", ");
+                    }
+                }
+                f.write_str("]");
+            }
             WithOptionValue::Value(value) => f.write_node(value),
             WithOptionValue::Ident(id) => {
                 f.write_node(id);
@@ -3368,6 +3774,7 @@ impl<T: AstInfo> AstDisplay for WithOptionValue<T> {
                 f.write_node(&display::comma_separated(replicas));
                 f.write_str(")");
             }
+            WithOptionValue::ClusterSchedule(schedule) => f.write_node(schedule),
             WithOptionValue::ConnectionAwsPrivatelink(aws_privatelink) => {
                 f.write_node(aws_privatelink);
             }
@@ -3379,6 +3786,7 @@ impl<T: AstInfo> AstDisplay for WithOptionValue<T> {
                 f.write_node(value);
             }
             WithOptionValue::Refresh(opt) => f.write_node(opt),
+            WithOptionValue::Expr(expr) => f.write_node(expr),
         }
     }
 }
@@ -3434,6 +3842,30 @@ impl<T: AstInfo> AstDisplay for RefreshOptionValue<T> {
     }
 }
 
+/// The value of a cluster's `SCHEDULE` option.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ClusterScheduleOptionValue {
+    /// The cluster's replicas are only managed through `ALTER CLUSTER`.
+    Manual,
+    /// The cluster is turned on during the minutes matched by the given cron
+    /// expression.
+    Cron(String),
+}
+
+impl AstDisplay for ClusterScheduleOptionValue {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            ClusterScheduleOptionValue::Manual => f.write_str("MANUAL"),
+            ClusterScheduleOptionValue::Cron(expr) => {
+                f.write_str("ON (CRON '");
+                f.write_node(&display::escape_single_quote_string(expr));
+                f.write_str("')");
+            }
+        }
+    }
+}
+impl_display!(ClusterScheduleOptionValue);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum TransactionMode {
     AccessMode(TransactionAccessMode),
@@ -3766,6 +4198,10 @@ pub enum IfExistsBehavior {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DeclareStatement<T: AstInfo> {
     pub name: Ident,
+    /// Whether the cursor was declared `SCROLL`.
+    pub scroll: bool,
+    /// Whether the cursor was declared `WITH HOLD`.
+    pub hold: bool,
     pub stmt: Box<T::NestedStatement>,
     pub sql: String,
 }
@@ -3774,7 +4210,14 @@ impl<T: AstInfo> AstDisplay for DeclareStatement<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str("DECLARE ");
         f.write_node(&self.name);
-        f.write_str(" CURSOR FOR ");
+        if self.scroll {
+            f.write_str(" SCROLL");
+        }
+        f.write_str(" CURSOR ");
+        if self.hold {
+            f.write_str("WITH HOLD ");
+        }
+        f.write_str("FOR ");
         f.write_node(&self.stmt);
     }
 }
@@ -3851,6 +4294,24 @@ impl_display_t!(FetchStatement);
 pub enum FetchDirection {
     ForwardAll,
     ForwardCount(u64),
+    BackwardAll,
+    BackwardCount(u64),
+    /// Fetch the row at the given position, counting from the end if
+    /// negative.
+    Absolute(i64),
+    /// Fetch the row at the given offset from the current position.
+    Relative(i64),
+}
+
+impl FetchDirection {
+    /// Reports whether fetching in this direction only ever moves the cursor
+    /// forward, and so does not require a scrollable cursor.
+    pub fn is_forward(&self) -> bool {
+        matches!(
+            self,
+            FetchDirection::ForwardAll | FetchDirection::ForwardCount(_)
+        )
+    }
 }
 
 impl AstDisplay for FetchDirection {
@@ -3858,6 +4319,10 @@ impl AstDisplay for FetchDirection {
         match self {
             FetchDirection::ForwardAll => f.write_str("ALL"),
             FetchDirection::ForwardCount(count) => f.write_str(format!("{}", count)),
+            FetchDirection::BackwardAll => f.write_str("BACKWARD ALL"),
+            FetchDirection::BackwardCount(count) => f.write_str(format!("BACKWARD {}", count)),
+            FetchDirection::Absolute(position) => f.write_str(format!("ABSOLUTE {}", position)),
+            FetchDirection::Relative(offset) => f.write_str(format!("RELATIVE {}", offset)),
         }
     }
 }
@@ -4464,6 +4929,7 @@ pub enum CommentObjectType<T: AstInfo> {
     Schema { name: T::SchemaName },
     Cluster { name: T::ClusterName },
     ClusterReplica { name: QualifiedReplica },
+    ContinualTask { name: T::ItemName },
 }
 
 impl<T: AstInfo> AstDisplay for CommentObjectType<T> {
@@ -4535,6 +5001,10 @@ impl<T: AstInfo> AstDisplay for CommentObjectType<T> {
                 f.write_str("CLUSTER REPLICA ");
                 f.write_node(name);
             }
+            ContinualTask { name } => {
+                f.write_str("CONTINUAL TASK ");
+                f.write_node(name);
+            }
         }
     }
 }
@@ -20,6 +20,7 @@
 
 //! SQL Parser
 
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::fmt;
 
@@ -178,6 +179,20 @@ pub fn parse_data_type(sql: &str) -> Result<RawDataType, ParserError> {
     }
 }
 
+/// Parses the body of a function created with `CREATE FUNCTION`, which is a
+/// single query that may be terminated by a semicolon.
+fn parse_function_body(sql: &str) -> Result<Query<Raw>, ParserError> {
+    let tokens = lexer::lex(sql)?;
+    let mut parser = Parser::new(sql, tokens);
+    let query = parser.parse_query()?;
+    let _ = parser.consume_token(&Token::Semicolon);
+    if parser.next_token().is_some() {
+        parser_err!(parser, parser.peek_prev_pos(), "extra token after query")
+    } else {
+        Ok(query)
+    }
+}
+
 /// Parses a string containing a comma-separated list of identifiers and
 /// returns their underlying string values.
 ///
@@ -1830,6 +1845,9 @@ impl<'a> Parser<'a> {
         } else if self.peek_keyword(TYPE) {
             self.parse_create_type()
                 .map_parser_err(StatementKind::CreateType)
+        } else if self.peek_keyword(FUNCTION) {
+            self.parse_create_function()
+                .map_parser_err(StatementKind::CreateFunction)
         } else if self.peek_keyword(ROLE) {
             self.parse_create_role()
                 .map_parser_err(StatementKind::CreateRole)
@@ -1868,6 +1886,9 @@ impl<'a> Parser<'a> {
         {
             self.parse_create_materialized_view()
                 .map_parser_err(StatementKind::CreateMaterializedView)
+        } else if self.peek_keywords(&[CONTINUAL, TASK]) {
+            self.parse_create_continual_task()
+                .map_parser_err(StatementKind::CreateContinualTask)
         } else if self.peek_keywords(&[USER]) {
             parser_err!(
                 self,
@@ -1915,6 +1936,15 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    fn parse_csv_header_column(&mut self) -> Result<CsvHeaderColumn<Raw>, ParserError> {
+        let name = self.parse_identifier()?;
+        let data_type = match self.peek_token() {
+            Some(Token::Comma) | Some(Token::RParen) => None,
+            _ => Some(self.parse_data_type()?),
+        };
+        Ok(CsvHeaderColumn { name, data_type })
+    }
+
     fn parse_format(&mut self) -> Result<Format<Raw>, ParserError> {
         let format = if self.parse_keyword(AVRO) {
             self.expect_keyword(USING)?;
@@ -1927,9 +1957,14 @@ impl<'a> Parser<'a> {
         } else if self.parse_keyword(CSV) {
             self.expect_keyword(WITH)?;
             let columns = if self.parse_keyword(HEADER) || self.parse_keyword(HEADERS) {
-                CsvColumns::Header {
-                    names: self.parse_parenthesized_column_list(Mandatory)?,
-                }
+                let columns = if self.consume_token(&Token::LParen) {
+                    let columns = self.parse_comma_separated(Parser::parse_csv_header_column)?;
+                    self.expect_token(&Token::RParen)?;
+                    columns
+                } else {
+                    vec![]
+                };
+                CsvColumns::Header { columns }
             } else {
                 let n_cols = self.parse_literal_uint()?;
                 self.expect_keyword(COLUMNS)?;
@@ -2067,12 +2102,16 @@ impl<'a> Parser<'a> {
                 })
             }
             VALUE => {
-                self.expect_keywords(&[DOC, ON])?;
-                let doc_on_identifier = self.parse_avro_doc_on_option_name()?;
-                CsrConfigOptionName::AvroDocOn(AvroDocOn {
-                    identifier: doc_on_identifier,
-                    for_schema: DocOnSchema::ValueOnly,
-                })
+                if self.parse_keywords(&[SCHEMA, EVOLUTION]) {
+                    CsrConfigOptionName::ValueSchemaEvolution
+                } else {
+                    self.expect_keywords(&[DOC, ON])?;
+                    let doc_on_identifier = self.parse_avro_doc_on_option_name()?;
+                    CsrConfigOptionName::AvroDocOn(AvroDocOn {
+                        identifier: doc_on_identifier,
+                        for_schema: DocOnSchema::ValueOnly,
+                    })
+                }
             }
             DOC => {
                 self.expect_keyword(ON)?;
@@ -2233,28 +2272,33 @@ impl<'a> Parser<'a> {
             TO => true,
             _ => unreachable!(),
         };
-        let connection_type =
-            match self.expect_one_of_keywords(&[AWS, KAFKA, CONFLUENT, POSTGRES, SSH, MYSQL])? {
-                AWS => {
-                    if self.parse_keyword(PRIVATELINK) {
-                        CreateConnectionType::AwsPrivatelink
-                    } else {
-                        CreateConnectionType::Aws
-                    }
-                }
-                KAFKA => CreateConnectionType::Kafka,
-                CONFLUENT => {
-                    self.expect_keywords(&[SCHEMA, REGISTRY])?;
-                    CreateConnectionType::Csr
-                }
-                POSTGRES => CreateConnectionType::Postgres,
-                SSH => {
-                    self.expect_keyword(TUNNEL)?;
-                    CreateConnectionType::Ssh
+        let connection_type = match self
+            .expect_one_of_keywords(&[AWS, KAFKA, CONFLUENT, POSTGRES, SSH, MYSQL, ICEBERG])?
+        {
+            AWS => {
+                if self.parse_keyword(PRIVATELINK) {
+                    CreateConnectionType::AwsPrivatelink
+                } else {
+                    CreateConnectionType::Aws
                 }
-                MYSQL => CreateConnectionType::MySql,
-                _ => unreachable!(),
-            };
+            }
+            KAFKA => CreateConnectionType::Kafka,
+            CONFLUENT => {
+                self.expect_keywords(&[SCHEMA, REGISTRY])?;
+                CreateConnectionType::Csr
+            }
+            POSTGRES => CreateConnectionType::Postgres,
+            SSH => {
+                self.expect_keyword(TUNNEL)?;
+                CreateConnectionType::Ssh
+            }
+            MYSQL => CreateConnectionType::MySql,
+            ICEBERG => {
+                self.expect_keyword(CATALOG)?;
+                CreateConnectionType::IcebergCatalog
+            }
+            _ => unreachable!(),
+        };
         if expect_paren {
             self.expect_token(&Token::LParen)?;
         }
@@ -2421,7 +2465,17 @@ impl<'a> Parser<'a> {
                 self.expect_keywords(&[GROUP, ID, PREFIX])?;
                 KafkaSinkConfigOptionName::ProgressGroupIdPrefix
             }
-            TOPIC => KafkaSinkConfigOptionName::Topic,
+            TOPIC => {
+                if self.parse_keywords(&[PARTITION, COUNT]) {
+                    KafkaSinkConfigOptionName::TopicPartitionCount
+                } else if self.parse_keywords(&[REPLICATION, FACTOR]) {
+                    KafkaSinkConfigOptionName::TopicReplicationFactor
+                } else if self.parse_keyword(CONFIG) {
+                    KafkaSinkConfigOptionName::TopicConfig
+                } else {
+                    KafkaSinkConfigOptionName::Topic
+                }
+            }
             TRANSACTIONAL => {
                 self.expect_keywords(&[ID, PREFIX])?;
                 KafkaSinkConfigOptionName::TransactionalIdPrefix
@@ -2438,6 +2492,24 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_iceberg_sink_config_option(
+        &mut self,
+    ) -> Result<IcebergSinkConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[COMMIT, NAMESPACE, TABLE])? {
+            COMMIT => {
+                self.expect_keyword(INTERVAL)?;
+                IcebergSinkConfigOptionName::CommitInterval
+            }
+            NAMESPACE => IcebergSinkConfigOptionName::Namespace,
+            TABLE => IcebergSinkConfigOptionName::Table,
+            _ => unreachable!(),
+        };
+        Ok(IcebergSinkConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     fn parse_connection_option_name(&mut self) -> Result<ConnectionOptionName, ParserError> {
         Ok(
             match self.expect_one_of_keywords(&[
@@ -2462,9 +2534,11 @@ impl<'a> Parser<'a> {
                 SESSION,
                 SSH,
                 SSL,
+                TOKEN,
                 URL,
                 USER,
                 USERNAME,
+                WAREHOUSE,
             ])? {
                 ACCESS => {
                     self.expect_keywords(&[KEY, ID])?;
@@ -2485,10 +2559,11 @@ impl<'a> Parser<'a> {
                     self.expect_keyword(ZONES)?;
                     ConnectionOptionName::AvailabilityZones
                 }
-                AWS => {
-                    self.expect_keyword(PRIVATELINK)?;
-                    ConnectionOptionName::AwsPrivatelink
-                }
+                AWS => match self.expect_one_of_keywords(&[CONNECTION, PRIVATELINK])? {
+                    CONNECTION => ConnectionOptionName::AwsConnection,
+                    PRIVATELINK => ConnectionOptionName::AwsPrivatelink,
+                    _ => unreachable!(),
+                },
                 BROKER => ConnectionOptionName::Broker,
                 BROKERS => ConnectionOptionName::Brokers,
                 DATABASE => ConnectionOptionName::Database,
@@ -2539,8 +2614,10 @@ impl<'a> Parser<'a> {
                     MODE => ConnectionOptionName::SslMode,
                     _ => unreachable!(),
                 },
+                TOKEN => ConnectionOptionName::Token,
                 URL => ConnectionOptionName::Url,
                 USER | USERNAME => ConnectionOptionName::User,
+                WAREHOUSE => ConnectionOptionName::Warehouse,
                 _ => unreachable!(),
             },
         )
@@ -2574,9 +2651,9 @@ impl<'a> Parser<'a> {
                     value: Some(WithOptionValue::Sequence(brokers)),
                 });
             }
-            ConnectionOptionName::SshTunnel => {
+            name @ (ConnectionOptionName::AwsConnection | ConnectionOptionName::SshTunnel) => {
                 return Ok(ConnectionOption {
-                    name: ConnectionOptionName::SshTunnel,
+                    name,
                     value: Some(self.parse_object_option_value()?),
                 });
             }
@@ -2774,16 +2851,44 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_source_option_name(&mut self) -> Result<CreateSourceOptionName, ParserError> {
-        let name = match self.expect_one_of_keywords(&[IGNORE, TIMELINE, TIMESTAMP, RETAIN])? {
+        let name = match self.expect_one_of_keywords(&[
+            ERRORS, IGNORE, RETRY, START, TIMELINE, TIMESTAMP, UPSERT, RETAIN,
+        ])? {
+            ERRORS => CreateSourceOptionName::Errors,
             IGNORE => {
                 self.expect_keyword(KEYS)?;
                 CreateSourceOptionName::IgnoreKeys
             }
-            TIMELINE => CreateSourceOptionName::Timeline,
-            TIMESTAMP => {
-                self.expect_keyword(INTERVAL)?;
-                CreateSourceOptionName::TimestampInterval
+            RETRY => match self.expect_one_of_keywords(&[BACKOFF, MAX])? {
+                BACKOFF => CreateSourceOptionName::RetryBackoff,
+                MAX => match self.expect_one_of_keywords(&[ATTEMPTS, BACKOFF])? {
+                    ATTEMPTS => CreateSourceOptionName::RetryMaxAttempts,
+                    BACKOFF => CreateSourceOptionName::RetryMaxBackoff,
+                    _ => unreachable!(),
+                },
+                _ => unreachable!(),
+            },
+            START => {
+                self.expect_keyword(TIMESTAMP)?;
+                CreateSourceOptionName::StartTimestamp
             }
+            TIMELINE => CreateSourceOptionName::Timeline,
+            TIMESTAMP => match self.expect_one_of_keywords(&[INTERVAL, POLICY])? {
+                INTERVAL => CreateSourceOptionName::TimestampInterval,
+                POLICY => CreateSourceOptionName::TimestampPolicy,
+                _ => unreachable!(),
+            },
+            UPSERT => match self.expect_one_of_keywords(&[COMPACTION, MEMORY])? {
+                COMPACTION => {
+                    self.expect_keyword(STYLE)?;
+                    CreateSourceOptionName::UpsertCompactionStyle
+                }
+                MEMORY => {
+                    self.expect_keyword(LIMIT)?;
+                    CreateSourceOptionName::UpsertMemoryLimit
+                }
+                _ => unreachable!(),
+            },
             RETAIN => {
                 self.expect_keyword(HISTORY)?;
                 CreateSourceOptionName::RetainHistory
@@ -2884,6 +2989,23 @@ impl<'a> Parser<'a> {
             None
         };
 
+        let deduplicate = if self.parse_keyword(DEDUPLICATE) {
+            self.expect_keywords(&[ON, HEADER])?;
+            let header_name = self.parse_literal_string()?;
+            let window = if self.parse_keyword(WITHIN) {
+                Some(self.parse_literal_string()?)
+            } else {
+                None
+            };
+
+            Some(CreateWebhookSourceDeduplicate {
+                header_name,
+                window,
+            })
+        } else {
+            None
+        };
+
         Ok(Statement::CreateWebhookSource(
             CreateWebhookSourceStatement {
                 name,
@@ -2891,6 +3013,7 @@ impl<'a> Parser<'a> {
                 body_format,
                 include_headers,
                 validate_using,
+                deduplicate,
                 in_cluster,
             },
         ))
@@ -3011,8 +3134,12 @@ impl<'a> Parser<'a> {
 
     /// Parse the name of a CREATE SINK optional parameter
     fn parse_create_sink_option_name(&mut self) -> Result<CreateSinkOptionName, ParserError> {
-        let name = match self.expect_one_of_keywords(&[SNAPSHOT])? {
+        let name = match self.expect_one_of_keywords(&[SNAPSHOT, PARTITION])? {
             SNAPSHOT => CreateSinkOptionName::Snapshot,
+            PARTITION => {
+                self.expect_keyword(BY)?;
+                CreateSinkOptionName::PartitionBy
+            }
             _ => unreachable!(),
         };
         Ok(name)
@@ -3020,10 +3147,15 @@ impl<'a> Parser<'a> {
 
     /// Parse a NAME = VALUE parameter for CREATE SINK
     fn parse_create_sink_option(&mut self) -> Result<CreateSinkOption<Raw>, ParserError> {
-        Ok(CreateSinkOption {
-            name: self.parse_create_sink_option_name()?,
-            value: self.parse_optional_option_value()?,
-        })
+        let name = self.parse_create_sink_option_name()?;
+        let value = match name {
+            CreateSinkOptionName::PartitionBy => {
+                let _ = self.consume_token(&Token::Eq);
+                Some(WithOptionValue::Expr(self.parse_expr()?))
+            }
+            CreateSinkOptionName::Snapshot => self.parse_optional_option_value()?,
+        };
+        Ok(CreateSinkOption { name, value })
     }
 
     fn parse_create_source_connection(
@@ -3219,19 +3351,64 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_create_sink_connection(&mut self) -> Result<CreateSinkConnection<Raw>, ParserError> {
-        self.expect_keyword(KAFKA)?;
-        self.expect_keyword(CONNECTION)?;
+        match self.expect_one_of_keywords(&[KAFKA, ICEBERG])? {
+            KAFKA => {
+                self.expect_keyword(CONNECTION)?;
 
-        let connection = self.parse_raw_name()?;
+                let connection = self.parse_raw_name()?;
 
-        let options = if self.consume_token(&Token::LParen) {
-            let options = self.parse_comma_separated(Parser::parse_kafka_sink_config_option)?;
-            self.expect_token(&Token::RParen)?;
-            options
-        } else {
-            vec![]
-        };
+                let options = if self.consume_token(&Token::LParen) {
+                    let options =
+                        self.parse_comma_separated(Parser::parse_kafka_sink_config_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+
+                let key = self.parse_sink_key()?;
+
+                let headers = if self.parse_keyword(HEADERS) {
+                    self.expect_keyword(FROM)?;
+                    Some(self.parse_identifier()?)
+                } else {
+                    None
+                };
 
+                Ok(CreateSinkConnection::Kafka {
+                    connection,
+                    options,
+                    key,
+                    headers,
+                })
+            }
+            ICEBERG => {
+                self.expect_keywords(&[CATALOG, CONNECTION])?;
+
+                let connection = self.parse_raw_name()?;
+
+                let options = if self.consume_token(&Token::LParen) {
+                    let options =
+                        self.parse_comma_separated(Parser::parse_iceberg_sink_config_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+
+                let key = self.parse_sink_key()?;
+
+                Ok(CreateSinkConnection::Iceberg {
+                    connection,
+                    options,
+                    key,
+                })
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn parse_sink_key(&mut self) -> Result<Option<KafkaSinkKey>, ParserError> {
         // one token of lookahead:
         // * `KEY (` means we're parsing a list of columns for the key
         // * `KEY FORMAT` means there is no key, we'll parse a KeyValueFormat later
@@ -3254,11 +3431,7 @@ impl<'a> Parser<'a> {
                 None
             };
 
-        Ok(CreateSinkConnection::Kafka {
-            connection,
-            options,
-            key,
-        })
+        Ok(key)
     }
 
     fn parse_create_view(&mut self) -> Result<Statement<Raw>, ParserError> {
@@ -3339,10 +3512,31 @@ impl<'a> Parser<'a> {
         ))
     }
 
+    fn parse_create_continual_task(&mut self) -> Result<Statement<Raw>, ParserError> {
+        self.expect_keywords(&[CONTINUAL, TASK])?;
+        let if_not_exists = self.parse_if_not_exists()?;
+        let name = self.parse_item_name()?;
+        let in_cluster = self.parse_optional_in_cluster()?;
+        self.expect_keywords(&[AS, INSERT, INTO])?;
+        let target = self.parse_raw_name()?;
+        let columns = self.parse_parenthesized_column_list(Optional)?;
+        let query = self.parse_query()?;
+        Ok(Statement::CreateContinualTask(
+            CreateContinualTaskStatement {
+                if_not_exists,
+                name,
+                in_cluster,
+                target,
+                columns,
+                query,
+            },
+        ))
+    }
+
     fn parse_materialized_view_option_name(
         &mut self,
     ) -> Result<MaterializedViewOptionName, ParserError> {
-        let option = self.expect_one_of_keywords(&[ASSERT, RETAIN, REFRESH])?;
+        let option = self.expect_one_of_keywords(&[ASSERT, RETAIN, REFRESH, MEMORY])?;
         let name = match option {
             ASSERT => {
                 self.expect_keywords(&[NOT, NULL])?;
@@ -3353,6 +3547,10 @@ impl<'a> Parser<'a> {
                 MaterializedViewOptionName::RetainHistory
             }
             REFRESH => MaterializedViewOptionName::Refresh,
+            MEMORY => {
+                self.expect_keyword(LIMIT)?;
+                MaterializedViewOptionName::MemoryLimit
+            }
             _ => unreachable!(),
         };
         Ok(name)
@@ -3532,11 +3730,11 @@ impl<'a> Parser<'a> {
         self.expect_keyword(ROLE)?;
         let name = self.parse_identifier()?;
         let _ = self.parse_keyword(WITH);
-        let options = self.parse_role_attributes();
+        let options = self.parse_role_attributes()?;
         Ok(Statement::CreateRole(CreateRoleStatement { name, options }))
     }
 
-    fn parse_role_attributes(&mut self) -> Vec<RoleAttribute> {
+    fn parse_role_attributes(&mut self) -> Result<Vec<RoleAttribute>, ParserError> {
         let mut options = vec![];
         loop {
             match self.parse_one_of_keywords(&[
@@ -3552,6 +3750,7 @@ impl<'a> Parser<'a> {
                 NOCREATEDB,
                 CREATEROLE,
                 NOCREATEROLE,
+                PASSWORD,
             ]) {
                 None => break,
                 Some(SUPERUSER) => options.push(RoleAttribute::SuperUser),
@@ -3566,22 +3765,38 @@ impl<'a> Parser<'a> {
                 Some(NOCREATEDB) => options.push(RoleAttribute::NoCreateDB),
                 Some(CREATEROLE) => options.push(RoleAttribute::CreateRole),
                 Some(NOCREATEROLE) => options.push(RoleAttribute::NoCreateRole),
+                Some(PASSWORD) => {
+                    let password = if self.parse_keyword(NULL) {
+                        None
+                    } else {
+                        Some(self.parse_literal_string()?)
+                    };
+                    options.push(RoleAttribute::Password(password));
+                }
                 Some(_) => unreachable!(),
             }
         }
-        options
+        Ok(options)
     }
 
     fn parse_create_secret(&mut self) -> Result<Statement<Raw>, ParserError> {
         self.expect_keyword(SECRET)?;
         let if_not_exists = self.parse_if_not_exists()?;
         let name = self.parse_item_name()?;
-        self.expect_keyword(AS)?;
+        let external = match self.expect_one_of_keywords(&[AS, FROM])? {
+            AS => false,
+            FROM => {
+                self.expect_keyword(EXTERNAL)?;
+                true
+            }
+            _ => unreachable!(),
+        };
         let value = self.parse_expr()?;
         Ok(Statement::CreateSecret(CreateSecretStatement {
             name,
             if_not_exists,
             value,
+            external,
         }))
     }
 
@@ -3621,6 +3836,81 @@ impl<'a> Parser<'a> {
         }
     }
 
+    fn parse_create_function(&mut self) -> Result<Statement<Raw>, ParserError> {
+        self.expect_keyword(FUNCTION)?;
+        let name = self.parse_item_name()?;
+        self.expect_token(&Token::LParen)?;
+        let params = if self.consume_token(&Token::RParen) {
+            vec![]
+        } else {
+            let params = self.parse_comma_separated(Parser::parse_function_parameter)?;
+            self.expect_token(&Token::RParen)?;
+            params
+        };
+        self.expect_keywords(&[RETURNS, TABLE])?;
+        self.expect_token(&Token::LParen)?;
+        let returns = self.parse_comma_separated(|parser| {
+            Ok(FunctionColumnDef {
+                name: parser.parse_identifier()?,
+                data_type: parser.parse_data_type()?,
+            })
+        })?;
+        self.expect_token(&Token::RParen)?;
+
+        // Like PostgreSQL, accept the body and the language in either order.
+        let mut body = None;
+        let mut language = false;
+        while body.is_none() || !language {
+            match self.expect_one_of_keywords(&[AS, LANGUAGE])? {
+                AS if body.is_none() => {
+                    let pos = self.peek_pos();
+                    let sql = self.parse_literal_string()?;
+                    let query = parse_function_body(&sql).map_err(|e| {
+                        self.error(pos, format!("invalid function body: {}", e.message))
+                    })?;
+                    body = Some(query);
+                }
+                LANGUAGE if !language => {
+                    self.expect_keyword(SQL)?;
+                    language = true;
+                }
+                keyword => {
+                    return parser_err!(
+                        self,
+                        self.peek_prev_pos(),
+                        "{keyword} specified more than once"
+                    )
+                }
+            }
+        }
+
+        Ok(Statement::CreateFunction(CreateFunctionStatement {
+            name,
+            params,
+            returns,
+            body: body.expect("loop exits once the body is parsed"),
+        }))
+    }
+
+    fn parse_function_parameter(&mut self) -> Result<FunctionParameter<Raw>, ParserError> {
+        // The name of a parameter is optional. If a complete data type ends
+        // the parameter, the parameter has no name.
+        let index = self.index;
+        if let Ok(data_type) = self.parse_data_type() {
+            if matches!(self.peek_token(), Some(Token::Comma | Token::RParen)) {
+                return Ok(FunctionParameter {
+                    name: None,
+                    data_type,
+                });
+            }
+        }
+        self.index = index;
+        Ok(FunctionParameter {
+            name: Some(self.parse_identifier()?),
+            data_type: self.parse_data_type()?,
+        })
+    }
+
     fn parse_create_type_list_option(&mut self) -> Result<CreateTypeListOption<Raw>, ParserError> {
         self.expect_keywords(&[ELEMENT, TYPE])?;
         let name = CreateTypeListOptionName::ElementType;
@@ -3681,8 +3971,10 @@ impl<'a> Parser<'a> {
             IDLE,
             INTROSPECTION,
             MANAGED,
+            MAX,
             REPLICAS,
             REPLICATION,
+            SCHEDULE,
             SIZE,
         ])?;
         let name = match option {
@@ -3701,11 +3993,16 @@ impl<'a> Parser<'a> {
                 _ => unreachable!(),
             },
             MANAGED => ClusterOptionName::Managed,
+            MAX => {
+                self.expect_keywords(&[CONCURRENT, QUERIES])?;
+                ClusterOptionName::MaxConcurrentQueries
+            }
             REPLICAS => ClusterOptionName::Replicas,
             REPLICATION => {
                 self.expect_keyword(FACTOR)?;
                 ClusterOptionName::ReplicationFactor
             }
+            SCHEDULE => ClusterOptionName::Schedule,
             SIZE => ClusterOptionName::Size,
             _ => unreachable!(),
         };
@@ -3718,11 +4015,33 @@ impl<'a> Parser<'a> {
         if name == ClusterOptionName::Replicas {
             return self.parse_cluster_option_replicas();
         }
+        if name == ClusterOptionName::Schedule {
+            return self.parse_cluster_option_schedule();
+        }
 
         let value = self.parse_optional_option_value()?;
         Ok(ClusterOption { name, value })
     }
 
+    fn parse_cluster_option_schedule(&mut self) -> Result<ClusterOption<Raw>, ParserError> {
+        let _ = self.consume_token(&Token::Eq);
+        let schedule = match self.expect_one_of_keywords(&[MANUAL, ON])? {
+            MANUAL => ClusterScheduleOptionValue::Manual,
+            ON => {
+                self.expect_token(&Token::LParen)?;
+                self.expect_keyword(CRON)?;
+                let expr = self.parse_literal_string()?;
+                self.expect_token(&Token::RParen)?;
+                ClusterScheduleOptionValue::Cron(expr)
+            }
+            _ => unreachable!(),
+        };
+        Ok(ClusterOption {
+            name: ClusterOptionName::Schedule,
+            value: Some(WithOptionValue::ClusterSchedule(schedule)),
+        })
+    }
+
     fn parse_cluster_option_replicas(&mut self) -> Result<ClusterOption<Raw>, ParserError> {
         self.expect_token(&Token::LParen)?;
         let replicas = if self.consume_token(&Token::RParen) {
@@ -3745,6 +4064,12 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_replica_option(&mut self) -> Result<ReplicaOption<Raw>, ParserError> {
+        let name = self.parse_replica_option_name()?;
+        let value = self.parse_optional_option_value()?;
+        Ok(ReplicaOption { name, value })
+    }
+
+    fn parse_replica_option_name(&mut self) -> Result<ReplicaOptionName, ParserError> {
         let name = match self.expect_one_of_keywords(&[
             AVAILABILITY,
             BILLED,
@@ -3798,8 +4123,7 @@ impl<'a> Parser<'a> {
             WORKERS => ReplicaOptionName::Workers,
             _ => unreachable!(),
         };
-        let value = self.parse_optional_option_value()?;
-        Ok(ReplicaOption { name, value })
+        Ok(name)
     }
 
     fn parse_cluster_feature(&mut self) -> Result<ClusterFeature<Raw>, ParserError> {
@@ -3918,19 +4242,20 @@ impl<'a> Parser<'a> {
     fn parse_drop_objects(&mut self) -> Result<Statement<Raw>, ParserError> {
         let object_type = self.expect_object_type()?;
         let if_exists = self.parse_if_exists()?;
-        match object_type {
+        let mut stmt = match object_type {
             ObjectType::Database => {
                 let name = UnresolvedObjectName::Database(self.parse_database_name()?);
                 let restrict = matches!(
                     self.parse_at_most_one_keyword(&[CASCADE, RESTRICT], "DROP")?,
                     Some(RESTRICT),
                 );
-                Ok(Statement::DropObjects(DropObjectsStatement {
+                DropObjectsStatement {
                     object_type: ObjectType::Database,
                     if_exists,
                     names: vec![name],
                     cascade: !restrict,
-                }))
+                    dry_run: false,
+                }
             }
             ObjectType::Schema => {
                 let names = self.parse_comma_separated(|parser| {
@@ -3941,26 +4266,28 @@ impl<'a> Parser<'a> {
                     self.parse_at_most_one_keyword(&[CASCADE, RESTRICT], "DROP")?,
                     Some(CASCADE),
                 );
-                Ok(Statement::DropObjects(DropObjectsStatement {
+                DropObjectsStatement {
                     object_type: ObjectType::Schema,
                     if_exists,
                     names,
                     cascade,
-                }))
+                    dry_run: false,
+                }
             }
             ObjectType::Role => {
                 let names = self.parse_comma_separated(|parser| {
                     Ok(UnresolvedObjectName::Role(parser.parse_identifier()?))
                 })?;
-                Ok(Statement::DropObjects(DropObjectsStatement {
+                DropObjectsStatement {
                     object_type: ObjectType::Role,
                     if_exists,
                     names,
                     cascade: false,
-                }))
+                    dry_run: false,
+                }
             }
-            ObjectType::Cluster => self.parse_drop_clusters(if_exists),
-            ObjectType::ClusterReplica => self.parse_drop_cluster_replicas(if_exists),
+            ObjectType::Cluster => self.parse_drop_clusters(if_exists)?,
+            ObjectType::ClusterReplica => self.parse_drop_cluster_replicas(if_exists)?,
             ObjectType::Table
             | ObjectType::View
             | ObjectType::MaterializedView
@@ -3969,7 +4296,9 @@ impl<'a> Parser<'a> {
             | ObjectType::Index
             | ObjectType::Type
             | ObjectType::Secret
-            | ObjectType::Connection => {
+            | ObjectType::Connection
+            | ObjectType::Func
+            | ObjectType::ContinualTask => {
                 let names = self.parse_comma_separated(|parser| {
                     Ok(UnresolvedObjectName::Item(parser.parse_item_name()?))
                 })?;
@@ -3977,22 +4306,30 @@ impl<'a> Parser<'a> {
                     self.parse_at_most_one_keyword(&[CASCADE, RESTRICT], "DROP")?,
                     Some(CASCADE),
                 );
-                Ok(Statement::DropObjects(DropObjectsStatement {
+                DropObjectsStatement {
                     object_type,
                     if_exists,
                     names,
                     cascade,
-                }))
+                    dry_run: false,
+                }
             }
-            ObjectType::Func | ObjectType::Subsource => parser_err!(
-                self,
-                self.peek_prev_pos(),
-                format!("Unsupported DROP on {object_type}")
-            ),
-        }
+            ObjectType::Subsource => {
+                return parser_err!(
+                    self,
+                    self.peek_prev_pos(),
+                    format!("Unsupported DROP on {object_type}")
+                )
+            }
+        };
+        stmt.dry_run = self.parse_keywords(&[DRY, RUN]);
+        Ok(Statement::DropObjects(stmt))
     }
 
-    fn parse_drop_clusters(&mut self, if_exists: bool) -> Result<Statement<Raw>, ParserError> {
+    fn parse_drop_clusters(
+        &mut self,
+        if_exists: bool,
+    ) -> Result<DropObjectsStatement, ParserError> {
         let names = self.parse_comma_separated(|parser| {
             Ok(UnresolvedObjectName::Cluster(parser.parse_identifier()?))
         })?;
@@ -4000,29 +4337,31 @@ impl<'a> Parser<'a> {
             self.parse_at_most_one_keyword(&[CASCADE, RESTRICT], "DROP")?,
             Some(CASCADE),
         );
-        Ok(Statement::DropObjects(DropObjectsStatement {
+        Ok(DropObjectsStatement {
             object_type: ObjectType::Cluster,
             if_exists,
             names,
             cascade,
-        }))
+            dry_run: false,
+        })
     }
 
     fn parse_drop_cluster_replicas(
         &mut self,
         if_exists: bool,
-    ) -> Result<Statement<Raw>, ParserError> {
+    ) -> Result<DropObjectsStatement, ParserError> {
         let names = self.parse_comma_separated(|p| {
             Ok(UnresolvedObjectName::ClusterReplica(
                 p.parse_cluster_replica_name()?,
             ))
         })?;
-        Ok(Statement::DropObjects(DropObjectsStatement {
+        Ok(DropObjectsStatement {
             object_type: ObjectType::ClusterReplica,
             if_exists,
             names,
             cascade: false,
-        }))
+            dry_run: false,
+        })
     }
 
     fn parse_drop_owned(&mut self) -> Result<Statement<Raw>, ParserError> {
@@ -4066,16 +4405,38 @@ impl<'a> Parser<'a> {
             vec![]
         };
 
+        let on_commit = if self.parse_keywords(&[ON, COMMIT]) {
+            Some(self.parse_on_commit_action()?)
+        } else {
+            None
+        };
+
         Ok(Statement::CreateTable(CreateTableStatement {
             name: table_name,
             columns,
             constraints,
             if_not_exists,
             temporary,
+            on_commit,
             with_options,
         }))
     }
 
+    fn parse_on_commit_action(&mut self) -> Result<OnCommitAction, ParserError> {
+        match self.expect_one_of_keywords(&[PRESERVE, DELETE, DROP])? {
+            PRESERVE => {
+                self.expect_keyword(ROWS)?;
+                Ok(OnCommitAction::PreserveRows)
+            }
+            DELETE => {
+                self.expect_keyword(ROWS)?;
+                Ok(OnCommitAction::DeleteRows)
+            }
+            DROP => Ok(OnCommitAction::Drop),
+            _ => unreachable!(),
+        }
+    }
+
     fn parse_columns(
         &mut self,
         optional: IsOptional,
@@ -4309,6 +4670,8 @@ impl<'a> Parser<'a> {
             } else {
                 Ok(WithOptionValue::Ident(ident!("secret")))
             }
+        } else if self.parse_keyword(MAP) {
+            Ok(WithOptionValue::Map(self.parse_option_map()?))
         } else if let Some(value) = self.maybe_parse(Parser::parse_value) {
             Ok(WithOptionValue::Value(value))
         } else if let Some(ident) = self.maybe_parse(Parser::parse_identifier) {
@@ -4318,6 +4681,30 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Parses the body of a `MAP['key' => value, ...]` option value, after the
+    /// `MAP` keyword has been consumed.
+    fn parse_option_map(&mut self) -> Result<BTreeMap<String, WithOptionValue<Raw>>, ParserError> {
+        self.expect_token(&Token::LBracket)?;
+        let mut map = BTreeMap::new();
+        if self.consume_token(&Token::RBracket) {
+            return Ok(map);
+        }
+        loop {
+            let pos = self.peek_pos();
+            let key = self.parse_literal_string()?;
+            self.expect_token(&Token::Op("=>".to_owned()))?;
+            let value = self.parse_option_value()?;
+            if map.insert(key.clone(), value).is_some() {
+                return parser_err!(self, pos, "duplicate key '{}' in MAP", key);
+            }
+            if !self.consume_token(&Token::Comma) {
+                break;
+            }
+        }
+        self.expect_token(&Token::RBracket)?;
+        Ok(map)
+    }
+
     fn parse_data_type_option_value(&mut self) -> Result<WithOptionValue<Raw>, ParserError> {
         let _ = self.consume_token(&Token::Eq);
         Ok(WithOptionValue::DataType(self.parse_data_type()?))
@@ -4329,6 +4716,9 @@ impl<'a> Parser<'a> {
         } else if self.parse_keywords(&[DEFAULT, PRIVILEGES]) {
             self.parse_alter_default_privileges()
                 .map_parser_err(StatementKind::AlterDefaultPrivileges)
+        } else if self.parse_keyword(QUOTA) {
+            self.parse_alter_quota()
+                .map_parser_err(StatementKind::AlterQuota)
         } else {
             self.parse_alter_object()
         }
@@ -4370,42 +4760,7 @@ impl<'a> Parser<'a> {
                 }))
             }
             ObjectType::Cluster => self.parse_alter_cluster(object_type),
-            ObjectType::ClusterReplica => {
-                let if_exists = self.parse_if_exists().map_no_statement_parser_err()?;
-                let name = UnresolvedObjectName::ClusterReplica(
-                    self.parse_cluster_replica_name()
-                        .map_no_statement_parser_err()?,
-                );
-                let action = self
-                    .expect_one_of_keywords(&[OWNER, RENAME])
-                    .map_no_statement_parser_err()?;
-                self.expect_keyword(TO).map_no_statement_parser_err()?;
-                match action {
-                    OWNER => {
-                        let new_owner = self
-                            .parse_identifier()
-                            .map_parser_err(StatementKind::AlterOwner)?;
-                        Ok(Statement::AlterOwner(AlterOwnerStatement {
-                            object_type,
-                            if_exists,
-                            name,
-                            new_owner,
-                        }))
-                    }
-                    RENAME => {
-                        let to_item_name = self
-                            .parse_identifier()
-                            .map_parser_err(StatementKind::AlterObjectRename)?;
-                        Ok(Statement::AlterObjectRename(AlterObjectRenameStatement {
-                            object_type,
-                            if_exists,
-                            name,
-                            to_item_name,
-                        }))
-                    }
-                    _ => unreachable!(),
-                }
-            }
+            ObjectType::ClusterReplica => self.parse_alter_cluster_replica(object_type),
             ObjectType::Database => {
                 let if_exists = self
                     .parse_if_exists()
@@ -4427,7 +4782,7 @@ impl<'a> Parser<'a> {
                 }))
             }
             ObjectType::Schema => self.parse_alter_schema(object_type),
-            ObjectType::Func | ObjectType::Subsource => parser_err!(
+            ObjectType::Func | ObjectType::Subsource | ObjectType::ContinualTask => parser_err!(
                 self,
                 self.peek_prev_pos(),
                 format!("Unsupported ALTER on {object_type}")
@@ -4519,6 +4874,80 @@ impl<'a> Parser<'a> {
         }
     }
 
+    fn parse_alter_cluster_replica(
+        &mut self,
+        object_type: ObjectType,
+    ) -> Result<Statement<Raw>, ParserStatementError> {
+        let if_exists = self.parse_if_exists().map_no_statement_parser_err()?;
+        let replica_name = self
+            .parse_cluster_replica_name()
+            .map_no_statement_parser_err()?;
+        let action = self
+            .expect_one_of_keywords(&[OWNER, RENAME, RESET, SET])
+            .map_no_statement_parser_err()?;
+        match action {
+            OWNER => {
+                self.expect_keyword(TO)
+                    .map_parser_err(StatementKind::AlterOwner)?;
+                let new_owner = self
+                    .parse_identifier()
+                    .map_parser_err(StatementKind::AlterOwner)?;
+                Ok(Statement::AlterOwner(AlterOwnerStatement {
+                    object_type,
+                    if_exists,
+                    name: UnresolvedObjectName::ClusterReplica(replica_name),
+                    new_owner,
+                }))
+            }
+            RENAME => {
+                self.expect_keyword(TO)
+                    .map_parser_err(StatementKind::AlterObjectRename)?;
+                let to_item_name = self
+                    .parse_identifier()
+                    .map_parser_err(StatementKind::AlterObjectRename)?;
+                Ok(Statement::AlterObjectRename(AlterObjectRenameStatement {
+                    object_type,
+                    if_exists,
+                    name: UnresolvedObjectName::ClusterReplica(replica_name),
+                    to_item_name,
+                }))
+            }
+            RESET => {
+                self.expect_token(&Token::LParen)
+                    .map_parser_err(StatementKind::AlterClusterReplica)?;
+                let names = self
+                    .parse_comma_separated(Parser::parse_replica_option_name)
+                    .map_parser_err(StatementKind::AlterClusterReplica)?;
+                self.expect_token(&Token::RParen)
+                    .map_parser_err(StatementKind::AlterClusterReplica)?;
+                Ok(Statement::AlterClusterReplica(
+                    AlterClusterReplicaStatement {
+                        if_exists,
+                        name: replica_name,
+                        action: AlterClusterReplicaAction::ResetOptions(names),
+                    },
+                ))
+            }
+            SET => {
+                self.expect_token(&Token::LParen)
+                    .map_parser_err(StatementKind::AlterClusterReplica)?;
+                let options = self
+                    .parse_comma_separated(Parser::parse_replica_option)
+                    .map_parser_err(StatementKind::AlterClusterReplica)?;
+                self.expect_token(&Token::RParen)
+                    .map_parser_err(StatementKind::AlterClusterReplica)?;
+                Ok(Statement::AlterClusterReplica(
+                    AlterClusterReplicaStatement {
+                        if_exists,
+                        name: replica_name,
+                        action: AlterClusterReplicaAction::SetOptions(options),
+                    },
+                ))
+            }
+            _ => unreachable!(),
+        }
+    }
+
     fn parse_alter_source(&mut self) -> Result<Statement<Raw>, ParserStatementError> {
         let if_exists = self.parse_if_exists().map_no_statement_parser_err()?;
         let source_name = self.parse_item_name().map_no_statement_parser_err()?;
@@ -4711,6 +5140,11 @@ impl<'a> Parser<'a> {
                     })
                 }
                 SET => {
+                    if let Some(result) =
+                        self.maybe_parse_alter_set_cluster(if_exists, &name, ObjectType::Index)
+                    {
+                        return result;
+                    }
                     self.expect_token(&Token::LParen)
                         .map_parser_err(StatementKind::AlterIndex)?;
                     let set_options = self
@@ -4763,10 +5197,15 @@ impl<'a> Parser<'a> {
 
         Ok(
             match self
-                .expect_one_of_keywords(&[AS, RENAME, OWNER])
+                .expect_one_of_keywords(&[AS, FROM, RENAME, OWNER])
                 .map_no_statement_parser_err()?
             {
-                AS => {
+                kw @ (AS | FROM) => {
+                    let external = kw == FROM;
+                    if external {
+                        self.expect_keyword(EXTERNAL)
+                            .map_parser_err(StatementKind::AlterSecret)?;
+                    }
                     let value = self
                         .parse_expr()
                         .map_parser_err(StatementKind::AlterSecret)?;
@@ -4774,6 +5213,7 @@ impl<'a> Parser<'a> {
                         name,
                         if_exists,
                         value,
+                        external,
                     })
                 }
                 RENAME => {
@@ -4820,6 +5260,13 @@ impl<'a> Parser<'a> {
                 .map_no_statement_parser_err()?
             {
                 RESET => {
+                    if self.parse_keyword(OFFSET) {
+                        return Ok(Statement::AlterSink(AlterSinkStatement {
+                            sink_name: name,
+                            if_exists,
+                            action: AlterSinkAction::ResetOffset,
+                        }));
+                    }
                     self.expect_token(&Token::LParen)
                         .map_parser_err(StatementKind::AlterSink)?;
                     let reset_options = self
@@ -5046,7 +5493,7 @@ impl<'a> Parser<'a> {
             }
             Some(WITH) | None => {
                 let _ = self.parse_keyword(WITH);
-                let attrs = self.parse_role_attributes();
+                let attrs = self.parse_role_attributes()?;
                 AlterRoleOption::Attributes(attrs)
             }
             Some(k) => unreachable!("unmatched keyword: {k}"),
@@ -5055,6 +5502,29 @@ impl<'a> Parser<'a> {
         Ok(Statement::AlterRole(AlterRoleStatement { name, option }))
     }
 
+    fn parse_alter_quota(&mut self) -> Result<Statement<Raw>, ParserError> {
+        self.expect_keyword(FOR)?;
+        let subject = match self.expect_one_of_keywords(&[ROLE, DATABASE])? {
+            ROLE => QuotaSubjectSpecification::Role(self.parse_identifier()?),
+            DATABASE => QuotaSubjectSpecification::Database(self.parse_database_name()?),
+            _ => unreachable!(),
+        };
+        let (quota, limit) = match self.expect_one_of_keywords(&[SET, RESET])? {
+            SET => {
+                let quota = self.parse_identifier()?;
+                self.expect_keyword_or_token(TO, &Token::Eq)?;
+                (quota, Some(self.parse_literal_uint()?))
+            }
+            RESET => (self.parse_identifier()?, None),
+            _ => unreachable!(),
+        };
+        Ok(Statement::AlterQuota(AlterQuotaStatement {
+            subject,
+            quota,
+            limit,
+        }))
+    }
+
     fn parse_alter_default_privileges(&mut self) -> Result<Statement<Raw>, ParserError> {
         self.expect_keyword(FOR)?;
         let target_roles = match self.expect_one_of_keywords(&[ROLE, USER, ALL])? {
@@ -5809,7 +6279,8 @@ impl<'a> Parser<'a> {
             | ObjectType::Type
             | ObjectType::Secret
             | ObjectType::Connection
-            | ObjectType::Func => UnresolvedObjectName::Item(self.parse_item_name()?),
+            | ObjectType::Func
+            | ObjectType::ContinualTask => UnresolvedObjectName::Item(self.parse_item_name()?),
             ObjectType::Role => UnresolvedObjectName::Role(self.parse_identifier()?),
             ObjectType::Cluster => UnresolvedObjectName::Cluster(self.parse_identifier()?),
             ObjectType::ClusterReplica => {
@@ -6499,6 +6970,27 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_show(&mut self) -> Result<ShowStatement<Raw>, ParserError> {
+        let redacted = self.parse_keyword(REDACTED);
+        if redacted && !self.peek_keyword(CREATE) {
+            return parser_err!(
+                self,
+                self.peek_pos(),
+                "SHOW REDACTED is only supported for SHOW REDACTED CREATE"
+            );
+        }
+        let extended = self.parse_keyword(EXTENDED);
+        if extended
+            && self
+                .peek_one_of_keywords(&[SOURCES, SINKS, CLUSTERS, MATERIALIZED])
+                .is_none()
+        {
+            return parser_err!(
+                self,
+                self.peek_pos(),
+                "SHOW EXTENDED is only supported for SHOW EXTENDED SOURCES, SINKS, \
+                MATERIALIZED VIEWS, and CLUSTERS"
+            );
+        }
         if self.parse_one_of_keywords(&[COLUMNS, FIELDS]).is_some() {
             self.parse_show_columns()
         } else if self.parse_keyword(OBJECTS) {
@@ -6511,6 +7003,7 @@ impl<'a> Parser<'a> {
                 object_type: ShowObjectType::Object,
                 from,
                 filter: self.parse_show_statement_filter()?,
+                extended: false,
             }))
         } else if let Some(object_type) = self.parse_plural_object_type() {
             let from = if object_type.lives_in_schema() {
@@ -6591,7 +7084,7 @@ impl<'a> Parser<'a> {
                         on_object,
                     }
                 }
-                ObjectType::Func => {
+                ObjectType::Func | ObjectType::ContinualTask => {
                     return parser_err!(
                         self,
                         self.peek_prev_pos(),
@@ -6603,6 +7096,16 @@ impl<'a> Parser<'a> {
                 object_type: show_object_type,
                 from,
                 filter: self.parse_show_statement_filter()?,
+                extended,
+            }))
+        } else if self.parse_keywords(&[DEPENDENTS, OF]) {
+            let of_object = self.parse_raw_name()?;
+            let cascade = self.parse_keyword(CASCADE);
+            Ok(ShowStatement::ShowObjects(ShowObjectsStatement {
+                object_type: ShowObjectType::Dependent { of_object, cascade },
+                from: None,
+                filter: self.parse_show_statement_filter()?,
+                extended: false,
             }))
         } else if self.parse_keyword(CLUSTER) {
             Ok(ShowStatement::ShowVariable(ShowVariableStatement {
@@ -6623,37 +7126,45 @@ impl<'a> Parser<'a> {
                 object_type: ShowObjectType::RoleMembership { role },
                 from: None,
                 filter: self.parse_show_statement_filter()?,
+                extended: false,
             }))
         } else if self.parse_keywords(&[CREATE, VIEW]) {
             Ok(ShowStatement::ShowCreateView(ShowCreateViewStatement {
                 view_name: self.parse_raw_name()?,
+                redacted,
             }))
         } else if self.parse_keywords(&[CREATE, MATERIALIZED, VIEW]) {
             Ok(ShowStatement::ShowCreateMaterializedView(
                 ShowCreateMaterializedViewStatement {
                     materialized_view_name: self.parse_raw_name()?,
+                    redacted,
                 },
             ))
         } else if self.parse_keywords(&[CREATE, SOURCE]) {
             Ok(ShowStatement::ShowCreateSource(ShowCreateSourceStatement {
                 source_name: self.parse_raw_name()?,
+                redacted,
             }))
         } else if self.parse_keywords(&[CREATE, TABLE]) {
             Ok(ShowStatement::ShowCreateTable(ShowCreateTableStatement {
                 table_name: self.parse_raw_name()?,
+                redacted,
             }))
         } else if self.parse_keywords(&[CREATE, SINK]) {
             Ok(ShowStatement::ShowCreateSink(ShowCreateSinkStatement {
                 sink_name: self.parse_raw_name()?,
+                redacted,
             }))
         } else if self.parse_keywords(&[CREATE, INDEX]) {
             Ok(ShowStatement::ShowCreateIndex(ShowCreateIndexStatement {
                 index_name: self.parse_raw_name()?,
+                redacted,
             }))
         } else if self.parse_keywords(&[CREATE, CONNECTION]) {
             Ok(ShowStatement::ShowCreateConnection(
                 ShowCreateConnectionStatement {
                     connection_name: self.parse_raw_name()?,
+                    redacted,
                 },
             ))
         } else {
@@ -6712,6 +7223,7 @@ impl<'a> Parser<'a> {
             object_type: ShowObjectType::Privileges { object_type, role },
             from: None,
             filter: self.parse_show_statement_filter()?,
+            extended: false,
         }))
     }
 
@@ -6730,6 +7242,7 @@ impl<'a> Parser<'a> {
             object_type: ShowObjectType::DefaultPrivileges { object_type, role },
             from: None,
             filter: self.parse_show_statement_filter()?,
+            extended: false,
         }))
     }
 
@@ -7558,26 +8071,30 @@ impl<'a> Parser<'a> {
         let name = self
             .parse_identifier()
             .map_parser_err(StatementKind::Declare)?;
+        // NO SCROLL is the default behavior so we can ignore it.
+        let scroll = if self.parse_keywords(&[NO, SCROLL]) {
+            false
+        } else {
+            self.parse_keyword(SCROLL)
+        };
         self.expect_keyword(CURSOR)
             .map_parser_err(StatementKind::Declare)?;
-        if self.parse_keyword(WITH) {
-            let err = parser_err!(
-                self,
-                self.peek_prev_pos(),
-                format!("WITH HOLD is unsupported for cursors")
-            )
-            .map_parser_err(StatementKind::Declare);
+        let hold = if self.parse_keyword(WITH) {
             self.expect_keyword(HOLD)
                 .map_parser_err(StatementKind::Declare)?;
-            return err;
-        }
-        // WITHOUT HOLD is optional and the default behavior so we can ignore it.
-        let _ = self.parse_keywords(&[WITHOUT, HOLD]);
+            true
+        } else {
+            // WITHOUT HOLD is optional and the default behavior so we can ignore it.
+            let _ = self.parse_keywords(&[WITHOUT, HOLD]);
+            false
+        };
         self.expect_keyword(FOR)
             .map_parser_err(StatementKind::Declare)?;
         let StatementParseResult { ast, sql } = self.parse_statement()?;
         Ok(Statement::Declare(DeclareStatement {
             name,
+            scroll,
+            hold,
             stmt: Box::new(ast),
             sql: sql.to_string(),
         }))
@@ -7646,13 +8163,35 @@ impl<'a> Parser<'a> {
     /// Parse a `FETCH` statement, assuming that the `FETCH` token
     /// has already been consumed.
     fn parse_fetch(&mut self) -> Result<Statement<Raw>, ParserError> {
-        let _ = self.parse_keyword(FORWARD);
-        let count = if let Some(count) = self.maybe_parse(Parser::parse_literal_uint) {
-            Some(FetchDirection::ForwardCount(count))
-        } else if self.parse_keyword(ALL) {
-            Some(FetchDirection::ForwardAll)
-        } else {
-            None
+        let count = match self
+            .parse_one_of_keywords(&[NEXT, PRIOR, FIRST, LAST, ABSOLUTE, RELATIVE, BACKWARD])
+        {
+            Some(NEXT) => Some(FetchDirection::ForwardCount(1)),
+            Some(PRIOR) => Some(FetchDirection::BackwardCount(1)),
+            Some(FIRST) => Some(FetchDirection::Absolute(1)),
+            Some(LAST) => Some(FetchDirection::Absolute(-1)),
+            Some(ABSOLUTE) => Some(FetchDirection::Absolute(self.parse_literal_int()?)),
+            Some(RELATIVE) => Some(FetchDirection::Relative(self.parse_literal_int()?)),
+            Some(BACKWARD) => {
+                if let Some(count) = self.maybe_parse(Parser::parse_literal_uint) {
+                    Some(FetchDirection::BackwardCount(count))
+                } else if self.parse_keyword(ALL) {
+                    Some(FetchDirection::BackwardAll)
+                } else {
+                    Some(FetchDirection::BackwardCount(1))
+                }
+            }
+            Some(_) => unreachable!(),
+            None => {
+                let _ = self.parse_keyword(FORWARD);
+                if let Some(count) = self.maybe_parse(Parser::parse_literal_uint) {
+                    Some(FetchDirection::ForwardCount(count))
+                } else if self.parse_keyword(ALL) {
+                    Some(FetchDirection::ForwardAll)
+                } else {
+                    None
+                }
+            }
         };
         let _ = self.parse_keyword(FROM);
         let name = self.parse_identifier()?;
@@ -7883,7 +8422,8 @@ impl<'a> Parser<'a> {
             | ObjectType::ClusterReplica
             | ObjectType::Role
             | ObjectType::Func
-            | ObjectType::Subsource => {
+            | ObjectType::Subsource
+            | ObjectType::ContinualTask => {
                 parser_err!(
                     self,
                     self.peek_prev_pos(),
@@ -7919,6 +8459,7 @@ impl<'a> Parser<'a> {
                 DATABASE,
                 SCHEMA,
                 FUNCTION,
+                CONTINUAL,
             ])? {
                 TABLE => ObjectType::Table,
                 VIEW => ObjectType::View,
@@ -7946,6 +8487,13 @@ impl<'a> Parser<'a> {
                 DATABASE => ObjectType::Database,
                 SCHEMA => ObjectType::Schema,
                 FUNCTION => ObjectType::Func,
+                CONTINUAL => {
+                    if let Err(e) = self.expect_keyword(TASK) {
+                        self.prev_token();
+                        return Err(e);
+                    }
+                    ObjectType::ContinualTask
+                }
                 _ => unreachable!(),
             },
         )
@@ -7970,6 +8518,7 @@ impl<'a> Parser<'a> {
                 DATABASE,
                 SCHEMA,
                 FUNCTION,
+                CONTINUAL,
             ])? {
                 TABLE => ObjectType::Table,
                 VIEW => ObjectType::View,
@@ -7998,6 +8547,14 @@ impl<'a> Parser<'a> {
                 DATABASE => ObjectType::Database,
                 SCHEMA => ObjectType::Schema,
                 FUNCTION => ObjectType::Func,
+                CONTINUAL => {
+                    if self.parse_keyword(TASK) {
+                        ObjectType::ContinualTask
+                    } else {
+                        self.prev_token();
+                        return None;
+                    }
+                }
                 _ => unreachable!(),
             },
         )
@@ -8283,6 +8840,7 @@ impl<'a> Parser<'a> {
             DATABASE,
             SCHEMA,
             CLUSTER,
+            CONTINUAL,
         ])? {
             TABLE => {
                 let name = self.parse_raw_name()?;
@@ -8350,6 +8908,11 @@ impl<'a> Parser<'a> {
                 let name = self.parse_column_name()?;
                 CommentObjectType::Column { name }
             }
+            CONTINUAL => {
+                self.expect_keyword(TASK)?;
+                let name = self.parse_raw_name()?;
+                CommentObjectType::ContinualTask { name }
+            }
             _ => unreachable!(),
         };
 
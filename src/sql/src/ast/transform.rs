@@ -11,22 +11,81 @@
 
 use std::collections::{BTreeMap, BTreeSet};
 
+use mz_ore::collections::CollectionExt;
 use mz_ore::str::StrExt;
 use mz_repr::GlobalId;
+use mz_sql_parser::ast::display::AstDisplay;
 
 use crate::ast::visit::{self, Visit};
 use crate::ast::visit_mut::{self, VisitMut};
 use crate::ast::{
-    AstInfo, CreateConnectionStatement, CreateIndexStatement, CreateMaterializedViewStatement,
-    CreateSecretStatement, CreateSinkStatement, CreateSourceStatement, CreateSubsourceStatement,
-    CreateTableStatement, CreateViewStatement, CreateWebhookSourceStatement, Expr, Ident, Query,
-    Raw, RawItemName, Statement, UnresolvedItemName, ViewDefinition,
+    AstInfo, CreateConnectionStatement, CreateContinualTaskStatement, CreateFunctionStatement,
+    CreateIndexStatement, CreateMaterializedViewStatement, CreateSecretStatement,
+    CreateSinkStatement, CreateSourceStatement, CreateSubsourceStatement, CreateTableStatement,
+    CreateViewStatement, CreateWebhookSourceStatement, Expr, Ident, Query, Raw, RawItemName,
+    Statement, UnresolvedItemName, ViewDefinition,
 };
 use crate::names::FullItemName;
 
+/// A rewrite of the names in the `CREATE` statements of catalog items, which keeps the statements
+/// of the items affected by a DDL operation, e.g. a rename, in sync with the catalog.
+#[derive(Debug, Clone)]
+pub enum CreateSqlRewrite {
+    /// Renames the item `from` to `to_item`.
+    ///
+    /// If `rename_self` is set, the statement is the `CREATE` statement of the renamed item, whose
+    /// own name is changed as well. Otherwise, only the references to the item are changed.
+    RenameItem {
+        from: FullItemName,
+        to_item: String,
+        rename_self: bool,
+    },
+    /// Renames the schema `from` in `database` to `to`.
+    RenameSchema {
+        database: String,
+        from: String,
+        to: String,
+    },
+}
+
+impl CreateSqlRewrite {
+    /// Applies the rewrite to `create_stmt`, or errors with a description of the ambiguous
+    /// reference that prevents it.
+    pub fn apply(&self, create_stmt: &mut Statement<Raw>) -> Result<(), String> {
+        match self {
+            CreateSqlRewrite::RenameItem {
+                from,
+                to_item,
+                rename_self,
+            } => {
+                if *rename_self {
+                    create_stmt_rename(create_stmt, to_item.clone());
+                }
+                create_stmt_rename_refs(create_stmt, from.clone(), to_item.clone())
+            }
+            CreateSqlRewrite::RenameSchema { database, from, to } => {
+                create_stmt_rename_schema_refs(create_stmt, database, from, to).map_err(
+                    |(schema, _item)| format!("ambiguous reference to schema named {schema}"),
+                )
+            }
+        }
+    }
+}
+
+/// Parses the persisted `create_sql` of a catalog item, applies `rewrite` to it, and prints it
+/// back.
+pub fn rewrite_create_sql(create_sql: &str, rewrite: &CreateSqlRewrite) -> Result<String, String> {
+    let mut create_stmt = crate::parse::parse(create_sql)
+        .expect("invalid create sql persisted to catalog")
+        .into_element()
+        .ast;
+    rewrite.apply(&mut create_stmt)?;
+    Ok(create_stmt.to_ast_string_stable())
+}
+
 /// Given a [`Statement`] rewrites all references of the schema name `cur_schema_name` to
 /// `new_schema_name`.
-pub fn create_stmt_rename_schema_refs(
+fn create_stmt_rename_schema_refs(
     create_stmt: &mut Statement<Raw>,
     database: &str,
     cur_schema: &str,
@@ -42,9 +101,11 @@ pub fn create_stmt_rename_schema_refs(
         | stmt @ Statement::CreateSink(_)
         | stmt @ Statement::CreateView(_)
         | stmt @ Statement::CreateMaterializedView(_)
+        | stmt @ Statement::CreateContinualTask(_)
         | stmt @ Statement::CreateTable(_)
         | stmt @ Statement::CreateIndex(_)
         | stmt @ Statement::CreateType(_)
+        | stmt @ Statement::CreateFunction(_)
         | stmt @ Statement::CreateSecret(_) => {
             let mut visitor = CreateSqlRewriteSchema {
                 database,
@@ -130,7 +191,7 @@ impl<'a, 'ast> VisitMut<'ast, Raw> for CreateSqlRewriteSchema<'a> {
 /// Changes the `name` used in an item's `CREATE` statement. To complete a
 /// rename operation, you must also call `create_stmt_rename_refs` on all dependent
 /// items.
-pub fn create_stmt_rename(create_stmt: &mut Statement<Raw>, to_item_name: String) {
+fn create_stmt_rename(create_stmt: &mut Statement<Raw>, to_item_name: String) {
     // TODO(sploiselle): Support renaming schemas and databases.
     match create_stmt {
         Statement::CreateIndex(CreateIndexStatement { name, .. }) => {
@@ -172,7 +233,7 @@ pub fn create_stmt_rename(create_stmt: &mut Statement<Raw>, to_item_name: String
 /// - `to_name.item` does not unambiguously refer to an item in the query after
 ///   the rename. Right now, given the first condition, this is just a coherence
 ///   check, but will be more meaningful once the first restriction is lifted.
-pub fn create_stmt_rename_refs(
+fn create_stmt_rename_refs(
     create_stmt: &mut Statement<Raw>,
     from_name: FullItemName,
     to_item_name: String,
@@ -200,7 +261,12 @@ pub fn create_stmt_rename_refs(
             definition: ViewDefinition { query, .. },
             ..
         })
-        | Statement::CreateMaterializedView(CreateMaterializedViewStatement { query, .. }) => {
+        | Statement::CreateMaterializedView(CreateMaterializedViewStatement { query, .. })
+        | Statement::CreateFunction(CreateFunctionStatement { body: query, .. }) => {
+            rewrite_query(from_name, to_item_name, query)?;
+        }
+        Statement::CreateContinualTask(CreateContinualTaskStatement { target, query, .. }) => {
+            maybe_update_item_name(target.name_mut());
             rewrite_query(from_name, to_item_name, query)?;
         }
         Statement::CreateSource(_)
@@ -487,3 +553,66 @@ impl<'ast> VisitMut<'ast, Raw> for CreateSqlIdReplacer<'_> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::names::{FullItemName, RawDatabaseSpecifier};
+
+    use super::{rewrite_create_sql, CreateSqlRewrite};
+
+    fn full_name(schema: &str, item: &str) -> FullItemName {
+        FullItemName {
+            database: RawDatabaseSpecifier::Name("materialize".into()),
+            schema: schema.into(),
+            item: item.into(),
+        }
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // too slow
+    fn test_rewrite_create_sql() {
+        let rename_item = |rename_self| CreateSqlRewrite::RenameItem {
+            from: full_name("public", "t"),
+            to_item: "u".into(),
+            rename_self,
+        };
+        assert_eq!(
+            rewrite_create_sql(
+                "CREATE VIEW materialize.public.v AS SELECT a FROM materialize.public.t",
+                &rename_item(false),
+            ),
+            Ok("CREATE VIEW \"materialize\".\"public\".\"v\" AS \
+                SELECT \"a\" FROM \"materialize\".\"public\".\"u\""
+                .into()),
+        );
+        assert_eq!(
+            rewrite_create_sql(
+                "CREATE TABLE materialize.public.t (a int)",
+                &rename_item(true),
+            ),
+            Ok("CREATE TABLE \"materialize\".\"public\".\"u\" (\"a\" \"int\")".into()),
+        );
+        // The new name of the item is already used as a column.
+        assert!(rewrite_create_sql(
+            "CREATE VIEW materialize.public.v AS SELECT u FROM materialize.public.t",
+            &rename_item(false),
+        )
+        .is_err());
+
+        let rename_schema = CreateSqlRewrite::RenameSchema {
+            database: "materialize".into(),
+            from: "public".into(),
+            to: "private".into(),
+        };
+        assert_eq!(
+            rewrite_create_sql(
+                "CREATE INDEX i IN CLUSTER c ON materialize.public.t (a)",
+                &rename_schema,
+            ),
+            Ok(
+                "CREATE INDEX \"i\" IN CLUSTER \"c\" ON \"materialize\".\"private\".\"t\" (\"a\")"
+                    .into()
+            ),
+        );
+    }
+}
@@ -438,6 +438,9 @@ pub trait CatalogSchema {
 pub struct RoleAttributes {
     /// Indicates whether the role has inheritance of privileges.
     pub inherit: bool,
+    /// The SCRAM-SHA-256 verifier for the role's password, if the role has a
+    /// password.
+    pub password: Option<String>,
     // Force use of constructor.
     _private: (),
 }
@@ -447,6 +450,7 @@ impl RoleAttributes {
     pub const fn new() -> RoleAttributes {
         RoleAttributes {
             inherit: true,
+            password: None,
             _private: (),
         }
     }
@@ -464,10 +468,11 @@ impl RoleAttributes {
 }
 
 impl From<PlannedRoleAttributes> for RoleAttributes {
-    fn from(PlannedRoleAttributes { inherit }: PlannedRoleAttributes) -> RoleAttributes {
+    fn from(PlannedRoleAttributes { inherit, password }: PlannedRoleAttributes) -> RoleAttributes {
         let default_attributes = RoleAttributes::new();
         RoleAttributes {
             inherit: inherit.unwrap_or(default_attributes.inherit),
+            password: password.unwrap_or(default_attributes.password),
             _private: (),
         }
     }
@@ -577,7 +582,7 @@ pub trait CatalogItem {
     ///
     /// If the catalog item is not of a type that produces functions (i.e.,
     /// anything other than a function), it returns an error.
-    fn func(&self) -> Result<&'static Func, CatalogError>;
+    fn func(&self) -> Result<&Func, CatalogError>;
 
     /// Returns the resolved source connection.
     ///
@@ -668,6 +673,8 @@ pub enum CatalogItemType {
     Secret,
     /// A connection.
     Connection,
+    /// A continual task.
+    ContinualTask,
 }
 
 impl CatalogItemType {
@@ -701,6 +708,7 @@ impl CatalogItemType {
             CatalogItemType::Func => false,
             CatalogItemType::Secret => false,
             CatalogItemType::Connection => false,
+            CatalogItemType::ContinualTask => false,
         }
     }
 }
@@ -718,6 +726,7 @@ impl fmt::Display for CatalogItemType {
             CatalogItemType::Func => f.write_str("func"),
             CatalogItemType::Secret => f.write_str("secret"),
             CatalogItemType::Connection => f.write_str("connection"),
+            CatalogItemType::ContinualTask => f.write_str("continual task"),
         }
     }
 }
@@ -735,6 +744,7 @@ impl From<CatalogItemType> for ObjectType {
             CatalogItemType::Func => ObjectType::Func,
             CatalogItemType::Secret => ObjectType::Secret,
             CatalogItemType::Connection => ObjectType::Connection,
+            CatalogItemType::ContinualTask => ObjectType::ContinualTask,
         }
     }
 }
@@ -1199,6 +1209,20 @@ pub enum CatalogError {
     ConfigAlreadyExists(String),
     /// Builtin migrations failed.
     FailedBuiltinSchemaMigration(String),
+    /// Creating an object, or transferring its ownership, would exceed a quota.
+    QuotaExceeded {
+        /// The quota that would be exceeded.
+        kind: QuotaKind,
+        /// Whether the quota would be exceeded by transferring the ownership of an existing
+        /// object rather than by creating a new one.
+        ownership_transfer: bool,
+        /// A description of the role or database that the quota applies to.
+        subject: String,
+        /// The limit of the quota.
+        limit: u64,
+        /// The current usage of the quota.
+        current: u64,
+    },
 }
 
 impl fmt::Display for CatalogError {
@@ -1245,6 +1269,21 @@ impl fmt::Display for CatalogError {
             Self::IdAllocatorAlreadyExists(name) => write!(f, "ID allocator '{name}' already exists"),
             Self::ConfigAlreadyExists(key) => write!(f, "config '{key}' already exists"),
             Self::FailedBuiltinSchemaMigration(objects) => write!(f, "failed to migrate schema of builtin objects: {objects}"),
+            Self::QuotaExceeded { kind, ownership_transfer, subject, limit, current } => {
+                let object = match kind {
+                    QuotaKind::MaxClusters => "cluster",
+                    QuotaKind::MaxObjects | QuotaKind::MaxStorageBytes => "object",
+                };
+                let action = if *ownership_transfer {
+                    "transferring ownership of"
+                } else {
+                    "creating"
+                };
+                write!(
+                    f,
+                    "{action} {object} would exceed the {kind} quota of {subject} (limit: {limit}, current: {current})"
+                )
+            }
         }
     }
 }
@@ -1351,6 +1390,7 @@ pub enum ObjectType {
     Database,
     Schema,
     Func,
+    ContinualTask,
 }
 
 impl ObjectType {
@@ -1367,6 +1407,7 @@ impl ObjectType {
             | ObjectType::Secret
             | ObjectType::Connection
             | ObjectType::Func
+            | ObjectType::ContinualTask
             | ObjectType::Database
             | ObjectType::Schema
             | ObjectType::Cluster
@@ -1395,6 +1436,7 @@ impl From<mz_sql_parser::ast::ObjectType> for ObjectType {
             mz_sql_parser::ast::ObjectType::Database => ObjectType::Database,
             mz_sql_parser::ast::ObjectType::Schema => ObjectType::Schema,
             mz_sql_parser::ast::ObjectType::Func => ObjectType::Func,
+            mz_sql_parser::ast::ObjectType::ContinualTask => ObjectType::ContinualTask,
         }
     }
 }
@@ -1417,6 +1459,7 @@ impl From<CommentObjectId> for ObjectType {
             CommentObjectId::Schema(_) => ObjectType::Schema,
             CommentObjectId::Cluster(_) => ObjectType::Cluster,
             CommentObjectId::ClusterReplica(_) => ObjectType::ClusterReplica,
+            CommentObjectId::ContinualTask(_) => ObjectType::ContinualTask,
         }
     }
 }
@@ -1439,6 +1482,7 @@ impl Display for ObjectType {
             ObjectType::Database => "DATABASE",
             ObjectType::Schema => "SCHEMA",
             ObjectType::Func => "FUNCTION",
+            ObjectType::ContinualTask => "CONTINUAL TASK",
         })
     }
 }
@@ -1604,6 +1648,83 @@ impl TryFrom<BTreeMap<String, RoleId>> for RoleMembership {
     }
 }
 
+/// The role or database that a quota applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub enum QuotaSubject {
+    /// The quota limits the resources owned by a role.
+    Role(RoleId),
+    /// The quota limits the resources contained in a database.
+    Database(DatabaseId),
+}
+
+impl QuotaSubject {
+    /// Returns the type of the subject, as reported in `mz_quotas`.
+    pub fn subject_type(&self) -> &'static str {
+        match self {
+            QuotaSubject::Role(_) => "role",
+            QuotaSubject::Database(_) => "database",
+        }
+    }
+}
+
+impl fmt::Display for QuotaSubject {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            QuotaSubject::Role(id) => id.fmt(f),
+            QuotaSubject::Database(id) => id.fmt(f),
+        }
+    }
+}
+
+/// A resource that can be limited by a quota.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub enum QuotaKind {
+    /// The number of catalog items.
+    MaxObjects,
+    /// The number of clusters. Only applies to roles.
+    MaxClusters,
+    /// The number of bytes stored durably.
+    MaxStorageBytes,
+}
+
+impl QuotaKind {
+    /// Returns the name of the quota, as used in `ALTER QUOTA`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuotaKind::MaxObjects => "max_objects",
+            QuotaKind::MaxClusters => "max_clusters",
+            QuotaKind::MaxStorageBytes => "max_storage_bytes",
+        }
+    }
+
+    /// Reports whether the quota can be assigned to `subject`.
+    pub fn applies_to(&self, subject: &QuotaSubject) -> bool {
+        match (self, subject) {
+            (QuotaKind::MaxClusters, QuotaSubject::Database(_)) => false,
+            _ => true,
+        }
+    }
+}
+
+impl fmt::Display for QuotaKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for QuotaKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "max_objects" => Ok(QuotaKind::MaxObjects),
+            "max_clusters" => Ok(QuotaKind::MaxClusters),
+            "max_storage_bytes" => Ok(QuotaKind::MaxStorageBytes),
+            _ => Err(format!("unknown quota {}", s.quoted())),
+        }
+    }
+}
+
 /// Specification for objects that will be affected by a default privilege.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
 pub struct DefaultPrivilegeObject {
@@ -20,10 +20,12 @@ use mz_ore::collections::CollectionExt;
 use mz_ore::str::StrExt;
 use mz_pgrepr::oid;
 use mz_repr::role_id::RoleId;
-use mz_repr::{ColumnName, ColumnType, Datum, RelationType, Row, ScalarBaseType, ScalarType};
+use mz_repr::{
+    ColumnName, ColumnType, Datum, RelationDesc, RelationType, Row, ScalarBaseType, ScalarType,
+};
 use once_cell::sync::Lazy;
 
-use crate::ast::{SelectStatement, Statement};
+use crate::ast::{Query, Raw, SelectStatement, Statement};
 use crate::catalog::{CatalogType, TypeCategory, TypeReference};
 use crate::names::{self, ResolvedItemName};
 use crate::plan::error::PlanError;
@@ -476,6 +478,42 @@ fn experimental_sql_impl_table_func(
     sql_impl_table_func_inner(sql, Some(feature))
 }
 
+/// Constructs the table function defined by `CREATE FUNCTION`, which takes
+/// parameters of the types in `params` and returns the rows of `body`.
+///
+/// Like the bodies of builtin functions implemented in SQL, the body is
+/// planned whenever the function is called.
+pub fn user_table_func(
+    params: Vec<(Option<ColumnName>, ScalarType)>,
+    returns: RelationDesc,
+    body: Query<Raw>,
+) -> Func {
+    let param_types = params
+        .iter()
+        .map(|(_, typ)| ParamType::Plain(typ.clone()))
+        .collect();
+    let op = Operation::variadic(move |ecx, args| {
+        let mut expr = query::plan_function_body(
+            ecx.qcx.scx,
+            ecx.qcx.lifetime,
+            &params,
+            &returns,
+            body.clone(),
+        )?;
+        expr.splice_parameters(&args, 0);
+        Ok(TableFuncPlan {
+            expr,
+            column_names: returns.iter_names().cloned().collect(),
+        })
+    });
+    Func::Table(vec![FuncImpl {
+        oid: oid::INVALID_OID,
+        params: ParamList::Exact(param_types),
+        return_type: ReturnType::set_of(ParamType::RecordAny),
+        op,
+    }])
+}
+
 /// Describes a single function's implementation.
 pub struct FuncImpl<R> {
     pub oid: u32,
@@ -2498,6 +2536,9 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
         "radians" => Scalar {
             params!(Float64) => UnaryFunc::Radians(func::Radians) => Float64, 1609;
         },
+        "range_merge" => Scalar {
+            params!(RangeAny, RangeAny) => BinaryFunc::RangeMerge => RangeAny, 4057;
+        },
         "repeat" => Scalar {
             params!(String, Int32) => BinaryFunc::RepeatString => String, 1622;
         },
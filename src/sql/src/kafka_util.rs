@@ -9,18 +9,20 @@
 
 //! Provides parsing and convenience functions for working with Kafka from the `sql` package.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use mz_kafka_util::client::DEFAULT_TOPIC_METADATA_REFRESH_INTERVAL;
 use mz_ore::task;
+use mz_repr::strconv;
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::{
-    KafkaSinkConfigOption, KafkaSinkConfigOptionName, KafkaSourceConfigOption,
-    KafkaSourceConfigOptionName,
+    Ident, KafkaSinkConfigOption, KafkaSinkConfigOptionName, KafkaSourceConfigOption,
+    KafkaSourceConfigOptionName, UnresolvedItemName,
 };
-use mz_storage_types::sinks::KafkaSinkCompressionType;
+use mz_storage_types::sinks::{AvroSchemaEvolution, KafkaSinkCompressionType};
 use rdkafka::consumer::{BaseConsumer, Consumer, ConsumerContext};
-use rdkafka::{Offset, TopicPartitionList};
+use rdkafka::{Message, Offset, TopicPartitionList};
 use tokio::time::Duration;
 
 use crate::ast::Value;
@@ -38,7 +40,7 @@ generate_extracted_config!(
         Duration,
         Default(DEFAULT_TOPIC_METADATA_REFRESH_INTERVAL)
     ),
-    (StartTimestamp, i64),
+    (StartTimestamp, KafkaStartTimestamp),
     (StartOffset, Vec<i64>)
 );
 
@@ -51,6 +53,13 @@ generate_extracted_config!(
     ),
     (ProgressGroupIdPrefix, String),
     (Topic, String),
+    (
+        TopicConfig,
+        BTreeMap<String, String>,
+        Default(BTreeMap::new())
+    ),
+    (TopicPartitionCount, i32),
+    (TopicReplicationFactor, i32),
     (TransactionalIdPrefix, String),
     (LegacyIds, bool)
 );
@@ -83,6 +92,72 @@ impl ImpliedValue for KafkaSinkCompressionType {
     }
 }
 
+impl TryFromValue<Value> for AvroSchemaEvolution {
+    fn try_from_value(v: Value) -> Result<Self, PlanError> {
+        match v {
+            Value::String(v) => match v.to_lowercase().as_str() {
+                "backward" => Ok(AvroSchemaEvolution::Backward),
+                "full" => Ok(AvroSchemaEvolution::Full),
+                "none" => Ok(AvroSchemaEvolution::None),
+                // The caller will add context, resulting in an error like
+                // "invalid VALUE SCHEMA EVOLUTION: <bad-evolution>".
+                _ => sql_bail!("{}", v),
+            },
+            _ => sql_bail!("schema evolution must be a string"),
+        }
+    }
+
+    fn name() -> String {
+        "Avro schema evolution".to_string()
+    }
+}
+
+impl ImpliedValue for AvroSchemaEvolution {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide a schema evolution value")
+    }
+}
+
+/// The value of a `START TIMESTAMP` option, in milliseconds since the Unix
+/// epoch or, if negative, relative to the current time.
+///
+/// The option accepts either a number of milliseconds or a timestamp string
+/// like `'2024-01-01T00:00:00Z'`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KafkaStartTimestamp(pub i64);
+
+impl TryFromValue<Value> for KafkaStartTimestamp {
+    fn try_from_value(v: Value) -> Result<Self, PlanError> {
+        match v {
+            Value::Number(_) => Ok(KafkaStartTimestamp(i64::try_from_value(v)?)),
+            Value::String(v) => {
+                let ts = strconv::parse_timestamptz(&v)?;
+                Ok(KafkaStartTimestamp(ts.timestamp_millis()))
+            }
+            _ => sql_bail!("cannot use value as timestamp"),
+        }
+    }
+
+    fn name() -> String {
+        "Kafka start timestamp".to_string()
+    }
+}
+
+impl ImpliedValue for KafkaStartTimestamp {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide a timestamp value")
+    }
+}
+
+/// Returns the external reference under which a Kafka source created with
+/// `ERRORS = DLQ` exposes its dead-letter queue to the subsource machinery.
+pub fn dead_letter_reference() -> UnresolvedItemName {
+    UnresolvedItemName(vec![
+        Ident::new_unchecked("kafka"),
+        Ident::new_unchecked("dlq"),
+    ])
+}
+
 /// Returns start offsets for the partitions of `topic` and the provided
 /// `START TIMESTAMP` option.
 ///
@@ -95,6 +170,9 @@ impl ImpliedValue for KafkaSinkCompressionType {
 /// * Non-negative numbers will used as is (e.g. `1622659034343`)
 /// * Negative numbers will be translated to a timestamp in millis
 ///   before now (e.g. `-10` means 10 millis ago)
+///
+/// Timestamp strings are converted to the former when the option is
+/// extracted.
 pub async fn lookup_start_offsets<C>(
     consumer: Arc<BaseConsumer<C>>,
     topic: &str,
@@ -225,6 +303,60 @@ where
     .map_err(|e| sql_err!("{}", e))?
 }
 
+/// Returns the key and value of the earliest retained message in the first
+/// non-empty partition of `topic`, or `None` if the topic contains no
+/// messages.
+pub async fn fetch_first_message<C>(
+    consumer: Arc<BaseConsumer<C>>,
+    topic: &str,
+    fetch_metadata_timeout: Duration,
+) -> Result<Option<(Option<Vec<u8>>, Option<Vec<u8>>)>, PlanError>
+where
+    C: ConsumerContext + 'static,
+{
+    task::spawn_blocking(|| format!("kafka_fetch_first_message:{topic}"), {
+        let topic = topic.to_string();
+        move || {
+            let partitions = mz_kafka_util::client::get_partitions(
+                consumer.as_ref().client(),
+                &topic,
+                fetch_metadata_timeout,
+            )
+            .map_err(|e| sql_err!("{}", e))?;
+
+            for pid in partitions {
+                let (low, high) = consumer
+                    .fetch_watermarks(&topic, pid, Duration::from_secs(10))
+                    .map_err(|e| sql_err!("{}", e))?;
+                if low >= high {
+                    continue;
+                }
+
+                let mut tpl = TopicPartitionList::with_capacity(1);
+                tpl.add_partition_offset(&topic, pid, Offset::Offset(low))
+                    .map_err(|e| sql_err!("{}", e))?;
+                consumer.assign(&tpl).map_err(|e| sql_err!("{}", e))?;
+
+                return match consumer.poll(Duration::from_secs(10)) {
+                    Some(Ok(message)) => Ok(Some((
+                        message.key().map(|key| key.to_vec()),
+                        message.payload().map(|payload| payload.to_vec()),
+                    ))),
+                    Some(Err(e)) => sql_bail!("{}", e),
+                    None => sql_bail!(
+                        "timed out fetching the first message of partition {} of topic {}",
+                        pid,
+                        topic
+                    ),
+                };
+            }
+            Ok(None)
+        }
+    })
+    .await
+    .map_err(|e| sql_err!("{}", e))?
+}
+
 /// Validates that we can connect to the broker and obtain metadata about the topic.
 pub async fn ensure_topic_exists<C>(
     consumer: Arc<BaseConsumer<C>>,
@@ -1119,7 +1119,8 @@ impl From<CommentObjectId> for ObjectId {
             | CommentObjectId::Func(global_id)
             | CommentObjectId::Connection(global_id)
             | CommentObjectId::Type(global_id)
-            | CommentObjectId::Secret(global_id) => ObjectId::Item(global_id),
+            | CommentObjectId::Secret(global_id)
+            | CommentObjectId::ContinualTask(global_id) => ObjectId::Item(global_id),
             CommentObjectId::Role(id) => ObjectId::Role(id),
             CommentObjectId::Database(id) => ObjectId::Database(id),
             CommentObjectId::Schema(id) => ObjectId::Schema(id),
@@ -1172,6 +1173,7 @@ pub enum CommentObjectId {
     Connection(GlobalId),
     Type(GlobalId),
     Secret(GlobalId),
+    ContinualTask(GlobalId),
     Role(RoleId),
     Database(DatabaseId),
     Schema((ResolvedDatabaseSpecifier, SchemaSpecifier)),
@@ -1796,6 +1798,9 @@ impl<'a> Fold<Raw, Aug> for NameResolver<'a> {
                     .map(|r| self.fold_replica_definition(r))
                     .collect(),
             ),
+            ClusterSchedule(schedule) => {
+                ClusterSchedule(self.fold_cluster_schedule_option_value(schedule))
+            }
             ConnectionKafkaBroker(broker) => ConnectionKafkaBroker(self.fold_kafka_broker(broker)),
             ConnectionAwsPrivatelink(privatelink) => {
                 ConnectionAwsPrivatelink(self.fold_connection_default_aws_privatelink(privatelink))
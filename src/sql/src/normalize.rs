@@ -21,11 +21,12 @@ use mz_repr::{ColumnName, GlobalId};
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::visit_mut::{self, VisitMut};
 use mz_sql_parser::ast::{
-    CreateConnectionStatement, CreateIndexStatement, CreateMaterializedViewStatement,
-    CreateSecretStatement, CreateSinkStatement, CreateSourceStatement, CreateSubsourceStatement,
-    CreateTableStatement, CreateTypeStatement, CreateViewStatement, CreateWebhookSourceStatement,
-    CteBlock, Function, FunctionArgs, Ident, IfExistsBehavior, MutRecBlock, Op, Query, Statement,
-    TableFactor, UnresolvedItemName, UnresolvedSchemaName, Value, ViewDefinition,
+    CreateConnectionStatement, CreateContinualTaskStatement, CreateFunctionStatement,
+    CreateIndexStatement, CreateMaterializedViewStatement, CreateSecretStatement,
+    CreateSinkStatement, CreateSourceStatement, CreateSubsourceStatement, CreateTableStatement,
+    CreateTypeStatement, CreateViewStatement, CreateWebhookSourceStatement, CteBlock, Function,
+    FunctionArgs, Ident, IfExistsBehavior, MutRecBlock, Op, Query, Statement, TableFactor,
+    UnresolvedItemName, UnresolvedSchemaName, Value, ViewDefinition,
 };
 
 use crate::names::{Aug, FullItemName, PartialItemName, PartialSchemaName, RawDatabaseSpecifier};
@@ -301,6 +302,7 @@ pub fn create_statement(
             constraints: _,
             if_not_exists,
             temporary,
+            on_commit: _,
             with_options: _,
         }) => {
             *name = if *temporary {
@@ -324,6 +326,7 @@ pub fn create_statement(
             include_headers: _,
             body_format: _,
             validate_using: _,
+            deduplicate: _,
             in_cluster: _,
         }) => {
             *name = allocate_name(name)?;
@@ -390,6 +393,25 @@ pub fn create_statement(
             *if_exists = IfExistsBehavior::Error;
         }
 
+        Statement::CreateContinualTask(CreateContinualTaskStatement {
+            if_not_exists,
+            name,
+            in_cluster: _,
+            target: _,
+            columns: _,
+            query,
+        }) => {
+            *name = allocate_name(name)?;
+            {
+                let mut normalizer = QueryNormalizer::new();
+                normalizer.visit_query_mut(query);
+                if let Some(err) = normalizer.err {
+                    return Err(err);
+                }
+            }
+            *if_not_exists = false;
+        }
+
         Statement::CreateIndex(CreateIndexStatement {
             name: _,
             in_cluster: _,
@@ -418,10 +440,24 @@ pub fn create_statement(
                 return Err(err);
             }
         }
+        Statement::CreateFunction(CreateFunctionStatement {
+            name,
+            params: _,
+            returns: _,
+            body,
+        }) => {
+            *name = allocate_name(name)?;
+            let mut normalizer = QueryNormalizer::new();
+            normalizer.visit_query_mut(body);
+            if let Some(err) = normalizer.err {
+                return Err(err);
+            }
+        }
         Statement::CreateSecret(CreateSecretStatement {
             name,
             if_not_exists,
             value: _,
+            external: _,
         }) => {
             *name = allocate_name(name)?;
             *if_not_exists = false;
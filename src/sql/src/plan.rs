@@ -60,13 +60,14 @@ use crate::ast::{
 };
 use crate::catalog::{
     CatalogType, DefaultPrivilegeAclItem, DefaultPrivilegeObject, IdReference, ObjectType,
-    RoleAttributes,
+    QuotaKind, QuotaSubject, RoleAttributes,
 };
 use crate::names::{
     Aug, CommentObjectId, FullItemName, ObjectId, QualifiedItemName, ResolvedDatabaseSpecifier,
     ResolvedIds, SchemaSpecifier, SystemObjectId,
 };
 
+pub(crate) mod cron;
 pub(crate) mod error;
 pub(crate) mod explain;
 pub(crate) mod expr;
@@ -83,8 +84,10 @@ pub(crate) mod transform_expr;
 pub(crate) mod typeconv;
 pub(crate) mod with_options;
 
+use crate::func::Func;
 use crate::plan;
 use crate::plan::with_options::OptionalDuration;
+pub use cron::CronSchedule;
 pub use error::PlanError;
 pub use explain::normalize_subqueries;
 pub use expr::{
@@ -119,10 +122,13 @@ pub enum Plan {
     CreateTable(CreateTablePlan),
     CreateView(CreateViewPlan),
     CreateMaterializedView(CreateMaterializedViewPlan),
+    CreateContinualTask(CreateContinualTaskPlan),
     CreateIndex(CreateIndexPlan),
     CreateType(CreateTypePlan),
+    CreateFunction(CreateFunctionPlan),
     Comment(CommentPlan),
     DiscardTemp,
+    DiscardPlans,
     DiscardAll,
     DropObjects(DropObjectsPlan),
     DropOwned(DropOwnedPlan),
@@ -155,6 +161,7 @@ pub enum Plan {
     AlterSetCluster(AlterSetClusterPlan),
     AlterConnection(AlterConnectionPlan),
     AlterSource(AlterSourcePlan),
+    AlterSinkResetOffset(AlterSinkResetOffsetPlan),
     PurifiedAlterSource {
         // The `ALTER SOURCE` plan
         alter_source: AlterSourcePlan,
@@ -163,6 +170,7 @@ pub enum Plan {
     },
     AlterClusterRename(AlterClusterRenamePlan),
     AlterClusterReplicaRename(AlterClusterReplicaRenamePlan),
+    AlterClusterReplica(AlterClusterReplicaPlan),
     AlterItemRename(AlterItemRenamePlan),
     AlterItemSwap(AlterItemSwapPlan),
     AlterSchemaRename(AlterSchemaRenamePlan),
@@ -172,6 +180,7 @@ pub enum Plan {
     AlterSystemReset(AlterSystemResetPlan),
     AlterSystemResetAll(AlterSystemResetAllPlan),
     AlterRole(AlterRolePlan),
+    AlterQuota(AlterQuotaPlan),
     AlterOwner(AlterOwnerPlan),
     Declare(DeclarePlan),
     Fetch(FetchPlan),
@@ -197,6 +206,9 @@ impl Plan {
     pub fn generated_from(stmt: &StatementKind) -> &'static [PlanKind] {
         match stmt {
             StatementKind::AlterCluster => &[PlanKind::AlterNoop, PlanKind::AlterCluster],
+            StatementKind::AlterClusterReplica => {
+                &[PlanKind::AlterNoop, PlanKind::AlterClusterReplica]
+            }
             StatementKind::AlterConnection => &[PlanKind::AlterNoop, PlanKind::AlterConnection],
             StatementKind::AlterDefaultPrivileges => &[PlanKind::AlterDefaultPrivileges],
             StatementKind::AlterIndex => &[
@@ -218,10 +230,10 @@ impl Plan {
                 PlanKind::AlterNoop,
             ],
             StatementKind::AlterRole => &[PlanKind::AlterRole],
+            StatementKind::AlterQuota => &[PlanKind::AlterQuota],
             StatementKind::AlterSecret => &[PlanKind::AlterNoop, PlanKind::AlterSecret],
             StatementKind::AlterSetCluster => &[PlanKind::AlterNoop, PlanKind::AlterSetCluster],
-            // TODO: If we ever support ALTER SINK again, this will need to be changed
-            StatementKind::AlterSink => &[PlanKind::AlterNoop],
+            StatementKind::AlterSink => &[PlanKind::AlterNoop, PlanKind::AlterSinkResetOffset],
             StatementKind::AlterSource => &[PlanKind::AlterNoop, PlanKind::AlterSource],
             StatementKind::AlterSystemReset => &[PlanKind::AlterNoop, PlanKind::AlterSystemReset],
             StatementKind::AlterSystemResetAll => {
@@ -244,6 +256,7 @@ impl Plan {
             StatementKind::CreateDatabase => &[PlanKind::CreateDatabase],
             StatementKind::CreateIndex => &[PlanKind::CreateIndex],
             StatementKind::CreateMaterializedView => &[PlanKind::CreateMaterializedView],
+            StatementKind::CreateContinualTask => &[PlanKind::CreateContinualTask],
             StatementKind::CreateRole => &[PlanKind::CreateRole],
             StatementKind::CreateSchema => &[PlanKind::CreateSchema],
             StatementKind::CreateSecret => &[PlanKind::CreateSecret],
@@ -253,12 +266,17 @@ impl Plan {
             | StatementKind::CreateWebhookSource => &[PlanKind::CreateSource],
             StatementKind::CreateTable => &[PlanKind::CreateTable],
             StatementKind::CreateType => &[PlanKind::CreateType],
+            StatementKind::CreateFunction => &[PlanKind::CreateFunction],
             StatementKind::CreateView => &[PlanKind::CreateView],
             StatementKind::Deallocate => &[PlanKind::Deallocate],
             StatementKind::Declare => &[PlanKind::Declare],
             StatementKind::Delete => &[PlanKind::ReadThenWrite],
-            StatementKind::Discard => &[PlanKind::DiscardAll, PlanKind::DiscardTemp],
-            StatementKind::DropObjects => &[PlanKind::DropObjects],
+            StatementKind::Discard => &[
+                PlanKind::DiscardAll,
+                PlanKind::DiscardPlans,
+                PlanKind::DiscardTemp,
+            ],
+            StatementKind::DropObjects => &[PlanKind::DropObjects, PlanKind::Select],
             StatementKind::DropOwned => &[PlanKind::DropOwned],
             StatementKind::Execute => &[PlanKind::Execute],
             StatementKind::ExplainPlan => &[PlanKind::ExplainPlan],
@@ -310,10 +328,13 @@ impl Plan {
             Plan::CreateTable(_) => "create table",
             Plan::CreateView(_) => "create view",
             Plan::CreateMaterializedView(_) => "create materialized view",
+            Plan::CreateContinualTask(_) => "create continual task",
             Plan::CreateIndex(_) => "create index",
             Plan::CreateType(_) => "create type",
+            Plan::CreateFunction(_) => "create function",
             Plan::Comment(_) => "comment",
             Plan::DiscardTemp => "discard temp",
+            Plan::DiscardPlans => "discard plans",
             Plan::DiscardAll => "discard all",
             Plan::DropObjects(plan) => match plan.object_type {
                 ObjectType::Table => "drop table",
@@ -331,6 +352,7 @@ impl Plan {
                 ObjectType::Database => "drop database",
                 ObjectType::Schema => "drop schema",
                 ObjectType::Func => "drop function",
+                ObjectType::ContinualTask => "drop continual task",
             },
             Plan::DropOwned(_) => "drop owned",
             Plan::EmptyQuery => "do nothing",
@@ -370,16 +392,19 @@ impl Plan {
                 ObjectType::Database => "alter database",
                 ObjectType::Schema => "alter schema",
                 ObjectType::Func => "alter function",
+                ObjectType::ContinualTask => "alter continual task",
             },
             Plan::AlterCluster(_) => "alter cluster",
             Plan::AlterClusterRename(_) => "alter cluster rename",
             Plan::AlterClusterSwap(_) => "alter cluster swap",
             Plan::AlterClusterReplicaRename(_) => "alter cluster replica rename",
+            Plan::AlterClusterReplica(_) => "alter cluster replica",
             Plan::AlterSetCluster(_) => "alter set cluster",
             Plan::AlterIndexSetOptions(_) => "alter index",
             Plan::AlterIndexResetOptions(_) => "alter index",
             Plan::AlterConnection(_) => "alter connection",
             Plan::AlterSource(_) | Plan::PurifiedAlterSource { .. } => "alter source",
+            Plan::AlterSinkResetOffset(_) => "alter sink",
             Plan::AlterItemRename(_) => "rename item",
             Plan::AlterItemSwap(_) => "swap item",
             Plan::AlterSchemaRename(_) => "alter rename schema",
@@ -389,6 +414,7 @@ impl Plan {
             Plan::AlterSystemReset(_) => "alter system",
             Plan::AlterSystemResetAll(_) => "alter system",
             Plan::AlterRole(_) => "alter role",
+            Plan::AlterQuota(_) => "alter quota",
             Plan::AlterOwner(plan) => match plan.object_type {
                 ObjectType::Table => "alter table owner",
                 ObjectType::View => "alter view owner",
@@ -405,6 +431,7 @@ impl Plan {
                 ObjectType::Database => "alter database owner",
                 ObjectType::Schema => "alter schema owner",
                 ObjectType::Func => "alter function owner",
+                ObjectType::ContinualTask => "alter continual task owner",
             },
             Plan::Declare(_) => "declare",
             Plan::Fetch(_) => "fetch",
@@ -506,6 +533,41 @@ pub struct CreateClusterManagedPlan {
     pub compute: ComputeReplicaConfig,
     pub disk: bool,
     pub optimizer_feature_overrides: OptimizerFeatureOverrides,
+    pub schedule: ClusterSchedule,
+    pub max_concurrent_queries: Option<u32>,
+}
+
+/// The schedule of a managed cluster, which determines when the cluster's
+/// replicas are provisioned.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ClusterSchedule {
+    /// The cluster always has its configured number of replicas.
+    #[default]
+    Manual,
+    /// The cluster only has replicas during the minutes matched by the cron
+    /// schedule, and none otherwise.
+    Cron(CronSchedule),
+}
+
+impl ClusterSchedule {
+    /// Reports whether a cluster with this schedule should have replicas at
+    /// `now`.
+    pub fn is_on(&self, now: DateTime<Utc>) -> bool {
+        match self {
+            ClusterSchedule::Manual => true,
+            ClusterSchedule::Cron(cron) => cron.matches(now),
+        }
+    }
+
+    /// Returns the number of replicas a cluster with this schedule and the
+    /// given replication factor should have at `now`.
+    pub fn effective_replication_factor(&self, replication_factor: u32, now: DateTime<Utc>) -> u32 {
+        if self.is_on(now) {
+            replication_factor
+        } else {
+            0
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -633,6 +695,13 @@ pub struct CreateMaterializedViewPlan {
     pub ambiguous_columns: bool,
 }
 
+#[derive(Debug, Clone)]
+pub struct CreateContinualTaskPlan {
+    pub name: QualifiedItemName,
+    pub continual_task: ContinualTask,
+    pub if_not_exists: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct CreateIndexPlan {
     pub name: QualifiedItemName,
@@ -646,6 +715,12 @@ pub struct CreateTypePlan {
     pub typ: Type,
 }
 
+#[derive(Debug)]
+pub struct CreateFunctionPlan {
+    pub name: QualifiedItemName,
+    pub function: Function,
+}
+
 #[derive(Debug)]
 pub struct DropObjectsPlan {
     /// The IDs of only the objects directly referenced in the `DROP` statement.
@@ -739,7 +814,7 @@ pub struct SubscribePlan {
     pub output: SubscribeOutput,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum SubscribeFrom {
     Id(GlobalId),
     Query {
@@ -965,6 +1040,11 @@ pub struct AlterSetClusterPlan {
     pub set_cluster: ClusterId,
 }
 
+#[derive(Debug)]
+pub struct AlterSinkResetOffsetPlan {
+    pub id: GlobalId,
+}
+
 #[derive(Debug)]
 pub struct AlterIndexSetOptionsPlan {
     pub id: GlobalId,
@@ -1041,6 +1121,15 @@ pub struct AlterClusterReplicaRenamePlan {
     pub to_name: String,
 }
 
+#[derive(Debug)]
+pub struct AlterClusterReplicaPlan {
+    pub cluster_id: ClusterId,
+    pub replica_id: ReplicaId,
+    pub name: QualifiedReplica,
+    pub introspection_debugging: AlterOptionParameter<bool>,
+    pub introspection_interval: AlterOptionParameter<OptionalDuration>,
+}
+
 #[derive(Debug)]
 pub struct AlterItemRenamePlan {
     pub id: GlobalId,
@@ -1086,6 +1175,8 @@ pub struct AlterItemSwapPlan {
 pub struct AlterSecretPlan {
     pub id: GlobalId,
     pub secret_as: MirScalarExpr,
+    /// Whether `secret_as` evaluates to a reference to an external secret.
+    pub external: bool,
 }
 
 #[derive(Debug)]
@@ -1109,6 +1200,14 @@ pub struct AlterRolePlan {
     pub option: PlannedAlterRoleOption,
 }
 
+#[derive(Debug)]
+pub struct AlterQuotaPlan {
+    pub subject: QuotaSubject,
+    pub kind: QuotaKind,
+    /// The new limit of the quota, or `None` to remove the quota.
+    pub limit: Option<u64>,
+}
+
 #[derive(Debug)]
 pub struct AlterOwnerPlan {
     pub id: ObjectId,
@@ -1119,6 +1218,8 @@ pub struct AlterOwnerPlan {
 #[derive(Debug)]
 pub struct DeclarePlan {
     pub name: String,
+    pub scroll: bool,
+    pub hold: bool,
     pub stmt: Statement<Raw>,
     pub sql: String,
     pub params: Params,
@@ -1243,6 +1344,9 @@ pub struct Table {
     pub desc: RelationDesc,
     pub defaults: Vec<Expr<Aug>>,
     pub temporary: bool,
+    /// Whether the rows of the table are deleted at the end of every
+    /// transaction, i.e. `ON COMMIT DELETE ROWS`.
+    pub delete_rows_on_commit: bool,
     pub compaction_window: Option<CompactionWindow>,
 }
 
@@ -1267,6 +1371,7 @@ pub enum DataSourceDesc {
         validate_using: Option<WebhookValidation>,
         body_format: WebhookBodyFormat,
         headers: WebhookHeaders,
+        deduplicate: Option<WebhookDeduplication>,
     },
 }
 
@@ -1327,6 +1432,22 @@ impl WebhookValidation {
     }
 }
 
+/// Describes how requests to a webhook source are deduplicated.
+#[derive(Clone, Debug, Serialize)]
+pub struct WebhookDeduplication {
+    /// Name of the header that contains the idempotency key of a request, in lowercase.
+    pub header_name: String,
+    /// How long the idempotency key of an appended request is remembered for.
+    pub window: Duration,
+}
+
+impl WebhookDeduplication {
+    /// The window used if none is specified.
+    pub const DEFAULT_WINDOW: Duration = Duration::from_secs(60 * 60);
+    /// The largest window that can be specified, which bounds how many keys we need to retain.
+    pub const MAX_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+}
+
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct WebhookHeaders {
     /// Optionally include a column named `headers` whose content is possibly filtered.
@@ -1388,6 +1509,8 @@ pub struct Connection {
 pub struct Secret {
     pub create_sql: String,
     pub secret_as: MirScalarExpr,
+    /// Whether `secret_as` evaluates to a reference to an external secret.
+    pub external: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -1417,6 +1540,20 @@ pub struct MaterializedView {
     pub compaction_window: Option<CompactionWindow>,
     pub refresh_schedule: Option<RefreshSchedule>,
     pub as_of: Option<Timestamp>,
+    /// The maximum number of bytes the arrangements of the materialized
+    /// view's dataflow may occupy, if limited.
+    pub memory_limit: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ContinualTask {
+    pub create_sql: String,
+    /// The query whose changes are inserted into the target table. Its output
+    /// has the columns of the target table, in order.
+    pub expr: HirRelationExpr,
+    /// The table that the task inserts into.
+    pub target_id: GlobalId,
+    pub cluster_id: ClusterId,
 }
 
 #[derive(Clone, Debug)]
@@ -1434,6 +1571,12 @@ pub struct Type {
     pub inner: CatalogType<IdReference>,
 }
 
+#[derive(Debug)]
+pub struct Function {
+    pub create_sql: String,
+    pub inner: Func,
+}
+
 /// Specifies when a `Peek` or `Subscribe` should occur.
 #[derive(Deserialize, Clone, Debug, PartialEq)]
 pub enum QueryWhen {
@@ -1517,6 +1660,7 @@ pub enum CopyFormat {
     Text,
     Csv,
     Binary,
+    Parquet,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -1549,6 +1693,8 @@ pub struct PlanClusterOption {
     pub replication_factor: AlterOptionParameter<u32>,
     pub size: AlterOptionParameter,
     pub disk: AlterOptionParameter<bool>,
+    pub schedule: AlterOptionParameter<ClusterSchedule>,
+    pub max_concurrent_queries: AlterOptionParameter<u32>,
 }
 
 impl Default for PlanClusterOption {
@@ -1563,6 +1709,8 @@ impl Default for PlanClusterOption {
             replication_factor: AlterOptionParameter::Unchanged,
             size: AlterOptionParameter::Unchanged,
             disk: AlterOptionParameter::Unchanged,
+            schedule: AlterOptionParameter::Unchanged,
+            max_concurrent_queries: AlterOptionParameter::Unchanged,
         }
     }
 }
@@ -0,0 +1,200 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Cron expressions, as used by cluster schedules.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A parsed five-field cron expression.
+///
+/// The fields are, in order: minute (0-59), hour (0-23), day of month (1-31),
+/// month (1-12), and day of week (0-7, where both 0 and 7 are Sunday). Each
+/// field is either `*` or a comma-separated list of values and ranges
+/// (`a-b`), each optionally followed by a step (`/n`). As in standard cron,
+/// if both the day of month and the day of week are restricted, a day matches
+/// if either of them matches. A field is restricted unless it starts with `*`,
+/// so `*/2` does not restrict the days it applies to.
+///
+/// All times are interpreted in UTC.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CronSchedule {
+    /// The expression this schedule was parsed from.
+    expr: String,
+    minutes: BTreeSet<u32>,
+    hours: BTreeSet<u32>,
+    days_of_month: BTreeSet<u32>,
+    months: BTreeSet<u32>,
+    days_of_week: BTreeSet<u32>,
+    /// Whether the day of month field does not start with `*`.
+    days_of_month_restricted: bool,
+    /// Whether the day of week field does not start with `*`.
+    days_of_week_restricted: bool,
+}
+
+impl CronSchedule {
+    /// Parses a cron expression.
+    pub fn parse(expr: &str) -> Result<CronSchedule, String> {
+        let fields: Vec<_> = expr.split_whitespace().collect();
+        let [minutes, hours, days_of_month, months, days_of_week] = fields[..] else {
+            return Err(format!(
+                "expected 5 fields in cron expression, found {}",
+                fields.len()
+            ));
+        };
+
+        let mut days_of_week_set = parse_field(days_of_week, "day of week", 0, 7)?;
+        if days_of_week_set.remove(&7) {
+            days_of_week_set.insert(0);
+        }
+
+        Ok(CronSchedule {
+            expr: expr.to_string(),
+            minutes: parse_field(minutes, "minute", 0, 59)?,
+            hours: parse_field(hours, "hour", 0, 23)?,
+            days_of_month: parse_field(days_of_month, "day of month", 1, 31)?,
+            months: parse_field(months, "month", 1, 12)?,
+            days_of_week: days_of_week_set,
+            days_of_month_restricted: !days_of_month.starts_with('*'),
+            days_of_week_restricted: !days_of_week.starts_with('*'),
+        })
+    }
+
+    /// Returns the expression this schedule was parsed from.
+    pub fn expr(&self) -> &str {
+        &self.expr
+    }
+
+    /// Reports whether the minute containing `time` is matched by this
+    /// schedule.
+    pub fn matches(&self, time: DateTime<Utc>) -> bool {
+        let day_of_month = self.days_of_month.contains(&time.day());
+        let day_of_week = self
+            .days_of_week
+            .contains(&time.weekday().num_days_from_sunday());
+        let day = match (self.days_of_month_restricted, self.days_of_week_restricted) {
+            (true, true) => day_of_month || day_of_week,
+            _ => day_of_month && day_of_week,
+        };
+        day && self.minutes.contains(&time.minute())
+            && self.hours.contains(&time.hour())
+            && self.months.contains(&time.month())
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expr)
+    }
+}
+
+/// Parses a single field of a cron expression, whose values must lie within
+/// `[min, max]`.
+fn parse_field(field: &str, name: &str, min: u32, max: u32) -> Result<BTreeSet<u32>, String> {
+    let parse_value = |s: &str| -> Result<u32, String> {
+        let value: u32 = s
+            .parse()
+            .map_err(|_| format!("invalid {name} value in cron expression: {s}"))?;
+        if value < min || value > max {
+            return Err(format!(
+                "{name} value in cron expression must be between {min} and {max}, found {value}"
+            ));
+        }
+        Ok(value)
+    };
+
+    let mut values = BTreeSet::new();
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .map_err(|_| format!("invalid {name} step in cron expression: {step}"))?;
+                if step == 0 {
+                    return Err(format!("{name} step in cron expression must be positive"));
+                }
+                (range, step)
+            }
+            None => (item, 1),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            range => match range.split_once('-') {
+                Some((start, end)) => (parse_value(start)?, parse_value(end)?),
+                None => {
+                    let value = parse_value(range)?;
+                    // Like standard cron, `a/n` means every `n`th value
+                    // starting at `a`.
+                    let end = if item.contains('/') { max } else { value };
+                    (value, end)
+                }
+            },
+        };
+        if start > end {
+            return Err(format!("invalid {name} range in cron expression: {range}"));
+        }
+        values.extend((start..=end).step_by(usize::try_from(step).expect("u32 fits in usize")));
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_cron_schedule() {
+        let time = |d, h, m| Utc.with_ymd_and_hms(2024, 3, d, h, m, 0).unwrap();
+
+        // Business hours on weekdays. March 1st, 2024 is a Friday.
+        let schedule = CronSchedule::parse("* 8-17 * * 1-5").unwrap();
+        assert!(schedule.matches(time(1, 8, 0)));
+        assert!(schedule.matches(time(1, 17, 59)));
+        assert!(!schedule.matches(time(1, 18, 0)));
+        assert!(!schedule.matches(time(2, 12, 0)));
+
+        let schedule = CronSchedule::parse("*/15 0 1,15 * *").unwrap();
+        assert!(schedule.matches(time(15, 0, 45)));
+        assert!(!schedule.matches(time(15, 0, 50)));
+        assert!(!schedule.matches(time(14, 0, 45)));
+
+        // Restricting both the day of month and the day of week matches either.
+        let schedule = CronSchedule::parse("* * 2 * 5").unwrap();
+        assert!(schedule.matches(time(1, 0, 0)));
+        assert!(schedule.matches(time(2, 0, 0)));
+        assert!(!schedule.matches(time(3, 0, 0)));
+
+        // Fields starting with `*` are not restricted, even with a step, so
+        // both fields must match. March 4th and 11th, 2024 are Mondays.
+        let schedule = CronSchedule::parse("* * */2 * 1").unwrap();
+        assert!(schedule.matches(time(11, 0, 0)));
+        assert!(!schedule.matches(time(1, 0, 0)));
+        assert!(!schedule.matches(time(4, 0, 0)));
+
+        // Sunday can be written as both 0 and 7.
+        let schedule = CronSchedule::parse("* * * * 7").unwrap();
+        assert!(schedule.matches(time(3, 0, 0)));
+
+        for invalid in [
+            "",
+            "* * * *",
+            "60 * * * *",
+            "* * 0 * *",
+            "5-1 * * * *",
+            "*/0 * * * *",
+        ] {
+            assert!(CronSchedule::parse(invalid).is_err(), "{invalid}");
+        }
+    }
+}
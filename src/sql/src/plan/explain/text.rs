@@ -25,6 +25,7 @@ use mz_expr::explain::{fmt_text_constant_rows, HumanizedExplain, HumanizerMode};
 use mz_expr::virtual_syntax::{AlgExcept, Except};
 use mz_expr::{Id, WindowFrame};
 use mz_ore::str::{separated, IndentLike};
+use mz_repr::adt::collation::Collation;
 use mz_repr::explain::text::DisplayText;
 use mz_repr::explain::{CompactScalarSeq, Indices, PlanRenderingContext};
 
@@ -386,6 +387,7 @@ impl fmt::Display for HirScalarExpr {
                             expr: expr.clone(),
                             desc: column_order.desc,
                             nulls_last: column_order.nulls_last,
+                            collation: column_order.collation,
                         }
                         // (We can ignore column_order.column because of the above assert.)
                     })
@@ -438,6 +440,8 @@ struct ColumnOrderWithExpr {
     pub desc: bool,
     /// Whether to sort nulls last.
     pub nulls_last: bool,
+    /// The collation with which to order strings.
+    pub collation: Collation,
 }
 
 impl fmt::Display for ColumnOrderWithExpr {
@@ -453,7 +457,11 @@ impl fmt::Display for ColumnOrderWithExpr {
             } else {
                 "nulls_first"
             },
-        )
+        )?;
+        if self.collation != Collation::CodePoint {
+            write!(f, " collate \"{}\"", self.collation)?;
+        }
+        Ok(())
     }
 }
 
@@ -136,6 +136,9 @@ pub struct Config {
     pub enable_new_outer_join_lowering: bool,
     /// Enable outer join lowering implemented in #25340.
     pub enable_variadic_left_join_lowering: bool,
+    /// Share the key values of an input across the left joins that
+    /// `enable_variadic_left_join_lowering` lowers jointly.
+    pub enable_left_join_key_sharing: bool,
 }
 
 impl From<&SystemVars> for Config {
@@ -143,6 +146,7 @@ impl From<&SystemVars> for Config {
         Self {
             enable_new_outer_join_lowering: vars.enable_new_outer_join_lowering(),
             enable_variadic_left_join_lowering: vars.enable_variadic_left_join_lowering(),
+            enable_left_join_key_sharing: vars.enable_left_join_key_sharing(),
         }
     }
 }
@@ -841,6 +845,7 @@ impl HirRelationExpr {
                             column: column_order.column + get_outer.arity(),
                             desc: column_order.desc,
                             nulls_last: column_order.nulls_last,
+                            collation: column_order.collation,
                         })
                         .collect();
 
@@ -29,6 +29,11 @@ use crate::plan::lowering::{ColumnMap, Config, CteMap};
 /// Importantly, we need to introduce the constraints that equate columns and expressions in the `Join`,
 /// as a `Filter` will still use SQL's equality, which treats NULL as unequal (we want them to match).
 /// We could replace each `(col = expr)` with `(col = expr OR (col IS NULL AND expr IS NULL))`.
+///
+/// In the common "fan-out" pattern, where several `right` inputs are joined on the same columns of
+/// the same prior input, the distinct key values extracted from that input are bound once and shared
+/// by all augmented `right` relations, so that they can reuse a single arrangement of those keys.
+/// This sharing is gated by `config.enable_left_join_key_sharing`.
 pub(crate) fn attempt_left_join_magic(
     left: &HirRelationExpr,
     rights: Vec<(&HirRelationExpr, &HirScalarExpr)>,
@@ -59,6 +64,11 @@ pub(crate) fn attempt_left_join_magic(
     // We may modify the values if we find promising prior values.
     let mut bindings = Vec::new();
     let mut augmented = Vec::new();
+    // Let bindings for the distinct key values extracted from some prior input, shared by all
+    // `right` inputs that equate their columns with the same columns of that input.
+    let mut key_bindings = Vec::new();
+    // Maps an input and a list of its (outer-adjusted) columns to the `Get` of its key binding.
+    let mut key_gets = std::collections::BTreeMap::new();
     // A vector associating result columns with their corresponding input number
     // (where 0 idicates columns from the outer context).
     let mut bound_to = (0..oa).map(|_| 0).collect::<Vec<_>>();
@@ -154,7 +164,9 @@ pub(crate) fn attempt_left_join_magic(
         // then we can fish out values from that input. If it equates values
         // across multiple inputs, we would need to fish out valid tuples and
         // no idea how we would get those w/o doing a join or a cartesian product.
-        let equations = if let Some(list) = decompose_equations(&on) {
+        let equations = if let Some(mut list) = decompose_equations(&on) {
+            // Order the equations canonically, so that identical key sets are recognized.
+            list.sort();
             list
         } else {
             tracing::debug!(case = 4, index, "attempt_left_join_magic");
@@ -196,34 +208,59 @@ pub(crate) fn attempt_left_join_magic(
             // which we will project to get distinct values, then difference and
             // threshold to find those present in left but missing in right.
             let get_left = &bindings[bound - 1].1;
-            // Set up a type for the all-nulls row we need to introduce.
-            let mut left_typ = get_left.typ();
-            for col in left_typ.column_types.iter_mut() {
-                col.nullable = true;
-            }
-            left_typ.keys.clear();
-            // `get_right` is already bound.
-            let left_vals = get_left
-                .clone()
-                .union(MirRelationExpr::Constant {
-                    rows: Ok(vec![(
-                        mz_repr::Row::pack(
-                            std::iter::repeat(mz_repr::Datum::Null).take(get_left.arity()),
-                        ),
-                        1,
-                    )]),
-                    typ: left_typ,
-                })
-                .project(
-                    equations
-                        .iter()
-                        .map(|(l, _)| if l < &oa { *l } else { l - offset })
-                        .collect::<Vec<_>>(),
-                )
-                .distinct();
+            let key_cols = equations
+                .iter()
+                .map(|(l, _)| if l < &oa { *l } else { l - offset })
+                .collect::<Vec<_>>();
+            // The distinct values of `key_cols` in `get_left`, plus an all-nulls row.
+            let key_values = |key_cols: &[usize]| {
+                // Set up a type for the all-nulls row we need to introduce.
+                let mut left_typ = get_left.typ();
+                for col in left_typ.column_types.iter_mut() {
+                    col.nullable = true;
+                }
+                left_typ.keys.clear();
+                get_left
+                    .clone()
+                    .union(MirRelationExpr::Constant {
+                        rows: Ok(vec![(
+                            mz_repr::Row::pack(
+                                std::iter::repeat(mz_repr::Datum::Null).take(get_left.arity()),
+                            ),
+                            1,
+                        )]),
+                        typ: left_typ,
+                    })
+                    .project(key_cols.to_vec())
+                    .distinct()
+            };
+            let left_vals = if config.enable_left_join_key_sharing {
+                // Reuse the key values of `get_left` if a prior `right` already extracted them.
+                key_gets
+                    .entry((bound, key_cols))
+                    .or_insert_with_key(|(_, key_cols)| {
+                        let value = key_values(key_cols);
+                        let id = LocalId::new(id_gen.allocate_id());
+                        let get = MirRelationExpr::local_get(id, value.typ());
+                        key_bindings.push((id, value));
+                        get
+                    })
+                    .clone()
+            } else {
+                key_values(&key_cols)
+            };
+            // `get_right` is already bound. Its rows with null keys must not participate,
+            // as they would otherwise match null keys under the null-equating predicates
+            // below, whereas SQL's equality never matches them.
+            let right_matchable = get_right.clone().filter(
+                equations
+                    .iter()
+                    .map(|(_, r)| MirScalarExpr::column(r - oa - ba).call_is_null().not())
+                    .collect::<Vec<_>>(),
+            );
 
             // We skip the distinct because the eventual `threshold` protects us.
-            let right_vals = get_right.clone().project(
+            let right_vals = right_matchable.clone().project(
                 equations
                     .iter()
                     .map(|(_, r)| r - oa - ba)
@@ -256,11 +293,7 @@ pub(crate) fn attempt_left_join_magic(
             // This is a quirk of how outer joins work: the matched columns are left as null.
 
             // Record the binding we'll need to make for `aug_id`.
-            augmented.push((
-                aug_id,
-                aug_right,
-                bindings[index + 1].1.clone().union(additions),
-            ));
+            augmented.push((aug_id, aug_right, right_matchable.union(additions)));
 
             // Update `body` to reflect the product, filtered by `on`.
             body = product.filter(recompose_equations(equations));
@@ -300,6 +333,13 @@ pub(crate) fn attempt_left_join_magic(
             body: Box::new(body),
         };
     }
+    while let Some((id, value)) = key_bindings.pop() {
+        body = MirRelationExpr::Let {
+            id,
+            value: Box::new(value),
+            body: Box::new(body),
+        };
+    }
     while let Some((id, _get, value)) = bindings.pop() {
         body = MirRelationExpr::Let {
             id,
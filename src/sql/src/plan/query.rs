@@ -46,6 +46,7 @@ use mz_ore::option::FallibleMapExt;
 use mz_ore::stack::{CheckedRecursion, RecursionGuard};
 use mz_ore::str::StrExt;
 use mz_repr::adt::char::CharLength;
+use mz_repr::adt::collation::Collation;
 use mz_repr::adt::numeric::{NumericMaxScale, NUMERIC_DATUM_MAX_PRECISION};
 use mz_repr::adt::timestamp::TimestampPrecision;
 use mz_repr::adt::varchar::VarCharMaxLength;
@@ -61,9 +62,10 @@ use mz_sql_parser::ast::{
     CreateWebhookSourceHeader, CreateWebhookSourceSecret, CteBlock, DeleteStatement, Distinct,
     Expr, Function, FunctionArgs, HomogenizingFunction, Ident, InsertSource, IsExprConstruct, Join,
     JoinConstraint, JoinOperator, Limit, MutRecBlock, MutRecBlockOption, MutRecBlockOptionName,
-    OrderByExpr, Query, Select, SelectItem, SelectOption, SelectOptionName, SetExpr, SetOperator,
-    ShowStatement, SubscriptPosition, TableAlias, TableFactor, TableWithJoins, UnresolvedItemName,
-    UpdateStatement, Value, Values, WindowFrame, WindowFrameBound, WindowFrameUnits, WindowSpec,
+    OrderByExpr, Query, Raw, Select, SelectItem, SelectOption, SelectOptionName, SetExpr,
+    SetOperator, ShowStatement, SubscriptPosition, TableAlias, TableFactor, TableWithJoins,
+    UnresolvedItemName, UpdateStatement, Value, Values, WindowFrame, WindowFrameBound,
+    WindowFrameUnits, WindowSpec,
 };
 use mz_sql_parser::ident;
 use uuid::Uuid;
@@ -71,7 +73,7 @@ use uuid::Uuid;
 use crate::catalog::{CatalogItemType, CatalogType, SessionCatalog};
 use crate::func::{self, Func, FuncSpec};
 use crate::names::{
-    Aug, FullItemName, PartialItemName, ResolvedDataType, ResolvedItemName, SchemaSpecifier,
+    self, Aug, FullItemName, PartialItemName, ResolvedDataType, ResolvedItemName, SchemaSpecifier,
 };
 use crate::normalize;
 use crate::plan::error::PlanError;
@@ -1194,6 +1196,14 @@ pub fn plan_index_exprs<'a>(
     };
     let mut out = vec![];
     for mut expr in exprs {
+        // Indexes order their keys by the order of the key's type, and
+        // collations are not part of types. Rather than silently ignoring the
+        // collation of a key, reject it.
+        if let Some(collation) = explicit_collation(&expr)? {
+            if collation != Collation::CodePoint {
+                bail_unsupported!(format!("index keys with collation \"{collation}\""));
+            }
+        }
         transform_ast::transform(scx, &mut expr)?;
         let expr = plan_expr_or_col_index(ecx, &expr)?;
         let mut expr = expr.lower_uncorrelated()?;
@@ -1576,6 +1586,51 @@ pub fn plan_nested_query(
     Ok((expr.project(project), scope))
 }
 
+/// Plans the body of a function created with `CREATE FUNCTION`.
+///
+/// The body can reference the parameters of the function as `$1`, `$2`, etc.,
+/// or by their names. The columns that the body produces are cast to the types
+/// of the columns in `returns`.
+pub fn plan_function_body(
+    scx: &StatementContext,
+    lifetime: QueryLifetime,
+    params: &[(Option<ColumnName>, ScalarType)],
+    returns: &RelationDesc,
+    body: Query<Raw>,
+) -> Result<HirRelationExpr, PlanError> {
+    let mut scx = scx.clone();
+    scx.param_types = RefCell::new(
+        params
+            .iter()
+            .enumerate()
+            .map(|(i, (_, typ))| (i + 1, typ.clone()))
+            .collect(),
+    );
+    scx.param_names = params.iter().map(|(name, _)| name.clone()).collect();
+
+    let (mut body, _) = names::resolve(scx.catalog, body)?;
+    transform_ast::transform(&scx, &mut body)?;
+    let mut qcx = QueryContext::root(&scx, lifetime);
+    let (expr, _) = plan_nested_query(&mut qcx, &body)?;
+
+    if expr.arity() != returns.arity() {
+        sql_bail!(
+            "function body returns {} columns, but RETURNS TABLE declares {}",
+            expr.arity(),
+            returns.arity()
+        );
+    }
+    let return_types = returns.iter_types().map(|typ| &typ.scalar_type);
+    cast_relation(&qcx, CastContext::Assignment, expr, return_types).map_err(|e| {
+        sql_err!(
+            "column {} is declared as type {} but the function body returns type {}",
+            returns.get_name(e.column).as_str().quoted(),
+            qcx.humanize_scalar_type(&e.target_type),
+            qcx.humanize_scalar_type(&e.source_type),
+        )
+    })
+}
+
 fn plan_set_expr(
     qcx: &mut QueryContext,
     q: &SetExpr<Aug>,
@@ -2613,7 +2668,7 @@ pub(crate) fn plan_order_by_exprs(
                 ecx.relation_type.arity() + map_exprs.len() - 1
             }
         };
-        order_by.push(resolve_desc_and_nulls_last(obe, column));
+        order_by.push(resolve_desc_and_nulls_last(obe, column)?);
     }
     Ok((order_by, map_exprs))
 }
@@ -4360,19 +4415,52 @@ where
     .into())
 }
 
+/// Resolves the name of a collation, which may be qualified by `pg_catalog`.
+fn resolve_collation(collation: &UnresolvedItemName) -> Result<Collation, PlanError> {
+    let name = match &collation.0[..] {
+        [name] => name,
+        [schema, name] if *schema == ident!(mz_repr::namespaces::PG_CATALOG_SCHEMA) => name,
+        _ => bail_unsupported!("COLLATE"),
+    };
+    match Collation::from_name(name.as_str()) {
+        Some(collation) => Ok(collation),
+        None => bail_unsupported!("COLLATE"),
+    }
+}
+
+/// Returns the collation that is explicitly applied to `expr`, if any.
+fn explicit_collation<T: AstInfo>(expr: &Expr<T>) -> Result<Option<Collation>, PlanError> {
+    match expr {
+        Expr::Collate { collation, .. } => Ok(Some(resolve_collation(collation)?)),
+        Expr::Nested(expr) => explicit_collation(expr),
+        _ => Ok(None),
+    }
+}
+
+/// Plans `expr`, which must be of a type that has collations.
+///
+/// Only string types have collations, as in PostgreSQL.
+fn plan_collatable_expr(ecx: &ExprContext, expr: &Expr<Aug>) -> Result<HirScalarExpr, PlanError> {
+    let expr = plan_expr(ecx, expr)?.type_as_any(ecx)?;
+    match ecx.scalar_type(&expr) {
+        ScalarType::String | ScalarType::Char { .. } | ScalarType::VarChar { .. } => Ok(expr),
+        typ => sql_bail!(
+            "collations are not supported by type {}",
+            ecx.humanize_scalar_type(&typ)
+        ),
+    }
+}
+
 fn plan_collate(
     ecx: &ExprContext,
     expr: &Expr<Aug>,
     collation: &UnresolvedItemName,
 ) -> Result<CoercibleScalarExpr, PlanError> {
-    if collation.0.len() == 2
-        && collation.0[0] == ident!(mz_repr::namespaces::PG_CATALOG_SCHEMA)
-        && collation.0[1] == ident!("default")
-    {
-        plan_expr(ecx, expr)
-    } else {
-        bail_unsupported!("COLLATE");
-    }
+    // Applying a collation does not change the value; it only changes how the
+    // comparisons and ORDER BY clauses that the value appears in order it.
+    // Those pick up the collation from the AST.
+    resolve_collation(collation)?;
+    Ok(plan_collatable_expr(ecx, expr)?.into())
 }
 
 /// Plans a slice of expressions.
@@ -4560,19 +4648,21 @@ pub fn coerce_homogeneous_exprs(
 }
 
 /// Creates a `ColumnOrder` from an `OrderByExpr` and column index.
-/// Column index is specified by the caller, but `desc` and `nulls_last` is figured out here.
+/// Column index is specified by the caller, but `desc`, `nulls_last`, and `collation` are
+/// figured out here.
 pub(crate) fn resolve_desc_and_nulls_last<T: AstInfo>(
     obe: &OrderByExpr<T>,
     column: usize,
-) -> ColumnOrder {
+) -> Result<ColumnOrder, PlanError> {
     let desc = !obe.asc.unwrap_or(true);
-    ColumnOrder {
+    Ok(ColumnOrder {
         column,
         desc,
         // https://www.postgresql.org/docs/14/queries-order.html
         //   "NULLS FIRST is the default for DESC order, and NULLS LAST otherwise"
         nulls_last: obe.nulls_last.unwrap_or(!desc),
-    }
+        collation: explicit_collation(&obe.expr)?.unwrap_or_default(),
+    })
 }
 
 /// Plans the ORDER BY clause of a window function.
@@ -4595,7 +4685,7 @@ fn plan_function_order_by(
             // `plan_expr` directly rather than `plan_order_by_or_distinct_expr`.
             let expr = plan_expr(ecx, &obe.expr)?.type_as_any(ecx)?;
             order_by_exprs.push(expr);
-            col_orders.push(resolve_desc_and_nulls_last(obe, i));
+            col_orders.push(resolve_desc_and_nulls_last(obe, i)?);
         }
     }
     Ok((order_by_exprs, col_orders))
@@ -4737,7 +4827,20 @@ fn plan_identifier(ecx: &ExprContext, names: &[Ident]) -> Result<HirScalarExpr,
         Err(e) => return Err(e),
     };
 
-    // The name doesn't refer to a column. Check if it is a whole-row reference
+    // The name doesn't refer to a column. Check if it refers to a parameter of
+    // the function whose body is being planned. Per PostgreSQL, columns take
+    // precedence over parameters.
+    if let Some(i) = ecx
+        .qcx
+        .scx
+        .param_names
+        .iter()
+        .position(|name| name.as_ref() == Some(&col_name))
+    {
+        return Ok(HirScalarExpr::Parameter(i + 1));
+    }
+
+    // The name doesn't refer to a column or a parameter. Check if it is a whole-row reference
     // to a table.
     let items = ecx.scope.items_from_table(
         &ecx.qcx.outer_scopes,
@@ -4808,6 +4911,11 @@ fn plan_op(
     expr2: Option<&Expr<Aug>>,
 ) -> Result<HirScalarExpr, PlanError> {
     let impls = func::resolve_op(op)?;
+    if let Some(expr2) = expr2 {
+        if let Some(collation) = comparison_collation(op, expr1, expr2)? {
+            return plan_collated_comparison(ecx, op, collation, expr1, expr2);
+        }
+    }
     let args = match expr2 {
         None => plan_exprs(ecx, &[expr1])?,
         Some(expr2) => plan_exprs(ecx, &[expr1, expr2])?,
@@ -4815,6 +4923,63 @@ fn plan_op(
     func::select_impl(ecx, FuncSpec::Op(op), impls, args, vec![])
 }
 
+/// Returns the collation with which `expr1 op expr2` must be planned, if `op` is
+/// a comparison and either operand has an explicit collation that does not
+/// order strings by their code points.
+fn comparison_collation(
+    op: &str,
+    expr1: &Expr<Aug>,
+    expr2: &Expr<Aug>,
+) -> Result<Option<Collation>, PlanError> {
+    if !matches!(op, "<" | "<=" | ">" | ">=" | "=" | "<>" | "!=") {
+        return Ok(None);
+    }
+    let collation = match (explicit_collation(expr1)?, explicit_collation(expr2)?) {
+        (Some(c1), Some(c2)) if c1 != c2 => sql_bail!(
+            "collation mismatch between explicit collations \"{}\" and \"{}\"",
+            c1,
+            c2
+        ),
+        (Some(collation), _) | (_, Some(collation)) => collation,
+        (None, None) => return Ok(None),
+    };
+    Ok(Some(collation).filter(|collation| *collation != Collation::CodePoint))
+}
+
+/// Plans `expr1 op expr2`, where `op` is a comparison, by comparing the
+/// operands with `collation` and comparing the result against zero.
+fn plan_collated_comparison(
+    ecx: &ExprContext,
+    op: &str,
+    collation: Collation,
+    expr1: &Expr<Aug>,
+    expr2: &Expr<Aug>,
+) -> Result<HirScalarExpr, PlanError> {
+    let func = match op {
+        "<" => BinaryFunc::Lt,
+        "<=" => BinaryFunc::Lte,
+        ">" => BinaryFunc::Gt,
+        ">=" => BinaryFunc::Gte,
+        "=" => BinaryFunc::Eq,
+        "<>" | "!=" => BinaryFunc::NotEq,
+        _ => unreachable!("not a comparison operator: {op}"),
+    };
+    // Compare `char` values as `text`, so that their padding is ignored.
+    let plan_operand = |expr: &Expr<Aug>| -> Result<HirScalarExpr, PlanError> {
+        let expr = plan_collatable_expr(ecx, expr)?;
+        match ecx.scalar_type(&expr) {
+            ScalarType::String => Ok(expr),
+            _ => typeconv::plan_cast(ecx, CastContext::Implicit, expr, &ScalarType::String),
+        }
+    };
+    let expr1 = plan_operand(expr1)?;
+    let expr2 = plan_operand(expr2)?;
+    let zero = HirScalarExpr::literal(Datum::Int32(0), ScalarType::Int32);
+    Ok(expr1
+        .call_binary(expr2, BinaryFunc::CompareCollated(collation))
+        .call_binary(zero, func))
+}
+
 fn plan_function<'a>(
     ecx: &ExprContext,
     f @ Function {
@@ -5039,11 +5204,11 @@ pub const IGNORE_NULLS_ERROR_MSG: &str =
 /// Resolves the name to a set of function implementations.
 ///
 /// If the name does not specify a known built-in function, returns an error.
-pub fn resolve_func(
-    ecx: &ExprContext,
+pub fn resolve_func<'a>(
+    ecx: &ExprContext<'a>,
     name: &ResolvedItemName,
     args: &mz_sql_parser::ast::FunctionArgs<Aug>,
-) -> Result<&'static Func, PlanError> {
+) -> Result<&'a Func, PlanError> {
     if let Ok(i) = ecx.qcx.scx.get_item_by_resolved_name(name) {
         if let Ok(f) = i.func() {
             return Ok(f);
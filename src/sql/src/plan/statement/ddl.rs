@@ -23,34 +23,41 @@ use mz_controller_types::{
     is_cluster_size_v2, ClusterId, ReplicaId, DEFAULT_REPLICA_LOGGING_INTERVAL,
 };
 use mz_expr::refresh_schedule::{RefreshEvery, RefreshSchedule};
-use mz_expr::{CollectionPlan, UnmaterializableFunc};
+use mz_expr::{CollectionPlan, MirScalarExpr, UnmaterializableFunc};
 use mz_interchange::avro::{AvroSchemaGenerator, AvroSchemaOptions, DocTarget};
+use mz_interchange::encode::column_names_and_types;
+use mz_interchange::{envelopes, protobuf};
 use mz_ore::cast::{CastFrom, TryCastFrom};
-use mz_ore::collections::HashSet;
+use mz_ore::collections::{CollectionExt, HashSet};
 use mz_ore::soft_panic_or_log;
 use mz_ore::str::StrExt;
+use mz_pgwire_common::ScramVerifier;
 use mz_proto::RustType;
 use mz_repr::adt::interval::Interval;
 use mz_repr::adt::mz_acl_item::{MzAclItem, PrivilegeMap};
 use mz_repr::adt::system::Oid;
+use mz_repr::bytes::ByteSize;
 use mz_repr::optimize::OptimizerFeatureOverrides;
 use mz_repr::role_id::RoleId;
 use mz_repr::{
     strconv, ColumnName, ColumnType, GlobalId, RelationDesc, RelationType, ScalarType, Timestamp,
 };
+use mz_rocksdb_types::config::CompactionStyle;
 use mz_sql_parser::ast::display::comma_separated;
 use mz_sql_parser::ast::{
-    self, AlterClusterAction, AlterClusterStatement, AlterConnectionAction, AlterConnectionOption,
-    AlterConnectionOptionName, AlterConnectionStatement, AlterIndexAction, AlterIndexStatement,
-    AlterObjectRenameStatement, AlterObjectSwapStatement, AlterRoleOption, AlterRoleStatement,
-    AlterSecretStatement, AlterSetClusterStatement, AlterSinkStatement, AlterSourceAction,
-    AlterSourceAddSubsourceOption, AlterSourceAddSubsourceOptionName, AlterSourceStatement,
-    AlterSystemResetAllStatement, AlterSystemResetStatement, AlterSystemSetStatement, AvroSchema,
-    AvroSchemaOption, AvroSchemaOptionName, ClusterFeature, ClusterFeatureName, ClusterOption,
-    ClusterOptionName, ColumnOption, CommentObjectType, CommentStatement,
-    CreateClusterReplicaStatement, CreateClusterStatement, CreateConnectionOption,
-    CreateConnectionOptionName, CreateConnectionStatement, CreateConnectionType,
-    CreateDatabaseStatement, CreateIndexStatement, CreateMaterializedViewStatement,
+    self, AlterClusterAction, AlterClusterReplicaAction, AlterClusterReplicaStatement,
+    AlterClusterStatement, AlterConnectionAction, AlterConnectionOption, AlterConnectionOptionName,
+    AlterConnectionStatement, AlterIndexAction, AlterIndexStatement, AlterObjectRenameStatement,
+    AlterObjectSwapStatement, AlterQuotaStatement, AlterRoleOption, AlterRoleStatement,
+    AlterSecretStatement, AlterSetClusterStatement, AlterSinkAction, AlterSinkStatement,
+    AlterSourceAction, AlterSourceAddSubsourceOption, AlterSourceAddSubsourceOptionName,
+    AlterSourceStatement, AlterSystemResetAllStatement, AlterSystemResetStatement,
+    AlterSystemSetStatement, AvroSchema, AvroSchemaOption, AvroSchemaOptionName, ClusterFeature,
+    ClusterFeatureName, ClusterOption, ClusterOptionName, ColumnOption, CommentObjectType,
+    CommentStatement, CreateClusterReplicaStatement, CreateClusterStatement,
+    CreateConnectionOption, CreateConnectionOptionName, CreateConnectionStatement,
+    CreateConnectionType, CreateContinualTaskStatement, CreateDatabaseStatement,
+    CreateFunctionStatement, CreateIndexStatement, CreateMaterializedViewStatement,
     CreateRoleStatement, CreateSchemaStatement, CreateSecretStatement, CreateSinkConnection,
     CreateSinkOption, CreateSinkOptionName, CreateSinkStatement, CreateSourceConnection,
     CreateSourceFormat, CreateSourceOption, CreateSourceOptionName, CreateSourceStatement,
@@ -59,13 +66,14 @@ use mz_sql_parser::ast::{
     CreateTypeMapOption, CreateTypeMapOptionName, CreateTypeStatement, CreateViewStatement,
     CreateWebhookSourceStatement, CsrConfigOption, CsrConfigOptionName, CsrConnection,
     CsrConnectionAvro, CsrConnectionProtobuf, CsrSeedProtobuf, CsvColumns, DeferredItemName,
-    DocOnIdentifier, DocOnSchema, DropObjectsStatement, DropOwnedStatement, Expr, Format, Ident,
-    IfExistsBehavior, IndexOption, IndexOptionName, KafkaSinkConfigOption, KeyConstraint,
-    LoadGeneratorOption, LoadGeneratorOptionName, MaterializedViewOption,
-    MaterializedViewOptionName, MySqlConfigOption, MySqlConfigOptionName, PgConfigOption,
-    PgConfigOptionName, ProtobufSchema, QualifiedReplica, ReferencedSubsources,
-    RefreshAtOptionValue, RefreshEveryOptionValue, RefreshOptionValue, ReplicaDefinition,
-    ReplicaOption, ReplicaOptionName, RoleAttribute, SetRoleVar, SourceIncludeMetadata, Statement,
+    DocOnIdentifier, DocOnSchema, DropObjectsStatement, DropOwnedStatement, Expr, Format,
+    IcebergSinkConfigOption, IcebergSinkConfigOptionName, Ident, IfExistsBehavior, IndexOption,
+    IndexOptionName, InsertSource, KafkaSinkConfigOption, KeyConstraint, LoadGeneratorOption,
+    LoadGeneratorOptionName, MaterializedViewOption, MaterializedViewOptionName, MySqlConfigOption,
+    MySqlConfigOptionName, OnCommitAction, PgConfigOption, PgConfigOptionName, ProtobufSchema,
+    QualifiedReplica, QuotaSubjectSpecification, ReferencedSubsources, RefreshAtOptionValue,
+    RefreshEveryOptionValue, RefreshOptionValue, ReplicaDefinition, ReplicaOption,
+    ReplicaOptionName, RoleAttribute, SetRoleVar, SourceIncludeMetadata, Statement,
     TableConstraint, TableOption, TableOptionName, UnresolvedDatabaseName, UnresolvedItemName,
     UnresolvedObjectName, UnresolvedSchemaName, Value, ViewDefinition,
 };
@@ -73,14 +81,15 @@ use mz_sql_parser::ident;
 use mz_storage_types::connections::inline::{ConnectionAccess, ReferencedConnection};
 use mz_storage_types::connections::Connection;
 use mz_storage_types::sinks::{
-    KafkaIdStyle, KafkaSinkConnection, KafkaSinkFormat, SinkEnvelope, StorageSinkConnection,
+    AvroSchemaEvolution, IcebergSinkConnection, KafkaIdStyle, KafkaSinkConnection, KafkaSinkFormat,
+    KafkaTopicOptions, ProtobufSinkMessage, SinkEnvelope, StorageSinkConnection,
 };
 use mz_storage_types::sources::encoding::{
     included_column_desc, AvroEncoding, ColumnSpec, CsvEncoding, DataEncoding, ProtobufEncoding,
     RegexEncoding, SourceDataEncoding,
 };
 use mz_storage_types::sources::envelope::{
-    KeyEnvelope, SourceEnvelope, UnplannedSourceEnvelope, UpsertStyle,
+    KeyEnvelope, SourceEnvelope, UnplannedSourceEnvelope, UpsertStateConfig, UpsertStyle,
 };
 use mz_storage_types::sources::kafka::{KafkaMetadataKind, KafkaSourceConnection};
 use mz_storage_types::sources::load_generator::{LoadGenerator, LoadGeneratorSourceConnection};
@@ -91,15 +100,21 @@ use mz_storage_types::sources::postgres::{
     CastType, PostgresSourceConnection, PostgresSourcePublicationDetails,
     ProtoPostgresSourcePublicationDetails,
 };
-use mz_storage_types::sources::{GenericSourceConnection, SourceConnection, SourceDesc, Timeline};
+use mz_storage_types::sources::{
+    GenericSourceConnection, RetryPolicy, SourceConnection, SourceDesc, Timeline, TimestampPolicy,
+};
 use prost::Message;
 
 use crate::ast::display::AstDisplay;
 use crate::catalog::{
     CatalogCluster, CatalogDatabase, CatalogError, CatalogItem, CatalogItemType,
-    CatalogRecordField, CatalogType, CatalogTypeDetails, ObjectType, SystemObjectType,
+    CatalogRecordField, CatalogType, CatalogTypeDetails, ObjectType, QuotaKind, QuotaSubject,
+    SystemObjectType,
+};
+use crate::func;
+use crate::kafka_util::{
+    self, KafkaSinkConfigOptionExtracted, KafkaSourceConfigOptionExtracted, KafkaStartTimestamp,
 };
-use crate::kafka_util::{KafkaSinkConfigOptionExtracted, KafkaSourceConfigOptionExtracted};
 use crate::names::{
     Aug, CommentObjectId, DatabaseId, ObjectId, PartialItemName, QualifiedItemName,
     RawDatabaseSpecifier, ResolvedClusterName, ResolvedColumnName, ResolvedDataType,
@@ -111,25 +126,26 @@ use crate::plan::expr::ColumnRef;
 use crate::plan::query::{plan_expr, scalar_type_from_catalog, ExprContext, QueryLifetime};
 use crate::plan::scope::Scope;
 use crate::plan::statement::ddl::connection::{INALTERABLE_OPTIONS, MUTUALLY_EXCLUSIVE_SETS};
-use crate::plan::statement::{scl, StatementContext, StatementDesc};
+use crate::plan::statement::{scl, show, StatementContext, StatementDesc};
 use crate::plan::typeconv::{plan_cast, CastContext};
 use crate::plan::with_options::{OptionalDuration, TryFromValue};
 use crate::plan::{
     plan_utils, query, transform_ast, AlterClusterPlan, AlterClusterRenamePlan,
-    AlterClusterReplicaRenamePlan, AlterClusterSwapPlan, AlterConnectionPlan,
-    AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan, AlterItemRenamePlan, AlterNoopPlan,
-    AlterOptionParameter, AlterRolePlan, AlterSchemaRenamePlan, AlterSchemaSwapPlan,
-    AlterSecretPlan, AlterSetClusterPlan, AlterSourcePlan, AlterSystemResetAllPlan,
-    AlterSystemResetPlan, AlterSystemSetPlan, CommentPlan, ComputeReplicaConfig,
-    ComputeReplicaIntrospectionConfig, CreateClusterManagedPlan, CreateClusterPlan,
-    CreateClusterReplicaPlan, CreateClusterUnmanagedPlan, CreateClusterVariant,
-    CreateConnectionPlan, CreateDatabasePlan, CreateIndexPlan, CreateMaterializedViewPlan,
-    CreateRolePlan, CreateSchemaPlan, CreateSecretPlan, CreateSinkPlan, CreateSourcePlan,
-    CreateTablePlan, CreateTypePlan, CreateViewPlan, DataSourceDesc, DropObjectsPlan,
-    DropOwnedPlan, FullItemName, HirScalarExpr, Index, Ingestion, MaterializedView, Params, Plan,
-    PlanClusterOption, PlanNotice, QueryContext, ReplicaConfig, Secret, Sink, Source, Table, Type,
-    VariableValue, View, WebhookBodyFormat, WebhookHeaderFilters, WebhookHeaders,
-    WebhookValidation,
+    AlterClusterReplicaPlan, AlterClusterReplicaRenamePlan, AlterClusterSwapPlan,
+    AlterConnectionPlan, AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan, AlterItemRenamePlan,
+    AlterNoopPlan, AlterOptionParameter, AlterQuotaPlan, AlterRolePlan, AlterSchemaRenamePlan,
+    AlterSchemaSwapPlan, AlterSecretPlan, AlterSetClusterPlan, AlterSinkResetOffsetPlan,
+    AlterSourcePlan, AlterSystemResetAllPlan, AlterSystemResetPlan, AlterSystemSetPlan,
+    ClusterSchedule, CommentPlan, ComputeReplicaConfig, ComputeReplicaIntrospectionConfig,
+    ContinualTask, CreateClusterManagedPlan, CreateClusterPlan, CreateClusterReplicaPlan,
+    CreateClusterUnmanagedPlan, CreateClusterVariant, CreateConnectionPlan,
+    CreateContinualTaskPlan, CreateDatabasePlan, CreateFunctionPlan, CreateIndexPlan,
+    CreateMaterializedViewPlan, CreateRolePlan, CreateSchemaPlan, CreateSecretPlan, CreateSinkPlan,
+    CreateSourcePlan, CreateTablePlan, CreateTypePlan, CreateViewPlan, DataSourceDesc,
+    DropObjectsPlan, DropOwnedPlan, FullItemName, Function, HirScalarExpr, Index, Ingestion,
+    MaterializedView, Params, Plan, PlanClusterOption, PlanNotice, QueryContext, ReplicaConfig,
+    Secret, Sink, Source, Table, Type, VariableValue, View, WebhookBodyFormat,
+    WebhookDeduplication, WebhookHeaderFilters, WebhookHeaders, WebhookValidation,
 };
 use crate::session::vars;
 use crate::session::vars::ENABLE_REFRESH_EVERY_MVS;
@@ -221,6 +237,7 @@ pub fn plan_create_table(
         constraints,
         if_not_exists,
         temporary,
+        on_commit,
         with_options,
     } = &stmt;
 
@@ -341,6 +358,12 @@ pub fn plan_create_table(
     let typ = RelationType::new(column_types).with_keys(keys);
 
     let temporary = *temporary;
+    let delete_rows_on_commit = match on_commit {
+        Some(_) if !temporary => sql_bail!("ON COMMIT can only be used on temporary tables"),
+        None | Some(OnCommitAction::PreserveRows) => false,
+        Some(OnCommitAction::DeleteRows) => true,
+        Some(OnCommitAction::Drop) => bail_unsupported!("ON COMMIT DROP"),
+    };
     let name = if temporary {
         scx.allocate_temporary_qualified_name(normalize::unresolved_item_name(name.to_owned())?)?
     } else {
@@ -381,6 +404,7 @@ pub fn plan_create_table(
         desc,
         defaults,
         temporary,
+        delete_rows_on_commit,
         compaction_window,
     };
     Ok(Plan::CreateTable(CreateTablePlan {
@@ -413,9 +437,17 @@ pub fn describe_create_subsource(
 
 generate_extracted_config!(
     CreateSourceOption,
+    (Errors, String),
     (IgnoreKeys, bool),
+    (RetryBackoff, Duration),
+    (RetryMaxAttempts, u64),
+    (RetryMaxBackoff, Duration),
+    (StartTimestamp, KafkaStartTimestamp),
     (Timeline, String),
     (TimestampInterval, Duration),
+    (TimestampPolicy, String),
+    (UpsertCompactionStyle, String),
+    (UpsertMemoryLimit, ByteSize),
     (RetainHistory, Duration)
 );
 
@@ -449,6 +481,7 @@ pub fn plan_create_webhook_source(
         body_format,
         include_headers,
         validate_using,
+        deduplicate,
         // We resolved `in_cluster` above, so we want to ignore it here.
         in_cluster: _,
     } = stmt;
@@ -470,6 +503,24 @@ pub fn plan_create_webhook_source(
         }
     }
 
+    let deduplicate = deduplicate
+        .map(|dedup| {
+            let window = match dedup.window {
+                Some(window) => strconv::parse_interval(&window)?.duration()?,
+                None => WebhookDeduplication::DEFAULT_WINDOW,
+            };
+            if window.is_zero() || window > WebhookDeduplication::MAX_WINDOW {
+                sql_bail!("DEDUPLICATE window must be greater than 0 and at most 1 day");
+            }
+            // Header names are case insensitive, and are presented to us in lowercase.
+            let header_name = dedup.header_name.to_lowercase();
+            Ok::<_, PlanError>(WebhookDeduplication {
+                header_name,
+                window,
+            })
+        })
+        .transpose()?;
+
     let body_format = match body_format {
         Format::Bytes => WebhookBodyFormat::Bytes,
         Format::Json { array } => WebhookBodyFormat::Json { array },
@@ -580,6 +631,7 @@ pub fn plan_create_webhook_source(
                 validate_using,
                 body_format,
                 headers,
+                deduplicate,
             },
             desc,
             compaction_window: None,
@@ -612,7 +664,15 @@ pub fn plan_create_source(
     let envelope = envelope.clone().unwrap_or(ast::SourceEnvelope::None);
 
     let allowed_with_options = vec![
+        CreateSourceOptionName::Errors,
+        CreateSourceOptionName::RetryBackoff,
+        CreateSourceOptionName::RetryMaxAttempts,
+        CreateSourceOptionName::RetryMaxBackoff,
+        CreateSourceOptionName::StartTimestamp,
         CreateSourceOptionName::TimestampInterval,
+        CreateSourceOptionName::TimestampPolicy,
+        CreateSourceOptionName::UpsertCompactionStyle,
+        CreateSourceOptionName::UpsertMemoryLimit,
         CreateSourceOptionName::RetainHistory,
     ];
     if let Some(op) = with_options
@@ -641,6 +701,45 @@ pub fn plan_create_source(
         bail_unsupported!("INCLUDE metadata with non-Kafka sources");
     }
 
+    let CreateSourceOptionExtracted {
+        errors,
+        timeline,
+        timestamp_interval,
+        timestamp_policy,
+        ignore_keys,
+        retain_history,
+        retry_backoff,
+        retry_max_attempts,
+        retry_max_backoff,
+        upsert_compaction_style,
+        upsert_memory_limit,
+        start_timestamp,
+        seen: _,
+    } = CreateSourceOptionExtracted::try_from(with_options.clone())?;
+
+    // Purification moves `START TIMESTAMP` into the options of Kafka sources.
+    if start_timestamp.is_some() {
+        sql_bail!("START TIMESTAMP is only supported for Kafka sources");
+    }
+
+    let dead_letter_queue = match errors {
+        Some(errors) => {
+            scx.require_feature_flag(&vars::ENABLE_SOURCE_DEAD_LETTER_QUEUE)?;
+            match errors.to_lowercase().as_str() {
+                "dlq" => true,
+                "fail" => false,
+                _ => sql_bail!(
+                    "unknown ERRORS mode {}; expected one of DLQ, FAIL",
+                    errors.quoted()
+                ),
+            }
+        }
+        None => false,
+    };
+    if dead_letter_queue && !matches!(connection, CreateSourceConnection::Kafka { .. }) {
+        bail_unsupported!("ERRORS = DLQ with non-Kafka sources");
+    }
+
     let (mut external_connection, available_subsources) = match connection {
         CreateSourceConnection::Kafka {
             connection: connection_name,
@@ -758,7 +857,21 @@ pub fn plan_create_source(
 
             let connection = GenericSourceConnection::Kafka(connection);
 
-            (connection, None)
+            // Decoding happens after the raw source, so the dead-letter queue
+            // is exposed as an additional output following the primary one.
+            let available_subsources = if dead_letter_queue {
+                if format.is_none() || !matches!(envelope, ast::SourceEnvelope::None) {
+                    sql_bail!("ERRORS = DLQ requires a FORMAT and ENVELOPE NONE");
+                }
+                Some(BTreeMap::from([(
+                    normalize::full_name(kafka_util::dead_letter_reference())?,
+                    1,
+                )]))
+            } else {
+                None
+            };
+
+            (connection, available_subsources)
         }
         CreateSourceConnection::Postgres {
             connection,
@@ -1172,14 +1285,6 @@ pub fn plan_create_source(
         conn.table_casts.retain(|pos, _| used_pos.contains(pos));
     }
 
-    let CreateSourceOptionExtracted {
-        timeline,
-        timestamp_interval,
-        ignore_keys,
-        retain_history,
-        seen: _,
-    } = CreateSourceOptionExtracted::try_from(with_options.clone())?;
-
     let encoding = match format {
         Some(format) => Some(get_encoding(scx, format, &envelope)?),
         None => None,
@@ -1292,7 +1397,37 @@ pub fn plan_create_source(
 
     let metadata_columns = external_connection.metadata_columns();
     let metadata_desc = included_column_desc(metadata_columns.clone());
-    let (envelope, mut desc) = envelope.desc(key_desc, value_desc, metadata_desc)?;
+    let (mut envelope, mut desc) = envelope.desc(key_desc, value_desc, metadata_desc)?;
+
+    if upsert_compaction_style.is_some() || upsert_memory_limit.is_some() {
+        scx.require_feature_flag(&vars::ENABLE_UPSERT_STATE_OPTIONS)?;
+        let compaction_style = upsert_compaction_style
+            .map(|style| {
+                style.parse::<CompactionStyle>().map_err(|_| {
+                    sql_err!(
+                        "unknown UPSERT COMPACTION STYLE {}; expected one of level, universal",
+                        style.quoted()
+                    )
+                })
+            })
+            .transpose()?;
+        let memory_limit = match upsert_memory_limit {
+            Some(limit) if limit.as_bytes() == 0 => {
+                sql_bail!("UPSERT MEMORY LIMIT must be greater than zero")
+            }
+            Some(limit) => Some(usize::cast_from(limit.as_bytes())),
+            None => None,
+        };
+        match &mut envelope {
+            SourceEnvelope::Upsert(upsert) => {
+                upsert.state_config = UpsertStateConfig {
+                    memory_limit,
+                    compaction_style,
+                };
+            }
+            _ => sql_bail!("UPSERT options require ENVELOPE UPSERT or ENVELOPE DEBEZIUM"),
+        }
+    }
 
     if ignore_keys.unwrap_or(false) {
         desc = desc.without_keys();
@@ -1344,8 +1479,39 @@ pub fn plan_create_source(
         }
     }
 
-    let timestamp_interval = match timestamp_interval {
-        Some(duration) => {
+    let timestamp_policy = match timestamp_policy {
+        Some(policy) => {
+            scx.require_feature_flag(&vars::ENABLE_SOURCE_TIMESTAMP_POLICY)?;
+            if timestamp_interval.is_some() {
+                sql_bail!("cannot specify both TIMESTAMP INTERVAL and TIMESTAMP POLICY");
+            }
+            let policy: TimestampPolicy = policy.parse().map_err(|e| sql_err!("{}", e))?;
+            if policy == TimestampPolicy::StrictPartitionOrder {
+                // Downstream consumers can only recover the upstream order of
+                // each partition if every record carries its position.
+                let includes_partition = include_metadata
+                    .iter()
+                    .any(|m| matches!(m, SourceIncludeMetadata::Partition { .. }));
+                let includes_offset = include_metadata
+                    .iter()
+                    .any(|m| matches!(m, SourceIncludeMetadata::Offset { .. }));
+                if !matches!(connection, CreateSourceConnection::Kafka { .. })
+                    || !includes_partition
+                    || !includes_offset
+                {
+                    sql_bail!(
+                        "TIMESTAMP POLICY 'strict partition order' requires a Kafka source \
+                        with INCLUDE PARTITION, OFFSET"
+                    );
+                }
+            }
+            Some(policy)
+        }
+        None => None,
+    };
+
+    let timestamp_interval = match (timestamp_interval, timestamp_policy) {
+        (Some(duration), _) => {
             let min = scx.catalog.system_vars().min_timestamp_interval();
             let max = scx.catalog.system_vars().max_timestamp_interval();
             if duration < min || duration > max {
@@ -1357,14 +1523,49 @@ pub fn plan_create_source(
             }
             duration
         }
-        None => scx.catalog.config().timestamp_interval,
+        (None, Some(TimestampPolicy::Throughput)) => {
+            scx.catalog.system_vars().max_timestamp_interval()
+        }
+        (None, Some(TimestampPolicy::Latency | TimestampPolicy::StrictPartitionOrder)) => {
+            scx.catalog.system_vars().min_timestamp_interval()
+        }
+        (None, None) => scx.catalog.config().timestamp_interval,
     };
 
+    let retry_policy =
+        if retry_backoff.is_some() || retry_max_backoff.is_some() || retry_max_attempts.is_some() {
+            scx.require_feature_flag(&vars::ENABLE_SOURCE_RETRY_POLICY)?;
+            let default = RetryPolicy::default();
+            let initial_backoff = retry_backoff.unwrap_or(default.initial_backoff);
+            // Without an explicit maximum, the backoff is fixed at its initial
+            // value, which matches the behavior of sources without a policy.
+            let max_backoff = retry_max_backoff.unwrap_or(initial_backoff);
+            if initial_backoff.is_zero() {
+                sql_bail!("RETRY BACKOFF must be greater than zero");
+            }
+            if max_backoff < initial_backoff {
+                sql_bail!("RETRY MAX BACKOFF must be greater than or equal to RETRY BACKOFF");
+            }
+            if retry_max_attempts == Some(0) {
+                sql_bail!("RETRY MAX ATTEMPTS must be greater than zero");
+            }
+            RetryPolicy {
+                initial_backoff,
+                max_backoff,
+                max_attempts: retry_max_attempts,
+            }
+        } else {
+            RetryPolicy::default()
+        };
+
     let source_desc = SourceDesc::<ReferencedConnection> {
         connection: external_connection,
         encoding,
         envelope: envelope.clone(),
         timestamp_interval,
+        timestamp_policy,
+        dead_letter_output: dead_letter_queue.then_some(1),
+        retry_policy,
     };
 
     let progress_subsource = match progress_subsource {
@@ -1985,21 +2186,25 @@ fn get_encoding_inner(
                 .map_err(|e| sql_err!("parsing regex: {e}"))?,
         }),
         Format::Csv { columns, delimiter } => {
-            let columns = match columns {
-                CsvColumns::Header { names } => {
-                    if names.is_empty() {
+            let (columns, casts) = match columns {
+                CsvColumns::Header { columns } => {
+                    if columns.is_empty() {
                         sql_bail!("[internal error] column spec should get names in purify")
                     }
-                    ColumnSpec::Header {
-                        names: names.iter().cloned().map(|n| n.into_string()).collect(),
-                    }
+                    let names = columns
+                        .iter()
+                        .map(|c| c.name.clone().into_string())
+                        .collect();
+                    let types: Vec<_> = columns.iter().map(|c| c.data_type.as_ref()).collect();
+                    (ColumnSpec::Header { names }, plan_csv_casts(scx, &types)?)
                 }
-                CsvColumns::Count(n) => ColumnSpec::Count(usize::cast_from(*n)),
+                CsvColumns::Count(n) => (ColumnSpec::Count(usize::cast_from(*n)), vec![]),
             };
             DataEncoding::Csv(CsvEncoding {
                 columns,
                 delimiter: u8::try_from(*delimiter)
                     .map_err(|_| sql_err!("CSV delimiter must be an ASCII character"))?,
+                casts,
             })
         }
         Format::Json { array: false } => DataEncoding::Json,
@@ -2009,6 +2214,62 @@ fn get_encoding_inner(
     Ok(SourceDataEncoding { key: None, value })
 }
 
+/// Plans the expressions that cast the text values of a CSV record to the
+/// types annotated in `FORMAT CSV WITH HEADER`.
+///
+/// Returns no expressions if none of the columns are annotated, in which case
+/// all columns are presented as text.
+fn plan_csv_casts(
+    scx: &StatementContext,
+    types: &[Option<&ResolvedDataType>],
+) -> Result<Vec<MirScalarExpr>, PlanError> {
+    if types.iter().all(|ty| ty.is_none()) {
+        return Ok(vec![]);
+    }
+    scx.require_feature_flag(&vars::ENABLE_CSV_HEADER_TYPES)?;
+
+    // Cast from an imaginary row of text values, one for each column.
+    let mut cast_scx = scx.clone();
+    cast_scx.param_types = Default::default();
+    let cast_qcx = QueryContext::root(&cast_scx, QueryLifetime::Source);
+    let cast_ecx = ExprContext {
+        qcx: &cast_qcx,
+        name: "plan_csv_cast",
+        scope: &Scope::empty(),
+        relation_type: &RelationType {
+            column_types: vec![ScalarType::String.nullable(false); types.len()],
+            keys: vec![],
+        },
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_parameters: false,
+        allow_windows: false,
+    };
+
+    let mut casts = vec![];
+    for (i, ty) in types.iter().enumerate() {
+        let Some(ty) = ty else {
+            casts.push(MirScalarExpr::Column(i));
+            continue;
+        };
+        let scalar_type = query::scalar_type_from_sql(scx, ty)?;
+        let col_expr = HirScalarExpr::Column(ColumnRef {
+            level: 0,
+            column: i,
+        });
+        let cast = plan_cast(&cast_ecx, CastContext::Explicit, col_expr, &scalar_type)?
+            .lower_uncorrelated()
+            .map_err(|_| {
+                sql_err!(
+                    "CSV columns cannot be cast to type {}",
+                    scx.humanize_scalar_type(&scalar_type)
+                )
+            })?;
+        casts.push(cast);
+    }
+    Ok(casts)
+}
+
 /// Extract the key envelope, if it is requested
 fn get_key_envelope(
     included_items: &[SourceIncludeMetadata],
@@ -2252,6 +2513,7 @@ pub fn plan_create_materialized_view(
         assert_not_null,
         retain_history,
         refresh,
+        memory_limit,
         seen: _,
     }: MaterializedViewOptionExtracted = stmt.with_options.try_into()?;
 
@@ -2381,6 +2643,16 @@ pub fn plan_create_materialized_view(
             Ok::<_, PlanError>(cw.try_into()?)
         })
         .transpose()?;
+    let memory_limit = match memory_limit {
+        Some(limit) => {
+            scx.require_feature_flag(&vars::ENABLE_DATAFLOW_MEMORY_LIMIT)?;
+            if limit.as_bytes() == 0 {
+                sql_bail!("MEMORY LIMIT must be greater than zero");
+            }
+            Some(usize::cast_from(limit.as_bytes()))
+        }
+        None => None,
+    };
     let mut non_null_assertions = assert_not_null
         .into_iter()
         .map(normalize::column_name)
@@ -2478,6 +2750,7 @@ pub fn plan_create_materialized_view(
             compaction_window,
             refresh_schedule,
             as_of,
+            memory_limit,
         },
         replace,
         drop_ids,
@@ -2490,9 +2763,74 @@ generate_extracted_config!(
     MaterializedViewOption,
     (AssertNotNull, Ident, AllowMultiple),
     (RetainHistory, Duration),
-    (Refresh, RefreshOptionValue<Aug>, AllowMultiple)
+    (Refresh, RefreshOptionValue<Aug>, AllowMultiple),
+    (MemoryLimit, ByteSize)
 );
 
+pub fn describe_create_continual_task(
+    _: &StatementContext,
+    _: CreateContinualTaskStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_create_continual_task(
+    scx: &StatementContext,
+    mut stmt: CreateContinualTaskStatement<Aug>,
+    params: &Params,
+) -> Result<Plan, PlanError> {
+    scx.require_feature_flag(&vars::ENABLE_CONTINUAL_TASK_CREATE)?;
+
+    let cluster_id = match &stmt.in_cluster {
+        None => scx.catalog.resolve_cluster(None)?.id(),
+        Some(in_cluster) => in_cluster.id,
+    };
+    stmt.in_cluster = Some(ResolvedClusterName {
+        id: cluster_id,
+        print_name: None,
+    });
+
+    let create_sql =
+        normalize::create_statement(scx, Statement::CreateContinualTask(stmt.clone()))?;
+
+    let CreateContinualTaskStatement {
+        if_not_exists,
+        name,
+        in_cluster: _,
+        target,
+        columns,
+        query,
+    } = stmt;
+    let name = scx.allocate_qualified_name(normalize::unresolved_item_name(name)?)?;
+
+    let (target_id, mut expr, _) =
+        query::plan_insert_query(scx, target, columns, InsertSource::Query(query), Vec::new())?;
+    expr.bind_parameters(params)?;
+
+    // Every change inserted into the target would be fed back into the task.
+    if expr.depends_on().contains(&target_id) {
+        let target = scx.catalog.get_item(&target_id);
+        sql_bail!(
+            "continual task cannot read from its target table {}",
+            scx.catalog
+                .resolve_full_name(target.name())
+                .to_string()
+                .quoted()
+        );
+    }
+
+    Ok(Plan::CreateContinualTask(CreateContinualTaskPlan {
+        name,
+        continual_task: ContinualTask {
+            create_sql,
+            expr,
+            target_id,
+            cluster_id,
+        },
+        if_not_exists,
+    }))
+}
+
 pub fn describe_create_sink(
     _: &StatementContext,
     _: CreateSinkStatement<Aug>,
@@ -2500,7 +2838,7 @@ pub fn describe_create_sink(
     Ok(StatementDesc::new(None))
 }
 
-generate_extracted_config!(CreateSinkOption, (Snapshot, bool));
+generate_extracted_config!(CreateSinkOption, (Snapshot, bool), (PartitionBy, Expr<Aug>));
 
 pub fn plan_create_sink(
     scx: &StatementContext,
@@ -2517,7 +2855,10 @@ pub fn plan_create_sink(
         with_options,
     } = stmt.clone();
 
-    const ALLOWED_WITH_OPTIONS: &[CreateSinkOptionName] = &[CreateSinkOptionName::Snapshot];
+    const ALLOWED_WITH_OPTIONS: &[CreateSinkOptionName] = &[
+        CreateSinkOptionName::Snapshot,
+        CreateSinkOptionName::PartitionBy,
+    ];
 
     if let Some(op) = with_options
         .iter()
@@ -2557,7 +2898,7 @@ pub fn plan_create_sink(
     let from = scx.get_item_by_resolved_name(&from)?;
     let desc = from.desc(&scx.catalog.resolve_full_name(from.name()))?;
     let key_indices = match &connection {
-        CreateSinkConnection::Kafka { key, .. } => {
+        CreateSinkConnection::Kafka { key, .. } | CreateSinkConnection::Iceberg { key, .. } => {
             if let Some(key) = key.clone() {
                 let key_columns = key
                     .key_columns
@@ -2637,10 +2978,17 @@ pub fn plan_create_sink(
         return Err(PlanError::UpsertSinkWithoutKey);
     }
 
+    let CreateSinkOptionExtracted {
+        snapshot,
+        partition_by,
+        seen: _,
+    } = with_options.try_into()?;
+
     let connection_builder = match connection {
         CreateSinkConnection::Kafka {
             connection,
             options,
+            headers,
             ..
         } => kafka_sink_builder(
             scx,
@@ -2649,14 +2997,33 @@ pub fn plan_create_sink(
             format,
             relation_key_indices,
             key_desc_and_indices,
+            headers,
+            partition_by,
             desc.into_owned(),
             envelope,
             from.id(),
         )?,
+        CreateSinkConnection::Iceberg {
+            connection,
+            options,
+            ..
+        } => {
+            if partition_by.is_some() {
+                sql_bail!("PARTITION BY is only supported for Kafka sinks");
+            }
+            iceberg_sink_builder(
+                scx,
+                connection,
+                options,
+                format,
+                relation_key_indices,
+                key_desc_and_indices,
+                desc.into_owned(),
+                envelope,
+            )?
+        }
     };
 
-    let CreateSinkOptionExtracted { snapshot, seen: _ } = with_options.try_into()?;
-
     // WITH SNAPSHOT defaults to true
     let with_snapshot = snapshot.unwrap_or(true);
 
@@ -2712,6 +3079,7 @@ pub struct CsrConfigOptionExtracted {
     pub(crate) null_defaults: bool,
     pub(crate) value_doc_options: BTreeMap<DocTarget, String>,
     pub(crate) key_doc_options: BTreeMap<DocTarget, String>,
+    pub(crate) value_schema_evolution: Option<AvroSchemaEvolution>,
 }
 
 impl std::convert::TryFrom<Vec<CsrConfigOption<Aug>>> for CsrConfigOptionExtracted {
@@ -2743,6 +3111,11 @@ impl std::convert::TryFrom<Vec<CsrConfigOption<Aug>>> for CsrConfigOptionExtract
                     extracted.null_defaults =
                         <bool>::try_from_value(option.value).map_err(better_error)?;
                 }
+                CsrConfigOptionName::ValueSchemaEvolution => {
+                    extracted.value_schema_evolution =
+                        <Option<AvroSchemaEvolution>>::try_from_value(option.value)
+                            .map_err(better_error)?;
+                }
                 CsrConfigOptionName::AvroDocOn(doc_on) => {
                     let value = String::try_from_value(option.value.ok_or_else(|| {
                         PlanError::InvalidOptionValue {
@@ -2800,6 +3173,8 @@ fn kafka_sink_builder(
     format: Option<Format<Aug>>,
     relation_key_indices: Option<Vec<usize>>,
     key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    headers: Option<Ident>,
+    partition_by: Option<Expr<Aug>>,
     value_desc: RelationDesc,
     envelope: SinkEnvelope,
     sink_from: GlobalId,
@@ -2817,6 +3192,9 @@ fn kafka_sink_builder(
 
     let KafkaSinkConfigOptionExtracted {
         topic,
+        topic_config,
+        topic_partition_count,
+        topic_replication_factor,
         compression_type,
         progress_group_id_prefix,
         transactional_id_prefix,
@@ -2842,6 +3220,62 @@ fn kafka_sink_builder(
 
     let topic_name = topic.ok_or_else(|| sql_err!("KAFKA CONNECTION must specify TOPIC"))?;
 
+    if topic_partition_count.is_some_and(|count| count <= 0) {
+        sql_bail!("TOPIC PARTITION COUNT must be greater than 0");
+    }
+    if topic_replication_factor.is_some_and(|factor| factor <= 0) {
+        sql_bail!("TOPIC REPLICATION FACTOR must be greater than 0");
+    }
+    if let Some(key) = topic_config.keys().find(|key| key.is_empty()) {
+        sql_bail!("invalid TOPIC CONFIG key: {}", key.quoted());
+    }
+    let topic_options = KafkaTopicOptions {
+        partition_count: topic_partition_count,
+        replication_factor: topic_replication_factor,
+        topic_config,
+    };
+
+    let headers_index = match headers {
+        Some(headers) => {
+            scx.require_feature_flag(&vars::ENABLE_KAFKA_SINK_HEADERS)?;
+            let headers = normalize::column_name(headers);
+            let (idx, typ) = value_desc
+                .get_by_name(&headers)
+                .ok_or_else(|| sql_err!("HEADERS column ({}) is unknown", headers))?;
+            if value_desc.get_unambiguous_name(idx).is_none() {
+                sql_bail!("HEADERS column ({}) is ambiguous", headers);
+            }
+            if !is_valid_headers_type(&typ.scalar_type) {
+                sql_bail!(
+                    "HEADERS column ({}) must have type map[text => text], map[text => bytea], \
+                    or be a list of records with a text key and a text or bytea value, \
+                    but has type {}",
+                    headers,
+                    scx.humanize_scalar_type(&typ.scalar_type)
+                );
+            }
+            Some(idx)
+        }
+        None => None,
+    };
+
+    let partition_by = match partition_by {
+        Some(partition_by) => {
+            scx.require_feature_flag(&vars::ENABLE_KAFKA_SINK_PARTITION_BY)?;
+            let key_indices = key_desc_and_indices
+                .as_ref()
+                .map(|(_desc, indices)| indices.as_slice());
+            Some(plan_kafka_sink_partition_by(
+                scx,
+                partition_by,
+                &value_desc,
+                key_indices,
+                envelope,
+            )?)
+        }
+        None => None,
+    };
+
     let format = match format {
         Some(Format::Avro(AvroSchema::Csr {
             csr_connection:
@@ -2885,6 +3319,7 @@ fn kafka_sink_builder(
                 null_defaults,
                 key_doc_options,
                 value_doc_options,
+                value_schema_evolution,
                 ..
             } = options.try_into()?;
 
@@ -2928,6 +3363,111 @@ fn kafka_sink_builder(
                 key_schema,
                 value_schema,
                 csr_connection,
+                value_schema_evolution,
+            }
+        }
+        Some(Format::Protobuf(schema)) => {
+            scx.require_feature_flag(&vars::ENABLE_PROTOBUF_SINK)?;
+
+            let key_columns = key_desc_and_indices
+                .as_ref()
+                .map(|(desc, _indices)| column_names_and_types(desc.clone()));
+            let mut value_columns = column_names_and_types(value_desc.clone());
+            if matches!(envelope, SinkEnvelope::Debezium) {
+                value_columns = envelopes::dbz_envelope(value_columns);
+            }
+
+            let derive_message = |message_name: &str, columns: &[(ColumnName, ColumnType)]| {
+                let (schema, descriptors) = protobuf::derive_schema(message_name, columns)
+                    .map_err(|e| sql_err!("deriving protobuf schema: {:#}", e))?;
+                Ok::<_, PlanError>(ProtobufSinkMessage {
+                    descriptors,
+                    message_name: format!("{}.{message_name}", protobuf::SINK_PACKAGE),
+                    schema: Some(schema),
+                })
+            };
+            let key = key_columns
+                .as_deref()
+                .map(|columns| derive_message("Key", columns))
+                .transpose()?;
+
+            let (value, csr_connection) = match schema {
+                ProtobufSchema::Csr {
+                    csr_connection:
+                        CsrConnectionProtobuf {
+                            connection:
+                                CsrConnection {
+                                    connection,
+                                    options,
+                                },
+                            seed,
+                        },
+                } => {
+                    if seed.is_some() {
+                        sql_bail!("SEED option does not make sense with sinks");
+                    }
+                    if !options.is_empty() {
+                        sql_bail!("Protobuf CSR connections do not support any options");
+                    }
+
+                    let item = scx.get_item_by_resolved_name(&connection)?;
+                    let csr_connection = match item.connection()? {
+                        Connection::Csr(_) => item.id(),
+                        _ => {
+                            sql_bail!(
+                                "{} is not a schema registry connection",
+                                scx.catalog
+                                    .resolve_full_name(item.name())
+                                    .to_string()
+                                    .quoted()
+                            )
+                        }
+                    };
+                    let message_name = match envelope {
+                        SinkEnvelope::Debezium => "Envelope",
+                        SinkEnvelope::Upsert => "Row",
+                    };
+                    (
+                        derive_message(message_name, &value_columns)?,
+                        Some(csr_connection),
+                    )
+                }
+                ProtobufSchema::InlineSchema {
+                    message_name,
+                    schema: ast::Schema { schema },
+                } => {
+                    let value = ProtobufSinkMessage {
+                        descriptors: strconv::parse_bytes(&schema)?,
+                        message_name,
+                        schema: None,
+                    };
+                    (value, None)
+                }
+            };
+
+            // Verify up front that the rows of the sink can be encoded as the
+            // messages, so that the sink can rely on it when it is rendered.
+            if let (Some(key), Some(key_columns)) = (&key, key_columns) {
+                protobuf::MessageEncoder::new(
+                    &key.descriptors,
+                    &key.message_name,
+                    key_columns,
+                    None,
+                )
+                .map_err(|e| sql_err!("{:#}", e))?;
+            }
+            protobuf::MessageEncoder::new(
+                &value.descriptors,
+                &value.message_name,
+                value_columns,
+                None,
+            )
+            .map_err(|e| sql_err!("{:#}", e))?;
+
+            KafkaSinkFormat::Protobuf {
+                key,
+                value,
+                csr_connection,
             }
         }
         Some(Format::Json { array: false }) => KafkaSinkFormat::Json,
@@ -2941,8 +3481,11 @@ fn kafka_sink_builder(
         connection: connection_id,
         format,
         topic: topic_name,
+        topic_options,
         relation_key_indices,
         key_desc_and_indices,
+        headers_index,
+        partition_by,
         value_desc,
         compression_type,
         progress_group_id,
@@ -2950,6 +3493,165 @@ fn kafka_sink_builder(
     }))
 }
 
+/// Plans the `PARTITION BY` expression of a Kafka sink.
+///
+/// The expression is cast to `text`, whose hash determines the partition of
+/// each message. With the upsert envelope, the expression is rewritten to
+/// evaluate over the key of each message, so that all updates to a key,
+/// including its deletion, land on the same partition. With the Debezium
+/// envelope, it evaluates over the sinked row.
+fn plan_kafka_sink_partition_by(
+    scx: &StatementContext,
+    mut expr: Expr<Aug>,
+    value_desc: &RelationDesc,
+    key_indices: Option<&[usize]>,
+    envelope: SinkEnvelope,
+) -> Result<MirScalarExpr, PlanError> {
+    transform_ast::transform(scx, &mut expr)?;
+    let qcx = QueryContext::root(scx, QueryLifetime::Source);
+    let ecx = ExprContext {
+        qcx: &qcx,
+        name: "PARTITION BY",
+        scope: &Scope::from_source(None, value_desc.iter_names()),
+        relation_type: value_desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_parameters: false,
+        allow_windows: false,
+    };
+    let mut expr = plan_expr(&ecx, &expr)?
+        .cast_to(&ecx, CastContext::Explicit, &ScalarType::String)?
+        .lower_uncorrelated()?;
+    if expr.contains_temporal() || expr.contains_unmaterializable() {
+        sql_bail!("PARTITION BY expression cannot call mz_now() or unmaterializable functions");
+    }
+
+    if envelope == SinkEnvelope::Upsert {
+        let key_indices = key_indices.expect("upsert sinks have a key");
+        let mut permutation = BTreeMap::new();
+        for column in expr.support() {
+            let Some(key_column) = key_indices.iter().position(|i| *i == column) else {
+                sql_bail!(
+                    "PARTITION BY expression of an upsert sink can only reference KEY columns, \
+                    but references column {}",
+                    value_desc.get_name(column).as_str().quoted()
+                );
+            };
+            permutation.insert(column, key_column);
+        }
+        expr.permute_map(&permutation);
+    }
+    Ok(expr)
+}
+
+/// Reports whether a column of type `typ` can provide the headers of the
+/// messages of a Kafka sink.
+///
+/// Headers are either a map from header keys to values, or a list of
+/// key-value records, which allows repeating a key.
+fn is_valid_headers_type(typ: &ScalarType) -> bool {
+    let is_valid_value_type =
+        |typ: &ScalarType| matches!(typ, ScalarType::String | ScalarType::Bytes);
+    match typ {
+        ScalarType::Map { value_type, .. } => is_valid_value_type(value_type),
+        ScalarType::List { element_type, .. } => match &**element_type {
+            ScalarType::Record { fields, .. } => match fields.as_slice() {
+                [(_, key), (_, value)] => {
+                    key.scalar_type == ScalarType::String && is_valid_value_type(&value.scalar_type)
+                }
+                _ => false,
+            },
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+generate_extracted_config!(
+    IcebergSinkConfigOption,
+    (CommitInterval, Duration, Default(Duration::from_secs(60))),
+    (Namespace, String),
+    (Table, String)
+);
+
+fn iceberg_sink_builder(
+    scx: &StatementContext,
+    connection: ResolvedItemName,
+    options: Vec<IcebergSinkConfigOption<Aug>>,
+    format: Option<Format<Aug>>,
+    relation_key_indices: Option<Vec<usize>>,
+    key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    value_desc: RelationDesc,
+    envelope: SinkEnvelope,
+) -> Result<StorageSinkConnection<ReferencedConnection>, PlanError> {
+    scx.require_feature_flag(&vars::ENABLE_ICEBERG_SINK)?;
+
+    // Get Iceberg catalog connection.
+    let connection_item = scx.get_item_by_resolved_name(&connection)?;
+    let connection_id = connection_item.id();
+    match connection_item.connection()? {
+        Connection::IcebergCatalog(_) => (),
+        _ => sql_bail!(
+            "{} is not an iceberg catalog connection",
+            scx.catalog.resolve_full_name(connection_item.name())
+        ),
+    };
+
+    let IcebergSinkConfigOptionExtracted {
+        commit_interval,
+        namespace,
+        table,
+        seen: _,
+    }: IcebergSinkConfigOptionExtracted = options.try_into()?;
+
+    let namespace =
+        namespace.ok_or_else(|| sql_err!("ICEBERG CATALOG CONNECTION must specify NAMESPACE"))?;
+    let namespace: Vec<_> = namespace.split('.').map(String::from).collect();
+    if namespace.iter().any(|level| level.is_empty()) {
+        sql_bail!("invalid NAMESPACE: levels must be non-empty");
+    }
+    let table = table.ok_or_else(|| sql_err!("ICEBERG CATALOG CONNECTION must specify TABLE"))?;
+    if commit_interval.is_zero() {
+        sql_bail!("COMMIT INTERVAL must be greater than zero");
+    }
+
+    if format.is_some() {
+        sql_bail!("Iceberg sinks do not support FORMAT; rows are written as Parquet");
+    }
+    if envelope != SinkEnvelope::Upsert {
+        sql_bail!("Iceberg sinks require ENVELOPE UPSERT");
+    }
+    let Some(key_desc_and_indices) = key_desc_and_indices else {
+        return Err(PlanError::UpsertSinkWithoutKey);
+    };
+
+    // Iceberg has no unsigned integer types, and silently reinterpreting
+    // large values as negative numbers would corrupt the table.
+    for (name, typ) in value_desc.iter() {
+        if matches!(
+            typ.scalar_type,
+            ScalarType::UInt16 | ScalarType::UInt32 | ScalarType::UInt64
+        ) {
+            sql_bail!(
+                "column {} has type {}, which Iceberg sinks do not support",
+                name.as_str().quoted(),
+                scx.humanize_scalar_type(&typ.scalar_type)
+            );
+        }
+    }
+
+    Ok(StorageSinkConnection::Iceberg(IcebergSinkConnection {
+        connection_id,
+        connection: connection_id,
+        namespace,
+        table,
+        key_desc_and_indices,
+        relation_key_indices,
+        value_desc,
+        commit_interval,
+    }))
+}
+
 pub fn describe_create_index(
     _: &StatementContext,
     _: CreateIndexStatement<Aug>,
@@ -3237,6 +3939,83 @@ generate_extracted_config!(
     (ValueType, ResolvedDataType)
 );
 
+pub fn describe_create_function(
+    _: &StatementContext,
+    _: CreateFunctionStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_create_function(
+    scx: &StatementContext,
+    stmt: CreateFunctionStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    scx.require_feature_flag(&vars::ENABLE_CREATE_FUNCTION)?;
+
+    let create_sql = normalize::create_statement(scx, Statement::CreateFunction(stmt.clone()))?;
+    let CreateFunctionStatement {
+        name,
+        params,
+        returns,
+        body: _,
+    } = stmt;
+
+    let mut param_names = BTreeSet::new();
+    let mut planned_params = vec![];
+    for param in params {
+        let name = param.name.map(normalize::column_name);
+        if let Some(name) = &name {
+            if !param_names.insert(name.clone()) {
+                sql_bail!(
+                    "parameter name {} used more than once",
+                    name.as_str().quoted()
+                );
+            }
+        }
+        let typ = query::scalar_type_from_sql(scx, &param.data_type)?;
+        if typ.is_custom_type() {
+            bail_unsupported!("custom types as function parameters");
+        }
+        planned_params.push((name, typ));
+    }
+
+    let mut desc = RelationDesc::empty();
+    for column in returns {
+        let name = normalize::column_name(column.name);
+        if desc.iter_names().any(|n| *n == name) {
+            sql_bail!("column {} specified more than once", name.as_str().quoted());
+        }
+        let typ = query::scalar_type_from_sql(scx, &column.data_type)?;
+        desc = desc.with_column(name, typ.nullable(true));
+    }
+
+    // The body is planned whenever the function is called. Use the body in
+    // `create_sql`, whose names are resolved to IDs, so that the function
+    // continues to refer to the same objects if they are renamed, and plan it
+    // once now to report any errors in it.
+    let body = match crate::parse::parse(&create_sql)?.into_element().ast {
+        Statement::CreateFunction(stmt) => stmt.body,
+        _ => unreachable!("create_sql is a CREATE FUNCTION statement"),
+    };
+    query::plan_function_body(
+        scx,
+        QueryLifetime::View,
+        &planned_params,
+        &desc,
+        body.clone(),
+    )?;
+
+    let name = scx.allocate_qualified_name(normalize::unresolved_item_name(name)?)?;
+
+    Ok(Plan::CreateFunction(CreateFunctionPlan {
+        name,
+        function: Function {
+            create_sql,
+            inner: func::user_table_func(planned_params, desc, body),
+        },
+    }))
+}
+
 #[derive(Debug)]
 pub enum PlannedAlterRoleOption {
     Attributes(PlannedRoleAttributes),
@@ -3246,10 +4025,16 @@ pub enum PlannedAlterRoleOption {
 #[derive(Debug)]
 pub struct PlannedRoleAttributes {
     pub inherit: Option<bool>,
+    /// The SCRAM-SHA-256 verifier for the role's new password, or `Some(None)`
+    /// if the role's password should be removed.
+    pub password: Option<Option<String>>,
 }
 
 fn plan_role_attributes(options: Vec<RoleAttribute>) -> Result<PlannedRoleAttributes, PlanError> {
-    let mut planned_attributes = PlannedRoleAttributes { inherit: None };
+    let mut planned_attributes = PlannedRoleAttributes {
+        inherit: None,
+        password: None,
+    };
 
     for option in options {
         match option {
@@ -3286,8 +4071,15 @@ fn plan_role_attributes(options: Vec<RoleAttribute>) -> Result<PlannedRoleAttrib
                 );
             }
 
+            RoleAttribute::Password(_) if planned_attributes.password.is_some() => {
+                sql_bail!("conflicting or redundant options");
+            }
+
             RoleAttribute::Inherit => planned_attributes.inherit = Some(true),
             RoleAttribute::NoInherit => planned_attributes.inherit = Some(false),
+            RoleAttribute::Password(password) => {
+                planned_attributes.password = Some(password.map(hash_password).transpose()?)
+            }
         }
     }
     if planned_attributes.inherit == Some(false) {
@@ -3297,6 +4089,23 @@ fn plan_role_attributes(options: Vec<RoleAttribute>) -> Result<PlannedRoleAttrib
     Ok(planned_attributes)
 }
 
+/// Computes the SCRAM-SHA-256 verifier to store for `password`.
+///
+/// As in PostgreSQL, a password that is already a SCRAM-SHA-256 verifier is
+/// stored as-is, which permits setting a password without revealing it.
+fn hash_password(password: String) -> Result<String, PlanError> {
+    if password.is_empty() {
+        sql_bail!("password must not be empty");
+    }
+    if ScramVerifier::parse(&password).is_some() {
+        return Ok(password);
+    }
+    match ScramVerifier::new(&password) {
+        Ok(verifier) => Ok(verifier.to_string()),
+        Err(e) => sql_bail!("unable to hash password: {e}"),
+    }
+}
+
 #[derive(Debug)]
 pub enum PlannedRoleVariable {
     Set { name: String, value: VariableValue },
@@ -3358,8 +4167,10 @@ generate_extracted_config!(
     (IntrospectionDebugging, bool),
     (IntrospectionInterval, OptionalDuration),
     (Managed, bool),
+    (MaxConcurrentQueries, u32),
     (Replicas, Vec<ReplicaDefinition<Aug>>),
     (ReplicationFactor, u32),
+    (Schedule, ClusterSchedule),
     (Size, String)
 );
 
@@ -3386,8 +4197,10 @@ pub fn plan_create_cluster(
         introspection_debugging,
         introspection_interval,
         managed,
+        max_concurrent_queries,
         replicas,
         replication_factor,
+        schedule,
         seen: _,
         size,
         disk: disk_in,
@@ -3411,6 +4224,15 @@ pub fn plan_create_cluster(
         if disk_in.is_some() {
             scx.require_feature_flag(&vars::ENABLE_DISK_CLUSTER_REPLICAS)?;
         }
+        if schedule.is_some() {
+            scx.require_feature_flag(&vars::ENABLE_CLUSTER_SCHEDULES)?;
+        }
+        if let Some(max_concurrent_queries) = max_concurrent_queries {
+            scx.require_feature_flag(&vars::ENABLE_CLUSTER_QUERY_QUEUEING)?;
+            if max_concurrent_queries == 0 {
+                sql_bail!("MAX CONCURRENT QUERIES must be greater than 0");
+            }
+        }
 
         let compute = plan_compute_replica_config(
             introspection_interval,
@@ -3468,6 +4290,8 @@ pub fn plan_create_cluster(
                 compute,
                 disk,
                 optimizer_feature_overrides,
+                schedule: schedule.unwrap_or_default(),
+                max_concurrent_queries,
             }),
         }))
     } else {
@@ -3495,6 +4319,12 @@ pub fn plan_create_cluster(
         if disk_in.is_some() {
             sql_bail!("DISK not supported for unmanaged clusters");
         }
+        if schedule.is_some() {
+            sql_bail!("SCHEDULE not supported for unmanaged clusters");
+        }
+        if max_concurrent_queries.is_some() {
+            sql_bail!("MAX CONCURRENT QUERIES not supported for unmanaged clusters");
+        }
         if !features.is_empty() {
             sql_bail!("FEATURES not supported for unmanaged clusters");
         }
@@ -3753,11 +4583,19 @@ pub fn plan_create_secret(
         name,
         if_not_exists,
         value,
+        external,
     } = &stmt;
 
+    if *external {
+        scx.require_feature_flag(&vars::ENABLE_EXTERNAL_SECRETS)?;
+    }
+
     let name = scx.allocate_qualified_name(normalize::unresolved_item_name(name.to_owned())?)?;
     let mut create_sql_statement = stmt.clone();
     create_sql_statement.value = Expr::Value(Value::String("********".to_string()));
+    // Whether a secret is external can change with `ALTER SECRET`, so it is not recorded in the
+    // catalog.
+    create_sql_statement.external = false;
     let create_sql =
         normalize::create_statement(scx, Statement::CreateSecret(create_sql_statement))?;
     let secret_as = query::plan_secret_as(scx, value.clone())?;
@@ -3765,6 +4603,7 @@ pub fn plan_create_secret(
     let secret = Secret {
         create_sql,
         secret_as,
+        external: *external,
     };
 
     Ok(Plan::CreateSecret(CreateSecretPlan {
@@ -3862,10 +4701,14 @@ fn plan_drop_database(
 }
 
 pub fn describe_drop_objects(
-    _: &StatementContext,
-    _: DropObjectsStatement,
+    scx: &StatementContext,
+    DropObjectsStatement { dry_run, .. }: DropObjectsStatement,
 ) -> Result<StatementDesc, PlanError> {
-    Ok(StatementDesc::new(None))
+    if dry_run {
+        show::show_object_ids(scx, &[], None)?.describe()
+    } else {
+        Ok(StatementDesc::new(None))
+    }
 }
 
 pub fn plan_drop_objects(
@@ -3875,13 +4718,9 @@ pub fn plan_drop_objects(
         if_exists,
         names,
         cascade,
+        dry_run,
     }: DropObjectsStatement,
 ) -> Result<Plan, PlanError> {
-    assert_ne!(
-        object_type,
-        mz_sql_parser::ast::ObjectType::Func,
-        "rejected in parser"
-    );
     let object_type = object_type.into();
 
     let mut referenced_ids = Vec::new();
@@ -3917,6 +4756,11 @@ pub fn plan_drop_objects(
     }
     let drop_ids = scx.catalog.object_dependents(&referenced_ids);
 
+    // A dry run lists the objects that would be dropped instead of dropping them.
+    if dry_run {
+        return show::show_object_ids(scx, &drop_ids, None)?.plan();
+    }
+
     Ok(Plan::DropObjects(DropObjectsPlan {
         referenced_ids,
         drop_ids,
@@ -4174,7 +5018,8 @@ fn dependency_prevents_drop(object_type: ObjectType, dep: &dyn CatalogItem) -> b
             | CatalogItemType::Sink
             | CatalogItemType::Type
             | CatalogItemType::Secret
-            | CatalogItemType::Connection => true,
+            | CatalogItemType::Connection
+            | CatalogItemType::ContinualTask => true,
             CatalogItemType::Index => false,
         },
     }
@@ -4547,8 +5392,10 @@ pub fn plan_alter_cluster(
                 introspection_debugging,
                 introspection_interval,
                 managed,
+                max_concurrent_queries,
                 replicas: replica_defs,
                 replication_factor,
+                schedule,
                 seen: _,
                 size,
                 disk,
@@ -4602,6 +5449,12 @@ pub fn plan_alter_cluster(
                     if disk.is_some() {
                         sql_bail!("DISK not supported for unmanaged clusters");
                     }
+                    if schedule.is_some() {
+                        sql_bail!("SCHEDULE not supported for unmanaged clusters");
+                    }
+                    if max_concurrent_queries.is_some() {
+                        sql_bail!("MAX CONCURRENT QUERIES not supported for unmanaged clusters");
+                    }
                 }
             }
 
@@ -4669,6 +5522,17 @@ pub fn plan_alter_cluster(
                 }
                 options.disk = AlterOptionParameter::Set(disk);
             }
+            if let Some(schedule) = schedule {
+                scx.require_feature_flag(&vars::ENABLE_CLUSTER_SCHEDULES)?;
+                options.schedule = AlterOptionParameter::Set(schedule);
+            }
+            if let Some(max_concurrent_queries) = max_concurrent_queries {
+                scx.require_feature_flag(&vars::ENABLE_CLUSTER_QUERY_QUEUEING)?;
+                if max_concurrent_queries == 0 {
+                    sql_bail!("MAX CONCURRENT QUERIES must be greater than 0");
+                }
+                options.max_concurrent_queries = AlterOptionParameter::Set(max_concurrent_queries);
+            }
             if !replicas.is_empty() {
                 options.replicas = AlterOptionParameter::Set(replicas);
             }
@@ -4684,8 +5548,10 @@ pub fn plan_alter_cluster(
                     IntrospectionDebugging => options.introspection_debugging = Reset,
                     IdleArrangementMergeEffort => options.idle_arrangement_merge_effort = Reset,
                     Managed => options.managed = Reset,
+                    MaxConcurrentQueries => options.max_concurrent_queries = Reset,
                     Replicas => options.replicas = Reset,
                     ReplicationFactor => options.replication_factor = Reset,
+                    Schedule => options.schedule = Reset,
                     Size => options.size = Reset,
                 }
             }
@@ -4698,6 +5564,99 @@ pub fn plan_alter_cluster(
     }))
 }
 
+pub fn describe_alter_cluster_replica_set_options(
+    _: &StatementContext,
+    _: AlterClusterReplicaStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_cluster_replica(
+    scx: &mut StatementContext,
+    AlterClusterReplicaStatement {
+        if_exists,
+        name,
+        action,
+    }: AlterClusterReplicaStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    let (cluster, replica_id) = match resolve_cluster_replica(scx, &name, if_exists)? {
+        Some(entry) => entry,
+        None => {
+            scx.catalog.add_notice(PlanNotice::ObjectDoesNotExist {
+                name: name.to_ast_string(),
+                object_type: ObjectType::ClusterReplica,
+            });
+
+            return Ok(Plan::AlterNoop(AlterNoopPlan {
+                object_type: ObjectType::ClusterReplica,
+            }));
+        }
+    };
+    ensure_cluster_is_not_managed(scx, cluster.id())?;
+
+    let mut introspection_debugging = AlterOptionParameter::Unchanged;
+    let mut introspection_interval = AlterOptionParameter::Unchanged;
+
+    let option_names: Vec<_> = match &action {
+        AlterClusterReplicaAction::SetOptions(options) => {
+            options.iter().map(|o| o.name.clone()).collect()
+        }
+        AlterClusterReplicaAction::ResetOptions(names) => names.clone(),
+    };
+    for option_name in option_names {
+        match option_name {
+            ReplicaOptionName::IntrospectionDebugging
+            | ReplicaOptionName::IntrospectionInterval => {}
+            option_name => sql_bail!(
+                "Cannot change {} of cluster replicas",
+                option_name.to_ast_string()
+            ),
+        }
+    }
+
+    match action {
+        AlterClusterReplicaAction::SetOptions(options) => {
+            let ReplicaOptionExtracted {
+                introspection_debugging: debugging,
+                introspection_interval: interval,
+                seen,
+                ..
+            }: ReplicaOptionExtracted = options.try_into()?;
+
+            if seen.contains(&ReplicaOptionName::IntrospectionDebugging) {
+                introspection_debugging = AlterOptionParameter::Set(debugging);
+            }
+            if let Some(interval) = interval {
+                introspection_interval = AlterOptionParameter::Set(interval);
+            }
+        }
+        AlterClusterReplicaAction::ResetOptions(option_names) => {
+            for option_name in option_names {
+                match option_name {
+                    ReplicaOptionName::IntrospectionDebugging => {
+                        introspection_debugging = AlterOptionParameter::Reset
+                    }
+                    ReplicaOptionName::IntrospectionInterval => {
+                        introspection_interval = AlterOptionParameter::Reset
+                    }
+                    _ => unreachable!("checked above"),
+                }
+            }
+        }
+    }
+
+    Ok(Plan::AlterClusterReplica(AlterClusterReplicaPlan {
+        cluster_id: cluster.id(),
+        replica_id,
+        name: QualifiedReplica {
+            cluster: Ident::new(cluster.name())?,
+            replica: name.replica,
+        },
+        introspection_debugging,
+        introspection_interval,
+    }))
+}
+
 pub fn describe_alter_set_cluster(
     _: &StatementContext,
     _: AlterSetClusterStatement<Aug>,
@@ -4720,8 +5679,8 @@ pub fn plan_alter_item_set_cluster(
 
     // Prevent access to `SET CLUSTER` for unsupported objects.
     match object_type {
-        ObjectType::MaterializedView => {}
-        ObjectType::Index | ObjectType::Sink | ObjectType::Source => {
+        ObjectType::MaterializedView | ObjectType::Index => {}
+        ObjectType::Sink | ObjectType::Source => {
             bail_unsupported!(20841, format!("ALTER {object_type} SET CLUSTER"))
         }
         _ => {
@@ -5115,7 +6074,11 @@ pub fn plan_alter_secret(
         name,
         if_exists,
         value,
+        external,
     } = stmt;
+    if external {
+        scx.require_feature_flag(&vars::ENABLE_EXTERNAL_SECRETS)?;
+    }
     let object_type = ObjectType::Secret;
     let id = match resolve_item_or_type(scx, object_type, name.clone(), if_exists)? {
         Some(entry) => entry.id(),
@@ -5131,7 +6094,11 @@ pub fn plan_alter_secret(
 
     let secret_as = query::plan_secret_as(scx, value)?;
 
-    Ok(Plan::AlterSecret(AlterSecretPlan { id, secret_as }))
+    Ok(Plan::AlterSecret(AlterSecretPlan {
+        id,
+        secret_as,
+        external,
+    }))
 }
 
 pub fn describe_alter_connection(
@@ -5242,6 +6209,7 @@ pub fn plan_alter_connection(
         Connection::Postgres(_) => CreateConnectionType::Postgres,
         Connection::Ssh(_) => CreateConnectionType::Ssh,
         Connection::MySql(_) => CreateConnectionType::MySql,
+        Connection::IcebergCatalog(_) => CreateConnectionType::IcebergCatalog,
     };
 
     // Collect all options irrespective of action taken on them.
@@ -5355,13 +6323,29 @@ pub fn plan_alter_sink(
     let AlterSinkStatement {
         sink_name,
         if_exists,
-        action: _,
+        action,
     } = stmt;
 
     let object_type = ObjectType::Sink;
-    let _ = resolve_item_or_type(scx, object_type, sink_name, if_exists)?;
+    let entry = resolve_item_or_type(scx, object_type, sink_name.clone(), if_exists)?;
 
-    bail_unsupported!("ALTER SINK");
+    match action {
+        AlterSinkAction::ResetOffset => {
+            let Some(entry) = entry else {
+                scx.catalog.add_notice(PlanNotice::ObjectDoesNotExist {
+                    name: sink_name.to_string(),
+                    object_type,
+                });
+                return Ok(Plan::AlterNoop(AlterNoopPlan { object_type }));
+            };
+            Ok(Plan::AlterSinkResetOffset(AlterSinkResetOffsetPlan {
+                id: entry.id(),
+            }))
+        }
+        AlterSinkAction::SetOptions(_) | AlterSinkAction::ResetOptions(_) => {
+            bail_unsupported!("ALTER SINK")
+        }
+    }
 }
 
 pub fn describe_alter_source(
@@ -5551,6 +6535,43 @@ pub fn plan_alter_role(
     }))
 }
 
+pub fn describe_alter_quota(
+    _: &StatementContext,
+    _: AlterQuotaStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_quota(
+    _scx: &StatementContext,
+    AlterQuotaStatement {
+        subject,
+        quota,
+        limit,
+    }: AlterQuotaStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    let subject = match subject {
+        QuotaSubjectSpecification::Role(name) => QuotaSubject::Role(name.id),
+        QuotaSubjectSpecification::Database(name) => QuotaSubject::Database(*name.database_id()),
+    };
+    let kind: QuotaKind = quota
+        .as_str()
+        .parse()
+        .map_err(|e: String| sql_err!("{}", e))?;
+    if !kind.applies_to(&subject) {
+        sql_bail!(
+            "quota {} cannot be set for a {}",
+            kind.as_str().quoted(),
+            subject.subject_type()
+        );
+    }
+    Ok(Plan::AlterQuota(AlterQuotaPlan {
+        subject,
+        kind,
+        limit,
+    }))
+}
+
 pub fn describe_comment(
     _: &StatementContext,
     _: CommentStatement<Aug>,
@@ -5587,7 +6608,8 @@ pub fn plan_comment(
         | com_ty @ CommentObjectType::Connection { name }
         | com_ty @ CommentObjectType::Source { name }
         | com_ty @ CommentObjectType::Sink { name }
-        | com_ty @ CommentObjectType::Secret { name } => {
+        | com_ty @ CommentObjectType::Secret { name }
+        | com_ty @ CommentObjectType::ContinualTask { name } => {
             let item = scx.get_item_by_resolved_name(name)?;
             match (com_ty, item.item_type()) {
                 (CommentObjectType::Table { .. }, CatalogItemType::Table) => {
@@ -5617,6 +6639,9 @@ pub fn plan_comment(
                 (CommentObjectType::Secret { .. }, CatalogItemType::Secret) => {
                     (CommentObjectId::Secret(item.id()), None)
                 }
+                (CommentObjectType::ContinualTask { .. }, CatalogItemType::ContinualTask) => {
+                    (CommentObjectId::ContinualTask(item.id()), None)
+                }
                 (com_ty, cat_ty) => {
                     let expected_type = match com_ty {
                         CommentObjectType::Table { .. } => ObjectType::Table,
@@ -5628,6 +6653,7 @@ pub fn plan_comment(
                         CommentObjectType::Source { .. } => ObjectType::Source,
                         CommentObjectType::Sink { .. } => ObjectType::Sink,
                         CommentObjectType::Secret { .. } => ObjectType::Secret,
+                        CommentObjectType::ContinualTask { .. } => ObjectType::ContinualTask,
                         _ => unreachable!("these are the only types we match on"),
                     };
 
@@ -5771,6 +6797,7 @@ pub(crate) fn resolve_item_or_type<'a>(
     let name = normalize::unresolved_item_name(name)?;
     let catalog_item = match object_type {
         ObjectType::Type => scx.catalog.resolve_type(&name),
+        ObjectType::Func => scx.catalog.resolve_function(&name),
         _ => scx.catalog.resolve_item(&name),
     };
 
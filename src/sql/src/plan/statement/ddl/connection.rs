@@ -14,6 +14,7 @@ use std::collections::{BTreeMap, BTreeSet};
 use array_concat::concat_arrays;
 use itertools::Itertools;
 use mz_ore::str::StrExt;
+use mz_repr::GlobalId;
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::ConnectionOptionName::*;
 use mz_sql_parser::ast::{
@@ -22,6 +23,7 @@ use mz_sql_parser::ast::{
     KafkaBrokerTunnel,
 };
 use mz_storage_types::connections::aws::{AwsAssumeRole, AwsAuth, AwsConnection, AwsCredentials};
+use mz_storage_types::connections::iceberg::{AwsConnectionReference, IcebergCatalogConnection};
 use mz_storage_types::connections::inline::ReferencedConnection;
 use mz_storage_types::connections::{
     AwsPrivatelink, AwsPrivatelinkConnection, CsrConnection, CsrConnectionHttpAuth,
@@ -40,6 +42,7 @@ generate_extracted_config!(
     (AssumeRoleArn, String),
     (AssumeRoleSessionName, String),
     (AvailabilityZones, Vec<String>),
+    (AwsConnection, with_options::Object),
     (AwsPrivatelink, ConnectionDefaultAwsPrivatelink<Aug>),
     // (AwsPrivatelink, with_options::Object),
     (Broker, Vec<KafkaBroker<Aug>>),
@@ -63,8 +66,10 @@ generate_extracted_config!(
     (SslKey, with_options::Secret),
     (SslMode, String),
     (SessionToken, StringOrSecret),
+    (Token, with_options::Secret),
     (Url, String),
-    (User, StringOrSecret)
+    (User, StringOrSecret),
+    (Warehouse, String)
 );
 
 generate_extracted_config!(
@@ -147,6 +152,7 @@ pub(super) fn validate_options_per_connection_type(
             SslMode,
             User,
         ],
+        CreateConnectionType::IcebergCatalog => &[AwsConnection, Token, Url, Warehouse],
     };
 
     for o in permitted_options {
@@ -429,6 +435,37 @@ impl ConnectionOptionExtracted {
                         .ok_or_else(|| sql_err!("USER option is required"))?,
                 })
             }
+            CreateConnectionType::IcebergCatalog => {
+                scx.require_feature_flag(&crate::session::vars::ENABLE_ICEBERG_SINK)?;
+
+                let url: reqwest::Url = match self.url {
+                    Some(url) => url
+                        .parse()
+                        .map_err(|e| sql_err!("parsing Iceberg catalog url: {e}"))?,
+                    None => sql_bail!("invalid CONNECTION: must specify URL"),
+                };
+                let aws_connection = match self.aws_connection {
+                    Some(aws_connection) => {
+                        let id = GlobalId::from(aws_connection);
+                        let item = scx.catalog.get_item(&id);
+                        match item.connection()? {
+                            Connection::Aws(_) => AwsConnectionReference {
+                                connection_id: id,
+                                connection: id,
+                            },
+                            _ => sql_bail!("{} is not an AWS connection", item.name().item),
+                        }
+                    }
+                    None => sql_bail!("invalid CONNECTION: must specify AWS CONNECTION"),
+                };
+
+                Connection::IcebergCatalog(IcebergCatalogConnection {
+                    url,
+                    warehouse: self.warehouse,
+                    token: self.token.map(|token| token.into()),
+                    aws_connection,
+                })
+            }
         };
 
         Ok(connection)
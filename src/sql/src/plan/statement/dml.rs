@@ -355,7 +355,8 @@ generate_extracted_config!(
     (EnableNewOuterJoinLowering, Option<bool>, Default(None)),
     (EnableEagerDeltaJoins, Option<bool>, Default(None)),
     (EnableEquivalencePropagation, Option<bool>, Default(None)),
-    (EnableVariadicLeftJoinLowering, Option<bool>, Default(None))
+    (EnableVariadicLeftJoinLowering, Option<bool>, Default(None)),
+    (EnableLeftJoinKeySharing, Option<bool>, Default(None))
 );
 
 impl TryFrom<ExplainPlanOptionExtracted> for ExplainConfig {
@@ -400,6 +401,7 @@ impl TryFrom<ExplainPlanOptionExtracted> for ExplainConfig {
                 enable_equivalence_propagation: v.enable_equivalence_propagation,
                 enable_new_outer_join_lowering: v.enable_new_outer_join_lowering,
                 enable_variadic_left_join_lowering: v.enable_variadic_left_join_lowering,
+                enable_left_join_key_sharing: v.enable_left_join_key_sharing,
                 reoptimize_imported_views: v.reoptimize_imported_views,
                 ..Default::default()
             },
@@ -964,15 +966,17 @@ fn plan_copy_to(
         _ => sql_bail!("only AWS CONNECTION is supported for COPY ... TO <expr>"),
     }
 
-    if format != CopyFormat::Csv {
-        sql_bail!("only CSV format is supported for COPY ... TO <expr>");
-    }
-
-    // TODO(mouli): Get these from sql options
-    let format_params = CopyFormatParams::Csv(
-        CopyCsvFormatParams::try_new(None, None, None, None, None)
-            .map_err(|e| sql_err!("{}", e))?,
-    );
+    let format_params = match format {
+        // TODO(mouli): Get these from sql options
+        CopyFormat::Csv => CopyFormatParams::Csv(
+            CopyCsvFormatParams::try_new(None, None, None, None, None)
+                .map_err(|e| sql_err!("{}", e))?,
+        ),
+        CopyFormat::Parquet => CopyFormatParams::Parquet,
+        CopyFormat::Text | CopyFormat::Binary => {
+            sql_bail!("only CSV and PARQUET formats are supported for COPY ... TO <expr>")
+        }
+    };
 
     // Converting the to expr to a HirScalarExpr
     let mut to_expr = to.clone();
@@ -1060,6 +1064,7 @@ fn plan_copy_from(
             )
         }
         CopyFormat::Binary => bail_unsupported!("FORMAT BINARY"),
+        CopyFormat::Parquet => bail_unsupported!("FORMAT PARQUET"),
     };
 
     let (id, _, columns) = query::plan_copy_from(scx, table_name, columns)?;
@@ -1096,6 +1101,7 @@ pub fn plan_copy(
         "text" => CopyFormat::Text,
         "csv" => CopyFormat::Csv,
         "binary" => CopyFormat::Binary,
+        "parquet" => CopyFormat::Parquet,
         _ => sql_bail!("unknown FORMAT: {}", options.format),
     };
     if let CopyDirection::To = direction {
@@ -1107,6 +1113,9 @@ pub fn plan_copy(
         }
     }
     match (&direction, &target) {
+        (CopyDirection::To, CopyTarget::Stdout) if format == CopyFormat::Parquet => {
+            sql_bail!("PARQUET format is only supported for COPY ... TO <expr>")
+        }
         (CopyDirection::To, CopyTarget::Stdout) => match relation {
             CopyRelation::Table { .. } => sql_bail!("table with COPY TO unsupported"),
             CopyRelation::Select(stmt) => {
@@ -21,7 +21,7 @@ use crate::ast::display::AstDisplay;
 use crate::ast::{
     CloseStatement, DeallocateStatement, DeclareStatement, DiscardStatement, DiscardTarget,
     ExecuteStatement, FetchOption, FetchOptionName, FetchStatement, PrepareStatement,
-    ResetVariableStatement, SetVariableStatement, SetVariableTo, ShowVariableStatement,
+    ResetVariableStatement, SetVariableStatement, SetVariableTo, ShowVariableStatement, Statement,
 };
 use crate::names::{self, Aug};
 use crate::plan::statement::{StatementContext, StatementDesc};
@@ -160,7 +160,7 @@ pub fn plan_discard(
         DiscardTarget::All => Ok(Plan::DiscardAll),
         DiscardTarget::Temp => Ok(Plan::DiscardTemp),
         DiscardTarget::Sequences => bail_unsupported!("DISCARD SEQUENCES"),
-        DiscardTarget::Plans => bail_unsupported!("DISCARD PLANS"),
+        DiscardTarget::Plans => Ok(Plan::DiscardPlans),
     }
 }
 
@@ -183,11 +183,24 @@ pub fn describe_declare(
 
 pub fn plan_declare(
     _: &StatementContext,
-    DeclareStatement { name, stmt, sql }: DeclareStatement<Aug>,
+    DeclareStatement {
+        name,
+        scroll,
+        hold,
+        stmt,
+        sql,
+    }: DeclareStatement<Aug>,
     params: &Params,
 ) -> Result<Plan, PlanError> {
+    // The rows of SCROLL and WITH HOLD cursors are buffered in the session,
+    // which is only possible for queries that eventually complete.
+    if (scroll || hold) && !matches!(*stmt, Statement::Select(_)) {
+        bail_unsupported!("SCROLL and WITH HOLD cursors for statements other than SELECT");
+    }
     Ok(Plan::Declare(DeclarePlan {
         name: name.to_string(),
+        scroll,
+        hold,
         stmt: *stmt,
         sql,
         params: params.clone(),
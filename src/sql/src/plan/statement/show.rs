@@ -30,9 +30,9 @@ use crate::ast::{
     ShowCreateSourceStatement, ShowCreateTableStatement, ShowCreateViewStatement,
     ShowObjectsStatement, ShowStatementFilter, Statement, Value,
 };
-use crate::catalog::{CatalogItemType, SessionCatalog};
+use crate::catalog::{CatalogItemType, ErrorMessageObjectDescription, SessionCatalog};
 use crate::names::{
-    self, Aug, NameSimplifier, ResolvedClusterName, ResolvedDatabaseName, ResolvedIds,
+    self, Aug, NameSimplifier, ObjectId, ResolvedClusterName, ResolvedDatabaseName, ResolvedIds,
     ResolvedItemName, ResolvedRoleName, ResolvedSchemaName,
 };
 use crate::parse;
@@ -55,9 +55,12 @@ pub fn describe_show_create_view(
 
 pub fn plan_show_create_view(
     scx: &StatementContext,
-    ShowCreateViewStatement { view_name }: ShowCreateViewStatement<Aug>,
+    ShowCreateViewStatement {
+        view_name,
+        redacted,
+    }: ShowCreateViewStatement<Aug>,
 ) -> Result<ShowCreatePlan, PlanError> {
-    plan_show_create(scx, &view_name, CatalogItemType::View)
+    plan_show_create(scx, &view_name, CatalogItemType::View, redacted)
 }
 
 pub fn describe_show_create_materialized_view(
@@ -75,12 +78,14 @@ pub fn plan_show_create_materialized_view(
     scx: &StatementContext,
     ShowCreateMaterializedViewStatement {
         materialized_view_name,
+        redacted,
     }: ShowCreateMaterializedViewStatement<Aug>,
 ) -> Result<ShowCreatePlan, PlanError> {
     plan_show_create(
         scx,
         &materialized_view_name,
         CatalogItemType::MaterializedView,
+        redacted,
     )
 }
 
@@ -99,6 +104,7 @@ fn plan_show_create(
     scx: &StatementContext,
     name: &ResolvedItemName,
     expect_type: CatalogItemType,
+    redacted: bool,
 ) -> Result<ShowCreatePlan, PlanError> {
     let item = scx.get_item_by_resolved_name(name)?;
     let name = name.full_name_str();
@@ -117,7 +123,7 @@ fn plan_show_create(
     if item.item_type() != expect_type {
         sql_bail!("{name} is not a {expect_type}");
     }
-    let create_sql = humanize_sql(scx.catalog, item.create_sql())?;
+    let create_sql = humanize_sql(scx.catalog, item.create_sql(), redacted)?;
     Ok(ShowCreatePlan {
         id: item.id(),
         row: Row::pack_slice(&[Datum::String(&name), Datum::String(&create_sql)]),
@@ -126,9 +132,12 @@ fn plan_show_create(
 
 pub fn plan_show_create_table(
     scx: &StatementContext,
-    ShowCreateTableStatement { table_name }: ShowCreateTableStatement<Aug>,
+    ShowCreateTableStatement {
+        table_name,
+        redacted,
+    }: ShowCreateTableStatement<Aug>,
 ) -> Result<ShowCreatePlan, PlanError> {
-    plan_show_create(scx, &table_name, CatalogItemType::Table)
+    plan_show_create(scx, &table_name, CatalogItemType::Table, redacted)
 }
 
 pub fn describe_show_create_source(
@@ -144,9 +153,12 @@ pub fn describe_show_create_source(
 
 pub fn plan_show_create_source(
     scx: &StatementContext,
-    ShowCreateSourceStatement { source_name }: ShowCreateSourceStatement<Aug>,
+    ShowCreateSourceStatement {
+        source_name,
+        redacted,
+    }: ShowCreateSourceStatement<Aug>,
 ) -> Result<ShowCreatePlan, PlanError> {
-    plan_show_create(scx, &source_name, CatalogItemType::Source)
+    plan_show_create(scx, &source_name, CatalogItemType::Source, redacted)
 }
 
 pub fn describe_show_create_sink(
@@ -162,9 +174,12 @@ pub fn describe_show_create_sink(
 
 pub fn plan_show_create_sink(
     scx: &StatementContext,
-    ShowCreateSinkStatement { sink_name }: ShowCreateSinkStatement<Aug>,
+    ShowCreateSinkStatement {
+        sink_name,
+        redacted,
+    }: ShowCreateSinkStatement<Aug>,
 ) -> Result<ShowCreatePlan, PlanError> {
-    plan_show_create(scx, &sink_name, CatalogItemType::Sink)
+    plan_show_create(scx, &sink_name, CatalogItemType::Sink, redacted)
 }
 
 pub fn describe_show_create_index(
@@ -180,9 +195,12 @@ pub fn describe_show_create_index(
 
 pub fn plan_show_create_index(
     scx: &StatementContext,
-    ShowCreateIndexStatement { index_name }: ShowCreateIndexStatement<Aug>,
+    ShowCreateIndexStatement {
+        index_name,
+        redacted,
+    }: ShowCreateIndexStatement<Aug>,
 ) -> Result<ShowCreatePlan, PlanError> {
-    plan_show_create(scx, &index_name, CatalogItemType::Index)
+    plan_show_create(scx, &index_name, CatalogItemType::Index, redacted)
 }
 
 pub fn describe_show_create_connection(
@@ -198,9 +216,12 @@ pub fn describe_show_create_connection(
 
 pub fn plan_show_create_connection(
     scx: &StatementContext,
-    ShowCreateConnectionStatement { connection_name }: ShowCreateConnectionStatement<Aug>,
+    ShowCreateConnectionStatement {
+        connection_name,
+        redacted,
+    }: ShowCreateConnectionStatement<Aug>,
 ) -> Result<ShowCreatePlan, PlanError> {
-    plan_show_create(scx, &connection_name, CatalogItemType::Connection)
+    plan_show_create(scx, &connection_name, CatalogItemType::Connection, redacted)
 }
 
 pub fn show_databases<'a>(
@@ -248,14 +269,17 @@ pub fn show_objects<'a>(
         object_type,
         from,
         filter,
+        extended,
     }: ShowObjectsStatement<Aug>,
 ) -> Result<ShowSelect<'a>, PlanError> {
     match object_type {
         ShowObjectType::Table => show_tables(scx, from, filter),
-        ShowObjectType::Source { in_cluster } => show_sources(scx, from, in_cluster, filter),
+        ShowObjectType::Source { in_cluster } => {
+            show_sources(scx, from, in_cluster, filter, extended)
+        }
         ShowObjectType::Subsource { on_source } => show_subsources(scx, from, on_source, filter),
         ShowObjectType::View => show_views(scx, from, filter),
-        ShowObjectType::Sink { in_cluster } => show_sinks(scx, from, in_cluster, filter),
+        ShowObjectType::Sink { in_cluster } => show_sinks(scx, from, in_cluster, filter, extended),
         ShowObjectType::Type => show_types(scx, from, filter),
         ShowObjectType::Object => show_all_objects(scx, from, filter),
         ShowObjectType::Role => {
@@ -264,7 +288,7 @@ pub fn show_objects<'a>(
         }
         ShowObjectType::Cluster => {
             assert!(from.is_none(), "parser should reject from");
-            show_clusters(scx, filter)
+            show_clusters(scx, filter, extended)
         }
         ShowObjectType::ClusterReplica => {
             assert!(from.is_none(), "parser should reject from");
@@ -273,7 +297,7 @@ pub fn show_objects<'a>(
         ShowObjectType::Secret => show_secrets(scx, from, filter),
         ShowObjectType::Connection => show_connections(scx, from, filter),
         ShowObjectType::MaterializedView { in_cluster } => {
-            show_materialized_views(scx, from, in_cluster, filter)
+            show_materialized_views(scx, from, in_cluster, filter, extended)
         }
         ShowObjectType::Index {
             in_cluster,
@@ -299,6 +323,10 @@ pub fn show_objects<'a>(
             assert!(from.is_none(), "parser should reject from");
             show_role_membership(scx, role, filter)
         }
+        ShowObjectType::Dependent { of_object, cascade } => {
+            assert!(from.is_none(), "parser should reject from");
+            show_dependents(scx, of_object, cascade, filter)
+        }
     }
 }
 
@@ -335,6 +363,7 @@ fn show_sources<'a>(
     from: Option<ResolvedSchemaName>,
     in_cluster: Option<ResolvedClusterName>,
     filter: Option<ShowStatementFilter<Aug>>,
+    extended: bool,
 ) -> Result<ShowSelect<'a>, PlanError> {
     let schema_spec = scx.resolve_optional_schema(&from)?;
     let mut where_clause = format!("schema_id = '{schema_spec}'");
@@ -344,17 +373,19 @@ fn show_sources<'a>(
             .expect("write on string cannot fail");
     }
 
+    let status = ExtendedStatus::new(extended, "sources.id", "source");
     let query = format!(
-        "SELECT name, type, size, cluster
-        FROM mz_internal.mz_show_sources
-        WHERE {where_clause}"
+        "SELECT sources.name, sources.type, size, cluster{}
+        FROM mz_internal.mz_show_sources AS sources{}
+        WHERE {where_clause}",
+        status.columns, status.join,
     );
     ShowSelect::new(
         scx,
         query,
         filter,
         None,
-        Some(&["name", "type", "size", "cluster"]),
+        Some(&status.projection(&["name", "type", "size", "cluster"])),
     )
 }
 
@@ -422,6 +453,7 @@ fn show_materialized_views<'a>(
     from: Option<ResolvedSchemaName>,
     in_cluster: Option<ResolvedClusterName>,
     filter: Option<ShowStatementFilter<Aug>>,
+    extended: bool,
 ) -> Result<ShowSelect<'a>, PlanError> {
     let schema_spec = scx.resolve_optional_schema(&from)?;
     let mut where_clause = format!("schema_id = '{schema_spec}'");
@@ -431,13 +463,21 @@ fn show_materialized_views<'a>(
             .expect("write on string cannot fail");
     }
 
+    let status = ExtendedStatus::new(extended, "mviews.id", "materialized-view");
     let query = format!(
-        "SELECT name, cluster
-         FROM mz_internal.mz_show_materialized_views
-         WHERE {where_clause}"
+        "SELECT mviews.name, cluster{}
+         FROM mz_internal.mz_show_materialized_views AS mviews{}
+         WHERE {where_clause}",
+        status.columns, status.join,
     );
 
-    ShowSelect::new(scx, query, filter, None, Some(&["name", "cluster"]))
+    ShowSelect::new(
+        scx,
+        query,
+        filter,
+        None,
+        Some(&status.projection(&["name", "cluster"])),
+    )
 }
 
 fn show_sinks<'a>(
@@ -445,6 +485,7 @@ fn show_sinks<'a>(
     from: Option<ResolvedSchemaName>,
     in_cluster: Option<ResolvedClusterName>,
     filter: Option<ShowStatementFilter<Aug>>,
+    extended: bool,
 ) -> Result<ShowSelect<'a>, PlanError> {
     let schema_spec = if let Some(ResolvedSchemaName::Schema { schema_spec, .. }) = from {
         schema_spec.to_string()
@@ -459,20 +500,67 @@ fn show_sinks<'a>(
             .expect("write on string cannot fail");
     }
 
+    let status = ExtendedStatus::new(extended, "sinks.id", "sink");
     let query = format!(
-        "SELECT name, type, size, cluster
-        FROM mz_internal.mz_show_sinks
-        WHERE {where_clause}"
+        "SELECT sinks.name, sinks.type, size, cluster{}
+        FROM mz_internal.mz_show_sinks AS sinks{}
+        WHERE {where_clause}",
+        status.columns, status.join,
     );
     ShowSelect::new(
         scx,
         query,
         filter,
         None,
-        Some(&["name", "type", "size", "cluster"]),
+        Some(&status.projection(&["name", "type", "size", "cluster"])),
     )
 }
 
+/// The status columns that `SHOW EXTENDED` adds to the output of `SHOW <objects>`, from
+/// `mz_internal.mz_object_statuses`.
+struct ExtendedStatus {
+    /// The select items of the status columns, preceded by a comma, or empty if the statement
+    /// is not extended.
+    columns: &'static str,
+    /// The join with `mz_object_statuses`, or empty if the statement is not extended.
+    join: String,
+    extended: bool,
+}
+
+impl ExtendedStatus {
+    const COLUMNS: [&'static str; 3] = ["status", "reason", "last_status_change_at"];
+
+    /// Returns the status columns of the objects of type `object_type` whose IDs are in
+    /// `id_column`.
+    fn new(extended: bool, id_column: &str, object_type: &str) -> ExtendedStatus {
+        if !extended {
+            return ExtendedStatus {
+                columns: "",
+                join: String::new(),
+                extended,
+            };
+        }
+        ExtendedStatus {
+            columns: ", statuses.status, statuses.reason, statuses.last_status_change_at",
+            join: format!(
+                "
+        LEFT JOIN mz_internal.mz_object_statuses AS statuses
+            ON statuses.id = {id_column} AND statuses.type = '{object_type}'"
+            ),
+            extended,
+        }
+    }
+
+    /// Returns `projection`, followed by the status columns if the statement is extended.
+    fn projection<'a>(&self, projection: &[&'a str]) -> Vec<&'a str> {
+        let mut projection = projection.to_vec();
+        if self.extended {
+            projection.extend(Self::COLUMNS);
+        }
+        projection
+    }
+}
+
 fn show_types<'a>(
     scx: &'a StatementContext<'a>,
     from: Option<ResolvedSchemaName>,
@@ -574,7 +662,8 @@ pub fn show_columns<'a>(
         | ty @ CatalogItemType::Func
         | ty @ CatalogItemType::Secret
         | ty @ CatalogItemType::Type
-        | ty @ CatalogItemType::Sink => {
+        | ty @ CatalogItemType::Sink
+        | ty @ CatalogItemType::ContinualTask => {
             sql_bail!("{full_name} is a {ty} and so does not have columns");
         }
     }
@@ -609,25 +698,42 @@ pub fn show_columns<'a>(
 pub fn show_clusters<'a>(
     scx: &'a StatementContext<'a>,
     filter: Option<ShowStatementFilter<Aug>>,
+    extended: bool,
 ) -> Result<ShowSelect<'a>, PlanError> {
-    let query = "
+    // Every cluster has at most one status, so grouping by the status columns doesn't split the
+    // replicas of a cluster into several rows.
+    let status = ExtendedStatus::new(extended, "mc.id", "cluster");
+    let query = format!(
+        "
 SELECT
     mc.name,
     pg_catalog.string_agg(mcr.name || ' (' || mcr.size || ')', ', ' ORDER BY mcr.name)
-        AS replicas
+        AS replicas,
+    pg_catalog.sum(mcrs.credits_per_hour) AS credits_per_hour{columns}
 FROM
     mz_catalog.mz_clusters mc
         LEFT JOIN mz_catalog.mz_cluster_replicas mcr ON mc.id = mcr.cluster_id
-GROUP BY mc.name"
-        .to_string();
-    ShowSelect::new(scx, query, filter, None, Some(&["name", "replicas"]))
+        LEFT JOIN mz_internal.mz_cluster_replica_sizes mcrs ON mcr.size = mcrs.size{join}
+GROUP BY mc.name{columns}",
+        columns = status.columns,
+        join = status.join,
+    );
+    ShowSelect::new(
+        scx,
+        query,
+        filter,
+        None,
+        Some(&status.projection(&["name", "replicas", "credits_per_hour"])),
+    )
 }
 
 pub fn show_cluster_replicas<'a>(
     scx: &'a StatementContext<'a>,
     filter: Option<ShowStatementFilter<Aug>>,
 ) -> Result<ShowSelect<'a>, PlanError> {
-    let query = "SELECT cluster, replica, size, ready FROM mz_internal.mz_show_cluster_replicas"
+    let query = "
+SELECT cluster, replica, size, ready, cpu_percent, memory_percent, credits_per_hour
+FROM mz_internal.mz_show_cluster_replicas"
         .to_string();
 
     ShowSelect::new(
@@ -635,7 +741,15 @@ pub fn show_cluster_replicas<'a>(
         query,
         filter,
         None,
-        Some(&["cluster", "replica", "size", "ready"]),
+        Some(&[
+            "cluster",
+            "replica",
+            "size",
+            "ready",
+            "cpu_percent",
+            "memory_percent",
+            "credits_per_hour",
+        ]),
     )
 }
 
@@ -779,6 +893,67 @@ pub fn show_role_membership<'a>(
     )
 }
 
+/// Shows the objects that depend on `of_object`.
+///
+/// Without `cascade`, only the objects that directly depend on `of_object` are shown. With
+/// `cascade`, all objects that `DROP ... CASCADE` would drop along with `of_object` are shown.
+fn show_dependents<'a>(
+    scx: &'a StatementContext<'a>,
+    of_object: ResolvedItemName,
+    cascade: bool,
+    filter: Option<ShowStatementFilter<Aug>>,
+) -> Result<ShowSelect<'a>, PlanError> {
+    let item = scx.get_item_by_resolved_name(&of_object)?;
+    let ids: Vec<_> = if cascade {
+        scx.catalog
+            .item_dependents(item.id())
+            .into_iter()
+            .filter(|id| *id != ObjectId::Item(item.id()))
+            .collect()
+    } else {
+        item.used_by()
+            .iter()
+            .map(|id| ObjectId::Item(*id))
+            .collect()
+    };
+    show_object_ids(scx, &ids, filter)
+}
+
+/// Shows the name and type of the objects identified by `ids`.
+///
+/// The objects are listed from the catalog rather than queried from the system tables, as not all
+/// of them are items.
+pub fn show_object_ids<'a>(
+    scx: &'a StatementContext<'a>,
+    ids: &[ObjectId],
+    filter: Option<ShowStatementFilter<Aug>>,
+) -> Result<ShowSelect<'a>, PlanError> {
+    let mut values = Vec::new();
+    for id in ids {
+        match ErrorMessageObjectDescription::from_id(id, scx.catalog) {
+            ErrorMessageObjectDescription::Object {
+                object_type,
+                object_name: Some(name),
+            } => values.push(format!(
+                "({}, {})",
+                Value::String(name),
+                Value::String(object_type.to_string())
+            )),
+            description => unreachable!("objects are described by name: {description:?}"),
+        }
+    }
+    // `VALUES` requires at least one row, so provide a placeholder that is filtered out.
+    let query = if values.is_empty() {
+        "SELECT name, type FROM (VALUES ('', '')) AS objects (name, type) WHERE false".to_string()
+    } else {
+        format!(
+            "SELECT name, type FROM (VALUES {}) AS objects (name, type)",
+            itertools::join(values, ", ")
+        )
+    };
+    ShowSelect::new(scx, query, filter, None, Some(&["name", "type"]))
+}
+
 /// An intermediate result when planning a `SHOW` query.
 ///
 /// Can be interrogated for its columns, or converted into a proper [`Plan`].
@@ -891,7 +1066,15 @@ impl<'a> ShowColumnsSelect<'a> {
 }
 
 /// Convert a SQL statement into a form suitable for human consumption.
-fn humanize_sql(catalog: &dyn SessionCatalog, sql: &str) -> Result<String, PlanError> {
+///
+/// If `redacted` is set, literals and secret references are redacted, as in
+/// the statement log, so that the statement can be shared without revealing
+/// any data.
+fn humanize_sql(
+    catalog: &dyn SessionCatalog,
+    sql: &str,
+    redacted: bool,
+) -> Result<String, PlanError> {
     let parsed = parse::parse(sql)?.into_element().ast;
     let (mut resolved, _) = names::resolve(catalog, parsed)?;
 
@@ -905,5 +1088,9 @@ fn humanize_sql(catalog: &dyn SessionCatalog, sql: &str) -> Result<String, PlanE
         _ => (),
     }
 
-    Ok(resolved.to_ast_string_stable())
+    if redacted {
+        Ok(resolved.to_ast_string_redacted())
+    } else {
+        Ok(resolved.to_ast_string_stable())
+    }
 }
@@ -9,20 +9,23 @@
 
 //! Provides tooling to handle `WITH` options.
 
+use std::collections::BTreeMap;
 use std::time::Duration;
 
+use mz_ore::str::StrExt;
 use mz_repr::adt::interval::Interval;
 use mz_repr::bytes::ByteSize;
 use mz_repr::{strconv, GlobalId};
 use mz_sql_parser::ast::{
-    ConnectionDefaultAwsPrivatelink, Ident, KafkaBroker, RefreshOptionValue, ReplicaDefinition,
+    ClusterScheduleOptionValue, ConnectionDefaultAwsPrivatelink, Expr, Ident, KafkaBroker,
+    RefreshOptionValue, ReplicaDefinition,
 };
 use mz_storage_types::connections::StringOrSecret;
 use serde::{Deserialize, Serialize};
 
 use crate::ast::{AstInfo, UnresolvedItemName, Value, WithOptionValue};
 use crate::names::{ResolvedDataType, ResolvedItemName};
-use crate::plan::{literal, Aug, PlanError};
+use crate::plan::{literal, Aug, ClusterSchedule, CronSchedule, PlanError};
 
 pub trait TryFromValue<T>: Sized {
     fn try_from_value(v: T) -> Result<Self, PlanError>;
@@ -435,6 +438,35 @@ impl<V: ImpliedValue> ImpliedValue for Vec<V> {
     }
 }
 
+impl<V: TryFromValue<WithOptionValue<Aug>>> TryFromValue<WithOptionValue<Aug>>
+    for BTreeMap<String, V>
+{
+    fn try_from_value(v: WithOptionValue<Aug>) -> Result<Self, PlanError> {
+        match v {
+            WithOptionValue::Map(a) => {
+                let mut out = BTreeMap::new();
+                for (k, v) in a {
+                    let v = V::try_from_value(v).map_err(|_| {
+                        anyhow::anyhow!("cannot use value for key {} in map", k.quoted())
+                    })?;
+                    out.insert(k, v);
+                }
+                Ok(out)
+            }
+            _ => sql_bail!("cannot use value as map"),
+        }
+    }
+    fn name() -> String {
+        format!("map of {}", V::name())
+    }
+}
+
+impl<V: ImpliedValue> ImpliedValue for BTreeMap<String, V> {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide a map value")
+    }
+}
+
 impl<T: AstInfo, V: TryFromValue<WithOptionValue<T>>> TryFromValue<WithOptionValue<T>>
     for Option<V>
 {
@@ -460,14 +492,17 @@ impl<V: TryFromValue<Value>, T: AstInfo + std::fmt::Debug> TryFromValue<WithOpti
             WithOptionValue::Ident(i) => V::try_from_value(Value::String(i.into_string())),
             WithOptionValue::RetainHistoryFor(v) => V::try_from_value(v),
             WithOptionValue::Sequence(_)
+            | WithOptionValue::Map(_)
             | WithOptionValue::Item(_)
             | WithOptionValue::UnresolvedItemName(_)
             | WithOptionValue::Secret(_)
             | WithOptionValue::DataType(_)
             | WithOptionValue::ClusterReplicas(_)
+            | WithOptionValue::ClusterSchedule(_)
             | WithOptionValue::ConnectionKafkaBroker(_)
             | WithOptionValue::ConnectionAwsPrivatelink(_)
-            | WithOptionValue::Refresh(_) => sql_bail!(
+            | WithOptionValue::Refresh(_)
+            | WithOptionValue::Expr(_) => sql_bail!(
                 "incompatible value types: cannot convert {} to {}",
                 match v {
                     // The first few are unreachable because they are handled at the top of the outer match.
@@ -475,14 +510,17 @@ impl<V: TryFromValue<Value>, T: AstInfo + std::fmt::Debug> TryFromValue<WithOpti
                     WithOptionValue::Ident(_) => unreachable!(),
                     WithOptionValue::RetainHistoryFor(_) => unreachable!(),
                     WithOptionValue::Sequence(_) => "sequences",
+                    WithOptionValue::Map(_) => "maps",
                     WithOptionValue::Item(_) => "object references",
                     WithOptionValue::UnresolvedItemName(_) => "object names",
                     WithOptionValue::Secret(_) => "secrets",
                     WithOptionValue::DataType(_) => "data types",
                     WithOptionValue::ClusterReplicas(_) => "cluster replicas",
+                    WithOptionValue::ClusterSchedule(_) => "cluster schedules",
                     WithOptionValue::ConnectionKafkaBroker(_) => "connection kafka brokers",
                     WithOptionValue::ConnectionAwsPrivatelink(_) => "connection kafka brokers",
                     WithOptionValue::Refresh(_) => "refresh option values",
+                    WithOptionValue::Expr(_) => "expressions",
                 },
                 V::name()
             ),
@@ -523,6 +561,32 @@ impl ImpliedValue for Vec<ReplicaDefinition<Aug>> {
     }
 }
 
+impl TryFromValue<WithOptionValue<Aug>> for ClusterSchedule {
+    fn try_from_value(v: WithOptionValue<Aug>) -> Result<Self, PlanError> {
+        match v {
+            WithOptionValue::ClusterSchedule(ClusterScheduleOptionValue::Manual) => {
+                Ok(ClusterSchedule::Manual)
+            }
+            WithOptionValue::ClusterSchedule(ClusterScheduleOptionValue::Cron(expr)) => {
+                match CronSchedule::parse(&expr) {
+                    Ok(cron) => Ok(ClusterSchedule::Cron(cron)),
+                    Err(e) => sql_bail!("invalid SCHEDULE: {}", e),
+                }
+            }
+            _ => sql_bail!("cannot use value as cluster schedule"),
+        }
+    }
+    fn name() -> String {
+        "cluster schedule".to_string()
+    }
+}
+
+impl ImpliedValue for ClusterSchedule {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide a cluster schedule")
+    }
+}
+
 impl TryFromValue<WithOptionValue<Aug>> for Vec<KafkaBroker<Aug>> {
     fn try_from_value(v: WithOptionValue<Aug>) -> Result<Self, PlanError> {
         let mut out = vec![];
@@ -570,6 +634,26 @@ impl ImpliedValue for RefreshOptionValue<Aug> {
     }
 }
 
+impl TryFromValue<WithOptionValue<Aug>> for Expr<Aug> {
+    fn try_from_value(v: WithOptionValue<Aug>) -> Result<Self, PlanError> {
+        if let WithOptionValue::Expr(e) = v {
+            Ok(e)
+        } else {
+            sql_bail!("cannot use value `{}` for an expression", v)
+        }
+    }
+
+    fn name() -> String {
+        "expression".to_string()
+    }
+}
+
+impl ImpliedValue for Expr<Aug> {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide an expression")
+    }
+}
+
 impl TryFromValue<WithOptionValue<Aug>> for ConnectionDefaultAwsPrivatelink<Aug> {
     fn try_from_value(v: WithOptionValue<Aug>) -> Result<Self, PlanError> {
         if let WithOptionValue::ConnectionAwsPrivatelink(r) = v {
@@ -15,6 +15,7 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::iter;
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::anyhow;
 use itertools::Itertools;
@@ -32,31 +33,34 @@ use mz_sql_parser::ast::visit_mut::{visit_expr_mut, VisitMut};
 use mz_sql_parser::ast::{
     AlterSourceAction, AlterSourceAddSubsourceOptionName, AlterSourceStatement, AvroDocOn,
     CreateMaterializedViewStatement, CreateSinkConnection, CreateSinkStatement,
-    CreateSubsourceOption, CreateSubsourceOptionName, CsrConfigOption, CsrConfigOptionName,
-    CsrConnection, CsrSeedAvro, CsrSeedProtobuf, CsrSeedProtobufSchema, DeferredItemName,
-    DocOnIdentifier, DocOnSchema, Expr, Function, FunctionArgs, Ident, KafkaSourceConfigOption,
-    KafkaSourceConfigOptionName, MaterializedViewOption, MaterializedViewOptionName,
-    MySqlConfigOption, MySqlConfigOptionName, PgConfigOption, PgConfigOptionName, RawItemName,
-    ReaderSchemaSelectionStrategy, RefreshAtOptionValue, RefreshEveryOptionValue,
-    RefreshOptionValue, SourceEnvelope, Statement, UnresolvedItemName,
+    CreateSourceOptionName, CreateSubsourceOption, CreateSubsourceOptionName, CsrConfigOption,
+    CsrConfigOptionName, CsrConnection, CsrSeedAvro, CsrSeedProtobuf, CsrSeedProtobufSchema,
+    DeferredItemName, DocOnIdentifier, DocOnSchema, Expr, Function, FunctionArgs, Ident,
+    KafkaSourceConfigOption, KafkaSourceConfigOptionName, MaterializedViewOption,
+    MaterializedViewOptionName, MySqlConfigOption, MySqlConfigOptionName, PgConfigOption,
+    PgConfigOptionName, RawItemName, ReaderSchemaSelectionStrategy, RefreshAtOptionValue,
+    RefreshEveryOptionValue, RefreshOptionValue, SourceEnvelope, Statement, UnresolvedItemName,
 };
 use mz_storage_types::configuration::StorageConfiguration;
 use mz_storage_types::connections::inline::IntoInlineConnection;
 use mz_storage_types::connections::Connection;
 use mz_storage_types::errors::ContextCreationError;
+use mz_storage_types::sources::kafka::KAFKA_PROGRESS_DESC;
 use mz_storage_types::sources::mysql::MySqlSourceDetails;
 use mz_storage_types::sources::postgres::PostgresSourcePublicationDetails;
-use mz_storage_types::sources::{GenericSourceConnection, SourceConnection};
+use mz_storage_types::sources::{dead_letter_desc, GenericSourceConnection, SourceConnection};
 use prost::Message;
 use protobuf_native::compiler::{SourceTreeDescriptorDatabase, VirtualSourceTree};
 use protobuf_native::MessageLite;
 use rdkafka::admin::AdminClient;
+use rdkafka::consumer::{BaseConsumer, ConsumerContext};
 use uuid::Uuid;
 
 use crate::ast::{
     AvroSchema, CreateSourceConnection, CreateSourceFormat, CreateSourceStatement,
     CreateSourceSubsource, CreateSubsourceStatement, CsrConnectionAvro, CsrConnectionProtobuf,
-    Format, ProtobufSchema, ReferencedSubsources, Value, WithOptionValue,
+    CsvColumns, CsvHeaderColumn, Format, ProtobufSchema, ReferencedSubsources, Value,
+    WithOptionValue,
 };
 use crate::catalog::{CatalogItemType, SessionCatalog, SubsourceCatalog};
 use crate::kafka_util::{KafkaSinkConfigOptionExtracted, KafkaSourceConfigOptionExtracted};
@@ -65,8 +69,9 @@ use crate::names::{
     ResolvedItemName,
 };
 use crate::plan::error::PlanError;
-use crate::plan::statement::ddl::load_generator_ast_to_generator;
+use crate::plan::statement::ddl::{load_generator_ast_to_generator, CreateSourceOptionExtracted};
 use crate::plan::StatementContext;
+use crate::session::vars;
 use crate::{kafka_util, normalize};
 
 use self::error::{
@@ -154,6 +159,31 @@ fn subsource_name_gen(
     Ok(UnresolvedItemName::from(partial))
 }
 
+/// Generates the name of a subsource of `source_name` by appending `suffix`
+/// to its item name, disambiguating it further if it would conflict with an
+/// existing item or type.
+fn generate_subsource_name(
+    scx: &StatementContext,
+    source_name: &UnresolvedItemName,
+    suffix: &str,
+) -> Result<UnresolvedItemName, PlanError> {
+    let (item, prefix) = source_name.0.split_last().unwrap();
+    let item_name = Ident::try_generate_name(item.to_string(), suffix, |candidate| {
+        let mut suggested_name = prefix.to_vec();
+        suggested_name.push(candidate.clone());
+
+        let partial = normalize::unresolved_item_name(UnresolvedItemName(suggested_name))?;
+        let qualified = scx.allocate_qualified_name(partial)?;
+        let item_exists = scx.catalog.get_item_by_name(&qualified).is_some();
+        let type_exists = scx.catalog.get_type_by_name(&qualified).is_some();
+        Ok::<_, PlanError>(!item_exists && !type_exists)
+    })?;
+
+    let mut full_name = prefix.to_vec();
+    full_name.push(item_name);
+    Ok(UnresolvedItemName(full_name))
+}
+
 /// Validates the requested subsources do not have name conflicts with each other
 /// and that the same upstream table is not referenced multiple times.
 fn validate_subsource_names<T>(
@@ -373,6 +403,7 @@ async fn purify_create_sink(
             connection,
             options,
             key: _,
+            headers: _,
         } => {
             let scx = StatementContext::new(None, &catalog);
             let connection = {
@@ -426,6 +457,9 @@ async fn purify_create_sink(
                 Err(KafkaSinkPurificationError::ZeroBrokers)?;
             }
         }
+        // The catalog is reachable by the time the connection has been
+        // validated, and the sink creates its table on demand.
+        CreateSinkConnection::Iceberg { .. } => {}
     }
 
     if let Some(format) = format {
@@ -491,6 +525,7 @@ async fn purify_create_source(
         format,
         envelope,
         include_metadata: _,
+        with_options,
         referenced_subsources,
         progress_subsource,
         ..
@@ -518,9 +553,7 @@ async fn purify_create_source(
     let mut subsources = vec![];
 
     let progress_desc = match &connection {
-        CreateSourceConnection::Kafka { .. } => {
-            &mz_storage_types::sources::kafka::KAFKA_PROGRESS_DESC
-        }
+        CreateSourceConnection::Kafka { .. } => &KAFKA_PROGRESS_DESC,
         CreateSourceConnection::Postgres { .. } => {
             &mz_storage_types::sources::postgres::PG_PROGRESS_DESC
         }
@@ -580,10 +613,24 @@ async fn purify_create_source(
                 })?;
             let consumer = Arc::new(consumer);
 
-            match (
-                extracted_options.start_offset,
-                extracted_options.start_timestamp,
-            ) {
+            // `START TIMESTAMP` may also be specified among the source's
+            // options, from where it is moved into the Kafka options below.
+            let CreateSourceOptionExtracted {
+                start_timestamp: source_start_timestamp,
+                ..
+            } = with_options.clone().try_into()?;
+            let start_timestamp = match (source_start_timestamp, extracted_options.start_timestamp)
+            {
+                (Some(_), Some(_)) => sql_bail!(
+                    "cannot specify START TIMESTAMP in both the source options and the Kafka \
+                     connection options"
+                ),
+                (Some(ts), None) | (None, Some(ts)) => Some(ts),
+                (None, None) => None,
+            };
+            with_options.retain(|val| val.name != CreateSourceOptionName::StartTimestamp);
+
+            match (extracted_options.start_offset, start_timestamp) {
                 (None, None) => {
                     // Validate that the topic at least exists.
                     kafka_util::ensure_topic_exists(
@@ -617,7 +664,7 @@ async fn purify_create_source(
                     let start_offsets = kafka_util::lookup_start_offsets(
                         Arc::clone(&consumer),
                         &topic,
-                        time_offset,
+                        time_offset.0,
                         now,
                         storage_configuration
                             .parameters
@@ -642,6 +689,53 @@ async fn purify_create_source(
                     });
                 }
             }
+
+            if let Some(format) = format {
+                purify_csv_header(
+                    &scx,
+                    format,
+                    Arc::clone(&consumer),
+                    &topic,
+                    storage_configuration
+                        .parameters
+                        .kafka_timeout_config
+                        .fetch_metadata_timeout,
+                )
+                .await?;
+            }
+
+            let CreateSourceOptionExtracted { errors, .. } = with_options.clone().try_into()?;
+            // The value itself is validated during planning; all we need to
+            // know here is whether to generate the dead-letter queue subsource.
+            if errors.is_some_and(|errors| errors.eq_ignore_ascii_case("dlq")) {
+                let subsource_name = generate_subsource_name(&scx, source_name, "_dlq")?;
+                let desc = dead_letter_desc(KAFKA_PROGRESS_DESC.clone());
+                let (columns, constraints) = scx.relation_desc_into_table_defs(&desc)?;
+
+                // Create the targeted AST node for the original CREATE SOURCE statement
+                let transient_id = GlobalId::Transient(get_transient_subsource_id());
+                let subsource =
+                    scx.allocate_resolved_item_name(transient_id, subsource_name.clone())?;
+                *referenced_subsources = Some(ReferencedSubsources::SubsetTables(vec![
+                    CreateSourceSubsource {
+                        reference: kafka_util::dead_letter_reference(),
+                        subsource: Some(DeferredItemName::Named(subsource)),
+                    },
+                ]));
+
+                // Create the subsource statement
+                let subsource = CreateSubsourceStatement {
+                    name: subsource_name,
+                    columns,
+                    constraints,
+                    if_not_exists: false,
+                    with_options: vec![CreateSubsourceOption {
+                        name: CreateSubsourceOptionName::References,
+                        value: Some(WithOptionValue::Value(Value::Boolean(true))),
+                    }],
+                };
+                subsources.push((transient_id, subsource));
+            }
         }
         CreateSourceConnection::Postgres {
             connection,
@@ -1227,21 +1321,8 @@ async fn purify_create_source(
             DeferredItemName::Named(_) => unreachable!("already checked for this value"),
         },
         None => {
-            let (item, prefix) = source_name.0.split_last().unwrap();
-            let item_name = Ident::try_generate_name(item.to_string(), "_progress", |candidate| {
-                let mut suggested_name = prefix.to_vec();
-                suggested_name.push(candidate.clone());
-
-                let partial = normalize::unresolved_item_name(UnresolvedItemName(suggested_name))?;
-                let qualified = scx.allocate_qualified_name(partial)?;
-                let item_exists = scx.catalog.get_item_by_name(&qualified).is_some();
-                let type_exists = scx.catalog.get_type_by_name(&qualified).is_some();
-                Ok::<_, PlanError>(!item_exists && !type_exists)
-            })?;
-
-            let mut full_name = prefix.to_vec();
-            full_name.push(item_name);
-            let full_name = normalize::unresolved_item_name(UnresolvedItemName(full_name))?;
+            let full_name = generate_subsource_name(&scx, source_name, "_progress")?;
+            let full_name = normalize::unresolved_item_name(full_name)?;
             let qualified_name = scx.allocate_qualified_name(full_name)?;
             let full_name = scx.catalog.resolve_full_name(&qualified_name);
 
@@ -1577,6 +1658,79 @@ async fn purify_source_format(
     Ok(())
 }
 
+/// Infers the column names of any `FORMAT CSV WITH HEADER` in `format` that
+/// does not specify them from the header row of the first message in `topic`.
+async fn purify_csv_header<C>(
+    scx: &StatementContext<'_>,
+    format: &mut CreateSourceFormat<Aug>,
+    consumer: Arc<BaseConsumer<C>>,
+    topic: &str,
+    fetch_metadata_timeout: Duration,
+) -> Result<(), PlanError>
+where
+    C: ConsumerContext + 'static,
+{
+    let mut to_infer = vec![];
+    let formats = match format {
+        CreateSourceFormat::Bare(value) => vec![("value", value)],
+        CreateSourceFormat::KeyValue { key, value } => vec![("key", key), ("value", value)],
+    };
+    for (part, format) in formats {
+        if let Format::Csv {
+            columns: CsvColumns::Header { columns },
+            delimiter,
+        } = format
+        {
+            if columns.is_empty() {
+                to_infer.push((part, *delimiter, columns));
+            }
+        }
+    }
+    if to_infer.is_empty() {
+        return Ok(());
+    }
+    scx.require_feature_flag(&vars::ENABLE_CSV_HEADER_TYPES)?;
+
+    let Some((key, value)) =
+        kafka_util::fetch_first_message(consumer, topic, fetch_metadata_timeout).await?
+    else {
+        return Err(KafkaSourcePurificationError::CsvHeaderEmptyTopic(topic.to_string()).into());
+    };
+    for (part, delimiter, columns) in to_infer {
+        let data = match part {
+            "key" => key.as_deref(),
+            _ => value.as_deref(),
+        };
+        let data = data.ok_or_else(|| KafkaSourcePurificationError::CsvHeaderMissingData {
+            topic: topic.to_string(),
+            part,
+        })?;
+        let delimiter = u8::try_from(delimiter)
+            .map_err(|_| sql_err!("CSV delimiter must be an ASCII character"))?;
+        let header = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .delimiter(delimiter)
+            .from_reader(data)
+            .records()
+            .next()
+            .transpose()
+            .map_err(|e| KafkaSourcePurificationError::CsvHeaderInvalid(e.to_string()))?
+            .ok_or_else(|| KafkaSourcePurificationError::CsvHeaderMissingData {
+                topic: topic.to_string(),
+                part,
+            })?;
+        for name in header.iter() {
+            let name = Ident::new(name)
+                .map_err(|e| KafkaSourcePurificationError::CsvHeaderInvalid(e.to_string()))?;
+            columns.push(CsvHeaderColumn {
+                name,
+                data_type: None,
+            });
+        }
+    }
+    Ok(())
+}
+
 async fn purify_source_format_single(
     catalog: &dyn SessionCatalog,
     format: &mut Format<Aug>,
@@ -37,7 +37,13 @@ pub enum PgSourcePurificationError {
     #[error("insufficient privileges")]
     UserLacksSelectOnTables { user: String, tables: Vec<String> },
     #[error("referenced items not tables with REPLICA IDENTITY FULL")]
-    NotTablesWReplicaIdentityFull { items: Vec<String> },
+    NotTablesWReplicaIdentityFull {
+        /// The offending items, and a description of their replica identity.
+        items: Vec<(String, String)>,
+        /// The subset of `items` whose updates may omit unchanged TOASTed
+        /// values.
+        toastable: Vec<String>,
+    },
     #[error("TEXT COLUMNS refers to table not currently being added")]
     DanglingTextColumns { items: Vec<PartialItemName> },
     #[error("referenced tables use unsupported types")]
@@ -80,9 +86,21 @@ impl PgSourcePurificationError {
                 user,
                 tables.join(", ")
             )),
-            Self::NotTablesWReplicaIdentityFull { items } => {
-                Some(format!("referenced items: {}", items.join(", ")))
-            }
+            Self::NotTablesWReplicaIdentityFull { items, toastable } => Some(format!(
+                "referenced items: {}",
+                itertools::join(
+                    items.iter().map(|(name, replica_identity)| {
+                        if toastable.contains(name) {
+                            format!(
+                                "{name} ({replica_identity}; updates omit unchanged TOASTed values)"
+                            )
+                        } else {
+                            format!("{name} ({replica_identity})")
+                        }
+                    }),
+                    ", "
+                )
+            )),
             Self::UnrecognizedTypes { cols } => Some(format!(
                 "the following columns contain unsupported types:\n{}",
                 itertools::join(
@@ -120,6 +138,16 @@ impl PgSourcePurificationError {
                 "you might be able to wait for other sources to finish snapshotting and try again".into()
             ),
             Self::ReplicationDisabled => Some("set max_wal_senders to a value > 0".into()),
+            Self::NotTablesWReplicaIdentityFull { items, .. } => Some(format!(
+                "set REPLICA IDENTITY FULL on each table in PostgreSQL: {}",
+                itertools::join(
+                    items
+                        .iter()
+                        .filter(|(_, replica_identity)| replica_identity != "not a table")
+                        .map(|(name, _)| format!("ALTER TABLE {name} REPLICA IDENTITY FULL;")),
+                    " "
+                )
+            )),
             _ => None,
         }
     }
@@ -136,6 +164,12 @@ pub enum KafkaSourcePurificationError {
     NotKafkaConnection(FullItemName),
     #[error("failed to create and connect Kafka consumer")]
     KafkaConsumerError(String),
+    #[error("cannot infer CSV header: topic {0} contains no messages")]
+    CsvHeaderEmptyTopic(String),
+    #[error("cannot infer CSV header: the first message in topic {topic} has no {part}")]
+    CsvHeaderMissingData { topic: String, part: &'static str },
+    #[error("cannot infer CSV header: {0}")]
+    CsvHeaderInvalid(String),
 }
 
 impl KafkaSourcePurificationError {
@@ -147,7 +181,14 @@ impl KafkaSourcePurificationError {
     }
 
     pub fn hint(&self) -> Option<String> {
-        None
+        match self {
+            Self::CsvHeaderEmptyTopic(_)
+            | Self::CsvHeaderMissingData { .. }
+            | Self::CsvHeaderInvalid(_) => {
+                Some("Specify the column names using FORMAT CSV WITH HEADER (name, ...).".into())
+            }
+            _ => None,
+        }
     }
 }
 
@@ -488,6 +488,11 @@ mod replica_identity {
     use crate::pure::PgSourcePurificationError;
 
     /// Ensures that all provided OIDs are tables with `REPLICA IDENTITY FULL`.
+    ///
+    /// Tables with any other replica identity omit the old row from updates and
+    /// deletes, and additionally omit unchanged TOASTed values from updates. The
+    /// returned error describes the current replica identity of each offending
+    /// table, and which of them contain columns that PostgreSQL may TOAST.
     pub async fn check_replica_identity_full(
         config: &Config,
         oids: Vec<Oid>,
@@ -507,11 +512,20 @@ mod replica_identity {
             }],
         );
 
-        let mut invalid_replica_identity = client
+        let rows = client
             .query(
                 "
             SELECT
-                input.oid::REGCLASS::TEXT AS name
+                input.oid::REGCLASS::TEXT AS name,
+                relreplident::TEXT AS replica_identity,
+                EXISTS (
+                    SELECT 1 FROM pg_attribute
+                    WHERE
+                        attrelid = input.oid
+                        AND attnum > 0
+                        AND NOT attisdropped
+                        AND attstorage != 'p'
+                ) AS toastable
             FROM
                 (SELECT unnest($1::OID[]) AS oid) AS input
                 LEFT JOIN pg_class ON input.oid = pg_class.oid
@@ -520,18 +534,33 @@ mod replica_identity {
                 &[&oids],
             )
             .await
-            .map_err(PostgresError::from)?
-            .into_iter()
-            .map(|row| row.get("name"))
-            .collect::<Vec<String>>();
+            .map_err(PostgresError::from)?;
+
+        let mut items = vec![];
+        let mut toastable = vec![];
+        for row in rows {
+            let name: String = row.get("name");
+            let replica_identity = match row.get::<_, Option<String>>("replica_identity") {
+                Some(ident) => match ident.as_str() {
+                    "d" => "REPLICA IDENTITY DEFAULT",
+                    "n" => "REPLICA IDENTITY NOTHING",
+                    "i" => "REPLICA IDENTITY USING INDEX",
+                    _ => "unknown REPLICA IDENTITY",
+                },
+                None => "not a table",
+            };
+            if row.get("toastable") {
+                toastable.push(name.clone());
+            }
+            items.push((name, replica_identity.to_string()));
+        }
 
-        if invalid_replica_identity.is_empty() {
+        if items.is_empty() {
             Ok(())
         } else {
-            invalid_replica_identity.sort();
-            Err(PgSourcePurificationError::NotTablesWReplicaIdentityFull {
-                items: invalid_replica_identity,
-            })?
+            items.sort();
+            toastable.sort();
+            Err(PgSourcePurificationError::NotTablesWReplicaIdentityFull { items, toastable })?
         }
     }
 }
@@ -536,6 +536,31 @@ fn generate_rbac_requirements(
             item_usage: &CREATE_ITEM_USAGE,
             ..Default::default()
         },
+        Plan::CreateContinualTask(plan::CreateContinualTaskPlan {
+            name,
+            continual_task,
+            if_not_exists: _,
+        }) => RbacRequirements {
+            privileges: vec![
+                (
+                    SystemObjectId::Object(name.qualifiers.clone().into()),
+                    AclMode::CREATE,
+                    role_id,
+                ),
+                (
+                    SystemObjectId::Object(continual_task.cluster_id.into()),
+                    AclMode::CREATE,
+                    role_id,
+                ),
+                (
+                    SystemObjectId::Object(continual_task.target_id.into()),
+                    AclMode::INSERT,
+                    role_id,
+                ),
+            ],
+            item_usage: &CREATE_ITEM_USAGE,
+            ..Default::default()
+        },
         Plan::CreateIndex(plan::CreateIndexPlan {
             name,
             index,
@@ -557,15 +582,18 @@ fn generate_rbac_requirements(
             item_usage: &CREATE_ITEM_USAGE,
             ..Default::default()
         },
-        Plan::CreateType(plan::CreateTypePlan { name, typ: _ }) => RbacRequirements {
-            privileges: vec![(
-                SystemObjectId::Object(name.qualifiers.clone().into()),
-                AclMode::CREATE,
-                role_id,
-            )],
-            item_usage: &CREATE_ITEM_USAGE,
-            ..Default::default()
-        },
+        Plan::CreateType(plan::CreateTypePlan { name, typ: _ })
+        | Plan::CreateFunction(plan::CreateFunctionPlan { name, function: _ }) => {
+            RbacRequirements {
+                privileges: vec![(
+                    SystemObjectId::Object(name.qualifiers.clone().into()),
+                    AclMode::CREATE,
+                    role_id,
+                )],
+                item_usage: &CREATE_ITEM_USAGE,
+                ..Default::default()
+            }
+        }
         Plan::Comment(plan::CommentPlan {
             object_id,
             sub_component: _,
@@ -921,6 +949,11 @@ fn generate_rbac_requirements(
             item_usage: &CREATE_ITEM_USAGE,
             ..Default::default()
         },
+        Plan::AlterSinkResetOffset(plan::AlterSinkResetOffsetPlan { id }) => RbacRequirements {
+            ownership: vec![ObjectId::Item(*id)],
+            item_usage: &CREATE_ITEM_USAGE,
+            ..Default::default()
+        },
         Plan::PurifiedAlterSource {
             // Keep in sync with  AlterSourcePlan elsewhere; right now this does
             // not affect the output privileges.
@@ -983,6 +1016,16 @@ fn generate_rbac_requirements(
             ownership: vec![ObjectId::ClusterReplica((*cluster_id, *replica_id))],
             ..Default::default()
         },
+        Plan::AlterClusterReplica(plan::AlterClusterReplicaPlan {
+            cluster_id,
+            replica_id,
+            name: _,
+            introspection_debugging: _,
+            introspection_interval: _,
+        }) => RbacRequirements {
+            ownership: vec![ObjectId::ClusterReplica((*cluster_id, *replica_id))],
+            ..Default::default()
+        },
         Plan::AlterItemRename(plan::AlterItemRenamePlan {
             id,
             current_full_name: _,
@@ -1053,7 +1096,11 @@ fn generate_rbac_requirements(
                 ..Default::default()
             }
         }
-        Plan::AlterSecret(plan::AlterSecretPlan { id, secret_as: _ }) => RbacRequirements {
+        Plan::AlterSecret(plan::AlterSecretPlan {
+            id,
+            secret_as: _,
+            external: _,
+        }) => RbacRequirements {
             ownership: vec![ObjectId::Item(*id)],
             item_usage: &CREATE_ITEM_USAGE,
             ..Default::default()
@@ -1074,6 +1121,15 @@ fn generate_rbac_requirements(
                 ..Default::default()
             },
         },
+        // Quotas limit the resources of roles and databases, so only superusers can change them.
+        Plan::AlterQuota(plan::AlterQuotaPlan {
+            subject: _,
+            kind: _,
+            limit: _,
+        }) => RbacRequirements {
+            superuser_action: Some("ALTER QUOTA".to_string()),
+            ..Default::default()
+        },
         Plan::AlterOwner(plan::AlterOwnerPlan {
             id,
             object_type: _,
@@ -1328,6 +1384,7 @@ fn generate_rbac_requirements(
             }
         }
         Plan::DiscardTemp
+        | Plan::DiscardPlans
         | Plan::DiscardAll
         | Plan::EmptyQuery
         | Plan::ShowAllVariables
@@ -1356,6 +1413,8 @@ fn generate_rbac_requirements(
         | Plan::AlterSystemResetAll(plan::AlterSystemResetAllPlan {})
         | Plan::Declare(plan::DeclarePlan {
             name: _,
+            scroll: _,
+            hold: _,
             stmt: _,
             sql: _,
             params: _,
@@ -1506,7 +1565,10 @@ fn generate_read_privileges_inner(
                 CatalogItemType::Type | CatalogItemType::Secret | CatalogItemType::Connection => {
                     privileges.push((SystemObjectId::Object(id.into()), AclMode::USAGE, role_id));
                 }
-                CatalogItemType::Sink | CatalogItemType::Index | CatalogItemType::Func => {}
+                CatalogItemType::Sink
+                | CatalogItemType::Index
+                | CatalogItemType::Func
+                | CatalogItemType::ContinualTask => {}
             }
         }
     }
@@ -1621,6 +1683,7 @@ pub const fn all_object_privileges(object_type: SystemObjectType) -> AclMode {
         SystemObjectType::Object(ObjectType::Database) => USAGE_CREATE_ACL_MODE,
         SystemObjectType::Object(ObjectType::Schema) => USAGE_CREATE_ACL_MODE,
         SystemObjectType::Object(ObjectType::Func) => EMPTY_ACL_MODE,
+        SystemObjectType::Object(ObjectType::ContinualTask) => EMPTY_ACL_MODE,
         SystemObjectType::System => ALL_SYSTEM_PRIVILEGES,
     }
 }
@@ -1648,7 +1711,8 @@ const fn default_builtin_object_acl_mode(object_type: ObjectType) -> AclMode {
         | ObjectType::Secret
         | ObjectType::Connection
         | ObjectType::Database
-        | ObjectType::Func => AclMode::empty(),
+        | ObjectType::Func
+        | ObjectType::ContinualTask => AclMode::empty(),
     }
 }
 
@@ -370,15 +370,22 @@ impl SessionVars {
             &SERVER_VERSION_NUM,
             &SQL_SAFE_UPDATES,
             &REAL_TIME_RECENCY,
+            &REAL_TIME_RECENCY_TIMEOUT,
             &EMIT_TIMESTAMP_NOTICE,
             &EMIT_TRACE_ID_NOTICE,
             &AUTO_ROUTE_INTROSPECTION_QUERIES,
             &ENABLE_SESSION_RBAC_CHECKS,
             &ENABLE_SESSION_CARDINALITY_ESTIMATES,
             &MAX_QUERY_RESULT_SIZE,
+            &MAX_QUERY_RESULT_ROWS,
+            &MAX_CURSOR_SPOOL_SIZE,
             &MAX_IDENTIFIER_LENGTH,
             &STATEMENT_LOGGING_SAMPLE_RATE,
             &EMIT_INTROSPECTION_QUERY_NOTICE,
+            &CLUSTER_REPLICA_FAILOVER,
+            &PREFERRED_AVAILABILITY_ZONE,
+            &EXCLUDED_CLUSTER_REPLICAS,
+            &QUERY_QUEUE_TIMEOUT,
             &UNSAFE_NEW_TRANSACTION_WALL_TIME,
             &WELCOME_MESSAGE,
         ]
@@ -648,6 +655,23 @@ impl SessionVars {
             .as_deref()
     }
 
+    /// Returns the value of the `cluster_replica_failover` configuration parameter.
+    pub fn cluster_replica_failover(&self) -> bool {
+        *self.expect_value(&CLUSTER_REPLICA_FAILOVER)
+    }
+
+    /// Returns the value of the `preferred_availability_zone` configuration parameter.
+    pub fn preferred_availability_zone(&self) -> Option<&str> {
+        self.expect_value::<Option<String>>(&PREFERRED_AVAILABILITY_ZONE)
+            .as_deref()
+    }
+
+    /// Returns the value of the `excluded_cluster_replicas` configuration parameter.
+    pub fn excluded_cluster_replicas(&self) -> &[Ident] {
+        self.expect_value::<Vec<Ident>>(&EXCLUDED_CLUSTER_REPLICAS)
+            .as_slice()
+    }
+
     /// Returns the value of the `DateStyle` configuration parameter.
     pub fn date_style(&self) -> &[&str] {
         &self.expect_value::<DateStyle>(&DATE_STYLE).0
@@ -709,6 +733,11 @@ impl SessionVars {
         self.expect_value(&STATEMENT_TIMEOUT)
     }
 
+    /// Returns the value of the `query_queue_timeout` configuration parameter.
+    pub fn query_queue_timeout(&self) -> &Duration {
+        self.expect_value(&QUERY_QUEUE_TIMEOUT)
+    }
+
     /// Returns the value of the `idle_in_transaction_session_timeout` configuration parameter.
     pub fn idle_in_transaction_session_timeout(&self) -> &Duration {
         self.expect_value(&IDLE_IN_TRANSACTION_SESSION_TIMEOUT)
@@ -730,6 +759,11 @@ impl SessionVars {
         *self.expect_value(&REAL_TIME_RECENCY)
     }
 
+    /// Returns the value of `real_time_recency_timeout` configuration parameter.
+    pub fn real_time_recency_timeout(&self) -> &Duration {
+        self.expect_value(&REAL_TIME_RECENCY_TIMEOUT)
+    }
+
     /// Returns the value of `emit_timestamp_notice` configuration parameter.
     pub fn emit_timestamp_notice(&self) -> bool {
         *self.expect_value(&EMIT_TIMESTAMP_NOTICE)
@@ -771,6 +805,17 @@ impl SessionVars {
             .as_bytes()
     }
 
+    /// Returns the value of the `max_query_result_rows` configuration parameter.
+    pub fn max_query_result_rows(&self) -> Option<usize> {
+        *self.expect_value::<Option<usize>>(&MAX_QUERY_RESULT_ROWS)
+    }
+
+    /// Returns the value of the `max_cursor_spool_size` configuration parameter.
+    pub fn max_cursor_spool_size(&self) -> u64 {
+        self.expect_value::<ByteSize>(&MAX_CURSOR_SPOOL_SIZE)
+            .as_bytes()
+    }
+
     /// Sets the external metadata associated with the user.
     pub fn set_external_user_metadata(&mut self, metadata: ExternalUserMetadata) {
         self.user.external_metadata = Some(metadata);
@@ -1095,7 +1140,9 @@ impl SystemVars {
             &MAX_ROLES,
             &MAX_RESULT_SIZE,
             &MAX_COPY_FROM_SIZE,
+            &MAX_SHARED_SUBSCRIBE_SIZE,
             &ALLOWED_CLUSTER_REPLICA_SIZES,
+            &AUDIT_EVENT_CONTEXT_FIELDS,
             &DISK_CLUSTER_REPLICAS_DEFAULT,
             &upsert_rocksdb::UPSERT_ROCKSDB_AUTO_SPILL_TO_DISK,
             &upsert_rocksdb::UPSERT_ROCKSDB_AUTO_SPILL_THRESHOLD_BYTES,
@@ -1124,7 +1171,9 @@ impl SystemVars {
             &STORAGE_RECORD_SOURCE_SINK_NAMESPACED_ERRORS,
             &PERSIST_FAST_PATH_LIMIT,
             &PERSIST_TXN_TABLES,
+            &PERSIST_SHARD_HEALTH_COLLECTION_INTERVAL,
             &METRICS_RETENTION,
+            &WRITE_TRANSACTION_READ_LOCK_TIMEOUT,
             &UNSAFE_MOCK_AUDIT_EVENT_TIMESTAMP,
             &ENABLE_RBAC_CHECKS,
             &PG_SOURCE_CONNECT_TIMEOUT,
@@ -1155,6 +1204,8 @@ impl SystemVars {
             &KEEP_N_SOURCE_STATUS_HISTORY_ENTRIES,
             &KEEP_N_SINK_STATUS_HISTORY_ENTRIES,
             &KEEP_N_PRIVATELINK_STATUS_HISTORY_ENTRIES,
+            &KEEP_N_OBJECT_STATUS_HISTORY_ENTRIES,
+            &DATAFLOW_STATISTICS_HISTORY_RETENTION,
             &DEFAULT_IDLE_ARRANGEMENT_MERGE_EFFORT,
             &DEFAULT_ARRANGEMENT_EXERT_PROPORTIONALITY,
             &ENABLE_STORAGE_SHARD_FINALIZATION,
@@ -1169,6 +1220,7 @@ impl SystemVars {
             &SENTRY_FILTERS,
             &WEBHOOKS_SECRETS_CACHING_TTL_SECS,
             &COORD_SLOW_MESSAGE_WARN_THRESHOLD,
+            &COORD_SLOW_MESSAGE_REPORT_THRESHOLD,
             &grpc_client::CONNECT_TIMEOUT,
             &grpc_client::HTTP2_KEEP_ALIVE_INTERVAL,
             &grpc_client::HTTP2_KEEP_ALIVE_TIMEOUT,
@@ -1187,6 +1239,14 @@ impl SystemVars {
             &STATEMENT_LOGGING_DEFAULT_SAMPLE_RATE,
             &STATEMENT_LOGGING_TARGET_DATA_RATE,
             &STATEMENT_LOGGING_MAX_DATA_CREDIT,
+            &STATEMENT_HISTORY_RETENTION,
+            &INDEX_USAGE_COLLECTION_INTERVAL,
+            &UNUSED_INDEX_NOTICE_THRESHOLD,
+            &MIXED_CLUSTER_WORKLOAD_CHECK_INTERVAL,
+            &CLUSTER_SIZING_CHECK_INTERVAL,
+            &CLUSTER_SIZING_WINDOW,
+            &READ_HOLDS_COLLECTION_INTERVAL,
+            &OBJECT_STATUS_CHECK_INTERVAL,
             &OPTIMIZER_STATS_TIMEOUT,
             &OPTIMIZER_ONESHOT_STATS_TIMEOUT,
             &PRIVATELINK_STATUS_UPDATE_QUOTA_PER_MINUTE,
@@ -1198,6 +1258,8 @@ impl SystemVars {
             &PG_TIMESTAMP_ORACLE_CONNECTION_POOL_TTL,
             &PG_TIMESTAMP_ORACLE_CONNECTION_POOL_TTL_STAGGER,
             &USER_STORAGE_MANAGED_COLLECTIONS_BATCH_DURATION,
+            &GRACEFUL_CLUSTER_RECONFIGURATION_TIMEOUT,
+            &CLUSTER_SCHEDULE_CHECK_INTERVAL,
         ];
 
         let dyncfgs = mz_dyncfgs::all_dyncfgs();
@@ -1592,6 +1654,12 @@ impl SystemVars {
         *self.expect_value(&MAX_COPY_FROM_SIZE)
     }
 
+    /// Returns the value of the `max_shared_subscribe_size` configuration parameter.
+    pub fn max_shared_subscribe_size(&self) -> u64 {
+        self.expect_value::<ByteSize>(&MAX_SHARED_SUBSCRIBE_SIZE)
+            .as_bytes()
+    }
+
     /// Returns the value of the `allowed_cluster_replica_sizes` configuration parameter.
     pub fn allowed_cluster_replica_sizes(&self) -> Vec<String> {
         self.expect_value::<Vec<Ident>>(&ALLOWED_CLUSTER_REPLICA_SIZES)
@@ -1600,6 +1668,14 @@ impl SystemVars {
             .collect()
     }
 
+    /// Returns the value of the `audit_event_context_fields` configuration parameter.
+    pub fn audit_event_context_fields(&self) -> Vec<String> {
+        self.expect_value::<Vec<Ident>>(&AUDIT_EVENT_CONTEXT_FIELDS)
+            .into_iter()
+            .map(|s| s.as_str().into())
+            .collect()
+    }
+
     /// Returns the `disk_cluster_replicas_default` configuration parameter.
     pub fn disk_cluster_replicas_default(&self) -> bool {
         *self.expect_value(&DISK_CLUSTER_REPLICAS_DEFAULT)
@@ -1689,6 +1765,11 @@ impl SystemVars {
         *self.expect_value(&PERSIST_TXN_TABLES)
     }
 
+    /// Returns the `persist_shard_health_collection_interval` configuration parameter.
+    pub fn persist_shard_health_collection_interval(&self) -> Duration {
+        *self.expect_value(&PERSIST_SHARD_HEALTH_COLLECTION_INTERVAL)
+    }
+
     /// Returns the `pg_source_connect_timeout` configuration parameter.
     pub fn pg_source_connect_timeout(&self) -> Duration {
         *self.expect_value(&PG_SOURCE_CONNECT_TIMEOUT)
@@ -1888,6 +1969,11 @@ impl SystemVars {
         *self.expect_value(&METRICS_RETENTION)
     }
 
+    /// Returns the `write_transaction_read_lock_timeout` configuration parameter.
+    pub fn write_transaction_read_lock_timeout(&self) -> Duration {
+        *self.expect_value(&WRITE_TRANSACTION_READ_LOCK_TIMEOUT)
+    }
+
     /// Returns the `unsafe_mock_audit_event_timestamp` configuration parameter.
     pub fn unsafe_mock_audit_event_timestamp(&self) -> Option<mz_repr::Timestamp> {
         *self.expect_value(&UNSAFE_MOCK_AUDIT_EVENT_TIMESTAMP)
@@ -1920,6 +2006,15 @@ impl SystemVars {
         *self.expect_value(&KEEP_N_PRIVATELINK_STATUS_HISTORY_ENTRIES)
     }
 
+    pub fn keep_n_object_status_history_entries(&self) -> usize {
+        *self.expect_value(&KEEP_N_OBJECT_STATUS_HISTORY_ENTRIES)
+    }
+
+    /// Returns the `dataflow_statistics_history_retention` configuration parameter.
+    pub fn dataflow_statistics_history_retention(&self) -> Duration {
+        *self.expect_value(&DATAFLOW_STATISTICS_HISTORY_RETENTION)
+    }
+
     /// Returns the `default_idle_arrangement_merge_effort` configuration parameter.
     pub fn default_idle_arrangement_merge_effort(&self) -> u32 {
         *self.expect_value(&DEFAULT_IDLE_ARRANGEMENT_MERGE_EFFORT)
@@ -1986,6 +2081,10 @@ impl SystemVars {
         *self.expect_value(&COORD_SLOW_MESSAGE_WARN_THRESHOLD)
     }
 
+    pub fn coord_slow_message_report_threshold(&self) -> Duration {
+        *self.expect_value(&COORD_SLOW_MESSAGE_REPORT_THRESHOLD)
+    }
+
     pub fn grpc_client_http2_keep_alive_interval(&self) -> Duration {
         *self.expect_value(&grpc_client::HTTP2_KEEP_ALIVE_INTERVAL)
     }
@@ -2061,6 +2160,46 @@ impl SystemVars {
         *self.expect_value(&STATEMENT_LOGGING_DEFAULT_SAMPLE_RATE)
     }
 
+    /// Returns the `statement_history_retention` configuration parameter.
+    pub fn statement_history_retention(&self) -> Duration {
+        *self.expect_value(&STATEMENT_HISTORY_RETENTION)
+    }
+
+    /// Returns the `index_usage_collection_interval` configuration parameter.
+    pub fn index_usage_collection_interval(&self) -> Duration {
+        *self.expect_value(&INDEX_USAGE_COLLECTION_INTERVAL)
+    }
+
+    /// Returns the `unused_index_notice_threshold` configuration parameter.
+    pub fn unused_index_notice_threshold(&self) -> Duration {
+        *self.expect_value(&UNUSED_INDEX_NOTICE_THRESHOLD)
+    }
+
+    /// Returns the `mixed_cluster_workload_check_interval` configuration parameter.
+    pub fn mixed_cluster_workload_check_interval(&self) -> Duration {
+        *self.expect_value(&MIXED_CLUSTER_WORKLOAD_CHECK_INTERVAL)
+    }
+
+    /// Returns the `cluster_sizing_check_interval` configuration parameter.
+    pub fn cluster_sizing_check_interval(&self) -> Duration {
+        *self.expect_value(&CLUSTER_SIZING_CHECK_INTERVAL)
+    }
+
+    /// Returns the `cluster_sizing_window` configuration parameter.
+    pub fn cluster_sizing_window(&self) -> Duration {
+        *self.expect_value(&CLUSTER_SIZING_WINDOW)
+    }
+
+    /// Returns the `read_holds_collection_interval` configuration parameter.
+    pub fn read_holds_collection_interval(&self) -> Duration {
+        *self.expect_value(&READ_HOLDS_COLLECTION_INTERVAL)
+    }
+
+    /// Returns the `object_status_check_interval` configuration parameter.
+    pub fn object_status_check_interval(&self) -> Duration {
+        *self.expect_value(&OBJECT_STATUS_CHECK_INTERVAL)
+    }
+
     /// Returns the `optimizer_stats_timeout` configuration parameter.
     pub fn optimizer_stats_timeout(&self) -> Duration {
         *self.expect_value(&OPTIMIZER_STATS_TIMEOUT)
@@ -2110,6 +2249,16 @@ impl SystemVars {
         *self.expect_value(&USER_STORAGE_MANAGED_COLLECTIONS_BATCH_DURATION)
     }
 
+    /// Returns the `graceful_cluster_reconfiguration_timeout` configuration parameter.
+    pub fn graceful_cluster_reconfiguration_timeout(&self) -> Duration {
+        *self.expect_value(&GRACEFUL_CLUSTER_RECONFIGURATION_TIMEOUT)
+    }
+
+    /// Returns the `cluster_schedule_check_interval` configuration parameter.
+    pub fn cluster_schedule_check_interval(&self) -> Duration {
+        *self.expect_value(&CLUSTER_SCHEDULE_CHECK_INTERVAL)
+    }
+
     /// Returns whether the named variable is a compute configuration parameter
     /// (things that go in `ComputeParameters` and are sent to replicas via `UpdateConfiguration`
     /// commands).
@@ -13,6 +13,7 @@ use std::fmt::Debug;
 use std::ops::{RangeBounds, RangeInclusive};
 
 use mz_repr::adt::numeric::Numeric;
+use mz_sql_parser::ast::Ident;
 
 use super::{Value, Var, VarError};
 
@@ -21,6 +22,13 @@ pub static NUMERIC_NON_NEGATIVE: NumericNonNegNonNan = NumericNonNegNonNan;
 pub static NUMERIC_BOUNDED_0_1_INCLUSIVE: NumericInRange<RangeInclusive<f64>> =
     NumericInRange(0.0f64..=1.0);
 
+pub static AUDIT_EVENT_CONTEXT_FIELD_NAMES: IdentsInSet = IdentsInSet(&[
+    "client_ip",
+    "application_name",
+    "http_request_id",
+    "sso_subject",
+]);
+
 #[derive(Debug)]
 pub enum ValueConstraint {
     /// Variable is read-only and cannot be updated.
@@ -149,3 +157,28 @@ where
         }
     }
 }
+
+/// Constrains a list of identifiers to a fixed set of names.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct IdentsInSet(pub &'static [&'static str]);
+
+impl DomainConstraint for IdentsInSet {
+    type Value = Vec<Ident>;
+
+    fn check(&self, var: &dyn Var, idents: &Vec<Ident>) -> Result<(), VarError> {
+        let invalid_values: Vec<_> = idents
+            .iter()
+            .filter(|ident| !self.0.contains(&ident.as_str()))
+            .map(|ident| ident.to_string())
+            .collect();
+        if invalid_values.is_empty() {
+            Ok(())
+        } else {
+            Err(VarError::ConstrainedParameter {
+                name: var.name(),
+                invalid_values,
+                valid_values: Some(self.0.to_vec()),
+            })
+        }
+    }
+}
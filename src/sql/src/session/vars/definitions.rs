@@ -33,7 +33,8 @@ use uncased::UncasedStr;
 
 use crate::session::user::{User, SUPPORT_USER, SYSTEM_USER};
 use crate::session::vars::constraints::{
-    DomainConstraint, ValueConstraint, NUMERIC_BOUNDED_0_1_INCLUSIVE, NUMERIC_NON_NEGATIVE,
+    DomainConstraint, ValueConstraint, AUDIT_EVENT_CONTEXT_FIELD_NAMES,
+    NUMERIC_BOUNDED_0_1_INCLUSIVE, NUMERIC_NON_NEGATIVE,
 };
 use crate::session::vars::errors::VarError;
 use crate::session::vars::polyfill::{lazy_value, value, LazyValueFn};
@@ -288,6 +289,32 @@ pub static CLUSTER_REPLICA: VarDefinition = VarDefinition::new(
     false,
 );
 
+pub static CLUSTER_REPLICA_FAILOVER: VarDefinition = VarDefinition::new(
+    "cluster_replica_failover",
+    value!(bool; false),
+    "Whether to serve SELECT and SUBSCRIBE queries from another replica if the replica \
+        selected by cluster_replica is not ready (Materialize).",
+    false,
+)
+.with_feature_flag(&ENABLE_CLUSTER_REPLICA_PREFERENCES);
+
+pub static PREFERRED_AVAILABILITY_ZONE: VarDefinition = VarDefinition::new(
+    "preferred_availability_zone",
+    value!(Option<String>; None),
+    "Sets the availability zone of the cluster replicas preferred for serving SELECT and \
+        SUBSCRIBE queries (Materialize).",
+    false,
+)
+.with_feature_flag(&ENABLE_CLUSTER_REPLICA_PREFERENCES);
+
+pub static EXCLUDED_CLUSTER_REPLICAS: VarDefinition = VarDefinition::new(
+    "excluded_cluster_replicas",
+    value!(Vec<Ident>; Vec::new()),
+    "Sets the cluster replicas that must not serve SELECT and SUBSCRIBE queries (Materialize).",
+    false,
+)
+.with_feature_flag(&ENABLE_CLUSTER_REPLICA_PREFERENCES);
+
 pub static DATABASE: VarDefinition = VarDefinition::new_lazy(
     "database",
     lazy_value!(String; || DEFAULT_DATABASE_NAME.to_string()),
@@ -347,12 +374,24 @@ pub static SEARCH_PATH: VarDefinition = VarDefinition::new_lazy(
 
 pub static STATEMENT_TIMEOUT: VarDefinition = VarDefinition::new(
     "statement_timeout",
-    value!(Duration; Duration::from_secs(10)),
-    "Sets the maximum allowed duration of INSERT...SELECT, UPDATE, and DELETE operations. \
-    If this value is specified without units, it is taken as milliseconds.",
+    value!(Duration; Duration::ZERO),
+    "Sets the maximum allowed duration of SELECT, SUBSCRIBE, INSERT...SELECT, UPDATE, and \
+    DELETE operations. If this value is specified without units, it is taken as milliseconds. \
+    A value of zero disables the timeout, except that INSERT...SELECT, UPDATE, and DELETE \
+    operations are still limited to 10 seconds.",
     false,
 );
 
+pub static QUERY_QUEUE_TIMEOUT: VarDefinition = VarDefinition::new(
+    "query_queue_timeout",
+    value!(Duration; Duration::from_secs(60)),
+    "Sets the maximum duration that a SELECT waits for admission to a cluster that is running \
+    its maximum number of concurrent queries. If this value is specified without units, it is \
+    taken as milliseconds. A value of zero disables the timeout (Materialize).",
+    false,
+)
+.with_feature_flag(&ENABLE_CLUSTER_QUERY_QUEUEING);
+
 pub static IDLE_IN_TRANSACTION_SESSION_TIMEOUT: VarDefinition = VarDefinition::new(
     "idle_in_transaction_session_timeout",
     value!(Duration; Duration::from_secs(60 * 2)),
@@ -536,6 +575,20 @@ pub static MAX_QUERY_RESULT_SIZE: VarDefinition = VarDefinition::new(
     false,
 );
 
+pub static MAX_QUERY_RESULT_ROWS: VarDefinition = VarDefinition::new(
+    "max_query_result_rows",
+    value!(Option<usize>; None),
+    "The maximum number of rows in a single query's result. Unlimited if empty (Materialize).",
+    false,
+);
+
+pub static MAX_CURSOR_SPOOL_SIZE: VarDefinition = VarDefinition::new(
+    "max_cursor_spool_size",
+    value!(ByteSize; ByteSize::gb(1)),
+    "The maximum size in bytes of the rows buffered for a SCROLL or WITH HOLD cursor (Materialize).",
+    false,
+);
+
 pub static MAX_COPY_FROM_SIZE: VarDefinition = VarDefinition::new(
     "max_copy_from_size",
     // 1 GiB, this limit is noted in the docs, if you change it make sure to update our docs.
@@ -544,6 +597,14 @@ pub static MAX_COPY_FROM_SIZE: VarDefinition = VarDefinition::new(
     false,
 );
 
+pub static MAX_SHARED_SUBSCRIBE_SIZE: VarDefinition = VarDefinition::new(
+    "max_shared_subscribe_size",
+    value!(ByteSize; ByteSize::mb(64)),
+    "The maximum size in bytes of the relation contents retained for a shared SUBSCRIBE \
+     dataflow, beyond which other SUBSCRIBEs no longer attach to it (Materialize).",
+    false,
+);
+
 pub static MAX_IDENTIFIER_LENGTH: VarDefinition = VarDefinition::new(
     "max_identifier_length",
     value!(usize; mz_sql_lexer::lexer::MAX_IDENTIFIER_LENGTH),
@@ -571,6 +632,16 @@ pub static METRICS_RETENTION: VarDefinition = VarDefinition::new(
     true,
 );
 
+/// The maximum duration that a write transaction holds the write lock that its reads acquire.
+pub static WRITE_TRANSACTION_READ_LOCK_TIMEOUT: VarDefinition = VarDefinition::new(
+    "write_transaction_read_lock_timeout",
+    value!(Duration; Duration::from_secs(30)),
+    "Sets the maximum duration that a write transaction can hold the write lock that its reads \
+    acquire. Once it has passed, the transaction can only be rolled back, and idle sessions are \
+    terminated (Materialize).",
+    true,
+);
+
 pub static ALLOWED_CLUSTER_REPLICA_SIZES: VarDefinition = VarDefinition::new(
     "allowed_cluster_replica_sizes",
     value!(Vec<Ident>; Vec::new()),
@@ -578,6 +649,20 @@ pub static ALLOWED_CLUSTER_REPLICA_SIZES: VarDefinition = VarDefinition::new(
     false,
 );
 
+pub static AUDIT_EVENT_CONTEXT_FIELDS: VarDefinition = VarDefinition::new_lazy(
+    "audit_event_context_fields",
+    lazy_value!(Vec<Ident>; || {
+        AUDIT_EVENT_CONTEXT_FIELD_NAMES
+            .0
+            .iter()
+            .map(|name| Ident::new_unchecked(*name))
+            .collect()
+    }),
+    "The fields of the client context to record in audit events (Materialize).",
+    false,
+)
+.with_constraint(&AUDIT_EVENT_CONTEXT_FIELD_NAMES);
+
 pub static PERSIST_FAST_PATH_LIMIT: VarDefinition = VarDefinition::new(
     "persist_fast_path_limit",
     value!(usize; 0),
@@ -603,6 +688,15 @@ pub static PERSIST_TXN_TABLES: VarDefinition = VarDefinition::new(
     true,
 );
 
+/// The interval at which `mz_internal.mz_persist_shard_health` is refreshed.
+pub static PERSIST_SHARD_HEALTH_COLLECTION_INTERVAL: VarDefinition = VarDefinition::new(
+    "persist_shard_health_collection_interval",
+    value!(Duration; Duration::from_secs(5 * 60)),
+    "The interval at which to collect the health of persist shards for \
+        `mz_persist_shard_health` (Materialize).",
+    true,
+);
+
 pub static TIMESTAMP_ORACLE_IMPL: VarDefinition = VarDefinition::new(
     "timestamp_oracle",
     value!(TimestampOracleImpl; TimestampOracleImpl::Postgres),
@@ -912,6 +1006,14 @@ pub static COORD_SLOW_MESSAGE_WARN_THRESHOLD: VarDefinition = VarDefinition::new
     true,
 );
 
+pub static COORD_SLOW_MESSAGE_REPORT_THRESHOLD: VarDefinition = VarDefinition::new(
+    "coord_slow_message_report_threshold",
+    value!(Duration; Duration::from_secs(1)),
+    "Sets the threshold at which a coordinator message is recorded in \
+    mz_coordinator_slow_messages.",
+    true,
+);
+
 /// Controls the connect_timeout setting when connecting to PG via `mz_postgres_util`.
 pub static PG_SOURCE_CONNECT_TIMEOUT: VarDefinition = VarDefinition::new(
     "pg_source_connect_timeout",
@@ -1211,6 +1313,15 @@ pub static REAL_TIME_RECENCY: VarDefinition = VarDefinition::new(
 )
 .with_feature_flag(&ALLOW_REAL_TIME_RECENCY);
 
+pub static REAL_TIME_RECENCY_TIMEOUT: VarDefinition = VarDefinition::new(
+    "real_time_recency_timeout",
+    value!(Duration; Duration::from_secs(10)),
+    "Sets the maximum time a query with real_time_recency enabled waits for sources to ingest \
+    the data that was available upstream when the query was issued (Materialize).",
+    false,
+)
+.with_feature_flag(&ALLOW_REAL_TIME_RECENCY);
+
 pub static EMIT_TIMESTAMP_NOTICE: VarDefinition = VarDefinition::new(
     "emit_timestamp_notice",
     value!(bool; false),
@@ -1352,6 +1463,70 @@ pub static STATEMENT_LOGGING_DEFAULT_SAMPLE_RATE: VarDefinition = VarDefinition:
 )
 .with_constraint(&NUMERIC_BOUNDED_0_1_INCLUSIVE);
 
+pub static STATEMENT_HISTORY_RETENTION: VarDefinition = VarDefinition::new(
+    "statement_history_retention",
+    value!(Duration; Duration::from_secs(30 * 24 * 60 * 60)),
+    "How long finished statements are reported in `mz_internal.mz_statement_history` \
+        (Materialize).",
+    true,
+);
+
+pub static INDEX_USAGE_COLLECTION_INTERVAL: VarDefinition = VarDefinition::new(
+    "index_usage_collection_interval",
+    value!(Duration; Duration::from_secs(60)),
+    "The interval at which to report index usage in `mz_internal.mz_index_usage` and to \
+        check for unused indexes (Materialize).",
+    true,
+);
+
+pub static MIXED_CLUSTER_WORKLOAD_CHECK_INTERVAL: VarDefinition = VarDefinition::new(
+    "mixed_cluster_workload_check_interval",
+    value!(Duration; Duration::from_secs(5 * 60)),
+    "The interval at which to check for clusters that host both sources or sinks and indexes \
+        (Materialize).",
+    true,
+);
+
+pub static CLUSTER_SIZING_CHECK_INTERVAL: VarDefinition = VarDefinition::new(
+    "cluster_sizing_check_interval",
+    value!(Duration; Duration::from_secs(60)),
+    "The interval at which to sample the CPU and memory utilization of clusters to recommend \
+        cluster sizes (Materialize).",
+    true,
+);
+
+pub static CLUSTER_SIZING_WINDOW: VarDefinition = VarDefinition::new(
+    "cluster_sizing_window",
+    value!(Duration; Duration::from_secs(60 * 60)),
+    "The period over which a cluster must be consistently over- or under-provisioned before \
+        a cluster size is recommended (Materialize).",
+    true,
+);
+
+pub static READ_HOLDS_COLLECTION_INTERVAL: VarDefinition = VarDefinition::new(
+    "read_holds_collection_interval",
+    value!(Duration; Duration::from_secs(30)),
+    "The interval at which to report the read holds on collections in \
+        `mz_internal.mz_read_holds` (Materialize).",
+    true,
+);
+
+pub static OBJECT_STATUS_CHECK_INTERVAL: VarDefinition = VarDefinition::new(
+    "object_status_check_interval",
+    value!(Duration; Duration::from_secs(10)),
+    "The interval at which to record the statuses of clusters and materialized views in \
+        `mz_internal.mz_object_status_history` (Materialize).",
+    true,
+);
+
+pub static UNUSED_INDEX_NOTICE_THRESHOLD: VarDefinition = VarDefinition::new(
+    "unused_index_notice_threshold",
+    value!(Duration; Duration::from_secs(7 * 24 * 60 * 60)),
+    "How long an index must go unused before an `Unused index` notice is raised for it \
+        (Materialize).",
+    true,
+);
+
 pub static AUTO_ROUTE_INTROSPECTION_QUERIES: VarDefinition = VarDefinition::new(
     "auto_route_introspection_queries",
     value!(bool; true),
@@ -1398,6 +1573,25 @@ pub static KEEP_N_PRIVATELINK_STATUS_HISTORY_ENTRIES: VarDefinition = VarDefinit
     true,
 );
 
+/// Controls [`mz_storage_types::parameters::StorageParameters::keep_n_object_status_history_entries`].
+pub static KEEP_N_OBJECT_STATUS_HISTORY_ENTRIES: VarDefinition = VarDefinition::new(
+    "keep_n_object_status_history_entries",
+    value!(usize; 5),
+    "On reboot, truncate all but the last n entries per ID in the mz_object_status_history \
+        collection (Materialize).",
+    true,
+);
+
+/// Controls [`mz_storage_types::parameters::StorageParameters::dataflow_statistics_history_retention`].
+pub static DATAFLOW_STATISTICS_HISTORY_RETENTION: VarDefinition = VarDefinition::new(
+    "dataflow_statistics_history_retention",
+    // 30 days
+    value!(Duration; Duration::from_secs(30 * 24 * 60 * 60)),
+    "On reboot, remove the samples older than this from the mz_dataflow_statistics_history \
+        collection (Materialize).",
+    true,
+);
+
 pub static ENABLE_STORAGE_SHARD_FINALIZATION: VarDefinition = VarDefinition::new(
     "enable_storage_shard_finalization",
     value!(bool; true),
@@ -1440,6 +1634,22 @@ pub static USER_STORAGE_MANAGED_COLLECTIONS_BATCH_DURATION: VarDefinition = VarD
     true,
 );
 
+pub static GRACEFUL_CLUSTER_RECONFIGURATION_TIMEOUT: VarDefinition = VarDefinition::new(
+    "graceful_cluster_reconfiguration_timeout",
+    value!(Duration; Duration::from_secs(60 * 60)),
+    "Maximum duration to wait for new replicas to hydrate during a graceful cluster \
+        reconfiguration, before cutting over regardless (Materialize).",
+    true,
+);
+
+pub static CLUSTER_SCHEDULE_CHECK_INTERVAL: VarDefinition = VarDefinition::new(
+    "cluster_schedule_check_interval",
+    value!(Duration; Duration::from_secs(10)),
+    "Interval at which the replicas of clusters with a SCHEDULE are turned on or off \
+        (Materialize).",
+    true,
+);
+
 pub static ENABLE_DEPENDENCY_READ_HOLD_ASSERTS: VarDefinition = VarDefinition::new(
     "enable_dependency_read_hold_asserts",
     value!(bool; true),
@@ -1933,6 +2143,41 @@ feature_flags!(
         internal: true,
         enable_for_item_parsing: true,
     },
+    {
+        name: enable_notices_for_unused_index,
+        desc: "raising notices for indexes that have not been used recently",
+        default: true,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_notices_for_optimization_did_not_converge,
+        desc: "emitting notices for optimizations that did not converge (doesn't affect EXPLAIN)",
+        default: true,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_notices_for_plan_changed,
+        desc: "raising notices for indexes and materialized views whose plans changed on restart",
+        default: true,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_notices_for_mixed_cluster_workload,
+        desc: "raising notices for clusters that host both sources or sinks and indexes",
+        default: true,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_notices_for_cluster_size_recommendation,
+        desc: "raising notices that recommend sizes for over- or under-provisioned clusters",
+        default: true,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
     {
         name: enable_explain_broken,
         desc: "EXPLAIN ... BROKEN <query> syntax",
@@ -2088,6 +2333,153 @@ feature_flags!(
         internal: true,
         enable_for_item_parsing: false,
     },
+    {
+        name: enable_left_join_key_sharing,
+        desc: "Share the key arrangements of fanned-out left joins in variadic left join lowering",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_source_timestamp_policy,
+        desc: "CREATE SOURCE ... WITH (TIMESTAMP POLICY)",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_source_dead_letter_queue,
+        desc: "CREATE SOURCE ... WITH (ERRORS = DLQ)",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_source_retry_policy,
+        desc: "CREATE SOURCE ... WITH (RETRY ...)",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_upsert_state_options,
+        desc: "CREATE SOURCE ... WITH (UPSERT ...)",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_iceberg_sink,
+        desc: "Create an Iceberg catalog connection or sink",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_csv_header_types,
+        desc: "FORMAT CSV WITH HEADER column types and header inference",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_protobuf_sink,
+        desc: "FORMAT PROTOBUF for sinks",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_kafka_sink_headers,
+        desc: "HEADERS FROM for Kafka sinks",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_kafka_sink_partition_by,
+        desc: "PARTITION BY for Kafka sinks",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_dataflow_memory_limit,
+        desc: "MEMORY LIMIT for materialized views",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_cluster_replica_preferences,
+        desc: "cluster replica preferences",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_graceful_cluster_reconfiguration,
+        desc: "graceful reconfiguration of managed clusters",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_builtin_expression_cache,
+        desc: "caching the optimized expressions of builtin views across restarts",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_plan_change_detection,
+        desc: "detecting changes to the plans of indexes and materialized views across restarts",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_cluster_schedules,
+        desc: "cluster schedules",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_cluster_query_queueing,
+        desc: "MAX CONCURRENT QUERIES",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_shared_subscribes,
+        desc: "sharing dataflows between identical SUBSCRIBEs",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_external_secrets,
+        desc: "CREATE SECRET ... FROM EXTERNAL",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_create_function,
+        desc: "CREATE FUNCTION",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_continual_task_create,
+        desc: "CREATE CONTINUAL TASK",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
 );
 
 impl From<&super::SystemVars> for OptimizerFeatures {
@@ -2099,6 +2491,7 @@ impl From<&super::SystemVars> for OptimizerFeatures {
             enable_new_outer_join_lowering: vars.enable_new_outer_join_lowering(),
             enable_reduce_mfp_fusion: vars.enable_reduce_mfp_fusion(),
             enable_variadic_left_join_lowering: vars.enable_variadic_left_join_lowering(),
+            enable_left_join_key_sharing: vars.enable_left_join_key_sharing(),
             persist_fast_path_limit: vars.persist_fast_path_limit(),
             reoptimize_imported_views: false,
         }
@@ -1039,6 +1039,7 @@ impl<'a> RunnerInner<'a> {
                     secrets_reader_local_file_dir: Some(secrets_dir),
                     secrets_reader_kubernetes_context: None,
                     secrets_reader_aws_prefix: None,
+                    secrets_reader_external_aws_allowed_arn_prefix: Vec::new(),
                 },
                 connection_context,
             },
@@ -2389,6 +2390,7 @@ fn generate_view_sql(
         if_exists: false,
         names: vec![UnresolvedObjectName::Item(name)],
         cascade: false,
+        dry_run: false,
     })
     .to_ast_string_stable();
 
@@ -357,6 +357,21 @@ pub struct StatusUpdate {
     pub error: Option<String>,
     pub hints: BTreeSet<String>,
     pub namespaced_errors: BTreeMap<String, String>,
+    /// The state of the object's retry policy, if it is restarting after a
+    /// failure.
+    pub retry: Option<RetryStatus>,
+}
+
+/// The progress of an object through its retry policy after a failure.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RetryStatus {
+    /// The number of consecutive restarts attempted so far.
+    pub attempt: u64,
+    /// The maximum number of restarts the retry policy permits, if any.
+    pub max_attempts: Option<u64>,
+    /// When the next restart will be attempted, or `None` if the object has
+    /// exhausted its retries.
+    pub next_attempt_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 impl StatusUpdate {
@@ -372,6 +387,7 @@ impl StatusUpdate {
             error: None,
             hints: Default::default(),
             namespaced_errors: Default::default(),
+            retry: None,
         }
     }
 }
@@ -390,9 +406,17 @@ impl From<StatusUpdate> for Row {
         let mut packer = row.packer();
         packer.extend([timestamp, id, status, error]);
 
-        if !update.hints.is_empty() || !update.namespaced_errors.is_empty() {
+        if !update.hints.is_empty()
+            || !update.namespaced_errors.is_empty()
+            || update.retry.is_some()
+        {
+            let next_attempt_at = update
+                .retry
+                .as_ref()
+                .and_then(|r| r.next_attempt_at)
+                .map(|ts| ts.to_rfc3339());
             packer.push_dict_with(|dict_packer| {
-                // `hint` and `namespaced` are ordered,
+                // `hint`, `namespaced`, and `retry` are ordered,
                 // as well as the BTree's they each contain.
                 if !update.hints.is_empty() {
                     dict_packer.push(Datum::String("hints"));
@@ -407,6 +431,24 @@ impl From<StatusUpdate> for Row {
                             .map(|(k, v)| (k.as_str(), Datum::String(v))),
                     );
                 }
+                if let Some(retry) = &update.retry {
+                    dict_packer.push(Datum::String("retry"));
+                    dict_packer.push_dict([
+                        ("attempt", Datum::from(u128::from(retry.attempt))),
+                        (
+                            "max_attempts",
+                            retry
+                                .max_attempts
+                                .map_or(Datum::JsonNull, |max| Datum::from(u128::from(max))),
+                        ),
+                        (
+                            "next_attempt_at",
+                            next_attempt_at
+                                .as_deref()
+                                .map_or(Datum::JsonNull, Datum::String),
+                        ),
+                    ]);
+                }
             });
         } else {
             packer.push(Datum::Null);
@@ -416,6 +458,154 @@ impl From<StatusUpdate> for Row {
     }
 }
 
+/// The health of an object, in the model that is shared across sources, sinks,
+/// clusters, and materialized views.
+///
+/// Each kind of object tracks its health in more detail elsewhere, e.g. in
+/// [`Status`] for sources and sinks. This model reduces those to the few states
+/// that are meaningful for all of them, so that all objects can be monitored
+/// in the same way.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ObjectStatus {
+    /// The object is not yet doing its work, e.g. because it is hydrating.
+    Starting,
+    /// The object is doing its work.
+    Running,
+    /// The object is prevented from doing its work, but might recover on its
+    /// own, e.g. once its cluster has replicas again.
+    Stalled,
+    /// The object failed in a way that requires intervention.
+    Errored,
+}
+
+impl std::str::FromStr for ObjectStatus {
+    type Err = anyhow::Error;
+    /// Keep in sync with [`ObjectStatus::as_str`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "starting" => ObjectStatus::Starting,
+            "running" => ObjectStatus::Running,
+            "stalled" => ObjectStatus::Stalled,
+            "errored" => ObjectStatus::Errored,
+            s => return Err(anyhow::anyhow!("{} is not a valid object status", s)),
+        })
+    }
+}
+
+impl ObjectStatus {
+    /// Keep in sync with `ObjectStatus::from_str`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ObjectStatus::Starting => "starting",
+            ObjectStatus::Running => "running",
+            ObjectStatus::Stalled => "stalled",
+            ObjectStatus::Errored => "errored",
+        }
+    }
+
+    /// Returns the shared status that corresponds to the status of a source or
+    /// sink, or `None` if the object no longer exists.
+    pub fn from_storage_status(status: Status) -> Option<ObjectStatus> {
+        match status {
+            Status::Starting => Some(ObjectStatus::Starting),
+            Status::Running => Some(ObjectStatus::Running),
+            Status::Paused | Status::Stalled => Some(ObjectStatus::Stalled),
+            Status::Ceased => Some(ObjectStatus::Errored),
+            Status::Dropped => None,
+        }
+    }
+}
+
+/// The kinds of objects whose health is reported as an [`ObjectStatus`].
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StatusObjectType {
+    Source,
+    Sink,
+    Cluster,
+    MaterializedView,
+}
+
+impl StatusObjectType {
+    /// Returns the name of the object type, as reported in
+    /// `mz_internal.mz_object_status_history`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatusObjectType::Source => "source",
+            StatusObjectType::Sink => "sink",
+            StatusObjectType::Cluster => "cluster",
+            StatusObjectType::MaterializedView => "materialized-view",
+        }
+    }
+}
+
+impl std::str::FromStr for StatusObjectType {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "source" => StatusObjectType::Source,
+            "sink" => StatusObjectType::Sink,
+            "cluster" => StatusObjectType::Cluster,
+            "materialized-view" => StatusObjectType::MaterializedView,
+            s => return Err(anyhow::anyhow!("{} is not a valid status object type", s)),
+        })
+    }
+}
+
+/// A transition of an object to a new [`ObjectStatus`].
+///
+/// Packs into a row of `mz_internal.mz_object_status_history`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ObjectStatusUpdate {
+    /// The ID of the object. Depending on `object_type`, this is the ID of a
+    /// catalog item or of a cluster.
+    pub object_id: String,
+    pub object_type: StatusObjectType,
+    pub status: ObjectStatus,
+    /// Why the object has the status, if it is not running.
+    pub reason: Option<String>,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+impl ObjectStatusUpdate {
+    /// Returns the shared status update that corresponds to the status update
+    /// of a source or sink, or `None` if the object no longer exists.
+    pub fn from_storage_update(
+        update: &StatusUpdate,
+        object_type: StatusObjectType,
+    ) -> Option<ObjectStatusUpdate> {
+        let status = ObjectStatus::from_storage_status(update.status)?;
+        let reason = match (&update.error, update.status) {
+            (Some(error), _) => Some(error.clone()),
+            (None, Status::Paused) => Some("paused".into()),
+            (None, _) => None,
+        };
+        Some(ObjectStatusUpdate {
+            object_id: update.id.to_string(),
+            object_type,
+            status,
+            reason,
+            timestamp: update.timestamp,
+        })
+    }
+}
+
+impl From<ObjectStatusUpdate> for Row {
+    fn from(update: ObjectStatusUpdate) -> Self {
+        use mz_repr::Datum;
+
+        let timestamp = Datum::TimestampTz(update.timestamp.try_into().expect("must fit"));
+        let mut row = Row::default();
+        row.packer().extend([
+            timestamp,
+            Datum::String(&update.object_id),
+            Datum::String(update.object_type.as_str()),
+            Datum::String(update.status.as_str()),
+            update.reason.as_deref().into(),
+        ]);
+        row
+    }
+}
+
 impl RustType<proto_storage_response::ProtoStatus> for Status {
     fn into_proto(&self) -> proto_storage_response::ProtoStatus {
         use proto_storage_response::proto_status::*;
@@ -458,6 +648,14 @@ impl RustType<proto_storage_response::ProtoStatusUpdate> for StatusUpdate {
             error: self.error.clone(),
             hints: self.hints.iter().cloned().collect(),
             namespaced_errors: self.namespaced_errors.clone(),
+            retry: self
+                .retry
+                .as_ref()
+                .map(|retry| proto_storage_response::ProtoRetryStatus {
+                    attempt: retry.attempt,
+                    max_attempts: retry.max_attempts,
+                    next_attempt_at: retry.next_attempt_at.into_proto(),
+                }),
         }
     }
 
@@ -475,6 +673,16 @@ impl RustType<proto_storage_response::ProtoStatusUpdate> for StatusUpdate {
             error: proto.error,
             hints: proto.hints.into_iter().collect(),
             namespaced_errors: proto.namespaced_errors,
+            retry: proto
+                .retry
+                .map(|retry| {
+                    Ok::<_, TryFromProtoError>(RetryStatus {
+                        attempt: retry.attempt,
+                        max_attempts: retry.max_attempts,
+                        next_attempt_at: retry.next_attempt_at.into_rust()?,
+                    })
+                })
+                .transpose()?,
         })
     }
 }
@@ -18,12 +18,14 @@
 //! Eventually, the source is dropped with either `drop_sources()` or by allowing compaction to the
 //! empty frontier.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use differential_dataflow::lattice::Lattice;
+use futures::future::BoxFuture;
 use mz_cluster_client::client::ClusterReplicaLocation;
 use mz_cluster_client::ReplicaId;
 use mz_persist_client::read::{Cursor, ReadHandle};
@@ -43,7 +45,7 @@ use timely::progress::{Antichain, ChangeBatch, Timestamp};
 use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::{mpsc, oneshot};
 
-use crate::client::TimestamplessUpdate;
+use crate::client::{ObjectStatusUpdate, TimestamplessUpdate};
 use crate::statistics::WebhookStatistics;
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, Eq, PartialEq, Hash, PartialOrd, Ord)]
@@ -76,9 +78,13 @@ pub enum IntrospectionType {
     ComputeReplicaHeartbeats,
     ComputeHydrationStatus,
     ComputeOperatorHydrationStatus,
+    ComputeDataflowStatisticsHistory,
 
     // Written by the Adapter for tracking AWS PrivateLink Connection Status History
     PrivatelinkConnectionStatusHistory,
+
+    // The shared status history of sources, sinks, clusters, and materialized views.
+    ObjectStatusHistory,
 }
 
 /// Describes how data is written to the collection.
@@ -417,6 +423,20 @@ pub trait StorageController: Debug {
         as_of: Antichain<Self::Timestamp>,
     ) -> Result<SnapshotPartsStats, StorageError>;
 
+    /// Returns a future that resolves to the earliest timestamp at which the sources among `ids`
+    /// have ingested all of the data available in their upstream systems when the future is first
+    /// polled.
+    ///
+    /// Subsources are resolved to the source that ingests them. Collections that do not ingest
+    /// data from an external system, or whose upstream system does not support real-time recency,
+    /// are ignored. The future fails with [`StorageError::RtrTimeout`] if a source does not ingest
+    /// its upstream data within `timeout`.
+    fn real_time_recent_timestamp(
+        &self,
+        ids: BTreeSet<GlobalId>,
+        timeout: Duration,
+    ) -> BoxFuture<'static, Result<Self::Timestamp, StorageError>>;
+
     /// Assigns a read policy to specific identifiers.
     ///
     /// The policies are assigned in the order presented, and repeated identifiers should
@@ -536,6 +556,13 @@ pub trait StorageController: Debug {
         updates: Vec<(Row, Diff)>,
     );
 
+    /// Records the statuses of objects in the shared object status history.
+    ///
+    /// Updates that change neither the status nor the reason of their object
+    /// are ignored, so callers can report the current status of objects
+    /// periodically.
+    async fn record_object_statuses(&mut self, updates: Vec<ObjectStatusUpdate>);
+
     /// Resets the txns system to a set of invariants necessary for correctness.
     ///
     /// Must be called on boot before create_collections or the various appends.
@@ -47,6 +47,11 @@ pub static MZ_SESSION_HISTORY_DESC: Lazy<RelationDesc> = Lazy::new(|| {
             ScalarType::String.nullable(false),
         )
         .with_column("authenticated_user", ScalarType::String.nullable(false))
+        .with_column("authentication_method", ScalarType::String.nullable(false))
+        .with_column(
+            "client_certificate_common_name",
+            ScalarType::String.nullable(true),
+        )
 });
 
 // NOTE: Update the views `mz_statement_execution_history_redacted`
@@ -86,6 +91,9 @@ pub static MZ_STATEMENT_EXECUTION_HISTORY_DESC: Lazy<RelationDesc> = Lazy::new(|
         .with_column("error_message", ScalarType::String.nullable(true))
         .with_column("rows_returned", ScalarType::Int64.nullable(true))
         .with_column("execution_strategy", ScalarType::String.nullable(true))
+        .with_column("plan_fingerprint", ScalarType::String.nullable(true))
+        .with_column("timestamp_constraint", ScalarType::String.nullable(true))
+        .with_column("expected_wait", ScalarType::Interval.nullable(true))
 });
 
 pub static MZ_SOURCE_STATUS_HISTORY_DESC: Lazy<RelationDesc> = Lazy::new(|| {
@@ -122,3 +130,28 @@ pub static MZ_AWS_PRIVATELINK_CONNECTION_STATUS_HISTORY_DESC: Lazy<RelationDesc>
             .with_column("connection_id", ScalarType::String.nullable(false))
             .with_column("status", ScalarType::String.nullable(false))
     });
+
+pub static MZ_OBJECT_STATUS_HISTORY_DESC: Lazy<RelationDesc> = Lazy::new(|| {
+    RelationDesc::empty()
+        .with_column(
+            "occurred_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        )
+        .with_column("object_id", ScalarType::String.nullable(false))
+        .with_column("object_type", ScalarType::String.nullable(false))
+        .with_column("status", ScalarType::String.nullable(false))
+        .with_column("reason", ScalarType::String.nullable(true))
+});
+
+pub static MZ_DATAFLOW_STATISTICS_HISTORY_DESC: Lazy<RelationDesc> = Lazy::new(|| {
+    RelationDesc::empty()
+        .with_column("object_id", ScalarType::String.nullable(false))
+        .with_column("replica_id", ScalarType::String.nullable(false))
+        .with_column(
+            "sampled_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        )
+        .with_column("records_processed", ScalarType::UInt64.nullable(false))
+        .with_column("arrangement_size", ScalarType::UInt64.nullable(false))
+        .with_column("error_count", ScalarType::Int64.nullable(false))
+});
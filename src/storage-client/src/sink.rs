@@ -15,15 +15,17 @@ use anyhow::{anyhow, bail, Context};
 use maplit::btreemap;
 use mz_kafka_util::client::{GetPartitionsError, MzClientContext, TimeoutConfig};
 use mz_ore::collections::CollectionExt;
+use mz_ore::str::StrExt;
 use mz_ore::task;
 use mz_repr::Timestamp;
 use mz_storage_types::configuration::StorageConfiguration;
 use mz_storage_types::errors::{ContextCreationError, ContextCreationErrorExt};
-use mz_storage_types::sinks::KafkaSinkConnection;
+use mz_storage_types::sinks::{AvroSchemaEvolution, KafkaSinkConnection};
 use rdkafka::admin::{AdminClient, AdminOptions, NewTopic, ResourceSpecifier, TopicReplication};
 use rdkafka::consumer::{BaseConsumer, Consumer};
-use rdkafka::error::KafkaError;
+use rdkafka::error::{KafkaError, RDKafkaErrorCode};
 use rdkafka::message::ToBytes;
+use rdkafka::producer::{BaseProducer, BaseRecord, Producer};
 use rdkafka::{ClientContext, Message, Offset, TopicPartitionList};
 use serde::{Deserialize, Deserializer, Serialize};
 use timely::progress::Antichain;
@@ -174,6 +176,11 @@ pub struct TopicConfig {
     pub replication_factor: i32,
     /// Describes how to clean up old data in the topic.
     pub cleanup_policy: TopicCleanupPolicy,
+    /// Additional topic configuration.
+    ///
+    /// These entries take precedence over the configuration derived from
+    /// `cleanup_policy`.
+    pub topic_config: BTreeMap<String, String>,
 }
 
 /// Describes how to clean up old data in the topic.
@@ -199,8 +206,10 @@ pub enum TopicCleanupPolicy {
 /// Ensures that the named Kafka topic exists.
 ///
 /// If the topic does not exist, the function creates the topic with the
-/// provided `config`. Note that if the topic already exists, the function does
-/// *not* verify that the topic's configuration matches `config`.
+/// provided `config`. If the topic already exists, the function verifies that
+/// the topic matches the explicitly requested partition count, replication
+/// factor, and `topic_config` entries. Note that the function does *not* verify
+/// that the topic's cleanup policy matches `config`.
 ///
 /// Returns a boolean indicating whether the topic already existed.
 pub async fn ensure_kafka_topic(
@@ -211,8 +220,12 @@ pub async fn ensure_kafka_topic(
         mut partition_count,
         mut replication_factor,
         cleanup_policy,
+        topic_config,
     }: TopicConfig,
 ) -> Result<bool, anyhow::Error> {
+    let requested_partition_count = partition_count;
+    let requested_replication_factor = replication_factor;
+
     let client: AdminClient<_> = connection
         .connection
         .create_with_context(
@@ -254,36 +267,149 @@ pub async fn ensure_kafka_topic(
         TopicReplication::Fixed(replication_factor),
     );
 
-    let retention_ms;
-    let retention_bytes;
+    let mut configs = BTreeMap::new();
     match cleanup_policy {
         TopicCleanupPolicy::Retention { ms, bytes } => {
-            kafka_topic = kafka_topic.set("cleanup.policy", "delete");
+            configs.insert("cleanup.policy".to_string(), "delete".to_string());
             if let Some(ms) = ms {
-                retention_ms = ms.to_string();
-                kafka_topic = kafka_topic.set("retention.ms", &retention_ms);
+                configs.insert("retention.ms".to_string(), ms.to_string());
             }
-            if let Some(bytes) = &bytes {
-                retention_bytes = bytes.to_string();
-                kafka_topic = kafka_topic.set("retention.bytes", &retention_bytes);
+            if let Some(bytes) = bytes {
+                configs.insert("retention.bytes".to_string(), bytes.to_string());
             }
         }
         TopicCleanupPolicy::Compaction => {
-            kafka_topic = kafka_topic.set("cleanup.policy", "compact");
+            configs.insert("cleanup.policy".to_string(), "compact".to_string());
         }
     }
+    configs.extend(topic_config.iter().map(|(k, v)| (k.clone(), v.clone())));
+    for (key, value) in &configs {
+        kafka_topic = kafka_topic.set(key, value);
+    }
 
-    mz_kafka_util::admin::ensure_topic(
+    let already_exists = mz_kafka_util::admin::ensure_topic(
         &client,
         &AdminOptions::new().request_timeout(Some(Duration::from_secs(5))),
         &kafka_topic,
     )
     .await
-    .with_context(|| format!("Error creating topic {} for sink", topic))
+    .with_context(|| format!("Error creating topic {} for sink", topic))?;
+
+    if already_exists {
+        let fetch_timeout = storage_configuration
+            .parameters
+            .kafka_timeout_config
+            .fetch_metadata_timeout;
+        validate_existing_topic(
+            &client,
+            topic,
+            requested_partition_count,
+            requested_replication_factor,
+            &topic_config,
+            fetch_timeout,
+        )
+        .await?;
+    }
+
+    Ok(already_exists)
+}
+
+/// Verifies that an existing topic matches the explicitly requested
+/// configuration.
+///
+/// A `partition_count` or `replication_factor` of `-1` is not verified.
+async fn validate_existing_topic<C: ClientContext>(
+    client: &AdminClient<C>,
+    topic: &str,
+    partition_count: i32,
+    replication_factor: i32,
+    topic_config: &BTreeMap<String, String>,
+    fetch_timeout: Duration,
+) -> Result<(), anyhow::Error> {
+    if partition_count != -1 || replication_factor != -1 {
+        let metadata = client
+            .inner()
+            .fetch_metadata(Some(topic), fetch_timeout)
+            .with_context(|| format!("error fetching metadata for existing topic {}", topic))?;
+        let topic_metadata = metadata
+            .topics()
+            .iter()
+            .find(|t| t.name() == topic)
+            .ok_or_else(|| anyhow!("unable to fetch metadata for existing topic {}", topic))?;
+        if let Some(err) = topic_metadata.error() {
+            bail!(
+                "error fetching metadata for existing topic {}: {}",
+                topic,
+                RDKafkaErrorCode::from(err)
+            );
+        }
+        let partitions = topic_metadata.partitions();
+        if partition_count != -1 && usize::try_from(partition_count).ok() != Some(partitions.len())
+        {
+            bail!(
+                "existing topic {} has {} partitions, but {} partitions were requested",
+                topic,
+                partitions.len(),
+                partition_count,
+            );
+        }
+        if replication_factor != -1 {
+            let expected = usize::try_from(replication_factor).ok();
+            if let Some(p) = partitions
+                .iter()
+                .find(|p| Some(p.replicas().len()) != expected)
+            {
+                bail!(
+                    "existing topic {} has a replication factor of {}, but a replication factor \
+                     of {} was requested",
+                    topic,
+                    p.replicas().len(),
+                    replication_factor,
+                );
+            }
+        }
+    }
+
+    if topic_config.is_empty() {
+        return Ok(());
+    }
+    let configs = client
+        .describe_configs(
+            &[ResourceSpecifier::Topic(topic)],
+            &AdminOptions::new().request_timeout(Some(Duration::from_secs(5))),
+        )
+        .await
+        .with_context(|| format!("error fetching configuration for existing topic {}", topic))?;
+    let config = configs.into_element().map_err(|e| {
+        anyhow!(
+            "error reading configuration for existing topic {}: {}",
+            topic,
+            e
+        )
+    })?;
+    for (key, expected) in topic_config {
+        let actual = config.get(key).and_then(|entry| entry.value.as_deref());
+        if actual != Some(expected.as_str()) {
+            bail!(
+                "existing topic {} has {} set to {}, but {} was requested",
+                topic,
+                key,
+                actual.unwrap_or("<unset>"),
+                expected,
+            );
+        }
+    }
+    Ok(())
 }
 
 /// Publish value and optional key schemas for a given topic.
 ///
+/// If `value_schema_evolution` is specified, publishing fails unless the value
+/// schema is compatible with the latest schema registered for the value
+/// schema's subject according to that evolution mode. The schema registry
+/// additionally enforces the subject's own compatibility level, which is never
+/// changed, as other producers may rely on it.
+///
 /// TODO(benesch): do we need to delete the Kafka topic if publishing the
 /// schema fails?
 pub async fn publish_kafka_schemas(
@@ -293,16 +419,53 @@ pub async fn publish_kafka_schemas(
     key_schema_type: Option<mz_ccsr::SchemaType>,
     value_schema: &str,
     value_schema_type: mz_ccsr::SchemaType,
+    value_schema_evolution: Option<AvroSchemaEvolution>,
 ) -> Result<(Option<i32>, i32), anyhow::Error> {
-    let value_schema_id = ccsr
-        .publish_schema(
-            &format!("{}-value", topic),
-            value_schema,
-            value_schema_type,
-            &[],
-        )
+    let value_subject = format!("{}-value", topic);
+    if let Some(evolution) = value_schema_evolution {
+        let previous = match ccsr.get_schema_by_subject(&value_subject).await {
+            Ok(previous) => Some(previous),
+            // There is no previous schema to evolve from.
+            Err(mz_ccsr::GetBySubjectError::SubjectNotFound) => None,
+            Err(e) => {
+                return Err(e)
+                    .context("unable to fetch the latest value schema from registry in kafka sink")
+            }
+        };
+        if let Some(previous) = previous {
+            if let Err(e) = evolution.check(&previous.raw, value_schema) {
+                return Err(e.context(format!(
+                    "VALUE SCHEMA EVOLUTION = {evolution} does not permit publishing the value \
+                     schema to schema registry subject {}",
+                    value_subject.quoted(),
+                )));
+            }
+        }
+    }
+    let value_schema_id = match ccsr
+        .publish_schema(&value_subject, value_schema, value_schema_type, &[])
         .await
-        .context("unable to publish value schema to registry in kafka sink")?;
+    {
+        Ok(id) => id,
+        Err(e @ mz_ccsr::PublishError::IncompatibleSchema) => {
+            // Report the compatibility level that rejected the schema, e.g.
+            // because the columns of the sink's relation changed since a
+            // previous sink published to the same topic.
+            let level = match ccsr.get_subject_compatibility_level(&value_subject).await {
+                Ok(level) => level.to_string(),
+                Err(_) => "unknown".into(),
+            };
+            return Err(e).context(format!(
+                "unable to publish value schema to registry in kafka sink: the schema is not \
+                 compatible with the schemas already registered for subject {}, whose \
+                 compatibility level is {level}",
+                value_subject.quoted(),
+            ));
+        }
+        Err(e) => {
+            return Err(e).context("unable to publish value schema to registry in kafka sink");
+        }
+    };
 
     let key_schema_id = if let Some(key_schema) = key_schema {
         let key_schema_type =
@@ -347,25 +510,127 @@ where
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct ProgressRecord {
     pub frontier: Vec<Timestamp>,
+    /// Whether this record discards all progress recorded before it, as written by
+    /// [`reset_sink_progress`]. The frontier of a reset record is the minimum timestamp, so that
+    /// versions that don't know about resets refuse to resume from it instead of skipping data.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub reset: bool,
 }
 
-fn parse_progress_record(payload: &[u8]) -> Result<Antichain<Timestamp>, anyhow::Error> {
-    Ok(match serde_json::from_slice::<ProgressRecord>(payload) {
-        Ok(progress) => Antichain::from(progress.frontier),
-        // If we fail to deserialize we might be reading a legacy progress record
-        Err(_) => match serde_json::from_slice::<LegacyProgressRecord>(payload) {
-            Ok(LegacyProgressRecord {
-                timestamp: Some(Some(time)),
-            }) => Antichain::from_elem(time.step_forward()),
-            Ok(LegacyProgressRecord {
-                timestamp: Some(None),
-            }) => Antichain::new(),
-            _ => match std::str::from_utf8(payload) {
-                Ok(payload) => bail!("invalid progress record: {payload}"),
-                Err(_) => bail!("invalid progress record bytes: {payload:?}"),
+/// Parses a progress record, returning `None` for reset records.
+fn parse_progress_record(payload: &[u8]) -> Result<Option<Antichain<Timestamp>>, anyhow::Error> {
+    Ok(Some(
+        match serde_json::from_slice::<ProgressRecord>(payload) {
+            Ok(ProgressRecord { reset: true, .. }) => return Ok(None),
+            Ok(progress) => Antichain::from(progress.frontier),
+            // If we fail to deserialize we might be reading a legacy progress record
+            Err(_) => match serde_json::from_slice::<LegacyProgressRecord>(payload) {
+                Ok(LegacyProgressRecord {
+                    timestamp: Some(Some(time)),
+                }) => Antichain::from_elem(time.step_forward()),
+                Ok(LegacyProgressRecord {
+                    timestamp: Some(None),
+                }) => Antichain::new(),
+                _ => match std::str::from_utf8(payload) {
+                    Ok(payload) => bail!("invalid progress record: {payload}"),
+                    Err(_) => bail!("invalid progress record bytes: {payload:?}"),
+                },
             },
         },
+    ))
+}
+
+/// Ensures that the progress topic of a sink exists.
+async fn ensure_progress_topic(
+    connection: &KafkaSinkConnection,
+    storage_configuration: &StorageConfiguration,
+    progress_topic: &str,
+) -> Result<(), ContextCreationError> {
+    ensure_kafka_topic(
+        connection,
+        storage_configuration,
+        progress_topic,
+        TopicConfig {
+            partition_count: 1,
+            // TODO: introduce and use `PROGRESS TOPIC REPLICATION FACTOR`
+            // on Kafka connections.
+            replication_factor: -1,
+            cleanup_policy: TopicCleanupPolicy::Compaction,
+            topic_config: BTreeMap::new(),
+        },
+    )
+    .await
+    .add_context("error registering kafka progress topic for sink")?;
+    Ok(())
+}
+
+/// Discards the progress that the sink identified by `sink_id` recorded in its progress topic,
+/// so that it starts over from its `as_of` the next time it starts, as if it was newly created.
+///
+/// This is a break-glass operation for when the data topic of the sink was deleted or
+/// recreated, and gives up on exactly-once delivery: whatever the sink produced before is
+/// produced again.
+///
+/// The running incarnation of the sink is fenced out by initializing transactions with the
+/// transactional ID of the sink, and so fails to commit any further progress. The caller must
+/// restart the sink afterwards.
+pub async fn reset_sink_progress(
+    sink_id: mz_repr::GlobalId,
+    connection: &KafkaSinkConnection,
+    storage_configuration: &StorageConfiguration,
+) -> Result<(), ContextCreationError> {
+    let TimeoutConfig {
+        socket_timeout,
+        transaction_timeout,
+        ..
+    } = storage_configuration.parameters.kafka_timeout_config;
+
+    let progress_topic = connection
+        .progress_topic(&storage_configuration.connection_context)
+        .into_owned();
+    ensure_progress_topic(connection, storage_configuration, &progress_topic).await?;
+
+    let options = btreemap! {
+        // Use the transactional ID of the sink, to fence out its running producer.
+        "transactional.id" => connection.transactional_id(
+            &storage_configuration.connection_context,
+            sink_id,
+        ),
+        // Allow Kafka monitoring tools to identify this producer.
+        "client.id" => connection.client_id(&storage_configuration.connection_context, sink_id),
+        "transaction.timeout.ms" => transaction_timeout.as_millis().to_string(),
+    };
+    let producer: BaseProducer<_> = connection
+        .connection
+        .create_with_context(storage_configuration, MzClientContext::default(), &options)
+        .await?;
+    let ctx = Arc::clone(producer.client().context());
+
+    let progress_key = ProgressKey::new(sink_id);
+    let payload = serde_json::to_vec(&ProgressRecord {
+        frontier: vec![Timestamp::MIN],
+        reset: true,
     })
+    .expect("infallible");
+
+    let task_name = format!("reset_sink_progress:{sink_id}");
+    task::spawn_blocking(
+        || task_name,
+        move || {
+            producer.init_transactions(socket_timeout)?;
+            producer.begin_transaction()?;
+            let record = BaseRecord::to(&progress_topic)
+                .payload(&payload)
+                .key(&progress_key);
+            producer.send(record).map_err(|(err, _)| err)?;
+            producer.commit_transaction(socket_timeout)?;
+            info!("reset progress of sink {sink_id} in topic {progress_topic}");
+            Ok::<_, KafkaError>(())
+        },
+    )
+    .await
+    .unwrap()
+    .check_ssh_status(&ctx)
 }
 
 /// Determines the latest progress record from the specified topic for the given
@@ -435,21 +700,7 @@ pub async fn determine_sink_resume_upper(
 
     let ctx = Arc::clone(progress_client_read_committed.client().context());
 
-    // Ensure the progress topic exists.
-    ensure_kafka_topic(
-        connection,
-        storage_configuration,
-        &progress_topic,
-        TopicConfig {
-            partition_count: 1,
-            // TODO: introduce and use `PROGRESS TOPIC REPLICATION FACTOR`
-            // on Kafka connections.
-            replication_factor: -1,
-            cleanup_policy: TopicCleanupPolicy::Compaction,
-        },
-    )
-    .await
-    .add_context("error registering kafka progress topic for sink")?;
+    ensure_progress_topic(connection, storage_configuration, &progress_topic).await?;
 
     let task_name = format!("get_latest_ts:{sink_id}");
     task::spawn_blocking(|| task_name, move || {
@@ -617,7 +868,11 @@ pub async fn determine_sink_resume_upper(
             let Some(payload) = message.payload() else {
                 continue
             };
-            let upper = parse_progress_record(payload)?;
+            let Some(upper) = parse_progress_record(payload)? else {
+                // The progress of the sink was reset, so it starts over.
+                last_upper = None;
+                continue;
+            };
 
             match last_upper {
                 Some(last_upper) if !PartialOrder::less_equal(&last_upper, &upper) => {
@@ -647,24 +902,47 @@ mod test {
 
         assert_eq!(
             parse_progress_record(b"{\"timestamp\":1}").unwrap(),
-            Antichain::from_elem(2.into()),
+            Some(Antichain::from_elem(2.into())),
         );
 
         assert_eq!(
             parse_progress_record(b"{\"timestamp\":null}").unwrap(),
-            Antichain::new(),
+            Some(Antichain::new()),
         );
 
         assert_eq!(
             parse_progress_record(b"{\"frontier\":[1]}").unwrap(),
-            Antichain::from_elem(1.into()),
+            Some(Antichain::from_elem(1.into())),
         );
 
         assert_eq!(
             parse_progress_record(b"{\"frontier\":[]}").unwrap(),
-            Antichain::new(),
+            Some(Antichain::new()),
         );
 
         assert!(parse_progress_record(b"{\"frontier\":null}").is_err());
     }
+
+    #[mz_ore::test]
+    fn progress_record_reset() {
+        let reset = ProgressRecord {
+            frontier: vec![Timestamp::MIN],
+            reset: true,
+        };
+        let payload = serde_json::to_vec(&reset).unwrap();
+        assert_eq!(payload, b"{\"frontier\":[0],\"reset\":true}");
+        assert_eq!(parse_progress_record(&payload).unwrap(), None);
+
+        // Regular progress records don't mention resets, like before resets existed.
+        let progress = ProgressRecord {
+            frontier: vec![1.into()],
+            reset: false,
+        };
+        let payload = serde_json::to_vec(&progress).unwrap();
+        assert_eq!(payload, b"{\"frontier\":[1]}");
+        assert_eq!(
+            parse_progress_record(&payload).unwrap(),
+            Some(Antichain::from_elem(1.into())),
+        );
+    }
 }
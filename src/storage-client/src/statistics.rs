@@ -90,6 +90,22 @@ pub static MZ_SOURCE_STATISTICS_RAW_DESC: Lazy<RelationDesc> = Lazy::new(|| {
         // A gauge of the number of _values_ (source defined unit) we have committed.
         // Never resets. Not to be confused with any of the counters above.
         .with_column("offset_committed", ScalarType::UInt64.nullable(true))
+        //
+        // Counters added after the initial schema.
+        //
+        // A counter of the messages that failed to decode and were routed to the source's
+        // dead-letter queue instead of its primary output. Always 0 for sources without one.
+        // Never resets.
+        .with_column("messages_dead_lettered", ScalarType::UInt64.nullable(false))
+        //
+        // Gauges added after the initial schema.
+        //
+        // The wall-clock time at which we most recently received a message from upstream
+        // for this source. `NULL` until the first message is received. Never regresses.
+        .with_column(
+            "last_received_at",
+            ScalarType::TimestampTz { precision: None }.nullable(true),
+        )
 });
 
 pub static MZ_SINK_STATISTICS_RAW_DESC: Lazy<RelationDesc> = Lazy::new(|| {
@@ -111,6 +127,33 @@ pub static MZ_SINK_STATISTICS_RAW_DESC: Lazy<RelationDesc> = Lazy::new(|| {
         // A counter of the bytes we have committed.
         // Never resets.
         .with_column("bytes_committed", ScalarType::UInt64.nullable(false))
+        //
+        // Counters added after the initial schema.
+        //
+        // A counter of the transactions we have committed.
+        // Never resets.
+        .with_column("transactions_committed", ScalarType::UInt64.nullable(false))
+        // A counter of the transactions we have aborted.
+        // Never resets.
+        .with_column("transactions_aborted", ScalarType::UInt64.nullable(false))
+        // A counter of the times the sink resumed from the progress that an earlier
+        // incarnation of it committed, e.g. after an error or a replica restart.
+        // Never resets.
+        .with_column("restarts", ScalarType::UInt64.nullable(false))
+        //
+        // Gauges added after the initial schema.
+        //
+        // The upper frontier of the last transaction we committed, which is where the sink
+        // resumes from. Reported as soon as the sink reads it from its progress topic on
+        // startup. `NULL` until then, and if the sink has no progress to resume from.
+        // Regresses when the progress of the sink is reset.
+        .with_column("progress_frontier", ScalarType::MzTimestamp.nullable(true))
+        // The wall-clock time at which we most recently committed a transaction. `NULL`
+        // until the first commit. Never regresses.
+        .with_column(
+            "last_committed_at",
+            ScalarType::TimestampTz { precision: None }.nullable(true),
+        )
 });
 
 // Types of statistics (counter and various types of gauges), that have different semantics
@@ -327,6 +370,66 @@ impl StorageMetric for Total {
     }
 }
 
+/// A wall-clock timestamp gauge, in milliseconds since the Unix epoch, that never regresses
+/// and can start out as `NULL`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct LatestTimestamp(Option<u64>);
+
+impl From<Option<u64>> for LatestTimestamp {
+    fn from(f: Option<u64>) -> Self {
+        LatestTimestamp(f)
+    }
+}
+
+impl StorageMetric for LatestTimestamp {
+    fn summarize<'a, I>(values: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Self>,
+        Self: Sized + 'a,
+    {
+        // Pick the most recent timestamp across workers. Workers that have not
+        // yet reported a value don't hold back the others.
+        Self(values.into_iter().filter_map(|v| v.0).max())
+    }
+
+    fn incorporate(&mut self, other: Self, _field_name: &'static str) {
+        // `NULL`s and older values don't reset the value, e.g. when a source
+        // restarts.
+        self.0 = std::cmp::max(self.0, other.0);
+    }
+}
+
+/// A timestamp frontier gauge that can start out as `NULL`, reported by a single worker.
+///
+/// Unlike [`LatestTimestamp`], newer values override older ones even if they regress, e.g. when
+/// the progress of a sink is reset.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct Frontier(Option<u64>);
+
+impl From<Option<u64>> for Frontier {
+    fn from(f: Option<u64>) -> Self {
+        Frontier(f)
+    }
+}
+
+impl StorageMetric for Frontier {
+    fn summarize<'a, I>(values: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Self>,
+        Self: Sized + 'a,
+    {
+        // Only one worker reports a frontier, so the others don't hold it back.
+        Self(values.into_iter().filter_map(|v| v.0).max())
+    }
+
+    fn incorporate(&mut self, other: Self, _field_name: &'static str) {
+        // `NULL`s don't reset the value, e.g. while a sink restarts.
+        if other.0.is_some() {
+            self.0 = other.0;
+        }
+    }
+}
+
 /// A gauge that has semantics based on the `StorageMetric` implementation of its inner.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Gauge<T>(T);
@@ -433,6 +536,10 @@ pub struct SourceStatisticsUpdate {
     pub snapshot_committed: Gauge<Boolean>,
     pub offset_known: SkippableGauge<Total>,
     pub offset_committed: SkippableGauge<Total>,
+
+    pub messages_dead_lettered: Counter,
+
+    pub last_received_at: Gauge<LatestTimestamp>,
 }
 
 impl SourceStatisticsUpdate {
@@ -470,6 +577,10 @@ impl SourceStatisticsUpdate {
             offset_committed: SkippableGauge::summarize(
                 values().into_iter().map(|s| &s.offset_committed),
             ),
+            messages_dead_lettered: Counter::summarize(
+                values().into_iter().map(|s| &s.messages_dead_lettered),
+            ),
+            last_received_at: Gauge::summarize(values().into_iter().map(|s| &s.last_received_at)),
         }
     }
 
@@ -479,6 +590,7 @@ impl SourceStatisticsUpdate {
         self.bytes_received.0 = 0;
         self.updates_staged.0 = 0;
         self.updates_committed.0 = 0;
+        self.messages_dead_lettered.0 = 0;
     }
 
     pub fn incorporate(&mut self, other: SourceStatisticsUpdate) {
@@ -495,6 +607,8 @@ impl SourceStatisticsUpdate {
             snapshot_committed,
             offset_known,
             offset_committed,
+            messages_dead_lettered,
+            last_received_at,
             ..
         } = self;
 
@@ -511,6 +625,8 @@ impl SourceStatisticsUpdate {
         snapshot_committed.incorporate(other.snapshot_committed, "snapshot_committed");
         offset_known.incorporate(other.offset_known, "offset_known");
         offset_committed.incorporate(other.offset_committed, "offset_committed");
+        messages_dead_lettered.incorporate(other.messages_dead_lettered, "messages_dead_lettered");
+        last_received_at.incorporate(other.last_received_at, "last_received_at");
     }
 }
 
@@ -539,6 +655,13 @@ impl PackableStats for SourceStatisticsUpdate {
         packer.push(Datum::from(self.snapshot_committed.0 .0));
         packer.push(Datum::from(self.offset_known.pack().0));
         packer.push(Datum::from(self.offset_committed.pack().0));
+        // Late counters.
+        packer.push(Datum::from(self.messages_dead_lettered.0));
+        // Late gauges.
+        let last_received_at = self.last_received_at.0 .0.map(|ms| {
+            Datum::TimestampTz(mz_ore::now::to_datetime(ms).try_into().expect("must fit"))
+        });
+        packer.push(last_received_at.unwrap_or(Datum::Null));
     }
 
     fn unpack(row: Row) -> (GlobalId, Self) {
@@ -568,6 +691,16 @@ impl PackableStats for SourceStatisticsUpdate {
             snapshot_committed: Gauge::gauge(iter.next().unwrap().unwrap_bool()),
             offset_known: SkippableGauge::gauge(Some(iter.next().unwrap().unwrap_uint64())),
             offset_committed: SkippableGauge::gauge(Some(iter.next().unwrap().unwrap_uint64())),
+
+            messages_dead_lettered: iter.next().unwrap().unwrap_uint64().into(),
+
+            last_received_at: Gauge::gauge({
+                let datum = iter.next().unwrap();
+                (!datum.is_null()).then(|| {
+                    let ms = datum.unwrap_timestamptz().timestamp_millis();
+                    u64::try_from(ms).expect("must fit")
+                })
+            }),
         };
 
         (s.id, s)
@@ -593,6 +726,10 @@ impl RustType<ProtoSourceStatisticsUpdate> for SourceStatisticsUpdate {
             snapshot_committed: self.snapshot_committed.0 .0,
             offset_known: self.offset_known.0.clone().map(|i| i.0),
             offset_committed: self.offset_committed.0.clone().map(|i| i.0),
+
+            messages_dead_lettered: self.messages_dead_lettered.0,
+
+            last_received_at: self.last_received_at.0 .0,
         }
     }
 
@@ -616,6 +753,10 @@ impl RustType<ProtoSourceStatisticsUpdate> for SourceStatisticsUpdate {
             snapshot_committed: Gauge::gauge(proto.snapshot_committed),
             offset_known: SkippableGauge::gauge(proto.offset_known),
             offset_committed: SkippableGauge::gauge(proto.offset_committed),
+
+            messages_dead_lettered: Counter(proto.messages_dead_lettered),
+
+            last_received_at: Gauge::gauge(proto.last_received_at),
         })
     }
 }
@@ -631,6 +772,13 @@ pub struct SinkStatisticsUpdate {
     pub messages_committed: Counter,
     pub bytes_staged: Counter,
     pub bytes_committed: Counter,
+
+    pub transactions_committed: Counter,
+    pub transactions_aborted: Counter,
+    pub restarts: Counter,
+
+    pub progress_frontier: Gauge<Frontier>,
+    pub last_committed_at: Gauge<LatestTimestamp>,
 }
 
 impl SinkStatisticsUpdate {
@@ -640,6 +788,11 @@ impl SinkStatisticsUpdate {
             messages_committed,
             bytes_staged,
             bytes_committed,
+            transactions_committed,
+            transactions_aborted,
+            restarts,
+            progress_frontier,
+            last_committed_at,
             ..
         } = self;
 
@@ -647,6 +800,11 @@ impl SinkStatisticsUpdate {
         messages_committed.incorporate(other.messages_committed, "messages_committed");
         bytes_staged.incorporate(other.bytes_staged, "bytes_staged");
         bytes_committed.incorporate(other.bytes_committed, "bytes_committed");
+        transactions_committed.incorporate(other.transactions_committed, "transactions_committed");
+        transactions_aborted.incorporate(other.transactions_aborted, "transactions_aborted");
+        restarts.incorporate(other.restarts, "restarts");
+        progress_frontier.incorporate(other.progress_frontier, "progress_frontier");
+        last_committed_at.incorporate(other.last_committed_at, "last_committed_at");
     }
 
     pub fn summarize<'a, I, F>(values: F) -> Self
@@ -663,6 +821,15 @@ impl SinkStatisticsUpdate {
             ),
             bytes_staged: Counter::summarize(values().into_iter().map(|s| &s.bytes_staged)),
             bytes_committed: Counter::summarize(values().into_iter().map(|s| &s.bytes_committed)),
+            transactions_committed: Counter::summarize(
+                values().into_iter().map(|s| &s.transactions_committed),
+            ),
+            transactions_aborted: Counter::summarize(
+                values().into_iter().map(|s| &s.transactions_aborted),
+            ),
+            restarts: Counter::summarize(values().into_iter().map(|s| &s.restarts)),
+            progress_frontier: Gauge::summarize(values().into_iter().map(|s| &s.progress_frontier)),
+            last_committed_at: Gauge::summarize(values().into_iter().map(|s| &s.last_committed_at)),
         }
     }
 
@@ -672,6 +839,9 @@ impl SinkStatisticsUpdate {
         self.messages_committed.0 = 0;
         self.bytes_staged.0 = 0;
         self.bytes_committed.0 = 0;
+        self.transactions_committed.0 = 0;
+        self.transactions_aborted.0 = 0;
+        self.restarts.0 = 0;
     }
 }
 
@@ -683,6 +853,21 @@ impl PackableStats for SinkStatisticsUpdate {
         packer.push(Datum::from(self.messages_committed.0));
         packer.push(Datum::from(self.bytes_staged.0));
         packer.push(Datum::from(self.bytes_committed.0));
+        // Late counters.
+        packer.push(Datum::from(self.transactions_committed.0));
+        packer.push(Datum::from(self.transactions_aborted.0));
+        packer.push(Datum::from(self.restarts.0));
+        // Late gauges.
+        let progress_frontier = self
+            .progress_frontier
+            .0
+             .0
+            .map(|ts| Datum::MzTimestamp(ts.into()));
+        packer.push(progress_frontier.unwrap_or(Datum::Null));
+        let last_committed_at = self.last_committed_at.0 .0.map(|ms| {
+            Datum::TimestampTz(mz_ore::now::to_datetime(ms).try_into().expect("must fit"))
+        });
+        packer.push(last_committed_at.unwrap_or(Datum::Null));
     }
 
     fn unpack(row: Row) -> (GlobalId, Self) {
@@ -695,6 +880,22 @@ impl PackableStats for SinkStatisticsUpdate {
             messages_committed: iter.next().unwrap().unwrap_uint64().into(),
             bytes_staged: iter.next().unwrap().unwrap_uint64().into(),
             bytes_committed: iter.next().unwrap().unwrap_uint64().into(),
+            // Late counters
+            transactions_committed: iter.next().unwrap().unwrap_uint64().into(),
+            transactions_aborted: iter.next().unwrap().unwrap_uint64().into(),
+            restarts: iter.next().unwrap().unwrap_uint64().into(),
+            // Late gauges
+            progress_frontier: Gauge::gauge({
+                let datum = iter.next().unwrap();
+                (!datum.is_null()).then(|| u64::from(datum.unwrap_mz_timestamp()))
+            }),
+            last_committed_at: Gauge::gauge({
+                let datum = iter.next().unwrap();
+                (!datum.is_null()).then(|| {
+                    let ms = datum.unwrap_timestamptz().timestamp_millis();
+                    u64::try_from(ms).expect("must fit")
+                })
+            }),
         };
 
         (s.id, s)
@@ -710,6 +911,13 @@ impl RustType<ProtoSinkStatisticsUpdate> for SinkStatisticsUpdate {
             messages_committed: self.messages_committed.0,
             bytes_staged: self.bytes_staged.0,
             bytes_committed: self.bytes_committed.0,
+
+            transactions_committed: self.transactions_committed.0,
+            transactions_aborted: self.transactions_aborted.0,
+            restarts: self.restarts.0,
+
+            progress_frontier: self.progress_frontier.0 .0,
+            last_committed_at: self.last_committed_at.0 .0,
         }
     }
 
@@ -723,6 +931,13 @@ impl RustType<ProtoSinkStatisticsUpdate> for SinkStatisticsUpdate {
             messages_committed: Counter(proto.messages_committed),
             bytes_staged: Counter(proto.bytes_staged),
             bytes_committed: Counter(proto.bytes_committed),
+
+            transactions_committed: Counter(proto.transactions_committed),
+            transactions_aborted: Counter(proto.transactions_aborted),
+            restarts: Counter(proto.restarts),
+
+            progress_frontier: Gauge::gauge(proto.progress_frontier),
+            last_committed_at: Gauge::gauge(proto.last_committed_at),
         })
     }
 }
@@ -754,6 +969,8 @@ impl WebhookStatistics {
             snapshot_committed: Gauge::gauge(true),
             offset_known: SkippableGauge::gauge(None::<Total>),
             offset_committed: SkippableGauge::gauge(None::<Total>),
+            messages_dead_lettered: Counter(0),
+            last_received_at: Gauge::gauge(None::<u64>),
         }
     }
 }
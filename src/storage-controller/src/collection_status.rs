@@ -8,13 +8,16 @@
 // by the Apache License, Version 2.0.
 
 use std::collections::BTreeMap;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use differential_dataflow::lattice::Lattice;
 use mz_ore::now::EpochMillis;
 use mz_persist_types::Codec64;
-use mz_repr::{GlobalId, Row, TimestampManipulation};
-use mz_storage_client::client::{Status, StatusUpdate};
+use mz_repr::{ColumnName, Datum, GlobalId, Row, TimestampManipulation};
+use mz_storage_client::client::{
+    ObjectStatus, ObjectStatusUpdate, Status, StatusObjectType, StatusUpdate,
+};
 use timely::progress::Timestamp;
 
 use crate::collection_mgmt::CollectionManager;
@@ -24,6 +27,7 @@ pub use mz_storage_client::healthcheck::*;
 
 /// A lightweight wrapper around [`CollectionManager`] that assists with
 /// appending status updates to to `mz_internal.mz_{source|status}_history`
+/// and to `mz_internal.mz_object_status_history`
 #[derive(Debug, Clone)]
 pub struct CollectionStatusManager<T>
 where
@@ -34,6 +38,9 @@ where
     /// A list of introspection IDs for managed collections
     introspection_ids: Arc<std::sync::Mutex<BTreeMap<IntrospectionType, GlobalId>>>,
     previous_statuses: BTreeMap<GlobalId, Status>,
+    /// The latest status and reason of each object in
+    /// `mz_internal.mz_object_status_history`.
+    previous_object_statuses: BTreeMap<(StatusObjectType, String), (ObjectStatus, Option<String>)>,
 }
 
 impl<T> CollectionStatusManager<T>
@@ -48,6 +55,7 @@ where
             collection_manager,
             introspection_ids,
             previous_statuses: Default::default(),
+            previous_object_statuses: Default::default(),
         }
     }
 
@@ -58,6 +66,13 @@ where
         self.previous_statuses.extend(previous_statuses)
     }
 
+    pub fn extend_previous_object_statuses<I>(&mut self, previous_statuses: I)
+    where
+        I: IntoIterator<Item = ((StatusObjectType, String), (ObjectStatus, Option<String>))>,
+    {
+        self.previous_object_statuses.extend(previous_statuses)
+    }
+
     pub(super) async fn append_updates(
         &mut self,
         updates: Vec<StatusUpdate>,
@@ -83,6 +98,25 @@ where
         self.previous_statuses
             .extend(new.iter().map(|r| (r.id, r.status)));
 
+        // Mirror the transitions of sources and sinks into the shared object
+        // status history.
+        let object_type = match type_ {
+            IntrospectionType::SourceStatusHistory => Some(StatusObjectType::Source),
+            IntrospectionType::SinkStatusHistory => Some(StatusObjectType::Sink),
+            _ => None,
+        };
+        if let Some(object_type) = object_type {
+            for update in new.iter().filter(|r| r.status == Status::Dropped) {
+                self.previous_object_statuses
+                    .remove(&(object_type, update.id.to_string()));
+            }
+            let object_updates = new
+                .iter()
+                .filter_map(|r| ObjectStatusUpdate::from_storage_update(r, object_type))
+                .collect();
+            self.append_object_updates(object_updates).await;
+        }
+
         self.collection_manager
             .append_to_collection(
                 source_status_history_id,
@@ -92,16 +126,120 @@ where
             )
             .await;
     }
+
+    /// Appends the updates to `mz_internal.mz_object_status_history` that
+    /// change the status or the reason of their object.
+    pub(super) async fn append_object_updates(&mut self, updates: Vec<ObjectStatusUpdate>) {
+        let object_status_history_id = *self
+            .introspection_ids
+            .lock()
+            .expect("poisoned lock")
+            .get(&IntrospectionType::ObjectStatusHistory)
+            .expect("object status history collection to be registered");
+
+        let mut new = Vec::new();
+        for update in updates {
+            let key = (update.object_type, update.object_id.clone());
+            let value = (update.status, update.reason.clone());
+            if self.previous_object_statuses.get(&key) != Some(&value) {
+                self.previous_object_statuses.insert(key, value);
+                new.push((Row::from(update), 1));
+            }
+        }
+        if new.is_empty() {
+            return;
+        }
+
+        self.collection_manager
+            .append_to_collection(object_status_history_id, new)
+            .await;
+    }
+}
+
+/// Unpacks a row of `mz_internal.mz_object_status_history` into the object it
+/// describes and the object's status and reason.
+pub(crate) fn unpack_object_status_row(
+    row: &Row,
+) -> ((StatusObjectType, String), (ObjectStatus, Option<String>)) {
+    let col = |name: &str| {
+        MZ_OBJECT_STATUS_HISTORY_DESC
+            .get_by_name(&ColumnName::from(name))
+            .expect("schema has not changed")
+            .0
+    };
+    let datums = row.unpack();
+    let object_type = StatusObjectType::from_str(datums[col("object_type")].unwrap_str())
+        .expect("object types must be uncorrupted");
+    let object_id = datums[col("object_id")].unwrap_str().to_string();
+    let status = ObjectStatus::from_str(datums[col("status")].unwrap_str())
+        .expect("statuses must be uncorrupted");
+    let reason = match datums[col("reason")] {
+        Datum::Null => None,
+        reason => Some(reason.unwrap_str().to_string()),
+    };
+    ((object_type, object_id), (status, reason))
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeSet;
 
-    use mz_repr::Datum;
+    use mz_storage_client::client::RetryStatus;
 
     use super::*;
 
+    #[mz_ore::test]
+    fn test_object_status_row() {
+        let update = StatusUpdate {
+            id: GlobalId::User(1),
+            timestamp: chrono::offset::Utc::now(),
+            status: Status::Stalled,
+            error: Some("error message".to_string()),
+            hints: Default::default(),
+            namespaced_errors: Default::default(),
+            retry: None,
+        };
+        let object_update =
+            ObjectStatusUpdate::from_storage_update(&update, StatusObjectType::Source)
+                .expect("stalled sources exist");
+        let row = Row::from(object_update);
+
+        assert_eq!(row.iter().count(), MZ_OBJECT_STATUS_HISTORY_DESC.arity());
+        for (datum, column_type) in row.iter().zip(MZ_OBJECT_STATUS_HISTORY_DESC.iter_types()) {
+            assert!(datum.is_instance_of(column_type));
+        }
+        let datums: Vec<_> = row.iter().skip(1).collect();
+        assert_eq!(
+            datums,
+            [
+                Datum::String("u1"),
+                Datum::String("source"),
+                Datum::String("stalled"),
+                Datum::String("error message"),
+            ]
+        );
+        assert_eq!(
+            unpack_object_status_row(&row),
+            (
+                (StatusObjectType::Source, "u1".to_string()),
+                (ObjectStatus::Stalled, Some("error message".to_string())),
+            )
+        );
+
+        let paused = StatusUpdate::new(GlobalId::User(1), update.timestamp, Status::Paused);
+        let object_update =
+            ObjectStatusUpdate::from_storage_update(&paused, StatusObjectType::Sink)
+                .expect("paused sinks exist");
+        assert_eq!(object_update.status, ObjectStatus::Stalled);
+        assert_eq!(object_update.reason.as_deref(), Some("paused"));
+
+        let dropped = StatusUpdate::new(GlobalId::User(1), update.timestamp, Status::Dropped);
+        assert_eq!(
+            ObjectStatusUpdate::from_storage_update(&dropped, StatusObjectType::Sink),
+            None
+        );
+    }
+
     #[mz_ore::test]
     fn test_row() {
         let error_message = "error message";
@@ -115,6 +253,7 @@ mod tests {
             error: Some(error_message.to_string()),
             hints: BTreeSet::from([hint.to_string()]),
             namespaced_errors: Default::default(),
+            retry: None,
         });
 
         for (datum, column_type) in row.iter().zip(MZ_SINK_STATUS_HISTORY_DESC.iter_types()) {
@@ -159,6 +298,7 @@ mod tests {
             error: Some(error_message.to_string()),
             hints: Default::default(),
             namespaced_errors: Default::default(),
+            retry: None,
         });
 
         for (datum, column_type) in row.iter().zip(MZ_SINK_STATUS_HISTORY_DESC.iter_types()) {
@@ -187,6 +327,7 @@ mod tests {
             error: None,
             hints: BTreeSet::from([hint.to_string()]),
             namespaced_errors: Default::default(),
+            retry: None,
         });
 
         for (datum, column_type) in row.iter().zip(MZ_SINK_STATUS_HISTORY_DESC.iter_types()) {
@@ -231,6 +372,7 @@ mod tests {
             error: Some(error_message.to_string()),
             hints: Default::default(),
             namespaced_errors: BTreeMap::from([("thing".to_string(), "error".to_string())]),
+            retry: None,
         });
 
         for (datum, column_type) in row.iter().zip(MZ_SINK_STATUS_HISTORY_DESC.iter_types()) {
@@ -276,6 +418,7 @@ mod tests {
             error: Some(error_message.to_string()),
             hints: BTreeSet::from([hint.to_string()]),
             namespaced_errors: BTreeMap::from([("thing".to_string(), "error".to_string())]),
+            retry: None,
         });
 
         for (datum, column_type) in row.iter().zip(MZ_SINK_STATUS_HISTORY_DESC.iter_types()) {
@@ -315,4 +458,49 @@ mod tests {
             ("thing", Datum::String("error"))
         );
     }
+
+    #[mz_ore::test]
+    fn test_row_with_retry() {
+        let error_message = "error message";
+        let id = GlobalId::User(1);
+        let status = Status::Stalled;
+        let row = Row::from(StatusUpdate {
+            id,
+            timestamp: chrono::offset::Utc::now(),
+            status,
+            error: Some(error_message.to_string()),
+            hints: Default::default(),
+            namespaced_errors: Default::default(),
+            retry: Some(RetryStatus {
+                attempt: 3,
+                max_attempts: Some(5),
+                next_attempt_at: None,
+            }),
+        });
+
+        for (datum, column_type) in row.iter().zip(MZ_SOURCE_STATUS_HISTORY_DESC.iter_types()) {
+            assert!(datum.is_instance_of(column_type));
+        }
+
+        let details = row
+            .iter()
+            .nth(4)
+            .unwrap()
+            .unwrap_map()
+            .iter()
+            .collect::<Vec<_>>();
+
+        assert_eq!(details.len(), 1);
+        let retry_datum = &details[0];
+
+        assert_eq!(retry_datum.0, "retry");
+        assert_eq!(
+            retry_datum.1.unwrap_map().iter().collect::<Vec<_>>(),
+            vec![
+                ("attempt", Datum::from(3u128)),
+                ("max_attempts", Datum::from(5u128)),
+                ("next_attempt_at", Datum::JsonNull),
+            ]
+        );
+    }
 }
@@ -51,8 +51,8 @@ use mz_repr::{ColumnName, Datum, Diff, GlobalId, RelationDesc, Row, TimestampMan
 use mz_stash::{self, AppendBatch, StashFactory, TypedCollection};
 use mz_stash_types::metrics::Metrics as StashMetrics;
 use mz_storage_client::client::{
-    ProtoStorageCommand, ProtoStorageResponse, RunIngestionCommand, RunSinkCommand, Status,
-    StatusUpdate, StorageCommand, StorageResponse, TimestamplessUpdate,
+    ObjectStatusUpdate, ProtoStorageCommand, ProtoStorageResponse, RunIngestionCommand,
+    RunSinkCommand, Status, StatusUpdate, StorageCommand, StorageResponse, TimestamplessUpdate,
 };
 use mz_storage_client::controller::{
     CollectionDescription, CollectionState, DataSource, DataSourceOther, ExportDescription,
@@ -90,6 +90,7 @@ mod collection_status;
 mod command_wals;
 mod persist_handles;
 mod rehydration;
+mod rtr;
 mod statistics;
 
 pub static METADATA_COLLECTION: TypedCollection<proto::GlobalId, proto::DurableCollectionMetadata> =
@@ -860,6 +861,31 @@ where
                             )
                             .await;
                         }
+                        IntrospectionType::ObjectStatusHistory => {
+                            let desc = &collection_status::MZ_OBJECT_STATUS_HISTORY_DESC;
+                            let key_cols = ["object_type", "object_id"].map(|name| {
+                                desc.get_by_name(&ColumnName::from(name))
+                                    .expect("schema has not changed")
+                                    .0
+                            });
+                            let last_rows = self
+                                .truncate_status_history(
+                                    IntrospectionType::ObjectStatusHistory,
+                                    &key_cols,
+                                )
+                                .await;
+
+                            self.collection_status_manager
+                                .extend_previous_object_statuses(
+                                    last_rows
+                                        .iter()
+                                        .map(collection_status::unpack_object_status_row),
+                                )
+                        }
+
+                        IntrospectionType::ComputeDataflowStatisticsHistory => {
+                            self.truncate_dataflow_statistics_history().await;
+                        }
 
                         // Truncate compute-maintained collections.
                         IntrospectionType::ComputeDependencies
@@ -974,6 +1000,7 @@ where
             .ok_or(StorageError::IdentifierMissing(id))
     }
 
+    #[instrument(name = "storage::create_exports")]
     async fn create_exports(
         &mut self,
         exports: Vec<(GlobalId, ExportDescription<Self::Timestamp>)>,
@@ -1400,6 +1427,67 @@ where
         result.map_err(|_| StorageError::ReadBeforeSince(id))
     }
 
+    fn real_time_recent_timestamp(
+        &self,
+        ids: BTreeSet<GlobalId>,
+        timeout: Duration,
+    ) -> BoxFuture<'static, Result<Self::Timestamp, StorageError>> {
+        let mut futures = Vec::new();
+        for (&id, collection) in &self.collections {
+            let DataSource::Ingestion(ingestion) = &collection.description.data_source else {
+                continue;
+            };
+            // Subsources are ingested by, and so timestamped like, their primary source.
+            let is_read =
+                ids.contains(&id) || ingestion.subsource_ids().any(|id| ids.contains(&id));
+            if !is_read || !rtr::supports_real_time_recency(&ingestion.desc.connection) {
+                continue;
+            }
+
+            let remap_id = ingestion.remap_collection_id;
+            let metadata = match self.collection(remap_id) {
+                Ok(remap_collection) => remap_collection.collection_metadata.clone(),
+                Err(err) => return Box::pin(async move { Err(err) }),
+            };
+            let persist = Arc::clone(&self.persist);
+            let connection = ingestion.desc.connection.clone();
+            let config = self.config.clone();
+            let fut = async move {
+                let persist_client = persist
+                    .open(metadata.persist_location.clone())
+                    .await
+                    .map_err(|err| StorageError::Generic(err.into()))?;
+                // Opening a leased reader holds back the since of the remap collection until the
+                // reader is dropped.
+                let remap_handle = persist_client
+                    .open_leased_reader::<SourceData, (), _, _>(
+                        metadata.data_shard,
+                        Arc::new(metadata.relation_desc.clone()),
+                        Arc::new(UnitSchema),
+                        Diagnostics {
+                            shard_name: remap_id.to_string(),
+                            handle_purpose: format!("real-time recency {}", id),
+                        },
+                    )
+                    .await
+                    .expect("invalid persist usage");
+                rtr::real_time_recency_ts(id, connection, config, remap_handle).await
+            };
+            futures.push(async move {
+                tokio::time::timeout(timeout, fut)
+                    .await
+                    .map_err(|_| StorageError::RtrTimeout(id))?
+            });
+        }
+
+        Box::pin(async move {
+            let results = futures::future::join_all(futures).await;
+            results
+                .into_iter()
+                .try_fold(T::minimum(), |ts, result| Ok(std::cmp::max(ts, result?)))
+        })
+    }
+
     #[instrument(level = "debug")]
     fn set_read_policy(&mut self, policies: Vec<(GlobalId, ReadPolicy<Self::Timestamp>)>) {
         let mut read_capability_changes = BTreeMap::default();
@@ -2049,6 +2137,12 @@ where
         self.append_to_managed_collection(id, updates).await;
     }
 
+    async fn record_object_statuses(&mut self, updates: Vec<ObjectStatusUpdate>) {
+        self.collection_status_manager
+            .append_object_updates(updates)
+            .await;
+    }
+
     /// With the CRDB based timestamp oracle, there is no longer write timestamp
     /// fencing. As in, when a new Coordinator, `B`, starts up, there is nothing
     /// that prevents an old Coordinator, `A`, from getting a new write
@@ -2701,44 +2795,79 @@ where
         &mut self,
         collection: IntrospectionType,
     ) -> BTreeMap<GlobalId, Row> {
-        let (keep_n, occurred_at_col, id_col) = match collection {
-            IntrospectionType::SourceStatusHistory => (
-                self.config.parameters.keep_n_source_status_history_entries,
-                collection_status::MZ_SOURCE_STATUS_HISTORY_DESC
-                    .get_by_name(&ColumnName::from("occurred_at"))
-                    .expect("schema has not changed")
-                    .0,
-                collection_status::MZ_SOURCE_STATUS_HISTORY_DESC
-                    .get_by_name(&ColumnName::from("source_id"))
-                    .expect("schema has not changed")
-                    .0,
-            ),
-            IntrospectionType::SinkStatusHistory => (
-                self.config.parameters.keep_n_sink_status_history_entries,
-                collection_status::MZ_SINK_STATUS_HISTORY_DESC
-                    .get_by_name(&ColumnName::from("occurred_at"))
-                    .expect("schema has not changed")
-                    .0,
-                collection_status::MZ_SINK_STATUS_HISTORY_DESC
-                    .get_by_name(&ColumnName::from("sink_id"))
-                    .expect("schema has not changed")
-                    .0,
-            ),
-            IntrospectionType::PrivatelinkConnectionStatusHistory => (
+        let id_col = match collection {
+            IntrospectionType::SourceStatusHistory => "source_id",
+            IntrospectionType::SinkStatusHistory => "sink_id",
+            IntrospectionType::PrivatelinkConnectionStatusHistory => "connection_id",
+            _ => unreachable!(),
+        };
+        let id_col = Self::status_history_desc(collection)
+            .get_by_name(&ColumnName::from(id_col))
+            .expect("schema has not changed")
+            .0;
+
+        self.truncate_status_history(collection, &[id_col])
+            .await
+            .into_iter()
+            .filter_map(|row| {
+                let id = row.iter().nth(id_col).expect("schema has not changed");
+                // Ignore any rows that can't be unwrapped correctly
+                let id = GlobalId::from_str(id.unwrap_str()).ok()?;
+                Some((id, row))
+            })
+            .collect()
+    }
+
+    /// Returns the schema of the status history `collection`.
+    fn status_history_desc(collection: IntrospectionType) -> &'static RelationDesc {
+        match collection {
+            IntrospectionType::SourceStatusHistory => {
+                &*collection_status::MZ_SOURCE_STATUS_HISTORY_DESC
+            }
+            IntrospectionType::SinkStatusHistory => {
+                &*collection_status::MZ_SINK_STATUS_HISTORY_DESC
+            }
+            IntrospectionType::PrivatelinkConnectionStatusHistory => {
+                &*collection_status::MZ_AWS_PRIVATELINK_CONNECTION_STATUS_HISTORY_DESC
+            }
+            IntrospectionType::ObjectStatusHistory => {
+                &*collection_status::MZ_OBJECT_STATUS_HISTORY_DESC
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Effectively truncates the status history shard except for the most
+    /// recent updates for each object, which is identified by the values in
+    /// `key_cols`.
+    ///
+    /// Returns the latest row per object.
+    async fn truncate_status_history(
+        &mut self,
+        collection: IntrospectionType,
+        key_cols: &[usize],
+    ) -> Vec<Row> {
+        let keep_n = match collection {
+            IntrospectionType::SourceStatusHistory => {
+                self.config.parameters.keep_n_source_status_history_entries
+            }
+            IntrospectionType::SinkStatusHistory => {
+                self.config.parameters.keep_n_sink_status_history_entries
+            }
+            IntrospectionType::PrivatelinkConnectionStatusHistory => {
                 self.config
                     .parameters
-                    .keep_n_privatelink_status_history_entries,
-                collection_status::MZ_AWS_PRIVATELINK_CONNECTION_STATUS_HISTORY_DESC
-                    .get_by_name(&ColumnName::from("occurred_at"))
-                    .expect("schema has not changed")
-                    .0,
-                collection_status::MZ_AWS_PRIVATELINK_CONNECTION_STATUS_HISTORY_DESC
-                    .get_by_name(&ColumnName::from("connection_id"))
-                    .expect("schema has not changed")
-                    .0,
-            ),
+                    .keep_n_privatelink_status_history_entries
+            }
+            IntrospectionType::ObjectStatusHistory => {
+                self.config.parameters.keep_n_object_status_history_entries
+            }
             _ => unreachable!(),
         };
+        let occurred_at_col = Self::status_history_desc(collection)
+            .get_by_name(&ColumnName::from("occurred_at"))
+            .expect("schema has not changed")
+            .0;
 
         let id = self.introspection_ids.lock().expect("poisoned")[&collection];
 
@@ -2750,17 +2879,21 @@ where
             }
             // If collection is closed or the frontier is the minimum, we cannot
             // or don't need to truncate (respectively).
-            _ => return BTreeMap::new(),
+            _ => return Vec::new(),
         };
 
         // BTreeMap<Id, MinHeap<(OccurredAt, Row)>>, to track the
         // earliest events for each id.
-        let mut last_n_entries_per_id: BTreeMap<Datum, BinaryHeap<Reverse<(Datum, Vec<Datum>)>>> =
-            BTreeMap::new();
+        let mut last_n_entries_per_id: BTreeMap<
+            Vec<Datum>,
+            BinaryHeap<Reverse<(Datum, Vec<Datum>)>>,
+        > = BTreeMap::new();
 
         // BTreeMap to keep track of the row with the latest timestamp for each id
-        let mut latest_row_per_id: BTreeMap<Datum, (CheckedTimestamp<DateTime<Utc>>, Vec<Datum>)> =
-            BTreeMap::new();
+        let mut latest_row_per_id: BTreeMap<
+            Vec<Datum>,
+            (CheckedTimestamp<DateTime<Utc>>, Vec<Datum>),
+        > = BTreeMap::new();
 
         // Consolidate the snapshot, so we can process it correctly below.
         differential_dataflow::consolidation::consolidate(&mut rows);
@@ -2769,7 +2902,7 @@ where
 
         for (row, diff) in rows.iter() {
             let status_row = row.unpack();
-            let id = status_row[id_col];
+            let id: Vec<_> = key_cols.iter().map(|col| status_row[*col]).collect();
             let occurred_at = status_row[occurred_at_col];
 
             // Duplicate rows ARE possible if many status changes happen in VERY quick succession,
@@ -2777,7 +2910,7 @@ where
             assert!(
                 *diff > 0,
                 "only know how to operate over consolidated data with diffs > 0, \
-                found diff {} for object {} in {:?}",
+                found diff {} for object {:?} in {:?}",
                 diff,
                 id,
                 collection
@@ -2788,13 +2921,13 @@ where
             match latest_row_per_id.get(&id) {
                 Some(existing) if &existing.0 > &timestamp => {}
                 _ => {
-                    latest_row_per_id.insert(id, (timestamp, status_row.clone()));
+                    latest_row_per_id.insert(id.clone(), (timestamp, status_row.clone()));
                 }
             }
 
             // Consider duplicated rows separately.
             for _ in 0..*diff {
-                let entries = last_n_entries_per_id.entry(id).or_default();
+                let entries = last_n_entries_per_id.entry(id.clone()).or_default();
 
                 // We CAN have multiple statuses (most likely Starting and Running) at the exact same
                 // millisecond, depending on how the `health_operator` is scheduled.
@@ -2832,21 +2965,61 @@ where
         self.append_to_managed_collection(id, updates).await;
 
         latest_row_per_id
-            .into_iter()
-            .filter_map(|(key, (_, row_vec))| {
-                match GlobalId::from_str(key.unwrap_str()) {
-                    Ok(id) => {
-                        let mut packer = row_buf.packer();
-                        packer.extend(row_vec.into_iter());
-                        Some((id, row_buf.clone()))
-                    }
-                    // Ignore any rows that can't be unwrapped correctly
-                    Err(_) => None,
-                }
+            .into_values()
+            .map(|(_, row_vec)| {
+                let mut packer = row_buf.packer();
+                packer.extend(row_vec.into_iter());
+                row_buf.clone()
             })
             .collect()
     }
 
+    /// Effectively truncates the dataflow statistics history shard to the
+    /// samples taken within the configured retention period.
+    async fn truncate_dataflow_statistics_history(&mut self) {
+        let sampled_at_col = collection_status::MZ_DATAFLOW_STATISTICS_HISTORY_DESC
+            .get_by_name(&ColumnName::from("sampled_at"))
+            .expect("schema has not changed")
+            .0;
+
+        let id = self.introspection_ids.lock().expect("poisoned")
+            [&IntrospectionType::ComputeDataflowStatisticsHistory];
+
+        let mut rows = match self.collections[&id].write_frontier.as_option() {
+            Some(f) if f > &T::minimum() => {
+                let as_of = f.step_back().unwrap();
+
+                self.snapshot(id, as_of).await.expect("snapshot succeeds")
+            }
+            // If collection is closed or the frontier is the minimum, we cannot
+            // or don't need to truncate (respectively).
+            _ => return,
+        };
+
+        let retention = self.config.parameters.dataflow_statistics_history_retention;
+        let retention_ms = u64::try_from(retention.as_millis()).unwrap_or(u64::MAX);
+        let cutoff = mz_ore::now::to_datetime((self.now)().saturating_sub(retention_ms));
+
+        // Consolidate the snapshot, so we only retract rows that exist.
+        differential_dataflow::consolidation::consolidate(&mut rows);
+
+        // Updates are only deletes because everything else is already in the shard.
+        let updates = rows
+            .into_iter()
+            .filter(|(row, _diff)| {
+                let sampled_at = row
+                    .iter()
+                    .nth(sampled_at_col)
+                    .expect("schema has not changed")
+                    .unwrap_timestamptz();
+                *sampled_at < cutoff
+            })
+            .map(|(row, diff)| (row, -diff))
+            .collect();
+
+        self.append_to_managed_collection(id, updates).await;
+    }
+
     /// Appends a new global ID, shard ID pair to the appropriate collection.
     /// Use a `diff` of 1 to append a new entry; -1 to retract an existing
     /// entry.
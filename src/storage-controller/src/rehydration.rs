@@ -396,6 +396,7 @@ where
                         "There is currently no replica running this source".to_string()
                     ]),
                     namespaced_errors: Default::default(),
+                    retry: None,
                 })
                 .collect(),
         ));
@@ -414,6 +415,7 @@ where
                         "There is currently no replica running this source".to_string()
                     ]),
                     namespaced_errors: Default::default(),
+                    retry: None,
                 })
                 .collect(),
         ));
@@ -0,0 +1,159 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Real-time recency: determining the earliest timestamp at which a source has ingested all of the
+//! data that is currently available in its upstream system.
+//!
+//! The upstream frontier is fetched directly from the external system, e.g. the high watermarks
+//! of a Kafka topic or the current WAL LSN of a Postgres database. The source's remap collection
+//! is then read until it contains a binding of a timestamp to an upstream frontier that is at
+//! least as far along.
+
+use differential_dataflow::lattice::Lattice;
+use mz_ore::vec::VecExt;
+use mz_persist_client::read::{ListenEvent, ReadHandle};
+use mz_persist_types::Codec64;
+use mz_repr::{Diff, GlobalId};
+use mz_storage_types::configuration::StorageConfiguration;
+use mz_storage_types::controller::StorageError;
+use mz_storage_types::sources::{GenericSourceConnection, SourceData, SourceTimestamp};
+use timely::order::PartialOrder;
+use timely::progress::frontier::MutableAntichain;
+use timely::progress::{Antichain, Timestamp};
+
+/// Reports whether real-time recency is supported for sources with `connection`.
+pub(crate) fn supports_real_time_recency(connection: &GenericSourceConnection) -> bool {
+    match connection {
+        GenericSourceConnection::Kafka(_) | GenericSourceConnection::Postgres(_) => true,
+        GenericSourceConnection::MySql(_) | GenericSourceConnection::LoadGenerator(_) => false,
+    }
+}
+
+/// Returns the earliest timestamp at which the source identified by `id` has ingested all of the
+/// data currently available upstream of `connection`.
+///
+/// `remap_handle` must be a read handle for the remap collection of the source.
+pub(crate) async fn real_time_recency_ts<T>(
+    id: GlobalId,
+    connection: GenericSourceConnection,
+    config: StorageConfiguration,
+    remap_handle: ReadHandle<SourceData, (), T, Diff>,
+) -> Result<T, StorageError>
+where
+    T: Timestamp + Lattice + Codec64,
+{
+    match connection {
+        GenericSourceConnection::Kafka(kafka) => {
+            let upstream_frontier = kafka
+                .fetch_write_frontier(&config)
+                .await
+                .map_err(StorageError::Generic)?;
+            await_remap_binding(id, upstream_frontier, remap_handle).await
+        }
+        GenericSourceConnection::Postgres(pg) => {
+            let upstream_frontier = pg
+                .fetch_write_frontier(&config)
+                .await
+                .map_err(StorageError::Generic)?;
+            await_remap_binding(id, upstream_frontier, remap_handle).await
+        }
+        GenericSourceConnection::MySql(_) | GenericSourceConnection::LoadGenerator(_) => {
+            Err(StorageError::InvalidUsage(format!(
+                "real-time recency is not supported for source {id}"
+            )))
+        }
+    }
+}
+
+/// Reads the remap collection of the source identified by `id` until it binds a timestamp to an
+/// upstream frontier beyond `upstream_frontier`, and returns the earliest such timestamp.
+async fn await_remap_binding<FromTime, T>(
+    id: GlobalId,
+    upstream_frontier: Antichain<FromTime>,
+    mut remap_handle: ReadHandle<SourceData, (), T, Diff>,
+) -> Result<T, StorageError>
+where
+    FromTime: SourceTimestamp,
+    T: Timestamp + Lattice + Codec64,
+{
+    // The remap collection accumulates, at each timestamp, to the frontier of the upstream data
+    // that the source has ingested as of that timestamp.
+    let mut remap_frontier = MutableAntichain::new();
+    let is_ingested = |remap_frontier: &MutableAntichain<FromTime>| {
+        PartialOrder::less_equal(&upstream_frontier.borrow(), &remap_frontier.frontier())
+    };
+
+    // The read handle holds back the since of the remap collection, so it is readable there.
+    let as_of = remap_handle.since().clone();
+    let Some(as_of_ts) = as_of.as_option().cloned() else {
+        return Err(StorageError::ReadBeforeSince(id));
+    };
+    let snapshot = remap_handle
+        .snapshot_and_fetch(as_of.clone())
+        .await
+        .map_err(|_| StorageError::ReadBeforeSince(id))?;
+    remap_frontier.update_iter(
+        snapshot
+            .into_iter()
+            .map(|((data, _), _, diff)| (decode_remap_row(data), diff)),
+    );
+    if is_ingested(&remap_frontier) {
+        return Ok(as_of_ts);
+    }
+
+    let mut listen = remap_handle
+        .listen(as_of)
+        .await
+        .map_err(|_| StorageError::ReadBeforeSince(id))?;
+    let mut pending = Vec::new();
+    loop {
+        for event in listen.fetch_next().await {
+            match event {
+                ListenEvent::Updates(updates) => {
+                    pending.extend(
+                        updates
+                            .into_iter()
+                            .map(|((data, _), ts, diff)| (ts, decode_remap_row(data), diff)),
+                    );
+                }
+                ListenEvent::Progress(upper) => {
+                    // All updates not beyond `upper` are complete. Apply them in timestamp order,
+                    // to find the first timestamp at which the upstream data was ingested.
+                    let mut ready: Vec<_> = pending
+                        .drain_filter_swapping(|(ts, _, _)| !upper.less_equal(ts))
+                        .collect();
+                    ready.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+                    let mut ready = ready.into_iter().peekable();
+                    while let Some((ts, from_ts, diff)) = ready.next() {
+                        remap_frontier.update_iter([(from_ts, diff)]);
+                        let ts_complete = ready.peek().map_or(true, |(next, _, _)| next != &ts);
+                        if ts_complete && is_ingested(&remap_frontier) {
+                            return Ok(ts);
+                        }
+                    }
+                    if upper.is_empty() {
+                        return Err(StorageError::InvalidUsage(format!(
+                            "the remap collection of source {id} was closed before it \
+                            ingested the upstream data"
+                        )));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Decodes an update of a remap collection into the upstream timestamp it binds.
+fn decode_remap_row<FromTime: SourceTimestamp>(data: Result<SourceData, String>) -> FromTime {
+    let row = data
+        .expect("invalid remap data")
+        .0
+        .expect("remap collections contain no errors");
+    FromTime::decode_row(&row)
+}
@@ -10,5 +10,6 @@
 //! Shared Storage dataflow operators
 
 pub mod metrics;
+pub mod parquet;
 pub mod persist_source;
 pub mod s3_oneshot_sink;
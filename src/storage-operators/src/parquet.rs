@@ -0,0 +1,427 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Encoding of rows into Parquet files, shared by `COPY ... TO <expr>` and the
+//! Iceberg sink.
+
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+use arrow2::array::{
+    Array, MutableArray, MutableBinaryArray, MutableBooleanArray, MutablePrimitiveArray,
+    MutableUtf8Array,
+};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow2::io::parquet::write::{
+    transverse, CompressionOptions, Encoding, FileWriter, RowGroupIterator, Version, WriteOptions,
+};
+use bytes::BytesMut;
+use mz_ore::cast::CastFrom;
+use mz_repr::{Datum, RelationDesc, Row, ScalarType};
+
+/// The maximum number of rows buffered in memory before they are encoded as a
+/// Parquet row group.
+const ROW_GROUP_ROWS: usize = 64 * 1024;
+
+/// The maximum in-memory size of the rows buffered before they are encoded as
+/// a Parquet row group.
+const ROW_GROUP_BYTES: usize = 8 << 20;
+
+/// Incrementally encodes rows into a single Parquet file.
+///
+/// Rows are buffered column-wise and written out as a row group every
+/// [`ROW_GROUP_ROWS`] rows or [`ROW_GROUP_BYTES`] bytes, whichever comes
+/// first. The encoded row groups can be taken with [`Self::take_encoded`]
+/// while the file is still being written, e.g. to upload them. Parquet files
+/// end with a footer describing every row group, which is returned by
+/// [`Self::finish`] together with any bytes that were not taken yet.
+pub struct ParquetFileWriter {
+    schema: Schema,
+    options: WriteOptions,
+    encodings: Vec<Vec<Encoding>>,
+    /// The columns of the row group currently being built.
+    columns: Vec<ColumnBuilder>,
+    /// The number of rows in the row group currently being built.
+    buffered_rows: usize,
+    /// The in-memory size of the rows in the row group currently being built.
+    buffered_row_bytes: usize,
+    /// The encoded bytes that were not taken yet.
+    encoded: SharedBuffer,
+    /// The number of encoded bytes that were taken.
+    taken_bytes: u64,
+    writer: FileWriter<SharedBuffer>,
+}
+
+impl ParquetFileWriter {
+    pub fn new(desc: &RelationDesc) -> Result<Self, anyhow::Error> {
+        let columns: Vec<_> = desc
+            .typ()
+            .column_types
+            .iter()
+            .map(|typ| ColumnBuilder::new(&typ.scalar_type))
+            .collect();
+        let fields: Vec<_> = desc
+            .iter()
+            .zip(&columns)
+            .map(|((name, typ), column)| {
+                Field::new(name.as_str(), column.data_type().clone(), typ.nullable)
+            })
+            .collect();
+        let schema = Schema::from(fields);
+        let encodings = schema
+            .fields
+            .iter()
+            .map(|field| transverse(&field.data_type, |_| Encoding::Plain))
+            .collect();
+        let options = WriteOptions {
+            write_statistics: true,
+            compression: CompressionOptions::Snappy,
+            version: Version::V2,
+            data_pagesize_limit: None, // use default limit
+        };
+        let encoded = SharedBuffer::default();
+        let writer = FileWriter::try_new(encoded.clone(), schema.clone(), options)?;
+        Ok(ParquetFileWriter {
+            schema,
+            options,
+            encodings,
+            columns,
+            buffered_rows: 0,
+            buffered_row_bytes: 0,
+            encoded,
+            taken_bytes: 0,
+            writer,
+        })
+    }
+
+    /// Appends `row` to the file.
+    pub fn push_row(&mut self, row: &Row) -> Result<(), anyhow::Error> {
+        for (datum, column) in row.iter().zip(&mut self.columns) {
+            column.push(datum);
+        }
+        self.buffered_rows += 1;
+        self.buffered_row_bytes += row.byte_len();
+        if self.buffered_rows >= ROW_GROUP_ROWS || self.buffered_row_bytes >= ROW_GROUP_BYTES {
+            self.write_row_group()?;
+        }
+        Ok(())
+    }
+
+    /// Returns the number of bytes of the file that were encoded so far,
+    /// including the bytes that were taken.
+    pub fn encoded_bytes(&self) -> u64 {
+        self.taken_bytes + u64::cast_from(self.encoded.len())
+    }
+
+    /// Returns the in-memory size of the rows that were not encoded yet.
+    pub fn buffered_row_bytes(&self) -> usize {
+        self.buffered_row_bytes
+    }
+
+    /// Returns the bytes that were encoded since the previous call, which are
+    /// no longer held by the writer.
+    pub fn take_encoded(&mut self) -> Vec<u8> {
+        let encoded = self.encoded.take();
+        self.taken_bytes += u64::cast_from(encoded.len());
+        encoded
+    }
+
+    /// Writes any buffered rows and the file footer, and returns the encoded
+    /// bytes that were not taken yet. If no bytes were taken, this is the
+    /// entire file.
+    pub fn finish(mut self) -> Result<Vec<u8>, anyhow::Error> {
+        self.write_row_group()?;
+        self.writer.end(None)?;
+        Ok(self.encoded.take())
+    }
+
+    fn write_row_group(&mut self) -> Result<(), anyhow::Error> {
+        if self.buffered_rows == 0 {
+            return Ok(());
+        }
+        let arrays = self.columns.iter_mut().map(|c| c.take()).collect();
+        self.buffered_rows = 0;
+        self.buffered_row_bytes = 0;
+        let row_groups = RowGroupIterator::try_new(
+            std::iter::once(Chunk::try_new(arrays)),
+            &self.schema,
+            self.options,
+            self.encodings.clone(),
+        )?;
+        for row_group in row_groups {
+            self.writer.write(row_group?)?;
+        }
+        Ok(())
+    }
+}
+
+/// The output of a [`FileWriter`], whose contents can be taken while the
+/// writer still owns it.
+///
+/// The writer keeps track of the offsets in the file itself, so it does not
+/// notice that the bytes it wrote before were taken.
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn len(&self) -> usize {
+        self.0.lock().expect("lock poisoned").len()
+    }
+
+    fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut *self.0.lock().expect("lock poisoned"))
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().expect("lock poisoned").extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Builds the arrow array for a single column of a row group.
+///
+/// Types with a natural Parquet counterpart are encoded as such. All other
+/// types are encoded as strings using their PostgreSQL text representation,
+/// which is lossless and matches what `COPY ... TO` produces for CSV.
+enum ColumnBuilder {
+    Boolean(MutableBooleanArray),
+    Int16(MutablePrimitiveArray<i16>),
+    Int32(MutablePrimitiveArray<i32>),
+    Int64(MutablePrimitiveArray<i64>),
+    UInt16(MutablePrimitiveArray<u16>),
+    UInt32(MutablePrimitiveArray<u32>),
+    UInt64(MutablePrimitiveArray<u64>),
+    Float32(MutablePrimitiveArray<f32>),
+    Float64(MutablePrimitiveArray<f64>),
+    /// Days since the Unix epoch.
+    Date(MutablePrimitiveArray<i32>),
+    /// Microseconds since the Unix epoch.
+    Timestamp(MutablePrimitiveArray<i64>),
+    /// Microseconds since the Unix epoch, in UTC.
+    TimestampTz(MutablePrimitiveArray<i64>),
+    Bytes(MutableBinaryArray<i32>),
+    Text(ScalarType, MutableUtf8Array<i32>),
+}
+
+impl ColumnBuilder {
+    fn new(typ: &ScalarType) -> Self {
+        match typ {
+            ScalarType::Bool => ColumnBuilder::Boolean(MutableBooleanArray::new()),
+            ScalarType::Int16 => ColumnBuilder::Int16(MutablePrimitiveArray::new()),
+            ScalarType::Int32 => ColumnBuilder::Int32(MutablePrimitiveArray::new()),
+            ScalarType::Int64 => ColumnBuilder::Int64(MutablePrimitiveArray::new()),
+            ScalarType::UInt16 => ColumnBuilder::UInt16(MutablePrimitiveArray::new()),
+            ScalarType::UInt32 => ColumnBuilder::UInt32(MutablePrimitiveArray::new()),
+            ScalarType::UInt64 => ColumnBuilder::UInt64(MutablePrimitiveArray::new()),
+            ScalarType::Float32 => ColumnBuilder::Float32(MutablePrimitiveArray::new()),
+            ScalarType::Float64 => ColumnBuilder::Float64(MutablePrimitiveArray::new()),
+            ScalarType::Date => {
+                ColumnBuilder::Date(MutablePrimitiveArray::new().to(DataType::Date32))
+            }
+            ScalarType::Timestamp { .. } => ColumnBuilder::Timestamp(
+                MutablePrimitiveArray::new().to(DataType::Timestamp(TimeUnit::Microsecond, None)),
+            ),
+            ScalarType::TimestampTz { .. } => {
+                ColumnBuilder::TimestampTz(MutablePrimitiveArray::new().to(DataType::Timestamp(
+                    TimeUnit::Microsecond,
+                    Some("+00:00".into()),
+                )))
+            }
+            ScalarType::Bytes => ColumnBuilder::Bytes(MutableBinaryArray::new()),
+            typ => ColumnBuilder::Text(typ.clone(), MutableUtf8Array::new()),
+        }
+    }
+
+    fn data_type(&self) -> &DataType {
+        match self {
+            ColumnBuilder::Boolean(a) => a.data_type(),
+            ColumnBuilder::Int16(a) => a.data_type(),
+            ColumnBuilder::Int32(a) | ColumnBuilder::Date(a) => a.data_type(),
+            ColumnBuilder::Int64(a)
+            | ColumnBuilder::Timestamp(a)
+            | ColumnBuilder::TimestampTz(a) => a.data_type(),
+            ColumnBuilder::UInt16(a) => a.data_type(),
+            ColumnBuilder::UInt32(a) => a.data_type(),
+            ColumnBuilder::UInt64(a) => a.data_type(),
+            ColumnBuilder::Float32(a) => a.data_type(),
+            ColumnBuilder::Float64(a) => a.data_type(),
+            ColumnBuilder::Bytes(a) => a.data_type(),
+            ColumnBuilder::Text(_, a) => a.data_type(),
+        }
+    }
+
+    fn push(&mut self, datum: Datum) {
+        let datum = (!datum.is_null()).then_some(datum);
+        match self {
+            ColumnBuilder::Boolean(a) => a.push(datum.map(|d| d.unwrap_bool())),
+            ColumnBuilder::Int16(a) => a.push(datum.map(|d| d.unwrap_int16())),
+            ColumnBuilder::Int32(a) => a.push(datum.map(|d| d.unwrap_int32())),
+            ColumnBuilder::Int64(a) => a.push(datum.map(|d| d.unwrap_int64())),
+            ColumnBuilder::UInt16(a) => a.push(datum.map(|d| d.unwrap_uint16())),
+            ColumnBuilder::UInt32(a) => a.push(datum.map(|d| d.unwrap_uint32())),
+            ColumnBuilder::UInt64(a) => a.push(datum.map(|d| d.unwrap_uint64())),
+            ColumnBuilder::Float32(a) => a.push(datum.map(|d| d.unwrap_float32())),
+            ColumnBuilder::Float64(a) => a.push(datum.map(|d| d.unwrap_float64())),
+            ColumnBuilder::Date(a) => a.push(datum.map(|d| d.unwrap_date().unix_epoch_days())),
+            ColumnBuilder::Timestamp(a) => {
+                a.push(datum.map(|d| d.unwrap_timestamp().to_naive().timestamp_micros()))
+            }
+            ColumnBuilder::TimestampTz(a) => {
+                a.push(datum.map(|d| d.unwrap_timestamptz().timestamp_micros()))
+            }
+            ColumnBuilder::Bytes(a) => a.push(datum.map(|d| d.unwrap_bytes())),
+            ColumnBuilder::Text(typ, a) => {
+                let value = datum.and_then(|d| mz_pgrepr::Value::from_datum(d, typ));
+                a.push(value.map(|value| {
+                    let mut buf = BytesMut::new();
+                    value.encode_text(&mut buf);
+                    String::from_utf8(buf.to_vec()).expect("text encoding is valid UTF-8")
+                }))
+            }
+        }
+    }
+
+    /// Returns the values pushed so far as an array, leaving the builder
+    /// empty.
+    fn take(&mut self) -> Box<dyn Array> {
+        match self {
+            ColumnBuilder::Boolean(a) => a.as_box(),
+            ColumnBuilder::Int16(a) => a.as_box(),
+            ColumnBuilder::Int32(a) | ColumnBuilder::Date(a) => a.as_box(),
+            ColumnBuilder::Int64(a)
+            | ColumnBuilder::Timestamp(a)
+            | ColumnBuilder::TimestampTz(a) => a.as_box(),
+            ColumnBuilder::UInt16(a) => a.as_box(),
+            ColumnBuilder::UInt32(a) => a.as_box(),
+            ColumnBuilder::UInt64(a) => a.as_box(),
+            ColumnBuilder::Float32(a) => a.as_box(),
+            ColumnBuilder::Float64(a) => a.as_box(),
+            ColumnBuilder::Bytes(a) => a.as_box(),
+            ColumnBuilder::Text(_, a) => a.as_box(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use arrow2::array::{BooleanArray, PrimitiveArray, Utf8Array};
+    use arrow2::io::parquet::read::{infer_schema, read_metadata, FileReader};
+    use mz_repr::adt::numeric::Numeric;
+    use mz_repr::{ColumnName, ColumnType, RelationType};
+
+    use super::*;
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // slow
+    fn test_parquet_roundtrip() {
+        let typ = RelationType::new(vec![
+            ColumnType {
+                scalar_type: ScalarType::Int64,
+                nullable: false,
+            },
+            ColumnType {
+                scalar_type: ScalarType::Bool,
+                nullable: true,
+            },
+            ColumnType {
+                scalar_type: ScalarType::Numeric { max_scale: None },
+                nullable: true,
+            },
+        ]);
+        let names = ["a", "b", "c"].into_iter().map(ColumnName::from);
+        let desc = RelationDesc::new(typ, names);
+
+        let mut writer = ParquetFileWriter::new(&desc).unwrap();
+        for (a, b, c) in [
+            (1, Datum::True, Datum::from(Numeric::from(1.5))),
+            (2, Datum::Null, Datum::Null),
+        ] {
+            writer
+                .push_row(&Row::pack_slice(&[Datum::Int64(a), b, c]))
+                .unwrap();
+        }
+        let file = writer.finish().unwrap();
+
+        let mut reader = Cursor::new(file);
+        let metadata = read_metadata(&mut reader).unwrap();
+        let schema = infer_schema(&metadata).unwrap();
+        let names: Vec<_> = schema.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["a", "b", "c"]);
+        let mut chunks = FileReader::new(reader, metadata.row_groups, schema, None, None, None);
+        let chunk = chunks.next().unwrap().unwrap();
+        assert!(chunks.next().is_none());
+
+        let arrays = chunk.arrays();
+        let a = arrays[0].as_any().downcast_ref::<PrimitiveArray<i64>>();
+        assert_eq!(a, Some(&PrimitiveArray::from_slice([1, 2])));
+        let b = arrays[1].as_any().downcast_ref::<BooleanArray>();
+        assert_eq!(b, Some(&BooleanArray::from([Some(true), None])));
+        let c = arrays[2].as_any().downcast_ref::<Utf8Array<i32>>();
+        assert_eq!(c, Some(&Utf8Array::from([Some("1.5"), None])));
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // slow
+    fn test_parquet_take_encoded() {
+        let typ = RelationType::new(vec![ColumnType {
+            scalar_type: ScalarType::Int64,
+            nullable: false,
+        }]);
+        let desc = RelationDesc::new(typ, [ColumnName::from("a")]);
+
+        let mut writer = ParquetFileWriter::new(&desc).unwrap();
+        let mut file = Vec::new();
+        let rows = ROW_GROUP_ROWS + 10;
+        for i in 0..rows {
+            let i = i64::try_from(i).unwrap();
+            writer
+                .push_row(&Row::pack_slice(&[Datum::Int64(i)]))
+                .unwrap();
+            file.extend(writer.take_encoded());
+        }
+        // The first row group was encoded and taken, the remaining rows are
+        // still buffered.
+        assert!(!file.is_empty());
+        assert_eq!(writer.encoded_bytes(), u64::cast_from(file.len()));
+        assert_eq!(
+            writer.buffered_row_bytes(),
+            10 * Row::pack_slice(&[Datum::Int64(0)]).byte_len()
+        );
+        file.extend(writer.finish().unwrap());
+
+        let mut reader = Cursor::new(file);
+        let metadata = read_metadata(&mut reader).unwrap();
+        assert_eq!(metadata.row_groups.len(), 2);
+        assert_eq!(metadata.num_rows, rows);
+        let schema = infer_schema(&metadata).unwrap();
+        let chunks = FileReader::new(reader, metadata.row_groups, schema, None, None, None);
+        let mut expected = 0;
+        for chunk in chunks {
+            let chunk = chunk.unwrap();
+            let a = chunk.arrays()[0]
+                .as_any()
+                .downcast_ref::<PrimitiveArray<i64>>()
+                .unwrap();
+            for value in a.values_iter() {
+                assert_eq!(*value, expected);
+                expected += 1;
+            }
+        }
+        assert_eq!(expected, i64::try_from(rows).unwrap());
+    }
+}
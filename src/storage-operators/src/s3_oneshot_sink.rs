@@ -19,6 +19,7 @@ use http::Uri;
 use mz_aws_util::s3_uploader::{
     CompletedUpload, S3MultiPartUploadError, S3MultiPartUploader, S3MultiPartUploaderConfig,
 };
+use mz_ore::cast::CastFrom;
 use mz_ore::task::JoinHandleExt;
 use mz_pgcopy::{encode_copy_format, CopyFormatParams};
 use mz_repr::{Diff, GlobalId, RelationDesc, Row, Timestamp};
@@ -33,6 +34,8 @@ use timely::progress::Antichain;
 use timely::PartialOrder;
 use tracing::info;
 
+use crate::parquet::ParquetFileWriter;
+
 pub fn copy_to<G, F>(
     input_collection: Collection<G, (Row, ()), Diff>,
     err_collection: Collection<G, (DataflowError, ()), Diff>,
@@ -166,9 +169,13 @@ struct CopyToS3Uploader {
     /// Keeping the uploader in an `Option` to later take owned value.
     current_file_uploader: Option<S3MultiPartUploader>,
     /// Temporary buffer to store the encoded bytes.
-    /// Currently at a time this will only store one single encoded row
-    /// before getting added to the `current_file_uploader`'s buffer.
+    /// For row-based formats this will only store one single encoded row
+    /// at a time before getting added to the `current_file_uploader`'s buffer.
+    /// For Parquet it stores the row groups encoded by a single row.
     buf: Vec<u8>,
+    /// The Parquet file currently being encoded, if the format is Parquet.
+    /// Its row groups are uploaded as soon as they are encoded.
+    parquet_writer: Option<ParquetFileWriter>,
 }
 
 impl CopyToS3Uploader {
@@ -189,12 +196,13 @@ impl CopyToS3Uploader {
             file_index: 0,
             current_file_uploader: None,
             buf: Vec::new(),
+            parquet_writer: None,
         }
     }
 
     /// Creates the uploader for the next file and starts the multi part upload.
     async fn start_new_file_upload(&mut self) -> Result<(), anyhow::Error> {
-        self.flush().await?;
+        self.finish_file_upload().await?;
         assert!(self.current_file_uploader.is_none());
 
         self.file_index += 1;
@@ -206,7 +214,16 @@ impl CopyToS3Uploader {
             .sdk_config
             .take()
             .expect("sdk_config should always be present");
-        let max_file_size = self.max_file_size;
+        let file_size_limit = match self.format {
+            // A Parquet file can't be split once it is being uploaded, so the
+            // file size is limited by `append_parquet_row` instead, which can
+            // only estimate the size of the rows that are not encoded yet.
+            CopyFormatParams::Parquet => std::cmp::max(
+                self.max_file_size,
+                S3MultiPartUploaderConfig::default().file_size_limit,
+            ),
+            _ => self.max_file_size,
+        };
         // Moving the aws s3 calls onto tokio tasks instead of using timely runtime.
         let handle = mz_ore::task::spawn(|| "s3_uploader::try_new", async move {
             let uploader = S3MultiPartUploader::try_new(
@@ -215,7 +232,7 @@ impl CopyToS3Uploader {
                 file_path,
                 S3MultiPartUploaderConfig {
                     part_size_limit: ByteSize::mib(10).as_u64(),
-                    file_size_limit: max_file_size,
+                    file_size_limit,
                 },
             )
             .await;
@@ -228,10 +245,14 @@ impl CopyToS3Uploader {
     }
 
     fn current_file_path(&self) -> String {
-        // TODO: remove hard-coded file extension .csv
+        let extension = match self.format {
+            CopyFormatParams::Text(_) => "txt",
+            CopyFormatParams::Csv(_) => "csv",
+            CopyFormatParams::Parquet => "parquet",
+        };
         format!(
-            "{}/{}-{:04}.csv",
-            self.path_prefix, self.file_name_prefix, self.file_index
+            "{}/{}-{:04}.{}",
+            self.path_prefix, self.file_name_prefix, self.file_index, extension
         )
     }
 
@@ -243,8 +264,15 @@ impl CopyToS3Uploader {
         (bucket.to_string(), path.to_string())
     }
 
-    /// Finishes any remaining in-progress upload.
+    /// Finishes any in-progress Parquet file and any remaining in-progress
+    /// upload.
     async fn flush(&mut self) -> Result<(), anyhow::Error> {
+        self.finish_parquet_file().await?;
+        self.finish_file_upload().await
+    }
+
+    /// Finishes the in-progress upload, if any.
+    async fn finish_file_upload(&mut self) -> Result<(), anyhow::Error> {
         if let Some(uploader) = self.current_file_uploader.take() {
             let current_file = self.current_file_path();
             // Moving the aws s3 calls onto tokio tasks instead of using timely runtime.
@@ -287,6 +315,10 @@ impl CopyToS3Uploader {
     /// exceed the max file size of the ongoing upload, then a new `S3MultiPartUploader` for a new file will
     /// be created and the row data will be appended there.
     async fn append_row(&mut self, row: &Row) -> Result<(), anyhow::Error> {
+        if let CopyFormatParams::Parquet = self.format {
+            return self.append_parquet_row(row).await;
+        }
+
         self.buf.clear();
         // encode the row and write to temp buffer.
         encode_copy_format(self.format.clone(), row, self.desc.typ(), &mut self.buf)
@@ -310,6 +342,62 @@ impl CopyToS3Uploader {
 
         Ok(())
     }
+
+    /// Appends the row to the in-progress Parquet file, and uploads the row
+    /// groups that were encoded. If the row would make the file exceed the max
+    /// file size, the in-progress file is finished first and the row is
+    /// appended to a new file.
+    ///
+    /// The size of the file is the size of its encoded row groups, plus the
+    /// in-memory size of the rows that were not encoded yet, which is usually
+    /// larger than their encoded size.
+    async fn append_parquet_row(&mut self, row: &Row) -> Result<(), anyhow::Error> {
+        if let Some(writer) = &self.parquet_writer {
+            let file_size = writer.encoded_bytes()
+                + u64::cast_from(writer.buffered_row_bytes())
+                + u64::cast_from(row.byte_len());
+            if file_size > self.max_file_size {
+                self.finish_parquet_file().await?;
+            }
+        }
+
+        let writer = match &mut self.parquet_writer {
+            Some(writer) => writer,
+            None => self
+                .parquet_writer
+                .insert(ParquetFileWriter::new(&self.desc)?),
+        };
+        writer.push_row(row)?;
+        let encoded = writer.take_encoded();
+        if !encoded.is_empty() {
+            self.upload_parquet_bytes(encoded).await?;
+        }
+        Ok(())
+    }
+
+    /// Finishes the in-progress Parquet file, if any, by uploading its
+    /// remaining row groups and its footer.
+    async fn finish_parquet_file(&mut self) -> Result<(), anyhow::Error> {
+        let Some(writer) = self.parquet_writer.take() else {
+            return Ok(());
+        };
+        let encoded = writer.finish()?;
+        self.upload_parquet_bytes(encoded).await?;
+        self.finish_file_upload().await
+    }
+
+    /// Uploads encoded bytes of the in-progress Parquet file, starting its
+    /// upload if needed.
+    async fn upload_parquet_bytes(&mut self, encoded: Vec<u8>) -> Result<(), anyhow::Error> {
+        if self.current_file_uploader.is_none() {
+            self.start_new_file_upload().await?;
+        }
+        self.buf = encoded;
+        self.upload_buffer().await?;
+        // Don't hold on to the memory of the encoded row groups.
+        self.buf = Vec::new();
+        Ok(())
+    }
 }
 
 /// On CI, these tests are enabled by adding the scratch-aws-access plugin
@@ -57,6 +57,7 @@ fn main() {
                 "storage-types/src/shim.proto",
                 "storage-types/src/errors.proto",
                 "storage-types/src/connections/aws.proto",
+                "storage-types/src/connections/iceberg.proto",
                 "storage-types/src/instances.proto",
                 "storage-types/src/parameters.proto",
                 "storage-types/src/sinks.proto",
@@ -46,9 +46,11 @@ use url::Url;
 
 use crate::configuration::StorageConfiguration;
 use crate::connections::aws::{AwsConnection, AwsConnectionValidationError};
+use crate::connections::iceberg::IcebergCatalogConnection;
 use crate::errors::{ContextCreationError, CsrConnectError};
 
 pub mod aws;
+pub mod iceberg;
 pub mod inline;
 
 include!(concat!(env!("OUT_DIR"), "/mz_storage_types.connections.rs"));
@@ -196,6 +198,7 @@ pub enum Connection<C: ConnectionAccess = InlinedConnection> {
     Aws(AwsConnection),
     AwsPrivatelink(AwsPrivatelinkConnection),
     MySql(MySqlConnection<C>),
+    IcebergCatalog(IcebergCatalogConnection<C>),
 }
 
 impl<R: ConnectionResolver> IntoInlineConnection<Connection, R>
@@ -210,6 +213,9 @@ impl<R: ConnectionResolver> IntoInlineConnection<Connection, R>
             Connection::Aws(aws) => Connection::Aws(aws),
             Connection::AwsPrivatelink(awspl) => Connection::AwsPrivatelink(awspl),
             Connection::MySql(mysql) => Connection::MySql(mysql.into_inline_connection(r)),
+            Connection::IcebergCatalog(iceberg) => {
+                Connection::IcebergCatalog(iceberg.into_inline_connection(r))
+            }
         }
     }
 }
@@ -225,6 +231,7 @@ impl<C: ConnectionAccess> Connection<C> {
             Connection::Aws(conn) => conn.validate_by_default(),
             Connection::AwsPrivatelink(conn) => conn.validate_by_default(),
             Connection::MySql(conn) => conn.validate_by_default(),
+            Connection::IcebergCatalog(conn) => conn.validate_by_default(),
         }
     }
 }
@@ -244,6 +251,7 @@ impl Connection<InlinedConnection> {
             Connection::Aws(conn) => conn.validate(id, storage_configuration).await?,
             Connection::AwsPrivatelink(conn) => conn.validate(id, storage_configuration).await?,
             Connection::MySql(conn) => conn.validate(id, storage_configuration).await?,
+            Connection::IcebergCatalog(conn) => conn.validate(id, storage_configuration).await?,
         }
         Ok(())
     }
@@ -282,6 +290,20 @@ impl Connection<InlinedConnection> {
             o => unreachable!("{o:?} is not a Kafka connection"),
         }
     }
+
+    pub fn unwrap_aws(self) -> <InlinedConnection as ConnectionAccess>::Aws {
+        match self {
+            Self::Aws(conn) => conn,
+            o => unreachable!("{o:?} is not an AWS connection"),
+        }
+    }
+
+    pub fn unwrap_iceberg_catalog(self) -> <InlinedConnection as ConnectionAccess>::IcebergCatalog {
+        match self {
+            Self::IcebergCatalog(conn) => conn,
+            o => unreachable!("{o:?} is not an Iceberg catalog connection"),
+        }
+    }
 }
 
 /// An error returned by [`Connection::validate`].
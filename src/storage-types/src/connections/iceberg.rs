@@ -0,0 +1,414 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Iceberg catalog configuration for sinks.
+//!
+//! Only the [Iceberg REST catalog protocol][rest] is supported. The catalog is
+//! responsible for tracking the current metadata of each table and for
+//! atomically swapping it on commit; data and metadata files are written
+//! directly to object storage using the configured AWS connection.
+//!
+//! [rest]: https://github.com/apache/iceberg/blob/main/open-api/rest-catalog-open-api.yaml
+
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, bail, Context};
+use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
+use mz_repr::url::any_url;
+use mz_repr::GlobalId;
+use proptest_derive::Arbitrary;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use url::Url;
+
+use crate::configuration::StorageConfiguration;
+use crate::connections::inline::{
+    ConnectionAccess, ConnectionResolver, InlinedConnection, IntoInlineConnection,
+    ReferencedConnection,
+};
+
+include!(concat!(
+    env!("OUT_DIR"),
+    "/mz_storage_types.connections.iceberg.rs"
+));
+
+/// A connection to an Iceberg REST catalog.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct IcebergCatalogConnection<C: ConnectionAccess = InlinedConnection> {
+    /// The base URL of the REST catalog.
+    #[proptest(strategy = "any_url()")]
+    pub url: Url,
+    /// The warehouse to request from the catalog, if any.
+    pub warehouse: Option<String>,
+    /// The ID of the secret containing a bearer token for the catalog, if any.
+    pub token: Option<GlobalId>,
+    /// The AWS connection used to write data and metadata files.
+    pub aws_connection: AwsConnectionReference<C>,
+}
+
+/// A reference to an AWS connection from another connection.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct AwsConnectionReference<C: ConnectionAccess = InlinedConnection> {
+    /// The ID of the AWS connection.
+    pub connection_id: GlobalId,
+    /// The AWS connection object.
+    pub connection: C::Aws,
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<IcebergCatalogConnection, R>
+    for IcebergCatalogConnection<ReferencedConnection>
+{
+    fn into_inline_connection(self, r: R) -> IcebergCatalogConnection {
+        let IcebergCatalogConnection {
+            url,
+            warehouse,
+            token,
+            aws_connection,
+        } = self;
+
+        IcebergCatalogConnection {
+            url,
+            warehouse,
+            token,
+            aws_connection: AwsConnectionReference {
+                connection_id: aws_connection.connection_id,
+                connection: r
+                    .resolve_connection(aws_connection.connection)
+                    .unwrap_aws(),
+            },
+        }
+    }
+}
+
+impl<C: ConnectionAccess> IcebergCatalogConnection<C> {
+    pub(crate) fn validate_by_default(&self) -> bool {
+        true
+    }
+}
+
+impl IcebergCatalogConnection {
+    /// Constructs a client for the REST catalog.
+    ///
+    /// This fetches the catalog's configuration, which determines the prefix
+    /// of all subsequent requests.
+    pub async fn connect(
+        &self,
+        storage_configuration: &StorageConfiguration,
+    ) -> Result<IcebergCatalogClient, anyhow::Error> {
+        let token = match self.token {
+            None => None,
+            Some(token) => Some(
+                storage_configuration
+                    .connection_context
+                    .secrets_reader
+                    .read_string(token)
+                    .await?,
+            ),
+        };
+
+        let mut base = self.url.clone();
+        base.path_segments_mut()
+            .map_err(|_| anyhow!("invalid Iceberg catalog URL: {}", self.url))?
+            .pop_if_empty()
+            .push("v1");
+
+        let mut client = IcebergCatalogClient {
+            client: reqwest::Client::new(),
+            base,
+            token,
+        };
+
+        #[derive(Deserialize)]
+        struct CatalogConfig {
+            #[serde(default)]
+            defaults: BTreeMap<String, String>,
+            #[serde(default)]
+            overrides: BTreeMap<String, String>,
+        }
+
+        let mut url = client.url(&["config"]);
+        if let Some(warehouse) = &self.warehouse {
+            url.query_pairs_mut().append_pair("warehouse", warehouse);
+        }
+        let config: CatalogConfig = client
+            .send(client.client.get(url))
+            .await?
+            .json()
+            .await
+            .context("decoding Iceberg catalog configuration")?;
+        let prefix = config
+            .overrides
+            .get("prefix")
+            .or_else(|| config.defaults.get("prefix"));
+        if let Some(prefix) = prefix {
+            client
+                .base
+                .path_segments_mut()
+                .expect("validated above")
+                .extend(prefix.split('/'));
+        }
+
+        Ok(client)
+    }
+
+    pub(crate) async fn validate(
+        &self,
+        _id: GlobalId,
+        storage_configuration: &StorageConfiguration,
+    ) -> Result<(), anyhow::Error> {
+        self.connect(storage_configuration).await?;
+        Ok(())
+    }
+}
+
+impl RustType<ProtoIcebergCatalogConnection> for IcebergCatalogConnection {
+    fn into_proto(&self) -> ProtoIcebergCatalogConnection {
+        ProtoIcebergCatalogConnection {
+            url: Some(self.url.into_proto()),
+            warehouse: self.warehouse.clone(),
+            token: self.token.into_proto(),
+            aws_connection_id: Some(self.aws_connection.connection_id.into_proto()),
+            aws_connection: Some(self.aws_connection.connection.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoIcebergCatalogConnection) -> Result<Self, TryFromProtoError> {
+        Ok(IcebergCatalogConnection {
+            url: proto
+                .url
+                .into_rust_if_some("ProtoIcebergCatalogConnection::url")?,
+            warehouse: proto.warehouse,
+            token: proto.token.into_rust()?,
+            aws_connection: AwsConnectionReference {
+                connection_id: proto
+                    .aws_connection_id
+                    .into_rust_if_some("ProtoIcebergCatalogConnection::aws_connection_id")?,
+                connection: proto
+                    .aws_connection
+                    .into_rust_if_some("ProtoIcebergCatalogConnection::aws_connection")?,
+            },
+        })
+    }
+}
+
+/// A client for an Iceberg REST catalog.
+#[derive(Debug, Clone)]
+pub struct IcebergCatalogClient {
+    client: reqwest::Client,
+    /// The URL that all request paths are relative to, including the `v1`
+    /// version segment and the catalog's prefix.
+    base: Url,
+    token: Option<String>,
+}
+
+/// An error returned by [`IcebergCatalogClient::commit_table`].
+#[derive(thiserror::Error, Debug)]
+pub enum IcebergCommitError {
+    /// One of the commit's requirements did not hold, e.g. because the table
+    /// was concurrently modified by another writer.
+    #[error("Iceberg commit conflict: {0}")]
+    Conflict(String),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl IcebergCatalogClient {
+    /// Loads the metadata of the specified table, or returns `None` if the
+    /// table does not exist.
+    pub async fn load_table(
+        &self,
+        namespace: &[String],
+        table: &str,
+    ) -> Result<Option<IcebergTableMetadata>, anyhow::Error> {
+        let url = self.table_url(namespace, table);
+        let response = self.request(self.client.get(url)).send().await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let response: LoadTableResponse = check_status(response)
+            .await?
+            .json()
+            .await
+            .context("decoding Iceberg table metadata")?;
+        Ok(Some(response.metadata))
+    }
+
+    /// Creates an unpartitioned table with the specified schema and
+    /// properties, and returns its metadata.
+    ///
+    /// Note that the catalog is free to reassign the field IDs of the schema.
+    pub async fn create_table(
+        &self,
+        namespace: &[String],
+        table: &str,
+        schema: serde_json::Value,
+        properties: BTreeMap<String, String>,
+    ) -> Result<IcebergTableMetadata, anyhow::Error> {
+        let url = self.url(&["namespaces", &namespace.join("\u{1f}"), "tables"]);
+        let body = json!({
+            "name": table,
+            "schema": schema,
+            "properties": properties,
+        });
+        let response: LoadTableResponse = self
+            .send(self.client.post(url).json(&body))
+            .await?
+            .json()
+            .await
+            .context("decoding Iceberg table metadata")?;
+        Ok(response.metadata)
+    }
+
+    /// Atomically applies `updates` to the specified table if all of
+    /// `requirements` hold, and returns the table's new metadata.
+    pub async fn commit_table(
+        &self,
+        namespace: &[String],
+        table: &str,
+        requirements: Vec<serde_json::Value>,
+        updates: Vec<serde_json::Value>,
+    ) -> Result<IcebergTableMetadata, IcebergCommitError> {
+        let url = self.table_url(namespace, table);
+        let body = json!({
+            "requirements": requirements,
+            "updates": updates,
+        });
+        let response = self
+            .request(self.client.post(url).json(&body))
+            .send()
+            .await
+            .map_err(anyhow::Error::from)?;
+        if response.status() == StatusCode::CONFLICT {
+            let body = response.text().await.unwrap_or_default();
+            return Err(IcebergCommitError::Conflict(body));
+        }
+        let response: LoadTableResponse = check_status(response)
+            .await?
+            .json()
+            .await
+            .context("decoding Iceberg table metadata")?;
+        Ok(response.metadata)
+    }
+
+    fn url(&self, segments: &[&str]) -> Url {
+        let mut url = self.base.clone();
+        url.path_segments_mut()
+            .expect("validated on connect")
+            .extend(segments);
+        url
+    }
+
+    fn table_url(&self, namespace: &[String], table: &str) -> Url {
+        // Multi-level namespaces are joined with the unit separator character.
+        self.url(&["namespaces", &namespace.join("\u{1f}"), "tables", table])
+    }
+
+    fn request(&self, builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.token {
+            Some(token) => builder.bearer_auth(token),
+            None => builder,
+        }
+    }
+
+    async fn send(
+        &self,
+        builder: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, anyhow::Error> {
+        let response = self.request(builder).send().await?;
+        check_status(response).await
+    }
+}
+
+async fn check_status(response: reqwest::Response) -> Result<reqwest::Response, anyhow::Error> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    bail!("Iceberg catalog request failed with status {status}: {body}")
+}
+
+#[derive(Deserialize)]
+struct LoadTableResponse {
+    metadata: IcebergTableMetadata,
+}
+
+/// The subset of an Iceberg table's metadata that Materialize relies on.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct IcebergTableMetadata {
+    pub table_uuid: String,
+    /// The base location of the table, e.g. `s3://bucket/path`.
+    pub location: String,
+    #[serde(default)]
+    pub last_sequence_number: i64,
+    pub current_schema_id: i32,
+    pub schemas: Vec<IcebergSchema>,
+    #[serde(default)]
+    current_snapshot_id: Option<i64>,
+    #[serde(default)]
+    pub snapshots: Vec<IcebergSnapshot>,
+    #[serde(default)]
+    pub properties: BTreeMap<String, String>,
+}
+
+impl IcebergTableMetadata {
+    /// Returns the table's current schema.
+    pub fn current_schema(&self) -> Result<&IcebergSchema, anyhow::Error> {
+        self.schemas
+            .iter()
+            .find(|s| s.schema_id == self.current_schema_id)
+            .ok_or_else(|| anyhow!("Iceberg table metadata is missing its current schema"))
+    }
+
+    /// Returns the ID of the table's current snapshot, if any.
+    pub fn current_snapshot_id(&self) -> Option<i64> {
+        // Older catalogs use -1 to indicate the absence of a snapshot.
+        self.current_snapshot_id.filter(|id| *id != -1)
+    }
+
+    /// Returns the table's current snapshot, if any.
+    pub fn current_snapshot(&self) -> Option<&IcebergSnapshot> {
+        let id = self.current_snapshot_id()?;
+        self.snapshots.iter().find(|s| s.snapshot_id == id)
+    }
+}
+
+/// An Iceberg schema.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct IcebergSchema {
+    pub schema_id: i32,
+    pub fields: Vec<IcebergField>,
+}
+
+/// A top-level field of an Iceberg schema.
+#[derive(Debug, Clone, Deserialize)]
+pub struct IcebergField {
+    pub id: i32,
+    pub name: String,
+    pub required: bool,
+    #[serde(rename = "type")]
+    pub typ: serde_json::Value,
+}
+
+/// An Iceberg snapshot.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct IcebergSnapshot {
+    pub snapshot_id: i64,
+    #[serde(default)]
+    pub parent_snapshot_id: Option<i64>,
+    #[serde(default)]
+    pub sequence_number: i64,
+    pub manifest_list: String,
+    #[serde(default)]
+    pub summary: BTreeMap<String, String>,
+}
@@ -88,6 +88,22 @@ pub trait ConnectionAccess:
         + Hash
         + Serialize
         + for<'a> Deserialize<'a>;
+    type Aws: Arbitrary
+        + Clone
+        + Debug
+        + Eq
+        + PartialEq
+        + Hash
+        + Serialize
+        + for<'a> Deserialize<'a>;
+    type IcebergCatalog: Arbitrary
+        + Clone
+        + Debug
+        + Eq
+        + PartialEq
+        + Hash
+        + Serialize
+        + for<'a> Deserialize<'a>;
 }
 
 /// Expresses that the struct contains references to connections. Use a
@@ -102,6 +118,8 @@ impl ConnectionAccess for ReferencedConnection {
     type Ssh = GlobalId;
     type Csr = GlobalId;
     type MySql = GlobalId;
+    type Aws = GlobalId;
+    type IcebergCatalog = GlobalId;
 }
 
 /// Expresses that the struct contains an inlined definition of a connection.
@@ -114,4 +132,6 @@ impl ConnectionAccess for InlinedConnection {
     type Ssh = super::SshConnection;
     type Csr = super::CsrConnection;
     type MySql = super::MySqlConnection;
+    type Aws = super::aws::AwsConnection;
+    type IcebergCatalog = super::iceberg::IcebergCatalogConnection;
 }
@@ -201,6 +201,9 @@ pub enum StorageError {
     ResourceExhausted(&'static str),
     /// The specified component is shutting down.
     ShuttingDown(&'static str),
+    /// The source identified did not ingest the data that was available upstream when a real-time
+    /// recency query was issued within the real-time recency timeout.
+    RtrTimeout(GlobalId),
     /// A generic error that happens during operations of the storage controller.
     // TODO(aljoscha): Get rid of this!
     Generic(anyhow::Error),
@@ -224,6 +227,7 @@ impl Error for StorageError {
             Self::InvalidUsage(_) => None,
             Self::ResourceExhausted(_) => None,
             Self::ShuttingDown(_) => None,
+            Self::RtrTimeout(_) => None,
             Self::Generic(err) => err.source(),
         }
     }
@@ -287,6 +291,11 @@ impl fmt::Display for StorageError {
             Self::InvalidUsage(err) => write!(f, "invalid usage: {}", err),
             Self::ResourceExhausted(rsc) => write!(f, "{rsc} is exhausted"),
             Self::ShuttingDown(cmp) => write!(f, "{cmp} is shutting down"),
+            Self::RtrTimeout(id) => write!(
+                f,
+                "timed out before ingesting the upstream data of {id} that was visible when the \
+                real-time recency query was issued"
+            ),
             Self::Generic(err) => std::fmt::Display::fmt(err, f),
         }
     }
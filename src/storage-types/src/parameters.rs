@@ -32,6 +32,10 @@ pub struct StorageParameters {
     pub keep_n_source_status_history_entries: usize,
     pub keep_n_sink_status_history_entries: usize,
     pub keep_n_privatelink_status_history_entries: usize,
+    pub keep_n_object_status_history_entries: usize,
+    /// How long to retain samples in the dataflow statistics history
+    /// collection. Older samples are removed on reboot.
+    pub dataflow_statistics_history_retention: Duration,
     /// A set of parameters used to tune RocksDB when used with `UPSERT` sources.
     pub upsert_rocksdb_tuning_config: mz_rocksdb_types::RocksDBTuningParameters,
     /// Whether or not to allow shard finalization to occur. Note that this will
@@ -92,6 +96,8 @@ impl Default for StorageParameters {
             keep_n_source_status_history_entries: Default::default(),
             keep_n_sink_status_history_entries: Default::default(),
             keep_n_privatelink_status_history_entries: Default::default(),
+            keep_n_object_status_history_entries: Default::default(),
+            dataflow_statistics_history_retention: Default::default(),
             upsert_rocksdb_tuning_config: Default::default(),
             finalize_shards: Default::default(),
             tracing: Default::default(),
@@ -191,6 +197,8 @@ impl StorageParameters {
             keep_n_source_status_history_entries,
             keep_n_sink_status_history_entries,
             keep_n_privatelink_status_history_entries,
+            keep_n_object_status_history_entries,
+            dataflow_statistics_history_retention,
             upsert_rocksdb_tuning_config,
             finalize_shards,
             tracing,
@@ -215,6 +223,8 @@ impl StorageParameters {
         self.keep_n_source_status_history_entries = keep_n_source_status_history_entries;
         self.keep_n_sink_status_history_entries = keep_n_sink_status_history_entries;
         self.keep_n_privatelink_status_history_entries = keep_n_privatelink_status_history_entries;
+        self.keep_n_object_status_history_entries = keep_n_object_status_history_entries;
+        self.dataflow_statistics_history_retention = dataflow_statistics_history_retention;
         self.upsert_rocksdb_tuning_config = upsert_rocksdb_tuning_config;
         self.finalize_shards = finalize_shards;
         self.tracing.update(tracing);
@@ -257,6 +267,12 @@ impl RustType<ProtoStorageParameters> for StorageParameters {
             keep_n_privatelink_status_history_entries: u64::cast_from(
                 self.keep_n_privatelink_status_history_entries,
             ),
+            keep_n_object_status_history_entries: u64::cast_from(
+                self.keep_n_object_status_history_entries,
+            ),
+            dataflow_statistics_history_retention: Some(
+                self.dataflow_statistics_history_retention.into_proto(),
+            ),
             upsert_rocksdb_tuning_config: Some(self.upsert_rocksdb_tuning_config.into_proto()),
             finalize_shards: self.finalize_shards,
             tracing: Some(self.tracing.into_proto()),
@@ -305,6 +321,14 @@ impl RustType<ProtoStorageParameters> for StorageParameters {
             keep_n_privatelink_status_history_entries: usize::cast_from(
                 proto.keep_n_privatelink_status_history_entries,
             ),
+            keep_n_object_status_history_entries: usize::cast_from(
+                proto.keep_n_object_status_history_entries,
+            ),
+            dataflow_statistics_history_retention: proto
+                .dataflow_statistics_history_retention
+                .into_rust_if_some(
+                    "ProtoStorageParameters::dataflow_statistics_history_retention",
+                )?,
             upsert_rocksdb_tuning_config: proto
                 .upsert_rocksdb_tuning_config
                 .into_rust_if_some("ProtoStorageParameters::upsert_rocksdb_tuning_config")?,
@@ -10,8 +10,13 @@
 //! Types and traits related to reporting changing collections out of `dataflow`.
 
 use std::borrow::Cow;
-use std::fmt::Debug;
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug};
+use std::time::Duration;
 
+use anyhow::Context;
+use mz_avro::schema::resolve_schemas;
+use mz_expr::MirScalarExpr;
 use mz_persist_client::ShardId;
 use mz_pgcopy::CopyFormatParams;
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
@@ -238,6 +243,7 @@ impl RustType<ProtoSinkEnvelope> for SinkEnvelope {
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum StorageSinkConnection<C: ConnectionAccess = InlinedConnection> {
     Kafka(KafkaSinkConnection<C>),
+    Iceberg(IcebergSinkConnection<C>),
 }
 
 impl<C: ConnectionAccess> StorageSinkConnection<C> {
@@ -257,6 +263,17 @@ impl<C: ConnectionAccess> StorageSinkConnection<C> {
             (StorageSinkConnection::Kafka(s), StorageSinkConnection::Kafka(o)) => {
                 s.alter_compatible(id, o)?
             }
+            (StorageSinkConnection::Iceberg(s), StorageSinkConnection::Iceberg(o)) => {
+                s.alter_compatible(id, o)?
+            }
+            _ => {
+                tracing::warn!(
+                    "StorageSinkConnection incompatible:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+                return Err(StorageError::InvalidAlter { id });
+            }
         }
 
         Ok(())
@@ -269,6 +286,7 @@ impl<R: ConnectionResolver> IntoInlineConnection<StorageSinkConnection, R>
     fn into_inline_connection(self, r: R) -> StorageSinkConnection {
         match self {
             Self::Kafka(conn) => StorageSinkConnection::Kafka(conn.into_inline_connection(r)),
+            Self::Iceberg(conn) => StorageSinkConnection::Iceberg(conn.into_inline_connection(r)),
         }
     }
 }
@@ -280,6 +298,7 @@ impl RustType<ProtoStorageSinkConnection> for StorageSinkConnection {
         ProtoStorageSinkConnection {
             kind: Some(match self {
                 Self::Kafka(conn) => KafkaV2(conn.into_proto()),
+                Self::Iceberg(conn) => Iceberg(conn.into_proto()),
             }),
         }
     }
@@ -292,6 +311,7 @@ impl RustType<ProtoStorageSinkConnection> for StorageSinkConnection {
 
         Ok(match kind {
             KafkaV2(proto) => Self::Kafka(proto.into_rust()?),
+            Iceberg(proto) => Self::Iceberg(proto.into_rust()?),
         })
     }
 }
@@ -302,6 +322,7 @@ impl<C: ConnectionAccess> StorageSinkConnection<C> {
         use StorageSinkConnection::*;
         match self {
             Kafka(KafkaSinkConnection { connection_id, .. }) => Some(*connection_id),
+            Iceberg(IcebergSinkConnection { connection_id, .. }) => Some(*connection_id),
         }
     }
 
@@ -310,6 +331,7 @@ impl<C: ConnectionAccess> StorageSinkConnection<C> {
         use StorageSinkConnection::*;
         match self {
             Kafka(_) => "kafka",
+            Iceberg(_) => "iceberg",
         }
     }
 }
@@ -382,8 +404,18 @@ pub struct KafkaSinkConnection<C: ConnectionAccess = InlinedConnection> {
     pub relation_key_indices: Option<Vec<usize>>,
     /// The user-specified key for the sink.
     pub key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    /// The index of the column in `value_desc` whose contents become the
+    /// headers of each message, if any.
+    pub headers_index: Option<usize>,
+    /// An expression whose hash determines the partition of each message, if
+    /// any. It evaluates to `text`, over the key of the message with the upsert
+    /// envelope and over the sinked row with the Debezium envelope.
+    #[proptest(strategy = "proptest::option::of(any::<MirScalarExpr>())")]
+    pub partition_by: Option<MirScalarExpr>,
     pub value_desc: RelationDesc,
     pub topic: String,
+    /// Options to use when creating the topic if it doesn't already exist.
+    pub topic_options: KafkaTopicOptions,
     pub compression_type: KafkaSinkCompressionType,
     pub progress_group_id: KafkaIdStyle,
     pub transactional_id: KafkaIdStyle,
@@ -467,8 +499,11 @@ impl<C: ConnectionAccess> KafkaSinkConnection<C> {
             format,
             relation_key_indices,
             key_desc_and_indices,
+            headers_index,
+            partition_by,
             value_desc,
             topic,
+            topic_options,
             compression_type,
             progress_group_id,
             transactional_id,
@@ -485,8 +520,11 @@ impl<C: ConnectionAccess> KafkaSinkConnection<C> {
                 key_desc_and_indices == &other.key_desc_and_indices,
                 "key_desc_and_indices",
             ),
+            (headers_index == &other.headers_index, "headers_index"),
+            (partition_by == &other.partition_by, "partition_by"),
             (value_desc == &other.value_desc, "value_desc"),
             (topic == &other.topic, "topic"),
+            (topic_options == &other.topic_options, "topic_options"),
             (
                 compression_type == &other.compression_type,
                 "compression_type",
@@ -526,8 +564,11 @@ impl<R: ConnectionResolver> IntoInlineConnection<KafkaSinkConnection, R>
             format,
             relation_key_indices,
             key_desc_and_indices,
+            headers_index,
+            partition_by,
             value_desc,
             topic,
+            topic_options,
             compression_type,
             progress_group_id,
             transactional_id,
@@ -538,8 +579,11 @@ impl<R: ConnectionResolver> IntoInlineConnection<KafkaSinkConnection, R>
             format: format.into_inline_connection(r),
             relation_key_indices,
             key_desc_and_indices,
+            headers_index,
+            partition_by,
             value_desc,
             topic,
+            topic_options,
             compression_type,
             progress_group_id,
             transactional_id,
@@ -547,6 +591,39 @@ impl<R: ConnectionResolver> IntoInlineConnection<KafkaSinkConnection, R>
     }
 }
 
+/// Options for the topic that a Kafka sink writes to.
+#[derive(Arbitrary, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct KafkaTopicOptions {
+    /// The number of partitions in the topic.
+    ///
+    /// `None` indicates the broker default.
+    pub partition_count: Option<i32>,
+    /// The replication factor of the topic.
+    ///
+    /// `None` indicates the broker default.
+    pub replication_factor: Option<i32>,
+    /// Additional Kafka configuration for the topic, e.g. `cleanup.policy`.
+    pub topic_config: BTreeMap<String, String>,
+}
+
+impl RustType<ProtoKafkaTopicOptions> for KafkaTopicOptions {
+    fn into_proto(&self) -> ProtoKafkaTopicOptions {
+        ProtoKafkaTopicOptions {
+            partition_count: self.partition_count,
+            replication_factor: self.replication_factor,
+            topic_config: self.topic_config.clone(),
+        }
+    }
+
+    fn from_proto(proto: ProtoKafkaTopicOptions) -> Result<Self, TryFromProtoError> {
+        Ok(KafkaTopicOptions {
+            partition_count: proto.partition_count,
+            replication_factor: proto.replication_factor,
+            topic_config: proto.topic_config,
+        })
+    }
+}
+
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum KafkaIdStyle {
     /// A new-style id that is optionally prefixed.
@@ -592,8 +669,11 @@ impl RustType<ProtoKafkaSinkConnectionV2> for KafkaSinkConnection {
             format: Some(self.format.into_proto()),
             key_desc_and_indices: self.key_desc_and_indices.into_proto(),
             relation_key_indices: self.relation_key_indices.into_proto(),
+            headers_index: self.headers_index.into_proto(),
+            partition_by: self.partition_by.into_proto(),
             value_desc: Some(self.value_desc.into_proto()),
             topic: self.topic.clone(),
+            topic_options: Some(self.topic_options.into_proto()),
             compression_type: Some(match self.compression_type {
                 KafkaSinkCompressionType::None => CompressionType::None(()),
                 KafkaSinkCompressionType::Gzip => CompressionType::Gzip(()),
@@ -620,10 +700,15 @@ impl RustType<ProtoKafkaSinkConnectionV2> for KafkaSinkConnection {
                 .into_rust_if_some("ProtoKafkaSinkConnectionV2::format")?,
             key_desc_and_indices: proto.key_desc_and_indices.into_rust()?,
             relation_key_indices: proto.relation_key_indices.into_rust()?,
+            headers_index: proto.headers_index.into_rust()?,
+            partition_by: proto.partition_by.into_rust()?,
             value_desc: proto
                 .value_desc
                 .into_rust_if_some("ProtoKafkaSinkConnectionV2::value_desc")?,
             topic: proto.topic,
+            topic_options: proto
+                .topic_options
+                .into_rust_if_some("ProtoKafkaSinkConnectionV2::topic_options")?,
             compression_type: match proto.compression_type {
                 Some(CompressionType::None(())) => KafkaSinkCompressionType::None,
                 Some(CompressionType::Gzip(())) => KafkaSinkCompressionType::Gzip,
@@ -646,14 +731,268 @@ impl RustType<ProtoKafkaSinkConnectionV2> for KafkaSinkConnection {
     }
 }
 
+/// A sink that maintains an Iceberg table with the contents of its input.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct IcebergSinkConnection<C: ConnectionAccess = InlinedConnection> {
+    pub connection_id: GlobalId,
+    pub connection: C::IcebergCatalog,
+    /// The namespace of the table, one element per level.
+    pub namespace: Vec<String>,
+    /// The name of the table within the namespace.
+    pub table: String,
+    /// The user-specified key for the sink, which identifies the rows that
+    /// each update replaces.
+    pub key_desc_and_indices: (RelationDesc, Vec<usize>),
+    /// A natural key of the sinked relation (view or source).
+    pub relation_key_indices: Option<Vec<usize>>,
+    pub value_desc: RelationDesc,
+    /// How often to commit a new snapshot to the table.
+    pub commit_interval: Duration,
+}
+
+impl<C: ConnectionAccess> IcebergSinkConnection<C> {
+    /// Determines if `self` is compatible with another `StorageSinkConnection`,
+    /// in such a way that it is possible to turn `self` into `other` through a
+    /// valid series of transformations (e.g. no transformation or `ALTER
+    /// CONNECTION`).
+    pub fn alter_compatible(
+        &self,
+        id: GlobalId,
+        other: &IcebergSinkConnection<C>,
+    ) -> Result<(), StorageError> {
+        if self == other {
+            return Ok(());
+        }
+        let IcebergSinkConnection {
+            connection_id,
+            // The details of the catalog connection itself may change
+            connection: _,
+            namespace,
+            table,
+            key_desc_and_indices,
+            relation_key_indices,
+            value_desc,
+            commit_interval,
+        } = self;
+
+        let compatibility_checks = [
+            (connection_id == &other.connection_id, "connection_id"),
+            (namespace == &other.namespace, "namespace"),
+            (table == &other.table, "table"),
+            (
+                key_desc_and_indices == &other.key_desc_and_indices,
+                "key_desc_and_indices",
+            ),
+            (
+                relation_key_indices == &other.relation_key_indices,
+                "relation_key_indices",
+            ),
+            (value_desc == &other.value_desc, "value_desc"),
+            (commit_interval == &other.commit_interval, "commit_interval"),
+        ];
+        for (compatible, field) in compatibility_checks {
+            if !compatible {
+                tracing::warn!(
+                    "IcebergSinkConnection incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+
+                return Err(StorageError::InvalidAlter { id });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<IcebergSinkConnection, R>
+    for IcebergSinkConnection<ReferencedConnection>
+{
+    fn into_inline_connection(self, r: R) -> IcebergSinkConnection {
+        let IcebergSinkConnection {
+            connection_id,
+            connection,
+            namespace,
+            table,
+            key_desc_and_indices,
+            relation_key_indices,
+            value_desc,
+            commit_interval,
+        } = self;
+        IcebergSinkConnection {
+            connection_id,
+            connection: r.resolve_connection(connection).unwrap_iceberg_catalog(),
+            namespace,
+            table,
+            key_desc_and_indices,
+            relation_key_indices,
+            value_desc,
+            commit_interval,
+        }
+    }
+}
+
+impl RustType<ProtoIcebergSinkConnection> for IcebergSinkConnection {
+    fn into_proto(&self) -> ProtoIcebergSinkConnection {
+        ProtoIcebergSinkConnection {
+            connection_id: Some(self.connection_id.into_proto()),
+            connection: Some(self.connection.into_proto()),
+            namespace: self.namespace.clone(),
+            table: self.table.clone(),
+            key_desc_and_indices: Some(self.key_desc_and_indices.into_proto()),
+            relation_key_indices: self.relation_key_indices.into_proto(),
+            value_desc: Some(self.value_desc.into_proto()),
+            commit_interval: Some(self.commit_interval.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoIcebergSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(IcebergSinkConnection {
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoIcebergSinkConnection::connection_id")?,
+            connection: proto
+                .connection
+                .into_rust_if_some("ProtoIcebergSinkConnection::connection")?,
+            namespace: proto.namespace,
+            table: proto.table,
+            key_desc_and_indices: proto
+                .key_desc_and_indices
+                .into_rust_if_some("ProtoIcebergSinkConnection::key_desc_and_indices")?,
+            relation_key_indices: proto.relation_key_indices.into_rust()?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoIcebergSinkConnection::value_desc")?,
+            commit_interval: proto
+                .commit_interval
+                .into_rust_if_some("ProtoIcebergSinkConnection::commit_interval")?,
+        })
+    }
+}
+
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum KafkaSinkFormat<C: ConnectionAccess = InlinedConnection> {
     Avro {
         key_schema: Option<String>,
         value_schema: String,
         csr_connection: C::Csr,
+        /// How the value schema must evolve relative to the latest schema
+        /// registered for the topic. If set, the sink checks the value schema
+        /// against the latest registered schema before publishing it.
+        value_schema_evolution: Option<AvroSchemaEvolution>,
     },
     Json,
+    Protobuf {
+        key: Option<ProtobufSinkMessage>,
+        value: ProtobufSinkMessage,
+        /// The schema registry to publish the schemas of the messages to, if
+        /// they were derived from the sink's relation.
+        csr_connection: Option<C::Csr>,
+    },
+}
+
+/// A Protobuf message that the keys or values of a sink are encoded as.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ProtobufSinkMessage {
+    /// An encoded `FileDescriptorSet` that contains the message.
+    pub descriptors: Vec<u8>,
+    /// The fully qualified name of the message.
+    pub message_name: String,
+    /// The text of the `.proto` file that declares the message, if the
+    /// message was derived from the sink's relation.
+    pub schema: Option<String>,
+}
+
+impl RustType<ProtoProtobufSinkMessage> for ProtobufSinkMessage {
+    fn into_proto(&self) -> ProtoProtobufSinkMessage {
+        ProtoProtobufSinkMessage {
+            descriptors: self.descriptors.clone(),
+            message_name: self.message_name.clone(),
+            schema: self.schema.clone(),
+        }
+    }
+
+    fn from_proto(proto: ProtoProtobufSinkMessage) -> Result<Self, TryFromProtoError> {
+        Ok(ProtobufSinkMessage {
+            descriptors: proto.descriptors,
+            message_name: proto.message_name,
+            schema: proto.schema,
+        })
+    }
+}
+
+/// The compatibility mode under which an Avro sink publishes an evolved schema
+/// to the schema registry.
+#[derive(Arbitrary, Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum AvroSchemaEvolution {
+    /// The new schema must be able to read data written with the previous
+    /// schema.
+    Backward,
+    /// The new schema must be both backward and forward compatible with the
+    /// previous schema.
+    Full,
+    /// The new schema is published without any compatibility checks.
+    None,
+}
+
+impl AvroSchemaEvolution {
+    /// Checks that `new_schema` may be published as the next version of a
+    /// subject whose latest schema is `previous_schema`, according to this
+    /// evolution mode.
+    pub fn check(&self, previous_schema: &str, new_schema: &str) -> Result<(), anyhow::Error> {
+        if *self == AvroSchemaEvolution::None {
+            return Ok(());
+        }
+        let previous: mz_avro::Schema = previous_schema
+            .parse()
+            .context("unable to parse the previous schema")?;
+        let new: mz_avro::Schema = new_schema
+            .parse()
+            .context("unable to parse the new schema")?;
+        resolve_schemas(&previous, &new)
+            .context("the new schema cannot read data written with the previous schema")?;
+        if *self == AvroSchemaEvolution::Full {
+            resolve_schemas(&new, &previous)
+                .context("the previous schema cannot read data written with the new schema")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for AvroSchemaEvolution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            AvroSchemaEvolution::Backward => "BACKWARD",
+            AvroSchemaEvolution::Full => "FULL",
+            AvroSchemaEvolution::None => "NONE",
+        })
+    }
+}
+
+impl RustType<ProtoAvroSchemaEvolution> for AvroSchemaEvolution {
+    fn into_proto(&self) -> ProtoAvroSchemaEvolution {
+        use proto_avro_schema_evolution::Kind;
+        ProtoAvroSchemaEvolution {
+            kind: Some(match self {
+                AvroSchemaEvolution::Backward => Kind::Backward(()),
+                AvroSchemaEvolution::Full => Kind::Full(()),
+                AvroSchemaEvolution::None => Kind::None(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoAvroSchemaEvolution) -> Result<Self, TryFromProtoError> {
+        use proto_avro_schema_evolution::Kind;
+        match proto.kind {
+            Some(Kind::Backward(())) => Ok(AvroSchemaEvolution::Backward),
+            Some(Kind::Full(())) => Ok(AvroSchemaEvolution::Full),
+            Some(Kind::None(())) => Ok(AvroSchemaEvolution::None),
+            None => Err(TryFromProtoError::missing_field(
+                "ProtoAvroSchemaEvolution::kind",
+            )),
+        }
+    }
 }
 
 impl<C: ConnectionAccess> KafkaSinkFormat<C> {
@@ -661,6 +1000,7 @@ impl<C: ConnectionAccess> KafkaSinkFormat<C> {
         match self {
             Self::Avro { .. } => "avro",
             Self::Json => "json",
+            Self::Protobuf { .. } => "protobuf",
         }
     }
 
@@ -676,16 +1016,22 @@ impl<C: ConnectionAccess> KafkaSinkFormat<C> {
                     value_schema,
                     // Connections may change
                     csr_connection: _,
+                    value_schema_evolution,
                 },
                 Self::Avro {
                     key_schema: other_key_schema,
                     value_schema: other_value_schema,
                     csr_connection: _,
+                    value_schema_evolution: other_value_schema_evolution,
                 },
             ) => {
                 let compatibility_checks = [
                     (key_schema == other_key_schema, "key_schema"),
                     (value_schema == other_value_schema, "value_schema"),
+                    (
+                        value_schema_evolution == other_value_schema_evolution,
+                        "value_schema_evolution",
+                    ),
                 ];
                 for (compatible, field) in compatibility_checks {
                     if !compatible {
@@ -699,6 +1045,39 @@ impl<C: ConnectionAccess> KafkaSinkFormat<C> {
                     }
                 }
             }
+            (
+                Self::Protobuf {
+                    key,
+                    value,
+                    // Connections may change
+                    csr_connection,
+                },
+                Self::Protobuf {
+                    key: other_key,
+                    value: other_value,
+                    csr_connection: other_csr_connection,
+                },
+            ) => {
+                let compatibility_checks = [
+                    (key == other_key, "key"),
+                    (value == other_value, "value"),
+                    (
+                        csr_connection.is_some() == other_csr_connection.is_some(),
+                        "csr_connection",
+                    ),
+                ];
+                for (compatible, field) in compatibility_checks {
+                    if !compatible {
+                        tracing::warn!(
+                            "KafkaSinkFormat::Protobuf incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                            self,
+                            other
+                        );
+
+                        return Err(StorageError::InvalidAlter { id });
+                    }
+                }
+            }
             (s, o) => {
                 if s != o {
                     tracing::warn!(
@@ -724,12 +1103,24 @@ impl<R: ConnectionResolver> IntoInlineConnection<KafkaSinkFormat, R>
                 key_schema,
                 value_schema,
                 csr_connection,
+                value_schema_evolution,
             } => KafkaSinkFormat::Avro {
                 key_schema,
                 value_schema,
                 csr_connection: r.resolve_connection(csr_connection).unwrap_csr(),
+                value_schema_evolution,
             },
             Self::Json => KafkaSinkFormat::Json,
+            Self::Protobuf {
+                key,
+                value,
+                csr_connection,
+            } => KafkaSinkFormat::Protobuf {
+                key,
+                value,
+                csr_connection: csr_connection
+                    .map(|csr_connection| r.resolve_connection(csr_connection).unwrap_csr()),
+            },
         }
     }
 }
@@ -743,12 +1134,23 @@ impl RustType<ProtoKafkaSinkFormat> for KafkaSinkFormat {
                     key_schema,
                     value_schema,
                     csr_connection,
+                    value_schema_evolution,
                 } => Kind::Avro(proto_kafka_sink_format::ProtoKafkaSinkAvroFormat {
                     key_schema: key_schema.clone(),
                     value_schema: value_schema.clone(),
                     csr_connection: Some(csr_connection.into_proto()),
+                    value_schema_evolution: value_schema_evolution.into_proto(),
                 }),
                 Self::Json => Kind::Json(()),
+                Self::Protobuf {
+                    key,
+                    value,
+                    csr_connection,
+                } => Kind::Protobuf(proto_kafka_sink_format::ProtoKafkaSinkProtobufFormat {
+                    key: key.into_proto(),
+                    value: Some(value.into_proto()),
+                    csr_connection: csr_connection.into_proto(),
+                }),
             }),
         }
     }
@@ -766,8 +1168,16 @@ impl RustType<ProtoKafkaSinkFormat> for KafkaSinkFormat {
                 csr_connection: proto
                     .csr_connection
                     .into_rust_if_some("ProtoKafkaSinkAvroFormat::csr_connection")?,
+                value_schema_evolution: proto.value_schema_evolution.into_rust()?,
             },
             Kind::Json(()) => Self::Json,
+            Kind::Protobuf(proto) => Self::Protobuf {
+                key: proto.key.into_rust()?,
+                value: proto
+                    .value
+                    .into_rust_if_some("ProtoKafkaSinkProtobufFormat::value")?,
+                csr_connection: proto.csr_connection.into_rust()?,
+            },
         })
     }
 }
@@ -806,3 +1216,39 @@ impl RustType<ProtoS3UploadInfo> for S3UploadInfo {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::AvroSchemaEvolution;
+
+    #[mz_ore::test]
+    fn test_avro_schema_evolution() {
+        let v1 = r#"{"type":"record","name":"row","fields":[{"name":"a","type":"int"}]}"#;
+        // Adds a nullable column with a default.
+        let v2 = r#"{"type":"record","name":"row","fields":[
+            {"name":"a","type":"int"},
+            {"name":"b","type":["null","string"],"default":null}
+        ]}"#;
+        // Adds a column without a default.
+        let v3 = r#"{"type":"record","name":"row","fields":[
+            {"name":"a","type":"int"},
+            {"name":"b","type":"string"}
+        ]}"#;
+        // Changes the type of a column.
+        let v4 = r#"{"type":"record","name":"row","fields":[{"name":"a","type":"string"}]}"#;
+
+        for (evolution, previous, new, ok) in [
+            (AvroSchemaEvolution::Backward, v1, v2, true),
+            (AvroSchemaEvolution::Backward, v2, v1, true),
+            (AvroSchemaEvolution::Backward, v1, v3, false),
+            (AvroSchemaEvolution::Backward, v1, v4, false),
+            (AvroSchemaEvolution::Full, v1, v2, true),
+            (AvroSchemaEvolution::Full, v1, v3, false),
+            (AvroSchemaEvolution::Full, v3, v1, false),
+            (AvroSchemaEvolution::None, v1, v4, true),
+        ] {
+            let res = evolution.check(previous, new);
+            assert_eq!(res.is_ok(), ok, "{evolution} {previous} -> {new}: {res:?}");
+        }
+    }
+}
@@ -19,6 +19,7 @@ use std::time::Duration;
 use bytes::BufMut;
 use itertools::EitherOrBoth::Both;
 use itertools::Itertools;
+use mz_ore::str::StrExt;
 use mz_persist_types::columnar::{
     ColumnFormat, ColumnGet, ColumnPush, Data, DataType, PartDecoder, PartEncoder, Schema,
 };
@@ -28,7 +29,7 @@ use mz_persist_types::Codec;
 use mz_proto::{IntoRustIfSome, ProtoMapEntry, ProtoType, RustType, TryFromProtoError};
 use mz_repr::{
     ColumnType, Datum, DatumDecoderT, DatumEncoderT, GlobalId, ProtoRow, RelationDesc, Row,
-    RowDecoder, RowEncoder,
+    RowDecoder, RowEncoder, ScalarType,
 };
 use proptest::prelude::any;
 use proptest_derive::Arbitrary;
@@ -625,6 +626,143 @@ impl RustType<ProtoCompression> for Compression {
     }
 }
 
+/// The policy a source uses to trade off freshness against overhead when
+/// assigning timestamps to the data it ingests.
+///
+/// Regardless of policy, updates read from the same upstream partition are
+/// always assigned non-decreasing timestamps in upstream order.
+#[derive(Arbitrary, Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TimestampPolicy {
+    /// Reclock at the coarsest permitted interval, minimizing the number of
+    /// distinct timestamps downstream consumers must process.
+    Throughput,
+    /// Reclock at the finest permitted interval, minimizing the time before
+    /// ingested data becomes readable.
+    Latency,
+    /// Like [`TimestampPolicy::Latency`], but additionally guarantees that
+    /// downstream consumers can recover the exact upstream order of each
+    /// partition, i.e. the source exposes each record's partition and offset.
+    StrictPartitionOrder,
+}
+
+impl TimestampPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimestampPolicy::Throughput => "throughput",
+            TimestampPolicy::Latency => "latency",
+            TimestampPolicy::StrictPartitionOrder => "strict partition order",
+        }
+    }
+}
+
+impl FromStr for TimestampPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "throughput" => Ok(TimestampPolicy::Throughput),
+            "latency" => Ok(TimestampPolicy::Latency),
+            "strict partition order" => Ok(TimestampPolicy::StrictPartitionOrder),
+            _ => Err(format!(
+                "unknown TIMESTAMP POLICY {}; expected one of 'throughput', 'latency', \
+                'strict partition order'",
+                s.quoted()
+            )),
+        }
+    }
+}
+
+impl RustType<ProtoTimestampPolicy> for TimestampPolicy {
+    fn into_proto(&self) -> ProtoTimestampPolicy {
+        use proto_timestamp_policy::Kind;
+        ProtoTimestampPolicy {
+            kind: Some(match self {
+                TimestampPolicy::Throughput => Kind::Throughput(()),
+                TimestampPolicy::Latency => Kind::Latency(()),
+                TimestampPolicy::StrictPartitionOrder => Kind::StrictPartitionOrder(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoTimestampPolicy) -> Result<Self, TryFromProtoError> {
+        use proto_timestamp_policy::Kind;
+        Ok(match proto.kind {
+            Some(Kind::Throughput(())) => TimestampPolicy::Throughput,
+            Some(Kind::Latency(())) => TimestampPolicy::Latency,
+            Some(Kind::StrictPartitionOrder(())) => TimestampPolicy::StrictPartitionOrder,
+            None => {
+                return Err(TryFromProtoError::MissingField(
+                    "ProtoTimestampPolicy::kind".into(),
+                ))
+            }
+        })
+    }
+}
+
+/// How a source responds to failures of its upstream connection.
+///
+/// After each failure the source is restarted with an exponentially
+/// increasing delay, starting at `initial_backoff` and capped at
+/// `max_backoff`. Once `max_attempts` consecutive restarts have failed, the
+/// source stops retrying and remains stalled until it is altered or its
+/// replica restarts.
+#[derive(Arbitrary, Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub max_attempts: Option<u64>,
+}
+
+impl Default for RetryPolicy {
+    /// Retry forever, waiting a fixed 30s between attempts.
+    fn default() -> Self {
+        RetryPolicy {
+            initial_backoff: Duration::from_secs(30),
+            max_backoff: Duration::from_secs(30),
+            max_attempts: None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns the delay before the restart numbered `attempt`, counting from
+    /// one.
+    pub fn backoff(&self, attempt: u64) -> Duration {
+        let exponent = u32::try_from(attempt.saturating_sub(1)).unwrap_or(u32::MAX);
+        2u32.checked_pow(exponent)
+            .and_then(|factor| self.initial_backoff.checked_mul(factor))
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff))
+    }
+
+    /// Reports whether the restart numbered `attempt` exceeds the policy's
+    /// maximum number of attempts.
+    pub fn is_exhausted(&self, attempt: u64) -> bool {
+        self.max_attempts.map_or(false, |max| attempt > max)
+    }
+}
+
+impl RustType<ProtoRetryPolicy> for RetryPolicy {
+    fn into_proto(&self) -> ProtoRetryPolicy {
+        ProtoRetryPolicy {
+            initial_backoff: Some(self.initial_backoff.into_proto()),
+            max_backoff: Some(self.max_backoff.into_proto()),
+            max_attempts: self.max_attempts,
+        }
+    }
+
+    fn from_proto(proto: ProtoRetryPolicy) -> Result<Self, TryFromProtoError> {
+        Ok(RetryPolicy {
+            initial_backoff: proto
+                .initial_backoff
+                .into_rust_if_some("ProtoRetryPolicy::initial_backoff")?,
+            max_backoff: proto
+                .max_backoff
+                .into_rust_if_some("ProtoRetryPolicy::max_backoff")?,
+            max_attempts: proto.max_attempts,
+        })
+    }
+}
+
 /// An external source of updates for a relational collection.
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Arbitrary)]
 pub struct SourceDesc<C: ConnectionAccess = InlinedConnection> {
@@ -632,6 +770,29 @@ pub struct SourceDesc<C: ConnectionAccess = InlinedConnection> {
     pub encoding: Option<encoding::SourceDataEncoding<C>>,
     pub envelope: SourceEnvelope,
     pub timestamp_interval: Duration,
+    /// The policy used to determine `timestamp_interval`, if one was
+    /// explicitly requested.
+    pub timestamp_policy: Option<TimestampPolicy>,
+    /// The output index to which records that fail to decode are routed, if
+    /// the source was created with a dead-letter queue. When unset, decode
+    /// errors are emitted into the error stream of the affected output.
+    pub dead_letter_output: Option<usize>,
+    /// How the source restarts after its upstream connection fails.
+    pub retry_policy: RetryPolicy,
+}
+
+/// Returns the schema of the dead-letter queue of a source whose progress is
+/// described by `timestamp_desc`.
+///
+/// Each row records the upstream position of a message that failed to decode,
+/// the raw bytes of whichever of its key and value failed, and the error.
+pub fn dead_letter_desc(timestamp_desc: RelationDesc) -> RelationDesc {
+    timestamp_desc.concat(
+        RelationDesc::empty()
+            .with_column("key", ScalarType::Bytes.nullable(true))
+            .with_column("value", ScalarType::Bytes.nullable(true))
+            .with_column("error", ScalarType::String.nullable(false)),
+    )
 }
 
 impl<R: ConnectionResolver> IntoInlineConnection<SourceDesc, R>
@@ -643,6 +804,9 @@ impl<R: ConnectionResolver> IntoInlineConnection<SourceDesc, R>
             encoding,
             envelope,
             timestamp_interval,
+            timestamp_policy,
+            dead_letter_output,
+            retry_policy,
         } = self;
 
         SourceDesc {
@@ -650,6 +814,9 @@ impl<R: ConnectionResolver> IntoInlineConnection<SourceDesc, R>
             encoding: encoding.map(|e| e.into_inline_connection(r)),
             envelope,
             timestamp_interval,
+            timestamp_policy,
+            dead_letter_output,
+            retry_policy,
         }
     }
 }
@@ -661,6 +828,9 @@ impl RustType<ProtoSourceDesc> for SourceDesc {
             encoding: self.encoding.into_proto(),
             envelope: Some(self.envelope.into_proto()),
             timestamp_interval: Some(self.timestamp_interval.into_proto()),
+            timestamp_policy: self.timestamp_policy.into_proto(),
+            dead_letter_output: self.dead_letter_output.into_proto(),
+            retry_policy: Some(self.retry_policy.into_proto()),
         }
     }
 
@@ -676,6 +846,15 @@ impl RustType<ProtoSourceDesc> for SourceDesc {
             timestamp_interval: proto
                 .timestamp_interval
                 .into_rust_if_some("ProtoSourceDesc::timestamp_interval")?,
+            timestamp_policy: proto.timestamp_policy.into_rust()?,
+            dead_letter_output: proto.dead_letter_output.into_rust()?,
+            // Sources created before retry policies were introduced use the
+            // default policy.
+            retry_policy: proto
+                .retry_policy
+                .map(RetryPolicy::from_proto)
+                .transpose()?
+                .unwrap_or_default(),
         })
     }
 }
@@ -736,6 +915,9 @@ impl<C: ConnectionAccess> crate::AlterCompatible for SourceDesc<C> {
             encoding,
             envelope,
             timestamp_interval,
+            timestamp_policy,
+            dead_letter_output,
+            retry_policy,
         } = &self;
 
         let compatibility_checks = [
@@ -749,6 +931,15 @@ impl<C: ConnectionAccess> crate::AlterCompatible for SourceDesc<C> {
                 timestamp_interval == &other.timestamp_interval,
                 "timestamp_interval",
             ),
+            (
+                timestamp_policy == &other.timestamp_policy,
+                "timestamp_policy",
+            ),
+            (
+                dead_letter_output == &other.dead_letter_output,
+                "dead_letter_output",
+            ),
+            (retry_policy == &other.retry_policy, "retry_policy"),
         ];
 
         for (compatible, field) in compatibility_checks {
@@ -1202,6 +1393,26 @@ mod tests {
         assert!("".parse::<Timeline>().is_err());
     }
 
+    #[mz_ore::test]
+    fn test_retry_policy_backoff() {
+        let policy = RetryPolicy {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(10),
+            max_attempts: Some(5),
+        };
+        let backoffs: Vec<_> = (1..=6).map(|attempt| policy.backoff(attempt)).collect();
+        assert_eq!(
+            backoffs,
+            [1, 2, 4, 8, 10, 10].map(Duration::from_secs).to_vec()
+        );
+        assert_eq!(policy.backoff(u64::MAX), Duration::from_secs(10));
+
+        assert!(!policy.is_exhausted(5));
+        assert!(policy.is_exhausted(6));
+        assert!(!RetryPolicy::default().is_exhausted(u64::MAX));
+        assert_eq!(RetryPolicy::default().backoff(7), Duration::from_secs(30));
+    }
+
     fn scalar_type_columnar_roundtrip(scalar_type: ScalarType) {
         let skip_decode = is_no_stats_type(&scalar_type);
 
@@ -10,10 +10,12 @@
 //! Types and traits related to the *decoding* of data for sources.
 
 use anyhow::Context;
+use mz_expr::MirScalarExpr;
 use mz_interchange::{avro, protobuf};
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::adt::regex::any_regex;
 use mz_repr::{ColumnType, RelationDesc, ScalarType};
+use proptest::prelude::any;
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
 
@@ -198,17 +200,24 @@ impl<C: ConnectionAccess> DataEncoding<C> {
                     let ty = ScalarType::String.nullable(true);
                     desc.with_column(name, ty)
                 }),
-            Self::Csv(CsvEncoding { columns, .. }) => match columns {
-                ColumnSpec::Count(n) => (1..=*n).fold(RelationDesc::empty(), |desc, i| {
-                    desc.with_column(format!("column{}", i), ScalarType::String.nullable(false))
-                }),
-                ColumnSpec::Header { names } => names
-                    .iter()
-                    .map(|s| &**s)
-                    .fold(RelationDesc::empty(), |desc, name| {
-                        desc.with_column(name, ScalarType::String.nullable(false))
+            Self::Csv(CsvEncoding { columns, casts, .. }) => {
+                let text_types = vec![ScalarType::String.nullable(false); columns.arity()];
+                let column_type = |i: usize| match casts.get(i) {
+                    Some(cast) => cast.typ(&text_types),
+                    None => text_types[i].clone(),
+                };
+                match columns {
+                    ColumnSpec::Count(n) => (1..=*n).fold(RelationDesc::empty(), |desc, i| {
+                        desc.with_column(format!("column{}", i), column_type(i - 1))
                     }),
-            },
+                    ColumnSpec::Header { names } => names
+                        .iter()
+                        .enumerate()
+                        .fold(RelationDesc::empty(), |desc, (i, name)| {
+                            desc.with_column(name, column_type(i))
+                        }),
+                }
+            }
             Self::Text => {
                 RelationDesc::empty().with_column("text", ScalarType::String.nullable(false))
             }
@@ -302,6 +311,12 @@ impl RustType<ProtoProtobufEncoding> for ProtobufEncoding {
 pub struct CsvEncoding {
     pub columns: ColumnSpec,
     pub delimiter: u8,
+    /// Expressions that produce each column of the output from a row of the
+    /// record's text values.
+    ///
+    /// Empty if all columns are to be presented as text.
+    #[proptest(strategy = "proptest::collection::vec(any::<MirScalarExpr>(), 0..4)")]
+    pub casts: Vec<MirScalarExpr>,
 }
 
 impl RustType<ProtoCsvEncoding> for CsvEncoding {
@@ -309,6 +324,7 @@ impl RustType<ProtoCsvEncoding> for CsvEncoding {
         ProtoCsvEncoding {
             columns: Some(self.columns.into_proto()),
             delimiter: self.delimiter.into_proto(),
+            casts: self.casts.into_proto(),
         }
     }
 
@@ -318,6 +334,7 @@ impl RustType<ProtoCsvEncoding> for CsvEncoding {
                 .columns
                 .into_rust_if_some("ProtoCsvEncoding::columns")?,
             delimiter: proto.delimiter.into_rust()?,
+            casts: proto.casts.into_rust()?,
         })
     }
 }
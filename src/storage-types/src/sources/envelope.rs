@@ -12,6 +12,7 @@
 use anyhow::{anyhow, bail};
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::{ColumnType, RelationDesc, RelationType, ScalarType};
+use mz_rocksdb_types::config::CompactionStyle;
 use proptest::prelude::any;
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
@@ -116,6 +117,47 @@ pub struct UpsertEnvelope {
     /// to deduplicate data in `upsert_core`
     #[proptest(strategy = "proptest::collection::vec(any::<usize>(), 0..4)")]
     pub key_indices: Vec<usize>,
+    /// Per-source overrides of how the upsert state is maintained.
+    pub state_config: UpsertStateConfig,
+}
+
+/// Per-source overrides of the cluster-wide configuration of upsert state.
+///
+/// These only take effect on replicas provisioned with disk; replicas without
+/// disk always keep upsert state in memory.
+#[derive(Arbitrary, Clone, Debug, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub struct UpsertStateConfig {
+    /// The size in bytes of each worker's in-memory upsert state beyond which
+    /// the state spills to disk. When set, spilling is enabled regardless of
+    /// the cluster-wide configuration.
+    pub memory_limit: Option<usize>,
+    /// The compaction style of the on-disk upsert state.
+    pub compaction_style: Option<CompactionStyle>,
+}
+
+impl RustType<ProtoUpsertStateConfig> for UpsertStateConfig {
+    fn into_proto(&self) -> ProtoUpsertStateConfig {
+        ProtoUpsertStateConfig {
+            memory_limit: self.memory_limit.into_proto(),
+            compaction_style: self.compaction_style.map(|style| style.to_string()),
+        }
+    }
+
+    fn from_proto(proto: ProtoUpsertStateConfig) -> Result<Self, TryFromProtoError> {
+        Ok(UpsertStateConfig {
+            memory_limit: proto.memory_limit.into_rust()?,
+            compaction_style: proto
+                .compaction_style
+                .map(|style| {
+                    style.parse().map_err(|_| {
+                        TryFromProtoError::UnknownEnumVariant(format!(
+                            "ProtoUpsertStateConfig::compaction_style: {style}"
+                        ))
+                    })
+                })
+                .transpose()?,
+        })
+    }
 }
 
 impl RustType<ProtoUpsertEnvelope> for UpsertEnvelope {
@@ -124,6 +166,7 @@ impl RustType<ProtoUpsertEnvelope> for UpsertEnvelope {
             source_arity: self.source_arity.into_proto(),
             style: Some(self.style.into_proto()),
             key_indices: self.key_indices.into_proto(),
+            state_config: Some(self.state_config.into_proto()),
         }
     }
 
@@ -134,6 +177,7 @@ impl RustType<ProtoUpsertEnvelope> for UpsertEnvelope {
                 .style
                 .into_rust_if_some("ProtoUpsertEnvelope::style")?,
             key_indices: proto.key_indices.into_rust()?,
+            state_config: proto.state_config.into_rust()?.unwrap_or_default(),
         })
     }
 }
@@ -227,6 +271,7 @@ impl UnplannedSourceEnvelope {
                     "into_source_envelope to be passed \
                     correct parameters for UnplannedSourceEnvelope::Upsert",
                 ),
+                state_config: UpsertStateConfig::default(),
             }),
             UnplannedSourceEnvelope::None(key_envelope) => SourceEnvelope::None(NoneEnvelope {
                 key_envelope,
@@ -11,9 +11,11 @@
 
 use std::collections::BTreeMap;
 use std::fmt;
+use std::sync::Arc;
 use std::time::Duration;
 
 use dec::OrderedDecimal;
+use mz_kafka_util::client::{get_partitions, MzClientContext};
 use mz_proto::{IntoRustIfSome, RustType, TryFromProtoError};
 use mz_repr::adt::numeric::Numeric;
 use mz_repr::{ColumnType, Datum, GlobalId, RelationDesc, Row, ScalarType};
@@ -21,8 +23,11 @@ use mz_timely_util::order::{Extrema, Partitioned};
 use once_cell::sync::Lazy;
 use proptest::prelude::any;
 use proptest_derive::Arbitrary;
+use rdkafka::consumer::{BaseConsumer, Consumer};
 use serde::{Deserialize, Serialize};
+use timely::progress::Antichain;
 
+use crate::configuration::StorageConfiguration;
 use crate::connections::inline::{
     ConnectionAccess, ConnectionResolver, InlinedConnection, IntoInlineConnection,
     ReferencedConnection,
@@ -112,6 +117,72 @@ impl<C: ConnectionAccess> KafkaSourceConnection<C> {
     }
 }
 
+impl KafkaSourceConnection {
+    /// Fetches the frontier of the data currently available upstream, i.e. the high watermark of
+    /// every partition of the topic.
+    ///
+    /// The frontier uses the same timestamps as the source's progress collection, so the source
+    /// has ingested all of the data currently in the topic once its progress reaches it.
+    pub async fn fetch_write_frontier(
+        &self,
+        storage_configuration: &StorageConfiguration,
+    ) -> Result<Antichain<Partitioned<RangeBound<i32>, MzOffset>>, anyhow::Error> {
+        let consumer: BaseConsumer<_> = self
+            .connection
+            .create_with_context(
+                storage_configuration,
+                MzClientContext::default(),
+                &BTreeMap::new(),
+            )
+            .await?;
+        let consumer = Arc::new(consumer);
+
+        let timeout = storage_configuration
+            .parameters
+            .kafka_timeout_config
+            .fetch_metadata_timeout;
+        let topic = self.topic.clone();
+        let high_watermarks =
+            mz_ore::task::spawn_blocking(|| format!("kafka_fetch_write_frontier:{topic}"), {
+                let topic = topic.clone();
+                move || {
+                    let pids = get_partitions(consumer.client(), &topic, timeout)?;
+                    let mut high_watermarks = BTreeMap::new();
+                    for pid in pids {
+                        let (_low, high) = consumer.fetch_watermarks(&topic, pid, timeout)?;
+                        high_watermarks.insert(pid, high);
+                    }
+                    Ok::<_, anyhow::Error>(high_watermarks)
+                }
+            })
+            .await??;
+
+        // Partitions that do not exist yet are complete at offset zero, just like in the
+        // frontier of the source itself.
+        let lower = high_watermarks
+            .keys()
+            .last()
+            .copied()
+            .map(RangeBound::after)
+            .unwrap_or(RangeBound::NegInfinity);
+        let mut frontier = Antichain::from_elem(Partitioned::new_range(
+            lower,
+            RangeBound::PosInfinity,
+            MzOffset::from(0),
+        ));
+        for (pid, high) in high_watermarks {
+            let high = u64::try_from(high).map_err(|_| {
+                anyhow::anyhow!("invalid negative offset {high} for partition {pid}")
+            })?;
+            frontier.insert(Partitioned::new_singleton(
+                RangeBound::exact(pid),
+                MzOffset::from(high),
+            ));
+        }
+        Ok(frontier)
+    }
+}
+
 impl<C: ConnectionAccess> SourceConnection for KafkaSourceConnection<C> {
     fn name(&self) -> &'static str {
         "kafka"
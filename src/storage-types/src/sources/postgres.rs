@@ -11,6 +11,7 @@
 
 use std::collections::BTreeMap;
 
+use anyhow::anyhow;
 use itertools::EitherOrBoth::Both;
 use itertools::Itertools;
 use mz_expr::MirScalarExpr;
@@ -21,13 +22,16 @@ use once_cell::sync::Lazy;
 use proptest::prelude::any;
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
+use timely::progress::Antichain;
+use tokio_postgres::types::PgLsn;
 
+use crate::configuration::StorageConfiguration;
 use crate::connections::inline::{
     ConnectionAccess, ConnectionResolver, InlinedConnection, IntoInlineConnection,
     ReferencedConnection,
 };
 use crate::controller::StorageError;
-use crate::sources::SourceConnection;
+use crate::sources::{MzOffset, SourceConnection};
 
 include!(concat!(
     env!("OUT_DIR"),
@@ -73,6 +77,43 @@ impl<R: ConnectionResolver> IntoInlineConnection<PostgresSourceConnection, R>
 pub static PG_PROGRESS_DESC: Lazy<RelationDesc> =
     Lazy::new(|| RelationDesc::empty().with_column("lsn", ScalarType::UInt64.nullable(true)));
 
+impl PostgresSourceConnection {
+    /// Fetches the frontier of the data currently available upstream, i.e. the current WAL LSN of
+    /// the upstream database.
+    ///
+    /// The frontier uses the same timestamps as the source's progress collection, so the source
+    /// has ingested all of the data currently committed upstream once its progress reaches it.
+    pub async fn fetch_write_frontier(
+        &self,
+        storage_configuration: &StorageConfiguration,
+    ) -> Result<Antichain<MzOffset>, anyhow::Error> {
+        let config = self
+            .connection
+            .config(
+                &*storage_configuration.connection_context.secrets_reader,
+                storage_configuration,
+            )
+            .await?;
+        let client = config
+            .connect(
+                "postgres_fetch_write_frontier",
+                &storage_configuration.connection_context.ssh_tunnel_manager,
+            )
+            .await?;
+
+        let row = mz_postgres_util::simple_query_opt(&client, "SELECT pg_current_wal_lsn()")
+            .await?
+            .ok_or_else(|| anyhow!("pg_current_wal_lsn() returned no rows"))?;
+        // `pg_current_wal_lsn` has the same "upper" semantics as the progress of the source.
+        let lsn = row
+            .get("pg_current_wal_lsn")
+            .ok_or_else(|| anyhow!("pg_current_wal_lsn() mysteriously has no value"))?
+            .parse::<PgLsn>()
+            .map_err(|_| anyhow!("pg_current_wal_lsn() returned an invalid LSN"))?;
+        Ok(Antichain::from_elem(MzOffset::from(lsn)))
+    }
+}
+
 impl<C: ConnectionAccess> SourceConnection for PostgresSourceConnection<C> {
     fn name(&self) -> &'static str {
         "postgres"
@@ -7,7 +7,8 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use mz_repr::{Datum, Row};
+use mz_expr::{EvalError, MirScalarExpr};
+use mz_repr::{Datum, Row, RowArena};
 use mz_storage_types::errors::DecodeErrorKind;
 use mz_storage_types::sources::encoding::CsvEncoding;
 
@@ -21,6 +22,7 @@ pub struct CsvDecoderState {
     ends: Vec<usize>,
     ends_cursor: usize,
     csv_reader: csv_core::Reader,
+    casts: Vec<MirScalarExpr>,
     row_buf: Row,
     events_error: usize,
     events_success: usize,
@@ -32,7 +34,11 @@ impl CsvDecoderState {
     }
 
     pub fn new(format: CsvEncoding) -> Self {
-        let CsvEncoding { columns, delimiter } = format;
+        let CsvEncoding {
+            columns,
+            delimiter,
+            casts,
+        } = format;
         let n_cols = columns.arity();
 
         let header_names = columns.into_header_names();
@@ -45,6 +51,7 @@ impl CsvDecoderState {
             ends: vec![0],
             ends_cursor: 1,
             csv_reader: csv_core::ReaderBuilder::new().delimiter(delimiter).build(),
+            casts,
             row_buf: Row::default(),
             events_error: 0,
             events_success: 0,
@@ -96,14 +103,45 @@ impl CsvDecoderState {
                         } else {
                             match std::str::from_utf8(&self.output[0..self.output_cursor]) {
                                 Ok(output) => {
-                                    self.events_success += 1;
-                                    let mut row_packer = self.row_buf.packer();
-                                    row_packer.extend((0..self.n_cols).map(|i| {
+                                    let datums = (0..self.n_cols).map(|i| {
                                         Datum::String(&output[self.ends[i]..self.ends[i + 1]])
-                                    }));
+                                    });
+                                    // The header row is compared against the
+                                    // expected names as text, so it must not
+                                    // be cast.
+                                    let apply_casts =
+                                        !self.casts.is_empty() && !self.next_row_is_header;
+                                    let result: Result<(), (usize, EvalError)> = if apply_casts {
+                                        let datums: Vec<_> = datums.collect();
+                                        let arena = RowArena::new();
+                                        let mut row_packer = self.row_buf.packer();
+                                        self.casts.iter().enumerate().try_for_each(|(i, cast)| {
+                                            let datum =
+                                                cast.eval(&datums, &arena).map_err(|e| (i, e))?;
+                                            row_packer.push(datum);
+                                            Ok(())
+                                        })
+                                    } else {
+                                        self.row_buf.packer().extend(datums);
+                                        Ok(())
+                                    };
                                     self.output_cursor = 0;
                                     self.ends_cursor = 1;
-                                    Ok(Some(self.row_buf.clone()))
+                                    match result {
+                                        Ok(()) => {
+                                            self.events_success += 1;
+                                            Ok(Some(self.row_buf.clone()))
+                                        }
+                                        Err((i, e)) => {
+                                            self.events_error += 1;
+                                            Err(DecodeErrorKind::Text(format!(
+                                                "CSV error at record number {}, column {}: {}",
+                                                self.total_events(),
+                                                i + 1,
+                                                e
+                                            )))
+                                        }
+                                    }
                                 }
                                 Err(e) => {
                                     self.events_error += 1;
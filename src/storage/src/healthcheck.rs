@@ -15,14 +15,14 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::fmt::Debug;
 use std::rc::Rc;
-use std::time::Duration;
 
 use chrono::{DateTime, Utc};
 use differential_dataflow::Hashable;
 use mz_ore::cast::CastFrom;
 use mz_ore::now::NowFn;
 use mz_repr::GlobalId;
-use mz_storage_client::client::{Status, StatusUpdate};
+use mz_storage_client::client::{RetryStatus, Status, StatusUpdate};
+use mz_storage_types::sources::RetryPolicy;
 use mz_timely_util::builder_async::{
     Event as AsyncEvent, OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton,
 };
@@ -34,10 +34,6 @@ use tracing::{error, info};
 
 use crate::internal_control::{InternalCommandSender, InternalStorageCommand};
 
-/// How long to wait before initiating a `SuspendAndRestart` command, to
-/// prevent hot restart loops.
-const SUSPEND_AND_RESTART_DELAY: Duration = Duration::from_secs(30);
-
 /// The namespace of the update. The `Ord` impl matter here, later variants are
 /// displayed over earlier ones.
 ///
@@ -53,6 +49,7 @@ pub enum StatusNamespace {
     Kafka,
     Postgres,
     MySql,
+    Iceberg,
     Ssh,
     Upsert,
     Decode,
@@ -73,6 +70,7 @@ impl fmt::Display for StatusNamespace {
             Kafka => write!(f, "kafka"),
             Postgres => write!(f, "postgres"),
             MySql => write!(f, "mysql"),
+            Iceberg => write!(f, "iceberg"),
             Ssh => write!(f, "ssh"),
             Upsert => write!(f, "upsert"),
             Decode => write!(f, "decode"),
@@ -248,6 +246,7 @@ pub trait HealthOperator {
         new_error: Option<&str>,
         hints: &BTreeSet<String>,
         namespaced_errors: &BTreeMap<StatusNamespace, String>,
+        retry: Option<&RetryStatus>,
         // TODO(guswynn): not urgent:
         // Ideally this would be entirely included in the `DefaultWriter`, but that
         // requires a fairly heavy change to the `health_operator`, which hardcodes
@@ -279,6 +278,7 @@ impl HealthOperator for DefaultWriter {
         new_error: Option<&str>,
         hints: &BTreeSet<String>,
         namespaced_errors: &BTreeMap<StatusNamespace, String>,
+        retry: Option<&RetryStatus>,
         write_namespaced_map: bool,
     ) {
         self.updates.borrow_mut().push(StatusUpdate {
@@ -295,6 +295,7 @@ impl HealthOperator for DefaultWriter {
             } else {
                 BTreeMap::new()
             },
+            retry: retry.cloned(),
         });
     }
 
@@ -348,6 +349,10 @@ pub(crate) fn health_operator<'g, G, P>(
     health_operator_impl: P,
     // Whether or not we should actually write namespaced errors in the `details` column.
     write_namespaced_map: bool,
+    // How to delay and bound the restarts of `halting_id`.
+    retry_policy: RetryPolicy,
+    // The number of consecutive restarts of each object, shared across restarts.
+    restart_attempts: Rc<RefCell<BTreeMap<GlobalId, u64>>>,
 ) -> PressOnDropButton
 where
     G: Scope<Timestamp = ()>,
@@ -400,6 +405,7 @@ where
                             status.error(),
                             &status.hints(),
                             status.errors().unwrap_or(&BTreeMap::new()),
+                            None,
                             write_namespaced_map,
                         )
                         .await;
@@ -410,6 +416,10 @@ where
         }
 
         let mut outputs_seen = BTreeMap::<usize, BTreeSet<_>>::new();
+        // Whether we have already acted on a halting error, by either
+        // scheduling a restart or giving up on restarting. Each instance of
+        // the dataflow counts as a single restart attempt.
+        let mut halted = false;
         while let Some(event) = input.next().await {
             if let AsyncEvent::Data(_cap, rows) = event {
                 for (worker_id, message) in rows {
@@ -482,6 +492,7 @@ where
                                 new_status.error(),
                                 &new_status.hints(),
                                 new_status.errors().unwrap_or(&BTreeMap::new()),
+                                None,
                                 write_namespaced_map,
                             )
                             .await;
@@ -489,6 +500,12 @@ where
                         *last_reported_status = Some(new_status.clone());
                     }
 
+                    // Once the object is healthy again, its next failure starts
+                    // a fresh round of retries.
+                    if *id == halting_id && new_status == OverallStatus::Running {
+                        restart_attempts.borrow_mut().remove(id);
+                    }
+
                     // Set halt with if None.
                     if halt_with_outer.is_none() && halt_with.is_some() {
                         halt_with_outer = Some((*id, halt_with.clone()));
@@ -509,12 +526,64 @@ where
                         halting_id
                     );
 
+                    if halted {
+                        continue;
+                    }
+                    halted = true;
+
+                    let attempt = {
+                        let mut restart_attempts = restart_attempts.borrow_mut();
+                        let attempt = restart_attempts.entry(id).or_insert(0);
+                        *attempt += 1;
+                        *attempt
+                    };
+                    let delay = retry_policy.backoff(attempt);
+                    let exhausted = retry_policy.is_exhausted(attempt);
+                    let retry = RetryStatus {
+                        attempt: attempt - 1,
+                        max_attempts: retry_policy.max_attempts,
+                        next_attempt_at: chrono::Duration::from_std(delay)
+                            .ok()
+                            .filter(|_| !exhausted)
+                            .map(|delay| mz_ore::now::to_datetime(now()) + delay),
+                    };
+
+                    // Re-record the current status of the halting object, now
+                    // annotated with the state of its retries.
+                    if let Some(status) = health_states
+                        .values()
+                        .find(|state| state.id == id)
+                        .and_then(|state| state.last_reported_status.as_ref())
+                    {
+                        health_operator_impl
+                            .record_new_status(
+                                id,
+                                mz_ore::now::to_datetime(now()),
+                                status.into(),
+                                status.error(),
+                                &status.hints(),
+                                status.errors().unwrap_or(&BTreeMap::new()),
+                                Some(&retry),
+                                write_namespaced_map,
+                            )
+                            .await;
+                    }
+
+                    if exhausted {
+                        info!(
+                            "Not restarting {object_type} {id} after {:?}: \
+                            retries exhausted after {} attempts",
+                            halt_with, retry.attempt
+                        );
+                        continue;
+                    }
+
                     info!(
                         "Broadcasting suspend-and-restart \
                         command because of {:?} after {:?} delay",
-                        halt_with, SUSPEND_AND_RESTART_DELAY
+                        halt_with, delay
                     );
-                    tokio::time::sleep(SUSPEND_AND_RESTART_DELAY).await;
+                    tokio::time::sleep(delay).await;
                     health_operator_impl.send_halt(id, halt_with).await;
                 }
             }
@@ -1030,6 +1099,7 @@ mod tests {
             new_error: Option<&str>,
             hints: &BTreeSet<String>,
             namespaced_errors: &BTreeMap<StatusNamespace, String>,
+            _retry: Option<&RetryStatus>,
             write_namespaced_map: bool,
         ) {
             let _ = self.sender.send(StatusToAssert {
@@ -1115,6 +1185,8 @@ mod tests {
                                     input_mapping: inputs,
                                 },
                                 write_namespaced_map,
+                                RetryPolicy::default(),
+                                Default::default(),
                             )));
                         });
                 });
@@ -204,7 +204,7 @@ use mz_ore::error::ErrorExt;
 use mz_repr::{GlobalId, Row};
 use mz_storage_types::controller::CollectionMetadata;
 use mz_storage_types::sinks::{MetadataFilled, StorageSinkDesc};
-use mz_storage_types::sources::{GenericSourceConnection, IngestionDescription};
+use mz_storage_types::sources::{GenericSourceConnection, IngestionDescription, RetryPolicy};
 use timely::communication::Allocate;
 use timely::dataflow::operators::{Concatenate, ConnectLoop, Feedback, Leave, Map};
 use timely::dataflow::Scope;
@@ -375,6 +375,8 @@ pub fn build_ingestion_dataflow<A: Allocate>(
                     .storage_configuration
                     .parameters
                     .record_namespaced_errors,
+                description.desc.retry_policy,
+                Rc::clone(&storage_state.restart_attempts),
             );
             tokens.push(health_token);
 
@@ -436,6 +438,8 @@ pub fn build_export_dataflow<A: Allocate>(
                     .storage_configuration
                     .parameters
                     .record_namespaced_errors,
+                RetryPolicy::default(),
+                Rc::clone(&storage_state.restart_attempts),
             );
             tokens.push(health_token);
 
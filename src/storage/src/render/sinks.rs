@@ -245,5 +245,6 @@ where
 {
     match connection {
         StorageSinkConnection::Kafka(connection) => Box::new(connection.clone()),
+        StorageSinkConnection::Iceberg(connection) => Box::new(connection.clone()),
     }
 }
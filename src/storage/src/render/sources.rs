@@ -143,14 +143,23 @@ where
 
     needed_tokens.extend(source_tokens);
 
+    let dead_letter_output = description.desc.dead_letter_output;
+    let mut dead_letter_collections = vec![];
+
     let mut outputs = vec![];
-    for (ok_source, err_source) in streams {
+    for (output_index, (ok_source, err_source)) in streams.into_iter().enumerate() {
+        // Raw sources never emit data at the dead-letter output. It is instead
+        // populated with the messages of the other outputs that fail to decode.
+        if Some(output_index) == dead_letter_output {
+            continue;
+        }
+
         // All sources should push their various error streams into this vector,
         // whose contents will be concatenated and inserted along the collection.
         // All subsources include the non-definite errors of the ingestion
         let error_collections = vec![err_source.map(DataflowError::from)];
 
-        let (ok, err, extra_tokens, health_stream) = render_source_stream(
+        let (ok, err, dead_letters, extra_tokens, health_stream) = render_source_stream(
             scope,
             dataflow_debug_name,
             id,
@@ -162,15 +171,25 @@ where
             starter.clone(),
         );
         needed_tokens.extend(extra_tokens);
+        dead_letter_collections.extend(dead_letters);
         outputs.push((ok, err));
 
         health = health.concat(&health_stream.leave());
     }
+
+    if let Some(output_index) = dead_letter_output {
+        let dead_letters = collection::concatenate(scope, dead_letter_collections);
+        outputs.insert(output_index, (dead_letters, Collection::empty(scope)));
+    }
+
     (outputs, health, needed_tokens)
 }
 
 /// Completes the rendering of a particular source stream by applying decoding and envelope
 /// processing as necessary
+///
+/// If the source has a dead-letter queue, the messages that fail to decode are returned as a
+/// separate collection instead of being passed on to the envelope.
 fn render_source_stream<G, FromTime>(
     scope: &mut G,
     dataflow_debug_name: &String,
@@ -184,12 +203,13 @@ fn render_source_stream<G, FromTime>(
 ) -> (
     Collection<G, Row, Diff>,
     Collection<G, DataflowError, Diff>,
+    Option<Collection<G, Row, Diff>>,
     Vec<PressOnDropButton>,
     Stream<G, HealthStatusMessage>,
 )
 where
     G: Scope<Timestamp = mz_repr::Timestamp>,
-    FromTime: Timestamp,
+    FromTime: SourceTimestamp,
 {
     let mut needed_tokens = vec![];
 
@@ -198,6 +218,9 @@ where
         envelope,
         connection: _,
         timestamp_interval: _,
+        timestamp_policy: _,
+        dead_letter_output,
+        retry_policy: _,
     } = description.desc;
 
     let (decoded_stream, decode_health) = match encoding {
@@ -220,6 +243,26 @@ where
         ),
     };
 
+    // Divert the messages that failed to decode into the dead-letter queue, if there is one, so
+    // that they don't poison the rest of the source.
+    let (decoded_stream, dead_letters) = match dead_letter_output {
+        Some(_) => {
+            let source_statistics = base_source_config.source_statistics.clone();
+            let (decoded, dead_letters) =
+                decoded_stream.inner.ok_err(move |(result, time, diff)| {
+                    match dead_letter_row(&result) {
+                        Some(row) => {
+                            source_statistics.inc_messages_dead_lettered_by(1);
+                            Err((row, time, diff))
+                        }
+                        None => Ok((result, time, diff)),
+                    }
+                });
+            (decoded.as_collection(), Some(dead_letters.as_collection()))
+        }
+        None => (decoded_stream, None),
+    };
+
     // render envelopes
     let (envelope_ok, envelope_err, envelope_health) = match &envelope {
         SourceEnvelope::Upsert(upsert_envelope) => {
@@ -437,7 +480,42 @@ where
     };
 
     // Return the collections and any needed tokens.
-    (collection, err_collection, needed_tokens, health)
+    (
+        collection,
+        err_collection,
+        dead_letters,
+        needed_tokens,
+        health,
+    )
+}
+
+/// Returns the dead-letter queue row for `result` if either its key or its value failed to
+/// decode. See [`dead_letter_desc`] for the schema of the row.
+fn dead_letter_row<FromTime: SourceTimestamp>(result: &DecodeResult<FromTime>) -> Option<Row> {
+    let key_err = match &result.key {
+        Some(Err(err)) => Some(err),
+        _ => None,
+    };
+    let value_err = match &result.value {
+        Some(Err(err)) => Some(err),
+        _ => None,
+    };
+    let error = match (key_err, value_err) {
+        (None, None) => return None,
+        (Some(key_err), None) => format!("key: {}", key_err.kind),
+        (None, Some(value_err)) => format!("value: {}", value_err.kind),
+        (Some(key_err), Some(value_err)) => {
+            format!("key: {}; value: {}", key_err.kind, value_err.kind)
+        }
+    };
+
+    let mut row = result.from_time.encode_row();
+    RowPacker::for_existing_row(&mut row).extend([
+        key_err.map_or(Datum::Null, |err| Datum::Bytes(&err.raw)),
+        value_err.map_or(Datum::Null, |err| Datum::Bytes(&err.raw)),
+        Datum::String(&error),
+    ]);
+    Some(row)
 }
 
 // Returns the maximum limit of inflight bytes for backpressure based on given config
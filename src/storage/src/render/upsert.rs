@@ -220,20 +220,31 @@ where
     };
 
     if let Some(scratch_directory) = instance_context.scratch_directory.as_ref() {
-        let tuning = dataflow_paramters.upsert_rocksdb_tuning_config.clone();
+        let state_config = &upsert_envelope.state_config;
 
-        let allow_auto_spill = storage_configuration
-            .parameters
-            .upsert_auto_spill_config
-            .allow_spilling_to_disk;
-        let spill_threshold = storage_configuration
-            .parameters
-            .upsert_auto_spill_config
-            .spill_to_disk_threshold_bytes;
+        let mut tuning = dataflow_paramters.upsert_rocksdb_tuning_config.clone();
+        if let Some(compaction_style) = state_config.compaction_style {
+            tuning.compaction_style = compaction_style;
+        }
+
+        // A per-source memory limit opts the source into spilling, even if
+        // spilling is not enabled cluster-wide.
+        let allow_auto_spill = state_config.memory_limit.is_some()
+            || storage_configuration
+                .parameters
+                .upsert_auto_spill_config
+                .allow_spilling_to_disk;
+        let spill_threshold = state_config.memory_limit.unwrap_or(
+            storage_configuration
+                .parameters
+                .upsert_auto_spill_config
+                .spill_to_disk_threshold_bytes,
+        );
 
         tracing::info!(
             ?tuning,
             ?storage_configuration.parameters.upsert_auto_spill_config,
+            ?state_config,
             "timely-{} rendering {} with rocksdb-backed upsert state",
             source_config.worker_id,
             source_config.id
@@ -0,0 +1,735 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Code to render the sink dataflow of an [`IcebergSinkConnection`].
+//!
+//! All updates are exchanged to a single worker, which buffers them until the
+//! configured commit interval has elapsed and then commits them to the table
+//! as a single Iceberg snapshot. Each commit consists of:
+//!
+//!   * a Parquet data file with the new value of every key that was inserted
+//!     or updated,
+//!   * a Parquet equality delete file with every key that was inserted,
+//!     updated, or deleted, which masks the previous values of those keys, and
+//!   * a manifest for each of the two files and a new manifest list, which
+//!     carries forward the manifests of the previous snapshot.
+//!
+//! Delete files only apply to data files with a lower sequence number, so the
+//! new values written by a commit are not masked by its own deletes.
+//!
+//! # Progress
+//!
+//! The frontier up to which updates have been committed is stored in the
+//! summary of every snapshot that the sink commits, along with the sink's ID.
+//! When the sink restarts it resumes from the frontier of the most recent such
+//! snapshot in the history of the table's current snapshot. Commits assert
+//! that the table's current snapshot has not changed since it was last
+//! observed. If another writer commits to the table concurrently the sink
+//! retries on top of the new snapshot, unless the conflicting commit was made
+//! by another instance of this sink, in which case it halts.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use std::time::Instant;
+
+use anyhow::{anyhow, bail, Context};
+use aws_sdk_s3::primitives::ByteStream;
+use differential_dataflow::{Collection, Hashable};
+use mz_avro::types::Value;
+use mz_ore::cast::CastFrom;
+use mz_ore::error::ErrorExt;
+use mz_ore::now::NowFn;
+use mz_ore::vec::VecExt;
+use mz_repr::{ColumnType, Diff, GlobalId, RelationDesc, Row, ScalarType, Timestamp};
+use mz_storage_operators::parquet::ParquetFileWriter;
+use mz_storage_types::configuration::StorageConfiguration;
+use mz_storage_types::connections::iceberg::{
+    IcebergCatalogClient, IcebergCommitError, IcebergTableMetadata,
+};
+use mz_storage_types::errors::DataflowError;
+use mz_storage_types::sinks::{IcebergSinkConnection, MetadataFilled, StorageSinkDesc};
+use mz_timely_util::antichain::AntichainExt;
+use mz_timely_util::builder_async::{
+    Event, OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton,
+};
+use serde_json::json;
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::{Concatenate, Map, ToStream};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::{Antichain, Timestamp as _};
+use timely::PartialOrder;
+use tracing::{error, info, warn};
+
+use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
+use crate::render::sinks::SinkRender;
+use crate::statistics::SinkStatistics;
+use crate::storage_state::StorageState;
+
+mod manifest;
+
+use self::manifest::{Content, DataFile};
+
+/// The snapshot summary key that records the ID of the sink that committed
+/// the snapshot.
+const SINK_ID_KEY: &str = "mz-sink-id";
+/// The snapshot summary key that records the frontier up to which the sink
+/// has committed updates, as a JSON array of timestamps.
+const FRONTIER_KEY: &str = "mz-frontier";
+/// The number of times a commit is retried on top of a concurrent commit by
+/// another writer.
+const MAX_COMMIT_ATTEMPTS: usize = 10;
+
+impl<G: Scope<Timestamp = Timestamp>> SinkRender<G> for IcebergSinkConnection {
+    fn uses_keys(&self) -> bool {
+        true
+    }
+
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        Some(&self.key_desc_and_indices.1)
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        self.relation_key_indices.as_deref()
+    }
+
+    fn render_continuous_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<MetadataFilled, Timestamp>,
+        sink_id: GlobalId,
+        input: Collection<G, (Option<Row>, Option<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+    ) -> (Stream<G, HealthStatusMessage>, Vec<PressOnDropButton>) {
+        let mut scope = input.scope();
+
+        let write_frontier = Rc::new(RefCell::new(Antichain::from_elem(Timestamp::minimum())));
+        storage_state
+            .sink_write_frontiers
+            .insert(sink_id, Rc::clone(&write_frontier));
+
+        let statistics = storage_state
+            .aggregated_statistics
+            .get_sink(&sink_id)
+            .expect("statistics initialized")
+            .clone();
+
+        let (sink_status, sink_token) = sink_collection(
+            format!("iceberg-{sink_id}-sink"),
+            &input,
+            sink_id,
+            self.clone(),
+            storage_state.storage_configuration.clone(),
+            sink.as_of.clone(),
+            statistics,
+            write_frontier,
+            storage_state.now.clone(),
+        );
+
+        let running_status = Some(HealthStatusMessage {
+            index: 0,
+            update: HealthStatusUpdate::Running,
+            namespace: StatusNamespace::Iceberg,
+        })
+        .to_stream(&mut scope);
+
+        let status = scope.concatenate([running_status, sink_status]);
+
+        (status, vec![sink_token])
+    }
+}
+
+/// Commits the upsert stream `input` to the sink's Iceberg table.
+///
+/// This operator exchanges all updates to a single worker by hashing on the given sink `id`.
+fn sink_collection<G: Scope<Timestamp = Timestamp>>(
+    name: String,
+    input: &Collection<G, (Option<Row>, Option<Row>), Diff>,
+    sink_id: GlobalId,
+    connection: IcebergSinkConnection,
+    storage_configuration: StorageConfiguration,
+    as_of: Antichain<Timestamp>,
+    statistics: SinkStatistics,
+    write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+    now: NowFn,
+) -> (Stream<G, HealthStatusMessage>, PressOnDropButton) {
+    let scope = input.scope();
+    let mut builder = AsyncOperatorBuilder::new(name.clone(), input.inner.scope());
+
+    // We want exactly one worker to commit to the table.
+    let hashed_id = sink_id.hashed();
+    let is_active_worker = usize::cast_from(hashed_id) % scope.peers() == scope.index();
+
+    let mut input = builder.new_disconnected_input(&input.inner, Exchange::new(move |_| hashed_id));
+
+    let (button, errors) = builder.build_fallible(move |_caps| {
+        Box::pin(async move {
+            if !is_active_worker {
+                write_frontier.borrow_mut().clear();
+                return Ok(());
+            }
+
+            let mut writer =
+                IcebergTableWriter::new(sink_id, &connection, &storage_configuration).await?;
+            let resume_upper = writer.upper.clone();
+            statistics.record_resume_upper(
+                (*resume_upper != [Timestamp::minimum()]).then_some(&resume_upper),
+            );
+
+            // The input has overcompacted if
+            let overcompacted =
+                // ..we have made some progress in the past
+                *resume_upper != [Timestamp::minimum()] &&
+                // ..but the since frontier is now beyond that
+                !PartialOrder::less_equal(&as_of, &resume_upper);
+            if overcompacted {
+                let err = format!(
+                    "{name}: input compacted past resume upper: as_of {}, resume_upper: {}",
+                    as_of.pretty(),
+                    resume_upper.pretty()
+                );
+                // This would normally be an assertion but because it can happen after a
+                // Materialize backup/restore we log an error so that it appears on Sentry but
+                // leaves the rest of the objects in the cluster unaffected.
+                error!("{err}");
+                return Err(anyhow!("{err}"));
+            }
+
+            info!(
+                "{name}: as_of: {}, resume upper: {}",
+                as_of.pretty(),
+                resume_upper.pretty()
+            );
+
+            let Some(upper) = resume_upper.as_option().copied() else {
+                return Ok(());
+            };
+            let mut deferred_updates = vec![];
+            let mut last_commit = Instant::now();
+            while let Some(event) = input.next().await {
+                match event {
+                    Event::Data(_cap, batch) => {
+                        for ((key, value), time, diff) in batch {
+                            // Updates not beyond the resume upper have already been committed.
+                            if time < upper {
+                                continue;
+                            }
+                            let key = key.expect("Iceberg sinks are always keyed");
+                            deferred_updates.push((key, value, time, diff));
+                        }
+                    }
+                    Event::Progress(progress) => {
+                        // Ignore progress updates before our resumption frontier, and until we
+                        // are past the as_of frontier. See the Kafka sink for why committing
+                        // before then would be incorrect.
+                        if !PartialOrder::less_equal(&resume_upper, &progress) {
+                            continue;
+                        }
+                        if !as_of.iter().all(|t| !progress.less_equal(t)) {
+                            continue;
+                        }
+                        // Batch updates into one snapshot per commit interval, except for the
+                        // final commit.
+                        if !progress.is_empty()
+                            && last_commit.elapsed() < connection.commit_interval
+                        {
+                            continue;
+                        }
+
+                        let mut ready = deferred_updates
+                            .drain_filter_swapping(|(_, _, time, _)| !progress.less_equal(time))
+                            .collect::<Vec<_>>();
+                        ready.sort_unstable_by(|a, b| a.2.cmp(&b.2));
+                        // Only the last value of each key matters.
+                        let mut latest = BTreeMap::new();
+                        for (key, value, _time, diff) in ready {
+                            if diff != 1 {
+                                warn!("{name}: ignoring upsert update with diff {diff}");
+                                continue;
+                            }
+                            latest.insert(key, value);
+                        }
+
+                        info!("{name}: committing snapshot for {}", progress.pretty());
+                        let (messages, bytes) = writer.commit(latest, &progress).await?;
+                        statistics.inc_messages_committed_by(messages);
+                        statistics.inc_bytes_committed_by(bytes);
+                        statistics.record_transaction_committed(&progress, now());
+                        last_commit = Instant::now();
+                        *write_frontier.borrow_mut() = progress.clone();
+                        if progress.is_empty() {
+                            break;
+                        }
+                    }
+                }
+            }
+            Ok(())
+        })
+    });
+
+    let statuses = errors.map(|error: Rc<anyhow::Error>| HealthStatusMessage {
+        index: 0,
+        update: HealthStatusUpdate::halting(format!("{}", error.display_with_causes()), None),
+        namespace: StatusNamespace::Iceberg,
+    });
+
+    (statuses, button.press_on_drop())
+}
+
+/// The state required to commit snapshots to a sink's table.
+struct IcebergTableWriter {
+    sink_id: GlobalId,
+    namespace: Vec<String>,
+    table: String,
+    catalog: IcebergCatalogClient,
+    s3: aws_sdk_s3::Client,
+    value_desc: RelationDesc,
+    key_desc: RelationDesc,
+    /// The field IDs of the key columns.
+    equality_ids: Vec<i32>,
+    /// The name mapping that lets readers resolve the columns of our Parquet
+    /// files, which do not embed field IDs.
+    name_mapping: String,
+    /// The table's metadata as of the last commit.
+    metadata: IcebergTableMetadata,
+    /// The manifests of the table's current snapshot.
+    manifests: Vec<Value>,
+    /// The frontier up to which updates have been committed.
+    upper: Antichain<Timestamp>,
+}
+
+impl IcebergTableWriter {
+    /// Loads the sink's table, creating it if it does not exist, and
+    /// determines the frontier to resume from.
+    async fn new(
+        sink_id: GlobalId,
+        connection: &IcebergSinkConnection,
+        storage_configuration: &StorageConfiguration,
+    ) -> Result<Self, anyhow::Error> {
+        let catalog = connection
+            .connection
+            .connect(storage_configuration)
+            .await
+            .context("connecting to Iceberg catalog")?;
+
+        let aws = &connection.connection.aws_connection;
+        let sdk_config = aws
+            .connection
+            .load_sdk_config(&storage_configuration.connection_context, aws.connection_id)
+            .await?;
+        let s3 = mz_aws_util::s3::new_client(&sdk_config);
+
+        let metadata = match catalog
+            .load_table(&connection.namespace, &connection.table)
+            .await?
+        {
+            Some(metadata) => metadata,
+            None => {
+                info!(
+                    "{sink_id}: creating Iceberg table {}.{}",
+                    connection.namespace.join("."),
+                    connection.table
+                );
+                let properties = BTreeMap::from([("format-version".into(), "2".into())]);
+                catalog
+                    .create_table(
+                        &connection.namespace,
+                        &connection.table,
+                        iceberg_schema(&connection.value_desc),
+                        properties,
+                    )
+                    .await?
+            }
+        };
+
+        // The catalog may have assigned different field IDs than we asked for,
+        // and a preexisting table certainly has its own, so we always look
+        // them up by name.
+        let schema = metadata.current_schema()?;
+        let mut field_ids = BTreeMap::new();
+        for (name, _typ) in connection.value_desc.iter() {
+            let field = schema
+                .fields
+                .iter()
+                .find(|f| f.name == name.as_str())
+                .ok_or_else(|| anyhow!("Iceberg table has no column named {}", name))?;
+            field_ids.insert(name.as_str().to_owned(), field.id);
+        }
+        let key_desc = connection.key_desc_and_indices.0.clone();
+        let equality_ids = key_desc
+            .iter_names()
+            .map(|name| field_ids[name.as_str()])
+            .collect();
+        let name_mapping = serde_json::to_string(
+            &field_ids
+                .iter()
+                .map(|(name, id)| json!({"field-id": id, "names": [name]}))
+                .collect::<Vec<_>>(),
+        )
+        .expect("valid JSON");
+
+        let mut writer = IcebergTableWriter {
+            sink_id,
+            namespace: connection.namespace.clone(),
+            table: connection.table.clone(),
+            catalog,
+            s3,
+            value_desc: connection.value_desc.clone(),
+            key_desc,
+            equality_ids,
+            name_mapping,
+            metadata,
+            manifests: vec![],
+            upper: Antichain::from_elem(Timestamp::minimum()),
+        };
+        writer.upper = writer
+            .committed_upper()?
+            .unwrap_or_else(|| Antichain::from_elem(Timestamp::minimum()));
+        writer.manifests = writer.load_manifests().await?;
+        Ok(writer)
+    }
+
+    /// Returns the frontier recorded by the most recent snapshot that this sink
+    /// committed, if any, searching backwards from the current snapshot.
+    fn committed_upper(&self) -> Result<Option<Antichain<Timestamp>>, anyhow::Error> {
+        let snapshots: BTreeMap<_, _> = self
+            .metadata
+            .snapshots
+            .iter()
+            .map(|s| (s.snapshot_id, s))
+            .collect();
+        let sink_id = self.sink_id.to_string();
+        let mut next = self.metadata.current_snapshot_id();
+        while let Some(snapshot) = next.and_then(|id| snapshots.get(&id)) {
+            if snapshot.summary.get(SINK_ID_KEY) == Some(&sink_id) {
+                let frontier = snapshot
+                    .summary
+                    .get(FRONTIER_KEY)
+                    .ok_or_else(|| anyhow!("snapshot {} has no frontier", snapshot.snapshot_id))?;
+                let frontier: Vec<u64> =
+                    serde_json::from_str(frontier).context("decoding committed frontier")?;
+                return Ok(Some(frontier.into_iter().map(Timestamp::from).collect()));
+            }
+            next = snapshot.parent_snapshot_id;
+        }
+        Ok(None)
+    }
+
+    /// Reads the manifests of the table's current snapshot.
+    async fn load_manifests(&self) -> Result<Vec<Value>, anyhow::Error> {
+        match self.metadata.current_snapshot() {
+            None => Ok(vec![]),
+            Some(snapshot) => {
+                let buf = self.get(&snapshot.manifest_list).await?;
+                manifest::decode_manifest_list(&buf)
+            }
+        }
+    }
+
+    /// Commits `updates` and advances the committed frontier to `upper`.
+    ///
+    /// Returns the number of keys and bytes written.
+    async fn commit(
+        &mut self,
+        updates: BTreeMap<Row, Option<Row>>,
+        upper: &Antichain<Timestamp>,
+    ) -> Result<(u64, u64), anyhow::Error> {
+        let mut files = vec![];
+        let mut bytes = 0;
+        if !updates.is_empty() {
+            let mut data = ParquetFileWriter::new(&self.value_desc)?;
+            let mut deletes = ParquetFileWriter::new(&self.key_desc)?;
+            let (mut data_count, mut delete_count) = (0, 0);
+            for (key, value) in &updates {
+                deletes.push_row(key)?;
+                delete_count += 1;
+                if let Some(value) = value {
+                    data.push_row(value)?;
+                    data_count += 1;
+                }
+            }
+            if data_count > 0 {
+                let file = self
+                    .put_data_file(Content::Data, data.finish()?, data_count)
+                    .await?;
+                bytes += file.file_size_in_bytes;
+                files.push(file);
+            }
+            let file = self
+                .put_data_file(Content::EqualityDeletes, deletes.finish()?, delete_count)
+                .await?;
+            bytes += file.file_size_in_bytes;
+            files.push(file);
+        }
+
+        let frontier: Vec<u64> = upper.iter().map(u64::from).collect();
+        let frontier = serde_json::to_string(&frontier).expect("valid JSON");
+
+        for attempt in 1.. {
+            let parent_snapshot_id = self.metadata.current_snapshot_id();
+            let snapshot_id = new_snapshot_id();
+            let sequence_number = self.metadata.last_sequence_number + 1;
+            let schema = self.metadata.current_schema()?;
+            let schema_json = schema_json(&self.metadata)?;
+
+            let mut manifests = self.manifests.clone();
+            for file in &files {
+                let buf =
+                    manifest::encode_manifest(file, snapshot_id, &schema_json, schema.schema_id);
+                let path = self.metadata_path(&format!("{}-m0.avro", uuid::Uuid::new_v4()));
+                let len = i64::try_from(buf.len()).expect("must fit");
+                self.put(&path, buf).await?;
+                manifests.push(manifest::manifest_file(
+                    file,
+                    path,
+                    len,
+                    snapshot_id,
+                    sequence_number,
+                ));
+            }
+            let manifest_list = self.metadata_path(&format!(
+                "snap-{snapshot_id}-{attempt}-{}.avro",
+                uuid::Uuid::new_v4()
+            ));
+            let buf = manifest::encode_manifest_list(
+                &manifests,
+                snapshot_id,
+                parent_snapshot_id,
+                sequence_number,
+            );
+            self.put(&manifest_list, buf).await?;
+
+            let operation = if files.is_empty() {
+                "append"
+            } else {
+                "overwrite"
+            };
+            let timestamp_ms = i64::try_from((mz_ore::now::SYSTEM_TIME)()).expect("must fit");
+            let mut table_updates = vec![
+                json!({
+                    "action": "add-snapshot",
+                    "snapshot": {
+                        "snapshot-id": snapshot_id,
+                        "parent-snapshot-id": parent_snapshot_id,
+                        "sequence-number": sequence_number,
+                        "timestamp-ms": timestamp_ms,
+                        "manifest-list": manifest_list,
+                        "schema-id": schema.schema_id,
+                        "summary": {
+                            "operation": operation,
+                            SINK_ID_KEY: self.sink_id.to_string(),
+                            FRONTIER_KEY: frontier,
+                        },
+                    },
+                }),
+                json!({
+                    "action": "set-snapshot-ref",
+                    "ref-name": "main",
+                    "type": "branch",
+                    "snapshot-id": snapshot_id,
+                }),
+            ];
+            if self.metadata.properties.get("schema.name-mapping.default")
+                != Some(&self.name_mapping)
+            {
+                table_updates.push(json!({
+                    "action": "set-properties",
+                    "updates": {"schema.name-mapping.default": self.name_mapping},
+                }));
+            }
+            let requirements = vec![
+                json!({"type": "assert-table-uuid", "uuid": self.metadata.table_uuid}),
+                json!({
+                    "type": "assert-ref-snapshot-id",
+                    "ref": "main",
+                    "snapshot-id": parent_snapshot_id,
+                }),
+            ];
+
+            match self
+                .catalog
+                .commit_table(&self.namespace, &self.table, requirements, table_updates)
+                .await
+            {
+                Ok(metadata) => {
+                    self.metadata = metadata;
+                    self.manifests = manifests;
+                    self.upper = upper.clone();
+                    let messages = u64::try_from(updates_len(&files)).expect("non-negative");
+                    return Ok((messages, u64::try_from(bytes).expect("non-negative")));
+                }
+                Err(IcebergCommitError::Conflict(msg)) if attempt < MAX_COMMIT_ATTEMPTS => {
+                    info!(
+                        "{}: retrying Iceberg commit after conflict: {msg}",
+                        self.sink_id
+                    );
+                    self.metadata = self
+                        .catalog
+                        .load_table(&self.namespace, &self.table)
+                        .await?
+                        .ok_or_else(|| anyhow!("Iceberg table was dropped"))?;
+                    let expected =
+                        (*self.upper != [Timestamp::minimum()]).then(|| self.upper.clone());
+                    if self.committed_upper()? != expected {
+                        bail!(
+                            "Iceberg table was concurrently written by another instance of \
+                             this sink"
+                        );
+                    }
+                    self.manifests = self.load_manifests().await?;
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+        unreachable!("loop exits via return")
+    }
+
+    async fn put_data_file(
+        &self,
+        content: Content,
+        buf: Vec<u8>,
+        record_count: i64,
+    ) -> Result<DataFile, anyhow::Error> {
+        let path = format!(
+            "{}/data/{}.parquet",
+            self.metadata.location.trim_end_matches('/'),
+            uuid::Uuid::new_v4()
+        );
+        let file_size_in_bytes = i64::try_from(buf.len()).expect("must fit");
+        self.put(&path, buf).await?;
+        let equality_ids = match content {
+            Content::Data => vec![],
+            Content::EqualityDeletes => self.equality_ids.clone(),
+        };
+        Ok(DataFile {
+            content,
+            path,
+            record_count,
+            file_size_in_bytes,
+            equality_ids,
+        })
+    }
+
+    fn metadata_path(&self, file: &str) -> String {
+        format!(
+            "{}/metadata/{file}",
+            self.metadata.location.trim_end_matches('/')
+        )
+    }
+
+    async fn put(&self, path: &str, buf: Vec<u8>) -> Result<(), anyhow::Error> {
+        let (bucket, key) = parse_s3_path(path)?;
+        self.s3
+            .put_object()
+            .bucket(bucket)
+            .key(key)
+            .body(ByteStream::from(buf))
+            .send()
+            .await
+            .with_context(|| format!("writing {path}"))?;
+        Ok(())
+    }
+
+    async fn get(&self, path: &str) -> Result<Vec<u8>, anyhow::Error> {
+        let (bucket, key) = parse_s3_path(path)?;
+        let object = self
+            .s3
+            .get_object()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+            .with_context(|| format!("reading {path}"))?;
+        let body = object
+            .body
+            .collect()
+            .await
+            .with_context(|| format!("reading {path}"))?;
+        Ok(body.into_bytes().to_vec())
+    }
+}
+
+/// Returns the number of rows in the data files among `files`.
+fn updates_len(files: &[DataFile]) -> i64 {
+    files
+        .iter()
+        .filter(|f| f.content == Content::Data)
+        .map(|f| f.record_count)
+        .sum()
+}
+
+/// Returns a random, positive snapshot ID.
+fn new_snapshot_id() -> i64 {
+    let (hi, _lo) = uuid::Uuid::new_v4().as_u64_pair();
+    i64::from_ne_bytes(hi.to_ne_bytes()) & i64::MAX
+}
+
+/// Splits an `s3://bucket/key` path into its bucket and key.
+fn parse_s3_path(path: &str) -> Result<(&str, &str), anyhow::Error> {
+    let rest = path
+        .strip_prefix("s3://")
+        .or_else(|| path.strip_prefix("s3a://"))
+        .ok_or_else(|| anyhow!("unsupported Iceberg table location {path}: expected S3"))?;
+    rest.split_once('/')
+        .ok_or_else(|| anyhow!("invalid S3 path {path}"))
+}
+
+/// Returns the Iceberg schema for a table containing the rows of `desc`.
+///
+/// Column types are mapped to the Iceberg type of the Parquet column that
+/// [`ParquetFileWriter`] writes for them.
+fn iceberg_schema(desc: &RelationDesc) -> serde_json::Value {
+    let fields: Vec<_> = desc
+        .iter()
+        .zip(1..)
+        .map(|((name, typ), id)| {
+            json!({
+                "id": id,
+                "name": name.as_str(),
+                "required": !typ.nullable,
+                "type": iceberg_type(typ),
+            })
+        })
+        .collect();
+    json!({"type": "struct", "schema-id": 0, "fields": fields})
+}
+
+fn iceberg_type(typ: &ColumnType) -> &'static str {
+    match typ.scalar_type {
+        ScalarType::Bool => "boolean",
+        ScalarType::Int16 | ScalarType::Int32 => "int",
+        ScalarType::Int64 => "long",
+        ScalarType::Float32 => "float",
+        ScalarType::Float64 => "double",
+        ScalarType::Date => "date",
+        ScalarType::Timestamp { .. } => "timestamp",
+        ScalarType::TimestampTz { .. } => "timestamptz",
+        ScalarType::Bytes => "binary",
+        _ => "string",
+    }
+}
+
+/// Returns the table's current schema in the JSON representation that is
+/// embedded in manifests.
+fn schema_json(metadata: &IcebergTableMetadata) -> Result<serde_json::Value, anyhow::Error> {
+    let schema = metadata.current_schema()?;
+    let fields: Vec<_> = schema
+        .fields
+        .iter()
+        .map(|f| {
+            json!({
+                "id": f.id,
+                "name": f.name,
+                "required": f.required,
+                "type": f.typ,
+            })
+        })
+        .collect();
+    Ok(json!({"type": "struct", "schema-id": schema.schema_id, "fields": fields}))
+}
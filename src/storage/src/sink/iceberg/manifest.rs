@@ -0,0 +1,342 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Encoding of Iceberg manifests and manifest lists.
+//!
+//! Iceberg stores the file-level metadata of a snapshot in Avro object
+//! container files. Readers resolve their fields by the `field-id` attributes
+//! of the embedded schema, which our Avro library does not preserve when
+//! serializing a parsed [`Schema`]. We therefore write the container files
+//! ourselves, embedding the schema text verbatim.
+//!
+//! Only the fields required by version 2 of the table spec are written.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use anyhow::Context;
+use mz_avro::schema::Schema;
+use mz_avro::types::Value;
+use mz_avro::{encode_unchecked, Reader};
+use once_cell::sync::Lazy;
+
+/// The schema of the entries of a manifest file.
+const MANIFEST_ENTRY_SCHEMA: &str = r#"{
+  "type": "record",
+  "name": "manifest_entry",
+  "fields": [
+    {"name": "status", "type": "int", "field-id": 0},
+    {"name": "snapshot_id", "type": ["null", "long"], "default": null, "field-id": 1},
+    {"name": "sequence_number", "type": ["null", "long"], "default": null, "field-id": 3},
+    {"name": "file_sequence_number", "type": ["null", "long"], "default": null, "field-id": 4},
+    {
+      "name": "data_file",
+      "field-id": 2,
+      "type": {
+        "type": "record",
+        "name": "r2",
+        "fields": [
+          {"name": "content", "type": "int", "field-id": 134},
+          {"name": "file_path", "type": "string", "field-id": 100},
+          {"name": "file_format", "type": "string", "field-id": 101},
+          {
+            "name": "partition",
+            "type": {"type": "record", "name": "r102", "fields": []},
+            "field-id": 102
+          },
+          {"name": "record_count", "type": "long", "field-id": 103},
+          {"name": "file_size_in_bytes", "type": "long", "field-id": 104},
+          {
+            "name": "equality_ids",
+            "type": ["null", {"type": "array", "items": "int", "element-id": 136}],
+            "default": null,
+            "field-id": 135
+          }
+        ]
+      }
+    }
+  ]
+}"#;
+
+/// The schema of the entries of a manifest list.
+const MANIFEST_FILE_SCHEMA: &str = r#"{
+  "type": "record",
+  "name": "manifest_file",
+  "fields": [
+    {"name": "manifest_path", "type": "string", "field-id": 500},
+    {"name": "manifest_length", "type": "long", "field-id": 501},
+    {"name": "partition_spec_id", "type": "int", "field-id": 502},
+    {"name": "content", "type": "int", "field-id": 517},
+    {"name": "sequence_number", "type": "long", "field-id": 515},
+    {"name": "min_sequence_number", "type": "long", "field-id": 516},
+    {"name": "added_snapshot_id", "type": "long", "field-id": 503},
+    {"name": "added_files_count", "type": "int", "field-id": 504},
+    {"name": "existing_files_count", "type": "int", "field-id": 505},
+    {"name": "deleted_files_count", "type": "int", "field-id": 506},
+    {"name": "added_rows_count", "type": "long", "field-id": 512},
+    {"name": "existing_rows_count", "type": "long", "field-id": 513},
+    {"name": "deleted_rows_count", "type": "long", "field-id": 514}
+  ]
+}"#;
+
+static MANIFEST_ENTRY: Lazy<Schema> =
+    Lazy::new(|| Schema::from_str(MANIFEST_ENTRY_SCHEMA).expect("valid schema"));
+static MANIFEST_FILE: Lazy<Schema> =
+    Lazy::new(|| Schema::from_str(MANIFEST_FILE_SCHEMA).expect("valid schema"));
+
+/// The kind of rows stored in a data file or tracked by a manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Content {
+    /// Rows to insert into the table.
+    Data,
+    /// Rows that delete all older rows with equal values in the equality
+    /// columns.
+    EqualityDeletes,
+}
+
+impl Content {
+    /// The value of the `content` field of a data file entry.
+    fn data_file_content(&self) -> i32 {
+        match self {
+            Content::Data => 0,
+            Content::EqualityDeletes => 2,
+        }
+    }
+
+    /// The value of the `content` field of a manifest list entry.
+    fn manifest_content(&self) -> i32 {
+        match self {
+            Content::Data => 0,
+            Content::EqualityDeletes => 1,
+        }
+    }
+
+    /// The value of the `content` key of a manifest's metadata.
+    fn name(&self) -> &'static str {
+        match self {
+            Content::Data => "data",
+            Content::EqualityDeletes => "deletes",
+        }
+    }
+}
+
+/// A Parquet file that was written to object storage.
+#[derive(Debug, Clone)]
+pub struct DataFile {
+    pub content: Content,
+    pub path: String,
+    pub record_count: i64,
+    pub file_size_in_bytes: i64,
+    /// The field IDs of the equality columns, if this is a delete file.
+    pub equality_ids: Vec<i32>,
+}
+
+/// Encodes a manifest that adds `file` to the table in `snapshot_id`.
+///
+/// The entry's sequence numbers are left unset, so that they are inherited
+/// from the manifest list entry once the snapshot is committed.
+pub fn encode_manifest(
+    file: &DataFile,
+    snapshot_id: i64,
+    table_schema: &serde_json::Value,
+    schema_id: i32,
+) -> Vec<u8> {
+    let equality_ids = match file.content {
+        Content::Data => None,
+        Content::EqualityDeletes => Some(Value::Array(
+            file.equality_ids.iter().copied().map(Value::Int).collect(),
+        )),
+    };
+    let data_file = Value::Record(vec![
+        (
+            "content".into(),
+            Value::Int(file.content.data_file_content()),
+        ),
+        ("file_path".into(), Value::String(file.path.clone())),
+        ("file_format".into(), Value::String("PARQUET".into())),
+        ("partition".into(), Value::Record(vec![])),
+        ("record_count".into(), Value::Long(file.record_count)),
+        (
+            "file_size_in_bytes".into(),
+            Value::Long(file.file_size_in_bytes),
+        ),
+        ("equality_ids".into(), nullable(equality_ids)),
+    ]);
+    let entry = Value::Record(vec![
+        // ADDED
+        ("status".into(), Value::Int(1)),
+        (
+            "snapshot_id".into(),
+            nullable(Some(Value::Long(snapshot_id))),
+        ),
+        ("sequence_number".into(), nullable(None)),
+        ("file_sequence_number".into(), nullable(None)),
+        ("data_file".into(), data_file),
+    ]);
+
+    let metadata = BTreeMap::from([
+        ("schema".into(), table_schema.to_string()),
+        ("schema-id".into(), schema_id.to_string()),
+        ("partition-spec".into(), "[]".into()),
+        ("partition-spec-id".into(), "0".into()),
+        ("format-version".into(), "2".into()),
+        ("content".into(), file.content.name().into()),
+    ]);
+    encode_container(MANIFEST_ENTRY_SCHEMA, &MANIFEST_ENTRY, metadata, &[entry])
+}
+
+/// Returns the manifest list entry for a manifest produced by
+/// [`encode_manifest`].
+pub fn manifest_file(
+    file: &DataFile,
+    manifest_path: String,
+    manifest_length: i64,
+    snapshot_id: i64,
+    sequence_number: i64,
+) -> Value {
+    Value::Record(vec![
+        ("manifest_path".into(), Value::String(manifest_path)),
+        ("manifest_length".into(), Value::Long(manifest_length)),
+        ("partition_spec_id".into(), Value::Int(0)),
+        (
+            "content".into(),
+            Value::Int(file.content.manifest_content()),
+        ),
+        ("sequence_number".into(), Value::Long(sequence_number)),
+        ("min_sequence_number".into(), Value::Long(sequence_number)),
+        ("added_snapshot_id".into(), Value::Long(snapshot_id)),
+        ("added_files_count".into(), Value::Int(1)),
+        ("existing_files_count".into(), Value::Int(0)),
+        ("deleted_files_count".into(), Value::Int(0)),
+        ("added_rows_count".into(), Value::Long(file.record_count)),
+        ("existing_rows_count".into(), Value::Long(0)),
+        ("deleted_rows_count".into(), Value::Long(0)),
+    ])
+}
+
+/// Encodes a manifest list for snapshot `snapshot_id`.
+pub fn encode_manifest_list(
+    manifests: &[Value],
+    snapshot_id: i64,
+    parent_snapshot_id: Option<i64>,
+    sequence_number: i64,
+) -> Vec<u8> {
+    let mut metadata = BTreeMap::from([
+        ("snapshot-id".into(), snapshot_id.to_string()),
+        ("sequence-number".into(), sequence_number.to_string()),
+        ("format-version".into(), "2".into()),
+    ]);
+    if let Some(parent) = parent_snapshot_id {
+        metadata.insert("parent-snapshot-id".into(), parent.to_string());
+    }
+    encode_container(MANIFEST_FILE_SCHEMA, &MANIFEST_FILE, metadata, manifests)
+}
+
+/// Decodes the entries of a manifest list written by any Iceberg writer.
+///
+/// Fields that are not part of [`MANIFEST_FILE_SCHEMA`], like partition
+/// summaries, are dropped, which is harmless for unpartitioned tables.
+pub fn decode_manifest_list(buf: &[u8]) -> Result<Vec<Value>, anyhow::Error> {
+    let reader = Reader::with_schema(&MANIFEST_FILE, buf).context("reading manifest list")?;
+    let mut manifests = vec![];
+    for value in reader {
+        manifests.push(value.context("decoding manifest list entry")?);
+    }
+    Ok(manifests)
+}
+
+fn nullable(value: Option<Value>) -> Value {
+    let (index, inner) = match value {
+        None => (0, Value::Null),
+        Some(value) => (1, value),
+    };
+    Value::Union {
+        index,
+        inner: Box::new(inner),
+        n_variants: 2,
+        null_variant: Some(0),
+    }
+}
+
+/// Encodes `records` as an uncompressed Avro object container file.
+fn encode_container(
+    schema_text: &str,
+    schema: &Schema,
+    metadata: BTreeMap<String, String>,
+    records: &[Value],
+) -> Vec<u8> {
+    let long = Schema::from_str(r#""long""#).expect("valid schema");
+    let map = Schema::from_str(r#"{"type": "map", "values": "bytes"}"#).expect("valid schema");
+
+    let mut header = metadata
+        .into_iter()
+        .map(|(k, v)| (k, Value::Bytes(v.into_bytes())))
+        .collect::<BTreeMap<_, _>>();
+    header.insert("avro.schema".into(), Value::Bytes(schema_text.into()));
+    header.insert("avro.codec".into(), Value::Bytes("null".into()));
+    let sync_marker = *uuid::Uuid::new_v4().as_bytes();
+
+    let mut buf = b"Obj\x01".to_vec();
+    encode_unchecked(&Value::Map(header), &map, &mut buf);
+    buf.extend_from_slice(&sync_marker);
+
+    let mut block = vec![];
+    for record in records {
+        encode_unchecked(record, schema, &mut block);
+    }
+    let count = i64::try_from(records.len()).expect("must fit");
+    let len = i64::try_from(block.len()).expect("must fit");
+    encode_unchecked(&Value::Long(count), &long, &mut buf);
+    encode_unchecked(&Value::Long(len), &long, &mut buf);
+    buf.extend_from_slice(&block);
+    buf.extend_from_slice(&sync_marker);
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function
+    fn test_manifest_list_roundtrip() {
+        let file = DataFile {
+            content: Content::EqualityDeletes,
+            path: "s3://bucket/table/data/a.parquet".into(),
+            record_count: 3,
+            file_size_in_bytes: 1024,
+            equality_ids: vec![1],
+        };
+        let manifests = vec![
+            manifest_file(&file, "s3://bucket/table/metadata/a.avro".into(), 512, 7, 2),
+            manifest_file(&file, "s3://bucket/table/metadata/b.avro".into(), 256, 8, 3),
+        ];
+        let buf = encode_manifest_list(&manifests, 8, Some(7), 3);
+        assert_eq!(decode_manifest_list(&buf).unwrap(), manifests);
+
+        let buf = encode_manifest_list(&[], 8, None, 3);
+        assert_eq!(decode_manifest_list(&buf).unwrap(), vec![]);
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function
+    fn test_manifest_is_readable() {
+        let file = DataFile {
+            content: Content::Data,
+            path: "s3://bucket/table/data/a.parquet".into(),
+            record_count: 3,
+            file_size_in_bytes: 1024,
+            equality_ids: vec![],
+        };
+        let buf = encode_manifest(&file, 7, &serde_json::json!({"type": "struct"}), 0);
+        let reader = Reader::new(&buf[..]).unwrap();
+        let entries: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+        assert_eq!(entries.len(), 1);
+    }
+}
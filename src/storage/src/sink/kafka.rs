@@ -78,15 +78,19 @@ use std::time::Duration;
 
 use anyhow::{anyhow, Context};
 use differential_dataflow::{AsCollection, Collection, Hashable};
+use mz_expr::MirScalarExpr;
 use mz_interchange::avro::{AvroEncoder, AvroSchemaGenerator, AvroSchemaOptions};
-use mz_interchange::encode::Encode;
+use mz_interchange::encode::{column_names_and_types, Encode};
+use mz_interchange::envelopes;
 use mz_interchange::json::JsonEncoder;
+use mz_interchange::protobuf::{MessageEncoder, ProtobufEncoder};
 use mz_kafka_util::client::{MzClientContext, TunnelingClientContext};
 use mz_ore::cast::CastFrom;
 use mz_ore::error::ErrorExt;
+use mz_ore::now::NowFn;
 use mz_ore::task;
 use mz_ore::vec::VecExt;
-use mz_repr::{Diff, GlobalId, Row, Timestamp};
+use mz_repr::{Datum, Diff, GlobalId, Row, RowArena, Timestamp};
 use mz_storage_client::sink::progress_key::ProgressKey;
 use mz_storage_client::sink::{ProgressRecord, TopicCleanupPolicy, TopicConfig};
 use mz_storage_types::configuration::StorageConfiguration;
@@ -104,6 +108,7 @@ use rdkafka::metadata::Metadata;
 use rdkafka::producer::{BaseProducer, BaseRecord, Producer};
 use rdkafka::types::RDKafkaErrorCode;
 use rdkafka::Statistics;
+use serde::{Deserialize, Serialize};
 use timely::dataflow::channels::pact::{Exchange, Pipeline};
 use timely::dataflow::operators::{CapabilitySet, Concatenate, Map, ToStream};
 use timely::dataflow::{Scope, Stream};
@@ -175,6 +180,7 @@ impl<G: Scope<Timestamp = Timestamp>> SinkRender<G> for KafkaSinkConnection {
             metrics,
             statistics,
             write_frontier,
+            storage_state.now.clone(),
         );
 
         let running_status = Some(HealthStatusMessage {
@@ -195,6 +201,9 @@ struct TransactionalProducer {
     task_name: String,
     /// The topic where all the updates go.
     data_topic: String,
+    /// The number of partitions of the data topic, if messages are assigned to partitions by the
+    /// `PARTITION BY` expression of the sink.
+    data_topic_partition_count: Option<u64>,
     /// The topic where all the upper frontiers go.
     progress_topic: String,
     /// The key each progress record is associated with.
@@ -283,6 +292,7 @@ impl TransactionalProducer {
         let producer = Self {
             task_name,
             data_topic: connection.topic.clone(),
+            data_topic_partition_count: None,
             progress_topic: connection
                 .progress_topic(&storage_configuration.connection_context)
                 .into_owned(),
@@ -335,23 +345,38 @@ impl TransactionalProducer {
     /// the system.
     async fn send(
         &mut self,
-        key: Option<&[u8]>,
-        value: Option<&[u8]>,
+        message: &KafkaMessage,
         time: Timestamp,
         diff: Diff,
     ) -> Result<(), ContextCreationError> {
         assert_eq!(diff, 1, "invalid sink update");
 
-        let headers = OwnedHeaders::new().insert(Header {
+        let mut headers = OwnedHeaders::new().insert(Header {
             key: "materialize-timestamp",
             value: Some(time.to_string().as_bytes()),
         });
+        for header in &message.headers {
+            headers = headers.insert(Header {
+                key: &header.key,
+                value: header.value.as_deref(),
+            });
+        }
+        let key = message.key.as_deref();
+        let value = message.value.as_deref();
+        // Messages without a partition hash are assigned a partition by librdkafka's default
+        // partitioner, based on their key.
+        let partition = match (message.partition_hash, self.data_topic_partition_count) {
+            (Some(hash), Some(count)) => {
+                Some(i32::try_from(hash % count).expect("partition counts fit in an i32"))
+            }
+            _ => None,
+        };
         let record = BaseRecord {
             topic: &self.data_topic,
             key,
             payload: value,
             headers: Some(headers),
-            partition: None,
+            partition,
             timestamp: None,
             delivery_opaque: (),
         };
@@ -386,6 +411,7 @@ impl TransactionalProducer {
     ) -> Result<(), ContextCreationError> {
         let progress = ProgressRecord {
             frontier: upper.into(),
+            reset: false,
         };
         let payload = serde_json::to_vec(&progress).expect("infallible");
         let record = BaseRecord::to(&self.progress_topic)
@@ -429,6 +455,7 @@ impl TransactionalProducer {
                     let timeout = self.socket_timeout;
                     self.spawn_blocking(move |p| p.abort_transaction(timeout))
                         .await?;
+                    self.statistics.inc_transactions_aborted();
                 }
                 Err(ContextCreationError::KafkaError(KafkaError::Transaction(
                     err,
@@ -488,7 +515,7 @@ async fn collect_statistics(mut receiver: watch::Receiver<Statistics>, metrics:
 /// Updates are sent in ascending timestamp order.
 fn sink_collection<G: Scope<Timestamp = Timestamp>>(
     name: String,
-    input: &Collection<G, (Option<Vec<u8>>, Option<Vec<u8>>), Diff>,
+    input: &Collection<G, KafkaMessage, Diff>,
     sink_id: GlobalId,
     connection: KafkaSinkConnection,
     storage_configuration: StorageConfiguration,
@@ -496,6 +523,7 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
     metrics: KafkaSinkMetrics,
     statistics: SinkStatistics,
     write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+    now: NowFn,
 ) -> (Stream<G, HealthStatusMessage>, PressOnDropButton) {
     let scope = input.scope();
     let mut builder = AsyncOperatorBuilder::new(name.clone(), input.inner.scope());
@@ -533,6 +561,9 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
                 &storage_configuration,
             )
             .await?;
+            producer
+                .statistics
+                .record_resume_upper(resume_upper.as_ref());
 
             let resume_upper = match resume_upper {
                 Some(upper) => {
@@ -553,14 +584,17 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
                         &connection,
                         &storage_configuration,
                         &connection.topic,
-                        // TODO: allow users to configure these parameters.
                         TopicConfig {
-                            partition_count: -1,
-                            replication_factor: -1,
+                            partition_count: connection.topic_options.partition_count.unwrap_or(-1),
+                            replication_factor: connection
+                                .topic_options
+                                .replication_factor
+                                .unwrap_or(-1),
                             cleanup_policy: TopicCleanupPolicy::Retention {
                                 ms: Some(-1),
                                 bytes: Some(-1),
                             },
+                            topic_config: connection.topic_options.topic_config.clone(),
                         },
                     )
                     .await?;
@@ -568,6 +602,19 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
                 }
             };
 
+            if connection.partition_by.is_some() {
+                let meta = producer.fetch_metadata().await?;
+                let partition_count = meta
+                    .topics()
+                    .iter()
+                    .find(|t| t.name() == connection.topic)
+                    .map_or(0, |t| t.partitions().len());
+                if partition_count == 0 {
+                    return Err(anyhow!("sink data topic has no partitions").into());
+                }
+                producer.data_topic_partition_count = Some(u64::cast_from(partition_count));
+            }
+
             // The input has overcompacted if
             let overcompacted =
                 // ..we have made some progress in the past
@@ -608,7 +655,7 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
             while let Some(event) = input.next().await {
                 match event {
                     Event::Data(_cap, batch) => {
-                        for ((key, value), time, diff) in batch {
+                        for (message, time, diff) in batch {
                             // We want to publish updates in time order and we know that we have
                             // already committed all times not beyond `upper`. Therefore, if this
                             // update happens *exactly* at upper then it is the minimum pending
@@ -620,15 +667,13 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
                             // can be *exactly* at upper but we can't know ahead of time which one
                             // will be advanced in the next progress message.
                             match upper.cmp(&time) {
-                                Ordering::Less => deferred_updates.push(((key, value), time, diff)),
+                                Ordering::Less => deferred_updates.push((message, time, diff)),
                                 Ordering::Equal => {
                                     if !transaction_begun {
                                         producer.begin_transaction().await?;
                                         transaction_begun = true;
                                     }
-                                    producer
-                                        .send(key.as_deref(), value.as_deref(), time, diff)
-                                        .await?;
+                                    producer.send(&message, time, diff).await?;
                                 }
                                 Ordering::Greater => continue,
                             }
@@ -669,14 +714,15 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
                                 .drain_filter_swapping(|(_, time, _)| !progress.less_equal(time)),
                         );
                         extra_updates.sort_unstable_by(|a, b| a.1.cmp(&b.1));
-                        for ((key, value), time, diff) in extra_updates.drain(..) {
-                            producer
-                                .send(key.as_deref(), value.as_deref(), time, diff)
-                                .await?;
+                        for (message, time, diff) in extra_updates.drain(..) {
+                            producer.send(&message, time, diff).await?;
                         }
 
                         info!("{name}: committing transaction for {}", progress.pretty());
                         producer.commit_transaction(progress.clone()).await?;
+                        producer
+                            .statistics
+                            .record_transaction_committed(&progress, now());
                         transaction_begun = false;
                         *write_frontier.borrow_mut() = progress.clone();
                         match progress.into_option() {
@@ -719,6 +765,26 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
     (statuses, button.press_on_drop())
 }
 
+/// A message to produce to the data topic of a Kafka sink.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct KafkaMessage {
+    key: Option<Vec<u8>>,
+    value: Option<Vec<u8>>,
+    /// The hash of the `PARTITION BY` expression of the sink for the message, if it has one and it
+    /// evaluated to a non-`NULL` value.
+    partition_hash: Option<u64>,
+    /// The user-defined headers of the message, which are produced in addition to the headers
+    /// that Materialize attaches to every message.
+    headers: Vec<KafkaHeader>,
+}
+
+/// A user-defined header of a [`KafkaMessage`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct KafkaHeader {
+    key: String,
+    value: Option<Vec<u8>>,
+}
+
 /// Encodes a stream of `(Option<Row>, Option<Row>)` updates using the specified encoder.
 ///
 /// Input [`Row`] updates must me compatible with the given implementor of [`Encode`].
@@ -729,7 +795,7 @@ fn encode_collection<G: Scope>(
     connection: KafkaSinkConnection,
     storage_configuration: StorageConfiguration,
 ) -> (
-    Collection<G, (Option<Vec<u8>>, Option<Vec<u8>>), Diff>,
+    Collection<G, KafkaMessage, Diff>,
     Stream<G, HealthStatusMessage>,
     PressOnDropButton,
 ) {
@@ -746,12 +812,15 @@ fn encode_collection<G: Scope>(
                 .as_ref()
                 .map(|(desc, _indices)| desc.clone());
             let value_desc = connection.value_desc;
+            let headers_index = connection.headers_index;
+            let partition_by = connection.partition_by;
 
             let encoder: Box<dyn Encode> = match connection.format {
                 KafkaSinkFormat::Avro {
                     key_schema,
                     value_schema,
                     csr_connection,
+                    value_schema_evolution,
                 } => {
                     // Ensure that schemas are registered with the schema registry.
                     //
@@ -766,6 +835,7 @@ fn encode_collection<G: Scope>(
                             Some(mz_ccsr::SchemaType::Avro),
                             &value_schema,
                             mz_ccsr::SchemaType::Avro,
+                            value_schema_evolution,
                         )
                         .await
                         .context("error publishing kafka schemas for sink")?;
@@ -788,11 +858,68 @@ fn encode_collection<G: Scope>(
                     value_desc,
                     matches!(envelope, SinkEnvelope::Debezium),
                 )),
+                KafkaSinkFormat::Protobuf {
+                    key,
+                    value,
+                    csr_connection,
+                } => {
+                    // Derived schemas are published to the schema registry, if
+                    // any, in which case messages carry the Confluent header.
+                    let (key_schema_id, value_schema_id) = match csr_connection {
+                        Some(csr_connection) => {
+                            let ccsr = csr_connection.connect(&storage_configuration).await?;
+                            let (key_schema_id, value_schema_id) =
+                                mz_storage_client::sink::publish_kafka_schemas(
+                                    &ccsr,
+                                    &connection.topic,
+                                    key.as_ref().and_then(|key| key.schema.as_deref()),
+                                    Some(mz_ccsr::SchemaType::Protobuf),
+                                    value
+                                        .schema
+                                        .as_deref()
+                                        .expect("published schemas are derived"),
+                                    mz_ccsr::SchemaType::Protobuf,
+                                    None,
+                                )
+                                .await
+                                .context("error publishing kafka schemas for sink")?;
+                            (key_schema_id, Some(value_schema_id))
+                        }
+                        None => (None, None),
+                    };
+
+                    let mut value_columns = column_names_and_types(value_desc);
+                    if matches!(envelope, SinkEnvelope::Debezium) {
+                        value_columns = envelopes::dbz_envelope(value_columns);
+                    }
+                    // Both messages are validated during planning.
+                    let key = match (key, key_desc) {
+                        (Some(key), Some(key_desc)) => Some(
+                            MessageEncoder::new(
+                                &key.descriptors,
+                                &key.message_name,
+                                column_names_and_types(key_desc),
+                                key_schema_id,
+                            )
+                            .context("invalid protobuf key message")?,
+                        ),
+                        _ => None,
+                    };
+                    let value = MessageEncoder::new(
+                        &value.descriptors,
+                        &value.message_name,
+                        value_columns,
+                        value_schema_id,
+                    )
+                    .context("invalid protobuf value message")?;
+                    Box::new(ProtobufEncoder::new(key, value))
+                }
             };
 
             // !IMPORTANT!
             // Correctness of this operator relies on no fallible operations happening after this
-            // point. This is a temporary workaround of build_fallible's bad interaction of owned
+            // point, except for encoding, which returns its capability to `capset` before failing.
+            // This is a temporary workaround of build_fallible's bad interaction of owned
             // capabilities and errors.
             // TODO(petrosagg): Make the fallible async operator safe
             *capset = CapabilitySet::new();
@@ -800,9 +927,43 @@ fn encode_collection<G: Scope>(
             while let Some(event) = input.next().await {
                 if let Event::Data(cap, rows) = event {
                     for ((key, value), time, diff) in rows {
-                        let key = key.map(|key| encoder.encode_key_unchecked(key));
-                        let value = value.map(|value| encoder.encode_value_unchecked(value));
-                        output.give(&cap, ((key, value), time, diff)).await;
+                        let sinked_row = value.as_ref().map(|value| sinked_row(value, envelope));
+                        let headers = match (&sinked_row, headers_index) {
+                            (Some(row), Some(index)) => decode_headers(row[index]),
+                            _ => vec![],
+                        };
+                        // With the upsert envelope, the expression is planned over the key, so
+                        // that deletions, which have no value, land on the partition of the key.
+                        let partition_hash = partition_by.as_ref().and_then(|expr| {
+                            let datums = match envelope {
+                                SinkEnvelope::Upsert => key.as_ref()?.iter().collect(),
+                                SinkEnvelope::Debezium => sinked_row.clone()?,
+                            };
+                            partition_hash(expr, &datums)
+                        });
+                        drop(sinked_row);
+                        let encoded = (|| {
+                            let key = key.map(|key| encoder.encode_key(key)).transpose()?;
+                            let value =
+                                value.map(|value| encoder.encode_value(value)).transpose()?;
+                            Ok::<_, anyhow::Error>((key, value))
+                        })();
+                        let (key, value) = match encoded {
+                            Ok(encoded) => encoded,
+                            Err(e) => {
+                                // Hold the frontier back at the update that could not be
+                                // encoded, so that the sink never commits progress past it.
+                                capset.insert(cap);
+                                return Err(e.context("encoding sink message"));
+                            }
+                        };
+                        let message = KafkaMessage {
+                            key,
+                            value,
+                            partition_hash,
+                            headers,
+                        };
+                        output.give(&cap, (message, time, diff)).await;
                     }
                 }
             }
@@ -818,3 +979,75 @@ fn encode_collection<G: Scope>(
 
     (stream.as_collection(), statuses, button.press_on_drop())
 }
+
+/// Returns the datums of the sinked row whose update `value` encodes: the row itself with the
+/// upsert envelope, and the newest of the `(before, after)` rows with the Debezium envelope.
+fn sinked_row(value: &Row, envelope: SinkEnvelope) -> Vec<Datum<'_>> {
+    match envelope {
+        SinkEnvelope::Upsert => value.iter().collect(),
+        SinkEnvelope::Debezium => {
+            let mut iter = value.iter();
+            let (before, after) = (iter.next(), iter.next());
+            [after, before]
+                .into_iter()
+                .flatten()
+                .find(|datum| !datum.is_null())
+                .map_or_else(Vec::new, |row| row.unwrap_list().iter().collect())
+        }
+    }
+}
+
+/// Hashes the result of evaluating the `PARTITION BY` expression `expr` over `datums`.
+///
+/// Returns `None` if the expression evaluates to `NULL` or errors, in which case the message falls
+/// back to the default partitioner.
+fn partition_hash(expr: &MirScalarExpr, datums: &[Datum]) -> Option<u64> {
+    let arena = RowArena::new();
+    match expr.eval(datums, &arena) {
+        Ok(Datum::String(s)) => Some(seahash::hash(s.as_bytes())),
+        Ok(_) | Err(_) => None,
+    }
+}
+
+/// Decodes the user-defined headers of a message from the `headers` column of the sinked row.
+///
+/// Headers whose key starts with `materialize-` are reserved for the headers that Materialize
+/// attaches to every message, and are dropped. A `NULL` header value produces a header without a
+/// value.
+fn decode_headers(headers: Datum) -> Vec<KafkaHeader> {
+    let header_value = |datum: Datum| match datum {
+        Datum::Null => None,
+        Datum::String(s) => Some(s.as_bytes().to_vec()),
+        Datum::Bytes(b) => Some(b.to_vec()),
+        datum => unreachable!("header values are text or bytea, got {datum:?}"),
+    };
+    let mut decoded = vec![];
+    match headers {
+        Datum::Null => {}
+        Datum::Map(map) => {
+            for (key, value) in map.iter() {
+                decoded.push(KafkaHeader {
+                    key: key.to_owned(),
+                    value: header_value(value),
+                });
+            }
+        }
+        Datum::List(list) => {
+            for record in list.iter().filter(|datum| !datum.is_null()) {
+                let mut fields = record.unwrap_list().iter();
+                let (key, value) = (fields.next(), fields.next());
+                let (Some(Datum::String(key)), Some(value)) = (key, value) else {
+                    // Records with a `NULL` key are not headers.
+                    continue;
+                };
+                decoded.push(KafkaHeader {
+                    key: key.to_owned(),
+                    value: header_value(value),
+                });
+            }
+        }
+        datum => unreachable!("headers are a map or a list, got {datum:?}"),
+    }
+    decoded.retain(|header| !header.key.starts_with("materialize-"));
+    decoded
+}
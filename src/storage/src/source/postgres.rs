@@ -306,6 +306,10 @@ pub enum DefiniteError {
     MissingToast,
     #[error("old row missing from replication stream. Did you forget to set REPLICA IDENTITY to FULL for your table?")]
     DefaultReplicaIdentity,
+    #[error(
+        "table's REPLICA IDENTITY was changed to {0}; Materialize requires REPLICA IDENTITY FULL"
+    )]
+    ReplicaIdentityNotFull(String),
     #[error("incompatible schema change: {0}")]
     // TODO: proper error variants for all the expected schema violations
     IncompatibleSchema(String),
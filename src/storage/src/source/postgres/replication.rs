@@ -96,7 +96,7 @@ use mz_timely_util::builder_async::{
 use mz_timely_util::operator::StreamExt as TimelyStreamExt;
 use once_cell::sync::Lazy;
 use postgres_protocol::message::backend::{
-    LogicalReplicationMessage, ReplicationMessage, TupleData,
+    LogicalReplicationMessage, ReplicaIdentity, ReplicationMessage, TupleData,
 };
 use serde::{Deserialize, Serialize};
 use timely::dataflow::channels::pact::Exchange;
@@ -666,6 +666,23 @@ fn extract_transaction<'a>(
                 Relation(body) => {
                     let rel_id = body.rel_id();
                     if let Some((_, expected_desc, casts)) = table_info.get(&rel_id) {
+                        // Without REPLICA IDENTITY FULL, updates omit unchanged TOASTed values
+                        // and updates and deletes omit the old row, so we can no longer
+                        // faithfully reproduce the table's contents.
+                        let replica_identity = match body.replica_identity() {
+                            ReplicaIdentity::Full => None,
+                            ReplicaIdentity::Default => Some("DEFAULT"),
+                            ReplicaIdentity::Nothing => Some("NOTHING"),
+                            ReplicaIdentity::Index => Some("USING INDEX"),
+                        };
+                        if let Some(replica_identity) = replica_identity {
+                            errored_tables.insert(rel_id);
+                            let err =
+                                DefiniteError::ReplicaIdentityNotFull(replica_identity.to_string());
+                            yield (rel_id, Err(err), 1);
+                            continue;
+                        }
+
                         // Because the replication stream doesn't include columns' attnums, we need
                         // to check the current local schema against the current remote schema to
                         // ensure e.g. we haven't received a schema update with the same terminal
@@ -273,6 +273,7 @@ where
     let source_id = config.id;
     let worker_id = config.worker_id;
     let source_statistics = config.source_statistics.clone();
+    let now = config.now.clone();
 
     let resume_uppers = resume_uppers.inspect(move |upper| {
         let upper = upper.pretty();
@@ -312,6 +313,9 @@ where
             let AsyncEvent::Data([cap_data, _cap_progress], mut data) = event else {
                 continue;
             };
+            if data.iter().any(|((_, message), _, _)| message.is_ok()) {
+                source_statistics.update_last_received_at(now());
+            }
             for ((output_index, message), _, _) in data.iter() {
                 let status = match message {
                     Ok(_) => HealthStatusUpdate::running(),
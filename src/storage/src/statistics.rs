@@ -19,6 +19,7 @@ use mz_ore::metrics::{
     CounterVecExt, DeleteOnDropCounter, DeleteOnDropGauge, GaugeVecExt, IntCounterVec, IntGaugeVec,
     MetricsRegistry, UIntGaugeVec,
 };
+use mz_ore::now::EpochMillis;
 use mz_repr::{GlobalId, Timestamp};
 use mz_storage_client::statistics::{
     Gauge, SinkStatisticsUpdate, SkippableGauge, SourceStatisticsUpdate,
@@ -39,6 +40,7 @@ pub(crate) struct SourceStatisticsMetricDefs {
     pub(crate) updates_staged: IntCounterVec,
     pub(crate) updates_committed: IntCounterVec,
     pub(crate) bytes_received: IntCounterVec,
+    pub(crate) messages_dead_lettered: IntCounterVec,
 
     // Gauges
     pub(crate) snapshot_committed: UIntGaugeVec,
@@ -80,6 +82,11 @@ impl SourceStatisticsMetricDefs {
                 help: "The number of bytes worth of messages the worker has received from upstream. The way the bytes are counted is source-specific.",
                 var_labels: ["source_id", "worker_id", "parent_source_id"],
             )),
+            messages_dead_lettered: registry.register(metric!(
+                name: "mz_source_messages_dead_lettered",
+                help: "The number of messages the worker has failed to decode and routed to the source's dead-letter queue.",
+                var_labels: ["source_id", "worker_id", "parent_source_id"],
+            )),
             bytes_indexed: registry.register(metric!(
                 name: "mz_source_bytes_indexed",
                 help: "The number of bytes of the source envelope state kept. This will be specific to the envelope in use.",
@@ -127,6 +134,7 @@ pub struct SourceStatisticsMetrics {
     pub(crate) updates_staged: DeleteOnDropCounter<'static, AtomicU64, Vec<String>>,
     pub(crate) updates_committed: DeleteOnDropCounter<'static, AtomicU64, Vec<String>>,
     pub(crate) bytes_received: DeleteOnDropCounter<'static, AtomicU64, Vec<String>>,
+    pub(crate) messages_dead_lettered: DeleteOnDropCounter<'static, AtomicU64, Vec<String>>,
 
     // Gauges
     pub(crate) snapshot_committed: DeleteOnDropGauge<'static, AtomicU64, Vec<String>>,
@@ -186,6 +194,11 @@ impl SourceStatisticsMetrics {
                 worker_id.to_string(),
                 parent_source_id.to_string(),
             ]),
+            messages_dead_lettered: defs.messages_dead_lettered.get_delete_on_drop_counter(vec![
+                id.to_string(),
+                worker_id.to_string(),
+                parent_source_id.to_string(),
+            ]),
             bytes_indexed: defs.bytes_indexed.get_delete_on_drop_gauge(vec![
                 id.to_string(),
                 worker_id.to_string(),
@@ -363,6 +376,7 @@ pub struct SourceStatisticsRecord {
     bytes_received: u64,
     updates_staged: u64,
     updates_committed: u64,
+    messages_dead_lettered: u64,
 
     // Gauges are always wrapped in an `Option` that represents if that gauge has been
     // initialized by that worker.
@@ -379,6 +393,10 @@ pub struct SourceStatisticsRecord {
     snapshot_committed: Option<bool>,
     offset_known: Option<Option<u64>>,
     offset_committed: Option<Option<u64>>,
+
+    // This gauge is `NULL` until the worker receives its first message, and
+    // is never reset.
+    last_received_at: Option<u64>,
 }
 
 impl SourceStatisticsRecord {
@@ -387,6 +405,7 @@ impl SourceStatisticsRecord {
         self.bytes_received = 0;
         self.updates_staged = 0;
         self.updates_committed = 0;
+        self.messages_dead_lettered = 0;
 
         // These gauges MUST be initialized across all workers before we aggregate and
         // report statistics.
@@ -410,6 +429,7 @@ impl SourceStatisticsRecord {
         self.bytes_received = 0;
         self.updates_staged = 0;
         self.updates_committed = 0;
+        self.messages_dead_lettered = 0;
     }
 
     /// Convert this record into an `SourceStatisticsUpdate` to be merged
@@ -422,6 +442,7 @@ impl SourceStatisticsRecord {
             bytes_received,
             updates_staged,
             updates_committed,
+            messages_dead_lettered,
             records_indexed,
             bytes_indexed,
             rehydration_latency_ms,
@@ -430,6 +451,7 @@ impl SourceStatisticsRecord {
             snapshot_committed,
             offset_known,
             offset_committed,
+            last_received_at,
         } = self.clone();
 
         SourceStatisticsUpdate {
@@ -446,6 +468,8 @@ impl SourceStatisticsRecord {
             snapshot_committed: Gauge::gauge(snapshot_committed.unwrap()),
             offset_known: SkippableGauge::gauge(offset_known.unwrap()),
             offset_committed: SkippableGauge::gauge(offset_committed.unwrap()),
+            messages_dead_lettered: messages_dead_lettered.into(),
+            last_received_at: Gauge::gauge(last_received_at),
         }
     }
 }
@@ -460,6 +484,14 @@ pub struct SinkStatisticsRecord {
     messages_committed: u64,
     bytes_staged: u64,
     bytes_committed: u64,
+    transactions_committed: u64,
+    transactions_aborted: u64,
+    restarts: u64,
+
+    // These gauges are `NULL` until the sink reports them, and are only
+    // reported by the worker that writes to the sink.
+    progress_frontier: Option<Timestamp>,
+    last_committed_at: Option<u64>,
 }
 
 impl SinkStatisticsRecord {
@@ -468,6 +500,11 @@ impl SinkStatisticsRecord {
         self.messages_committed = 0;
         self.bytes_staged = 0;
         self.bytes_committed = 0;
+        self.transactions_committed = 0;
+        self.transactions_aborted = 0;
+        self.restarts = 0;
+        self.progress_frontier = None;
+        self.last_committed_at = None;
     }
 
     /// Reset counters so that we continue to ship diffs to the controller.
@@ -476,6 +513,9 @@ impl SinkStatisticsRecord {
         self.messages_committed = 0;
         self.bytes_staged = 0;
         self.bytes_committed = 0;
+        self.transactions_committed = 0;
+        self.transactions_aborted = 0;
+        self.restarts = 0;
     }
 
     /// Convert this record into an `SinkStatisticsUpdate` to be merged
@@ -488,6 +528,11 @@ impl SinkStatisticsRecord {
             messages_committed,
             bytes_staged,
             bytes_committed,
+            transactions_committed,
+            transactions_aborted,
+            restarts,
+            progress_frontier,
+            last_committed_at,
         } = self.clone();
 
         SinkStatisticsUpdate {
@@ -496,6 +541,11 @@ impl SinkStatisticsRecord {
             messages_committed: messages_committed.into(),
             bytes_staged: bytes_staged.into(),
             bytes_committed: bytes_committed.into(),
+            transactions_committed: transactions_committed.into(),
+            transactions_aborted: transactions_aborted.into(),
+            restarts: restarts.into(),
+            progress_frontier: Gauge::gauge(progress_frontier.map(u64::from)),
+            last_committed_at: Gauge::gauge(last_committed_at),
         }
     }
 }
@@ -526,6 +576,7 @@ impl SourceStatistics {
                     updates_staged: 0,
                     updates_committed: 0,
                     bytes_received: 0,
+                    messages_dead_lettered: 0,
                     records_indexed: Some(0),
                     bytes_indexed: Some(0),
                     rehydration_latency_ms: None,
@@ -534,6 +585,7 @@ impl SourceStatistics {
                     snapshot_committed: None,
                     offset_known: Some(None),
                     offset_committed: Some(None),
+                    last_received_at: None,
                 },
                 prom: SourceStatisticsMetrics::new(
                     metrics,
@@ -606,6 +658,13 @@ impl SourceStatistics {
         cur.prom.messages_received.inc_by(value);
     }
 
+    /// Set the `last_received_at` stat to the given wall-clock time, unless
+    /// it is older than the current value.
+    pub fn update_last_received_at(&self, now: EpochMillis) {
+        let mut cur = self.stats.borrow_mut();
+        cur.stats.last_received_at = std::cmp::max(cur.stats.last_received_at, Some(now));
+    }
+
     /// Increment the `updates` stat.
     pub fn inc_updates_staged_by(&self, value: u64) {
         let mut cur = self.stats.borrow_mut();
@@ -627,6 +686,13 @@ impl SourceStatistics {
         cur.prom.bytes_received.inc_by(value);
     }
 
+    /// Increment the `messages_dead_lettered` stat.
+    pub fn inc_messages_dead_lettered_by(&self, value: u64) {
+        let mut cur = self.stats.borrow_mut();
+        cur.stats.messages_dead_lettered = cur.stats.messages_dead_lettered + value;
+        cur.prom.messages_dead_lettered.inc_by(value);
+    }
+
     /// Update the `bytes_indexed` stat.
     /// A positive value will add and a negative value will subtract.
     pub fn update_bytes_indexed_by(&self, value: i64) {
@@ -767,6 +833,11 @@ impl SinkStatistics {
                     messages_committed: 0,
                     bytes_staged: 0,
                     bytes_committed: 0,
+                    transactions_committed: 0,
+                    transactions_aborted: 0,
+                    restarts: 0,
+                    progress_frontier: None,
+                    last_committed_at: None,
                 },
                 prom: SinkStatisticsMetrics::new(metrics, id, worker_id),
             })),
@@ -823,6 +894,35 @@ impl SinkStatistics {
         cur.stats.bytes_committed = cur.stats.bytes_committed + value;
         cur.prom.bytes_committed.inc_by(value);
     }
+
+    /// Record a committed transaction whose upper is `progress_frontier`, at the given wall-clock
+    /// time.
+    pub fn record_transaction_committed(
+        &self,
+        progress_frontier: &Antichain<Timestamp>,
+        now: EpochMillis,
+    ) {
+        let mut cur = self.stats.borrow_mut();
+        cur.stats.transactions_committed += 1;
+        cur.stats.progress_frontier = progress_frontier.as_option().copied();
+        cur.stats.last_committed_at = std::cmp::max(cur.stats.last_committed_at, Some(now));
+    }
+
+    /// Increment the `transactions_aborted` stat.
+    pub fn inc_transactions_aborted(&self) {
+        let mut cur = self.stats.borrow_mut();
+        cur.stats.transactions_aborted += 1;
+    }
+
+    /// Record the frontier that the sink resumes from, as read from its progress topic on
+    /// startup. Counts as a restart if an earlier incarnation of the sink made progress.
+    pub fn record_resume_upper(&self, resume_upper: Option<&Antichain<Timestamp>>) {
+        let mut cur = self.stats.borrow_mut();
+        if let Some(resume_upper) = resume_upper {
+            cur.stats.restarts += 1;
+            cur.stats.progress_frontier = resume_upper.as_option().copied();
+        }
+    }
 }
 
 /// A structure that keeps track of _local_ statistics, as well as aggregating
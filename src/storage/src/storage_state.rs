@@ -222,6 +222,7 @@ impl<'w, A: Allocate> Worker<'w, A> {
                 timely_worker.peers(),
             ),
             object_status_updates: Default::default(),
+            restart_attempts: Default::default(),
             internal_cmd_tx: command_sequencer,
             async_worker,
             storage_configuration: StorageConfiguration::new(
@@ -303,6 +304,13 @@ pub struct StorageState {
     /// status updates if the status of the ingestion/export in question has _changed_.
     pub object_status_updates: Rc<RefCell<Vec<StatusUpdate>>>,
 
+    /// The number of consecutive times each source or sink has been suspended
+    /// and restarted without becoming healthy in between.
+    ///
+    /// Lives outside the dataflows themselves so that it survives the
+    /// restarts it counts, which lets health operators enforce retry policies.
+    pub restart_attempts: Rc<RefCell<BTreeMap<GlobalId, u64>>>,
+
     /// Sender for cluster-internal storage commands. These can be sent from
     /// within workers/operators and will be distributed to all workers. For
     /// example, for shutting down an entire dataflow from within a
@@ -860,6 +868,7 @@ impl<'w, A: Allocate> Worker<'w, A> {
 
                     self.storage_state.sink_tokens.remove(id);
 
+                    self.storage_state.restart_attempts.borrow_mut().remove(id);
                     self.storage_state.aggregated_statistics.deinitialize(*id);
                 }
             }
@@ -1230,6 +1239,10 @@ impl StorageState {
                     // reconciliation later.
                     self.ingestions.insert(id, description.clone());
 
+                    // Ingestions (re)started by the controller get a fresh
+                    // retry budget.
+                    self.restart_attempts.borrow_mut().remove(&id);
+
                     // Initialize shared frontier reporting.
                     for id in description.subsource_ids() {
                         self.reported_frontiers
@@ -1257,6 +1270,7 @@ impl StorageState {
                     // Remember the sink description to facilitate possible
                     // reconciliation later.
                     let prev = self.exports.insert(export.id, export.description.clone());
+                    self.restart_attempts.borrow_mut().remove(&export.id);
 
                     // New sink, add state.
                     if prev.is_none() {
@@ -70,6 +70,7 @@ pub async fn run_verify_topic(
 
     let await_value_schema = cmd.args.opt_bool("await-value-schema")?.unwrap_or(false);
     let await_key_schema = cmd.args.opt_bool("await-key-schema")?.unwrap_or(false);
+    let partition_count: Option<usize> = cmd.args.opt_parse("partition-count")?;
 
     cmd.args.done()?;
 
@@ -89,11 +90,22 @@ pub async fn run_verify_topic(
                 .inner()
                 .fetch_metadata(None, Duration::from_secs(1))?;
 
-            meta.topics()
+            let t = meta
+                .topics()
                 .iter()
                 .find(|t| t.name() == topic)
-                .ok_or(anyhow::anyhow!("topic not found"))
-                .map(|_| ())
+                .ok_or(anyhow::anyhow!("topic not found"))?;
+            if let Some(partition_count) = partition_count {
+                if t.partitions().len() != partition_count {
+                    bail!(
+                        "expected topic {} to have {} partitions, but it has {}",
+                        topic,
+                        partition_count,
+                        t.partitions().len()
+                    );
+                }
+            }
+            Ok(())
         })
         .await?;
 
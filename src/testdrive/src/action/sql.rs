@@ -64,6 +64,8 @@ pub async fn run_sql(mut cmd: SqlCommand, state: &State) -> Result<ControlFlow,
         | CreateTable(_)
         | CreateIndex(_)
         | CreateType(_)
+        | CreateFunction(_)
+        | CreateContinualTask(_)
         | CreateRole(_)
         | AlterObjectRename(_)
         | AlterIndex(_)
@@ -18,6 +18,7 @@ use mz_expr::visit::Visit;
 use mz_expr::{
     AggregateExpr, ColumnOrder, EvalError, MirRelationExpr, MirScalarExpr, TableFunc, UnaryFunc,
 };
+use mz_repr::adt::collation::Collation;
 use mz_repr::{ColumnType, Datum, Diff, RelationType, Row, RowArena};
 
 use crate::{any, TransformCtx, TransformError};
@@ -582,6 +583,7 @@ impl FoldConstants {
                     // groups doesn't matter.
                     desc: false,
                     nulls_last: false,
+                    collation: Collation::CodePoint,
                 })
                 .collect::<Vec<ColumnOrder>>();
             let mut lhs_datum_vec = mz_repr::DatumVec::new();
@@ -84,6 +84,13 @@ macro_rules! any {
     ($($x:expr,)+) => ( $($x)||+ )
 }
 
+/// The default number of [`Fixpoint`] iterations that a [`TransformCtx`] can
+/// run.
+///
+/// Fixpoints usually settle within a handful of iterations, so this is only
+/// reached by plans on which some transforms undo each other's work.
+pub const DEFAULT_FIXPOINT_FUEL: usize = 10_000;
+
 /// Arguments that get threaded through all transforms, plus a `DataflowMetainfo` that can be
 /// manipulated by the transforms.
 #[derive(Debug)]
@@ -100,6 +107,10 @@ pub struct TransformCtx<'a> {
     pub typecheck_ctx: &'a SharedContext,
     /// Transforms can use this field to communicate information outside the result plans.
     pub df_meta: &'a mut DataflowMetainfo,
+    /// The number of [`Fixpoint`] iterations that can still be run with this
+    /// context, across all fixpoints. A fixpoint that runs out of fuel stops
+    /// and reports that it did not converge.
+    pub fuel: usize,
 }
 
 impl<'a> TransformCtx<'a> {
@@ -121,6 +132,7 @@ impl<'a> TransformCtx<'a> {
             features,
             typecheck_ctx,
             df_meta,
+            fuel: DEFAULT_FIXPOINT_FUEL,
         }
     }
 
@@ -142,6 +154,7 @@ impl<'a> TransformCtx<'a> {
             features,
             df_meta,
             typecheck_ctx,
+            fuel: DEFAULT_FIXPOINT_FUEL,
         }
     }
 
@@ -272,38 +285,15 @@ pub struct Fixpoint {
 }
 
 impl Fixpoint {
-    /// Run a single iteration of the [`Fixpoint`] transform by iterating
-    /// through all transforms.
-    #[mz_ore::instrument(
-        target = "optimizer",
-        level = "debug",
-        fields(path.segment = iter_name)
-    )]
-    fn apply_transforms(
-        &self,
-        relation: &mut MirRelationExpr,
-        ctx: &mut TransformCtx,
-        iter_name: String,
-    ) -> Result<(), TransformError> {
-        for transform in self.transforms.iter() {
-            transform.transform(relation, ctx)?;
-        }
-        mz_repr::explain::trace_plan(relation);
-        Ok(())
-    }
-}
-
-impl Transform for Fixpoint {
-    #[mz_ore::instrument(
-        target = "optimizer",
-        level = "debug",
-        fields(path.segment = self.name)
-    )]
-    fn transform(
+    /// Runs the transforms until `relation` no longer changes, the fuel of
+    /// `ctx` is exhausted, or the relation stops getting simpler.
+    ///
+    /// Every iteration consumes one unit of fuel from `ctx`.
+    pub fn run(
         &self,
         relation: &mut MirRelationExpr,
         ctx: &mut TransformCtx,
-    ) -> Result<(), TransformError> {
+    ) -> Result<FixpointOutcome, TransformError> {
         // The number of iterations for a relation to settle depends on the
         // number of nodes in the relation. Instead of picking an arbitrary
         // hard limit on the number of iterations, we use a soft limit and
@@ -315,11 +305,18 @@ impl Transform for Fixpoint {
         loop {
             let start_size = relation.size();
             for i in iter_no..iter_no + self.limit {
+                if ctx.fuel == 0 {
+                    return Ok(FixpointOutcome::DidNotConverge {
+                        iterations: i,
+                        reason: NonConvergenceReason::OutOfFuel,
+                    });
+                }
+                ctx.fuel -= 1;
                 let original = relation.clone();
                 self.apply_transforms(relation, ctx, format!("{i:04}"))?;
                 if *relation == original {
                     mz_repr::explain::trace_plan(relation);
-                    return Ok(());
+                    return Ok(FixpointOutcome::Converged { iterations: i + 1 });
                 }
             }
             let final_size = relation.size();
@@ -339,19 +336,103 @@ impl Transform for Fixpoint {
                     self.limit
                 );
             } else {
-                return Err(TransformError::Internal(format!(
-                    "fixpoint {} ran for {} iterations \
-                     without reaching a fixpoint or reducing the relation size; \
-                     final_size ({}) >= start_size ({}); \
+                return Ok(FixpointOutcome::DidNotConverge {
+                    iterations: iter_no,
+                    reason: NonConvergenceReason::NoProgress,
+                });
+            }
+        }
+    }
+
+    /// Run a single iteration of the [`Fixpoint`] transform by iterating
+    /// through all transforms.
+    #[mz_ore::instrument(
+        target = "optimizer",
+        level = "debug",
+        fields(path.segment = iter_name)
+    )]
+    fn apply_transforms(
+        &self,
+        relation: &mut MirRelationExpr,
+        ctx: &mut TransformCtx,
+        iter_name: String,
+    ) -> Result<(), TransformError> {
+        for transform in self.transforms.iter() {
+            transform.transform(relation, ctx)?;
+        }
+        mz_repr::explain::trace_plan(relation);
+        Ok(())
+    }
+}
+
+impl Transform for Fixpoint {
+    #[mz_ore::instrument(
+        target = "optimizer",
+        level = "debug",
+        fields(path.segment = self.name)
+    )]
+    fn transform(
+        &self,
+        relation: &mut MirRelationExpr,
+        ctx: &mut TransformCtx,
+    ) -> Result<(), TransformError> {
+        match self.run(relation, ctx)? {
+            FixpointOutcome::Converged { .. } => {}
+            FixpointOutcome::DidNotConverge { iterations, reason } => {
+                tracing::warn!(
+                    "fixpoint {} did not converge after {} iterations ({}); \
                      transformed relation:\n{}",
                     self.name,
-                    iter_no,
-                    start_size,
-                    final_size,
+                    iterations,
+                    reason,
                     relation.pretty()
-                )));
+                );
+                // Every iteration preserves the semantics of the relation, so
+                // we can continue with the plan we have, even if it is not as
+                // simple as it could be.
+                ctx.df_meta
+                    .push_optimizer_notice_dedup(notice::OptimizationDidNotConverge {
+                        fixpoint: self.name,
+                        iterations,
+                    });
             }
         }
+        Ok(())
+    }
+}
+
+/// The outcome of running a [`Fixpoint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixpointOutcome {
+    /// The relation reached a fixpoint after the given number of iterations.
+    Converged {
+        /// The number of iterations that were run.
+        iterations: usize,
+    },
+    /// The relation did not reach a fixpoint.
+    DidNotConverge {
+        /// The number of iterations that were run.
+        iterations: usize,
+        /// Why the fixpoint gave up.
+        reason: NonConvergenceReason,
+    },
+}
+
+/// The reason a [`Fixpoint`] did not converge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NonConvergenceReason {
+    /// The [`TransformCtx`] ran out of fuel.
+    OutOfFuel,
+    /// The relation did not get smaller over the last `limit` iterations.
+    NoProgress,
+}
+
+impl fmt::Display for NonConvergenceReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NonConvergenceReason::OutOfFuel => f.write_str("out of fuel"),
+            NonConvergenceReason::NoProgress => f.write_str("no progress"),
+        }
     }
 }
 
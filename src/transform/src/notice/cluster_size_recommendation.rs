@@ -0,0 +1,129 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Hosts [`ClusterSizeRecommendation`].
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use mz_repr::explain::ExprHumanizer;
+use mz_repr::GlobalId;
+
+use crate::notice::{ActionKind, OptimizerNoticeApi};
+
+/// A cluster has been consistently far over- or under-provisioned for its size.
+///
+/// Like [`crate::notice::MixedClusterWorkload`], this notice is not emitted by an optimizer
+/// pipeline. It is raised by the coordinator, which periodically samples the CPU and memory
+/// utilization of the replicas of every cluster, and is retracted as soon as the recommendation
+/// no longer applies.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClusterSizeRecommendation {
+    /// The name of the cluster, quoted as an identifier.
+    pub cluster: String,
+    /// The current size of the cluster.
+    pub current_size: String,
+    /// The recommended size of the cluster.
+    pub recommended_size: String,
+    /// Whether the cluster is over-provisioned, as opposed to under-provisioned.
+    pub over_provisioned: bool,
+    /// The peak memory utilization of the cluster in percent, if known.
+    pub memory_utilization: Option<u64>,
+    /// The peak CPU utilization of the cluster in percent, if known.
+    pub cpu_utilization: Option<u64>,
+}
+
+impl ClusterSizeRecommendation {
+    /// Returns whether `self` and `other` recommend the same size for the same reason, ignoring
+    /// the utilization they observed.
+    pub fn same_recommendation(&self, other: &Self) -> bool {
+        self.cluster == other.cluster
+            && self.current_size == other.current_size
+            && self.recommended_size == other.recommended_size
+            && self.over_provisioned == other.over_provisioned
+    }
+}
+
+impl OptimizerNoticeApi for ClusterSizeRecommendation {
+    fn dependencies(&self) -> BTreeSet<GlobalId> {
+        BTreeSet::new()
+    }
+
+    fn fmt_message(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        _humanizer: &dyn ExprHumanizer,
+        _redacted: bool,
+    ) -> fmt::Result {
+        let provisioned = if self.over_provisioned {
+            "over-provisioned"
+        } else {
+            "under-provisioned"
+        };
+        write!(
+            f,
+            "Cluster {} is {provisioned} for its size '{}' (peak memory utilization: {}, \
+            peak CPU utilization: {}).",
+            self.cluster,
+            self.current_size,
+            fmt_utilization(self.memory_utilization),
+            fmt_utilization(self.cpu_utilization),
+        )
+    }
+
+    fn fmt_hint(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        _humanizer: &dyn ExprHumanizer,
+        _redacted: bool,
+    ) -> fmt::Result {
+        if self.over_provisioned {
+            write!(
+                f,
+                "The replicas of the cluster consistently used a small fraction of their \
+                resources. Consider resizing the cluster to size '{}', which consumes fewer \
+                credits.",
+                self.recommended_size
+            )
+        } else {
+            write!(
+                f,
+                "The replicas of the cluster consistently used nearly all of their resources, \
+                which slows down their dataflows and risks running out of memory. Consider \
+                resizing the cluster to size '{}'.",
+                self.recommended_size
+            )
+        }
+    }
+
+    fn fmt_action(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        _humanizer: &dyn ExprHumanizer,
+        _redacted: bool,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "ALTER CLUSTER {} SET (SIZE = '{}');",
+            self.cluster, self.recommended_size
+        )
+    }
+
+    fn action_kind(&self, _humanizer: &dyn ExprHumanizer) -> ActionKind {
+        ActionKind::SqlStatements
+    }
+}
+
+/// Formats a utilization in percent, or `unknown`.
+fn fmt_utilization(utilization: Option<u64>) -> String {
+    match utilization {
+        Some(utilization) => format!("{utilization}%"),
+        None => "unknown".into(),
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Hosts [`MixedClusterWorkload`].
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use mz_repr::explain::ExprHumanizer;
+use mz_repr::GlobalId;
+
+use crate::notice::{ActionKind, OptimizerNoticeApi};
+
+/// A cluster hosts both sources or sinks and indexes that serve queries.
+///
+/// Like [`crate::notice::UnusedIndex`], this notice is not emitted by an optimizer pipeline. It
+/// is raised by the coordinator, which periodically inspects the objects installed on every
+/// cluster, and is retracted as soon as the workloads of the cluster are split.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MixedClusterWorkload {
+    /// The name of the cluster, quoted as an identifier.
+    pub cluster: String,
+    /// The size of the replicas of the cluster.
+    pub cluster_size: String,
+    /// The name of the cluster that the indexes should move to, quoted as an identifier.
+    pub serving_cluster: String,
+    /// The ids of the sources and sinks installed on the cluster.
+    pub ingestion_ids: Vec<GlobalId>,
+    /// The ids of the indexes installed on the cluster.
+    pub index_ids: Vec<GlobalId>,
+}
+
+impl OptimizerNoticeApi for MixedClusterWorkload {
+    fn dependencies(&self) -> BTreeSet<GlobalId> {
+        self.ingestion_ids
+            .iter()
+            .chain(self.index_ids.iter())
+            .copied()
+            .collect()
+    }
+
+    fn fmt_message(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        _humanizer: &dyn ExprHumanizer,
+        _redacted: bool,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "Cluster {} hosts {} source(s) or sink(s) as well as {} index(es) that serve queries.",
+            self.cluster,
+            self.ingestion_ids.len(),
+            self.index_ids.len()
+        )
+    }
+
+    fn fmt_hint(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        _humanizer: &dyn ExprHumanizer,
+        _redacted: bool,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "Sources and sinks compete with indexes for the CPU and memory of their cluster, \
+            so queries served from the indexes slow down whenever ingestion is busy. \
+            Consider moving the indexes to a dedicated cluster."
+        )
+    }
+
+    fn fmt_action(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        humanizer: &dyn ExprHumanizer,
+        _redacted: bool,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "CREATE CLUSTER {} (SIZE = '{}');",
+            self.serving_cluster, self.cluster_size
+        )?;
+        for index_id in &self.index_ids {
+            let index_name = humanizer
+                .humanize_id(*index_id)
+                .unwrap_or_else(|| index_id.to_string());
+            write!(
+                f,
+                "\nALTER INDEX {index_name} SET CLUSTER {};",
+                self.serving_cluster
+            )?;
+        }
+        Ok(())
+    }
+
+    fn action_kind(&self, _humanizer: &dyn ExprHumanizer) -> ActionKind {
+        ActionKind::SqlStatements
+    }
+}
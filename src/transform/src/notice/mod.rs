@@ -25,16 +25,28 @@
 //! 3. Implement [`OptimizerNoticeApi`] for that struct.
 //! 4. Re-export the notice type in this module.
 //! 5. Add the notice type to the `raw_optimizer_notices` macro which generates
-//!    the [`RawOptimizerNotice`] enum and other boilerplate code.
+//!    the [`RawOptimizerNotice`] enum and other boilerplate code. Every notice
+//!    type needs a stable, unused numeric `id`, a stable `slug`, and the `name`
+//!    shown to users. Invalid or duplicate entries fail to compile.
 
 // Modules (one for each notice type).
+mod cluster_size_recommendation;
 mod index_already_exists;
 mod index_key_empty;
 mod index_too_wide_for_literal_constraints;
+mod mixed_cluster_workload;
+mod optimization_did_not_converge;
+mod plan_changed;
+mod unused_index;
 
+pub use cluster_size_recommendation::ClusterSizeRecommendation;
 pub use index_already_exists::IndexAlreadyExists;
 pub use index_key_empty::IndexKeyEmpty;
 pub use index_too_wide_for_literal_constraints::IndexTooWideForLiteralConstraints;
+pub use mixed_cluster_workload::MixedClusterWorkload;
+pub use optimization_did_not_converge::OptimizationDidNotConverge;
+pub use plan_changed::PlanChanged;
+pub use unused_index::UnusedIndex;
 
 use std::collections::BTreeSet;
 use std::fmt::{self, Error, Formatter, Write};
@@ -277,8 +289,40 @@ impl<'a, T: OptimizerNoticeApi> fmt::Display for HumanizedAction<'a, T> {
     }
 }
 
+/// Returns whether `a` and `b` are equal, in a `const` context.
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Returns whether `s` is a non-empty kebab-case slug, in a `const` context.
+const fn is_slug(s: &str) -> bool {
+    let s = s.as_bytes();
+    if s.is_empty() || s[0] == b'-' || s[s.len() - 1] == b'-' {
+        return false;
+    }
+    let mut i = 0;
+    while i < s.len() {
+        if !(s[i].is_ascii_lowercase() || s[i].is_ascii_digit() || s[i] == b'-') {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
 macro_rules! raw_optimizer_notices {
-    ($($ty:ident => $name:literal,)+) => {
+    ($($ty:ident => { id: $id:literal, slug: $slug:literal, name: $name:literal $(,)? },)+) => {
         paste::paste!{
             /// Notices that the optimizer wants to show to users.
             #[derive(EnumKind, Clone, Debug, Eq, PartialEq)]
@@ -338,8 +382,55 @@ macro_rules! raw_optimizer_notices {
                         $(Self::$ty => stringify!($ty),)+
                     }
                 }
+
+                /// Returns the stable numeric ID of this optimizer notice kind.
+                ///
+                /// IDs are never reused, even if a notice kind is removed.
+                pub fn id(&self) -> u64 {
+                    match self {
+                        $(Self::$ty => $id,)+
+                    }
+                }
+
+                /// Returns the stable slug of this optimizer notice kind, which
+                /// identifies it in URLs, e.g. links to the documentation.
+                pub fn slug(&self) -> &'static str {
+                    match self {
+                        $(Self::$ty => $slug,)+
+                    }
+                }
+
+                /// Returns all optimizer notice kinds.
+                pub fn all() -> &'static [OptimizerNoticeKind] {
+                    &[$(Self::$ty,)+]
+                }
             }
 
+            // Check the notice kinds at compile time, so that a notice cannot
+            // be added without the identifiers that other parts of the system
+            // rely on.
+            const _: () = {
+                let ids: &[u64] = &[$($id,)+];
+                let slugs: &[&str] = &[$($slug,)+];
+                let names: &[&str] = &[$($name,)+];
+                let labels: &[&str] = &[$(stringify!($ty),)+];
+                let mut i = 0;
+                while i < ids.len() {
+                    assert!(ids[i] > 0, "notice kind IDs must be positive");
+                    assert!(is_slug(slugs[i]), "notice kind slugs must be kebab-case");
+                    assert!(!names[i].is_empty(), "notice kind names must not be empty");
+                    assert!(!labels[i].is_empty(), "notice kind metric labels must not be empty");
+                    let mut j = i + 1;
+                    while j < ids.len() {
+                        assert!(ids[i] != ids[j], "notice kind IDs must be unique");
+                        assert!(!str_eq(slugs[i], slugs[j]), "notice kind slugs must be unique");
+                        assert!(!str_eq(names[i], names[j]), "notice kind names must be unique");
+                        j += 1;
+                    }
+                    i += 1;
+                }
+            };
+
             $(
                 impl From<$ty> for RawOptimizerNotice {
                     fn from(value: $ty) -> Self {
@@ -352,9 +443,46 @@ macro_rules! raw_optimizer_notices {
 }
 
 raw_optimizer_notices![
-    IndexAlreadyExists => "An identical index already exists",
-    IndexTooWideForLiteralConstraints => "Index too wide for literal constraints",
-    IndexKeyEmpty => "Empty index key",
+    IndexAlreadyExists => {
+        id: 1,
+        slug: "index-already-exists",
+        name: "An identical index already exists",
+    },
+    IndexTooWideForLiteralConstraints => {
+        id: 2,
+        slug: "index-too-wide-for-literal-constraints",
+        name: "Index too wide for literal constraints",
+    },
+    IndexKeyEmpty => {
+        id: 3,
+        slug: "index-key-empty",
+        name: "Empty index key",
+    },
+    UnusedIndex => {
+        id: 4,
+        slug: "unused-index",
+        name: "Unused index",
+    },
+    OptimizationDidNotConverge => {
+        id: 5,
+        slug: "optimization-did-not-converge",
+        name: "Optimization did not converge",
+    },
+    PlanChanged => {
+        id: 6,
+        slug: "plan-changed",
+        name: "Plan changed",
+    },
+    MixedClusterWorkload => {
+        id: 7,
+        slug: "mixed-cluster-workload",
+        name: "Mixed cluster workload",
+    },
+    ClusterSizeRecommendation => {
+        id: 8,
+        slug: "cluster-size-recommendation",
+        name: "Cluster size recommendation",
+    },
 ];
 
 impl RawOptimizerNotice {
@@ -0,0 +1,81 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Hosts [`OptimizationDidNotConverge`].
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use mz_repr::explain::ExprHumanizer;
+use mz_repr::GlobalId;
+
+use crate::notice::{ActionKind, OptimizerNoticeApi};
+
+/// A [`crate::Fixpoint`] transform stopped before the plan settled on a stable
+/// shape, either because it ran out of fuel or because it stopped making
+/// progress.
+///
+/// Every intermediate plan is equivalent to the original one, so the resulting
+/// plan is correct, but it may be less efficient than a fully optimized one.
+/// A plan that does not converge usually means that two transforms undo each
+/// other's work, which is a bug in the optimizer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OptimizationDidNotConverge {
+    /// The name of the fixpoint that did not converge.
+    pub fixpoint: &'static str,
+    /// The number of iterations the fixpoint ran for before it stopped.
+    pub iterations: usize,
+}
+
+impl OptimizerNoticeApi for OptimizationDidNotConverge {
+    fn dependencies(&self) -> BTreeSet<GlobalId> {
+        BTreeSet::new()
+    }
+
+    fn fmt_message(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        _humanizer: &dyn ExprHumanizer,
+        _redacted: bool,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "Optimization did not converge. \
+            The optimizer stopped the {} transform after {} iterations \
+            without reaching a stable plan.",
+            self.fixpoint, self.iterations
+        )
+    }
+
+    fn fmt_hint(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        _humanizer: &dyn ExprHumanizer,
+        _redacted: bool,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "The resulting plan is correct, but it might be less efficient than expected. \
+            Please report this to Materialize support."
+        )
+    }
+
+    fn fmt_action(
+        &self,
+        _f: &mut fmt::Formatter<'_>,
+        _humanizer: &dyn ExprHumanizer,
+        _redacted: bool,
+    ) -> fmt::Result {
+        Ok(())
+    }
+
+    fn action_kind(&self, _humanizer: &dyn ExprHumanizer) -> ActionKind {
+        ActionKind::None
+    }
+}
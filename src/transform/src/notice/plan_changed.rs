@@ -0,0 +1,91 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Hosts [`PlanChanged`].
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use mz_repr::explain::ExprHumanizer;
+use mz_repr::GlobalId;
+
+use crate::notice::{ActionKind, OptimizerNoticeApi};
+
+/// The optimized plan of an index or materialized view differs from the plan it had before
+/// Materialize restarted, usually because of an upgrade.
+///
+/// Like [`crate::notice::UnusedIndex`], this notice is not emitted by an optimizer pipeline. It
+/// is raised by the coordinator, which compares the plans it produces during bootstrap against
+/// the plans recorded in the durable catalog.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlanChanged {
+    /// The id of the index or materialized view whose plan changed.
+    pub item_id: GlobalId,
+    /// The version of Materialize that produced the previous plan.
+    pub previous_version: String,
+    /// The version of Materialize that produced the current plan.
+    pub current_version: String,
+}
+
+impl OptimizerNoticeApi for PlanChanged {
+    fn dependencies(&self) -> BTreeSet<GlobalId> {
+        BTreeSet::from([self.item_id])
+    }
+
+    fn fmt_message(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        humanizer: &dyn ExprHumanizer,
+        _redacted: bool,
+    ) -> fmt::Result {
+        let item_name = humanizer
+            .humanize_id(self.item_id)
+            .unwrap_or_else(|| self.item_id.to_string());
+
+        if self.previous_version == self.current_version {
+            write!(
+                f,
+                "The plan of {item_name} changed since the last restart of Materialize."
+            )
+        } else {
+            write!(
+                f,
+                "The plan of {item_name} changed between Materialize {} and {}.",
+                self.previous_version, self.current_version
+            )
+        }
+    }
+
+    fn fmt_hint(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        _humanizer: &dyn ExprHumanizer,
+        _redacted: bool,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "The new plan computes the same results, but it might need more memory or CPU. \
+            Compare the resource usage of its cluster with the usage before the change, \
+            and inspect the new plan with EXPLAIN."
+        )
+    }
+
+    fn fmt_action(
+        &self,
+        _f: &mut fmt::Formatter<'_>,
+        _humanizer: &dyn ExprHumanizer,
+        _redacted: bool,
+    ) -> fmt::Result {
+        Ok(())
+    }
+
+    fn action_kind(&self, _humanizer: &dyn ExprHumanizer) -> ActionKind {
+        ActionKind::None
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Hosts [`UnusedIndex`].
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use mz_repr::explain::ExprHumanizer;
+use mz_repr::GlobalId;
+
+use crate::notice::{ActionKind, OptimizerNoticeApi};
+
+/// An index that has not been used by any peek or dataflow for longer than the
+/// `unused_index_notice_threshold`.
+///
+/// Unlike the other notices, this one is not emitted by an optimizer pipeline. It is raised by
+/// the coordinator, which tracks how often each index is used, and is retracted as soon as the
+/// index is used again.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnusedIndex {
+    /// The id of the unused index.
+    pub index_id: GlobalId,
+}
+
+impl OptimizerNoticeApi for UnusedIndex {
+    fn dependencies(&self) -> BTreeSet<GlobalId> {
+        BTreeSet::from([self.index_id])
+    }
+
+    fn fmt_message(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        humanizer: &dyn ExprHumanizer,
+        _redacted: bool,
+    ) -> fmt::Result {
+        let index_name = humanizer
+            .humanize_id(self.index_id)
+            .unwrap_or_else(|| self.index_id.to_string());
+
+        write!(
+            f,
+            "Index {index_name} has not been used by any query or dataflow recently."
+        )
+    }
+
+    fn fmt_hint(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        _humanizer: &dyn ExprHumanizer,
+        _redacted: bool,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "An index consumes memory and CPU on its cluster even when nothing reads from it. \
+            Consider dropping it if it is no longer needed."
+        )
+    }
+
+    fn fmt_action(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        humanizer: &dyn ExprHumanizer,
+        _redacted: bool,
+    ) -> fmt::Result {
+        let index_name = humanizer
+            .humanize_id(self.index_id)
+            .unwrap_or_else(|| self.index_id.to_string());
+
+        write!(f, "DROP INDEX {index_name};")
+    }
+
+    fn action_kind(&self, _humanizer: &dyn ExprHumanizer) -> ActionKind {
+        ActionKind::SqlStatements
+    }
+}
@@ -290,3 +290,43 @@ impl mz_transform::Transform for Identity {
         Ok(())
     }
 }
+
+#[mz_ore::test]
+fn test_fixpoint_fuel() {
+    use mz_expr::MirRelationExpr;
+    use mz_repr::RelationType;
+    use mz_transform::notice::{OptimizationDidNotConverge, RawOptimizerNotice};
+    use mz_transform::{FixpointOutcome, NonConvergenceReason, Transform};
+
+    let features = mz_repr::optimize::OptimizerFeatures::default();
+    let typecheck_ctx = mz_transform::typecheck::empty_context();
+    let mut df_meta = DataflowMetainfo::default();
+    let mut relation = MirRelationExpr::constant(vec![], RelationType::empty());
+    let fixpoint = mz_transform::normalize();
+
+    // A relation that is already normalized converges after one iteration.
+    let mut ctx = mz_transform::TransformCtx::local(&features, &typecheck_ctx, &mut df_meta);
+    ctx.fuel = 10;
+    let outcome = fixpoint.run(&mut relation, &mut ctx).unwrap();
+    assert_eq!(outcome, FixpointOutcome::Converged { iterations: 1 });
+    assert_eq!(ctx.fuel, 9);
+
+    // A fixpoint that runs out of fuel stops and reports it as a notice.
+    ctx.fuel = 0;
+    let outcome = fixpoint.run(&mut relation, &mut ctx).unwrap();
+    assert_eq!(
+        outcome,
+        FixpointOutcome::DidNotConverge {
+            iterations: 0,
+            reason: NonConvergenceReason::OutOfFuel,
+        }
+    );
+    fixpoint.transform(&mut relation, &mut ctx).unwrap();
+    assert_eq!(
+        df_meta.optimizer_notices,
+        vec![RawOptimizerNotice::from(OptimizationDidNotConverge {
+            fixpoint: "normalize",
+            iterations: 0,
+        })]
+    );
+}
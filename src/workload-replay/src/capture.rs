@@ -0,0 +1,155 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Reading captured statements from the statement history.
+
+use std::time::Duration;
+
+use tokio_postgres::Client;
+
+/// The successful `SELECT` statements that statement logging captured in a time range, in the
+/// order in which they began.
+///
+/// Unlike `mz_internal.mz_statement_history`, the query includes the parameters of prepared
+/// statements.
+const CAPTURE_QUERY: &str = "
+SELECT
+    malt.execution_id::text, mst.sql, malt.params, malt.plan_fingerprint,
+    EXTRACT(EPOCH FROM malt.began_at)::float8,
+    EXTRACT(EPOCH FROM malt.finished_at - malt.began_at)::float8
+FROM mz_internal.mz_activity_log_thinned malt,
+     (SELECT DISTINCT prepared_day, sql_hash, sql FROM mz_internal.mz_sql_text) mst
+WHERE malt.sql_hash = mst.sql_hash
+AND date_trunc('day', malt.prepared_at) = mst.prepared_day
+AND malt.statement_type = 'select'
+AND malt.finished_status = 'success'
+AND malt.began_at >= $1::text::timestamptz
+AND malt.began_at < coalesce($2::text::timestamptz, now())
+AND ($3::text IS NULL OR malt.cluster_name = $3::text)
+ORDER BY malt.began_at, malt.execution_id
+LIMIT coalesce($4::int8, 9223372036854775807)";
+
+/// A statement captured by statement logging.
+#[derive(Debug, Clone)]
+pub struct CapturedStatement {
+    /// The ID of the original execution.
+    pub execution_id: String,
+    /// The SQL text of the statement, which may reference parameters.
+    pub sql: String,
+    /// The values of the parameters of the statement, in their text representation.
+    pub params: Vec<Option<String>>,
+    /// The fingerprint of the plan that the statement originally ran with.
+    pub plan_fingerprint: Option<String>,
+    /// When the statement began, in seconds since the Unix epoch.
+    pub began_at: f64,
+    /// How long the statement originally took.
+    pub duration: Duration,
+}
+
+/// Filters for the statements to capture.
+#[derive(Debug, Clone)]
+pub struct CaptureFilter {
+    /// Only capture statements that began at or after this time.
+    pub since: String,
+    /// Only capture statements that began before this time, or before now if `None`.
+    pub until: Option<String>,
+    /// Only capture statements that ran on this cluster.
+    pub cluster: Option<String>,
+    /// The maximum number of statements to capture.
+    pub limit: Option<i64>,
+}
+
+/// Reads the statements that match `filter` from the statement history of the environment that
+/// `client` is connected to.
+///
+/// Statement logging samples statements, so the captured workload is a sample of the original
+/// workload whenever `statement_logging_sample_rate` is below 1.
+pub async fn capture(
+    client: &Client,
+    filter: &CaptureFilter,
+) -> Result<Vec<CapturedStatement>, anyhow::Error> {
+    let rows = client
+        .query(
+            CAPTURE_QUERY,
+            &[&filter.since, &filter.until, &filter.cluster, &filter.limit],
+        )
+        .await?;
+    let mut statements = Vec::with_capacity(rows.len());
+    for row in rows {
+        let duration: Option<f64> = row.get(5);
+        statements.push(CapturedStatement {
+            execution_id: row.get(0),
+            sql: row.get(1),
+            params: row.get(2),
+            plan_fingerprint: row.get(3),
+            began_at: row.get(4),
+            duration: Duration::from_secs_f64(duration.unwrap_or(0.0).max(0.0)),
+        });
+    }
+    Ok(statements)
+}
+
+/// Returns the offset from the start of the replay at which each of `statements` is replayed.
+///
+/// The gaps between statements are divided by `speedup`. A `speedup` of zero replays all
+/// statements right away.
+pub fn schedule(statements: &[CapturedStatement], speedup: f64) -> Vec<Duration> {
+    let Some(first) = statements.first() else {
+        return Vec::new();
+    };
+    statements
+        .iter()
+        .map(|statement| {
+            if speedup == 0.0 {
+                return Duration::ZERO;
+            }
+            let offset = (statement.began_at - first.began_at).max(0.0) / speedup;
+            Duration::from_secs_f64(offset)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statement(began_at: f64) -> CapturedStatement {
+        CapturedStatement {
+            execution_id: began_at.to_string(),
+            sql: "SELECT 1".into(),
+            params: Vec::new(),
+            plan_fingerprint: None,
+            began_at,
+            duration: Duration::ZERO,
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_schedule() {
+        let statements = [statement(100.0), statement(101.0), statement(104.0)];
+        assert_eq!(
+            schedule(&statements, 1.0),
+            [
+                Duration::ZERO,
+                Duration::from_secs(1),
+                Duration::from_secs(4)
+            ],
+        );
+        assert_eq!(
+            schedule(&statements, 2.0),
+            [
+                Duration::ZERO,
+                Duration::from_millis(500),
+                Duration::from_secs(2)
+            ],
+        );
+        assert_eq!(schedule(&statements, 0.0), [Duration::ZERO; 3]);
+        assert_eq!(schedule(&[], 1.0), []);
+    }
+}
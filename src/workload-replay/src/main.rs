@@ -0,0 +1,241 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Replays captured statement history against a Materialize environment.
+//!
+//! The tool reads the successful `SELECT` statements that statement logging captured in a time
+//! range, re-executes them against a cluster with the pacing of the original workload, optionally
+//! accelerated, and reports how the latencies of the statements changed, grouped by the
+//! fingerprints of their original plans. This makes it possible to check how a real workload
+//! behaves on a new cluster size or after an upgrade before moving it over.
+//!
+//! Only read-only statements are replayed, so the replay does not change the environment it runs
+//! against. Replayed statements are not logged themselves.
+
+use std::io;
+use std::process;
+use std::time::{Duration, Instant};
+
+use anyhow::bail;
+use clap::Parser;
+use mz_ore::cli::{self, CliConfig};
+use mz_ore::error::ErrorExt;
+use mz_ore::task;
+use tokio::sync::mpsc;
+use tokio_postgres::{Client, Config};
+use tracing::{info, warn};
+use tracing_subscriber::filter::EnvFilter;
+
+use crate::capture::{CaptureFilter, CapturedStatement};
+use crate::report::{Outcome, Report};
+
+mod capture;
+mod report;
+
+/// Replays captured statement history against a Materialize environment.
+#[derive(Parser, Debug)]
+#[clap(name = "mz-workload-replay", next_line_help = true)]
+struct Args {
+    /// The URL of the environment whose statement history to replay.
+    ///
+    /// The user must be allowed to read the statement history, e.g. by having the `mz_monitor`
+    /// role.
+    #[clap(long, env = "SOURCE_URL", value_name = "POSTGRES_URL")]
+    source_url: Config,
+    /// The URL of the environment to replay the statements against. Defaults to the source
+    /// environment.
+    #[clap(long, env = "TARGET_URL", value_name = "POSTGRES_URL")]
+    target_url: Option<Config>,
+    /// The cluster to replay the statements on. Defaults to the default cluster of the target
+    /// user.
+    #[clap(long, value_name = "CLUSTER")]
+    cluster: Option<String>,
+    /// Only replay the statements that originally ran on this cluster.
+    #[clap(long, value_name = "CLUSTER")]
+    source_cluster: Option<String>,
+    /// Only replay the statements that began at or after this time.
+    #[clap(long, value_name = "TIMESTAMP")]
+    since: String,
+    /// Only replay the statements that began before this time. Defaults to now.
+    #[clap(long, value_name = "TIMESTAMP")]
+    until: Option<String>,
+    /// The maximum number of statements to replay.
+    #[clap(long, value_name = "N")]
+    limit: Option<i64>,
+    /// How many times faster than the original workload to replay the statements.
+    ///
+    /// `1` preserves the gaps between the statements of the original workload, `2` halves them,
+    /// and `0` replays the statements back to back.
+    #[clap(long, value_name = "FACTOR", default_value = "1")]
+    speedup: f64,
+    /// The number of connections to replay the statements on, which bounds the number of
+    /// statements that run concurrently.
+    #[clap(long, value_name = "N", default_value = "8")]
+    concurrency: usize,
+    /// Which log messages to emit.
+    ///
+    /// See environmentd's `--log-filter` option for details.
+    #[clap(long, value_name = "FILTER", default_value = "info")]
+    log_filter: EnvFilter,
+}
+
+#[tokio::main]
+async fn main() {
+    let args: Args = cli::parse_args(CliConfig {
+        env_prefix: Some("MZ_WORKLOAD_REPLAY_"),
+        enable_version_flag: false,
+    });
+    if let Err(e) = run(args).await {
+        eprintln!("mz-workload-replay: fatal: {}", e.display_with_causes());
+        process::exit(1);
+    }
+}
+
+async fn run(args: Args) -> Result<(), anyhow::Error> {
+    tracing_subscriber::fmt()
+        .with_env_filter(args.log_filter)
+        .with_writer(io::stderr)
+        .init();
+
+    if !args.speedup.is_finite() || args.speedup < 0.0 {
+        bail!(
+            "--speedup must be a non-negative number, got {}",
+            args.speedup
+        );
+    }
+    if args.concurrency == 0 {
+        bail!("--concurrency must be at least 1");
+    }
+
+    let source = connect(&args.source_url, None).await?;
+    let filter = CaptureFilter {
+        since: args.since,
+        until: args.until,
+        cluster: args.source_cluster,
+        limit: args.limit,
+    };
+    let statements = capture::capture(&source, &filter).await?;
+    drop(source);
+    if statements.is_empty() {
+        bail!("no captured statements match the filters");
+    }
+    info!("captured {} statements", statements.len());
+
+    let target_url = args.target_url.as_ref().unwrap_or(&args.source_url);
+    let (pool_tx, mut pool_rx) = mpsc::unbounded_channel();
+    for _ in 0..args.concurrency {
+        let client = connect(target_url, args.cluster.as_deref()).await?;
+        pool_tx.send(client).expect("receiver is alive");
+    }
+
+    let schedule = capture::schedule(&statements, args.speedup);
+    let start = tokio::time::Instant::now();
+    let mut handles = Vec::with_capacity(statements.len());
+    for (statement, offset) in statements.into_iter().zip(schedule) {
+        tokio::time::sleep_until(start + offset).await;
+        let client = pool_rx.recv().await.expect("sender is alive");
+        let pool_tx = pool_tx.clone();
+        handles.push(task::spawn(|| "workload_replay_statement", async move {
+            let replayed = replay(&client, &statement).await.map_err(|e| {
+                format!(
+                    "{} (original execution {})",
+                    e.display_with_causes(),
+                    statement.execution_id
+                )
+            });
+            // If sending fails, the replay is over.
+            let _ = pool_tx.send(client);
+            Outcome {
+                plan_fingerprint: statement.plan_fingerprint,
+                sql: statement.sql,
+                original: statement.duration,
+                replayed,
+            }
+        }));
+    }
+
+    let mut report = Report::default();
+    for handle in handles {
+        report.add(handle.await?);
+    }
+    for (fingerprint, err) in report.errors() {
+        warn!("statement with plan fingerprint {fingerprint} failed: {err}");
+    }
+    print!("{report}");
+    Ok(())
+}
+
+/// Connects to the environment at `config` and prepares the session for the replay.
+async fn connect(config: &Config, cluster: Option<&str>) -> Result<Client, anyhow::Error> {
+    let tls = mz_tls_util::make_tls(config)?;
+    let (client, connection) = config.connect(tls).await?;
+    task::spawn(|| "workload_replay_connection", async move {
+        if let Err(e) = connection.await {
+            warn!("connection error: {}", e.display_with_causes());
+        }
+    });
+    // Don't log the replayed statements, which would pollute the statement history that later
+    // replays capture.
+    client
+        .batch_execute(
+            "SET application_name = 'mz_workload_replay';
+            SET statement_logging_sample_rate = 0;",
+        )
+        .await?;
+    if let Some(cluster) = cluster {
+        client
+            .batch_execute(&format!("SET cluster = {}", quote_ident(cluster)))
+            .await?;
+    }
+    Ok(client)
+}
+
+/// Executes `statement` on `client` and returns how long it took.
+///
+/// Statements with parameters are prepared first and then executed with their captured
+/// parameter values. Only the execution counts towards the latency.
+async fn replay(
+    client: &Client,
+    statement: &CapturedStatement,
+) -> Result<Duration, tokio_postgres::Error> {
+    if statement.params.is_empty() {
+        let start = Instant::now();
+        client.simple_query(&statement.sql).await?;
+        return Ok(start.elapsed());
+    }
+
+    let name = "mz_workload_replay";
+    client
+        .batch_execute(&format!("PREPARE {name} AS {}", statement.sql))
+        .await?;
+    let params: Vec<_> = statement
+        .params
+        .iter()
+        .map(|param| match param {
+            Some(param) => quote_literal(param),
+            None => "NULL".into(),
+        })
+        .collect();
+    let start = Instant::now();
+    let result = client
+        .simple_query(&format!("EXECUTE {name} ({})", params.join(", ")))
+        .await;
+    let elapsed = start.elapsed();
+    client.batch_execute(&format!("DEALLOCATE {name}")).await?;
+    result?;
+    Ok(elapsed)
+}
+
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+fn quote_literal(literal: &str) -> String {
+    format!("'{}'", literal.replace('\'', "''"))
+}
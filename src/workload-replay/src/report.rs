@@ -0,0 +1,270 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Aggregation of replay latencies by plan fingerprint.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+/// The key of statements that ran without a plan fingerprint, like those that Materialize
+/// answered without building a dataflow.
+const NO_FINGERPRINT: &str = "<none>";
+
+/// The outcome of replaying a single statement.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    /// The fingerprint of the plan that the statement originally ran with.
+    pub plan_fingerprint: Option<String>,
+    /// The SQL text of the statement.
+    pub sql: String,
+    /// How long the statement originally took.
+    pub original: Duration,
+    /// How long the replayed statement took, or the error it failed with.
+    pub replayed: Result<Duration, String>,
+}
+
+/// The latencies of the replayed statements that share a plan fingerprint.
+#[derive(Debug, Default)]
+struct FingerprintStats {
+    /// The SQL text of one of the statements.
+    example_sql: String,
+    /// The original latencies of the statements that replayed successfully.
+    original: Vec<Duration>,
+    /// The latencies of the statements that replayed successfully.
+    replayed: Vec<Duration>,
+    /// The errors that statements failed with during the replay.
+    errors: Vec<String>,
+}
+
+/// The latency deltas of a replay, by plan fingerprint.
+#[derive(Debug, Default)]
+pub struct Report {
+    by_fingerprint: BTreeMap<String, FingerprintStats>,
+}
+
+impl Report {
+    /// Adds the outcome of a replayed statement to the report.
+    pub fn add(&mut self, outcome: Outcome) {
+        let key = outcome
+            .plan_fingerprint
+            .unwrap_or_else(|| NO_FINGERPRINT.into());
+        let stats = self.by_fingerprint.entry(key).or_default();
+        if stats.example_sql.is_empty() {
+            stats.example_sql = outcome.sql;
+        }
+        match outcome.replayed {
+            Ok(replayed) => {
+                stats.original.push(outcome.original);
+                stats.replayed.push(replayed);
+            }
+            Err(err) => stats.errors.push(err),
+        }
+    }
+
+    /// Returns the summary of each plan fingerprint, ordered by descending regression of the
+    /// median latency.
+    pub fn summaries(&self) -> Vec<FingerprintSummary> {
+        let mut summaries: Vec<_> = self
+            .by_fingerprint
+            .iter()
+            .map(|(fingerprint, stats)| FingerprintSummary {
+                plan_fingerprint: fingerprint.clone(),
+                example_sql: stats.example_sql.clone(),
+                replayed: stats.replayed.len(),
+                errors: stats.errors.len(),
+                original_p50: percentile(&stats.original, 50),
+                replayed_p50: percentile(&stats.replayed, 50),
+                original_p99: percentile(&stats.original, 99),
+                replayed_p99: percentile(&stats.replayed, 99),
+            })
+            .collect();
+        summaries.sort_by(|a, b| {
+            let delta = |s: &FingerprintSummary| s.p50_delta().unwrap_or(f64::NEG_INFINITY);
+            delta(b).total_cmp(&delta(a))
+        });
+        summaries
+    }
+
+    /// Returns the errors that statements failed with during the replay, with the fingerprints
+    /// of the statements.
+    pub fn errors(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.by_fingerprint.iter().flat_map(|(fingerprint, stats)| {
+            stats
+                .errors
+                .iter()
+                .map(move |err| (fingerprint.as_str(), err.as_str()))
+        })
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<20} {:>8} {:>8} {:>13} {:>13} {:>9} {:>13} {:>13} {:>9}  example",
+            "plan_fingerprint",
+            "replayed",
+            "errors",
+            "orig p50 ms",
+            "replay p50 ms",
+            "p50 delta",
+            "orig p99 ms",
+            "replay p99 ms",
+            "p99 delta",
+        )?;
+        for summary in self.summaries() {
+            writeln!(
+                f,
+                "{:<20} {:>8} {:>8} {:>13} {:>13} {:>9} {:>13} {:>13} {:>9}  {}",
+                summary.plan_fingerprint,
+                summary.replayed,
+                summary.errors,
+                fmt_millis(summary.original_p50),
+                fmt_millis(summary.replayed_p50),
+                fmt_delta(summary.p50_delta()),
+                fmt_millis(summary.original_p99),
+                fmt_millis(summary.replayed_p99),
+                fmt_delta(summary.p99_delta()),
+                summary
+                    .example_sql
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The latencies of the statements that share a plan fingerprint, before and during the replay.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FingerprintSummary {
+    /// The plan fingerprint.
+    pub plan_fingerprint: String,
+    /// The SQL text of one of the statements.
+    pub example_sql: String,
+    /// The number of statements that replayed successfully.
+    pub replayed: usize,
+    /// The number of statements that failed during the replay.
+    pub errors: usize,
+    /// The original median latency of the statements that replayed successfully.
+    pub original_p50: Option<Duration>,
+    /// The median latency of the statements that replayed successfully.
+    pub replayed_p50: Option<Duration>,
+    /// The original 99th percentile latency of the statements that replayed successfully.
+    pub original_p99: Option<Duration>,
+    /// The 99th percentile latency of the statements that replayed successfully.
+    pub replayed_p99: Option<Duration>,
+}
+
+impl FingerprintSummary {
+    /// Returns the relative change of the median latency, e.g. `0.5` if the replayed statements
+    /// were 50% slower.
+    pub fn p50_delta(&self) -> Option<f64> {
+        relative_delta(self.original_p50?, self.replayed_p50?)
+    }
+
+    /// Returns the relative change of the 99th percentile latency.
+    pub fn p99_delta(&self) -> Option<f64> {
+        relative_delta(self.original_p99?, self.replayed_p99?)
+    }
+}
+
+/// Returns the `p`th percentile of `latencies`, using the nearest-rank method.
+fn percentile(latencies: &[Duration], p: usize) -> Option<Duration> {
+    if latencies.is_empty() {
+        return None;
+    }
+    let mut sorted = latencies.to_vec();
+    sorted.sort();
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    Some(sorted[rank - 1])
+}
+
+/// Returns the change from `original` to `replayed`, relative to `original`.
+fn relative_delta(original: Duration, replayed: Duration) -> Option<f64> {
+    if original.is_zero() {
+        return None;
+    }
+    Some((replayed.as_secs_f64() - original.as_secs_f64()) / original.as_secs_f64())
+}
+
+fn fmt_millis(latency: Option<Duration>) -> String {
+    match latency {
+        Some(latency) => format!("{:.1}", latency.as_secs_f64() * 1000.0),
+        None => "-".into(),
+    }
+}
+
+fn fmt_delta(delta: Option<f64>) -> String {
+    match delta {
+        Some(delta) => format!("{:+.0}%", delta * 100.0),
+        None => "-".into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(
+        fingerprint: Option<&str>,
+        original_ms: u64,
+        replayed: Result<u64, &str>,
+    ) -> Outcome {
+        Outcome {
+            plan_fingerprint: fingerprint.map(Into::into),
+            sql: "SELECT 1".into(),
+            original: Duration::from_millis(original_ms),
+            replayed: replayed
+                .map(Duration::from_millis)
+                .map_err(|err| err.to_string()),
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_percentile() {
+        let latencies: Vec<_> = (1..=100).map(Duration::from_millis).collect();
+        assert_eq!(percentile(&latencies, 50), Some(Duration::from_millis(50)));
+        assert_eq!(percentile(&latencies, 99), Some(Duration::from_millis(99)));
+        assert_eq!(
+            percentile(&[Duration::from_millis(7)], 99),
+            Some(Duration::from_millis(7))
+        );
+        assert_eq!(percentile(&[], 50), None);
+    }
+
+    #[mz_ore::test]
+    fn test_report() {
+        let mut report = Report::default();
+        report.add(outcome(Some("fast"), 10, Ok(5)));
+        report.add(outcome(Some("fast"), 10, Ok(5)));
+        report.add(outcome(Some("slow"), 10, Ok(20)));
+        report.add(outcome(Some("slow"), 10, Err("canceled")));
+        report.add(outcome(None, 0, Ok(1)));
+
+        let summaries = report.summaries();
+        let fingerprints: Vec<_> = summaries
+            .iter()
+            .map(|s| s.plan_fingerprint.as_str())
+            .collect();
+        assert_eq!(fingerprints, ["slow", "fast", NO_FINGERPRINT]);
+
+        assert_eq!(summaries[0].replayed, 1);
+        assert_eq!(summaries[0].errors, 1);
+        assert_eq!(summaries[0].p50_delta(), Some(1.0));
+        assert_eq!(summaries[1].replayed, 2);
+        assert_eq!(summaries[1].p50_delta(), Some(-0.5));
+        assert_eq!(summaries[2].p50_delta(), None);
+
+        let errors: Vec<_> = report.errors().collect();
+        assert_eq!(errors, [("slow", "canceled")]);
+    }
+}
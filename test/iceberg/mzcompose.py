@@ -0,0 +1,195 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+"""Tests of Iceberg sinks that run against a REST catalog backed by minio."""
+
+import io
+import json
+import time
+from typing import Any
+
+import boto3
+import fastavro
+import requests
+
+from materialize.mzcompose import DEFAULT_CLOUD_REGION
+from materialize.mzcompose.composition import Composition
+from materialize.mzcompose.service import Service
+from materialize.mzcompose.services.materialized import Materialized
+from materialize.mzcompose.services.minio import Minio
+
+AWS_ACCESS_KEY_ID = "minioadmin"
+AWS_SECRET_ACCESS_KEY = "minioadmin"
+AWS_ENDPOINT_URL_MZ = "http://minio:9000"
+WAREHOUSE_BUCKET = "warehouse"
+NAMESPACE = "mz"
+TABLE = "t"
+
+SERVICES = [
+    Minio(),
+    Service(
+        name="iceberg-rest",
+        config={
+            "image": "tabulario/iceberg-rest:1.5.0",
+            "ports": [8181],
+            "depends_on": ["minio"],
+            "environment": [
+                f"AWS_ACCESS_KEY_ID={AWS_ACCESS_KEY_ID}",
+                f"AWS_SECRET_ACCESS_KEY={AWS_SECRET_ACCESS_KEY}",
+                f"AWS_REGION={DEFAULT_CLOUD_REGION}",
+                f"CATALOG_WAREHOUSE=s3://{WAREHOUSE_BUCKET}/",
+                "CATALOG_IO__IMPL=org.apache.iceberg.aws.s3.S3FileIO",
+                f"CATALOG_S3_ENDPOINT={AWS_ENDPOINT_URL_MZ}",
+                "CATALOG_S3_PATH__STYLE__ACCESS=true",
+            ],
+        },
+    ),
+    Materialized(depends_on=["minio", "iceberg-rest"]),
+]
+
+
+def workflow_default(c: Composition) -> None:
+    for name in c.workflows:
+        if name == "default":
+            continue
+
+        with c.test_case(name):
+            c.workflow(name)
+
+
+def workflow_sink(c: Composition) -> None:
+    """Commit to a table, resume after a restart, and write equality deletes."""
+
+    c.down(destroy_volumes=True)
+    c.up("minio", "iceberg-rest", "materialized")
+
+    s3 = boto3.client(
+        "s3",
+        endpoint_url=f"http://localhost:{c.port('minio', 9000)}",
+        region_name=DEFAULT_CLOUD_REGION,
+        aws_access_key_id=AWS_ACCESS_KEY_ID,
+        aws_secret_access_key=AWS_SECRET_ACCESS_KEY,
+    )
+    s3.create_bucket(Bucket=WAREHOUSE_BUCKET)
+    catalog_url = f"http://localhost:{c.port('iceberg-rest', 8181)}/v1"
+    requests.post(
+        f"{catalog_url}/namespaces", json={"namespace": [NAMESPACE]}
+    ).raise_for_status()
+
+    c.sql(
+        port=6877,
+        user="mz_system",
+        sql="""
+        ALTER SYSTEM SET enable_aws_connection = true;
+        ALTER SYSTEM SET enable_iceberg_sink = true;
+        """,
+    )
+    c.sql(
+        f"""
+        CREATE SECRET aws_secret AS '{AWS_SECRET_ACCESS_KEY}';
+        CREATE CONNECTION aws_conn TO AWS (
+            ACCESS KEY ID = '{AWS_ACCESS_KEY_ID}',
+            SECRET ACCESS KEY = SECRET aws_secret,
+            ENDPOINT = '{AWS_ENDPOINT_URL_MZ}',
+            REGION = '{DEFAULT_CLOUD_REGION}'
+        );
+        CREATE CONNECTION iceberg_conn TO ICEBERG CATALOG (
+            URL 'http://iceberg-rest:8181',
+            AWS CONNECTION aws_conn
+        );
+        CREATE TABLE t (a int NOT NULL, b text);
+        INSERT INTO t VALUES (1, 'a'), (2, 'b'), (3, 'c');
+        CREATE SINK snk FROM t
+            INTO ICEBERG CATALOG CONNECTION iceberg_conn (
+                NAMESPACE '{NAMESPACE}',
+                TABLE '{TABLE}',
+                COMMIT INTERVAL '1s'
+            )
+            KEY (a) NOT ENFORCED ENVELOPE UPSERT;
+        """
+    )
+    sink_id = c.sql_query("SELECT id FROM mz_sinks WHERE name = 'snk'")[0][0]
+
+    # The snapshot of the table is committed as new rows, each of which is
+    # also written to an equality delete file that masks any previous value.
+    wait_for_rows(catalog_url, s3, sink_id, data=3, deletes=3)
+    frontier = committed_frontier(catalog_url, sink_id)
+
+    # After a restart the sink resumes from the committed frontier, so the
+    # rows that it committed before are not committed again.
+    c.kill("materialized")
+    c.up("materialized")
+    c.sql("INSERT INTO t VALUES (4, 'd')")
+    wait_for_rows(catalog_url, s3, sink_id, data=4, deletes=4)
+    assert committed_frontier(catalog_url, sink_id) > frontier
+
+    # Updates write the new value along with an equality delete of the key,
+    # and deletes only write the equality delete.
+    c.sql("UPDATE t SET b = 'z' WHERE a = 1")
+    c.sql("DELETE FROM t WHERE a = 2")
+    wait_for_rows(catalog_url, s3, sink_id, data=5, deletes=6)
+
+    snapshots = sink_snapshots(catalog_url, sink_id)
+    assert snapshots[-1]["summary"]["operation"] == "overwrite"
+    for parent, child in zip(snapshots, snapshots[1:]):
+        assert child["parent-snapshot-id"] == parent["snapshot-id"]
+
+
+def sink_snapshots(catalog_url: str, sink_id: str) -> list[dict[str, Any]]:
+    """Returns the snapshots committed by the sink, oldest first."""
+    response = requests.get(f"{catalog_url}/namespaces/{NAMESPACE}/tables/{TABLE}")
+    # The sink creates the table when it starts.
+    if response.status_code == 404:
+        return []
+    response.raise_for_status()
+    metadata = response.json()["metadata"]
+    return [
+        snapshot
+        for snapshot in metadata.get("snapshots", [])
+        if snapshot["summary"].get("mz-sink-id") == sink_id
+    ]
+
+
+def committed_frontier(catalog_url: str, sink_id: str) -> list[int]:
+    snapshots = sink_snapshots(catalog_url, sink_id)
+    assert snapshots, "sink has not committed a snapshot"
+    return json.loads(snapshots[-1]["summary"]["mz-frontier"])
+
+
+def read_avro(s3: Any, path: str) -> list[dict[str, Any]]:
+    bucket, key = path.removeprefix("s3://").split("/", 1)
+    body = s3.get_object(Bucket=bucket, Key=key)["Body"].read()
+    return list(fastavro.reader(io.BytesIO(body)))
+
+
+def added_rows(catalog_url: str, s3: Any, sink_id: str) -> tuple[int, int]:
+    """Returns the number of data and equality delete rows added by the sink."""
+    data, deletes = 0, 0
+    for snapshot in sink_snapshots(catalog_url, sink_id):
+        for manifest in read_avro(s3, snapshot["manifest-list"]):
+            if manifest["added_snapshot_id"] != snapshot["snapshot-id"]:
+                continue
+            if manifest["content"] == 0:
+                data += manifest["added_rows_count"]
+            else:
+                deletes += manifest["added_rows_count"]
+    return data, deletes
+
+
+def wait_for_rows(
+    catalog_url: str, s3: Any, sink_id: str, data: int, deletes: int
+) -> None:
+    deadline = time.time() + 60
+    while True:
+        rows = added_rows(catalog_url, s3, sink_id)
+        if rows == (data, deletes):
+            return
+        assert rows[0] <= data and rows[1] <= deletes, f"unexpected rows: {rows}"
+        assert time.time() < deadline, f"timed out waiting for rows: {rows}"
+        time.sleep(1)
@@ -177,6 +177,123 @@ def workflow_audit_log(c: Composition) -> None:
         raise Exception("audit logs emtpy or not equal after restart")
 
 
+def workflow_audit_log_sink(c: Composition) -> None:
+    """Test that a sink on the audit log exports each event exactly once across restarts."""
+    c.up("zookeeper", "kafka", "schema-registry", "materialized")
+    c.up("testdrive_no_reset", persistent=True)
+
+    # With ENVELOPE NONE every message becomes a row, so events that are
+    # exported again after the restart show up as duplicate keys, and a
+    # tombstone, i.e. a retracted event, errors the source.
+    c.testdrive(
+        service="testdrive_no_reset",
+        input=dedent(
+            """
+            > CREATE CONNECTION kafka_conn
+              TO KAFKA (BROKER '${testdrive.kafka-addr}', SECURITY PROTOCOL PLAINTEXT);
+
+            > CREATE SINK audit_log_sink
+              FROM mz_audit_events
+              INTO KAFKA CONNECTION kafka_conn (TOPIC 'restart-audit-log')
+              KEY (id)
+              FORMAT JSON
+              ENVELOPE UPSERT
+
+            > CREATE SOURCE audit_log_export
+              FROM KAFKA CONNECTION kafka_conn (TOPIC 'restart-audit-log')
+              KEY FORMAT JSON
+              VALUE FORMAT JSON
+              INCLUDE KEY
+              ENVELOPE NONE
+
+            > CREATE TABLE before_restart (a int)
+
+            > SELECT count(*) FROM audit_log_export
+              WHERE data->'details'->>'name' = 'before_restart'
+            1
+            """
+        ),
+    )
+
+    # The coordinator rewrites the audit log when it boots.
+    c.kill("materialized")
+    c.up("materialized")
+
+    # Once an event recorded after the restart is exported, anything that the
+    # restarted sink exported again precedes it in the topic.
+    c.testdrive(
+        service="testdrive_no_reset",
+        input=dedent(
+            """
+            > CREATE TABLE after_restart (a int)
+
+            > SELECT count(*) FROM audit_log_export
+              WHERE data->'details'->>'name' = 'after_restart'
+            1
+
+            > SELECT count(*) = count(DISTINCT key) FROM audit_log_export
+            true
+
+            > SELECT (SELECT count(*) FROM audit_log_export) = (SELECT count(*) FROM mz_audit_events)
+            true
+            """
+        ),
+    )
+
+
+def workflow_continual_task(c: Composition) -> None:
+    """Test that a continual task inserts the changes that it missed before a restart."""
+    c.up("materialized")
+    c.up("testdrive_no_reset", persistent=True)
+
+    # Without replicas the task can't insert anything, so all changes are
+    # still pending when Materialize restarts.
+    c.testdrive(
+        service="testdrive_no_reset",
+        input=dedent(
+            """
+            $ postgres-execute connection=postgres://mz_system:materialize@${testdrive.materialize-internal-sql-addr}
+            ALTER SYSTEM SET enable_continual_task_create = true
+
+            > CREATE CLUSTER ct_cluster SIZE '1', REPLICATION FACTOR 0
+
+            > CREATE TABLE ct_input (a int)
+
+            > CREATE TABLE ct_output (a int)
+
+            > CREATE CONTINUAL TASK ct IN CLUSTER ct_cluster
+              AS INSERT INTO ct_output SELECT a FROM ct_input
+
+            > INSERT INTO ct_input VALUES (1), (2)
+            """
+        ),
+    )
+
+    c.kill("materialized")
+    c.up("materialized")
+
+    c.testdrive(
+        service="testdrive_no_reset",
+        input=dedent(
+            """
+            > INSERT INTO ct_input VALUES (3)
+
+            > ALTER CLUSTER ct_cluster SET (REPLICATION FACTOR 1)
+
+            > SELECT * FROM ct_output
+            1
+            2
+            3
+
+            > SELECT s.status FROM mz_internal.mz_continual_task_statuses s
+              JOIN mz_internal.mz_continual_tasks ct ON s.id = ct.id
+              WHERE ct.name = 'ct'
+            running
+            """
+        ),
+    )
+
+
 # Test for GitHub issue #13726
 def workflow_timelines(c: Composition) -> None:
     for _ in range(3):
@@ -263,7 +380,7 @@ def workflow_allowed_cluster_replica_sizes(c: Composition) -> None:
             # We can create a cluster with sizes '1' and '2'
             > CREATE CLUSTER test REPLICAS (r1 (SIZE '1'), r2 (SIZE '2'))
 
-            > SHOW CLUSTER REPLICAS WHERE cluster = 'test'
+            > SELECT cluster, replica, size, ready FROM (SHOW CLUSTER REPLICAS) WHERE cluster = 'test'
             test r1 1 true
             test r2 2 true
 
@@ -288,7 +405,7 @@ def workflow_allowed_cluster_replica_sizes(c: Composition) -> None:
             $ postgres-connect name=mz_system url=postgres://mz_system:materialize@${testdrive.materialize-internal-sql-addr}
 
             # Cluster replica of disallowed sizes still exist
-            > SHOW CLUSTER REPLICAS WHERE cluster = 'test'
+            > SELECT cluster, replica, size, ready FROM (SHOW CLUSTER REPLICAS) WHERE cluster = 'test'
             test r1 1 true
             test r2 2 true
 
@@ -302,7 +419,7 @@ def workflow_allowed_cluster_replica_sizes(c: Composition) -> None:
 
             > CREATE CLUSTER REPLICA test.r3 SIZE '2'
 
-            > SHOW CLUSTER REPLICAS WHERE cluster = 'test'
+            > SELECT cluster, replica, size, ready FROM (SHOW CLUSTER REPLICAS) WHERE cluster = 'test'
             test r1 1 true
             test r2 2 true
             test r3 2 true
@@ -440,6 +557,57 @@ def workflow_bound_size_mz_status_history(c: Composition) -> None:
     )
 
 
+def workflow_plan_changes(c: Composition) -> None:
+    c.up("materialized")
+
+    c.sql("CREATE TABLE t1 (a INT, b INT)")
+    c.sql("CREATE TABLE t2 (b INT, c INT)")
+    c.sql("CREATE TABLE t3 (c INT, d INT)")
+    c.sql("CREATE DEFAULT INDEX t1_idx ON t1")
+    c.sql(
+        "CREATE MATERIALIZED VIEW mv AS SELECT * FROM t1 JOIN t2 USING (b) JOIN t3 USING (c)"
+    )
+    (mv_id,) = c.sql_query("SELECT id FROM mz_materialized_views WHERE name = 'mv'")[0]
+
+    # Plans that did not change are not reported.
+    c.kill("materialized")
+    c.up("materialized")
+    assert c.sql_query("SELECT object_id FROM mz_internal.mz_plan_changes") == []
+
+    # Changing an optimizer feature changes the plan of the join on restart.
+    c.sql(
+        "ALTER SYSTEM SET enable_eager_delta_joins = false",
+        port=6877,
+        user="mz_system",
+    )
+    c.kill("materialized")
+    c.up("materialized")
+    changes = c.sql_query(
+        "SELECT object_id, previous_fingerprint <> current_fingerprint FROM mz_internal.mz_plan_changes"
+    )
+    assert changes == [(mv_id, True)], changes
+    notices = c.sql_query(
+        f"SELECT notice_type FROM mz_internal.mz_notices WHERE object_id = '{mv_id}'"
+    )
+    assert ("Plan changed",) in notices, notices
+
+    # The change is still reported after a restart that leaves the plan intact.
+    c.kill("materialized")
+    c.up("materialized")
+    changes = c.sql_query("SELECT object_id FROM mz_internal.mz_plan_changes")
+    assert changes == [(mv_id,)], changes
+
+    # Dropping the object retracts the change. The retraction is written in the
+    # background, so it may take a moment to show up.
+    c.sql("DROP MATERIALIZED VIEW mv")
+    for _ in range(20):
+        if c.sql_query("SELECT object_id FROM mz_internal.mz_plan_changes") == []:
+            break
+        time.sleep(0.5)
+    else:
+        raise UIError("plan change not retracted after dropping the object")
+
+
 def workflow_default(c: Composition) -> None:
     for name in c.workflows:
         if name == "default":